@@ -1,5 +1,5 @@
-// This shader draws a circle with a given input color
-#import bevy_ui::ui_vertex_output::UiVertexOutput
+// This shader draws an image up to a slider, clipped to the rounded corners of the node
+#import bevy_ui::ui_vertex_output::{UiVertexOutput, border_radius_coverage}
 
 @group(1) @binding(0) var<uniform> color: vec4<f32>;
 @group(1) @binding(1) var<uniform> slider: f32;
@@ -10,7 +10,8 @@
 @fragment
 fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
     if in.uv.x < slider {
-        let output_color = textureSample(material_color_texture, material_color_sampler, in.uv) * color;
+        var output_color = textureSample(material_color_texture, material_color_sampler, in.uv) * color;
+        output_color.a *= border_radius_coverage(in);
         return output_color;
     } else {
         return vec4(0.0);
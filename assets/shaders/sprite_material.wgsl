@@ -0,0 +1,41 @@
+#import bevy_sprite::{
+    sprite_bindings::{sprite_texture, sprite_sampler},
+    sprite_io::VertexOutput,
+    sprite_view_bindings::view,
+}
+#ifdef TONEMAP_IN_SHADER
+#import bevy_core_pipeline::tonemapping
+#endif
+
+struct HitMaterial {
+    // The color the sprite flashes to, blended by its alpha.
+    flash: vec4<f32>,
+    // Drawn around the opaque pixels of the sprite, if its alpha isn't zero.
+    outline: vec4<f32>,
+    outline_thickness: f32,
+}
+
+@group(2) @binding(0) var<uniform> material: HitMaterial;
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    var color = in.color * textureSample(sprite_texture, sprite_sampler, in.uv);
+    color = vec4(mix(color.rgb, material.flash.rgb, material.flash.a), color.a);
+
+    // Outline the transparent pixels that are close to an opaque one.
+    let texel = material.outline_thickness / vec2<f32>(textureDimensions(sprite_texture));
+    var neighbors = 0.0;
+    for (var i = 0; i < 8; i++) {
+        let angle = f32(i) * 0.785398;
+        let offset = vec2(cos(angle), sin(angle)) * texel;
+        neighbors = max(neighbors, textureSample(sprite_texture, sprite_sampler, in.uv + offset).a);
+    }
+    let outline = vec4(material.outline.rgb, material.outline.a * neighbors);
+    color = mix(outline, color, color.a);
+
+#ifdef TONEMAP_IN_SHADER
+    color = tonemapping::tone_mapping(color, view.color_grading);
+#endif
+
+    return color;
+}
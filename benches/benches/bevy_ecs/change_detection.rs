@@ -3,6 +3,7 @@ use bevy_ecs::{
     entity::Entity,
     prelude::{Added, Changed, EntityWorldMut, QueryState},
     query::QueryFilter,
+    system::{IntoSystem, Query, System},
     world::World,
 };
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
@@ -15,7 +16,9 @@ criterion_group!(
     all_changed_detection,
     few_changed_detection,
     none_changed_detection,
-    multiple_archetype_none_changed_detection
+    multiple_archetype_none_changed_detection,
+    all_modified,
+    none_changed_any_changed_detection
 );
 criterion_main!(benches);
 
@@ -373,3 +376,90 @@ fn multiple_archetype_none_changed_detection(criterion: &mut Criterion) {
         }
     }
 }
+
+fn all_modified_generic<T: Component + Default + BenchModify>(
+    group: &mut BenchGroup,
+    entity_count: u32,
+) {
+    group.bench_function(
+        format!("{}_entities_{}", entity_count, std::any::type_name::<T>()),
+        |bencher| {
+            bencher.iter_batched_ref(
+                || {
+                    let mut world = setup::<T>(entity_count);
+                    world.clear_trackers();
+                    let query = world.query::<&mut T>();
+                    (world, query)
+                },
+                |(ref mut world, ref mut query)| {
+                    for mut component in query.iter_mut(world) {
+                        black_box(component.bench_modify());
+                    }
+                },
+                criterion::BatchSize::LargeInput,
+            );
+        },
+    );
+}
+
+fn all_modified(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("all_modified");
+    group.warm_up_time(std::time::Duration::from_millis(500));
+    group.measurement_time(std::time::Duration::from_secs(4));
+    for &entity_count in ENTITIES_TO_BENCH_COUNT {
+        generic_bench(
+            &mut group,
+            vec![
+                Box::new(all_modified_generic::<Table>),
+                Box::new(all_modified_generic::<Sparse>),
+            ],
+            entity_count,
+        );
+    }
+}
+
+fn none_changed_any_changed_detection_generic<T: Component + Default>(
+    group: &mut BenchGroup,
+    entity_count: u32,
+) {
+    group.bench_function(
+        format!("{}_entities_{}", entity_count, std::any::type_name::<T>()),
+        |bencher| {
+            bencher.iter_batched_ref(
+                || {
+                    let mut world = setup::<T>(entity_count);
+                    let mut any_changed =
+                        IntoSystem::into_system(|query: Query<&T>| query.any_changed());
+                    any_changed.initialize(&mut world);
+                    any_changed.run((), &mut world);
+                    // Mutable access without writes keeps the components unchanged
+                    let mut query = world.query::<&mut T>();
+                    for component in query.iter_mut(&mut world) {
+                        black_box(component);
+                    }
+                    (world, any_changed)
+                },
+                |(ref mut world, ref mut any_changed)| {
+                    assert!(!any_changed.run((), world));
+                },
+                criterion::BatchSize::LargeInput,
+            );
+        },
+    );
+}
+
+fn none_changed_any_changed_detection(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("none_changed_any_changed_detection");
+    group.warm_up_time(std::time::Duration::from_millis(500));
+    group.measurement_time(std::time::Duration::from_secs(4));
+    for &entity_count in ENTITIES_TO_BENCH_COUNT {
+        generic_bench(
+            &mut group,
+            vec![
+                Box::new(none_changed_any_changed_detection_generic::<Table>),
+                Box::new(none_changed_any_changed_detection_generic::<Sparse>),
+            ],
+            entity_count,
+        );
+    }
+}
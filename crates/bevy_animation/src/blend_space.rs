@@ -0,0 +1,451 @@
+//! Blend spaces, which compute the weights of animations from parameters.
+
+use bevy_math::Vec2;
+use bevy_reflect::Reflect;
+use bevy_utils::hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::graph::AnimationNodeIndex;
+
+/// A blend node of an [`AnimationGraph`](crate::graph::AnimationGraph) that
+/// computes the weights of its children from the parameters of the
+/// [`AnimationPlayer`](crate::AnimationPlayer).
+///
+/// Each child is placed at a position in the space of the parameters, and the
+/// children closest to the current value of the parameters are blended
+/// together. For example, a walk animation placed at a speed of 1.5 and a run
+/// animation placed at a speed of 4.0 are blended half and half when the speed
+/// parameter is 2.75.
+///
+/// The children of the blend space must be played on the
+/// [`AnimationPlayer`](crate::AnimationPlayer) to be blended, usually all the
+/// time, as their weights are set to 0.0 when they aren't needed.
+#[derive(Clone, Reflect, Debug, Serialize, Deserialize)]
+pub enum BlendSpace {
+    /// Blends the children placed along a single parameter.
+    OneDimensional(BlendSpace1d),
+    /// Blends the children placed on the plane of two parameters.
+    TwoDimensional(BlendSpace2d),
+}
+
+/// A [`BlendSpace`] whose children are placed along a single parameter, such
+/// as the speed of a character.
+///
+/// The two children surrounding the value of the parameter are blended
+/// linearly. Outside of the children, the closest one is played alone.
+#[derive(Clone, Reflect, Debug, Serialize, Deserialize)]
+pub struct BlendSpace1d {
+    /// The name of the parameter, as set with
+    /// [`AnimationPlayer::set_parameter`](crate::AnimationPlayer::set_parameter).
+    pub parameter: String,
+    /// The children and their positions, sorted by position.
+    children: Vec<(AnimationNodeIndex, f32)>,
+}
+
+/// A [`BlendSpace`] whose children are placed on the plane of two parameters,
+/// such as the forward and sideways velocities of a character.
+///
+/// The children are triangulated, and the three children of the triangle
+/// containing the value of the parameters are blended with barycentric
+/// weights. Outside of the triangles, the closest point on their edges is
+/// used.
+#[derive(Clone, Reflect, Debug, Serialize, Deserialize)]
+pub struct BlendSpace2d {
+    /// The names of the parameters along the X and Y axes, as set with
+    /// [`AnimationPlayer::set_parameter`](crate::AnimationPlayer::set_parameter).
+    pub parameters: [String; 2],
+    /// The children and their positions.
+    children: Vec<(AnimationNodeIndex, Vec2)>,
+    /// The Delaunay triangulation of the positions of the children, as indices
+    /// into `children`.
+    #[serde(skip)]
+    triangles: Vec<[usize; 3]>,
+}
+
+impl BlendSpace {
+    /// Writes the weight of each child into `weights`, indexed by the index of
+    /// the child in the graph, given the parameters of the player.
+    ///
+    /// Missing parameters are treated as 0.0.
+    pub(crate) fn write_weights(&self, parameters: &HashMap<String, f32>, weights: &mut [f32]) {
+        let parameter = |name: &String| parameters.get(name).copied().unwrap_or_default();
+        match self {
+            BlendSpace::OneDimensional(blend_space) => {
+                blend_space.write_weights(parameter(&blend_space.parameter), weights);
+            }
+            BlendSpace::TwoDimensional(blend_space) => {
+                let [x, y] = &blend_space.parameters;
+                blend_space.write_weights(Vec2::new(parameter(x), parameter(y)), weights);
+            }
+        }
+    }
+}
+
+impl BlendSpace1d {
+    /// Creates a blend space without children, blending along the given
+    /// parameter.
+    pub fn new(parameter: impl Into<String>) -> Self {
+        Self {
+            parameter: parameter.into(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Places a child of the blend space at the given position.
+    ///
+    /// The child should be connected to the blend space node in the graph.
+    pub fn insert(&mut self, child: AnimationNodeIndex, position: f32) {
+        self.remove(child);
+        let index = self
+            .children
+            .partition_point(|&(_, other_position)| other_position <= position);
+        self.children.insert(index, (child, position));
+    }
+
+    /// Removes a child from the blend space.
+    ///
+    /// Returns true if the child was in the blend space.
+    pub fn remove(&mut self, child: AnimationNodeIndex) -> bool {
+        let len = self.children.len();
+        self.children.retain(|&(other, _)| other != child);
+        self.children.len() != len
+    }
+
+    /// Returns the children of the blend space and their positions, sorted by
+    /// position.
+    pub fn children_positions(&self) -> &[(AnimationNodeIndex, f32)] {
+        &self.children
+    }
+
+    /// Returns the children of the blend space.
+    pub fn children(&self) -> impl Iterator<Item = AnimationNodeIndex> + '_ {
+        self.children.iter().map(|&(child, _)| child)
+    }
+
+    /// Calls `f` with the weight of each child for the given value of the
+    /// parameter.
+    pub fn weights(&self, value: f32, mut f: impl FnMut(AnimationNodeIndex, f32)) {
+        let upper = self
+            .children
+            .partition_point(|&(_, position)| position <= value);
+        // The two children blended, and the weight of the upper one.
+        let (lower, upper, t) = if upper == 0 {
+            (0, 0, 0.0)
+        } else if upper == self.children.len() {
+            (upper - 1, upper - 1, 0.0)
+        } else {
+            let (lower_position, upper_position) =
+                (self.children[upper - 1].1, self.children[upper].1);
+            let t = (value - lower_position) / (upper_position - lower_position);
+            (upper - 1, upper, t)
+        };
+        for (index, &(child, _)) in self.children.iter().enumerate() {
+            let weight = if index == lower {
+                1.0 - t
+            } else if index == upper {
+                t
+            } else {
+                0.0
+            };
+            f(child, weight);
+        }
+    }
+
+    fn write_weights(&self, value: f32, weights: &mut [f32]) {
+        self.weights(value, |child, weight| weights[child.index()] = weight);
+    }
+}
+
+impl BlendSpace2d {
+    /// Creates a blend space without children, blending along the given X and
+    /// Y parameters.
+    pub fn new(x_parameter: impl Into<String>, y_parameter: impl Into<String>) -> Self {
+        Self {
+            parameters: [x_parameter.into(), y_parameter.into()],
+            children: Vec::new(),
+            triangles: Vec::new(),
+        }
+    }
+
+    /// Places a child of the blend space at the given position.
+    ///
+    /// The child should be connected to the blend space node in the graph.
+    pub fn insert(&mut self, child: AnimationNodeIndex, position: Vec2) {
+        self.children.retain(|&(other, _)| other != child);
+        self.children.push((child, position));
+        self.triangulate();
+    }
+
+    /// Removes a child from the blend space.
+    ///
+    /// Returns true if the child was in the blend space.
+    pub fn remove(&mut self, child: AnimationNodeIndex) -> bool {
+        let len = self.children.len();
+        self.children.retain(|&(other, _)| other != child);
+        self.triangulate();
+        self.children.len() != len
+    }
+
+    /// Returns the children of the blend space and their positions.
+    pub fn children_positions(&self) -> &[(AnimationNodeIndex, Vec2)] {
+        &self.children
+    }
+
+    /// Returns the children of the blend space.
+    pub fn children(&self) -> impl Iterator<Item = AnimationNodeIndex> + '_ {
+        self.children.iter().map(|&(child, _)| child)
+    }
+
+    /// Returns the triangles blended together, as indices into
+    /// [`Self::children_positions`].
+    pub fn triangles(&self) -> &[[usize; 3]] {
+        &self.triangles
+    }
+
+    /// Calls `f` with the weight of each child for the given value of the
+    /// parameters.
+    pub fn weights(&self, value: Vec2, mut f: impl FnMut(AnimationNodeIndex, f32)) {
+        let blended = self.blended(value);
+        for (index, &(child, _)) in self.children.iter().enumerate() {
+            let weight = blended
+                .iter()
+                .filter(|&&(blended_index, _)| blended_index == index)
+                .map(|&(_, weight)| weight)
+                .sum();
+            f(child, weight);
+        }
+    }
+
+    fn write_weights(&self, value: Vec2, weights: &mut [f32]) {
+        self.weights(value, |child, weight| weights[child.index()] = weight);
+    }
+
+    /// Returns up to three children and their weights for the given value of
+    /// the parameters.
+    fn blended(&self, value: Vec2) -> Vec<(usize, f32)> {
+        let position = |index: usize| self.children[index].1;
+        match self.children.len() {
+            0 => return Vec::new(),
+            1 => return vec![(0, 1.0)],
+            _ => {}
+        }
+
+        // Use the triangle containing the value if there's one.
+        for &[a, b, c] in &self.triangles {
+            let [u, v, w] = barycentric(value, position(a), position(b), position(c));
+            if u >= -f32::EPSILON && v >= -f32::EPSILON && w >= -f32::EPSILON {
+                return vec![(a, u.max(0.0)), (b, v.max(0.0)), (c, w.max(0.0))];
+            }
+        }
+
+        // Otherwise, use the closest point on the edges of the triangles, or
+        // between consecutive children if they couldn't be triangulated
+        // because they're aligned.
+        let edges: Vec<(usize, usize)> = if self.triangles.is_empty() {
+            let first = position(0);
+            let direction = self
+                .children
+                .iter()
+                .map(|&(_, position)| position - first)
+                .max_by(|a, b| a.length_squared().total_cmp(&b.length_squared()))
+                .unwrap_or_default();
+            let mut sorted: Vec<usize> = (0..self.children.len()).collect();
+            sorted.sort_by(|&a, &b| {
+                let along = |index| (position(index) - first).dot(direction);
+                along(a).total_cmp(&along(b))
+            });
+            sorted.windows(2).map(|pair| (pair[0], pair[1])).collect()
+        } else {
+            self.triangles
+                .iter()
+                .flat_map(|&[a, b, c]| [(a, b), (b, c), (c, a)])
+                .collect()
+        };
+        let mut closest = (f32::INFINITY, 0, 0, 0.0);
+        for (a, b) in edges {
+            let (t, distance_squared) = closest_on_segment(value, position(a), position(b));
+            if distance_squared < closest.0 {
+                closest = (distance_squared, a, b, t);
+            }
+        }
+        let (_, a, b, t) = closest;
+        vec![(a, 1.0 - t), (b, t)]
+    }
+
+    /// Computes the Delaunay triangulation of the children with the
+    /// Bowyer-Watson algorithm.
+    pub(crate) fn triangulate(&mut self) {
+        self.triangles.clear();
+        let count = self.children.len();
+        if count < 3 {
+            return;
+        }
+
+        // Start with a triangle containing all the children.
+        let mut points: Vec<Vec2> = self
+            .children
+            .iter()
+            .map(|&(_, position)| position)
+            .collect();
+        let (min, max) = points.iter().fold(
+            (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+            |(min, max), &point| (min.min(point), max.max(point)),
+        );
+        let center = (min + max) * 0.5;
+        let size = (max - min).max_element().max(1.0) * 20.0;
+        points.extend([
+            center + Vec2::new(-size, -size),
+            center + Vec2::new(size, -size),
+            center + Vec2::new(0.0, size),
+        ]);
+        let mut triangles = vec![[count, count + 1, count + 2]];
+
+        for index in 0..count {
+            let point = points[index];
+            let (bad, good): (Vec<_>, Vec<_>) = triangles
+                .into_iter()
+                .partition(|&[a, b, c]| in_circumcircle(point, points[a], points[b], points[c]));
+            triangles = good;
+
+            // Connect the point to the edges on the boundary of the hole left
+            // by the triangles whose circumcircle contains it.
+            let edges: Vec<(usize, usize)> = bad
+                .iter()
+                .flat_map(|&[a, b, c]| [(a, b), (b, c), (c, a)])
+                .collect();
+            for &(a, b) in &edges {
+                let shared = edges
+                    .iter()
+                    .filter(|&&(c, d)| (c, d) == (a, b) || (c, d) == (b, a))
+                    .count()
+                    > 1;
+                if !shared {
+                    triangles.push([a, b, index]);
+                }
+            }
+        }
+
+        // Remove the triangles using the vertices of the first triangle, as
+        // well as the flat triangles of aligned children.
+        triangles.retain(|&[a, b, c]| {
+            a < count
+                && b < count
+                && c < count
+                && (points[b] - points[a])
+                    .perp_dot(points[c] - points[a])
+                    .abs()
+                    > f32::EPSILON
+        });
+        self.triangles = triangles;
+    }
+}
+
+/// Returns the barycentric coordinates of `point` in the triangle `a`, `b`,
+/// `c`.
+fn barycentric(point: Vec2, a: Vec2, b: Vec2, c: Vec2) -> [f32; 3] {
+    let (ab, ac, ap) = (b - a, c - a, point - a);
+    let area = ab.perp_dot(ac);
+    if area.abs() <= f32::EPSILON {
+        return [-1.0; 3];
+    }
+    let v = ap.perp_dot(ac) / area;
+    let w = ab.perp_dot(ap) / area;
+    [1.0 - v - w, v, w]
+}
+
+/// Returns whether `point` is inside the circumcircle of the triangle `a`,
+/// `b`, `c`.
+fn in_circumcircle(point: Vec2, a: Vec2, b: Vec2, c: Vec2) -> bool {
+    let (a, b, c) = (a - point, b - point, c - point);
+    let determinant = a.length_squared() * b.perp_dot(c) - b.length_squared() * a.perp_dot(c)
+        + c.length_squared() * a.perp_dot(b);
+    // The sign of the determinant depends on the winding of the triangle.
+    let winding = (b - a).perp_dot(c - a);
+    determinant * winding.signum() > 0.0
+}
+
+/// Returns the interpolation factor from `a` to `b` of the closest point to
+/// `point` on the segment, and the squared distance to that point.
+fn closest_on_segment(point: Vec2, a: Vec2, b: Vec2) -> (f32, f32) {
+    let ab = b - a;
+    let t = if ab.length_squared() <= f32::EPSILON {
+        0.0
+    } else {
+        ((point - a).dot(ab) / ab.length_squared()).clamp(0.0, 1.0)
+    };
+    (t, point.distance_squared(a + ab * t))
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::Vec2;
+    use petgraph::graph::NodeIndex;
+
+    use super::{BlendSpace1d, BlendSpace2d};
+
+    fn weights_1d(blend_space: &BlendSpace1d, value: f32) -> Vec<f32> {
+        let mut weights = Vec::new();
+        blend_space.weights(value, |_, weight| weights.push(weight));
+        weights
+    }
+
+    fn weights_2d(blend_space: &BlendSpace2d, value: Vec2) -> Vec<f32> {
+        let mut weights = Vec::new();
+        blend_space.weights(value, |_, weight| weights.push(weight));
+        weights
+    }
+
+    #[test]
+    fn blend_space_1d_blends_surrounding_children() {
+        let mut blend_space = BlendSpace1d::new("speed");
+        blend_space.insert(NodeIndex::new(2), 4.0);
+        blend_space.insert(NodeIndex::new(0), 0.0);
+        blend_space.insert(NodeIndex::new(1), 1.5);
+
+        assert_eq!(weights_1d(&blend_space, -1.0), [1.0, 0.0, 0.0]);
+        assert_eq!(weights_1d(&blend_space, 2.75), [0.0, 0.5, 0.5]);
+        assert_eq!(weights_1d(&blend_space, 1.5), [0.0, 1.0, 0.0]);
+        assert_eq!(weights_1d(&blend_space, 10.0), [0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn blend_space_2d_triangulates_children() {
+        let mut blend_space = BlendSpace2d::new("x", "y");
+        for (index, position) in [Vec2::ZERO, Vec2::X, Vec2::Y, Vec2::NEG_X, Vec2::NEG_Y]
+            .into_iter()
+            .enumerate()
+        {
+            blend_space.insert(NodeIndex::new(index), position);
+        }
+        assert_eq!(blend_space.triangles().len(), 4);
+
+        // A child is played alone at its position.
+        let weights = weights_2d(&blend_space, Vec2::X);
+        assert!((weights[1] - 1.0).abs() < 1e-5);
+        assert!((weights.iter().sum::<f32>() - 1.0).abs() < 1e-5);
+
+        // Inside a triangle, its three children are blended.
+        let weights = weights_2d(&blend_space, Vec2::new(0.25, 0.25));
+        assert!((weights[0] - 0.5).abs() < 1e-5);
+        assert!((weights[1] - 0.25).abs() < 1e-5);
+        assert!((weights[2] - 0.25).abs() < 1e-5);
+
+        // Outside, the closest edge is used.
+        let weights = weights_2d(&blend_space, Vec2::new(2.0, 2.0));
+        assert!((weights[1] - 0.5).abs() < 1e-5);
+        assert!((weights[2] - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn blend_space_2d_handles_aligned_children() {
+        let mut blend_space = BlendSpace2d::new("x", "y");
+        blend_space.insert(NodeIndex::new(0), Vec2::ZERO);
+        blend_space.insert(NodeIndex::new(1), Vec2::X);
+        blend_space.insert(NodeIndex::new(2), Vec2::X * 2.0);
+        assert!(blend_space.triangles().is_empty());
+
+        let weights = weights_2d(&blend_space, Vec2::new(1.5, 1.0));
+        assert!((weights[1] - 0.5).abs() < 1e-5);
+        assert!((weights[2] - 0.5).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,174 @@
+//! Events placed at given times of animation clips, fired when their playback
+//! crosses them, such as footsteps or hit frames.
+
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_reflect::Reflect;
+
+use crate::{
+    graph::{AnimationGraph, AnimationNodeIndex},
+    ActiveAnimation, AnimationClip, AnimationPlayer,
+};
+
+/// An event placed at a given time of an [`AnimationClip`].
+///
+/// It's fired as an [`AnimationEvent`] when the playback of the clip crosses
+/// its time.
+#[derive(Reflect, Clone, Debug, PartialEq)]
+pub struct AnimationEventMarker {
+    /// The time of the event in the clip, in seconds.
+    pub time: f32,
+    /// The name of the event, such as `"footstep"`.
+    pub name: String,
+    /// Any data attached to the event, such as the JSON of the extras of a
+    /// glTF animation, or an empty string.
+    pub payload: String,
+}
+
+/// Fired when the playback of an [`AnimationClip`] crosses one of its
+/// [markers](AnimationEventMarker).
+///
+/// It's sent as an [`Event`], and triggered on the entity of the
+/// [`AnimationPlayer`], so it can be observed:
+///
+/// ```
+/// # use bevy_animation::prelude::*;
+/// # use bevy_ecs::prelude::*;
+/// fn play_footsteps(mut commands: Commands, players: Query<Entity, Added<AnimationPlayer>>) {
+///     for entity in &players {
+///         commands.entity(entity).observe(|trigger: Trigger<AnimationEvent>| {
+///             if trigger.event().name == "footstep" {
+///                 // Play the sound of a footstep.
+///             }
+///         });
+///     }
+/// }
+/// ```
+///
+/// Clips without influence, such as those blended out by a blend space, don't
+/// fire their events.
+#[derive(Event, Clone, Debug, PartialEq)]
+pub struct AnimationEvent {
+    /// The entity of the [`AnimationPlayer`] playing the clip.
+    pub entity: Entity,
+    /// The node of the [`AnimationGraph`] playing the clip.
+    pub node: AnimationNodeIndex,
+    /// The time of the event in the clip, in seconds.
+    pub time: f32,
+    /// The weight of the clip when the event was crossed.
+    pub weight: f32,
+    /// The name of the event.
+    pub name: String,
+    /// The data attached to the event.
+    pub payload: String,
+}
+
+/// A system that fires the [`AnimationEvent`]s of the clips crossed by their
+/// playback this frame.
+pub fn trigger_animation_events(
+    mut commands: Commands,
+    clips: Res<Assets<AnimationClip>>,
+    graphs: Res<Assets<AnimationGraph>>,
+    players: Query<(Entity, &AnimationPlayer, &Handle<AnimationGraph>)>,
+    mut events: EventWriter<AnimationEvent>,
+) {
+    for (entity, player, graph_handle) in &players {
+        let Some(graph) = graphs.get(graph_handle) else {
+            continue;
+        };
+        for (&node, animation) in player.active_animations.iter() {
+            if animation.computed_weight == 0.0 {
+                continue;
+            }
+            let Some(clip) = graph
+                .get(node)
+                .and_then(|node| node.clip.as_ref())
+                .and_then(|clip_handle| clips.get(clip_handle))
+            else {
+                continue;
+            };
+            for marker in crossed_markers(clip, animation) {
+                let event = AnimationEvent {
+                    entity,
+                    node,
+                    time: marker.time,
+                    weight: animation.computed_weight,
+                    name: marker.name.clone(),
+                    payload: marker.payload.clone(),
+                };
+                commands.trigger_targets(event.clone(), entity);
+                events.send(event);
+            }
+        }
+    }
+}
+
+/// Returns the markers of a clip crossed by its playback since the last frame,
+/// in the order they were crossed.
+fn crossed_markers<'a>(
+    clip: &'a AnimationClip,
+    animation: &ActiveAnimation,
+) -> Vec<&'a AnimationEventMarker> {
+    let (last, now) = (animation.last_seek_time, animation.seek_time);
+    let markers = clip.events();
+    if animation.speed >= 0.0 {
+        if now >= last {
+            markers
+                .iter()
+                .filter(|marker| marker.time >= last && marker.time < now)
+                .collect()
+        } else {
+            // The clip went around its end.
+            let before_end = markers.iter().filter(|marker| marker.time >= last);
+            let after_start = markers.iter().filter(|marker| marker.time < now);
+            before_end.chain(after_start).collect()
+        }
+    } else if now <= last {
+        markers
+            .iter()
+            .rev()
+            .filter(|marker| marker.time <= last && marker.time > now)
+            .collect()
+    } else {
+        // The clip went around its start.
+        let after_start = markers.iter().rev().filter(|marker| marker.time <= last);
+        let before_end = markers.iter().rev().filter(|marker| marker.time > now);
+        after_start.chain(before_end).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ActiveAnimation, AnimationClip};
+
+    use super::crossed_markers;
+
+    fn crossed(clip: &AnimationClip, speed: f32, last: f32, now: f32) -> Vec<&str> {
+        let animation = ActiveAnimation {
+            speed,
+            last_seek_time: last,
+            seek_time: now,
+            ..ActiveAnimation::default()
+        };
+        crossed_markers(clip, &animation)
+            .into_iter()
+            .map(|marker| marker.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn crossing_markers() {
+        let mut clip = AnimationClip::default();
+        clip.add_event(0.5, "right", "");
+        clip.add_event(0.0, "start", "");
+        clip.add_event(1.0, "left", "");
+        clip.set_duration(1.5);
+
+        assert_eq!(crossed(&clip, 1.0, 0.0, 0.1), ["start"]);
+        assert_eq!(crossed(&clip, 1.0, 0.1, 0.6), ["right"]);
+        assert!(crossed(&clip, 1.0, 0.6, 0.6).is_empty());
+        assert_eq!(crossed(&clip, 1.0, 0.6, 0.2), ["left", "start"]);
+        assert_eq!(crossed(&clip, -1.0, 1.2, 0.4), ["left", "right"]);
+        assert_eq!(crossed(&clip, -1.0, 0.2, 1.2), ["start"]);
+    }
+}
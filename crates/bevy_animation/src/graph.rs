@@ -5,12 +5,14 @@ use std::ops::{Index, IndexMut};
 
 use bevy_asset::io::Reader;
 use bevy_asset::{Asset, AssetId, AssetLoader, AssetPath, AsyncReadExt as _, Handle, LoadContext};
+use bevy_math::Vec2;
 use bevy_reflect::{Reflect, ReflectSerialize};
 use petgraph::graph::{DiGraph, NodeIndex};
 use ron::de::SpannedError;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::blend_space::{BlendSpace, BlendSpace1d, BlendSpace2d};
 use crate::AnimationClip;
 
 /// A graph structure that describes how animation clips are to be blended
@@ -30,6 +32,10 @@ use crate::AnimationClip;
 /// specify an animation clip to play. When a graph is created, it starts with
 /// only a single blend node, the root node.
 ///
+/// Blend nodes can also be *blend spaces*, which compute the weights of their
+/// children from the parameters of the [`crate::AnimationPlayer`]. See
+/// [`BlendSpace`] for more information.
+///
 /// For example, consider the following graph:
 ///
 /// ```text
@@ -105,6 +111,12 @@ pub struct AnimationGraphNode {
     /// has weight 0.3 and its parent blend node has weight 0.6, the computed
     /// weight of the animation clip is 0.18.
     pub weight: f32,
+
+    /// The blend space of this node, if any.
+    ///
+    /// If the blend space is present, the weights of the children of this
+    /// node are multiplied by the weights the blend space computes for them.
+    pub blend_space: Option<BlendSpace>,
 }
 
 /// An [`AssetLoader`] that can load [`AnimationGraph`]s as assets.
@@ -156,6 +168,9 @@ pub struct SerializedAnimationGraphNode {
     pub clip: Option<SerializedAnimationClip>,
     /// Corresponds to the `weight` field on [`AnimationGraphNode`].
     pub weight: f32,
+    /// Corresponds to the `blend_space` field on [`AnimationGraphNode`].
+    #[serde(default)]
+    pub blend_space: Option<BlendSpace>,
 }
 
 /// A version of `Handle<AnimationClip>` suitable for serializing as an asset.
@@ -205,6 +220,7 @@ impl AnimationGraph {
         let node_index = self.graph.add_node(AnimationGraphNode {
             clip: Some(clip),
             weight,
+            blend_space: None,
         });
         self.graph.add_edge(parent, node_index, ());
         node_index
@@ -239,13 +255,99 @@ impl AnimationGraph {
     /// animation evaluation, the descendants of this blend node will have their
     /// weights multiplied by the weight of the blend.
     pub fn add_blend(&mut self, weight: f32, parent: AnimationNodeIndex) -> AnimationNodeIndex {
-        let node_index = self
-            .graph
-            .add_node(AnimationGraphNode { clip: None, weight });
+        let node_index = self.graph.add_node(AnimationGraphNode {
+            clip: None,
+            weight,
+            blend_space: None,
+        });
         self.graph.add_edge(parent, node_index, ());
         node_index
     }
 
+    /// Adds a blend space node blending its children along a single parameter
+    /// to the animation graph with the given weight and returns its index.
+    ///
+    /// The blend space will be placed under the supplied `parent` node. Add
+    /// children to it with [`Self::add_blend_space_clip_1d`].
+    pub fn add_blend_space_1d(
+        &mut self,
+        parameter: impl Into<String>,
+        weight: f32,
+        parent: AnimationNodeIndex,
+    ) -> AnimationNodeIndex {
+        let node_index = self.add_blend(weight, parent);
+        self.graph[node_index].blend_space =
+            Some(BlendSpace::OneDimensional(BlendSpace1d::new(parameter)));
+        node_index
+    }
+
+    /// Adds a blend space node blending its children on the plane of two
+    /// parameters to the animation graph with the given weight and returns its
+    /// index.
+    ///
+    /// The blend space will be placed under the supplied `parent` node. Add
+    /// children to it with [`Self::add_blend_space_clip_2d`].
+    pub fn add_blend_space_2d(
+        &mut self,
+        x_parameter: impl Into<String>,
+        y_parameter: impl Into<String>,
+        weight: f32,
+        parent: AnimationNodeIndex,
+    ) -> AnimationNodeIndex {
+        let node_index = self.add_blend(weight, parent);
+        self.graph[node_index].blend_space = Some(BlendSpace::TwoDimensional(BlendSpace2d::new(
+            x_parameter,
+            y_parameter,
+        )));
+        node_index
+    }
+
+    /// Adds an [`AnimationClip`] to a one-dimensional blend space at the given
+    /// position along its parameter, and returns its index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `blend_space` isn't a node added with
+    /// [`Self::add_blend_space_1d`].
+    pub fn add_blend_space_clip_1d(
+        &mut self,
+        clip: Handle<AnimationClip>,
+        position: f32,
+        blend_space: AnimationNodeIndex,
+    ) -> AnimationNodeIndex {
+        let node_index = self.add_clip(clip, 1.0, blend_space);
+        let Some(BlendSpace::OneDimensional(blend_space)) =
+            &mut self.graph[blend_space].blend_space
+        else {
+            panic!("{blend_space:?} isn't a one-dimensional blend space");
+        };
+        blend_space.insert(node_index, position);
+        node_index
+    }
+
+    /// Adds an [`AnimationClip`] to a two-dimensional blend space at the given
+    /// position on the plane of its parameters, and returns its index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `blend_space` isn't a node added with
+    /// [`Self::add_blend_space_2d`].
+    pub fn add_blend_space_clip_2d(
+        &mut self,
+        clip: Handle<AnimationClip>,
+        position: Vec2,
+        blend_space: AnimationNodeIndex,
+    ) -> AnimationNodeIndex {
+        let node_index = self.add_clip(clip, 1.0, blend_space);
+        let Some(BlendSpace::TwoDimensional(blend_space)) =
+            &mut self.graph[blend_space].blend_space
+        else {
+            panic!("{blend_space:?} isn't a two-dimensional blend space");
+        };
+        blend_space.insert(node_index, position);
+        node_index
+    }
+
     /// Adds an edge from the edge `from` to `to`, making `to` a child of
     /// `from`.
     ///
@@ -318,6 +420,7 @@ impl Default for AnimationGraphNode {
         Self {
             clip: None,
             weight: 1.0,
+            blend_space: None,
         }
     }
 }
@@ -362,6 +465,13 @@ impl AssetLoader for AnimationGraphAssetLoader {
                         }
                     }),
                     weight: serialized_node.weight,
+                    blend_space: serialized_node.blend_space.clone().map(|mut blend_space| {
+                        // The triangulation isn't serialized.
+                        if let BlendSpace::TwoDimensional(ref mut blend_space) = blend_space {
+                            blend_space.triangulate();
+                        }
+                        blend_space
+                    }),
                 },
                 |_, _| (),
             ),
@@ -383,6 +493,7 @@ impl From<AnimationGraph> for SerializedAnimationGraph {
             graph: animation_graph.graph.map(
                 |_, node| SerializedAnimationGraphNode {
                     weight: node.weight,
+                    blend_space: node.blend_space.clone(),
                     clip: node.clip.as_ref().map(|clip| match clip.path() {
                         Some(path) => SerializedAnimationClip::AssetPath(path.clone()),
                         None => SerializedAnimationClip::AssetId(clip.id()),
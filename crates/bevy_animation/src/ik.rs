@@ -0,0 +1,458 @@
+//! Inverse kinematics, which bends chains of bones so that their end reaches a
+//! target, such as a foot reaching the ground or a hand reaching a handle.
+
+use bevy_ecs::{entity::MapEntities, prelude::*, reflect::ReflectMapEntities};
+use bevy_hierarchy::Parent;
+use bevy_math::{Quat, Vec3};
+use bevy_reflect::Reflect;
+use bevy_transform::components::{GlobalTransform, Transform};
+
+/// Bends a chain of three bones, such as a leg or an arm, so that its end
+/// reaches the position of a target entity.
+///
+/// The bones are solved after the animations are applied, and before the
+/// transforms are propagated, so the result is visible in the same frame. The
+/// component can be placed on any entity, such as the end bone.
+///
+/// ```
+/// # use bevy_animation::prelude::*;
+/// # use bevy_ecs::prelude::*;
+/// # fn setup(mut commands: Commands, thigh: Entity, shin: Entity, foot: Entity, ground: Entity, knee_target: Entity) {
+/// commands
+///     .entity(foot)
+///     .insert(TwoBoneIk::new(thigh, shin, foot, ground).with_pole(knee_target));
+/// # }
+/// ```
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component, MapEntities)]
+pub struct TwoBoneIk {
+    /// The first bone of the chain, such as a thigh or an upper arm.
+    pub root: Entity,
+    /// The middle bone of the chain, which bends, such as a shin or a forearm.
+    pub joint: Entity,
+    /// The end of the chain, such as a foot or a hand, which reaches the
+    /// target.
+    pub end: Entity,
+    /// The entity whose position the end of the chain reaches.
+    pub target: Entity,
+    /// The entity towards which the joint bends, such as a point in front of
+    /// the knee, or `None` to keep the joint bending the way it's animated.
+    pub pole: Option<Entity>,
+    /// How much the solution overrides the animated pose, from 0.0 to 1.0.
+    pub weight: f32,
+}
+
+/// Bends a chain of any number of bones, such as a tail or a spine, so that
+/// its end reaches the position of a target entity, with the FABRIK
+/// algorithm.
+///
+/// The bones are solved after the animations are applied, and before the
+/// transforms are propagated, so the result is visible in the same frame.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component, MapEntities)]
+pub struct FabrikChain {
+    /// The bones of the chain, from its root to its end.
+    pub bones: Vec<Entity>,
+    /// The entity whose position the end of the chain reaches.
+    pub target: Entity,
+    /// The entity towards which the bones of the chain bend, if any.
+    pub pole: Option<Entity>,
+    /// The maximum number of iterations used to reach the target.
+    pub iterations: u32,
+    /// The distance to the target under which the target is considered
+    /// reached, in world units.
+    pub tolerance: f32,
+    /// How much the solution overrides the animated pose, from 0.0 to 1.0.
+    pub weight: f32,
+}
+
+impl TwoBoneIk {
+    /// Creates a chain from its three bones, reaching the given target.
+    pub fn new(root: Entity, joint: Entity, end: Entity, target: Entity) -> Self {
+        Self {
+            root,
+            joint,
+            end,
+            target,
+            pole: None,
+            weight: 1.0,
+        }
+    }
+
+    /// Bends the joint towards the given entity.
+    pub fn with_pole(mut self, pole: Entity) -> Self {
+        self.pole = Some(pole);
+        self
+    }
+
+    /// Sets how much the solution overrides the animated pose.
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
+impl FabrikChain {
+    /// Creates a chain from its bones, from its root to its end, reaching the
+    /// given target.
+    pub fn new(bones: impl IntoIterator<Item = Entity>, target: Entity) -> Self {
+        Self {
+            bones: bones.into_iter().collect(),
+            target,
+            pole: None,
+            iterations: 10,
+            tolerance: 0.001,
+            weight: 1.0,
+        }
+    }
+
+    /// Bends the bones towards the given entity.
+    pub fn with_pole(mut self, pole: Entity) -> Self {
+        self.pole = Some(pole);
+        self
+    }
+
+    /// Sets the maximum number of iterations used to reach the target.
+    pub fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Sets the distance to the target under which it's considered reached.
+    pub fn with_tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Sets how much the solution overrides the animated pose.
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
+impl MapEntities for TwoBoneIk {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.root = entity_mapper.map_entity(self.root);
+        self.joint = entity_mapper.map_entity(self.joint);
+        self.end = entity_mapper.map_entity(self.end);
+        self.target = entity_mapper.map_entity(self.target);
+        self.pole = self.pole.map(|pole| entity_mapper.map_entity(pole));
+    }
+}
+
+impl MapEntities for FabrikChain {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        for bone in &mut self.bones {
+            *bone = entity_mapper.map_entity(*bone);
+        }
+        self.target = entity_mapper.map_entity(self.target);
+        self.pole = self.pole.map(|pole| entity_mapper.map_entity(pole));
+    }
+}
+
+/// A system that solves the [`TwoBoneIk`] and [`FabrikChain`] components,
+/// rotating their bones after the animations are applied.
+pub fn solve_inverse_kinematics(
+    two_bone_iks: Query<&TwoBoneIk>,
+    fabrik_chains: Query<&FabrikChain>,
+    mut transforms: Query<&mut Transform>,
+    parents: Query<&Parent>,
+) {
+    for ik in &two_bone_iks {
+        if ik.weight <= 0.0 {
+            continue;
+        }
+        let global = |entity| global_transform(entity, &transforms, &parents);
+        let (Some(root), Some(joint), Some(end), Some(target)) = (
+            global(ik.root),
+            global(ik.joint),
+            global(ik.end),
+            global(ik.target),
+        ) else {
+            continue;
+        };
+        let pole = ik.pole.and_then(global).map(|pole| pole.translation);
+        let (root_rotation, joint_rotation) = solve_two_bone(
+            root.translation,
+            joint.translation,
+            end.translation,
+            target.translation,
+            pole,
+        );
+
+        // Rotate the joint first, as its parent is still in its former pose.
+        rotate_bone(
+            ik.joint,
+            joint_rotation,
+            ik.weight,
+            &mut transforms,
+            &parents,
+        );
+        rotate_bone(ik.root, root_rotation, ik.weight, &mut transforms, &parents);
+    }
+
+    for chain in &fabrik_chains {
+        if chain.weight <= 0.0 || chain.bones.len() < 2 {
+            continue;
+        }
+        let global = |entity| global_transform(entity, &transforms, &parents);
+        let Some(mut positions) = chain
+            .bones
+            .iter()
+            .map(|&bone| global(bone).map(|global| global.translation))
+            .collect::<Option<Vec<_>>>()
+        else {
+            continue;
+        };
+        let Some(target) = global(chain.target) else {
+            continue;
+        };
+        let pole = chain.pole.and_then(global).map(|pole| pole.translation);
+        solve_fabrik(
+            &mut positions,
+            target.translation,
+            pole,
+            chain.iterations,
+            chain.tolerance,
+        );
+
+        // Point each bone to the solved position of the next one, from the
+        // root, as each rotation moves the bones after it.
+        for (index, window) in chain.bones.windows(2).enumerate() {
+            let (Some(bone), Some(next)) = (
+                global_transform(window[0], &transforms, &parents),
+                global_transform(window[1], &transforms, &parents),
+            ) else {
+                break;
+            };
+            let from = (next.translation - bone.translation).normalize_or_zero();
+            let to = (positions[index + 1] - bone.translation).normalize_or_zero();
+            if from == Vec3::ZERO || to == Vec3::ZERO {
+                continue;
+            }
+            let rotation = Quat::from_rotation_arc(from, to);
+            rotate_bone(window[0], rotation, chain.weight, &mut transforms, &parents);
+        }
+    }
+}
+
+/// Returns the global transform of an entity from the local transforms of its
+/// ancestors, as the [`GlobalTransform`]s aren't propagated yet.
+fn global_transform(
+    entity: Entity,
+    transforms: &Query<&mut Transform>,
+    parents: &Query<&Parent>,
+) -> Option<Transform> {
+    let mut global = GlobalTransform::from(*transforms.get(entity).ok()?);
+    let mut current = entity;
+    while let Ok(parent) = parents.get(current) {
+        current = parent.get();
+        let Ok(transform) = transforms.get(current) else {
+            break;
+        };
+        global = GlobalTransform::from(*transform) * global;
+    }
+    Some(global.compute_transform())
+}
+
+/// Rotates a bone by the given rotation in world space, scaled by the weight.
+fn rotate_bone(
+    bone: Entity,
+    rotation: Quat,
+    weight: f32,
+    transforms: &mut Query<&mut Transform>,
+    parents: &Query<&Parent>,
+) {
+    let parent_rotation = parents
+        .get(bone)
+        .ok()
+        .and_then(|parent| global_transform(parent.get(), transforms, parents))
+        .map_or(Quat::IDENTITY, |parent| parent.rotation);
+    let Ok(mut transform) = transforms.get_mut(bone) else {
+        return;
+    };
+    // Bring the rotation into the space of the parent of the bone.
+    let rotation = Quat::IDENTITY.slerp(rotation, weight.min(1.0));
+    let rotation = parent_rotation.inverse() * rotation * parent_rotation;
+    transform.rotation = (rotation * transform.rotation).normalize();
+}
+
+/// Returns the rotations of the root and the joint of a chain of two bones, in
+/// world space, which bring its end to the target.
+///
+/// The rotation of the joint is applied before the rotation of the root,
+/// which moves the joint and the end with it.
+fn solve_two_bone(a: Vec3, b: Vec3, c: Vec3, target: Vec3, pole: Option<Vec3>) -> (Quat, Quat) {
+    const EPSILON: f32 = 0.0001;
+
+    let length_ab = (b - a).length();
+    let length_cb = (c - b).length();
+    let length_at = (target - a)
+        .length()
+        .clamp(EPSILON, length_ab + length_cb - EPSILON);
+
+    let angle = |from: Vec3, to: Vec3| {
+        from.normalize_or_zero()
+            .dot(to.normalize_or_zero())
+            .clamp(-1.0, 1.0)
+            .acos()
+    };
+
+    // The current angles of the triangle, and the ones reaching the target,
+    // from the law of cosines.
+    let ac_ab_0 = angle(c - a, b - a);
+    let ba_bc_0 = angle(a - b, c - b);
+    let ac_at_0 = angle(c - a, target - a);
+    let ac_ab_1 = ((length_cb * length_cb - length_ab * length_ab - length_at * length_at)
+        / (-2.0 * length_ab * length_at))
+        .clamp(-1.0, 1.0)
+        .acos();
+    let ba_bc_1 = ((length_at * length_at - length_ab * length_ab - length_cb * length_cb)
+        / (-2.0 * length_ab * length_cb))
+        .clamp(-1.0, 1.0)
+        .acos();
+
+    // Bend in the current plane of the chain, or any plane when it's straight.
+    let mut bend_axis = (c - a).cross(b - a).normalize_or_zero();
+    if bend_axis == Vec3::ZERO {
+        bend_axis = (c - a).any_orthonormal_vector();
+    }
+    let bend_root = Quat::from_axis_angle(bend_axis, ac_ab_1 - ac_ab_0);
+    let bend_joint = Quat::from_axis_angle(bend_axis, ba_bc_1 - ba_bc_0);
+
+    // Then turn the chain towards the target.
+    let turn_axis = (c - a).cross(target - a).normalize_or_zero();
+    let turn = if turn_axis == Vec3::ZERO {
+        Quat::IDENTITY
+    } else {
+        Quat::from_axis_angle(turn_axis, ac_at_0)
+    };
+    let mut root_rotation = turn * bend_root;
+
+    // Finally twist the chain around the direction of the target, so that the
+    // joint points towards the pole.
+    if let Some(pole) = pole {
+        let axis = (target - a).normalize_or_zero();
+        let joint = root_rotation * (b - a);
+        let joint = (joint - axis * joint.dot(axis)).normalize_or_zero();
+        let pole = (pole - a) - axis * (pole - a).dot(axis);
+        let pole = pole.normalize_or_zero();
+        if axis != Vec3::ZERO && joint != Vec3::ZERO && pole != Vec3::ZERO {
+            let twist = joint.cross(pole).dot(axis).atan2(joint.dot(pole));
+            root_rotation = Quat::from_axis_angle(axis, twist) * root_rotation;
+        }
+    }
+
+    (root_rotation, bend_joint)
+}
+
+/// Moves the positions of the bones of a chain so that its end reaches the
+/// target, keeping the distances between them and the position of its root.
+fn solve_fabrik(
+    positions: &mut [Vec3],
+    target: Vec3,
+    pole: Option<Vec3>,
+    iterations: u32,
+    tolerance: f32,
+) {
+    let lengths: Vec<f32> = positions
+        .windows(2)
+        .map(|window| (window[1] - window[0]).length())
+        .collect();
+    let root = positions[0];
+
+    // Stretch towards a target out of reach.
+    if (target - root).length() >= lengths.iter().sum::<f32>() {
+        let direction = (target - root).normalize_or_zero();
+        for (index, length) in lengths.iter().enumerate() {
+            positions[index + 1] = positions[index] + direction * *length;
+        }
+        return;
+    }
+
+    // Bend the chain towards the pole first, so that it keeps bending that way.
+    if let Some(pole) = pole {
+        for index in 1..positions.len() - 1 {
+            let (previous, next) = (positions[index - 1], positions[index + 1]);
+            let axis = (next - previous).normalize_or_zero();
+            let project = |point: Vec3| {
+                let offset = point - previous;
+                (offset - axis * offset.dot(axis)).normalize_or_zero()
+            };
+            let (bone, pole) = (project(positions[index]), project(pole));
+            if axis != Vec3::ZERO && bone != Vec3::ZERO && pole != Vec3::ZERO {
+                let rotation = Quat::from_rotation_arc(bone, pole);
+                positions[index] = previous + rotation * (positions[index] - previous);
+            }
+        }
+    }
+
+    let end = positions.len() - 1;
+    for _ in 0..iterations {
+        if (positions[end] - target).length() <= tolerance {
+            break;
+        }
+
+        // Backward, from the target to the root.
+        positions[end] = target;
+        for index in (0..end).rev() {
+            let direction = (positions[index] - positions[index + 1]).normalize_or_zero();
+            positions[index] = positions[index + 1] + direction * lengths[index];
+        }
+
+        // Forward, from the root to the target.
+        positions[0] = root;
+        for index in 0..end {
+            let direction = (positions[index + 1] - positions[index]).normalize_or_zero();
+            positions[index + 1] = positions[index] + direction * lengths[index];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::Vec3;
+
+    use super::{solve_fabrik, solve_two_bone};
+
+    #[test]
+    fn two_bone_reaches_target() {
+        let (a, b, c) = (
+            Vec3::ZERO,
+            Vec3::new(0.0, -1.0, 0.1),
+            Vec3::new(0.0, -2.0, 0.0),
+        );
+        let target = Vec3::new(0.5, -1.5, 0.5);
+        let pole = Vec3::new(0.0, -1.0, 2.0);
+        let (root_rotation, joint_rotation) = solve_two_bone(a, b, c, target, Some(pole));
+
+        let joint = a + root_rotation * (b - a);
+        let end = joint + root_rotation * joint_rotation * (c - b);
+        assert!((end - target).length() < 1e-3);
+        assert!(((joint - a).length() - (b - a).length()).abs() < 1e-4);
+
+        // The knee points towards the pole.
+        let axis = (target - a).normalize();
+        let knee = (joint - a) - axis * (joint - a).dot(axis);
+        let pole = (pole - a) - axis * (pole - a).dot(axis);
+        assert!(knee.normalize().dot(pole.normalize()) > 0.999);
+    }
+
+    #[test]
+    fn fabrik_reaches_target() {
+        let mut positions = [0.0, 1.0, 2.0, 3.0].map(|y| Vec3::new(0.0, y, 0.0));
+        let target = Vec3::new(1.5, 1.5, 0.0);
+        solve_fabrik(&mut positions, target, None, 20, 0.001);
+
+        assert_eq!(positions[0], Vec3::ZERO);
+        assert!((positions[3] - target).length() <= 0.001);
+        for window in positions.windows(2) {
+            assert!(((window[1] - window[0]).length() - 1.0).abs() < 1e-4);
+        }
+
+        // Targets out of reach stretch the chain.
+        solve_fabrik(&mut positions, Vec3::new(10.0, 0.0, 0.0), None, 20, 0.001);
+        assert!((positions[3] - Vec3::new(3.0, 0.0, 0.0)).length() < 1e-4);
+    }
+}
@@ -8,7 +8,12 @@
 //! Animation for the game engine Bevy
 
 mod animatable;
+mod blend_space;
+mod event;
 mod graph;
+mod ik;
+mod root_motion;
+mod state_machine;
 mod transition;
 mod util;
 
@@ -46,11 +51,19 @@ use uuid::Uuid;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        animatable::*, graph::*, transition::*, AnimationClip, AnimationPlayer, AnimationPlugin,
-        Interpolation, Keyframes, VariableCurve,
+        animatable::*, blend_space::*, event::*, graph::*, ik::*, root_motion::*, state_machine::*,
+        transition::*, AnimationClip, AnimationPlayer, AnimationPlugin, Interpolation, Keyframes,
+        VariableCurve,
     };
 }
 
+use crate::event::{trigger_animation_events, AnimationEvent, AnimationEventMarker};
+use crate::ik::{solve_inverse_kinematics, FabrikChain, TwoBoneIk};
+use crate::root_motion::{apply_root_motion, extract_root_motion, RootMotion, RootMotionDelta};
+use crate::state_machine::{
+    advance_state_machines, AnimationStateEntered, AnimationStateExited, AnimationStateMachine,
+    AnimationStateMachinePlayer,
+};
 use crate::transition::{advance_transitions, expire_completed_transitions};
 
 /// The [UUID namespace] of animation targets (e.g. bones).
@@ -177,6 +190,7 @@ pub enum Interpolation {
 #[derive(Asset, Reflect, Clone, Debug, Default)]
 pub struct AnimationClip {
     curves: AnimationCurves,
+    events: Vec<AnimationEventMarker>,
     duration: f32,
 }
 
@@ -308,6 +322,36 @@ impl AnimationClip {
             .max(*curve.keyframe_timestamps.last().unwrap_or(&0.0));
         self.curves.entry(target_id).or_default().push(curve);
     }
+
+    /// The [`AnimationEventMarker`]s of this clip, sorted by time.
+    #[inline]
+    pub fn events(&self) -> &[AnimationEventMarker] {
+        &self.events
+    }
+
+    /// Adds an event at the given time of this clip, fired as an
+    /// [`AnimationEvent`] when its playback crosses that time.
+    ///
+    /// If the event is beyond the current duration of this clip, this method
+    /// lengthens this clip to include it.
+    pub fn add_event(
+        &mut self,
+        time: f32,
+        name: impl Into<String>,
+        payload: impl Into<String>,
+    ) -> &mut Self {
+        self.duration = self.duration.max(time);
+        let index = self.events.partition_point(|marker| marker.time <= time);
+        self.events.insert(
+            index,
+            AnimationEventMarker {
+                time,
+                name: name.into(),
+                payload: payload.into(),
+            },
+        );
+        self
+    }
 }
 
 /// Repetition behavior of an animation.
@@ -343,6 +387,8 @@ pub struct ActiveAnimation {
     ///
     /// Note: This will always be in the range [0.0, animation clip duration]
     seek_time: f32,
+    /// The timestamp inside of the animation clip before it was last advanced.
+    last_seek_time: f32,
     /// Number of times the animation has completed.
     /// If the animation is playing in reverse, this increments when the animation passes the start.
     completions: u32,
@@ -358,6 +404,7 @@ impl Default for ActiveAnimation {
             speed: 1.0,
             elapsed: 0.0,
             seek_time: 0.0,
+            last_seek_time: 0.0,
             completions: 0,
             paused: false,
         }
@@ -423,6 +470,12 @@ impl ActiveAnimation {
         self.weight = weight;
     }
 
+    /// Returns the actual weight of this animation in the last evaluation of
+    /// the [`AnimationGraph`], including the weights of its blend spaces.
+    pub fn computed_weight(&self) -> f32 {
+        self.computed_weight
+    }
+
     /// Pause the animation.
     pub fn pause(&mut self) -> &mut Self {
         self.paused = true;
@@ -513,6 +566,10 @@ pub struct AnimationPlayer {
     /// ordering when applying the animations.
     active_animations: BTreeMap<AnimationNodeIndex, ActiveAnimation>,
     blend_weights: HashMap<AnimationNodeIndex, f32>,
+    /// The parameters read by the blend spaces of the [`AnimationGraph`].
+    parameters: HashMap<String, f32>,
+    /// The motion of the root bone this frame, extracted by [`RootMotion`].
+    root_motion: RootMotionDelta,
 }
 
 // This is needed since `#[derive(Clone)]` does not generate optimized `clone_from`.
@@ -521,12 +578,16 @@ impl Clone for AnimationPlayer {
         Self {
             active_animations: self.active_animations.clone(),
             blend_weights: self.blend_weights.clone(),
+            parameters: self.parameters.clone(),
+            root_motion: self.root_motion,
         }
     }
 
     fn clone_from(&mut self, source: &Self) {
         self.active_animations.clone_from(&source.active_animations);
         self.blend_weights.clone_from(&source.blend_weights);
+        self.parameters.clone_from(&source.parameters);
+        self.root_motion = source.root_motion;
     }
 }
 
@@ -550,6 +611,8 @@ pub struct AnimationGraphEvaluator {
     dfs_visited: FixedBitSet,
     /// Accumulated weights for each node.
     weights: Vec<f32>,
+    /// The weights computed by the blend spaces for each of their children.
+    blend_space_weights: Vec<f32>,
 }
 
 thread_local! {
@@ -695,6 +758,44 @@ impl AnimationPlayer {
     pub fn animation_is_playing(&self, animation: AnimationNodeIndex) -> bool {
         self.active_animations.contains_key(&animation)
     }
+
+    /// Returns the value of a parameter read by the [`BlendSpace`]s of the
+    /// graph, or `None` if it was never set.
+    ///
+    /// [`BlendSpace`]: crate::blend_space::BlendSpace
+    pub fn parameter(&self, name: &str) -> Option<f32> {
+        self.parameters.get(name).copied()
+    }
+
+    /// Sets the value of a parameter read by the [`BlendSpace`]s of the graph,
+    /// such as the speed of a character.
+    ///
+    /// [`BlendSpace`]: crate::blend_space::BlendSpace
+    pub fn set_parameter(&mut self, name: &str, value: f32) -> &mut Self {
+        match self.parameters.get_mut(name) {
+            Some(parameter) => *parameter = value,
+            None => {
+                self.parameters.insert(name.to_owned(), value);
+            }
+        }
+        self
+    }
+
+    /// Iterates through the parameters read by the [`BlendSpace`]s of the
+    /// graph.
+    ///
+    /// [`BlendSpace`]: crate::blend_space::BlendSpace
+    pub fn parameters(&self) -> impl Iterator<Item = (&str, f32)> {
+        self.parameters
+            .iter()
+            .map(|(name, &value)| (name.as_str(), value))
+    }
+
+    /// Returns the motion of the root bone of the animations this frame, when
+    /// extracted by a [`RootMotion`] on the same entity.
+    pub fn root_motion(&self) -> RootMotionDelta {
+        self.root_motion
+    }
 }
 
 /// A system that advances the time for all playing animations.
@@ -722,6 +823,7 @@ pub fn advance_animations(
             let AnimationPlayer {
                 ref mut active_animations,
                 ref blend_weights,
+                ref parameters,
                 ..
             } = *player;
 
@@ -736,18 +838,25 @@ pub fn advance_animations(
 
                 let node = &animation_graph[node_index];
 
-                // Calculate weight from the graph.
+                // Calculate weight from the graph. The parents were visited
+                // first, so their weights already include their ancestors.
                 let mut weight = node.weight;
                 for parent_index in animation_graph
                     .graph
                     .neighbors_directed(node_index, Direction::Incoming)
                 {
-                    weight *= animation_graph[parent_index].weight;
+                    weight *= evaluator.weights[parent_index.index()];
+                }
+                weight *= evaluator.blend_space_weights[node_index.index()];
+
+                // Compute the weights of the children of blend spaces.
+                if let Some(ref blend_space) = node.blend_space {
+                    blend_space.write_weights(parameters, &mut evaluator.blend_space_weights);
                 }
-                evaluator.weights[node_index.index()] = weight;
 
                 if let Some(active_animation) = active_animations.get_mut(&node_index) {
                     // Tick the animation if necessary.
+                    active_animation.last_seek_time = active_animation.seek_time;
                     if !active_animation.paused {
                         if let Some(ref clip_handle) = node.clip {
                             if let Some(clip) = animation_clips.get(clip_handle) {
@@ -761,7 +870,9 @@ pub fn advance_animations(
                     weight *= blend_weight;
                 }
 
-                // Write in the computed weight.
+                // Write in the computed weight, which is propagated to the
+                // descendants, so that fading a blend node fades them too.
+                evaluator.weights[node_index.index()] = weight;
                 if let Some(active_animation) = active_animations.get_mut(&node_index) {
                     active_animation.computed_weight = weight;
                 }
@@ -781,7 +892,11 @@ pub fn advance_animations(
 pub fn animate_targets(
     clips: Res<Assets<AnimationClip>>,
     graphs: Res<Assets<AnimationGraph>>,
-    players: Query<(&AnimationPlayer, &Handle<AnimationGraph>)>,
+    players: Query<(
+        &AnimationPlayer,
+        &Handle<AnimationGraph>,
+        Option<&RootMotion>,
+    )>,
     mut targets: Query<(
         Entity,
         &AnimationTarget,
@@ -798,7 +913,9 @@ pub fn animate_targets(
     targets
         .par_iter_mut()
         .for_each(|(id, target, name, (transform, morph_weights))| {
-            let Ok((animation_player, animation_graph_handle)) = players.get(target.player) else {
+            let Ok((animation_player, animation_graph_handle, root_motion)) =
+                players.get(target.player)
+            else {
                 trace!(
                     "Either an animation player {:?} or a graph was missing for the target \
                      entity {:?} ({:?}); no animations will play this frame",
@@ -842,7 +959,9 @@ pub fn animate_targets(
             for (&animation_graph_node_index, active_animation) in
                 animation_player.active_animations.iter()
             {
-                if active_animation.weight == 0.0 {
+                // Skip the animations without influence, such as those
+                // blended out by a blend space.
+                if active_animation.computed_weight == 0.0 {
                     continue;
                 }
 
@@ -863,6 +982,16 @@ pub fn animate_targets(
 
                 target_context.apply(curves, weight / total_weight, active_animation.seek_time);
             }
+
+            // Remove the motion of the root bone, which moves the character
+            // instead.
+            if let (Some(root_motion), Some(transform)) =
+                (root_motion, target_context.transform.as_mut())
+            {
+                if root_motion.target == target.id && total_weight > 0.0 {
+                    root_motion.strip(transform);
+                }
+            }
         });
 }
 
@@ -1170,16 +1299,30 @@ impl Plugin for AnimationPlugin {
             .init_asset_loader::<AnimationGraphAssetLoader>()
             .register_asset_reflect::<AnimationClip>()
             .register_asset_reflect::<AnimationGraph>()
+            .init_asset::<AnimationStateMachine>()
+            .register_asset_reflect::<AnimationStateMachine>()
             .register_type::<AnimationPlayer>()
             .register_type::<AnimationTarget>()
             .register_type::<AnimationTransitions>()
+            .register_type::<AnimationStateMachinePlayer>()
+            .register_type::<RootMotion>()
+            .register_type::<TwoBoneIk>()
+            .register_type::<FabrikChain>()
             .register_type::<NodeIndex>()
+            .add_event::<AnimationStateEntered>()
+            .add_event::<AnimationStateExited>()
+            .add_event::<AnimationEvent>()
             .add_systems(
                 PostUpdate,
                 (
+                    advance_state_machines,
                     advance_transitions,
                     advance_animations,
+                    trigger_animation_events,
+                    extract_root_motion,
                     animate_targets,
+                    apply_root_motion,
+                    solve_inverse_kinematics,
                     expire_completed_transitions,
                 )
                     .chain()
@@ -1232,6 +1375,9 @@ impl AnimationGraphEvaluator {
 
         self.weights.clear();
         self.weights.extend(iter::repeat(0.0).take(node_count));
+
+        self.blend_space_weights.clear();
+        self.blend_space_weights.resize(node_count, 1.0);
     }
 }
 
@@ -0,0 +1,425 @@
+//! Root motion, which moves a character with the motion of the root bone of
+//! its animations, rather than letting the animations move its skeleton away.
+
+use std::{f32::consts::PI, ops::Add};
+
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{entity::MapEntities, prelude::*, reflect::ReflectMapEntities};
+use bevy_hierarchy::Parent;
+use bevy_math::{BVec3, FloatExt, Quat, Vec3};
+use bevy_reflect::Reflect;
+use bevy_transform::components::{GlobalTransform, Transform};
+
+use crate::{
+    cubic_spline_interpolation, graph::AnimationGraph, ActiveAnimation, AnimationClip,
+    AnimationPlayer, AnimationTarget, AnimationTargetId, Interpolation, Keyframes, VariableCurve,
+};
+
+/// Extracts the motion of the root bone from the animations of the
+/// [`AnimationPlayer`] on the same entity.
+///
+/// The extracted motion is stripped from the root bone, so that the skeleton
+/// stays in place, and is exposed each frame by
+/// [`AnimationPlayer::root_motion`]. It can then be handed to a physics engine
+/// or a character controller, or applied to the [`Transform`] of the
+/// character with [`RootMotion::apply_to`].
+///
+/// ```
+/// # use bevy_animation::{prelude::*, AnimationTargetId};
+/// # use bevy_core::Name;
+/// # use bevy_ecs::prelude::*;
+/// # fn setup(mut commands: Commands, character: Entity, player: Entity) {
+/// let root_bone = AnimationTargetId::from_names(
+///     [Name::new("root"), Name::new("hips")].iter(),
+/// );
+/// commands
+///     .entity(player)
+///     .insert(RootMotion::new(root_bone).applied_to(character));
+/// # }
+/// ```
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component, MapEntities)]
+pub struct RootMotion {
+    /// The root bone, whose motion is extracted.
+    pub target: AnimationTargetId,
+    /// The axes of the translation of the root bone to extract, in the space of
+    /// its parent.
+    ///
+    /// By default, the horizontal axes X and Z are extracted, and the vertical
+    /// motion, such as the bobbing of a walk, stays in the animations.
+    pub translation_axes: BVec3,
+    /// Whether to extract the rotation of the root bone around the Y axis of
+    /// its parent, so that the character turns with the animations.
+    pub rotation: bool,
+    /// The entity whose [`Transform`] is moved by the root motion each frame,
+    /// usually the root entity of the character, or `None` to only expose it
+    /// by [`AnimationPlayer::root_motion`].
+    pub apply_to: Option<Entity>,
+    /// The entity of the root bone, once found.
+    #[reflect(ignore)]
+    root_entity: Option<Entity>,
+    /// The translation stripped from the root bone this frame.
+    #[reflect(ignore)]
+    translation_offset: Vec3,
+    /// The rotation around the Y axis stripped from the root bone this frame,
+    /// in radians.
+    #[reflect(ignore)]
+    rotation_offset: f32,
+}
+
+/// The motion of the root bone of the animations of an [`AnimationPlayer`]
+/// during a frame, extracted by [`RootMotion`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+pub struct RootMotionDelta {
+    /// The translation of the root bone, in world space.
+    pub translation: Vec3,
+    /// The rotation of the root bone, in world space.
+    pub rotation: Quat,
+}
+
+impl Default for RootMotionDelta {
+    fn default() -> Self {
+        Self {
+            translation: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+        }
+    }
+}
+
+impl RootMotion {
+    /// Extracts the horizontal translation and the rotation around the Y axis
+    /// of the given root bone.
+    pub fn new(target: AnimationTargetId) -> Self {
+        Self {
+            target,
+            translation_axes: BVec3::new(true, false, true),
+            rotation: true,
+            apply_to: None,
+            root_entity: None,
+            translation_offset: Vec3::ZERO,
+            rotation_offset: 0.0,
+        }
+    }
+
+    /// Sets the axes of the translation to extract.
+    pub fn with_translation_axes(mut self, translation_axes: BVec3) -> Self {
+        self.translation_axes = translation_axes;
+        self
+    }
+
+    /// Sets whether to extract the rotation around the Y axis.
+    pub fn with_rotation(mut self, rotation: bool) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Moves the [`Transform`] of the given entity by the root motion.
+    pub fn applied_to(mut self, entity: Entity) -> Self {
+        self.apply_to = Some(entity);
+        self
+    }
+
+    /// Removes the extracted motion from the transform of the root bone.
+    pub(crate) fn strip(&self, transform: &mut Transform) {
+        transform.translation -= self.translation_offset;
+        transform.rotation = Quat::from_rotation_y(-self.rotation_offset) * transform.rotation;
+    }
+}
+
+impl MapEntities for RootMotion {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.apply_to = self.apply_to.map(|entity| entity_mapper.map_entity(entity));
+    }
+}
+
+/// A system that extracts the motion of the root bones of the animations,
+/// after they've been advanced.
+pub fn extract_root_motion(
+    clips: Res<Assets<AnimationClip>>,
+    graphs: Res<Assets<AnimationGraph>>,
+    mut players: Query<(
+        Entity,
+        &mut AnimationPlayer,
+        &mut RootMotion,
+        &Handle<AnimationGraph>,
+    )>,
+    targets: Query<(Entity, &AnimationTarget)>,
+    parents: Query<&Parent>,
+    global_transforms: Query<&GlobalTransform>,
+) {
+    for (entity, mut player, mut root_motion, graph_handle) in &mut players {
+        let Some(graph) = graphs.get(graph_handle) else {
+            continue;
+        };
+        if root_motion.root_entity.is_none() {
+            root_motion.root_entity = targets
+                .iter()
+                .find(|(_, target)| target.player == entity && target.id == root_motion.target)
+                .map(|(root_entity, _)| root_entity);
+        }
+
+        // Average the motion of the clips animating the root bone, like
+        // `animate_targets` averages their poses.
+        let mut total_weight = 0.0;
+        let mut translation_offset = Vec3::ZERO;
+        let mut translation_delta = Vec3::ZERO;
+        let mut rotation_offset = 0.0;
+        let mut rotation_delta = 0.0;
+        for (&node_index, animation) in player.active_animations.iter() {
+            let weight = animation.computed_weight;
+            if weight == 0.0 {
+                continue;
+            }
+            let Some(clip) = graph
+                .get(node_index)
+                .and_then(|node| node.clip.as_ref())
+                .and_then(|clip_handle| clips.get(clip_handle))
+            else {
+                continue;
+            };
+            let Some(curves) = clip.curves_for_target(root_motion.target) else {
+                continue;
+            };
+            total_weight += weight;
+
+            for curve in curves {
+                match curve.keyframes {
+                    Keyframes::Translation(_) => {
+                        let difference = |from, to| {
+                            sample_translation(curve, to) - sample_translation(curve, from)
+                        };
+                        translation_offset += difference(0.0, animation.seek_time) * weight;
+                        translation_delta +=
+                            travelled(animation, clip.duration, difference) * weight;
+                    }
+                    Keyframes::Rotation(_) => {
+                        let difference = |from, to| {
+                            wrap_angle(
+                                yaw(sample_rotation(curve, to)) - yaw(sample_rotation(curve, from)),
+                            )
+                        };
+                        rotation_offset += difference(0.0, animation.seek_time) * weight;
+                        rotation_delta += travelled(animation, clip.duration, difference) * weight;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if total_weight == 0.0 || !root_motion.rotation {
+            rotation_offset = 0.0;
+            rotation_delta = 0.0;
+        }
+        let mask = |value: Vec3| {
+            if total_weight == 0.0 {
+                return Vec3::ZERO;
+            }
+            Vec3::select(
+                root_motion.translation_axes,
+                value / total_weight,
+                Vec3::ZERO,
+            )
+        };
+        let translation_delta = mask(translation_delta);
+        root_motion.translation_offset = mask(translation_offset);
+        if total_weight != 0.0 {
+            rotation_offset /= total_weight;
+            rotation_delta /= total_weight;
+        }
+        root_motion.rotation_offset = rotation_offset;
+
+        // The motion is in the space of the parent of the root bone.
+        let parent_transform = root_motion
+            .root_entity
+            .and_then(|root_entity| parents.get(root_entity).ok())
+            .and_then(|parent| global_transforms.get(parent.get()).ok())
+            .copied()
+            .unwrap_or_default();
+        let (_, parent_rotation, _) = parent_transform.to_scale_rotation_translation();
+        player.root_motion = RootMotionDelta {
+            translation: parent_transform
+                .affine()
+                .transform_vector3(translation_delta),
+            rotation: parent_rotation
+                * Quat::from_rotation_y(rotation_delta)
+                * parent_rotation.inverse(),
+        };
+    }
+}
+
+/// A system that moves the entities the root motion is
+/// [applied to](RootMotion::apply_to).
+pub fn apply_root_motion(
+    players: Query<(&AnimationPlayer, &RootMotion)>,
+    mut transforms: Query<(&mut Transform, Option<&Parent>)>,
+    global_transforms: Query<&GlobalTransform>,
+) {
+    for (player, root_motion) in &players {
+        let Some((mut transform, parent)) = root_motion
+            .apply_to
+            .and_then(|entity| transforms.get_mut(entity).ok())
+        else {
+            continue;
+        };
+        let delta = player.root_motion();
+        if delta == RootMotionDelta::default() {
+            continue;
+        }
+
+        // Bring the motion from world space to the space of the parent.
+        match parent.and_then(|parent| global_transforms.get(parent.get()).ok()) {
+            Some(parent_transform) => {
+                let (_, parent_rotation, _) = parent_transform.to_scale_rotation_translation();
+                transform.translation += parent_transform
+                    .affine()
+                    .inverse()
+                    .transform_vector3(delta.translation);
+                transform.rotation = parent_rotation.inverse()
+                    * delta.rotation
+                    * parent_rotation
+                    * transform.rotation;
+            }
+            None => {
+                transform.translation += delta.translation;
+                transform.rotation = delta.rotation * transform.rotation;
+            }
+        }
+    }
+}
+
+/// Returns the difference of a value of a clip between the last frame and this
+/// one, going around the end of the clip when it loops.
+fn travelled<T: Add<Output = T>>(
+    animation: &ActiveAnimation,
+    duration: f32,
+    difference: impl Fn(f32, f32) -> T,
+) -> T {
+    let (last, now) = (animation.last_seek_time, animation.seek_time);
+    if animation.speed >= 0.0 && now < last {
+        difference(last, duration) + difference(0.0, now)
+    } else if animation.speed < 0.0 && now > last {
+        difference(last, 0.0) + difference(duration, now)
+    } else {
+        difference(last, now)
+    }
+}
+
+/// Returns the angle of a rotation around the Y axis, ignoring its other axes.
+fn yaw(rotation: Quat) -> f32 {
+    2.0 * rotation.y.atan2(rotation.w)
+}
+
+/// Brings an angle back between -π and π.
+fn wrap_angle(angle: f32) -> f32 {
+    (angle + PI).rem_euclid(2.0 * PI) - PI
+}
+
+/// Returns the keyframe of a curve before the given time and how far the time
+/// is to the next one, or the keyframe the time is clamped to when it's outside
+/// of the curve.
+fn find_keyframe(curve: &VariableCurve, time: f32) -> Result<(usize, f32), usize> {
+    let timestamps = &curve.keyframe_timestamps;
+    match curve.find_current_keyframe(time) {
+        Some(step_start) => {
+            let lerp = f32::inverse_lerp(timestamps[step_start], timestamps[step_start + 1], time);
+            Ok((step_start, lerp))
+        }
+        None if time <= timestamps[0] => Err(0),
+        None => Err(timestamps.len() - 1),
+    }
+}
+
+/// Samples a translation curve at the given time, clamped to its keyframes.
+fn sample_translation(curve: &VariableCurve, time: f32) -> Vec3 {
+    let Keyframes::Translation(ref keyframes) = curve.keyframes else {
+        return Vec3::ZERO;
+    };
+    let step_duration = |step_start: usize| {
+        curve.keyframe_timestamps[step_start + 1] - curve.keyframe_timestamps[step_start]
+    };
+    match (&curve.interpolation, find_keyframe(curve, time)) {
+        (Interpolation::CubicSpline, Err(keyframe)) => keyframes[keyframe * 3 + 1],
+        (_, Err(keyframe)) | (Interpolation::Step, Ok((keyframe, _))) => keyframes[keyframe],
+        (Interpolation::Linear, Ok((step_start, lerp))) => {
+            keyframes[step_start].lerp(keyframes[step_start + 1], lerp)
+        }
+        (Interpolation::CubicSpline, Ok((step_start, lerp))) => cubic_spline_interpolation(
+            keyframes[step_start * 3 + 1],
+            keyframes[step_start * 3 + 2],
+            keyframes[(step_start + 1) * 3],
+            keyframes[(step_start + 1) * 3 + 1],
+            lerp,
+            step_duration(step_start),
+        ),
+    }
+}
+
+/// Samples a rotation curve at the given time, clamped to its keyframes.
+fn sample_rotation(curve: &VariableCurve, time: f32) -> Quat {
+    let Keyframes::Rotation(ref keyframes) = curve.keyframes else {
+        return Quat::IDENTITY;
+    };
+    let step_duration = |step_start: usize| {
+        curve.keyframe_timestamps[step_start + 1] - curve.keyframe_timestamps[step_start]
+    };
+    match (&curve.interpolation, find_keyframe(curve, time)) {
+        (Interpolation::CubicSpline, Err(keyframe)) => keyframes[keyframe * 3 + 1],
+        (_, Err(keyframe)) | (Interpolation::Step, Ok((keyframe, _))) => keyframes[keyframe],
+        (Interpolation::Linear, Ok((step_start, lerp))) => {
+            keyframes[step_start].slerp(keyframes[step_start + 1], lerp)
+        }
+        (Interpolation::CubicSpline, Ok((step_start, lerp))) => cubic_spline_interpolation(
+            keyframes[step_start * 3 + 1],
+            keyframes[step_start * 3 + 2],
+            keyframes[(step_start + 1) * 3],
+            keyframes[(step_start + 1) * 3 + 1],
+            lerp,
+            step_duration(step_start),
+        )
+        .normalize(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use bevy_math::{Quat, Vec3};
+
+    use crate::{ActiveAnimation, Interpolation, Keyframes, VariableCurve};
+
+    use super::{sample_translation, travelled, wrap_angle, yaw};
+
+    #[test]
+    fn root_motion_loops() {
+        let curve = VariableCurve {
+            keyframe_timestamps: vec![0.0, 1.0],
+            keyframes: Keyframes::Translation(vec![Vec3::ZERO, Vec3::new(0.0, 0.0, 2.0)]),
+            interpolation: Interpolation::Linear,
+        };
+        let difference =
+            |from, to| sample_translation(&curve, to) - sample_translation(&curve, from);
+
+        let mut animation = ActiveAnimation::default();
+        animation.update(0.25, 1.0);
+        assert_eq!(
+            travelled(&animation, 1.0, difference),
+            Vec3::new(0.0, 0.0, 0.5)
+        );
+
+        // Going around the end of the clip keeps moving forward.
+        animation.last_seek_time = 0.75;
+        animation.seek_time = 0.25;
+        assert_eq!(
+            travelled(&animation, 1.0, difference),
+            Vec3::new(0.0, 0.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn root_motion_yaw() {
+        assert!((yaw(Quat::from_rotation_y(FRAC_PI_2)) - FRAC_PI_2).abs() < 1e-5);
+        assert!(yaw(Quat::from_rotation_x(FRAC_PI_2)).abs() < 1e-5);
+        assert!((wrap_angle(3.0 * FRAC_PI_2) + FRAC_PI_2).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,590 @@
+//! Animation state machines, which play the nodes of an animation graph
+//! according to the parameters of the player.
+
+use std::borrow::Cow;
+
+use bevy_asset::{Asset, Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_reflect::Reflect;
+use bevy_time::Time;
+use bevy_utils::Duration;
+use petgraph::Direction;
+
+use crate::{
+    graph::{AnimationGraph, AnimationNodeIndex},
+    AnimationClip, AnimationPlayer, RepeatAnimation,
+};
+
+/// The index of a state in an [`AnimationStateMachine`].
+pub type AnimationStateIndex = usize;
+
+/// A state machine playing the nodes of an [`AnimationGraph`], switching
+/// between them with cross-fades as the parameters of the
+/// [`AnimationPlayer`] change.
+///
+/// Each state plays a node of the graph, which can be a clip, a blend node or
+/// a blend space. The state machine starts in its first state, and moves to
+/// another state along the first of its transitions whose conditions are met.
+///
+/// To use it, place an [`AnimationStateMachinePlayer`] on the entity of the
+/// [`AnimationPlayer`] and its [`Handle<AnimationGraph>`], and drive it with
+/// [`AnimationPlayer::set_parameter`]:
+///
+/// ```
+/// # use bevy_animation::prelude::*;
+/// # use bevy_asset::Handle;
+/// # use bevy_utils::Duration;
+/// # fn build(idle: Handle<AnimationClip>, run: Handle<AnimationClip>, jump: Handle<AnimationClip>) {
+/// let mut graph = AnimationGraph::new();
+/// let [idle, run, jump] = [idle, run, jump].map(|clip| graph.add_clip(clip, 1.0, graph.root));
+///
+/// let mut state_machine = AnimationStateMachine::new();
+/// let idle = state_machine.add_state(AnimationState::new("idle", idle));
+/// let run = state_machine.add_state(AnimationState::new("run", run));
+/// let jump = state_machine.add_state(AnimationState::new("jump", jump).once());
+/// let fade = Duration::from_millis(200);
+/// state_machine
+///     .add_transition(idle, run, fade)
+///     .when(TransitionCondition::Greater("speed".into(), 0.1));
+/// state_machine
+///     .add_transition(run, idle, fade)
+///     .when(TransitionCondition::Less("speed".into(), 0.1));
+/// state_machine
+///     .add_transition_from_any(jump, fade)
+///     .when(TransitionCondition::Trigger("jump".into()));
+/// state_machine
+///     .add_transition(jump, idle, fade)
+///     .with_exit_time(1.0);
+/// # }
+/// ```
+#[derive(Asset, Reflect, Clone, Debug, Default)]
+pub struct AnimationStateMachine {
+    states: Vec<AnimationState>,
+    transitions: Vec<AnimationStateTransition>,
+}
+
+/// A state of an [`AnimationStateMachine`].
+#[derive(Reflect, Clone, Debug)]
+pub struct AnimationState {
+    /// The name of the state.
+    pub name: Cow<'static, str>,
+    /// The node of the [`AnimationGraph`] played in this state.
+    ///
+    /// All the clips under this node are played, and their weights are faded
+    /// together during the transitions.
+    pub node: AnimationNodeIndex,
+    /// Whether the clips of the state repeat forever, or play once.
+    pub repeat: bool,
+    /// The speed of the clips of the state.
+    pub speed: f32,
+}
+
+/// A transition between the states of an [`AnimationStateMachine`].
+#[derive(Reflect, Clone, Debug)]
+pub struct AnimationStateTransition {
+    /// The state this transition leaves, or `None` to leave any other state.
+    pub from: Option<AnimationStateIndex>,
+    /// The state this transition enters.
+    pub to: AnimationStateIndex,
+    /// The conditions on the parameters of the [`AnimationPlayer`] which must
+    /// all be met to take this transition.
+    pub conditions: Vec<TransitionCondition>,
+    /// The progress of the state this transition leaves which must be reached
+    /// to take this transition, in completions of its clips.
+    ///
+    /// For example, 1.0 waits for the clips to be played once, and 0.5 waits
+    /// for half of them to be played.
+    pub exit_time: Option<f32>,
+    /// The duration of the cross-fade from the state this transition leaves
+    /// to the state it enters.
+    pub duration: Duration,
+}
+
+/// A condition on a parameter of the [`AnimationPlayer`], which must be met to
+/// take an [`AnimationStateTransition`].
+///
+/// Missing parameters are treated as 0.0.
+#[derive(Reflect, Clone, Debug)]
+pub enum TransitionCondition {
+    /// The parameter is greater than the value.
+    Greater(Cow<'static, str>, f32),
+    /// The parameter is less than the value.
+    Less(Cow<'static, str>, f32),
+    /// The parameter isn't 0.0.
+    True(Cow<'static, str>),
+    /// The parameter is 0.0.
+    False(Cow<'static, str>),
+    /// The parameter isn't 0.0, and is reset to 0.0 when the transition is
+    /// taken, so that it's only taken once.
+    Trigger(Cow<'static, str>),
+}
+
+impl AnimationStateMachine {
+    /// Creates a state machine without states.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a state, and returns its index.
+    ///
+    /// The first state added is the initial state of the state machine.
+    pub fn add_state(&mut self, state: AnimationState) -> AnimationStateIndex {
+        self.states.push(state);
+        self.states.len() - 1
+    }
+
+    /// Adds a transition from the state `from` to the state `to`, cross-fading
+    /// over the given `duration`, and returns it so conditions can be added.
+    ///
+    /// Without conditions, the transition is taken right away. Transitions are
+    /// checked in the order they were added.
+    pub fn add_transition(
+        &mut self,
+        from: AnimationStateIndex,
+        to: AnimationStateIndex,
+        duration: Duration,
+    ) -> &mut AnimationStateTransition {
+        self.push_transition(Some(from), to, duration)
+    }
+
+    /// Adds a transition from any other state to the state `to`, cross-fading
+    /// over the given `duration`, and returns it so conditions can be added.
+    pub fn add_transition_from_any(
+        &mut self,
+        to: AnimationStateIndex,
+        duration: Duration,
+    ) -> &mut AnimationStateTransition {
+        self.push_transition(None, to, duration)
+    }
+
+    fn push_transition(
+        &mut self,
+        from: Option<AnimationStateIndex>,
+        to: AnimationStateIndex,
+        duration: Duration,
+    ) -> &mut AnimationStateTransition {
+        self.transitions.push(AnimationStateTransition {
+            from,
+            to,
+            conditions: Vec::new(),
+            exit_time: None,
+            duration,
+        });
+        self.transitions.last_mut().unwrap()
+    }
+
+    /// Returns the state with the given index, if it exists.
+    pub fn state(&self, state: AnimationStateIndex) -> Option<&AnimationState> {
+        self.states.get(state)
+    }
+
+    /// Returns a mutable reference to the state with the given index, if it
+    /// exists.
+    pub fn state_mut(&mut self, state: AnimationStateIndex) -> Option<&mut AnimationState> {
+        self.states.get_mut(state)
+    }
+
+    /// Returns the index of the first state with the given name, if any.
+    pub fn find_state(&self, name: &str) -> Option<AnimationStateIndex> {
+        self.states.iter().position(|state| state.name == name)
+    }
+
+    /// Returns the states of this state machine.
+    pub fn states(&self) -> &[AnimationState] {
+        &self.states
+    }
+
+    /// Returns the transitions of this state machine.
+    pub fn transitions(&self) -> &[AnimationStateTransition] {
+        &self.transitions
+    }
+
+    /// Returns the transitions of this state machine, mutably.
+    pub fn transitions_mut(&mut self) -> &mut [AnimationStateTransition] {
+        &mut self.transitions
+    }
+}
+
+impl AnimationState {
+    /// Creates a state playing the given node of the [`AnimationGraph`],
+    /// repeating its clips at normal speed.
+    pub fn new(name: impl Into<Cow<'static, str>>, node: AnimationNodeIndex) -> Self {
+        Self {
+            name: name.into(),
+            node,
+            repeat: true,
+            speed: 1.0,
+        }
+    }
+
+    /// Plays the clips of the state once, instead of repeating them.
+    pub fn once(mut self) -> Self {
+        self.repeat = false;
+        self
+    }
+
+    /// Sets the speed of the clips of the state.
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+}
+
+impl AnimationStateTransition {
+    /// Adds a condition which must be met to take this transition.
+    pub fn when(&mut self, condition: TransitionCondition) -> &mut Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    /// Waits for the state this transition leaves to reach the given progress,
+    /// in completions of its clips, to take this transition.
+    pub fn with_exit_time(&mut self, exit_time: f32) -> &mut Self {
+        self.exit_time = Some(exit_time);
+        self
+    }
+}
+
+/// Plays an [`AnimationStateMachine`] on the [`AnimationPlayer`] of the same
+/// entity.
+///
+/// The state machine takes control of the weights of the animations of its
+/// states, so it shouldn't be combined with
+/// [`AnimationTransitions`](crate::transition::AnimationTransitions), nor
+/// with animations played directly on the [`AnimationPlayer`] under the nodes
+/// of its states.
+#[derive(Component, Reflect, Clone, Debug, Default)]
+#[reflect(Component)]
+pub struct AnimationStateMachinePlayer {
+    /// The state machine to play.
+    pub state_machine: Handle<AnimationStateMachine>,
+    /// The current state, or `None` before the state machine starts.
+    current: Option<AnimationStateIndex>,
+    /// The time spent in the current state, in seconds.
+    elapsed: f32,
+    /// The weight of the current state.
+    weight: f32,
+    /// The states fading out, the most recent last.
+    fading: Vec<FadingAnimationState>,
+}
+
+/// A state of an [`AnimationStateMachinePlayer`] fading out.
+#[derive(Reflect, Clone, Debug)]
+struct FadingAnimationState {
+    state: AnimationStateIndex,
+    /// The current weight, going down to 0.0.
+    weight: f32,
+    /// How much to decrease `weight` per second.
+    weight_decline_per_sec: f32,
+}
+
+impl AnimationStateMachinePlayer {
+    /// Creates a player for the given state machine, which starts in its first
+    /// state.
+    pub fn new(state_machine: Handle<AnimationStateMachine>) -> Self {
+        Self {
+            state_machine,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the current state, or `None` before the state machine starts.
+    pub fn current_state(&self) -> Option<AnimationStateIndex> {
+        self.current
+    }
+
+    /// Returns the time spent in the current state, in seconds.
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// Returns true while the previous states are fading out.
+    pub fn is_transitioning(&self) -> bool {
+        !self.fading.is_empty()
+    }
+}
+
+/// Sent when an [`AnimationStateMachinePlayer`] enters a state.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AnimationStateEntered {
+    /// The entity of the [`AnimationStateMachinePlayer`].
+    pub entity: Entity,
+    /// The state entered.
+    pub state: AnimationStateIndex,
+}
+
+/// Sent when an [`AnimationStateMachinePlayer`] leaves a state, as it starts
+/// fading out.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AnimationStateExited {
+    /// The entity of the [`AnimationStateMachinePlayer`].
+    pub entity: Entity,
+    /// The state left.
+    pub state: AnimationStateIndex,
+}
+
+/// A system that takes the transitions of the [`AnimationStateMachine`]s and
+/// fades the weights of their states.
+#[allow(clippy::too_many_arguments)]
+pub fn advance_state_machines(
+    time: Res<Time>,
+    state_machines: Res<Assets<AnimationStateMachine>>,
+    graphs: Res<Assets<AnimationGraph>>,
+    clips: Res<Assets<AnimationClip>>,
+    mut players: Query<(
+        Entity,
+        &mut AnimationStateMachinePlayer,
+        &mut AnimationPlayer,
+        &Handle<AnimationGraph>,
+    )>,
+    mut entered: EventWriter<AnimationStateEntered>,
+    mut exited: EventWriter<AnimationStateExited>,
+) {
+    let delta_seconds = time.delta_seconds();
+    for (entity, mut machine_player, mut player, graph_handle) in &mut players {
+        let (Some(state_machine), Some(graph)) = (
+            state_machines.get(&machine_player.state_machine),
+            graphs.get(graph_handle),
+        ) else {
+            continue;
+        };
+        let machine_player = &mut *machine_player;
+
+        let Some(current) = machine_player.current else {
+            if !state_machine.states.is_empty() {
+                enter_state(state_machine, graph, &mut player, machine_player, 0);
+                machine_player.weight = 1.0;
+                entered.send(AnimationStateEntered { entity, state: 0 });
+            }
+            continue;
+        };
+        machine_player.elapsed += delta_seconds;
+
+        let taken = state_machine.transitions.iter().find(|transition| {
+            transition
+                .from
+                .map_or(transition.to != current, |from| from == current)
+                && !transition.exit_time.is_some_and(|exit_time| {
+                    let node = state_machine.states[current].node;
+                    state_progress(graph, &clips, &player, node) < exit_time
+                })
+                && transition
+                    .conditions
+                    .iter()
+                    .all(|condition| condition.is_met(&player))
+        });
+        let current = match taken {
+            Some(transition) => {
+                for condition in &transition.conditions {
+                    if let TransitionCondition::Trigger(name) = condition {
+                        player.set_parameter(name, 0.0);
+                    }
+                }
+                machine_player.fading.push(FadingAnimationState {
+                    state: current,
+                    weight: machine_player.weight,
+                    weight_decline_per_sec: 1.0 / transition.duration.as_secs_f32(),
+                });
+                enter_state(
+                    state_machine,
+                    graph,
+                    &mut player,
+                    machine_player,
+                    transition.to,
+                );
+                exited.send(AnimationStateExited {
+                    entity,
+                    state: current,
+                });
+                entered.send(AnimationStateEntered {
+                    entity,
+                    state: transition.to,
+                });
+                transition.to
+            }
+            None => current,
+        };
+
+        // Like `AnimationTransitions`, the most recent states get as much
+        // weight as they want, and the current state gets whatever's left.
+        let mut remaining_weight = 1.0;
+        for fading in machine_player.fading.iter_mut().rev() {
+            fading.weight =
+                (fading.weight - fading.weight_decline_per_sec * delta_seconds).max(0.0);
+            let weight = fading.weight * remaining_weight;
+            set_state_weight(state_machine, &mut player, fading.state, weight);
+            remaining_weight -= weight;
+        }
+        machine_player.weight = remaining_weight;
+        set_state_weight(state_machine, &mut player, current, remaining_weight);
+
+        // Stop the nodes of the states that faded out, unless they're shared
+        // with the states still playing.
+        let (faded, fading): (Vec<_>, Vec<_>) = machine_player
+            .fading
+            .drain(..)
+            .partition(|fading| fading.weight <= 0.0);
+        machine_player.fading = fading;
+        if faded.is_empty() {
+            continue;
+        }
+        let playing: Vec<_> = machine_player
+            .fading
+            .iter()
+            .map(|fading| fading.state)
+            .chain([current])
+            .flat_map(|state| subtree(graph, state_machine.states[state].node))
+            .collect();
+        for faded in faded {
+            for node in subtree(graph, state_machine.states[faded.state].node) {
+                if !playing.contains(&node) {
+                    player.stop(node);
+                }
+            }
+        }
+    }
+}
+
+/// Starts playing the nodes of a state, and makes it the current state.
+fn enter_state(
+    state_machine: &AnimationStateMachine,
+    graph: &AnimationGraph,
+    player: &mut AnimationPlayer,
+    machine_player: &mut AnimationStateMachinePlayer,
+    state_index: AnimationStateIndex,
+) {
+    // The nodes still playing in the states fading out keep playing from
+    // where they are, and the others restart.
+    let playing: Vec<_> = machine_player
+        .fading
+        .iter()
+        .flat_map(|fading| subtree(graph, state_machine.states[fading.state].node))
+        .collect();
+    let state = &state_machine.states[state_index];
+    for node in subtree(graph, state.node) {
+        let animation = player.start(node);
+        if !playing.contains(&node) {
+            animation.replay();
+        }
+        animation
+            .set_repeat(if state.repeat {
+                RepeatAnimation::Forever
+            } else {
+                RepeatAnimation::Never
+            })
+            .set_speed(state.speed);
+    }
+
+    // A state entered again while fading out stops fading.
+    machine_player
+        .fading
+        .retain(|fading| fading.state != state_index);
+    machine_player.current = Some(state_index);
+    machine_player.elapsed = 0.0;
+}
+
+/// Sets the weight of the node of a state, which is propagated to the clips
+/// under it.
+fn set_state_weight(
+    state_machine: &AnimationStateMachine,
+    player: &mut AnimationPlayer,
+    state: AnimationStateIndex,
+    weight: f32,
+) {
+    if let Some(animation) = player.animation_mut(state_machine.states[state].node) {
+        animation.set_weight(weight);
+    }
+}
+
+/// Returns the progress of the clips of a state, in completions of the clip
+/// with the most weight.
+fn state_progress(
+    graph: &AnimationGraph,
+    clips: &Assets<AnimationClip>,
+    player: &AnimationPlayer,
+    node: AnimationNodeIndex,
+) -> f32 {
+    subtree(graph, node)
+        .into_iter()
+        .filter_map(|node| {
+            let animation = player.animation(node)?;
+            let clip = clips.get(graph[node].clip.as_ref()?)?;
+            let progress = if animation.is_finished() || clip.duration() <= 0.0 {
+                animation.completions() as f32
+            } else {
+                animation.completions() as f32 + animation.seek_time() / clip.duration()
+            };
+            Some((animation.computed_weight(), progress))
+        })
+        .max_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map_or(0.0, |(_, progress)| progress)
+}
+
+/// Returns a node and all the nodes under it.
+fn subtree(graph: &AnimationGraph, node: AnimationNodeIndex) -> Vec<AnimationNodeIndex> {
+    let mut nodes = vec![node];
+    let mut index = 0;
+    while let Some(&node) = nodes.get(index) {
+        for child in graph.graph.neighbors_directed(node, Direction::Outgoing) {
+            if !nodes.contains(&child) {
+                nodes.push(child);
+            }
+        }
+        index += 1;
+    }
+    nodes
+}
+
+impl TransitionCondition {
+    /// Returns whether the condition is met by the parameters of the player.
+    pub fn is_met(&self, player: &AnimationPlayer) -> bool {
+        let parameter = |name: &str| player.parameter(name).unwrap_or_default();
+        match self {
+            TransitionCondition::Greater(name, value) => parameter(name) > *value,
+            TransitionCondition::Less(name, value) => parameter(name) < *value,
+            TransitionCondition::True(name) | TransitionCondition::Trigger(name) => {
+                parameter(name) != 0.0
+            }
+            TransitionCondition::False(name) => parameter(name) == 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{graph::AnimationGraph, AnimationPlayer};
+
+    use super::{subtree, TransitionCondition};
+
+    #[test]
+    fn transition_conditions() {
+        let mut player = AnimationPlayer::default();
+        player
+            .set_parameter("speed", 2.0)
+            .set_parameter("grounded", 1.0);
+
+        assert!(TransitionCondition::Greater("speed".into(), 1.0).is_met(&player));
+        assert!(!TransitionCondition::Less("speed".into(), 1.0).is_met(&player));
+        assert!(TransitionCondition::True("grounded".into()).is_met(&player));
+        assert!(!TransitionCondition::False("grounded".into()).is_met(&player));
+
+        // Missing parameters are 0.0.
+        assert!(TransitionCondition::False("jump".into()).is_met(&player));
+        assert!(!TransitionCondition::Trigger("jump".into()).is_met(&player));
+        assert!(TransitionCondition::Less("height".into(), 1.0).is_met(&player));
+    }
+
+    #[test]
+    fn state_nodes() {
+        let mut graph = AnimationGraph::new();
+        let blend = graph.add_blend(1.0, graph.root);
+        let first = graph.add_blend(1.0, blend);
+        let second = graph.add_blend(1.0, blend);
+        let other = graph.add_blend(1.0, graph.root);
+
+        let nodes = subtree(&graph, blend);
+        assert_eq!(nodes, vec![blend, second, first]);
+        assert!(!nodes.contains(&other));
+        assert_eq!(subtree(&graph, first), vec![first]);
+    }
+}
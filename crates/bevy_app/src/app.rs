@@ -5,8 +5,10 @@ use crate::{
 pub use bevy_derive::AppLabel;
 use bevy_ecs::{
     event::{event_update_system, ManualEventReader},
+    index::{update_component_index, ComponentIndex},
     intern::Interned,
     prelude::*,
+    relation::{update_relation_index, RelationIndex},
     schedule::{ScheduleBuildSettings, ScheduleLabel},
     system::{IntoObserverSystem, SystemId},
 };
@@ -15,6 +17,7 @@ use bevy_utils::tracing::info_span;
 use bevy_utils::{tracing::debug, HashMap};
 use std::{
     fmt::Debug,
+    hash::Hash,
     process::{ExitCode, Termination},
 };
 use std::{
@@ -843,6 +846,36 @@ impl App {
         self.world_mut().observe(observer);
         self
     }
+
+    /// Indexes the entities by the value of their `C` component, to find the ones with a given
+    /// value through a [`QueryByIndex`](bevy_ecs::index::QueryByIndex).
+    ///
+    /// Inserting and removing `C` updates the index right away, while the values changed in place
+    /// are picked up in [`First`].
+    ///
+    /// See [`World::index_component`] for the panics.
+    pub fn index_component<C: Component + Eq + Hash + Clone>(&mut self) -> &mut Self {
+        if !self.world().contains_resource::<ComponentIndex<C>>() {
+            self.world_mut().index_component::<C>();
+            self.add_systems(First, update_component_index::<C>);
+        }
+        self
+    }
+
+    /// Indexes the entities related through `R`, to look them up with a
+    /// [`Related`](bevy_ecs::relation::Related) system parameter.
+    ///
+    /// Inserting and removing `R` updates the index right away, while the targets changed in place
+    /// and the relations to despawned targets are picked up in [`First`].
+    ///
+    /// See [`World::register_relation`] for the panics.
+    pub fn register_relation<R: Relation>(&mut self) -> &mut Self {
+        if !self.world().contains_resource::<RelationIndex<R>>() {
+            self.world_mut().register_relation::<R>();
+            self.add_systems(First, update_relation_index::<R>);
+        }
+        self
+    }
 }
 
 type RunnerFn = Box<dyn FnOnce(App) -> AppExit>;
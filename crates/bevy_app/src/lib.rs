@@ -16,6 +16,7 @@ mod schedule_runner;
 mod sub_app;
 #[cfg(not(target_arch = "wasm32"))]
 mod terminal_ctrl_c_handler;
+mod world_sync;
 
 pub use app::*;
 pub use bevy_derive::DynamicPlugin;
@@ -27,6 +28,7 @@ pub use schedule_runner::*;
 pub use sub_app::*;
 #[cfg(not(target_arch = "wasm32"))]
 pub use terminal_ctrl_c_handler::*;
+pub use world_sync::*;
 
 #[allow(missing_docs)]
 pub mod prelude {
@@ -1,4 +1,4 @@
-use crate::{App, AppLabel, InternedAppLabel, Plugin, Plugins, PluginsState};
+use crate::{App, AppLabel, InternedAppLabel, Plugin, Plugins, PluginsState, SourceWorld};
 use bevy_ecs::{
     event::EventRegistry,
     prelude::*,
@@ -164,6 +164,46 @@ impl SubApp {
         self
     }
 
+    /// Sets the extract method to run the `schedule` on the app's world, where the `World` to
+    /// extract data from is available as the [`SourceWorld`] resource.
+    ///
+    /// Components are synchronized by adding [`sync_component`](crate::sync_component) systems to
+    /// the `schedule`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_app::{App, AppLabel, SourceWorld, SubApp, Main, sync_component};
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::schedule::ScheduleLabel;
+    /// #[derive(Component, Clone)]
+    /// struct Position(f32);
+    ///
+    /// #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, AppLabel)]
+    /// struct SimulationApp;
+    ///
+    /// #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, ScheduleLabel)]
+    /// struct SyncSchedule;
+    ///
+    /// let mut sub_app = SubApp::new();
+    /// sub_app
+    ///     .set_extract_schedule(SyncSchedule)
+    ///     .add_systems(SyncSchedule, sync_component::<Position>);
+    ///
+    /// let mut app = App::new();
+    /// app.insert_sub_app(SimulationApp, sub_app);
+    /// ```
+    pub fn set_extract_schedule(&mut self, schedule: impl ScheduleLabel) -> &mut Self {
+        let schedule = schedule.intern();
+        self.world.init_resource::<SourceWorld>();
+        self.world.add_schedule(Schedule::new(schedule));
+        self.set_extract(move |source, world| {
+            world.resource_mut::<SourceWorld>().swap(source);
+            world.run_schedule(schedule);
+            world.resource_mut::<SourceWorld>().swap(source);
+        })
+    }
+
     /// See [`App::insert_resource`].
     pub fn insert_resource<R: Resource>(&mut self, resource: R) -> &mut Self {
         self.world.insert_resource(resource);
@@ -0,0 +1,211 @@
+use bevy_ecs::{
+    entity::{Entities, EntityHashMap, EntityMapper, MapEntities},
+    prelude::*,
+    query::QueryState,
+};
+use std::ops::{Deref, DerefMut};
+
+/// The [`World`] a [`SubApp`](crate::SubApp) extracts from, while its extract schedule runs.
+///
+/// See [`SubApp::set_extract_schedule`](crate::SubApp::set_extract_schedule). Outside of the
+/// extract schedule, this is an empty world.
+#[derive(Resource, Default)]
+pub struct SourceWorld(World);
+
+impl Deref for SourceWorld {
+    type Target = World;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for SourceWorld {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl SourceWorld {
+    /// Swaps the source world in or out of this resource.
+    pub(crate) fn swap(&mut self, world: &mut World) {
+        std::mem::swap(&mut self.0, world);
+    }
+}
+
+/// Maps the entities of the [`SourceWorld`] to the entities synchronized by [`sync_component`].
+#[derive(Resource, Default, Debug)]
+pub struct SyncedEntities(EntityHashMap<Entity>);
+
+impl SyncedEntities {
+    /// Returns the entity synchronized with the `source` entity.
+    pub fn get(&self, source: Entity) -> Option<Entity> {
+        self.0.get(&source).copied()
+    }
+
+    /// Iterates over the source entities and the entities synchronized with them.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, Entity)> + '_ {
+        self.0.iter().map(|(&source, &target)| (source, target))
+    }
+
+    /// Returns an [`EntityMapper`] mapping the source entities, that reserves an entity in
+    /// `entities` for the ones that aren't synchronized yet.
+    pub fn mapper<'a>(&'a mut self, entities: &'a Entities) -> SyncedEntityMapper<'a> {
+        SyncedEntityMapper {
+            map: &mut self.0,
+            entities,
+        }
+    }
+}
+
+/// The [`EntityMapper`] of [`SyncedEntities`], see [`SyncedEntities::mapper`].
+///
+/// The reserved entities must be flushed with [`World::flush`] before they're used.
+pub struct SyncedEntityMapper<'a> {
+    map: &'a mut EntityHashMap<Entity>,
+    entities: &'a Entities,
+}
+
+impl EntityMapper for SyncedEntityMapper<'_> {
+    fn map_entity(&mut self, entity: Entity) -> Entity {
+        *self
+            .map
+            .entry(entity)
+            .or_insert_with(|| self.entities.reserve_entity())
+    }
+
+    fn mappings(&self) -> impl Iterator<Item = (Entity, Entity)> {
+        self.map.iter().map(|(&source, &target)| (source, target))
+    }
+}
+
+/// Copies the `C` components of the [`SourceWorld`] that changed since the last update to the
+/// [`SyncedEntities`], and removes the components that were removed from the source.
+///
+/// This is added to the extract schedule of a [`SubApp`](crate::SubApp), see
+/// [`SubApp::set_extract_schedule`](crate::SubApp::set_extract_schedule). The entities are
+/// spawned the first time they're synchronized, and despawned with their source entity.
+///
+/// Components referencing other entities use [`sync_component_with_entities`] instead.
+pub fn sync_component<C: Component + Clone>(
+    world: &mut World,
+    query: Local<Option<QueryState<(Entity, &'static C), Changed<C>>>>,
+) {
+    sync(world, query, |_, _| {});
+}
+
+/// Like [`sync_component`], and maps the entities referenced by the components to the
+/// [`SyncedEntities`].
+pub fn sync_component_with_entities<C: Component + Clone + MapEntities>(
+    world: &mut World,
+    query: Local<Option<QueryState<(Entity, &'static C), Changed<C>>>>,
+) {
+    sync(world, query, |component, mapper| {
+        component.map_entities(mapper);
+    });
+}
+
+fn sync<C: Component + Clone>(
+    world: &mut World,
+    mut query: Local<Option<QueryState<(Entity, &'static C), Changed<C>>>>,
+    map_entities: fn(&mut C, &mut SyncedEntityMapper),
+) {
+    world.init_resource::<SyncedEntities>();
+    world.resource_scope(|world, mut source: Mut<SourceWorld>| {
+        world.resource_scope(|world, mut synced: Mut<SyncedEntities>| {
+            for entity in source.removed::<C>() {
+                let Some(target) = synced.get(entity) else {
+                    continue;
+                };
+                if source.get_entity(entity).is_some() {
+                    if let Some(mut target) = world.get_entity_mut(target) {
+                        target.remove::<C>();
+                    }
+                } else {
+                    synced.0.remove(&entity);
+                    world.despawn(target);
+                }
+            }
+
+            let query = query.get_or_insert_with(|| source.query_filtered());
+            for (entity, component) in query.iter(&source) {
+                let mut component = component.clone();
+                let mut mapper = synced.mapper(world.entities());
+                let target = mapper.map_entity(entity);
+                map_entities(&mut component, &mut mapper);
+                world.flush();
+                world.entity_mut(target).insert(component);
+            }
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{
+        entity::{EntityMapper, MapEntities},
+        prelude::*,
+        schedule::ScheduleLabel,
+    };
+
+    use super::{sync_component, sync_component_with_entities, SyncedEntities};
+    use crate::{self as bevy_app, App, AppLabel, SubApp};
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    struct Position(f32);
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    struct Target(Entity);
+
+    impl MapEntities for Target {
+        fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+            self.0 = entity_mapper.map_entity(self.0);
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, AppLabel)]
+    struct SimulationApp;
+
+    #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, ScheduleLabel)]
+    struct SyncSchedule;
+
+    #[test]
+    fn sync_components() {
+        let mut sub_app = SubApp::new();
+        sub_app.set_extract_schedule(SyncSchedule).add_systems(
+            SyncSchedule,
+            (
+                sync_component::<Position>,
+                sync_component_with_entities::<Target>,
+            ),
+        );
+        let mut app = App::new();
+        app.insert_sub_app(SimulationApp, sub_app);
+
+        let a = app.world_mut().spawn(Position(1.0)).id();
+        let b = app.world_mut().spawn((Position(2.0), Target(a))).id();
+        app.update();
+
+        let world = app.sub_app_mut(SimulationApp).world_mut();
+        let synced = world.resource::<SyncedEntities>();
+        let (synced_a, synced_b) = (synced.get(a).unwrap(), synced.get(b).unwrap());
+        assert_eq!(world.get::<Position>(synced_a), Some(&Position(1.0)));
+        assert_eq!(world.get::<Position>(synced_b), Some(&Position(2.0)));
+        assert_eq!(world.get::<Target>(synced_b), Some(&Target(synced_a)));
+
+        app.world_mut().get_mut::<Position>(a).unwrap().0 = 3.0;
+        app.world_mut().entity_mut(b).remove::<Target>();
+        app.update();
+
+        let world = app.sub_app_mut(SimulationApp).world_mut();
+        assert_eq!(world.get::<Position>(synced_a), Some(&Position(3.0)));
+        assert_eq!(world.get::<Target>(synced_b), None);
+
+        app.world_mut().despawn(a);
+        app.update();
+
+        let world = app.sub_app_mut(SimulationApp).world_mut();
+        assert!(world.get_entity(synced_a).is_none());
+        assert!(world.resource::<SyncedEntities>().get(a).is_none());
+    }
+}
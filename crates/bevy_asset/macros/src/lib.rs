@@ -31,6 +31,125 @@ pub fn derive_asset(input: TokenStream) -> TokenStream {
     })
 }
 
+const ASSET_ATTRIBUTE: &str = "asset";
+
+/// How a field of an `AssetCollection` is loaded.
+enum CollectionField {
+    /// `#[asset(path = "...")]` on a `Handle<A>`.
+    Path(syn::LitStr),
+    /// `#[asset(paths("...", "..."))]` on a `Vec<Handle<A>>`.
+    Paths(Vec<syn::LitStr>),
+    /// `#[asset(folder = "...")]` on a `Handle<LoadedFolder>`.
+    Folder(syn::LitStr),
+}
+
+#[proc_macro_derive(AssetCollection, attributes(asset))]
+pub fn derive_asset_collection(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let bevy_asset_path: Path = bevy_asset_path();
+    match derive_asset_collection_internal(&ast, &bevy_asset_path) {
+        Ok(asset_collection) => TokenStream::from(asset_collection),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+fn parse_collection_field(field: &syn::Field) -> Result<Option<CollectionField>, syn::Error> {
+    let mut collection_field = None;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident(ASSET_ATTRIBUTE))
+    {
+        attr.parse_nested_meta(|meta| {
+            if collection_field.is_some() {
+                return Err(meta.error("only one of `path`, `paths` and `folder` can be used"));
+            }
+            if meta.path.is_ident("path") {
+                collection_field = Some(CollectionField::Path(meta.value()?.parse()?));
+            } else if meta.path.is_ident("folder") {
+                collection_field = Some(CollectionField::Folder(meta.value()?.parse()?));
+            } else if meta.path.is_ident("paths") {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                let paths = content.parse_terminated(
+                    syn::parse::ParseBuffer::parse::<syn::LitStr>,
+                    syn::Token![,],
+                )?;
+                collection_field = Some(CollectionField::Paths(paths.into_iter().collect()));
+            } else {
+                return Err(meta.error("expected `path`, `paths` or `folder`"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(collection_field)
+}
+
+fn derive_asset_collection_internal(
+    ast: &DeriveInput,
+    bevy_asset_path: &Path,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let struct_name = &ast.ident;
+    let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
+
+    let Data::Struct(syn::DataStruct {
+        fields: syn::Fields::Named(fields),
+        ..
+    }) = &ast.data
+    else {
+        return Err(syn::Error::new(
+            Span::call_site().into(),
+            "AssetCollection derive only works on structs with named fields",
+        ));
+    };
+
+    let mut field_loads = Vec::new();
+    let mut field_visitors = Vec::new();
+    for field in &fields.named {
+        let ident = field.ident.as_ref().unwrap();
+        match parse_collection_field(field)? {
+            Some(CollectionField::Path(path)) => {
+                field_loads.push(quote!(#ident: asset_server.load(#path)));
+                field_visitors.push(quote!(visit((&self.#ident).into());));
+            }
+            Some(CollectionField::Paths(paths)) => {
+                field_loads.push(quote!(#ident: vec![#(asset_server.load(#paths)),*]));
+                field_visitors.push(quote! {
+                    for handle in &self.#ident {
+                        visit(handle.into());
+                    }
+                });
+            }
+            Some(CollectionField::Folder(path)) => {
+                field_loads.push(quote!(#ident: asset_server.load_folder(#path)));
+                field_visitors.push(quote!(visit((&self.#ident).into());));
+            }
+            None => field_loads.push(quote!(#ident: ::core::default::Default::default())),
+        }
+    }
+
+    // prevent unused variable warnings in case there are no assets
+    let (asset_server, visit) = if field_visitors.is_empty() {
+        (quote!(_asset_server), quote!(_visit))
+    } else {
+        (quote!(asset_server), quote!(visit))
+    };
+
+    Ok(quote! {
+        impl #impl_generics #bevy_asset_path::AssetCollection for #struct_name #type_generics #where_clause {
+            fn load(#asset_server: &#bevy_asset_path::AssetServer) -> Self {
+                Self {
+                    #(#field_loads,)*
+                }
+            }
+
+            fn visit_handles(&self, #visit: &mut impl FnMut(#bevy_asset_path::UntypedAssetId)) {
+                #(#field_visitors)*
+            }
+        }
+    })
+}
+
 #[proc_macro_derive(VisitAssetDependencies, attributes(dependency))]
 pub fn derive_asset_dependency_visitor(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
@@ -1,5 +1,8 @@
 use crate::{self as bevy_asset};
-use crate::{Asset, AssetEvent, AssetHandleProvider, AssetId, AssetServer, Handle, UntypedHandle};
+use crate::{
+    Asset, AssetEvent, AssetHandleProvider, AssetId, AssetServer, AssetUsages, Handle,
+    RetainPolicy, UntypedHandle,
+};
 use bevy_ecs::{
     prelude::EventWriter,
     system::{Res, ResMut, Resource},
@@ -295,6 +298,18 @@ pub struct Assets<A: Asset> {
     /// Assets managed by the `Assets` struct with live strong `Handle`s
     /// originating from `get_strong_handle`.
     duplicate_handles: HashMap<AssetId<A>, u16>,
+    retain_policy: RetainPolicy,
+    /// Assets whose strong `Handle`s were dropped, kept by the `retain_policy`.
+    unused: HashMap<AssetId<A>, UnusedAsset>,
+    release_unused: bool,
+}
+
+/// An asset kept by its [`RetainPolicy`] after its strong handles were dropped.
+#[derive(Default)]
+struct UnusedAsset {
+    /// Whether each of the pending handle drops was managed by the [`AssetServer`].
+    drops: Vec<bool>,
+    unused_frames: u32,
 }
 
 impl<A: Asset> Default for Assets<A> {
@@ -308,6 +323,9 @@ impl<A: Asset> Default for Assets<A> {
             hash_map: Default::default(),
             queued_events: Default::default(),
             duplicate_handles: Default::default(),
+            retain_policy: Default::default(),
+            unused: Default::default(),
+            release_unused: false,
         }
     }
 }
@@ -480,6 +498,29 @@ impl<A: Asset> Assets<A> {
         }
     }
 
+    /// Sets the [`RetainPolicy`] deciding when the assets of this collection are removed once all
+    /// of their strong handles are dropped.
+    pub fn set_retain_policy(&mut self, policy: RetainPolicy) {
+        self.retain_policy = policy;
+    }
+
+    /// Returns the [`RetainPolicy`] of this collection.
+    pub fn retain_policy(&self) -> RetainPolicy {
+        self.retain_policy
+    }
+
+    /// Returns the ids of the assets whose strong handles were all dropped, but which are kept by
+    /// the [`RetainPolicy`].
+    pub fn unused_ids(&self) -> impl Iterator<Item = AssetId<A>> + '_ {
+        self.unused.keys().copied()
+    }
+
+    /// Removes the unused assets kept by [`RetainPolicy::Manual`] or [`RetainPolicy::WhileUsed`]
+    /// the next time the handle drops are processed, in [`PreUpdate`](bevy_app::PreUpdate).
+    pub fn release_unused(&mut self) {
+        self.release_unused = true;
+    }
+
     /// Returns `true` if there are no assets in this collection.
     pub fn is_empty(&self) -> bool {
         self.dense_storage.is_empty() && self.hash_map.is_empty()
@@ -535,30 +576,88 @@ impl<A: Asset> Assets<A> {
     }
 
     /// A system that synchronizes the state of assets in this collection with the [`AssetServer`]. This manages
-    /// [`Handle`] drop events.
-    pub fn track_assets(mut assets: ResMut<Self>, asset_server: Res<AssetServer>) {
+    /// [`Handle`] drop events, and removes the unused assets according to the [`RetainPolicy`].
+    pub fn track_assets(
+        mut assets: ResMut<Self>,
+        asset_server: Res<AssetServer>,
+        usages: Res<AssetUsages<A>>,
+    ) {
         let assets = &mut *assets;
         // note that we must hold this lock for the entire duration of this function to ensure
         // that `asset_server.load` calls that occur during it block, which ensures that
         // re-loads are kicked off appropriately. This function must be "transactional" relative
         // to other asset info operations
         let mut infos = asset_server.data.infos.write();
+        let used = usages.end_frame();
         while let Ok(drop_event) = assets.handle_provider.drop_receiver.try_recv() {
             let id = drop_event.id.typed();
 
-            if drop_event.asset_server_managed {
-                let untyped_id = id.untyped();
+            if assets.retain_policy == RetainPolicy::default() && !used.contains_key(&id) {
+                assets.process_drop(&mut infos, id, drop_event.asset_server_managed);
+            } else {
+                assets
+                    .unused
+                    .entry(id)
+                    .or_default()
+                    .drops
+                    .push(drop_event.asset_server_managed);
+            }
+        }
 
-                // the process_handle_drop call checks whether new handles have been created since the drop event was fired, before removing the asset
-                if !infos.process_handle_drop(untyped_id) {
-                    // a new handle has been created, or the asset doesn't exist
-                    continue;
+        if assets.unused.is_empty() {
+            assets.release_unused = false;
+            return;
+        }
+        let release_unused = std::mem::take(&mut assets.release_unused);
+        let mut released = Vec::new();
+        for (id, unused) in &mut assets.unused {
+            if used.contains_key(id) {
+                unused.unused_frames = 0;
+            } else {
+                unused.unused_frames += 1;
+            }
+            let release = match assets.retain_policy {
+                RetainPolicy::WhileUsed { grace_frames } => {
+                    release_unused || unused.unused_frames > grace_frames
                 }
+                RetainPolicy::Manual => release_unused,
+                RetainPolicy::Always => false,
+            };
+            // the asset was removed manually
+            let removed = match id {
+                AssetId::Index { index, .. } => assets.dense_storage.get(*index).is_none(),
+                AssetId::Uuid { uuid } => !assets.hash_map.contains_key(uuid),
+            };
+            if release || removed {
+                released.push(*id);
+            }
+        }
+        for id in released {
+            let unused = assets.unused.remove(&id).unwrap();
+            for asset_server_managed in unused.drops {
+                assets.process_drop(&mut infos, id, asset_server_managed);
             }
+        }
+    }
 
-            assets.queued_events.push(AssetEvent::Unused { id });
-            assets.remove_dropped(id);
+    fn process_drop(
+        &mut self,
+        infos: &mut crate::server::AssetInfos,
+        id: AssetId<A>,
+        asset_server_managed: bool,
+    ) {
+        if asset_server_managed {
+            let untyped_id = id.untyped();
+
+            // the process_handle_drop call checks whether new handles have been created since the drop event was fired, before removing the asset
+            if !infos.process_handle_drop(untyped_id) {
+                // a new handle has been created, or the asset doesn't exist
+                return;
+            }
         }
+
+        self.queued_events.push(AssetEvent::Unused { id });
+        self.remove_dropped(id);
     }
 
     /// A system that applies accumulated asset change events to the [`Events`] resource.
@@ -0,0 +1,189 @@
+use crate::{AssetServer, RecursiveDependencyLoadState, UntypedAssetId};
+use bevy_ecs::prelude::*;
+
+/// A [`Resource`] holding the handles of assets loaded together, like all the assets needed by a
+/// level, with a single [`load_state`](AssetCollection::load_state).
+///
+/// This is usually derived, with the paths of the assets in the `asset` attribute of the fields:
+///
+/// ```
+/// # use bevy_asset::{AssetCollection, Asset, Handle, LoadedFolder};
+/// # use bevy_ecs::system::Resource;
+/// # use bevy_reflect::TypePath;
+/// # #[derive(Asset, TypePath)]
+/// # struct Image;
+/// # #[derive(Asset, TypePath)]
+/// # struct AudioSource;
+/// #[derive(AssetCollection, Resource)]
+/// struct GameAssets {
+///     #[asset(path = "textures/player.png")]
+///     player: Handle<Image>,
+///     #[asset(paths("sounds/jump.ogg", "sounds/land.ogg"))]
+///     sounds: Vec<Handle<AudioSource>>,
+///     #[asset(folder = "levels")]
+///     levels: Handle<LoadedFolder>,
+///     // the fields without the attribute are set to their default value
+///     jumps: u32,
+/// }
+/// ```
+///
+/// The collection is loaded at startup with [`AssetApp::init_asset_collection`](crate::AssetApp::init_asset_collection),
+/// and [`asset_collection_loaded`] can be used to run systems once it's loaded. With the
+/// `bevy_state` feature, `LoadingState` loads collections when entering a state, and moves to the
+/// next state once they are loaded.
+pub trait AssetCollection: Resource + Sized {
+    /// Starts loading the assets of the collection.
+    fn load(asset_server: &AssetServer) -> Self;
+
+    /// Calls `visit` with the id of each asset of the collection.
+    fn visit_handles(&self, visit: &mut impl FnMut(UntypedAssetId));
+
+    /// Returns the combined load state of the assets of the collection and of their dependencies.
+    ///
+    /// The collection is [`Failed`](RecursiveDependencyLoadState::Failed) if any of its assets
+    /// failed to load, and [`Loaded`](RecursiveDependencyLoadState::Loaded) once all of them are
+    /// loaded.
+    fn load_state(&self, asset_server: &AssetServer) -> RecursiveDependencyLoadState {
+        let mut loading = false;
+        let mut failed = false;
+        self.visit_handles(
+            &mut |id| match asset_server.recursive_dependency_load_state(id) {
+                RecursiveDependencyLoadState::Loaded => {}
+                RecursiveDependencyLoadState::Failed => failed = true,
+                RecursiveDependencyLoadState::NotLoaded | RecursiveDependencyLoadState::Loading => {
+                    loading = true;
+                }
+            },
+        );
+        if failed {
+            RecursiveDependencyLoadState::Failed
+        } else if loading {
+            RecursiveDependencyLoadState::Loading
+        } else {
+            RecursiveDependencyLoadState::Loaded
+        }
+    }
+}
+
+/// Starts loading the [`AssetCollection`] `C` and inserts it as a resource.
+pub fn load_asset_collection<C: AssetCollection>(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    commands.insert_resource(C::load(&asset_server));
+}
+
+/// A condition that is true once all the assets of the [`AssetCollection`] `C` are loaded with
+/// their dependencies.
+pub fn asset_collection_loaded<C: AssetCollection>(
+    collection: Option<Res<C>>,
+    asset_server: Res<AssetServer>,
+) -> bool {
+    collection.is_some_and(|collection| {
+        collection.load_state(&asset_server) == RecursiveDependencyLoadState::Loaded
+    })
+}
+
+#[cfg(feature = "bevy_state")]
+pub use loading_state::*;
+
+#[cfg(feature = "bevy_state")]
+mod loading_state {
+    use super::{asset_collection_loaded, load_asset_collection, AssetCollection};
+    use bevy_app::{App, Update};
+    use bevy_ecs::{prelude::*, system::RunSystemOnce};
+    use bevy_state::{
+        prelude::{in_state, NextState, OnEnter},
+        state::FreelyMutableState,
+    };
+
+    /// Loads [`AssetCollection`]s when entering the `loading` state, and moves to the `next` state
+    /// once they are all loaded, added with [`LoadingStateApp::add_loading_state`].
+    ///
+    /// ```
+    /// # use bevy_app::App;
+    /// # use bevy_asset::{AssetCollection, LoadingState, LoadingStateApp};
+    /// # use bevy_ecs::system::Resource;
+    /// # use bevy_state::prelude::States;
+    /// # #[derive(AssetCollection, Resource)]
+    /// # struct GameAssets {}
+    /// #[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
+    /// enum GameState {
+    ///     #[default]
+    ///     Loading,
+    ///     Playing,
+    /// }
+    ///
+    /// # let mut app = App::new();
+    /// app.add_loading_state(
+    ///     LoadingState::new(GameState::Loading, GameState::Playing)
+    ///         .load_collection::<GameAssets>(),
+    /// );
+    /// ```
+    ///
+    /// If an asset fails to load, the state is not changed.
+    pub struct LoadingState<S: FreelyMutableState> {
+        loading: S,
+        next: S,
+        loads: Vec<fn(&mut World)>,
+        checks: Vec<fn(&mut World) -> bool>,
+    }
+
+    impl<S: FreelyMutableState> LoadingState<S> {
+        /// Creates a [`LoadingState`] moving from `loading` to `next`.
+        pub fn new(loading: S, next: S) -> Self {
+            Self {
+                loading,
+                next,
+                loads: Vec::new(),
+                checks: Vec::new(),
+            }
+        }
+
+        /// Loads the [`AssetCollection`] `C` when entering the loading state.
+        pub fn load_collection<C: AssetCollection>(mut self) -> Self {
+            self.loads
+                .push(|world| world.run_system_once(load_asset_collection::<C>));
+            self.checks
+                .push(|world| world.run_system_once(asset_collection_loaded::<C>));
+            self
+        }
+    }
+
+    /// Adds [`LoadingState`]s to an [`App`].
+    pub trait LoadingStateApp {
+        /// Adds the given [`LoadingState`]. The state `S` must be initialized.
+        fn add_loading_state<S: FreelyMutableState>(
+            &mut self,
+            loading_state: LoadingState<S>,
+        ) -> &mut Self;
+    }
+
+    impl LoadingStateApp for App {
+        fn add_loading_state<S: FreelyMutableState>(
+            &mut self,
+            loading_state: LoadingState<S>,
+        ) -> &mut Self {
+            let LoadingState {
+                loading,
+                next,
+                loads,
+                checks,
+            } = loading_state;
+            self.add_systems(OnEnter(loading.clone()), move |world: &mut World| {
+                for load in &loads {
+                    load(world);
+                }
+            })
+            .add_systems(
+                Update,
+                (move |world: &mut World| {
+                    if checks.iter().all(|check| check(world)) {
+                        world.resource_mut::<NextState<S>>().set(next.clone());
+                    }
+                })
+                .run_if(in_state(loading)),
+            )
+        }
+    }
+}
@@ -1,5 +1,6 @@
-use crate::{Asset, AssetId, AssetLoadError, AssetPath, UntypedAssetId};
+use crate::{Asset, AssetId, AssetLoadError, AssetPath, AssetSaveError, UntypedAssetId};
 use bevy_ecs::event::Event;
+use bevy_utils::CowArc;
 use std::fmt::Debug;
 
 /// An event emitted when a specific [`Asset`] fails to load.
@@ -41,6 +42,49 @@ impl<A: Asset> From<&AssetLoadFailedEvent<A>> for UntypedAssetLoadFailedEvent {
     }
 }
 
+/// An event emitted when an asset was saved with [`AssetServer::save`](crate::AssetServer::save).
+#[derive(Event, Clone, Debug)]
+pub struct AssetSaved {
+    pub id: UntypedAssetId,
+    /// The path the asset was saved to.
+    pub path: AssetPath<'static>,
+}
+
+/// An event emitted when an asset failed to save with [`AssetServer::save`](crate::AssetServer::save).
+#[derive(Event, Clone, Debug)]
+pub struct AssetSaveFailedEvent {
+    pub id: UntypedAssetId,
+    /// The path the asset was saved to.
+    pub path: AssetPath<'static>,
+    /// Why the asset failed to save.
+    pub error: AssetSaveError,
+}
+
+/// An event emitted when the file of an asset was reloaded, because a change was detected while
+/// watching for changes or with [`AssetServer::reload`](crate::AssetServer::reload).
+///
+/// [`AssetEvent::Modified`] is sent for the reloaded assets, and for all the assets holding handles
+/// to them, directly or through other assets, so that the data derived from them is rebuilt. For
+/// example a material using an image of a reloaded glTF file is modified too.
+#[derive(Event, Clone, Debug)]
+pub struct AssetReloaded {
+    /// The path of the reloaded file, without label.
+    pub path: AssetPath<'static>,
+    /// The labels of the labeled assets loaded from the file.
+    pub labels: Vec<CowArc<'static, str>>,
+    /// The assets holding handles to the reloaded assets, directly or through other assets.
+    ///
+    /// This is only tracked while watching for changes, and is empty otherwise.
+    pub dependants: Vec<UntypedAssetId>,
+}
+
+impl AssetReloaded {
+    /// Returns `true` if the labeled asset with the given `label` was reloaded.
+    pub fn contains_label(&self, label: &str) -> bool {
+        self.labels.iter().any(|reloaded| &**reloaded == label)
+    }
+}
+
 /// Events that occur for a specific loaded [`Asset`], such as "value changed" events and "dependency" events.
 #[derive(Event)]
 pub enum AssetEvent<A: Asset> {
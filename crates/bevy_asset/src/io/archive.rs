@@ -0,0 +1,545 @@
+//! An [`AssetReader`] for bundled asset archives.
+//!
+//! An [`Archive`] packs the assets (and their `.meta` files) of a directory into a single file, with
+//! an index of the paths stored at the end of the file. The assets can be compressed individually
+//! with [`ArchiveCompression`]. Archives are built with an [`ArchiveWriter`], usually as part of
+//! the build of a game.
+//!
+//! An [`ArchiveAssetReader`] mounts several archives with a priority, and reads each asset from the
+//! archive with the highest priority containing it. This makes it possible to ship patches and mods
+//! as archives only containing the changed assets, overriding the assets of the base game:
+//!
+//! ```no_run
+//! # use bevy_app::App;
+//! # use bevy_asset::{AssetApp, io::{AssetSourceId, AssetSource, archive::{Archive, ArchiveAssetReader}}};
+//! # let mut app = App::new();
+//! let reader = ArchiveAssetReader::default();
+//! reader.mount(Archive::open("game.pak").unwrap(), 0);
+//! reader.mount(Archive::open("patch_1.pak").unwrap(), 1);
+//! reader.mount(Archive::open("mods/better_trees.pak").unwrap(), 10);
+//!
+//! // This must be registered before `AssetPlugin` to replace the default source.
+//! app.register_asset_source(
+//!     AssetSourceId::Default,
+//!     AssetSource::build().with_reader(move || Box::new(reader.clone())),
+//! );
+//! ```
+
+use crate::io::{get_meta_path, AssetReader, AssetReaderError, PathStream, Reader, VecReader};
+use bevy_utils::{HashMap, HashSet};
+use parking_lot::RwLock;
+use std::{
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+use thiserror::Error;
+
+const MAGIC: [u8; 4] = *b"BPAK";
+const VERSION: u32 = 1;
+/// The magic bytes, the version, the offset of the index and the number of entries.
+const HEADER_SIZE: usize = 4 + 4 + 8 + 4;
+
+/// How an asset is compressed in an [`Archive`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveCompression {
+    /// The asset is stored as is. This is best for assets that are already compressed, like
+    /// images in compressed GPU formats or audio.
+    #[default]
+    None,
+    /// The asset is compressed with LZ4, which is fast to decompress.
+    Lz4,
+}
+
+impl ArchiveCompression {
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::None),
+            1 => Some(Self::Lz4),
+            _ => None,
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            Self::None => 0,
+            Self::Lz4 => 1,
+        }
+    }
+}
+
+/// Errors that occur while opening an [`Archive`].
+#[derive(Error, Debug)]
+pub enum ArchiveError {
+    /// Encountered an I/O error while reading the archive.
+    #[error("encountered an io error while reading an asset archive: {0}")]
+    Io(#[from] std::io::Error),
+    /// The file doesn't start with the magic bytes of an asset archive.
+    #[error("the file is not an asset archive")]
+    InvalidMagic,
+    /// The archive was written with an unsupported version of the format.
+    #[error("the asset archive has the unsupported version {0}, expected {VERSION}")]
+    UnsupportedVersion(u32),
+    /// The index of the archive is truncated or invalid.
+    #[error("the index of the asset archive is corrupted")]
+    CorruptedIndex,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ArchiveEntry {
+    offset: u64,
+    stored_size: u64,
+    size: u64,
+    compression: ArchiveCompression,
+}
+
+#[derive(Debug)]
+enum ArchiveData {
+    Memory(Arc<[u8]>),
+    #[cfg(not(target_arch = "wasm32"))]
+    File(PathBuf),
+}
+
+/// A bundle of assets, read by an [`ArchiveAssetReader`].
+///
+/// Only the index is loaded when the archive is opened, the assets are read when they are loaded.
+#[derive(Debug)]
+pub struct Archive {
+    data: ArchiveData,
+    entries: HashMap<PathBuf, ArchiveEntry>,
+}
+
+impl Archive {
+    /// Opens the archive stored in the file at `path`, reading its index.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, ArchiveError> {
+        use std::io::{Read, Seek, SeekFrom};
+
+        let path = path.as_ref();
+        let mut file = std::fs::File::open(path)?;
+        let mut header = [0; HEADER_SIZE];
+        file.read_exact(&mut header)?;
+        let (index_offset, entry_count) = parse_header(&header)?;
+        file.seek(SeekFrom::Start(index_offset))?;
+        let mut index = Vec::new();
+        file.read_to_end(&mut index)?;
+
+        Ok(Self {
+            entries: parse_index(&index, entry_count)?,
+            data: ArchiveData::File(path.to_path_buf()),
+        })
+    }
+
+    /// Reads the archive from `bytes`, for example an archive embedded in the executable with
+    /// [`include_bytes`] or downloaded on the web.
+    pub fn from_bytes(bytes: impl Into<Arc<[u8]>>) -> Result<Self, ArchiveError> {
+        let bytes = bytes.into();
+        let header = bytes
+            .get(..HEADER_SIZE)
+            .ok_or(ArchiveError::CorruptedIndex)?;
+        let (index_offset, entry_count) = parse_header(header)?;
+        let index = usize::try_from(index_offset)
+            .ok()
+            .and_then(|offset| bytes.get(offset..))
+            .ok_or(ArchiveError::CorruptedIndex)?;
+
+        Ok(Self {
+            entries: parse_index(index, entry_count)?,
+            data: ArchiveData::Memory(bytes),
+        })
+    }
+
+    /// Returns the number of files in the archive, including the `.meta` files.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the archive doesn't contain any file.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns `true` if the archive contains a file at `path`.
+    pub fn contains(&self, path: &Path) -> bool {
+        self.entries.contains_key(path)
+    }
+
+    /// Returns the paths of the files in the archive, in no particular order.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.entries.keys().map(PathBuf::as_path)
+    }
+
+    async fn read_entry(&self, entry: ArchiveEntry) -> Result<Vec<u8>, AssetReaderError> {
+        let stored = match &self.data {
+            ArchiveData::Memory(bytes) => usize::try_from(entry.offset)
+                .ok()
+                .zip(usize::try_from(entry.offset + entry.stored_size).ok())
+                .and_then(|(start, end)| bytes.get(start..end))
+                .ok_or_else(|| invalid_data("the asset is out of the bounds of the archive"))?
+                .to_vec(),
+            #[cfg(not(target_arch = "wasm32"))]
+            ArchiveData::File(path) => {
+                use futures_lite::{AsyncReadExt, AsyncSeekExt};
+
+                let mut file = async_fs::File::open(path).await?;
+                file.seek(std::io::SeekFrom::Start(entry.offset)).await?;
+                let mut stored = vec![0; entry.stored_size as usize];
+                file.read_exact(&mut stored).await?;
+                stored
+            }
+        };
+
+        match entry.compression {
+            ArchiveCompression::None => Ok(stored),
+            ArchiveCompression::Lz4 => lz4_flex::block::decompress(&stored, entry.size as usize)
+                .map_err(|err| invalid_data(&err.to_string())),
+        }
+    }
+}
+
+fn invalid_data(message: &str) -> AssetReaderError {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message.to_string()).into()
+}
+
+fn parse_header(header: &[u8]) -> Result<(u64, u32), ArchiveError> {
+    if header[0..4] != MAGIC {
+        return Err(ArchiveError::InvalidMagic);
+    }
+    let version = u32::from_le_bytes(header[4..8].try_into().unwrap());
+    if version != VERSION {
+        return Err(ArchiveError::UnsupportedVersion(version));
+    }
+    let index_offset = u64::from_le_bytes(header[8..16].try_into().unwrap());
+    let entry_count = u32::from_le_bytes(header[16..20].try_into().unwrap());
+    Ok((index_offset, entry_count))
+}
+
+fn parse_index(
+    mut index: &[u8],
+    entry_count: u32,
+) -> Result<HashMap<PathBuf, ArchiveEntry>, ArchiveError> {
+    fn take<'a>(index: &mut &'a [u8], len: usize) -> Result<&'a [u8], ArchiveError> {
+        if index.len() < len {
+            return Err(ArchiveError::CorruptedIndex);
+        }
+        let (bytes, rest) = index.split_at(len);
+        *index = rest;
+        Ok(bytes)
+    }
+    fn take_u64(index: &mut &[u8]) -> Result<u64, ArchiveError> {
+        Ok(u64::from_le_bytes(take(index, 8)?.try_into().unwrap()))
+    }
+
+    let mut entries = HashMap::with_capacity(entry_count as usize);
+    for _ in 0..entry_count {
+        let path_len = u32::from_le_bytes(take(&mut index, 4)?.try_into().unwrap());
+        let path = std::str::from_utf8(take(&mut index, path_len as usize)?)
+            .map_err(|_| ArchiveError::CorruptedIndex)?;
+        let entry = ArchiveEntry {
+            offset: take_u64(&mut index)?,
+            stored_size: take_u64(&mut index)?,
+            size: take_u64(&mut index)?,
+            compression: ArchiveCompression::from_u8(take(&mut index, 1)?[0])
+                .ok_or(ArchiveError::CorruptedIndex)?,
+        };
+        entries.insert(PathBuf::from(path), entry);
+    }
+    Ok(entries)
+}
+
+/// Builds an [`Archive`].
+///
+/// ```
+/// # use bevy_asset::io::archive::{Archive, ArchiveCompression, ArchiveWriter};
+/// let mut writer = ArchiveWriter::new();
+/// writer.add("levels/forest.level", b"trees", ArchiveCompression::Lz4);
+/// writer.add("levels/forest.level.meta", b"(meta_format_version: \"1.0\")", ArchiveCompression::None);
+/// let archive = Archive::from_bytes(writer.finish()).unwrap();
+/// assert_eq!(archive.len(), 2);
+/// ```
+pub struct ArchiveWriter {
+    data: Vec<u8>,
+    entries: Vec<(String, ArchiveEntry)>,
+}
+
+impl Default for ArchiveWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ArchiveWriter {
+    /// Creates an empty archive.
+    pub fn new() -> Self {
+        Self {
+            data: vec![0; HEADER_SIZE],
+            entries: Vec::new(),
+        }
+    }
+
+    /// Adds the file at `path`, relative to the root of the asset source, with the given
+    /// `compression`. The `.meta` file of an asset is added like any other file, at the path of
+    /// the asset with `.meta` appended.
+    ///
+    /// If a file was already added at `path`, it's replaced.
+    pub fn add(
+        &mut self,
+        path: impl AsRef<Path>,
+        bytes: &[u8],
+        compression: ArchiveCompression,
+    ) -> &mut Self {
+        let path = path
+            .as_ref()
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => Some(name.to_string_lossy()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+        let stored = match compression {
+            ArchiveCompression::None => bytes.to_vec(),
+            ArchiveCompression::Lz4 => lz4_flex::block::compress(bytes),
+        };
+        let entry = ArchiveEntry {
+            offset: self.data.len() as u64,
+            stored_size: stored.len() as u64,
+            size: bytes.len() as u64,
+            compression,
+        };
+        self.data.extend_from_slice(&stored);
+        self.entries.retain(|(other, _)| *other != path);
+        self.entries.push((path, entry));
+        self
+    }
+
+    /// Writes the index and returns the bytes of the archive.
+    pub fn finish(self) -> Vec<u8> {
+        let Self { mut data, entries } = self;
+        let index_offset = data.len() as u64;
+        for (path, entry) in &entries {
+            data.extend_from_slice(&(path.len() as u32).to_le_bytes());
+            data.extend_from_slice(path.as_bytes());
+            data.extend_from_slice(&entry.offset.to_le_bytes());
+            data.extend_from_slice(&entry.stored_size.to_le_bytes());
+            data.extend_from_slice(&entry.size.to_le_bytes());
+            data.push(entry.compression.to_u8());
+        }
+
+        data[0..4].copy_from_slice(&MAGIC);
+        data[4..8].copy_from_slice(&VERSION.to_le_bytes());
+        data[8..16].copy_from_slice(&index_offset.to_le_bytes());
+        data[16..20].copy_from_slice(&(entries.len() as u32).to_le_bytes());
+        data
+    }
+}
+
+struct MountedArchive {
+    archive: Arc<Archive>,
+    priority: i32,
+}
+
+#[derive(Default)]
+struct MountedArchives {
+    /// Sorted from the highest priority to the lowest.
+    archives: Vec<MountedArchive>,
+    /// The index in `archives` of the archive each file is read from.
+    files: HashMap<PathBuf, usize>,
+    directories: HashMap<PathBuf, HashSet<PathBuf>>,
+}
+
+impl MountedArchives {
+    fn rebuild_index(&mut self) {
+        self.files.clear();
+        self.directories.clear();
+        for (index, mounted) in self.archives.iter().enumerate() {
+            for path in mounted.archive.paths() {
+                if self.files.contains_key(path) {
+                    continue;
+                }
+                self.files.insert(path.to_path_buf(), index);
+                let mut child = path;
+                while let Some(parent) = child.parent() {
+                    let children = self.directories.entry(parent.to_path_buf()).or_default();
+                    if !children.insert(child.to_path_buf()) {
+                        break;
+                    }
+                    child = parent;
+                }
+            }
+        }
+    }
+}
+
+/// An [`AssetReader`] reading the assets from the [`Archive`]s mounted on it.
+///
+/// Each asset is read from the archive with the highest priority containing it. The archives
+/// mounted last win among the archives with the same priority. Lookups don't depend on the number
+/// of mounted archives, since their indices are merged when they're mounted.
+///
+/// This is cheap to clone, the clones share the mounted archives.
+#[derive(Clone, Default)]
+pub struct ArchiveAssetReader {
+    mounted: Arc<RwLock<MountedArchives>>,
+}
+
+impl ArchiveAssetReader {
+    /// Mounts `archive` with the given `priority`. Its files override the files at the same paths
+    /// of the archives with a lower priority.
+    ///
+    /// Archives can be mounted while the app is running, for example when enabling mods, but the
+    /// assets that were already loaded are not reloaded.
+    pub fn mount(&self, archive: Archive, priority: i32) {
+        let mut mounted = self.mounted.write();
+        let index = mounted
+            .archives
+            .iter()
+            .position(|mounted| mounted.priority <= priority)
+            .unwrap_or(mounted.archives.len());
+        mounted.archives.insert(
+            index,
+            MountedArchive {
+                archive: Arc::new(archive),
+                priority,
+            },
+        );
+        mounted.rebuild_index();
+    }
+
+    /// Mounts `archive` with the given `priority`, see [`ArchiveAssetReader::mount`].
+    pub fn with_archive(self, archive: Archive, priority: i32) -> Self {
+        self.mount(archive, priority);
+        self
+    }
+
+    /// Returns the number of mounted archives.
+    pub fn archive_count(&self) -> usize {
+        self.mounted.read().archives.len()
+    }
+
+    async fn read_file(&self, path: &Path) -> Result<Vec<u8>, AssetReaderError> {
+        let (archive, entry) = {
+            let mounted = self.mounted.read();
+            let archive = mounted
+                .files
+                .get(path)
+                .map(|index| mounted.archives[*index].archive.clone())
+                .ok_or_else(|| AssetReaderError::NotFound(path.to_path_buf()))?;
+            let entry = archive.entries[path];
+            (archive, entry)
+        };
+        archive.read_entry(entry).await
+    }
+}
+
+impl AssetReader for ArchiveAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<Box<Reader<'a>>, AssetReaderError> {
+        let bytes = self.read_file(path).await?;
+        let reader: Box<Reader> = Box::new(VecReader::new(bytes));
+        Ok(reader)
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<Box<Reader<'a>>, AssetReaderError> {
+        let bytes = self.read_file(&get_meta_path(path)).await?;
+        let reader: Box<Reader> = Box::new(VecReader::new(bytes));
+        Ok(reader)
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        let mounted = self.mounted.read();
+        let children = mounted
+            .directories
+            .get(path)
+            .ok_or_else(|| AssetReaderError::NotFound(path.to_path_buf()))?
+            .iter()
+            // the meta files are read with the assets
+            .filter(|child| {
+                child
+                    .extension()
+                    .map_or(true, |extension| extension != "meta")
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        let stream: Box<PathStream> = Box::new(futures_lite::stream::iter(children));
+        Ok(stream)
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        Ok(self.mounted.read().directories.contains_key(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_lite::{future::block_on, AsyncReadExt, StreamExt};
+
+    fn read(reader: &ArchiveAssetReader, path: &str) -> Option<Vec<u8>> {
+        block_on(async {
+            let mut file = reader.read(Path::new(path)).await.ok()?;
+            let mut bytes = Vec::new();
+            file.read_to_end(&mut bytes).await.unwrap();
+            Some(bytes)
+        })
+    }
+
+    #[test]
+    fn mounted_archives() {
+        let mut base = ArchiveWriter::new();
+        base.add("a.txt", b"base a", ArchiveCompression::None)
+            .add("dir/b.txt", b"base b", ArchiveCompression::Lz4)
+            .add("dir/b.txt.meta", b"base b meta", ArchiveCompression::None);
+        let mut patch = ArchiveWriter::new();
+        patch
+            .add("dir/b.txt", b"patched b", ArchiveCompression::Lz4)
+            .add("dir/sub/c.txt", b"patched c", ArchiveCompression::None);
+
+        let reader = ArchiveAssetReader::default();
+        // the priority decides the overrides, not the order of the mounts
+        reader.mount(Archive::from_bytes(patch.finish()).unwrap(), 1);
+        reader.mount(Archive::from_bytes(base.finish()).unwrap(), 0);
+
+        assert_eq!(read(&reader, "a.txt").unwrap(), b"base a");
+        assert_eq!(read(&reader, "dir/b.txt").unwrap(), b"patched b");
+        assert_eq!(read(&reader, "dir/sub/c.txt").unwrap(), b"patched c");
+        assert!(read(&reader, "missing.txt").is_none());
+
+        let meta = block_on(reader.read_meta_bytes(Path::new("dir/b.txt"))).unwrap();
+        assert_eq!(meta, b"base b meta");
+
+        assert!(block_on(reader.is_directory(Path::new("dir/sub"))).unwrap());
+        assert!(!block_on(reader.is_directory(Path::new("a.txt"))).unwrap());
+        let mut children = block_on(async {
+            reader
+                .read_directory(Path::new("dir"))
+                .await
+                .unwrap()
+                .collect::<Vec<_>>()
+                .await
+        });
+        children.sort();
+        assert_eq!(
+            children,
+            vec![PathBuf::from("dir/b.txt"), PathBuf::from("dir/sub")]
+        );
+    }
+
+    #[test]
+    fn invalid_archive() {
+        assert!(matches!(
+            Archive::from_bytes(vec![0; HEADER_SIZE]),
+            Err(ArchiveError::InvalidMagic)
+        ));
+
+        let mut writer = ArchiveWriter::new();
+        writer.add("a.txt", b"a", ArchiveCompression::None);
+        let mut bytes = writer.finish();
+        bytes.truncate(bytes.len() - 4);
+        assert!(matches!(
+            Archive::from_bytes(bytes),
+            Err(ArchiveError::CorruptedIndex)
+        ));
+    }
+}
@@ -0,0 +1,355 @@
+//! Loads assets served over HTTP(S), see [`HttpAssetPlugin`].
+
+use crate::io::{
+    get_meta_path, AssetReader, AssetReaderError, AssetSource, PathStream, Reader, VecReader,
+};
+use crate::AssetApp;
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::prelude::*;
+#[cfg(not(target_arch = "wasm32"))]
+use bevy_utils::tracing::warn;
+use crossbeam_channel::{Receiver, Sender};
+use std::path::{Path, PathBuf};
+
+/// Adds the `http` and `https` [`AssetSource`]s, so that assets can be loaded from URLs with
+/// [`AssetServer::load`](crate::AssetServer::load):
+///
+/// ```no_run
+/// # use bevy_asset::{Asset, AssetServer, Handle};
+/// # use bevy_reflect::TypePath;
+/// # #[derive(Asset, TypePath)]
+/// # struct Model;
+/// # fn system(asset_server: bevy_ecs::system::Res<AssetServer>) {
+/// let model: Handle<Model> = asset_server.load("https://example.com/models/model.glb");
+/// # }
+/// ```
+///
+/// On native platforms, the responses are kept in the [`cache_path`](Self::cache_path) directory and
+/// revalidated with their `ETag` and `Last-Modified` headers, so unchanged assets are not downloaded
+/// again. The cached copy is also used when the server can't be reached. On the web, the browser
+/// cache is used instead.
+///
+/// The progress of the downloads is sent as [`HttpAssetProgress`] events.
+///
+/// This must be added before [`AssetPlugin`](crate::AssetPlugin), like the other asset sources.
+pub struct HttpAssetPlugin {
+    /// The directory the responses are cached in, or `None` to always download the assets.
+    ///
+    /// Defaults to `http_cache` in the [base path](crate::io::file::FileAssetReader::get_base_path)
+    /// on native platforms. This is ignored on the web.
+    pub cache_path: Option<PathBuf>,
+}
+
+impl Default for HttpAssetPlugin {
+    fn default() -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        let cache_path = Some(crate::io::file::FileAssetReader::get_base_path().join("http_cache"));
+        #[cfg(target_arch = "wasm32")]
+        let cache_path = None;
+        Self { cache_path }
+    }
+}
+
+impl Plugin for HttpAssetPlugin {
+    fn build(&self, app: &mut App) {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        for scheme in ["http", "https"] {
+            let reader = HttpAssetReader {
+                scheme,
+                cache_path: self.cache_path.clone(),
+                progress: sender.clone(),
+            };
+            let processed_reader = reader.clone();
+            app.register_asset_source(
+                scheme,
+                AssetSource::build()
+                    .with_reader(move || Box::new(reader.clone()))
+                    .with_processed_reader(move || Box::new(processed_reader.clone())),
+            );
+        }
+        app.add_event::<HttpAssetProgress>()
+            .insert_resource(HttpAssetProgressReceiver(receiver))
+            .add_systems(PreUpdate, send_http_asset_progress);
+    }
+}
+
+/// The progress of a download started by the `http` and `https` asset sources of
+/// [`HttpAssetPlugin`].
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub enum HttpAssetProgress {
+    /// Part of the response was received.
+    Downloading {
+        /// The URL of the asset.
+        url: String,
+        /// The number of bytes received so far.
+        downloaded: u64,
+        /// The size of the response, if the server sent it.
+        total: Option<u64>,
+    },
+    /// The response was fully received.
+    Finished {
+        /// The URL of the asset.
+        url: String,
+        /// The size of the response.
+        size: u64,
+        /// Whether the response was read from the cache, because the asset didn't change or the
+        /// server couldn't be reached. This is always `false` on the web.
+        cached: bool,
+    },
+}
+
+#[derive(Resource)]
+struct HttpAssetProgressReceiver(Receiver<HttpAssetProgress>);
+
+fn send_http_asset_progress(
+    receiver: Res<HttpAssetProgressReceiver>,
+    mut events: EventWriter<HttpAssetProgress>,
+) {
+    events.send_batch(receiver.0.try_iter());
+}
+
+/// Reads the assets at the URLs made from the `scheme` and the asset path, added by
+/// [`HttpAssetPlugin`].
+#[derive(Clone)]
+pub struct HttpAssetReader {
+    scheme: &'static str,
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    cache_path: Option<PathBuf>,
+    progress: Sender<HttpAssetProgress>,
+}
+
+impl HttpAssetReader {
+    fn url(&self, path: &Path) -> String {
+        format!(
+            "{}://{}",
+            self.scheme,
+            path.to_string_lossy().replace('\\', "/")
+        )
+    }
+
+    fn send_progress(&self, progress: HttpAssetProgress) {
+        // The receiver is only dropped with the app
+        let _ = self.progress.send(progress);
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    async fn fetch(&self, path: &Path) -> Result<Vec<u8>, AssetReaderError> {
+        let url = self.url(path);
+        let bytes = crate::io::wasm::fetch(PathBuf::from(&url)).await?;
+        self.send_progress(HttpAssetProgress::Finished {
+            url,
+            size: bytes.len() as u64,
+            cached: false,
+        });
+        Ok(bytes)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn fetch(&self, path: &Path) -> Result<Vec<u8>, AssetReaderError> {
+        let reader = self.clone();
+        let url = self.url(path);
+        let path = path.to_owned();
+        // ureq is blocking, so the request runs on a separate thread
+        blocking::unblock(move || reader.fetch_blocking(url, path)).await
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn fetch_blocking(&self, url: String, path: PathBuf) -> Result<Vec<u8>, AssetReaderError> {
+        let cached = self
+            .cache_path
+            .as_deref()
+            .map(|cache_path| CachedResponse::new(cache_path, &url))
+            .filter(CachedResponse::exists);
+
+        let mut request = ureq::get(&url);
+        if let Some(validators) = cached.as_ref().and_then(CachedResponse::read_validators) {
+            if let Some(etag) = &validators.etag {
+                request = request.set("If-None-Match", etag);
+            }
+            if let Some(last_modified) = &validators.last_modified {
+                request = request.set("If-Modified-Since", last_modified);
+            }
+        }
+
+        match request.call() {
+            Ok(response) if response.status() == 304 => {
+                // the cached response exists if validators were sent
+                let bytes = cached.unwrap().read()?;
+                self.send_progress(HttpAssetProgress::Finished {
+                    url,
+                    size: bytes.len() as u64,
+                    cached: true,
+                });
+                Ok(bytes)
+            }
+            Ok(response) => {
+                let validators = Validators {
+                    etag: response.header("ETag").map(ToString::to_string),
+                    last_modified: response.header("Last-Modified").map(ToString::to_string),
+                };
+                let bytes = self.read_body(&url, response)?;
+                if let Some(cache_path) = &self.cache_path {
+                    if validators.etag.is_some() || validators.last_modified.is_some() {
+                        CachedResponse::new(cache_path, &url).write(&bytes, &validators);
+                    }
+                }
+                self.send_progress(HttpAssetProgress::Finished {
+                    url,
+                    size: bytes.len() as u64,
+                    cached: false,
+                });
+                Ok(bytes)
+            }
+            Err(ureq::Error::Status(404, _)) => Err(AssetReaderError::NotFound(path)),
+            Err(ureq::Error::Status(status, _)) => Err(AssetReaderError::HttpError(status)),
+            Err(ureq::Error::Transport(error)) => match cached {
+                Some(cached) => {
+                    warn!("Failed to request {url}, using the cached response: {error}");
+                    let bytes = cached.read()?;
+                    self.send_progress(HttpAssetProgress::Finished {
+                        url,
+                        size: bytes.len() as u64,
+                        cached: true,
+                    });
+                    Ok(bytes)
+                }
+                None => Err(std::io::Error::new(std::io::ErrorKind::Other, error).into()),
+            },
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn read_body(&self, url: &str, response: ureq::Response) -> Result<Vec<u8>, AssetReaderError> {
+        use std::io::Read;
+
+        const CHUNK_SIZE: usize = 64 * 1024;
+
+        let total = response
+            .header("Content-Length")
+            .and_then(|length| length.parse::<u64>().ok());
+        let mut body = response.into_reader();
+        let mut bytes = Vec::new();
+        let mut chunk = vec![0; CHUNK_SIZE];
+        loop {
+            let read = body.read(&mut chunk)?;
+            if read == 0 {
+                return Ok(bytes);
+            }
+            bytes.extend_from_slice(&chunk[..read]);
+            self.send_progress(HttpAssetProgress::Downloading {
+                url: url.to_string(),
+                downloaded: bytes.len() as u64,
+                total,
+            });
+        }
+    }
+}
+
+impl AssetReader for HttpAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<Box<Reader<'a>>, AssetReaderError> {
+        let bytes = self.fetch(path).await?;
+        let reader: Box<Reader> = Box::new(VecReader::new(bytes));
+        Ok(reader)
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<Box<Reader<'a>>, AssetReaderError> {
+        let bytes = self.fetch(&get_meta_path(path)).await?;
+        let reader: Box<Reader> = Box::new(VecReader::new(bytes));
+        Ok(reader)
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        // directories can't be listed over HTTP
+        Err(AssetReaderError::NotFound(path.to_owned()))
+    }
+
+    async fn is_directory<'a>(&'a self, _path: &'a Path) -> Result<bool, AssetReaderError> {
+        Ok(false)
+    }
+}
+
+/// The validators of a cached response, sent back to check if the asset changed.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, PartialEq, Eq)]
+struct Validators {
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+/// A response in the cache, stored in a file named after the hash of its URL, next to a file with
+/// its [`Validators`].
+#[cfg(not(target_arch = "wasm32"))]
+struct CachedResponse {
+    path: PathBuf,
+    validators_path: PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl CachedResponse {
+    fn new(cache_path: &Path, url: &str) -> Self {
+        let hash = blake3::hash(url.as_bytes()).to_hex();
+        Self {
+            path: cache_path.join(hash.as_str()),
+            validators_path: cache_path.join(format!("{hash}.ron")),
+        }
+    }
+
+    fn exists(&self) -> bool {
+        self.path.is_file()
+    }
+
+    fn read(&self) -> Result<Vec<u8>, AssetReaderError> {
+        Ok(std::fs::read(&self.path)?)
+    }
+
+    fn read_validators(&self) -> Option<Validators> {
+        let validators = std::fs::read_to_string(&self.validators_path).ok()?;
+        ron::from_str(&validators).ok()
+    }
+
+    /// Stores the response, logging the errors as the asset can still be used.
+    fn write(&self, bytes: &[u8], validators: &Validators) {
+        let result = (|| {
+            if let Some(parent) = self.path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&self.path, bytes)?;
+            let validators = ron::to_string(validators)
+                .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+            std::fs::write(&self.validators_path, validators)
+        })();
+        if let Err(err) = result {
+            warn!("Failed to cache the response at {:?}: {err}", self.path);
+        }
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::{CachedResponse, Validators};
+
+    #[test]
+    fn cached_response() {
+        let cache_path = std::env::temp_dir().join("bevy_asset_http_cache_test");
+        let url = "https://example.com/models/model.glb";
+        let cached = CachedResponse::new(&cache_path, url);
+        assert_ne!(
+            cached.path,
+            CachedResponse::new(&cache_path, "http://example.com/models/model.glb").path
+        );
+
+        let validators = Validators {
+            etag: Some("\"abc\"".to_string()),
+            last_modified: None,
+        };
+        cached.write(&[1, 2, 3], &validators);
+        assert!(cached.exists());
+        assert_eq!(cached.read().unwrap(), vec![1, 2, 3]);
+        assert_eq!(cached.read_validators(), Some(validators));
+
+        let _ = std::fs::remove_dir_all(cache_path);
+    }
+}
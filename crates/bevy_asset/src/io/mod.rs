@@ -7,10 +7,14 @@ compile_error!(
 
 #[cfg(target_os = "android")]
 pub mod android;
+#[cfg(feature = "asset_archive")]
+pub mod archive;
 pub mod embedded;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod file;
 pub mod gated;
+#[cfg(feature = "http_source")]
+pub mod http;
 pub mod memory;
 pub mod processor_gated;
 #[cfg(target_arch = "wasm32")]
@@ -50,39 +50,44 @@ fn js_value_to_err(context: &str) -> impl FnOnce(JsValue) -> std::io::Error + '_
     }
 }
 
+/// Fetches the bytes at the given `path` with the JS `fetch` API.
+pub(crate) async fn fetch(path: PathBuf) -> Result<Vec<u8>, AssetReaderError> {
+    // The JS global scope includes a self-reference via a specialising name, which can be used to determine the type of global context available.
+    let global: Global = js_sys::global().unchecked_into();
+    let promise = if !global.window().is_undefined() {
+        let window: web_sys::Window = global.unchecked_into();
+        window.fetch_with_str(path.to_str().unwrap())
+    } else if !global.worker().is_undefined() {
+        let worker: web_sys::WorkerGlobalScope = global.unchecked_into();
+        worker.fetch_with_str(path.to_str().unwrap())
+    } else {
+        let error = std::io::Error::new(
+            std::io::ErrorKind::Other,
+            "Unsupported JavaScript global context",
+        );
+        return Err(AssetReaderError::Io(error.into()));
+    };
+    let resp_value = JsFuture::from(promise)
+        .await
+        .map_err(js_value_to_err("fetch path"))?;
+    let resp = resp_value
+        .dyn_into::<Response>()
+        .map_err(js_value_to_err("convert fetch to Response"))?;
+    match resp.status() {
+        200 => {
+            let data = JsFuture::from(resp.array_buffer().unwrap()).await.unwrap();
+            Ok(Uint8Array::new(&data).to_vec())
+        }
+        404 => Err(AssetReaderError::NotFound(path)),
+        status => Err(AssetReaderError::HttpError(status)),
+    }
+}
+
 impl HttpWasmAssetReader {
     async fn fetch_bytes<'a>(&self, path: PathBuf) -> Result<Box<Reader<'a>>, AssetReaderError> {
-        // The JS global scope includes a self-reference via a specialising name, which can be used to determine the type of global context available.
-        let global: Global = js_sys::global().unchecked_into();
-        let promise = if !global.window().is_undefined() {
-            let window: web_sys::Window = global.unchecked_into();
-            window.fetch_with_str(path.to_str().unwrap())
-        } else if !global.worker().is_undefined() {
-            let worker: web_sys::WorkerGlobalScope = global.unchecked_into();
-            worker.fetch_with_str(path.to_str().unwrap())
-        } else {
-            let error = std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "Unsupported JavaScript global context",
-            );
-            return Err(AssetReaderError::Io(error.into()));
-        };
-        let resp_value = JsFuture::from(promise)
-            .await
-            .map_err(js_value_to_err("fetch path"))?;
-        let resp = resp_value
-            .dyn_into::<Response>()
-            .map_err(js_value_to_err("convert fetch to Response"))?;
-        match resp.status() {
-            200 => {
-                let data = JsFuture::from(resp.array_buffer().unwrap()).await.unwrap();
-                let bytes = Uint8Array::new(&data).to_vec();
-                let reader: Box<Reader> = Box::new(VecReader::new(bytes));
-                Ok(reader)
-            }
-            404 => Err(AssetReaderError::NotFound(path)),
-            status => Err(AssetReaderError::HttpError(status)),
-        }
+        let bytes = fetch(path).await?;
+        let reader: Box<Reader> = Box::new(VecReader::new(bytes));
+        Ok(reader)
     }
 }
 
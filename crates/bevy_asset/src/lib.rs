@@ -21,6 +21,7 @@ pub mod prelude {
 }
 
 mod assets;
+mod collection;
 mod direct_access_ext;
 mod event;
 mod folder;
@@ -30,10 +31,12 @@ mod loader;
 mod loader_builders;
 mod path;
 mod reflect;
+mod retain;
 mod server;
 
 pub use assets::*;
-pub use bevy_asset_macros::Asset;
+pub use bevy_asset_macros::{Asset, AssetCollection};
+pub use collection::*;
 pub use direct_access_ext::DirectAssetAccessExt;
 pub use event::*;
 pub use folder::*;
@@ -46,6 +49,7 @@ pub use loader_builders::{
 };
 pub use path::*;
 pub use reflect::*;
+pub use retain::*;
 pub use server::*;
 
 /// Rusty Object Notation, a crate used to serialize and deserialize bevy assets.
@@ -54,8 +58,9 @@ pub use ron;
 use crate::{
     io::{embedded::EmbeddedAssetRegistry, AssetSourceBuilder, AssetSourceBuilders, AssetSourceId},
     processor::{AssetProcessor, Process},
+    saver::AssetSaver,
 };
-use bevy_app::{App, Last, Plugin, PreUpdate};
+use bevy_app::{App, Last, Plugin, PreStartup, PreUpdate};
 use bevy_ecs::{
     reflect::AppTypeRegistry,
     schedule::{IntoSystemConfigs, IntoSystemSetConfigs, SystemSet},
@@ -200,7 +205,9 @@ impl Plugin for AssetPlugin {
                             watch,
                         ))
                         .insert_resource(processor)
-                        .add_systems(bevy_app::Startup, AssetProcessor::start);
+                        .add_event::<processor::AssetProcessorEvent>()
+                        .add_systems(bevy_app::Startup, AssetProcessor::start)
+                        .add_systems(PreUpdate, AssetProcessor::send_processor_events);
                     }
                     #[cfg(not(feature = "asset_processor"))]
                     {
@@ -221,8 +228,17 @@ impl Plugin for AssetPlugin {
             .init_asset::<LoadedUntypedAsset>()
             .init_asset::<()>()
             .add_event::<UntypedAssetLoadFailedEvent>()
+            .add_event::<AssetReloaded>()
+            .add_event::<AssetSaved>()
+            .add_event::<AssetSaveFailedEvent>()
             .configure_sets(PreUpdate, TrackAssets.after(handle_internal_asset_events))
-            .add_systems(PreUpdate, handle_internal_asset_events)
+            .add_systems(
+                PreUpdate,
+                (
+                    handle_internal_asset_events,
+                    update_asset_streaming.after(handle_internal_asset_events),
+                ),
+            )
             .register_type::<AssetPath>();
     }
 }
@@ -286,6 +302,9 @@ impl VisitAssetDependencies for Vec<UntypedHandle> {
 pub trait AssetApp {
     /// Registers the given `loader` in the [`App`]'s [`AssetServer`].
     fn register_asset_loader<L: AssetLoader>(&mut self, loader: L) -> &mut Self;
+    /// Registers the given `saver` in the [`App`]'s [`AssetServer`], used to save the runtime
+    /// assets with [`AssetServer::save`].
+    fn register_asset_saver<S: AssetSaver>(&mut self, saver: S) -> &mut Self;
     /// Registers the given `processor` in the [`App`]'s [`AssetProcessor`].
     fn register_asset_processor<P: Process>(&mut self, processor: P) -> &mut Self;
     /// Registers the given [`AssetSourceBuilder`] with the given `id`.
@@ -319,6 +338,11 @@ pub trait AssetApp {
     /// Preregisters a loader for the given extensions, that will block asset loads until a real loader
     /// is registered.
     fn preregister_asset_loader<L: AssetLoader>(&mut self, extensions: &[&str]) -> &mut Self;
+    /// Sets the [`RetainPolicy`] of the assets of type `A`, deciding when they are removed once
+    /// they are unused. The [`Asset`] must be initialized first.
+    fn set_retain_policy<A: Asset>(&mut self, policy: RetainPolicy) -> &mut Self;
+    /// Starts loading the [`AssetCollection`] `C` at startup, and inserts it as a resource.
+    fn init_asset_collection<C: AssetCollection>(&mut self) -> &mut Self;
 }
 
 impl AssetApp for App {
@@ -329,6 +353,11 @@ impl AssetApp for App {
         self
     }
 
+    fn register_asset_saver<S: AssetSaver>(&mut self, saver: S) -> &mut Self {
+        self.world().resource::<AssetServer>().register_saver(saver);
+        self
+    }
+
     fn register_asset_processor<P: Process>(&mut self, processor: P) -> &mut Self {
         if let Some(asset_processor) = self.world().get_resource::<AssetProcessor>() {
             asset_processor.register_processor(processor);
@@ -386,6 +415,7 @@ impl AssetApp for App {
                 ));
         }
         self.insert_resource(assets)
+            .init_resource::<AssetUsages<A>>()
             .allow_ambiguous_resource::<Assets<A>>()
             .add_event::<AssetEvent<A>>()
             .add_event::<AssetLoadFailedEvent<A>>()
@@ -397,6 +427,7 @@ impl AssetApp for App {
                     .in_set(AssetEvents),
             )
             .add_systems(PreUpdate, Assets::<A>::track_assets.in_set(TrackAssets))
+            .add_systems(Last, save_assets::<A>)
     }
 
     fn register_asset_reflect<A>(&mut self) -> &mut Self
@@ -422,6 +453,17 @@ impl AssetApp for App {
             .preregister_loader::<L>(extensions);
         self
     }
+
+    fn set_retain_policy<A: Asset>(&mut self, policy: RetainPolicy) -> &mut Self {
+        self.world_mut()
+            .resource_mut::<Assets<A>>()
+            .set_retain_policy(policy);
+        self
+    }
+
+    fn init_asset_collection<C: AssetCollection>(&mut self) -> &mut Self {
+        self.add_systems(PreStartup, load_asset_collection::<C>)
+    }
 }
 
 /// A system set that holds all "track asset" operations.
@@ -437,18 +479,22 @@ pub struct AssetEvents;
 #[cfg(test)]
 mod tests {
     use crate::{
-        self as bevy_asset,
+        self as bevy_asset, asset_collection_loaded,
         folder::LoadedFolder,
         handle::Handle,
         io::{
             gated::{GateOpener, GatedReader},
             memory::{Dir, MemoryAssetReader},
-            AssetReader, AssetReaderError, AssetSource, AssetSourceId, Reader,
+            AssetReader, AssetReaderError, AssetSource, AssetSourceId, AssetWriter,
+            AssetWriterError, Reader, Writer,
         },
         loader::{AssetLoader, LoadContext},
-        Asset, AssetApp, AssetEvent, AssetId, AssetLoadError, AssetLoadFailedEvent, AssetPath,
-        AssetPlugin, AssetServer, Assets, DependencyLoadState, LoadState,
-        RecursiveDependencyLoadState,
+        saver::{AssetSaver, SavedAsset},
+        Asset, AssetApp, AssetCollection, AssetEvent, AssetId, AssetLoadError,
+        AssetLoadFailedEvent, AssetPath, AssetPlugin, AssetReloaded, AssetSaveError,
+        AssetSaveFailedEvent, AssetSaved, AssetServer, AssetUsages, Assets, DependencyLoadState,
+        LoadPriority, LoadState, RecursiveDependencyLoadState, RetainPolicy, StreamingBudget,
+        StreamingState,
     };
     use bevy_app::{App, Update};
     use bevy_core::TaskPoolPlugin;
@@ -456,11 +502,13 @@ mod tests {
     use bevy_ecs::{
         event::ManualEventReader,
         schedule::{LogLevel, ScheduleBuildSettings},
+        system::RunSystemOnce,
     };
     use bevy_log::LogPlugin;
     use bevy_reflect::TypePath;
+    use bevy_tasks::{block_on, IoTaskPool};
     use bevy_utils::{Duration, HashMap};
-    use futures_lite::AsyncReadExt;
+    use futures_lite::{AsyncReadExt, AsyncWriteExt};
     use serde::{Deserialize, Serialize};
     use std::{path::Path, sync::Arc};
     use thiserror::Error;
@@ -1115,6 +1163,416 @@ mod tests {
     embedded_dependencies: [],
     sub_texts: [],
 )"#;
+    #[test]
+    fn streamed_loads() {
+        // The particular usage of GatedReader in this test will cause deadlocking if running single-threaded
+        #[cfg(not(feature = "multi_threaded"))]
+        panic!("This test requires the \"multi_threaded\" feature, otherwise it will deadlock.\ncargo test --package bevy_asset --features multi_threaded");
+
+        let dir = Dir::default();
+        let (a_path, b_path, c_path) = ("a.cool.ron", "b.cool.ron", "c.cool.ron");
+        for path in [a_path, b_path, c_path] {
+            dir.insert_asset_text(Path::new(path), SIMPLE_TEXT);
+        }
+
+        let (mut app, gate_opener) = test_app(dir);
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader);
+        let server = app.world().resource::<AssetServer>().clone();
+        server.set_streaming_budget(StreamingBudget {
+            loads_per_frame: 1,
+            concurrent_loads: 1,
+        });
+
+        let a: Handle<CoolText> = server.load_with_priority(a_path, LoadPriority::Low);
+        let b: Handle<CoolText> = server.load_with_priority(b_path, LoadPriority::High);
+        let c: Handle<CoolText> = server.load_with_priority(c_path, LoadPriority::Normal);
+        app.update();
+        // only the load with the highest priority is started
+        assert_eq!(server.streaming_state(&b), Some(StreamingState::Loading));
+        assert_eq!(server.streaming_state(&c), Some(StreamingState::Pending));
+        assert_eq!(server.streaming_state(&a), Some(StreamingState::Pending));
+
+        assert!(server.pause_load(&c));
+        assert!(server.cancel_load(&a));
+        assert_eq!(server.streaming_state(&a), None);
+        run_app_until(&mut app, |_| {
+            matches!(server.load_state(&a), LoadState::Failed(_)).then_some(())
+        });
+
+        gate_opener.open(b_path);
+        run_app_until(&mut app, |world| get::<CoolText>(world, b.id()).map(|_| ()));
+        // the paused load isn't started
+        app.update();
+        assert_eq!(server.streaming_state(&c), Some(StreamingState::Paused));
+
+        assert!(server.resume_load(&c));
+        app.update();
+        assert_eq!(server.streaming_state(&c), Some(StreamingState::Loading));
+        gate_opener.open(c_path);
+        run_app_until(&mut app, |world| get::<CoolText>(world, c.id()).map(|_| ()));
+    }
+
+    #[test]
+    fn reload_dependants() {
+        let dir = Dir::default();
+        let a_path = "a.cool.ron";
+        let a_ron = r#"
+(
+    text: "a",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: ["hello"],
+)"#;
+        let b_ron = r#"
+(
+    text: "b",
+    dependencies: ["c.cool.ron"],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        let c_ron = r#"
+(
+    text: "c",
+    dependencies: ["a.cool.ron"],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        dir.insert_asset_text(Path::new(a_path), a_ron);
+        dir.insert_asset_text(Path::new("b.cool.ron"), b_ron);
+        dir.insert_asset_text(Path::new("c.cool.ron"), c_ron);
+
+        #[derive(Resource, Default)]
+        struct Reloaded(Vec<AssetReloaded>);
+
+        let mut app = App::new();
+        let reader = MemoryAssetReader { root: dir.clone() };
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build().with_reader(move || Box::new(reader.clone())),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            LogPlugin::default(),
+            AssetPlugin {
+                watch_for_changes_override: Some(true),
+                ..Default::default()
+            },
+        ))
+        .init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .init_resource::<Reloaded>()
+        .init_resource::<StoredEvents>()
+        .register_asset_loader(CoolTextLoader)
+        .add_systems(
+            Update,
+            (
+                |mut events: EventReader<AssetReloaded>, mut reloaded: ResMut<Reloaded>| {
+                    reloaded.0.extend(events.read().cloned());
+                },
+                store_asset_events,
+            ),
+        );
+
+        let server = app.world().resource::<AssetServer>().clone();
+        let b: Handle<CoolText> = server.load("b.cool.ron");
+        run_app_until(&mut app, |_| {
+            server.is_loaded_with_dependencies(&b).then_some(())
+        });
+        let c = server.get_handle::<CoolText>("c.cool.ron").unwrap();
+        app.world_mut().resource_mut::<StoredEvents>().0.clear();
+
+        dir.insert_asset_text(Path::new(a_path), &a_ron.replace("\"a\"", "\"a2\""));
+        server.reload(a_path);
+        run_app_until(&mut app, |world| {
+            (!world.resource::<Reloaded>().0.is_empty()).then_some(())
+        });
+
+        // the asset events are sent at the end of the update
+        app.update();
+
+        let a = server.get_handle::<CoolText>(a_path).unwrap();
+        assert_eq!(get(app.world(), a.id()).unwrap().text, "a2");
+        let reloaded = &app.world().resource::<Reloaded>().0[0];
+        assert_eq!(reloaded.path, AssetPath::from(a_path));
+        assert!(reloaded.contains_label("hello"));
+        // the dependants of the reloaded asset are modified, including the transitive ones
+        assert_eq!(reloaded.dependants.len(), 2);
+        assert!(reloaded.dependants.contains(&b.id().untyped()));
+        assert!(reloaded.dependants.contains(&c.id().untyped()));
+        let events = &app.world().resource::<StoredEvents>().0;
+        assert!(events.contains(&AssetEvent::Modified { id: b.id() }));
+        assert!(events.contains(&AssetEvent::Modified { id: c.id() }));
+    }
+
+    // The tasks of the single threaded task pool can't be polled.
+    #[cfg(feature = "multi_threaded")]
+    #[test]
+    fn load_async() {
+        let dir = Dir::default();
+        dir.insert_asset_text(Path::new("a.cool.ron"), SIMPLE_TEXT);
+        dir.insert_asset_text(Path::new("b.cool.ron"), SIMPLE_TEXT);
+
+        let mut app = App::new();
+        let reader = MemoryAssetReader { root: dir };
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build().with_reader(move || Box::new(reader.clone())),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            LogPlugin::default(),
+            AssetPlugin::default(),
+        ))
+        .init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .register_asset_loader(CoolTextLoader);
+
+        let server = app.world().resource::<AssetServer>().clone();
+        let task = IoTaskPool::get().spawn(async move {
+            let a = server.load_async::<CoolText>("a.cool.ron").await?;
+            let b: Handle<CoolText> = server.load("b.cool.ron");
+            server.wait_for_all([&a, &b]).await?;
+            let missing: Handle<CoolText> = server.load("missing.cool.ron");
+            let error = server.wait_for_all([&a, &missing]).await.unwrap_err();
+            Ok::<_, AssetLoadError>((a, b, error))
+        });
+        run_app_until(&mut app, |_| task.is_finished().then_some(()));
+
+        let (a, b, error) = block_on(task).unwrap();
+        assert!(get::<CoolText>(app.world(), a.id()).is_some());
+        assert!(get::<CoolText>(app.world(), b.id()).is_some());
+        assert!(matches!(error, AssetLoadError::AssetReaderError(_)));
+    }
+
+    #[test]
+    fn save_runtime_assets() {
+        struct CoolTextSaver;
+
+        impl AssetSaver for CoolTextSaver {
+            type Asset = CoolText;
+            type Settings = ();
+            type OutputLoader = CoolTextLoader;
+            type Error = std::io::Error;
+
+            async fn save<'a>(
+                &'a self,
+                writer: &'a mut Writer,
+                asset: SavedAsset<'a, Self::Asset>,
+                _settings: &'a Self::Settings,
+            ) -> Result<(), Self::Error> {
+                let ron = CoolTextRon {
+                    text: asset.text.clone(),
+                    dependencies: Vec::new(),
+                    embedded_dependencies: Vec::new(),
+                    sub_texts: Vec::new(),
+                };
+                let bytes = ron::to_string(&ron).unwrap();
+                writer.write_all(bytes.as_bytes()).await
+            }
+        }
+
+        /// Only supports writing whole files, which is what saving assets does.
+        struct MemoryAssetWriter {
+            root: Dir,
+        }
+
+        impl AssetWriter for MemoryAssetWriter {
+            async fn write<'a>(&'a self, _: &'a Path) -> Result<Box<Writer>, AssetWriterError> {
+                unimplemented!()
+            }
+            async fn write_meta<'a>(
+                &'a self,
+                _: &'a Path,
+            ) -> Result<Box<Writer>, AssetWriterError> {
+                unimplemented!()
+            }
+            async fn remove<'a>(&'a self, _: &'a Path) -> Result<(), AssetWriterError> {
+                unimplemented!()
+            }
+            async fn remove_meta<'a>(&'a self, _: &'a Path) -> Result<(), AssetWriterError> {
+                unimplemented!()
+            }
+            async fn rename<'a>(
+                &'a self,
+                _: &'a Path,
+                _: &'a Path,
+            ) -> Result<(), AssetWriterError> {
+                unimplemented!()
+            }
+            async fn rename_meta<'a>(
+                &'a self,
+                _: &'a Path,
+                _: &'a Path,
+            ) -> Result<(), AssetWriterError> {
+                unimplemented!()
+            }
+            async fn remove_directory<'a>(&'a self, _: &'a Path) -> Result<(), AssetWriterError> {
+                unimplemented!()
+            }
+            async fn remove_empty_directory<'a>(
+                &'a self,
+                _: &'a Path,
+            ) -> Result<(), AssetWriterError> {
+                unimplemented!()
+            }
+            async fn remove_assets_in_directory<'a>(
+                &'a self,
+                _: &'a Path,
+            ) -> Result<(), AssetWriterError> {
+                unimplemented!()
+            }
+            async fn write_bytes<'a>(
+                &'a self,
+                path: &'a Path,
+                bytes: &'a [u8],
+            ) -> Result<(), AssetWriterError> {
+                self.root.insert_asset(path, bytes.to_vec());
+                Ok(())
+            }
+            async fn write_meta_bytes<'a>(
+                &'a self,
+                path: &'a Path,
+                bytes: &'a [u8],
+            ) -> Result<(), AssetWriterError> {
+                self.root.insert_meta(path, bytes.to_vec());
+                Ok(())
+            }
+        }
+
+        #[derive(Resource, Default)]
+        struct SaveEvents {
+            saved: Vec<AssetSaved>,
+            failed: Vec<AssetSaveFailedEvent>,
+        }
+
+        let dir = Dir::default();
+        let mut app = App::new();
+        let reader = MemoryAssetReader { root: dir.clone() };
+        let writer_root = dir.clone();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || Box::new(reader.clone()))
+                .with_writer(move |_| {
+                    Some(Box::new(MemoryAssetWriter {
+                        root: writer_root.clone(),
+                    }))
+                }),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            LogPlugin::default(),
+            AssetPlugin::default(),
+        ))
+        .init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .init_resource::<SaveEvents>()
+        .register_asset_loader(CoolTextLoader)
+        .register_asset_saver(CoolTextSaver)
+        .add_systems(
+            Update,
+            |mut saved: EventReader<AssetSaved>,
+             mut failed: EventReader<AssetSaveFailedEvent>,
+             mut events: ResMut<SaveEvents>| {
+                events.saved.extend(saved.read().cloned());
+                events.failed.extend(failed.read().cloned());
+            },
+        );
+
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<CoolText>>()
+            .add(CoolText {
+                text: "runtime".to_string(),
+                ..Default::default()
+            });
+        let server = app.world().resource::<AssetServer>().clone();
+        server.save(&handle, "saved.cool.ron");
+        server.save(&handle, "saved.txt");
+        run_app_until(&mut app, |world| {
+            let events = world.resource::<SaveEvents>();
+            (events.saved.len() + events.failed.len() == 2).then_some(())
+        });
+
+        let events = app.world().resource::<SaveEvents>();
+        assert_eq!(events.saved[0].path, AssetPath::from("saved.cool.ron"));
+        assert!(matches!(
+            events.failed[0].error,
+            AssetSaveError::MissingSaver { .. }
+        ));
+        assert!(dir.get_metadata(Path::new("saved.cool.ron")).is_some());
+
+        let saved: Handle<CoolText> = server.load("saved.cool.ron");
+        run_app_until(&mut app, |world| {
+            let text = get::<CoolText>(world, saved.id())?;
+            assert_eq!(text.text, "runtime");
+            Some(())
+        });
+    }
+
+    #[test]
+    fn asset_collection() {
+        #[derive(AssetCollection, Resource)]
+        struct CoolTexts {
+            #[asset(path = "a.cool.ron")]
+            a: Handle<CoolText>,
+            #[asset(paths("b.cool.ron", "c.cool.ron"))]
+            others: Vec<Handle<CoolText>>,
+            count: usize,
+        }
+
+        let dir = Dir::default();
+        for text in ["a", "b", "c"] {
+            dir.insert_asset_text(
+                Path::new(&format!("{text}.cool.ron")),
+                &format!(
+                    r#"(
+    text: "{text}",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#
+                ),
+            );
+        }
+
+        let mut app = App::new();
+        let reader = MemoryAssetReader { root: dir };
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build().with_reader(move || Box::new(reader.clone())),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            LogPlugin::default(),
+            AssetPlugin::default(),
+        ))
+        .init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .register_asset_loader(CoolTextLoader)
+        .init_asset_collection::<CoolTexts>();
+
+        run_app_until(&mut app, |world| {
+            world
+                .run_system_once(asset_collection_loaded::<CoolTexts>)
+                .then_some(())
+        });
+
+        let collection = app.world().resource::<CoolTexts>();
+        assert_eq!(collection.others.len(), 2);
+        assert_eq!(collection.count, 0);
+        let texts = app.world().resource::<Assets<CoolText>>();
+        assert_eq!(texts.get(&collection.a).unwrap().text, "a");
+        assert_eq!(texts.get(&collection.others[1]).unwrap().text, "c");
+        let server = app.world().resource::<AssetServer>();
+        assert_eq!(
+            collection.load_state(server),
+            RecursiveDependencyLoadState::Loaded
+        );
+    }
+
     #[test]
     fn keep_gotten_strong_handles() {
         let dir = Dir::default();
@@ -1150,6 +1608,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn retain_policies() {
+        let (mut app, _) = test_app(Dir::default());
+        app.init_asset::<CoolText>()
+            .set_retain_policy::<CoolText>(RetainPolicy::WhileUsed { grace_frames: 2 });
+        let usages = app.world().resource::<AssetUsages<CoolText>>().clone();
+        let contains = |app: &App, id| app.world().resource::<Assets<CoolText>>().contains(id);
+
+        // the handle is dropped right away
+        let id = app
+            .world_mut()
+            .resource_mut::<Assets<CoolText>>()
+            .add(CoolText::default())
+            .id();
+        app.update();
+        app.update();
+        assert!(contains(&app, id));
+
+        // a use restarts the grace frames
+        usages.mark_used(id);
+        app.update();
+        assert_eq!(usages.uses(id), 1);
+        app.update();
+        app.update();
+        assert!(contains(&app, id));
+        app.update();
+        assert!(!contains(&app, id));
+
+        app.set_retain_policy::<CoolText>(RetainPolicy::Manual);
+        let id = app
+            .world_mut()
+            .resource_mut::<Assets<CoolText>>()
+            .add(CoolText::default())
+            .id();
+        for _ in 0..10 {
+            app.update();
+        }
+        assert!(contains(&app, id));
+        let mut texts = app.world_mut().resource_mut::<Assets<CoolText>>();
+        assert_eq!(texts.unused_ids().collect::<Vec<_>>(), vec![id]);
+        texts.release_unused();
+        app.update();
+        assert!(!contains(&app, id));
+    }
+
     #[test]
     fn manual_asset_management() {
         // The particular usage of GatedReader in this test will cause deadlocking if running single-threaded
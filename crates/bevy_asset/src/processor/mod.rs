@@ -26,6 +26,7 @@ use bevy_utils::{
     ConditionalSendFuture,
 };
 use bevy_utils::{HashMap, HashSet};
+use crossbeam_channel::{Receiver, Sender};
 use futures_io::ErrorKind;
 use futures_lite::{AsyncReadExt, AsyncWriteExt, StreamExt};
 use parking_lot::RwLock;
@@ -73,6 +74,8 @@ pub struct AssetProcessorData {
     initialized_receiver: async_broadcast::Receiver<()>,
     finished_sender: async_broadcast::Sender<()>,
     finished_receiver: async_broadcast::Receiver<()>,
+    event_sender: Sender<AssetProcessorEvent>,
+    event_receiver: Receiver<AssetProcessorEvent>,
 }
 
 impl AssetProcessor {
@@ -103,6 +106,7 @@ impl AssetProcessor {
         *state_guard = state;
         if last_state != ProcessorState::Finished && state == ProcessorState::Finished {
             self.data.finished_sender.broadcast(()).await.unwrap();
+            self.send_event(AssetProcessorEvent::Finished);
         } else if last_state != ProcessorState::Processing && state == ProcessorState::Processing {
             self.data.initialized_sender.broadcast(()).await.unwrap();
         }
@@ -150,6 +154,19 @@ impl AssetProcessor {
         log.end_processing(path).await.unwrap();
     }
 
+    fn send_event(&self, event: AssetProcessorEvent) {
+        // The receiver lives as long as the sender, in the processor data
+        self.data.event_sender.send(event).unwrap();
+    }
+
+    /// Sends the [`AssetProcessorEvent`]s of the processor running in the background to the [`App`](bevy_app::App).
+    pub fn send_processor_events(
+        processor: Res<Self>,
+        mut events: EventWriter<AssetProcessorEvent>,
+    ) {
+        events.send_batch(processor.data.event_receiver.try_iter());
+    }
+
     /// Starts the processor in a background thread.
     pub fn start(_processor: Res<Self>) {
         #[cfg(any(target_arch = "wasm32", not(feature = "multi_threaded")))]
@@ -682,6 +699,31 @@ impl AssetProcessor {
     async fn process_asset(&self, source: &AssetSource, path: PathBuf) {
         let asset_path = AssetPath::from(path).with_source(source.id());
         let result = self.process_asset_internal(source, &asset_path).await;
+        let event = match &result {
+            Ok(ProcessResult::Processed(_)) => Some(AssetProcessorEvent::Processed {
+                path: asset_path.clone(),
+                skipped: false,
+            }),
+            Ok(ProcessResult::SkippedNotChanged) => Some(AssetProcessorEvent::Processed {
+                path: asset_path.clone(),
+                skipped: true,
+            }),
+            // These are not failures, see `ProcessorAssetInfos::finish_processing`
+            Ok(ProcessResult::Ignored)
+            | Err(ProcessError::ExtensionRequired)
+            | Err(ProcessError::MissingAssetLoaderForExtension(_))
+            | Err(ProcessError::AssetReaderError {
+                err: AssetReaderError::NotFound(_),
+                ..
+            }) => None,
+            Err(err) => Some(AssetProcessorEvent::Failed {
+                path: asset_path.clone(),
+                error: err.to_string(),
+            }),
+        };
+        if let Some(event) = event {
+            self.send_event(event);
+        }
         let mut infos = self.data.asset_infos.write().await;
         infos.finish_processing(asset_path, result).await;
     }
@@ -825,6 +867,7 @@ impl AssetProcessor {
         // Directly writing to the asset destination in the processor necessitates this behavior
         // TODO: this class of failure can be recovered via re-processing + smarter log validation that allows for duplicate transactions in the event of failures
         self.log_begin_processing(asset_path).await;
+        self.send_event(AssetProcessorEvent::Started(asset_path.clone()));
         if let Some(processor) = processor {
             let mut writer = processed_writer.write(path).await.map_err(writer_err)?;
             let mut processed_meta = {
@@ -964,6 +1007,7 @@ impl AssetProcessorData {
     pub fn new(source: AssetSources) -> Self {
         let (mut finished_sender, finished_receiver) = async_broadcast::broadcast(1);
         let (mut initialized_sender, initialized_receiver) = async_broadcast::broadcast(1);
+        let (event_sender, event_receiver) = crossbeam_channel::unbounded();
         // allow overflow on these "one slot" channels to allow receivers to retrieve the "latest" state, and to allow senders to
         // not block if there was older state present.
         finished_sender.set_overflow(true);
@@ -975,6 +1019,8 @@ impl AssetProcessorData {
             finished_receiver,
             initialized_sender,
             initialized_receiver,
+            event_sender,
+            event_receiver,
             state: async_lock::RwLock::new(ProcessorState::Initializing),
             log: Default::default(),
             processors: Default::default(),
@@ -1077,6 +1123,28 @@ pub enum ProcessResult {
     Ignored,
 }
 
+/// The progress of the [`AssetProcessor`], for editor UIs.
+///
+/// The events are sent in the [`App`](bevy_app::App) running the processor.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub enum AssetProcessorEvent {
+    /// The asset started processing, because it or one of its process dependencies changed.
+    Started(AssetPath<'static>),
+    /// The asset was processed.
+    Processed {
+        path: AssetPath<'static>,
+        /// Whether processing was skipped, because neither the asset nor its process dependencies changed.
+        skipped: bool,
+    },
+    /// Processing the asset failed.
+    Failed {
+        path: AssetPath<'static>,
+        error: String,
+    },
+    /// All the assets have been processed, until an asset changes.
+    Finished,
+}
+
 /// The final status of processing an asset
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum ProcessStatus {
@@ -1241,17 +1309,22 @@ impl ProcessorAssetInfos {
             }
             Err(err) => {
                 error!("Failed to process asset {asset_path}: {err}");
-                // if this failed because a dependency could not be loaded, make sure it is reprocessed if that dependency is reprocessed
-                if let ProcessError::AssetLoadError(AssetLoadError::AssetLoaderError(dependency)) =
-                    err
-                {
+                // if this failed because a dependency could not be loaded or processed, make sure it is reprocessed if that dependency is reprocessed
+                let dependency = match &err {
+                    ProcessError::AssetLoadError(AssetLoadError::AssetLoaderError(dependency)) => {
+                        Some(dependency.path())
+                    }
+                    ProcessError::MissingProcessDependency(dependency) => Some(dependency),
+                    _ => None,
+                };
+                if let Some(dependency) = dependency {
                     let info = self.get_mut(&asset_path).expect("info should exist");
                     info.processed_info = Some(ProcessedInfo {
                         hash: AssetHash::default(),
                         full_hash: AssetHash::default(),
                         process_dependencies: vec![],
                     });
-                    self.add_dependant(dependency.path(), asset_path.to_owned());
+                    self.add_dependant(dependency, asset_path.to_owned());
                 }
 
                 let info = self.get_mut(&asset_path).expect("info should exist");
@@ -1,17 +1,18 @@
 use crate::io::SliceReader;
 use crate::{
     io::{
-        AssetReaderError, AssetWriterError, MissingAssetWriterError,
+        AssetReaderError, AssetWriterError, MissingAssetSourceError, MissingAssetWriterError,
         MissingProcessedAssetReaderError, MissingProcessedAssetWriterError, Writer,
     },
     meta::{AssetAction, AssetMeta, AssetMetaDyn, ProcessDependencyInfo, ProcessedInfo, Settings},
-    processor::AssetProcessor,
+    processor::{AssetProcessor, ProcessStatus},
     saver::{AssetSaver, SavedAsset},
     transformer::{AssetTransformer, TransformedAsset},
     AssetLoadError, AssetLoader, AssetPath, DeserializeMetaError, ErasedLoadedAsset,
     MissingAssetLoaderForExtensionError, MissingAssetLoaderForTypeNameError,
 };
 use bevy_utils::{BoxedFuture, ConditionalSendFuture};
+use futures_lite::AsyncReadExt;
 use serde::{Deserialize, Serialize};
 use std::marker::PhantomData;
 use thiserror::Error;
@@ -143,6 +144,8 @@ pub enum ProcessError {
         err: AssetWriterError,
     },
     #[error(transparent)]
+    MissingAssetSourceError(#[from] MissingAssetSourceError),
+    #[error(transparent)]
     MissingAssetWriterError(#[from] MissingAssetWriterError),
     #[error(transparent)]
     MissingProcessedAssetReaderError(#[from] MissingProcessedAssetReaderError),
@@ -165,6 +168,10 @@ pub enum ProcessError {
     AssetTransformError(Box<dyn std::error::Error + Send + Sync + 'static>),
     #[error("Assets without extensions are not supported.")]
     ExtensionRequired,
+    #[error("The process dependency '{0}' does not exist or failed to process")]
+    MissingProcessDependency(AssetPath<'static>),
+    #[error("The asset '{0}' cannot be a process dependency of itself")]
+    CyclicProcessDependency(AssetPath<'static>),
 }
 
 impl<Loader, Transformer, Saver> Process for LoadTransformAndSave<Loader, Transformer, Saver>
@@ -368,6 +375,90 @@ impl<'a> ProcessContext<'a> {
         Ok(loaded_asset)
     }
 
+    /// Registers the processed version of the asset at `path` as a "process dependency" of the current
+    /// asset, waiting until it has been processed.
+    ///
+    /// This is used by processors whose output depends on the output of other processors, like a material
+    /// processor reading the compressed version of its textures. When the dependency is re-processed, the
+    /// current asset will be re-processed too, as well as the assets depending on it. The dependencies must
+    /// not form a cycle, or the assets in the cycle will never finish processing.
+    pub async fn add_process_dependency(
+        &mut self,
+        path: impl Into<AssetPath<'static>>,
+    ) -> Result<(), ProcessError> {
+        let path = path.into();
+        self.wait_for_process_dependency(&path).await?;
+        self.register_process_dependency(path).await
+    }
+
+    /// Reads the bytes of the processed version of the asset at `path`, and registers it as a
+    /// "process dependency" of the current asset. See [`ProcessContext::add_process_dependency`].
+    pub async fn read_processed_dependency(
+        &mut self,
+        path: impl Into<AssetPath<'static>>,
+    ) -> Result<Vec<u8>, ProcessError> {
+        let path = path.into();
+        self.wait_for_process_dependency(&path).await?;
+        let reader_err = |err| ProcessError::AssetReaderError {
+            path: path.clone(),
+            err,
+        };
+        let processor = self.processor;
+        let source = processor.server.get_source(path.source())?;
+        let mut reader = source
+            .processed_reader()?
+            .read(path.path())
+            .await
+            .map_err(reader_err)?;
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(|err| reader_err(AssetReaderError::Io(err.into())))?;
+        // Registered while the reader holds the transaction lock of the dependency, so the hash
+        // can't be newer than the bytes
+        self.register_process_dependency(path.clone()).await?;
+        drop(reader);
+        Ok(bytes)
+    }
+
+    async fn wait_for_process_dependency(
+        &self,
+        path: &AssetPath<'static>,
+    ) -> Result<(), ProcessError> {
+        if path == self.path {
+            return Err(ProcessError::CyclicProcessDependency(path.clone()));
+        }
+        match self.processor.data.wait_until_processed(path.clone()).await {
+            ProcessStatus::Processed => Ok(()),
+            ProcessStatus::Failed | ProcessStatus::NonExistent => {
+                Err(ProcessError::MissingProcessDependency(path.clone()))
+            }
+        }
+    }
+
+    async fn register_process_dependency(
+        &mut self,
+        path: AssetPath<'static>,
+    ) -> Result<(), ProcessError> {
+        let full_hash = self
+            .processor
+            .data
+            .asset_infos
+            .read()
+            .await
+            .get(&path)
+            .and_then(|info| info.processed_info.as_ref())
+            .map(|info| info.full_hash);
+        let Some(full_hash) = full_hash else {
+            return Err(ProcessError::MissingProcessDependency(path));
+        };
+        self.new_processed_info
+            .process_dependencies
+            .push(ProcessDependencyInfo { full_hash, path });
+        Ok(())
+    }
+
     /// The path of the asset being processed.
     #[inline]
     pub fn path(&self) -> &AssetPath<'static> {
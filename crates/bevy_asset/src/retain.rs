@@ -0,0 +1,96 @@
+use crate::{Asset, AssetId};
+use bevy_ecs::system::Resource;
+use bevy_utils::HashMap;
+use parking_lot::Mutex;
+use std::sync::Arc;
+
+/// Decides when the assets of a type are removed from their [`Assets`](crate::Assets) collection
+/// once all of their strong [`Handle`](crate::Handle)s are dropped, set with
+/// [`AssetApp::set_retain_policy`](crate::AssetApp::set_retain_policy).
+///
+/// Keeping unused assets around avoids reloading them when they are needed again shortly after,
+/// like the textures of an area the player keeps walking in and out of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetainPolicy {
+    /// The asset is removed once it has no strong handles and hasn't been marked as used in its
+    /// [`AssetUsages`] for more than `grace_frames` frames.
+    ///
+    /// With no grace frames, this is the default behavior of removing the assets as soon as their
+    /// last strong handle is dropped.
+    WhileUsed {
+        /// The number of frames an unused asset is kept for.
+        grace_frames: u32,
+    },
+    /// The asset is never removed automatically, only with [`Assets::remove`](crate::Assets::remove).
+    Always,
+    /// The unused assets are kept until [`Assets::release_unused`](crate::Assets::release_unused)
+    /// is called, for example during loading screens.
+    Manual,
+}
+
+impl Default for RetainPolicy {
+    fn default() -> Self {
+        Self::WhileUsed { grace_frames: 0 }
+    }
+}
+
+struct AssetUsagesInternal<A: Asset> {
+    current: HashMap<AssetId<A>, u32>,
+    previous: HashMap<AssetId<A>, u32>,
+}
+
+/// Counts the uses of the assets of type `A` that don't go through strong handles, like the render
+/// world drawing an asset by its [`AssetId`], so that [`RetainPolicy::WhileUsed`] keeps them while
+/// they are used.
+///
+/// This is cheap to clone, the clones share the counts. The render world has a clone of the
+/// usages of the assets extracted with a `RenderAssetPlugin`.
+#[derive(Resource)]
+pub struct AssetUsages<A: Asset> {
+    internal: Arc<Mutex<AssetUsagesInternal<A>>>,
+}
+
+impl<A: Asset> Default for AssetUsages<A> {
+    fn default() -> Self {
+        Self {
+            internal: Arc::new(Mutex::new(AssetUsagesInternal {
+                current: HashMap::default(),
+                previous: HashMap::default(),
+            })),
+        }
+    }
+}
+
+impl<A: Asset> Clone for AssetUsages<A> {
+    fn clone(&self) -> Self {
+        Self {
+            internal: self.internal.clone(),
+        }
+    }
+}
+
+impl<A: Asset> AssetUsages<A> {
+    /// Counts a use of the asset with the given `id` during the current frame.
+    pub fn mark_used(&self, id: impl Into<AssetId<A>>) {
+        *self.internal.lock().current.entry(id.into()).or_insert(0) += 1;
+    }
+
+    /// Returns the number of uses of the asset with the given `id` counted during the previous
+    /// frame.
+    pub fn uses(&self, id: impl Into<AssetId<A>>) -> u32 {
+        self.internal
+            .lock()
+            .previous
+            .get(&id.into())
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Starts counting the uses of a new frame, and returns the uses of the frame that ended.
+    pub(crate) fn end_frame(&self) -> HashMap<AssetId<A>, u32> {
+        let mut internal = self.internal.lock();
+        let internal = &mut *internal;
+        internal.previous = std::mem::take(&mut internal.current);
+        internal.previous.clone()
+    }
+}
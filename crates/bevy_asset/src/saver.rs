@@ -1,3 +1,4 @@
+use crate::meta::{AssetAction, AssetMeta, AssetMetaDyn};
 use crate::transformer::TransformedAsset;
 use crate::{io::Writer, meta::Settings, Asset, ErasedLoadedAsset};
 use crate::{AssetLoader, Handle, LabeledAsset, UntypedHandle};
@@ -67,6 +68,59 @@ impl<S: AssetSaver> ErasedAssetSaver for S {
     }
 }
 
+/// An [`AssetSaver`] of assets of type `A`, used to save the runtime assets with
+/// [`AssetServer::save`](crate::AssetServer::save).
+pub(crate) trait TypedAssetSaver<A: Asset>: Send + Sync + 'static {
+    /// Saves `asset` with the default settings of the saver, and returns the serialized meta to load
+    /// it back.
+    fn save<'a>(
+        &'a self,
+        writer: &'a mut Writer,
+        asset: &'a A,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync + 'static>>>;
+
+    /// The type name of the [`AssetSaver::OutputLoader`].
+    fn output_loader(&self) -> &'static str;
+
+    /// The type name of the [`AssetSaver`].
+    fn type_name(&self) -> &'static str;
+}
+
+impl<S: AssetSaver> TypedAssetSaver<S::Asset> for S {
+    fn save<'a>(
+        &'a self,
+        writer: &'a mut Writer,
+        asset: &'a S::Asset,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync + 'static>>> {
+        Box::pin(async move {
+            let labeled_assets = HashMap::default();
+            let saved_asset = SavedAsset {
+                value: asset,
+                labeled_assets: &labeled_assets,
+            };
+            let settings = S::Settings::default();
+            let loader_settings = match AssetSaver::save(self, writer, saved_asset, &settings).await
+            {
+                Ok(loader_settings) => loader_settings,
+                Err(err) => return Err(err.into()),
+            };
+            let meta = AssetMeta::<S::OutputLoader, ()>::new(AssetAction::Load {
+                loader: self.output_loader().to_string(),
+                settings: loader_settings,
+            });
+            Ok(AssetMetaDyn::serialize(&meta))
+        })
+    }
+
+    fn output_loader(&self) -> &'static str {
+        std::any::type_name::<S::OutputLoader>()
+    }
+
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<S>()
+    }
+}
+
 /// An [`Asset`] (and any labeled "sub assets") intended to be saved.
 pub struct SavedAsset<'a, A: Asset> {
     value: &'a A,
@@ -11,6 +11,7 @@ use crossbeam_channel::Sender;
 use std::{
     any::TypeId,
     sync::{Arc, Weak},
+    task::Waker,
 };
 use thiserror::Error;
 
@@ -34,9 +35,16 @@ pub(crate) struct AssetInfo {
     ///
     /// [`LoadedAsset`]: crate::loader::LoadedAsset
     loader_dependencies: HashMap<AssetPath<'static>, AssetHash>,
+    /// The assets this asset holds handles to. This will only be populated if
+    /// [`AssetInfos::watching_for_changes`] is set to `true` to save memory.
+    dependencies: HashSet<UntypedAssetId>,
     /// The number of handle drops to skip for this asset.
     /// See usage (and comments) in `get_or_create_path_handle` for context.
     handle_drops_to_skip: usize,
+    /// The tasks waiting for this asset to finish loading, with [`AssetServer::wait_for_asset`].
+    ///
+    /// [`AssetServer::wait_for_asset`]: crate::AssetServer::wait_for_asset
+    pub(crate) waiting_tasks: Vec<Waker>,
 }
 
 impl AssetInfo {
@@ -52,9 +60,11 @@ impl AssetInfo {
             loading_rec_dependencies: HashSet::default(),
             failed_rec_dependencies: HashSet::default(),
             loader_dependencies: HashMap::default(),
+            dependencies: HashSet::default(),
             dependants_waiting_on_load: HashSet::default(),
             dependants_waiting_on_recursive_dep_load: HashSet::default(),
             handle_drops_to_skip: 0,
+            waiting_tasks: Vec::new(),
         }
     }
 }
@@ -67,8 +77,12 @@ pub(crate) struct AssetInfos {
     /// This should only be set at startup.
     pub(crate) watching_for_changes: bool,
     /// Tracks assets that depend on the "key" asset path inside their asset loaders ("loader dependencies")
+    /// The keys don't have labels, since a change to the file invalidates all of its labeled assets.
     /// This should only be set when watching for changes to avoid unnecessary work.
     pub(crate) loader_dependants: HashMap<AssetPath<'static>, HashSet<AssetPath<'static>>>,
+    /// Tracks assets that hold handles to the "key" asset ("runtime dependencies")
+    /// This should only be set when watching for changes to avoid unnecessary work.
+    pub(crate) dependants: HashMap<UntypedAssetId, HashSet<UntypedAssetId>>,
     /// Tracks living labeled assets for a given source asset.
     /// This should only be set when watching for changes to avoid unnecessary work.
     pub(crate) living_labeled_assets: HashMap<AssetPath<'static>, HashSet<Box<str>>>,
@@ -76,6 +90,7 @@ pub(crate) struct AssetInfos {
     pub(crate) dependency_loaded_event_sender: TypeIdMap<fn(&mut World, UntypedAssetId)>,
     pub(crate) dependency_failed_event_sender:
         TypeIdMap<fn(&mut World, UntypedAssetId, AssetPath<'static>, AssetLoadError)>,
+    pub(crate) modified_event_sender: TypeIdMap<fn(&mut World, UntypedAssetId)>,
 }
 
 impl std::fmt::Debug for AssetInfos {
@@ -357,12 +372,53 @@ impl AssetInfos {
         }
     }
 
+    /// Replaces the runtime dependencies of `id` with `dependencies`, updating
+    /// [`AssetInfos::dependants`].
+    fn update_dependants(&mut self, id: UntypedAssetId, dependencies: HashSet<UntypedAssetId>) {
+        let Some(info) = self.infos.get_mut(&id) else {
+            return;
+        };
+        for dependency in info.dependencies.difference(&dependencies) {
+            if let Entry::Occupied(mut entry) = self.dependants.entry(*dependency) {
+                entry.get_mut().remove(&id);
+                if entry.get().is_empty() {
+                    entry.remove();
+                }
+            }
+        }
+        for dependency in &dependencies {
+            self.dependants.entry(*dependency).or_default().insert(id);
+        }
+        info.dependencies = dependencies;
+    }
+
+    /// Returns the assets holding handles to the `reloaded` assets, directly or through other
+    /// assets, without the `reloaded` assets themselves.
+    pub(crate) fn collect_dependants(&self, reloaded: &[UntypedAssetId]) -> Vec<UntypedAssetId> {
+        let mut visited: HashSet<UntypedAssetId> = reloaded.iter().copied().collect();
+        let mut queue = reloaded.to_vec();
+        let mut dependants = Vec::new();
+        while let Some(id) = queue.pop() {
+            let Some(direct_dependants) = self.dependants.get(&id) else {
+                continue;
+            };
+            for dependant in direct_dependants {
+                if self.infos.contains_key(dependant) && visited.insert(*dependant) {
+                    dependants.push(*dependant);
+                    queue.push(*dependant);
+                }
+            }
+        }
+        dependants
+    }
+
     /// Returns `true` if the asset should be removed from the collection.
     pub(crate) fn process_handle_drop(&mut self, id: UntypedAssetId) -> bool {
         Self::process_handle_drop_internal(
             &mut self.infos,
             &mut self.path_to_id,
             &mut self.loader_dependants,
+            &mut self.dependants,
             &mut self.living_labeled_assets,
             self.watching_for_changes,
             id,
@@ -383,6 +439,9 @@ impl AssetInfos {
         }
 
         loaded_asset.value.insert(loaded_asset_id, world);
+        if self.watching_for_changes {
+            self.update_dependants(loaded_asset_id, loaded_asset.dependencies.clone());
+        }
         let mut loading_deps = loaded_asset.dependencies;
         let mut failed_deps = HashSet::new();
         let mut loading_rec_deps = loading_deps.clone();
@@ -462,7 +521,7 @@ impl AssetInfos {
                     for loader_dependency in loaded_asset.loader_dependencies.keys() {
                         let dependants = self
                             .loader_dependants
-                            .entry(loader_dependency.clone())
+                            .entry(loader_dependency.without_label().into_owned())
                             .or_default();
                         dependants.insert(asset_path.clone());
                     }
@@ -478,6 +537,9 @@ impl AssetInfos {
             info.load_state = LoadState::Loaded;
             info.dep_load_state = dep_load_state;
             info.rec_dep_load_state = rec_dep_load_state;
+            for waker in info.waiting_tasks.drain(..) {
+                waker.wake();
+            }
             if watching_for_changes {
                 info.loader_dependencies = loaded_asset.loader_dependencies;
             }
@@ -594,6 +656,9 @@ impl AssetInfos {
             info.load_state = LoadState::Failed(Box::new(error));
             info.dep_load_state = DependencyLoadState::Failed;
             info.rec_dep_load_state = RecursiveDependencyLoadState::Failed;
+            for waker in info.waiting_tasks.drain(..) {
+                waker.wake();
+            }
             (
                 std::mem::take(&mut info.dependants_waiting_on_load),
                 std::mem::take(&mut info.dependants_waiting_on_recursive_dep_load),
@@ -620,7 +685,9 @@ impl AssetInfos {
         living_labeled_assets: &mut HashMap<AssetPath<'static>, HashSet<Box<str>>>,
     ) {
         for loader_dependency in info.loader_dependencies.keys() {
-            if let Some(dependants) = loader_dependants.get_mut(loader_dependency) {
+            if let Some(dependants) =
+                loader_dependants.get_mut(&loader_dependency.without_label().into_owned())
+            {
                 dependants.remove(path);
             }
         }
@@ -646,6 +713,7 @@ impl AssetInfos {
         infos: &mut HashMap<UntypedAssetId, AssetInfo>,
         path_to_id: &mut HashMap<AssetPath<'static>, TypeIdMap<UntypedAssetId>>,
         loader_dependants: &mut HashMap<AssetPath<'static>, HashSet<AssetPath<'static>>>,
+        dependants: &mut HashMap<UntypedAssetId, HashSet<UntypedAssetId>>,
         living_labeled_assets: &mut HashMap<AssetPath<'static>, HashSet<Box<str>>>,
         watching_for_changes: bool,
         id: UntypedAssetId,
@@ -664,6 +732,18 @@ impl AssetInfos {
         let type_id = entry.key().type_id();
 
         let info = entry.remove();
+        // the waiting tasks see that the asset isn't tracked anymore
+        for waker in &info.waiting_tasks {
+            waker.wake_by_ref();
+        }
+        if watching_for_changes {
+            dependants.remove(&id);
+            for dependency in &info.dependencies {
+                if let Some(dependency_dependants) = dependants.get_mut(dependency) {
+                    dependency_dependants.remove(&id);
+                }
+            }
+        }
         let Some(path) = &info.path else {
             return true;
         };
@@ -702,6 +782,7 @@ impl AssetInfos {
                         &mut self.infos,
                         &mut self.path_to_id,
                         &mut self.loader_dependants,
+                        &mut self.dependants,
                         &mut self.living_labeled_assets,
                         self.watching_for_changes,
                         id.untyped(provider.type_id),
@@ -1,5 +1,7 @@
 mod info;
 mod loaders;
+mod saving;
+mod streaming;
 
 use crate::{
     folder::LoadedFolder,
@@ -13,9 +15,10 @@ use crate::{
         MetaTransform, Settings,
     },
     path::AssetPath,
-    Asset, AssetEvent, AssetHandleProvider, AssetId, AssetLoadFailedEvent, AssetMetaCheck, Assets,
-    DeserializeMetaError, ErasedLoadedAsset, Handle, LoadedUntypedAsset, UntypedAssetId,
-    UntypedAssetLoadFailedEvent, UntypedHandle,
+    Asset, AssetEvent, AssetHandleProvider, AssetId, AssetLoadFailedEvent, AssetMetaCheck,
+    AssetReloaded, AssetSaveFailedEvent, AssetSaved, Assets, DeserializeMetaError,
+    ErasedLoadedAsset, Handle, LoadedUntypedAsset, UntypedAssetId, UntypedAssetLoadFailedEvent,
+    UntypedHandle,
 };
 use bevy_ecs::prelude::*;
 use bevy_tasks::IoTaskPool;
@@ -23,14 +26,21 @@ use bevy_utils::tracing::{error, info};
 use bevy_utils::{CowArc, HashSet};
 use crossbeam_channel::{Receiver, Sender};
 use futures_lite::StreamExt;
+pub(crate) use info::AssetInfos;
 use info::*;
 use loaders::*;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
+use saving::AssetSaving;
 use std::future::Future;
+use std::task::Poll;
 use std::{any::Any, path::PathBuf};
 use std::{any::TypeId, path::Path, sync::Arc};
+use streaming::AssetStreaming;
 use thiserror::Error;
 
+pub use saving::{save_assets, AssetSaveError};
+pub use streaming::{update_asset_streaming, LoadPriority, StreamingBudget, StreamingState};
+
 // Needed for doc string
 #[allow(unused_imports)]
 use crate::io::{AssetReader, AssetWriter};
@@ -63,6 +73,8 @@ pub(crate) struct AssetServerData {
     sources: AssetSources,
     mode: AssetServerMode,
     meta_check: AssetMetaCheck,
+    streaming: Mutex<AssetStreaming>,
+    saving: Mutex<AssetSaving>,
 }
 
 /// The "asset mode" the server is currently in.
@@ -123,6 +135,8 @@ impl AssetServer {
                 asset_event_receiver,
                 loaders,
                 infos: RwLock::new(infos),
+                streaming: Default::default(),
+                saving: Default::default(),
             }),
         }
     }
@@ -168,12 +182,21 @@ impl AssetServer {
                 });
         }
 
+        fn modified_sender<A: Asset>(world: &mut World, id: UntypedAssetId) {
+            // getting the asset mutably sends `AssetEvent::Modified`
+            world.resource_mut::<Assets<A>>().get_mut(id.typed::<A>());
+        }
+
         let mut infos = self.data.infos.write();
 
         infos
             .dependency_loaded_event_sender
             .insert(TypeId::of::<A>(), sender::<A>);
 
+        infos
+            .modified_event_sender
+            .insert(TypeId::of::<A>(), modified_sender::<A>);
+
         infos
             .dependency_failed_event_sender
             .insert(TypeId::of::<A>(), failed_sender::<A>);
@@ -402,6 +425,75 @@ impl AssetServer {
         self.load_internal(None, path, false, None).await
     }
 
+    /// Loads the [`Asset`] of type `A` stored at `path`, like [`AssetServer::load`], and returns its
+    /// handle once the asset is in its [`Assets`] collection, or the error that prevented it from
+    /// loading.
+    ///
+    /// This can be awaited in tasks spawned on the [`IoTaskPool`] or the other task pools, for
+    /// example to build procedural content from the loaded assets. The assets are added to their
+    /// collections by the app, so the future only completes while the app keeps updating. The
+    /// dependencies of the asset may still be loading, see
+    /// [`AssetServer::is_loaded_with_dependencies`].
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub async fn load_async<'a, A: Asset>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+    ) -> Result<Handle<A>, AssetLoadError> {
+        let handle = self.load(path);
+        self.wait_for_asset(&handle).await?;
+        Ok(handle)
+    }
+
+    /// Waits for the asset with the given `id` to be loaded in its [`Assets`] collection, and
+    /// returns the error that prevented it from loading if it failed.
+    ///
+    /// Returns [`AssetLoadError::NotTracked`] if the asset isn't tracked by the [`AssetServer`],
+    /// like the assets added directly to their [`Assets`] collection, or if all of its strong
+    /// handles are dropped while waiting.
+    pub async fn wait_for_asset(
+        &self,
+        id: impl Into<UntypedAssetId>,
+    ) -> Result<(), AssetLoadError> {
+        let id = id.into();
+        std::future::poll_fn(|cx| {
+            let mut infos = self.data.infos.write();
+            let Some(info) = infos.get_mut(id) else {
+                return Poll::Ready(Err(AssetLoadError::NotTracked { id }));
+            };
+            match &info.load_state {
+                LoadState::Loaded => Poll::Ready(Ok(())),
+                LoadState::Failed(error) => Poll::Ready(Err((**error).clone())),
+                LoadState::NotLoaded | LoadState::Loading => {
+                    if !info
+                        .waiting_tasks
+                        .iter()
+                        .any(|waker| waker.will_wake(cx.waker()))
+                    {
+                        info.waiting_tasks.push(cx.waker().clone());
+                    }
+                    Poll::Pending
+                }
+            }
+        })
+        .await
+    }
+
+    /// Waits for all the assets with the given `ids` to be loaded, see
+    /// [`AssetServer::wait_for_asset`]. Returns the first error, if any of them failed to load.
+    ///
+    /// This is meant for loading screens, which can await all the assets of a level in a task
+    /// instead of checking their [`LoadState`] every frame.
+    pub async fn wait_for_all<I: Into<UntypedAssetId>>(
+        &self,
+        ids: impl IntoIterator<Item = I>,
+    ) -> Result<(), AssetLoadError> {
+        let ids: Vec<UntypedAssetId> = ids.into_iter().map(Into::into).collect();
+        for id in ids {
+            self.wait_for_asset(id).await?;
+        }
+        Ok(())
+    }
+
     pub(crate) fn load_untyped_with_meta_transform<'a>(
         &self,
         path: impl Into<AssetPath<'a>>,
@@ -612,7 +704,24 @@ impl AssetServer {
                     handle.unwrap()
                 };
 
+                let reloaded = force.then(|| {
+                    let (labels, mut ids): (Vec<_>, Vec<_>) = loaded_asset
+                        .labeled_assets
+                        .iter()
+                        .map(|(label, labeled_asset)| (label.clone(), labeled_asset.handle.id()))
+                        .unzip();
+                    ids.push(base_handle.id());
+                    InternalAssetEvent::Reloaded {
+                        path: base_path.clone(),
+                        labels,
+                        ids,
+                    }
+                });
                 self.send_loaded_asset(base_handle.id(), loaded_asset);
+                // this is sent after the loaded assets, so they are all updated when it's handled
+                if let Some(reloaded) = reloaded {
+                    self.send_asset_event(reloaded);
+                }
                 Ok(final_handle)
             }
             Err(err) => {
@@ -1192,6 +1301,9 @@ pub fn handle_internal_asset_events(world: &mut World) {
     world.resource_scope(|world, server: Mut<AssetServer>| {
         let mut infos = server.data.infos.write();
         let mut untyped_failures = vec![];
+        let mut reloaded_events = vec![];
+        let mut saved_events = vec![];
+        let mut save_failures = vec![];
         for event in server.data.asset_event_receiver.try_iter() {
             match event {
                 InternalAssetEvent::Loaded { id, loaded_asset } => {
@@ -1226,12 +1338,43 @@ pub fn handle_internal_asset_events(world: &mut World) {
                         .expect("Asset failed event sender should exist");
                     sender(world, id, path, error);
                 }
+                InternalAssetEvent::Reloaded { path, labels, ids } => {
+                    // The assets holding handles to the reloaded assets are modified too, so that
+                    // the data derived from them (like the bind groups of materials) is rebuilt
+                    let dependants = infos.collect_dependants(&ids);
+                    for id in &dependants {
+                        if let Some(sender) = infos.modified_event_sender.get(&id.type_id()) {
+                            sender(world, *id);
+                        }
+                    }
+                    reloaded_events.push(AssetReloaded {
+                        path,
+                        labels,
+                        dependants,
+                    });
+                }
+                InternalAssetEvent::Saved { id, path } => {
+                    saved_events.push(AssetSaved { id, path });
+                }
+                InternalAssetEvent::SaveFailed { id, path, error } => {
+                    error!("{}", error);
+                    save_failures.push(AssetSaveFailedEvent { id, path, error });
+                }
             }
         }
 
         if !untyped_failures.is_empty() {
             world.send_event_batch(untyped_failures);
         }
+        if !reloaded_events.is_empty() {
+            world.send_event_batch(reloaded_events);
+        }
+        if !saved_events.is_empty() {
+            world.send_event_batch(saved_events);
+        }
+        if !save_failures.is_empty() {
+            world.send_event_batch(save_failures);
+        }
 
         fn queue_ancestors(
             asset_path: &AssetPath,
@@ -1240,8 +1383,11 @@ pub fn handle_internal_asset_events(world: &mut World) {
         ) {
             if let Some(dependants) = infos.loader_dependants.get(asset_path) {
                 for dependant in dependants {
-                    paths_to_reload.insert(dependant.to_owned());
-                    queue_ancestors(dependant, infos, paths_to_reload);
+                    // reloading the file of a labeled dependant reloads all of its labeled assets
+                    let dependant = dependant.without_label().into_owned();
+                    if paths_to_reload.insert(dependant.clone()) {
+                        queue_ancestors(&dependant, infos, paths_to_reload);
+                    }
                 }
             }
         }
@@ -1324,6 +1470,20 @@ pub(crate) enum InternalAssetEvent {
         path: AssetPath<'static>,
         error: AssetLoadError,
     },
+    Reloaded {
+        path: AssetPath<'static>,
+        labels: Vec<CowArc<'static, str>>,
+        ids: Vec<UntypedAssetId>,
+    },
+    Saved {
+        id: UntypedAssetId,
+        path: AssetPath<'static>,
+    },
+    SaveFailed {
+        id: UntypedAssetId,
+        path: AssetPath<'static>,
+        error: AssetSaveError,
+    },
 }
 
 /// The load state of an asset.
@@ -1419,6 +1579,10 @@ pub enum AssetLoadError {
         label: String,
         all_labels: Vec<String>,
     },
+    #[error("The load of asset '{path}' was cancelled")]
+    Cancelled { path: AssetPath<'static> },
+    #[error("The asset {id} is not tracked by the asset server")]
+    NotTracked { id: UntypedAssetId },
 }
 
 #[derive(Error, Debug, Clone)]
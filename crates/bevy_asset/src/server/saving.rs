@@ -0,0 +1,203 @@
+use crate::{
+    io::{MissingAssetSourceError, MissingAssetWriterError},
+    path::AssetPath,
+    saver::{AssetSaver, TypedAssetSaver},
+    Asset, AssetId, AssetServer, Assets, UntypedAssetId,
+};
+use bevy_ecs::prelude::*;
+use bevy_tasks::{block_on, IoTaskPool};
+use bevy_utils::TypeIdMap;
+use std::{any::Any, any::TypeId, sync::Arc};
+use thiserror::Error;
+
+use super::InternalAssetEvent;
+
+/// An error that occurs when saving an asset with [`AssetServer::save`].
+#[derive(Error, Debug, Clone)]
+pub enum AssetSaveError {
+    #[error("The asset {0} does not exist")]
+    MissingAsset(UntypedAssetId),
+    #[error("No AssetSaver is registered for assets of type {type_name} that can save to {path}")]
+    MissingSaver {
+        type_name: &'static str,
+        path: AssetPath<'static>,
+    },
+    #[error(transparent)]
+    MissingAssetSource(#[from] MissingAssetSourceError),
+    #[error(transparent)]
+    MissingAssetWriter(#[from] MissingAssetWriterError),
+    #[error("Failed to save {path} with {saver}: {error}")]
+    SaverError {
+        path: AssetPath<'static>,
+        saver: &'static str,
+        error: Arc<dyn std::error::Error + Send + Sync + 'static>,
+    },
+    #[error("Failed to write {path}: {error}")]
+    WriterError {
+        path: AssetPath<'static>,
+        error: Arc<crate::io::AssetWriterError>,
+    },
+}
+
+struct PendingSave {
+    id: UntypedAssetId,
+    path: AssetPath<'static>,
+}
+
+/// The savers registered with [`AssetServer::register_saver`], and the saves waiting for their
+/// asset.
+#[derive(Default)]
+pub(crate) struct AssetSaving {
+    /// The `Vec<Arc<dyn TypedAssetSaver<A>>>` of each asset type.
+    savers: TypeIdMap<Box<dyn Any + Send + Sync>>,
+    pending: Vec<PendingSave>,
+}
+
+impl AssetServer {
+    /// Registers a new [`AssetSaver`], used by [`AssetServer::save`] to save the assets of type
+    /// [`AssetSaver::Asset`] to the extensions of the [`AssetSaver::OutputLoader`].
+    pub fn register_saver<S: AssetSaver>(&self, saver: S) {
+        let mut saving = self.data.saving.lock();
+        let savers = saving
+            .savers
+            .entry(TypeId::of::<S::Asset>())
+            .or_insert_with(|| Box::<Vec<Arc<dyn TypedAssetSaver<S::Asset>>>>::default());
+        savers
+            .downcast_mut::<Vec<Arc<dyn TypedAssetSaver<S::Asset>>>>()
+            .unwrap()
+            .push(Arc::new(saver));
+    }
+
+    /// Saves the [`Asset`] with the given `id` to `path`, with a registered [`AssetSaver`] whose
+    /// [`AssetSaver::OutputLoader`] supports the extension of `path`. The saved asset is loaded back
+    /// like the other assets, with the settings returned by the saver written to its meta file.
+    ///
+    /// This is meant for saving assets created or modified at runtime, like in editors and
+    /// procedural tools. The asset is encoded at the end of the frame, then written to the
+    /// [`AssetWriter`](crate::io::AssetWriter) of the source of `path` in the background.
+    /// [`AssetSaved`](crate::AssetSaved) is sent once it's written, or
+    /// [`AssetSaveFailedEvent`](crate::AssetSaveFailedEvent) if it couldn't be saved.
+    pub fn save<'a, A: Asset>(&self, id: impl Into<AssetId<A>>, path: impl Into<AssetPath<'a>>) {
+        self.data.saving.lock().pending.push(PendingSave {
+            id: id.into().untyped(),
+            path: path.into().into_owned(),
+        });
+    }
+
+    fn find_saver<A: Asset>(
+        &self,
+        path: &AssetPath<'static>,
+    ) -> Result<Arc<dyn TypedAssetSaver<A>>, AssetSaveError> {
+        let missing_saver = || AssetSaveError::MissingSaver {
+            type_name: std::any::type_name::<A>(),
+            path: path.clone(),
+        };
+        let extension = path.get_full_extension().ok_or_else(missing_saver)?;
+        let extensions: Vec<&str> = std::iter::once(extension.as_str())
+            .chain(AssetPath::iter_secondary_extensions(&extension))
+            .collect();
+
+        let saving = self.data.saving.lock();
+        let savers = saving
+            .savers
+            .get(&TypeId::of::<A>())
+            .and_then(|savers| savers.downcast_ref::<Vec<Arc<dyn TypedAssetSaver<A>>>>())
+            .ok_or_else(missing_saver)?;
+        let loaders = self.data.loaders.read();
+        // the last registered saver wins, like the loaders
+        savers
+            .iter()
+            .rev()
+            .find(|saver| {
+                let Some(super::MaybeAssetLoader::Ready(loader)) =
+                    loaders.get_by_name(saver.output_loader())
+                else {
+                    return false;
+                };
+                loader
+                    .extensions()
+                    .iter()
+                    .any(|loader_extension| extensions.contains(loader_extension))
+            })
+            .cloned()
+            .ok_or_else(missing_saver)
+    }
+
+    /// Encodes the asset with its saver, and starts writing it.
+    fn start_save<A: Asset>(
+        &self,
+        id: UntypedAssetId,
+        path: AssetPath<'static>,
+        asset: Option<&A>,
+    ) -> Result<(), AssetSaveError> {
+        let asset = asset.ok_or(AssetSaveError::MissingAsset(id))?;
+        let saver = self.find_saver::<A>(&path)?;
+        // check the writer now to not encode the asset for nothing
+        self.get_source(path.source())?.writer()?;
+
+        // the asset is borrowed from the world, so it's encoded right away
+        let mut bytes = Vec::new();
+        let meta = block_on(saver.save(&mut bytes, asset)).map_err(|error| {
+            AssetSaveError::SaverError {
+                path: path.clone(),
+                saver: saver.type_name(),
+                error: error.into(),
+            }
+        })?;
+
+        let server = self.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                let event = match server.write_saved_asset(&path, &bytes, &meta).await {
+                    Ok(()) => InternalAssetEvent::Saved { id, path },
+                    Err(error) => InternalAssetEvent::SaveFailed { id, path, error },
+                };
+                server.send_asset_event(event);
+            })
+            .detach();
+        Ok(())
+    }
+
+    async fn write_saved_asset(
+        &self,
+        path: &AssetPath<'static>,
+        bytes: &[u8],
+        meta: &[u8],
+    ) -> Result<(), AssetSaveError> {
+        let writer = self.get_source(path.source())?.writer()?;
+        let writer_error = |error| AssetSaveError::WriterError {
+            path: path.clone(),
+            error: Arc::new(error),
+        };
+        writer
+            .write_bytes(path.path(), bytes)
+            .await
+            .map_err(writer_error)?;
+        writer
+            .write_meta_bytes(path.path(), meta)
+            .await
+            .map_err(writer_error)
+    }
+}
+
+/// Starts the saves of the assets of type `A` requested with [`AssetServer::save`].
+pub fn save_assets<A: Asset>(server: Res<AssetServer>, assets: Res<Assets<A>>) {
+    let pending: Vec<PendingSave> = {
+        let mut saving = server.data.saving.lock();
+        if saving.pending.is_empty() {
+            return;
+        }
+        let (pending, others) = std::mem::take(&mut saving.pending)
+            .into_iter()
+            .partition(|save| save.id.type_id() == TypeId::of::<A>());
+        saving.pending = others;
+        pending
+    };
+
+    for PendingSave { id, path } in pending {
+        let asset = assets.get(id.typed::<A>());
+        if let Err(error) = server.start_save(id, path.clone(), asset) {
+            server.send_asset_event(InternalAssetEvent::SaveFailed { id, path, error });
+        }
+    }
+}
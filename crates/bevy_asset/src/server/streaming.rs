@@ -0,0 +1,377 @@
+use crate::{
+    path::AssetPath, Asset, AssetLoadError, AssetServer, Handle, UntypedAssetId, UntypedHandle,
+};
+use bevy_ecs::prelude::*;
+use bevy_tasks::IoTaskPool;
+use bevy_utils::{tracing::error, HashMap};
+use parking_lot::Mutex;
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll, Waker},
+};
+
+use super::{HandleLoadingMode, InternalAssetEvent};
+
+/// The priority of a streamed load, started with [`AssetServer::load_with_priority`].
+///
+/// The pending loads with the highest priority are started first, in the order they were requested.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LoadPriority {
+    /// Assets that can wait, like the assets of distant areas.
+    Low,
+    /// The default priority.
+    #[default]
+    Normal,
+    /// Assets that will be needed soon, like the assets near the camera.
+    High,
+    /// Assets that are needed right away, started before all the others.
+    Critical,
+}
+
+/// The budget of the [`AssetServer`] to start the streamed loads, set with
+/// [`AssetServer::set_streaming_budget`].
+///
+/// Limiting the number of loads running at once leaves IO and decoding time to the loads with the
+/// highest priority, and avoids hitches when a lot of assets are requested at once, like during level
+/// transitions. The loads started with [`AssetServer::load`] are not limited by the budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamingBudget {
+    /// The maximum number of streamed loads started per frame.
+    pub loads_per_frame: usize,
+    /// The maximum number of streamed loads running at once, including the paused ones.
+    pub concurrent_loads: usize,
+}
+
+impl Default for StreamingBudget {
+    fn default() -> Self {
+        Self {
+            loads_per_frame: 8,
+            concurrent_loads: 32,
+        }
+    }
+}
+
+/// The state of a streamed load, returned by [`AssetServer::streaming_state`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamingState {
+    /// The load is waiting for the budget to start.
+    Pending,
+    /// The load is running.
+    Loading,
+    /// The load is paused, with [`AssetServer::pause_load`].
+    Paused,
+}
+
+#[derive(Default)]
+struct LoadControl {
+    paused: AtomicBool,
+    cancelled: AtomicBool,
+    finished: AtomicBool,
+    waker: Mutex<Option<Waker>>,
+}
+
+impl LoadControl {
+    fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::Release);
+        if !paused {
+            self.wake();
+        }
+    }
+
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+        self.wake();
+    }
+
+    fn wake(&self) {
+        if let Some(waker) = self.waker.lock().take() {
+            waker.wake();
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Acquire)
+    }
+}
+
+/// A future that stops polling the load while it's paused, so it can only be paused between two IO
+/// operations, and drops it once it's cancelled.
+///
+/// The load is controlled through its [`LoadControl`] rather than its task, since the tasks of the
+/// single threaded task pool can't be polled or cancelled.
+struct PausableLoad<F> {
+    future: Pin<Box<F>>,
+    control: Arc<LoadControl>,
+}
+
+impl<F: Future<Output = ()>> Future for PausableLoad<F> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.control.cancelled.load(Ordering::Acquire) {
+            return Poll::Ready(());
+        }
+        if self.control.paused.load(Ordering::Acquire) {
+            *self.control.waker.lock() = Some(cx.waker().clone());
+            // check again in case the load was resumed or cancelled before the waker was stored
+            if self.control.paused.load(Ordering::Acquire)
+                && !self.control.cancelled.load(Ordering::Acquire)
+            {
+                return Poll::Pending;
+            }
+        }
+        let poll = self.future.as_mut().poll(cx);
+        if poll.is_ready() {
+            self.control.finished.store(true, Ordering::Release);
+        }
+        poll
+    }
+}
+
+struct PendingLoad {
+    handle: UntypedHandle,
+    path: AssetPath<'static>,
+    priority: LoadPriority,
+    paused: bool,
+    /// The order of the requests, to start the loads with the same priority in order.
+    sequence: u64,
+}
+
+struct RunningLoad {
+    path: AssetPath<'static>,
+    control: Arc<LoadControl>,
+}
+
+/// The streamed loads of an [`AssetServer`].
+#[derive(Default)]
+pub(crate) struct AssetStreaming {
+    budget: StreamingBudget,
+    pending: Vec<PendingLoad>,
+    running: HashMap<UntypedAssetId, RunningLoad>,
+    sequence: u64,
+}
+
+impl AssetServer {
+    /// Begins loading an [`Asset`] of type `A` stored at `path` with the given `priority`, when the
+    /// [`StreamingBudget`] allows it.
+    ///
+    /// This is meant for streaming the assets of open worlds, where the loads of the assets needed
+    /// first should not wait for the others. Streamed loads can be re-prioritized with
+    /// [`AssetServer::set_load_priority`], paused with [`AssetServer::pause_load`], and cancelled with
+    /// [`AssetServer::cancel_load`]. A pending load is dropped if its handle is dropped.
+    ///
+    /// Otherwise, this behaves like [`AssetServer::load`]. If the asset is already loading, the
+    /// priority is ignored.
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub fn load_with_priority<'a, A: Asset>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+        priority: LoadPriority,
+    ) -> Handle<A> {
+        let path = path.into().into_owned();
+        let (handle, should_load) = self.data.infos.write().get_or_create_path_handle::<A>(
+            path.clone(),
+            HandleLoadingMode::Request,
+            None,
+        );
+
+        if should_load {
+            let mut streaming = self.data.streaming.lock();
+            let sequence = streaming.sequence;
+            streaming.sequence += 1;
+            streaming.pending.push(PendingLoad {
+                handle: handle.clone().untyped(),
+                path,
+                priority,
+                paused: false,
+                sequence,
+            });
+        }
+
+        handle
+    }
+
+    /// Sets the [`StreamingBudget`] limiting how many streamed loads run at once.
+    pub fn set_streaming_budget(&self, budget: StreamingBudget) {
+        self.data.streaming.lock().budget = budget;
+    }
+
+    /// Returns the current [`StreamingBudget`].
+    pub fn streaming_budget(&self) -> StreamingBudget {
+        self.data.streaming.lock().budget
+    }
+
+    /// Changes the priority of a pending streamed load. Returns `false` if the load isn't pending.
+    pub fn set_load_priority(&self, id: impl Into<UntypedAssetId>, priority: LoadPriority) -> bool {
+        let id = id.into();
+        let mut streaming = self.data.streaming.lock();
+        match streaming
+            .pending
+            .iter_mut()
+            .find(|load| load.handle.id() == id)
+        {
+            Some(load) => {
+                load.priority = priority;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Pauses a streamed load. A pending load won't be started, and a running load won't do any more
+    /// IO until it's resumed with [`AssetServer::resume_load`].
+    ///
+    /// Returns `false` if the load isn't pending or running.
+    pub fn pause_load(&self, id: impl Into<UntypedAssetId>) -> bool {
+        self.set_load_paused(id.into(), true)
+    }
+
+    /// Resumes a streamed load paused with [`AssetServer::pause_load`].
+    ///
+    /// Returns `false` if the load isn't pending or running.
+    pub fn resume_load(&self, id: impl Into<UntypedAssetId>) -> bool {
+        self.set_load_paused(id.into(), false)
+    }
+
+    fn set_load_paused(&self, id: UntypedAssetId, paused: bool) -> bool {
+        let mut streaming = self.data.streaming.lock();
+        if let Some(load) = streaming.running.get(&id) {
+            load.control.set_paused(paused);
+            true
+        } else if let Some(load) = streaming
+            .pending
+            .iter_mut()
+            .find(|load| load.handle.id() == id)
+        {
+            load.paused = paused;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Cancels a pending or running streamed load. The asset fails to load with
+    /// [`AssetLoadError::Cancelled`].
+    ///
+    /// Returns `false` if the load isn't pending or running.
+    pub fn cancel_load(&self, id: impl Into<UntypedAssetId>) -> bool {
+        let id = id.into();
+        let path = {
+            let mut streaming = self.data.streaming.lock();
+            if let Some(index) = streaming
+                .pending
+                .iter()
+                .position(|load| load.handle.id() == id)
+            {
+                streaming.pending.remove(index).path
+            } else {
+                match streaming.running.remove(&id) {
+                    // a finished load already sent its result
+                    Some(load) if load.control.is_finished() => return false,
+                    Some(load) => {
+                        load.control.cancel();
+                        load.path
+                    }
+                    None => return false,
+                }
+            }
+        };
+        self.send_asset_event(InternalAssetEvent::Failed {
+            id,
+            path: path.clone(),
+            error: AssetLoadError::Cancelled { path },
+        });
+        true
+    }
+
+    /// Returns the [`StreamingState`] of a streamed load, or `None` if it isn't pending or running.
+    pub fn streaming_state(&self, id: impl Into<UntypedAssetId>) -> Option<StreamingState> {
+        let id = id.into();
+        let streaming = self.data.streaming.lock();
+        if let Some(load) = streaming.running.get(&id) {
+            if load.control.paused.load(Ordering::Acquire) {
+                Some(StreamingState::Paused)
+            } else {
+                Some(StreamingState::Loading)
+            }
+        } else {
+            streaming
+                .pending
+                .iter()
+                .find(|load| load.handle.id() == id)
+                .map(|load| {
+                    if load.paused {
+                        StreamingState::Paused
+                    } else {
+                        StreamingState::Pending
+                    }
+                })
+        }
+    }
+
+    /// Starts the pending streamed loads with the highest priority, within the [`StreamingBudget`].
+    pub(crate) fn update_streaming(&self) {
+        let mut streaming = self.data.streaming.lock();
+        let streaming = &mut *streaming;
+        streaming
+            .running
+            .retain(|_, load| !load.control.is_finished());
+        // drop the pending loads whose handles were all dropped
+        streaming.pending.retain(|load| match &load.handle {
+            UntypedHandle::Strong(handle) => Arc::strong_count(handle) > 1,
+            UntypedHandle::Weak(_) => false,
+        });
+
+        let available = streaming
+            .budget
+            .concurrent_loads
+            .saturating_sub(streaming.running.len())
+            .min(streaming.budget.loads_per_frame);
+        if available == 0 || streaming.pending.is_empty() {
+            return;
+        }
+        streaming.pending.sort_by(|a, b| {
+            b.priority
+                .cmp(&a.priority)
+                .then(a.sequence.cmp(&b.sequence))
+        });
+
+        let mut started = 0;
+        let mut index = 0;
+        while started < available && index < streaming.pending.len() {
+            if streaming.pending[index].paused {
+                index += 1;
+                continue;
+            }
+            let PendingLoad { handle, path, .. } = streaming.pending.remove(index);
+            let id = handle.id();
+            let control = Arc::new(LoadControl::default());
+            let server = self.clone();
+            let load_path = path.clone();
+            let load = PausableLoad {
+                future: Box::pin(async move {
+                    if let Err(err) = server
+                        .load_internal(Some(handle), load_path, false, None)
+                        .await
+                    {
+                        error!("{}", err);
+                    }
+                }),
+                control: control.clone(),
+            };
+            IoTaskPool::get().spawn(load).detach();
+            streaming.running.insert(id, RunningLoad { path, control });
+            started += 1;
+        }
+    }
+}
+
+/// Starts the streamed loads of the [`AssetServer`], see [`AssetServer::load_with_priority`].
+pub fn update_asset_streaming(server: Res<AssetServer>) {
+    server.update_streaming();
+}
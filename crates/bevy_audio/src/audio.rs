@@ -1,4 +1,4 @@
-use crate::{AudioSource, Decodable};
+use crate::{AudioSource, AudioTime, Decodable, LoopPoints};
 use bevy_asset::{Asset, Handle};
 use bevy_derive::Deref;
 use bevy_ecs::prelude::*;
@@ -70,6 +70,14 @@ pub struct PlaybackSettings {
     /// Optional scale factor applied to the positions of this audio source and the listener,
     /// overriding the default value configured on [`AudioPlugin::default_spatial_scale`](crate::AudioPlugin::default_spatial_scale).
     pub spatial_scale: Option<SpatialScale>,
+    /// The time of the [`AudioClock`](crate::AudioClock) to start playing at, to the sample.
+    ///
+    /// The sound starts right away when this is `None`, or when the time has already passed.
+    pub start_time: Option<AudioTime>,
+    /// The region of the sound repeated by [`PlaybackMode::Loop`], after an intro played once.
+    ///
+    /// The whole sound is repeated when this is `None`.
+    pub loop_points: Option<LoopPoints>,
 }
 
 impl Default for PlaybackSettings {
@@ -88,6 +96,8 @@ impl PlaybackSettings {
         paused: false,
         spatial: false,
         spatial_scale: None,
+        start_time: None,
+        loop_points: None,
     };
 
     /// Will play the associated audio source in a loop.
@@ -137,6 +147,18 @@ impl PlaybackSettings {
         self.spatial_scale = Some(spatial_scale);
         self
     }
+
+    /// Helper to start playing at a time of the [`AudioClock`](crate::AudioClock).
+    pub const fn start_at(mut self, time: AudioTime) -> Self {
+        self.start_time = Some(time);
+        self
+    }
+
+    /// Helper to repeat a region of the sound, after an intro played once.
+    pub const fn with_loop_points(mut self, loop_points: LoopPoints) -> Self {
+        self.loop_points = Some(loop_points);
+        self
+    }
 }
 
 /// Settings for the listener for spatial audio sources.
@@ -190,6 +212,46 @@ impl GlobalVolume {
     }
 }
 
+/// What happens to the playing audio while the app is in the background, see
+/// [`BackgroundAudioPolicy`].
+#[derive(Clone, Copy, Debug, Default, Reflect)]
+pub enum BackgroundAudio {
+    /// The audio keeps playing.
+    #[default]
+    Play,
+    /// The audio is paused, and resumed when the app is back in the foreground.
+    ///
+    /// The audio that was already paused stays paused.
+    Pause,
+    /// The volume of the audio is multiplied by this volume, and restored when the app is back in
+    /// the foreground.
+    Duck(Volume),
+}
+
+/// Use this [`Resource`] to choose what happens to the playing audio when the app loses focus or
+/// is suspended.
+///
+/// The app loses focus when none of its windows is focused. The changes made to the sinks while
+/// the app is in the background are overridden when it's back in the foreground.
+#[derive(Resource, Clone, Copy, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct BackgroundAudioPolicy {
+    /// What happens to the audio when the app loses focus.
+    pub focus_lost: BackgroundAudio,
+    /// What happens to the audio when the app is suspended, as reported by
+    /// [`AppLifecycle`](bevy_window::AppLifecycle).
+    pub suspended: BackgroundAudio,
+}
+
+impl Default for BackgroundAudioPolicy {
+    fn default() -> Self {
+        Self {
+            focus_lost: BackgroundAudio::Play,
+            suspended: BackgroundAudio::Pause,
+        }
+    }
+}
+
 /// A scale factor applied to the positions of audio sources and listeners for
 /// spatial audio.
 ///
@@ -1,16 +1,28 @@
 use crate::{
-    AudioSourceBundle, Decodable, DefaultSpatialScale, GlobalVolume, PlaybackMode,
-    PlaybackSettings, SpatialAudioSink, SpatialListener,
+    AudioBus, AudioEffectChain, AudioEffects, AudioSinkPlayback, AudioSourceBundle,
+    BackgroundAudio, BackgroundAudioPolicy, Decodable, DefaultSpatialScale, DopplerSettings,
+    GlobalVolume, MixerBuses, PlaybackMode, PlaybackSettings, SharedSpatial, SpatialAudioSink,
+    SpatialEmitter, SpatialListener, SpatialVelocity,
 };
 use bevy_asset::{Asset, Assets, Handle};
 use bevy_ecs::{prelude::*, system::SystemParam};
 use bevy_hierarchy::DespawnRecursiveExt;
 use bevy_math::Vec3;
 use bevy_transform::prelude::GlobalTransform;
-use bevy_utils::tracing::warn;
-use rodio::{OutputStream, OutputStreamHandle, Sink, Source, SpatialSink};
+use bevy_utils::{
+    tracing::{info, warn},
+    Duration, Instant,
+};
+use bevy_window::{AppLifecycle, Window};
+use rodio::{
+    cpal::traits::{DeviceTrait, HostTrait},
+    OutputStream, OutputStreamHandle, Sink, Source, SpatialSink,
+};
+
+use crate::{spatialize, AudioClock, AudioSink, LoopPointsSource, ScheduledSource};
 
-use crate::AudioSink;
+/// How often the default audio device is checked for changes.
+const DEVICE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
 
 /// Used internally to play audio on the current "audio device"
 ///
@@ -23,29 +35,52 @@ use crate::AudioSink;
 ///
 /// This is fine when initializing this once (as is default when adding this plugin),
 /// since the memory cost will be the same.
-/// However, repeatedly inserting this resource into the app will **leak more memory**.
+/// However, repeatedly inserting this resource into the app will **leak more memory**,
+/// which also happens each time the default audio device changes.
 #[derive(Resource)]
 pub(crate) struct AudioOutput {
     stream_handle: Option<OutputStreamHandle>,
+    /// The name of the device the stream plays on.
+    device_name: Option<String>,
 }
 
-impl Default for AudioOutput {
-    fn default() -> Self {
+impl AudioOutput {
+    /// Opens the default audio device, advancing the [`AudioClock`] as it plays.
+    fn new(audio_clock: &AudioClock) -> Self {
+        let device_name = default_device_name();
         if let Ok((stream, stream_handle)) = OutputStream::try_default() {
             // We leak `OutputStream` to prevent the audio from stopping.
             std::mem::forget(stream);
+            if let Err(err) = stream_handle.play_raw(audio_clock.source()) {
+                warn!("Failed to start the audio clock: {err}");
+            }
             Self {
                 stream_handle: Some(stream_handle),
+                device_name,
             }
         } else {
             warn!("No audio device found.");
             Self {
                 stream_handle: None,
+                device_name: None,
             }
         }
     }
 }
 
+impl FromWorld for AudioOutput {
+    fn from_world(world: &mut World) -> Self {
+        let audio_clock = world.get_resource_or_insert_with(AudioClock::default);
+        Self::new(&audio_clock)
+    }
+}
+
+fn default_device_name() -> Option<String> {
+    rodio::cpal::default_host()
+        .default_output_device()
+        .and_then(|device| device.name().ok())
+}
+
 /// Marker for internal use, to despawn entities when playback finishes.
 #[derive(Component)]
 pub struct PlaybackDespawnMarker;
@@ -56,7 +91,16 @@ pub struct PlaybackRemoveMarker;
 
 #[derive(SystemParam)]
 pub(crate) struct EarPositions<'w, 's> {
-    pub(crate) query: Query<'w, 's, (Entity, &'static GlobalTransform, &'static SpatialListener)>,
+    pub(crate) query: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static GlobalTransform,
+            &'static SpatialListener,
+            Option<&'static SpatialVelocity>,
+        ),
+    >,
 }
 impl<'w, 's> EarPositions<'w, 's> {
     /// Gets a set of transformed ear positions.
@@ -68,7 +112,7 @@ impl<'w, 's> EarPositions<'w, 's> {
             .query
             .iter()
             .next()
-            .map(|(_, transform, settings)| {
+            .map(|(_, transform, settings, _)| {
                 (
                     transform.transform_point(settings.left_ear_offset),
                     transform.transform_point(settings.right_ear_offset),
@@ -82,6 +126,15 @@ impl<'w, 's> EarPositions<'w, 's> {
         (left_ear, right_ear)
     }
 
+    /// Gets the velocity of the listener, for the doppler effect.
+    pub(crate) fn velocity(&self) -> Vec3 {
+        self.query
+            .iter()
+            .next()
+            .and_then(|(.., velocity)| velocity)
+            .map_or(Vec3::ZERO, |velocity| velocity.0)
+    }
+
     pub(crate) fn multiple_listeners(&self) -> bool {
         self.query.iter().len() > 1
     }
@@ -95,6 +148,7 @@ impl<'w, 's> EarPositions<'w, 's> {
 ///
 /// This system detects such entities, checks if their source asset
 /// data is available, and creates/inserts the sink.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
     audio_output: Res<AudioOutput>,
     audio_sources: Res<Assets<Source>>,
@@ -105,11 +159,17 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
             &Handle<Source>,
             &PlaybackSettings,
             Option<&GlobalTransform>,
+            Option<&AudioEffects>,
+            Option<&AudioBus>,
+            SpatialEmitter,
         ),
         (Without<AudioSink>, Without<SpatialAudioSink>),
     >,
+    mixer_buses: Res<MixerBuses>,
     ear_positions: EarPositions,
     default_spatial_scale: Res<DefaultSpatialScale>,
+    doppler: Res<DopplerSettings>,
+    audio_clock: Res<AudioClock>,
     mut commands: Commands,
 ) where
     f32: rodio::cpal::FromSample<Source::DecoderItem>,
@@ -119,10 +179,22 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
         return;
     };
 
-    for (entity, source_handle, settings, maybe_emitter_transform) in &query_nonplaying {
+    for (entity, source_handle, settings, maybe_emitter_transform, effects, bus, emitter) in
+        &query_nonplaying
+    {
         let Some(audio_source) = audio_sources.get(source_handle) else {
             continue;
         };
+        let mixer_bus = bus.and_then(|bus| {
+            let mixer_bus = mixer_buses.get(bus);
+            if mixer_bus.is_none() {
+                warn!(
+                    "The AudioMixer has no bus {:?}, playing the sound without it.",
+                    bus.0
+                );
+            }
+            mixer_bus
+        });
         // audio data is available (has loaded), begin playback and insert sink component
         if settings.spatial {
             let (left_ear, right_ear) = ear_positions.get();
@@ -138,18 +210,24 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
 
             let scale = settings.spatial_scale.unwrap_or(default_spatial_scale.0).0;
 
-            let emitter_translation = if let Some(emitter_transform) = maybe_emitter_transform {
-                (emitter_transform.translation() * scale).into()
-            } else {
+            let emitter_transform = maybe_emitter_transform.copied().unwrap_or_else(|| {
                 warn!("Spatial AudioBundle with no GlobalTransform component. Using zero.");
-                Vec3::ZERO.into()
-            };
+                GlobalTransform::IDENTITY
+            });
+            let spatialized = spatialize(
+                &emitter_transform,
+                &emitter,
+                (left_ear, right_ear),
+                ear_positions.velocity(),
+                scale,
+                &doppler,
+            );
 
             let sink = match SpatialSink::try_new(
                 stream_handle,
-                emitter_translation,
-                (left_ear * scale).into(),
-                (right_ear * scale).into(),
+                spatialized.emitter.into(),
+                spatialized.left_ear.into(),
+                spatialized.right_ear.into(),
             ) {
                 Ok(sink) => sink,
                 Err(err) => {
@@ -157,6 +235,8 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
                     continue;
                 }
             };
+            let effect_chain =
+                AudioEffectChain::new(effects, mixer_bus, Some(SharedSpatial::new(&spatialized)));
 
             sink.set_speed(settings.speed);
             sink.set_volume(settings.volume.0 * global_volume.volume.0);
@@ -165,28 +245,34 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
                 sink.pause();
             }
 
+            append_to_spatial_sink(
+                &sink,
+                ScheduledSource::new(
+                    effect_chain.apply(playback_source(audio_source, settings)),
+                    audio_clock.clone(),
+                    settings.start_time,
+                ),
+            );
+            let mut entity_commands = commands.entity(entity);
             match settings.mode {
-                PlaybackMode::Loop => {
-                    sink.append(audio_source.decoder().repeat_infinite());
-                    commands.entity(entity).insert(SpatialAudioSink { sink });
-                }
-                PlaybackMode::Once => {
-                    sink.append(audio_source.decoder());
-                    commands.entity(entity).insert(SpatialAudioSink { sink });
+                PlaybackMode::Loop | PlaybackMode::Once => {
+                    entity_commands.insert((SpatialAudioSink { sink }, effect_chain));
                 }
                 PlaybackMode::Despawn => {
-                    sink.append(audio_source.decoder());
-                    commands
-                        .entity(entity)
-                        // PERF: insert as bundle to reduce archetype moves
-                        .insert((SpatialAudioSink { sink }, PlaybackDespawnMarker));
+                    // PERF: insert as bundle to reduce archetype moves
+                    entity_commands.insert((
+                        SpatialAudioSink { sink },
+                        effect_chain,
+                        PlaybackDespawnMarker,
+                    ));
                 }
                 PlaybackMode::Remove => {
-                    sink.append(audio_source.decoder());
-                    commands
-                        .entity(entity)
-                        // PERF: insert as bundle to reduce archetype moves
-                        .insert((SpatialAudioSink { sink }, PlaybackRemoveMarker));
+                    // PERF: insert as bundle to reduce archetype moves
+                    entity_commands.insert((
+                        SpatialAudioSink { sink },
+                        effect_chain,
+                        PlaybackRemoveMarker,
+                    ));
                 }
             };
         } else {
@@ -197,6 +283,7 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
                     continue;
                 }
             };
+            let effect_chain = AudioEffectChain::new(effects, mixer_bus, None);
 
             sink.set_speed(settings.speed);
             sink.set_volume(settings.volume.0 * global_volume.volume.0);
@@ -205,34 +292,77 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
                 sink.pause();
             }
 
+            append_to_sink(
+                &sink,
+                ScheduledSource::new(
+                    effect_chain.apply(playback_source(audio_source, settings)),
+                    audio_clock.clone(),
+                    settings.start_time,
+                ),
+            );
+            let mut entity_commands = commands.entity(entity);
             match settings.mode {
-                PlaybackMode::Loop => {
-                    sink.append(audio_source.decoder().repeat_infinite());
-                    commands.entity(entity).insert(AudioSink { sink });
-                }
-                PlaybackMode::Once => {
-                    sink.append(audio_source.decoder());
-                    commands.entity(entity).insert(AudioSink { sink });
+                PlaybackMode::Loop | PlaybackMode::Once => {
+                    entity_commands.insert((AudioSink { sink }, effect_chain));
                 }
                 PlaybackMode::Despawn => {
-                    sink.append(audio_source.decoder());
-                    commands
-                        .entity(entity)
-                        // PERF: insert as bundle to reduce archetype moves
-                        .insert((AudioSink { sink }, PlaybackDespawnMarker));
+                    // PERF: insert as bundle to reduce archetype moves
+                    entity_commands.insert((
+                        AudioSink { sink },
+                        effect_chain,
+                        PlaybackDespawnMarker,
+                    ));
                 }
                 PlaybackMode::Remove => {
-                    sink.append(audio_source.decoder());
-                    commands
-                        .entity(entity)
-                        // PERF: insert as bundle to reduce archetype moves
-                        .insert((AudioSink { sink }, PlaybackRemoveMarker));
+                    // PERF: insert as bundle to reduce archetype moves
+                    entity_commands.insert((
+                        AudioSink { sink },
+                        effect_chain,
+                        PlaybackRemoveMarker,
+                    ));
                 }
             };
         }
     }
 }
 
+/// Builds the source of a sound, looping it as set up by its [`PlaybackSettings`].
+fn playback_source<Source: Decodable>(
+    audio_source: &Source,
+    settings: &PlaybackSettings,
+) -> Box<dyn rodio::Source<Item = f32> + Send>
+where
+    f32: rodio::cpal::FromSample<Source::DecoderItem>,
+{
+    let PlaybackMode::Loop = settings.mode else {
+        return Box::new(audio_source.decoder().convert_samples());
+    };
+    let loop_points = settings.loop_points.unwrap_or_default();
+    if let Some(decoder) = audio_source.looping_decoder(loop_points) {
+        Box::new(decoder.convert_samples())
+    } else if settings.loop_points.is_some() {
+        Box::new(LoopPointsSource::new(
+            audio_source.decoder().convert_samples(),
+            loop_points,
+        ))
+    } else {
+        Box::new(audio_source.decoder().repeat_infinite().convert_samples())
+    }
+}
+
+/// Appends a source to a sink.
+///
+/// This is a separate function so that the `f32: FromSample<Source::DecoderItem>` bound of the
+/// systems playing audio doesn't shadow the `f32: FromSample<f32>` implementation.
+fn append_to_sink(sink: &Sink, source: impl Source<Item = f32> + Send + 'static) {
+    sink.append(source);
+}
+
+/// Appends a source to a spatial sink, see [`append_to_sink`].
+fn append_to_spatial_sink(sink: &SpatialSink, source: impl Source<Item = f32> + Send + 'static) {
+    sink.append(source);
+}
+
 pub(crate) fn cleanup_finished_audio<T: Decodable + Asset>(
     mut commands: Commands,
     query_nonspatial_despawn: Query<
@@ -264,67 +394,171 @@ pub(crate) fn cleanup_finished_audio<T: Decodable + Asset>(
     }
     for (entity, sink) in &query_nonspatial_remove {
         if sink.sink.empty() {
-            commands
-                .entity(entity)
-                .remove::<(AudioSourceBundle<T>, AudioSink, PlaybackRemoveMarker)>();
+            commands.entity(entity).remove::<(
+                AudioSourceBundle<T>,
+                AudioSink,
+                AudioEffectChain,
+                PlaybackRemoveMarker,
+            )>();
         }
     }
     for (entity, sink) in &query_spatial_remove {
         if sink.sink.empty() {
-            commands
-                .entity(entity)
-                .remove::<(AudioSourceBundle<T>, SpatialAudioSink, PlaybackRemoveMarker)>();
+            commands.entity(entity).remove::<(
+                AudioSourceBundle<T>,
+                SpatialAudioSink,
+                AudioEffectChain,
+                PlaybackRemoveMarker,
+            )>();
         }
     }
 }
 
-/// Run Condition to only play audio if the audio output is available
-pub(crate) fn audio_output_available(audio_output: Res<AudioOutput>) -> bool {
-    audio_output.stream_handle.is_some()
-}
-
-/// Updates spatial audio sinks when emitter positions change.
-pub(crate) fn update_emitter_positions(
-    mut emitters: Query<
-        (&GlobalTransform, &SpatialAudioSink, &PlaybackSettings),
-        Or<(Changed<GlobalTransform>, Changed<PlaybackSettings>)>,
+/// Switches the [`AudioOutput`] to the default audio device when it changes, for example when
+/// headphones are unplugged.
+///
+/// The sinks of the previous device are removed, so the sounds are played again from the start
+/// on the new device, keeping their pause state and speed.
+pub(crate) fn update_audio_output(
+    mut commands: Commands,
+    mut audio_output: ResMut<AudioOutput>,
+    audio_clock: Res<AudioClock>,
+    mut last_check: Local<Option<Instant>>,
+    mut sinks: Query<
+        (
+            Entity,
+            &AudioSink,
+            &mut PlaybackSettings,
+            Option<&InBackground>,
+        ),
+        Without<SpatialAudioSink>,
     >,
-    default_spatial_scale: Res<DefaultSpatialScale>,
+    mut spatial_sinks: Query<(
+        Entity,
+        &SpatialAudioSink,
+        &mut PlaybackSettings,
+        Option<&InBackground>,
+    )>,
 ) {
-    for (transform, sink, settings) in emitters.iter_mut() {
-        let scale = settings.spatial_scale.unwrap_or(default_spatial_scale.0).0;
+    let now = Instant::now();
+    if last_check.is_some_and(|last_check| now - last_check < DEVICE_CHECK_INTERVAL) {
+        return;
+    }
+    *last_check = Some(now);
 
-        let translation = transform.translation() * scale;
-        sink.set_emitter_position(translation);
+    let device_name = default_device_name();
+    if device_name == audio_output.device_name {
+        return;
+    }
+    *audio_output = AudioOutput::new(&audio_clock);
+    if let Some(device_name) = &audio_output.device_name {
+        info!("Audio device changed to {device_name}.");
+    }
+
+    for (entity, sink, mut settings, background) in &mut sinks {
+        settings.paused = sink.is_paused() && !matches!(background, Some(InBackground::Paused));
+        settings.speed = sink.speed();
+        commands.entity(entity).remove::<(
+            AudioSink,
+            AudioEffectChain,
+            InBackground,
+            PlaybackDespawnMarker,
+            PlaybackRemoveMarker,
+        )>();
+    }
+    for (entity, sink, mut settings, background) in &mut spatial_sinks {
+        settings.paused = sink.is_paused() && !matches!(background, Some(InBackground::Paused));
+        settings.speed = sink.speed();
+        commands.entity(entity).remove::<(
+            SpatialAudioSink,
+            AudioEffectChain,
+            InBackground,
+            PlaybackDespawnMarker,
+            PlaybackRemoveMarker,
+        )>();
     }
 }
 
-/// Updates spatial audio sink ear positions when spatial listeners change.
-pub(crate) fn update_listener_positions(
-    mut emitters: Query<(&SpatialAudioSink, &PlaybackSettings)>,
-    changed_listener: Query<
-        (),
-        (
-            Or<(
-                Changed<SpatialListener>,
-                Changed<GlobalTransform>,
-                Changed<PlaybackSettings>,
-            )>,
-            With<SpatialListener>,
-        ),
-    >,
-    ear_positions: EarPositions,
-    default_spatial_scale: Res<DefaultSpatialScale>,
+/// Records how a sink was changed by the [`BackgroundAudioPolicy`], to restore it in the
+/// foreground.
+#[derive(Component)]
+pub(crate) enum InBackground {
+    /// The sink was paused.
+    Paused,
+    /// The sink was ducked, from this volume.
+    Ducked(f32),
+}
+
+/// Applies the [`BackgroundAudioPolicy`] to the sinks when the app loses focus or is suspended,
+/// and restores them when it's back in the foreground.
+pub(crate) fn update_background_audio(
+    mut commands: Commands,
+    policy: Res<BackgroundAudioPolicy>,
+    windows: Query<&Window>,
+    mut lifecycle: EventReader<AppLifecycle>,
+    mut suspended: Local<bool>,
+    sinks: Query<(Entity, &AudioSink, Option<&InBackground>)>,
+    spatial_sinks: Query<(Entity, &SpatialAudioSink, Option<&InBackground>)>,
 ) {
-    if !default_spatial_scale.is_changed() && changed_listener.is_empty() {
-        return;
+    for event in lifecycle.read() {
+        *suspended = matches!(event, AppLifecycle::WillSuspend | AppLifecycle::Suspended);
     }
+    let behavior = if *suspended {
+        Some(policy.suspended)
+    } else if !windows.is_empty() && !windows.iter().any(|window| window.focused) {
+        Some(policy.focus_lost)
+    } else {
+        None
+    };
 
-    let (left_ear, right_ear) = ear_positions.get();
+    for (entity, sink, background) in &sinks {
+        apply_background_audio(&mut commands, entity, sink, background, behavior);
+    }
+    for (entity, sink, background) in &spatial_sinks {
+        apply_background_audio(&mut commands, entity, sink, background, behavior);
+    }
+}
 
-    for (sink, settings) in emitters.iter_mut() {
-        let scale = settings.spatial_scale.unwrap_or(default_spatial_scale.0).0;
+fn apply_background_audio(
+    commands: &mut Commands,
+    entity: Entity,
+    sink: &impl AudioSinkPlayback,
+    background: Option<&InBackground>,
+    behavior: Option<BackgroundAudio>,
+) {
+    // The sink is already in the state of the behavior
+    match (background, behavior) {
+        (None, None | Some(BackgroundAudio::Play))
+        | (Some(InBackground::Paused), Some(BackgroundAudio::Pause))
+        | (Some(InBackground::Ducked(_)), Some(BackgroundAudio::Duck(_))) => return,
+        _ => {}
+    }
 
-        sink.set_ears_position(left_ear * scale, right_ear * scale);
+    // Restore the sink before applying the new behavior
+    if let Some(background) = background {
+        match background {
+            InBackground::Paused => sink.play(),
+            InBackground::Ducked(volume) => sink.set_volume(*volume),
+        }
+        commands.entity(entity).remove::<InBackground>();
     }
+
+    match behavior {
+        Some(BackgroundAudio::Pause) if !sink.is_paused() => {
+            sink.pause();
+            commands.entity(entity).insert(InBackground::Paused);
+        }
+        Some(BackgroundAudio::Duck(volume)) => {
+            commands
+                .entity(entity)
+                .insert(InBackground::Ducked(sink.volume()));
+            sink.set_volume(sink.volume() * volume.0);
+        }
+        _ => {}
+    }
+}
+
+/// Run Condition to only play audio if the audio output is available
+pub(crate) fn audio_output_available(audio_output: Res<AudioOutput>) -> bool {
+    audio_output.stream_handle.is_some()
 }
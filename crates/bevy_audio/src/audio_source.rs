@@ -1,8 +1,12 @@
+use crate::{AudioStream, LoopPoints, StreamingSettings};
 use bevy_asset::{
     io::{AsyncReadExt, Reader},
     Asset, AssetLoader, LoadContext,
 };
 use bevy_reflect::TypePath;
+use bevy_utils::Duration;
+use rodio::{source::SeekError, Source};
+use serde::{Deserialize, Serialize};
 use std::{io::Cursor, sync::Arc};
 
 /// A source of audio data
@@ -20,6 +24,12 @@ pub struct AudioSource {
     /// If the format used is not enabled,
     /// then this will panic with an `UnrecognizedFormat` error.
     pub bytes: Arc<[u8]>,
+    /// Streams the sound while it plays, decoding it in the background.
+    ///
+    /// This is usually set with the [`AudioLoaderSettings`] of the sound, and is best suited to
+    /// long sounds like music. When it's `None`, the sound is decoded on the audio thread, and a
+    /// looping sound is kept fully decoded in memory.
+    pub streaming: Option<StreamingSettings>,
 }
 
 impl AsRef<[u8]> for AudioSource {
@@ -39,21 +49,29 @@ impl AsRef<[u8]> for AudioSource {
 #[derive(Default)]
 pub struct AudioLoader;
 
+/// The settings of the [`AudioLoader`].
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct AudioLoaderSettings {
+    /// Streams the sound while it plays, see [`AudioSource::streaming`].
+    pub streaming: Option<StreamingSettings>,
+}
+
 impl AssetLoader for AudioLoader {
     type Asset = AudioSource;
-    type Settings = ();
+    type Settings = AudioLoaderSettings;
     type Error = std::io::Error;
 
     async fn load<'a>(
         &'a self,
         reader: &'a mut Reader<'_>,
-        _settings: &'a Self::Settings,
+        settings: &'a Self::Settings,
         _load_context: &'a mut LoadContext<'_>,
     ) -> Result<AudioSource, Self::Error> {
         let mut bytes = Vec::new();
         reader.read_to_end(&mut bytes).await?;
         Ok(AudioSource {
             bytes: bytes.into(),
+            streaming: settings.streaming,
         })
     }
 
@@ -93,14 +111,100 @@ pub trait Decodable: Send + Sync + 'static {
 
     /// Build and return a [`Self::Decoder`] of the implementing type
     fn decoder(&self) -> Self::Decoder;
+
+    /// Build and return a [`Self::Decoder`] which plays the sound up to the end of `loop_points`,
+    /// and then repeats their region forever.
+    ///
+    /// When this returns `None`, which it does by default, looping sounds repeat the samples of
+    /// [`Self::decoder`], which are kept in memory after they are first played.
+    fn looping_decoder(&self, _loop_points: LoopPoints) -> Option<Self::Decoder> {
+        None
+    }
+}
+
+/// The [`Decodable::Decoder`] of an [`AudioSource`].
+pub struct AudioSourceDecoder(AudioSourceDecoderKind);
+
+enum AudioSourceDecoderKind {
+    Decoder(Box<rodio::Decoder<Cursor<AudioSource>>>),
+    Stream(AudioStream),
+}
+
+impl Iterator for AudioSourceDecoder {
+    type Item = i16;
+
+    fn next(&mut self) -> Option<i16> {
+        match &mut self.0 {
+            AudioSourceDecoderKind::Decoder(decoder) => decoder.next(),
+            AudioSourceDecoderKind::Stream(stream) => stream.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.0 {
+            AudioSourceDecoderKind::Decoder(decoder) => decoder.size_hint(),
+            AudioSourceDecoderKind::Stream(stream) => stream.size_hint(),
+        }
+    }
+}
+
+impl Source for AudioSourceDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        match &self.0 {
+            AudioSourceDecoderKind::Decoder(decoder) => decoder.current_frame_len(),
+            AudioSourceDecoderKind::Stream(stream) => stream.current_frame_len(),
+        }
+    }
+
+    fn channels(&self) -> u16 {
+        match &self.0 {
+            AudioSourceDecoderKind::Decoder(decoder) => decoder.channels(),
+            AudioSourceDecoderKind::Stream(stream) => stream.channels(),
+        }
+    }
+
+    fn sample_rate(&self) -> u32 {
+        match &self.0 {
+            AudioSourceDecoderKind::Decoder(decoder) => decoder.sample_rate(),
+            AudioSourceDecoderKind::Stream(stream) => stream.sample_rate(),
+        }
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        match &self.0 {
+            AudioSourceDecoderKind::Decoder(decoder) => decoder.total_duration(),
+            AudioSourceDecoderKind::Stream(stream) => stream.total_duration(),
+        }
+    }
+
+    fn try_seek(&mut self, position: Duration) -> Result<(), SeekError> {
+        match &mut self.0 {
+            AudioSourceDecoderKind::Decoder(decoder) => decoder.try_seek(position),
+            AudioSourceDecoderKind::Stream(stream) => stream.try_seek(position),
+        }
+    }
 }
 
 impl Decodable for AudioSource {
-    type DecoderItem = <rodio::Decoder<Cursor<AudioSource>> as Iterator>::Item;
-    type Decoder = rodio::Decoder<Cursor<AudioSource>>;
+    type DecoderItem = i16;
+    type Decoder = AudioSourceDecoder;
 
     fn decoder(&self) -> Self::Decoder {
-        rodio::Decoder::new(Cursor::new(self.clone())).unwrap()
+        AudioSourceDecoder(match self.streaming {
+            Some(settings) => {
+                AudioSourceDecoderKind::Stream(AudioStream::new(self, settings, None))
+            }
+            None => AudioSourceDecoderKind::Decoder(Box::new(
+                rodio::Decoder::new(Cursor::new(self.clone())).unwrap(),
+            )),
+        })
+    }
+
+    fn looping_decoder(&self, loop_points: LoopPoints) -> Option<Self::Decoder> {
+        let settings = self.streaming?;
+        Some(AudioSourceDecoder(AudioSourceDecoderKind::Stream(
+            AudioStream::new(self, settings, Some(loop_points)),
+        )))
     }
 }
 
@@ -0,0 +1,346 @@
+use async_channel::{Receiver, Sender};
+use bevy_ecs::prelude::*;
+use bevy_reflect::prelude::*;
+use bevy_utils::tracing::{info, warn};
+use rodio::cpal::{
+    self,
+    traits::{DeviceTrait, HostTrait, StreamTrait},
+    FromSample, SampleFormat, SizedSample,
+};
+
+/// An audio input device, such as a microphone, which can be captured with [`AudioCapture`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Reflect)]
+pub struct AudioInputDevice {
+    /// The name of the device, used to select it in [`AudioCapture::device`].
+    pub name: String,
+    /// The number of channels captured from the device.
+    pub channels: u16,
+    /// The number of samples per second captured from the device, for each channel.
+    pub sample_rate: u32,
+}
+
+impl AudioInputDevice {
+    /// Returns the input devices available on the system.
+    ///
+    /// This queries the system, so it's best not to call it every frame.
+    pub fn available() -> Vec<Self> {
+        match cpal::default_host().input_devices() {
+            Ok(devices) => devices.filter_map(|device| Self::new(&device)).collect(),
+            Err(err) => {
+                warn!("Failed to list the audio input devices: {err}");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Returns the default input device of the system, if there is one.
+    pub fn system_default() -> Option<Self> {
+        Self::new(&cpal::default_host().default_input_device()?)
+    }
+
+    fn new(device: &cpal::Device) -> Option<Self> {
+        let config = device.default_input_config().ok()?;
+        Some(Self {
+            name: device.name().ok()?,
+            channels: config.channels(),
+            sample_rate: config.sample_rate().0,
+        })
+    }
+}
+
+/// Use this [`Resource`] to capture the sound of an input device, such as a microphone, which is
+/// sent as [`CapturedAudio`] events.
+///
+/// The capture is disabled by default, as it usually requires the permission of the user.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_audio::{AudioCapture, CapturedAudio};
+/// fn enable_microphone(mut capture: ResMut<AudioCapture>) {
+///     capture.enabled = true;
+/// }
+///
+/// fn react_to_voice(mut captured_audio: EventReader<CapturedAudio>) {
+///     for audio in captured_audio.read() {
+///         if audio.rms() > 0.1 {
+///             // Someone is speaking loudly.
+///         }
+///     }
+/// }
+/// ```
+#[derive(Resource, Clone, Debug, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct AudioCapture {
+    /// Whether the input device is captured.
+    pub enabled: bool,
+    /// The [name](AudioInputDevice::name) of the device to capture, or `None` for the default
+    /// input device of the system.
+    pub device: Option<String>,
+    /// The device being captured.
+    #[reflect(ignore)]
+    active_device: Option<AudioInputDevice>,
+}
+
+impl AudioCapture {
+    /// Returns the device being captured, or `None` when the capture is disabled or failed to
+    /// start.
+    pub fn active_device(&self) -> Option<&AudioInputDevice> {
+        self.active_device.as_ref()
+    }
+}
+
+/// The samples captured from the input device of the [`AudioCapture`] since the last frame.
+#[derive(Event, Clone, Debug)]
+pub struct CapturedAudio {
+    /// The number of channels of the samples.
+    pub channels: u16,
+    /// The number of samples per second, for each channel.
+    pub sample_rate: u32,
+    /// The interleaved samples, from -1.0 to 1.0.
+    pub samples: Vec<f32>,
+}
+
+impl CapturedAudio {
+    /// Returns the root mean square of the samples, a measure of their loudness from 0.0 to 1.0.
+    pub fn rms(&self) -> f32 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        let sum: f32 = self.samples.iter().map(|sample| sample * sample).sum();
+        (sum / self.samples.len() as f32).sqrt()
+    }
+
+    /// Returns the largest absolute value of the samples.
+    pub fn peak(&self) -> f32 {
+        self.samples
+            .iter()
+            .fold(0.0, |peak, sample| sample.abs().max(peak))
+    }
+}
+
+/// The number of buffers of captured samples kept while they aren't read, after which the new
+/// ones are dropped.
+const CAPTURE_BUFFERS: usize = 64;
+
+/// The stream capturing the input device of the [`AudioCapture`].
+///
+/// This isn't [`Send`] on every platform, so it's kept on the main thread.
+#[derive(Default)]
+pub(crate) struct AudioCaptureStream {
+    /// The stream of the device, capturing while it's kept.
+    stream: Option<cpal::Stream>,
+    /// The buffers of samples sent by the stream.
+    buffers: Option<Receiver<Vec<f32>>>,
+    /// The device requested when the stream was last started, or `None` when it was stopped.
+    requested: Option<Option<String>>,
+}
+
+impl AudioCaptureStream {
+    fn start(&mut self, name: Option<&str>) -> Option<AudioInputDevice> {
+        let host = cpal::default_host();
+        let device = match name {
+            Some(name) => host
+                .input_devices()
+                .ok()?
+                .find(|device| device.name().is_ok_and(|device_name| device_name == name)),
+            None => host.default_input_device(),
+        };
+        let Some(device) = device else {
+            match name {
+                Some(name) => warn!("No audio input device found named {name}."),
+                None => warn!("No audio input device found."),
+            }
+            return None;
+        };
+        let input_device = AudioInputDevice::new(&device)?;
+        let config = match device.default_input_config() {
+            Ok(config) => config,
+            Err(err) => {
+                warn!("Failed to configure the audio input device: {err}");
+                return None;
+            }
+        };
+        let (sender, receiver) = async_channel::bounded(CAPTURE_BUFFERS);
+        let stream_config = config.config();
+        let stream = match config.sample_format() {
+            SampleFormat::I8 => build_stream::<i8>(&device, &stream_config, sender),
+            SampleFormat::I16 => build_stream::<i16>(&device, &stream_config, sender),
+            SampleFormat::I32 => build_stream::<i32>(&device, &stream_config, sender),
+            SampleFormat::U8 => build_stream::<u8>(&device, &stream_config, sender),
+            SampleFormat::U16 => build_stream::<u16>(&device, &stream_config, sender),
+            SampleFormat::U32 => build_stream::<u32>(&device, &stream_config, sender),
+            SampleFormat::F32 => build_stream::<f32>(&device, &stream_config, sender),
+            SampleFormat::F64 => build_stream::<f64>(&device, &stream_config, sender),
+            format => {
+                warn!("Unsupported sample format of the audio input device: {format}");
+                return None;
+            }
+        };
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                warn!("Failed to capture the audio input device: {err}");
+                return None;
+            }
+        };
+        if let Err(err) = stream.play() {
+            warn!("Failed to start capturing the audio input device: {err}");
+            return None;
+        }
+        self.stream = Some(stream);
+        self.buffers = Some(receiver);
+        Some(input_device)
+    }
+}
+
+fn build_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    sender: Sender<Vec<f32>>,
+) -> Result<cpal::Stream, cpal::BuildStreamError>
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+{
+    device.build_input_stream(
+        config,
+        move |data: &[T], _| send_buffer(data, &sender),
+        |err| warn!("Error while capturing the audio input device: {err}"),
+        None,
+    )
+}
+
+/// Sends a buffer of samples captured by the audio thread, converted to `f32`.
+fn send_buffer<T>(data: &[T], sender: &Sender<Vec<f32>>)
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+{
+    let samples = data
+        .iter()
+        .map(|&sample| sample.to_sample::<f32>())
+        .collect();
+    // Drop the samples when they aren't read, rather than blocking the audio thread.
+    let _ = sender.try_send(samples);
+}
+
+/// Starts and stops capturing the input device of the [`AudioCapture`], and sends the captured
+/// samples as [`CapturedAudio`] events.
+pub(crate) fn update_audio_capture(
+    mut capture: ResMut<AudioCapture>,
+    mut capture_stream: NonSendMut<AudioCaptureStream>,
+    mut captured_audio: EventWriter<CapturedAudio>,
+) {
+    let requested = capture.enabled.then(|| capture.device.clone());
+    if capture.is_changed() && requested != capture_stream.requested {
+        capture_stream.stream = None;
+        capture_stream.buffers = None;
+        let active_device = requested
+            .as_ref()
+            .and_then(|name| capture_stream.start(name.as_deref()));
+        capture_stream.requested = requested;
+        if let Some(device) = &active_device {
+            info!("Capturing the audio input device {}.", device.name);
+        }
+        // Don't let this change trigger another update.
+        capture.bypass_change_detection().active_device = active_device;
+    }
+
+    let (Some(buffers), Some(device)) = (&capture_stream.buffers, &capture.active_device) else {
+        return;
+    };
+    let mut samples = Vec::new();
+    while let Ok(buffer) = buffers.try_recv() {
+        samples.extend(buffer);
+    }
+    if !samples.is_empty() {
+        captured_audio.send(CapturedAudio {
+            channels: device.channels,
+            sample_rate: device.sample_rate,
+            samples,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        send_buffer, update_audio_capture, AudioCapture, AudioCaptureStream, AudioInputDevice,
+        CapturedAudio, CAPTURE_BUFFERS,
+    };
+    use bevy_ecs::{event::Events, prelude::*};
+
+    #[test]
+    fn converts_and_buffers_samples() {
+        let (sender, receiver) = async_channel::bounded(CAPTURE_BUFFERS);
+        send_buffer(&[i16::MIN, 0, i16::MAX], &sender);
+        send_buffer(&[0u8, 128, 255], &sender);
+        assert_eq!(
+            receiver.try_recv().unwrap(),
+            [-1.0, 0.0, i16::MAX as f32 / 32768.0]
+        );
+        assert_eq!(receiver.try_recv().unwrap(), [-1.0, 0.0, 127.0 / 128.0]);
+
+        // The buffers past the capacity are dropped while they aren't read.
+        for i in 0..CAPTURE_BUFFERS + 8 {
+            send_buffer(&[i as f32], &sender);
+        }
+        let buffers: Vec<Vec<f32>> = std::iter::from_fn(|| receiver.try_recv().ok()).collect();
+        assert_eq!(buffers.len(), CAPTURE_BUFFERS);
+        assert_eq!(buffers[0], [0.0]);
+        assert_eq!(buffers[CAPTURE_BUFFERS - 1], [(CAPTURE_BUFFERS - 1) as f32]);
+    }
+
+    #[test]
+    fn sends_captured_audio() {
+        let device = AudioInputDevice {
+            name: "microphone".to_string(),
+            channels: 2,
+            sample_rate: 44_100,
+        };
+        let (sender, receiver) = async_channel::bounded(CAPTURE_BUFFERS);
+        let mut world = World::new();
+        world.init_resource::<Events<CapturedAudio>>();
+        world.insert_resource(AudioCapture {
+            enabled: true,
+            device: Some(device.name.clone()),
+            active_device: Some(device.clone()),
+        });
+        // A stream already capturing the requested device.
+        world.insert_non_send_resource(AudioCaptureStream {
+            stream: None,
+            buffers: Some(receiver),
+            requested: Some(Some(device.name.clone())),
+        });
+        let update = world.register_system(update_audio_capture);
+
+        send_buffer(&[0.5f32, -0.5], &sender);
+        send_buffer(&[0.25f32, 0.0], &sender);
+        world.run_system(update).unwrap();
+        world.run_system(update).unwrap();
+
+        let events = world.resource::<Events<CapturedAudio>>();
+        let captured: Vec<_> = events.get_reader().read(events).cloned().collect();
+        assert_eq!(captured.len(), 1);
+        assert_eq!(captured[0].channels, 2);
+        assert_eq!(captured[0].sample_rate, 44_100);
+        assert_eq!(captured[0].samples, [0.5, -0.5, 0.25, 0.0]);
+        assert_eq!(captured[0].peak(), 0.5);
+        assert_eq!(captured[0].rms(), 0.375);
+        assert_eq!(
+            world.resource::<AudioCapture>().active_device(),
+            Some(&device)
+        );
+    }
+
+    #[test]
+    fn silence_has_no_loudness() {
+        let silence = CapturedAudio {
+            channels: 1,
+            sample_rate: 48_000,
+            samples: Vec::new(),
+        };
+        assert_eq!(silence.rms(), 0.0);
+        assert_eq!(silence.peak(), 0.0);
+    }
+}
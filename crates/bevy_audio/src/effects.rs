@@ -0,0 +1,757 @@
+use crate::{BusProcessor, SharedBus, SharedSpatial, SpatialProcessor};
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::prelude::*;
+use bevy_reflect::prelude::*;
+use rodio::{source::SeekError, Source};
+use std::{
+    f32::consts::TAU,
+    mem,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+/// The chain of effects applied, in order, to the sound of an audio entity.
+///
+/// The effects can be changed while the sound is playing, for example to smoothly lower the
+/// cutoff of a low-pass filter when the listener goes underwater. Changing the parameters of an
+/// effect keeps its state, so the sound doesn't click, while changing the kind of an effect at
+/// some position in the chain starts it anew.
+///
+/// ```
+/// # use bevy_audio::{AudioEffect, AudioEffects, EqBand};
+/// let effects = AudioEffects(vec![
+///     AudioEffect::LowPass { cutoff: 1200.0 },
+///     AudioEffect::Equalizer(vec![EqBand::new(100.0, 6.0)]),
+///     AudioEffect::Reverb {
+///         send: 0.3,
+///         room_size: 0.8,
+///         damping: 0.5,
+///     },
+/// ]);
+/// ```
+#[derive(Component, Clone, Debug, Default, PartialEq, Deref, DerefMut, Reflect)]
+#[reflect(Component, Default)]
+pub struct AudioEffects(pub Vec<AudioEffect>);
+
+/// An effect applied to the sound of an audio entity, in its [`AudioEffects`].
+#[derive(Clone, Debug, PartialEq, Reflect)]
+pub enum AudioEffect {
+    /// Attenuates the frequencies above the cutoff, muffling the sound.
+    LowPass {
+        /// The cutoff frequency, in hertz.
+        cutoff: f32,
+    },
+    /// Attenuates the frequencies below the cutoff, thinning the sound.
+    HighPass {
+        /// The cutoff frequency, in hertz.
+        cutoff: f32,
+    },
+    /// Boosts or cuts bands of frequencies.
+    Equalizer(Vec<EqBand>),
+    /// Adds the reverberation of a room to the sound.
+    Reverb {
+        /// The volume of the reverberation added to the sound, usually between `0.0` and `1.0`.
+        send: f32,
+        /// The size of the room, between `0.0` and `1.0`, the larger the longer the sound
+        /// reverberates.
+        room_size: f32,
+        /// How much the high frequencies of the reverberation are absorbed, between `0.0` and
+        /// `1.0`.
+        damping: f32,
+    },
+}
+
+/// A band of frequencies boosted or cut by an [`AudioEffect::Equalizer`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+pub struct EqBand {
+    /// The center frequency of the band, in hertz.
+    pub frequency: f32,
+    /// The gain applied to the band, in decibels. Negative gains cut the band.
+    pub gain: f32,
+    /// The quality factor of the band, the higher the narrower the band.
+    pub q: f32,
+}
+
+impl EqBand {
+    /// Creates a band of an octave and a half around `frequency`, with the given `gain` in
+    /// decibels.
+    pub fn new(frequency: f32, gain: f32) -> Self {
+        Self {
+            frequency,
+            gain,
+            q: 1.0,
+        }
+    }
+}
+
+/// A chain of effects shared with the audio thread.
+#[derive(Default)]
+pub(crate) struct SharedEffects {
+    effects: Mutex<Vec<AudioEffect>>,
+    /// Incremented each time the effects change.
+    version: AtomicU32,
+}
+
+impl SharedEffects {
+    pub(crate) fn set(&self, effects: &[AudioEffect]) {
+        effects.clone_into(&mut self.effects.lock().unwrap());
+        self.version.fetch_add(1, Ordering::Release);
+    }
+}
+
+/// The handle to the effects of a playing sound, inserted along with its sink.
+#[derive(Component, Clone)]
+pub(crate) struct AudioEffectChain {
+    effects: Arc<SharedEffects>,
+    /// The bus the sound is mixed into.
+    bus: Option<Arc<SharedBus>>,
+    /// How the sound of a spatial emitter is heard by the listener.
+    spatial: Option<Arc<SharedSpatial>>,
+}
+
+impl AudioEffectChain {
+    pub(crate) fn new(
+        effects: Option<&AudioEffects>,
+        bus: Option<Arc<SharedBus>>,
+        spatial: Option<SharedSpatial>,
+    ) -> Self {
+        let chain = Self {
+            effects: Arc::default(),
+            bus,
+            spatial: spatial.map(Arc::new),
+        };
+        if let Some(effects) = effects {
+            chain.set(effects);
+        }
+        chain
+    }
+
+    pub(crate) fn set(&self, effects: &[AudioEffect]) {
+        self.effects.set(effects);
+    }
+
+    pub(crate) fn spatial(&self) -> Option<&SharedSpatial> {
+        self.spatial.as_deref()
+    }
+
+    /// Wraps the source of the sound, to apply the effects, the spatial emitter and the bus to it.
+    pub(crate) fn apply<S>(&self, source: S) -> EffectChainSource<S>
+    where
+        S: Source<Item = f32>,
+    {
+        let spatial = self.spatial.clone().map(SpatialProcessor::new);
+        let bus = self.bus.clone().map(BusProcessor::new);
+        EffectChainSource {
+            input: source,
+            effects: EffectsProcessor::new(self.effects.clone()),
+            speed: spatial.as_ref().map_or(1.0, SpatialProcessor::speed)
+                * bus.as_ref().map_or(1.0, BusProcessor::speed),
+            spatial,
+            bus,
+            // Set up the effects for the format of the input on the first sample.
+            channels: 0,
+            sample_rate: 0,
+            channel: 0,
+        }
+    }
+}
+
+/// Applies a chain of [`SharedEffects`] on the audio thread.
+pub(crate) struct EffectsProcessor {
+    shared: Arc<SharedEffects>,
+    /// The version of the effects in use.
+    version: u32,
+    effects: Vec<AudioEffect>,
+    states: Vec<EffectState>,
+}
+
+impl EffectsProcessor {
+    pub(crate) fn new(shared: Arc<SharedEffects>) -> Self {
+        Self {
+            shared,
+            version: u32::MAX,
+            effects: Vec::new(),
+            states: Vec::new(),
+        }
+    }
+
+    /// Picks up the changes to the effects, and starts them anew when the format of the sound
+    /// changed.
+    pub(crate) fn update(&mut self, format_changed: bool, channels: u16, sample_rate: u32) {
+        let mut changed = format_changed;
+        if format_changed {
+            self.states.clear();
+        }
+
+        let version = self.shared.version.load(Ordering::Acquire);
+        if version != self.version {
+            // Never block the audio thread, the effects are picked up on a later frame otherwise.
+            if let Ok(effects) = self.shared.effects.try_lock() {
+                self.version = version;
+                effects.clone_into(&mut self.effects);
+                changed = true;
+            }
+        }
+        if !changed {
+            return;
+        }
+
+        self.states.truncate(self.effects.len());
+        for (index, effect) in self.effects.iter().enumerate() {
+            match self.states.get_mut(index) {
+                Some(state) => state.update(effect, channels, sample_rate),
+                None => self
+                    .states
+                    .push(EffectState::new(effect, channels, sample_rate)),
+            }
+        }
+    }
+
+    pub(crate) fn process(&mut self, sample: f32, channel: usize) -> f32 {
+        self.states
+            .iter_mut()
+            .fold(sample, |sample, state| state.process(sample, channel))
+    }
+}
+
+/// A gain moving smoothly towards its target on the audio thread, so that its changes don't
+/// click.
+pub(crate) struct SmoothedGain {
+    gain: f32,
+    /// How much of the way to the target is covered each frame.
+    smoothing: f32,
+}
+
+impl SmoothedGain {
+    /// The time constant of the smoothing, in seconds.
+    const TIME_CONSTANT: f32 = 0.01;
+
+    pub(crate) fn new(gain: f32) -> Self {
+        Self {
+            gain,
+            smoothing: 1.0,
+        }
+    }
+
+    /// Moves towards the target gain at the start of a frame.
+    pub(crate) fn update(&mut self, target: f32, format_changed: bool, sample_rate: u32) {
+        if format_changed {
+            self.smoothing = 1.0 - (-1.0 / (Self::TIME_CONSTANT * sample_rate as f32)).exp();
+        }
+        self.gain += (target - self.gain) * self.smoothing;
+    }
+
+    pub(crate) fn get(&self) -> f32 {
+        self.gain
+    }
+}
+
+/// Applies the effects of an [`AudioEffectChain`] to a source, followed by its spatial emitter
+/// and its bus.
+pub(crate) struct EffectChainSource<S> {
+    input: S,
+    effects: EffectsProcessor,
+    spatial: Option<SpatialProcessor>,
+    bus: Option<BusProcessor>,
+    channels: u16,
+    sample_rate: u32,
+    /// The channel of the next sample.
+    channel: u16,
+    /// The speed of the spatial emitter and of the bus, applied by changing the reported sample
+    /// rate.
+    speed: f32,
+}
+
+impl<S> EffectChainSource<S>
+where
+    S: Source<Item = f32>,
+{
+    /// Picks up the changes to the effects, to the spatial emitter, to the bus and to the format
+    /// of the input, at the start of a frame.
+    fn update(&mut self) {
+        let (channels, sample_rate) = (self.input.channels(), self.input.sample_rate());
+        let format_changed = channels != self.channels || sample_rate != self.sample_rate;
+        self.channels = channels;
+        self.sample_rate = sample_rate;
+
+        self.effects.update(format_changed, channels, sample_rate);
+        self.speed = 1.0;
+        if let Some(spatial) = &mut self.spatial {
+            self.speed *= spatial.update(format_changed, channels, sample_rate);
+        }
+        if let Some(bus) = &mut self.bus {
+            self.speed *= bus.update(format_changed, channels, sample_rate);
+        }
+    }
+}
+
+impl<S> Iterator for EffectChainSource<S>
+where
+    S: Source<Item = f32>,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.channel == 0 {
+            self.update();
+        }
+        let mut sample = self.input.next()?;
+        let channel = self.channel as usize;
+        sample = self.effects.process(sample, channel);
+        if let Some(spatial) = &mut self.spatial {
+            sample = spatial.process(sample, channel);
+        }
+        if let Some(bus) = &mut self.bus {
+            sample = bus.process(sample, channel);
+        }
+        self.channel = (self.channel + 1) % self.channels.max(1);
+        Some(sample)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<S> Source for EffectChainSource<S>
+where
+    S: Source<Item = f32>,
+{
+    fn current_frame_len(&self) -> Option<usize> {
+        self.input.current_frame_len()
+    }
+
+    fn channels(&self) -> u16 {
+        self.input.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        ((self.input.sample_rate() as f32 * self.speed) as u32).max(1)
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        self.channel = 0;
+        Ok(())
+    }
+}
+
+/// The state of an [`AudioEffect`] on the audio thread.
+enum EffectState {
+    Filter(Filter),
+    Equalizer(Vec<Filter>),
+    Reverb(Reverb),
+}
+
+impl EffectState {
+    fn new(effect: &AudioEffect, channels: u16, sample_rate: u32) -> Self {
+        let channels = channels as usize;
+        let sample_rate = sample_rate as f32;
+        match effect {
+            AudioEffect::LowPass { .. } | AudioEffect::HighPass { .. } => {
+                let mut filter = Filter::new(channels);
+                filter.set(effect, sample_rate);
+                Self::Filter(filter)
+            }
+            AudioEffect::Equalizer(bands) => Self::Equalizer(
+                bands
+                    .iter()
+                    .map(|band| {
+                        let mut filter = Filter::new(channels);
+                        filter.coefficients = Coefficients::peaking(band, sample_rate);
+                        filter
+                    })
+                    .collect(),
+            ),
+            AudioEffect::Reverb {
+                send,
+                room_size,
+                damping,
+            } => Self::Reverb(Reverb::new(
+                channels,
+                sample_rate,
+                *send,
+                *room_size,
+                *damping,
+            )),
+        }
+    }
+
+    /// Updates the parameters of the state, or starts it anew if the kind of the effect changed.
+    fn update(&mut self, effect: &AudioEffect, channels: u16, sample_rate: u32) {
+        match (&mut *self, effect) {
+            (Self::Filter(filter), AudioEffect::LowPass { .. } | AudioEffect::HighPass { .. }) => {
+                filter.set(effect, sample_rate as f32);
+            }
+            (Self::Equalizer(filters), AudioEffect::Equalizer(bands))
+                if filters.len() == bands.len() =>
+            {
+                for (filter, band) in filters.iter_mut().zip(bands) {
+                    filter.coefficients = Coefficients::peaking(band, sample_rate as f32);
+                }
+            }
+            (
+                Self::Reverb(reverb),
+                AudioEffect::Reverb {
+                    send,
+                    room_size,
+                    damping,
+                },
+            ) => reverb.set(*send, *room_size, *damping),
+            _ => *self = Self::new(effect, channels, sample_rate),
+        }
+    }
+
+    fn process(&mut self, sample: f32, channel: usize) -> f32 {
+        match self {
+            Self::Filter(filter) => filter.process(sample, channel),
+            Self::Equalizer(filters) => filters
+                .iter_mut()
+                .fold(sample, |sample, filter| filter.process(sample, channel)),
+            Self::Reverb(reverb) => reverb.process(sample, channel),
+        }
+    }
+}
+
+/// The coefficients of a biquad filter, normalized by `a0`.
+#[derive(Clone, Copy, Default)]
+struct Coefficients {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+}
+
+impl Coefficients {
+    /// The quality factor of the low and high pass filters, for a flat pass band.
+    const BUTTERWORTH_Q: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+    /// Returns the cosine and the `alpha` of the filters of the Audio EQ Cookbook.
+    fn angle(frequency: f32, q: f32, sample_rate: f32) -> (f32, f32) {
+        let frequency = frequency.clamp(10.0, sample_rate * 0.49);
+        let (sin, cos) = (TAU * frequency / sample_rate).sin_cos();
+        (cos, sin / (2.0 * q.max(0.01)))
+    }
+
+    fn low_pass(cutoff: f32, sample_rate: f32) -> Self {
+        let (cos, alpha) = Self::angle(cutoff, Self::BUTTERWORTH_Q, sample_rate);
+        Self::normalized(
+            [(1.0 - cos) / 2.0, 1.0 - cos, (1.0 - cos) / 2.0],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        )
+    }
+
+    fn high_pass(cutoff: f32, sample_rate: f32) -> Self {
+        let (cos, alpha) = Self::angle(cutoff, Self::BUTTERWORTH_Q, sample_rate);
+        Self::normalized(
+            [(1.0 + cos) / 2.0, -(1.0 + cos), (1.0 + cos) / 2.0],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        )
+    }
+
+    fn peaking(band: &EqBand, sample_rate: f32) -> Self {
+        let (cos, alpha) = Self::angle(band.frequency, band.q, sample_rate);
+        let amplitude = 10f32.powf(band.gain / 40.0);
+        Self::normalized(
+            [1.0 + alpha * amplitude, -2.0 * cos, 1.0 - alpha * amplitude],
+            [1.0 + alpha / amplitude, -2.0 * cos, 1.0 - alpha / amplitude],
+        )
+    }
+
+    fn normalized([b0, b1, b2]: [f32; 3], [a0, a1, a2]: [f32; 3]) -> Self {
+        Self {
+            b0: b0 / a0,
+            b1: b1 / a0,
+            b2: b2 / a0,
+            a1: a1 / a0,
+            a2: a2 / a0,
+        }
+    }
+}
+
+/// A biquad filter, in transposed direct form II, with a state per channel.
+struct Filter {
+    coefficients: Coefficients,
+    state: Vec<[f32; 2]>,
+}
+
+impl Filter {
+    fn new(channels: usize) -> Self {
+        Self {
+            coefficients: Coefficients::default(),
+            state: vec![[0.0; 2]; channels],
+        }
+    }
+
+    fn set(&mut self, effect: &AudioEffect, sample_rate: f32) {
+        self.coefficients = match effect {
+            AudioEffect::LowPass { cutoff } => Coefficients::low_pass(*cutoff, sample_rate),
+            AudioEffect::HighPass { cutoff } => Coefficients::high_pass(*cutoff, sample_rate),
+            _ => return,
+        };
+    }
+
+    fn process(&mut self, sample: f32, channel: usize) -> f32 {
+        let Coefficients { b0, b1, b2, a1, a2 } = self.coefficients;
+        let Some([z1, z2]) = self.state.get_mut(channel) else {
+            return sample;
+        };
+        let output = b0 * sample + *z1;
+        *z1 = b1 * sample - a1 * output + *z2;
+        *z2 = b2 * sample - a2 * output;
+        output
+    }
+}
+
+/// A reverberation in the style of Freeverb: parallel comb filters followed by allpass filters,
+/// with slightly longer delays on odd channels for a wider sound.
+struct Reverb {
+    channels: Vec<ReverbChannel>,
+    send: f32,
+    feedback: f32,
+    damping: f32,
+}
+
+struct ReverbChannel {
+    combs: Vec<Comb>,
+    allpasses: Vec<Delay>,
+}
+
+struct Delay {
+    buffer: Vec<f32>,
+    index: usize,
+}
+
+struct Comb {
+    delay: Delay,
+    filtered: f32,
+}
+
+impl Delay {
+    fn new(length: usize) -> Self {
+        Self {
+            buffer: vec![0.0; length.max(1)],
+            index: 0,
+        }
+    }
+
+    /// Writes a sample, and returns the sample written `length` samples ago.
+    fn push(&mut self, sample: f32) -> f32 {
+        let output = mem::replace(&mut self.buffer[self.index], sample);
+        self.index = (self.index + 1) % self.buffer.len();
+        output
+    }
+
+    fn last(&self) -> f32 {
+        self.buffer[self.index]
+    }
+}
+
+impl Reverb {
+    /// The lengths of the delays of the comb filters, in samples at 44.1 kHz.
+    const COMB_LENGTHS: [usize; 8] = [1116, 1188, 1277, 1356, 1422, 1491, 1557, 1617];
+    /// The lengths of the delays of the allpass filters, in samples at 44.1 kHz.
+    const ALLPASS_LENGTHS: [usize; 4] = [556, 441, 341, 225];
+    /// How much longer the delays of odd channels are, in samples at 44.1 kHz.
+    const STEREO_SPREAD: usize = 23;
+    /// The gain applied to the input of the combs, which add up.
+    const INPUT_GAIN: f32 = 0.015;
+    /// The gain applied to the reverberation.
+    const WET_GAIN: f32 = 3.0;
+
+    fn new(channels: usize, sample_rate: f32, send: f32, room_size: f32, damping: f32) -> Self {
+        let scale = sample_rate / 44_100.0;
+        let length = |length: usize, channel: usize| {
+            let spread = if channel % 2 == 1 {
+                Self::STEREO_SPREAD
+            } else {
+                0
+            };
+            ((length + spread) as f32 * scale) as usize
+        };
+        let mut reverb = Self {
+            channels: (0..channels)
+                .map(|channel| ReverbChannel {
+                    combs: Self::COMB_LENGTHS
+                        .iter()
+                        .map(|&comb| Comb {
+                            delay: Delay::new(length(comb, channel)),
+                            filtered: 0.0,
+                        })
+                        .collect(),
+                    allpasses: Self::ALLPASS_LENGTHS
+                        .iter()
+                        .map(|&allpass| Delay::new(length(allpass, channel)))
+                        .collect(),
+                })
+                .collect(),
+            send: 0.0,
+            feedback: 0.0,
+            damping: 0.0,
+        };
+        reverb.set(send, room_size, damping);
+        reverb
+    }
+
+    fn set(&mut self, send: f32, room_size: f32, damping: f32) {
+        self.send = send.max(0.0);
+        self.feedback = 0.7 + 0.28 * room_size.clamp(0.0, 1.0);
+        self.damping = 0.4 * damping.clamp(0.0, 1.0);
+    }
+
+    fn process(&mut self, sample: f32, channel: usize) -> f32 {
+        let Some(state) = self.channels.get_mut(channel) else {
+            return sample;
+        };
+        let input = sample * Self::INPUT_GAIN;
+        let mut wet = 0.0;
+        for comb in &mut state.combs {
+            let output = comb.delay.last();
+            comb.filtered = output * (1.0 - self.damping) + comb.filtered * self.damping;
+            comb.delay.push(input + comb.filtered * self.feedback);
+            wet += output;
+        }
+        for allpass in &mut state.allpasses {
+            let delayed = allpass.last();
+            allpass.push(wet + delayed * 0.5);
+            wet = delayed - wet;
+        }
+        sample + wet * Self::WET_GAIN * self.send
+    }
+}
+
+/// Applies the changes of the [`AudioEffects`] of the playing sounds to their effect chains.
+pub(crate) fn update_audio_effects(
+    changed_effects: Query<(&AudioEffectChain, &AudioEffects), Changed<AudioEffects>>,
+    chains: Query<&AudioEffectChain>,
+    mut removed_effects: RemovedComponents<AudioEffects>,
+) {
+    for (chain, effects) in &changed_effects {
+        chain.set(effects);
+    }
+    for entity in removed_effects.read() {
+        if let Ok(chain) = chains.get(entity) {
+            chain.set(&[]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AudioEffect, AudioEffectChain, AudioEffects, EqBand};
+    use rodio::buffer::SamplesBuffer;
+    use std::f32::consts::TAU;
+
+    const SAMPLE_RATE: u32 = 48_000;
+
+    fn apply(effects: Vec<AudioEffect>, samples: Vec<f32>) -> Vec<f32> {
+        AudioEffectChain::new(Some(&AudioEffects(effects)), None, None)
+            .apply(SamplesBuffer::new(1, SAMPLE_RATE, samples))
+            .collect()
+    }
+
+    fn sine(frequency: f32) -> Vec<f32> {
+        (0..SAMPLE_RATE / 10)
+            .map(|i| (TAU * frequency * i as f32 / SAMPLE_RATE as f32).sin())
+            .collect()
+    }
+
+    /// The peak amplitude of the second half of the samples, once the filters settled.
+    fn peak(samples: &[f32]) -> f32 {
+        samples[samples.len() / 2..]
+            .iter()
+            .fold(0.0, |peak, sample| sample.abs().max(peak))
+    }
+
+    #[test]
+    fn low_pass() {
+        let effects = vec![AudioEffect::LowPass { cutoff: 1000.0 }];
+        assert!((peak(&apply(effects.clone(), sine(100.0))) - 1.0).abs() < 0.02);
+        assert!(peak(&apply(effects.clone(), sine(10_000.0))) < 0.02);
+        // At the cutoff, the Butterworth response is 3 dB down.
+        let peak = peak(&apply(effects, sine(1000.0)));
+        assert!((peak - std::f32::consts::FRAC_1_SQRT_2).abs() < 0.02);
+    }
+
+    #[test]
+    fn high_pass() {
+        let effects = vec![AudioEffect::HighPass { cutoff: 1000.0 }];
+        assert!(peak(&apply(effects.clone(), sine(100.0))) < 0.02);
+        assert!((peak(&apply(effects, sine(10_000.0))) - 1.0).abs() < 0.02);
+    }
+
+    #[test]
+    fn equalizer() {
+        let band = EqBand::new(1000.0, 6.0);
+        let boosted = peak(&apply(
+            vec![AudioEffect::Equalizer(vec![band])],
+            sine(1000.0),
+        ));
+        assert!((boosted - 10f32.powf(6.0 / 20.0)).abs() < 0.02);
+
+        let cut = EqBand { gain: -6.0, ..band };
+        let cut = peak(&apply(
+            vec![AudioEffect::Equalizer(vec![cut])],
+            sine(1000.0),
+        ));
+        assert!((cut - 10f32.powf(-6.0 / 20.0)).abs() < 0.02);
+
+        // Far from the band, the sound isn't changed.
+        let far = peak(&apply(
+            vec![AudioEffect::Equalizer(vec![band])],
+            sine(15_000.0),
+        ));
+        assert!((far - 1.0).abs() < 0.02);
+
+        let flat = EqBand { gain: 0.0, ..band };
+        let samples = sine(440.0);
+        assert_eq!(
+            apply(vec![AudioEffect::Equalizer(vec![flat])], samples.clone()),
+            samples
+        );
+    }
+
+    #[test]
+    fn reverb() {
+        let mut impulse = vec![0.0; SAMPLE_RATE as usize];
+        impulse[0] = 1.0;
+        let reverb = |send| AudioEffect::Reverb {
+            send,
+            room_size: 0.8,
+            damping: 0.5,
+        };
+
+        assert_eq!(apply(vec![reverb(0.0)], impulse.clone()), impulse);
+
+        let output = apply(vec![reverb(0.5)], impulse);
+        assert_eq!(output[0], 1.0);
+        // The reverberation starts after the shortest comb filter delay, and then decays.
+        let first_echo = 1116 * SAMPLE_RATE as usize / 44_100;
+        assert!(output[1..first_echo].iter().all(|&sample| sample == 0.0));
+        let energy = |samples: &[f32]| samples.iter().map(|sample| sample * sample).sum::<f32>();
+        let early = energy(&output[first_echo..first_echo + 4800]);
+        let late = energy(&output[output.len() - 4800..]);
+        assert!(early > 0.0);
+        assert!(late < early);
+    }
+
+    #[test]
+    fn chain_is_applied_in_order() {
+        let effects = vec![
+            AudioEffect::LowPass { cutoff: 1000.0 },
+            AudioEffect::HighPass { cutoff: 1000.0 },
+        ];
+        // Both filters are 3 dB down at their cutoff.
+        let peak = peak(&apply(effects, sine(1000.0)));
+        assert!((peak - 0.5).abs() < 0.02);
+        assert_eq!(apply(Vec::new(), sine(1000.0)), sine(1000.0));
+    }
+}
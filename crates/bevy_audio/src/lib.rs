@@ -30,31 +30,48 @@
 mod audio;
 mod audio_output;
 mod audio_source;
+mod capture;
+mod effects;
+mod mixer;
 mod pitch;
+mod procedural;
+mod scheduling;
 mod sinks;
+mod spatial;
+mod streaming;
 
 #[allow(missing_docs)]
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        AudioBundle, AudioSink, AudioSinkPlayback, AudioSource, AudioSourceBundle, Decodable,
-        GlobalVolume, Pitch, PitchBundle, PlaybackSettings, SpatialAudioSink, SpatialListener,
+        AudioBundle, AudioBus, AudioClock, AudioEffect, AudioEffects, AudioMixer, AudioSink,
+        AudioSinkPlayback, AudioSource, AudioSourceBundle, AudioTime, BackgroundAudio,
+        BackgroundAudioPolicy, Decodable, GlobalVolume, Pitch, PitchBundle, PlaybackSettings,
+        SpatialAudioSink, SpatialListener,
     };
 }
 
 pub use audio::*;
 pub use audio_source::*;
+pub use capture::*;
+pub use effects::*;
+pub use mixer::*;
 pub use pitch::*;
+pub use procedural::*;
+pub use scheduling::*;
 
 pub use rodio::cpal::Sample as CpalSample;
-pub use rodio::source::Source;
+pub use rodio::source::{SeekError, Source};
 pub use rodio::Sample;
 pub use sinks::*;
+pub use spatial::*;
+pub use streaming::*;
 
 use bevy_app::prelude::*;
 use bevy_asset::{Asset, AssetApp};
 use bevy_ecs::prelude::*;
 use bevy_transform::TransformSystem;
+use bevy_window::AppLifecycle;
 
 use audio_output::*;
 
@@ -72,6 +89,10 @@ pub struct AudioPlugin {
     /// The scale factor applied to the positions of audio sources and listeners for
     /// spatial audio.
     pub default_spatial_scale: SpatialScale,
+    /// What happens to the audio when the app loses focus or is suspended.
+    pub background_policy: BackgroundAudioPolicy,
+    /// The buses the sounds are mixed into.
+    pub mixer: AudioMixer,
 }
 
 impl Plugin for AudioPlugin {
@@ -82,17 +103,49 @@ impl Plugin for AudioPlugin {
             .register_type::<DefaultSpatialScale>()
             .register_type::<PlaybackMode>()
             .register_type::<PlaybackSettings>()
+            .register_type::<BackgroundAudio>()
+            .register_type::<BackgroundAudioPolicy>()
+            .register_type::<AudioEffects>()
+            .register_type::<AudioBus>()
+            .register_type::<AudioMixer>()
+            .register_type::<SpatialAttenuation>()
+            .register_type::<SpatialCone>()
+            .register_type::<SpatialOcclusion>()
+            .register_type::<SpatialVelocity>()
+            .register_type::<DopplerSettings>()
+            .register_type::<Tempo>()
+            .register_type::<AudioCapture>()
             .insert_resource(self.global_volume)
             .insert_resource(DefaultSpatialScale(self.default_spatial_scale))
+            .insert_resource(self.background_policy)
+            .insert_resource(self.mixer.clone())
+            .init_resource::<MixerBuses>()
+            .init_resource::<DopplerSettings>()
+            .init_resource::<AudioCapture>()
+            .init_non_send_resource::<AudioCaptureStream>()
+            .add_event::<AppLifecycle>()
+            .add_event::<CapturedAudio>()
             .configure_sets(
                 PostUpdate,
                 AudioPlaySet
                     .run_if(audio_output_available)
                     .after(TransformSystem::TransformPropagate), // For spatial audio transforms
             )
+            .add_systems(PreUpdate, update_audio_capture)
             .add_systems(
                 PostUpdate,
-                (update_emitter_positions, update_listener_positions).in_set(AudioPlaySet),
+                (
+                    update_audio_output.before(AudioPlaySet),
+                    update_audio_mixer
+                        .after(update_audio_output)
+                        .before(AudioPlaySet),
+                    (
+                        update_spatial_audio,
+                        update_background_audio,
+                        update_audio_effects,
+                    )
+                        .in_set(AudioPlaySet),
+                ),
             )
             .init_resource::<AudioOutput>();
 
@@ -103,6 +156,7 @@ impl Plugin for AudioPlugin {
         }
 
         app.add_audio_source::<Pitch>();
+        app.add_audio_source::<ProceduralAudio>();
     }
 }
 
@@ -0,0 +1,426 @@
+use crate::{
+    AudioEffect, AudioSink, AudioSinkPlayback, EffectsProcessor, SharedEffects, SmoothedGain,
+    SpatialAudioSink, Volume,
+};
+use bevy_ecs::prelude::*;
+use bevy_reflect::prelude::*;
+use bevy_utils::{Duration, HashMap, HashSet, Instant};
+use std::{
+    borrow::Cow,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+};
+
+/// The bus of the [`AudioMixer`] the sound of an audio entity is mixed into.
+///
+/// The bus is read when the sound starts playing. Sounds without a bus are only affected by the
+/// [`GlobalVolume`](crate::GlobalVolume).
+#[derive(Component, Clone, Debug, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component, PartialEq, Hash)]
+pub struct AudioBus(pub Cow<'static, str>);
+
+impl AudioBus {
+    /// The bus of the music.
+    pub const MUSIC: Self = Self(Cow::Borrowed("music"));
+    /// The bus of the sound effects.
+    pub const SFX: Self = Self(Cow::Borrowed("sfx"));
+    /// The bus of the voices, such as dialogue.
+    pub const VOICE: Self = Self(Cow::Borrowed("voice"));
+
+    /// Creates a bus with the given name.
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        Self(name.into())
+    }
+}
+
+/// The settings of a bus of the [`AudioMixer`], applied to the sounds mixed into it while they
+/// play.
+#[derive(Clone, Debug, Reflect)]
+#[reflect(Default)]
+pub struct AudioBusSettings {
+    /// The volume of the sounds of the bus is multiplied by this volume.
+    pub volume: Volume,
+    /// The speed of the sounds of the bus is multiplied by this speed, which changes their pitch.
+    pub speed: f32,
+    /// The effects applied to each sound of the bus, after its own
+    /// [`AudioEffects`](crate::AudioEffects).
+    pub effects: Vec<AudioEffect>,
+    /// Lowers the volume of the bus while the sounds of another bus play.
+    pub ducking: Option<Ducking>,
+    /// The bus this bus is mixed into, to group buses, like the buses of the footsteps and of the
+    /// weapons in the bus of the sound effects.
+    ///
+    /// The volume, speed, ducking and effects of the parent bus, and of its own parent, also
+    /// apply to the sounds of this bus, after those of this bus.
+    pub parent: Option<AudioBus>,
+}
+
+impl Default for AudioBusSettings {
+    fn default() -> Self {
+        Self {
+            volume: Volume::default(),
+            speed: 1.0,
+            effects: Vec::new(),
+            ducking: None,
+            parent: None,
+        }
+    }
+}
+
+/// Lowers the volume of a bus while the sounds of another bus play, for example to lower the
+/// music while a character speaks.
+#[derive(Clone, Debug, Reflect)]
+pub struct Ducking {
+    /// The bus whose playing sounds duck this bus.
+    pub by: AudioBus,
+    /// The volume of the bus is multiplied by this volume while it's ducked.
+    pub volume: Volume,
+    /// How long the volume takes to go down when the sounds of the other bus start playing.
+    pub attack: Duration,
+    /// How long the volume takes to go back up when the sounds of the other bus stop playing.
+    pub release: Duration,
+}
+
+impl Ducking {
+    /// Ducks to the given `volume` while the sounds of the bus `by` play.
+    pub fn new(by: AudioBus, volume: Volume) -> Self {
+        Self {
+            by,
+            volume,
+            attack: Duration::from_millis(200),
+            release: Duration::from_millis(800),
+        }
+    }
+}
+
+/// Use this [`Resource`] to set up the buses the sounds are mixed into, with an [`AudioBus`].
+///
+/// Changes to the buses are applied to the sounds while they play.
+///
+/// ```
+/// # use bevy_audio::{AudioBus, AudioMixer, Ducking, Volume};
+/// let mut mixer = AudioMixer::default();
+/// mixer.bus_mut(&AudioBus::MUSIC).ducking = Some(Ducking::new(AudioBus::VOICE, Volume::new(0.3)));
+/// mixer.bus_mut(&AudioBus::SFX).volume = Volume::new(0.8);
+/// // The footsteps are also affected by the volume of the sound effects.
+/// mixer.bus_mut(&AudioBus::new("footsteps")).parent = Some(AudioBus::SFX);
+/// ```
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct AudioMixer {
+    /// The settings of the buses.
+    pub buses: HashMap<AudioBus, AudioBusSettings>,
+}
+
+impl Default for AudioMixer {
+    fn default() -> Self {
+        Self {
+            buses: [AudioBus::MUSIC, AudioBus::SFX, AudioBus::VOICE]
+                .into_iter()
+                .map(|bus| (bus, AudioBusSettings::default()))
+                .collect(),
+        }
+    }
+}
+
+impl AudioMixer {
+    /// Returns the settings of a bus, adding it if it's missing.
+    pub fn bus_mut(&mut self, bus: &AudioBus) -> &mut AudioBusSettings {
+        self.buses.entry(bus.clone()).or_default()
+    }
+
+    /// Returns a bus and its settings, followed by its parents, see [`AudioBusSettings::parent`].
+    ///
+    /// The chain ends at the first bus missing from the mixer, or at the first bus already in it.
+    pub fn bus_chain<'a>(
+        &'a self,
+        bus: &'a AudioBus,
+    ) -> impl Iterator<Item = (&'a AudioBus, &'a AudioBusSettings)> + 'a {
+        let mut next = Some(bus);
+        let mut visited = Vec::new();
+        std::iter::from_fn(move || {
+            let (bus, settings) = self.buses.get_key_value(next.take()?)?;
+            if visited.contains(&bus) {
+                return None;
+            }
+            visited.push(bus);
+            next = settings.parent.as_ref();
+            Some((bus, settings))
+        })
+    }
+
+    /// Returns the volume the sounds of a bus are multiplied by, the product of the volumes of
+    /// the bus and of its parents, without their ducking.
+    pub fn effective_volume(&self, bus: &AudioBus) -> Volume {
+        Volume(
+            self.bus_chain(bus)
+                .map(|(_, settings)| settings.volume.0)
+                .product(),
+        )
+    }
+}
+
+/// The state of a bus shared with the audio thread.
+pub(crate) struct SharedBus {
+    /// The bits of the gain applied to the sounds.
+    gain: AtomicU32,
+    /// The bits of the speed of the sounds.
+    speed: AtomicU32,
+    effects: Arc<SharedEffects>,
+}
+
+/// The state of the buses of the [`AudioMixer`].
+#[derive(Resource, Default)]
+pub(crate) struct MixerBuses(HashMap<AudioBus, MixerBus>);
+
+struct MixerBus {
+    shared: Arc<SharedBus>,
+    /// The effects last sent to the audio thread.
+    effects: Vec<AudioEffect>,
+    /// The current gain of the ducking of the bus.
+    ducking_gain: f32,
+}
+
+impl MixerBuses {
+    pub(crate) fn get(&self, bus: &AudioBus) -> Option<Arc<SharedBus>> {
+        self.0.get(bus).map(|bus| bus.shared.clone())
+    }
+}
+
+/// Applies the gain, speed and effects of a bus to a sound on the audio thread.
+pub(crate) struct BusProcessor {
+    shared: Arc<SharedBus>,
+    effects: EffectsProcessor,
+    gain: SmoothedGain,
+}
+
+impl BusProcessor {
+    pub(crate) fn new(shared: Arc<SharedBus>) -> Self {
+        Self {
+            effects: EffectsProcessor::new(shared.effects.clone()),
+            gain: SmoothedGain::new(f32::from_bits(shared.gain.load(Ordering::Relaxed))),
+            shared,
+        }
+    }
+
+    /// Picks up the changes to the bus at the start of a frame, and returns its speed.
+    pub(crate) fn update(&mut self, format_changed: bool, channels: u16, sample_rate: u32) -> f32 {
+        self.effects.update(format_changed, channels, sample_rate);
+        let gain = f32::from_bits(self.shared.gain.load(Ordering::Relaxed));
+        self.gain.update(gain, format_changed, sample_rate);
+        self.speed()
+    }
+
+    pub(crate) fn speed(&self) -> f32 {
+        f32::from_bits(self.shared.speed.load(Ordering::Relaxed))
+    }
+
+    pub(crate) fn process(&mut self, sample: f32, channel: usize) -> f32 {
+        self.effects.process(sample, channel) * self.gain.get()
+    }
+}
+
+/// Applies the changes of the [`AudioMixer`] and the ducking of its buses to the playing sounds.
+pub(crate) fn update_audio_mixer(
+    mixer: Res<AudioMixer>,
+    mut buses: ResMut<MixerBuses>,
+    mut last_update: Local<Option<Instant>>,
+    sinks: Query<(&AudioBus, Option<&AudioSink>, Option<&SpatialAudioSink>)>,
+) {
+    let now = Instant::now();
+    let delta = last_update.map_or(Duration::ZERO, |last_update| now - last_update);
+    *last_update = Some(now);
+
+    if mixer.is_changed() {
+        buses.0.retain(|bus, _| mixer.buses.contains_key(bus));
+        for (bus, settings) in &mixer.buses {
+            let state = buses.0.entry(bus.clone()).or_insert_with(|| MixerBus {
+                shared: Arc::new(SharedBus {
+                    gain: AtomicU32::new(settings.volume.0.to_bits()),
+                    speed: AtomicU32::new(1f32.to_bits()),
+                    effects: Arc::default(),
+                }),
+                effects: Vec::new(),
+                ducking_gain: 1.0,
+            });
+            let effects: Vec<AudioEffect> = mixer
+                .bus_chain(bus)
+                .flat_map(|(_, settings)| settings.effects.iter().cloned())
+                .collect();
+            if state.effects != effects {
+                state.shared.effects.set(&effects);
+                state.effects = effects;
+            }
+            let speed: f32 = mixer
+                .bus_chain(bus)
+                .map(|(_, settings)| settings.speed.max(0.01))
+                .product();
+            state.shared.speed.store(speed.to_bits(), Ordering::Relaxed);
+        }
+    }
+
+    let playing_buses: HashSet<&AudioBus> = sinks
+        .iter()
+        .filter(|(_, sink, spatial_sink)| {
+            let playing = |sink: &dyn AudioSinkPlayback| !sink.is_paused() && !sink.empty();
+            sink.is_some_and(|sink| playing(sink)) || spatial_sink.is_some_and(|sink| playing(sink))
+        })
+        .map(|(bus, ..)| bus)
+        .collect();
+
+    for (bus, settings) in &mixer.buses {
+        let Some(state) = buses.0.get_mut(bus) else {
+            continue;
+        };
+        let (target, duration) = match &settings.ducking {
+            Some(ducking) if playing_buses.contains(&ducking.by) => {
+                (ducking.volume.0, ducking.attack)
+            }
+            Some(ducking) => (1.0, ducking.release),
+            None => (1.0, Duration::ZERO),
+        };
+        if duration.is_zero() {
+            state.ducking_gain = target;
+        } else {
+            // Move at the speed covering the whole range of the ducking in its duration.
+            let range = settings
+                .ducking
+                .as_ref()
+                .map_or(1.0, |ducking| (1.0 - ducking.volume.0).abs())
+                .max(f32::EPSILON);
+            let step = range * delta.as_secs_f32() / duration.as_secs_f32();
+            let difference = target - state.ducking_gain;
+            state.ducking_gain += difference.clamp(-step, step);
+        }
+    }
+
+    for (bus, state) in &buses.0 {
+        let gain: f32 = mixer
+            .bus_chain(bus)
+            .map(|(bus, settings)| {
+                let ducking_gain = buses.0.get(bus).map_or(1.0, |state| state.ducking_gain);
+                settings.volume.0 * ducking_gain
+            })
+            .product();
+        state.shared.gain.store(gain.to_bits(), Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{update_audio_mixer, AudioBus, AudioMixer, Ducking, MixerBuses};
+    use crate::{AudioEffect, AudioSink, Volume};
+    use bevy_ecs::{prelude::*, system::RunSystemOnce};
+    use bevy_utils::Duration;
+    use rodio::{buffer::SamplesBuffer, Sink};
+    use std::sync::atomic::Ordering;
+
+    const FOOTSTEPS: AudioBus = AudioBus(std::borrow::Cow::Borrowed("footsteps"));
+
+    fn world(mixer: AudioMixer) -> World {
+        let mut world = World::new();
+        world.insert_resource(mixer);
+        world.init_resource::<MixerBuses>();
+        world
+    }
+
+    fn gain(world: &World, bus: &AudioBus) -> f32 {
+        let shared = world.resource::<MixerBuses>().get(bus).unwrap();
+        f32::from_bits(shared.gain.load(Ordering::Relaxed))
+    }
+
+    fn speed(world: &World, bus: &AudioBus) -> f32 {
+        let shared = world.resource::<MixerBuses>().get(bus).unwrap();
+        f32::from_bits(shared.speed.load(Ordering::Relaxed))
+    }
+
+    #[test]
+    fn nested_buses() {
+        let mut mixer = AudioMixer::default();
+        let sfx = mixer.bus_mut(&AudioBus::SFX);
+        sfx.volume = Volume::new(0.8);
+        sfx.speed = 2.0;
+        sfx.effects = vec![AudioEffect::LowPass { cutoff: 1000.0 }];
+        let footsteps = mixer.bus_mut(&FOOTSTEPS);
+        footsteps.volume = Volume::new(0.5);
+        footsteps.speed = 0.5;
+        footsteps.effects = vec![AudioEffect::HighPass { cutoff: 100.0 }];
+        footsteps.parent = Some(AudioBus::SFX);
+
+        let chain: Vec<_> = mixer.bus_chain(&FOOTSTEPS).map(|(bus, _)| bus).collect();
+        assert_eq!(chain, [&FOOTSTEPS, &AudioBus::SFX]);
+        assert!((mixer.effective_volume(&FOOTSTEPS).get() - 0.4).abs() < 1e-6);
+        assert_eq!(mixer.effective_volume(&AudioBus::SFX).get(), 0.8);
+
+        // A cycle ends the chain, instead of looping.
+        mixer.bus_mut(&AudioBus::SFX).parent = Some(FOOTSTEPS);
+        assert_eq!(mixer.bus_chain(&FOOTSTEPS).count(), 2);
+        mixer.bus_mut(&AudioBus::SFX).parent = None;
+
+        let mut world = world(mixer);
+        world.run_system_once(update_audio_mixer);
+        assert!((gain(&world, &FOOTSTEPS) - 0.4).abs() < 1e-6);
+        assert_eq!(gain(&world, &AudioBus::SFX), 0.8);
+        assert_eq!(speed(&world, &FOOTSTEPS), 1.0);
+        assert_eq!(speed(&world, &AudioBus::SFX), 2.0);
+        // The effects of the bus are applied before those of its parent.
+        assert_eq!(
+            world.resource::<MixerBuses>().0[&FOOTSTEPS].effects,
+            [
+                AudioEffect::HighPass { cutoff: 100.0 },
+                AudioEffect::LowPass { cutoff: 1000.0 }
+            ]
+        );
+
+        world
+            .resource_mut::<AudioMixer>()
+            .bus_mut(&AudioBus::SFX)
+            .volume = Volume::new(0.2);
+        world.run_system_once(update_audio_mixer);
+        assert!((gain(&world, &FOOTSTEPS) - 0.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn ducking() {
+        let mut mixer = AudioMixer::default();
+        mixer.bus_mut(&AudioBus::MUSIC).ducking = Some(Ducking {
+            attack: Duration::ZERO,
+            release: Duration::ZERO,
+            ..Ducking::new(AudioBus::VOICE, Volume::new(0.25))
+        });
+        mixer.bus_mut(&FOOTSTEPS).parent = Some(AudioBus::MUSIC);
+        let mut world = world(mixer);
+        let update = world.register_system(update_audio_mixer);
+
+        world.run_system(update).unwrap();
+        assert_eq!(gain(&world, &AudioBus::MUSIC), 1.0);
+
+        let (sink, _output) = Sink::new_idle();
+        sink.append(SamplesBuffer::new(1, 48_000, vec![0.0; 4800]));
+        let voice = world.spawn((AudioSink { sink }, AudioBus::VOICE)).id();
+        world.run_system(update).unwrap();
+        assert_eq!(gain(&world, &AudioBus::MUSIC), 0.25);
+        assert_eq!(gain(&world, &FOOTSTEPS), 0.25);
+        assert_eq!(gain(&world, &AudioBus::VOICE), 1.0);
+
+        // Paused sounds don't duck the other buses.
+        world.get::<AudioSink>(voice).unwrap().sink.pause();
+        world.run_system(update).unwrap();
+        assert_eq!(gain(&world, &AudioBus::MUSIC), 1.0);
+
+        // With an attack, the volume goes down over time instead of at once.
+        world.get::<AudioSink>(voice).unwrap().sink.play();
+        world
+            .resource_mut::<AudioMixer>()
+            .bus_mut(&AudioBus::MUSIC)
+            .ducking
+            .as_mut()
+            .unwrap()
+            .attack = Duration::from_secs(3600);
+        world.run_system(update).unwrap();
+        let gain = gain(&world, &AudioBus::MUSIC);
+        assert!(gain > 0.25 && gain <= 1.0);
+    }
+}
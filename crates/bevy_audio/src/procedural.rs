@@ -0,0 +1,195 @@
+use crate::{AudioSourceBundle, Decodable};
+use bevy_asset::Asset;
+use bevy_reflect::TypePath;
+use rodio::Source;
+use std::{fmt, sync::Arc, time::Duration};
+
+type Generator = Box<dyn FnMut() -> f32 + Send>;
+
+/// A source of audio synthesized on the fly, one sample after the other.
+///
+/// The samples are produced by a generator, created each time the sound starts playing. Helpers
+/// create the sources of common waveforms:
+///
+/// ```
+/// # use bevy_audio::ProceduralAudio;
+/// # use std::time::Duration;
+/// // A 440 Hz sine wave
+/// let note = ProceduralAudio::sine(440.0).with_duration(Duration::from_secs(1));
+/// // An engine hum, from a function of the time in seconds
+/// let engine = ProceduralAudio::from_fn(|time| {
+///     let wobble = 1.0 + 0.1 * (time * std::f32::consts::TAU * 3.0).sin();
+///     (time * std::f32::consts::TAU * 80.0 * wobble).sin() * 0.5
+/// });
+/// ```
+///
+/// The sound is played forever without a duration, which is the way to play a sound that's
+/// updated while it's playing, from state shared with the generator.
+#[derive(Asset, Clone, TypePath)]
+pub struct ProceduralAudio {
+    /// The number of samples per second.
+    pub sample_rate: u32,
+    /// The duration of the sound, or `None` to play it forever.
+    pub duration: Option<Duration>,
+    generator: Arc<dyn Fn(u32) -> Generator + Send + Sync>,
+}
+
+impl fmt::Debug for ProceduralAudio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProceduralAudio")
+            .field("sample_rate", &self.sample_rate)
+            .field("duration", &self.duration)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ProceduralAudio {
+    /// The default sample rate of the procedural sources.
+    pub const DEFAULT_SAMPLE_RATE: u32 = 44_100;
+
+    /// Creates a source from a function creating the generator of the samples, which is called
+    /// with the sample rate each time the sound starts playing.
+    ///
+    /// The generator returns the next sample each time it's called, between `-1.0` and `1.0`.
+    pub fn new<G>(generator: impl Fn(u32) -> G + Send + Sync + 'static) -> Self
+    where
+        G: FnMut() -> f32 + Send + 'static,
+    {
+        Self {
+            sample_rate: Self::DEFAULT_SAMPLE_RATE,
+            duration: None,
+            generator: Arc::new(move |sample_rate| Box::new(generator(sample_rate))),
+        }
+    }
+
+    /// Creates a source from a function of the time since the sound started playing, in seconds.
+    pub fn from_fn(f: impl Fn(f32) -> f32 + Send + Sync + 'static) -> Self {
+        let f = Arc::new(f);
+        Self::new(move |sample_rate| {
+            let f = f.clone();
+            let mut index = 0u64;
+            move || {
+                let time = index as f64 / sample_rate as f64;
+                index += 1;
+                f(time as f32)
+            }
+        })
+    }
+
+    /// Creates a sine wave of the given `frequency`, in hertz.
+    pub fn sine(frequency: f32) -> Self {
+        Self::new(move |sample_rate| {
+            let mut phase = 0.0f32;
+            move || {
+                let sample = (phase * std::f32::consts::TAU).sin();
+                phase = (phase + frequency / sample_rate as f32).fract();
+                sample
+            }
+        })
+    }
+
+    /// Creates white noise.
+    pub fn noise() -> Self {
+        Self::new(|_| {
+            // xorshift32, the noise doesn't need a better generator
+            let mut state = 0x9E37_79B9u32;
+            move || {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as f32 / u32::MAX as f32 * 2.0 - 1.0
+            }
+        })
+    }
+
+    /// Creates a source looping over a single period of a waveform, the `table`, at the given
+    /// `frequency` in hertz.
+    ///
+    /// The samples between the values of the table are linearly interpolated.
+    pub fn wavetable(table: impl Into<Arc<[f32]>>, frequency: f32) -> Self {
+        let table: Arc<[f32]> = table.into();
+        Self::new(move |sample_rate| {
+            let table = table.clone();
+            let step = frequency * table.len() as f32 / sample_rate as f32;
+            let mut position = 0.0f32;
+            move || {
+                if table.is_empty() {
+                    return 0.0;
+                }
+                let index = position as usize;
+                let next = table[(index + 1) % table.len()];
+                let sample = table[index] + (next - table[index]) * position.fract();
+                position = (position + step) % table.len() as f32;
+                sample
+            }
+        })
+    }
+
+    /// Sets the sample rate of the source.
+    pub fn with_sample_rate(mut self, sample_rate: u32) -> Self {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    /// Stops the sound after the `duration`, instead of playing it forever.
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+}
+
+/// The [`Source`] playing a [`ProceduralAudio`].
+pub struct ProceduralDecoder {
+    generator: Generator,
+    sample_rate: u32,
+    duration: Option<Duration>,
+    remaining_samples: Option<u64>,
+}
+
+impl Iterator for ProceduralDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(remaining_samples) = &mut self.remaining_samples {
+            *remaining_samples = remaining_samples.checked_sub(1)?;
+        }
+        Some((self.generator)())
+    }
+}
+
+impl Source for ProceduralDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.duration
+    }
+}
+
+impl Decodable for ProceduralAudio {
+    type DecoderItem = f32;
+    type Decoder = ProceduralDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        ProceduralDecoder {
+            generator: (self.generator)(self.sample_rate),
+            sample_rate: self.sample_rate,
+            duration: self.duration,
+            remaining_samples: self
+                .duration
+                .map(|duration| (duration.as_secs_f64() * self.sample_rate as f64) as u64),
+        }
+    }
+}
+
+/// Bundle for playing a procedural sound
+pub type ProceduralAudioBundle = AudioSourceBundle<ProceduralAudio>;
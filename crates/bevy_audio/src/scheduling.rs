@@ -0,0 +1,498 @@
+use bevy_ecs::prelude::*;
+use bevy_reflect::prelude::*;
+use bevy_utils::Duration;
+use rodio::{source::SeekError, Source};
+use std::{
+    ops::{Add, Sub},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+/// A time on the [`AudioClock`], measured since the audio output started.
+///
+/// Unlike the time of the frames, which depends on when they happen to run, this is the time of
+/// the samples sent to the audio device, so sounds started at an [`AudioTime`] stay in sync with
+/// each other to the sample.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+pub struct AudioTime(Duration);
+
+impl AudioTime {
+    /// The time the audio output started.
+    pub const ZERO: Self = Self(Duration::ZERO);
+
+    /// Creates an audio time from the duration since the audio output started.
+    pub const fn from_duration(duration: Duration) -> Self {
+        Self(duration)
+    }
+
+    /// Creates an audio time from the seconds since the audio output started.
+    pub fn from_secs_f64(seconds: f64) -> Self {
+        Self(Duration::from_secs_f64(seconds.max(0.0)))
+    }
+
+    /// Returns the duration since the audio output started.
+    pub const fn as_duration(self) -> Duration {
+        self.0
+    }
+
+    /// Returns the seconds since the audio output started.
+    pub fn as_secs_f64(self) -> f64 {
+        self.0.as_secs_f64()
+    }
+}
+
+impl Add<Duration> for AudioTime {
+    type Output = Self;
+
+    fn add(self, duration: Duration) -> Self {
+        Self(self.0 + duration)
+    }
+}
+
+impl Sub<Duration> for AudioTime {
+    type Output = Self;
+
+    fn sub(self, duration: Duration) -> Self {
+        Self(self.0.saturating_sub(duration))
+    }
+}
+
+impl Sub for AudioTime {
+    type Output = Duration;
+
+    /// Returns the duration between two times, or zero if `other` is later.
+    fn sub(self, other: Self) -> Duration {
+        self.0.saturating_sub(other.0)
+    }
+}
+
+/// Use this [`Resource`] to read the time of the audio output, to schedule sounds with
+/// [`PlaybackSettings::start_at`](crate::PlaybackSettings::start_at).
+///
+/// The clock advances with the samples mixed for the audio device, and keeps running while the
+/// sounds are paused. Sounds must be scheduled a bit ahead of [`now`](Self::now), at least the
+/// duration of a frame, to start exactly on time.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_asset::AssetServer;
+/// # use bevy_audio::{AudioBundle, AudioClock, PlaybackSettings};
+/// # use bevy_utils::Duration;
+/// fn play_in_half_a_second(mut commands: Commands, clock: Res<AudioClock>, asset_server: Res<AssetServer>) {
+///     commands.spawn(AudioBundle {
+///         source: asset_server.load("sounds/kick.ogg"),
+///         settings: PlaybackSettings::DESPAWN.start_at(clock.now() + Duration::from_millis(500)),
+///     });
+/// }
+/// ```
+#[derive(Resource, Clone, Default)]
+pub struct AudioClock {
+    /// The number of samples mixed by the [`ClockSource`].
+    samples: Arc<AtomicU64>,
+}
+
+impl AudioClock {
+    /// Returns the current time of the audio output.
+    pub fn now(&self) -> AudioTime {
+        let samples = self.samples.load(Ordering::Relaxed);
+        AudioTime(Duration::from_secs_f64(
+            samples as f64 / ClockSource::SAMPLE_RATE as f64,
+        ))
+    }
+
+    /// Returns a silent source advancing the clock while it's mixed into the audio output.
+    pub(crate) fn source(&self) -> ClockSource {
+        ClockSource {
+            samples: self.samples.clone(),
+        }
+    }
+}
+
+/// A silent source counting the samples mixed into the audio output.
+///
+/// It's resampled to the output like any other source, so its samples count the time of the
+/// output whatever its sample rate.
+pub(crate) struct ClockSource {
+    samples: Arc<AtomicU64>,
+}
+
+impl ClockSource {
+    const SAMPLE_RATE: u32 = 48_000;
+}
+
+impl Iterator for ClockSource {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        self.samples.fetch_add(1, Ordering::Relaxed);
+        Some(0.0)
+    }
+}
+
+impl Source for ClockSource {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        Self::SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// The tempo of a piece of music, to start sounds on its beats.
+///
+/// ```
+/// # use bevy_audio::{AudioTime, Tempo};
+/// # use bevy_utils::Duration;
+/// let tempo = Tempo::new(120.0, AudioTime::ZERO);
+/// let now = AudioTime::from_duration(Duration::from_millis(1100));
+/// assert_eq!(tempo.next_beat(now), AudioTime::from_duration(Duration::from_millis(1500)));
+/// assert_eq!(tempo.next_bar(now, 4), AudioTime::from_duration(Duration::from_secs(2)));
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Tempo {
+    /// The number of beats per minute.
+    pub beats_per_minute: f64,
+    /// The time of the first beat, usually the time the music was started at.
+    pub origin: AudioTime,
+}
+
+impl Tempo {
+    /// Creates a tempo of `beats_per_minute`, with a first beat at `origin`.
+    pub fn new(beats_per_minute: f64, origin: AudioTime) -> Self {
+        Self {
+            beats_per_minute,
+            origin,
+        }
+    }
+
+    /// Returns the duration of a beat.
+    pub fn beat_duration(&self) -> Duration {
+        Duration::from_secs_f64(60.0 / self.beats_per_minute.max(f64::EPSILON))
+    }
+
+    /// Returns the number of beats from the origin to `time`, negative before the origin.
+    pub fn beats_at(&self, time: AudioTime) -> f64 {
+        (time.as_secs_f64() - self.origin.as_secs_f64()) * self.beats_per_minute / 60.0
+    }
+
+    /// Returns the time of a beat, counted from the origin.
+    pub fn beat_time(&self, beat: f64) -> AudioTime {
+        AudioTime::from_secs_f64(
+            self.origin.as_secs_f64() + beat * 60.0 / self.beats_per_minute.max(f64::EPSILON),
+        )
+    }
+
+    /// Returns the time of the first beat at or after `time`.
+    pub fn next_beat(&self, time: AudioTime) -> AudioTime {
+        self.next_multiple(time, 1)
+    }
+
+    /// Returns the time of the first bar of `beats_per_bar` beats at or after `time`.
+    pub fn next_bar(&self, time: AudioTime, beats_per_bar: u32) -> AudioTime {
+        self.next_multiple(time, beats_per_bar.max(1))
+    }
+
+    fn next_multiple(&self, time: AudioTime, beats: u32) -> AudioTime {
+        let beats = beats as f64;
+        // Ignore the rounding errors of the times, which would skip a beat.
+        let count = (self.beats_at(time) / beats - 1e-6).ceil();
+        self.beat_time(count.max(0.0) * beats)
+    }
+}
+
+/// The region of a sound repeated by [`PlaybackMode::Loop`](crate::PlaybackMode::Loop), after an
+/// intro played once.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+pub struct LoopPoints {
+    /// Where the loop starts in the sound. What's before it is only played once.
+    pub start: Duration,
+    /// Where the loop ends in the sound and goes back to its start, or `None` for the end of the
+    /// sound.
+    pub end: Option<Duration>,
+}
+
+impl LoopPoints {
+    /// Returns the number of frames before a point of the loop.
+    pub(crate) fn frames(point: Duration, sample_rate: u32) -> u64 {
+        (point.as_secs_f64() * sample_rate as f64).round() as u64
+    }
+}
+
+/// Plays silence until the start time of a sound, if it has one, and then the sound.
+pub(crate) struct ScheduledSource<S> {
+    input: S,
+    clock: AudioClock,
+    start: Option<AudioTime>,
+    /// The samples of silence left to play, known when the source is first played.
+    silence: Option<usize>,
+}
+
+impl<S> ScheduledSource<S>
+where
+    S: Source<Item = f32>,
+{
+    pub(crate) fn new(input: S, clock: AudioClock, start: Option<AudioTime>) -> Self {
+        Self {
+            input,
+            clock,
+            start,
+            silence: None,
+        }
+    }
+}
+
+impl<S> Iterator for ScheduledSource<S>
+where
+    S: Source<Item = f32>,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let silence = self.silence.get_or_insert_with(|| {
+            let Some(start) = self.start else {
+                return 0;
+            };
+            // The source is pulled along with the clock, so the delay is played in real time.
+            let delay = start - self.clock.now();
+            let frames = LoopPoints::frames(delay, self.input.sample_rate());
+            frames as usize * self.input.channels() as usize
+        });
+        if *silence > 0 {
+            *silence -= 1;
+            return Some(0.0);
+        }
+        self.input.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<S> Source for ScheduledSource<S>
+where
+    S: Source<Item = f32>,
+{
+    fn current_frame_len(&self) -> Option<usize> {
+        match self.silence {
+            Some(silence) if silence > 0 => Some(silence),
+            _ => self.input.current_frame_len(),
+        }
+    }
+
+    fn channels(&self) -> u16 {
+        self.input.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.input.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    fn try_seek(&mut self, position: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(position)?;
+        self.silence = Some(0);
+        Ok(())
+    }
+}
+
+/// Plays a sound up to the end of its [`LoopPoints`], and then repeats its loop forever.
+///
+/// The samples of the loop are kept in memory as they are first played, while the intro isn't.
+/// The format of the sound must not change.
+pub(crate) struct LoopPointsSource<S> {
+    /// The sound, until the end of the loop is first reached.
+    input: Option<S>,
+    channels: u16,
+    sample_rate: u32,
+    /// The samples before the start and the end of the loop.
+    start: usize,
+    end: Option<usize>,
+    /// The number of samples read from the input.
+    position: usize,
+    /// The samples of the loop.
+    samples: Vec<f32>,
+    /// The position in the loop, once the input is done.
+    index: usize,
+}
+
+impl<S> LoopPointsSource<S>
+where
+    S: Source<Item = f32>,
+{
+    pub(crate) fn new(input: S, loop_points: LoopPoints) -> Self {
+        let (channels, sample_rate) = (input.channels(), input.sample_rate());
+        let samples = |point| LoopPoints::frames(point, sample_rate) as usize * channels as usize;
+        Self {
+            input: Some(input),
+            channels,
+            sample_rate,
+            start: samples(loop_points.start),
+            end: loop_points.end.map(samples),
+            position: 0,
+            samples: Vec::new(),
+            index: 0,
+        }
+    }
+}
+
+impl<S> Iterator for LoopPointsSource<S>
+where
+    S: Source<Item = f32>,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if let Some(input) = &mut self.input {
+            let sample = if self.end.is_some_and(|end| self.position >= end) {
+                None
+            } else {
+                input.next()
+            };
+            if let Some(sample) = sample {
+                if self.position >= self.start {
+                    self.samples.push(sample);
+                }
+                self.position += 1;
+                return Some(sample);
+            }
+            self.input = None;
+        }
+        let sample = *self.samples.get(self.index)?;
+        self.index = (self.index + 1) % self.samples.len();
+        Some(sample)
+    }
+}
+
+impl<S> Source for LoopPointsSource<S>
+where
+    S: Source<Item = f32>,
+{
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AudioClock, AudioTime, LoopPoints, LoopPointsSource, ScheduledSource, Tempo};
+    use bevy_utils::Duration;
+    use rodio::{buffer::SamplesBuffer, Source};
+
+    /// A source of the given samples, played at a sample rate of 1 kHz, so each frame lasts a
+    /// millisecond.
+    fn samples(channels: u16, samples: Vec<f32>) -> SamplesBuffer<f32> {
+        SamplesBuffer::new(channels, 1000, samples)
+    }
+
+    fn ramp(len: usize) -> Vec<f32> {
+        (1..=len).map(|sample| sample as f32).collect()
+    }
+
+    fn millis(millis: u64) -> AudioTime {
+        AudioTime::from_duration(Duration::from_millis(millis))
+    }
+
+    #[test]
+    fn clock_follows_the_mixed_samples() {
+        let clock = AudioClock::default();
+        assert_eq!(clock.now(), AudioTime::ZERO);
+        clock.source().take(24_000).for_each(drop);
+        assert_eq!(clock.now(), millis(500));
+    }
+
+    #[test]
+    fn start_time() {
+        let clock = AudioClock::default();
+        let source = ScheduledSource::new(samples(1, ramp(3)), clock.clone(), Some(millis(5)));
+        assert_eq!(
+            source.collect::<Vec<_>>(),
+            [0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 2.0, 3.0]
+        );
+
+        // The delay is counted from when the sound is first played, for every channel.
+        clock.source().take(48 * 2).for_each(drop);
+        let source = ScheduledSource::new(samples(2, ramp(2)), clock.clone(), Some(millis(5)));
+        assert_eq!(
+            source.collect::<Vec<_>>(),
+            [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 2.0]
+        );
+
+        // Sounds scheduled in the past, or not at all, start right away.
+        let source = ScheduledSource::new(samples(1, ramp(2)), clock.clone(), Some(millis(1)));
+        assert_eq!(source.collect::<Vec<_>>(), [1.0, 2.0]);
+        let source = ScheduledSource::new(samples(1, ramp(2)), clock.clone(), None);
+        assert_eq!(source.collect::<Vec<_>>(), [1.0, 2.0]);
+
+        // Seeking skips the silence.
+        let mut source = ScheduledSource::new(samples(1, ramp(4)), clock, Some(millis(10)));
+        source.try_seek(Duration::from_millis(2)).unwrap();
+        assert_eq!(source.collect::<Vec<_>>(), [3.0, 4.0]);
+    }
+
+    #[test]
+    fn loop_points() {
+        let looping = LoopPoints {
+            start: Duration::from_millis(3),
+            end: Some(Duration::from_millis(7)),
+        };
+        let source = LoopPointsSource::new(samples(1, ramp(10)), looping);
+        assert_eq!(
+            source.take(15).collect::<Vec<_>>(),
+            [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 4.0, 5.0, 6.0, 7.0, 4.0, 5.0, 6.0, 7.0]
+        );
+
+        // Without an end, the loop goes on to the end of the sound.
+        let looping = LoopPoints {
+            start: Duration::from_millis(3),
+            end: None,
+        };
+        let source = LoopPointsSource::new(samples(2, ramp(10)), looping);
+        assert_eq!(source.total_duration(), None);
+        assert_eq!(
+            source.take(14).collect::<Vec<_>>(),
+            [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 7.0, 8.0, 9.0, 10.0]
+        );
+    }
+
+    #[test]
+    fn beats() {
+        let tempo = Tempo::new(120.0, millis(250));
+        assert_eq!(tempo.beat_duration(), Duration::from_millis(500));
+        assert_eq!(tempo.beats_at(millis(1250)), 2.0);
+        assert_eq!(tempo.beats_at(AudioTime::ZERO), -0.5);
+        assert_eq!(tempo.beat_time(3.0), millis(1750));
+        // A time on a beat is its own next beat.
+        assert_eq!(tempo.next_beat(millis(1250)), millis(1250));
+        assert_eq!(tempo.next_beat(millis(1251)), millis(1750));
+        assert_eq!(tempo.next_beat(AudioTime::ZERO), millis(250));
+        assert_eq!(tempo.next_bar(millis(1300), 4), millis(2250));
+    }
+}
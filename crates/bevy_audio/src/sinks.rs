@@ -1,7 +1,8 @@
 use bevy_ecs::component::Component;
 use bevy_math::Vec3;
 use bevy_transform::prelude::Transform;
-use rodio::{Sink, SpatialSink};
+use bevy_utils::Duration;
+use rodio::{source::SeekError, Sink, SpatialSink};
 
 /// Common interactions with an audio sink.
 pub trait AudioSinkPlayback {
@@ -71,6 +72,18 @@ pub trait AudioSinkPlayback {
 
     /// Returns true if this sink has no more sounds to play.
     fn empty(&self) -> bool;
+
+    /// Returns the position of the sound in the sink, taking its speed into account.
+    fn position(&self) -> Duration;
+
+    /// Moves the sound in the sink to the given position.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SeekError::NotSupported`] if the sound can't seek, which is the case of the
+    /// `ogg` and `flac` files unless they're streamed with
+    /// [`StreamingSettings`](crate::StreamingSettings).
+    fn try_seek(&self, position: Duration) -> Result<(), SeekError>;
 }
 
 /// Used to control audio during playback.
@@ -124,6 +137,14 @@ impl AudioSinkPlayback for AudioSink {
     fn empty(&self) -> bool {
         self.sink.empty()
     }
+
+    fn position(&self) -> Duration {
+        self.sink.get_pos()
+    }
+
+    fn try_seek(&self, position: Duration) -> Result<(), SeekError> {
+        self.sink.try_seek(position)
+    }
 }
 
 /// Used to control spatial audio during playback.
@@ -177,6 +198,14 @@ impl AudioSinkPlayback for SpatialAudioSink {
     fn empty(&self) -> bool {
         self.sink.empty()
     }
+
+    fn position(&self) -> Duration {
+        self.sink.get_pos()
+    }
+
+    fn try_seek(&self, position: Duration) -> Result<(), SeekError> {
+        self.sink.try_seek(position)
+    }
 }
 
 impl SpatialAudioSink {
@@ -0,0 +1,615 @@
+use crate::{
+    AudioEffect, AudioEffectChain, DefaultSpatialScale, EffectsProcessor, PlaybackSettings,
+    SharedEffects, SmoothedGain, SpatialAudioSink,
+};
+use bevy_ecs::{prelude::*, query::QueryData};
+use bevy_math::{Dir3, Vec2, Vec3};
+use bevy_reflect::prelude::*;
+use bevy_transform::prelude::GlobalTransform;
+use std::{
+    f32::consts::{FRAC_PI_2, PI},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+};
+
+use crate::audio_output::EarPositions;
+
+/// How the volume of a spatial sound decreases with its distance to the [`SpatialListener`](crate::SpatialListener).
+///
+/// The distances are measured between the emitter and the middle of the ears of the listener,
+/// after their positions are scaled by the [`SpatialScale`](crate::SpatialScale). Spatial
+/// sounds without this component use its default, an inverse square attenuation.
+///
+/// ```
+/// # use bevy_audio::{AttenuationModel, SpatialAttenuation};
+/// // Fades out linearly from full volume at 2 units to silence at 30 units.
+/// let attenuation = SpatialAttenuation {
+///     model: AttenuationModel::Linear,
+///     min_distance: 2.0,
+///     max_distance: 30.0,
+/// };
+/// ```
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub struct SpatialAttenuation {
+    /// How the volume decreases between `min_distance` and `max_distance`.
+    pub model: AttenuationModel,
+    /// The distance under which the sound plays at its full volume.
+    pub min_distance: f32,
+    /// The distance beyond which the sound can't be heard.
+    pub max_distance: f32,
+}
+
+impl Default for SpatialAttenuation {
+    fn default() -> Self {
+        Self {
+            model: AttenuationModel::InverseSquare,
+            min_distance: 1.0,
+            max_distance: f32::INFINITY,
+        }
+    }
+}
+
+impl SpatialAttenuation {
+    /// Returns the volume of the sound at the given distance.
+    pub fn volume(&self, distance: f32) -> f32 {
+        if distance >= self.max_distance {
+            return 0.0;
+        }
+        let min_distance = self.min_distance.max(f32::EPSILON);
+        if distance <= min_distance {
+            return 1.0;
+        }
+        let volume = match &self.model {
+            AttenuationModel::Linear => {
+                1.0 - (distance - min_distance) / (self.max_distance - min_distance)
+            }
+            AttenuationModel::Inverse { rolloff } => {
+                min_distance / (min_distance + rolloff * (distance - min_distance))
+            }
+            AttenuationModel::InverseSquare => (min_distance / distance).powi(2),
+            AttenuationModel::Custom(points) => sample_curve(points, distance),
+        };
+        volume.clamp(0.0, 1.0)
+    }
+}
+
+/// Samples a piecewise linear curve, keeping its first and last values beyond its ends.
+fn sample_curve(points: &[Vec2], x: f32) -> f32 {
+    let index = points.partition_point(|point| point.x < x);
+    match (
+        index.checked_sub(1).and_then(|i| points.get(i)),
+        points.get(index),
+    ) {
+        (Some(start), Some(end)) => {
+            let t = (x - start.x) / (end.x - start.x).max(f32::EPSILON);
+            start.y + (end.y - start.y) * t
+        }
+        (Some(point), None) | (None, Some(point)) => point.y,
+        (None, None) => 1.0,
+    }
+}
+
+/// The curve of a [`SpatialAttenuation`].
+#[derive(Clone, Debug, PartialEq, Reflect)]
+pub enum AttenuationModel {
+    /// The volume decreases linearly, down to silence at the maximum distance.
+    Linear,
+    /// The volume is inversely proportional to the distance, like in most game engines.
+    Inverse {
+        /// How fast the volume decreases, `1.0` halving it when the distance doubles.
+        rolloff: f32,
+    },
+    /// The volume is inversely proportional to the square of the distance.
+    InverseSquare,
+    /// The volume follows a curve through the given points, the `x` of each point being a
+    /// distance and its `y` the volume at that distance.
+    ///
+    /// The points must be sorted by distance. The volume is interpolated linearly between them.
+    Custom(Vec<Vec2>),
+}
+
+/// Makes a spatial sound directional, louder in front of its emitter than behind it.
+///
+/// The volume is full inside the inner cone, and decreases to `outer_volume` at the edge of the
+/// outer cone, staying at that volume outside of it.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub struct SpatialCone {
+    /// The direction the sound is emitted towards, relative to the transform of the emitter.
+    pub direction: Dir3,
+    /// The angle of the inner cone, from one side to the other, in radians.
+    pub inner_angle: f32,
+    /// The angle of the outer cone, from one side to the other, in radians.
+    pub outer_angle: f32,
+    /// The volume of the sound outside of the outer cone.
+    pub outer_volume: f32,
+}
+
+impl Default for SpatialCone {
+    fn default() -> Self {
+        Self {
+            direction: Dir3::NEG_Z,
+            inner_angle: FRAC_PI_2,
+            outer_angle: PI,
+            outer_volume: 0.2,
+        }
+    }
+}
+
+impl SpatialCone {
+    /// Returns the volume of the sound heard at the given angle from the direction of the cone.
+    pub fn volume(&self, angle: f32) -> f32 {
+        let (inner, outer) = (self.inner_angle / 2.0, self.outer_angle / 2.0);
+        if angle <= inner {
+            1.0
+        } else if angle >= outer {
+            self.outer_volume
+        } else {
+            let t = (angle - inner) / (outer - inner);
+            1.0 + (self.outer_volume - 1.0) * t
+        }
+    }
+}
+
+/// How much the sound of a spatial emitter is blocked on its way to the
+/// [`SpatialListener`](crate::SpatialListener), by walls for example.
+///
+/// Bevy doesn't compute the occlusion itself: update this component from your own systems, for
+/// example by casting rays between the emitter and the listener with your physics engine.
+/// Occluded sounds are quieter and muffled.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub struct SpatialOcclusion {
+    /// How much of the sound is blocked, from `0.0`, not at all, to `1.0`, fully.
+    pub factor: f32,
+    /// The volume of the sound when it's fully blocked.
+    pub occluded_volume: f32,
+    /// The cutoff frequency of the low-pass filter muffling the sound when it's fully blocked,
+    /// in hertz.
+    pub occluded_cutoff: f32,
+}
+
+impl Default for SpatialOcclusion {
+    fn default() -> Self {
+        Self {
+            factor: 0.0,
+            occluded_volume: 0.3,
+            occluded_cutoff: 800.0,
+        }
+    }
+}
+
+impl SpatialOcclusion {
+    /// The cutoff frequency when the sound isn't blocked, above the frequencies people can hear.
+    const OPEN_CUTOFF: f32 = 20_000.0;
+
+    fn volume(&self) -> f32 {
+        let factor = self.factor.clamp(0.0, 1.0);
+        1.0 + (self.occluded_volume - 1.0) * factor
+    }
+
+    fn cutoff(&self) -> f32 {
+        // Frequencies are perceived logarithmically.
+        let factor = self.factor.clamp(0.0, 1.0);
+        let (open, occluded) = (Self::OPEN_CUTOFF.ln(), self.occluded_cutoff.max(10.0).ln());
+        (open + (occluded - open) * factor).exp()
+    }
+}
+
+/// The velocity of a spatial emitter or of the [`SpatialListener`](crate::SpatialListener), in
+/// units per second, which changes the pitch of the sound with the doppler effect.
+///
+/// Keep it up to date with the velocity of the entity, for example from your physics engine.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub struct SpatialVelocity(pub Vec3);
+
+/// Use this [`Resource`] to configure the doppler effect of the spatial sounds with a
+/// [`SpatialVelocity`].
+#[derive(Resource, Clone, Copy, Debug, Reflect)]
+#[reflect(Resource, Default)]
+pub struct DopplerSettings {
+    /// The speed of sound, in units per second.
+    pub speed_of_sound: f32,
+    /// Multiplies the velocities, to exaggerate the doppler effect or turn it off with `0.0`.
+    pub factor: f32,
+}
+
+impl Default for DopplerSettings {
+    fn default() -> Self {
+        Self {
+            speed_of_sound: 343.0,
+            factor: 1.0,
+        }
+    }
+}
+
+impl DopplerSettings {
+    /// Limits how much the pitch changes, when things move nearly as fast as the sound.
+    const MAX_SPEED: f32 = 4.0;
+
+    /// Returns the change of speed of the sound emitted at `emitter` and heard at `listener`.
+    fn speed(
+        &self,
+        emitter: Vec3,
+        emitter_velocity: Vec3,
+        listener: Vec3,
+        listener_velocity: Vec3,
+    ) -> f32 {
+        let speed_of_sound = self.speed_of_sound.max(f32::EPSILON);
+        let direction = (listener - emitter).normalize_or_zero();
+        let emitter_speed =
+            (emitter_velocity.dot(direction) * self.factor).min(speed_of_sound * 0.99);
+        let listener_speed = listener_velocity.dot(direction) * self.factor;
+        ((speed_of_sound - listener_speed) / (speed_of_sound - emitter_speed))
+            .clamp(1.0 / Self::MAX_SPEED, Self::MAX_SPEED)
+    }
+}
+
+/// The components of a spatial emitter changing how it's heard.
+#[derive(QueryData)]
+pub(crate) struct SpatialEmitter {
+    attenuation: Option<&'static SpatialAttenuation>,
+    cone: Option<&'static SpatialCone>,
+    occlusion: Option<&'static SpatialOcclusion>,
+    velocity: Option<&'static SpatialVelocity>,
+}
+
+/// The positions given to a spatial sink, along with the changes to its sound.
+pub(crate) struct Spatialized {
+    pub(crate) emitter: Vec3,
+    pub(crate) left_ear: Vec3,
+    pub(crate) right_ear: Vec3,
+    gain: f32,
+    speed: f32,
+    cutoff: Option<f32>,
+}
+
+/// Computes how a spatial emitter is heard by the listener.
+pub(crate) fn spatialize(
+    emitter_transform: &GlobalTransform,
+    emitter: &SpatialEmitterItem,
+    (left_ear, right_ear): (Vec3, Vec3),
+    listener_velocity: Vec3,
+    scale: Vec3,
+    doppler: &DopplerSettings,
+) -> Spatialized {
+    let emitter_position = emitter_transform.translation();
+    let listener_position = (left_ear + right_ear) / 2.0;
+
+    let distance = (emitter_position * scale).distance(listener_position * scale);
+    let mut gain = match emitter.attenuation {
+        Some(attenuation) => attenuation.volume(distance),
+        None => SpatialAttenuation::default().volume(distance),
+    };
+    if let Some(cone) = emitter.cone {
+        let direction = emitter_transform
+            .affine()
+            .transform_vector3(*cone.direction);
+        let angle = direction.angle_between(listener_position - emitter_position);
+        gain *= cone.volume(if angle.is_nan() { 0.0 } else { angle });
+    }
+    if let Some(occlusion) = emitter.occlusion {
+        gain *= occlusion.volume();
+    }
+    let speed = doppler.speed(
+        emitter_position,
+        emitter.velocity.map_or(Vec3::ZERO, |velocity| velocity.0),
+        listener_position,
+        listener_velocity,
+    );
+
+    // The sink attenuates the sound with the square of the distance to each ear, so bring the
+    // emitter and the ears closer to keep the distances under 1 while the panning stays the
+    // same, and attenuate the sound on our side instead.
+    let (mut emitter_position, mut left_ear, mut right_ear) = (
+        emitter_position * scale,
+        left_ear * scale,
+        right_ear * scale,
+    );
+    let center = (left_ear + right_ear) / 2.0;
+    let farthest = emitter_position
+        .distance(left_ear)
+        .max(emitter_position.distance(right_ear));
+    if farthest > 1.0 {
+        let shrink = |position: Vec3| center + (position - center) / farthest;
+        emitter_position = shrink(emitter_position);
+        left_ear = shrink(left_ear);
+        right_ear = shrink(right_ear);
+    }
+
+    Spatialized {
+        emitter: emitter_position,
+        left_ear,
+        right_ear,
+        gain,
+        speed,
+        cutoff: emitter.occlusion.map(SpatialOcclusion::cutoff),
+    }
+}
+
+/// The changes to the sound of a spatial emitter shared with the audio thread.
+pub(crate) struct SharedSpatial {
+    /// The bits of the gain applied to the sound.
+    gain: AtomicU32,
+    /// The bits of the speed of the sound.
+    speed: AtomicU32,
+    /// The bits of the cutoff of the occlusion last sent to the audio thread.
+    cutoff: AtomicU32,
+    effects: Arc<SharedEffects>,
+}
+
+impl SharedSpatial {
+    pub(crate) fn new(spatialized: &Spatialized) -> Self {
+        let shared = Self {
+            gain: AtomicU32::new(spatialized.gain.to_bits()),
+            speed: AtomicU32::new(spatialized.speed.to_bits()),
+            cutoff: AtomicU32::new(f32::NAN.to_bits()),
+            effects: Arc::default(),
+        };
+        shared.set(spatialized);
+        shared
+    }
+
+    fn set(&self, spatialized: &Spatialized) {
+        self.gain
+            .store(spatialized.gain.to_bits(), Ordering::Relaxed);
+        self.speed
+            .store(spatialized.speed.to_bits(), Ordering::Relaxed);
+
+        // Only send the filter to the audio thread when it audibly changes.
+        let cutoff = spatialized.cutoff.unwrap_or(f32::NAN);
+        let last_cutoff = f32::from_bits(self.cutoff.load(Ordering::Relaxed));
+        let changed = match (cutoff.is_nan(), last_cutoff.is_nan()) {
+            (false, false) => (cutoff / last_cutoff).ln().abs() > 0.01,
+            (cutoff_is_nan, last_is_nan) => cutoff_is_nan != last_is_nan,
+        };
+        if changed {
+            self.cutoff.store(cutoff.to_bits(), Ordering::Relaxed);
+            let effects = match spatialized.cutoff {
+                Some(cutoff) => vec![AudioEffect::LowPass { cutoff }],
+                None => Vec::new(),
+            };
+            self.effects.set(&effects);
+        }
+    }
+}
+
+/// Applies the gain, speed and occlusion of a spatial emitter to its sound on the audio thread.
+pub(crate) struct SpatialProcessor {
+    shared: Arc<SharedSpatial>,
+    effects: EffectsProcessor,
+    gain: SmoothedGain,
+}
+
+impl SpatialProcessor {
+    pub(crate) fn new(shared: Arc<SharedSpatial>) -> Self {
+        Self {
+            effects: EffectsProcessor::new(shared.effects.clone()),
+            gain: SmoothedGain::new(f32::from_bits(shared.gain.load(Ordering::Relaxed))),
+            shared,
+        }
+    }
+
+    /// Picks up the changes to the emitter at the start of a frame, and returns its speed.
+    pub(crate) fn update(&mut self, format_changed: bool, channels: u16, sample_rate: u32) -> f32 {
+        self.effects.update(format_changed, channels, sample_rate);
+        let gain = f32::from_bits(self.shared.gain.load(Ordering::Relaxed));
+        self.gain.update(gain, format_changed, sample_rate);
+        self.speed()
+    }
+
+    pub(crate) fn speed(&self) -> f32 {
+        f32::from_bits(self.shared.speed.load(Ordering::Relaxed))
+    }
+
+    pub(crate) fn process(&mut self, sample: f32, channel: usize) -> f32 {
+        self.effects.process(sample, channel) * self.gain.get()
+    }
+}
+
+/// Updates the positions of the spatial sinks, and how their sounds are heard by the listener.
+pub(crate) fn update_spatial_audio(
+    emitters: Query<(
+        &GlobalTransform,
+        &SpatialAudioSink,
+        &AudioEffectChain,
+        &PlaybackSettings,
+        SpatialEmitter,
+    )>,
+    ear_positions: EarPositions,
+    default_spatial_scale: Res<DefaultSpatialScale>,
+    doppler: Res<DopplerSettings>,
+) {
+    let ears = ear_positions.get();
+    let listener_velocity = ear_positions.velocity();
+    for (transform, sink, effect_chain, settings, emitter) in &emitters {
+        let scale = settings.spatial_scale.unwrap_or(default_spatial_scale.0).0;
+        let spatialized = spatialize(
+            transform,
+            &emitter,
+            ears,
+            listener_velocity,
+            scale,
+            &doppler,
+        );
+        sink.set_emitter_position(spatialized.emitter);
+        sink.set_ears_position(spatialized.left_ear, spatialized.right_ear);
+        if let Some(spatial) = effect_chain.spatial() {
+            spatial.set(&spatialized);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        spatialize, AttenuationModel, DopplerSettings, SpatialAttenuation, SpatialCone,
+        SpatialEmitterItem, SpatialOcclusion, SpatialVelocity,
+    };
+    use bevy_math::{Dir3, Vec2, Vec3};
+    use bevy_transform::prelude::{GlobalTransform, Transform};
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    fn assert_near(value: f32, expected: f32) {
+        assert!(
+            (value - expected).abs() < 1e-4 * expected.abs().max(1.0),
+            "{value} isn't near {expected}"
+        );
+    }
+
+    fn attenuation(model: AttenuationModel) -> SpatialAttenuation {
+        SpatialAttenuation {
+            model,
+            min_distance: 2.0,
+            max_distance: 10.0,
+        }
+    }
+
+    #[test]
+    fn attenuation_models() {
+        let linear = attenuation(AttenuationModel::Linear);
+        assert_eq!(linear.volume(1.0), 1.0);
+        assert_near(linear.volume(6.0), 0.5);
+        assert_eq!(linear.volume(10.0), 0.0);
+        assert_eq!(linear.volume(20.0), 0.0);
+
+        let inverse = attenuation(AttenuationModel::Inverse { rolloff: 1.0 });
+        assert_eq!(inverse.volume(2.0), 1.0);
+        assert_near(inverse.volume(4.0), 0.5);
+        assert_near(inverse.volume(8.0), 0.25);
+        let fast = attenuation(AttenuationModel::Inverse { rolloff: 2.0 });
+        assert_near(fast.volume(4.0), 1.0 / 3.0);
+
+        let inverse_square = attenuation(AttenuationModel::InverseSquare);
+        assert_near(inverse_square.volume(4.0), 0.25);
+        assert_eq!(inverse_square.volume(10.0), 0.0);
+        assert_near(SpatialAttenuation::default().volume(100.0), 1e-4);
+
+        let custom = attenuation(AttenuationModel::Custom(vec![
+            Vec2::new(3.0, 0.8),
+            Vec2::new(5.0, 0.4),
+            Vec2::new(9.0, 0.2),
+        ]));
+        assert_eq!(custom.volume(2.5), 0.8);
+        assert_near(custom.volume(4.0), 0.6);
+        assert_near(custom.volume(7.0), 0.3);
+        assert_eq!(custom.volume(9.5), 0.2);
+    }
+
+    #[test]
+    fn cone() {
+        let cone = SpatialCone {
+            direction: Dir3::NEG_Z,
+            inner_angle: FRAC_PI_2,
+            outer_angle: PI,
+            outer_volume: 0.2,
+        };
+        assert_eq!(cone.volume(0.0), 1.0);
+        assert_eq!(cone.volume(FRAC_PI_4), 1.0);
+        assert_near(cone.volume(3.0 * PI / 8.0), 0.6);
+        assert_eq!(cone.volume(FRAC_PI_2), 0.2);
+        assert_eq!(cone.volume(PI), 0.2);
+    }
+
+    #[test]
+    fn doppler() {
+        let doppler = DopplerSettings {
+            speed_of_sound: 100.0,
+            factor: 1.0,
+        };
+        let (emitter, listener) = (Vec3::ZERO, Vec3::X * 10.0);
+        let speed = |emitter_velocity, listener_velocity| {
+            doppler.speed(emitter, emitter_velocity, listener, listener_velocity)
+        };
+        assert_eq!(speed(Vec3::ZERO, Vec3::ZERO), 1.0);
+        // Approaching raises the pitch, moving away lowers it.
+        assert_near(speed(Vec3::X * 50.0, Vec3::ZERO), 2.0);
+        assert_near(speed(Vec3::NEG_X * 100.0, Vec3::ZERO), 0.5);
+        assert_near(speed(Vec3::ZERO, Vec3::NEG_X * 50.0), 1.5);
+        // Moving across the line between them doesn't change the pitch.
+        assert_eq!(speed(Vec3::Y * 50.0, Vec3::Z * 50.0), 1.0);
+        // The speed is limited when moving as fast as the sound.
+        assert_eq!(
+            speed(Vec3::X * 1000.0, Vec3::ZERO),
+            DopplerSettings::MAX_SPEED
+        );
+
+        let disabled = DopplerSettings {
+            factor: 0.0,
+            ..doppler
+        };
+        assert_eq!(
+            disabled.speed(emitter, Vec3::X * 50.0, listener, Vec3::ZERO),
+            1.0
+        );
+    }
+
+    #[test]
+    fn occlusion() {
+        let occlusion = SpatialOcclusion::default();
+        assert_eq!(occlusion.volume(), 1.0);
+        assert_near(occlusion.cutoff(), SpatialOcclusion::OPEN_CUTOFF);
+        let occluded = SpatialOcclusion {
+            factor: 1.0,
+            ..occlusion
+        };
+        assert_near(occluded.volume(), 0.3);
+        assert_near(occluded.cutoff(), 800.0);
+        let half = SpatialOcclusion {
+            factor: 0.5,
+            ..occlusion
+        };
+        assert_near(half.volume(), 0.65);
+        assert_near(half.cutoff(), (20_000.0f32 * 800.0).sqrt());
+    }
+
+    #[test]
+    fn spatialized_emitter() {
+        let attenuation = attenuation(AttenuationModel::Linear);
+        // Emitting away from the listener.
+        let cone = SpatialCone {
+            direction: Dir3::X,
+            ..Default::default()
+        };
+        let occlusion = SpatialOcclusion {
+            factor: 1.0,
+            ..Default::default()
+        };
+        let velocity = SpatialVelocity(Vec3::NEG_X * 10.0);
+        let emitter = SpatialEmitterItem {
+            attenuation: Some(&attenuation),
+            cone: Some(&cone),
+            occlusion: Some(&occlusion),
+            velocity: Some(&velocity),
+        };
+        let transform = GlobalTransform::from(Transform::from_xyz(6.0, 0.0, 0.0));
+        let ears = (Vec3::new(0.0, 0.0, -0.5), Vec3::new(0.0, 0.0, 0.5));
+        let doppler = DopplerSettings::default();
+        let spatialized = spatialize(&transform, &emitter, ears, Vec3::ZERO, Vec3::ONE, &doppler);
+
+        assert_near(spatialized.gain, 0.5 * 0.2 * 0.3);
+        // Coming closer to the listener.
+        assert_near(spatialized.speed, 343.0 / 333.0);
+        assert_near(spatialized.cutoff.unwrap(), 800.0);
+        // The positions are brought within a unit of each other, keeping their directions.
+        assert!(spatialized.emitter.distance(spatialized.left_ear) <= 1.0 + 1e-4);
+        assert!(spatialized.emitter.distance(spatialized.right_ear) <= 1.0 + 1e-4);
+        assert_near(spatialized.emitter.normalize().dot(Vec3::X), 1.0);
+        assert_near(spatialized.left_ear.z, -spatialized.right_ear.z);
+
+        // The scale applies to the distances.
+        let scaled = spatialize(
+            &transform,
+            &emitter,
+            ears,
+            Vec3::ZERO,
+            Vec3::splat(0.5),
+            &doppler,
+        );
+        assert_near(scaled.gain, 0.875 * 0.2 * 0.3);
+    }
+}
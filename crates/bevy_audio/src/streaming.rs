@@ -0,0 +1,424 @@
+use crate::{AudioSource, LoopPoints};
+use async_channel::{Receiver, Sender, TryRecvError};
+use bevy_tasks::{AsyncComputeTaskPool, TaskPool};
+use bevy_utils::{tracing::warn, Duration};
+use rodio::{source::SeekError, Source};
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+
+/// How an [`AudioSource`] is streamed while it plays, instead of being decoded as it's played
+/// on the audio thread.
+///
+/// The sound is decoded in chunks on the [`AsyncComputeTaskPool`], a few chunks ahead of the
+/// playback, so only the buffered chunks of decoded samples are kept in memory. Looping sounds
+/// are decoded again from the start of their [`LoopPoints`] instead of being kept decoded in
+/// memory.
+///
+/// Set it with the [`AudioLoaderSettings`](crate::AudioLoaderSettings) of the sound:
+///
+/// ```
+/// # use bevy_asset::{AssetServer, Handle};
+/// # use bevy_audio::{AudioLoaderSettings, AudioSource, StreamingSettings};
+/// # fn load(asset_server: &AssetServer) -> Handle<AudioSource> {
+/// asset_server.load_with_settings("sounds/soundtrack.ogg", |settings: &mut AudioLoaderSettings| {
+///     settings.streaming = Some(StreamingSettings::default());
+/// })
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamingSettings {
+    /// The number of frames decoded in each chunk, a frame being a sample for each channel.
+    pub chunk_frames: usize,
+    /// The number of chunks decoded ahead of the playback.
+    ///
+    /// More chunks use more memory, but are less likely to run out when the task pool is busy,
+    /// which plays silence until the next chunk is decoded.
+    pub buffered_chunks: usize,
+}
+
+impl Default for StreamingSettings {
+    fn default() -> Self {
+        Self {
+            chunk_frames: 4096,
+            buffered_chunks: 8,
+        }
+    }
+}
+
+/// A chunk of decoded samples sent to the audio thread.
+struct StreamChunk {
+    /// The seek this chunk was decoded after, to drop the chunks decoded before a seek.
+    generation: u32,
+    channels: u16,
+    sample_rate: u32,
+    /// The interleaved samples, which are empty at the end of the sound.
+    samples: Vec<i16>,
+}
+
+/// A request of the audio thread to move the decoding to another position.
+struct StreamSeek {
+    generation: u32,
+    position: Duration,
+}
+
+/// Plays the samples of an [`AudioSource`] decoded in the background.
+pub(crate) struct AudioStream {
+    chunks: Receiver<StreamChunk>,
+    seeks: Sender<StreamSeek>,
+    generation: u32,
+    /// The chunk being played.
+    samples: Vec<i16>,
+    index: usize,
+    channels: u16,
+    sample_rate: u32,
+    /// The samples of silence left to play while waiting for the next chunk.
+    silence: usize,
+    total_duration: Option<Duration>,
+    ended: bool,
+}
+
+impl AudioStream {
+    pub(crate) fn new(
+        source: &AudioSource,
+        settings: StreamingSettings,
+        looping: Option<LoopPoints>,
+    ) -> Self {
+        let mut decoder = StreamDecoder::new(source.clone(), looping);
+        let total_duration = match looping {
+            Some(_) => None,
+            None => decoder.decoder.total_duration(),
+        };
+        let chunk_frames = settings.chunk_frames.max(1);
+        // Decode the first chunk right away, so the sound doesn't start with silence.
+        let first_chunk = decoder.decode_chunk(0, chunk_frames);
+        let (chunks_sender, chunks) = async_channel::bounded(settings.buffered_chunks.max(1));
+        let (seeks, seeks_receiver) = async_channel::unbounded();
+        AsyncComputeTaskPool::get_or_init(TaskPool::default)
+            .spawn(decoder.run(chunk_frames, chunks_sender, seeks_receiver))
+            .detach();
+
+        let mut stream = Self {
+            chunks,
+            seeks,
+            generation: 0,
+            samples: Vec::new(),
+            index: 0,
+            channels: first_chunk.channels,
+            sample_rate: first_chunk.sample_rate,
+            silence: 0,
+            total_duration,
+            ended: false,
+        };
+        stream.play_chunk(first_chunk);
+        stream
+    }
+
+    fn play_chunk(&mut self, chunk: StreamChunk) {
+        if chunk.samples.is_empty() {
+            self.ended = true;
+        } else {
+            self.channels = chunk.channels;
+            self.sample_rate = chunk.sample_rate;
+            self.samples = chunk.samples;
+            self.index = 0;
+        }
+    }
+
+    /// Moves to the next chunk, or to a frame of silence if it isn't decoded yet.
+    fn next_chunk(&mut self) {
+        self.samples.clear();
+        self.index = 0;
+        while !self.ended {
+            match self.chunks.try_recv() {
+                Ok(chunk) if chunk.generation != self.generation => {}
+                Ok(chunk) => {
+                    self.play_chunk(chunk);
+                    return;
+                }
+                Err(TryRecvError::Empty) => {
+                    self.silence = self.channels as usize;
+                    return;
+                }
+                Err(TryRecvError::Closed) => self.ended = true,
+            }
+        }
+    }
+}
+
+impl Iterator for AudioStream {
+    type Item = i16;
+
+    fn next(&mut self) -> Option<i16> {
+        let sample = if let Some(&sample) = self.samples.get(self.index) {
+            self.index += 1;
+            sample
+        } else if self.silence > 0 {
+            self.silence -= 1;
+            0
+        } else {
+            return None;
+        };
+        // Move on at the end of the frame, so the format of the next one is known before it
+        // starts.
+        if self.index == self.samples.len() && self.silence == 0 {
+            self.next_chunk();
+        }
+        Some(sample)
+    }
+}
+
+impl Source for AudioStream {
+    fn current_frame_len(&self) -> Option<usize> {
+        if self.index < self.samples.len() {
+            Some(self.samples.len() - self.index)
+        } else {
+            Some(self.silence)
+        }
+    }
+
+    fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.total_duration
+    }
+
+    fn try_seek(&mut self, position: Duration) -> Result<(), SeekError> {
+        self.generation = self.generation.wrapping_add(1);
+        self.seeks
+            .try_send(StreamSeek {
+                generation: self.generation,
+                position,
+            })
+            .map_err(|error| SeekError::Other(Box::new(error)))?;
+        self.ended = false;
+        self.silence = 0;
+        self.next_chunk();
+        Ok(())
+    }
+}
+
+/// Decodes an [`AudioSource`] in the background for an [`AudioStream`].
+struct StreamDecoder {
+    source: AudioSource,
+    decoder: rodio::Decoder<Cursor<AudioSource>>,
+    looping: Option<LoopPoints>,
+    /// The position of the decoder in the sound.
+    position: Duration,
+    /// The number of frames before the position, at the current sample rate.
+    frame: u64,
+}
+
+impl StreamDecoder {
+    fn new(source: AudioSource, looping: Option<LoopPoints>) -> Self {
+        Self {
+            decoder: rodio::Decoder::new(Cursor::new(source.clone())).unwrap(),
+            source,
+            looping,
+            position: Duration::ZERO,
+            frame: 0,
+        }
+    }
+
+    fn restart(&mut self) {
+        self.decoder = rodio::Decoder::new(Cursor::new(self.source.clone())).unwrap();
+        self.position = Duration::ZERO;
+        self.frame = 0;
+    }
+
+    /// Decodes up to `frames` frames, stopping early when the format changes.
+    fn decode_chunk(&mut self, generation: u32, frames: usize) -> StreamChunk {
+        let mut chunk = self.decode_samples(generation, frames);
+        if let (true, Some(loop_points)) = (chunk.samples.is_empty(), self.looping) {
+            // Skip to the start of the loop exactly, as seeking isn't precise in every format.
+            self.restart();
+            self.skip_to(loop_points.start);
+            chunk = self.decode_samples(generation, frames);
+        }
+        chunk
+    }
+
+    fn decode_samples(&mut self, generation: u32, frames: usize) -> StreamChunk {
+        let channels = self.decoder.channels();
+        let sample_rate = self.decoder.sample_rate();
+        let loop_end = self
+            .looping
+            .and_then(|loop_points| loop_points.end)
+            .map(|end| LoopPoints::frames(end, sample_rate));
+        let frames = match loop_end {
+            Some(end) => frames.min(end.saturating_sub(self.frame) as usize),
+            None => frames,
+        };
+        let len = frames * channels as usize;
+        let mut samples = Vec::with_capacity(len);
+        while samples.len() < len
+            && self.decoder.channels() == channels
+            && self.decoder.sample_rate() == sample_rate
+        {
+            let frame_len = self.decoder.current_frame_len().unwrap_or(usize::MAX);
+            let count = (len - samples.len()).min(frame_len.max(1));
+            let start = samples.len();
+            samples.extend(self.decoder.by_ref().take(count));
+            if samples.len() - start < count {
+                break;
+            }
+        }
+        let decoded_frames = (samples.len() / channels.max(1) as usize) as u64;
+        self.frame += decoded_frames;
+        self.position += Duration::from_secs_f64(decoded_frames as f64 / sample_rate.max(1) as f64);
+        StreamChunk {
+            generation,
+            channels,
+            sample_rate,
+            samples,
+        }
+    }
+
+    fn seek(&mut self, position: Duration) {
+        if self.decoder.try_seek(position).is_ok() {
+            self.position = position;
+            self.frame = LoopPoints::frames(position, self.decoder.sample_rate());
+            return;
+        }
+        // Not every format can seek, so decode up to the position instead.
+        if position < self.position {
+            self.restart();
+        }
+        self.skip_to(position);
+    }
+
+    /// Decodes up to a position after the current one, dropping the samples.
+    fn skip_to(&mut self, position: Duration) {
+        let sample_rate = self.decoder.sample_rate();
+        let frames = LoopPoints::frames(position, sample_rate).saturating_sub(self.frame);
+        let samples = frames as usize * self.decoder.channels() as usize;
+        if self.decoder.by_ref().take(samples).count() < samples {
+            warn!("Seeking past the end of an audio stream, to {position:?}");
+        }
+        self.position = position;
+        self.frame = LoopPoints::frames(position, sample_rate);
+    }
+
+    /// Decodes the chunks ahead of the playback, until the [`AudioStream`] is dropped.
+    async fn run(
+        mut self,
+        chunk_frames: usize,
+        chunks: Sender<StreamChunk>,
+        seeks: Receiver<StreamSeek>,
+    ) {
+        let mut generation = 0;
+        loop {
+            while let Ok(seek) = seeks.try_recv() {
+                generation = seek.generation;
+                self.seek(seek.position);
+            }
+            let chunk = self.decode_chunk(generation, chunk_frames);
+            let ended = chunk.samples.is_empty();
+            if chunks.send(chunk).await.is_err() {
+                return;
+            }
+            if ended {
+                // The sound can still be played again by seeking back.
+                let Ok(seek) = seeks.recv().await else {
+                    return;
+                };
+                generation = seek.generation;
+                self.seek(seek.position);
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "wav"))]
+mod tests {
+    use super::{AudioStream, StreamingSettings};
+    use crate::{AudioSource, LoopPoints};
+    use bevy_utils::Duration;
+    use rodio::Source;
+
+    const SAMPLE_RATE: u32 = 8000;
+
+    /// A mono sound whose samples are their index plus one, so they can't be mistaken for the
+    /// silence played while waiting for a chunk.
+    fn ramp(frames: u32) -> AudioSource {
+        let data: Vec<u8> = (1..=frames as i16).flat_map(i16::to_le_bytes).collect();
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+        bytes.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes());
+        bytes.extend_from_slice(&2u16.to_le_bytes());
+        bytes.extend_from_slice(&16u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&data);
+        AudioSource {
+            bytes: bytes.into(),
+            streaming: None,
+        }
+    }
+
+    fn settings() -> StreamingSettings {
+        StreamingSettings {
+            chunk_frames: 64,
+            buffered_chunks: 2,
+        }
+    }
+
+    /// Plays the next `count` samples, skipping the silence of the chunks not decoded yet.
+    fn play(stream: &mut AudioStream, count: usize) -> Vec<i16> {
+        stream.filter(|&sample| sample != 0).take(count).collect()
+    }
+
+    #[test]
+    fn streams_all_samples() {
+        let mut stream = AudioStream::new(&ramp(1000), settings(), None);
+        assert_eq!(stream.channels(), 1);
+        assert_eq!(stream.sample_rate(), SAMPLE_RATE);
+        assert_eq!(
+            stream.total_duration(),
+            Some(Duration::from_millis(1000 * 1000 / SAMPLE_RATE as u64))
+        );
+        assert_eq!(
+            play(&mut stream, usize::MAX),
+            (1..=1000).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn seeks() {
+        let mut stream = AudioStream::new(&ramp(4000), settings(), None);
+        assert_eq!(play(&mut stream, 10), (1..=10).collect::<Vec<_>>());
+
+        stream.try_seek(Duration::from_millis(250)).unwrap();
+        assert_eq!(play(&mut stream, 3), [2001, 2002, 2003]);
+
+        // Back to the start, after the end of the sound.
+        assert_eq!(play(&mut stream, usize::MAX).last(), Some(&4000));
+        stream.try_seek(Duration::ZERO).unwrap();
+        assert_eq!(play(&mut stream, 3), [1, 2, 3]);
+    }
+
+    #[test]
+    fn loops_between_loop_points() {
+        let looping = LoopPoints {
+            start: Duration::from_millis(10),
+            end: Some(Duration::from_millis(20)),
+        };
+        let mut stream = AudioStream::new(&ramp(1000), settings(), Some(looping));
+        assert_eq!(stream.total_duration(), None);
+        let samples = play(&mut stream, 240);
+        // The intro, then the loop from frame 80 to frame 160, over and over.
+        assert_eq!(samples[..160], (1..=160).collect::<Vec<_>>());
+        assert_eq!(samples[160..], (81..=160).collect::<Vec<_>>());
+    }
+}
@@ -13,10 +13,21 @@ use bevy_render::{
 };
 use bevy_transform::prelude::{GlobalTransform, Transform};
 
+/// Configuration for the "main 2d render graph".
 #[derive(Component, Default, Reflect, Clone, ExtractComponent)]
 #[extract_component_filter(With<Camera>)]
 #[reflect(Component)]
-pub struct Camera2d;
+pub struct Camera2d {
+    /// Whether the main 2d pass has a stencil texture, in the
+    /// [`CORE_2D_STENCIL_FORMAT`](crate::core_2d::CORE_2D_STENCIL_FORMAT). Defaults to `false`.
+    ///
+    /// This lets 2d materials read and write the stencil buffer, for masking. The stencil
+    /// buffer is cleared to `0` at the start of the main 2d pass.
+    ///
+    /// Every pipeline drawing in the main 2d pass of the camera must then use this stencil format,
+    /// which the built-in mesh, sprite and gizmo pipelines do.
+    pub stencil: bool,
+}
 
 #[derive(Bundle, Clone)]
 pub struct Camera2dBundle {
@@ -54,7 +65,7 @@ impl Default for Camera2dBundle {
             transform,
             global_transform: Default::default(),
             camera: Camera::default(),
-            camera_2d: Camera2d,
+            camera_2d: Camera2d::default(),
             tonemapping: Tonemapping::None,
             deband_dither: DebandDither::Disabled,
             main_texture_usages: Default::default(),
@@ -86,7 +97,7 @@ impl Camera2dBundle {
             transform,
             global_transform: Default::default(),
             camera: Camera::default(),
-            camera_2d: Camera2d,
+            camera_2d: Camera2d::default(),
             tonemapping: Tonemapping::None,
             deband_dither: DebandDither::Disabled,
             main_texture_usages: Default::default(),
@@ -36,21 +36,32 @@ use bevy_app::{App, Plugin};
 use bevy_ecs::{entity::EntityHashSet, prelude::*};
 use bevy_math::FloatOrd;
 use bevy_render::{
-    camera::Camera,
+    camera::{Camera, ExtractedCamera},
     extract_component::ExtractComponentPlugin,
     render_graph::{EmptyNode, RenderGraphApp, ViewNodeRunner},
     render_phase::{
         sort_phase_system, CachedRenderPipelinePhaseItem, DrawFunctionId, DrawFunctions, PhaseItem,
         PhaseItemExtraIndex, SortedPhaseItem, ViewSortedRenderPhases,
     },
-    render_resource::CachedRenderPipelineId,
+    render_resource::{
+        CachedRenderPipelineId, Extent3d, LoadOp, Operations, RenderPassDepthStencilAttachment,
+        StoreOp, Texture, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        TextureView,
+    },
+    renderer::RenderDevice,
+    texture::TextureCache,
+    view::Msaa,
     Extract, ExtractSchedule, Render, RenderApp, RenderSet,
 };
+use bevy_utils::HashMap;
 
 use crate::{tonemapping::TonemappingNode, upscaling::UpscalingNode};
 
 use self::graph::{Core2d, Node2d};
 
+/// The format of the stencil texture of cameras with [`Camera2d::stencil`] enabled.
+pub const CORE_2D_STENCIL_FORMAT: TextureFormat = TextureFormat::Stencil8;
+
 pub struct Core2dPlugin;
 
 impl Plugin for Core2dPlugin {
@@ -67,7 +78,10 @@ impl Plugin for Core2dPlugin {
             .add_systems(ExtractSchedule, extract_core_2d_camera_phases)
             .add_systems(
                 Render,
-                sort_phase_system::<Transparent2d>.in_set(RenderSet::PhaseSort),
+                (
+                    sort_phase_system::<Transparent2d>.in_set(RenderSet::PhaseSort),
+                    prepare_core_2d_stencil_textures.in_set(RenderSet::PrepareResources),
+                ),
             );
 
         render_app
@@ -95,8 +109,36 @@ impl Plugin for Core2dPlugin {
     }
 }
 
+/// The key [`Transparent2d`] items are sorted by, drawing the items with the lowest keys first.
+///
+/// Items are sorted by `layer`, then by `order` within a layer, then by `depth`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Sort2dKey {
+    pub layer: i32,
+    pub order: i32,
+    pub depth: FloatOrd,
+}
+
+impl Sort2dKey {
+    /// The key of items drawn on top of everything else.
+    pub const MAX: Self = Self {
+        layer: i32::MAX,
+        order: i32::MAX,
+        depth: FloatOrd(f32::INFINITY),
+    };
+
+    /// Returns the key of an item on the default layer and order, sorted by `depth`.
+    pub fn from_depth(depth: f32) -> Self {
+        Self {
+            layer: 0,
+            order: 0,
+            depth: FloatOrd(depth),
+        }
+    }
+}
+
 pub struct Transparent2d {
-    pub sort_key: FloatOrd,
+    pub sort_key: Sort2dKey,
     pub entity: Entity,
     pub pipeline: CachedRenderPipelineId,
     pub draw_function: DrawFunctionId,
@@ -137,7 +179,7 @@ impl PhaseItem for Transparent2d {
 }
 
 impl SortedPhaseItem for Transparent2d {
-    type SortKey = FloatOrd;
+    type SortKey = Sort2dKey;
 
     #[inline]
     fn sort_key(&self) -> Self::SortKey {
@@ -147,7 +189,10 @@ impl SortedPhaseItem for Transparent2d {
     #[inline]
     fn sort(items: &mut [Self]) {
         // radsort is a stable radix sort that performed better than `slice::sort_by_key` or `slice::sort_unstable_by_key`.
-        radsort::sort_by_key(items, |item| item.sort_key().0);
+        radsort::sort_by_key(items, |item| {
+            let key = item.sort_key();
+            (key.layer, key.order, key.depth.0)
+        });
     }
 }
 
@@ -180,3 +225,70 @@ pub fn extract_core_2d_camera_phases(
     // Clear out all dead views.
     transparent_2d_phases.retain(|camera_entity, _| live_entities.contains(camera_entity));
 }
+
+/// The stencil texture of the main 2d pass of cameras with [`Camera2d::stencil`] enabled.
+#[derive(Component)]
+pub struct ViewStencilTexture {
+    pub texture: Texture,
+    pub view: TextureView,
+}
+
+impl ViewStencilTexture {
+    /// Get this texture view as an attachment, cleared to `0`.
+    pub fn get_attachment(&self) -> RenderPassDepthStencilAttachment {
+        RenderPassDepthStencilAttachment {
+            view: &self.view,
+            depth_ops: None,
+            stencil_ops: Some(Operations {
+                load: LoadOp::Clear(0),
+                store: StoreOp::Store,
+            }),
+        }
+    }
+}
+
+pub fn prepare_core_2d_stencil_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    msaa: Res<Msaa>,
+    render_device: Res<RenderDevice>,
+    transparent_2d_phases: Res<ViewSortedRenderPhases<Transparent2d>>,
+    views_2d: Query<(Entity, &ExtractedCamera, &Camera2d)>,
+) {
+    let mut textures = HashMap::default();
+    for (entity, camera, camera_2d) in &views_2d {
+        if !camera_2d.stencil || !transparent_2d_phases.contains_key(&entity) {
+            continue;
+        }
+        let Some(physical_target_size) = camera.physical_target_size else {
+            continue;
+        };
+
+        let cached_texture = textures
+            .entry(camera.target.clone())
+            .or_insert_with(|| {
+                let descriptor = TextureDescriptor {
+                    label: Some("view_stencil_texture"),
+                    size: Extent3d {
+                        depth_or_array_layers: 1,
+                        width: physical_target_size.x,
+                        height: physical_target_size.y,
+                    },
+                    mip_level_count: 1,
+                    sample_count: msaa.samples(),
+                    dimension: TextureDimension::D2,
+                    format: CORE_2D_STENCIL_FORMAT,
+                    usage: TextureUsages::RENDER_ATTACHMENT,
+                    view_formats: &[],
+                };
+
+                texture_cache.get(&render_device, descriptor)
+            })
+            .clone();
+
+        commands.entity(entity).insert(ViewStencilTexture {
+            texture: cached_texture.texture,
+            view: cached_texture.default_view,
+        });
+    }
+}
@@ -50,6 +50,22 @@ pub struct Camera3d {
     ///
     /// **Note:** You can get better-looking results at any quality level by enabling TAA. See: [`TemporalAntiAliasPlugin`](crate::experimental::taa::TemporalAntiAliasPlugin).
     pub screen_space_specular_transmission_quality: ScreenSpaceTransmissionQuality,
+    /// Whether the depth texture of the main 3d pass has a stencil aspect, using
+    /// [`CORE_3D_DEPTH_STENCIL_FORMAT`](crate::core_3d::CORE_3D_DEPTH_STENCIL_FORMAT) instead of
+    /// [`CORE_3D_DEPTH_FORMAT`](crate::core_3d::CORE_3D_DEPTH_FORMAT). Defaults to `false`.
+    ///
+    /// This lets materials read and write the stencil buffer, for outlines, portals or masking.
+    /// The stencil buffer is cleared to `0` along with the depth at the start of the main 3d pass.
+    ///
+    /// ### Notes
+    ///
+    /// - This is ignored by cameras with a [`DepthPrepass`](crate::prepass::DepthPrepass),
+    ///   [`NormalPrepass`](crate::prepass::NormalPrepass),
+    ///   [`MotionVectorPrepass`](crate::prepass::MotionVectorPrepass) or
+    ///   [`DeferredPrepass`](crate::prepass::DeferredPrepass), as the prepasses share the depth
+    ///   texture and don't support stencil formats.
+    /// - The stencil format has less depth precision, which may cause more z-fighting.
+    pub stencil: bool,
 }
 
 impl Default for Camera3d {
@@ -59,6 +75,7 @@ impl Default for Camera3d {
             depth_texture_usages: TextureUsages::RENDER_ATTACHMENT.into(),
             screen_space_specular_transmission_steps: 1,
             screen_space_specular_transmission_quality: Default::default(),
+            stencil: false,
         }
     }
 }
@@ -42,6 +42,12 @@ pub mod graph {
 // PERF: vulkan docs recommend using 24 bit depth for better performance
 pub const CORE_3D_DEPTH_FORMAT: TextureFormat = TextureFormat::Depth32Float;
 
+/// The format of the depth texture of cameras with [`Camera3d::stencil`] enabled.
+///
+/// This trades some depth precision for an 8 bit stencil aspect, and is supported everywhere
+/// unlike [`TextureFormat::Depth32FloatStencil8`].
+pub const CORE_3D_DEPTH_STENCIL_FORMAT: TextureFormat = TextureFormat::Depth24PlusStencil8;
+
 /// True if multisampled depth textures are supported on this platform.
 ///
 /// In theory, Naga supports depth textures on WebGL 2. In practice, it doesn't,
@@ -546,6 +552,7 @@ pub fn extract_camera_prepass_phase(
             (
                 Entity,
                 &Camera,
+                &Camera3d,
                 Has<DepthPrepass>,
                 Has<NormalPrepass>,
                 Has<MotionVectorPrepass>,
@@ -558,8 +565,15 @@ pub fn extract_camera_prepass_phase(
 ) {
     live_entities.clear();
 
-    for (entity, camera, depth_prepass, normal_prepass, motion_vector_prepass, deferred_prepass) in
-        cameras_3d.iter()
+    for (
+        entity,
+        camera,
+        camera_3d,
+        depth_prepass,
+        normal_prepass,
+        motion_vector_prepass,
+        deferred_prepass,
+    ) in cameras_3d.iter()
     {
         if !camera.is_active {
             continue;
@@ -597,6 +611,11 @@ pub fn extract_camera_prepass_phase(
         if deferred_prepass {
             entity.insert(DeferredPrepass);
         }
+        if camera_3d.stencil
+            && !(depth_prepass || normal_prepass || motion_vector_prepass || deferred_prepass)
+        {
+            entity.insert(ViewDepthStencil);
+        }
     }
 
     opaque_3d_prepass_phases.retain(|entity, _| live_entities.contains(entity));
@@ -615,10 +634,16 @@ pub fn prepare_core_3d_depth_textures(
     alpha_mask_3d_phases: Res<ViewBinnedRenderPhases<AlphaMask3d>>,
     transmissive_3d_phases: Res<ViewSortedRenderPhases<Transmissive3d>>,
     transparent_3d_phases: Res<ViewSortedRenderPhases<Transparent3d>>,
-    views_3d: Query<(Entity, &ExtractedCamera, Option<&DepthPrepass>, &Camera3d)>,
+    views_3d: Query<(
+        Entity,
+        &ExtractedCamera,
+        Option<&DepthPrepass>,
+        &Camera3d,
+        Has<ViewDepthStencil>,
+    )>,
 ) {
     let mut render_target_usage = HashMap::default();
-    for (view, camera, depth_prepass, camera_3d) in &views_3d {
+    for (view, camera, depth_prepass, camera_3d, _) in &views_3d {
         if !opaque_3d_phases.contains_key(&view)
             || !alpha_mask_3d_phases.contains_key(&view)
             || !transmissive_3d_phases.contains_key(&view)
@@ -640,13 +665,19 @@ pub fn prepare_core_3d_depth_textures(
     }
 
     let mut textures = HashMap::default();
-    for (entity, camera, _, camera_3d) in &views_3d {
+    for (entity, camera, _, camera_3d, stencil) in &views_3d {
         let Some(physical_target_size) = camera.physical_target_size else {
             continue;
         };
 
+        let format = if stencil {
+            CORE_3D_DEPTH_STENCIL_FORMAT
+        } else {
+            CORE_3D_DEPTH_FORMAT
+        };
+
         let cached_texture = textures
-            .entry(camera.target.clone())
+            .entry((camera.target.clone(), format))
             .or_insert_with(|| {
                 // The size of the depth texture
                 let size = Extent3d {
@@ -665,7 +696,7 @@ pub fn prepare_core_3d_depth_textures(
                     mip_level_count: 1,
                     sample_count: msaa.samples(),
                     dimension: TextureDimension::D2,
-                    format: CORE_3D_DEPTH_FORMAT,
+                    format,
                     usage,
                     view_formats: &[],
                 };
@@ -674,16 +705,27 @@ pub fn prepare_core_3d_depth_textures(
             })
             .clone();
 
-        commands.entity(entity).insert(ViewDepthTexture::new(
+        let mut depth_texture = ViewDepthTexture::new(
             cached_texture,
             match camera_3d.depth_load_op {
                 Camera3dDepthLoadOp::Clear(v) => Some(v),
                 Camera3dDepthLoadOp::Load => None,
             },
-        ));
+        );
+        if stencil {
+            depth_texture = depth_texture.with_stencil();
+        }
+        commands.entity(entity).insert(depth_texture);
     }
 }
 
+/// Marks the views whose depth texture uses the [`CORE_3D_DEPTH_STENCIL_FORMAT`], see
+/// [`Camera3d::stencil`].
+///
+/// Pipelines drawing into the main 3d pass of these views need to use the same depth format.
+#[derive(Component, Clone, Copy, Default)]
+pub struct ViewDepthStencil;
+
 #[derive(Component)]
 pub struct ViewTransmissionTexture {
     pub texture: Texture,
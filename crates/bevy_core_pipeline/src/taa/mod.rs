@@ -4,18 +4,20 @@ use crate::{
     prelude::Camera3d,
     prepass::{DepthPrepass, MotionVectorPrepass, ViewPrepassTextures},
 };
-use bevy_app::{App, Plugin};
+use bevy_app::{App, Plugin, PostUpdate};
 use bevy_asset::{load_internal_asset, Handle};
 use bevy_core::FrameCount;
 use bevy_ecs::{
+    event::{Event, EventReader},
     prelude::{Bundle, Component, Entity},
     query::{QueryItem, With},
+    reflect::ReflectComponent,
     schedule::IntoSystemConfigs,
     system::{Commands, Query, Res, ResMut, Resource},
     world::{FromWorld, World},
 };
-use bevy_math::vec2;
-use bevy_reflect::Reflect;
+use bevy_math::{vec2, Vec2};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_render::{
     camera::{ExtractedCamera, MipBias, TemporalJitter},
     prelude::{Camera, Projection},
@@ -47,7 +49,9 @@ impl Plugin for TemporalAntiAliasPlugin {
         load_internal_asset!(app, TAA_SHADER_HANDLE, "taa.wgsl", Shader::from_wgsl);
 
         app.insert_resource(Msaa::Off)
-            .register_type::<TemporalAntiAliasSettings>();
+            .register_type::<TemporalAntiAliasSettings>()
+            .add_event::<ResetTemporalAntiAliasHistory>()
+            .add_systems(PostUpdate, reset_taa_history);
 
         let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
             return;
@@ -133,7 +137,11 @@ pub struct TemporalAntiAliasBundle {
 /// 2. Render particles after TAA
 ///
 /// If no [`MipBias`] component is attached to the camera, TAA will add a MipBias(-1.0) component.
+///
+/// On camera cuts and teleports, reset the history with [`reset`](Self::reset)
+/// or by sending a [`ResetTemporalAntiAliasHistory`] event.
 #[derive(Component, Reflect, Clone)]
+#[reflect(Component, Default)]
 pub struct TemporalAntiAliasSettings {
     /// Set to true to delete the saved temporal history (past frames).
     ///
@@ -143,11 +151,104 @@ pub struct TemporalAntiAliasSettings {
     /// After setting this to true, it will automatically be toggled
     /// back to false at the end of the frame.
     pub reset: bool,
+    /// The tradeoff between smoothness, ghosting and sharpness.
+    pub quality: TemporalAntiAliasQuality,
 }
 
 impl Default for TemporalAntiAliasSettings {
     fn default() -> Self {
-        Self { reset: true }
+        Self {
+            reset: true,
+            quality: TemporalAntiAliasQuality::default(),
+        }
+    }
+}
+
+/// The quality preset of [`TemporalAntiAliasSettings`].
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[reflect(Default)]
+pub enum TemporalAntiAliasQuality {
+    /// 4 jitter positions and min/max clamping of the history.
+    ///
+    /// Converges the fastest, but leaves more aliasing and ghosting.
+    Low,
+    /// 8 jitter positions and variance clipping of the history.
+    #[default]
+    Medium,
+    /// 16 jitter positions and variance clipping of the history.
+    ///
+    /// Gives the smoothest result on still images, but takes longer to
+    /// converge, which can show as shimmering in motion.
+    High,
+    Custom {
+        /// The number of points of the Halton sequence the camera jitters
+        /// through, which is the number of subpixel samples accumulated.
+        jitter_sample_count: u32,
+        /// How the history is constrained to the colors of the current frame.
+        clamping: TemporalAntiAliasClamping,
+    },
+}
+
+impl TemporalAntiAliasQuality {
+    fn jitter_sample_count(&self) -> u32 {
+        match self {
+            Self::Low => 4,
+            Self::Medium => 8,
+            Self::High => 16,
+            Self::Custom {
+                jitter_sample_count,
+                ..
+            } => (*jitter_sample_count).max(1),
+        }
+    }
+
+    fn clamping(&self) -> TemporalAntiAliasClamping {
+        match self {
+            Self::Low => TemporalAntiAliasClamping::MinMax,
+            Self::Medium | Self::High => TemporalAntiAliasClamping::VarianceClipping,
+            Self::Custom { clamping, .. } => *clamping,
+        }
+    }
+}
+
+/// How temporal anti-aliasing constrains the history to the colors of the
+/// neighborhood of each pixel in the current frame, which rejects stale
+/// history and reduces ghosting.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[reflect(Default)]
+pub enum TemporalAntiAliasClamping {
+    /// Clips the history to the bounding box of the 3x3 neighborhood.
+    ///
+    /// This is lenient, so it keeps more detail, but also more ghosting.
+    MinMax,
+    /// Clips the history to the mean of the 3x3 neighborhood, plus or minus
+    /// its standard deviation.
+    #[default]
+    VarianceClipping,
+}
+
+/// Deletes the temporal anti-aliasing history of a camera, or of all cameras,
+/// like setting [`TemporalAntiAliasSettings::reset`].
+///
+/// Send this on camera cuts, teleports and scene switches, where the previous
+/// frames no longer represent the current one and would leave ghost trails.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct ResetTemporalAntiAliasHistory {
+    /// The camera to reset, or `None` to reset every camera with
+    /// [`TemporalAntiAliasSettings`].
+    pub camera: Option<Entity>,
+}
+
+fn reset_taa_history(
+    mut events: EventReader<ResetTemporalAntiAliasHistory>,
+    mut cameras: Query<(Entity, &mut TemporalAntiAliasSettings)>,
+) {
+    for event in events.read() {
+        for (entity, mut taa_settings) in &mut cameras {
+            if event.camera.map_or(true, |camera| camera == entity) {
+                taa_settings.reset = true;
+            }
+        }
     }
 }
 
@@ -289,6 +390,7 @@ impl FromWorld for TaaPipeline {
 struct TaaPipelineKey {
     hdr: bool,
     reset: bool,
+    clamping: TemporalAntiAliasClamping,
 }
 
 impl SpecializedRenderPipeline for TaaPipeline {
@@ -308,6 +410,10 @@ impl SpecializedRenderPipeline for TaaPipeline {
             shader_defs.push("RESET".into());
         }
 
+        if key.clamping == TemporalAntiAliasClamping::MinMax {
+            shader_defs.push("MIN_MAX_CLAMPING".into());
+        }
+
         RenderPipelineDescriptor {
             label: Some("taa_pipeline".into()),
             layout: vec![self.taa_bind_group_layout.clone()],
@@ -359,28 +465,18 @@ fn extract_taa_settings(mut commands: Commands, mut main_world: ResMut<MainWorld
 
 fn prepare_taa_jitter_and_mip_bias(
     frame_count: Res<FrameCount>,
-    mut query: Query<
-        (Entity, &mut TemporalJitter, Option<&MipBias>),
-        With<TemporalAntiAliasSettings>,
-    >,
+    mut query: Query<(
+        Entity,
+        &mut TemporalJitter,
+        &TemporalAntiAliasSettings,
+        Option<&MipBias>,
+    )>,
     mut commands: Commands,
 ) {
-    // Halton sequence (2, 3) - 0.5, skipping i = 0
-    let halton_sequence = [
-        vec2(0.0, -0.16666666),
-        vec2(-0.25, 0.16666669),
-        vec2(0.25, -0.3888889),
-        vec2(-0.375, -0.055555552),
-        vec2(0.125, 0.2777778),
-        vec2(-0.125, -0.2777778),
-        vec2(0.375, 0.055555582),
-        vec2(-0.4375, 0.3888889),
-    ];
-
-    let offset = halton_sequence[frame_count.0 as usize % halton_sequence.len()];
-
-    for (entity, mut jitter, mip_bias) in &mut query {
-        jitter.offset = offset;
+    for (entity, mut jitter, taa_settings, mip_bias) in &mut query {
+        // Halton sequence (2, 3) - 0.5, skipping i = 0
+        let index = frame_count.0 % taa_settings.quality.jitter_sample_count() + 1;
+        jitter.offset = vec2(halton(index, 2), halton(index, 3)) - Vec2::splat(0.5);
 
         if mip_bias.is_none() {
             commands.entity(entity).insert(MipBias(-1.0));
@@ -388,6 +484,18 @@ fn prepare_taa_jitter_and_mip_bias(
     }
 }
 
+/// Returns the element with the given index of the Halton sequence with the given base.
+fn halton(mut index: u32, base: u32) -> f32 {
+    let mut fraction = 1.0;
+    let mut result = 0.0;
+    while index > 0 {
+        fraction /= base as f32;
+        result += fraction * (index % base) as f32;
+        index /= base;
+    }
+    result
+}
+
 #[derive(Component)]
 pub struct TemporalAntiAliasHistoryTextures {
     write: CachedTexture,
@@ -459,6 +567,7 @@ fn prepare_taa_pipelines(
         let mut pipeline_key = TaaPipelineKey {
             hdr: view.hdr,
             reset: taa_settings.reset,
+            clamping: taa_settings.quality.clamping(),
         };
         let pipeline_id = pipelines.specialize(&pipeline_cache, &pipeline, pipeline_key.clone());
 
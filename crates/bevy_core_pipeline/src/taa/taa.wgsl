@@ -152,13 +152,20 @@ fn taa(@location(0) uv: vec2<f32>) -> Output {
     let s_bl = sample_view_target(uv + vec2(-texel_size.x, -texel_size.y));
     let s_bm = sample_view_target(uv + vec2( 0.0,          -texel_size.y));
     let s_br = sample_view_target(uv + vec2( texel_size.x, -texel_size.y));
+    history_color = RGB_to_YCoCg(history_color);
+#ifdef MIN_MAX_CLAMPING
+    // Min/max clamping: clip to the bounding box of the neighborhood instead
+    let aabb_min = min(min(min(min(s_tl, s_tm), min(s_tr, s_ml)), min(min(s_mm, s_mr), min(s_bl, s_bm))), s_br);
+    let aabb_max = max(max(max(max(s_tl, s_tm), max(s_tr, s_ml)), max(max(s_mm, s_mr), max(s_bl, s_bm))), s_br);
+    history_color = clip_towards_aabb_center(history_color, s_mm, aabb_min, aabb_max);
+#else
     let moment_1 = s_tl + s_tm + s_tr + s_ml + s_mm + s_mr + s_bl + s_bm + s_br;
     let moment_2 = (s_tl * s_tl) + (s_tm * s_tm) + (s_tr * s_tr) + (s_ml * s_ml) + (s_mm * s_mm) + (s_mr * s_mr) + (s_bl * s_bl) + (s_bm * s_bm) + (s_br * s_br);
     let mean = moment_1 / 9.0;
     let variance = (moment_2 / 9.0) - (mean * mean);
     let std_deviation = sqrt(max(variance, vec3(0.0)));
-    history_color = RGB_to_YCoCg(history_color);
     history_color = clip_towards_aabb_center(history_color, s_mm, mean - std_deviation, mean + std_deviation);
+#endif
     history_color = YCoCg_to_RGB(history_color);
 
     // How confident we are that the history is representative of the current frame
@@ -11,11 +11,15 @@ use bevy_render::render_resource::binding_types::{
 };
 use bevy_render::renderer::RenderDevice;
 use bevy_render::texture::{CompressedImageFormats, GpuImage, Image, ImageSampler, ImageType};
-use bevy_render::view::{ExtractedView, ViewTarget, ViewUniform};
-use bevy_render::{camera::Camera, texture::FallbackImage};
+use bevy_render::view::{ExtractedView, ExtractedWindows, ViewTarget, ViewUniform};
+use bevy_render::{
+    camera::{Camera, ExtractedCamera, NormalizedRenderTarget},
+    texture::FallbackImage,
+};
 use bevy_render::{render_resource::*, Render, RenderApp, RenderSet};
 #[cfg(not(feature = "tonemapping_luts"))]
 use bevy_utils::tracing::error;
+use bevy_window::OutputColorSpace;
 use bitflags::bitflags;
 
 mod node;
@@ -113,7 +117,10 @@ impl Plugin for TonemappingPlugin {
             .init_resource::<SpecializedRenderPipelines<TonemappingPipeline>>()
             .add_systems(
                 Render,
-                prepare_view_tonemapping_pipelines.in_set(RenderSet::Prepare),
+                (
+                    disable_tonemapping_for_linear_output.in_set(RenderSet::ManageViews),
+                    prepare_view_tonemapping_pipelines.in_set(RenderSet::Prepare),
+                ),
             );
     }
 
@@ -341,6 +348,35 @@ impl FromWorld for TonemappingPipeline {
 #[derive(Component)]
 pub struct ViewTonemappingPipeline(CachedRenderPipelineId);
 
+/// Disables tonemapping and debanding for the views that render to a window
+/// with the [`OutputColorSpace::LinearPassthrough`] color space, so that their
+/// pixel values reach the surface unchanged.
+pub fn disable_tonemapping_for_linear_output(
+    windows: Res<ExtractedWindows>,
+    mut views: Query<(
+        &ExtractedCamera,
+        Option<&mut Tonemapping>,
+        Option<&mut DebandDither>,
+    )>,
+) {
+    for (camera, tonemapping, dither) in &mut views {
+        let Some(NormalizedRenderTarget::Window(window_ref)) = &camera.target else {
+            continue;
+        };
+        if windows.get(&window_ref.entity()).map_or(true, |window| {
+            window.output_color_space != OutputColorSpace::LinearPassthrough
+        }) {
+            continue;
+        }
+        if let Some(mut tonemapping) = tonemapping {
+            *tonemapping = Tonemapping::None;
+        }
+        if let Some(mut dither) = dither {
+            *dither = DebandDither::Disabled;
+        }
+    }
+}
+
 pub fn prepare_view_tonemapping_pipelines(
     mut commands: Commands,
     pipeline_cache: Res<PipelineCache>,
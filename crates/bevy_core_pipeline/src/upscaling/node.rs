@@ -2,7 +2,7 @@ use crate::{blit::BlitPipeline, upscaling::ViewUpscalingPipeline};
 use bevy_ecs::{prelude::*, query::QueryItem};
 use bevy_render::camera::{ClearColor, ClearColorConfig};
 use bevy_render::{
-    camera::{CameraOutputMode, ExtractedCamera},
+    camera::{CameraOutputMode, ExtractedCamera, ExtractedPixelPerfect},
     render_graph::{NodeRunError, RenderGraphContext, ViewNode},
     render_resource::{
         BindGroup, BindGroupEntries, PipelineCache, RenderPassDescriptor, TextureViewId,
@@ -22,20 +22,21 @@ impl ViewNode for UpscalingNode {
         &'static ViewTarget,
         &'static ViewUpscalingPipeline,
         Option<&'static ExtractedCamera>,
+        Option<&'static ExtractedPixelPerfect>,
     );
 
     fn run(
         &self,
         _graph: &mut RenderGraphContext,
         render_context: &mut RenderContext,
-        (target, upscaling_target, camera): QueryItem<Self::ViewQuery>,
+        (target, upscaling_target, camera, pixel_perfect): QueryItem<Self::ViewQuery>,
         world: &World,
     ) -> Result<(), NodeRunError> {
         let pipeline_cache = world.get_resource::<PipelineCache>().unwrap();
         let blit_pipeline = world.get_resource::<BlitPipeline>().unwrap();
         let clear_color_global = world.get_resource::<ClearColor>().unwrap();
 
-        let clear_color = if let Some(camera) = camera {
+        let mut clear_color = if let Some(camera) = camera {
             match camera.output_mode {
                 CameraOutputMode::Write { clear_color, .. } => clear_color,
                 CameraOutputMode::Skip => return Ok(()),
@@ -43,6 +44,10 @@ impl ViewNode for UpscalingNode {
         } else {
             ClearColorConfig::Default
         };
+        // The letterbox of a pixel perfect camera is the part of the output it doesn't draw to.
+        if let Some(pixel_perfect) = pixel_perfect {
+            clear_color = pixel_perfect.letterbox;
+        }
         let clear_color = match clear_color {
             ClearColorConfig::Default => Some(clear_color_global.0),
             ClearColorConfig::Custom(color) => Some(color),
@@ -86,6 +91,17 @@ impl ViewNode for UpscalingNode {
 
         render_pass.set_pipeline(pipeline);
         render_pass.set_bind_group(0, bind_group, &[]);
+        if let Some(pixel_perfect) = pixel_perfect {
+            let output_rect = pixel_perfect.output_rect;
+            render_pass.set_viewport(
+                output_rect.min.x as f32,
+                output_rect.min.y as f32,
+                output_rect.width() as f32,
+                output_rect.height() as f32,
+                0.0,
+                1.0,
+            );
+        }
         render_pass.draw(0..3, 0..1);
 
         Ok(())
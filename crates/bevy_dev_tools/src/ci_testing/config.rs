@@ -1,5 +1,7 @@
 use bevy_ecs::prelude::*;
-use serde::Deserialize;
+use bevy_input::{keyboard::KeyCode, mouse::MouseButton};
+use bevy_math::Vec2;
+use serde::{Deserialize, Serialize};
 
 /// A configuration struct for automated CI testing.
 ///
@@ -25,6 +27,16 @@ pub struct CiTestingSetup {
     ///
     /// [`TimeUpdateStrategy::ManualDuration`]: bevy_time::TimeUpdateStrategy::ManualDuration
     pub fixed_frame_time: Option<f32>,
+    /// The number of frames to run before stopping the program with [`AppExit::Success`], as if
+    /// an [`CiTestingEvent::AppExit`] was sent on that frame.
+    ///
+    /// [`AppExit::Success`]: bevy_app::AppExit::Success
+    pub frame_count: Option<u32>,
+    /// The seed to insert as the [`CiTestingSeed`] resource, so random number generators seeded
+    /// from it give the same results on every run.
+    pub seed: Option<u64>,
+    /// A file to write the [`CiTestingDiagnostics`] to when the program stops.
+    pub diagnostics_output: Option<String>,
 }
 
 /// An event to send at a given frame, used for CI testing.
@@ -43,12 +55,49 @@ pub enum CiTestingEvent {
     AppExit,
     /// Sends a [`CiTestingCustomEvent`] using the given [`String`].
     Custom(String),
+    /// Presses a key on the primary window, as if the user did.
+    KeyPress(KeyCode),
+    /// Releases a key on the primary window.
+    KeyRelease(KeyCode),
+    /// Presses a mouse button on the primary window.
+    MouseButtonPress(MouseButton),
+    /// Releases a mouse button on the primary window.
+    MouseButtonRelease(MouseButton),
+    /// Moves the cursor to the given logical position in the primary window.
+    CursorMove(Vec2),
 }
 
 /// A custom event that can be configured from a configuration file for CI testing.
 #[derive(Event)]
 pub struct CiTestingCustomEvent(pub String);
 
+/// The seed of [`CiTestingSetup::seed`].
+///
+/// Seed the random number generators of the application from this resource when it exists, so
+/// that screenshots and diagnostics can be compared between runs.
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CiTestingSeed(pub u64);
+
+/// The diagnostics written to [`CiTestingSetup::diagnostics_output`] when the program stops.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct CiTestingDiagnostics {
+    /// The number of frames that ran.
+    pub frames: u32,
+    /// The measurements of every diagnostic.
+    pub diagnostics: Vec<CiTestingDiagnostic>,
+}
+
+/// The measurements of a diagnostic, see [`CiTestingDiagnostics`].
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct CiTestingDiagnostic {
+    /// The path of the diagnostic.
+    pub path: String,
+    /// The average of the recorded measurements.
+    pub average: Option<f64>,
+    /// The smoothed latest measurement.
+    pub smoothed: Option<f64>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,6 +119,7 @@ mod tests {
         let expected = CiTestingConfig {
             setup: CiTestingSetup {
                 fixed_frame_time: Some(0.03),
+                ..Default::default()
             },
             events: vec![
                 CiTestingEventOnFrame(100, CiTestingEvent::Custom("Hello, world!".into())),
@@ -82,4 +132,44 @@ mod tests {
 
         assert_eq!(config, expected);
     }
+
+    #[test]
+    fn deserialize_frame_capture() {
+        const INPUT: &str = r#"
+(
+    setup: (
+        fixed_frame_time: Some(0.02),
+        frame_count: Some(120),
+        seed: Some(42),
+        diagnostics_output: Some("diagnostics.ron"),
+    ),
+    events: [
+        (10, KeyPress(Space)),
+        (11, KeyRelease(Space)),
+        (20, CursorMove((64.0, 32.0))),
+        (21, MouseButtonPress(Left)),
+        (119, Screenshot),
+    ],
+)"#;
+
+        let expected = CiTestingConfig {
+            setup: CiTestingSetup {
+                fixed_frame_time: Some(0.02),
+                frame_count: Some(120),
+                seed: Some(42),
+                diagnostics_output: Some("diagnostics.ron".into()),
+            },
+            events: vec![
+                CiTestingEventOnFrame(10, CiTestingEvent::KeyPress(KeyCode::Space)),
+                CiTestingEventOnFrame(11, CiTestingEvent::KeyRelease(KeyCode::Space)),
+                CiTestingEventOnFrame(20, CiTestingEvent::CursorMove(Vec2::new(64.0, 32.0))),
+                CiTestingEventOnFrame(21, CiTestingEvent::MouseButtonPress(MouseButton::Left)),
+                CiTestingEventOnFrame(119, CiTestingEvent::Screenshot),
+            ],
+        };
+
+        let config: CiTestingConfig = ron::from_str(INPUT).unwrap();
+
+        assert_eq!(config, expected);
+    }
 }
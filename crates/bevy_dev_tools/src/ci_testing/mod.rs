@@ -15,6 +15,11 @@ use std::time::Duration;
 /// (`ci_testing_config.ron` by default) and executes its specified actions. For a reference of the
 /// allowed configuration, see [`CiTestingConfig`].
 ///
+/// Together, [`CiTestingSetup::fixed_frame_time`], [`CiTestingSetup::frame_count`] and
+/// [`CiTestingSetup::seed`] make a run render the same frames every time, so that the screenshots
+/// and the [`CiTestingDiagnostics`] it saves can be compared, while input events like
+/// [`CiTestingEvent::KeyPress`] script the interactions of a player.
+///
 /// This plugin is included within `DefaultPlugins` and `MinimalPlugins` when the `bevy_ci_testing`
 /// feature is enabled. It is recommended to only used this plugin during testing (manual or
 /// automatic), and disable it during regular development and for production builds.
@@ -46,8 +51,13 @@ impl Plugin for CiTestingPlugin {
             )));
         }
 
+        if let Some(seed) = config.setup.seed {
+            app.insert_resource(CiTestingSeed(seed));
+        }
+
+        // Events are sent at the start of the frame so input events are handled in the same frame.
         app.add_event::<CiTestingCustomEvent>()
             .insert_resource(config)
-            .add_systems(Update, systems::send_events);
+            .add_systems(First, systems::send_events);
     }
 }
@@ -1,24 +1,39 @@
 use super::config::*;
 use bevy_app::AppExit;
+use bevy_diagnostic::DiagnosticsStore;
 use bevy_ecs::prelude::*;
+use bevy_input::{
+    keyboard::{Key, KeyCode, KeyboardInput, NativeKey},
+    mouse::{MouseButton, MouseButtonInput},
+    ButtonState,
+};
 use bevy_render::view::screenshot::ScreenshotManager;
 use bevy_utils::tracing::{debug, info, warn};
-use bevy_window::PrimaryWindow;
+use bevy_window::{CursorMoved, PrimaryWindow, Window};
 
 pub(crate) fn send_events(world: &mut World, mut current_frame: Local<u32>) {
     let mut config = world.resource_mut::<CiTestingConfig>();
 
     // Take all events for the current frame, leaving all the remaining alone.
     let events = std::mem::take(&mut config.events);
-    let (to_run, remaining): (Vec<_>, _) = events
+    let (mut to_run, remaining): (Vec<_>, _) = events
         .into_iter()
         .partition(|event| event.0 == *current_frame);
     config.events = remaining;
 
+    // Stop after the configured number of frames, once the other events of the frame ran.
+    if config.setup.frame_count == Some(*current_frame) {
+        to_run.push(CiTestingEventOnFrame(
+            *current_frame,
+            CiTestingEvent::AppExit,
+        ));
+    }
+
     for CiTestingEventOnFrame(_, event) in to_run {
         debug!("Handling event: {:?}", event);
         match event {
             CiTestingEvent::AppExit => {
+                write_diagnostics(world, *current_frame);
                 world.send_event(AppExit::Success);
                 info!("Exiting after {} frames. Test successful!", *current_frame);
             }
@@ -44,8 +59,92 @@ pub(crate) fn send_events(world: &mut World, mut current_frame: Local<u32>) {
             CiTestingEvent::Custom(event_string) => {
                 world.send_event(CiTestingCustomEvent(event_string));
             }
+            CiTestingEvent::KeyPress(key_code) => {
+                send_keyboard_input(world, key_code, ButtonState::Pressed);
+            }
+            CiTestingEvent::KeyRelease(key_code) => {
+                send_keyboard_input(world, key_code, ButtonState::Released);
+            }
+            CiTestingEvent::MouseButtonPress(button) => {
+                send_mouse_button_input(world, button, ButtonState::Pressed);
+            }
+            CiTestingEvent::MouseButtonRelease(button) => {
+                send_mouse_button_input(world, button, ButtonState::Released);
+            }
+            CiTestingEvent::CursorMove(position) => {
+                let mut primary_window_query =
+                    world.query_filtered::<(Entity, &mut Window), With<PrimaryWindow>>();
+                let Ok((window, mut primary_window)) = primary_window_query.get_single_mut(world)
+                else {
+                    warn!("Moving the cursor, but PrimaryWindow is not available");
+                    continue;
+                };
+                let delta = primary_window
+                    .cursor_position()
+                    .map(|previous| position - previous);
+                primary_window.set_cursor_position(Some(position));
+                world.send_event(CursorMoved {
+                    window,
+                    position,
+                    delta,
+                });
+            }
         }
     }
 
     *current_frame += 1;
 }
+
+fn primary_window_entity(world: &mut World) -> Entity {
+    world
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .get_single(world)
+        .unwrap_or(Entity::PLACEHOLDER)
+}
+
+fn send_keyboard_input(world: &mut World, key_code: KeyCode, state: ButtonState) {
+    let window = primary_window_entity(world);
+    world.send_event(KeyboardInput {
+        key_code,
+        logical_key: Key::Unidentified(NativeKey::Unidentified),
+        state,
+        window,
+    });
+}
+
+fn send_mouse_button_input(world: &mut World, button: MouseButton, state: ButtonState) {
+    let window = primary_window_entity(world);
+    world.send_event(MouseButtonInput {
+        button,
+        state,
+        window,
+    });
+}
+
+fn write_diagnostics(world: &World, frames: u32) {
+    let config = world.resource::<CiTestingConfig>();
+    let Some(path) = &config.setup.diagnostics_output else {
+        return;
+    };
+
+    let diagnostics = CiTestingDiagnostics {
+        frames,
+        diagnostics: world
+            .get_resource::<DiagnosticsStore>()
+            .into_iter()
+            .flat_map(DiagnosticsStore::iter)
+            .map(|diagnostic| CiTestingDiagnostic {
+                path: diagnostic.path().to_string(),
+                average: diagnostic.average(),
+                smoothed: diagnostic.smoothed(),
+            })
+            .collect(),
+    };
+    let result = ron::ser::to_string_pretty(&diagnostics, Default::default())
+        .map_err(|error| error.to_string())
+        .and_then(|text| std::fs::write(path, text).map_err(|error| error.to_string()));
+    match result {
+        Ok(()) => info!("Wrote diagnostics to {path}."),
+        Err(error) => warn!("Failed to write diagnostics to {path}: {error}"),
+    }
+}
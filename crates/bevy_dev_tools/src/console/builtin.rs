@@ -0,0 +1,367 @@
+//! The commands every [`DevConsole`] starts with.
+
+use bevy_core::Name;
+use bevy_ecs::{
+    entity::Entity,
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+    world::World,
+};
+use bevy_reflect::{
+    attributes::{CustomAttributes, DefaultValue, DisplayName, NumericRange, Tooltip},
+    serde::TypedReflectDeserializer,
+    GetPath, Reflect, TypeInfo, TypeRegistration, TypeRegistry,
+};
+use serde::de::DeserializeSeed;
+
+use super::{next_word, ConsoleCommandError, ConsoleCommands, DevConsole};
+
+pub(super) fn add_builtin_commands(commands: &mut ConsoleCommands) {
+    commands.add_raw(
+        "help",
+        "Lists the commands, or describes one: `help [command]`",
+        help,
+    );
+    commands.add(
+        "clear",
+        "Clears the console output",
+        |world: &mut World, ()| {
+            world.resource_mut::<DevConsole>().clear_output();
+        },
+    );
+    commands.add_raw(
+        "get",
+        "Prints a reflected resource or one of its fields: `get <Resource>[.field.path]`",
+        get_resource,
+    );
+    commands.add_raw(
+        "set",
+        "Sets a field of a reflected resource from RON: `set <Resource>[.field.path] <value>`",
+        set_resource,
+    );
+    commands.add_raw(
+        "get_component",
+        "Prints a reflected component or one of its fields: \
+        `get_component <entity> <Component>[.field.path]`",
+        get_component,
+    );
+    commands.add_raw(
+        "set_component",
+        "Sets a field of a reflected component from RON: \
+        `set_component <entity> <Component>[.field.path] <value>`",
+        set_component,
+    );
+    commands.add_raw(
+        "describe",
+        "Lists the fields of a reflected type with their metadata: `describe <Type>`",
+        describe,
+    );
+}
+
+fn help(world: &mut World, args: &str) -> Result<(), ConsoleCommandError> {
+    let Some(commands) = world.get_resource::<ConsoleCommands>() else {
+        return Ok(());
+    };
+    let lines: Vec<String> = match next_word(args)? {
+        Some((name, _)) => {
+            let description = commands
+                .description(&name)
+                .ok_or(ConsoleCommandError::UnknownCommand(name.clone()))?;
+            vec![format!("{name}: {description}")]
+        }
+        None => commands
+            .names()
+            .map(|name| format!("{name}: {}", commands.description(name).unwrap_or_default()))
+            .collect(),
+    };
+    let mut console = world.resource_mut::<DevConsole>();
+    for line in lines {
+        console.print(line);
+    }
+    Ok(())
+}
+
+fn get_resource(world: &mut World, args: &str) -> Result<(), ConsoleCommandError> {
+    let (target, _) = next_word(args)?.ok_or_else(|| usage("get"))?;
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let (registration, path) = find_registration(&registry, &target)?;
+    let value = reflect_resource(registration)?
+        .reflect(world)
+        .ok_or_else(|| missing_resource(registration))?;
+    let text = format!("{:?}", reflect_field(value, path)?);
+    world.resource_mut::<DevConsole>().print(text);
+    Ok(())
+}
+
+fn set_resource(world: &mut World, args: &str) -> Result<(), ConsoleCommandError> {
+    let (target, value) = next_word(args)?.ok_or_else(|| usage("set"))?;
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let (registration, path) = find_registration(&registry, &target)?;
+    let mut resource = reflect_resource(registration)?
+        .reflect_mut(world)
+        .ok_or_else(|| missing_resource(registration))?;
+    apply_value(&mut *resource, path, value, &registry)
+}
+
+fn get_component(world: &mut World, args: &str) -> Result<(), ConsoleCommandError> {
+    let (entity, rest) = next_word(args)?.ok_or_else(|| usage("get_component"))?;
+    let (target, _) = next_word(rest)?.ok_or_else(|| usage("get_component"))?;
+    let entity = find_entity(world, &entity)?;
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let (registration, path) = find_registration(&registry, &target)?;
+    let value = reflect_component(registration)?
+        .reflect(world.entity(entity))
+        .ok_or_else(|| missing_component(registration, entity))?;
+    let text = format!("{:?}", reflect_field(value, path)?);
+    world.resource_mut::<DevConsole>().print(text);
+    Ok(())
+}
+
+fn set_component(world: &mut World, args: &str) -> Result<(), ConsoleCommandError> {
+    let (entity, rest) = next_word(args)?.ok_or_else(|| usage("set_component"))?;
+    let (target, value) = next_word(rest)?.ok_or_else(|| usage("set_component"))?;
+    let entity = find_entity(world, &entity)?;
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let (registration, path) = find_registration(&registry, &target)?;
+    let mut component = reflect_component(registration)?
+        .reflect_mut(world.entity_mut(entity))
+        .ok_or_else(|| missing_component(registration, entity))?;
+    apply_value(&mut *component, path, value, &registry)
+}
+
+fn describe(world: &mut World, args: &str) -> Result<(), ConsoleCommandError> {
+    let (target, _) = next_word(args)?.ok_or_else(|| usage("describe"))?;
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let (registration, _) = find_registration(&registry, &target)?;
+    let type_info = registration.type_info();
+    let fields: Vec<_> = match type_info {
+        TypeInfo::Struct(info) => info
+            .iter()
+            .map(|field| {
+                (
+                    field.name().to_string(),
+                    field.type_path_table().short_path(),
+                    field.custom_attributes(),
+                )
+            })
+            .collect(),
+        TypeInfo::TupleStruct(info) => info
+            .iter()
+            .map(|field| {
+                (
+                    field.index().to_string(),
+                    field.type_path_table().short_path(),
+                    field.custom_attributes(),
+                )
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let mut console = world.resource_mut::<DevConsole>();
+    console.print(type_info.type_path());
+    for (name, type_path, attributes) in fields {
+        console.print(format!(
+            "  {name}: {type_path}{}",
+            describe_attributes(attributes)
+        ));
+    }
+    Ok(())
+}
+
+/// Formats the [`DisplayName`], [`NumericRange`], [`DefaultValue`] and [`Tooltip`] of a field.
+fn describe_attributes(attributes: &CustomAttributes) -> String {
+    let mut text = String::new();
+    if let Some(DisplayName(name)) = attributes.get::<DisplayName>() {
+        text.push_str(&format!(" \"{name}\""));
+    }
+    if let Some(range) = attributes.get::<NumericRange>() {
+        text.push_str(&format!(" [{}..={}", range.min, range.max));
+        if let Some(step) = range.step {
+            text.push_str(&format!(", step {step}"));
+        }
+        text.push(']');
+    }
+    if let Some(default) = attributes.get::<DefaultValue>() {
+        text.push_str(&format!(" = {:?}", default.value()));
+    }
+    if let Some(Tooltip(tooltip)) = attributes.get::<Tooltip>() {
+        text.push_str(&format!(" - {tooltip}"));
+    }
+    text
+}
+
+fn usage(command: &str) -> ConsoleCommandError {
+    ConsoleCommandError::Failed(format!(
+        "Missing arguments, see `help {command}` for the usage"
+    ))
+}
+
+/// Splits `target` into a type name and a field path, and finds the type by its full or short
+/// type path.
+fn find_registration<'a, 't>(
+    registry: &'a TypeRegistry,
+    target: &'t str,
+) -> Result<(&'a TypeRegistration, &'t str), ConsoleCommandError> {
+    let (type_name, path) = target.split_once('.').unwrap_or((target, ""));
+    let registration = registry
+        .get_with_type_path(type_name)
+        .or_else(|| registry.get_with_short_type_path(type_name))
+        .ok_or_else(|| {
+            ConsoleCommandError::Failed(format!(
+                "No registered type is called `{type_name}`, or the short name is ambiguous"
+            ))
+        })?;
+    Ok((registration, path))
+}
+
+fn reflect_resource(
+    registration: &TypeRegistration,
+) -> Result<&ReflectResource, ConsoleCommandError> {
+    registration.data::<ReflectResource>().ok_or_else(|| {
+        ConsoleCommandError::Failed(format!(
+            "`{}` doesn't reflect `Resource`",
+            registration.type_info().type_path()
+        ))
+    })
+}
+
+fn reflect_component(
+    registration: &TypeRegistration,
+) -> Result<&ReflectComponent, ConsoleCommandError> {
+    registration.data::<ReflectComponent>().ok_or_else(|| {
+        ConsoleCommandError::Failed(format!(
+            "`{}` doesn't reflect `Component`",
+            registration.type_info().type_path()
+        ))
+    })
+}
+
+fn missing_resource(registration: &TypeRegistration) -> ConsoleCommandError {
+    ConsoleCommandError::Failed(format!(
+        "The `{}` resource doesn't exist",
+        registration.type_info().type_path()
+    ))
+}
+
+fn missing_component(registration: &TypeRegistration, entity: Entity) -> ConsoleCommandError {
+    ConsoleCommandError::Failed(format!(
+        "Entity {entity} doesn't have a `{}` component",
+        registration.type_info().type_path()
+    ))
+}
+
+/// Finds an entity by its [`Name`], or by its index and generation as displayed, like `12v1`.
+fn find_entity(world: &mut World, name: &str) -> Result<Entity, ConsoleCommandError> {
+    let displayed = name.split_once('v').and_then(|(index, generation)| {
+        let entity = world.entities().resolve_from_id(index.parse().ok()?)?;
+        (entity.generation() == generation.parse::<u32>().ok()?).then_some(entity)
+    });
+    if let Some(entity) = displayed.filter(|&entity| world.get_entity(entity).is_some()) {
+        return Ok(entity);
+    }
+
+    world
+        .query::<(Entity, &Name)>()
+        .iter(world)
+        .find_map(|(entity, entity_name)| (entity_name.as_str() == name).then_some(entity))
+        .ok_or_else(|| ConsoleCommandError::Failed(format!("No entity is called `{name}`")))
+}
+
+fn reflect_field<'a>(
+    value: &'a dyn Reflect,
+    path: &str,
+) -> Result<&'a dyn Reflect, ConsoleCommandError> {
+    if path.is_empty() {
+        return Ok(value);
+    }
+    value
+        .reflect_path(path)
+        .map_err(|error| ConsoleCommandError::Failed(error.to_string()))
+}
+
+/// Finds the custom attributes of the field of `target` at `path`, when it's a named or tuple
+/// struct field.
+fn field_attributes(target: &dyn Reflect, path: &str) -> Option<&'static CustomAttributes> {
+    if path.is_empty() {
+        return None;
+    }
+    let (parent_path, name) = path.rsplit_once('.').unwrap_or(("", path));
+    let parent = reflect_field(target, parent_path).ok()?;
+    match parent.get_represented_type_info()? {
+        TypeInfo::Struct(info) => Some(info.field(name)?.custom_attributes()),
+        TypeInfo::TupleStruct(info) => Some(info.field_at(name.parse().ok()?)?.custom_attributes()),
+        _ => None,
+    }
+}
+
+/// Converts a reflected number to an `f64`, to compare it to a [`NumericRange`].
+fn as_f64(value: &dyn Reflect) -> Option<f64> {
+    macro_rules! downcast {
+        ($($ty:ty),*) => {
+            $(if let Some(value) = value.downcast_ref::<$ty>() {
+                return Some(*value as f64);
+            })*
+        };
+    }
+    if let Some(value) = value.downcast_ref::<f64>() {
+        return Some(*value);
+    }
+    downcast!(f32, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+    None
+}
+
+/// Deserializes `value` as RON into the field of `target` at `path`.
+///
+/// Numbers outside of the [`NumericRange`] of the field are rejected.
+fn apply_value(
+    target: &mut dyn Reflect,
+    path: &str,
+    value: &str,
+    registry: &TypeRegistry,
+) -> Result<(), ConsoleCommandError> {
+    let range =
+        field_attributes(target, path).and_then(|attributes| attributes.get::<NumericRange>());
+    let field = if path.is_empty() {
+        target
+    } else {
+        target
+            .reflect_path_mut(path)
+            .map_err(|error| ConsoleCommandError::Failed(error.to_string()))?
+    };
+    let registration = field
+        .get_represented_type_info()
+        .and_then(|type_info| registry.get(type_info.type_id()))
+        .ok_or_else(|| {
+            ConsoleCommandError::Failed(format!(
+                "The type of `{}` isn't registered",
+                field.reflect_type_path()
+            ))
+        })?;
+
+    let invalid_value = |error: ron::Error| {
+        ConsoleCommandError::Failed(format!(
+            "Invalid `{}` value: {error}",
+            registration.type_info().type_path()
+        ))
+    };
+    let mut deserializer =
+        ron::Deserializer::from_str(value.trim()).map_err(|error| invalid_value(error.code))?;
+    let value = TypedReflectDeserializer::new(registration, registry)
+        .deserialize(&mut deserializer)
+        .map_err(invalid_value)?;
+    if let (Some(range), Some(number)) = (range, as_f64(&*value)) {
+        if !range.contains(number) {
+            return Err(ConsoleCommandError::Failed(format!(
+                "`{path}` must be between {} and {}",
+                range.min, range.max
+            )));
+        }
+    }
+    field.apply(&*value);
+    Ok(())
+}
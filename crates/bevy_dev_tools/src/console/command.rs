@@ -0,0 +1,300 @@
+//! Registration, parsing and dispatch of console commands.
+
+use std::{any::type_name, collections::BTreeMap, fmt::Display, str::FromStr, sync::Arc};
+
+use bevy_ecs::{system::Resource, world::World};
+use bevy_utils::{all_tuples, get_short_name};
+use thiserror::Error;
+
+/// The function run by a console command, given the raw text following the command name.
+pub type ConsoleCommandFn =
+    dyn Fn(&mut World, &str) -> Result<(), ConsoleCommandError> + Send + Sync + 'static;
+
+struct ConsoleCommand {
+    description: String,
+    run: Arc<ConsoleCommandFn>,
+}
+
+/// The commands that can be run from the [`DevConsole`](super::DevConsole).
+///
+/// Commands are usually added with [`DevConsoleAppExt::add_console_command`](super::DevConsoleAppExt::add_console_command).
+#[derive(Resource, Default)]
+pub struct ConsoleCommands {
+    commands: BTreeMap<String, ConsoleCommand>,
+}
+
+impl ConsoleCommands {
+    /// Adds a command whose arguments are parsed into `A` before `handler` runs.
+    ///
+    /// A command with the same name is replaced.
+    pub fn add<A: ConsoleArgs>(
+        &mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        handler: impl Fn(&mut World, A) + Send + Sync + 'static,
+    ) {
+        self.add_raw(name, description, move |world, args| {
+            let args = A::parse(&tokenize(args)?)?;
+            handler(world, args);
+            Ok(())
+        });
+    }
+
+    /// Adds a command that is given the unparsed text following its name.
+    ///
+    /// A command with the same name is replaced.
+    pub fn add_raw(
+        &mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        handler: impl Fn(&mut World, &str) -> Result<(), ConsoleCommandError> + Send + Sync + 'static,
+    ) {
+        self.commands.insert(
+            name.into(),
+            ConsoleCommand {
+                description: description.into(),
+                run: Arc::new(handler),
+            },
+        );
+    }
+
+    /// Removes the command called `name`, returning whether it existed.
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.commands.remove(name).is_some()
+    }
+
+    /// Returns the names of all commands, in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.commands.keys().map(String::as_str)
+    }
+
+    /// Returns the description of the command called `name`.
+    pub fn description(&self, name: &str) -> Option<&str> {
+        self.commands
+            .get(name)
+            .map(|command| command.description.as_str())
+    }
+
+    /// Runs a command line with the [`ConsoleCommands`] of `world`: the first word is the command
+    /// name, the rest its arguments.
+    ///
+    /// The command has full access to `world`, including to this resource.
+    pub fn run(world: &mut World, line: &str) -> Result<(), ConsoleCommandError> {
+        let line = line.trim();
+        let (name, args) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let run = world
+            .get_resource::<Self>()
+            .and_then(|commands| commands.commands.get(name))
+            .map(|command| command.run.clone())
+            .ok_or_else(|| ConsoleCommandError::UnknownCommand(name.to_string()))?;
+        run(world, args.trim_start())
+    }
+}
+
+/// An error returned when a console command fails.
+#[derive(Error, Debug)]
+pub enum ConsoleCommandError {
+    /// No command with this name was registered.
+    #[error("Unknown command `{0}`, type `help` for the list of commands")]
+    UnknownCommand(String),
+    /// The arguments couldn't be parsed.
+    #[error(transparent)]
+    Args(#[from] ConsoleArgsError),
+    /// The command ran but failed.
+    #[error("{0}")]
+    Failed(String),
+}
+
+/// An error returned when the arguments of a console command can't be parsed.
+#[derive(Error, Debug)]
+pub enum ConsoleArgsError {
+    /// The command was given the wrong number of arguments.
+    #[error("Expected {expected} argument(s), got {got}")]
+    WrongCount {
+        /// The number of arguments the command takes.
+        expected: usize,
+        /// The number of arguments it was given.
+        got: usize,
+    },
+    /// An argument couldn't be parsed into the type the command expects.
+    #[error("Argument {index} `{value}` is not a valid `{expected}`: {reason}")]
+    Invalid {
+        /// The position of the argument, starting from 1.
+        index: usize,
+        /// The text of the argument.
+        value: String,
+        /// The name of the expected type.
+        expected: String,
+        /// Why the argument couldn't be parsed.
+        reason: String,
+    },
+    /// A quoted argument was never closed.
+    #[error("Unterminated quote")]
+    UnterminatedQuote,
+}
+
+/// Arguments of a console command, parsed from its words.
+///
+/// This is implemented for `()`, for [`Vec<String>`] to take the words as they are, and for
+/// tuples of up to 8 [`FromStr`] types to take exactly that many arguments.
+pub trait ConsoleArgs: Sized {
+    /// Parses the words following the command name.
+    fn parse(args: &[String]) -> Result<Self, ConsoleArgsError>;
+}
+
+impl ConsoleArgs for () {
+    fn parse(args: &[String]) -> Result<Self, ConsoleArgsError> {
+        if args.is_empty() {
+            Ok(())
+        } else {
+            Err(ConsoleArgsError::WrongCount {
+                expected: 0,
+                got: args.len(),
+            })
+        }
+    }
+}
+
+impl ConsoleArgs for Vec<String> {
+    fn parse(args: &[String]) -> Result<Self, ConsoleArgsError> {
+        Ok(args.to_vec())
+    }
+}
+
+fn parse_arg<T: FromStr>(index: usize, value: &str) -> Result<T, ConsoleArgsError>
+where
+    T::Err: Display,
+{
+    value
+        .parse()
+        .map_err(|error: T::Err| ConsoleArgsError::Invalid {
+            index: index + 1,
+            value: value.to_string(),
+            expected: get_short_name(type_name::<T>()),
+            reason: error.to_string(),
+        })
+}
+
+macro_rules! impl_console_args {
+    ($($t:ident),*) => {
+        impl<$($t: FromStr),*> ConsoleArgs for ($($t,)*)
+        where
+            $($t::Err: Display,)*
+        {
+            fn parse(args: &[String]) -> Result<Self, ConsoleArgsError> {
+                let names: &[&str] = &[$(stringify!($t)),*];
+                if args.len() != names.len() {
+                    return Err(ConsoleArgsError::WrongCount {
+                        expected: names.len(),
+                        got: args.len(),
+                    });
+                }
+                let mut args = args.iter().enumerate();
+                Ok(($({
+                    let (index, value) = args.next().unwrap();
+                    parse_arg::<$t>(index, value)?
+                },)*))
+            }
+        }
+    };
+}
+
+all_tuples!(impl_console_args, 1, 8, T);
+
+/// Splits the first word off `text`, returning it and the rest of the text.
+///
+/// Words are separated by whitespace, and double quotes group several words into one, with `\"`
+/// and `\\` escaping a quote and a backslash. Returns `None` if `text` has no more words.
+pub fn next_word(text: &str) -> Result<Option<(String, &str)>, ConsoleArgsError> {
+    let text = text.trim_start();
+    let Some(rest) = text.strip_prefix('"') else {
+        if text.is_empty() {
+            return Ok(None);
+        }
+        let (word, rest) = text.split_at(text.find(char::is_whitespace).unwrap_or(text.len()));
+        return Ok(Some((word.to_string(), rest)));
+    };
+
+    let mut word = String::new();
+    let mut chars = rest.char_indices();
+    while let Some((index, char)) = chars.next() {
+        match char {
+            '"' => return Ok(Some((word, &rest[index + 1..]))),
+            '\\' => match chars.next() {
+                Some((_, escaped @ ('"' | '\\'))) => word.push(escaped),
+                Some((_, other)) => {
+                    word.push('\\');
+                    word.push(other);
+                }
+                None => break,
+            },
+            _ => word.push(char),
+        }
+    }
+    Err(ConsoleArgsError::UnterminatedQuote)
+}
+
+/// Splits `text` into words, see [`next_word`].
+pub fn tokenize(mut text: &str) -> Result<Vec<String>, ConsoleArgsError> {
+    let mut words = Vec::new();
+    while let Some((word, rest)) = next_word(text)? {
+        words.push(word);
+        text = rest;
+    }
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenize_quotes() {
+        assert_eq!(
+            tokenize(r#"spawn  "Big \"Bad\" Wolf" 3"#).unwrap(),
+            ["spawn", r#"Big "Bad" Wolf"#, "3"]
+        );
+        assert_eq!(tokenize("   ").unwrap(), Vec::<String>::new());
+        assert!(matches!(
+            tokenize(r#"say "hello"#),
+            Err(ConsoleArgsError::UnterminatedQuote)
+        ));
+    }
+
+    #[test]
+    fn parse_tuple_args() {
+        let args = tokenize("3 1.5 true").unwrap();
+        assert_eq!(<(u32, f32, bool)>::parse(&args).unwrap(), (3, 1.5, true));
+        assert!(matches!(
+            <(u32,)>::parse(&args),
+            Err(ConsoleArgsError::WrongCount {
+                expected: 1,
+                got: 3
+            })
+        ));
+        assert!(matches!(
+            <(u32, u32, bool)>::parse(&args),
+            Err(ConsoleArgsError::Invalid { index: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn run_command() {
+        let mut world = World::new();
+        let mut commands = ConsoleCommands::default();
+        commands.add("add", "", |world: &mut World, (a, b): (i32, i32)| {
+            world.insert_resource(Total(a + b));
+        });
+        world.insert_resource(commands);
+
+        ConsoleCommands::run(&mut world, "add 2 -5").unwrap();
+        assert_eq!(world.resource::<Total>().0, -3);
+        assert!(matches!(
+            ConsoleCommands::run(&mut world, "sub 2 5"),
+            Err(ConsoleCommandError::UnknownCommand(name)) if name == "sub"
+        ));
+    }
+
+    #[derive(Resource)]
+    struct Total(i32);
+}
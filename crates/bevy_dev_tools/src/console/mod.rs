@@ -0,0 +1,378 @@
+//! An in-game developer console to run debug commands.
+//!
+//! Press the [`DevConsoleConfig::toggle_key`] (`` ` `` by default) to open the console, then type
+//! `help` for the list of commands. <kbd>Tab</kbd> completes command and type names, and
+//! <kbd>Up</kbd> and <kbd>Down</kbd> go through the history.
+//!
+//! The console is opt-in, it requires the `bevy_dev_console` feature.
+//!
+//! The `set` commands respect the [`NumericRange`](bevy_reflect::attributes::NumericRange) of the
+//! fields they edit, and `describe` lists the fields of a type along with their display name,
+//! range, default value and tooltip attributes.
+//!
+//! Besides the commands of the [`DevConsolePlugin`] itself, which read and edit reflected
+//! resources and components, projects add their own with
+//! [`DevConsoleAppExt::add_console_command`]. Their arguments are parsed from the words typed
+//! after the command name:
+//!
+//! ```no_run
+//! # use bevy_app::App;
+//! # use bevy_ecs::world::World;
+//! use bevy_dev_tools::console::{DevConsole, DevConsoleAppExt, DevConsolePlugin};
+//!
+//! App::new()
+//!     .add_plugins(DevConsolePlugin::default())
+//!     .add_console_command(
+//!         "give_gold",
+//!         "Gives the player some gold: `give_gold <amount>`",
+//!         |world: &mut World, (amount,): (u32,)| {
+//!             // ...
+//!             world.resource_mut::<DevConsole>().print(format!("Gave {amount} gold"));
+//!         },
+//!     )
+//!     .run();
+//! ```
+
+use std::mem;
+
+use bevy_app::{App, Plugin, PreUpdate, Startup, Update};
+use bevy_color::Color;
+use bevy_ecs::{
+    event::EventReader,
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+    schedule::IntoSystemConfigs,
+    system::{Res, ResMut, Resource},
+    world::World,
+};
+use bevy_input::{
+    keyboard::{Key, KeyCode, KeyboardInput},
+    ButtonInput, ButtonState, InputSystem,
+};
+use bevy_text::TextStyle;
+use bevy_utils::default;
+
+mod builtin;
+mod command;
+mod ui;
+
+pub use command::*;
+
+/// Global [`ZIndex`](bevy_ui::ZIndex) used to render the console.
+///
+/// This is above the [`FPS_OVERLAY_ZINDEX`](crate::fps_overlay::FPS_OVERLAY_ZINDEX), and still
+/// leaves room to render on top of the console.
+pub const DEV_CONSOLE_ZINDEX: i32 = i32::MAX - 16;
+
+/// A plugin that adds a toggleable developer console to the Bevy application.
+///
+/// See the [module docs](self) for how to use it.
+#[derive(Default)]
+pub struct DevConsolePlugin {
+    /// Starting configuration of the console, this can be later be changed through the
+    /// [`DevConsoleConfig`] resource.
+    pub config: DevConsoleConfig,
+}
+
+impl Plugin for DevConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.config.clone())
+            .init_resource::<DevConsole>();
+        builtin::add_builtin_commands(
+            &mut app
+                .world_mut()
+                .get_resource_or_insert_with(ConsoleCommands::default),
+        );
+        app.add_systems(Startup, ui::setup)
+            .add_systems(
+                PreUpdate,
+                (handle_input, run_submitted_commands)
+                    .chain()
+                    .after(InputSystem),
+            )
+            .add_systems(Update, ui::update);
+    }
+}
+
+/// Configuration options for the developer console.
+#[derive(Resource, Clone)]
+pub struct DevConsoleConfig {
+    /// The key that opens and closes the console.
+    pub toggle_key: KeyCode,
+    /// Configuration of text in the console.
+    pub text_config: TextStyle,
+    /// The color behind the console text.
+    pub background_color: Color,
+    /// The number of output lines shown while the console is open.
+    pub visible_lines: usize,
+    /// The number of output lines kept.
+    pub max_output_lines: usize,
+    /// The number of submitted command lines kept in the history.
+    pub max_history: usize,
+    /// Whether to clear the [`ButtonInput<KeyCode>`] resource while the console is open, so typing
+    /// commands doesn't also control the application.
+    ///
+    /// Systems reading [`KeyboardInput`] events directly still receive them.
+    pub capture_keyboard: bool,
+}
+
+impl Default for DevConsoleConfig {
+    fn default() -> Self {
+        DevConsoleConfig {
+            toggle_key: KeyCode::Backquote,
+            text_config: TextStyle {
+                font_size: 16.0,
+                color: Color::WHITE,
+                ..default()
+            },
+            background_color: Color::srgba(0.05, 0.05, 0.05, 0.85),
+            visible_lines: 20,
+            max_output_lines: 500,
+            max_history: 100,
+            capture_keyboard: true,
+        }
+    }
+}
+
+/// The state of the developer console: whether it's open, what's being typed, the output and the
+/// history.
+#[derive(Resource, Default)]
+pub struct DevConsole {
+    open: bool,
+    input: String,
+    output: Vec<String>,
+    history: Vec<String>,
+    /// The history entry shown in the input, while going through the history.
+    history_cursor: Option<usize>,
+    submitted: Vec<String>,
+}
+
+impl DevConsole {
+    /// Whether the console is shown and receiving keyboard input.
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Opens or closes the console.
+    pub fn set_open(&mut self, open: bool) {
+        self.open = open;
+    }
+
+    /// This will toggle the console, closing it if open and opening it if closed.
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    /// The text being typed.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// The lines printed to the console, oldest first.
+    pub fn output(&self) -> &[String] {
+        &self.output
+    }
+
+    /// The submitted command lines, oldest first.
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Prints a line to the console output. Multi-line text is split into several lines.
+    pub fn print(&mut self, text: impl AsRef<str>) {
+        self.output
+            .extend(text.as_ref().lines().map(str::to_string));
+    }
+
+    /// Removes all the output.
+    pub fn clear_output(&mut self) {
+        self.output.clear();
+    }
+
+    /// Submits a command line as if it was typed, to run at the next
+    /// [`PreUpdate`] schedule.
+    pub fn submit(&mut self, line: impl Into<String>) {
+        let line = line.into();
+        if self.history.last() != Some(&line) {
+            self.history.push(line.clone());
+        }
+        self.history_cursor = None;
+        self.submitted.push(line);
+    }
+
+    fn previous_history(&mut self) {
+        let cursor = match self.history_cursor {
+            Some(cursor) => cursor.saturating_sub(1),
+            None if self.history.is_empty() => return,
+            None => self.history.len() - 1,
+        };
+        self.history_cursor = Some(cursor);
+        self.input.clone_from(&self.history[cursor]);
+    }
+
+    fn next_history(&mut self) {
+        let Some(cursor) = self.history_cursor else {
+            return;
+        };
+        if cursor + 1 < self.history.len() {
+            self.history_cursor = Some(cursor + 1);
+            self.input.clone_from(&self.history[cursor + 1]);
+        } else {
+            self.history_cursor = None;
+            self.input.clear();
+        }
+    }
+
+    /// Completes the last word of the input with the `candidates` it's a prefix of, as far as
+    /// they agree, and prints them if there are several.
+    fn autocomplete<'a>(&mut self, candidates: impl Iterator<Item = &'a str>) {
+        let start = self.input.rfind(' ').map_or(0, |index| index + 1);
+        let word = &self.input[start..];
+        let mut matches: Vec<&str> = candidates
+            .filter(|candidate| candidate.starts_with(word))
+            .collect();
+        matches.sort_unstable();
+        matches.dedup();
+
+        let completion = match matches.as_slice() {
+            [] => return,
+            [only] => format!("{only} "),
+            [first, rest @ ..] => {
+                let common = rest.iter().fold(first.len(), |common, candidate| {
+                    first[..common]
+                        .char_indices()
+                        .zip(candidate.chars())
+                        .find(|((_, a), b)| a != b)
+                        .map_or(common.min(candidate.len()), |((index, _), _)| index)
+                });
+                let completion = first[..common].to_string();
+                self.print(matches.join("  "));
+                completion
+            }
+        };
+        self.input.truncate(start);
+        self.input.push_str(&completion);
+    }
+
+    fn trim(&mut self, max_output_lines: usize, max_history: usize) {
+        let excess = self.output.len().saturating_sub(max_output_lines);
+        self.output.drain(..excess);
+        let excess = self.history.len().saturating_sub(max_history);
+        self.history.drain(..excess);
+    }
+}
+
+/// Adds console commands to an [`App`].
+pub trait DevConsoleAppExt {
+    /// Adds a command to the developer console, replacing any command with the same name.
+    ///
+    /// The words typed after the command name are parsed into `A` before `handler` runs, see
+    /// [`ConsoleArgs`] for the supported arguments. Use [`DevConsole::print`] to give feedback.
+    fn add_console_command<A: ConsoleArgs>(
+        &mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        handler: impl Fn(&mut World, A) + Send + Sync + 'static,
+    ) -> &mut Self;
+}
+
+impl DevConsoleAppExt for App {
+    fn add_console_command<A: ConsoleArgs>(
+        &mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        handler: impl Fn(&mut World, A) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_insert_with(ConsoleCommands::default)
+            .add(name, description, handler);
+        self
+    }
+}
+
+fn handle_input(
+    mut console: ResMut<DevConsole>,
+    config: Res<DevConsoleConfig>,
+    commands: Res<ConsoleCommands>,
+    type_registry: Res<AppTypeRegistry>,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    keys: Option<ResMut<ButtonInput<KeyCode>>>,
+) {
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        if event.key_code == config.toggle_key {
+            console.toggle();
+            continue;
+        }
+        if !console.open {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Enter => {
+                let line = mem::take(&mut console.input);
+                if !line.trim().is_empty() {
+                    console.submit(line);
+                }
+            }
+            Key::Backspace => {
+                console.input.pop();
+            }
+            Key::Escape => console.open = false,
+            Key::ArrowUp => console.previous_history(),
+            Key::ArrowDown => console.next_history(),
+            Key::Tab => {
+                if console.input.trim_start().contains(' ') {
+                    let type_registry = type_registry.read();
+                    console.autocomplete(
+                        type_registry
+                            .iter()
+                            .filter(|registration| {
+                                registration.data::<ReflectResource>().is_some()
+                                    || registration.data::<ReflectComponent>().is_some()
+                            })
+                            .map(|registration| {
+                                registration.type_info().type_path_table().short_path()
+                            }),
+                    );
+                } else {
+                    console.autocomplete(commands.names());
+                }
+            }
+            Key::Space => console.input.push(' '),
+            Key::Character(characters) => console
+                .input
+                .extend(characters.chars().filter(|char| !char.is_control())),
+            _ => {}
+        }
+    }
+
+    if console.open && config.capture_keyboard {
+        if let Some(mut keys) = keys {
+            keys.reset_all();
+        }
+    }
+}
+
+fn run_submitted_commands(world: &mut World) {
+    if world.resource::<DevConsole>().submitted.is_empty() {
+        return;
+    }
+
+    let submitted = mem::take(&mut world.resource_mut::<DevConsole>().submitted);
+    for line in submitted {
+        world
+            .resource_mut::<DevConsole>()
+            .print(format!("> {line}"));
+        if let Err(error) = ConsoleCommands::run(world, &line) {
+            world.resource_mut::<DevConsole>().print(error.to_string());
+        }
+    }
+
+    let config = world.resource::<DevConsoleConfig>();
+    let (max_output_lines, max_history) = (config.max_output_lines, config.max_history);
+    world
+        .resource_mut::<DevConsole>()
+        .trim(max_output_lines, max_history);
+}
@@ -0,0 +1,98 @@
+//! The overlay showing the [`DevConsole`].
+
+use bevy_ecs::{
+    change_detection::DetectChanges,
+    component::Component,
+    query::{With, Without},
+    system::{Commands, Query, Res},
+};
+use bevy_hierarchy::{BuildChildren, ChildBuild};
+use bevy_text::{Text, TextSection};
+use bevy_ui::{
+    node_bundles::{NodeBundle, TextBundle},
+    BackgroundColor, Display, FlexDirection, PositionType, Style, UiRect, Val, ZIndex,
+};
+use bevy_utils::default;
+
+use super::{DevConsole, DevConsoleConfig, DEV_CONSOLE_ZINDEX};
+
+#[derive(Component)]
+pub(super) struct DevConsoleRoot;
+
+#[derive(Component)]
+pub(super) struct DevConsoleOutput;
+
+#[derive(Component)]
+pub(super) struct DevConsoleInput;
+
+pub(super) fn setup(mut commands: Commands, config: Res<DevConsoleConfig>) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    // We need to make sure the console doesn't affect the position of other UI nodes
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(8.0)),
+                    display: Display::None,
+                    ..default()
+                },
+                background_color: config.background_color.into(),
+                // Render the console on top of everything
+                z_index: ZIndex::Global(DEV_CONSOLE_ZINDEX),
+                ..default()
+            },
+            DevConsoleRoot,
+        ))
+        .with_children(|c| {
+            c.spawn((
+                TextBundle::from_section("", config.text_config.clone()),
+                DevConsoleOutput,
+            ));
+            c.spawn((
+                TextBundle::from_sections([
+                    TextSection::new("> ", config.text_config.clone()),
+                    TextSection::from_style(config.text_config.clone()),
+                ]),
+                DevConsoleInput,
+            ));
+        });
+}
+
+pub(super) fn update(
+    console: Res<DevConsole>,
+    config: Res<DevConsoleConfig>,
+    mut roots: Query<(&mut Style, &mut BackgroundColor), With<DevConsoleRoot>>,
+    mut outputs: Query<&mut Text, (With<DevConsoleOutput>, Without<DevConsoleInput>)>,
+    mut inputs: Query<&mut Text, (With<DevConsoleInput>, Without<DevConsoleOutput>)>,
+) {
+    if !console.is_changed() && !config.is_changed() {
+        return;
+    }
+
+    for (mut style, mut background_color) in &mut roots {
+        style.display = if console.is_open() {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        background_color.0 = config.background_color;
+    }
+
+    let output = console.output();
+    let visible = &output[output.len().saturating_sub(config.visible_lines)..];
+    for mut text in &mut outputs {
+        let section = &mut text.sections[0];
+        section.value = visible.join("\n");
+        section.style = config.text_config.clone();
+    }
+
+    for mut text in &mut inputs {
+        // Show a cursor after what's being typed
+        text.sections[1].value = format!("{}_", console.input());
+        for section in text.sections.iter_mut() {
+            section.style = config.text_config.clone();
+        }
+    }
+}
@@ -11,6 +11,7 @@ use bevy_ecs::{
     system::{Commands, Query, Res, Resource},
 };
 use bevy_hierarchy::{BuildChildren, ChildBuild};
+use bevy_render::renderer::FrameLatencyDiagnosticsPlugin;
 use bevy_text::{Font, Text, TextSection, TextStyle};
 use bevy_ui::{
     node_bundles::{NodeBundle, TextBundle},
@@ -25,7 +26,8 @@ pub const FPS_OVERLAY_ZINDEX: i32 = i32::MAX - 32;
 
 /// A plugin that adds an FPS overlay to the Bevy application.
 ///
-/// This plugin will add the [`FrameTimeDiagnosticsPlugin`] if it wasn't added before.
+/// This plugin will add the [`FrameTimeDiagnosticsPlugin`] and [`FrameLatencyDiagnosticsPlugin`]
+/// if they weren't added before.
 ///
 /// Note: It is recommended to use native overlay of rendering statistics when possible for lower overhead and more accurate results.
 /// The correct way to do this will vary by platform:
@@ -42,6 +44,9 @@ impl Plugin for FpsOverlayPlugin {
         if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
             app.add_plugins(FrameTimeDiagnosticsPlugin);
         }
+        if !app.is_plugin_added::<FrameLatencyDiagnosticsPlugin>() {
+            app.add_plugins(FrameLatencyDiagnosticsPlugin);
+        }
         app.insert_resource(self.config.clone())
             .add_systems(Startup, setup)
             .add_systems(
@@ -59,6 +64,9 @@ impl Plugin for FpsOverlayPlugin {
 pub struct FpsOverlayConfig {
     /// Configuration of text in the overlay.
     pub text_config: TextStyle,
+    /// Whether to also show the time between receiving the input of a frame and presenting it,
+    /// see [`FrameLatencyDiagnosticsPlugin`].
+    pub show_latency: bool,
 }
 
 impl Default for FpsOverlayConfig {
@@ -68,7 +76,9 @@ impl Default for FpsOverlayConfig {
                 font: Handle::<Font>::default(),
                 font_size: 32.0,
                 color: Color::WHITE,
+                ..default()
             },
+            show_latency: false,
         }
     }
 }
@@ -93,19 +103,35 @@ fn setup(mut commands: Commands, overlay_config: Res<FpsOverlayConfig>) {
                 TextBundle::from_sections([
                     TextSection::new("FPS: ", overlay_config.text_config.clone()),
                     TextSection::from_style(overlay_config.text_config.clone()),
+                    TextSection::from_style(overlay_config.text_config.clone()),
+                    TextSection::from_style(overlay_config.text_config.clone()),
                 ]),
                 FpsText,
             ));
         });
 }
 
-fn update_text(diagnostic: Res<DiagnosticsStore>, mut query: Query<&mut Text, With<FpsText>>) {
+fn update_text(
+    diagnostic: Res<DiagnosticsStore>,
+    overlay_config: Res<FpsOverlayConfig>,
+    mut query: Query<&mut Text, With<FpsText>>,
+) {
     for mut text in &mut query {
         if let Some(fps) = diagnostic.get(&FrameTimeDiagnosticsPlugin::FPS) {
             if let Some(value) = fps.smoothed() {
                 text.sections[1].value = format!("{value:.2}");
             }
         }
+        if !overlay_config.show_latency {
+            text.sections[2].value.clear();
+            text.sections[3].value.clear();
+        } else if let Some(latency) = diagnostic.get(&FrameLatencyDiagnosticsPlugin::FRAME_LATENCY)
+        {
+            if let Some(value) = latency.smoothed() {
+                text.sections[2].value = "\nLatency: ".to_owned();
+                text.sections[3].value = format!("{value:.2} ms");
+            }
+        }
     }
 }
 
@@ -0,0 +1,125 @@
+//! Module containing logic for the infinite reference grid.
+
+use bevy_app::{Plugin, PostUpdate};
+use bevy_color::{Alpha, Color};
+use bevy_ecs::{component::Component, schedule::IntoSystemConfigs, system::Query};
+use bevy_gizmos::gizmos::Gizmos;
+use bevy_math::Vec3;
+use bevy_transform::{components::GlobalTransform, TransformSystem};
+
+/// The maximum number of lines drawn in each direction, before only the major lines are drawn.
+const MAX_LINES: f32 = 400.0;
+
+/// A plugin that draws an [`InfiniteGrid`] under the cameras that have one.
+#[derive(Default)]
+pub struct InfiniteGridPlugin;
+
+impl Plugin for InfiniteGridPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.add_systems(
+            PostUpdate,
+            draw_infinite_grids.after(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+/// An editor-style reference grid on the XZ plane, following the camera it's added to.
+///
+/// The lines fade out with their distance to the camera, and the lines of the X and Z axes are
+/// highlighted. When the fade distance is too large for the spacing, only the major lines are
+/// drawn.
+///
+/// The grid is drawn with [`Gizmos`] by the [`InfiniteGridPlugin`], so it's visible in all the
+/// cameras rendering the default gizmos.
+#[derive(Component, Debug, Clone)]
+pub struct InfiniteGrid {
+    /// The distance between two lines of the grid.
+    pub spacing: f32,
+    /// Draws every n-th line with [`InfiniteGrid::major_color`].
+    pub major_every: u32,
+    /// The distance from the camera at which the lines are completely faded out.
+    pub fade_distance: f32,
+    /// The color of the minor lines.
+    pub color: Color,
+    /// The color of the major lines.
+    pub major_color: Color,
+    /// The color of the X axis.
+    pub x_axis_color: Color,
+    /// The color of the Z axis.
+    pub z_axis_color: Color,
+}
+
+impl Default for InfiniteGrid {
+    fn default() -> Self {
+        Self {
+            spacing: 1.0,
+            major_every: 10,
+            fade_distance: 100.0,
+            color: Color::srgba(0.5, 0.5, 0.5, 0.3),
+            major_color: Color::srgba(0.6, 0.6, 0.6, 0.6),
+            x_axis_color: Color::srgb(0.9, 0.2, 0.2),
+            z_axis_color: Color::srgb(0.2, 0.4, 0.9),
+        }
+    }
+}
+
+impl InfiniteGrid {
+    fn line_color(&self, index: i64, axis_color: Color, spacing_multiple: i64) -> Color {
+        if index == 0 {
+            axis_color
+        } else if (index * spacing_multiple) % self.major_every.max(1) as i64 == 0 {
+            self.major_color
+        } else {
+            self.color
+        }
+    }
+}
+
+fn draw_infinite_grids(mut gizmos: Gizmos, grids: Query<(&GlobalTransform, &InfiniteGrid)>) {
+    for (transform, grid) in &grids {
+        let camera = transform.translation();
+        let height = camera.y.abs();
+        let fade_distance = grid.fade_distance;
+        if grid.spacing <= 0.0 || height >= fade_distance {
+            continue;
+        }
+
+        // Only draw the major lines when there would be too many lines.
+        let mut spacing = grid.spacing;
+        let mut spacing_multiple = 1;
+        if fade_distance / spacing > MAX_LINES {
+            spacing_multiple = grid.major_every.max(1) as i64;
+            spacing *= spacing_multiple as f32;
+        }
+
+        let count = (fade_distance / spacing).ceil() as i64;
+        for (axis, other, axis_color) in [
+            // Lines parallel to the Z axis, at a constant x
+            (Vec3::X, Vec3::Z, grid.z_axis_color),
+            // Lines parallel to the X axis, at a constant z
+            (Vec3::Z, Vec3::X, grid.x_axis_color),
+        ] {
+            let center = (camera.dot(axis) / spacing).round() as i64;
+            for index in center - count..=center + count {
+                let offset = index as f32 * spacing;
+                // The closest point of the line to the camera
+                let lateral = offset - camera.dot(axis);
+                let closest_distance = (lateral * lateral + height * height).sqrt();
+                if closest_distance >= fade_distance {
+                    continue;
+                }
+                let half_length =
+                    (fade_distance * fade_distance - closest_distance * closest_distance).sqrt();
+
+                let color = grid.line_color(index, axis_color, spacing_multiple);
+                let alpha = color.alpha() * (1.0 - closest_distance / fade_distance);
+                let closest = axis * offset + other * camera.dot(other);
+                gizmos.linestrip_gradient([
+                    (closest - other * half_length, color.with_alpha(0.0)),
+                    (closest, color.with_alpha(alpha)),
+                    (closest + other * half_length, color.with_alpha(0.0)),
+                ]);
+            }
+        }
+    }
+}
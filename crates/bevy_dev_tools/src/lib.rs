@@ -13,8 +13,13 @@ use bevy_app::prelude::*;
 #[cfg(feature = "bevy_ci_testing")]
 pub mod ci_testing;
 
+#[cfg(feature = "bevy_dev_console")]
+pub mod console;
+
 pub mod fps_overlay;
 
+pub mod infinite_grid;
+
 #[cfg(feature = "bevy_ui_debug")]
 pub mod ui_debug_overlay;
 
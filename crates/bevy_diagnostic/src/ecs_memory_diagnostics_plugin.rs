@@ -0,0 +1,50 @@
+use bevy_app::prelude::*;
+use bevy_ecs::{storage::Table, world::World};
+
+use crate::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+
+/// Adds "archetype count", "table bytes" and "sparse set bytes" diagnostics to an App, the
+/// memory allocated by the tables and sparse sets storing the components of the world.
+///
+/// Use [`World::memory_stats`] to find out which archetypes and components take that memory.
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) to output diagnostics to the console.
+#[derive(Default)]
+pub struct EcsMemoryDiagnosticsPlugin;
+
+impl Plugin for EcsMemoryDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(Diagnostic::new(Self::ARCHETYPE_COUNT))
+            .register_diagnostic(Diagnostic::new(Self::TABLE_BYTES).with_suffix(" B"))
+            .register_diagnostic(Diagnostic::new(Self::SPARSE_SET_BYTES).with_suffix(" B"))
+            .add_systems(Update, Self::diagnostic_system);
+    }
+}
+
+impl EcsMemoryDiagnosticsPlugin {
+    pub const ARCHETYPE_COUNT: DiagnosticPath = DiagnosticPath::const_new("ecs/archetype_count");
+    pub const TABLE_BYTES: DiagnosticPath = DiagnosticPath::const_new("ecs/table_bytes");
+    pub const SPARSE_SET_BYTES: DiagnosticPath = DiagnosticPath::const_new("ecs/sparse_set_bytes");
+
+    pub fn diagnostic_system(mut diagnostics: Diagnostics, world: &World) {
+        diagnostics.add_measurement(&Self::ARCHETYPE_COUNT, || world.archetypes().len() as f64);
+        diagnostics.add_measurement(&Self::TABLE_BYTES, || {
+            world
+                .storages()
+                .tables
+                .iter()
+                .map(Table::allocated_bytes)
+                .sum::<usize>() as f64
+        });
+        diagnostics.add_measurement(&Self::SPARSE_SET_BYTES, || {
+            world
+                .storages()
+                .sparse_sets
+                .iter()
+                .map(|(_, sparse_set)| sparse_set.allocated_bytes())
+                .sum::<usize>() as f64
+        });
+    }
+}
@@ -0,0 +1,331 @@
+#![allow(unsafe_code)]
+#![allow(deprecated)]
+
+use std::{
+    borrow::Cow,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use bevy_app::{App, First, Last, Plugin};
+use bevy_ecs::{
+    change_detection::Mut,
+    schedule::Schedules,
+    system::{BoxedSystem, IntoSystem, Resource, System},
+    world::World,
+};
+use bevy_utils::{
+    tracing::{error, info, warn},
+    Instant,
+};
+use libloading::{Library, Symbol};
+use thiserror::Error;
+
+/// The signature of the function a hot patched library exports as `_bevy_hot_systems`, see
+/// [`HotPatchPlugin`].
+pub type RegisterHotSystems = fn(&mut HotSystems);
+
+/// Errors that can occur when loading a hot patched library.
+#[derive(Debug, Error)]
+pub enum HotPatchError {
+    /// The library couldn't be copied to load it.
+    #[error("cannot copy hot patched library: {0}")]
+    Io(#[from] std::io::Error),
+    /// An error occurred when loading the library.
+    #[error("cannot load hot patched library: {0}")]
+    Library(#[source] libloading::Error),
+    /// The library doesn't export a `_bevy_hot_systems` function.
+    #[error("hot patched library does not export `_bevy_hot_systems`: {0}")]
+    Symbol(#[source] libloading::Error),
+}
+
+/// The systems exported by a hot patched library, see [`HotPatchPlugin`].
+#[derive(Default)]
+pub struct HotSystems {
+    systems: Vec<HotSystem>,
+}
+
+impl HotSystems {
+    /// Exports a system, which replaces the systems with the same name in the app's schedules.
+    pub fn add<M, S>(&mut self, system: S) -> &mut Self
+    where
+        S: IntoSystem<(), (), M> + Clone + Send + Sync + 'static,
+    {
+        let name = System::name(&IntoSystem::into_system(system.clone()));
+        self.systems.push(HotSystem {
+            name,
+            new: Box::new(move || Box::new(IntoSystem::into_system(system.clone()))),
+        });
+        self
+    }
+}
+
+/// A system exported by a hot patched library, created again for each schedule it replaces a
+/// system in.
+struct HotSystem {
+    name: Cow<'static, str>,
+    new: Box<dyn Fn() -> BoxedSystem + Send + Sync>,
+}
+
+/// Swaps the systems of the app with the ones of a dynamic library whenever it's rebuilt, without
+/// restarting the app.
+///
+/// The library is usually a build of the crate with the game logic, with `crate-type = ["rlib",
+/// "dylib"]` so the app can also link to it statically. It exports the systems to hot patch
+/// through a `_bevy_hot_systems` function with the [`RegisterHotSystems`] signature:
+///
+/// ```
+/// # use bevy_dynamic_plugin::HotSystems;
+/// # fn move_player() {}
+/// # fn update_score() {}
+/// #[no_mangle]
+/// pub fn _bevy_hot_systems(systems: &mut HotSystems) {
+///     systems.add(move_player).add(update_score);
+/// }
+/// ```
+///
+/// Each exported system replaces the system of the same name in every schedule, see
+/// [`Schedule::replace_system`](bevy_ecs::schedule::Schedule::replace_system). This happens in
+/// [`Last`], so the new systems run from the next frame on, except for the systems of [`Last`]
+/// itself: it's removed from the [`Schedules`] while it runs, so they're replaced in [`First`] of
+/// the next frame instead. Adding, removing or reordering systems and changing the layout of types
+/// still requires a restart.
+///
+/// The replaced systems lose their state: their [`Local`](bevy_ecs::system::Local)s start over
+/// from their default values, and so do the events they read with an
+/// [`EventReader`](bevy_ecs::event::EventReader), which read the events of the last two frames
+/// again.
+///
+/// Each rebuild loads a new copy of the library, and none of them are unloaded before the app
+/// exits: the replaced systems and the values they created may still point to their code. A long
+/// session leaks one library per rebuild.
+///
+/// Like the rest of this crate, this is unsound and will be removed in 0.15.
+#[deprecated(
+    since = "0.14.0",
+    note = "The current dynamic plugin system is unsound and will be removed in 0.15."
+)]
+pub struct HotPatchPlugin {
+    path: PathBuf,
+    poll_interval: Duration,
+}
+
+impl HotPatchPlugin {
+    /// Watches the library at `path`, checking if it was rebuilt every 500ms.
+    ///
+    /// # Safety
+    ///
+    /// Like [`dynamically_load_plugin`](crate::dynamically_load_plugin), this loads foreign code
+    /// every time the library changes: each build of the library must be built by the same
+    /// compiler with the exact same dependencies as the app, and its `_bevy_hot_systems` symbol
+    /// must have the [`RegisterHotSystems`] signature. The initialization routines of the library
+    /// are run when it's loaded, see the safety section of [`libloading::Library::new`].
+    pub unsafe fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            poll_interval: Duration::from_millis(500),
+        }
+    }
+
+    /// Sets how often to check if the library was rebuilt.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+}
+
+impl Plugin for HotPatchPlugin {
+    fn build(&self, app: &mut App) {
+        let modified = modified(&self.path);
+        app.insert_resource(HotPatch {
+            path: self.path.clone(),
+            poll_interval: self.poll_interval,
+            last_poll: Instant::now(),
+            modified,
+            pending: None,
+            reloads: 0,
+            deferred: None,
+        })
+        .add_systems(First, hot_patch_last)
+        .add_systems(Last, hot_patch_systems);
+    }
+}
+
+#[derive(Resource)]
+struct HotPatch {
+    path: PathBuf,
+    poll_interval: Duration,
+    last_poll: Instant,
+    modified: Option<SystemTime>,
+    /// The modification time seen during the previous poll, it's loaded once it stops changing
+    /// so it isn't read while being written.
+    pending: Option<SystemTime>,
+    reloads: usize,
+    /// The systems to replace in [`Last`] once it's done running, with how many systems each of
+    /// them replaced in the other schedules.
+    deferred: Option<(HotSystems, Vec<usize>)>,
+}
+
+impl HotPatch {
+    /// Returns `true` if the library was rebuilt since it was last loaded.
+    fn poll(&mut self) -> bool {
+        if self.last_poll.elapsed() < self.poll_interval {
+            return false;
+        }
+        self.last_poll = Instant::now();
+
+        let modified = modified(&self.path);
+        if modified == self.modified {
+            self.pending = None;
+            return false;
+        }
+        if modified != self.pending {
+            self.pending = modified;
+            return false;
+        }
+        self.modified = modified;
+        self.pending = None;
+        true
+    }
+
+    /// Loads a copy of the library, as most platforms don't load a path again or lock the file
+    /// while it's loaded.
+    fn load(&mut self) -> Result<HotSystems, HotPatchError> {
+        self.reloads += 1;
+        let mut file_name = self.path.file_stem().unwrap_or_default().to_owned();
+        file_name.push(format!("-hot-{}-{}", std::process::id(), self.reloads));
+        let mut copy = std::env::temp_dir().join(file_name);
+        if let Some(extension) = self.path.extension() {
+            copy.set_extension(extension);
+        }
+        fs::copy(&self.path, &copy)?;
+
+        // SAFETY: The library is built from the same sources as the app, see `HotPatchPlugin::new`.
+        let library = unsafe { Library::new(&copy).map_err(HotPatchError::Library)? };
+        let mut systems = HotSystems::default();
+        {
+            // SAFETY: The symbol has the `RegisterHotSystems` signature, see `HotPatchPlugin::new`.
+            let register: Symbol<RegisterHotSystems> = unsafe {
+                library
+                    .get(b"_bevy_hot_systems")
+                    .map_err(HotPatchError::Symbol)?
+            };
+            register(&mut systems);
+        }
+
+        // The replaced systems and the values they created may still point to the code of older
+        // libraries, so none of them are ever unloaded
+        std::mem::forget(library);
+        Ok(systems)
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+fn hot_patch_systems(world: &mut World) {
+    let mut hot_patch = world.resource_mut::<HotPatch>();
+    if !hot_patch.poll() {
+        return;
+    }
+    match hot_patch.load() {
+        Ok(systems) => patch_running_schedules(world, systems),
+        Err(error) => error!("{error}"),
+    }
+}
+
+/// Replaces the systems of every schedule with the `systems` of the hot patched library, except for
+/// the systems of [`Last`], which is running, that are replaced by [`hot_patch_last`].
+fn patch_running_schedules(world: &mut World, systems: HotSystems) {
+    let replaced = world.resource_scope(|world, mut schedules: Mut<Schedules>| {
+        systems
+            .systems
+            .iter()
+            .map(|system| {
+                let mut replaced = 0;
+                for (_, schedule) in schedules.iter_mut() {
+                    if schedule.replace_system(world, (system.new)()).is_ok() {
+                        replaced += 1;
+                    }
+                }
+                replaced
+            })
+            .collect()
+    });
+    world.resource_mut::<HotPatch>().deferred = Some((systems, replaced));
+}
+
+fn hot_patch_last(world: &mut World) {
+    let Some((systems, mut replaced)) = world.resource_mut::<HotPatch>().deferred.take() else {
+        return;
+    };
+
+    world.resource_scope(|world, mut schedules: Mut<Schedules>| {
+        if let Some(last) = schedules.get_mut(Last) {
+            for (system, replaced) in systems.systems.iter().zip(&mut replaced) {
+                if last.replace_system(world, (system.new)()).is_ok() {
+                    *replaced += 1;
+                }
+            }
+        }
+    });
+
+    for (system, replaced) in systems.systems.iter().zip(replaced) {
+        if replaced == 0 {
+            warn!("No system to hot patch is called `{}`", system.name);
+        } else {
+            info!("Hot patched `{}` in {replaced} schedules", system.name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::{App, Last, Update};
+    use bevy_ecs::{
+        system::{Local, ResMut, Resource},
+        world::World,
+    };
+
+    use super::{patch_running_schedules, HotPatchPlugin, HotSystems};
+
+    #[derive(Resource, Default)]
+    struct Runs(Vec<u32>);
+
+    fn count_runs(mut runs: Local<u32>, mut all_runs: ResMut<Runs>) {
+        *runs += 1;
+        all_runs.0.push(*runs);
+    }
+
+    #[test]
+    fn patch_every_schedule() {
+        // Patches the systems once from `Last`, like `HotPatchPlugin` does once the library is
+        // rebuilt
+        fn patch_once(world: &mut World, mut patched: Local<bool>) {
+            if !*patched {
+                *patched = true;
+                let mut systems = HotSystems::default();
+                systems.add(count_runs);
+                patch_running_schedules(world, systems);
+            }
+        }
+
+        let mut app = App::new();
+        // SAFETY: The library doesn't exist, so nothing is loaded.
+        app.add_plugins(unsafe { HotPatchPlugin::new("missing") })
+            .init_resource::<Runs>()
+            .add_systems(Update, count_runs)
+            .add_systems(Last, (count_runs, patch_once));
+        app.update();
+        assert_eq!(app.world().resource::<Runs>().0, [1, 1]);
+
+        // The replaced systems start over, including the one of the running `Last` schedule
+        app.update();
+        app.update();
+        assert_eq!(app.world().resource::<Runs>().0, [1, 1, 1, 1, 2, 2]);
+    }
+}
@@ -9,6 +9,9 @@
 //! This crate allows loading dynamic libraries (`.dylib`, `.so`) that export a single
 //! [`Plugin`](bevy_app::Plugin). For usage, see [`dynamically_load_plugin`].
 //!
+//! With the `hotpatching` feature, the `HotPatchPlugin` also swaps the systems of a running app
+//! with the ones of a dynamic library every time it's rebuilt.
+//!
 //! # Deprecation
 //!
 //! The current dynamic plugin system is unsound and will be removed in 0.15. You may be interested
@@ -36,6 +39,10 @@
 //! [Bevy Assets - Development tools]: https://bevyengine.org/assets/#development-tools
 //! [`stabby`]: https://github.com/ZettaScaleLabs/stabby
 
+#[cfg(feature = "hotpatching")]
+mod hot_patch;
 mod loader;
 
+#[cfg(feature = "hotpatching")]
+pub use hot_patch::*;
 pub use loader::*;
@@ -12,11 +12,18 @@ pub fn derive_event(input: TokenStream) -> TokenStream {
         .predicates
         .push(parse_quote! { Self: Send + Sync + 'static });
 
+    let set = match parse_event_attr(&ast) {
+        Ok(set) => set,
+        Err(e) => return e.into_compile_error().into(),
+    };
+    let set = Ident::new(set, Span::call_site());
+
     let struct_name = &ast.ident;
     let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
 
     TokenStream::from(quote! {
         impl #impl_generics #bevy_ecs_path::event::Event for #struct_name #type_generics #where_clause {
+            const SET: #bevy_ecs_path::event::EventSet = #bevy_ecs_path::event::EventSet::#set;
         }
 
         impl #impl_generics #bevy_ecs_path::component::Component for #struct_name #type_generics #where_clause {
@@ -113,6 +120,38 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
     Ok(attrs)
 }
 
+pub const EVENT: &str = "event";
+pub const SET: &str = "set";
+
+// values for `set` attribute
+const IMMEDIATE: &str = "Immediate";
+const PARALLEL: &str = "Parallel";
+
+fn parse_event_attr(ast: &DeriveInput) -> Result<&'static str> {
+    let mut set = IMMEDIATE;
+
+    for meta in ast.attrs.iter().filter(|a| a.path().is_ident(EVENT)) {
+        meta.parse_nested_meta(|nested| {
+            if nested.path.is_ident(SET) {
+                set = match nested.value()?.parse::<LitStr>()?.value() {
+                    s if s == IMMEDIATE => IMMEDIATE,
+                    s if s == PARALLEL => PARALLEL,
+                    s => {
+                        return Err(nested.error(format!(
+                            "Invalid event set `{s}`, expected '{IMMEDIATE}' or '{PARALLEL}'.",
+                        )));
+                    }
+                };
+                Ok(())
+            } else {
+                Err(nested.error("Unsupported attribute"))
+            }
+        })?;
+    }
+
+    Ok(set)
+}
+
 fn storage_path(bevy_ecs_path: &Path, ty: StorageTy) -> TokenStream2 {
     let storage_type = match ty {
         StorageTy::Table => Ident::new("Table", Span::call_site()),
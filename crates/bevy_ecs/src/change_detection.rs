@@ -3,6 +3,7 @@
 use crate::{
     component::{Tick, TickCells},
     ptr::PtrMut,
+    storage::{Column, ColumnChangedTick},
     system::Resource,
 };
 use bevy_ptr::{Ptr, UnsafeCellDeref};
@@ -307,12 +308,12 @@ macro_rules! change_detection_mut_impl {
 
             #[inline]
             fn set_changed(&mut self) {
-                *self.ticks.changed = self.ticks.this_run;
+                self.ticks.set_changed(self.ticks.this_run);
             }
 
             #[inline]
             fn set_last_changed(&mut self, last_changed: Tick) {
-                *self.ticks.changed = last_changed;
+                self.ticks.set_changed(last_changed);
             }
 
             #[inline]
@@ -361,6 +362,7 @@ macro_rules! impl_methods {
                         changed: self.ticks.changed,
                         last_run: self.ticks.last_run,
                         this_run: self.ticks.this_run,
+                        column_tick: self.ticks.column_tick,
                     }
                 }
             }
@@ -454,6 +456,8 @@ pub(crate) struct TicksMut<'w> {
     pub(crate) changed: &'w mut Tick,
     pub(crate) last_run: Tick,
     pub(crate) this_run: Tick,
+    /// The changed tick of the column storing the value, see [`Column::changed_tick`].
+    pub(crate) column_tick: Option<&'w ColumnChangedTick>,
 }
 
 impl<'w> TicksMut<'w> {
@@ -472,6 +476,25 @@ impl<'w> TicksMut<'w> {
             changed: unsafe { cells.changed.deref_mut() },
             last_run,
             this_run,
+            column_tick: None,
+        }
+    }
+
+    /// Records the changes of the value in the `column` that stores it.
+    #[inline]
+    pub(crate) fn with_column(self, column: &'w Column) -> Self {
+        Self {
+            column_tick: Some(column.changed_tick_cell()),
+            ..self
+        }
+    }
+
+    /// Sets the changed tick of the value, and moves the changed tick of its column forward.
+    #[inline]
+    pub(crate) fn set_changed(&mut self, tick: Tick) {
+        *self.changed = tick;
+        if let Some(column_tick) = self.column_tick {
+            column_tick.mark_changed(tick);
         }
     }
 }
@@ -822,6 +845,7 @@ impl<'w, T: ?Sized> Mut<'w, T> {
                 changed: last_changed,
                 last_run,
                 this_run,
+                column_tick: None,
             },
         }
     }
@@ -900,6 +924,7 @@ impl<'w> MutUntyped<'w> {
                 changed: self.ticks.changed,
                 last_run: self.ticks.last_run,
                 this_run: self.ticks.this_run,
+                column_tick: self.ticks.column_tick,
             },
         }
     }
@@ -993,12 +1018,12 @@ impl<'w> DetectChangesMut for MutUntyped<'w> {
 
     #[inline]
     fn set_changed(&mut self) {
-        *self.ticks.changed = self.ticks.this_run;
+        self.ticks.set_changed(self.ticks.this_run);
     }
 
     #[inline]
     fn set_last_changed(&mut self, last_changed: Tick) {
-        *self.ticks.changed = last_changed;
+        self.ticks.set_changed(last_changed);
     }
 
     #[inline]
@@ -1158,6 +1183,7 @@ mod tests {
             changed: &mut component_ticks.changed,
             last_run: Tick::new(3),
             this_run: Tick::new(4),
+            column_tick: None,
         };
         let mut res = R {};
         let res_mut = ResMut {
@@ -1203,6 +1229,7 @@ mod tests {
             changed: &mut component_ticks.changed,
             last_run: Tick::new(3),
             this_run: Tick::new(4),
+            column_tick: None,
         };
         let mut res = R {};
         let non_send_mut = NonSendMut {
@@ -1233,6 +1260,7 @@ mod tests {
             changed: &mut component_ticks.changed,
             last_run,
             this_run,
+            column_tick: None,
         };
 
         let mut outer = Outer(0);
@@ -1318,6 +1346,7 @@ mod tests {
             changed: &mut component_ticks.changed,
             last_run,
             this_run,
+            column_tick: None,
         };
 
         let mut value: i32 = 5;
@@ -1352,6 +1381,7 @@ mod tests {
             changed: &mut component_ticks.changed,
             last_run: Tick::new(3),
             this_run: Tick::new(4),
+            column_tick: None,
         };
         let mut c = C {};
         let mut_typed = Mut {
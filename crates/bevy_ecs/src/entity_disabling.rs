@@ -0,0 +1,90 @@
+//! Disabling entities, to take them out of the queries without despawning them.
+//!
+//! Entities with the [`Disabled`] component are skipped by every query that doesn't mention
+//! [`Disabled`] itself: this is the default query filter of the [`World`](crate::world::World).
+//! A query sees the disabled entities when it accesses or filters on [`Disabled`], for example
+//! with `Has<Disabled>`, `Option<&Disabled>` or `With<Disabled>`.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_ecs::entity_disabling::Disabled;
+//! #[derive(Component)]
+//! struct Health(u32);
+//!
+//! let mut world = World::new();
+//! world.spawn(Health(1));
+//! world.spawn((Health(2), Disabled));
+//!
+//! assert_eq!(world.query::<&Health>().iter(&world).count(), 1);
+//! assert_eq!(world.query::<(&Health, Has<Disabled>)>().iter(&world).count(), 2);
+//! ```
+//!
+//! Queries reading all the components, like `Query<EntityRef>`, and the direct accesses through
+//! the [`World`](crate::world::World) aren't filtered.
+
+use crate as bevy_ecs;
+#[cfg(feature = "bevy_reflect")]
+use crate::reflect::ReflectComponent;
+use crate::{
+    component::{Component, ComponentId},
+    query::FilteredAccess,
+    world::DISABLED,
+};
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+
+/// Marks an entity as disabled: the queries skip it unless they mention [`Disabled`].
+///
+/// See the [module-level documentation](self).
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "bevy_reflect", derive(Reflect))]
+#[cfg_attr(feature = "bevy_reflect", reflect(Component, Default, PartialEq))]
+pub struct Disabled;
+
+/// Adds the default query filters to `component_access`: [`Disabled`] entities are excluded,
+/// unless the query already accesses or filters on [`Disabled`].
+pub(crate) fn add_default_filters(component_access: &mut FilteredAccess<ComponentId>) {
+    if !component_access.contains(DISABLED) {
+        component_access.and_without(DISABLED);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Disabled;
+    use crate as bevy_ecs;
+    use crate::{
+        component::Component,
+        query::{Has, With, Without},
+        world::{EntityRef, World},
+    };
+
+    #[derive(Component)]
+    struct A(u32);
+
+    #[test]
+    fn queries_skip_disabled_entities() {
+        let mut world = World::new();
+        let enabled = world.spawn(A(1)).id();
+        let disabled = world.spawn((A(2), Disabled)).id();
+
+        let mut query = world.query::<&A>();
+        assert_eq!(query.iter(&world).map(|a| a.0).collect::<Vec<_>>(), [1]);
+        assert!(query.get(&world, disabled).is_err());
+        assert_eq!(world.query::<EntityRef>().iter(&world).count(), 2);
+
+        let mut with = world.query_filtered::<&A, With<Disabled>>();
+        assert_eq!(with.iter(&world).map(|a| a.0).collect::<Vec<_>>(), [2]);
+        let mut without = world.query_filtered::<&A, Without<Disabled>>();
+        assert_eq!(without.iter(&world).map(|a| a.0).collect::<Vec<_>>(), [1]);
+        let mut has = world.query::<(&A, Has<Disabled>)>();
+        assert_eq!(has.iter(&world).count(), 2);
+        let mut option = world.query::<(&A, Option<&Disabled>)>();
+        assert_eq!(option.iter(&world).count(), 2);
+
+        world.entity_mut(disabled).remove::<Disabled>();
+        world.entity_mut(enabled).insert(Disabled);
+        assert_eq!(query.iter(&world).map(|a| a.0).collect::<Vec<_>>(), [2]);
+        assert_eq!(world.get::<A>(enabled).map(|a| a.0), Some(1));
+    }
+}
@@ -23,6 +23,10 @@ use std::{
 ///
 /// Events must be thread-safe.
 ///
+/// The observers of an event run when it's triggered, unless it's in the [`EventSet::Parallel`]
+/// set. When deriving this trait, the set is chosen with the `#[event(set = "Parallel")]`
+/// attribute.
+///
 /// [`World`]: crate::world::World
 /// [`ComponentId`]: crate::component::ComponentId
 /// [`Observer`]: crate::observer::Observer
@@ -31,7 +35,31 @@ use std::{
     label = "invalid `Event`",
     note = "consider annotating `{Self}` with `#[derive(Event)]`"
 )]
-pub trait Event: Component {}
+pub trait Event: Component {
+    /// Chooses how the observers of this event are run when it's triggered.
+    const SET: EventSet = EventSet::Immediate;
+}
+
+/// Chooses how the [`Observer`]s of an [`Event`] are run when it's triggered.
+///
+/// [`Observer`]: crate::observer::Observer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum EventSet {
+    /// The observers run one after the other, as soon as the event is triggered.
+    #[default]
+    Immediate,
+    /// The triggers are queued, and delivered by [`deliver_parallel_triggers`], which runs the
+    /// observers that don't conflict in parallel.
+    ///
+    /// This is meant for commutative events, such as collisions or damage, that are triggered
+    /// thousands of times per frame: the observers can't rely on the order of the triggers, or on
+    /// the changes other observers made to the event. Parallel events can only target entities,
+    /// and only the observers without components in their [`Trigger`] run for them.
+    ///
+    /// [`deliver_parallel_triggers`]: crate::observer::deliver_parallel_triggers
+    /// [`Trigger`]: crate::observer::Trigger
+    Parallel,
+}
 
 /// An `EventId` uniquely identifies an event stored in a specific [`World`].
 ///
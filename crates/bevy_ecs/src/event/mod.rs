@@ -8,7 +8,7 @@ mod update;
 mod writer;
 
 pub(crate) use base::EventInstance;
-pub use base::{Event, EventId};
+pub use base::{Event, EventId, EventSet};
 pub use bevy_ecs_macros::Event;
 pub use collections::{Events, SendBatchIds};
 #[cfg(feature = "multi_threaded")]
@@ -0,0 +1,273 @@
+//! Indexes of the entities by the value of one of their components.
+
+use std::{hash::Hash, slice};
+
+use bevy_utils::HashMap;
+
+use crate as bevy_ecs;
+use crate::{
+    component::{Component, ComponentId},
+    entity::{Entity, EntityHashMap},
+    entity_disabling::Disabled,
+    query::{Changed, Has, QueryData, QueryFilter, QueryManyIter},
+    system::{Query, Res, ResMut, Resource, SystemParam},
+    world::{DeferredWorld, World},
+};
+
+/// A map from the values of the component `C` to the entities that have them, to find the
+/// entities with a given value without going through all of them.
+///
+/// The index is created with [`World::index_component`], or `App::index_component` which also
+/// keeps it up to date when the values are changed in place. It's read through
+/// [`QueryByIndex`].
+#[derive(Resource)]
+pub struct ComponentIndex<C: Component + Eq + Hash + Clone> {
+    entities: HashMap<C, Vec<Entity>>,
+    values: EntityHashMap<C>,
+}
+
+impl<C: Component + Eq + Hash + Clone> Default for ComponentIndex<C> {
+    fn default() -> Self {
+        Self {
+            entities: HashMap::default(),
+            values: EntityHashMap::default(),
+        }
+    }
+}
+
+impl<C: Component + Eq + Hash + Clone> ComponentIndex<C> {
+    /// The entities whose `C` is equal to `value`.
+    pub fn get(&self, value: &C) -> &[Entity] {
+        self.entities.get(value).map_or(&[], Vec::as_slice)
+    }
+
+    /// The indexed value of the `C` of `entity`.
+    pub fn value(&self, entity: Entity) -> Option<&C> {
+        self.values.get(&entity)
+    }
+
+    /// Iterates over the distinct values of `C`.
+    pub fn values(&self) -> impl Iterator<Item = &C> {
+        self.entities.keys()
+    }
+
+    fn insert(&mut self, entity: Entity, value: C) {
+        self.remove(entity);
+        self.entities.entry(value.clone()).or_default().push(entity);
+        self.values.insert(entity, value);
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        let Some(value) = self.values.remove(&entity) else {
+            return;
+        };
+        if let Some(entities) = self.entities.get_mut(&value) {
+            entities.retain(|&other| other != entity);
+            if entities.is_empty() {
+                self.entities.remove(&value);
+            }
+        }
+    }
+}
+
+/// A [`Query`] over the entities whose `C` has a given value, looked up in the
+/// [`ComponentIndex`] of `C`.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::index::QueryByIndex;
+/// #[derive(Component, PartialEq, Eq, Hash, Clone)]
+/// struct TileCoord(i32, i32);
+///
+/// #[derive(Component)]
+/// struct Blocked;
+///
+/// fn is_walkable(tiles: QueryByIndex<TileCoord, Has<Blocked>>) -> bool {
+///     !tiles.iter(&TileCoord(4, 2)).any(|blocked| blocked)
+/// }
+///
+/// let mut world = World::new();
+/// world.index_component::<TileCoord>();
+/// world.spawn((TileCoord(4, 2), Blocked));
+/// # use bevy_ecs::system::RunSystemOnce;
+/// assert!(!world.run_system_once(is_walkable));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `C` isn't indexed, see [`World::index_component`].
+#[derive(SystemParam)]
+pub struct QueryByIndex<
+    'w,
+    's,
+    C: Component + Eq + Hash + Clone,
+    D: QueryData + 'static = Entity,
+    F: QueryFilter + 'static = (),
+> {
+    index: Res<'w, ComponentIndex<C>>,
+    query: Query<'w, 's, D, F>,
+}
+
+impl<'w, 's, C, D, F> QueryByIndex<'w, 's, C, D, F>
+where
+    C: Component + Eq + Hash + Clone,
+    D: QueryData + 'static,
+    F: QueryFilter + 'static,
+{
+    /// Returns an iterator over the read-only query items of the entities whose `C` is equal to
+    /// `value`.
+    pub fn iter(
+        &self,
+        value: &C,
+    ) -> QueryManyIter<'_, 's, D::ReadOnly, F, slice::Iter<'_, Entity>> {
+        self.query.iter_many(self.index.get(value))
+    }
+
+    /// Returns an iterator over the query items of the entities whose `C` is equal to `value`.
+    ///
+    /// Like with [`Query::iter_many_mut`], the items are fetched with
+    /// [`QueryManyIter::fetch_next`].
+    pub fn iter_mut(&mut self, value: &C) -> QueryManyIter<'_, 's, D, F, slice::Iter<'_, Entity>> {
+        self.query.iter_many_mut(self.index.get(value))
+    }
+
+    /// The underlying [`ComponentIndex`].
+    pub fn index(&self) -> &ComponentIndex<C> {
+        &self.index
+    }
+
+    /// The underlying [`Query`], for the accesses that don't go through the index.
+    pub fn query(&mut self) -> &mut Query<'w, 's, D, F> {
+        &mut self.query
+    }
+}
+
+fn on_insert<C: Component + Eq + Hash + Clone>(
+    mut world: DeferredWorld,
+    entity: Entity,
+    _: ComponentId,
+) {
+    let Some(value) = world.get::<C>(entity).cloned() else {
+        return;
+    };
+    world
+        .resource_mut::<ComponentIndex<C>>()
+        .insert(entity, value);
+}
+
+fn on_remove<C: Component + Eq + Hash + Clone>(
+    mut world: DeferredWorld,
+    entity: Entity,
+    _: ComponentId,
+) {
+    world.resource_mut::<ComponentIndex<C>>().remove(entity);
+}
+
+/// Updates the [`ComponentIndex`] of `C` with the values changed in place since the last run.
+///
+/// Inserting and removing `C` updates the index right away, this system is added by
+/// `App::index_component` for the values mutated through [`Mut`](crate::change_detection::Mut).
+pub fn update_component_index<C: Component + Eq + Hash + Clone>(
+    mut index: ResMut<ComponentIndex<C>>,
+    changed: Query<(Entity, &C, Has<Disabled>), Changed<C>>,
+) {
+    for (entity, value, _) in &changed {
+        if index.value(entity) != Some(value) {
+            index.insert(entity, value.clone());
+        }
+    }
+}
+
+impl World {
+    /// Starts indexing the entities by the value of their `C`, to look them up with
+    /// [`QueryByIndex`].
+    ///
+    /// The index is updated when `C` is inserted or removed. Values changed in place are only
+    /// picked up by [`update_component_index`], see `App::index_component`.
+    ///
+    /// Does nothing if `C` is already indexed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `C` already has component hooks, or if an entity already has a `C`.
+    pub fn index_component<C: Component + Eq + Hash + Clone>(&mut self) {
+        if self.contains_resource::<ComponentIndex<C>>() {
+            return;
+        }
+        self.init_resource::<ComponentIndex<C>>();
+        self.register_component_hooks::<C>()
+            .on_insert(on_insert::<C>)
+            .on_remove(on_remove::<C>);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{update_component_index, ComponentIndex, QueryByIndex};
+    use crate as bevy_ecs;
+    use crate::{component::Component, system::RunSystemOnce, world::World};
+
+    #[derive(Component, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+    struct TileCoord(i32, i32);
+
+    #[derive(Component)]
+    struct Height(u32);
+
+    #[test]
+    fn component_index() {
+        let mut world = World::new();
+        world.index_component::<TileCoord>();
+        let a = world.spawn((TileCoord(0, 0), Height(1))).id();
+        let b = world.spawn((TileCoord(0, 0), Height(2))).id();
+        let c = world.spawn((TileCoord(1, 0), Height(4))).id();
+
+        let index = world.resource::<ComponentIndex<TileCoord>>();
+        assert_eq!(index.get(&TileCoord(0, 0)), [a, b]);
+        assert_eq!(index.value(c), Some(&TileCoord(1, 0)));
+
+        world.entity_mut(a).insert(TileCoord(1, 0));
+        world.entity_mut(b).remove::<TileCoord>();
+        let index = world.resource::<ComponentIndex<TileCoord>>();
+        assert!(index.get(&TileCoord(0, 0)).is_empty());
+        assert_eq!(index.get(&TileCoord(1, 0)), [c, a]);
+
+        // Values changed in place wait for `update_component_index`
+        world.get_mut::<TileCoord>(c).unwrap().0 = 2;
+        world.run_system_once(update_component_index::<TileCoord>);
+        let index = world.resource::<ComponentIndex<TileCoord>>();
+        assert_eq!(index.get(&TileCoord(1, 0)), [a]);
+        assert_eq!(index.get(&TileCoord(2, 0)), [c]);
+
+        world.despawn(a);
+        let index = world.resource::<ComponentIndex<TileCoord>>();
+        assert_eq!(index.values().collect::<Vec<_>>(), [&TileCoord(2, 0)]);
+    }
+
+    #[test]
+    fn query_by_index() {
+        let mut world = World::new();
+        world.index_component::<TileCoord>();
+        world.spawn((TileCoord(0, 0), Height(1)));
+        world.spawn((TileCoord(0, 0), Height(2)));
+        world.spawn((TileCoord(1, 0), Height(4)));
+
+        world.run_system_once(|mut tiles: QueryByIndex<TileCoord, &mut Height>| {
+            let mut heights = tiles.iter_mut(&TileCoord(0, 0));
+            while let Some(mut height) = heights.fetch_next() {
+                height.0 *= 10;
+            }
+        });
+        let heights = world.run_system_once(|tiles: QueryByIndex<TileCoord, &Height>| {
+            tiles
+                .iter(&TileCoord(0, 0))
+                .map(|height| height.0)
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(heights, [10, 20]);
+
+        let all = world.run_system_once(|mut tiles: QueryByIndex<TileCoord, &Height>| {
+            tiles.query().iter().count()
+        });
+        assert_eq!(all, 3);
+    }
+}
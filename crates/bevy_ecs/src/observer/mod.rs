@@ -1,9 +1,12 @@
 //! Types for creating and storing [`Observer`]s
 
 mod entity_observer;
+mod parallel;
 mod runner;
 mod trigger_event;
 
+pub use parallel::deliver_parallel_triggers;
+pub(crate) use parallel::ParallelTriggers;
 pub use runner::*;
 pub use trigger_event::*;
 
@@ -391,8 +394,11 @@ mod tests {
     use bevy_ptr::OwningPtr;
 
     use crate as bevy_ecs;
-    use crate::observer::{EmitDynamicTrigger, Observer, ObserverDescriptor, ObserverState};
+    use crate::observer::{
+        deliver_parallel_triggers, EmitDynamicTrigger, Observer, ObserverDescriptor, ObserverState,
+    };
     use crate::prelude::*;
+    use crate::system::RunSystemOnce;
 
     #[derive(Component)]
     struct A;
@@ -410,9 +416,16 @@ mod tests {
     #[derive(Event)]
     struct EventA;
 
+    #[derive(Event, Clone)]
+    #[event(set = "Parallel")]
+    struct EventParallel(usize);
+
     #[derive(Resource, Default)]
     struct R(usize);
 
+    #[derive(Resource, Default)]
+    struct R2(usize);
+
     impl R {
         #[track_caller]
         fn assert_order(&mut self, count: usize) {
@@ -662,4 +675,34 @@ mod tests {
         world.flush();
         assert_eq!(1, world.resource::<R>().0);
     }
+
+    #[test]
+    fn observer_parallel_trigger() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+        world.init_resource::<R2>();
+
+        world.observe(|trigger: Trigger<EventParallel>, mut res: ResMut<R>| {
+            res.0 += trigger.event().0;
+        });
+        world.observe(|trigger: Trigger<EventParallel>, mut res: ResMut<R2>| {
+            res.0 += trigger.event().0;
+        });
+        let entity = world.spawn_empty().id();
+        world
+            .entity_mut(entity)
+            .observe(|trigger: Trigger<EventParallel>, mut res: ResMut<R>| {
+                res.0 += 10 * trigger.event().0;
+            });
+        world.flush();
+
+        world.trigger(EventParallel(1));
+        world.trigger_targets(EventParallel(2), entity);
+        world.flush();
+        assert_eq!(0, world.resource::<R>().0);
+
+        world.run_system_once(deliver_parallel_triggers::<EventParallel>);
+        assert_eq!(23, world.resource::<R>().0);
+        assert_eq!(3, world.resource::<R2>().0);
+    }
 }
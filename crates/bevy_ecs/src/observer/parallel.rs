@@ -0,0 +1,218 @@
+use bevy_tasks::{ComputeTaskPool, TaskPool};
+
+use crate::{
+    self as bevy_ecs,
+    archetype::ArchetypeComponentId,
+    component::ComponentId,
+    entity::Entity,
+    event::Event,
+    observer::{Observer, ObserverState, ObserverTrigger, Trigger, TriggerTargets},
+    query::Access,
+    system::Resource,
+    world::{unsafe_world_cell::UnsafeWorldCell, World},
+};
+
+/// The triggers of an [`EventSet::Parallel`](crate::event::EventSet::Parallel) event, waiting to
+/// be delivered by [`deliver_parallel_triggers`].
+#[derive(Resource)]
+pub(crate) struct ParallelTriggers<E: Event> {
+    triggers: Vec<(E, Vec<Entity>)>,
+}
+
+impl<E: Event> Default for ParallelTriggers<E> {
+    fn default() -> Self {
+        Self {
+            triggers: Vec::new(),
+        }
+    }
+}
+
+impl<E: Event> ParallelTriggers<E> {
+    /// Queues the `event` for the `targets`, the components of the targets are ignored.
+    pub(crate) fn queue(world: &mut World, event: E, targets: impl TriggerTargets) {
+        world
+            .get_resource_or_insert_with(Self::default)
+            .triggers
+            .push((event, targets.entities().collect()));
+    }
+}
+
+/// An observer of a parallel event, borrowed from the world while the triggers are delivered.
+struct ParallelObserver<'w, E: Event> {
+    entity: Entity,
+    watched_entities: &'w [Entity],
+    observer: &'w mut Observer<E, ()>,
+}
+
+impl<E: Event + Clone> ParallelObserver<'_, E> {
+    /// Runs the observer for each of the `triggers` it watches.
+    ///
+    /// # Safety
+    /// - `update_archetype_component_access` must have been called on the system with `world`
+    /// - the observers running at the same time must have compatible access
+    unsafe fn run(
+        &mut self,
+        triggers: &[(E, Vec<Entity>)],
+        event_type: ComponentId,
+        world: UnsafeWorldCell,
+    ) {
+        for (event, targets) in triggers {
+            if targets.is_empty() {
+                if self.watched_entities.is_empty() {
+                    // SAFETY: Ensured by the caller
+                    unsafe { self.run_for(event, event_type, Entity::PLACEHOLDER, world) };
+                }
+                continue;
+            }
+            for &target in targets {
+                if self.watched_entities.is_empty() || self.watched_entities.contains(&target) {
+                    // SAFETY: Ensured by the caller
+                    unsafe { self.run_for(event, event_type, target, world) };
+                }
+            }
+        }
+    }
+
+    /// # Safety
+    /// See [`ParallelObserver::run`].
+    unsafe fn run_for(
+        &mut self,
+        event: &E,
+        event_type: ComponentId,
+        target: Entity,
+        world: UnsafeWorldCell,
+    ) {
+        let mut event = event.clone();
+        let trigger: Trigger<E> = Trigger::new(
+            &mut event,
+            ObserverTrigger {
+                observer: self.entity,
+                event_type,
+                entity: target,
+            },
+        );
+        // SAFETY: the static lifetime is encapsulated in Trigger / cannot leak out, see
+        // `observer_system_runner`.
+        let trigger: Trigger<'static, E> = unsafe { std::mem::transmute(trigger) };
+        // SAFETY: Ensured by the caller, the system is an `ObserverSystem` so won't mutate world
+        // beyond the access of a `DeferredWorld`
+        unsafe { self.observer.system.run_unsafe(trigger, world) };
+    }
+}
+
+/// Delivers the queued triggers of the [`EventSet::Parallel`](crate::event::EventSet::Parallel)
+/// event `E` to its observers.
+///
+/// The observers are split in batches of observers whose data access doesn't conflict, and the
+/// observers of a batch run in parallel on the [`ComputeTaskPool`]. Each observer runs for all the
+/// triggers it watches, in the order they were triggered, with a clone of the event. The commands
+/// of the observers are applied once all of them ran.
+///
+/// This is an exclusive system, added to the schedule where the triggers should be delivered:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::observer::deliver_parallel_triggers;
+/// #[derive(Event, Clone)]
+/// #[event(set = "Parallel")]
+/// struct Damage(u32);
+///
+/// #[derive(Resource, Default)]
+/// struct TotalDamage(u32);
+///
+/// let mut world = World::new();
+/// world.init_resource::<TotalDamage>();
+/// world.observe(|trigger: Trigger<Damage>, mut total: ResMut<TotalDamage>| {
+///     total.0 += trigger.event().0;
+/// });
+/// world.flush();
+///
+/// world.trigger(Damage(3));
+/// world.trigger(Damage(4));
+/// assert_eq!(world.resource::<TotalDamage>().0, 0);
+///
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems(deliver_parallel_triggers::<Damage>);
+/// schedule.run(&mut world);
+/// assert_eq!(world.resource::<TotalDamage>().0, 7);
+/// ```
+pub fn deliver_parallel_triggers<E: Event + Clone>(world: &mut World) {
+    let Some(mut queue) = world.get_resource_mut::<ParallelTriggers<E>>() else {
+        return;
+    };
+    let triggers = std::mem::take(&mut queue.triggers);
+    if triggers.is_empty() {
+        return;
+    }
+    // Register the observers spawned since the last flush
+    world.flush();
+    let event_type = world.init_component::<E>();
+
+    let mut query = world.query::<(Entity, &ObserverState, &mut Observer<E, ()>)>();
+    let world_cell = world.as_unsafe_world_cell();
+    // SAFETY: The observer systems don't access the `ObserverState` and `Observer` components,
+    // like in `observer_system_runner`.
+    let observers = unsafe { query.iter_unchecked(world_cell) }
+        .filter(|(_, state, _)| state.descriptor.components.is_empty())
+        .map(|(entity, state, observer)| ParallelObserver {
+            entity,
+            watched_entities: &state.descriptor.entities,
+            observer: observer.into_inner(),
+        });
+
+    let mut batches: Vec<(Access<ArchetypeComponentId>, Vec<ParallelObserver<E>>)> = Vec::new();
+    let mut non_send_observers = Vec::new();
+    for observer in observers {
+        let system = &mut observer.observer.system;
+        system.update_archetype_component_access(world_cell);
+        if !system.is_send() {
+            non_send_observers.push(observer);
+            continue;
+        }
+        let access = system.archetype_component_access();
+        match batches
+            .iter_mut()
+            .find(|(batch_access, _)| batch_access.is_compatible(access))
+        {
+            Some((batch_access, batch)) => {
+                batch_access.extend(access);
+                batch.push(observer);
+            }
+            None => batches.push((access.clone(), vec![observer])),
+        }
+    }
+
+    let pool = ComputeTaskPool::get_or_init(TaskPool::default);
+    for (_, batch) in &mut batches {
+        let triggers = &triggers;
+        pool.scope(|scope| {
+            for observer in batch {
+                scope.spawn(async move {
+                    // SAFETY: The observers of a batch have compatible access, that was just
+                    // updated.
+                    unsafe { observer.run(triggers, event_type, world_cell) };
+                });
+            }
+        });
+    }
+    for observer in &mut non_send_observers {
+        // SAFETY: The observer runs alone, on the thread of the world.
+        unsafe { observer.run(&triggers, event_type, world_cell) };
+    }
+
+    for observer in batches
+        .into_iter()
+        .flat_map(|(_, batch)| batch)
+        .chain(non_send_observers)
+    {
+        // SAFETY: No observer is running, and the only outstanding references are to the
+        // `Observer` components.
+        unsafe {
+            observer
+                .observer
+                .system
+                .queue_deferred(world_cell.into_deferred());
+        }
+    }
+    world.flush();
+}
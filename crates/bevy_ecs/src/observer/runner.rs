@@ -261,7 +261,7 @@ pub type ObserverRunner = fn(DeferredWorld, ObserverTrigger, PtrMut);
 ///
 /// [`SystemParam`]: crate::system::SystemParam
 pub struct Observer<T: 'static, B: Bundle> {
-    system: BoxedObserverSystem<T, B>,
+    pub(super) system: BoxedObserverSystem<T, B>,
     descriptor: ObserverDescriptor,
 }
 
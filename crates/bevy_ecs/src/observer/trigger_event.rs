@@ -1,7 +1,8 @@
 use crate::{
     component::ComponentId,
     entity::Entity,
-    event::Event,
+    event::{Event, EventSet},
+    observer::ParallelTriggers,
     world::{Command, DeferredWorld, World},
 };
 
@@ -16,6 +17,10 @@ pub struct TriggerEvent<E, Targets: TriggerTargets = ()> {
 
 impl<E: Event, Targets: TriggerTargets> Command for TriggerEvent<E, Targets> {
     fn apply(mut self, world: &mut World) {
+        if E::SET == EventSet::Parallel {
+            ParallelTriggers::queue(world, self.event, self.targets);
+            return;
+        }
         let event_type = world.init_component::<E>();
         trigger_event(world, event_type, &mut self.event, self.targets);
     }
@@ -383,6 +383,16 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
         self.required.grow_and_insert(index.sparse_set_index());
     }
 
+    /// Returns `true` if this accesses the element given by `index`, or filters on it.
+    pub fn contains(&self, index: T) -> bool {
+        self.access.has_read(index.clone())
+            || self.access.has_archetypal(index.clone())
+            || self.filter_sets.iter().any(|filter| {
+                filter.with.contains(index.sparse_set_index())
+                    || filter.without.contains(index.sparse_set_index())
+            })
+    }
+
     /// Adds a `With` filter: corresponds to a conjunction (AND) operation.
     ///
     /// Suppose we begin with `Or<(With<A>, With<B>)>`, which is represented by an array of two `AccessFilter` instances.
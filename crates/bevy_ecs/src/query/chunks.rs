@@ -0,0 +1,298 @@
+use std::{cell::UnsafeCell, ops::Range};
+
+use bevy_utils::all_tuples;
+
+use crate::{
+    component::{Component, ComponentId, Tick},
+    entity::Entity,
+    query::{ArchetypeFilter, DebugCheckedUnwrap, QueryData, QueryState, StorageId},
+    storage::{Table, Tables},
+    world::unsafe_world_cell::UnsafeWorldCell,
+};
+
+/// [`QueryData`] that can be fetched as slices of the rows of a [`Table`].
+///
+/// This is used by [`Query::iter_chunks`](crate::system::Query::iter_chunks) and its variants.
+/// It's implemented for [`Entity`], `&T`, `&mut T`, and tuples of them.
+///
+/// # Safety
+///
+/// `fetch_chunk` must only access the components in the access of the [`WorldQuery`](crate::query::WorldQuery).
+pub unsafe trait QueryChunkData: QueryData {
+    /// The slices of the data of a chunk, which all have the same length.
+    type Chunk<'w>: Send;
+
+    /// Fetches the `rows` of the `table`.
+    ///
+    /// # Safety
+    ///
+    /// - `table` must be matched by the query, and `rows` must be in bounds.
+    /// - The caller must have the access of the query to the components of `table`.
+    /// - No other chunk of the same `rows` of `table` can be alive, if this query is mutable.
+    unsafe fn fetch_chunk<'w>(
+        state: &Self::State,
+        table: &'w Table,
+        rows: Range<usize>,
+        this_run: Tick,
+    ) -> Self::Chunk<'w>;
+}
+
+// SAFETY: No component is accessed
+unsafe impl QueryChunkData for Entity {
+    type Chunk<'w> = &'w [Entity];
+
+    unsafe fn fetch_chunk<'w>(
+        _state: &(),
+        table: &'w Table,
+        rows: Range<usize>,
+        _this_run: Tick,
+    ) -> Self::Chunk<'w> {
+        &table.entities()[rows]
+    }
+}
+
+// SAFETY: Only the `T` component is read
+unsafe impl<T: Component> QueryChunkData for &T {
+    type Chunk<'w> = &'w [T];
+
+    unsafe fn fetch_chunk<'w>(
+        &component_id: &ComponentId,
+        table: &'w Table,
+        rows: Range<usize>,
+        _this_run: Tick,
+    ) -> Self::Chunk<'w> {
+        // SAFETY: The table is matched by the query, so it has a column of `T`
+        let cells = &table
+            .get_column(component_id)
+            .debug_checked_unwrap()
+            .get_data_slice::<T>()[rows];
+        // SAFETY: `UnsafeCell<T>` has the same layout as `T`, and the caller has read access
+        std::slice::from_raw_parts(cells.as_ptr().cast::<T>(), cells.len())
+    }
+}
+
+// SAFETY: Only the `T` component is written
+unsafe impl<T: Component> QueryChunkData for &mut T {
+    type Chunk<'w> = &'w mut [T];
+
+    unsafe fn fetch_chunk<'w>(
+        &component_id: &ComponentId,
+        table: &'w Table,
+        rows: Range<usize>,
+        this_run: Tick,
+    ) -> Self::Chunk<'w> {
+        // SAFETY: The table is matched by the query, so it has a column of `T`
+        let column = table.get_column(component_id).debug_checked_unwrap();
+        // The whole chunk is marked as changed, since the writes can't be tracked
+        for tick in &column.get_changed_ticks_slice()[rows.clone()] {
+            *tick.get() = this_run;
+        }
+        column.mark_changed(this_run);
+        let cells = &column.get_data_slice::<T>()[rows];
+        // SAFETY: `UnsafeCell<T>` has the same layout as `T`, and the caller has exclusive access
+        // to these rows
+        std::slice::from_raw_parts_mut(UnsafeCell::raw_get(cells.as_ptr()), cells.len())
+    }
+}
+
+macro_rules! impl_tuple_query_chunk_data {
+    ($($name: ident),*) => {
+        #[allow(non_snake_case)]
+        #[allow(clippy::unused_unit)]
+        // SAFETY: defers to soundness of the `$name: QueryChunkData` impls
+        unsafe impl<$($name: QueryChunkData),*> QueryChunkData for ($($name,)*) {
+            type Chunk<'w> = ($($name::Chunk<'w>,)*);
+
+            #[allow(unused_variables)]
+            unsafe fn fetch_chunk<'w>(
+                state: &Self::State,
+                table: &'w Table,
+                rows: Range<usize>,
+                this_run: Tick,
+            ) -> Self::Chunk<'w> {
+                let ($($name,)*) = state;
+                ($($name::fetch_chunk($name, table, rows.clone(), this_run),)*)
+            }
+        }
+    };
+}
+
+all_tuples!(impl_tuple_query_chunk_data, 0, 15, F);
+
+/// An [`Iterator`] over the query data of a [`Query`](crate::system::Query), in chunks of up to
+/// `N` entities of the same [`Table`].
+///
+/// This struct is created by the [`Query::iter_chunks`](crate::system::Query::iter_chunks) and
+/// [`Query::iter_chunks_mut`](crate::system::Query::iter_chunks_mut) methods.
+pub struct QueryChunkIter<'w, 's, D: QueryChunkData, F: ArchetypeFilter, const N: usize> {
+    tables: &'w Tables,
+    table_ids: std::slice::Iter<'s, StorageId>,
+    table: Option<&'w Table>,
+    row: usize,
+    state: &'s QueryState<D, F>,
+    this_run: Tick,
+}
+
+impl<'w, 's, D: QueryChunkData, F: ArchetypeFilter, const N: usize>
+    QueryChunkIter<'w, 's, D, F, N>
+{
+    /// # Safety
+    /// - `world` must have permission to access any of the components registered in `query_state`.
+    /// - `world` must be the same one used to initialize `query_state`.
+    pub(crate) unsafe fn new(
+        world: UnsafeWorldCell<'w>,
+        query_state: &'s QueryState<D, F>,
+        this_run: Tick,
+    ) -> Self {
+        assert!(N > 0, "The chunks must contain at least one entity.");
+        assert!(
+            D::IS_DENSE && F::IS_DENSE,
+            "Iterating in chunks requires all the components of the query to be stored in tables."
+        );
+        QueryChunkIter {
+            // SAFETY: We only access table data that has been registered in `query_state`.
+            tables: unsafe { &world.storages().tables },
+            table_ids: query_state.matched_storage_ids.iter(),
+            table: None,
+            row: 0,
+            state: query_state,
+            this_run,
+        }
+    }
+}
+
+impl<'w, 's, D: QueryChunkData, F: ArchetypeFilter, const N: usize> Iterator
+    for QueryChunkIter<'w, 's, D, F, N>
+{
+    type Item = D::Chunk<'w>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(table) = self.table {
+                if self.row < table.entity_count() {
+                    let rows = self.row..(self.row + N).min(table.entity_count());
+                    self.row = rows.end;
+                    // SAFETY:
+                    // - The table is matched by the query and `rows` is in bounds.
+                    // - The iterator has the access of the query, see `Self::new`.
+                    // - Each chunk of rows is only returned once.
+                    return Some(unsafe {
+                        D::fetch_chunk(&self.state.fetch_state, table, rows, self.this_run)
+                    });
+                }
+            }
+            // SAFETY: The query is dense, so the matched storage ids are table ids.
+            let table_id = unsafe { self.table_ids.next()?.table_id };
+            self.table = Some(&self.tables[table_id]);
+            self.row = 0;
+        }
+    }
+}
+
+/// A parallel iterator over the query data of a [`Query`](crate::system::Query), in chunks of up
+/// to `N` entities of the same [`Table`].
+///
+/// This struct is created by the [`Query::par_iter_chunks`](crate::system::Query::par_iter_chunks)
+/// and [`Query::par_iter_chunks_mut`](crate::system::Query::par_iter_chunks_mut) methods.
+pub struct QueryChunkParIter<'w, 's, D: QueryChunkData, F: ArchetypeFilter, const N: usize> {
+    pub(crate) world: UnsafeWorldCell<'w>,
+    pub(crate) state: &'s QueryState<D, F>,
+    pub(crate) this_run: Tick,
+}
+
+impl<'w, 's, D: QueryChunkData, F: ArchetypeFilter, const N: usize>
+    QueryChunkParIter<'w, 's, D, F, N>
+{
+    /// Runs `func` on each chunk in parallel.
+    ///
+    /// # Panics
+    /// If the [`ComputeTaskPool`] is not initialized. If using this from a query that is being
+    /// initialized and run from the ECS scheduler, this should never panic.
+    ///
+    /// [`ComputeTaskPool`]: bevy_tasks::ComputeTaskPool
+    pub fn for_each<FN: Fn(D::Chunk<'w>) + Send + Sync>(self, func: FN) {
+        // SAFETY: The parallel iterator has the same access as the `Query` it was created from.
+        let chunks =
+            unsafe { QueryChunkIter::<D, F, N>::new(self.world, self.state, self.this_run) };
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            chunks.for_each(func);
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let pool = bevy_tasks::ComputeTaskPool::get();
+            let thread_count = pool.thread_num();
+            if thread_count <= 1 {
+                return chunks.for_each(func);
+            }
+
+            let chunks: Vec<_> = chunks.collect();
+            let chunks_per_task = chunks.len().div_ceil(thread_count).max(1);
+            let mut chunks = chunks.into_iter();
+            let func = &func;
+            pool.scope(|scope| loop {
+                let batch: Vec<_> = chunks.by_ref().take(chunks_per_task).collect();
+                if batch.is_empty() {
+                    break;
+                }
+                scope.spawn(async move { batch.into_iter().for_each(func) });
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+    use crate::system::RunSystemOnce;
+
+    #[derive(Component)]
+    struct A(u32);
+
+    #[derive(Component)]
+    struct B;
+
+    #[test]
+    fn iter_chunks() {
+        let mut world = World::new();
+        world.spawn_batch((0..10).map(A));
+        world.spawn_batch((10..13).map(|i| (A(i), B)));
+
+        world.run_system_once(|mut query: Query<&mut A>| {
+            let mut lengths: Vec<_> = query.iter_chunks::<4>().map(<[A]>::len).collect();
+            lengths.sort();
+            assert_eq!(lengths, [2, 3, 4, 4]);
+
+            for chunk in query.iter_chunks_mut::<4>() {
+                for a in chunk {
+                    a.0 *= 2;
+                }
+            }
+        });
+
+        world.run_system_once(|query: Query<(Entity, &A), With<B>>| {
+            let (entities, values) = query.iter_chunks::<8>().next().unwrap();
+            assert_eq!(entities.len(), 3);
+            assert_eq!(values.iter().map(|a| a.0).collect::<Vec<_>>(), [20, 22, 24]);
+        });
+    }
+
+    #[test]
+    fn iter_chunks_mut_marks_changed() {
+        let mut world = World::new();
+        world.spawn_batch((0..10).map(A));
+        let changed = world.register_system(|query: Query<&A, Changed<A>>| {
+            (query.iter().count(), query.any_changed())
+        });
+
+        assert_eq!(world.run_system(changed).unwrap(), (10, true));
+        assert_eq!(world.run_system(changed).unwrap(), (0, false));
+
+        // Fetching the chunks marks all of their rows, even those left as they are.
+        world.run_system_once(|mut query: Query<&mut A>| for _ in query.iter_chunks_mut::<4>() {});
+        assert_eq!(world.run_system(changed).unwrap(), (10, true));
+    }
+}
@@ -4,7 +4,7 @@ use crate::{
     component::{Component, ComponentId, Components, StorageType, Tick},
     entity::{Entities, Entity, EntityLocation},
     query::{Access, DebugCheckedUnwrap, FilteredAccess, WorldQuery},
-    storage::{ComponentSparseSet, Table, TableRow},
+    storage::{ColumnChangedTick, ComponentSparseSet, Table, TableRow},
     world::{
         unsafe_world_cell::UnsafeWorldCell, EntityMut, EntityRef, FilteredEntityMut,
         FilteredEntityRef, Mut, Ref, World,
@@ -1209,6 +1209,7 @@ pub struct WriteFetch<'w, T> {
         ThinSlicePtr<'w, UnsafeCell<T>>,
         ThinSlicePtr<'w, UnsafeCell<Tick>>,
         ThinSlicePtr<'w, UnsafeCell<Tick>>,
+        &'w ColumnChangedTick,
     )>,
     // T::STORAGE_TYPE = StorageType::SparseSet
     sparse_set: Option<&'w ComponentSparseSet>,
@@ -1298,6 +1299,7 @@ unsafe impl<'__w, T: Component> WorldQuery for &'__w mut T {
             column.get_data_slice().into(),
             column.get_added_ticks_slice().into(),
             column.get_changed_ticks_slice().into(),
+            column.changed_tick_cell(),
         ));
     }
 
@@ -1310,7 +1312,7 @@ unsafe impl<'__w, T: Component> WorldQuery for &'__w mut T {
         match T::STORAGE_TYPE {
             StorageType::Table => {
                 // SAFETY: STORAGE_TYPE = Table
-                let (table_components, added_ticks, changed_ticks) =
+                let (table_components, added_ticks, changed_ticks, column_tick) =
                     unsafe { fetch.table_data.debug_checked_unwrap() };
 
                 // SAFETY: The caller ensures `table_row` is in range.
@@ -1327,6 +1329,7 @@ unsafe impl<'__w, T: Component> WorldQuery for &'__w mut T {
                         changed: changed.deref_mut(),
                         this_run: fetch.this_run,
                         last_run: fetch.last_run,
+                        column_tick: Some(column_tick),
                     },
                 }
             }
@@ -1343,7 +1346,8 @@ unsafe impl<'__w, T: Component> WorldQuery for &'__w mut T {
 
                 Mut {
                     value: component.assert_unique().deref_mut(),
-                    ticks: TicksMut::from_tick_cells(ticks, fetch.last_run, fetch.this_run),
+                    ticks: TicksMut::from_tick_cells(ticks, fetch.last_run, fetch.this_run)
+                        .with_column(component_sparse_set.column()),
                 }
             }
         }
@@ -3,6 +3,7 @@ use crate::{
     batching::BatchingStrategy,
     component::{ComponentId, Components, Tick},
     entity::Entity,
+    entity_disabling::add_default_filters,
     prelude::FromWorld,
     query::{
         Access, DebugCheckedUnwrap, FilteredAccess, QueryCombinationIter, QueryIter, QueryParIter,
@@ -10,11 +11,12 @@ use crate::{
     storage::{SparseSetIndex, TableId},
     world::{unsafe_world_cell::UnsafeWorldCell, World, WorldId},
 };
+use bevy_ptr::UnsafeCellDeref;
 use bevy_utils::tracing::warn;
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::Span;
 use fixedbitset::FixedBitSet;
-use std::{borrow::Borrow, fmt, mem::MaybeUninit, ptr};
+use std::{borrow::Borrow, cell::UnsafeCell, fmt, mem::MaybeUninit, ptr};
 
 use super::{
     NopWorldQuery, QueryBuilder, QueryData, QueryEntityError, QueryFilter, QueryManyIter,
@@ -193,6 +195,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         // Merge the temporary filter access with the main access. This ensures that filter access is
         // properly considered in a global "cross-query" context (both within systems and across systems).
         component_access.extend(&filter_component_access);
+        add_default_filters(&mut component_access);
 
         Self {
             world_id: world.id(),
@@ -217,6 +220,8 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         let mut fetch_state = D::init_state(builder.world_mut());
         let filter_state = F::init_state(builder.world_mut());
         D::set_access(&mut fetch_state, builder.access());
+        let mut component_access = builder.access().clone();
+        add_default_filters(&mut component_access);
 
         let mut state = Self {
             world_id: builder.world().id(),
@@ -224,7 +229,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
             matched_storage_ids: Vec::new(),
             fetch_state,
             filter_state,
-            component_access: builder.access().clone(),
+            component_access,
             matched_tables: Default::default(),
             matched_archetypes: Default::default(),
             #[cfg(feature = "trace")]
@@ -308,6 +313,85 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         }
     }
 
+    /// Checks if any component read or written by the query changed since `last_run` on the
+    /// entities of the matched archetypes, for the given [`UnsafeWorldCell`].
+    ///
+    /// The storages whose [`Column::changed_tick`](crate::storage::Column::changed_tick) isn't
+    /// newer than `last_run` are skipped without looking at their entities.
+    ///
+    /// # Safety
+    ///
+    /// - `world` must have permission to read any components read or written by this query.
+    /// - `world` must match the one used to create this [`QueryState`].
+    pub(crate) unsafe fn any_changed_unsafe_world_cell(
+        &self,
+        world: UnsafeWorldCell,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> bool {
+        let is_changed = |tick: &UnsafeCell<Tick>| {
+            // SAFETY: The caller ensures that `world` can read the component.
+            unsafe { tick.deref() }.is_newer_than(last_run, this_run)
+        };
+        // SAFETY: The caller ensures that `world` can read the components accessed by the query.
+        let storages = unsafe { world.storages() };
+        let components = self.component_access.access().reads_and_writes();
+
+        if D::IS_DENSE && F::IS_DENSE {
+            let tables = components.flat_map(|component_id| {
+                self.matched_storage_ids.iter().filter_map(move |id| {
+                    // SAFETY: The query is dense, so its storages are tables.
+                    let table = &storages.tables[unsafe { id.table_id }];
+                    table.get_column(component_id)
+                })
+            });
+            for column in tables {
+                if column.changed_tick().is_newer_than(last_run, this_run)
+                    && column.get_changed_ticks_slice().iter().any(is_changed)
+                {
+                    return true;
+                }
+            }
+            return false;
+        }
+
+        for component_id in components {
+            let sparse_set = storages.sparse_sets.get(component_id);
+            for id in &self.matched_storage_ids {
+                // SAFETY: The query isn't dense, so its storages are archetypes.
+                let archetype = &world.archetypes()[unsafe { id.archetype_id }];
+                if archetype.is_empty() || !archetype.contains(component_id) {
+                    continue;
+                }
+                let changed = match sparse_set {
+                    Some(sparse_set) => {
+                        sparse_set.changed_tick().is_newer_than(last_run, this_run)
+                            && archetype.entities().iter().any(|entity| {
+                                sparse_set
+                                    .get_changed_tick(entity.id())
+                                    .is_some_and(is_changed)
+                            })
+                    }
+                    None => {
+                        let table = &storages.tables[archetype.table_id()];
+                        table.get_column(component_id).is_some_and(|column| {
+                            column.changed_tick().is_newer_than(last_run, this_run)
+                                && archetype.entities().iter().any(|entity| {
+                                    column
+                                        .get_changed_tick(entity.table_row())
+                                        .is_some_and(is_changed)
+                                })
+                        })
+                    }
+                };
+                if changed {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
     /// Updates the state's internal view of the [`World`]'s archetypes. If this is not called before querying data,
     /// the results may not accurately reflect what is in the `world`.
     ///
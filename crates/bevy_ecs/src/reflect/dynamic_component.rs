@@ -0,0 +1,241 @@
+use std::{alloc::Layout, borrow::Cow};
+
+use bevy_ptr::{OwningPtr, Ptr};
+use bevy_reflect::{Reflect, TypeInfo};
+use thiserror::Error;
+
+use crate::{
+    change_detection::{Mut, MutUntyped},
+    component::{ComponentDescriptor, ComponentId, StorageType},
+    world::{World, WorldId},
+};
+
+/// Describes a component registered at runtime with [`World::register_dynamic_component`].
+///
+/// Dynamic components are meant for scripting and editors, which define components that aren't
+/// Rust types. Their values are reflected values, such as a
+/// [`DynamicStruct`](bevy_reflect::DynamicStruct).
+#[derive(Debug, Clone)]
+pub struct DynamicComponentDescriptor {
+    name: Cow<'static, str>,
+    storage_type: StorageType,
+    represented_type: Option<&'static TypeInfo>,
+}
+
+impl DynamicComponentDescriptor {
+    /// Creates the descriptor of a dynamic component with the given `name`, stored in tables.
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            name: name.into(),
+            storage_type: StorageType::Table,
+            represented_type: None,
+        }
+    }
+
+    /// Sets the [`StorageType`] of the component.
+    pub fn with_storage_type(mut self, storage_type: StorageType) -> Self {
+        self.storage_type = storage_type;
+        self
+    }
+
+    /// Only accepts the values representing the type described by `type_info`.
+    ///
+    /// The represented type of a value is returned by [`Reflect::get_represented_type_info`].
+    pub fn with_represented_type(mut self, type_info: &'static TypeInfo) -> Self {
+        self.represented_type = Some(type_info);
+        self
+    }
+}
+
+/// Identifies a component registered with [`World::register_dynamic_component`].
+///
+/// The values of the component are inserted with [`EntityWorldMut::insert_dynamic`], and read with
+/// the `get_dynamic` methods of the entity references. The components are queried with a
+/// [`QueryBuilder`] and the [`ComponentId`] of the dynamic component:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::query::QueryBuilder;
+/// # use bevy_ecs::reflect::DynamicComponentDescriptor;
+/// # use bevy_ecs::world::FilteredEntityRef;
+/// # use bevy_reflect::DynamicStruct;
+/// let mut world = World::new();
+/// let health = world.register_dynamic_component(DynamicComponentDescriptor::new("Health"));
+///
+/// let mut value = DynamicStruct::default();
+/// value.insert("current", 10u32);
+/// world
+///     .spawn_empty()
+///     .insert_dynamic(health, Box::new(value))
+///     .unwrap();
+///
+/// let mut query = QueryBuilder::<FilteredEntityRef>::new(&mut world)
+///     .ref_id(health.component_id())
+///     .build();
+/// for entity in query.iter(&world) {
+///     let value = entity.get_dynamic(health).unwrap();
+///     println!("{value:?}");
+/// }
+/// ```
+///
+/// [`EntityWorldMut::insert_dynamic`]: crate::world::EntityWorldMut::insert_dynamic
+/// [`QueryBuilder`]: crate::query::QueryBuilder
+#[derive(Debug, Clone, Copy)]
+pub struct DynamicComponentId {
+    world_id: WorldId,
+    component_id: ComponentId,
+    represented_type: Option<&'static TypeInfo>,
+}
+
+impl DynamicComponentId {
+    /// Returns the [`ComponentId`] of the component, to build queries.
+    pub fn component_id(&self) -> ComponentId {
+        self.component_id
+    }
+
+    /// Returns the type represented by the values of the component, if there's one.
+    pub fn represented_type(&self) -> Option<&'static TypeInfo> {
+        self.represented_type
+    }
+
+    #[track_caller]
+    pub(crate) fn assert_world(&self, world_id: WorldId) {
+        assert_eq!(
+            self.world_id, world_id,
+            "Attempted to use a dynamic component registered in another world."
+        );
+    }
+
+    pub(crate) fn check_type(&self, value: &dyn Reflect) -> Result<(), DynamicTypeMismatch> {
+        let Some(expected) = self.represented_type else {
+            return Ok(());
+        };
+        let found = match value.get_represented_type_info() {
+            Some(info) => info.type_path(),
+            None => value.reflect_type_path(),
+        };
+        if found == expected.type_path() {
+            Ok(())
+        } else {
+            Err(DynamicTypeMismatch {
+                expected: expected.type_path(),
+                found: found.to_string(),
+            })
+        }
+    }
+}
+
+/// An error returned when inserting a value that doesn't represent the type of a dynamic
+/// component, see [`DynamicComponentDescriptor::with_represented_type`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Expected a value of type `{expected}`, found a value of type `{found}`.")]
+pub struct DynamicTypeMismatch {
+    /// The type represented by the component.
+    pub expected: &'static str,
+    /// The type of the value.
+    pub found: String,
+}
+
+/// The values of the dynamic components are stored boxed.
+type DynamicValue = Box<dyn Reflect>;
+
+/// # Safety
+/// `ptr` must point to a [`DynamicValue`].
+unsafe fn drop_dynamic_value(ptr: OwningPtr<'_>) {
+    ptr.drop_as::<DynamicValue>();
+}
+
+/// # Safety
+/// `ptr` must point to the value of a dynamic component.
+pub(crate) unsafe fn deref_dynamic(ptr: Ptr<'_>) -> &dyn Reflect {
+    ptr.deref::<DynamicValue>().as_ref()
+}
+
+/// # Safety
+/// `value` must point to the value of a dynamic component.
+pub(crate) unsafe fn deref_dynamic_mut(value: MutUntyped<'_>) -> Mut<'_, dyn Reflect> {
+    value.map_unchanged(|ptr| ptr.deref_mut::<DynamicValue>().as_mut())
+}
+
+impl World {
+    /// Registers a component defined at runtime, whose values are reflected values.
+    ///
+    /// See [`DynamicComponentId`] to use the component.
+    pub fn register_dynamic_component(
+        &mut self,
+        descriptor: DynamicComponentDescriptor,
+    ) -> DynamicComponentId {
+        // SAFETY:
+        // - The drop fn drops a `DynamicValue`, the type of the layout
+        // - `Box<dyn Reflect>` is `Send + Sync`
+        let component_descriptor = unsafe {
+            ComponentDescriptor::new_with_layout(
+                descriptor.name,
+                descriptor.storage_type,
+                Layout::new::<DynamicValue>(),
+                Some(drop_dynamic_value),
+            )
+        };
+        DynamicComponentId {
+            world_id: self.id(),
+            component_id: self.init_component_with_descriptor(component_descriptor),
+            represented_type: descriptor.represented_type,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_reflect::{DynamicStruct, Reflect, TypePath, Typed};
+
+    use super::{DynamicComponentDescriptor, DynamicTypeMismatch};
+    use crate::{
+        query::QueryBuilder,
+        world::{FilteredEntityMut, World},
+    };
+
+    #[derive(Reflect, Debug, PartialEq)]
+    struct Health(u32);
+
+    #[test]
+    fn dynamic_components() {
+        let mut world = World::new();
+        let name = world.register_dynamic_component(DynamicComponentDescriptor::new("Name"));
+        let health = world.register_dynamic_component(
+            DynamicComponentDescriptor::new("Health").with_represented_type(Health::type_info()),
+        );
+
+        let mut value = DynamicStruct::default();
+        value.insert("first", "Ferris".to_string());
+        let entity = world
+            .spawn_empty()
+            .insert_dynamic(name, value.clone_value())
+            .unwrap()
+            .insert_dynamic(health, Box::new(Health(10)))
+            .unwrap()
+            .id();
+        assert_eq!(
+            world
+                .entity_mut(entity)
+                .insert_dynamic(health, Box::new(10u32))
+                .err(),
+            Some(DynamicTypeMismatch {
+                expected: Health::type_path(),
+                found: "u32".to_string(),
+            })
+        );
+
+        let mut query = QueryBuilder::<FilteredEntityMut>::new(&mut world)
+            .ref_id(name.component_id())
+            .mut_id(health.component_id())
+            .build();
+        for mut entity in query.iter_mut(&mut world) {
+            let name = entity.get_dynamic(name).unwrap();
+            assert!(name.reflect_partial_eq(&value).unwrap());
+            entity.get_dynamic_mut(health).unwrap().apply(&Health(20));
+        }
+
+        let health = world.entity(entity).get_dynamic(health).unwrap();
+        assert_eq!(health.downcast_ref::<Health>(), Some(&Health(20)));
+    }
+}
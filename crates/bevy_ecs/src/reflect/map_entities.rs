@@ -1,6 +1,6 @@
 use crate::{
     component::Component,
-    entity::{Entity, EntityHashMap, MapEntities, SceneEntityMapper},
+    entity::{Entity, EntityHashMap, EntityMapper, MapEntities, SceneEntityMapper},
     world::World,
 };
 use bevy_reflect::FromType;
@@ -14,6 +14,7 @@ use bevy_reflect::FromType;
 pub struct ReflectMapEntities {
     map_all_entities: fn(&mut World, &mut SceneEntityMapper),
     map_entities: fn(&mut World, &mut SceneEntityMapper, &[Entity]),
+    map_entities_in_world: fn(&mut World, &EntityHashMap<Entity>, &[Entity]),
 }
 
 impl ReflectMapEntities {
@@ -46,6 +47,34 @@ impl ReflectMapEntities {
             (self.map_entities)(world, mapper, entities);
         });
     }
+
+    /// Applies [`MapEntities`] behavior to elements in an [`EntityHashMap<Entity>`] like
+    /// [`map_entities`](Self::map_entities), except that the references to entities absent from
+    /// the map are left as they are.
+    ///
+    /// This is useful when copying entities within the same world, where the references to
+    /// entities outside of the copied set are still valid.
+    pub fn map_entities_in_world(
+        &self,
+        world: &mut World,
+        entity_map: &EntityHashMap<Entity>,
+        entities: &[Entity],
+    ) {
+        (self.map_entities_in_world)(world, entity_map, entities);
+    }
+}
+
+/// Maps the entities of an [`EntityHashMap<Entity>`], and keeps the others.
+struct InWorldEntityMapper<'m>(&'m EntityHashMap<Entity>);
+
+impl EntityMapper for InWorldEntityMapper<'_> {
+    fn map_entity(&mut self, entity: Entity) -> Entity {
+        self.0.get(&entity).copied().unwrap_or(entity)
+    }
+
+    fn mappings(&self) -> impl Iterator<Item = (Entity, Entity)> {
+        self.0.iter().map(|(&source, &target)| (source, target))
+    }
 }
 
 impl<C: Component + MapEntities> FromType<C> for ReflectMapEntities {
@@ -70,6 +99,14 @@ impl<C: Component + MapEntities> FromType<C> for ReflectMapEntities {
                     }
                 }
             },
+            map_entities_in_world: |world, entity_map, entities| {
+                let mut entity_mapper = InWorldEntityMapper(entity_map);
+                for &entity in entities {
+                    if let Some(mut component) = world.get_mut::<C>(entity) {
+                        component.map_entities(&mut entity_mapper);
+                    }
+                }
+            },
         }
     }
 }
@@ -0,0 +1,308 @@
+//! Indexes of the entities related to each other through a component.
+
+use std::{marker::PhantomData, ops::Deref};
+
+use crate as bevy_ecs;
+use crate::{
+    component::{Component, ComponentId},
+    entity::{Entities, Entity, EntityHashMap},
+    entity_disabling::Disabled,
+    query::{Changed, Has},
+    system::{Query, Res, ResMut, Resource, SystemParam},
+    world::{DeferredWorld, World},
+};
+
+/// A [`Component`] that relates its entity to a target entity, like a unit to its faction or an
+/// item to the inventory holding it.
+///
+/// Once registered with [`World::register_relation`], the entities related to a target are looked
+/// up with the [`Related`] system parameter, instead of maintaining a map by hand:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::relation::{Related, Relation};
+/// #[derive(Component)]
+/// struct InFaction(Entity);
+///
+/// impl Relation for InFaction {
+///     fn target(&self) -> Entity {
+///         self.0
+///     }
+/// }
+///
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// #[derive(Component)]
+/// struct Player;
+///
+/// fn heal_allies(
+///     players: Query<&InFaction, With<Player>>,
+///     factions: Related<InFaction>,
+///     mut units: Query<&mut Health>,
+/// ) {
+///     for faction in &players {
+///         let mut allies = units.iter_many_mut(factions.sources(faction.0));
+///         while let Some(mut health) = allies.fetch_next() {
+///             health.0 += 1;
+///         }
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// world.register_relation::<InFaction>();
+/// # bevy_ecs::system::assert_is_system(heal_allies);
+/// ```
+///
+/// The index is updated right away when the component is inserted or removed. The targets changed
+/// in place through a [`Mut`](crate::change_detection::Mut), and the relations to despawned
+/// targets, are only picked up by [`update_relation_index`], see `App::register_relation`.
+pub trait Relation: Component {
+    /// The entity this relation points to.
+    fn target(&self) -> Entity;
+}
+
+/// The entities related through the [`Relation`] `R`, see [`Related`].
+#[derive(Resource)]
+pub struct RelationIndex<R: Relation> {
+    sources: EntityHashMap<Vec<Entity>>,
+    targets: EntityHashMap<Entity>,
+    marker: PhantomData<R>,
+}
+
+impl<R: Relation> Default for RelationIndex<R> {
+    fn default() -> Self {
+        Self {
+            sources: EntityHashMap::default(),
+            targets: EntityHashMap::default(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<R: Relation> RelationIndex<R> {
+    /// The entities whose `R` points to `target`, in the order they were related to it.
+    pub fn sources(&self, target: Entity) -> &[Entity] {
+        self.sources.get(&target).map_or(&[], Vec::as_slice)
+    }
+
+    /// The entity the `R` of `source` points to, if it has one.
+    pub fn target(&self, source: Entity) -> Option<Entity> {
+        self.targets.get(&source).copied()
+    }
+
+    /// Iterates over the entities that at least one `R` points to.
+    pub fn targets(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.sources.keys().copied()
+    }
+
+    fn insert(&mut self, source: Entity, target: Entity) {
+        if let Some(previous) = self.targets.insert(source, target) {
+            self.remove_source(previous, source);
+        }
+        self.sources.entry(target).or_default().push(source);
+    }
+
+    fn remove(&mut self, source: Entity) {
+        if let Some(target) = self.targets.remove(&source) {
+            self.remove_source(target, source);
+        }
+    }
+
+    /// Drops the relations to the targets that were despawned.
+    fn remove_despawned_targets(&mut self, entities: &Entities) {
+        let targets = &mut self.targets;
+        self.sources.retain(|&target, sources| {
+            let alive = entities.contains(target);
+            if !alive {
+                for source in sources {
+                    targets.remove(source);
+                }
+            }
+            alive
+        });
+    }
+
+    fn remove_source(&mut self, target: Entity, source: Entity) {
+        if let Some(sources) = self.sources.get_mut(&target) {
+            sources.retain(|&entity| entity != source);
+            if sources.is_empty() {
+                self.sources.remove(&target);
+            }
+        }
+    }
+}
+
+/// A [`SystemParam`] to look up the entities related through the [`Relation`] `R`.
+///
+/// This isn't a query filter: combine it with [`Query::iter_many`](crate::system::Query::iter_many)
+/// to go through the components of the related entities, see [`Relation`] for an example.
+///
+/// # Panics
+///
+/// Panics if `R` wasn't registered with [`World::register_relation`].
+#[derive(SystemParam)]
+pub struct Related<'w, R: Relation> {
+    index: Res<'w, RelationIndex<R>>,
+}
+
+impl<'w, R: Relation> Deref for Related<'w, R> {
+    type Target = RelationIndex<R>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.index
+    }
+}
+
+/// Updates the [`RelationIndex`] of `R` with the targets changed in place since the last run, and
+/// drops the relations to despawned targets.
+///
+/// Inserting and removing `R` updates the index right away, this system is added by
+/// `App::register_relation` for the rest.
+pub fn update_relation_index<R: Relation>(
+    mut index: ResMut<RelationIndex<R>>,
+    // `Has<Disabled>` includes the disabled entities
+    changed: Query<(Entity, &R, Has<Disabled>), Changed<R>>,
+    entities: &Entities,
+) {
+    for (source, relation, _) in &changed {
+        let target = relation.target();
+        if index.target(source) != Some(target) {
+            index.insert(source, target);
+        }
+    }
+    if index.targets().any(|target| !entities.contains(target)) {
+        index.remove_despawned_targets(entities);
+    }
+}
+
+fn on_insert<R: Relation>(mut world: DeferredWorld, source: Entity, _: ComponentId) {
+    let Some(target) = world.get::<R>(source).map(R::target) else {
+        return;
+    };
+    world
+        .resource_mut::<RelationIndex<R>>()
+        .insert(source, target);
+}
+
+fn on_remove<R: Relation>(mut world: DeferredWorld, source: Entity, _: ComponentId) {
+    world.resource_mut::<RelationIndex<R>>().remove(source);
+}
+
+impl World {
+    /// Starts indexing the entities related through `R`, to look them up with [`Related`].
+    ///
+    /// The index is updated when `R` is inserted or removed. The targets changed in place and the
+    /// relations to despawned targets are only picked up by [`update_relation_index`], see
+    /// `App::register_relation`.
+    ///
+    /// Does nothing if `R` is already registered.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `R` already has component hooks, or if an entity already has an `R`.
+    pub fn register_relation<R: Relation>(&mut self) {
+        if self.contains_resource::<RelationIndex<R>>() {
+            return;
+        }
+        self.init_resource::<RelationIndex<R>>();
+        self.register_component_hooks::<R>()
+            .on_insert(on_insert::<R>)
+            .on_remove(on_remove::<R>);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{update_relation_index, Related, Relation, RelationIndex};
+    use crate as bevy_ecs;
+    use crate::{
+        component::Component,
+        entity::Entity,
+        system::{Query, RunSystemOnce},
+        world::World,
+    };
+
+    #[derive(Component)]
+    struct InFaction(Entity);
+
+    impl Relation for InFaction {
+        fn target(&self) -> Entity {
+            self.0
+        }
+    }
+
+    #[derive(Component)]
+    struct Strength(u32);
+
+    #[test]
+    fn relation_index() {
+        let mut world = World::new();
+        world.register_relation::<InFaction>();
+        let red = world.spawn_empty().id();
+        let blue = world.spawn_empty().id();
+        let a = world.spawn((InFaction(red), Strength(1))).id();
+        let b = world.spawn((InFaction(red), Strength(2))).id();
+        let c = world.spawn((InFaction(blue), Strength(4))).id();
+
+        let index = world.resource::<RelationIndex<InFaction>>();
+        assert_eq!(index.sources(red), [a, b]);
+        assert_eq!(index.sources(blue), [c]);
+        assert_eq!(index.target(a), Some(red));
+
+        world.entity_mut(a).insert(InFaction(blue));
+        world.entity_mut(b).remove::<InFaction>();
+        world.despawn(c);
+        let index = world.resource::<RelationIndex<InFaction>>();
+        assert!(index.sources(red).is_empty());
+        assert_eq!(index.sources(blue), [a]);
+        assert_eq!(index.target(b), None);
+        assert_eq!(index.targets().collect::<Vec<_>>(), [blue]);
+    }
+
+    #[test]
+    fn update_changed_and_despawned_targets() {
+        let mut world = World::new();
+        world.register_relation::<InFaction>();
+        let update = world.register_system(update_relation_index::<InFaction>);
+        let red = world.spawn_empty().id();
+        let blue = world.spawn_empty().id();
+        let a = world.spawn(InFaction(red)).id();
+        let b = world.spawn(InFaction(red)).id();
+        world.run_system(update).unwrap();
+
+        world.get_mut::<InFaction>(a).unwrap().0 = blue;
+        world.run_system(update).unwrap();
+        let index = world.resource::<RelationIndex<InFaction>>();
+        assert_eq!(index.sources(red), [b]);
+        assert_eq!(index.sources(blue), [a]);
+
+        world.despawn(red);
+        world.run_system(update).unwrap();
+        let index = world.resource::<RelationIndex<InFaction>>();
+        assert_eq!(index.targets().collect::<Vec<_>>(), [blue]);
+        assert_eq!(index.target(b), None);
+        assert_eq!(index.target(a), Some(blue));
+    }
+
+    #[test]
+    fn related_system_param() {
+        let mut world = World::new();
+        world.register_relation::<InFaction>();
+        let red = world.spawn_empty().id();
+        let blue = world.spawn_empty().id();
+        world.spawn((InFaction(red), Strength(1)));
+        world.spawn((InFaction(red), Strength(2)));
+        world.spawn((InFaction(blue), Strength(4)));
+
+        let strength = world.run_system_once(
+            move |factions: Related<InFaction>, units: Query<&Strength>| {
+                units
+                    .iter_many(factions.sources(red))
+                    .map(|strength| strength.0)
+                    .sum::<u32>()
+            },
+        );
+        assert_eq!(strength, 3);
+    }
+}
@@ -1031,6 +1031,57 @@ pub mod common_conditions {
         !query.is_empty()
     }
 
+    /// A [`Condition`](super::Condition)-satisfying system that returns `true`
+    /// if a component of the given type was added or changed on any entity since the condition
+    /// last ran.
+    ///
+    /// This uses [`Query::any_changed`], which skips the entities of the component when none of
+    /// them changed instead of going through each of them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # #[derive(Resource, Default)]
+    /// # struct Counter(u8);
+    /// # let mut app = Schedule::default();
+    /// # let mut world = World::new();
+    /// # world.init_resource::<Counter>();
+    /// app.add_systems(
+    ///     my_system.run_if(any_component_changed::<MyComponent>),
+    /// );
+    ///
+    /// #[derive(Component)]
+    /// struct MyComponent(u8);
+    ///
+    /// fn my_system(mut counter: ResMut<Counter>) {
+    ///     counter.0 += 1;
+    /// }
+    ///
+    /// // No entities exist yet with a `MyComponent` component so `my_system` won't run
+    /// app.run(&mut world);
+    /// assert_eq!(world.resource::<Counter>().0, 0);
+    ///
+    /// let entity = world.spawn(MyComponent(0)).id();
+    ///
+    /// // `MyComponent` was just added so `my_system` will run
+    /// app.run(&mut world);
+    /// assert_eq!(world.resource::<Counter>().0, 1);
+    ///
+    /// // Nothing changed since the last run so `my_system` won't run
+    /// app.run(&mut world);
+    /// assert_eq!(world.resource::<Counter>().0, 1);
+    ///
+    /// world.get_mut::<MyComponent>(entity).unwrap().0 = 1;
+    ///
+    /// // `MyComponent` was changed so `my_system` will run
+    /// app.run(&mut world);
+    /// assert_eq!(world.resource::<Counter>().0, 2);
+    /// ```
+    pub fn any_component_changed<T: Component>(query: Query<&T>) -> bool {
+        query.any_changed()
+    }
+
     /// Generates a [`Condition`](super::Condition)-satisfying closure that returns `true`
     /// if there are any entity with a component of the given type removed.
     pub fn any_component_removed<T: Component>() -> impl FnMut(RemovedComponents<T>) -> bool {
@@ -1393,6 +1444,7 @@ mod tests {
                 .distributive_run_if(resource_removed::<TestResource>())
                 .distributive_run_if(on_event::<TestEvent>())
                 .distributive_run_if(any_with_component::<TestComponent>)
+                .distributive_run_if(any_component_changed::<TestComponent>)
                 .distributive_run_if(not(run_once())),
         );
     }
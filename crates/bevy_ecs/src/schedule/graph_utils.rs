@@ -10,6 +10,7 @@ use crate::schedule::set::*;
 ///
 /// [`ScheduleGraph`]: super::ScheduleGraph
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum NodeId {
     /// Identifier for a system.
     System(usize),
@@ -0,0 +1,77 @@
+use crate::schedule::NodeId;
+
+/// A description of the systems and sets of a [`Schedule`](super::Schedule), with the reasons why
+/// they are ordered the way they are, returned by
+/// [`Schedule::graph_info`](super::Schedule::graph_info).
+///
+/// It's meant for external tools rendering the schedules, and can be serialized with the
+/// `serialize` feature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScheduleGraphInfo {
+    /// The label of the schedule.
+    pub label: String,
+    /// The systems of the schedule, including the sync points inserted when it was built.
+    pub systems: Vec<SystemInfo>,
+    /// The system sets of the schedule, including the sets of the system types.
+    pub sets: Vec<SystemSetInfo>,
+    /// The edges from the sets to the systems and sets they contain.
+    pub hierarchy: Vec<(NodeId, NodeId)>,
+    /// The ordering edges, from the systems and sets that run before to the ones that run after.
+    pub dependencies: Vec<(NodeId, NodeId)>,
+    /// The pairs of systems and sets whose ambiguities are ignored, with `ambiguous_with`.
+    pub ignored_ambiguities: Vec<(NodeId, NodeId)>,
+    /// The systems and sets in an order satisfying the dependencies, empty until the schedule is
+    /// initialized.
+    pub topological_order: Vec<NodeId>,
+    /// The pairs of systems whose data access conflicts while their order isn't specified, empty
+    /// until the schedule is initialized.
+    pub conflicts: Vec<SystemConflictInfo>,
+}
+
+/// A system of a [`ScheduleGraphInfo`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct SystemInfo {
+    /// The id of the system in the graph.
+    pub id: NodeId,
+    /// The name of the system.
+    pub name: String,
+    /// The names of the run conditions of the system.
+    pub conditions: Vec<String>,
+    /// Whether the system has exclusive access to the world.
+    pub is_exclusive: bool,
+    /// Whether the ambiguities of the system with all the others are ignored.
+    pub ambiguous_with_all: bool,
+}
+
+/// A system set of a [`ScheduleGraphInfo`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct SystemSetInfo {
+    /// The id of the set in the graph.
+    pub id: NodeId,
+    /// The name of the set.
+    pub name: String,
+    /// The names of the run conditions of the set.
+    pub conditions: Vec<String>,
+    /// Whether the set is the set of all the instances of a system type.
+    pub is_system_type: bool,
+    /// Whether the set is anonymous, created for a group of systems.
+    pub is_anonymous: bool,
+    /// Whether the ambiguities of the systems of the set with all the others are ignored.
+    pub ambiguous_with_all: bool,
+}
+
+/// A conflict between two systems of a [`ScheduleGraphInfo`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct SystemConflictInfo {
+    /// The id of the first system.
+    pub system_a: NodeId,
+    /// The id of the second system.
+    pub system_b: NodeId,
+    /// The names of the components and resources both systems access, with at least one of them
+    /// writing them. It's empty when the systems conflict on [`World`](crate::world::World) access.
+    pub components: Vec<String>,
+}
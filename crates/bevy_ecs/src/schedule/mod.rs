@@ -4,6 +4,7 @@ mod condition;
 mod config;
 mod executor;
 mod graph_utils;
+mod info;
 #[allow(clippy::module_inception)]
 mod schedule;
 mod set;
@@ -13,6 +14,7 @@ pub use self::condition::*;
 pub use self::config::*;
 pub use self::executor::*;
 use self::graph_utils::*;
+pub use self::info::*;
 pub use self::schedule::*;
 pub use self::set::*;
 
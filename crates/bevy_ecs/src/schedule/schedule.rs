@@ -23,7 +23,7 @@ use crate::{
     world::World,
 };
 
-pub use stepping::Stepping;
+pub use stepping::{DataBreakpointHit, Stepping};
 
 /// Resource that stores [`Schedule`]s mapped to [`ScheduleLabel`]s excluding the current running [`Schedule`].
 #[derive(Default, Resource)]
@@ -301,6 +301,50 @@ impl Schedule {
         self
     }
 
+    /// Replaces the first system of the schedule with the same [`System::name`] as `system`,
+    /// keeping its ordering, run conditions and sets. Gives `system` back if there is no such
+    /// system.
+    ///
+    /// The state of the replaced system, like its [`Local`](crate::system::Local)s, is lost, but
+    /// the change ticks carry over so the new system doesn't see everything as changed. This is
+    /// how updated system code is swapped in between frames when hot patching.
+    pub fn replace_system(
+        &mut self,
+        world: &mut World,
+        mut system: BoxedSystem,
+    ) -> Result<(), BoxedSystem> {
+        let name = system.name();
+        if let Some(old) = self
+            .executable
+            .systems
+            .iter_mut()
+            .find(|old| old.name() == name)
+        {
+            system.initialize(world);
+            system.set_last_run(old.get_last_run());
+            *old = system;
+        } else if let Some(index) = self
+            .graph
+            .systems
+            .iter()
+            .position(|node| node.get().is_some_and(|old| old.name() == name))
+        {
+            let id = NodeId::System(index);
+            let node = self.graph.systems[index].get_mut().unwrap();
+            system.set_last_run(node.get_last_run());
+            *node = system;
+            if !self.graph.uninit.iter().any(|&(uninit, _)| uninit == id) {
+                self.graph.uninit.push((id, 0));
+            }
+        } else {
+            return Err(system);
+        }
+
+        // The access of the new system may differ, so the schedule is rebuilt
+        self.graph.changed = true;
+        Ok(())
+    }
+
     /// Suppress warnings and errors that would result from systems in these sets having ambiguities
     /// (conflicting access but indeterminate order) with systems in `set`.
     #[track_caller]
@@ -392,8 +436,23 @@ impl Schedule {
                 Some(mut stepping) => stepping.skipped_systems(self),
             };
 
+            // Separate the change ticks of the systems from the earlier changes, so the data
+            // breakpoints only catch the mutations of this schedule
+            let start = world
+                .get_resource::<Stepping>()
+                .is_some_and(Stepping::has_data_breakpoints)
+                .then(|| world.increment_change_tick());
+
             self.executor
                 .run(&mut self.executable, world, skip_systems.as_ref());
+
+            if let Some(start) = start {
+                world.resource_scope(
+                    |world, mut stepping: crate::change_detection::Mut<Stepping>| {
+                        stepping.check_data_breakpoints(self, world, start);
+                    },
+                );
+            }
         }
     }
 
@@ -507,6 +566,94 @@ impl Schedule {
             self.executable.systems.len()
         }
     }
+
+    /// Returns a description of the systems and sets of this schedule, with their run conditions,
+    /// the edges ordering them and the conflicts between their data access.
+    ///
+    /// The conflicts and the order of the systems are only known once the schedule is
+    /// initialized, with [`Schedule::initialize`] or by running it.
+    pub fn graph_info(&self, components: &Components) -> ScheduleGraphInfo {
+        let graph = &self.graph;
+        let condition_names = |conditions: &[BoxedCondition]| {
+            conditions
+                .iter()
+                .map(|condition| condition.name().to_string())
+                .collect()
+        };
+        let edges = |graph: &DiGraphMap<NodeId, ()>| {
+            graph.all_edges().map(|(from, to, ())| (from, to)).collect()
+        };
+
+        // Once the schedule is built, the systems and the conditions are moved from the graph to
+        // the executable schedule.
+        let executable = &self.executable;
+        let executable_systems = executable
+            .system_ids
+            .iter()
+            .zip(&executable.systems)
+            .zip(&executable.system_conditions)
+            .map(|((&id, system), conditions)| (id, &**system, conditions.as_slice()));
+        let mut systems: Vec<_> = graph
+            .systems()
+            .chain(executable_systems)
+            .map(|(id, system, conditions)| SystemInfo {
+                id,
+                name: system.name().to_string(),
+                conditions: condition_names(conditions),
+                is_exclusive: system.is_exclusive(),
+                ambiguous_with_all: graph.ambiguous_with_all.contains(&id),
+            })
+            .collect();
+        systems.sort_by_key(|system| system.id);
+        let mut sets: Vec<_> = graph
+            .system_sets()
+            .map(|(id, _, conditions)| {
+                let set = &graph.system_sets[id.index()];
+                let conditions = executable
+                    .set_ids
+                    .iter()
+                    .position(|&set_id| set_id == id)
+                    .map_or(conditions, |index| &executable.set_conditions[index]);
+                SystemSetInfo {
+                    id,
+                    name: set.name(),
+                    conditions: condition_names(conditions),
+                    is_system_type: set.is_system_type(),
+                    is_anonymous: set.is_anonymous(),
+                    ambiguous_with_all: graph.ambiguous_with_all.contains(&id),
+                }
+            })
+            .collect();
+        sets.sort_by_key(|set| set.id);
+        let conflicts = graph
+            .conflicting_systems
+            .iter()
+            .map(|(system_a, system_b, conflicts)| SystemConflictInfo {
+                system_a: *system_a,
+                system_b: *system_b,
+                components: conflicts
+                    .iter()
+                    .filter_map(|&id| components.get_name(id))
+                    .map(ToString::to_string)
+                    .collect(),
+            })
+            .collect();
+
+        ScheduleGraphInfo {
+            label: format!("{:?}", self.label),
+            systems,
+            sets,
+            hierarchy: edges(&graph.hierarchy.graph),
+            dependencies: edges(&graph.dependency.graph),
+            ignored_ambiguities: graph
+                .ambiguous_with
+                .all_edges()
+                .map(|(a, b, ())| (a, b))
+                .collect(),
+            topological_order: graph.dependency.topsort.clone(),
+            conflicts,
+        }
+    }
 }
 
 /// A directed acyclic graph structure.
@@ -2046,7 +2193,7 @@ mod tests {
             tests::ResMut, IntoSystemConfigs, IntoSystemSetConfigs, Schedule,
             ScheduleBuildSettings, SystemSet,
         },
-        system::Commands,
+        system::{Commands, IntoSystem, Local},
         world::World,
     };
 
@@ -2058,6 +2205,92 @@ mod tests {
     #[derive(Resource)]
     struct Resource2;
 
+    #[test]
+    fn graph_info() {
+        #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+        struct Set;
+
+        fn a(_: ResMut<Resource1>) {}
+        fn b(_: ResMut<Resource1>) {}
+        fn c(_: Res<Resource1>) {}
+
+        let mut world = World::new();
+        world.insert_resource(Resource1);
+        let mut schedule = Schedule::default();
+        schedule.configure_sets(Set.run_if(|| true));
+        schedule.add_systems((a, b.in_set(Set), c.after(Set)));
+        schedule.initialize(&mut world).unwrap();
+
+        let info = schedule.graph_info(world.components());
+        let id = |name: &str| {
+            info.systems
+                .iter()
+                .find(|system| system.name.ends_with(name))
+                .unwrap()
+                .id
+        };
+        let set = info.sets.iter().find(|set| set.name == "Set").unwrap();
+        assert_eq!(set.conditions.len(), 1);
+        assert!(info.hierarchy.contains(&(set.id, id("::b"))));
+        assert!(info.dependencies.contains(&(set.id, id("::c"))));
+        assert_eq!(
+            info.topological_order.len(),
+            info.systems.len() + info.sets.len()
+        );
+
+        let mut conflicts: Vec<_> = info
+            .conflicts
+            .iter()
+            .map(|conflict| {
+                let mut systems = [conflict.system_a, conflict.system_b];
+                systems.sort();
+                (systems, conflict.components.clone())
+            })
+            .collect();
+        conflicts.sort();
+        let mut expected = vec![
+            ([id("::a"), id("::b")], vec![]),
+            ([id("::a"), id("::c")], vec![]),
+        ];
+        for (systems, components) in &mut expected {
+            systems.sort();
+            components.push(std::any::type_name::<Resource1>().to_string());
+        }
+        expected.sort();
+        assert_eq!(conflicts, expected);
+    }
+
+    #[test]
+    fn graph_info_of_built_schedule() {
+        #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+        struct Set;
+
+        fn a() {}
+        fn b() {}
+
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.configure_sets(Set.run_if(|| true));
+        schedule.add_systems((a.run_if(|| true).run_if(|| false), b.in_set(Set)));
+        // Running the schedule moves the systems and their conditions to the executable schedule.
+        schedule.run(&mut world);
+
+        let info = schedule.graph_info(world.components());
+        assert_eq!(info.systems.len(), 2);
+        let conditions = |name: &str| {
+            info.systems
+                .iter()
+                .find(|system| system.name.ends_with(name))
+                .unwrap()
+                .conditions
+                .len()
+        };
+        assert_eq!(conditions("::a"), 2);
+        assert_eq!(conditions("::b"), 0);
+        let set = info.sets.iter().find(|set| set.name == "Set").unwrap();
+        assert_eq!(set.conditions.len(), 1);
+    }
+
     // regression test for https://github.com/bevyengine/bevy/issues/9114
     #[test]
     fn ambiguous_with_not_breaking_run_conditions() {
@@ -2632,4 +2865,32 @@ mod tests {
             .expect("CheckSystemRan Resource Should Exist");
         assert_eq!(value.0, 2);
     }
+
+    #[test]
+    fn replace_system() {
+        fn count(mut count: Local<usize>, mut ran: ResMut<CheckSystemRan>) {
+            *count += 1;
+            ran.0 = *count;
+        }
+
+        let mut world = World::new();
+        world.insert_resource(CheckSystemRan(0));
+        let mut schedule = Schedule::default();
+        schedule.add_systems(count);
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<CheckSystemRan>().0, 2);
+
+        // The new system starts with a fresh `Local`
+        assert!(schedule
+            .replace_system(&mut world, Box::new(IntoSystem::into_system(count)))
+            .is_ok());
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<CheckSystemRan>().0, 1);
+
+        let other = |_: ResMut<CheckSystemRan>| {};
+        assert!(schedule
+            .replace_system(&mut world, Box::new(IntoSystem::into_system(other)))
+            .is_err());
+    }
 }
@@ -1,10 +1,14 @@
 use fixedbitset::FixedBitSet;
 use std::any::TypeId;
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 use crate::{
+    component::{Component, ComponentId, Tick},
+    entity::Entity,
     schedule::{InternedScheduleLabel, NodeId, Schedule, ScheduleLabel},
     system::{IntoSystem, ResMut, Resource},
+    world::World,
 };
 use bevy_utils::{
     tracing::{error, info, warn},
@@ -70,6 +74,37 @@ enum SystemIdentifier {
     Node(NodeId),
 }
 
+// Two methods of referring to Components, via TypeId, or ComponentId
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ComponentIdentifier {
+    Type(TypeId),
+    Id(ComponentId),
+}
+
+/// A component of an entity that pauses stepping when it's mutated, see
+/// [`Stepping::set_data_breakpoint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DataBreakpoint {
+    entity: Entity,
+    component: ComponentIdentifier,
+}
+
+/// The mutation that triggered a data breakpoint, see [`Stepping::data_breakpoint_hit`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataBreakpointHit {
+    /// The entity of the mutated component.
+    pub entity: Entity,
+    /// The mutated component.
+    pub component: ComponentId,
+    /// The schedule the component was mutated in.
+    pub schedule: InternedScheduleLabel,
+    /// The name of the system that mutated the component.
+    ///
+    /// This is `None` when no system of the schedule ran at the tick of the mutation, e.g. when
+    /// the component was mutated by commands.
+    pub system: Option<Cow<'static, str>>,
+}
+
 /// Updates to [`Stepping.schedule_states`] that will be applied at the start
 /// of the next render frame
 enum Update {
@@ -111,6 +146,12 @@ pub struct Stepping {
 
     // Updates apply at the start of the next render frame
     updates: Vec<Update>,
+
+    // components that pause stepping when they're mutated
+    data_breakpoints: Vec<DataBreakpoint>,
+
+    // the last mutation that triggered a data breakpoint
+    data_breakpoint_hit: Option<DataBreakpointHit>,
 }
 
 impl std::fmt::Debug for Stepping {
@@ -370,6 +411,128 @@ impl Stepping {
     }
 
     /// lookup the first system for the supplied schedule index
+    /// Set a data breakpoint on the `T` component of `entity`.
+    ///
+    /// When a system of a schedule with stepping enabled mutates the component, stepping waits,
+    /// and [`Stepping::data_breakpoint_hit`] returns the responsible system. While stepping one
+    /// system at a time, this stops right after the system. While continuing, the systems running
+    /// in the same schedule are not stopped, only the following ones.
+    ///
+    /// Data breakpoints are only checked while stepping is enabled.
+    pub fn set_data_breakpoint<T: Component>(&mut self, entity: Entity) -> &mut Self {
+        self.add_data_breakpoint(entity, ComponentIdentifier::Type(TypeId::of::<T>()))
+    }
+
+    /// Set a data breakpoint on the component of `entity` with the given [`ComponentId`].
+    ///
+    /// See [`Stepping::set_data_breakpoint`].
+    pub fn set_data_breakpoint_by_id(
+        &mut self,
+        entity: Entity,
+        component: ComponentId,
+    ) -> &mut Self {
+        self.add_data_breakpoint(entity, ComponentIdentifier::Id(component))
+    }
+
+    fn add_data_breakpoint(&mut self, entity: Entity, component: ComponentIdentifier) -> &mut Self {
+        let breakpoint = DataBreakpoint { entity, component };
+        if !self.data_breakpoints.contains(&breakpoint) {
+            self.data_breakpoints.push(breakpoint);
+        }
+        self
+    }
+
+    /// Clear the data breakpoint on the `T` component of `entity`.
+    pub fn clear_data_breakpoint<T: Component>(&mut self, entity: Entity) -> &mut Self {
+        let component = ComponentIdentifier::Type(TypeId::of::<T>());
+        self.data_breakpoints
+            .retain(|breakpoint| *breakpoint != DataBreakpoint { entity, component });
+        self
+    }
+
+    /// Clear the data breakpoint on the component of `entity` with the given [`ComponentId`].
+    pub fn clear_data_breakpoint_by_id(
+        &mut self,
+        entity: Entity,
+        component: ComponentId,
+    ) -> &mut Self {
+        let component = ComponentIdentifier::Id(component);
+        self.data_breakpoints
+            .retain(|breakpoint| *breakpoint != DataBreakpoint { entity, component });
+        self
+    }
+
+    /// Clear all data breakpoints.
+    pub fn clear_data_breakpoints(&mut self) -> &mut Self {
+        self.data_breakpoints.clear();
+        self
+    }
+
+    /// Returns the last mutation that triggered a data breakpoint, until stepping is continued or
+    /// stepped.
+    pub fn data_breakpoint_hit(&self) -> Option<&DataBreakpointHit> {
+        self.data_breakpoint_hit.as_ref()
+    }
+
+    /// Returns `true` if [`Stepping::check_data_breakpoints`] needs to run after the schedules.
+    pub fn has_data_breakpoints(&self) -> bool {
+        self.action != Action::RunAll && !self.data_breakpoints.is_empty()
+    }
+
+    /// Check if a system of the `schedule`, that just ran, mutated a component with a data
+    /// breakpoint. `start` is the last change tick of the world before the schedule ran.
+    ///
+    /// Note: This is called by [`Schedule::run`] after [`Stepping::skipped_systems`].
+    pub fn check_data_breakpoints(&mut self, schedule: &Schedule, world: &World, start: Tick) {
+        let label = schedule.label();
+        if !self.has_data_breakpoints() || !self.schedule_states.contains_key(&label) {
+            return;
+        }
+
+        let change_tick = world.read_change_tick();
+        for breakpoint in &self.data_breakpoints {
+            let component = match breakpoint.component {
+                ComponentIdentifier::Type(type_id) => world.components().get_id(type_id),
+                ComponentIdentifier::Id(id) => Some(id),
+            };
+            let Some((component, ticks)) = component.and_then(|component| {
+                let ticks = world
+                    .get_entity(breakpoint.entity)?
+                    .get_change_ticks_by_id(component)?;
+                Some((component, ticks))
+            }) else {
+                continue;
+            };
+            if !ticks.is_changed(start, change_tick) {
+                continue;
+            }
+
+            // Systems mutate components with the tick they ran at, which is then their last run.
+            let system = schedule.systems().ok().and_then(|mut systems| {
+                systems
+                    .find(|(_, system)| system.get_last_run() == ticks.changed)
+                    .map(|(_, system)| system.name())
+            });
+            info!(
+                "data breakpoint: {:?} of {:?} was mutated by {} in {:?}",
+                world
+                    .components()
+                    .get_info(component)
+                    .map_or("unknown component", |info| info.name()),
+                breakpoint.entity,
+                system.as_deref().unwrap_or("commands"),
+                label,
+            );
+            self.data_breakpoint_hit = Some(DataBreakpointHit {
+                entity: breakpoint.entity,
+                component,
+                schedule: label,
+                system,
+            });
+            self.action = Action::Waiting;
+        }
+    }
+
     fn first_system_index_for_schedule(&self, index: usize) -> usize {
         let label = match self.schedule_order.get(index) {
             None => return 0,
@@ -462,6 +625,7 @@ impl Stepping {
 
                     // permitted action transition; make the change
                     self.action = action;
+                    self.data_breakpoint_hit = None;
                 }
                 Update::AddSchedule(l) => {
                     self.schedule_states.insert(l, ScheduleState::default());
@@ -1557,4 +1721,44 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn data_breakpoint() {
+        #[derive(Component)]
+        struct Position(f32);
+
+        fn read_position(_query: Query<&Position>) {}
+        fn move_position(mut query: Query<&mut Position>) {
+            for mut position in &mut query {
+                position.0 += 1.0;
+            }
+        }
+
+        let mut world = World::new();
+        let entity = world.spawn(Position(0.0)).id();
+        let mut schedule = Schedule::new(TestSchedule);
+        schedule.add_systems((read_position, move_position).chain());
+
+        let mut stepping = Stepping::new();
+        stepping
+            .add_schedule(TestSchedule)
+            .enable()
+            .set_data_breakpoint::<Position>(entity)
+            .continue_frame()
+            .next_frame();
+        world.insert_resource(stepping);
+        schedule.run(&mut world);
+
+        let stepping = world.resource::<Stepping>();
+        let hit = stepping.data_breakpoint_hit().unwrap();
+        assert_eq!(hit.entity, entity);
+        assert_eq!(hit.schedule, TestSchedule.intern());
+        assert!(hit.system.as_deref().unwrap().ends_with("move_position"));
+        assert_eq!(stepping.action, Action::Waiting);
+
+        // The systems don't run while waiting
+        world.resource_mut::<Stepping>().next_frame();
+        schedule.run(&mut world);
+        assert_eq!(world.get::<Position>(entity).unwrap().0, 1.0);
+    }
 }
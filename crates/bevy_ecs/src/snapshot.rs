@@ -0,0 +1,594 @@
+//! Snapshots of the values of selected components, to restore the world to an earlier state.
+//!
+//! [`WorldSnapshot`] is the primitive of rollback netcode and of "rewind" debugging tools, and
+//! [`Rollback`] keeps the snapshots of the recent frames of a simulation, to replay them when the
+//! input of one of them changes.
+
+use std::{any::Any, collections::VecDeque, sync::Arc};
+
+use bevy_utils::HashMap;
+use thiserror::Error;
+
+use crate::{
+    component::{Component, StorageType, Tick},
+    entity::{Entity, EntityHashSet},
+    entity_disabling::Disabled,
+    ptr::OwningPtr,
+    query::{Has, With},
+    schedule::Schedule,
+    storage::{TableId, TableRow},
+    system::Resource,
+    world::World,
+};
+
+type TakeComponentSnapshot =
+    fn(&World, Option<&dyn ComponentSnapshotData>, Tick) -> Box<dyn ComponentSnapshotData>;
+
+/// The components saved by the [`WorldSnapshot`]s.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::snapshot::SnapshotConfig;
+/// #[derive(Component, Clone)]
+/// struct Position(f32);
+///
+/// let mut world = World::new();
+/// let entity = world.spawn(Position(0.0)).id();
+///
+/// let config = SnapshotConfig::new().with_component::<Position>();
+/// let snapshot = config.take(&world);
+///
+/// world.entity_mut(entity).get_mut::<Position>().unwrap().0 = 10.0;
+/// snapshot.restore(&mut world);
+/// assert_eq!(world.entity(entity).get::<Position>().unwrap().0, 0.0);
+/// ```
+#[derive(Default, Clone)]
+pub struct SnapshotConfig {
+    components: Vec<TakeComponentSnapshot>,
+}
+
+impl SnapshotConfig {
+    /// Creates a config saving no component.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Saves the values of the component `C`.
+    pub fn with_component<C: Component + Clone>(mut self) -> Self {
+        self.components.push(ComponentSnapshot::<C>::take);
+        self
+    }
+
+    /// Takes a snapshot of the components of all the entities of the `world`.
+    pub fn take(&self, world: &World) -> WorldSnapshot {
+        self.take_with(world, None)
+    }
+
+    /// Takes a snapshot of the components of all the entities of the `world`, sharing the values
+    /// of the tables that didn't change since the `previous` snapshot.
+    ///
+    /// This makes the snapshots cheap when most of the entities don't change between them. The
+    /// `previous` snapshot should be taken with the same config.
+    pub fn take_from(&self, world: &World, previous: &WorldSnapshot) -> WorldSnapshot {
+        self.take_with(world, Some(previous))
+    }
+
+    fn take_with(&self, world: &World, previous: Option<&WorldSnapshot>) -> WorldSnapshot {
+        // The changes made from now on have a newer tick than the snapshot
+        let tick = world.increment_change_tick();
+        let components = self
+            .components
+            .iter()
+            .enumerate()
+            .map(|(index, take)| match previous {
+                Some(previous) => take(
+                    world,
+                    previous.components.get(index).map(AsRef::as_ref),
+                    previous.tick,
+                ),
+                None => take(world, None, tick),
+            })
+            .collect();
+        WorldSnapshot { tick, components }
+    }
+}
+
+/// The values of some components of all the entities, taken with [`SnapshotConfig::take`].
+pub struct WorldSnapshot {
+    tick: Tick,
+    components: Vec<Box<dyn ComponentSnapshotData>>,
+}
+
+impl WorldSnapshot {
+    /// Restores the values of the components to their values in the snapshot.
+    ///
+    /// Only the values changed since the snapshot are written back, in place, so they are the
+    /// only ones detected as changed, and no hook or observer runs for them. The entities
+    /// despawned since the snapshot are spawned back, with the components of the snapshot, unless
+    /// their ids were reused. The components are removed from the entities that didn't have them.
+    pub fn restore(&self, world: &mut World) {
+        for component in &self.components {
+            component.restore(world, self.tick);
+        }
+    }
+}
+
+trait ComponentSnapshotData: Send + Sync + 'static {
+    fn as_any(&self) -> &dyn Any;
+
+    fn restore(&self, world: &mut World, since: Tick);
+}
+
+/// The values of `C` in a table, shared between the snapshots while they don't change.
+struct TableSnapshot<C> {
+    entities: Vec<Entity>,
+    values: Vec<C>,
+}
+
+struct ComponentSnapshot<C> {
+    tables: HashMap<TableId, Arc<TableSnapshot<C>>>,
+    sparse_set: Vec<(Entity, C)>,
+}
+
+impl<C: Component + Clone> ComponentSnapshot<C> {
+    fn take(
+        world: &World,
+        previous: Option<&dyn ComponentSnapshotData>,
+        since: Tick,
+    ) -> Box<dyn ComponentSnapshotData> {
+        let mut snapshot = Self {
+            tables: HashMap::default(),
+            sparse_set: Vec::new(),
+        };
+        let Some(component_id) = world.component_id::<C>() else {
+            return Box::new(snapshot);
+        };
+        match C::STORAGE_TYPE {
+            StorageType::Table => {
+                let previous =
+                    previous.and_then(|previous| previous.as_any().downcast_ref::<Self>());
+                let this_run = world.read_change_tick();
+                for (index, table) in world.storages().tables.iter().enumerate() {
+                    let Some(column) = table.get_column(component_id) else {
+                        continue;
+                    };
+                    if table.is_empty() {
+                        continue;
+                    }
+                    let table_id = TableId::from_usize(index);
+                    let unchanged = previous
+                        .and_then(|previous| previous.tables.get(&table_id))
+                        .filter(|previous| {
+                            previous.entities == table.entities()
+                                && !column.changed_tick().is_newer_than(since, this_run)
+                        });
+                    let table_snapshot = match unchanged {
+                        Some(unchanged) => unchanged.clone(),
+                        None => {
+                            // SAFETY: The column stores the values of `C`
+                            let values = unsafe { column.get_data_slice::<C>() };
+                            Arc::new(TableSnapshot {
+                                entities: table.entities().to_vec(),
+                                values: values
+                                    .iter()
+                                    // SAFETY: The world is borrowed, so the values aren't written
+                                    .map(|value| unsafe { &*value.get() }.clone())
+                                    .collect(),
+                            })
+                        }
+                    };
+                    snapshot.tables.insert(table_id, table_snapshot);
+                }
+            }
+            StorageType::SparseSet => {
+                for archetype in world.archetypes().iter() {
+                    if !archetype.contains(component_id) {
+                        continue;
+                    }
+                    snapshot
+                        .sparse_set
+                        .extend(archetype.entities().iter().filter_map(|archetype_entity| {
+                            let entity = archetype_entity.id();
+                            Some((entity, world.get::<C>(entity)?.clone()))
+                        }));
+                }
+            }
+        }
+        Box::new(snapshot)
+    }
+}
+
+impl<C: Component + Clone> ComponentSnapshotData for ComponentSnapshot<C> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn restore(&self, world: &mut World, since: Tick) {
+        let component_id = world.init_component::<C>();
+        let this_run = world.read_change_tick();
+        let mut restored = EntityHashSet::default();
+        let mut moved = Vec::new();
+        for (&table_id, snapshot) in &self.tables {
+            let column = world
+                .storages
+                .tables
+                .get_mut(table_id)
+                .filter(|table| table.entities() == snapshot.entities)
+                .and_then(|table| table.get_column_mut(component_id));
+            let Some(column) = column else {
+                // The entities moved to other tables, or were despawned
+                moved.push(snapshot);
+                continue;
+            };
+            restored.extend(snapshot.entities.iter().copied());
+            if !column.changed_tick().is_newer_than(since, this_run) {
+                continue;
+            }
+            for (row, value) in snapshot.values.iter().enumerate() {
+                // SAFETY: The column is borrowed mutably, so the ticks aren't written
+                let changed = unsafe { *column.get_changed_ticks_slice()[row].get() };
+                if changed.is_newer_than(since, this_run) {
+                    OwningPtr::make(value.clone(), |ptr| {
+                        // SAFETY: The row is in the column, which stores the values of `C`
+                        unsafe { column.replace(TableRow::from_usize(row), ptr, this_run) };
+                    });
+                }
+            }
+        }
+
+        let values = moved
+            .into_iter()
+            .flat_map(|table| table.entities.iter().zip(&table.values))
+            .chain(
+                self.sparse_set
+                    .iter()
+                    .map(|(entity, value)| (entity, value)),
+            );
+        for (&entity, value) in values {
+            let Some(mut entity_mut) = world.get_or_spawn(entity) else {
+                continue;
+            };
+            let changed = entity_mut
+                .get_change_ticks::<C>()
+                .map(|ticks| ticks.is_changed(since, this_run));
+            match changed {
+                Some(false) => {}
+                Some(true) => *entity_mut.get_mut::<C>().unwrap() = value.clone(),
+                None => {
+                    entity_mut.insert(value.clone());
+                }
+            }
+            restored.insert(entity);
+        }
+
+        // `Has<Disabled>` includes the disabled entities
+        let added: Vec<Entity> = world
+            .query_filtered::<(Entity, Has<Disabled>), With<C>>()
+            .iter(world)
+            .map(|(entity, _)| entity)
+            .filter(|entity| !restored.contains(entity))
+            .collect();
+        for entity in added {
+            world.entity_mut(entity).remove::<C>();
+        }
+    }
+}
+
+/// An error returned by [`Rollback`] when a frame isn't saved, because it's too old or it wasn't
+/// run yet.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("The frame {0} isn't saved in the rollback history.")]
+pub struct FrameUnavailable(pub u64);
+
+/// The snapshots and inputs of the recent frames of a simulation, to roll it back and replay it
+/// when the input of a past frame changes, like when the input of a remote player arrives late.
+///
+/// Each frame is run by inserting its input `I` as a resource and running a [`Schedule`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::snapshot::{Rollback, SnapshotConfig};
+/// #[derive(Component, Clone)]
+/// struct Position(i32);
+///
+/// #[derive(Resource, Clone)]
+/// struct Velocity(i32);
+///
+/// let mut world = World::new();
+/// let entity = world.spawn(Position(0)).id();
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems(|velocity: Res<Velocity>, mut positions: Query<&mut Position>| {
+///     for mut position in &mut positions {
+///         position.0 += velocity.0;
+///     }
+/// });
+///
+/// let mut rollback = Rollback::new(SnapshotConfig::new().with_component::<Position>(), 8);
+/// for _ in 0..3 {
+///     rollback.advance(&mut world, &mut schedule, Velocity(1));
+/// }
+/// assert_eq!(world.get::<Position>(entity).unwrap().0, 3);
+///
+/// // The input of the second frame was wrong
+/// rollback
+///     .rollback(&mut world, &mut schedule, 1, Velocity(5))
+///     .unwrap();
+/// assert_eq!(world.get::<Position>(entity).unwrap().0, 7);
+/// ```
+pub struct Rollback<I: Resource + Clone> {
+    config: SnapshotConfig,
+    capacity: usize,
+    first_frame: u64,
+    frames: VecDeque<(WorldSnapshot, I)>,
+}
+
+impl<I: Resource + Clone> Rollback<I> {
+    /// Creates a history saving the `capacity` most recent frames, with the components of the
+    /// `config`.
+    ///
+    /// # Panics
+    /// If `capacity` is 0.
+    pub fn new(config: SnapshotConfig, capacity: usize) -> Self {
+        assert!(
+            capacity > 0,
+            "The rollback history must save at least one frame."
+        );
+        Self {
+            config,
+            capacity,
+            first_frame: 0,
+            frames: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// The number of the next frame to run.
+    pub fn current_frame(&self) -> u64 {
+        self.first_frame + self.frames.len() as u64
+    }
+
+    /// The oldest frame that can be rolled back to.
+    pub fn first_frame(&self) -> u64 {
+        self.first_frame
+    }
+
+    /// Runs the next frame with the `input`, saving the state of the world before it.
+    pub fn advance(&mut self, world: &mut World, schedule: &mut Schedule, input: I) {
+        let snapshot = match self.frames.back() {
+            Some((previous, _)) => self.config.take_from(world, previous),
+            None => self.config.take(world),
+        };
+        if self.frames.len() == self.capacity {
+            self.frames.pop_front();
+            self.first_frame += 1;
+        }
+        self.frames.push_back((snapshot, input.clone()));
+        world.insert_resource(input);
+        schedule.run(world);
+    }
+
+    /// Replaces the input of the `frame`, and runs again the frames since then.
+    pub fn rollback(
+        &mut self,
+        world: &mut World,
+        schedule: &mut Schedule,
+        frame: u64,
+        input: I,
+    ) -> Result<(), FrameUnavailable> {
+        let index = self.frame_index(frame)?;
+        self.frames[index].0.restore(world);
+        self.frames[index].1 = input;
+        let inputs: Vec<I> = self.frames.drain(index..).map(|(_, input)| input).collect();
+        for input in inputs {
+            self.advance(world, schedule, input);
+        }
+        Ok(())
+    }
+
+    /// Restores the world to its state before the `frame`, forgetting about the frames since
+    /// then.
+    pub fn rewind(&mut self, world: &mut World, frame: u64) -> Result<(), FrameUnavailable> {
+        let index = self.frame_index(frame)?;
+        self.frames[index].0.restore(world);
+        self.frames.truncate(index);
+        Ok(())
+    }
+
+    fn frame_index(&self, frame: u64) -> Result<usize, FrameUnavailable> {
+        frame
+            .checked_sub(self.first_frame)
+            .map(|index| index as usize)
+            .filter(|&index| index < self.frames.len())
+            .ok_or(FrameUnavailable(frame))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::{ComponentSnapshot, FrameUnavailable, Rollback, SnapshotConfig};
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct A(u32);
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    #[component(storage = "SparseSet")]
+    struct B(u32);
+
+    #[derive(Component)]
+    struct C;
+
+    #[test]
+    fn snapshot_restore() {
+        let mut world = World::new();
+        let e1 = world.spawn((A(1), B(1))).id();
+        let e2 = world.spawn(A(2)).id();
+        let e3 = world.spawn(C).id();
+
+        let config = SnapshotConfig::new()
+            .with_component::<A>()
+            .with_component::<B>();
+        let snapshot = config.take(&world);
+
+        world.entity_mut(e1).insert(A(10)).remove::<B>();
+        world.despawn(e2);
+        world.entity_mut(e3).insert((A(3), B(3)));
+        snapshot.restore(&mut world);
+
+        assert_eq!(world.get::<A>(e1), Some(&A(1)));
+        assert_eq!(world.get::<B>(e1), Some(&B(1)));
+        assert_eq!(world.get::<A>(e2), Some(&A(2)));
+        assert_eq!(world.get::<A>(e3), None);
+        assert_eq!(world.get::<B>(e3), None);
+        assert!(world.get::<C>(e3).is_some());
+    }
+
+    #[derive(Resource, Default)]
+    struct Inserts(u32);
+
+    #[test]
+    fn snapshot_restores_changed_values_only() {
+        let mut world = World::new();
+        world.init_resource::<Inserts>();
+        world
+            .register_component_hooks::<A>()
+            .on_insert(|mut world, _, _| world.resource_mut::<Inserts>().0 += 1);
+        let changed = world.spawn(A(1)).id();
+        let unchanged = world.spawn(A(2)).id();
+        let other_table = world.spawn((A(3), C)).id();
+
+        let config = SnapshotConfig::new().with_component::<A>();
+        let snapshot = config.take(&world);
+        world.get_mut::<A>(changed).unwrap().0 = 10;
+        let ticks = |world: &World| {
+            [changed, unchanged, other_table].map(|entity| {
+                world
+                    .entity(entity)
+                    .get_change_ticks::<A>()
+                    .unwrap()
+                    .changed
+            })
+        };
+        let before = ticks(&world);
+        world.increment_change_tick();
+        snapshot.restore(&mut world);
+
+        assert_eq!(world.get::<A>(changed), Some(&A(1)));
+        assert_eq!(world.resource::<Inserts>().0, 3);
+        let after = ticks(&world);
+        assert_ne!(after[0], before[0]);
+        assert_eq!(after[1..], before[1..]);
+
+        // The table of the unchanged entity was left as it was, so it's still shared
+        let table = world.entity(other_table).location().table_id;
+        let restored = config.take_from(&world, &snapshot);
+        let tables = |snapshot: &super::WorldSnapshot| {
+            snapshot.components[0]
+                .as_any()
+                .downcast_ref::<ComponentSnapshot<A>>()
+                .unwrap()
+                .tables[&table]
+                .clone()
+        };
+        assert!(Arc::ptr_eq(&tables(&snapshot), &tables(&restored)));
+    }
+
+    #[test]
+    fn snapshot_shares_unchanged_tables() {
+        let mut world = World::new();
+        let unchanged = world.spawn(A(1)).id();
+        let changed = world.spawn((A(2), C)).id();
+
+        let config = SnapshotConfig::new().with_component::<A>();
+        let first = config.take(&world);
+        world.get_mut::<A>(changed).unwrap().0 = 3;
+        let second = config.take_from(&world, &first);
+
+        let tables = |snapshot: &super::WorldSnapshot| {
+            snapshot.components[0]
+                .as_any()
+                .downcast_ref::<ComponentSnapshot<A>>()
+                .unwrap()
+                .tables
+                .clone()
+        };
+        let (first, second) = (tables(&first), tables(&second));
+        let table_a = world.entity(unchanged).location().table_id;
+        let table_ac = world.entity(changed).location().table_id;
+        assert!(Arc::ptr_eq(&first[&table_a], &second[&table_a]));
+        assert!(!Arc::ptr_eq(&first[&table_ac], &second[&table_ac]));
+        assert_eq!(second[&table_ac].values, [A(3)]);
+    }
+
+    #[test]
+    fn snapshot_after_half_of_the_tick_range() {
+        let mut world = World::new();
+        // The tables are created once the change tick went past half of the tick range
+        *world.change_tick.get_mut() = u32::MAX / 2 + 10;
+        let unchanged = world.spawn(A(1)).id();
+        let changed = world.spawn((A(2), C)).id();
+
+        let config = SnapshotConfig::new().with_component::<A>();
+        let first = config.take(&world);
+        world.get_mut::<A>(changed).unwrap().0 = 3;
+        let second = config.take_from(&world, &first);
+
+        let tables = |snapshot: &super::WorldSnapshot| {
+            snapshot.components[0]
+                .as_any()
+                .downcast_ref::<ComponentSnapshot<A>>()
+                .unwrap()
+                .tables
+                .clone()
+        };
+        let table_ac = world.entity(changed).location().table_id;
+        assert!(!Arc::ptr_eq(
+            &tables(&first)[&table_ac],
+            &tables(&second)[&table_ac]
+        ));
+        assert_eq!(tables(&second)[&table_ac].values, [A(3)]);
+
+        world.get_mut::<A>(unchanged).unwrap().0 = 4;
+        first.restore(&mut world);
+        assert_eq!(world.get::<A>(unchanged), Some(&A(1)));
+        assert_eq!(world.get::<A>(changed), Some(&A(2)));
+    }
+
+    #[derive(Resource, Clone)]
+    struct Input(u32);
+
+    #[test]
+    fn rollback_replays_inputs() {
+        let mut world = World::new();
+        let entity = world.spawn(A(0)).id();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(|input: Res<Input>, mut query: Query<&mut A>| {
+            for mut a in &mut query {
+                a.0 += input.0;
+            }
+        });
+
+        let mut rollback = Rollback::new(SnapshotConfig::new().with_component::<A>(), 3);
+        for i in 1..=4 {
+            rollback.advance(&mut world, &mut schedule, Input(i));
+        }
+        assert_eq!(world.get::<A>(entity), Some(&A(10)));
+        assert_eq!(rollback.first_frame(), 1);
+        assert_eq!(
+            rollback.rollback(&mut world, &mut schedule, 0, Input(0)),
+            Err(FrameUnavailable(0))
+        );
+
+        rollback
+            .rollback(&mut world, &mut schedule, 2, Input(0))
+            .unwrap();
+        assert_eq!(world.get::<A>(entity), Some(&A(7)));
+        assert_eq!(rollback.current_frame(), 4);
+
+        rollback.rewind(&mut world, 1).unwrap();
+        assert_eq!(world.get::<A>(entity), Some(&A(1)));
+        assert_eq!(rollback.current_frame(), 1);
+    }
+}
@@ -5,7 +5,7 @@ use crate::{
 };
 use bevy_ptr::{OwningPtr, Ptr};
 use nonmax::NonMaxUsize;
-use std::{cell::UnsafeCell, hash::Hash, marker::PhantomData};
+use std::{cell::UnsafeCell, hash::Hash, marker::PhantomData, mem};
 
 type EntityIndex = u32;
 
@@ -112,6 +112,12 @@ impl<I: SparseSetIndex, V> SparseArray<I, V> {
     }
 }
 
+/// The size of the elements of `slice`, for `ComponentSparseSet::entities` whose element type
+/// depends on `debug_assertions`.
+fn element_size<T>(_slice: &[T]) -> usize {
+    mem::size_of::<T>()
+}
+
 /// A sparse data structure of [`Component`](crate::component::Component)s.
 ///
 /// Designed for relatively fast insertions and deletions.
@@ -158,6 +164,22 @@ impl ComponentSparseSet {
         self.dense.len() == 0
     }
 
+    /// Returns the number of bytes taken by the component values, their change ticks
+    /// and the entities they belong to.
+    #[inline]
+    pub fn used_bytes(&self) -> usize {
+        self.dense.used_bytes() + self.entities.len() * element_size(&self.entities)
+    }
+
+    /// Returns the number of bytes allocated by the sparse set, including the capacity
+    /// that isn't used yet and the sparse index of the entities.
+    #[inline]
+    pub fn allocated_bytes(&self) -> usize {
+        self.dense.allocated_bytes()
+            + self.entities.capacity() * element_size(&self.entities)
+            + self.sparse.values.capacity() * mem::size_of::<Option<TableRow>>()
+    }
+
     /// Inserts the `entity` key and component `value` pair into this sparse
     /// set.
     ///
@@ -262,6 +284,20 @@ impl ComponentSparseSet {
         unsafe { Some(self.dense.get_changed_tick_unchecked(dense_index)) }
     }
 
+    /// Returns the last tick at which any value of the sparse set may have been changed, see
+    /// [`Column::changed_tick`].
+    #[inline]
+    pub fn changed_tick(&self) -> Tick {
+        self.dense.changed_tick()
+    }
+
+    /// Returns the [`Column`] storing the values of the sparse set, whose changed tick is
+    /// [`ComponentSparseSet::changed_tick`].
+    #[inline]
+    pub(crate) fn column(&self) -> &Column {
+        &self.dense
+    }
+
     /// Returns a reference to the "added" and "changed" ticks of the entity's component value.
     ///
     /// Returns `None` if `entity` does not have a component in the sparse set.
@@ -9,7 +9,9 @@ use bevy_utils::HashMap;
 use std::alloc::Layout;
 use std::{
     cell::UnsafeCell,
+    mem,
     ops::{Index, IndexMut},
+    sync::atomic::{AtomicU32, Ordering},
 };
 
 /// An opaque unique ID for a [`Table`] within a [`World`].
@@ -25,7 +27,7 @@ use std::{
 /// [`World`]: crate::world::World
 /// [`Archetype`]: crate::archetype::Archetype
 /// [`Archetype::table_id`]: crate::archetype::Archetype::table_id
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 // SAFETY: Must be repr(transparent) due to the safety requirements on EntityLocation
 #[repr(transparent)]
 pub struct TableId(u32);
@@ -142,7 +144,8 @@ impl TableRow {
 /// same index (i.e. the entity at row 3 has its data at index 3 and its change detection ticks at
 /// index 3). A slice to these contiguous blocks of memory can be fetched
 /// via [`Column::get_data_slice`], [`Column::get_added_ticks_slice`], and
-/// [`Column::get_changed_ticks_slice`].
+/// [`Column::get_changed_ticks_slice`]. The column also keeps the last tick at which any of its
+/// values may have changed, see [`Column::changed_tick`].
 ///
 /// Like many other low-level storage types, [`Column`] has a limited and highly unsafe
 /// interface. It's highly advised to use higher level types and their safe abstractions
@@ -152,6 +155,7 @@ pub struct Column {
     data: BlobVec,
     added_ticks: Vec<UnsafeCell<Tick>>,
     changed_ticks: Vec<UnsafeCell<Tick>>,
+    changed_tick: ColumnChangedTick,
 }
 
 impl Column {
@@ -163,6 +167,7 @@ impl Column {
             data: unsafe { BlobVec::new(component_info.layout(), component_info.drop(), capacity) },
             added_ticks: Vec::with_capacity(capacity),
             changed_ticks: Vec::with_capacity(capacity),
+            changed_tick: ColumnChangedTick(AtomicU32::new(0)),
         }
     }
 
@@ -187,6 +192,7 @@ impl Column {
             .changed_ticks
             .get_unchecked_mut(row.as_usize())
             .get_mut() = tick;
+        self.mark_inserted(tick);
     }
 
     /// Writes component data to the column at given row.
@@ -202,6 +208,7 @@ impl Column {
             .changed_ticks
             .get_unchecked_mut(row.as_usize())
             .get_mut() = change_tick;
+        self.mark_inserted(change_tick);
     }
 
     /// Gets the current number of elements stored in the column.
@@ -216,6 +223,21 @@ impl Column {
         self.data.is_empty()
     }
 
+    /// Gets the number of bytes taken by the elements of the column and their change ticks.
+    #[inline]
+    pub fn used_bytes(&self) -> usize {
+        self.len() * (self.item_layout().size() + 2 * mem::size_of::<UnsafeCell<Tick>>())
+    }
+
+    /// Gets the number of bytes allocated by the column, including the capacity
+    /// that isn't used yet.
+    #[inline]
+    pub fn allocated_bytes(&self) -> usize {
+        self.data.capacity() * self.item_layout().size()
+            + (self.added_ticks.capacity() + self.changed_ticks.capacity())
+                * mem::size_of::<UnsafeCell<Tick>>()
+    }
+
     /// Removes an element from the [`Column`].
     ///
     /// - The value will be dropped if it implements [`Drop`].
@@ -281,6 +303,7 @@ impl Column {
             other.added_ticks.swap_remove(src_row.as_usize());
         *self.changed_ticks.get_unchecked_mut(dst_row.as_usize()) =
             other.changed_ticks.swap_remove(src_row.as_usize());
+        self.mark_inserted(other.changed_tick());
     }
 
     /// Pushes a new value onto the end of the [`Column`].
@@ -291,6 +314,7 @@ impl Column {
         self.data.push(ptr);
         self.added_ticks.push(UnsafeCell::new(ticks.added));
         self.changed_ticks.push(UnsafeCell::new(ticks.changed));
+        self.mark_inserted(ticks.changed);
     }
 
     #[inline]
@@ -483,6 +507,47 @@ impl Column {
         }
     }
 
+    /// Returns the last tick at which any value of the column may have been changed.
+    ///
+    /// This is updated when values are inserted, and when they are changed through a [`Mut`], but
+    /// not when they are only mutably accessed. No value of the column has a newer changed tick, so
+    /// this can rule out changes to the whole column without looking at the tick of each value.
+    ///
+    /// [`Mut`]: crate::change_detection::Mut
+    #[inline]
+    pub fn changed_tick(&self) -> Tick {
+        self.changed_tick.get()
+    }
+
+    /// Returns the changed tick of the column, which the [`Mut`]s of its values move forward when
+    /// they change them.
+    ///
+    /// [`Mut`]: crate::change_detection::Mut
+    #[inline]
+    pub(crate) fn changed_tick_cell(&self) -> &ColumnChangedTick {
+        &self.changed_tick
+    }
+
+    /// Records that values of the column were changed at `tick`, see [`Column::changed_tick`].
+    #[inline]
+    pub(crate) fn mark_changed(&self, tick: Tick) {
+        self.changed_tick.mark_changed(tick);
+    }
+
+    /// Records that a value was inserted in the column, or moved into it, with the changed `tick`.
+    ///
+    /// When this is the only value of the column, the tick of the column is replaced instead of
+    /// moved forward: the previous values are gone, and the tick of a new column isn't related to
+    /// the change tick of the world, so it could look newer than `tick` once ticks wrapped around.
+    #[inline]
+    fn mark_inserted(&mut self, tick: Tick) {
+        if self.len() == 1 {
+            *self.changed_tick.0.get_mut() = tick.get();
+        } else {
+            self.mark_changed(tick);
+        }
+    }
+
     /// Clears the column, removing all values.
     ///
     /// Note that this function has no effect on the allocated capacity of the [`Column`]>
@@ -500,6 +565,42 @@ impl Column {
         for component_ticks in &mut self.changed_ticks {
             component_ticks.get_mut().check_tick(change_tick);
         }
+
+        // Only move the tick of the column forward, so it stays newer than the ticks of its values
+        let changed_tick = self.changed_tick.0.get_mut();
+        let max_age = u32::MAX / 4;
+        if change_tick.get().wrapping_sub(*changed_tick) > max_age {
+            *changed_tick = change_tick.get().wrapping_sub(max_age);
+        }
+    }
+}
+
+/// The last tick at which any value of a [`Column`] may have been changed, see
+/// [`Column::changed_tick`].
+#[derive(Debug)]
+pub(crate) struct ColumnChangedTick(AtomicU32);
+
+impl ColumnChangedTick {
+    #[inline]
+    fn get(&self) -> Tick {
+        Tick::new(self.0.load(Ordering::Relaxed))
+    }
+
+    /// Moves the tick forward to `tick`.
+    ///
+    /// Values can be changed from several threads with different ticks, so this only moves the
+    /// tick of the column forward. `check_change_ticks` keeps it within half of the tick range of
+    /// the ticks of the values, so the newest tick is the one that's less than half of the range
+    /// ahead, even when ticks wrap around. Once the tick is up to date, this doesn't write to it.
+    #[inline]
+    pub(crate) fn mark_changed(&self, tick: Tick) {
+        let tick = tick.get();
+        let _ = self
+            .0
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+                let ahead = tick.wrapping_sub(current);
+                (ahead != 0 && ahead < u32::MAX / 2).then_some(tick)
+            });
     }
 }
 
@@ -782,6 +883,19 @@ impl Table {
         self.columns.values()
     }
 
+    /// Iterates over the [`Column`]s of the [`Table`], along with the [`ComponentId`] of the
+    /// components they store.
+    pub fn iter_with_ids(&self) -> impl Iterator<Item = (ComponentId, &Column)> {
+        self.columns.iter().map(|(id, column)| (*id, column))
+    }
+
+    /// Gets the number of bytes allocated by the [`Table`] for its entities and
+    /// components, including the capacity that isn't used yet.
+    pub fn allocated_bytes(&self) -> usize {
+        self.entities.capacity() * mem::size_of::<Entity>()
+            + self.iter().map(Column::allocated_bytes).sum::<usize>()
+    }
+
     /// Clears all of the stored components in the [`Table`].
     pub(crate) fn clear(&mut self) {
         self.entities.clear();
@@ -835,6 +949,14 @@ impl Tables {
         self.tables.get(id.as_usize())
     }
 
+    /// Fetches a mutable reference to a [`Table`] by its [`TableId`].
+    ///
+    /// Returns `None` if `id` is invalid.
+    #[inline]
+    pub(crate) fn get_mut(&mut self, id: TableId) -> Option<&mut Table> {
+        self.tables.get_mut(id.as_usize())
+    }
+
     /// Fetches mutable references to two different [`Table`]s.
     ///
     /// # Panics
@@ -920,9 +1042,9 @@ mod tests {
     use crate::ptr::OwningPtr;
     use crate::storage::Storages;
     use crate::{
-        component::{Components, Tick},
+        component::{ComponentTicks, Components, Tick},
         entity::Entity,
-        storage::{TableBuilder, TableRow},
+        storage::{Column, TableBuilder, TableRow},
     };
     #[derive(Component)]
     struct W<T>(T);
@@ -955,4 +1077,42 @@ mod tests {
         assert_eq!(table.entity_capacity(), 256);
         assert_eq!(table.entity_count(), 200);
     }
+
+    #[test]
+    fn column_changed_tick_only_moves_forward() {
+        fn push(column: &mut Column, tick: Tick) {
+            OwningPtr::make(W(0u32), |ptr| {
+                // SAFETY: `ptr` points to a `W<u32>`, the component of the column
+                unsafe { column.push(ptr, ComponentTicks::new(tick)) };
+            });
+        }
+
+        let mut components = Components::default();
+        let mut storages = Storages::default();
+        let component_id = components.init_component::<W<u32>>(&mut storages);
+        let mut column = Column::with_capacity(components.get_info(component_id).unwrap(), 0);
+
+        // The first value sets the tick of the column, even when the world's change tick went past
+        // half of the tick range since the initial tick of the column.
+        let tick = u32::MAX / 2 + 10;
+        push(&mut column, Tick::new(tick));
+        assert_eq!(column.changed_tick(), Tick::new(tick));
+        // An older tick, such as one from a system that started earlier, is ignored.
+        column.mark_changed(Tick::new(tick - 5));
+        assert_eq!(column.changed_tick(), Tick::new(tick));
+        column.mark_changed(Tick::new(u32::MAX - 5));
+        assert_eq!(column.changed_tick(), Tick::new(u32::MAX - 5));
+
+        // Ticks are compared across their wraparound.
+        column.mark_changed(Tick::new(3));
+        assert_eq!(column.changed_tick(), Tick::new(3));
+        column.mark_changed(Tick::new(u32::MAX - 1));
+        push(&mut column, Tick::new(u32::MAX - 1));
+        assert_eq!(column.changed_tick(), Tick::new(3));
+
+        // Once the column is empty, the next value replaces its tick.
+        column.clear();
+        push(&mut column, Tick::new(1));
+        assert_eq!(column.changed_tick(), Tick::new(1));
+    }
 }
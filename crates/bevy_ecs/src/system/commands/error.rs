@@ -0,0 +1,68 @@
+use bevy_utils::tracing::error;
+use thiserror::Error;
+
+use crate::{self as bevy_ecs, entity::Entity, event::Event, system::Resource, world::World};
+
+/// An error returned by a fallible command, such as [`EntityCommands::checked_insert`].
+///
+/// The errors are triggered as [`CommandFailed`] and then handled according to the
+/// [`CommandErrorPolicy`].
+///
+/// [`EntityCommands::checked_insert`]: super::EntityCommands::checked_insert
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum CommandError {
+    /// The entity the command was queued for doesn't exist.
+    #[error("The entity {0:?} does not exist.")]
+    NoSuchEntity(Entity),
+    /// The entity already has a component the command would have overwritten.
+    #[error("The entity {entity:?} already has a component of type `{component}`.")]
+    ComponentAlreadyPresent {
+        /// The entity the command was queued for.
+        entity: Entity,
+        /// The name of the component.
+        component: String,
+    },
+}
+
+/// Triggered for every [`CommandError`], before it's handled by the [`CommandErrorPolicy`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::CommandFailed;
+/// # let mut world = World::new();
+/// world.observe(|trigger: Trigger<CommandFailed>| {
+///     println!("{}", trigger.event().0);
+/// });
+/// ```
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct CommandFailed(pub CommandError);
+
+/// Chooses what happens when a fallible command fails.
+///
+/// Inserting `CommandErrorPolicy::Panic` makes tests fail on the first [`CommandError`].
+/// Without this resource, the errors are logged.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CommandErrorPolicy {
+    /// Panic with the error.
+    Panic,
+    /// Log the error.
+    #[default]
+    Log,
+    /// Ignore the error, it's only sent to the [`CommandFailed`] observers.
+    Ignore,
+}
+
+/// Triggers [`CommandFailed`] for the `error`, then handles it according to the
+/// [`CommandErrorPolicy`].
+pub(crate) fn report_command_error(world: &mut World, error: CommandError) {
+    world.trigger(CommandFailed(error.clone()));
+    match world
+        .get_resource::<CommandErrorPolicy>()
+        .copied()
+        .unwrap_or_default()
+    {
+        CommandErrorPolicy::Panic => panic!("{error}"),
+        CommandErrorPolicy::Log => error!("{error}"),
+        CommandErrorPolicy::Ignore => {}
+    }
+}
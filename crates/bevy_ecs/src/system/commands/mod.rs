@@ -1,3 +1,4 @@
+mod error;
 mod parallel_scope;
 
 use super::{Deferred, IntoObserverSystem, IntoSystem, RegisterSystem, Resource};
@@ -13,6 +14,7 @@ use crate::{
     world::{Command, CommandQueue, EntityWorldMut, FromWorld, World},
 };
 use bevy_utils::tracing::{error, info};
+pub use error::*;
 pub use parallel_scope::*;
 
 /// A [`Command`] queue to perform structural changes to the [`World`].
@@ -748,6 +750,41 @@ impl<'w, 's> Commands<'w, 's> {
         self.push(command);
     }
 
+    /// Pushes a fallible command to the queue.
+    ///
+    /// When the command returns a [`CommandError`], it's triggered as [`CommandFailed`] and
+    /// handled according to the [`CommandErrorPolicy`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::system::CommandError;
+    /// # #[derive(Resource)]
+    /// # struct Target(Entity);
+    /// fn despawn_target_system(mut commands: Commands, target: Res<Target>) {
+    ///     let target = target.0;
+    ///     commands.add_fallible(move |world: &mut World| {
+    ///         world
+    ///             .get_entity_mut(target)
+    ///             .ok_or(CommandError::NoSuchEntity(target))?
+    ///             .despawn();
+    ///         Ok(())
+    ///     });
+    /// }
+    /// # bevy_ecs::system::assert_is_system(despawn_target_system);
+    /// ```
+    pub fn add_fallible<C>(&mut self, command: C)
+    where
+        C: FnOnce(&mut World) -> Result<(), CommandError> + Send + 'static,
+    {
+        self.push(move |world: &mut World| {
+            if let Err(error) = command(world) {
+                report_command_error(world, error);
+            }
+        });
+    }
+
     /// Sends a "global" [`Trigger`] without any targets. This will run any [`Observer`] of the `event` that
     /// isn't scoped to specific targets.
     pub fn trigger(&mut self, event: impl Event) {
@@ -982,6 +1019,34 @@ impl EntityCommands<'_> {
         self.add(try_insert(bundle))
     }
 
+    /// Adds a [`Bundle`] of components to the entity, reporting a [`CommandError`] instead of
+    /// panicking.
+    ///
+    /// This will overwrite any previous value(s) of the same component type.
+    ///
+    /// # Errors
+    ///
+    /// The [`CommandErrorPolicy`] handles a [`CommandError::NoSuchEntity`] if the entity doesn't
+    /// exist.
+    ///
+    /// # Panics
+    ///
+    /// Like [`Self::insert`], the panics of the hooks and observers of the components aren't
+    /// caught.
+    pub fn checked_insert(&mut self, bundle: impl Bundle) -> &mut Self {
+        self.add(checked_insert(bundle, true))
+    }
+
+    /// Adds a [`Bundle`] of components to the entity, unless it already has one of them.
+    ///
+    /// # Errors
+    ///
+    /// Like [`Self::checked_insert`], and [`CommandError::ComponentAlreadyPresent`] without
+    /// inserting anything if the entity already has one of the components.
+    pub fn insert_new(&mut self, bundle: impl Bundle) -> &mut Self {
+        self.add(checked_insert(bundle, false))
+    }
+
     /// Removes a [`Bundle`] of components from the entity.
     ///
     /// # Example
@@ -1065,6 +1130,21 @@ impl EntityCommands<'_> {
         self.add(despawn);
     }
 
+    /// Despawns the entity, reporting a [`CommandError`] instead of emitting a warning.
+    ///
+    /// # Errors
+    ///
+    /// The [`CommandErrorPolicy`] handles a [`CommandError::NoSuchEntity`] if the entity doesn't
+    /// exist.
+    ///
+    /// # Panics
+    ///
+    /// Like [`Self::despawn`], the panics of the hooks and observers of its components aren't
+    /// caught.
+    pub fn checked_despawn(&mut self) {
+        self.add(checked_despawn);
+    }
+
     /// Pushes an [`EntityCommand`] to the queue, which will get executed for the current [`Entity`].
     ///
     /// # Examples
@@ -1245,6 +1325,39 @@ fn try_insert(bundle: impl Bundle) -> impl EntityCommand {
     }
 }
 
+/// An [`EntityCommand`] that adds the components in a [`Bundle`] to an entity, and reports a
+/// [`CommandError`] on failure. Fails if the entity has one of the components unless `overwrite`.
+fn checked_insert<T: Bundle>(bundle: T, overwrite: bool) -> impl EntityCommand {
+    move |entity: Entity, world: &mut World| {
+        let Some(mut entity_mut) = world.get_entity_mut(entity) else {
+            return report_command_error(world, CommandError::NoSuchEntity(entity));
+        };
+        if !overwrite {
+            let mut present = None;
+            T::get_component_ids(entity_mut.world().components(), &mut |id| {
+                if let Some(id) = id.filter(|&id| entity_mut.contains_id(id)) {
+                    present.get_or_insert(id);
+                }
+            });
+            if let Some(id) = present {
+                let component = world.components().get_info(id).unwrap().name().to_string();
+                let error = CommandError::ComponentAlreadyPresent { entity, component };
+                return report_command_error(world, error);
+            }
+        }
+        entity_mut.insert(bundle);
+    }
+}
+
+/// An [`EntityCommand`] that despawns an entity, and reports a [`CommandError`] on failure.
+fn checked_despawn(entity: Entity, world: &mut World) {
+    world.flush();
+    let Some(entity_mut) = world.get_entity_mut(entity) else {
+        return report_command_error(world, CommandError::NoSuchEntity(entity));
+    };
+    entity_mut.despawn();
+}
+
 /// An [`EntityCommand`] that removes components from an entity.
 /// For a [`Bundle`] type `T`, this will remove any components in the bundle.
 /// Any components in the bundle that aren't found on the entity will be ignored.
@@ -1327,7 +1440,8 @@ mod tests {
     use crate::{
         self as bevy_ecs,
         component::Component,
-        system::{Commands, Resource},
+        observer::Trigger,
+        system::{CommandError, CommandErrorPolicy, CommandFailed, Commands, ResMut, Resource},
         world::{CommandQueue, World},
     };
     use std::{
@@ -1557,4 +1671,62 @@ mod tests {
         assert!(world.contains_resource::<W<i32>>());
         assert!(world.contains_resource::<W<f64>>());
     }
+
+    #[derive(Resource, Default)]
+    struct CommandErrors(Vec<CommandError>);
+
+    #[test]
+    fn checked_commands_report_errors() {
+        let mut world = World::default();
+        world.init_resource::<CommandErrors>();
+        world.insert_resource(CommandErrorPolicy::Ignore);
+        world.observe(
+            |trigger: Trigger<CommandFailed>, mut errors: ResMut<CommandErrors>| {
+                errors.0.push(trigger.event().0.clone());
+            },
+        );
+        let entity = world.spawn(W(1u32)).id();
+        let despawned = world.spawn_empty().id();
+
+        let mut queue = CommandQueue::default();
+        {
+            let mut commands = Commands::new(&mut queue, &world);
+            commands.entity(despawned).despawn();
+            commands.entity(entity).insert_new((W(2u64), W(3u32)));
+            commands.entity(entity).checked_insert(W(4u32));
+            commands.entity(despawned).checked_insert(W(5u32));
+            commands.entity(despawned).checked_despawn();
+        }
+        queue.apply(&mut world);
+
+        assert_eq!(world.get::<W<u32>>(entity).unwrap().0, 4);
+        assert!(world.get::<W<u64>>(entity).is_none());
+        assert_eq!(
+            world.resource::<CommandErrors>().0,
+            vec![
+                CommandError::ComponentAlreadyPresent {
+                    entity,
+                    component: std::any::type_name::<W<u32>>().to_string(),
+                },
+                CommandError::NoSuchEntity(despawned),
+                CommandError::NoSuchEntity(despawned),
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn command_error_policy_panic() {
+        let mut world = World::default();
+        world.insert_resource(CommandErrorPolicy::Panic);
+        let entity = world.spawn_empty().id();
+
+        let mut queue = CommandQueue::default();
+        {
+            let mut commands = Commands::new(&mut queue, &world);
+            commands.entity(entity).despawn();
+            commands.entity(entity).checked_despawn();
+        }
+        queue.apply(&mut world);
+    }
 }
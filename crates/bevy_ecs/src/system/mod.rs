@@ -348,7 +348,7 @@ mod tests {
         self as bevy_ecs,
         archetype::{ArchetypeComponentId, Archetypes},
         bundle::Bundles,
-        change_detection::DetectChanges,
+        change_detection::{DetectChanges, DetectChangesMut},
         component::{Component, Components, Tick},
         entity::{Entities, Entity},
         prelude::AnyOf,
@@ -1120,6 +1120,66 @@ mod tests {
         with_filter.run((), &mut world);
     }
 
+    #[test]
+    fn query_any_changed() {
+        #[derive(Component)]
+        #[component(storage = "SparseSet")]
+        struct Sparse;
+
+        fn mutate_a(mut query: Query<&mut A>) {
+            for mut a in &mut query {
+                a.set_changed();
+            }
+        }
+
+        let mut world = World::default();
+        let entity = world.spawn((A, W(0u32))).id();
+        world.spawn((B, Sparse));
+
+        let mut any_changed =
+            IntoSystem::into_system(|a: Query<&A>, w: Query<&W<u32>>, sparse: Query<&Sparse>| {
+                (a.any_changed(), w.any_changed(), sparse.any_changed())
+            });
+        any_changed.initialize(&mut world);
+        assert_eq!(any_changed.run((), &mut world), (true, true, true));
+        assert_eq!(any_changed.run((), &mut world), (false, false, false));
+
+        world.get_mut::<W<u32>>(entity).unwrap().0 = 1;
+        world.entity_mut(entity).insert(Sparse);
+        assert_eq!(any_changed.run((), &mut world), (false, true, true));
+
+        // Mutable access alone doesn't change the components
+        let mut access_all = IntoSystem::into_system(
+            |mut query: Query<(&mut A, &mut W<u32>, Option<&mut Sparse>)>| {
+                for item in &mut query {
+                    std::hint::black_box(item);
+                }
+            },
+        );
+        access_all.initialize(&mut world);
+        access_all.run((), &mut world);
+        assert_eq!(any_changed.run((), &mut world), (false, false, false));
+
+        let mut mutate_a = IntoSystem::into_system(mutate_a);
+        mutate_a.initialize(&mut world);
+        mutate_a.run((), &mut world);
+        assert_eq!(any_changed.run((), &mut world), (true, false, false));
+
+        // Moving the entity to another table keeps its components changed
+        world.get_mut::<A>(entity).unwrap().set_changed();
+        world.entity_mut(entity).insert(B);
+        assert_eq!(any_changed.run((), &mut world), (true, false, false));
+
+        // The components of new tables are detected after the change tick went past half of the
+        // tick range, as well as the changes to the existing ones
+        *world.change_tick.get_mut() += u32::MAX / 2 + 10;
+        world.check_change_ticks();
+        assert_eq!(any_changed.run((), &mut world), (false, false, false));
+        world.spawn(W(1u32));
+        world.get_mut::<A>(entity).unwrap().set_changed();
+        assert_eq!(any_changed.run((), &mut world), (true, true, false));
+    }
+
     #[test]
     #[allow(clippy::too_many_arguments)]
     fn can_have_16_parameters() {
@@ -3,8 +3,9 @@ use crate::{
     component::Tick,
     entity::Entity,
     query::{
-        QueryCombinationIter, QueryData, QueryEntityError, QueryFilter, QueryIter, QueryManyIter,
-        QueryParIter, QuerySingleError, QueryState, ROQueryItem, ReadOnlyQueryData,
+        ArchetypeFilter, QueryChunkData, QueryChunkIter, QueryChunkParIter, QueryCombinationIter,
+        QueryData, QueryEntityError, QueryFilter, QueryIter, QueryManyIter, QueryParIter,
+        QuerySingleError, QueryState, ROQueryItem, ReadOnlyQueryData,
     },
     world::unsafe_world_cell::UnsafeWorldCell,
 };
@@ -809,6 +810,124 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
         }
     }
 
+    /// Returns an [`Iterator`] over the read-only query data, in chunks of up to `N` entities.
+    ///
+    /// Each chunk is a tuple of slices, one per item of the query data, of entities in the same
+    /// table. The chunks start at multiples of `N` in their table, so only the last chunk of each
+    /// table can be shorter. This lets the inner loops over the slices be auto-vectorized.
+    ///
+    /// This can only be called for read-only queries, see [`iter_chunks_mut`] for write-queries.
+    ///
+    /// # Panics
+    ///
+    /// If `N` is zero, or if a component of the query is not stored in tables.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Mass(f32);
+    /// fn total_mass_system(query: Query<&Mass>) {
+    ///     let mut total = 0.0;
+    ///     for masses in query.iter_chunks::<8>() {
+    ///         total += masses.iter().map(|mass| mass.0).sum::<f32>();
+    ///     }
+    ///     println!("{total}");
+    /// }
+    /// # bevy_ecs::system::assert_is_system(total_mass_system);
+    /// ```
+    ///
+    /// [`iter_chunks_mut`]: Self::iter_chunks_mut
+    #[inline]
+    pub fn iter_chunks<const N: usize>(&self) -> QueryChunkIter<'_, 's, D::ReadOnly, F, N>
+    where
+        D::ReadOnly: QueryChunkData,
+        F: ArchetypeFilter,
+    {
+        // SAFETY:
+        // - `self.world` has permission to access the required components.
+        // - The query is read-only, so it can be aliased even if it was originally mutable.
+        unsafe { QueryChunkIter::new(self.world, self.state.as_readonly(), self.this_run) }
+    }
+
+    /// Returns an [`Iterator`] over the query data, in chunks of up to `N` entities.
+    ///
+    /// See [`iter_chunks`](Self::iter_chunks).
+    ///
+    /// Writes to the slices can't be tracked, so every row of a chunk fetched with `&mut T` is
+    /// marked as changed when the chunk is fetched, whether it's written or not. `Changed<T>`
+    /// filters then match all of these entities, so prefer [`iter_mut`](Self::iter_mut) when only
+    /// a few of them are written.
+    ///
+    /// The chunks start at multiples of `N` rows of their table, but their slices are only
+    /// aligned like their component type, not to the size of SIMD registers.
+    ///
+    /// # Panics
+    ///
+    /// If `N` is zero, or if a component of the query is not stored in tables.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Position(f32);
+    /// # #[derive(Component)]
+    /// # struct Velocity(f32);
+    /// fn move_system(mut query: Query<(&mut Position, &Velocity)>) {
+    ///     for (positions, velocities) in query.iter_chunks_mut::<8>() {
+    ///         for (position, velocity) in positions.iter_mut().zip(velocities) {
+    ///             position.0 += velocity.0;
+    ///         }
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(move_system);
+    /// ```
+    #[inline]
+    pub fn iter_chunks_mut<const N: usize>(&mut self) -> QueryChunkIter<'_, 's, D, F, N>
+    where
+        D: QueryChunkData,
+        F: ArchetypeFilter,
+    {
+        // SAFETY: `self.world` has permission to access the required components.
+        unsafe { QueryChunkIter::new(self.world, self.state, self.this_run) }
+    }
+
+    /// Returns a parallel iterator over the read-only query data, in chunks of up to `N` entities.
+    ///
+    /// See [`iter_chunks`](Self::iter_chunks) and [`par_iter`](Self::par_iter).
+    #[inline]
+    pub fn par_iter_chunks<const N: usize>(&self) -> QueryChunkParIter<'_, 's, D::ReadOnly, F, N>
+    where
+        D::ReadOnly: QueryChunkData,
+        F: ArchetypeFilter,
+    {
+        QueryChunkParIter {
+            world: self.world,
+            state: self.state.as_readonly(),
+            this_run: self.this_run,
+        }
+    }
+
+    /// Returns a parallel iterator over the query data, in chunks of up to `N` entities.
+    ///
+    /// See [`iter_chunks_mut`](Self::iter_chunks_mut) and [`par_iter_mut`](Self::par_iter_mut).
+    #[inline]
+    pub fn par_iter_chunks_mut<const N: usize>(&mut self) -> QueryChunkParIter<'_, 's, D, F, N>
+    where
+        D: QueryChunkData,
+        F: ArchetypeFilter,
+    {
+        QueryChunkParIter {
+            world: self.world,
+            state: self.state,
+            this_run: self.this_run,
+        }
+    }
+
     /// Returns the read-only query item for the given [`Entity`].
     ///
     /// In case of a nonexisting entity or mismatched component, a [`QueryEntityError`] is returned instead.
@@ -1254,6 +1373,48 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
         }
     }
 
+    /// Returns `true` if any component read or written by the query was changed since the last
+    /// time the system ran, on any entity of the archetypes the query matches.
+    ///
+    /// Every column of components keeps the tick of its latest change, so unlike going through
+    /// the query with a [`Changed`] filter this doesn't look at the entities of the unchanged
+    /// components. This makes it cheap to skip the work of a system when nothing it depends on
+    /// changed:
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Transform;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Collider;
+    /// #
+    /// fn rebuild_broad_phase(colliders: Query<(&Transform, &Collider)>) {
+    ///     if !colliders.any_changed() {
+    ///         return;
+    ///     }
+    ///     // ...
+    /// }
+    /// # bevy_ecs::system::assert_is_system(rebuild_broad_phase);
+    /// ```
+    ///
+    /// The per-entity filters like [`Changed`] aren't applied, and neither are the components that
+    /// are only accessed through [`EntityRef`](crate::world::EntityRef).
+    ///
+    /// [`Changed`]: crate::query::Changed
+    #[inline]
+    pub fn any_changed(&self) -> bool {
+        // SAFETY:
+        // - `self.world` has permission to read any data required by the WorldQuery.
+        // - `&self` ensures that no one currently has write access.
+        // - `self.world` matches `self.state`.
+        unsafe {
+            self.state
+                .any_changed_unsafe_world_cell(self.world, self.last_run, self.this_run)
+        }
+    }
+
     /// Returns `true` if the given [`Entity`] matches the query.
     ///
     /// This is always guaranteed to run in `O(1)` time.
@@ -639,6 +639,7 @@ unsafe impl<'a, T: Resource> SystemParam for ResMut<'a, T> {
                 changed: value.ticks.changed,
                 last_run: system_meta.last_run,
                 this_run: change_tick,
+                column_tick: None,
             },
         }
     }
@@ -669,6 +670,7 @@ unsafe impl<'a, T: Resource> SystemParam for Option<ResMut<'a, T>> {
                     changed: value.ticks.changed,
                     last_run: system_meta.last_run,
                     this_run: change_tick,
+                    column_tick: None,
                 },
             })
     }
@@ -9,6 +9,8 @@ pub const ON_ADD: ComponentId = ComponentId::new(0);
 pub const ON_INSERT: ComponentId = ComponentId::new(1);
 /// [`ComponentId`] for [`OnRemove`]
 pub const ON_REMOVE: ComponentId = ComponentId::new(2);
+/// [`ComponentId`] for [`Disabled`](crate::entity_disabling::Disabled)
+pub const DISABLED: ComponentId = ComponentId::new(3);
 
 /// Trigger emitted when a component is added to an entity.
 #[derive(Event)]
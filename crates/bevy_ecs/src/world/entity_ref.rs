@@ -1,3 +1,5 @@
+#[cfg(feature = "bevy_reflect")]
+use crate::reflect::{deref_dynamic, deref_dynamic_mut, DynamicComponentId, DynamicTypeMismatch};
 use crate::{
     archetype::{Archetype, ArchetypeId, Archetypes},
     bundle::{Bundle, BundleId, BundleInfo, BundleInserter, DynamicBundle},
@@ -13,6 +15,8 @@ use crate::{
     world::{DeferredWorld, Mut, World},
 };
 use bevy_ptr::{OwningPtr, Ptr};
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::Reflect;
 use std::{any::TypeId, marker::PhantomData};
 use thiserror::Error;
 
@@ -156,6 +160,19 @@ impl<'w> EntityRef<'w> {
         // SAFETY: We have read-only access to all components of this entity.
         unsafe { self.0.get_by_id(component_id) }
     }
+
+    /// Gets the value of the dynamic component `id` from the entity.
+    ///
+    /// # Panics
+    /// If `id` was registered in another [`World`].
+    #[cfg(feature = "bevy_reflect")]
+    #[inline]
+    pub fn get_dynamic(&self, id: DynamicComponentId) -> Option<&'w dyn Reflect> {
+        id.assert_world(self.0.world().id());
+        self.get_by_id(id.component_id())
+            // SAFETY: `id` is a dynamic component of this world
+            .map(|ptr| unsafe { deref_dynamic(ptr) })
+    }
 }
 
 impl<'w> From<EntityWorldMut<'w>> for EntityRef<'w> {
@@ -470,6 +487,29 @@ impl<'w> EntityMut<'w> {
         // consuming `self` ensures that no references exist to this entity's components.
         unsafe { self.0.get_mut_by_id(component_id) }
     }
+
+    /// Gets the value of the dynamic component `id` from the entity.
+    ///
+    /// # Panics
+    /// If `id` was registered in another [`World`].
+    #[cfg(feature = "bevy_reflect")]
+    #[inline]
+    pub fn get_dynamic(&self, id: DynamicComponentId) -> Option<&'_ dyn Reflect> {
+        self.as_readonly().get_dynamic(id)
+    }
+
+    /// Gets a mutable reference to the value of the dynamic component `id` from the entity.
+    ///
+    /// # Panics
+    /// If `id` was registered in another [`World`].
+    #[cfg(feature = "bevy_reflect")]
+    #[inline]
+    pub fn get_dynamic_mut(&mut self, id: DynamicComponentId) -> Option<Mut<'_, dyn Reflect>> {
+        id.assert_world(self.0.world().id());
+        self.get_mut_by_id(id.component_id())
+            // SAFETY: `id` is a dynamic component of this world
+            .map(|value| unsafe { deref_dynamic_mut(value) })
+    }
 }
 
 impl<'w> From<&'w mut EntityMut<'_>> for EntityMut<'w> {
@@ -816,6 +856,59 @@ impl<'w> EntityWorldMut<'w> {
         self
     }
 
+    /// Inserts the `value` of the dynamic component `id` into the entity.
+    ///
+    /// This will overwrite any previous value of the component.
+    ///
+    /// # Errors
+    /// If the component only accepts the values of a represented type, see
+    /// [`DynamicComponentDescriptor::with_represented_type`], and `value` doesn't represent it.
+    ///
+    /// # Panics
+    /// If `id` was registered in another [`World`].
+    ///
+    /// [`DynamicComponentDescriptor::with_represented_type`]: crate::reflect::DynamicComponentDescriptor::with_represented_type
+    #[cfg(feature = "bevy_reflect")]
+    pub fn insert_dynamic(
+        &mut self,
+        id: DynamicComponentId,
+        value: Box<dyn Reflect>,
+    ) -> Result<&mut Self, DynamicTypeMismatch> {
+        id.assert_world(self.world.id());
+        id.check_type(value.as_ref())?;
+        OwningPtr::make(value, |ptr| {
+            // SAFETY: `id` is a dynamic component of this world, whose values are
+            // `Box<dyn Reflect>`
+            unsafe {
+                self.insert_by_id(id.component_id(), ptr);
+            }
+        });
+        Ok(self)
+    }
+
+    /// Gets the value of the dynamic component `id` from the entity.
+    ///
+    /// # Panics
+    /// If `id` was registered in another [`World`].
+    #[cfg(feature = "bevy_reflect")]
+    #[inline]
+    pub fn get_dynamic(&self, id: DynamicComponentId) -> Option<&'_ dyn Reflect> {
+        EntityRef::from(self).get_dynamic(id)
+    }
+
+    /// Gets a mutable reference to the value of the dynamic component `id` from the entity.
+    ///
+    /// # Panics
+    /// If `id` was registered in another [`World`].
+    #[cfg(feature = "bevy_reflect")]
+    #[inline]
+    pub fn get_dynamic_mut(&mut self, id: DynamicComponentId) -> Option<Mut<'_, dyn Reflect>> {
+        id.assert_world(self.world.id());
+        self.get_mut_by_id(id.component_id())
+            // SAFETY: `id` is a dynamic component of this world
+            .map(|value| unsafe { deref_dynamic_mut(value) })
+    }
+
     /// Inserts a dynamic [`Bundle`] into the entity.
     ///
     /// This will overwrite any previous value(s) of the same component type.
@@ -1938,6 +2031,19 @@ impl<'w> FilteredEntityRef<'w> {
             .then(|| unsafe { self.entity.get_by_id(component_id) })
             .flatten()
     }
+
+    /// Gets the value of the dynamic component `id` from the entity.
+    ///
+    /// # Panics
+    /// If `id` was registered in another [`World`].
+    #[cfg(feature = "bevy_reflect")]
+    #[inline]
+    pub fn get_dynamic(&self, id: DynamicComponentId) -> Option<&'w dyn Reflect> {
+        id.assert_world(self.entity.world().id());
+        self.get_by_id(id.component_id())
+            // SAFETY: `id` is a dynamic component of this world
+            .map(|ptr| unsafe { deref_dynamic(ptr) })
+    }
 }
 
 impl<'w> From<FilteredEntityMut<'w>> for FilteredEntityRef<'w> {
@@ -2214,6 +2320,29 @@ impl<'w> FilteredEntityMut<'w> {
             .then(|| unsafe { self.entity.get_mut_by_id(component_id) })
             .flatten()
     }
+
+    /// Gets the value of the dynamic component `id` from the entity.
+    ///
+    /// # Panics
+    /// If `id` was registered in another [`World`].
+    #[cfg(feature = "bevy_reflect")]
+    #[inline]
+    pub fn get_dynamic(&self, id: DynamicComponentId) -> Option<&'_ dyn Reflect> {
+        self.as_readonly().get_dynamic(id)
+    }
+
+    /// Gets a mutable reference to the value of the dynamic component `id` from the entity.
+    ///
+    /// # Panics
+    /// If `id` was registered in another [`World`].
+    #[cfg(feature = "bevy_reflect")]
+    #[inline]
+    pub fn get_dynamic_mut(&mut self, id: DynamicComponentId) -> Option<Mut<'_, dyn Reflect>> {
+        id.assert_world(self.entity.world().id());
+        self.get_mut_by_id(id.component_id())
+            // SAFETY: `id` is a dynamic component of this world
+            .map(|value| unsafe { deref_dynamic_mut(value) })
+    }
 }
 
 impl<'a> From<EntityMut<'a>> for FilteredEntityMut<'a> {
@@ -0,0 +1,204 @@
+use crate::{
+    archetype::ArchetypeId,
+    component::{ComponentId, StorageType},
+    storage::TableId,
+    world::World,
+};
+
+/// A report of the memory taken by the entities and components of a [`World`].
+///
+/// Created by [`World::memory_stats`]. The byte counts only include the component data stored
+/// in the [`World`], not the heap allocations owned by the components themselves, like the
+/// contents of a `Vec` field.
+#[derive(Debug, Clone, Default)]
+pub struct WorldMemoryStats {
+    /// The archetypes of the world, by increasing [`ArchetypeId`].
+    pub archetypes: Vec<ArchetypeMemoryStats>,
+    /// The tables of the world, by increasing [`TableId`].
+    pub tables: Vec<TableMemoryStats>,
+    /// The components stored in the world, by decreasing [`ComponentMemoryStats::allocated_bytes`].
+    pub components: Vec<ComponentMemoryStats>,
+}
+
+/// The entities of an [`Archetype`](crate::archetype::Archetype), see [`WorldMemoryStats`].
+#[derive(Debug, Clone)]
+pub struct ArchetypeMemoryStats {
+    /// The ID of the archetype.
+    pub id: ArchetypeId,
+    /// The table storing the [`StorageType::Table`] components of the archetype.
+    pub table_id: TableId,
+    /// The number of entities in the archetype.
+    pub entity_count: usize,
+    /// The components of the archetype.
+    pub components: Vec<ComponentId>,
+}
+
+/// The memory taken by a [`Table`](crate::storage::Table), see [`WorldMemoryStats`].
+#[derive(Debug, Clone)]
+pub struct TableMemoryStats {
+    /// The ID of the table.
+    pub id: TableId,
+    /// The number of entities stored in the table.
+    pub entity_count: usize,
+    /// The number of entities the table can store without reallocating.
+    pub entity_capacity: usize,
+    /// The number of bytes allocated by the table, including its unused capacity.
+    pub allocated_bytes: usize,
+}
+
+/// The memory taken by a component across all of its storage, see [`WorldMemoryStats`].
+#[derive(Debug, Clone)]
+pub struct ComponentMemoryStats {
+    /// The ID of the component.
+    pub id: ComponentId,
+    /// The name of the component.
+    pub name: String,
+    /// Where the component is stored.
+    pub storage_type: StorageType,
+    /// The number of entities with the component.
+    pub entity_count: usize,
+    /// The number of bytes taken by the component values and their change ticks.
+    pub used_bytes: usize,
+    /// The number of bytes allocated to store the component, including the unused capacity.
+    pub allocated_bytes: usize,
+}
+
+impl WorldMemoryStats {
+    /// The number of bytes allocated by all the tables and sparse sets.
+    pub fn total_allocated_bytes(&self) -> usize {
+        let tables = self.tables.iter().map(|table| table.allocated_bytes);
+        let sparse_sets = self
+            .components
+            .iter()
+            .filter(|component| component.storage_type == StorageType::SparseSet)
+            .map(|component| component.allocated_bytes);
+        tables.chain(sparse_sets).sum()
+    }
+}
+
+impl World {
+    /// Collects the number of entities of each archetype and the memory taken by each table and
+    /// component, to find out what takes up memory in large worlds.
+    ///
+    /// This goes through every archetype, table and sparse set, so it's best not called every
+    /// frame.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Position([f32; 3]);
+    ///
+    /// let mut world = World::new();
+    /// world.spawn_batch((0..100).map(|_| Position([0.0; 3])));
+    ///
+    /// let stats = world.memory_stats();
+    /// let position = &stats.components[0];
+    /// assert_eq!(position.entity_count, 100);
+    /// assert!(position.used_bytes >= 100 * std::mem::size_of::<Position>());
+    /// ```
+    pub fn memory_stats(&self) -> WorldMemoryStats {
+        let archetypes = self
+            .archetypes()
+            .iter()
+            .map(|archetype| ArchetypeMemoryStats {
+                id: archetype.id(),
+                table_id: archetype.table_id(),
+                entity_count: archetype.len(),
+                components: archetype.components().collect(),
+            })
+            .collect();
+
+        let mut components = self
+            .components()
+            .iter()
+            .map(|info| ComponentMemoryStats {
+                id: info.id(),
+                name: info.name().to_string(),
+                storage_type: info.storage_type(),
+                entity_count: 0,
+                used_bytes: 0,
+                allocated_bytes: 0,
+            })
+            .collect::<Vec<_>>();
+
+        let mut tables = Vec::new();
+        for (index, table) in self.storages().tables.iter().enumerate() {
+            tables.push(TableMemoryStats {
+                id: TableId::from_usize(index),
+                entity_count: table.entity_count(),
+                entity_capacity: table.entity_capacity(),
+                allocated_bytes: table.allocated_bytes(),
+            });
+            for (id, column) in table.iter_with_ids() {
+                let stats = &mut components[id.index()];
+                stats.entity_count += column.len();
+                stats.used_bytes += column.used_bytes();
+                stats.allocated_bytes += column.allocated_bytes();
+            }
+        }
+        for (id, sparse_set) in self.storages().sparse_sets.iter() {
+            let stats = &mut components[id.index()];
+            stats.entity_count = sparse_set.len();
+            stats.used_bytes = sparse_set.used_bytes();
+            stats.allocated_bytes = sparse_set.allocated_bytes();
+        }
+
+        // Skip resources, and components that were never stored
+        components.retain(|component| component.allocated_bytes > 0);
+        components.sort_by_key(|component| std::cmp::Reverse(component.allocated_bytes));
+
+        WorldMemoryStats {
+            archetypes,
+            tables,
+            components,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::{component::Component, world::World};
+
+    #[derive(Component)]
+    #[allow(dead_code)]
+    struct A(u64);
+
+    #[derive(Component)]
+    #[allow(dead_code)]
+    #[component(storage = "SparseSet")]
+    struct B(u32);
+
+    #[test]
+    fn memory_stats() {
+        let mut world = World::new();
+        world.spawn_batch((0..10).map(A));
+        world.spawn_batch((0..5).map(|i| (A(i), B(i as u32))));
+
+        let stats = world.memory_stats();
+        let a = stats
+            .components
+            .iter()
+            .find(|component| component.name.ends_with("::A"))
+            .unwrap();
+        assert_eq!(a.entity_count, 15);
+        assert!(a.used_bytes >= 15 * std::mem::size_of::<A>());
+        assert!(a.allocated_bytes >= a.used_bytes);
+
+        let b = stats
+            .components
+            .iter()
+            .find(|component| component.name.ends_with("::B"))
+            .unwrap();
+        assert_eq!(b.entity_count, 5);
+
+        let counts = stats
+            .archetypes
+            .iter()
+            .filter(|archetype| archetype.entity_count > 0)
+            .map(|archetype| archetype.entity_count)
+            .collect::<Vec<_>>();
+        assert_eq!(counts, [10, 5]);
+        assert!(stats.total_allocated_bytes() >= a.used_bytes + b.used_bytes);
+    }
+}
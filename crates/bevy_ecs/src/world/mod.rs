@@ -6,6 +6,7 @@ mod deferred_world;
 mod entity_ref;
 pub mod error;
 mod identifier;
+mod memory_stats;
 mod spawn_batch;
 pub mod unsafe_world_cell;
 
@@ -20,6 +21,7 @@ pub use entity_ref::{
     OccupiedEntry, VacantEntry,
 };
 pub use identifier::WorldId;
+pub use memory_stats::*;
 pub use spawn_batch::*;
 
 use crate::{
@@ -31,6 +33,7 @@ use crate::{
         Components, Tick,
     },
     entity::{AllocAtWithoutReplacement, Entities, Entity, EntityHashSet, EntityLocation},
+    entity_disabling::Disabled,
     event::{Event, EventId, Events, SendBatchIds},
     observer::Observers,
     query::{DebugCheckedUnwrap, QueryData, QueryEntityError, QueryFilter, QueryState},
@@ -165,6 +168,7 @@ impl World {
         assert_eq!(ON_ADD, self.init_component::<OnAdd>());
         assert_eq!(ON_INSERT, self.init_component::<OnInsert>());
         assert_eq!(ON_REMOVE, self.init_component::<OnRemove>());
+        assert_eq!(DISABLED, self.init_component::<Disabled>());
     }
     /// Creates a new empty [`World`].
     ///
@@ -1880,6 +1884,7 @@ impl World {
                 changed: &mut ticks.changed,
                 last_run: last_change_tick,
                 this_run: change_tick,
+                column_tick: None,
             },
         };
         let result = f(self, value_mut);
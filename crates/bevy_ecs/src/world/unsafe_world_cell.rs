@@ -729,7 +729,7 @@ impl<'w> UnsafeEntityCell<'w> {
                 self.entity,
                 self.location,
             )
-            .map(|(value, cells)| Ref {
+            .map(|(value, cells, _)| Ref {
                 // SAFETY: returned component is of type T
                 value: value.deref::<T>(),
                 ticks: Ticks::from_tick_cells(cells, last_change_tick, change_tick),
@@ -828,10 +828,11 @@ impl<'w> UnsafeEntityCell<'w> {
                 self.entity,
                 self.location,
             )
-            .map(|(value, cells)| Mut {
+            .map(|(value, cells, column)| Mut {
                 // SAFETY: returned component is of type T
                 value: value.assert_unique().deref_mut::<T>(),
-                ticks: TicksMut::from_tick_cells(cells, last_change_tick, change_tick),
+                ticks: TicksMut::from_tick_cells(cells, last_change_tick, change_tick)
+                    .with_column(column),
             })
         }
     }
@@ -888,14 +889,15 @@ impl<'w> UnsafeEntityCell<'w> {
                 self.entity,
                 self.location,
             )
-            .map(|(value, cells)| MutUntyped {
+            .map(|(value, cells, column)| MutUntyped {
                 // SAFETY: world access validated by caller and ties world lifetime to `MutUntyped` lifetime
                 value: value.assert_unique(),
                 ticks: TicksMut::from_tick_cells(
                     cells,
                     self.world.last_change_tick(),
                     self.world.change_tick(),
-                ),
+                )
+                .with_column(column),
             })
         }
     }
@@ -957,7 +959,8 @@ unsafe fn get_component(
     }
 }
 
-/// Get an untyped pointer to a particular [`Component`] and its [`ComponentTicks`]
+/// Get an untyped pointer to a particular [`Component`], its [`ComponentTicks`] and the [`Column`]
+/// storing it
 ///
 /// # Safety
 /// - `location` must refer to an archetype that contains `entity`
@@ -972,7 +975,7 @@ unsafe fn get_component_and_ticks(
     storage_type: StorageType,
     entity: Entity,
     location: EntityLocation,
-) -> Option<(Ptr<'_>, TickCells<'_>)> {
+) -> Option<(Ptr<'_>, TickCells<'_>, &Column)> {
     match storage_type {
         StorageType::Table => {
             let components = world.fetch_table(location, component_id)?;
@@ -984,9 +987,14 @@ unsafe fn get_component_and_ticks(
                     added: components.get_added_tick_unchecked(location.table_row),
                     changed: components.get_changed_tick_unchecked(location.table_row),
                 },
+                components,
             ))
         }
-        StorageType::SparseSet => world.fetch_sparse_set(component_id)?.get_with_ticks(entity),
+        StorageType::SparseSet => {
+            let sparse_set = world.fetch_sparse_set(component_id)?;
+            let (value, cells) = sparse_set.get_with_ticks(entity)?;
+            Some((value, cells, sparse_set.column()))
+        }
     }
 }
 
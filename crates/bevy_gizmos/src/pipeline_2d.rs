@@ -7,7 +7,7 @@ use crate::{
 };
 use bevy_app::{App, Plugin};
 use bevy_asset::Handle;
-use bevy_core_pipeline::core_2d::Transparent2d;
+use bevy_core_pipeline::core_2d::{Camera2d, Sort2dKey, Transparent2d, CORE_2D_STENCIL_FORMAT};
 
 use bevy_ecs::{
     prelude::Entity,
@@ -15,7 +15,6 @@ use bevy_ecs::{
     system::{Query, Res, ResMut, Resource},
     world::{FromWorld, World},
 };
-use bevy_math::FloatOrd;
 use bevy_render::{
     render_asset::{prepare_assets, RenderAssets},
     render_phase::{
@@ -139,7 +138,15 @@ impl SpecializedRenderPipeline for LineGizmoPipeline {
             }),
             layout,
             primitive: PrimitiveState::default(),
-            depth_stencil: None,
+            depth_stencil: key.mesh_key.contains(Mesh2dPipelineKey::STENCIL).then_some(
+                DepthStencilState {
+                    format: CORE_2D_STENCIL_FORMAT,
+                    depth_write_enabled: false,
+                    depth_compare: CompareFunction::Always,
+                    stencil: StencilState::default(),
+                    bias: DepthBiasState::default(),
+                },
+            ),
             multisample: MultisampleState {
                 count: key.mesh_key.msaa_samples(),
                 mask: !0,
@@ -224,7 +231,15 @@ impl SpecializedRenderPipeline for LineJointGizmoPipeline {
             }),
             layout,
             primitive: PrimitiveState::default(),
-            depth_stencil: None,
+            depth_stencil: key.mesh_key.contains(Mesh2dPipelineKey::STENCIL).then_some(
+                DepthStencilState {
+                    format: CORE_2D_STENCIL_FORMAT,
+                    depth_write_enabled: false,
+                    depth_compare: CompareFunction::Always,
+                    stencil: StencilState::default(),
+                    bias: DepthBiasState::default(),
+                },
+            ),
             multisample: MultisampleState {
                 count: key.mesh_key.msaa_samples(),
                 mask: !0,
@@ -259,17 +274,25 @@ fn queue_line_gizmos_2d(
     line_gizmos: Query<(Entity, &Handle<LineGizmo>, &GizmoMeshConfig)>,
     line_gizmo_assets: Res<RenderAssets<GpuLineGizmo>>,
     mut transparent_render_phases: ResMut<ViewSortedRenderPhases<Transparent2d>>,
-    mut views: Query<(Entity, &ExtractedView, Option<&RenderLayers>)>,
+    mut views: Query<(
+        Entity,
+        &ExtractedView,
+        Option<&RenderLayers>,
+        Option<&Camera2d>,
+    )>,
 ) {
     let draw_function = draw_functions.read().get_id::<DrawLineGizmo2d>().unwrap();
 
-    for (view_entity, view, render_layers) in &mut views {
+    for (view_entity, view, render_layers, camera_2d) in &mut views {
         let Some(transparent_phase) = transparent_render_phases.get_mut(&view_entity) else {
             continue;
         };
 
-        let mesh_key = Mesh2dPipelineKey::from_msaa_samples(msaa.samples())
+        let mut mesh_key = Mesh2dPipelineKey::from_msaa_samples(msaa.samples())
             | Mesh2dPipelineKey::from_hdr(view.hdr);
+        if camera_2d.is_some_and(|camera_2d| camera_2d.stencil) {
+            mesh_key |= Mesh2dPipelineKey::STENCIL;
+        }
 
         let render_layers = render_layers.unwrap_or_default();
         for (entity, handle, config) in &line_gizmos {
@@ -295,7 +318,7 @@ fn queue_line_gizmos_2d(
                 entity,
                 draw_function,
                 pipeline,
-                sort_key: FloatOrd(f32::INFINITY),
+                sort_key: Sort2dKey::MAX,
                 batch_range: 0..1,
                 extra_index: PhaseItemExtraIndex::NONE,
             });
@@ -313,20 +336,28 @@ fn queue_line_joint_gizmos_2d(
     line_gizmos: Query<(Entity, &Handle<LineGizmo>, &GizmoMeshConfig)>,
     line_gizmo_assets: Res<RenderAssets<GpuLineGizmo>>,
     mut transparent_render_phases: ResMut<ViewSortedRenderPhases<Transparent2d>>,
-    mut views: Query<(Entity, &ExtractedView, Option<&RenderLayers>)>,
+    mut views: Query<(
+        Entity,
+        &ExtractedView,
+        Option<&RenderLayers>,
+        Option<&Camera2d>,
+    )>,
 ) {
     let draw_function = draw_functions
         .read()
         .get_id::<DrawLineJointGizmo2d>()
         .unwrap();
 
-    for (view_entity, view, render_layers) in &mut views {
+    for (view_entity, view, render_layers, camera_2d) in &mut views {
         let Some(transparent_phase) = transparent_render_phases.get_mut(&view_entity) else {
             continue;
         };
 
-        let mesh_key = Mesh2dPipelineKey::from_msaa_samples(msaa.samples())
+        let mut mesh_key = Mesh2dPipelineKey::from_msaa_samples(msaa.samples())
             | Mesh2dPipelineKey::from_hdr(view.hdr);
+        if camera_2d.is_some_and(|camera_2d| camera_2d.stencil) {
+            mesh_key |= Mesh2dPipelineKey::STENCIL;
+        }
 
         let render_layers = render_layers.unwrap_or_default();
         for (entity, handle, config) in &line_gizmos {
@@ -354,7 +385,7 @@ fn queue_line_joint_gizmos_2d(
                 entity,
                 draw_function,
                 pipeline,
-                sort_key: FloatOrd(f32::INFINITY),
+                sort_key: Sort2dKey::MAX,
                 batch_range: 0..1,
                 extra_index: PhaseItemExtraIndex::NONE,
             });
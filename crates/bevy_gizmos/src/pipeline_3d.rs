@@ -8,7 +8,9 @@ use crate::{
 use bevy_app::{App, Plugin};
 use bevy_asset::Handle;
 use bevy_core_pipeline::{
-    core_3d::{Transparent3d, CORE_3D_DEPTH_FORMAT},
+    core_3d::{
+        Transparent3d, ViewDepthStencil, CORE_3D_DEPTH_FORMAT, CORE_3D_DEPTH_STENCIL_FORMAT,
+    },
     prepass::{DeferredPrepass, DepthPrepass, MotionVectorPrepass, NormalPrepass},
 };
 
@@ -146,7 +148,11 @@ impl SpecializedRenderPipeline for LineGizmoPipeline {
             layout,
             primitive: PrimitiveState::default(),
             depth_stencil: Some(DepthStencilState {
-                format: CORE_3D_DEPTH_FORMAT,
+                format: if key.view_key.contains(MeshPipelineKey::DEPTH_STENCIL) {
+                    CORE_3D_DEPTH_STENCIL_FORMAT
+                } else {
+                    CORE_3D_DEPTH_FORMAT
+                },
                 depth_write_enabled: true,
                 depth_compare: CompareFunction::Greater,
                 stencil: StencilState::default(),
@@ -244,7 +250,11 @@ impl SpecializedRenderPipeline for LineJointGizmoPipeline {
             layout,
             primitive: PrimitiveState::default(),
             depth_stencil: Some(DepthStencilState {
-                format: CORE_3D_DEPTH_FORMAT,
+                format: if key.view_key.contains(MeshPipelineKey::DEPTH_STENCIL) {
+                    CORE_3D_DEPTH_STENCIL_FORMAT
+                } else {
+                    CORE_3D_DEPTH_FORMAT
+                },
                 depth_write_enabled: true,
                 depth_compare: CompareFunction::Greater,
                 stencil: StencilState::default(),
@@ -294,6 +304,7 @@ fn queue_line_gizmos_3d(
             Has<MotionVectorPrepass>,
             Has<DeferredPrepass>,
         ),
+        Has<ViewDepthStencil>,
     )>,
 ) {
     let draw_function = draw_functions.read().get_id::<DrawLineGizmo3d>().unwrap();
@@ -303,6 +314,7 @@ fn queue_line_gizmos_3d(
         view,
         render_layers,
         (normal_prepass, depth_prepass, motion_vector_prepass, deferred_prepass),
+        depth_stencil,
     ) in &mut views
     {
         let Some(transparent_phase) = transparent_render_phases.get_mut(&view_entity) else {
@@ -330,6 +342,10 @@ fn queue_line_gizmos_3d(
             view_key |= MeshPipelineKey::DEFERRED_PREPASS;
         }
 
+        if depth_stencil {
+            view_key |= MeshPipelineKey::DEPTH_STENCIL;
+        }
+
         for (entity, handle, config) in &line_gizmos {
             if !config.render_layers.intersects(render_layers) {
                 continue;
@@ -382,6 +398,7 @@ fn queue_line_joint_gizmos_3d(
             Has<MotionVectorPrepass>,
             Has<DeferredPrepass>,
         ),
+        Has<ViewDepthStencil>,
     )>,
 ) {
     let draw_function = draw_functions
@@ -394,6 +411,7 @@ fn queue_line_joint_gizmos_3d(
         view,
         render_layers,
         (normal_prepass, depth_prepass, motion_vector_prepass, deferred_prepass),
+        depth_stencil,
     ) in &mut views
     {
         let Some(transparent_phase) = transparent_render_phases.get_mut(&view_entity) else {
@@ -421,6 +439,10 @@ fn queue_line_joint_gizmos_3d(
             view_key |= MeshPipelineKey::DEFERRED_PREPASS;
         }
 
+        if depth_stencil {
+            view_key |= MeshPipelineKey::DEPTH_STENCIL;
+        }
+
         for (entity, handle, config) in &line_gizmos {
             if !config.render_layers.intersects(render_layers) {
                 continue;
@@ -1,5 +1,5 @@
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
-#![forbid(unsafe_code)]
+#![deny(unsafe_code)]
 #![doc(
     html_logo_url = "https://bevyengine.org/assets/icon.png",
     html_favicon_url = "https://bevyengine.org/assets/icon.png"
@@ -94,14 +94,31 @@
 //! Be careful when using this feature, if you misspell a label it will simply ignore it without warning.
 //!
 //! You can use [`GltfAssetLabel`] to ensure you are using the correct label.
+//!
+//! # Mesh compression
+//!
+//! With the `meshopt_compression` feature, buffers compressed with the `EXT_meshopt_compression`
+//! extension, as written by `gltfpack`, are decoded while the file is loaded. Meshes compressed
+//! with `KHR_draco_mesh_compression` are not supported, and only load when the file also contains
+//! their uncompressed data.
+//!
+//! # Levels of detail
+//!
+//! The loader can generate simplified levels of detail for every mesh primitive, configured
+//! through [`GltfLoaderSettings::lods`], usually in the `.meta` file of the asset. They're
+//! generated when the file is loaded rather than by an asset processor.
 
 #[cfg(feature = "bevy_animation")]
 use bevy_animation::AnimationClip;
 use bevy_utils::HashMap;
 
 mod loader;
+mod lod;
+#[cfg(feature = "meshopt_compression")]
+mod meshopt_compression;
 mod vertex_attributes;
 pub use loader::*;
+pub use lod::GltfLodSettings;
 
 use bevy_app::prelude::*;
 use bevy_asset::{Asset, AssetApp, AssetPath, Handle};
@@ -112,6 +129,7 @@ use bevy_render::{
     mesh::{Mesh, MeshVertexAttribute},
     renderer::RenderDevice,
     texture::CompressedImageFormats,
+    view::VisibilityRange,
 };
 use bevy_scene::Scene;
 
@@ -308,6 +326,28 @@ pub struct GltfPrimitive {
     pub extras: Option<GltfExtras>,
     /// Additional data of the `material`.
     pub material_extras: Option<GltfExtras>,
+    /// Simplified versions of `mesh`, from the most to the least detailed.
+    ///
+    /// Only generated when [`GltfLoaderSettings::lods`] isn't empty.
+    pub lods: Vec<GltfPrimitiveLod>,
+    /// The range of camera distances in which `mesh` should be rendered when [`Self::lods`]
+    /// isn't empty, see [`GltfPrimitiveLod::visibility_range`].
+    pub visibility_range: Option<VisibilityRange>,
+}
+
+/// A simplified version of the mesh of a [`GltfPrimitive`], generated according to
+/// one of the [`GltfLodSettings`] of the loader.
+#[derive(Debug, Clone)]
+pub struct GltfPrimitiveLod {
+    /// The simplified mesh.
+    pub mesh: Handle<Mesh>,
+    /// The deviation of the simplified mesh from the original one, relative to the size of the mesh.
+    pub error: f32,
+    /// The range of camera distances in which this level should be rendered.
+    ///
+    /// Scenes loaded with levels of detail spawn an entity for every level, each with its
+    /// [`VisibilityRange`], so that only one of them is visible at a time.
+    pub visibility_range: VisibilityRange,
 }
 
 impl GltfPrimitive {
@@ -335,6 +375,8 @@ impl GltfPrimitive {
             material,
             extras,
             material_extras,
+            lods: Vec::new(),
+            visibility_range: None,
         }
     }
 
@@ -429,6 +471,16 @@ pub enum GltfAssetLabel {
         /// Index of this primitive in its parent mesh
         primitive: usize,
     },
+    /// `Mesh{}/Primitive{}/Lod{}`: Simplified level of detail of a glTF Primitive as a Bevy `Mesh`,
+    /// see [`GltfLoaderSettings::lods`]
+    PrimitiveLod {
+        /// Index of the mesh for this primitive
+        mesh: usize,
+        /// Index of this primitive in its parent mesh
+        primitive: usize,
+        /// Index of the level of detail, starting at 1 for the first simplified level
+        lod: usize,
+    },
     /// `Mesh{}/Primitive{}/MorphTargets`: Morph target animation data for a glTF Primitive
     MorphTarget {
         /// Index of the mesh for this primitive
@@ -462,6 +514,11 @@ impl std::fmt::Display for GltfAssetLabel {
             GltfAssetLabel::Primitive { mesh, primitive } => {
                 f.write_str(&format!("Mesh{mesh}/Primitive{primitive}"))
             }
+            GltfAssetLabel::PrimitiveLod {
+                mesh,
+                primitive,
+                lod,
+            } => f.write_str(&format!("Mesh{mesh}/Primitive{primitive}/Lod{lod}")),
             GltfAssetLabel::MorphTarget { mesh, primitive } => {
                 f.write_str(&format!("Mesh{mesh}/Primitive{primitive}/MorphTargets"))
             }
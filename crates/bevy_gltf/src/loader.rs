@@ -1,6 +1,8 @@
+#[cfg(feature = "meshopt_compression")]
+use crate::meshopt_compression;
 use crate::{
-    vertex_attributes::convert_attribute, Gltf, GltfAssetLabel, GltfExtras, GltfMaterialExtras,
-    GltfMeshExtras, GltfNode, GltfSceneExtras,
+    lod::lod_visibility_range, vertex_attributes::convert_attribute, Gltf, GltfAssetLabel,
+    GltfExtras, GltfLodSettings, GltfMaterialExtras, GltfMeshExtras, GltfNode, GltfSceneExtras,
 };
 
 #[cfg(feature = "bevy_animation")]
@@ -49,7 +51,7 @@ use gltf::{
     texture::{Info, MagFilter, MinFilter, TextureTransform, WrappingMode},
     Material, Node, Primitive, Semantic,
 };
-use gltf::{json, Document};
+use gltf::{json, json::validation::Validate, Document};
 use serde::{Deserialize, Serialize};
 use serde_json::{value, Value};
 #[cfg(feature = "bevy_animation")]
@@ -109,6 +111,17 @@ pub enum GltfError {
     /// Failed to load a file.
     #[error("failed to load file: {0}")]
     Io(#[from] std::io::Error),
+    /// Failed to decode a buffer view compressed with `EXT_meshopt_compression`.
+    #[error("failed to decode compressed buffer view {0}: {1}")]
+    MeshoptDecode(usize, &'static str),
+    /// The file requires an extension that the loader doesn't support.
+    ///
+    /// Meshes compressed with `KHR_draco_mesh_compression` are only loaded when the file also
+    /// contains uncompressed data for them, files that require it must be exported without Draco
+    /// compression, or with `EXT_meshopt_compression` instead. `EXT_meshopt_compression` requires
+    /// the `meshopt_compression` feature.
+    #[error("unsupported required extension: {0}")]
+    UnsupportedExtension(String),
 }
 
 /// Loads glTF files with all of their data as their corresponding bevy representations.
@@ -156,6 +169,18 @@ pub struct GltfLoaderSettings {
     pub load_lights: bool,
     /// If true, the loader will include the root of the gltf root node.
     pub include_source: bool,
+    /// Levels of detail to generate for every mesh primitive, from the most to the least detailed.
+    ///
+    /// The simplified meshes are added as [`GltfAssetLabel::PrimitiveLod`] labeled assets and listed
+    /// in [`GltfPrimitive::lods`](crate::GltfPrimitive::lods). Scenes spawn an entity for every
+    /// level of a primitive, using a [`VisibilityRange`](bevy_render::view::VisibilityRange) to
+    /// switch between them with the distance to the camera.
+    ///
+    /// Generating levels of detail requires the `mesh_lod` feature, and is fairly slow. The levels
+    /// are generated every time the file is loaded, there is no asset processor writing them ahead
+    /// of time since glTF files can't be saved back yet. Set this in the `.meta` file of the assets
+    /// that need levels of detail rather than for all the glTF files of an app.
+    pub lods: Vec<GltfLodSettings>,
 }
 
 impl Default for GltfLoaderSettings {
@@ -166,6 +191,7 @@ impl Default for GltfLoaderSettings {
             load_cameras: true,
             load_lights: true,
             include_source: false,
+            lods: Vec::new(),
         }
     }
 }
@@ -190,6 +216,53 @@ impl AssetLoader for GltfLoader {
     }
 }
 
+/// Required extensions that are implemented by the loader rather than by the `gltf` crate.
+const LOADER_EXTENSIONS: &[&str] = &[
+    #[cfg(feature = "meshopt_compression")]
+    meshopt_compression::EXTENSION_NAME,
+];
+
+/// Required extensions that are rejected with [`GltfError::UnsupportedExtension`].
+const UNSUPPORTED_EXTENSIONS: &[&str] = &[
+    "KHR_draco_mesh_compression",
+    #[cfg(not(feature = "meshopt_compression"))]
+    "EXT_meshopt_compression",
+];
+
+/// Parses and validates a glTF file, accepting the required extensions in [`LOADER_EXTENSIONS`].
+fn parse_gltf(bytes: &[u8]) -> Result<gltf::Gltf, GltfError> {
+    let gltf = gltf::Gltf::from_slice_without_validation(bytes)?;
+    let root = gltf.document.as_json();
+    if let Some(extension) = root
+        .extensions_required
+        .iter()
+        .find(|extension| UNSUPPORTED_EXTENSIONS.contains(&extension.as_str()))
+    {
+        return Err(GltfError::UnsupportedExtension(extension.clone()));
+    }
+
+    let mut errors = Vec::new();
+    root.validate(root, json::Path::new, &mut |path, error| {
+        let path = path();
+        let handled = matches!(error, json::validation::Error::Unsupported)
+            && root
+                .extensions_required
+                .iter()
+                .enumerate()
+                .any(|(index, extension)| {
+                    LOADER_EXTENSIONS.contains(&extension.as_str())
+                        && path.as_str() == format!("extensionsRequired[{index}]")
+                });
+        if !handled {
+            errors.push((path, error));
+        }
+    });
+    if !errors.is_empty() {
+        return Err(gltf::Error::Validation(errors).into());
+    }
+    Ok(gltf)
+}
+
 /// Loads an entire glTF file.
 async fn load_gltf<'a, 'b, 'c>(
     loader: &GltfLoader,
@@ -197,7 +270,7 @@ async fn load_gltf<'a, 'b, 'c>(
     load_context: &'b mut LoadContext<'c>,
     settings: &'b GltfLoaderSettings,
 ) -> Result<Gltf, GltfError> {
-    let gltf = gltf::Gltf::from_slice(bytes)?;
+    let gltf = parse_gltf(bytes)?;
     let file_name = load_context
         .asset_path()
         .path()
@@ -209,6 +282,14 @@ async fn load_gltf<'a, 'b, 'c>(
         .to_string();
     let buffer_data = load_buffers(&gltf, load_context).await?;
 
+    #[cfg(not(feature = "mesh_lod"))]
+    if !settings.lods.is_empty() {
+        warn!(
+            "Ignoring the levels of detail requested for {}, generating them requires the `mesh_lod` feature",
+            file_name
+        );
+    }
+
     let mut linear_textures = HashSet::default();
 
     for material in gltf.materials() {
@@ -328,6 +409,21 @@ async fn load_gltf<'a, 'b, 'c>(
                     );
                 }
             }
+            // Events placed on the timeline of the animation are read from its
+            // extras, as glTF has no standard way to store them.
+            let extras = animation.extras().as_ref();
+            if let Some(extras) = extras
+                .and_then(|extras| serde_json::from_str::<AnimationEventExtras>(extras.get()).ok())
+            {
+                for event in extras.events {
+                    let payload = match event.payload {
+                        Value::Null => String::new(),
+                        Value::String(payload) => payload,
+                        payload => payload.to_string(),
+                    };
+                    animation_clip.add_event(event.time, event.name, payload);
+                }
+            }
             let handle = load_context.add_labeled_asset(
                 GltfAssetLabel::Animation(animation.index()).to_string(),
                 animation_clip,
@@ -562,8 +658,31 @@ async fn load_gltf<'a, 'b, 'c>(
                 });
             }
 
+            #[cfg(feature = "mesh_lod")]
+            let lods = {
+                let _generate_lods_span = info_span!("generate_lods", name = file_name).entered();
+                crate::lod::generate_lods(&mesh, &settings.lods)
+                    .into_iter()
+                    .zip(1..)
+                    .map(|((lod_mesh, error), lod)| {
+                        let lod_label = GltfAssetLabel::PrimitiveLod {
+                            mesh: gltf_mesh.index(),
+                            primitive: primitive.index(),
+                            lod,
+                        };
+                        super::GltfPrimitiveLod {
+                            mesh: load_context.add_labeled_asset(lod_label.to_string(), lod_mesh),
+                            error,
+                            visibility_range: lod_visibility_range(&settings.lods, lod),
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            };
+            #[cfg(not(feature = "mesh_lod"))]
+            let lods = Vec::new();
+
             let mesh_handle = load_context.add_labeled_asset(primitive_label.to_string(), mesh);
-            primitives.push(super::GltfPrimitive::new(
+            let mut gltf_primitive = super::GltfPrimitive::new(
                 &gltf_mesh,
                 &primitive,
                 mesh_handle,
@@ -573,7 +692,12 @@ async fn load_gltf<'a, 'b, 'c>(
                     .and_then(|i| materials.get(i).cloned()),
                 get_gltf_extras(primitive.extras()),
                 get_gltf_extras(primitive.material().extras()),
-            ));
+            );
+            if !lods.is_empty() {
+                gltf_primitive.visibility_range = Some(lod_visibility_range(&settings.lods, 0));
+            }
+            gltf_primitive.lods = lods;
+            primitives.push(gltf_primitive);
         }
 
         let mesh =
@@ -722,6 +846,7 @@ async fn load_gltf<'a, 'b, 'c>(
             entity.insert(SkinnedMesh {
                 inverse_bindposes: skinned_mesh_inverse_bindposes[skin_index].clone(),
                 joints: joint_entities,
+                ..Default::default()
             });
         }
         let loaded_scene = scene_load_context.finish(Scene::new(world), None);
@@ -1299,59 +1424,88 @@ fn load_node(
                     };
                     let bounds = primitive.bounding_box();
 
-                    let mut mesh_entity = parent.spawn(PbrBundle {
-                        // TODO: handle missing label handle errors here?
-                        mesh: load_context.get_label_handle(primitive_label.to_string()),
-                        material: load_context.get_label_handle(&material_label),
-                        ..Default::default()
-                    });
+                    // Every generated level of detail is spawned next to the primitive, only one
+                    // of them is visible at a time thanks to their visibility ranges.
+                    let mesh_labels = std::iter::once(primitive_label.to_string())
+                        .chain(
+                            (1..=settings.lods.len())
+                                .map(|lod| {
+                                    GltfAssetLabel::PrimitiveLod {
+                                        mesh: mesh.index(),
+                                        primitive: primitive.index(),
+                                        lod,
+                                    }
+                                    .to_string()
+                                })
+                                .take_while(|label| root_load_context.has_labeled_asset(label)),
+                        )
+                        .collect::<Vec<_>>();
 
-                    let target_count = primitive.morph_targets().len();
-                    if target_count != 0 {
-                        let weights = match mesh.weights() {
-                            Some(weights) => weights.to_vec(),
-                            None => vec![0.0; target_count],
-                        };
+                    for (lod, mesh_label) in mesh_labels.iter().enumerate() {
+                        let mut mesh_entity = parent.spawn(PbrBundle {
+                            // TODO: handle missing label handle errors here?
+                            mesh: load_context.get_label_handle(mesh_label.clone()),
+                            material: load_context.get_label_handle(&material_label),
+                            ..Default::default()
+                        });
 
-                        if morph_weights.is_none() {
-                            morph_weights = Some(weights.clone());
+                        let target_count = primitive.morph_targets().len();
+                        if target_count != 0 {
+                            let weights = match mesh.weights() {
+                                Some(weights) => weights.to_vec(),
+                                None => vec![0.0; target_count],
+                            };
+
+                            if morph_weights.is_none() {
+                                morph_weights = Some(weights.clone());
+                            }
+
+                            // unwrap: the parent's call to `MeshMorphWeights::new`
+                            // means this code doesn't run if it returns an `Err`.
+                            // According to https://registry.khronos.org/glTF/specs/2.0/glTF-2.0.html#morph-targets
+                            // they should all have the same length.
+                            // > All morph target accessors MUST have the same count as
+                            // > the accessors of the original primitive.
+                            mesh_entity.insert(MeshMorphWeights::new(weights).unwrap());
                         }
+                        mesh_entity.insert(Aabb::from_min_max(
+                            Vec3::from_slice(&bounds.min),
+                            Vec3::from_slice(&bounds.max),
+                        ));
 
-                        // unwrap: the parent's call to `MeshMorphWeights::new`
-                        // means this code doesn't run if it returns an `Err`.
-                        // According to https://registry.khronos.org/glTF/specs/2.0/glTF-2.0.html#morph-targets
-                        // they should all have the same length.
-                        // > All morph target accessors MUST have the same count as
-                        // > the accessors of the original primitive.
-                        mesh_entity.insert(MeshMorphWeights::new(weights).unwrap());
-                    }
-                    mesh_entity.insert(Aabb::from_min_max(
-                        Vec3::from_slice(&bounds.min),
-                        Vec3::from_slice(&bounds.max),
-                    ));
-
-                    if let Some(extras) = primitive.extras() {
-                        mesh_entity.insert(GltfExtras {
-                            value: extras.get().to_string(),
-                        });
-                    }
+                        if let Some(extras) = primitive.extras() {
+                            mesh_entity.insert(GltfExtras {
+                                value: extras.get().to_string(),
+                            });
+                        }
 
-                    if let Some(extras) = mesh.extras() {
-                        mesh_entity.insert(GltfMeshExtras {
-                            value: extras.get().to_string(),
-                        });
-                    }
+                        if let Some(extras) = mesh.extras() {
+                            mesh_entity.insert(GltfMeshExtras {
+                                value: extras.get().to_string(),
+                            });
+                        }
 
-                    if let Some(extras) = material.extras() {
-                        mesh_entity.insert(GltfMaterialExtras {
-                            value: extras.get().to_string(),
-                        });
-                    }
+                        if let Some(extras) = material.extras() {
+                            mesh_entity.insert(GltfMaterialExtras {
+                                value: extras.get().to_string(),
+                            });
+                        }
 
-                    mesh_entity.insert(Name::new(primitive_name(&mesh, &primitive)));
-                    // Mark for adding skinned mesh
-                    if let Some(skin) = gltf_node.skin() {
-                        entity_to_skin_index_map.insert(mesh_entity.id(), skin.index());
+                        if lod == 0 {
+                            mesh_entity.insert(Name::new(primitive_name(&mesh, &primitive)));
+                        } else {
+                            mesh_entity.insert(Name::new(format!(
+                                "{} (LOD{lod})",
+                                primitive_name(&mesh, &primitive)
+                            )));
+                        }
+                        if mesh_labels.len() > 1 {
+                            mesh_entity.insert(lod_visibility_range(&settings.lods, lod));
+                        }
+                        // Mark for adding skinned mesh
+                        if let Some(skin) = gltf_node.skin() {
+                            entity_to_skin_index_map.insert(mesh_entity.id(), skin.index());
+                        }
                     }
                 }
             }
@@ -1633,6 +1787,12 @@ async fn load_buffers(
 
     let mut buffer_data = Vec::new();
     for buffer in gltf.buffers() {
+        #[cfg(feature = "meshopt_compression")]
+        if meshopt_compression::is_fallback_buffer(&buffer) {
+            // Only holds the data of compressed buffer views, decoded below.
+            buffer_data.push(vec![0; buffer.length()]);
+            continue;
+        }
         match buffer.source() {
             gltf::buffer::Source::Uri(uri) => {
                 let uri = percent_encoding::percent_decode_str(uri)
@@ -1662,6 +1822,9 @@ async fn load_buffers(
         }
     }
 
+    #[cfg(feature = "meshopt_compression")]
+    meshopt_compression::decode_buffer_views(gltf, &mut buffer_data)?;
+
     Ok(buffer_data)
 }
 
@@ -1799,6 +1962,23 @@ struct MorphTargetNames {
     pub target_names: Vec<String>,
 }
 
+/// The events of an animation, stored in its extras as
+/// `{ "events": [{ "time": 0.5, "name": "footstep", "payload": ... }] }`.
+#[cfg(feature = "bevy_animation")]
+#[derive(Deserialize)]
+struct AnimationEventExtras {
+    events: Vec<AnimationEventExtra>,
+}
+
+#[cfg(feature = "bevy_animation")]
+#[derive(Deserialize)]
+struct AnimationEventExtra {
+    time: f32,
+    name: String,
+    #[serde(default)]
+    payload: Value,
+}
+
 // A helper structure for `load_node` that contains information about the
 // nearest ancestor animation root.
 #[cfg(feature = "bevy_animation")]
@@ -0,0 +1,152 @@
+//! Generation of simplified levels of detail for the meshes of glTF files.
+
+use bevy_render::view::VisibilityRange;
+#[cfg(feature = "mesh_lod")]
+use bevy_render::{
+    mesh::{Indices, Mesh, VertexAttributeValues},
+    render_resource::PrimitiveTopology,
+};
+#[cfg(feature = "mesh_lod")]
+use bevy_utils::tracing::warn;
+#[cfg(feature = "mesh_lod")]
+use meshopt::{simplify, typed_to_bytes, SimplifyOptions, VertexDataAdapter};
+use serde::{Deserialize, Serialize};
+
+/// A level of detail to generate for every mesh primitive, see [`GltfLoaderSettings::lods`](crate::GltfLoaderSettings::lods).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct GltfLodSettings {
+    /// The fraction of the triangles of the original primitive to keep, between `0.0` and `1.0`.
+    pub ratio: f32,
+    /// The maximum deviation from the original primitive, relative to the size of the primitive.
+    ///
+    /// Simplification stops before reaching [`ratio`](Self::ratio) if it would exceed this error,
+    /// for example `0.01` allows the surface to move by 1% of the size of the primitive.
+    pub max_error: f32,
+    /// The distance from the camera, in world units, from which this level replaces the previous one.
+    pub distance: f32,
+}
+
+/// Returns the range of camera distances in which `level` of a primitive is rendered, `0` being
+/// the original primitive and the next levels being the ones described by `lods`.
+pub(crate) fn lod_visibility_range(lods: &[GltfLodSettings], level: usize) -> VisibilityRange {
+    let start = level
+        .checked_sub(1)
+        .and_then(|index| lods.get(index))
+        .map_or(0.0, |lod| lod.distance);
+    let end = lods.get(level).map_or(f32::INFINITY, |lod| lod.distance);
+    VisibilityRange::abrupt(start, end)
+}
+
+/// Simplifies `mesh` into each of the levels of `lods`, returning the simplified meshes and
+/// their actual error, relative to the size of the mesh.
+///
+/// Only the indices are simplified, so that morph targets and skinning keep working: each level is
+/// a copy of `mesh` with all of its vertices and different indices, which costs as much memory as
+/// the original vertices for every level. Returns no levels for meshes that aren't triangle lists
+/// or that don't have [`Mesh::ATTRIBUTE_POSITION`] as [`VertexAttributeValues::Float32x3`].
+#[cfg(feature = "mesh_lod")]
+pub(crate) fn generate_lods(mesh: &Mesh, lods: &[GltfLodSettings]) -> Vec<(Mesh, f32)> {
+    if lods.is_empty() || mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return Vec::new();
+    }
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        warn!("Failed to generate levels of detail: the mesh has no `Float32x3` positions");
+        return Vec::new();
+    };
+    let indices: Vec<u32> = match mesh.indices() {
+        Some(indices) => indices.iter().map(|index| index as u32).collect(),
+        None => (0..positions.len() as u32).collect(),
+    };
+    let vertices = match VertexDataAdapter::new(
+        typed_to_bytes(positions),
+        std::mem::size_of::<[f32; 3]>(),
+        0,
+    ) {
+        Ok(vertices) => vertices,
+        Err(err) => {
+            warn!("Failed to generate levels of detail: {err}");
+            return Vec::new();
+        }
+    };
+
+    lods.iter()
+        .map(|lod| {
+            let target_count = (indices.len() as f32 * lod.ratio.clamp(0.0, 1.0)) as usize;
+            let mut error = 0.0;
+            let simplified = simplify(
+                &indices,
+                &vertices,
+                target_count / 3 * 3,
+                lod.max_error,
+                SimplifyOptions::LockBorder,
+                Some(&mut error),
+            );
+            let mut lod_mesh = mesh.clone();
+            lod_mesh.insert_indices(if mesh.count_vertices() <= u16::MAX as usize {
+                Indices::U16(simplified.into_iter().map(|index| index as u16).collect())
+            } else {
+                Indices::U32(simplified)
+            });
+            (lod_mesh, error)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lod_visibility_range, GltfLodSettings};
+    use bevy_render::view::VisibilityRange;
+
+    #[test]
+    fn lod_visibility_ranges_are_contiguous() {
+        let lod = |distance| GltfLodSettings {
+            ratio: 0.5,
+            max_error: 0.01,
+            distance,
+        };
+        let lods = [lod(10.0), lod(40.0)];
+        assert_eq!(
+            lod_visibility_range(&lods, 0),
+            VisibilityRange::abrupt(0.0, 10.0)
+        );
+        assert_eq!(
+            lod_visibility_range(&lods, 1),
+            VisibilityRange::abrupt(10.0, 40.0)
+        );
+        assert_eq!(
+            lod_visibility_range(&lods, 2),
+            VisibilityRange::abrupt(40.0, f32::INFINITY)
+        );
+    }
+
+    #[cfg(feature = "mesh_lod")]
+    #[test]
+    fn generate_simplified_levels() {
+        use super::generate_lods;
+        use bevy_math::primitives::Plane3d;
+        use bevy_render::mesh::{Mesh, MeshBuilder, Meshable};
+
+        let mesh = Plane3d::default().mesh().subdivisions(30).build();
+        let lod = GltfLodSettings {
+            ratio: 0.1,
+            max_error: 0.01,
+            distance: 10.0,
+        };
+        let lods = generate_lods(&mesh, &[lod]);
+        assert_eq!(lods.len(), 1);
+        let (lod_mesh, error) = &lods[0];
+        assert!(lod_mesh.indices().unwrap().len() < mesh.indices().unwrap().len() / 2);
+        assert!(*error <= 0.01);
+        assert_eq!(
+            lod_mesh
+                .attribute(Mesh::ATTRIBUTE_POSITION)
+                .unwrap()
+                .as_float3(),
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+                .unwrap()
+                .as_float3()
+        );
+    }
+}
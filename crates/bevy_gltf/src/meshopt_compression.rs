@@ -0,0 +1,456 @@
+//! Decoding of buffer views compressed with the `EXT_meshopt_compression` extension, with the
+//! vertex and index decoders of [`meshopt`].
+//!
+//! <https://github.com/KhronosGroup/glTF/blob/main/extensions/2.0/Vendor/EXT_meshopt_compression/README.md>
+
+#![allow(unsafe_code)]
+
+use meshopt::ffi;
+use serde::Deserialize;
+
+use crate::GltfError;
+
+/// The name of the extension, as it appears in glTF files.
+pub(crate) const EXTENSION_NAME: &str = "EXT_meshopt_compression";
+
+/// The `EXT_meshopt_compression` object of a buffer view.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CompressedView {
+    buffer: usize,
+    #[serde(default)]
+    byte_offset: usize,
+    byte_length: usize,
+    byte_stride: usize,
+    count: usize,
+    mode: CompressionMode,
+    #[serde(default)]
+    filter: CompressionFilter,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+enum CompressionMode {
+    Attributes,
+    Triangles,
+    Indices,
+}
+
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+enum CompressionFilter {
+    #[default]
+    None,
+    Octahedral,
+    Quaternion,
+    Exponential,
+}
+
+/// Returns `true` if `buffer` only exists to hold the data decoded from compressed buffer views.
+///
+/// Such buffers usually have no `uri`, and must not be loaded even when they have one.
+pub(crate) fn is_fallback_buffer(buffer: &gltf::Buffer) -> bool {
+    buffer
+        .extensions()
+        .and_then(|extensions| extensions.get(EXTENSION_NAME))
+        .and_then(|extension| extension.get("fallback"))
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false)
+}
+
+/// Decodes every buffer view that has compressed data into the range of its buffer it describes,
+/// so that accessors can read it as if it was stored uncompressed.
+pub(crate) fn decode_buffer_views(
+    gltf: &gltf::Gltf,
+    buffer_data: &mut [Vec<u8>],
+) -> Result<(), GltfError> {
+    for view in gltf.views() {
+        let Some(extension) = view
+            .extensions()
+            .and_then(|extensions| extensions.get(EXTENSION_NAME))
+        else {
+            continue;
+        };
+        let error = |reason| GltfError::MeshoptDecode(view.index(), reason);
+
+        let compressed: CompressedView = serde_json::from_value(extension.clone())
+            .map_err(|_| error("invalid extension object"))?;
+        // The decoded data fills the buffer view, which bounds the size written by the decoders
+        // by the size of the buffer it's in.
+        if compressed.count.checked_mul(compressed.byte_stride) != Some(view.length()) {
+            return Err(error("the decoded size doesn't match the buffer view"));
+        }
+        let source = compressed
+            .byte_offset
+            .checked_add(compressed.byte_length)
+            .and_then(|end| {
+                buffer_data
+                    .get(compressed.buffer)?
+                    .get(compressed.byte_offset..end)
+            })
+            .ok_or(error("compressed data is out of the bounds of its buffer"))?
+            // The compressed and decoded data may be in the same buffer
+            .to_vec();
+
+        let target = view
+            .offset()
+            .checked_add(view.length())
+            .and_then(|end| {
+                buffer_data
+                    .get_mut(view.buffer().index())?
+                    .get_mut(view.offset()..end)
+            })
+            .ok_or(error("decoded data is out of the bounds of its buffer"))?;
+        decode(
+            target,
+            &source,
+            compressed.count,
+            compressed.byte_stride,
+            compressed.mode,
+            compressed.filter,
+        )
+        .map_err(error)?;
+    }
+    Ok(())
+}
+
+/// Decodes `count` elements of `stride` bytes from `data` into `target`.
+///
+/// The decoders assert their arguments, which are checked first so a malformed file can't abort
+/// the app.
+fn decode(
+    target: &mut [u8],
+    data: &[u8],
+    count: usize,
+    stride: usize,
+    mode: CompressionMode,
+    filter: CompressionFilter,
+) -> Result<(), &'static str> {
+    debug_assert_eq!(target.len(), count * stride);
+    let result = match mode {
+        CompressionMode::Attributes => {
+            if stride == 0 || stride > 256 || stride % 4 != 0 {
+                return Err("invalid stride for attributes");
+            }
+            let valid_filter = match filter {
+                CompressionFilter::None | CompressionFilter::Exponential => true,
+                CompressionFilter::Octahedral => stride == 4 || stride == 8,
+                CompressionFilter::Quaternion => stride == 8,
+            };
+            if !valid_filter {
+                return Err("invalid stride for the filter");
+            }
+            // SAFETY: `target` holds `count` vertices of `stride` bytes, and `data` is read up to
+            // its length.
+            let result = unsafe {
+                ffi::meshopt_decodeVertexBuffer(
+                    target.as_mut_ptr().cast(),
+                    count,
+                    stride,
+                    data.as_ptr(),
+                    data.len(),
+                )
+            };
+            if result == 0 {
+                apply_filter(target, stride, filter);
+            }
+            result
+        }
+        CompressionMode::Triangles => {
+            if (stride != 2 && stride != 4) || count % 3 != 0 {
+                return Err("invalid stride or count for triangles");
+            }
+            // SAFETY: `target` holds `count` indices of `stride` bytes, and `data` is read up to
+            // its length.
+            unsafe {
+                ffi::meshopt_decodeIndexBuffer(
+                    target.as_mut_ptr().cast(),
+                    count,
+                    stride,
+                    data.as_ptr(),
+                    data.len(),
+                )
+            }
+        }
+        CompressionMode::Indices => {
+            if stride != 2 && stride != 4 {
+                return Err("invalid stride for indices");
+            }
+            // SAFETY: `target` holds `count` indices of `stride` bytes, and `data` is read up to
+            // its length.
+            unsafe {
+                ffi::meshopt_decodeIndexSequence(
+                    target.as_mut_ptr().cast(),
+                    count,
+                    stride,
+                    data.as_ptr(),
+                    data.len(),
+                )
+            }
+        }
+    };
+    match result {
+        0 => Ok(()),
+        -1 => Err("unsupported codec version"),
+        _ => Err("malformed compressed data"),
+    }
+}
+
+/// Reverses the transformations applied to attributes before encoding them, like the
+/// `meshopt_decodeFilter*` functions, which aren't built by [`meshopt`].
+fn apply_filter(vertices: &mut [u8], stride: usize, filter: CompressionFilter) {
+    match filter {
+        CompressionFilter::None => {}
+        CompressionFilter::Octahedral => {
+            // Unit vectors stored as octahedral coordinates, in 8 or 16 bit components.
+            let size = stride / 4;
+            let max = ((1 << (size * 8 - 1)) - 1) as f32;
+            for vertex in vertices.chunks_exact_mut(stride) {
+                let mut x = read_signed(vertex, 0, size) as f32;
+                let mut y = read_signed(vertex, 1, size) as f32;
+                // The third component stores the value of one at the precision of the encoding.
+                let z = read_signed(vertex, 2, size) as f32 - x.abs() - y.abs();
+                let t = z.min(0.0);
+                x += if x >= 0.0 { t } else { -t };
+                y += if y >= 0.0 { t } else { -t };
+                let scale = max / (x * x + y * y + z * z).sqrt();
+                for (component, value) in [x, y, z].into_iter().enumerate() {
+                    write_signed(vertex, component, size, round_signed(value * scale));
+                }
+            }
+        }
+        CompressionFilter::Quaternion => {
+            // Unit quaternions stored as their three smallest components, the last component
+            // stores the index of the largest one and the precision of the encoding.
+            for vertex in vertices.chunks_exact_mut(stride) {
+                let last = read_signed(vertex, 3, 2);
+                let scale = std::f32::consts::FRAC_1_SQRT_2 / (last | 3) as f32;
+                let x = read_signed(vertex, 0, 2) as f32 * scale;
+                let y = read_signed(vertex, 1, 2) as f32 * scale;
+                let z = read_signed(vertex, 2, 2) as f32 * scale;
+                let w = (1.0 - x * x - y * y - z * z).max(0.0).sqrt();
+                let largest = (last & 3) as usize;
+                for (offset, value) in [(1, x), (2, y), (3, z), (0, w)] {
+                    let component = (largest + offset) & 3;
+                    write_signed(vertex, component, 2, round_signed(value * 32767.0));
+                }
+            }
+        }
+        CompressionFilter::Exponential => {
+            // 32 bit floats stored as a 24 bit mantissa and an 8 bit exponent.
+            for value in vertices.chunks_exact_mut(4) {
+                let bits = u32::from_le_bytes([value[0], value[1], value[2], value[3]]);
+                let mantissa = ((bits << 8) as i32) >> 8;
+                let exponent = (bits as i32) >> 24;
+                let decoded = f32::from_bits(((exponent + 127) as u32) << 23) * mantissa as f32;
+                value.copy_from_slice(&decoded.to_le_bytes());
+            }
+        }
+    }
+}
+
+fn read_signed(vertex: &[u8], component: usize, size: usize) -> i32 {
+    if size == 1 {
+        vertex[component] as i8 as i32
+    } else {
+        i16::from_le_bytes([vertex[component * 2], vertex[component * 2 + 1]]) as i32
+    }
+}
+
+fn write_signed(vertex: &mut [u8], component: usize, size: usize, value: i32) {
+    if size == 1 {
+        vertex[component] = value as i8 as u8;
+    } else {
+        vertex[component * 2..component * 2 + 2].copy_from_slice(&(value as i16).to_le_bytes());
+    }
+}
+
+fn round_signed(value: f32) -> i32 {
+    (value + if value >= 0.0 { 0.5 } else { -0.5 }) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_filter, decode, decode_buffer_views, CompressionFilter, CompressionMode};
+    use crate::GltfError;
+
+    /// A file with `view_length` bytes of vertices with a stride of 8 decoded into a fallback
+    /// buffer from `compressed_length` bytes of the first buffer.
+    fn compressed_gltf(compressed_length: usize, view_length: usize) -> gltf::Gltf {
+        let json = format!(
+            r#"{{
+                "asset": {{ "version": "2.0" }},
+                "extensionsUsed": ["EXT_meshopt_compression"],
+                "extensionsRequired": ["EXT_meshopt_compression"],
+                "buffers": [
+                    {{ "byteLength": {compressed_length} }},
+                    {{
+                        "byteLength": 800,
+                        "extensions": {{ "EXT_meshopt_compression": {{ "fallback": true }} }}
+                    }}
+                ],
+                "bufferViews": [{{
+                    "buffer": 1,
+                    "byteLength": {view_length},
+                    "byteStride": 8,
+                    "extensions": {{
+                        "EXT_meshopt_compression": {{
+                            "buffer": 0,
+                            "byteLength": {compressed_length},
+                            "byteStride": 8,
+                            "count": 100,
+                            "mode": "ATTRIBUTES"
+                        }}
+                    }}
+                }}]
+            }}"#
+        );
+        gltf::Gltf::from_slice_without_validation(json.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn decode_vertices() {
+        let vertices: Vec<[u8; 8]> = (0..100u8)
+            .map(|i| [i, i / 2, 0, 255, i, 7, i.wrapping_mul(3), 1])
+            .collect();
+        let data = meshopt::encode_vertex_buffer(&vertices).unwrap();
+
+        let mut target = vec![0; 800];
+        decode(
+            &mut target,
+            &data,
+            100,
+            8,
+            CompressionMode::Attributes,
+            CompressionFilter::None,
+        )
+        .unwrap();
+        assert_eq!(target, vertices.concat());
+    }
+
+    #[test]
+    fn decode_buffer_view_into_fallback_buffer() {
+        let vertices: Vec<[u16; 4]> = (0..100u16).map(|i| [i, 2 * i, 1000 - i, 7]).collect();
+        let data = meshopt::encode_vertex_buffer(&vertices).unwrap();
+        let gltf = compressed_gltf(data.len(), 800);
+
+        let mut buffer_data = vec![data, vec![0; 800]];
+        decode_buffer_views(&gltf, &mut buffer_data).unwrap();
+        let decoded = buffer_data[1]
+            .chunks_exact(2)
+            .map(|value| u16::from_le_bytes([value[0], value[1]]))
+            .collect::<Vec<_>>();
+        assert_eq!(decoded, vertices.concat());
+
+        // The decoded size must match the buffer view
+        let gltf = compressed_gltf(buffer_data[0].len(), 400);
+        assert!(matches!(
+            decode_buffer_views(&gltf, &mut buffer_data),
+            Err(GltfError::MeshoptDecode(0, _))
+        ));
+    }
+
+    #[test]
+    fn decode_triangles() {
+        let indices = [0, 1, 2, 2, 1, 3, 4, 5, 6, 3, 2, 7];
+        let data = meshopt::encode_index_buffer(&indices, 8).unwrap();
+
+        let mut target = vec![0; 24];
+        decode(
+            &mut target,
+            &data,
+            12,
+            2,
+            CompressionMode::Triangles,
+            CompressionFilter::None,
+        )
+        .unwrap();
+        let decoded = target
+            .chunks_exact(2)
+            .map(|index| u16::from_le_bytes([index[0], index[1]]) as u32)
+            .collect::<Vec<_>>();
+        // The codec may rotate the triangles, keeping their winding
+        let rotated = |indices: &[u32]| {
+            indices
+                .chunks_exact(3)
+                .map(|triangle| {
+                    let first = (0..3).min_by_key(|&i| triangle[i]).unwrap();
+                    [0, 1, 2].map(|i| triangle[(first + i) % 3])
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(rotated(&decoded), rotated(&indices));
+    }
+
+    #[test]
+    fn decode_indices() {
+        let data = [0xd1, 0, 4, 4, 0, 2, 8, 0, 0, 0, 0];
+        let mut target = vec![0; 12];
+        decode(
+            &mut target,
+            &data,
+            6,
+            2,
+            CompressionMode::Indices,
+            CompressionFilter::None,
+        )
+        .unwrap();
+        let indices = target
+            .chunks_exact(2)
+            .map(|index| u16::from_le_bytes([index[0], index[1]]))
+            .collect::<Vec<_>>();
+        assert_eq!(indices, [0, 1, 2, 2, 1, 3]);
+    }
+
+    #[test]
+    fn apply_filters() {
+        // (0, 0, -1), in the corner of the octahedron
+        let mut normal = [127, 127, 127, 0];
+        apply_filter(&mut normal, 4, CompressionFilter::Octahedral);
+        assert_eq!(normal, [0, 0, -127i8 as u8, 0]);
+
+        // The identity, with 12 bits of precision
+        let mut quaternion = [0, 0, 0, 0, 0, 0, 0, 0];
+        quaternion[6..].copy_from_slice(&((2047 << 2) | 3i16).to_le_bytes());
+        apply_filter(&mut quaternion, 8, CompressionFilter::Quaternion);
+        assert_eq!(quaternion, [0, 0, 0, 0, 0, 0, 0xff, 0x7f]);
+
+        // 3 * 2^-1
+        let mut value = ((-1i32 as u32) << 24 | 3).to_le_bytes();
+        apply_filter(&mut value, 4, CompressionFilter::Exponential);
+        assert_eq!(f32::from_le_bytes(value), 1.5);
+    }
+
+    #[test]
+    fn reject_malformed_data() {
+        let mut target = vec![0; 800];
+        let result = decode(
+            &mut target,
+            &[0xa0, 1, 2, 3],
+            100,
+            8,
+            CompressionMode::Attributes,
+            CompressionFilter::None,
+        );
+        assert_eq!(result, Err("malformed compressed data"));
+
+        let result = decode(
+            &mut target[..400],
+            &[0xa0],
+            100,
+            4,
+            CompressionMode::Attributes,
+            CompressionFilter::Quaternion,
+        );
+        assert_eq!(result, Err("invalid stride for the filter"));
+        let result = decode(
+            &mut target[..600],
+            &[0xa0],
+            100,
+            6,
+            CompressionMode::Attributes,
+            CompressionFilter::None,
+        );
+        assert_eq!(result, Err("invalid stride for attributes"));
+    }
+}
@@ -0,0 +1,248 @@
+use crate::{
+    components::{Children, Parent},
+    BuildChildren,
+};
+use bevy_ecs::{
+    entity::{Entity, EntityHashMap},
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectMapEntities},
+    system::EntityCommands,
+    world::{Command, EntityWorldMut, World},
+};
+use bevy_reflect::Reflect;
+use bevy_utils::tracing::debug;
+use std::any::TypeId;
+
+/// Clones the given entity and all its descendants into `destination`, see [`CloneTreeExt`].
+#[derive(Debug)]
+pub struct CloneTree {
+    /// The root of the tree to clone.
+    pub source: Entity,
+    /// The entity that becomes the root of the cloned tree.
+    pub destination: Entity,
+}
+
+impl Command for CloneTree {
+    fn apply(self, world: &mut World) {
+        #[cfg(feature = "trace")]
+        let _span = bevy_utils::tracing::info_span!(
+            "command",
+            name = "CloneTree",
+            entity = bevy_utils::tracing::field::debug(self.source)
+        )
+        .entered();
+        clone_tree(world, self.source, self.destination);
+    }
+}
+
+/// Function for cloning an entity and all its descendants into `destination`, see
+/// [`CloneTreeExt`].
+pub fn clone_tree(world: &mut World, source: Entity, destination: Entity) {
+    // Spawn the entities of the new tree first, so entity references can be mapped to them
+    let mut entity_map = EntityHashMap::default();
+    entity_map.insert(source, destination);
+    let mut tree = vec![(source, destination)];
+    let mut index = 0;
+    while let Some(&(source, destination)) = tree.get(index) {
+        index += 1;
+        let Some(children) = world
+            .get::<Children>(source)
+            .map(|children| children.to_vec())
+        else {
+            continue;
+        };
+        for child in children {
+            let clone = world.spawn_empty().id();
+            world.entity_mut(destination).add_child(clone);
+            entity_map.insert(child, clone);
+            tree.push((child, clone));
+        }
+    }
+
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let mut map_entities = Vec::new();
+    for &(source, destination) in &tree {
+        let Some(source_entity) = world.get_entity(source) else {
+            continue;
+        };
+        let component_ids: Vec<_> = source_entity.archetype().components().collect();
+
+        for component_id in component_ids {
+            let Some(info) = world.components().get_info(component_id) else {
+                continue;
+            };
+            let Some(type_id) = info.type_id() else {
+                continue;
+            };
+            // The hierarchy of the clone is built above
+            if type_id == TypeId::of::<Parent>() || type_id == TypeId::of::<Children>() {
+                continue;
+            }
+            let Some(registration) = registry.get(type_id) else {
+                debug!(
+                    "Skipped cloning unregistered component {} of {source:?}",
+                    info.name()
+                );
+                continue;
+            };
+            let Some(reflect_component) = registration.data::<ReflectComponent>() else {
+                debug!(
+                    "Skipped cloning component {} of {source:?} without `#[reflect(Component)]`",
+                    info.name()
+                );
+                continue;
+            };
+            let Some(value) = reflect_component
+                .reflect(world.entity(source))
+                .map(Reflect::clone_value)
+            else {
+                continue;
+            };
+            reflect_component.insert(&mut world.entity_mut(destination), &*value, &registry);
+
+            if registration.data::<ReflectMapEntities>().is_some()
+                && !map_entities.contains(&type_id)
+            {
+                map_entities.push(type_id);
+            }
+        }
+    }
+
+    // Point the references within the cloned tree to the new entities
+    let clones: Vec<_> = tree.iter().map(|&(_, destination)| destination).collect();
+    for type_id in map_entities {
+        if let Some(reflect_map_entities) = registry.get_type_data::<ReflectMapEntities>(type_id) {
+            reflect_map_entities.map_entities_in_world(world, &entity_map, &clones);
+        }
+    }
+
+    // The clone is a sibling of the source
+    if let Some(parent) = world.get::<Parent>(source).map(Parent::get) {
+        world.entity_mut(parent).add_child(destination);
+    }
+}
+
+/// Trait that holds functions for cloning an entity and its descendants.
+pub trait CloneTreeExt {
+    /// Clones this entity and all its descendants, and returns the root of the new tree.
+    ///
+    /// The components registered with `#[reflect(Component)]` in the [`AppTypeRegistry`] are
+    /// cloned through reflection, the others are skipped. Asset handles are shared with the
+    /// original tree, and the [`Entity`] references of components registered with
+    /// `#[reflect(MapEntities)]` are remapped to the cloned entities when they point within the
+    /// tree. The new root gets the same [`Parent`] as this entity.
+    fn clone_tree(&mut self) -> Entity;
+}
+
+impl CloneTreeExt for EntityCommands<'_> {
+    fn clone_tree(&mut self) -> Entity {
+        let source = self.id();
+        let destination = self.commands().spawn_empty().id();
+        self.commands().add(CloneTree {
+            source,
+            destination,
+        });
+        destination
+    }
+}
+
+impl CloneTreeExt for EntityWorldMut<'_> {
+    fn clone_tree(&mut self) -> Entity {
+        let source = self.id();
+        self.world_scope(|world| {
+            let destination = world.spawn_empty().id();
+            clone_tree(world, source, destination);
+            destination
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{
+        component::Component,
+        entity::{Entity, EntityMapper, MapEntities},
+        reflect::{AppTypeRegistry, ReflectComponent, ReflectMapEntities},
+        system::Commands,
+        world::{CommandQueue, World},
+    };
+    use bevy_reflect::Reflect;
+
+    use super::CloneTreeExt;
+    use crate::{
+        child_builder::{BuildChildren, ChildBuild},
+        components::{Children, Parent},
+    };
+
+    #[derive(Component, Reflect, Clone, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Name(String);
+
+    #[derive(Component, Reflect)]
+    #[reflect(Component, MapEntities)]
+    struct Target(Entity);
+
+    impl MapEntities for Target {
+        fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+            self.0 = entity_mapper.map_entity(self.0);
+        }
+    }
+
+    #[derive(Component)]
+    struct NotReflected;
+
+    fn world() -> World {
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<Name>();
+            registry.register::<Target>();
+        }
+        world.insert_resource(registry);
+        world
+    }
+
+    #[test]
+    fn clone_tree() {
+        let mut world = world();
+        let outside = world.spawn_empty().id();
+        let grandparent = world.spawn_empty().id();
+        let mut first_child = Entity::PLACEHOLDER;
+        let source = world
+            .spawn((Name("root".to_owned()), NotReflected))
+            .set_parent(grandparent)
+            .with_children(|parent| {
+                first_child = parent.spawn(Name("first".to_owned())).id();
+                parent.spawn((Name("second".to_owned()), Target(outside)));
+            })
+            .id();
+        world.entity_mut(source).insert(Target(first_child));
+
+        let mut queue = CommandQueue::default();
+        let clone = Commands::new(&mut queue, &world)
+            .entity(source)
+            .clone_tree();
+        queue.apply(&mut world);
+
+        assert_ne!(clone, source);
+        assert_eq!(world.get::<Name>(clone).unwrap().0, "root");
+        assert!(world.get::<NotReflected>(clone).is_none());
+        assert_eq!(world.get::<Parent>(clone).unwrap().get(), grandparent);
+        assert_eq!(world.get::<Children>(grandparent).unwrap().len(), 2);
+
+        let children = world.get::<Children>(clone).unwrap().to_vec();
+        assert_eq!(children.len(), 2);
+        assert!(!world
+            .get::<Children>(source)
+            .unwrap()
+            .contains(&children[0]));
+        assert_eq!(world.get::<Name>(children[0]).unwrap().0, "first");
+        assert_eq!(world.get::<Name>(children[1]).unwrap().0, "second");
+
+        // References within the tree point to the clones, the others are kept
+        assert_eq!(world.get::<Target>(clone).unwrap().0, children[0]);
+        assert_eq!(world.get::<Target>(children[1]).unwrap().0, outside);
+        assert_eq!(world.get::<Target>(source).unwrap().0, first_child);
+    }
+}
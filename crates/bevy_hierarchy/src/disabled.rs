@@ -0,0 +1,137 @@
+#[cfg(feature = "reflect")]
+use bevy_ecs::reflect::ReflectComponent;
+use bevy_ecs::{
+    component::Component,
+    entity::{Entity, EntityHashSet},
+    query::{Added, Changed, Has, Or, With},
+    removal_detection::RemovedComponents,
+    system::{Commands, Query},
+};
+#[cfg(feature = "reflect")]
+use bevy_reflect::std_traits::ReflectDefault;
+
+pub use bevy_ecs::entity_disabling::Disabled;
+
+use crate::{Children, Parent};
+
+/// Disables this entity and all its descendants.
+///
+/// [`propagate_disabled`] inserts [`Disabled`] on the entities of the tree, and removes it again
+/// once they leave the tree or this component is removed. The entities that were disabled on their
+/// own stay disabled.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+#[cfg_attr(feature = "reflect", reflect(Component, Default, PartialEq))]
+pub struct DisableHierarchy;
+
+/// Records that [`Disabled`] was inserted by [`propagate_disabled`], to only remove that one.
+#[derive(Component)]
+pub struct InheritedDisabled;
+
+/// Updates [`Disabled`] on the descendants of the entities with [`DisableHierarchy`].
+///
+/// The trees are only walked again in the frames where [`DisableHierarchy`] is added or removed,
+/// or where [`Children`] or [`Parent`] change.
+///
+/// This is added to `PostUpdate` by the `HierarchyPlugin`.
+#[allow(clippy::type_complexity)]
+pub fn propagate_disabled(
+    mut commands: Commands,
+    // `Has<Disabled>` includes the disabled entities in these queries
+    changed: Query<
+        Has<Disabled>,
+        Or<(Added<DisableHierarchy>, Changed<Children>, Changed<Parent>)>,
+    >,
+    mut removed_roots: RemovedComponents<DisableHierarchy>,
+    mut removed_parents: RemovedComponents<Parent>,
+    roots: Query<(Entity, Has<Disabled>), With<DisableHierarchy>>,
+    nodes: Query<(Has<Disabled>, Option<&Children>)>,
+    inherited: Query<(Entity, Has<Disabled>), With<InheritedDisabled>>,
+) {
+    let removed = removed_roots.read().count() + removed_parents.read().count();
+    if removed == 0 && changed.is_empty() {
+        return;
+    }
+
+    let mut tree = EntityHashSet::default();
+    let mut stack: Vec<Entity> = roots.iter().map(|(entity, _)| entity).collect();
+    while let Some(entity) = stack.pop() {
+        if tree.insert(entity) {
+            if let Ok((_, Some(children))) = nodes.get(entity) {
+                stack.extend(children.iter());
+            }
+        }
+    }
+
+    for (entity, _) in &inherited {
+        if !tree.contains(&entity) {
+            commands
+                .entity(entity)
+                .remove::<(Disabled, InheritedDisabled)>();
+        }
+    }
+    for entity in tree {
+        if matches!(nodes.get(entity), Ok((false, _))) {
+            commands
+                .entity(entity)
+                .try_insert((Disabled, InheritedDisabled));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{entity::Entity, world::World};
+
+    use super::{propagate_disabled, DisableHierarchy, Disabled};
+    use crate::{BuildChildren, Children};
+
+    #[test]
+    fn propagate_disabled_to_descendants() {
+        let mut world = World::new();
+        let propagate_disabled = world.register_system(propagate_disabled);
+        let [root, child, grandchild, manual] = std::array::from_fn(|_| world.spawn_empty().id());
+        world.entity_mut(root).push_children(&[child, manual]);
+        world.entity_mut(child).add_child(grandchild);
+        world.entity_mut(manual).insert(Disabled);
+
+        let is_disabled = |world: &World, entities: [Entity; 4]| {
+            entities.map(|e| world.get::<Disabled>(e).is_some())
+        };
+
+        world.entity_mut(root).insert(DisableHierarchy);
+        world.run_system(propagate_disabled).unwrap();
+        assert_eq!(
+            is_disabled(&world, [root, child, grandchild, manual]),
+            [true; 4]
+        );
+        assert_eq!(world.query::<&Children>().iter(&world).count(), 0);
+
+        // Entities leaving the tree are enabled again
+        world.entity_mut(grandchild).remove_parent();
+        world.run_system(propagate_disabled).unwrap();
+        assert_eq!(
+            is_disabled(&world, [root, child, grandchild, manual]),
+            [true, true, false, true]
+        );
+        world.entity_mut(grandchild).set_parent(child);
+
+        // Entities disabled on their own stay disabled
+        world.entity_mut(root).remove::<DisableHierarchy>();
+        world.run_system(propagate_disabled).unwrap();
+        assert_eq!(
+            is_disabled(&world, [root, child, grandchild, manual]),
+            [false, false, false, true]
+        );
+
+        // The trees aren't walked again without hierarchy changes
+        world.entity_mut(root).insert(DisableHierarchy);
+        world.run_system(propagate_disabled).unwrap();
+        world.entity_mut(child).remove::<Disabled>();
+        world.run_system(propagate_disabled).unwrap();
+        assert_eq!(
+            is_disabled(&world, [root, child, grandchild, manual]),
+            [true, false, true, true]
+        );
+    }
+}
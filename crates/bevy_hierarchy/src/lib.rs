@@ -44,10 +44,23 @@
 //! In most cases, these operations will invalidate the hierarchy.
 //! Instead, you should use the provided [hierarchical despawn extension methods].
 //!
+//! ## Cloning entities
+//!
+//! Similarly, an entity and all its descendants can be duplicated
+//! with the [hierarchical clone extension methods],
+//! which clone their reflected components
+//! and keep the entity references within the new hierarchy consistent.
+//!
+//! ## Disabling entities
+//!
+//! Inserting [`DisableHierarchy`] on an entity marks it and all its descendants
+//! as [`Disabled`], which takes them out of the queries, until it's removed again.
+//!
 //! [command and world]: BuildChildren
 //! [diagnostic plugin]: ValidParentCheckPlugin
 //! [events]: HierarchyEvent
 //! [hierarchical despawn extension methods]: DespawnRecursiveExt
+//! [hierarchical clone extension methods]: CloneTreeExt
 //! [plugin]: HierarchyPlugin
 //! [query extension methods]: HierarchyQueryExt
 
@@ -57,6 +70,11 @@ pub use components::*;
 mod hierarchy;
 pub use hierarchy::*;
 
+#[cfg(feature = "reflect")]
+mod clone_tree;
+#[cfg(feature = "reflect")]
+pub use clone_tree::*;
+
 mod child_builder;
 pub use child_builder::*;
 
@@ -69,10 +87,20 @@ pub use valid_parent_check_plugin::*;
 mod query_extension;
 pub use query_extension::*;
 
+mod disabled;
+pub use disabled::*;
+
 #[doc(hidden)]
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{child_builder::*, components::*, hierarchy::*, query_extension::*};
+    pub use crate::{
+        child_builder::*, components::*, hierarchy::*, query_extension::*, DisableHierarchy,
+        Disabled,
+    };
+
+    #[doc(hidden)]
+    #[cfg(feature = "reflect")]
+    pub use crate::CloneTreeExt;
 
     #[doc(hidden)]
     #[cfg(feature = "bevy_app")]
@@ -96,6 +124,9 @@ impl Plugin for HierarchyPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Children>()
             .register_type::<Parent>()
-            .add_event::<HierarchyEvent>();
+            .register_type::<Disabled>()
+            .register_type::<DisableHierarchy>()
+            .add_event::<HierarchyEvent>()
+            .add_systems(PostUpdate, propagate_disabled);
     }
 }
@@ -13,6 +13,7 @@ use bevy_app::{Plugin, PluginGroup, PluginGroupBuilder};
 /// * [`InputPlugin`](crate::input::InputPlugin)
 /// * [`WindowPlugin`](crate::window::WindowPlugin)
 /// * [`AccessibilityPlugin`](crate::a11y::AccessibilityPlugin)
+/// * [`HttpAssetPlugin`](crate::asset::io::http::HttpAssetPlugin) - with feature `http_source`
 /// * [`AssetPlugin`](crate::asset::AssetPlugin) - with feature `bevy_asset`
 /// * [`ScenePlugin`](crate::scene::ScenePlugin) - with feature `bevy_scene`
 /// * [`WinitPlugin`](crate::winit::WinitPlugin) - with feature `bevy_winit`
@@ -29,6 +30,7 @@ use bevy_app::{Plugin, PluginGroup, PluginGroupBuilder};
 /// * [`GilrsPlugin`](crate::gilrs::GilrsPlugin) - with feature `bevy_gilrs`
 /// * [`AnimationPlugin`](crate::animation::AnimationPlugin) - with feature `bevy_animation`
 /// * [`GizmoPlugin`](crate::gizmos::GizmoPlugin) - with feature `bevy_gizmos`
+/// * [`ParticlesPlugin`](crate::particles::ParticlesPlugin) - with feature `bevy_particles`
 /// * [`StatesPlugin`](crate::app::StatesPlugin) - with feature `bevy_state`
 /// * [`DevToolsPlugin`](crate::dev_tools::DevToolsPlugin) - with feature `bevy_dev_tools`
 /// * [`CiTestingPlugin`](crate::dev_tools::ci_testing::CiTestingPlugin) - with feature `bevy_ci_testing`
@@ -64,6 +66,11 @@ impl PluginGroup for DefaultPlugins {
             group = group.add(bevy_app::TerminalCtrlCHandlerPlugin);
         }
 
+        #[cfg(feature = "http_source")]
+        {
+            group = group.add(bevy_asset::io::http::HttpAssetPlugin::default());
+        }
+
         #[cfg(feature = "bevy_asset")]
         {
             group = group.add(bevy_asset::AssetPlugin::default());
@@ -145,6 +152,11 @@ impl PluginGroup for DefaultPlugins {
             group = group.add(bevy_gizmos::GizmoPlugin);
         }
 
+        #[cfg(feature = "bevy_particles")]
+        {
+            group = group.add(bevy_particles::ParticlesPlugin);
+        }
+
         #[cfg(feature = "bevy_state")]
         {
             group = group.add(bevy_state::app::StatesPlugin);
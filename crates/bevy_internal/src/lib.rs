@@ -42,6 +42,8 @@ pub use bevy_hierarchy as hierarchy;
 pub use bevy_input as input;
 pub use bevy_log as log;
 pub use bevy_math as math;
+#[cfg(feature = "bevy_particles")]
+pub use bevy_particles as particles;
 #[cfg(feature = "bevy_pbr")]
 pub use bevy_pbr as pbr;
 #[cfg(feature = "bevy_picking")]
@@ -59,6 +59,10 @@ pub use crate::dynamic_plugin::*;
 #[cfg(feature = "bevy_gizmos")]
 pub use crate::gizmos::prelude::*;
 
+#[doc(hidden)]
+#[cfg(feature = "bevy_particles")]
+pub use crate::particles::prelude::*;
+
 #[doc(hidden)]
 #[cfg(feature = "bevy_gilrs")]
 pub use crate::gilrs::*;
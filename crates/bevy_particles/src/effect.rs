@@ -0,0 +1,247 @@
+use std::ops::Range;
+
+use bevy_asset::Asset;
+use bevy_color::LinearRgba;
+use bevy_math::Vec3;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_utils::tracing::warn;
+
+/// The maximum number of keys of a [`ParticleCurve`]. Further keys are ignored.
+pub const MAX_PARTICLE_CURVE_KEYS: usize = 8;
+
+/// A particle effect: how a [`ParticleSystem`](crate::ParticleSystem) emits particles, how they
+/// move and how they look over their lifetime.
+///
+/// ```
+/// # use bevy_particles::*;
+/// # use bevy_color::LinearRgba;
+/// # use bevy_math::Vec3;
+/// let sparks = ParticleEffect {
+///     capacity: 2000,
+///     emitter: ParticleEmitter {
+///         rate: 400.0,
+///         shape: EmitterShape::Cone {
+///             radius: 0.1,
+///             angle: 0.4,
+///         },
+///         ..Default::default()
+///     },
+///     lifetime: 1.0..2.0,
+///     speed: 4.0..6.0,
+///     acceleration: Vec3::new(0.0, -9.8, 0.0),
+///     color: ParticleCurve::new([
+///         (0.0, LinearRgba::rgb(4.0, 2.0, 0.5)),
+///         (1.0, LinearRgba::new(1.0, 0.2, 0.0, 0.0)),
+///     ]),
+///     size: ParticleCurve::constant(0.05),
+///     blend_mode: ParticleBlendMode::Additive,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Asset, Reflect, Clone, Debug)]
+#[reflect(Default)]
+pub struct ParticleEffect {
+    /// The maximum number of particles alive at once.
+    ///
+    /// It should be at least the [`rate`](ParticleEmitter::rate) of the emitter multiplied by the
+    /// longest [`lifetime`](Self::lifetime). On the GPU, new particles replace the oldest ones
+    /// once the capacity is reached. On the CPU, no particle is emitted until others die.
+    pub capacity: u32,
+    /// Whether the particles are simulated on the CPU or on the GPU.
+    pub simulation: ParticleSimulation,
+    /// How many particles are emitted, and where.
+    pub emitter: ParticleEmitter,
+    /// The range the lifetime of each particle is randomly picked in, in seconds.
+    pub lifetime: Range<f32>,
+    /// The range the initial speed of each particle is randomly picked in, in world units per
+    /// second. Particles move away from the emitter, see [`EmitterShape`].
+    pub speed: Range<f32>,
+    /// A constant acceleration of the particles, such as gravity, in world units per second
+    /// squared.
+    pub acceleration: Vec3,
+    /// How quickly the particles slow down, as a fraction of their velocity lost per second.
+    pub drag: f32,
+    /// The color of the particles over their lifetime. Particles are drawn as round dots fading
+    /// out towards their edges, tinted by this color.
+    pub color: ParticleCurve<LinearRgba>,
+    /// The diameter of the particles over their lifetime, in world units.
+    pub size: ParticleCurve<f32>,
+    /// How the particles are blended with what is behind them.
+    pub blend_mode: ParticleBlendMode,
+    /// The distance, in world units, over which particles fade out as they get close to the
+    /// geometry behind them, instead of cutting through it. `0.0` disables soft particles.
+    ///
+    /// Only applies in 3D, to cameras with a [`DepthPrepass`](bevy_core_pipeline::prepass::DepthPrepass).
+    pub soft_distance: f32,
+    /// Whether the particles bounce off the geometry seen by the
+    /// [`ParticleCollisionCamera`](crate::ParticleCollisionCamera).
+    ///
+    /// Only applies to effects simulated on the GPU.
+    pub collision: Option<DepthCollision>,
+}
+
+impl Default for ParticleEffect {
+    fn default() -> Self {
+        Self {
+            capacity: 1000,
+            simulation: ParticleSimulation::default(),
+            emitter: ParticleEmitter::default(),
+            lifetime: 1.0..1.0,
+            speed: 1.0..1.0,
+            acceleration: Vec3::ZERO,
+            drag: 0.0,
+            color: ParticleCurve::constant(LinearRgba::WHITE),
+            size: ParticleCurve::constant(1.0),
+            blend_mode: ParticleBlendMode::default(),
+            soft_distance: 0.0,
+            collision: None,
+        }
+    }
+}
+
+/// Where the particles of a [`ParticleEffect`] are simulated.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[reflect(Default)]
+pub enum ParticleSimulation {
+    /// The particles are simulated on the CPU, in the main world, and sent to the GPU every
+    /// frame. Suited to effects of up to a few thousand particles.
+    #[default]
+    Cpu,
+    /// The particles are simulated by a compute shader, and never leave the GPU. Suited to
+    /// effects of up to millions of particles.
+    Gpu,
+}
+
+/// How the particles of a [`ParticleEffect`] are blended with what is behind them.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[reflect(Default)]
+pub enum ParticleBlendMode {
+    /// The particles are drawn over what is behind them, by their alpha.
+    #[default]
+    Blend,
+    /// The particles add their color, multiplied by their alpha, to what is behind them, as
+    /// fire and sparks do.
+    Additive,
+}
+
+/// Emits the particles of a [`ParticleEffect`], from the [`GlobalTransform`](bevy_transform::components::GlobalTransform)
+/// of the [`ParticleSystem`](crate::ParticleSystem).
+#[derive(Reflect, Clone, Debug)]
+#[reflect(Default)]
+pub struct ParticleEmitter {
+    /// The number of particles emitted per second.
+    pub rate: f32,
+    /// The number of particles emitted at once when the particle system starts.
+    pub burst: u32,
+    /// Where the particles are emitted, and which direction they start moving in.
+    pub shape: EmitterShape,
+}
+
+impl Default for ParticleEmitter {
+    fn default() -> Self {
+        Self {
+            rate: 10.0,
+            burst: 0,
+            shape: EmitterShape::default(),
+        }
+    }
+}
+
+/// Where the particles are emitted, and which direction they start moving in.
+///
+/// The shapes are in the local space of the emitter, so they are moved, rotated and scaled
+/// with it.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[reflect(Default)]
+pub enum EmitterShape {
+    /// Particles are emitted at the origin, in every direction in 3D.
+    #[default]
+    Point,
+    /// Particles are emitted within a circle in the XY plane, moving away from its center,
+    /// suited to 2D.
+    Circle {
+        /// The radius of the circle.
+        radius: f32,
+    },
+    /// Particles are emitted within an arc of a circle in the XY plane, centered on the Y axis
+    /// and spanning `angle` radians on both of its sides, moving away from its center.
+    Arc {
+        /// The radius of the circle.
+        radius: f32,
+        /// Half the angle of the arc, in radians.
+        angle: f32,
+    },
+    /// Particles are emitted within a sphere, moving away from its center.
+    Sphere {
+        /// The radius of the sphere.
+        radius: f32,
+    },
+    /// Particles are emitted within a disc of the `radius` in the XZ plane, moving up in a cone
+    /// around the Y axis opened `angle` radians on all its sides.
+    Cone {
+        /// The radius of the base of the cone.
+        radius: f32,
+        /// The angle between the axis and the side of the cone, in radians.
+        angle: f32,
+    },
+}
+
+/// How particles bounce off the depth buffer, see [`ParticleEffect::collision`].
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Default)]
+pub struct DepthCollision {
+    /// The fraction of the velocity towards the surface kept when bouncing off it.
+    pub restitution: f32,
+    /// The fraction of the velocity along the surface lost when bouncing off it.
+    pub friction: f32,
+    /// How far behind the surfaces seen in the depth buffer particles still collide with them,
+    /// in world units. Particles further behind them are considered to pass behind the geometry.
+    pub thickness: f32,
+}
+
+impl Default for DepthCollision {
+    fn default() -> Self {
+        Self {
+            restitution: 0.5,
+            friction: 0.2,
+            thickness: 0.5,
+        }
+    }
+}
+
+/// A value of the particles changing over their lifetime, interpolated linearly between keys.
+///
+/// Each key is a time between `0.0`, when a particle is emitted, and `1.0`, when it dies, and
+/// the value at that time. Up to [`MAX_PARTICLE_CURVE_KEYS`] keys are used.
+#[derive(Reflect, Clone, Debug, PartialEq)]
+pub struct ParticleCurve<T> {
+    keys: Vec<(f32, T)>,
+}
+
+impl<T: Copy> ParticleCurve<T> {
+    /// Creates a curve from its keys, which are sorted by time.
+    pub fn new(keys: impl IntoIterator<Item = (f32, T)>) -> Self {
+        let mut keys: Vec<_> = keys.into_iter().collect();
+        keys.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        if keys.len() > MAX_PARTICLE_CURVE_KEYS {
+            warn!(
+                "ParticleCurve has {} keys, only the first {MAX_PARTICLE_CURVE_KEYS} are used",
+                keys.len()
+            );
+            keys.truncate(MAX_PARTICLE_CURVE_KEYS);
+        }
+        Self { keys }
+    }
+
+    /// Creates a curve keeping the same value over the lifetime of the particles.
+    pub fn constant(value: T) -> Self {
+        Self {
+            keys: vec![(0.0, value)],
+        }
+    }
+
+    /// Returns the keys of the curve, sorted by time.
+    pub fn keys(&self) -> &[(f32, T)] {
+        &self.keys
+    }
+}
@@ -0,0 +1,179 @@
+#![cfg_attr(docsrs, feature(doc_auto_cfg))]
+#![forbid(unsafe_code)]
+#![doc(
+    html_logo_url = "https://bevyengine.org/assets/icon.png",
+    html_favicon_url = "https://bevyengine.org/assets/icon.png"
+)]
+
+//! Particle effects for Bevy, simulated on the CPU or on the GPU, and drawn by 2d and 3d cameras.
+//!
+//! A [`ParticleEffect`] asset describes how particles are emitted, how they move and how they
+//! look over their lifetime. A [`ParticleSystem`] emits the particles of an effect from its
+//! entity.
+
+mod effect;
+mod particle_system;
+mod render;
+
+pub use effect::*;
+pub use particle_system::*;
+
+/// The `bevy_particles` prelude.
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{
+        DepthCollision, EmitterShape, ParticleBlendMode, ParticleCollisionCamera, ParticleCurve,
+        ParticleEffect, ParticleEmitter, ParticleSimulation, ParticleSystem, ParticleSystemBundle,
+    };
+}
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{load_internal_asset, AssetApp, Handle};
+use bevy_core_pipeline::{
+    core_2d::Transparent2d,
+    core_3d::{
+        graph::{Core3d, Node3d},
+        Transparent3d,
+    },
+};
+use bevy_ecs::prelude::*;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    graph::CameraDriverLabel,
+    render_graph::{RenderGraph, RenderGraphApp, ViewNodeRunner},
+    render_phase::AddRenderCommand,
+    render_resource::{Shader, SpecializedRenderPipelines},
+    renderer::RenderDevice,
+    view::{check_visibility, VisibilitySystems},
+    ExtractSchedule, Render, RenderApp, RenderSet,
+};
+use bevy_transform::TransformSystem;
+use bevy_utils::tracing::warn;
+use render::{
+    extract_particle_systems, prepare_particle_bind_groups, prepare_particle_buffers,
+    prepare_particle_view_bind_groups, queue_particle_systems, CollideParticles,
+    DrawParticleSystem, GpuParticleSystems, ParticleCollisionNode, ParticlePipeline,
+    ParticleSimulationNode, ParticleSimulationPipeline, SimulateParticles,
+};
+
+/// The handle of the shader module of the types and functions shared by the particle shaders.
+pub const PARTICLE_TYPES_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(8561627205862905508);
+/// The handle of the compute shader simulating the particles on the GPU.
+pub const SIMULATE_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(4969666970782674619);
+/// The handle of the compute shader bouncing the particles off the depth buffer.
+pub const COLLIDE_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(13168229520520557331);
+/// The handle of the shader drawing the particles.
+pub const PARTICLES_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(12463327774172547180);
+
+/// Adds [`ParticleEffect`]s, emitted by [`ParticleSystem`]s.
+///
+/// Particles are drawn with storage buffers and simulated on the GPU with compute shaders, so they
+/// aren't drawn on WebGL2.
+#[derive(Default)]
+pub struct ParticlesPlugin;
+
+/// A convenient alias for `With<ParticleSystem>`, for use with
+/// [`bevy_render::view::VisibleEntities`].
+pub type WithParticleSystem = With<ParticleSystem>;
+
+/// Marks the camera whose depth buffer the particles with a [`DepthCollision`] bounce off.
+///
+/// The camera needs a [`DepthPrepass`](bevy_core_pipeline::prepass::DepthPrepass). Particles
+/// only collide with the geometry in the view of the camera, so this is usually the main 3d
+/// camera, and particles out of its view go through everything.
+#[derive(Component, ExtractComponent, Reflect, Clone, Copy, Debug, Default)]
+#[reflect(Component, Default)]
+pub struct ParticleCollisionCamera;
+
+impl Plugin for ParticlesPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            PARTICLE_TYPES_SHADER_HANDLE,
+            "render/particle_types.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            SIMULATE_SHADER_HANDLE,
+            "render/simulate.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            COLLIDE_SHADER_HANDLE,
+            "render/collide.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            PARTICLES_SHADER_HANDLE,
+            "render/particles.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.init_asset::<ParticleEffect>()
+            .register_asset_reflect::<ParticleEffect>()
+            .register_type::<ParticleSystem>()
+            .register_type::<ParticleCollisionCamera>()
+            .add_plugins(ExtractComponentPlugin::<ParticleCollisionCamera>::default())
+            .add_systems(
+                PostUpdate,
+                (
+                    update_particle_systems.after(TransformSystem::TransformPropagate),
+                    check_visibility::<WithParticleSystem>
+                        .in_set(VisibilitySystems::CheckVisibility),
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        let render_device = render_app.world().resource::<RenderDevice>();
+        if render_device.limits().max_storage_buffers_per_shader_stage == 0 {
+            warn!("Particles aren't supported on this device, as it lacks storage buffers");
+            return;
+        }
+
+        render_app
+            .init_resource::<ParticlePipeline>()
+            .init_resource::<ParticleSimulationPipeline>()
+            .init_resource::<SpecializedRenderPipelines<ParticlePipeline>>()
+            .init_resource::<GpuParticleSystems>()
+            .add_render_command::<Transparent2d, DrawParticleSystem>()
+            .add_render_command::<Transparent3d, DrawParticleSystem>()
+            .add_systems(ExtractSchedule, extract_particle_systems)
+            .add_systems(
+                Render,
+                (
+                    queue_particle_systems.in_set(RenderSet::Queue),
+                    prepare_particle_buffers.in_set(RenderSet::PrepareResources),
+                    (
+                        prepare_particle_bind_groups,
+                        prepare_particle_view_bind_groups,
+                    )
+                        .in_set(RenderSet::PrepareBindGroups),
+                ),
+            )
+            .add_render_graph_node::<ViewNodeRunner<ParticleCollisionNode>>(
+                Core3d,
+                CollideParticles,
+            )
+            .add_render_graph_edges(
+                Core3d,
+                (
+                    Node3d::EndPrepasses,
+                    CollideParticles,
+                    Node3d::StartMainPass,
+                ),
+            );
+
+        let mut render_graph = render_app.world_mut().resource_mut::<RenderGraph>();
+        render_graph.add_node(SimulateParticles, ParticleSimulationNode);
+        render_graph.add_node_edge(SimulateParticles, CameraDriverLabel);
+    }
+}
@@ -0,0 +1,315 @@
+use std::f32::consts::TAU;
+
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_math::Vec3;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::view::{InheritedVisibility, ViewVisibility, Visibility};
+use bevy_time::Time;
+use bevy_transform::components::{GlobalTransform, Transform};
+use bytemuck::{Pod, Zeroable};
+
+use crate::{EmitterShape, ParticleEffect, ParticleSimulation};
+
+/// Emits and simulates the particles of a [`ParticleEffect`] from the
+/// [`GlobalTransform`] of its entity.
+///
+/// The particles are simulated in world space: they keep moving on their own when the particle
+/// system moves.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component, Default)]
+pub struct ParticleSystem {
+    /// The effect of the particles emitted by the particle system.
+    pub effect: Handle<ParticleEffect>,
+    /// Whether new particles are emitted. The particles already emitted keep moving until they
+    /// die when it is `false`.
+    pub emitting: bool,
+    /// Whether the simulation is paused, freezing the particles.
+    pub paused: bool,
+    #[reflect(ignore)]
+    pub(crate) state: ParticleSystemState,
+}
+
+/// The state of the emission of a [`ParticleSystem`], and its particles when they are simulated
+/// on the CPU.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ParticleSystemState {
+    pub(crate) started: bool,
+    /// The fraction of a particle left to emit.
+    pub(crate) accumulator: f32,
+    /// The number of particles emitted since the start, used to give each particle its own
+    /// random values.
+    pub(crate) emitted: u32,
+    /// The particles emitted by the last update.
+    pub(crate) spawn_count: u32,
+    /// The index in the GPU buffer of the first particle emitted by the last update.
+    pub(crate) spawn_offset: u32,
+    /// The time step of the last update.
+    pub(crate) delta: f32,
+    /// The seed of the random values of the particles, different for each particle system.
+    pub(crate) seed: u32,
+    /// Incremented when the particle system restarts, to clear the particles simulated on the
+    /// GPU.
+    pub(crate) generation: u32,
+    pub(crate) particles: Vec<Particle>,
+}
+
+impl Default for ParticleSystem {
+    fn default() -> Self {
+        Self::new(Handle::default())
+    }
+}
+
+impl ParticleSystem {
+    /// Creates a particle system emitting the particles of the `effect`.
+    pub fn new(effect: Handle<ParticleEffect>) -> Self {
+        Self {
+            effect,
+            emitting: true,
+            paused: false,
+            state: ParticleSystemState::default(),
+        }
+    }
+
+    /// Removes all the particles and starts emitting again, with the burst of the emitter.
+    pub fn restart(&mut self) {
+        self.state = ParticleSystemState {
+            generation: self.state.generation.wrapping_add(1),
+            ..Default::default()
+        };
+    }
+
+    /// Returns the number of particles alive, if they are simulated on the CPU.
+    ///
+    /// The particles simulated on the GPU are never read back.
+    pub fn cpu_particle_count(&self) -> usize {
+        self.state.particles.len()
+    }
+}
+
+/// A particle, laid out as in the buffers of the GPU.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub(crate) struct Particle {
+    pub(crate) position: Vec3,
+    /// The age of the particle, in seconds. The particle is dead once it reaches its lifetime.
+    pub(crate) age: f32,
+    pub(crate) velocity: Vec3,
+    pub(crate) lifetime: f32,
+}
+
+/// A component bundle for entities emitting particles.
+#[derive(Bundle, Clone, Debug, Default)]
+pub struct ParticleSystemBundle {
+    /// The particle system, emitting its effect.
+    pub particle_system: ParticleSystem,
+    /// The local transform of the emitter, relative to its parent.
+    pub transform: Transform,
+    /// The absolute transform of the emitter. This should generally not be written to directly.
+    pub global_transform: GlobalTransform,
+    /// User indication of whether an entity is visible
+    pub visibility: Visibility,
+    /// Inherited visibility of an entity.
+    pub inherited_visibility: InheritedVisibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub view_visibility: ViewVisibility,
+}
+
+/// System emitting the particles of the [`ParticleSystem`]s, and simulating those simulated on
+/// the CPU.
+pub fn update_particle_systems(
+    time: Res<Time>,
+    effects: Res<Assets<ParticleEffect>>,
+    mut particle_systems: Query<(Entity, &mut ParticleSystem, &GlobalTransform)>,
+) {
+    let delta = time.delta_seconds();
+    for (entity, mut particle_system, transform) in &mut particle_systems {
+        let Some(effect) = effects.get(&particle_system.effect) else {
+            continue;
+        };
+        let emitting = particle_system.emitting;
+        let paused = particle_system.paused;
+        let state = &mut particle_system.state;
+        if paused {
+            state.spawn_count = 0;
+            state.delta = 0.0;
+            continue;
+        }
+
+        let mut spawn_count = 0;
+        if emitting {
+            if !state.started {
+                state.started = true;
+                spawn_count += effect.emitter.burst;
+            }
+            state.accumulator += effect.emitter.rate * delta;
+            let spawned = state.accumulator.floor();
+            state.accumulator -= spawned;
+            spawn_count += spawned as u32;
+        }
+        state.delta = delta;
+        state.seed = pcg_hash(entity.index());
+
+        match effect.simulation {
+            ParticleSimulation::Cpu => {
+                simulate_particles(&mut state.particles, effect, delta);
+                let spawn_count =
+                    spawn_count.min(effect.capacity.saturating_sub(state.particles.len() as u32));
+                let (seed, emitted) = (state.seed, state.emitted);
+                state.particles.extend(
+                    (0..spawn_count)
+                        .map(|i| spawn_particle(effect, transform, seed, emitted.wrapping_add(i))),
+                );
+                state.emitted = state.emitted.wrapping_add(spawn_count);
+                state.spawn_count = spawn_count;
+            }
+            ParticleSimulation::Gpu => {
+                // The particles are emitted in a ring buffer, replacing the oldest ones.
+                let capacity = effect.capacity.max(1);
+                let spawn_count = spawn_count.min(capacity);
+                state.spawn_offset = state.emitted % capacity;
+                state.emitted = state.emitted.wrapping_add(spawn_count);
+                state.spawn_count = spawn_count;
+            }
+        }
+    }
+}
+
+/// Moves the `particles` by `delta` seconds, and removes the dead ones. Must match
+/// `simulate.wgsl`.
+fn simulate_particles(particles: &mut Vec<Particle>, effect: &ParticleEffect, delta: f32) {
+    let drag = 1.0 / (1.0 + effect.drag * delta);
+    particles.retain_mut(|particle| {
+        particle.age += delta;
+        particle.velocity = (particle.velocity + effect.acceleration * delta) * drag;
+        particle.position += particle.velocity * delta;
+        particle.age < particle.lifetime
+    });
+}
+
+/// Returns the `number`th particle emitted by a particle system with the given `seed`. Must
+/// match `particle_types.wgsl`.
+pub(crate) fn spawn_particle(
+    effect: &ParticleEffect,
+    transform: &GlobalTransform,
+    seed: u32,
+    number: u32,
+) -> Particle {
+    let random = |stream: usize| random_float(seed, number, stream as u32);
+    let (position, direction) = effect.emitter.shape.sample(std::array::from_fn(random));
+    let speed = lerp(effect.speed.start, effect.speed.end, random(4));
+    Particle {
+        position: transform.transform_point(position),
+        age: 0.0,
+        velocity: transform
+            .affine()
+            .transform_vector3(direction)
+            .normalize_or_zero()
+            * speed,
+        lifetime: lerp(effect.lifetime.start, effect.lifetime.end, random(5)),
+    }
+}
+
+impl EmitterShape {
+    /// Returns the position and direction of a particle emitted with the given random numbers.
+    fn sample(&self, [r0, r1, r2, r3]: [f32; 4]) -> (Vec3, Vec3) {
+        let in_circle = |radius: f32, angle: f32| {
+            let direction = Vec3::new(angle.cos(), angle.sin(), 0.0);
+            (direction * radius * r1.sqrt(), direction)
+        };
+        match *self {
+            EmitterShape::Point => (Vec3::ZERO, in_sphere(r0, r1)),
+            EmitterShape::Circle { radius } => in_circle(radius, r0 * TAU),
+            EmitterShape::Arc { radius, angle } => {
+                in_circle(radius, TAU / 4.0 + (r0 * 2.0 - 1.0) * angle)
+            }
+            EmitterShape::Sphere { radius } => {
+                let direction = in_sphere(r0, r1);
+                (direction * radius * r2.cbrt(), direction)
+            }
+            EmitterShape::Cone { radius, angle } => {
+                let base_angle = r0 * TAU;
+                let distance = radius * r1.sqrt();
+                let position = Vec3::new(base_angle.cos(), 0.0, base_angle.sin()) * distance;
+                let cos_theta = 1.0 - r2 * (1.0 - angle.cos());
+                let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+                let phi = r3 * TAU;
+                let direction = Vec3::new(sin_theta * phi.cos(), cos_theta, sin_theta * phi.sin());
+                (position, direction)
+            }
+        }
+    }
+}
+
+/// Returns a direction uniformly distributed on the unit sphere.
+fn in_sphere(r0: f32, r1: f32) -> Vec3 {
+    let z = r0 * 2.0 - 1.0;
+    let phi = r1 * TAU;
+    let r = (1.0 - z * z).max(0.0).sqrt();
+    Vec3::new(r * phi.cos(), r * phi.sin(), z)
+}
+
+fn lerp(start: f32, end: f32, t: f32) -> f32 {
+    start + (end - start) * t
+}
+
+fn pcg_hash(input: u32) -> u32 {
+    let state = input.wrapping_mul(747796405).wrapping_add(2891336453);
+    let word = ((state >> ((state >> 28) + 4)) ^ state).wrapping_mul(277803737);
+    (word >> 22) ^ word
+}
+
+/// Returns a random number in `[0, 1)` for the `stream`th random value of the `number`th
+/// particle of a particle system with the given `seed`.
+fn random_float(seed: u32, number: u32, stream: u32) -> f32 {
+    let hash = pcg_hash(seed ^ pcg_hash(number.wrapping_mul(8).wrapping_add(stream)));
+    (hash >> 8) as f32 / 16777216.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn simulate_cpu_particles() {
+        let effect = ParticleEffect {
+            capacity: 4,
+            emitter: crate::ParticleEmitter {
+                rate: 10.0,
+                burst: 3,
+                shape: EmitterShape::Point,
+            },
+            lifetime: 0.25..0.25,
+            acceleration: Vec3::new(0.0, -10.0, 0.0),
+            ..Default::default()
+        };
+        let mut particles = vec![spawn_particle(&effect, &GlobalTransform::IDENTITY, 0, 0)];
+        assert_eq!(particles[0].position, Vec3::ZERO);
+        assert!((particles[0].velocity.length() - 1.0).abs() < 1e-5);
+
+        let velocity = particles[0].velocity;
+        simulate_particles(&mut particles, &effect, 0.1);
+        assert_eq!(particles[0].velocity, velocity + Vec3::new(0.0, -1.0, 0.0));
+        simulate_particles(&mut particles, &effect, 0.2);
+        assert!(particles.is_empty());
+    }
+
+    #[test]
+    fn emitter_shapes() {
+        for number in 0..100 {
+            let random = std::array::from_fn(|stream| random_float(7, number, stream as u32));
+            assert!(random.iter().all(|r| (0.0..1.0).contains(r)));
+
+            let (position, direction) = EmitterShape::Circle { radius: 2.0 }.sample(random);
+            assert!(position.length() <= 2.0 && position.z == 0.0);
+            assert!((direction.length() - 1.0).abs() < 1e-5);
+
+            let (_, direction) = EmitterShape::Cone {
+                radius: 1.0,
+                angle: 0.5,
+            }
+            .sample(random);
+            assert!(direction.angle_between(Vec3::Y) <= 0.5 + 1e-5);
+        }
+    }
+}
@@ -0,0 +1,82 @@
+#import bevy_render::view::View
+#import bevy_particles::particle_types::{Particle, ParticleEffect}
+
+@group(0) @binding(0) var<uniform> effect: ParticleEffect;
+@group(0) @binding(1) var<storage, read_write> particles: array<Particle>;
+
+@group(1) @binding(0) var<uniform> view: View;
+#ifdef MULTISAMPLED
+@group(1) @binding(1) var depth_texture: texture_depth_multisampled_2d;
+#else
+@group(1) @binding(1) var depth_texture: texture_depth_2d;
+#endif
+
+// Returns the world position of the surface seen in the `pixel` of the depth texture.
+fn surface_position(pixel: vec2<i32>, size: vec2<i32>) -> vec3<f32> {
+    let depth = textureLoad(depth_texture, pixel, 0);
+    let uv = (vec2<f32>(pixel) + 0.5) / vec2<f32>(size);
+    let ndc = vec3(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, depth);
+    let world_position = view.world_from_clip * vec4(ndc, 1.0);
+    return world_position.xyz / world_position.w;
+}
+
+fn view_z(world_position: vec3<f32>) -> f32 {
+    let view_position = view.view_from_world * vec4(world_position, 1.0);
+    return view_position.z;
+}
+
+// Bounces the particles off the surfaces seen in the depth prepass of the view.
+@compute @workgroup_size(64, 1, 1)
+fn collide(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let index = global_id.x;
+    if index >= effect.capacity {
+        return;
+    }
+    var particle = particles[index];
+    if particle.age >= particle.lifetime {
+        return;
+    }
+
+    let clip_position = view.clip_from_world * vec4(particle.position, 1.0);
+    if clip_position.w <= 0.0 {
+        return;
+    }
+    let ndc = clip_position.xyz / clip_position.w;
+    if any(abs(ndc.xy) > vec2(1.0)) {
+        return;
+    }
+    let size = vec2<i32>(textureDimensions(depth_texture));
+    let uv = ndc.xy * vec2(0.5, -0.5) + 0.5;
+    let pixel = clamp(vec2<i32>(uv * vec2<f32>(size)), vec2(0), size - 2);
+
+    // The particle collides when it is behind the surface, by less than the thickness.
+    // Positions in front of the camera have negative view space z.
+    let surface = surface_position(pixel, size);
+    let penetration = view_z(surface) - view_z(particle.position);
+    if !(penetration > 0.0 && penetration < effect.collision_thickness) {
+        return;
+    }
+
+    // The normal of the surface, from the positions seen in the neighboring pixels.
+    let right = surface_position(pixel + vec2(1, 0), size);
+    let down = surface_position(pixel + vec2(0, 1), size);
+    var normal = cross(down - surface, right - surface);
+    if dot(normal, normal) < 1e-12 {
+        return;
+    }
+    normal = normalize(normal);
+    if dot(normal, view.world_position - surface) < 0.0 {
+        normal = -normal;
+    }
+
+    let normal_speed = dot(particle.velocity, normal);
+    if normal_speed < 0.0 {
+        let normal_velocity = normal * normal_speed;
+        let tangent_velocity = particle.velocity - normal_velocity;
+        particle.velocity = tangent_velocity * (1.0 - effect.collision_friction)
+            - normal_velocity * effect.collision_restitution;
+    }
+    // Moves the particle back onto the surface.
+    particle.position += normal * penetration / max(abs(dot(normal, view.world_from_view[2].xyz)), 0.1);
+    particles[index] = particle;
+}
@@ -0,0 +1,791 @@
+mod node;
+
+use std::num::NonZeroU64;
+
+pub(crate) use node::{
+    CollideParticles, ParticleCollisionNode, ParticleSimulationNode, SimulateParticles,
+};
+
+use bevy_asset::Assets;
+use bevy_color::ColorToComponents;
+use bevy_core_pipeline::{
+    core_2d::{Camera2d, Sort2dKey, Transparent2d, CORE_2D_STENCIL_FORMAT},
+    core_3d::{
+        Transparent3d, ViewDepthStencil, CORE_3D_DEPTH_FORMAT, CORE_3D_DEPTH_STENCIL_FORMAT,
+    },
+    prepass::{DepthPrepass, ViewPrepassTextures},
+};
+use bevy_ecs::{
+    entity::EntityHashMap,
+    prelude::*,
+    query::ROQueryItem,
+    system::{lifetimeless::*, SystemParamItem},
+};
+use bevy_math::{Mat4, Vec2, Vec3, Vec4};
+use bevy_render::{
+    render_phase::{
+        DrawFunctions, PhaseItem, PhaseItemExtraIndex, RenderCommand, RenderCommandResult,
+        SetItemPipeline, TrackedRenderPass, ViewSortedRenderPhases,
+    },
+    render_resource::{binding_types::*, *},
+    renderer::{RenderDevice, RenderQueue},
+    texture::BevyDefault,
+    view::{
+        ExtractedView, Msaa, ViewTarget, ViewUniform, ViewUniformOffset, ViewUniforms,
+        VisibleEntities,
+    },
+    Extract,
+};
+use bevy_transform::components::GlobalTransform;
+
+use crate::{
+    particle_system::{Particle, ParticleSystemState},
+    EmitterShape, ParticleBlendMode, ParticleCollisionCamera, ParticleEffect, ParticleSimulation,
+    ParticleSystem, WithParticleSystem, COLLIDE_SHADER_HANDLE, MAX_PARTICLE_CURVE_KEYS,
+    PARTICLES_SHADER_HANDLE, SIMULATE_SHADER_HANDLE,
+};
+
+/// The workgroup size of the compute shaders simulating the particles.
+const WORKGROUP_SIZE: u32 = 64;
+
+const PARTICLE_SIZE: Option<NonZeroU64> = NonZeroU64::new(std::mem::size_of::<Particle>() as u64);
+
+/// The parameters of a [`ParticleEffect`] and of the emission of a [`ParticleSystem`] for the
+/// current frame, as read by the shaders. Must match `particle_types.wgsl`.
+#[derive(ShaderType, Clone, Default)]
+pub(crate) struct ParticleEffectUniform {
+    world_from_emitter: Mat4,
+    acceleration: Vec3,
+    drag: f32,
+    lifetime: Vec2,
+    speed: Vec2,
+    shape: u32,
+    shape_radius: f32,
+    shape_angle: f32,
+    capacity: u32,
+    spawn_offset: u32,
+    spawn_count: u32,
+    spawn_number: u32,
+    seed: u32,
+    delta: f32,
+    soft_distance: f32,
+    collision_restitution: f32,
+    collision_friction: f32,
+    collision_thickness: f32,
+    color_key_count: u32,
+    size_key_count: u32,
+    color_keys: [Vec4; MAX_PARTICLE_CURVE_KEYS],
+    color_times: [Vec4; MAX_PARTICLE_CURVE_KEYS / 4],
+    size_keys: [Vec4; MAX_PARTICLE_CURVE_KEYS / 4],
+    size_times: [Vec4; MAX_PARTICLE_CURVE_KEYS / 4],
+}
+
+impl ParticleEffectUniform {
+    fn new(
+        effect: &ParticleEffect,
+        state: &ParticleSystemState,
+        transform: &GlobalTransform,
+    ) -> Self {
+        let (shape, shape_radius, shape_angle) = match effect.emitter.shape {
+            EmitterShape::Point => (0, 0.0, 0.0),
+            EmitterShape::Circle { radius } => (1, radius, 0.0),
+            EmitterShape::Arc { radius, angle } => (2, radius, angle),
+            EmitterShape::Sphere { radius } => (3, radius, 0.0),
+            EmitterShape::Cone { radius, angle } => (4, radius, angle),
+        };
+        let collision = effect.collision.unwrap_or_default();
+
+        let mut uniform = Self {
+            world_from_emitter: transform.compute_matrix(),
+            acceleration: effect.acceleration,
+            drag: effect.drag,
+            lifetime: Vec2::new(effect.lifetime.start, effect.lifetime.end),
+            speed: Vec2::new(effect.speed.start, effect.speed.end),
+            shape,
+            shape_radius,
+            shape_angle,
+            capacity: effect.capacity.max(1),
+            spawn_offset: state.spawn_offset,
+            spawn_count: state.spawn_count,
+            spawn_number: state.emitted.wrapping_sub(state.spawn_count),
+            seed: state.seed,
+            delta: state.delta,
+            soft_distance: effect.soft_distance,
+            collision_restitution: collision.restitution,
+            collision_friction: collision.friction,
+            collision_thickness: collision.thickness,
+            color_key_count: 1,
+            size_key_count: 1,
+            color_keys: [Vec4::ONE; MAX_PARTICLE_CURVE_KEYS],
+            color_times: Default::default(),
+            size_keys: [Vec4::ONE; MAX_PARTICLE_CURVE_KEYS / 4],
+            size_times: Default::default(),
+        };
+        let color_keys = effect.color.keys();
+        if !color_keys.is_empty() {
+            uniform.color_key_count = color_keys.len() as u32;
+            for (i, (time, color)) in color_keys.iter().enumerate() {
+                uniform.color_keys[i] = color.to_vec4();
+                uniform.color_times[i / 4][i % 4] = *time;
+            }
+        }
+        let size_keys = effect.size.keys();
+        if !size_keys.is_empty() {
+            uniform.size_key_count = size_keys.len() as u32;
+            for (i, (time, size)) in size_keys.iter().enumerate() {
+                uniform.size_keys[i / 4][i % 4] = *size;
+                uniform.size_times[i / 4][i % 4] = *time;
+            }
+        }
+        uniform
+    }
+}
+
+#[derive(Component)]
+pub(crate) struct ExtractedParticleSystem {
+    uniform: ParticleEffectUniform,
+    simulation: ParticleSimulation,
+    blend_mode: ParticleBlendMode,
+    collision: bool,
+    generation: u32,
+    translation: Vec3,
+    /// The particles simulated on the CPU.
+    particles: Vec<Particle>,
+}
+
+pub(crate) fn extract_particle_systems(
+    mut commands: Commands,
+    mut previous_len: Local<usize>,
+    effects: Extract<Res<Assets<ParticleEffect>>>,
+    particle_systems: Extract<Query<(Entity, &ParticleSystem, &GlobalTransform)>>,
+) {
+    let mut values = Vec::with_capacity(*previous_len);
+    for (entity, particle_system, transform) in &particle_systems {
+        let Some(effect) = effects.get(&particle_system.effect) else {
+            continue;
+        };
+        let state = &particle_system.state;
+        values.push((
+            entity,
+            ExtractedParticleSystem {
+                uniform: ParticleEffectUniform::new(effect, state, transform),
+                simulation: effect.simulation,
+                blend_mode: effect.blend_mode,
+                collision: effect.collision.is_some(),
+                generation: state.generation,
+                translation: transform.translation(),
+                particles: match effect.simulation {
+                    ParticleSimulation::Cpu => state.particles.clone(),
+                    ParticleSimulation::Gpu => Vec::new(),
+                },
+            },
+        ));
+    }
+    *previous_len = values.len();
+    commands.insert_or_spawn_batch(values);
+}
+
+/// The buffers of a [`ParticleSystem`], kept from frame to frame.
+pub(crate) struct GpuParticleSystem {
+    capacity: u32,
+    simulation: ParticleSimulation,
+    collision: bool,
+    generation: u32,
+    particles: Buffer,
+    effect: UniformBuffer<ParticleEffectUniform>,
+    /// The number of particles to draw: the particles alive when they are simulated on the CPU,
+    /// and the whole buffer when they are simulated on the GPU.
+    instance_count: u32,
+    simulate_bind_group: Option<BindGroup>,
+    draw_bind_group: Option<BindGroup>,
+}
+
+#[derive(Resource, Default)]
+pub(crate) struct GpuParticleSystems(EntityHashMap<GpuParticleSystem>);
+
+pub(crate) fn prepare_particle_buffers(
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut gpu_particle_systems: ResMut<GpuParticleSystems>,
+    particle_systems: Query<(Entity, &ExtractedParticleSystem)>,
+) {
+    gpu_particle_systems
+        .0
+        .retain(|entity, _| particle_systems.contains(*entity));
+
+    for (entity, extracted) in &particle_systems {
+        let capacity = extracted.uniform.capacity;
+        let outdated = gpu_particle_systems
+            .0
+            .get(&entity)
+            .filter(|gpu| {
+                gpu.capacity == capacity
+                    && gpu.simulation == extracted.simulation
+                    && gpu.generation == extracted.generation
+            })
+            .is_none();
+        if outdated {
+            // New buffers are zeroed, which makes all their particles dead.
+            let particles = render_device.create_buffer(&BufferDescriptor {
+                label: Some("particles_buffer"),
+                size: capacity as u64 * std::mem::size_of::<Particle>() as u64,
+                usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            let mut effect = UniformBuffer::default();
+            effect.set_label(Some("particle_effect_uniform"));
+            gpu_particle_systems.0.insert(
+                entity,
+                GpuParticleSystem {
+                    capacity,
+                    simulation: extracted.simulation,
+                    collision: extracted.collision,
+                    generation: extracted.generation,
+                    particles,
+                    effect,
+                    instance_count: 0,
+                    simulate_bind_group: None,
+                    draw_bind_group: None,
+                },
+            );
+        }
+        let gpu_particle_system = gpu_particle_systems.0.get_mut(&entity).unwrap();
+
+        gpu_particle_system.collision = extracted.collision;
+        gpu_particle_system.effect.set(extracted.uniform.clone());
+        gpu_particle_system
+            .effect
+            .write_buffer(&render_device, &render_queue);
+        gpu_particle_system.instance_count = match extracted.simulation {
+            ParticleSimulation::Cpu => {
+                let particles =
+                    &extracted.particles[..extracted.particles.len().min(capacity as usize)];
+                if !particles.is_empty() {
+                    render_queue.write_buffer(
+                        &gpu_particle_system.particles,
+                        0,
+                        bytemuck::cast_slice(particles),
+                    );
+                }
+                particles.len() as u32
+            }
+            ParticleSimulation::Gpu => capacity,
+        };
+    }
+}
+
+pub(crate) fn prepare_particle_bind_groups(
+    render_device: Res<RenderDevice>,
+    particle_pipeline: Res<ParticlePipeline>,
+    simulation_pipeline: Res<ParticleSimulationPipeline>,
+    mut gpu_particle_systems: ResMut<GpuParticleSystems>,
+) {
+    for gpu_particle_system in gpu_particle_systems.0.values_mut() {
+        if gpu_particle_system.draw_bind_group.is_some() {
+            continue;
+        }
+        let Some(effect) = gpu_particle_system.effect.binding() else {
+            continue;
+        };
+        let particles = gpu_particle_system.particles.as_entire_binding();
+        gpu_particle_system.draw_bind_group = Some(render_device.create_bind_group(
+            "particle_system_bind_group",
+            &particle_pipeline.particles_layout,
+            &BindGroupEntries::sequential((effect.clone(), particles.clone())),
+        ));
+        if gpu_particle_system.simulation == ParticleSimulation::Gpu {
+            gpu_particle_system.simulate_bind_group = Some(render_device.create_bind_group(
+                "particle_simulation_bind_group",
+                &simulation_pipeline.layout,
+                &BindGroupEntries::sequential((effect, particles)),
+            ));
+        }
+    }
+}
+
+/// The bind group of the view the particles are drawn in.
+#[derive(Component)]
+pub(crate) struct ParticleViewBindGroup {
+    value: BindGroup,
+}
+
+/// The bind group of the view of a [`ParticleCollisionCamera`], whose depth prepass the
+/// particles collide with.
+#[derive(Component)]
+pub(crate) struct ParticleCollisionViewBindGroup {
+    value: BindGroup,
+    multisampled: bool,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn prepare_particle_view_bind_groups(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    particle_pipeline: Res<ParticlePipeline>,
+    simulation_pipeline: Res<ParticleSimulationPipeline>,
+    view_uniforms: Res<ViewUniforms>,
+    msaa: Res<Msaa>,
+    views: Query<
+        (
+            Entity,
+            Option<&ViewPrepassTextures>,
+            Has<DepthPrepass>,
+            Has<ParticleCollisionCamera>,
+        ),
+        With<ExtractedView>,
+    >,
+) {
+    let Some(view_binding) = view_uniforms.uniforms.binding() else {
+        return;
+    };
+    let multisampled = msaa.samples() > 1;
+
+    for (entity, prepass_textures, depth_prepass, collision_camera) in &views {
+        let depth_view = prepass_textures.and_then(ViewPrepassTextures::depth_view);
+        let value = match (depth_prepass, depth_view) {
+            (false, _) => render_device.create_bind_group(
+                "particle_view_bind_group",
+                &particle_pipeline.view_layout,
+                &BindGroupEntries::single(view_binding.clone()),
+            ),
+            (true, Some(depth_view)) => render_device.create_bind_group(
+                "particle_view_bind_group",
+                particle_pipeline.soft_view_layout(multisampled),
+                &BindGroupEntries::sequential((view_binding.clone(), depth_view)),
+            ),
+            // The pipelines of the view read its depth prepass, which isn't ready.
+            (true, None) => continue,
+        };
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.insert(ParticleViewBindGroup { value });
+
+        if let (true, Some(depth_view)) = (collision_camera, depth_view) {
+            entity_commands.insert(ParticleCollisionViewBindGroup {
+                value: render_device.create_bind_group(
+                    "particle_collision_view_bind_group",
+                    simulation_pipeline.collision_view_layout(multisampled),
+                    &BindGroupEntries::sequential((view_binding.clone(), depth_view)),
+                ),
+                multisampled,
+            });
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn queue_particle_systems(
+    transparent_2d_draw_functions: Res<DrawFunctions<Transparent2d>>,
+    transparent_3d_draw_functions: Res<DrawFunctions<Transparent3d>>,
+    particle_pipeline: Res<ParticlePipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<ParticlePipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    msaa: Res<Msaa>,
+    particle_systems: Query<&ExtractedParticleSystem>,
+    mut transparent_2d_phases: ResMut<ViewSortedRenderPhases<Transparent2d>>,
+    mut transparent_3d_phases: ResMut<ViewSortedRenderPhases<Transparent3d>>,
+    views: Query<(
+        Entity,
+        &ExtractedView,
+        &VisibleEntities,
+        Option<&Camera2d>,
+        Has<ViewDepthStencil>,
+        Has<DepthPrepass>,
+    )>,
+) {
+    let draw_particles_2d = transparent_2d_draw_functions
+        .read()
+        .id::<DrawParticleSystem>();
+    let draw_particles_3d = transparent_3d_draw_functions
+        .read()
+        .id::<DrawParticleSystem>();
+
+    for (view_entity, view, visible_entities, camera_2d, depth_stencil, depth_prepass) in &views {
+        let view_key = ParticlePipelineKey {
+            hdr: view.hdr,
+            msaa_samples: msaa.samples(),
+            core_3d: false,
+            depth_stencil: false,
+            soft_particles: false,
+            blend_mode: ParticleBlendMode::Blend,
+        };
+        let visible_particle_systems = visible_entities
+            .iter::<WithParticleSystem>()
+            .filter_map(|entity| Some((*entity, particle_systems.get(*entity).ok()?)));
+
+        if let Some(transparent_phase) = transparent_3d_phases.get_mut(&view_entity) {
+            let view_key = ParticlePipelineKey {
+                core_3d: true,
+                depth_stencil,
+                soft_particles: depth_prepass,
+                ..view_key
+            };
+            let rangefinder = view.rangefinder3d();
+            for (entity, particle_system) in visible_particle_systems {
+                let pipeline = pipelines.specialize(
+                    &pipeline_cache,
+                    &particle_pipeline,
+                    ParticlePipelineKey {
+                        blend_mode: particle_system.blend_mode,
+                        ..view_key
+                    },
+                );
+                transparent_phase.add(Transparent3d {
+                    entity,
+                    draw_function: draw_particles_3d,
+                    pipeline,
+                    distance: rangefinder.distance_translation(&particle_system.translation),
+                    batch_range: 0..1,
+                    extra_index: PhaseItemExtraIndex::NONE,
+                });
+            }
+        } else if let Some(transparent_phase) = transparent_2d_phases.get_mut(&view_entity) {
+            let view_key = ParticlePipelineKey {
+                depth_stencil: camera_2d.is_some_and(|camera_2d| camera_2d.stencil),
+                ..view_key
+            };
+            for (entity, particle_system) in visible_particle_systems {
+                let pipeline = pipelines.specialize(
+                    &pipeline_cache,
+                    &particle_pipeline,
+                    ParticlePipelineKey {
+                        blend_mode: particle_system.blend_mode,
+                        ..view_key
+                    },
+                );
+                transparent_phase.add(Transparent2d {
+                    sort_key: Sort2dKey::from_depth(particle_system.translation.z),
+                    entity,
+                    pipeline,
+                    draw_function: draw_particles_2d,
+                    batch_range: 0..1,
+                    extra_index: PhaseItemExtraIndex::NONE,
+                });
+            }
+        }
+    }
+}
+
+#[derive(Resource)]
+pub(crate) struct ParticlePipeline {
+    view_layout: BindGroupLayout,
+    /// The layout of the views with a depth prepass, for soft particles.
+    soft_view_layout: BindGroupLayout,
+    soft_view_layout_multisampled: BindGroupLayout,
+    particles_layout: BindGroupLayout,
+}
+
+impl ParticlePipeline {
+    fn soft_view_layout(&self, multisampled: bool) -> &BindGroupLayout {
+        if multisampled {
+            &self.soft_view_layout_multisampled
+        } else {
+            &self.soft_view_layout
+        }
+    }
+}
+
+impl FromWorld for ParticlePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let visibility = ShaderStages::VERTEX_FRAGMENT;
+
+        Self {
+            view_layout: render_device.create_bind_group_layout(
+                "particle_view_layout",
+                &BindGroupLayoutEntries::single(visibility, uniform_buffer::<ViewUniform>(true)),
+            ),
+            soft_view_layout: render_device.create_bind_group_layout(
+                "particle_soft_view_layout",
+                &BindGroupLayoutEntries::sequential(
+                    visibility,
+                    (uniform_buffer::<ViewUniform>(true), texture_depth_2d()),
+                ),
+            ),
+            soft_view_layout_multisampled: render_device.create_bind_group_layout(
+                "particle_soft_view_layout_multisampled",
+                &BindGroupLayoutEntries::sequential(
+                    visibility,
+                    (
+                        uniform_buffer::<ViewUniform>(true),
+                        texture_depth_2d_multisampled(),
+                    ),
+                ),
+            ),
+            particles_layout: render_device.create_bind_group_layout(
+                "particle_system_layout",
+                &BindGroupLayoutEntries::sequential(
+                    visibility,
+                    (
+                        uniform_buffer::<ParticleEffectUniform>(false),
+                        storage_buffer_read_only_sized(false, PARTICLE_SIZE),
+                    ),
+                ),
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct ParticlePipelineKey {
+    hdr: bool,
+    msaa_samples: u32,
+    /// Whether the view is drawn by the main 3d pass, or else the main 2d pass.
+    core_3d: bool,
+    /// Whether the depth texture of the view has a stencil aspect, see [`ViewDepthStencil`] and
+    /// [`Camera2d::stencil`].
+    depth_stencil: bool,
+    /// Whether the view has a depth prepass the particles fade out against.
+    soft_particles: bool,
+    blend_mode: ParticleBlendMode,
+}
+
+impl SpecializedRenderPipeline for ParticlePipeline {
+    type Key = ParticlePipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut shader_defs = Vec::new();
+        let view_layout = if key.soft_particles {
+            shader_defs.push("SOFT_PARTICLES".into());
+            if key.msaa_samples > 1 {
+                shader_defs.push("MULTISAMPLED".into());
+            }
+            self.soft_view_layout(key.msaa_samples > 1)
+        } else {
+            &self.view_layout
+        };
+
+        let format = if key.hdr {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+
+        let blend = match key.blend_mode {
+            ParticleBlendMode::Blend => BlendState::ALPHA_BLENDING,
+            ParticleBlendMode::Additive => BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::SrcAlpha,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+                alpha: BlendComponent {
+                    src_factor: BlendFactor::Zero,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+            },
+        };
+
+        let depth_stencil = if key.core_3d {
+            Some(DepthStencilState {
+                format: if key.depth_stencil {
+                    CORE_3D_DEPTH_STENCIL_FORMAT
+                } else {
+                    CORE_3D_DEPTH_FORMAT
+                },
+                depth_write_enabled: false,
+                depth_compare: CompareFunction::GreaterEqual,
+                stencil: StencilState::default(),
+                bias: DepthBiasState::default(),
+            })
+        } else {
+            key.depth_stencil.then_some(DepthStencilState {
+                format: CORE_2D_STENCIL_FORMAT,
+                depth_write_enabled: false,
+                depth_compare: CompareFunction::Always,
+                stencil: StencilState::default(),
+                bias: DepthBiasState::default(),
+            })
+        };
+
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: PARTICLES_SHADER_HANDLE,
+                entry_point: "vertex".into(),
+                shader_defs: shader_defs.clone(),
+                buffers: Vec::new(),
+            },
+            fragment: Some(FragmentState {
+                shader: PARTICLES_SHADER_HANDLE,
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: Some(blend),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            layout: vec![view_layout.clone(), self.particles_layout.clone()],
+            primitive: PrimitiveState::default(),
+            depth_stencil,
+            multisample: MultisampleState {
+                count: key.msaa_samples,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            label: Some("particle_pipeline".into()),
+            push_constant_ranges: Vec::new(),
+        }
+    }
+}
+
+#[derive(Resource)]
+pub(crate) struct ParticleSimulationPipeline {
+    layout: BindGroupLayout,
+    collision_view_layout: BindGroupLayout,
+    collision_view_layout_multisampled: BindGroupLayout,
+    simulate: CachedComputePipelineId,
+    collide: CachedComputePipelineId,
+    collide_multisampled: CachedComputePipelineId,
+}
+
+impl ParticleSimulationPipeline {
+    fn collision_view_layout(&self, multisampled: bool) -> &BindGroupLayout {
+        if multisampled {
+            &self.collision_view_layout_multisampled
+        } else {
+            &self.collision_view_layout
+        }
+    }
+}
+
+impl FromWorld for ParticleSimulationPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let layout = render_device.create_bind_group_layout(
+            "particle_simulation_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    uniform_buffer::<ParticleEffectUniform>(false),
+                    storage_buffer_sized(false, PARTICLE_SIZE),
+                ),
+            ),
+        );
+        let collision_view_layout = render_device.create_bind_group_layout(
+            "particle_collision_view_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (uniform_buffer::<ViewUniform>(true), texture_depth_2d()),
+            ),
+        );
+        let collision_view_layout_multisampled = render_device.create_bind_group_layout(
+            "particle_collision_view_layout_multisampled",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    uniform_buffer::<ViewUniform>(true),
+                    texture_depth_2d_multisampled(),
+                ),
+            ),
+        );
+
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let simulate = pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+            label: Some("particle_simulation_pipeline".into()),
+            layout: vec![layout.clone()],
+            push_constant_ranges: Vec::new(),
+            shader: SIMULATE_SHADER_HANDLE,
+            shader_defs: Vec::new(),
+            entry_point: "simulate".into(),
+        });
+        let queue_collide = |view_layout: &BindGroupLayout, shader_defs| {
+            pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+                label: Some("particle_collision_pipeline".into()),
+                layout: vec![layout.clone(), view_layout.clone()],
+                push_constant_ranges: Vec::new(),
+                shader: COLLIDE_SHADER_HANDLE,
+                shader_defs,
+                entry_point: "collide".into(),
+            })
+        };
+        let collide = queue_collide(&collision_view_layout, Vec::new());
+        let collide_multisampled = queue_collide(
+            &collision_view_layout_multisampled,
+            vec!["MULTISAMPLED".into()],
+        );
+
+        Self {
+            layout,
+            collision_view_layout,
+            collision_view_layout_multisampled,
+            simulate,
+            collide,
+            collide_multisampled,
+        }
+    }
+}
+
+pub(crate) type DrawParticleSystem = (
+    SetItemPipeline,
+    SetParticleViewBindGroup<0>,
+    SetParticleSystemBindGroup<1>,
+    DrawParticles,
+);
+
+pub(crate) struct SetParticleViewBindGroup<const I: usize>;
+impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetParticleViewBindGroup<I> {
+    type Param = ();
+    type ViewQuery = (Read<ViewUniformOffset>, Read<ParticleViewBindGroup>);
+    type ItemQuery = ();
+
+    fn render<'w>(
+        _item: &P,
+        (view_uniform, view_bind_group): ROQueryItem<'w, Self::ViewQuery>,
+        _entity: Option<()>,
+        _param: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        pass.set_bind_group(I, &view_bind_group.value, &[view_uniform.offset]);
+        RenderCommandResult::Success
+    }
+}
+
+pub(crate) struct SetParticleSystemBindGroup<const I: usize>;
+impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetParticleSystemBindGroup<I> {
+    type Param = SRes<GpuParticleSystems>;
+    type ViewQuery = ();
+    type ItemQuery = ();
+
+    fn render<'w>(
+        item: &P,
+        _view: (),
+        _entity: Option<()>,
+        gpu_particle_systems: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(bind_group) = gpu_particle_systems
+            .into_inner()
+            .0
+            .get(&item.entity())
+            .and_then(|gpu_particle_system| gpu_particle_system.draw_bind_group.as_ref())
+        else {
+            return RenderCommandResult::Failure;
+        };
+        pass.set_bind_group(I, bind_group, &[]);
+        RenderCommandResult::Success
+    }
+}
+
+pub(crate) struct DrawParticles;
+impl<P: PhaseItem> RenderCommand<P> for DrawParticles {
+    type Param = SRes<GpuParticleSystems>;
+    type ViewQuery = ();
+    type ItemQuery = ();
+
+    fn render<'w>(
+        item: &P,
+        _view: (),
+        _entity: Option<()>,
+        gpu_particle_systems: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(gpu_particle_system) = gpu_particle_systems.into_inner().0.get(&item.entity())
+        else {
+            return RenderCommandResult::Failure;
+        };
+        // Each particle is a quad of two triangles.
+        pass.draw(0..6, 0..gpu_particle_system.instance_count);
+        RenderCommandResult::Success
+    }
+}
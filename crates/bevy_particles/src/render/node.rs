@@ -0,0 +1,121 @@
+use bevy_ecs::{query::QueryItem, system::lifetimeless::Read, world::World};
+use bevy_render::{
+    render_graph::{Node, NodeRunError, RenderGraphContext, RenderLabel, ViewNode},
+    render_resource::{ComputePassDescriptor, PipelineCache},
+    renderer::RenderContext,
+    view::ViewUniformOffset,
+};
+
+use super::{
+    GpuParticleSystems, ParticleCollisionViewBindGroup, ParticleSimulationPipeline, WORKGROUP_SIZE,
+};
+use crate::ParticleSimulation;
+
+#[derive(RenderLabel, Debug, Clone, Hash, PartialEq, Eq)]
+pub(crate) struct SimulateParticles;
+
+#[derive(RenderLabel, Debug, Clone, Hash, PartialEq, Eq)]
+pub(crate) struct CollideParticles;
+
+/// Emits and moves the particles simulated on the GPU, once per frame before any camera is
+/// rendered.
+#[derive(Default)]
+pub(crate) struct ParticleSimulationNode;
+
+impl Node for ParticleSimulationNode {
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let simulation_pipeline = world.resource::<ParticleSimulationPipeline>();
+        let Some(pipeline) = pipeline_cache.get_compute_pipeline(simulation_pipeline.simulate)
+        else {
+            return Ok(());
+        };
+
+        let mut compute_pass =
+            render_context
+                .command_encoder()
+                .begin_compute_pass(&ComputePassDescriptor {
+                    label: Some("particle_simulation_pass"),
+                    timestamp_writes: None,
+                });
+        compute_pass.set_pipeline(pipeline);
+        for gpu_particle_system in world.resource::<GpuParticleSystems>().0.values() {
+            if gpu_particle_system.simulation != ParticleSimulation::Gpu {
+                continue;
+            }
+            let Some(bind_group) = &gpu_particle_system.simulate_bind_group else {
+                continue;
+            };
+            compute_pass.set_bind_group(0, bind_group, &[]);
+            compute_pass.dispatch_workgroups(
+                gpu_particle_system.capacity.div_ceil(WORKGROUP_SIZE),
+                1,
+                1,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Bounces the particles simulated on the GPU off the depth prepass of the views with a
+/// [`ParticleCollisionCamera`](crate::ParticleCollisionCamera), before their main pass.
+#[derive(Default)]
+pub(crate) struct ParticleCollisionNode;
+
+impl ViewNode for ParticleCollisionNode {
+    type ViewQuery = (
+        Read<ViewUniformOffset>,
+        Read<ParticleCollisionViewBindGroup>,
+    );
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (view_uniform_offset, view_bind_group): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let simulation_pipeline = world.resource::<ParticleSimulationPipeline>();
+        let pipeline_id = if view_bind_group.multisampled {
+            simulation_pipeline.collide_multisampled
+        } else {
+            simulation_pipeline.collide
+        };
+        let Some(pipeline) = pipeline_cache.get_compute_pipeline(pipeline_id) else {
+            return Ok(());
+        };
+
+        let mut compute_pass =
+            render_context
+                .command_encoder()
+                .begin_compute_pass(&ComputePassDescriptor {
+                    label: Some("particle_collision_pass"),
+                    timestamp_writes: None,
+                });
+        compute_pass.set_pipeline(pipeline);
+        compute_pass.set_bind_group(1, &view_bind_group.value, &[view_uniform_offset.offset]);
+        for gpu_particle_system in world.resource::<GpuParticleSystems>().0.values() {
+            if !gpu_particle_system.collision {
+                continue;
+            }
+            let Some(bind_group) = &gpu_particle_system.simulate_bind_group else {
+                continue;
+            };
+            compute_pass.set_bind_group(0, bind_group, &[]);
+            compute_pass.dispatch_workgroups(
+                gpu_particle_system.capacity.div_ceil(WORKGROUP_SIZE),
+                1,
+                1,
+            );
+        }
+
+        Ok(())
+    }
+}
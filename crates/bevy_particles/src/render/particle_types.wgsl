@@ -0,0 +1,154 @@
+#define_import_path bevy_particles::particle_types
+
+const PI: f32 = 3.141592653589793;
+const TAU: f32 = 6.283185307179586;
+
+const SHAPE_POINT: u32 = 0u;
+const SHAPE_CIRCLE: u32 = 1u;
+const SHAPE_ARC: u32 = 2u;
+const SHAPE_SPHERE: u32 = 3u;
+const SHAPE_CONE: u32 = 4u;
+
+struct Particle {
+    position: vec3<f32>,
+    // The particle is dead once its age reaches its lifetime.
+    age: f32,
+    velocity: vec3<f32>,
+    lifetime: f32,
+}
+
+// Must match `ParticleEffectUniform`.
+struct ParticleEffect {
+    world_from_emitter: mat4x4<f32>,
+    acceleration: vec3<f32>,
+    drag: f32,
+    lifetime: vec2<f32>,
+    speed: vec2<f32>,
+    shape: u32,
+    shape_radius: f32,
+    shape_angle: f32,
+    capacity: u32,
+    // The particles emitted this frame replace those at `spawn_offset` and after, wrapping
+    // around the buffer.
+    spawn_offset: u32,
+    spawn_count: u32,
+    // The number of the first particle emitted this frame, since the particle system started.
+    spawn_number: u32,
+    seed: u32,
+    delta: f32,
+    soft_distance: f32,
+    collision_restitution: f32,
+    collision_friction: f32,
+    collision_thickness: f32,
+    color_key_count: u32,
+    size_key_count: u32,
+    color_keys: array<vec4<f32>, 8>,
+    color_times: array<vec4<f32>, 2>,
+    size_keys: array<vec4<f32>, 2>,
+    size_times: array<vec4<f32>, 2>,
+}
+
+// Must match `pcg_hash` in `particle_system.rs`.
+fn pcg_hash(input: u32) -> u32 {
+    let state = input * 747796405u + 2891336453u;
+    let word = ((state >> ((state >> 28u) + 4u)) ^ state) * 277803737u;
+    return (word >> 22u) ^ word;
+}
+
+// Must match `random_float` in `particle_system.rs`.
+fn random_float(seed: u32, number: u32, stream: u32) -> f32 {
+    let hash = pcg_hash(seed ^ pcg_hash(number * 8u + stream));
+    return f32(hash >> 8u) / 16777216.0;
+}
+
+fn in_sphere(r0: f32, r1: f32) -> vec3<f32> {
+    let z = r0 * 2.0 - 1.0;
+    let phi = r1 * TAU;
+    let r = sqrt(max(1.0 - z * z, 0.0));
+    return vec3(r * cos(phi), r * sin(phi), z);
+}
+
+// Returns the `number`th particle emitted by the particle system. Must match `spawn_particle` in
+// `particle_system.rs`.
+fn spawn_particle(effect: ParticleEffect, number: u32) -> Particle {
+    let r0 = random_float(effect.seed, number, 0u);
+    let r1 = random_float(effect.seed, number, 1u);
+    let r2 = random_float(effect.seed, number, 2u);
+    let r3 = random_float(effect.seed, number, 3u);
+
+    var position = vec3(0.0);
+    var direction = vec3(0.0);
+    switch effect.shape {
+        case SHAPE_CIRCLE, SHAPE_ARC: {
+            var angle = r0 * TAU;
+            if effect.shape == SHAPE_ARC {
+                angle = TAU / 4.0 + (r0 * 2.0 - 1.0) * effect.shape_angle;
+            }
+            direction = vec3(cos(angle), sin(angle), 0.0);
+            position = direction * effect.shape_radius * sqrt(r1);
+        }
+        case SHAPE_SPHERE: {
+            direction = in_sphere(r0, r1);
+            position = direction * effect.shape_radius * pow(r2, 1.0 / 3.0);
+        }
+        case SHAPE_CONE: {
+            let base_angle = r0 * TAU;
+            let distance = effect.shape_radius * sqrt(r1);
+            position = vec3(cos(base_angle), 0.0, sin(base_angle)) * distance;
+            let cos_theta = 1.0 - r2 * (1.0 - cos(effect.shape_angle));
+            let sin_theta = sqrt(max(1.0 - cos_theta * cos_theta, 0.0));
+            let phi = r3 * TAU;
+            direction = vec3(sin_theta * cos(phi), cos_theta, sin_theta * sin(phi));
+        }
+        default: {
+            direction = in_sphere(r0, r1);
+        }
+    }
+
+    let speed = mix(effect.speed.x, effect.speed.y, random_float(effect.seed, number, 4u));
+    var world_direction = (effect.world_from_emitter * vec4(direction, 0.0)).xyz;
+    if dot(world_direction, world_direction) > 0.0 {
+        world_direction = normalize(world_direction);
+    }
+
+    var particle: Particle;
+    particle.position = (effect.world_from_emitter * vec4(position, 1.0)).xyz;
+    particle.age = 0.0;
+    particle.velocity = world_direction * speed;
+    particle.lifetime = mix(effect.lifetime.x, effect.lifetime.y, random_float(effect.seed, number, 5u));
+    return particle;
+}
+
+// Returns the color of a particle at time `t` of its lifetime, between `0.0` and `1.0`.
+fn sample_color(effect: ParticleEffect, t: f32) -> vec4<f32> {
+    // Arrays can only be indexed dynamically through variables.
+    var keys = effect.color_keys;
+    var times = effect.color_times;
+    var color = keys[0];
+    for (var key = 1u; key < effect.color_key_count; key += 1u) {
+        let start = times[(key - 1u) / 4u][(key - 1u) % 4u];
+        let end = times[key / 4u][key % 4u];
+        if t <= start {
+            break;
+        }
+        color = mix(keys[key - 1u], keys[key], saturate((t - start) / max(end - start, 1e-6)));
+    }
+    return color;
+}
+
+// Returns the size of a particle at time `t` of its lifetime, between `0.0` and `1.0`.
+fn sample_size(effect: ParticleEffect, t: f32) -> f32 {
+    var keys = effect.size_keys;
+    var times = effect.size_times;
+    var size = keys[0][0];
+    for (var key = 1u; key < effect.size_key_count; key += 1u) {
+        let start = times[(key - 1u) / 4u][(key - 1u) % 4u];
+        let end = times[key / 4u][key % 4u];
+        if t <= start {
+            break;
+        }
+        let previous = keys[(key - 1u) / 4u][(key - 1u) % 4u];
+        size = mix(previous, keys[key / 4u][key % 4u], saturate((t - start) / max(end - start, 1e-6)));
+    }
+    return size;
+}
@@ -0,0 +1,80 @@
+#import bevy_render::view::View
+#import bevy_particles::particle_types::{Particle, ParticleEffect, sample_color, sample_size}
+
+@group(0) @binding(0) var<uniform> view: View;
+#ifdef SOFT_PARTICLES
+#ifdef MULTISAMPLED
+@group(0) @binding(1) var depth_texture: texture_depth_multisampled_2d;
+#else
+@group(0) @binding(1) var depth_texture: texture_depth_2d;
+#endif
+#endif
+
+@group(1) @binding(0) var<uniform> effect: ParticleEffect;
+@group(1) @binding(1) var<storage, read> particles: array<Particle>;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    // The position in the quad, from -1 to 1.
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+};
+
+// Draws each particle as a quad facing the camera, of 6 vertices.
+@vertex
+fn vertex(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32,
+) -> VertexOutput {
+    var out: VertexOutput;
+    let particle = particles[instance_index];
+    if particle.age >= particle.lifetime {
+        // Dead particles are degenerate quads, drawing nothing.
+        out.position = vec4(0.0, 0.0, 0.0, 1.0);
+        return out;
+    }
+
+    var corners = array<vec2<f32>, 6>(
+        vec2(-1.0, -1.0),
+        vec2(1.0, -1.0),
+        vec2(1.0, 1.0),
+        vec2(-1.0, -1.0),
+        vec2(1.0, 1.0),
+        vec2(-1.0, 1.0),
+    );
+    let corner = corners[vertex_index % 6u];
+
+    let t = particle.age / particle.lifetime;
+    let radius = sample_size(effect, t) * 0.5;
+    let right = view.world_from_view[0].xyz;
+    let up = view.world_from_view[1].xyz;
+    let world_position = particle.position + (right * corner.x + up * corner.y) * radius;
+
+    out.position = view.clip_from_world * vec4(world_position, 1.0);
+    out.uv = corner;
+    out.color = sample_color(effect, t);
+    return out;
+}
+
+#ifdef SOFT_PARTICLES
+fn view_z(ndc_depth: f32) -> f32 {
+    let view_position = view.view_from_clip * vec4(0.0, 0.0, ndc_depth, 1.0);
+    return view_position.z / view_position.w;
+}
+#endif
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    // A round dot, fading out towards its edge.
+    var alpha = in.color.a * (1.0 - smoothstep(0.5, 1.0, length(in.uv)));
+
+#ifdef SOFT_PARTICLES
+    if effect.soft_distance > 0.0 {
+        let scene_depth = textureLoad(depth_texture, vec2<i32>(in.position.xy), 0);
+        let distance = view_z(in.position.z) - view_z(scene_depth);
+        alpha *= saturate(distance / effect.soft_distance);
+    }
+#endif
+
+    return vec4(in.color.rgb, alpha);
+}
@@ -0,0 +1,30 @@
+#import bevy_particles::particle_types::{Particle, ParticleEffect, spawn_particle}
+
+@group(0) @binding(0) var<uniform> effect: ParticleEffect;
+@group(0) @binding(1) var<storage, read_write> particles: array<Particle>;
+
+// Emits and moves the particles of a particle system simulated on the GPU. Must match
+// `simulate_particles` in `particle_system.rs`.
+@compute @workgroup_size(64, 1, 1)
+fn simulate(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let index = global_id.x;
+    if index >= effect.capacity {
+        return;
+    }
+
+    // The particles are emitted in a ring buffer, replacing the oldest ones.
+    let spawn_index = (index + effect.capacity - effect.spawn_offset) % effect.capacity;
+    if spawn_index < effect.spawn_count {
+        particles[index] = spawn_particle(effect, effect.spawn_number + spawn_index);
+        return;
+    }
+
+    var particle = particles[index];
+    if particle.age >= particle.lifetime {
+        return;
+    }
+    particle.age += effect.delta;
+    particle.velocity = (particle.velocity + effect.acceleration * effect.delta) / (1.0 + effect.drag * effect.delta);
+    particle.position += particle.velocity * effect.delta;
+    particles[index] = particle;
+}
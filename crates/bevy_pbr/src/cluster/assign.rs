@@ -16,8 +16,8 @@ use bevy_transform::components::GlobalTransform;
 use bevy_utils::tracing::warn;
 
 use crate::{
-    ClusterConfig, ClusterFarZMode, Clusters, GlobalVisibleClusterableObjects, PointLight,
-    SpotLight, ViewClusterBindings, VisibleClusterableObjects,
+    ClusterConfig, ClusterFarZMode, ClusterZSlicing, Clusters, GlobalVisibleClusterableObjects,
+    PointLight, SpotLight, ViewClusterBindings, VisibleClusterableObjects,
     CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT, MAX_UNIFORM_BUFFER_CLUSTERABLE_OBJECTS,
 };
 
@@ -221,6 +221,9 @@ pub(crate) fn assign_objects_to_clusters(
         let view_from_world_scale_max = view_from_world_scale.abs().max_element();
         let view_from_world = world_from_view.inverse();
         let is_orthographic = camera.clip_from_view().w_axis.w == 1.0;
+        // NOTE: Orthographic projections always use linear depth slices
+        let linear_z_slicing = is_orthographic || config.z_slicing() == ClusterZSlicing::Linear;
+        let max_objects_per_cluster = config.max_objects_per_cluster();
 
         let far_z = match config.far_z_mode() {
             ClusterFarZMode::MaxClusterableObjectRange => {
@@ -236,8 +239,12 @@ pub(crate) fn assign_objects_to_clusters(
             }
             ClusterFarZMode::Constant(far) => far,
         };
-        let first_slice_depth = match (is_orthographic, requested_cluster_dimensions.z) {
-            (true, _) => {
+        let first_slice_depth = match (
+            is_orthographic,
+            linear_z_slicing,
+            requested_cluster_dimensions.z,
+        ) {
+            (true, _, _) => {
                 // NOTE: Based on glam's Mat4::orthographic_rh(), as used to calculate the orthographic projection
                 // matrix, we can calculate the projection's view-space near plane as follows:
                 // component 3,2 = r * near and 2,2 = r where r = 1.0 / (near - far)
@@ -248,7 +255,9 @@ pub(crate) fn assign_objects_to_clusters(
                 // = (3,2 - 1.0) / 2,2
                 (camera.clip_from_view().w_axis.z - 1.0) / camera.clip_from_view().z_axis.z
             }
-            (false, 1) => config.first_slice_depth().max(far_z),
+            // Linear slices start at the camera
+            (false, true, _) => 0.0,
+            (false, false, 1) => config.first_slice_depth().max(far_z),
             _ => config.first_slice_depth(),
         };
         let first_slice_depth = first_slice_depth * view_from_world_scale.z;
@@ -259,7 +268,7 @@ pub(crate) fn assign_objects_to_clusters(
             first_slice_depth,
             far_z,
             requested_cluster_dimensions.z as f32,
-            is_orthographic,
+            linear_z_slicing,
         );
 
         if config.dynamic_resizing() {
@@ -288,13 +297,13 @@ pub(crate) fn assign_objects_to_clusters(
                     cluster_factors,
                     requested_cluster_dimensions.z,
                     clusterable_object_aabb_min.z,
-                    is_orthographic,
+                    linear_z_slicing,
                 );
                 let z_cluster_max = view_z_to_z_slice(
                     cluster_factors,
                     requested_cluster_dimensions.z,
                     clusterable_object_aabb_max.z,
-                    is_orthographic,
+                    linear_z_slicing,
                 );
                 let z_count =
                     z_cluster_min.max(z_cluster_max) - z_cluster_min.min(z_cluster_max) + 1;
@@ -339,6 +348,7 @@ pub(crate) fn assign_objects_to_clusters(
         clusters.update(screen_size, requested_cluster_dimensions);
         clusters.near = first_slice_depth;
         clusters.far = far_z;
+        clusters.linear_z_slicing = linear_z_slicing;
 
         // NOTE: Maximum 4096 clusters due to uniform buffer size constraints
         debug_assert!(
@@ -413,7 +423,7 @@ pub(crate) fn assign_objects_to_clusters(
 
         let z_slices = clusters.dimensions.z;
         for z in 0..=z_slices {
-            let view_z = z_slice_to_view_z(first_slice_depth, far_z, z_slices, z, is_orthographic);
+            let view_z = z_slice_to_view_z(first_slice_depth, far_z, z_slices, z, linear_z_slicing);
             let normal = -Vec3::Z;
             let d = view_z * normal.z;
             z_planes.push(HalfSpace::new(normal.extend(d)));
@@ -456,14 +466,14 @@ pub(crate) fn assign_objects_to_clusters(
                 let min_cluster = ndc_position_to_cluster(
                     clusters.dimensions,
                     cluster_factors,
-                    is_orthographic,
+                    linear_z_slicing,
                     clusterable_object_aabb_xy_ndc_z_view_min,
                     clusterable_object_aabb_xy_ndc_z_view_min.z,
                 );
                 let max_cluster = ndc_position_to_cluster(
                     clusters.dimensions,
                     cluster_factors,
-                    is_orthographic,
+                    linear_z_slicing,
                     clusterable_object_aabb_xy_ndc_z_view_max,
                     clusterable_object_aabb_xy_ndc_z_view_max.z,
                 );
@@ -500,7 +510,7 @@ pub(crate) fn assign_objects_to_clusters(
                 let cluster_coordinates = ndc_position_to_cluster(
                     clusters.dimensions,
                     cluster_factors,
-                    is_orthographic,
+                    linear_z_slicing,
                     object_center_ndc,
                     view_clusterable_object_sphere.center.z,
                 );
@@ -608,6 +618,7 @@ pub(crate) fn assign_objects_to_clusters(
                                         screen_size.as_vec2(),
                                         view_from_clip,
                                         is_orthographic,
+                                        linear_z_slicing,
                                         clusters.dimensions,
                                         UVec3::new(x, y, z),
                                     );
@@ -638,23 +649,28 @@ pub(crate) fn assign_objects_to_clusters(
                                         + clusterable_object.range * view_from_world_scale_max;
                                 let back_cull = v1_len < -cluster_aabb_sphere.radius;
 
-                                if !angle_cull && !front_cull && !back_cull {
+                                let cluster_objects =
+                                    &mut clusters.clusterable_objects[cluster_index];
+                                if !angle_cull
+                                    && !front_cull
+                                    && !back_cull
+                                    && cluster_objects.entities.len() < max_objects_per_cluster
+                                {
                                     // this cluster is affected by the spot light
-                                    clusters.clusterable_objects[cluster_index]
-                                        .entities
-                                        .push(clusterable_object.entity);
-                                    clusters.clusterable_objects[cluster_index].spot_light_count +=
-                                        1;
+                                    cluster_objects.entities.push(clusterable_object.entity);
+                                    cluster_objects.spot_light_count += 1;
                                 }
                                 cluster_index += clusters.dimensions.z as usize;
                             }
                         } else {
                             for _ in min_x..=max_x {
                                 // all clusters within range are affected by point lights
-                                clusters.clusterable_objects[cluster_index]
-                                    .entities
-                                    .push(clusterable_object.entity);
-                                clusters.clusterable_objects[cluster_index].point_light_count += 1;
+                                let cluster_objects =
+                                    &mut clusters.clusterable_objects[cluster_index];
+                                if cluster_objects.entities.len() < max_objects_per_cluster {
+                                    cluster_objects.entities.push(clusterable_object.entity);
+                                    cluster_objects.point_light_count += 1;
+                                }
                                 cluster_index += clusters.dimensions.z as usize;
                             }
                         }
@@ -688,6 +704,7 @@ fn compute_aabb_for_cluster(
     screen_size: Vec2,
     view_from_clip: Mat4,
     is_orthographic: bool,
+    linear_z_slicing: bool,
     cluster_dimensions: UVec3,
     ijk: UVec3,
 ) -> Aabb {
@@ -720,14 +737,18 @@ fn compute_aabb_for_cluster(
         let p_max = screen_to_view(screen_size, view_from_clip, p_max, 1.0);
 
         let z_far_over_z_near = -z_far / -z_near;
-        let cluster_near = if ijk.z == 0.0 {
+        let cluster_near = if linear_z_slicing {
+            -z_near + (z_near - z_far) * ijk.z / cluster_dimensions.z as f32
+        } else if ijk.z == 0.0 {
             0.0
         } else {
             -z_near * z_far_over_z_near.powf((ijk.z - 1.0) / (cluster_dimensions.z - 1) as f32)
         };
         // NOTE: This could be simplified to:
         // cluster_far = cluster_near * z_far_over_z_near;
-        let cluster_far = if cluster_dimensions.z == 1 {
+        let cluster_far = if linear_z_slicing {
+            -z_near + (z_near - z_far) * (ijk.z + 1.0) / cluster_dimensions.z as f32
+        } else if cluster_dimensions.z == 1 {
             -z_far
         } else {
             -z_near * z_far_over_z_near.powf(ijk.z / (cluster_dimensions.z - 1) as f32)
@@ -752,9 +773,9 @@ fn z_slice_to_view_z(
     far: f32,
     z_slices: u32,
     z_slice: u32,
-    is_orthographic: bool,
+    linear_z_slicing: bool,
 ) -> f32 {
-    if is_orthographic {
+    if linear_z_slicing {
         return -near - (far - near) * z_slice as f32 / z_slices as f32;
     }
 
@@ -769,7 +790,7 @@ fn z_slice_to_view_z(
 fn ndc_position_to_cluster(
     cluster_dimensions: UVec3,
     cluster_factors: Vec2,
-    is_orthographic: bool,
+    linear_z_slicing: bool,
     ndc_p: Vec3,
     view_z: f32,
 ) -> UVec3 {
@@ -780,7 +801,7 @@ fn ndc_position_to_cluster(
         cluster_factors,
         cluster_dimensions.z,
         view_z,
-        is_orthographic,
+        linear_z_slicing,
     );
     xy.as_uvec2()
         .extend(z_slice)
@@ -893,10 +914,10 @@ fn view_z_to_z_slice(
     cluster_factors: Vec2,
     z_slices: u32,
     view_z: f32,
-    is_orthographic: bool,
+    linear_z_slicing: bool,
 ) -> u32 {
-    let z_slice = if is_orthographic {
-        // NOTE: view_z is correct in the orthographic case
+    let z_slice = if linear_z_slicing {
+        // NOTE: view_z is correct in the linear case
         ((view_z - cluster_factors.x) * cluster_factors.y).floor() as u32
     } else {
         // NOTE: had to use -view_z to make it positive else log(negative) is nan
@@ -14,6 +14,7 @@ use bevy_math::{AspectRatio, UVec2, UVec3, UVec4, Vec3Swizzles as _, Vec4};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_render::{
     camera::Camera,
+    extract_component::ExtractComponent,
     render_resource::{
         BindingResource, BufferBindingType, ShaderSize as _, ShaderType, StorageBuffer,
         UniformBuffer,
@@ -67,14 +68,34 @@ pub enum ClusterFarZMode {
     Constant(f32),
 }
 
+/// Configure how the view frustum is divided into depth slices for clustered forward rendering
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default)]
+pub enum ClusterZSlicing {
+    /// The depth slices get exponentially deeper away from the camera, after a first slice that
+    /// extends to [`ClusterZConfig::first_slice_depth`]. This matches how perspective projections
+    /// shrink objects with distance, so it's a good default for most 3D scenes.
+    #[default]
+    Exponential,
+    /// All depth slices have the same depth, from the camera to the far `Z` plane. This spreads
+    /// lights more evenly across the slices when they are mostly far from a perspective camera.
+    ///
+    /// Orthographic projections always use linear slices.
+    Linear,
+}
+
 /// Configure the depth-slicing strategy for clustered forward rendering
 #[derive(Debug, Copy, Clone, Reflect)]
 #[reflect(Default)]
 pub struct ClusterZConfig {
     /// Far `Z` plane of the first depth slice
+    ///
+    /// Only used with [`ClusterZSlicing::Exponential`] and perspective projections.
     pub first_slice_depth: f32,
     /// Strategy for how to evaluate the far `Z` plane of the furthest depth slice
     pub far_z_mode: ClusterFarZMode,
+    /// How the depth range is divided into slices
+    pub slicing: ClusterZSlicing,
 }
 
 /// Configuration of the clustering strategy for clustered forward rendering
@@ -93,6 +114,10 @@ pub enum ClusterConfig {
         /// Specify if clusters should automatically resize in `X/Y` if there is a risk of exceeding
         /// the available cluster-object index limit
         dynamic_resizing: bool,
+        /// The maximum number of clusterable objects assigned to each cluster. Objects past it
+        /// are left out of the cluster, which bounds the lighting cost of each fragment at the
+        /// expense of missing lights. `u32::MAX` for no limit.
+        max_objects_per_cluster: u32,
     },
     /// Fixed number of `Z` slices, `X` and `Y` calculated to give square clusters
     /// with at most total clusters. For top-down games where lights will generally always be within a
@@ -106,9 +131,30 @@ pub enum ClusterConfig {
         /// Specify if clusters should automatically resize in `X/Y` if there is a risk of exceeding
         /// the available clusterable object index limit
         dynamic_resizing: bool,
+        /// The maximum number of clusterable objects assigned to each cluster. Objects past it
+        /// are left out of the cluster, which bounds the lighting cost of each fragment at the
+        /// expense of missing lights. `u32::MAX` for no limit.
+        max_objects_per_cluster: u32,
     },
 }
 
+/// Overlays a visualization of the clusters on the meshes rendered by a camera, to help with
+/// tuning its [`ClusterConfig`] in scenes with many lights.
+///
+/// This adds pipeline variants for the camera, so it's meant for debugging only.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Component, ExtractComponent, Reflect)]
+#[reflect(Component, Default)]
+pub enum ClusterDebugVisualization {
+    /// A heatmap of the number of clusterable objects in the cluster of each fragment, from blue
+    /// for none through green and yellow to red for 64 or more.
+    #[default]
+    ClusterableObjectCount,
+    /// Alternating colors for the depth slices.
+    ZSlices,
+    /// A random color for each cluster.
+    Clusters,
+}
+
 #[derive(Component, Debug, Default)]
 pub struct Clusters {
     /// Tile size
@@ -119,6 +165,8 @@ pub struct Clusters {
     /// and explicitly-configured to avoid having unnecessarily many slices close to the camera.
     pub(crate) near: f32,
     pub(crate) far: f32,
+    /// Whether the depth slices are linear, see [`ClusterZSlicing`]
+    pub(crate) linear_z_slicing: bool,
     pub(crate) clusterable_objects: Vec<VisibleClusterableObjects>,
 }
 
@@ -142,7 +190,7 @@ pub struct GlobalClusterableObjectMeta {
 
 #[derive(Copy, Clone, ShaderType, Default, Debug)]
 pub struct GpuClusterableObject {
-    // For point lights: the lower-right 2x2 values of the projection matrix [2][2] [2][3] [3][2] [3][3]
+    // For point lights: the UV of the shadow atlas tile (x,y), the UV size of a face and the near plane
     // For spot lights: 2 components of the direction (x,z), spot_scale and spot_offset
     pub(crate) light_custom_data: Vec4,
     pub(crate) color_inverse_square_range: Vec4,
@@ -176,6 +224,8 @@ pub struct ExtractedClusterConfig {
     pub(crate) far: f32,
     /// Number of clusters in `X` / `Y` / `Z` in the view frustum
     pub(crate) dimensions: UVec3,
+    /// Whether the depth slices are linear, see [`ClusterZSlicing`]
+    pub(crate) linear_z_slicing: bool,
 }
 
 enum ExtractedClusterableObjectElement {
@@ -230,6 +280,7 @@ impl Default for ClusterZConfig {
         Self {
             first_slice_depth: 5.0,
             far_z_mode: ClusterFarZMode::MaxClusterableObjectRange,
+            slicing: ClusterZSlicing::Exponential,
         }
     }
 }
@@ -243,6 +294,7 @@ impl Default for ClusterConfig {
             z_slices: 24,
             z_config: ClusterZConfig::default(),
             dynamic_resizing: true,
+            max_objects_per_cluster: u32::MAX,
         }
     }
 }
@@ -304,6 +356,31 @@ impl ClusterConfig {
         }
     }
 
+    fn z_slicing(&self) -> ClusterZSlicing {
+        match self {
+            ClusterConfig::None | ClusterConfig::Single => ClusterZSlicing::Exponential,
+            ClusterConfig::XYZ { z_config, .. } | ClusterConfig::FixedZ { z_config, .. } => {
+                z_config.slicing
+            }
+        }
+    }
+
+    /// The maximum number of clusterable objects assigned to each cluster, see
+    /// [`Clusters::max_clusterable_objects_per_cluster`].
+    pub fn max_objects_per_cluster(&self) -> usize {
+        match self {
+            ClusterConfig::None | ClusterConfig::Single => usize::MAX,
+            ClusterConfig::XYZ {
+                max_objects_per_cluster,
+                ..
+            }
+            | ClusterConfig::FixedZ {
+                max_objects_per_cluster,
+                ..
+            } => *max_objects_per_cluster as usize,
+        }
+    }
+
     fn dynamic_resizing(&self) -> bool {
         match self {
             ClusterConfig::None | ClusterConfig::Single => false,
@@ -318,6 +395,31 @@ impl ClusterConfig {
 }
 
 impl Clusters {
+    /// Number of clusters in `X` / `Y` / `Z` in the view frustum.
+    pub fn dimensions(&self) -> UVec3 {
+        self.dimensions
+    }
+
+    /// The clusterable objects assigned to each cluster, indexed by
+    /// `(y * dimensions.x + x) * dimensions.z + z`.
+    pub fn clusterable_objects(&self) -> &[VisibleClusterableObjects] {
+        &self.clusterable_objects
+    }
+
+    /// The largest number of clusterable objects assigned to a cluster.
+    ///
+    /// This is where the lighting of the view is the most expensive, like in the red areas of
+    /// [`ClusterDebugVisualization::ClusterableObjectCount`]. When it reaches
+    /// [`ClusterConfig::max_objects_per_cluster`], objects may be left out of clusters, which
+    /// shows as lights cut off along cluster edges.
+    pub fn max_clusterable_objects_per_cluster(&self) -> usize {
+        self.clusterable_objects
+            .iter()
+            .map(|objects| objects.entities.len())
+            .max()
+            .unwrap_or(0)
+    }
+
     fn update(&mut self, screen_size: UVec2, requested_dimensions: UVec3) {
         debug_assert!(
             requested_dimensions.x > 0 && requested_dimensions.y > 0 && requested_dimensions.z > 0
@@ -342,6 +444,7 @@ impl Clusters {
         self.dimensions = UVec3::ZERO;
         self.near = 0.0;
         self.far = 0.0;
+        self.linear_z_slicing = false;
         self.clusterable_objects.clear();
     }
 }
@@ -542,6 +645,7 @@ pub fn extract_clusters(
                 near: clusters.near,
                 far: clusters.far,
                 dimensions: clusters.dimensions,
+                linear_z_slicing: clusters.linear_z_slicing,
             },
         ));
     }
@@ -1,6 +1,7 @@
-use bevy_math::UVec2;
+use bevy_ecs::entity::Entity;
+use bevy_math::{UVec2, UVec3};
 
-use crate::{ClusterConfig, Clusters};
+use crate::{ClusterConfig, Clusters, VisibleClusterableObjects};
 
 fn test_cluster_tiling(config: ClusterConfig, screen_size: UVec2) -> Clusters {
     let dims = config.dimensions_for_screen_size(screen_size);
@@ -52,3 +53,37 @@ fn test_default_cluster_setup_small_x() {
         }
     }
 }
+
+#[test]
+fn clusterable_object_counts() {
+    let config = ClusterConfig::XYZ {
+        dimensions: UVec3::new(2, 1, 3),
+        z_config: Default::default(),
+        dynamic_resizing: false,
+        max_objects_per_cluster: 4,
+    };
+    assert_eq!(config.max_objects_per_cluster(), 4);
+    assert_eq!(
+        ClusterConfig::default().max_objects_per_cluster(),
+        u32::MAX as usize
+    );
+    assert_eq!(ClusterConfig::Single.max_objects_per_cluster(), usize::MAX);
+
+    let mut clusters = test_cluster_tiling(config, UVec2::new(64, 32));
+    assert_eq!(clusters.dimensions(), UVec3::new(2, 1, 3));
+    assert_eq!(clusters.max_clusterable_objects_per_cluster(), 0);
+
+    clusters.clusterable_objects = (0..6)
+        .map(|cluster| VisibleClusterableObjects {
+            entities: (0..cluster % 5).map(Entity::from_raw).collect(),
+            point_light_count: cluster as usize % 5,
+            spot_light_count: 0,
+        })
+        .collect();
+    assert_eq!(clusters.clusterable_objects().len(), 6);
+    assert_eq!(clusters.clusterable_objects()[3].len(), 3);
+    assert_eq!(
+        clusters.max_clusterable_objects_per_cluster(),
+        config.max_objects_per_cluster()
+    );
+}
@@ -0,0 +1,36 @@
+//! Debug views replacing the shading of the meshes, to diagnose rendering issues.
+
+use bevy_ecs::{component::Component, reflect::ReflectComponent};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::extract_component::ExtractComponent;
+
+/// Add this component to a 3D camera to render a debug view instead of the shaded meshes.
+///
+/// This adds pipeline variants for the camera, so it's meant for debugging only. It only affects
+/// the meshes rendered with the forward [`StandardMaterial`](crate::StandardMaterial) shader, and
+/// custom materials using `bevy_pbr::debug_view` in their fragment shader.
+///
+/// Wireframes over the shaded meshes are rendered by the
+/// [`WireframePlugin`](crate::wireframe::WireframePlugin) instead.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Component, ExtractComponent, Reflect)]
+#[reflect(Component, Default)]
+pub enum DebugViewMode {
+    /// The distance to the camera, from white at the camera to black in the distance.
+    #[default]
+    Depth,
+    /// The world space normals, after normal mapping.
+    WorldNormals,
+    /// The view space normals, after normal mapping.
+    ViewNormals,
+    /// A heatmap of the number of fragments drawn for each pixel, the brighter the more. The depth
+    /// test is disabled, to also count the hidden fragments.
+    Overdraw,
+    /// The shading, with the colors of the shadow cascades of the directional lights.
+    ///
+    /// See [`CascadeShadowConfig::visualize_cascades`](crate::CascadeShadowConfig::visualize_cascades)
+    /// to visualize them in all the cameras.
+    Cascades,
+    /// The second UVs of the meshes, used by the [`Lightmap`](crate::Lightmap)s. The meshes without
+    /// them are magenta.
+    LightmapUvs,
+}
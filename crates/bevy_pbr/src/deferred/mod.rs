@@ -1,8 +1,8 @@
 use crate::{
-    graph::NodePbr, irradiance_volume::IrradianceVolume, prelude::EnvironmentMapLight,
-    MeshPipeline, MeshViewBindGroup, RenderViewLightProbes, ScreenSpaceAmbientOcclusionSettings,
-    ScreenSpaceReflectionsUniform, ViewLightProbesUniformOffset,
-    ViewScreenSpaceReflectionsUniformOffset,
+    graph::NodePbr, irradiance_volume::IrradianceVolume, light_texture::LIGHT_TEXTURES_ARE_USABLE,
+    prelude::EnvironmentMapLight, MeshPipeline, MeshViewBindGroup, RenderViewLightProbes,
+    ScreenSpaceAmbientOcclusionSettings, ScreenSpaceReflectionsUniform,
+    ViewLightProbesUniformOffset, ViewScreenSpaceReflectionsUniformOffset,
 };
 use bevy_app::prelude::*;
 use bevy_asset::{load_internal_asset, Handle};
@@ -29,6 +29,7 @@ use bevy_render::{
 };
 
 use crate::{
+    cluster_debug_shader_def, cluster_debug_visualization_pipeline_key, ClusterDebugVisualization,
     MeshPipelineKey, ShadowFilteringMethod, ViewFogUniformOffset, ViewLightsUniformOffset,
 };
 
@@ -325,9 +326,17 @@ impl SpecializedRenderPipeline for DeferredLightingLayout {
             shader_defs.push("HAS_PREVIOUS_MORPH".into());
         }
 
+        if let Some(cluster_debug_def) = cluster_debug_shader_def(key) {
+            shader_defs.push(cluster_debug_def.into());
+        }
+
         // Always true, since we're in the deferred lighting pipeline
         shader_defs.push("DEFERRED_PREPASS".into());
 
+        if LIGHT_TEXTURES_ARE_USABLE {
+            shader_defs.push("LIGHT_TEXTURES_ARE_USABLE".into());
+        }
+
         let shadow_filter_method =
             key.intersection(MeshPipelineKey::SHADOW_FILTER_METHOD_RESERVED_BITS);
         if shadow_filter_method == MeshPipelineKey::SHADOW_FILTER_METHOD_HARDWARE_2X2 {
@@ -441,6 +450,7 @@ pub fn prepare_deferred_lighting_pipelines(
             ),
             Has<RenderViewLightProbes<EnvironmentMapLight>>,
             Has<RenderViewLightProbes<IrradianceVolume>>,
+            Option<&ClusterDebugVisualization>,
         ),
         With<DeferredPrepass>,
     >,
@@ -455,6 +465,7 @@ pub fn prepare_deferred_lighting_pipelines(
         (normal_prepass, depth_prepass, motion_vector_prepass),
         has_environment_maps,
         has_irradiance_volumes,
+        cluster_debug_visualization,
     ) in &views
     {
         let mut view_key = MeshPipelineKey::from_hdr(view.hdr);
@@ -515,6 +526,10 @@ pub fn prepare_deferred_lighting_pipelines(
             view_key |= MeshPipelineKey::IRRADIANCE_VOLUME;
         }
 
+        if let Some(cluster_debug_visualization) = cluster_debug_visualization {
+            view_key |= cluster_debug_visualization_pipeline_key(*cluster_debug_visualization);
+        }
+
         match shadow_filter_method.unwrap_or(&ShadowFilteringMethod::default()) {
             ShadowFilteringMethod::Hardware2x2 => {
                 view_key |= MeshPipelineKey::SHADOW_FILTER_METHOD_HARDWARE_2X2;
@@ -4,8 +4,8 @@ use bevy_render::{
     mesh::MeshVertexBufferLayoutRef,
     render_asset::RenderAssets,
     render_resource::{
-        AsBindGroup, AsBindGroupError, BindGroupLayout, RenderPipelineDescriptor, Shader,
-        ShaderRef, SpecializedMeshPipelineError, UnpreparedBindGroup,
+        AsBindGroup, AsBindGroupError, BindGroupLayout, BlendState, RenderPipelineDescriptor,
+        Shader, ShaderRef, SpecializedMeshPipelineError, UnpreparedBindGroup,
     },
     renderer::RenderDevice,
     texture::{FallbackImage, GpuImage},
@@ -30,6 +30,15 @@ pub struct MaterialExtensionKey<E: MaterialExtension> {
 pub trait MaterialExtension: Asset + AsBindGroup + Clone + Sized {
     /// Returns this material's vertex shader. If [`ShaderRef::Default`] is returned, the base material mesh vertex shader
     /// will be used.
+    ///
+    /// To displace vertices, for example to animate them, the shader doesn't need to reimplement the whole vertex stage:
+    /// it can compute a `bevy_pbr::mesh_functions::VertexDisplacement` and return the result of
+    /// `bevy_pbr::mesh_vertex::process_vertex`, which applies morph targets, skinning and the displacement.
+    /// The per-instance data of the mesh is available through `bevy_pbr::mesh_functions`, using the `instance_index`
+    /// of the vertex, and the extension's own data through its bindings in `@group(2)`.
+    ///
+    /// The same displacement should be applied in the [`prepass_vertex_shader`](Self::prepass_vertex_shader), with
+    /// `bevy_pbr::prepass_vertex::process_vertex`, so that shadows and the prepasses match, see [`WindSway`](crate::WindSway).
     fn vertex_shader() -> ShaderRef {
         ShaderRef::Default
     }
@@ -220,6 +229,10 @@ impl<B: Material, E: MaterialExtension> Material for ExtendedMaterial<B, E> {
         B::reads_view_transmission_texture(&self.base)
     }
 
+    fn blend_state(&self) -> Option<BlendState> {
+        B::blend_state(&self.base)
+    }
+
     fn prepass_vertex_shader() -> ShaderRef {
         match E::prepass_vertex_shader() {
             ShaderRef::Default => B::prepass_vertex_shader(),
@@ -296,6 +309,8 @@ impl<B: Material, E: MaterialExtension> Material for ExtendedMaterial<B, E> {
         let base_key = MaterialPipelineKey::<B> {
             mesh_key: key.mesh_key,
             bind_group_data: key.bind_group_data.0,
+            stencil: key.stencil,
+            blend_state: key.blend_state,
         };
         B::specialize(&base_pipeline, descriptor, layout, base_key)?;
 
@@ -24,11 +24,13 @@ pub mod experimental {
 
 mod bundle;
 mod cluster;
+mod debug_view;
 pub mod deferred;
 mod extended_material;
 mod fog;
 mod light;
 mod light_probe;
+mod light_texture;
 mod lightmap;
 mod material;
 mod parallax;
@@ -36,18 +38,23 @@ mod pbr_material;
 mod prepass;
 mod render;
 mod ssao;
+mod ssgi;
 mod ssr;
+mod ssss;
 mod volumetric_fog;
+mod wind_sway;
 
 use bevy_color::{Color, LinearRgba};
 use std::marker::PhantomData;
 
 pub use bundle::*;
 pub use cluster::*;
+pub use debug_view::*;
 pub use extended_material::*;
 pub use fog::*;
 pub use light::*;
 pub use light_probe::*;
+pub use light_texture::*;
 pub use lightmap::*;
 pub use material::*;
 pub use parallax::*;
@@ -55,8 +62,11 @@ pub use pbr_material::*;
 pub use prepass::*;
 pub use render::*;
 pub use ssao::*;
+pub use ssgi::*;
 pub use ssr::*;
+pub use ssss::*;
 pub use volumetric_fog::*;
+pub use wind_sway::*;
 
 pub mod prelude {
     #[doc(hidden)]
@@ -71,6 +81,7 @@ pub mod prelude {
             environment_map::{EnvironmentMapLight, ReflectionProbeBundle},
             LightProbe,
         },
+        light_texture::{LightTexture, LightTextureMapping},
         material::{Material, MaterialPlugin},
         parallax::ParallaxMappingMethod,
         pbr_material::StandardMaterial,
@@ -94,6 +105,10 @@ pub mod graph {
         GpuPreprocess,
         /// Label for the screen space reflections pass.
         ScreenSpaceReflections,
+        /// Label for the screen space global illumination pass.
+        GlobalIllumination,
+        /// Label for the screen space subsurface scattering pass.
+        ScreenSpaceSubsurfaceScattering,
     }
 }
 
@@ -123,6 +138,7 @@ pub const PBR_TYPES_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(17080
 pub const PBR_BINDINGS_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(5635987986427308186);
 pub const UTILS_HANDLE: Handle<Shader> = Handle::weak_from_u128(1900548483293416725);
 pub const CLUSTERED_FORWARD_HANDLE: Handle<Shader> = Handle::weak_from_u128(166852093121196815);
+pub const DEBUG_VIEW_HANDLE: Handle<Shader> = Handle::weak_from_u128(8846066830117434450);
 pub const PBR_LIGHTING_HANDLE: Handle<Shader> = Handle::weak_from_u128(14170772752254856967);
 pub const PBR_TRANSMISSION_HANDLE: Handle<Shader> = Handle::weak_from_u128(77319684653223658032);
 pub const SHADOWS_HANDLE: Handle<Shader> = Handle::weak_from_u128(11350275143789590502);
@@ -189,6 +205,12 @@ impl Plugin for PbrPlugin {
             "render/clustered_forward.wgsl",
             Shader::from_wgsl
         );
+        load_internal_asset!(
+            app,
+            DEBUG_VIEW_HANDLE,
+            "render/debug_view.wgsl",
+            Shader::from_wgsl
+        );
         load_internal_asset!(
             app,
             PBR_LIGHTING_HANDLE,
@@ -288,20 +310,24 @@ impl Plugin for PbrPlugin {
             .register_type::<Cascades>()
             .register_type::<CascadesVisibleEntities>()
             .register_type::<ClusterConfig>()
+            .register_type::<ClusterDebugVisualization>()
             .register_type::<CubemapVisibleEntities>()
+            .register_type::<DebugViewMode>()
             .register_type::<DirectionalLight>()
             .register_type::<DirectionalLightShadowMap>()
+            .register_type::<InstanceCustomData>()
             .register_type::<NotShadowCaster>()
             .register_type::<NotShadowReceiver>()
             .register_type::<PointLight>()
-            .register_type::<PointLightShadowMap>()
+            .register_type::<ShadowAtlasPriority>()
+            .register_type::<ShadowAtlasSettings>()
             .register_type::<SpotLight>()
             .register_type::<FogSettings>()
             .register_type::<ShadowFilteringMethod>()
             .init_resource::<AmbientLight>()
             .init_resource::<GlobalVisibleClusterableObjects>()
             .init_resource::<DirectionalLightShadowMap>()
-            .init_resource::<PointLightShadowMap>()
+            .init_resource::<ShadowAtlasSettings>()
             .register_type::<DefaultOpaqueRendererMethod>()
             .init_resource::<DefaultOpaqueRendererMethod>()
             .add_plugins((
@@ -328,6 +354,13 @@ impl Plugin for PbrPlugin {
                 VolumetricFogPlugin,
                 ScreenSpaceReflectionsPlugin,
             ))
+            .add_plugins((
+                ExtractComponentPlugin::<ClusterDebugVisualization>::default(),
+                ExtractComponentPlugin::<DebugViewMode>::default(),
+                LightTexturePlugin,
+                GlobalIlluminationPlugin,
+                ScreenSpaceSubsurfaceScatteringPlugin,
+            ))
             .configure_sets(
                 PostUpdate,
                 (
@@ -381,6 +414,10 @@ impl Plugin for PbrPlugin {
                         // which would override any results from this otherwise
                         .after(VisibilitySystems::CheckVisibility),
                 ),
+            )
+            .add_systems(
+                PostUpdate,
+                apply_point_light_shadow_map.before(SimulationLightSystems::UpdateLightFrusta),
             );
 
         if self.add_default_deferred_lighting_plugin {
@@ -87,18 +87,97 @@ pub mod light_consts {
     }
 }
 
+/// Controls the shadow atlas that the shadow maps of all the [`PointLight`]s are rendered into.
+///
+/// Each shadow casting point light gets a tile of the atlas holding the six faces of its cube
+/// shadow map. The resolution of the faces is chosen every frame from how large the range of the
+/// light appears on screen, scaled by its [`ShadowAtlasPriority`], and rounded up to a power of two
+/// between [`min_resolution`](Self::min_resolution) and [`max_resolution`](Self::max_resolution).
+///
+/// When the tiles don't fit in the atlas, the largest ones are made smaller first. If the lights
+/// still don't fit at the minimum resolution, those with the smallest tiles stop casting shadows.
+///
+/// A tile is three faces wide and two faces high, so the default atlas fits two lights at the
+/// default maximum resolution, or forty lights at a resolution of 256.
 #[derive(Resource, Clone, Debug, Reflect)]
 #[reflect(Resource)]
+pub struct ShadowAtlasSettings {
+    /// The width and height of the atlas, in texels.
+    pub size: u32,
+    /// The smallest resolution of a face of a point light shadow map, in texels.
+    pub min_resolution: u32,
+    /// The largest resolution of a face of a point light shadow map, in texels.
+    pub max_resolution: u32,
+}
+
+impl Default for ShadowAtlasSettings {
+    fn default() -> Self {
+        Self {
+            size: 4096,
+            min_resolution: 128,
+            max_resolution: 1024,
+        }
+    }
+}
+
+/// Scales the resolution that a [`PointLight`] gets in the shadow atlas, see [`ShadowAtlasSettings`].
+///
+/// Lights with a higher priority get sharper shadows and are the last ones to lose their shadows
+/// when the atlas is full. Lights without this component have a priority of `1.0`.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct ShadowAtlasPriority(pub f32);
+
+impl Default for ShadowAtlasPriority {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// Controls the resolution of [`PointLight`] shadow maps.
+///
+/// Point light shadow maps are allocated in the shadow atlas: when this resource is inserted or
+/// changed, `size` becomes the [`ShadowAtlasSettings::max_resolution`], and the atlas grows to fit
+/// at least one light at that resolution.
+#[deprecated = "Use `ShadowAtlasSettings` instead"]
+#[derive(Clone, Debug)]
 pub struct PointLightShadowMap {
     pub size: usize,
 }
 
+#[allow(deprecated)]
+impl Resource for PointLightShadowMap {}
+
+#[allow(deprecated)]
 impl Default for PointLightShadowMap {
     fn default() -> Self {
         Self { size: 1024 }
     }
 }
 
+#[allow(deprecated)]
+impl PointLightShadowMap {
+    /// Applies this resolution to the shadow atlas `settings`.
+    pub fn apply_to(&self, settings: &mut ShadowAtlasSettings) {
+        let resolution = self.size.max(1) as u32;
+        settings.max_resolution = resolution;
+        settings.min_resolution = settings.min_resolution.min(resolution);
+        // A tile is three faces wide and two faces high.
+        settings.size = settings.size.max(resolution * 3);
+    }
+}
+
+/// Applies the deprecated [`PointLightShadowMap`] to the [`ShadowAtlasSettings`] when it changes.
+#[allow(deprecated)]
+pub fn apply_point_light_shadow_map(
+    shadow_map: Option<Res<PointLightShadowMap>>,
+    mut shadow_atlas_settings: ResMut<ShadowAtlasSettings>,
+) {
+    if let Some(shadow_map) = shadow_map.filter(DetectChanges::is_changed) {
+        shadow_map.apply_to(&mut shadow_atlas_settings);
+    }
+}
+
 /// A convenient alias for `Or<(With<PointLight>, With<SpotLight>,
 /// With<DirectionalLight>)>`, for use with [`VisibleEntities`].
 pub type WithLight = Or<(With<PointLight>, With<SpotLight>, With<DirectionalLight>)>;
@@ -138,6 +217,19 @@ pub struct CascadeShadowConfig {
     pub overlap_proportion: f32,
     /// The (positive) distance to the near boundary of the first cascade.
     pub minimum_distance: f32,
+    /// Overrides of the shadow biases and overlap proportion of each cascade, indexed like
+    /// `bounds`. Cascades without an entry use the biases of the [`DirectionalLight`] and
+    /// `overlap_proportion`.
+    pub cascade_overrides: Vec<CascadeOverrides>,
+    /// Whether to stabilize the cascades, so that shadows don't shimmer as the camera moves
+    /// or rotates.
+    ///
+    /// Stabilized cascades keep the same size and are moved in whole shadow map texels, which
+    /// wastes some of the shadow map resolution. Disabling stabilization fits the cascades
+    /// tightly around the view frustum, which gives sharper shadows for mostly static cameras.
+    pub stabilize: bool,
+    /// Whether to color the areas covered by each cascade, to help tweaking `bounds`.
+    pub visualize_cascades: bool,
 }
 
 impl Default for CascadeShadowConfig {
@@ -146,6 +238,32 @@ impl Default for CascadeShadowConfig {
     }
 }
 
+impl CascadeShadowConfig {
+    /// Returns the proportion of overlap the cascade at `index` has with the previous cascade.
+    pub fn cascade_overlap_proportion(&self, index: usize) -> f32 {
+        self.cascade_overrides
+            .get(index)
+            .and_then(|overrides| overrides.overlap_proportion)
+            .unwrap_or(self.overlap_proportion)
+    }
+}
+
+/// Overrides of the shadow settings of a single cascade of a [`CascadeShadowConfig`].
+///
+/// Distant cascades cover more of the scene with each shadow map texel, and often need larger
+/// biases to avoid shadow acne than the cascades near the camera.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Default)]
+pub struct CascadeOverrides {
+    /// Overrides [`DirectionalLight::shadow_depth_bias`] for this cascade.
+    pub depth_bias: Option<f32>,
+    /// Overrides [`DirectionalLight::shadow_normal_bias`] for this cascade.
+    pub normal_bias: Option<f32>,
+    /// Overrides [`CascadeShadowConfig::overlap_proportion`] for this cascade, the proportion
+    /// of overlap with the previous cascade.
+    pub overlap_proportion: Option<f32>,
+}
+
 fn calculate_cascade_bounds(
     num_cascades: usize,
     nearest_bound: f32,
@@ -195,6 +313,14 @@ pub struct CascadeShadowConfigBuilder {
     /// The overlap is used to make the transition from one cascade's shadow map to the next
     /// less abrupt by blending between both shadow maps.
     pub overlap_proportion: f32,
+    /// Overrides of the shadow biases and overlap proportion of each cascade,
+    /// see [`CascadeShadowConfig::cascade_overrides`].
+    pub cascade_overrides: Vec<CascadeOverrides>,
+    /// Whether to stabilize the cascades, see [`CascadeShadowConfig::stabilize`].
+    pub stabilize: bool,
+    /// Whether to color the areas covered by each cascade,
+    /// see [`CascadeShadowConfig::visualize_cascades`].
+    pub visualize_cascades: bool,
 }
 
 impl CascadeShadowConfigBuilder {
@@ -225,6 +351,17 @@ impl CascadeShadowConfigBuilder {
             "overlap_proportion must be in [0.0, 1.0) but was {}",
             self.overlap_proportion
         );
+        for overlap_proportion in self
+            .cascade_overrides
+            .iter()
+            .filter_map(|overrides| overrides.overlap_proportion)
+        {
+            assert!(
+                (0.0..1.0).contains(&overlap_proportion),
+                "cascade overlap_proportion overrides must be in [0.0, 1.0) but was {}",
+                overlap_proportion
+            );
+        }
         CascadeShadowConfig {
             bounds: calculate_cascade_bounds(
                 self.num_cascades,
@@ -233,6 +370,9 @@ impl CascadeShadowConfigBuilder {
             ),
             overlap_proportion: self.overlap_proportion,
             minimum_distance: self.minimum_distance,
+            cascade_overrides: self.cascade_overrides.clone(),
+            stabilize: self.stabilize,
+            visualize_cascades: self.visualize_cascades,
         }
     }
 }
@@ -251,6 +391,9 @@ impl Default for CascadeShadowConfigBuilder {
                 maximum_distance: 100.0,
                 first_cascade_far_bound: 5.0,
                 overlap_proportion: 0.2,
+                cascade_overrides: Vec::new(),
+                stabilize: true,
+                visualize_cascades: false,
             }
         } else {
             Self {
@@ -259,6 +402,9 @@ impl Default for CascadeShadowConfigBuilder {
                 maximum_distance: 1000.0,
                 first_cascade_far_bound: 5.0,
                 overlap_proportion: 0.2,
+                cascade_overrides: Vec::new(),
+                stabilize: true,
+                visualize_cascades: false,
             }
         }
     }
@@ -344,7 +490,7 @@ pub fn build_directional_light_cascades<P: CameraProjection + Component>(
                 .map(|(idx, far_bound)| {
                     // Negate bounds as -z is camera forward direction.
                     let z_near = if idx > 0 {
-                        (1.0 - cascades_config.overlap_proportion)
+                        (1.0 - cascades_config.cascade_overlap_proportion(idx))
                             * -cascades_config.bounds[idx - 1]
                     } else {
                         -cascades_config.minimum_distance
@@ -358,6 +504,7 @@ pub fn build_directional_light_cascades<P: CameraProjection + Component>(
                         directional_light_shadow_map.size as f32,
                         world_from_light,
                         camera_to_light_view,
+                        cascades_config.stabilize,
                     )
                 })
                 .collect();
@@ -369,11 +516,15 @@ pub fn build_directional_light_cascades<P: CameraProjection + Component>(
 /// Returns a [`Cascade`] for the frustum defined by `frustum_corners`.
 /// The corner vertices should be specified in the following order:
 /// first the bottom right, top right, top left, bottom left for the near plane, then similar for the far plane.
+///
+/// If `stabilize` is `true`, the cascade only moves in whole texels and keeps the same size as
+/// the camera rotates, otherwise it is fitted tightly around the frustum.
 fn calculate_cascade(
     frustum_corners: [Vec3A; 8],
     cascade_texture_size: f32,
     world_from_light: Mat4,
     light_from_camera: Mat4,
+    stabilize: bool,
 ) -> Cascade {
     let mut min = Vec3A::splat(f32::MAX);
     let mut max = Vec3A::splat(f32::MIN);
@@ -383,30 +534,41 @@ fn calculate_cascade(
         max = max.max(corner_light_view);
     }
 
-    // NOTE: Use the larger of the frustum slice far plane diagonal and body diagonal lengths as this
-    //       will be the maximum possible projection size. Use the ceiling to get an integer which is
-    //       very important for floating point stability later. It is also important that these are
-    //       calculated using the original camera space corner positions for floating point precision
-    //       as even though the lengths using corner_light_view above should be the same, precision can
-    //       introduce small but significant differences.
-    // NOTE: The size remains the same unless the view frustum or cascade configuration is modified.
-    let cascade_diameter = (frustum_corners[0] - frustum_corners[6])
-        .length()
-        .max((frustum_corners[4] - frustum_corners[6]).length())
-        .ceil();
-
-    // NOTE: If we ensure that cascade_texture_size is a power of 2, then as we made cascade_diameter an
-    //       integer, cascade_texel_size is then an integer multiple of a power of 2 and can be
-    //       exactly represented in a floating point value.
-    let cascade_texel_size = cascade_diameter / cascade_texture_size;
-    // NOTE: For shadow stability it is very important that the near_plane_center is at integer
-    //       multiples of the texel size to be exactly representable in a floating point value.
-    let near_plane_center = Vec3A::new(
-        (0.5 * (min.x + max.x) / cascade_texel_size).floor() * cascade_texel_size,
-        (0.5 * (min.y + max.y) / cascade_texel_size).floor() * cascade_texel_size,
-        // NOTE: max.z is the near plane for right-handed y-up
-        max.z,
-    );
+    let (cascade_diameter, cascade_texel_size, near_plane_center) = if stabilize {
+        // NOTE: Use the larger of the frustum slice far plane diagonal and body diagonal lengths as this
+        //       will be the maximum possible projection size. Use the ceiling to get an integer which is
+        //       very important for floating point stability later. It is also important that these are
+        //       calculated using the original camera space corner positions for floating point precision
+        //       as even though the lengths using corner_light_view above should be the same, precision can
+        //       introduce small but significant differences.
+        // NOTE: The size remains the same unless the view frustum or cascade configuration is modified.
+        let cascade_diameter = (frustum_corners[0] - frustum_corners[6])
+            .length()
+            .max((frustum_corners[4] - frustum_corners[6]).length())
+            .ceil();
+
+        // NOTE: If we ensure that cascade_texture_size is a power of 2, then as we made cascade_diameter an
+        //       integer, cascade_texel_size is then an integer multiple of a power of 2 and can be
+        //       exactly represented in a floating point value.
+        let cascade_texel_size = cascade_diameter / cascade_texture_size;
+        // NOTE: For shadow stability it is very important that the near_plane_center is at integer
+        //       multiples of the texel size to be exactly representable in a floating point value.
+        let near_plane_center = Vec3A::new(
+            (0.5 * (min.x + max.x) / cascade_texel_size).floor() * cascade_texel_size,
+            (0.5 * (min.y + max.y) / cascade_texel_size).floor() * cascade_texel_size,
+            // NOTE: max.z is the near plane for right-handed y-up
+            max.z,
+        );
+        (cascade_diameter, cascade_texel_size, near_plane_center)
+    } else {
+        let cascade_diameter = (max.x - min.x).max(max.y - min.y);
+        let near_plane_center = Vec3A::new(0.5 * (min.x + max.x), 0.5 * (min.y + max.y), max.z);
+        (
+            cascade_diameter,
+            cascade_diameter / cascade_texture_size,
+            near_plane_center,
+        )
+    };
 
     // It is critical for `world_to_cascade` to be stable. So rather than forming `cascade_to_world`
     // and inverting it, which risks instability due to numerical precision, we directly form
@@ -565,13 +727,20 @@ pub fn update_directional_light_frusta(
 // NOTE: Run this after assign_lights_to_clusters!
 pub fn update_point_light_frusta(
     global_lights: Res<GlobalVisibleClusterableObjects>,
+    shadow_atlas_settings: Res<ShadowAtlasSettings>,
     mut views: Query<
         (Entity, &GlobalTransform, &PointLight, &mut CubemapFrusta),
         Or<(Changed<GlobalTransform>, Changed<PointLight>)>,
     >,
 ) {
-    let clip_from_view =
-        Mat4::perspective_infinite_reverse_rh(std::f32::consts::FRAC_PI_2, 1.0, POINT_LIGHT_NEAR_Z);
+    // The faces are rendered with a padding that widens their field of view, the most at the
+    // smallest resolution of the shadow atlas.
+    let clip_from_view = point_light_face_clip_from_view(
+        shadow_atlas_settings
+            .min_resolution
+            .max(1)
+            .next_power_of_two(),
+    );
     let view_rotations = CUBE_MAP_FACES
         .iter()
         .map(|CubeMapFace { target, up }| Transform::IDENTITY.looking_at(*target, *up))
@@ -0,0 +1,292 @@
+//! Loading of IES photometric profiles as light textures.
+
+use bevy_asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext};
+use bevy_render::{
+    render_asset::RenderAssetUsages,
+    render_resource::{Extent3d, TextureDimension, TextureFormat},
+    texture::Image,
+};
+use thiserror::Error;
+
+/// The width of the images produced by [`IesLoader`], covering horizontal angles from 0° to 360°.
+pub const IES_TEXTURE_WIDTH: u32 = 128;
+/// The height of the images produced by [`IesLoader`], covering vertical angles from 0° to 180°.
+pub const IES_TEXTURE_HEIGHT: u32 = 128;
+
+/// Loads `.ies` files, photometric profiles in the IESNA LM-63 format published by
+/// manufacturers of real-world luminaires, as an [`Image`] to be used in a
+/// [`LightTexture`](crate::LightTexture) with [`LightTextureMapping::Photometric`](crate::LightTextureMapping::Photometric).
+///
+/// The image is a single channel `R32Float` texture: the horizontal axis maps horizontal angles
+/// from 0° to 360°, and the vertical axis maps vertical angles from 0° (straight down, the nadir)
+/// to 180°. Values are normalized so that the brightest direction is `1.0`, the intensity of the
+/// light is taken from the light component and not from the profile.
+///
+/// Only type C photometry, used by almost all architectural luminaires, is supported.
+#[derive(Default)]
+pub struct IesLoader;
+
+/// Possible errors that can be produced by [`IesLoader`].
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum IesLoaderError {
+    /// An [IO](std::io) Error.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// The file does not contain a `TILT=` line.
+    #[error("missing TILT line")]
+    MissingTilt,
+    /// The tilt data is stored in a separate file, which is not supported.
+    #[error("unsupported tilt file: {0}")]
+    UnsupportedTilt(String),
+    /// A value could not be parsed as a number.
+    #[error("invalid number: {0}")]
+    InvalidNumber(String),
+    /// The file ended before all the values were read.
+    #[error("unexpected end of file")]
+    UnexpectedEnd,
+    /// The profile uses type A or type B photometry.
+    #[error("unsupported photometric type {0}, only type C (1) is supported")]
+    UnsupportedPhotometricType(u32),
+    /// The profile has no angles or no candela values.
+    #[error("profile has no candela values")]
+    Empty,
+}
+
+impl AssetLoader for IesLoader {
+    type Asset = Image;
+    type Settings = ();
+    type Error = IesLoaderError;
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Image, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let profile = IesProfile::parse(&String::from_utf8_lossy(&bytes))?;
+        Ok(profile.to_image())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ies"]
+    }
+}
+
+/// The candela distribution of a type C IES profile.
+#[derive(Debug, Clone, PartialEq)]
+struct IesProfile {
+    /// Vertical angles in degrees, 0° being the nadir, in increasing order.
+    vertical_angles: Vec<f32>,
+    /// Horizontal angles in degrees, in increasing order.
+    horizontal_angles: Vec<f32>,
+    /// The candela values, all vertical angles of the first horizontal angle first.
+    candela: Vec<f32>,
+}
+
+impl IesProfile {
+    fn parse(text: &str) -> Result<Self, IesLoaderError> {
+        // Keyword lines such as `[MANUFAC]` come before the `TILT=` line, numbers after it.
+        let mut lines = text.lines();
+        let tilt = lines
+            .by_ref()
+            .find_map(|line| line.trim().strip_prefix("TILT="))
+            .ok_or(IesLoaderError::MissingTilt)?
+            .trim()
+            .to_string();
+        let rest = lines.collect::<Vec<_>>().join("\n");
+        let mut values = rest
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|value| !value.is_empty());
+        let mut next = || -> Result<f32, IesLoaderError> {
+            let value = values.next().ok_or(IesLoaderError::UnexpectedEnd)?;
+            value
+                .parse()
+                .map_err(|_| IesLoaderError::InvalidNumber(value.to_string()))
+        };
+
+        match tilt.as_str() {
+            "NONE" => {}
+            "INCLUDE" => {
+                // Lamp to luminaire geometry, then the tilt angles and multiplying factors,
+                // neither of which affect the distribution of a luminaire that isn't tilted.
+                next()?;
+                let count = next()? as usize;
+                for _ in 0..count * 2 {
+                    next()?;
+                }
+            }
+            _ => return Err(IesLoaderError::UnsupportedTilt(tilt)),
+        }
+
+        let _lamp_count = next()?;
+        let _lumens_per_lamp = next()?;
+        let multiplier = next()?;
+        let vertical_count = next()? as usize;
+        let horizontal_count = next()? as usize;
+        let photometric_type = next()? as u32;
+        let _units = next()?;
+        let _width = next()?;
+        let _length = next()?;
+        let _height = next()?;
+        let _ballast_factor = next()?;
+        let _future_use = next()?;
+        let _input_watts = next()?;
+
+        if photometric_type != 1 {
+            return Err(IesLoaderError::UnsupportedPhotometricType(photometric_type));
+        }
+        if vertical_count == 0 || horizontal_count == 0 {
+            return Err(IesLoaderError::Empty);
+        }
+
+        let vertical_angles = (0..vertical_count)
+            .map(|_| next())
+            .collect::<Result<_, _>>()?;
+        let horizontal_angles = (0..horizontal_count)
+            .map(|_| next())
+            .collect::<Result<_, _>>()?;
+        let candela = (0..vertical_count * horizontal_count)
+            .map(|_| next().map(|value| value * multiplier))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            vertical_angles,
+            horizontal_angles,
+            candela,
+        })
+    }
+
+    /// Returns the candela value in the given direction, in degrees.
+    fn sample(&self, vertical: f32, horizontal: f32) -> f32 {
+        // Profiles only store the angles that aren't implied by the symmetry of the luminaire.
+        let first = self.horizontal_angles[0];
+        let last = self.horizontal_angles[self.horizontal_angles.len() - 1];
+        let horizontal = if self.horizontal_angles.len() == 1 {
+            first
+        } else if first == 90.0 && last == 270.0 {
+            // Symmetric about the 90° to 270° plane.
+            if horizontal < 90.0 {
+                180.0 - horizontal
+            } else if horizontal > 270.0 {
+                540.0 - horizontal
+            } else {
+                horizontal
+            }
+        } else if last == 90.0 {
+            // Symmetric in each quadrant.
+            let horizontal = if horizontal > 180.0 {
+                360.0 - horizontal
+            } else {
+                horizontal
+            };
+            if horizontal > 90.0 {
+                180.0 - horizontal
+            } else {
+                horizontal
+            }
+        } else if last == 180.0 && horizontal > 180.0 {
+            // Symmetric about the 0° to 180° plane.
+            360.0 - horizontal
+        } else {
+            horizontal
+        };
+
+        let Some((v0, v1, vt)) = interpolation(&self.vertical_angles, vertical) else {
+            // No light is emitted outside of the measured vertical angles.
+            return 0.0;
+        };
+        let (h0, h1, ht) =
+            interpolation(&self.horizontal_angles, horizontal).unwrap_or((0, 0, 0.0));
+        let count = self.vertical_angles.len();
+        let at = |h: usize, v: usize| self.candela.get(h * count + v).copied().unwrap_or(0.0);
+        let lerp = |a: f32, b: f32, t: f32| a + (b - a) * t;
+        lerp(
+            lerp(at(h0, v0), at(h0, v1), vt),
+            lerp(at(h1, v0), at(h1, v1), vt),
+            ht,
+        )
+    }
+
+    /// Resamples the profile into an image, see [`IesLoader`].
+    fn to_image(&self) -> Image {
+        let mut values = Vec::with_capacity((IES_TEXTURE_WIDTH * IES_TEXTURE_HEIGHT) as usize);
+        for y in 0..IES_TEXTURE_HEIGHT {
+            let vertical = (y as f32 + 0.5) / IES_TEXTURE_HEIGHT as f32 * 180.0;
+            for x in 0..IES_TEXTURE_WIDTH {
+                let horizontal = (x as f32 + 0.5) / IES_TEXTURE_WIDTH as f32 * 360.0;
+                values.push(self.sample(vertical, horizontal).max(0.0));
+            }
+        }
+        let max = values.iter().copied().fold(0.0, f32::max);
+        if max > 0.0 {
+            values.iter_mut().for_each(|value| *value /= max);
+        }
+
+        Image::new(
+            Extent3d {
+                width: IES_TEXTURE_WIDTH,
+                height: IES_TEXTURE_HEIGHT,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            bytemuck::cast_slice(&values).to_vec(),
+            TextureFormat::R32Float,
+            RenderAssetUsages::default(),
+        )
+    }
+}
+
+/// Returns the indices of the angles surrounding `angle` and the interpolation factor between
+/// them, or `None` if `angle` is outside of the range of `angles`.
+fn interpolation(angles: &[f32], angle: f32) -> Option<(usize, usize, f32)> {
+    const EPSILON: f32 = 1e-3;
+    let first = *angles.first()?;
+    let last = *angles.last()?;
+    if angle < first - EPSILON || angle > last + EPSILON {
+        return None;
+    }
+    let upper = angles
+        .iter()
+        .position(|&a| a >= angle)
+        .unwrap_or(angles.len() - 1);
+    if upper == 0 {
+        return Some((0, 0, 0.0));
+    }
+    let (a0, a1) = (angles[upper - 1], angles[upper]);
+    let t = if a1 > a0 {
+        ((angle - a0) / (a1 - a0)).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    Some((upper - 1, upper, t))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IesProfile;
+
+    const PROFILE: &str = "IESNA:LM-63-2002
+[MANUFAC] Test
+TILT=NONE
+1 1000 2.0 3 1 1 2 0 0 0
+1.0 1.0 100
+0 45 90
+0
+100 50 0";
+
+    #[test]
+    fn parse_rotationally_symmetric_profile() {
+        let profile = IesProfile::parse(PROFILE).unwrap();
+        assert_eq!(profile.vertical_angles, vec![0.0, 45.0, 90.0]);
+        assert_eq!(profile.horizontal_angles, vec![0.0]);
+        assert_eq!(profile.candela, vec![200.0, 100.0, 0.0]);
+
+        // Interpolated between vertical angles, constant around the vertical axis.
+        assert_eq!(profile.sample(22.5, 0.0), 150.0);
+        assert_eq!(profile.sample(22.5, 270.0), 150.0);
+        // Nothing is emitted above the measured angles.
+        assert_eq!(profile.sample(135.0, 0.0), 0.0);
+    }
+}
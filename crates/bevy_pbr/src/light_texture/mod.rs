@@ -0,0 +1,405 @@
+//! Light textures, also known as light cookies, which modulate the light that point and spot
+//! lights emit depending on the direction.
+//!
+//! A [`LightTexture`] can project an image, for example the shadow of a window frame or of
+//! leaves, from a [`SpotLight`](crate::SpotLight). It can also use a photometric profile in the
+//! IES format, loaded as an image by [`IesLoader`], to reproduce the distribution of light from a
+//! real-world luminaire.
+//!
+//! During extraction, the images of all light textures are resampled on the CPU into the layers
+//! of a single texture array, so that any number of lights can be evaluated in the same shader
+//! invocation. That means the images must keep their data in the main world, see
+//! [`RenderAssetUsages::MAIN_WORLD`](bevy_render::render_asset::RenderAssetUsages::MAIN_WORLD).
+//! Images are only resampled when they are first used or when they change.
+//!
+//! Light textures aren't available on WebGL 2 and WebGPU, as they would exceed the number of
+//! texture bindings available there.
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{AssetApp, AssetEvent, AssetId, Assets, Handle};
+use bevy_color::Srgba;
+use bevy_ecs::{
+    component::Component,
+    event::EventReader,
+    reflect::ReflectComponent,
+    schedule::IntoSystemConfigs,
+    system::{Query, Res, ResMut, Resource},
+    world::{FromWorld, World},
+};
+use bevy_math::Vec4;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    render_resource::{
+        AddressMode, Extent3d, FilterMode, ImageCopyTexture, ImageDataLayout, Origin3d, Sampler,
+        SamplerDescriptor, Texture, TextureAspect, TextureDescriptor, TextureDimension,
+        TextureFormat, TextureUsages, TextureView, TextureViewDescriptor, TextureViewDimension,
+    },
+    renderer::{RenderDevice, RenderQueue},
+    texture::Image,
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+};
+use bevy_utils::{warn_once, HashSet};
+
+mod ies;
+
+pub use ies::*;
+
+/// The maximum number of distinct images that can be used by light textures at the same time.
+pub const MAX_LIGHT_TEXTURES: usize = 16;
+
+/// The size, in texels, of each layer of the light texture array.
+///
+/// Light texture images are resampled to this size.
+pub const LIGHT_TEXTURE_SIZE: u32 = 256;
+
+/// On WebGL and WebGPU, light textures are disabled, as they would push the mesh view bind group
+/// over the number of texture bindings available there.
+pub(crate) const LIGHT_TEXTURES_ARE_USABLE: bool = cfg!(not(target_arch = "wasm32"));
+
+/// A plugin that provides an implementation of light textures, see [`LightTexture`].
+pub struct LightTexturePlugin;
+
+/// An image that modulates the color and intensity of the light emitted by a
+/// [`PointLight`](crate::PointLight) or [`SpotLight`](crate::SpotLight) depending on its
+/// direction, also known as a light cookie.
+///
+/// The image is multiplied with the color of the light, so black blocks the light entirely.
+/// Single channel images, such as those loaded from `.ies` files with [`IesLoader`], modulate
+/// the intensity only.
+///
+/// The rotation of a spot light around its direction, and the rotation of point lights, isn't
+/// taken into account: see [`LightTextureMapping`] for how the image is oriented.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct LightTexture {
+    /// The image. Its data must be available in the main world.
+    pub image: Handle<Image>,
+    /// How the image is mapped to the directions around the light.
+    pub mapping: LightTextureMapping,
+}
+
+/// How a [`LightTexture`] is mapped to the directions around a light.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Default)]
+pub enum LightTextureMapping {
+    /// The image is projected along the direction of a spot light, covering its outer cone,
+    /// with the top of the image towards the world's up direction, or towards `-Z` for lights
+    /// pointing straight up or down.
+    ///
+    /// Point lights don't have a direction to project along, and use
+    /// [`LightTextureMapping::Photometric`] instead.
+    #[default]
+    Projected,
+    /// The image is mapped like a photometric profile loaded by [`IesLoader`]: the horizontal
+    /// axis covers the horizontal angles from 0° to 360°, and the vertical axis covers the
+    /// vertical angles from 0° to 180°, measured from the direction of the light. For spot
+    /// lights, the horizontal angle of 0° is to the right of the light, oriented as with
+    /// [`LightTextureMapping::Projected`].
+    ///
+    /// For point lights, the direction of the light is straight down (the world's `-Y` axis),
+    /// and the horizontal angle of 0° is the world's `+X` axis.
+    Photometric,
+}
+
+/// The light texture of a light, extracted to the render world.
+#[derive(Clone, Copy, Debug)]
+pub struct ExtractedLightTexture {
+    /// The image of the light texture.
+    pub image: AssetId<Image>,
+    /// How the image is mapped to the directions around the light.
+    pub mapping: LightTextureMapping,
+}
+
+impl From<&LightTexture> for ExtractedLightTexture {
+    fn from(light_texture: &LightTexture) -> Self {
+        Self {
+            image: light_texture.image.id(),
+            mapping: light_texture.mapping,
+        }
+    }
+}
+
+/// The texture array containing the images of all light textures, in the render world.
+#[derive(Resource)]
+pub struct RenderLightTextures {
+    /// The images in each layer, and whether they have been resampled into it.
+    layers: Vec<(AssetId<Image>, bool)>,
+    /// Resampled layer data that hasn't been uploaded yet.
+    pending_layers: Vec<(u32, Vec<u8>)>,
+    /// Whether the texture array needs to be recreated, because the number of layers changed.
+    reallocate: bool,
+    texture: Texture,
+    /// The view of the texture array, bound to the mesh view bind group.
+    pub texture_view: TextureView,
+    /// The sampler used for light textures, bound to the mesh view bind group.
+    pub sampler: Sampler,
+}
+
+impl RenderLightTextures {
+    /// Returns the layer of the texture array that contains `image`, if it has been resampled.
+    pub fn layer(&self, image: AssetId<Image>) -> Option<u32> {
+        self.layers
+            .iter()
+            .position(|&(layer_image, ready)| layer_image == image && ready)
+            .map(|layer| layer as u32)
+    }
+}
+
+impl FromWorld for RenderLightTextures {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let (texture, texture_view) = create_light_texture_array(render_device, 1);
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("light_texture_sampler"),
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+        Self {
+            layers: Vec::new(),
+            pending_layers: Vec::new(),
+            reallocate: false,
+            texture,
+            texture_view,
+            sampler,
+        }
+    }
+}
+
+impl Plugin for LightTexturePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<LightTexture>()
+            .init_asset_loader::<IesLoader>();
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<RenderLightTextures>()
+            .add_systems(ExtractSchedule, extract_light_textures)
+            .add_systems(
+                Render,
+                prepare_light_textures.in_set(RenderSet::PrepareResources),
+            );
+    }
+}
+
+fn create_light_texture_array(render_device: &RenderDevice, layers: u32) -> (Texture, TextureView) {
+    let texture = render_device.create_texture(&TextureDescriptor {
+        label: Some("light_texture_array"),
+        size: Extent3d {
+            width: LIGHT_TEXTURE_SIZE,
+            height: LIGHT_TEXTURE_SIZE,
+            depth_or_array_layers: layers,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: TextureDimension::D2,
+        format: TextureFormat::Rgba8Unorm,
+        usage: TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+    let texture_view = texture.create_view(&TextureViewDescriptor {
+        label: Some("light_texture_array_view"),
+        dimension: Some(TextureViewDimension::D2Array),
+        ..Default::default()
+    });
+    (texture, texture_view)
+}
+
+/// Assigns a layer of the light texture array to every image used by a [`LightTexture`], and
+/// resamples the images that are new or have changed.
+fn extract_light_textures(
+    mut render_light_textures: ResMut<RenderLightTextures>,
+    light_textures: Extract<Query<&LightTexture>>,
+    images: Extract<Res<Assets<Image>>>,
+    mut image_events: Extract<EventReader<AssetEvent<Image>>>,
+) {
+    let mut changed_images = HashSet::new();
+    for event in image_events.read() {
+        match event {
+            AssetEvent::Added { id }
+            | AssetEvent::Modified { id }
+            | AssetEvent::LoadedWithDependencies { id } => {
+                changed_images.insert(*id);
+            }
+            AssetEvent::Removed { .. } | AssetEvent::Unused { .. } => {}
+        }
+    }
+
+    let mut used_images = light_textures
+        .iter()
+        .map(|light_texture| light_texture.image.id())
+        .collect::<Vec<_>>();
+    used_images.sort_unstable();
+    used_images.dedup();
+    if used_images.len() > MAX_LIGHT_TEXTURES {
+        warn_once!(
+            "Light textures use {} different images, but only {MAX_LIGHT_TEXTURES} are supported",
+            used_images.len()
+        );
+        used_images.truncate(MAX_LIGHT_TEXTURES);
+    }
+
+    let render_light_textures = &mut *render_light_textures;
+    let layers_changed = used_images.len() != render_light_textures.layers.len()
+        || used_images
+            .iter()
+            .zip(&render_light_textures.layers)
+            .any(|(image, (layer_image, _))| image != layer_image);
+    if layers_changed {
+        render_light_textures.layers = used_images.into_iter().map(|id| (id, false)).collect();
+        render_light_textures.pending_layers.clear();
+        render_light_textures.reallocate = true;
+    }
+
+    for (layer, (id, ready)) in render_light_textures.layers.iter_mut().enumerate() {
+        if *ready && !changed_images.contains(id) {
+            continue;
+        }
+        let Some(image) = images.get(*id) else {
+            continue;
+        };
+        match resample_light_texture(image) {
+            Some(data) => {
+                render_light_textures
+                    .pending_layers
+                    .push((layer as u32, data));
+                *ready = true;
+            }
+            None => {
+                warn_once!(
+                    "Light texture images must be uncompressed 8 bit or 32 bit float images with their data in the main world, \
+                    but an image has format {:?}",
+                    image.texture_descriptor.format
+                );
+            }
+        }
+    }
+}
+
+/// Uploads the resampled light texture images to the texture array.
+fn prepare_light_textures(
+    mut render_light_textures: ResMut<RenderLightTextures>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    let render_light_textures = &mut *render_light_textures;
+    if render_light_textures.reallocate {
+        let layers = render_light_textures.layers.len().max(1) as u32;
+        (
+            render_light_textures.texture,
+            render_light_textures.texture_view,
+        ) = create_light_texture_array(&render_device, layers);
+        render_light_textures.reallocate = false;
+    }
+
+    for (layer, data) in render_light_textures.pending_layers.drain(..) {
+        render_queue.write_texture(
+            ImageCopyTexture {
+                texture: &render_light_textures.texture,
+                mip_level: 0,
+                origin: Origin3d {
+                    x: 0,
+                    y: 0,
+                    z: layer,
+                },
+                aspect: TextureAspect::All,
+            },
+            &data,
+            ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(LIGHT_TEXTURE_SIZE * 4),
+                rows_per_image: None,
+            },
+            Extent3d {
+                width: LIGHT_TEXTURE_SIZE,
+                height: LIGHT_TEXTURE_SIZE,
+                depth_or_array_layers: 1,
+            },
+        );
+    }
+}
+
+/// Resamples the first layer of `image` to [`LIGHT_TEXTURE_SIZE`] texels, returning linear
+/// `Rgba8Unorm` data, or `None` if the format of the image isn't supported.
+fn resample_light_texture(image: &Image) -> Option<Vec<u8>> {
+    let width = image.width();
+    let height = image.height();
+    let format = image.texture_descriptor.format;
+    let texel_size = match format {
+        TextureFormat::R8Unorm => 1,
+        TextureFormat::Rg8Unorm => 2,
+        TextureFormat::Rgba8Unorm
+        | TextureFormat::Rgba8UnormSrgb
+        | TextureFormat::Bgra8Unorm
+        | TextureFormat::Bgra8UnormSrgb
+        | TextureFormat::R32Float => 4,
+        TextureFormat::Rgba32Float => 16,
+        _ => return None,
+    };
+    if width == 0 || height == 0 || image.data.len() < (width * height) as usize * texel_size {
+        return None;
+    }
+
+    let texel = |x: u32, y: u32| -> Vec4 {
+        let offset = (y * width + x) as usize * texel_size;
+        let bytes = &image.data[offset..offset + texel_size];
+        let unorm = |byte: u8| byte as f32 / 255.0;
+        let srgb = |byte: u8| Srgba::gamma_function(unorm(byte));
+        let float = |index: usize| {
+            f32::from_le_bytes(
+                bytes[index * 4..index * 4 + 4]
+                    .try_into()
+                    .unwrap_or_default(),
+            )
+        };
+        let rgb = match format {
+            TextureFormat::R8Unorm | TextureFormat::Rg8Unorm => [unorm(bytes[0]); 3],
+            TextureFormat::Rgba8Unorm => [bytes[0], bytes[1], bytes[2]].map(unorm),
+            TextureFormat::Rgba8UnormSrgb => [bytes[0], bytes[1], bytes[2]].map(srgb),
+            TextureFormat::Bgra8Unorm => [bytes[2], bytes[1], bytes[0]].map(unorm),
+            TextureFormat::Bgra8UnormSrgb => [bytes[2], bytes[1], bytes[0]].map(srgb),
+            TextureFormat::R32Float => [float(0); 3],
+            _ => [float(0), float(1), float(2)],
+        };
+        let alpha = match format {
+            TextureFormat::R8Unorm | TextureFormat::R32Float => 1.0,
+            TextureFormat::Rg8Unorm => unorm(bytes[1]),
+            TextureFormat::Rgba32Float => float(3),
+            _ => unorm(bytes[3]),
+        };
+        Vec4::new(rgb[0], rgb[1], rgb[2], alpha)
+    };
+
+    // Bilinear filtering, the images are usually smooth or close to the size of a layer.
+    let mut data = Vec::with_capacity((LIGHT_TEXTURE_SIZE * LIGHT_TEXTURE_SIZE * 4) as usize);
+    for y in 0..LIGHT_TEXTURE_SIZE {
+        let source_y = ((y as f32 + 0.5) / LIGHT_TEXTURE_SIZE as f32 * height as f32 - 0.5)
+            .clamp(0.0, (height - 1) as f32);
+        for x in 0..LIGHT_TEXTURE_SIZE {
+            let source_x = ((x as f32 + 0.5) / LIGHT_TEXTURE_SIZE as f32 * width as f32 - 0.5)
+                .clamp(0.0, (width - 1) as f32);
+            let (x0, y0) = (source_x as u32, source_y as u32);
+            let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+            let (tx, ty) = (source_x.fract(), source_y.fract());
+            let top = texel(x0, y0).lerp(texel(x1, y0), tx);
+            let bottom = texel(x0, y1).lerp(texel(x1, y1), tx);
+            // The alpha channel blocks the light like black does.
+            let value = top.lerp(bottom, ty);
+            let color = value.truncate() * value.w;
+            data.extend(
+                color
+                    .extend(1.0)
+                    .to_array()
+                    .map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8),
+            );
+        }
+    }
+    Some(data)
+}
@@ -8,7 +8,7 @@ use bevy_asset::{Asset, AssetId, AssetServer};
 use bevy_core_pipeline::{
     core_3d::{
         AlphaMask3d, Camera3d, Opaque3d, Opaque3dBinKey, ScreenSpaceTransmissionQuality,
-        Transmissive3d, Transparent3d,
+        Transmissive3d, Transparent3d, ViewDepthStencil,
     },
     prepass::{
         DeferredPrepass, DepthPrepass, MotionVectorPrepass, NormalPrepass, OpaqueNoLightmap3dBinKey,
@@ -33,7 +33,7 @@ use bevy_render::{
     texture::FallbackImage,
     view::{ExtractedView, Msaa, RenderVisibilityRanges, VisibleEntities, WithMesh},
 };
-use bevy_utils::tracing::error;
+use bevy_utils::{tracing::error, warn_once};
 use std::marker::PhantomData;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::{hash::Hash, num::NonZeroU32};
@@ -135,6 +135,38 @@ pub trait Material: Asset + AsBindGroup + Clone + Sized {
         0.0
     }
 
+    #[inline]
+    /// Returns the stencil test and operations of this material, or `None` to ignore the stencil buffer.
+    ///
+    /// This only applies to views whose depth texture has a stencil aspect, see
+    /// [`Camera3d::stencil`]. The reference value the stencil is compared to and written with is
+    /// [`Material::stencil_reference`].
+    fn stencil(&self) -> Option<StencilState> {
+        None
+    }
+
+    #[inline]
+    /// Returns the reference value used by the [`Material::stencil`] test and operations.
+    fn stencil_reference(&self) -> u32 {
+        0
+    }
+
+    #[inline]
+    /// Returns the blending of this material with the main pass color target, or `None` to use
+    /// the one of its [`AlphaMode`].
+    ///
+    /// The material is still sorted and drawn according to its [`AlphaMode`], so transparent
+    /// blend states should be used with a transparent [`AlphaMode`] such as [`AlphaMode::Blend`].
+    ///
+    /// Blend factors that use a second blend source, such as [`BlendFactor::Src1`], are only
+    /// supported with [`WgpuFeatures::DUAL_SOURCE_BLENDING`] and fall back to the blending of the
+    /// [`AlphaMode`] otherwise. The `DUAL_SOURCE_BLENDING` shader def is set for them, and the
+    /// fragment shader writes the second source in `FragmentOutput::blend`, see
+    /// `bevy_pbr::forward_io`.
+    fn blend_state(&self) -> Option<BlendState> {
+        None
+    }
+
     #[inline]
     /// Returns whether the material would like to read from [`ViewTransmissionTexture`](bevy_core_pipeline::core_3d::ViewTransmissionTexture).
     ///
@@ -319,6 +351,10 @@ where
 pub struct MaterialPipelineKey<M: Material> {
     pub mesh_key: MeshPipelineKey,
     pub bind_group_data: M::Data,
+    /// The [`Material::stencil`] state, used if the [`MeshPipelineKey::DEPTH_STENCIL`] flag is set.
+    pub stencil: Option<StencilState>,
+    /// The [`Material::blend_state`], replacing the blending of the [`AlphaMode`].
+    pub blend_state: Option<BlendState>,
 }
 
 impl<M: Material> Eq for MaterialPipelineKey<M> where M::Data: PartialEq {}
@@ -328,7 +364,10 @@ where
     M::Data: PartialEq,
 {
     fn eq(&self, other: &Self) -> bool {
-        self.mesh_key == other.mesh_key && self.bind_group_data == other.bind_group_data
+        self.mesh_key == other.mesh_key
+            && self.bind_group_data == other.bind_group_data
+            && self.stencil == other.stencil
+            && self.blend_state == other.blend_state
     }
 }
 
@@ -340,6 +379,8 @@ where
         Self {
             mesh_key: self.mesh_key,
             bind_group_data: self.bind_group_data.clone(),
+            stencil: self.stencil.clone(),
+            blend_state: self.blend_state,
         }
     }
 }
@@ -351,6 +392,8 @@ where
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.mesh_key.hash(state);
         self.bind_group_data.hash(state);
+        self.stencil.hash(state);
+        self.blend_state.hash(state);
     }
 }
 
@@ -398,6 +441,24 @@ where
 
         descriptor.layout.insert(2, self.material_layout.clone());
 
+        if key.mesh_key.contains(MeshPipelineKey::DEPTH_STENCIL) {
+            if let (Some(depth_stencil), Some(stencil)) =
+                (descriptor.depth_stencil.as_mut(), &key.stencil)
+            {
+                depth_stencil.stencil = stencil.clone();
+            }
+        }
+
+        if let (Some(blend_state), Some(fragment)) = (key.blend_state, descriptor.fragment.as_mut())
+        {
+            if let Some(Some(target)) = fragment.targets.first_mut() {
+                target.blend = Some(blend_state);
+            }
+            if uses_dual_source_blending(&blend_state) {
+                fragment.shader_defs.push("DUAL_SOURCE_BLENDING".into());
+            }
+        }
+
         M::specialize(self, &mut descriptor, layout, key)?;
         Ok(descriptor)
     }
@@ -431,6 +492,7 @@ type DrawMaterial<M> = (
     SetMeshViewBindGroup<0>,
     SetMeshBindGroup<1>,
     SetMaterialBindGroup<M, 2>,
+    SetMaterialStencilReference<M>,
     DrawMesh,
 );
 
@@ -466,8 +528,58 @@ impl<P: PhaseItem, M: Material, const I: usize> RenderCommand<P> for SetMaterial
     }
 }
 
+/// Sets the [`Material::stencil_reference`] of a [`Material`] that uses the stencil buffer.
+pub struct SetMaterialStencilReference<M: Material>(PhantomData<M>);
+impl<P: PhaseItem, M: Material> RenderCommand<P> for SetMaterialStencilReference<M> {
+    type Param = (
+        SRes<RenderAssets<PreparedMaterial<M>>>,
+        SRes<RenderMaterialInstances<M>>,
+    );
+    type ViewQuery = ();
+    type ItemQuery = ();
+
+    #[inline]
+    fn render<'w>(
+        item: &P,
+        _view: (),
+        _item_query: Option<()>,
+        (materials, material_instances): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let materials = materials.into_inner();
+        let material_instances = material_instances.into_inner();
+
+        let Some(material_asset_id) = material_instances.get(&item.entity()) else {
+            return RenderCommandResult::Failure;
+        };
+        let Some(material) = materials.get(*material_asset_id) else {
+            return RenderCommandResult::Failure;
+        };
+        if material.properties.stencil.is_some() {
+            pass.set_stencil_reference(material.properties.stencil_reference);
+        }
+        RenderCommandResult::Success
+    }
+}
+
 pub type RenderMaterialInstances<M> = ExtractedInstances<AssetId<M>>;
 
+/// Returns whether any factor of `blend_state` uses the second blend source of the shader.
+fn uses_dual_source_blending(blend_state: &BlendState) -> bool {
+    [blend_state.color, blend_state.alpha]
+        .iter()
+        .flat_map(|component| [component.src_factor, component.dst_factor])
+        .any(|factor| {
+            matches!(
+                factor,
+                BlendFactor::Src1
+                    | BlendFactor::OneMinusSrc1
+                    | BlendFactor::Src1Alpha
+                    | BlendFactor::OneMinusSrc1Alpha
+            )
+        })
+}
+
 pub const fn alpha_mode_pipeline_key(alpha_mode: AlphaMode, msaa: &Msaa) -> MeshPipelineKey {
     match alpha_mode {
         // Premultiplied and Add share the same pipeline key
@@ -518,6 +630,29 @@ pub const fn screen_space_specular_transmission_pipeline_key(
     }
 }
 
+pub const fn cluster_debug_visualization_pipeline_key(
+    cluster_debug_visualization: ClusterDebugVisualization,
+) -> MeshPipelineKey {
+    match cluster_debug_visualization {
+        ClusterDebugVisualization::ClusterableObjectCount => {
+            MeshPipelineKey::CLUSTER_DEBUG_OBJECT_COUNT
+        }
+        ClusterDebugVisualization::ZSlices => MeshPipelineKey::CLUSTER_DEBUG_Z_SLICES,
+        ClusterDebugVisualization::Clusters => MeshPipelineKey::CLUSTER_DEBUG_CLUSTERS,
+    }
+}
+
+pub const fn debug_view_pipeline_key(debug_view_mode: DebugViewMode) -> MeshPipelineKey {
+    match debug_view_mode {
+        DebugViewMode::Depth => MeshPipelineKey::DEBUG_VIEW_DEPTH,
+        DebugViewMode::WorldNormals => MeshPipelineKey::DEBUG_VIEW_WORLD_NORMALS,
+        DebugViewMode::ViewNormals => MeshPipelineKey::DEBUG_VIEW_VIEW_NORMALS,
+        DebugViewMode::Overdraw => MeshPipelineKey::DEBUG_VIEW_OVERDRAW,
+        DebugViewMode::Cascades => MeshPipelineKey::DEBUG_VIEW_CASCADES,
+        DebugViewMode::LightmapUvs => MeshPipelineKey::DEBUG_VIEW_LIGHTMAP_UVS,
+    }
+}
+
 /// For each view, iterates over all the meshes visible from that view and adds
 /// them to [`BinnedRenderPhase`]s or [`SortedRenderPhase`]s as appropriate.
 #[allow(clippy::too_many_arguments)]
@@ -555,6 +690,7 @@ pub fn queue_material_meshes<M: Material>(
         Option<&DebandDither>,
         Option<&ShadowFilteringMethod>,
         Has<ScreenSpaceAmbientOcclusionSettings>,
+        Has<ScreenSpaceSubsurfaceScatteringUniform>,
         (
             Has<NormalPrepass>,
             Has<DepthPrepass>,
@@ -568,6 +704,8 @@ pub fn queue_material_meshes<M: Material>(
             Has<RenderViewLightProbes<EnvironmentMapLight>>,
             Has<RenderViewLightProbes<IrradianceVolume>>,
         ),
+        (Option<&ClusterDebugVisualization>, Option<&DebugViewMode>),
+        Has<ViewDepthStencil>,
     )>,
 ) where
     M::Data: PartialEq + Eq + Hash + Clone,
@@ -580,11 +718,14 @@ pub fn queue_material_meshes<M: Material>(
         dither,
         shadow_filter_method,
         ssao,
+        subsurface_scattering,
         (normal_prepass, depth_prepass, motion_vector_prepass, deferred_prepass),
         camera_3d,
         temporal_jitter,
         projection,
         (has_environment_maps, has_irradiance_volumes),
+        (cluster_debug_visualization, debug_view_mode),
+        depth_stencil,
     ) in &mut views
     {
         let (
@@ -638,6 +779,11 @@ pub fn queue_material_meshes<M: Material>(
             view_key |= MeshPipelineKey::IRRADIANCE_VOLUME;
         }
 
+        // The subsurface scattering pass can't read the resolved main texture with MSAA.
+        if subsurface_scattering && msaa.samples() == 1 {
+            view_key |= MeshPipelineKey::SCREEN_SPACE_SUBSURFACE_SCATTERING;
+        }
+
         if let Some(projection) = projection {
             view_key |= match projection {
                 Projection::Perspective(_) => MeshPipelineKey::VIEW_PROJECTION_PERSPECTIVE,
@@ -674,6 +820,15 @@ pub fn queue_material_meshes<M: Material>(
                 camera_3d.screen_space_specular_transmission_quality,
             );
         }
+        if let Some(cluster_debug_visualization) = cluster_debug_visualization {
+            view_key |= cluster_debug_visualization_pipeline_key(*cluster_debug_visualization);
+        }
+        if let Some(debug_view_mode) = debug_view_mode {
+            view_key |= debug_view_pipeline_key(*debug_view_mode);
+        }
+        if depth_stencil {
+            view_key |= MeshPipelineKey::DEPTH_STENCIL;
+        }
 
         let rangefinder = view.rangefinder3d();
         for visible_entity in visible_entities.iter::<WithMesh>() {
@@ -707,6 +862,13 @@ pub fn queue_material_meshes<M: Material>(
                 mesh_key |= MeshPipelineKey::VISIBILITY_RANGE_DITHER;
             }
 
+            if mesh_instance
+                .flags
+                .contains(RenderMeshInstanceFlags::DUAL_QUATERNION_SKINNING)
+            {
+                mesh_key |= MeshPipelineKey::DUAL_QUATERNION_SKINNING;
+            }
+
             if motion_vector_prepass {
                 // If the previous frame have skins or morph targets, note that.
                 if mesh_instance
@@ -729,6 +891,8 @@ pub fn queue_material_meshes<M: Material>(
                 MaterialPipelineKey {
                     mesh_key,
                     bind_group_data: material.key.clone(),
+                    stencil: material.properties.stencil.clone(),
+                    blend_state: material.properties.blend_state,
                 },
                 &mesh.layout,
             );
@@ -887,6 +1051,12 @@ pub struct MaterialProperties {
     /// This allows taking color output from the [`Opaque3d`] pass as an input, (for screen-space transmission) but requires
     /// rendering to take place in a separate [`Transmissive3d`] pass.
     pub reads_view_transmission_texture: bool,
+    /// The [`Material::stencil`] state of the material.
+    pub stencil: Option<StencilState>,
+    /// The [`Material::stencil_reference`] value of the material.
+    pub stencil_reference: u32,
+    /// The [`Material::blend_state`] of the material, if it's supported by the device.
+    pub blend_state: Option<BlendState>,
 }
 
 /// Data prepared for a [`Material`] instance.
@@ -932,6 +1102,19 @@ impl<M: Material> RenderAsset for PreparedMaterial<M> {
                 );
                 mesh_pipeline_key_bits.insert(alpha_mode_pipeline_key(material.alpha_mode(), msaa));
 
+                let blend_state = material.blend_state().filter(|blend_state| {
+                    let supported = !uses_dual_source_blending(blend_state)
+                        || render_device
+                            .features()
+                            .contains(WgpuFeatures::DUAL_SOURCE_BLENDING);
+                    if !supported {
+                        warn_once!(
+                            "Dual-source blending isn't supported by the device, falling back to the blending of the alpha mode"
+                        );
+                    }
+                    supported
+                });
+
                 Ok(PreparedMaterial {
                     bindings: prepared.bindings,
                     bind_group: prepared.bind_group,
@@ -943,6 +1126,9 @@ impl<M: Material> RenderAsset for PreparedMaterial<M> {
                             .contains(MeshPipelineKey::READS_VIEW_TRANSMISSION_TEXTURE),
                         render_method: method,
                         mesh_pipeline_key_bits,
+                        stencil: material.stencil(),
+                        stencil_reference: material.stencil_reference(),
+                        blend_state,
                     },
                 })
             }
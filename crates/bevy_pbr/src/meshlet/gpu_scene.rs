@@ -3,8 +3,8 @@ use super::{
     persistent_buffer::PersistentGpuBuffer,
 };
 use crate::{
-    Material, MeshFlags, MeshTransforms, MeshUniform, NotShadowCaster, NotShadowReceiver,
-    PreviousGlobalTransform, RenderMaterialInstances, ShadowView,
+    InstanceCustomData, Material, MeshFlags, MeshTransforms, MeshUniform, NotShadowCaster,
+    NotShadowReceiver, PreviousGlobalTransform, RenderMaterialInstances, ShadowView,
 };
 use bevy_asset::{AssetEvent, AssetId, AssetServer, Assets, Handle, UntypedAssetId};
 use bevy_core_pipeline::{
@@ -19,7 +19,7 @@ use bevy_ecs::{
     system::{Commands, Local, Query, Res, ResMut, Resource, SystemState},
     world::{FromWorld, World},
 };
-use bevy_math::{UVec2, Vec4Swizzles};
+use bevy_math::{UVec2, Vec4, Vec4Swizzles};
 use bevy_render::{
     render_resource::{binding_types::*, *},
     renderer::{RenderDevice, RenderQueue},
@@ -55,6 +55,7 @@ pub fn extract_meshlet_meshes(
                     Option<&RenderLayers>,
                     Has<NotShadowReceiver>,
                     Has<NotShadowCaster>,
+                    Option<&InstanceCustomData>,
                 )>,
                 Res<AssetServer>,
                 ResMut<Assets<MeshletMesh>>,
@@ -101,6 +102,7 @@ pub fn extract_meshlet_meshes(
         render_layers,
         not_shadow_receiver,
         not_shadow_caster,
+        custom_data,
     ) in &instances_query
     {
         // Skip instances with an unloaded MeshletMesh asset
@@ -135,10 +137,11 @@ pub fn extract_meshlet_meshes(
             previous_world_from_local: (&previous_transform).into(),
             flags: flags.bits(),
         };
-        gpu_scene
-            .instance_uniforms
-            .get_mut()
-            .push(MeshUniform::new(&transforms, None));
+        gpu_scene.instance_uniforms.get_mut().push(MeshUniform::new(
+            &transforms,
+            None,
+            custom_data.map_or(Vec4::ZERO, |custom_data| custom_data.0),
+        ));
     }
 }
 
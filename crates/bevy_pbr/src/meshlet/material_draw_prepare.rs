@@ -153,6 +153,8 @@ pub fn prepare_material_meshlet_meshes_main_opaque_pass<M: Material>(
                 MaterialPipelineKey {
                     mesh_key: view_key,
                     bind_group_data: material.key.clone(),
+                    stencil: None,
+                    blend_state: None,
                 },
                 fake_vertex_buffer_layout,
             ) else {
@@ -288,6 +290,8 @@ pub fn prepare_material_meshlet_meshes_prepass<M: Material>(
                 MaterialPipelineKey {
                     mesh_key: view_key,
                     bind_group_data: material.key.clone(),
+                    stencil: None,
+                    blend_state: None,
                 },
                 fake_vertex_buffer_layout,
             ) else {
@@ -346,6 +346,26 @@ pub struct StandardMaterial {
     #[doc(alias = "extinction_color")]
     pub attenuation_color: Color,
 
+    /// How far, on average, light scatters beneath the material's surface before leaving it, in
+    /// world units.
+    ///
+    /// Defaults to `0.0`, i.e. no subsurface scattering. Values around `0.01` (1cm) suit skin,
+    /// and larger distances wax, marble or foliage.
+    ///
+    /// **Note:** Subsurface scattering is a screen-space effect: the diffuse light of the
+    /// material is blurred by this distance only on cameras with a
+    /// [`ScreenSpaceSubsurfaceScattering`](crate::ScreenSpaceSubsurfaceScattering) component,
+    /// and only with [`AlphaMode::Opaque`] or [`AlphaMode::Mask`] and forward rendering.
+    #[doc(alias = "sss")]
+    pub subsurface_radius: f32,
+
+    /// The color diffuse light takes as it scatters beneath the surface, multiplied with the
+    /// [`StandardMaterial::base_color`].
+    ///
+    /// Defaults to [`Color::WHITE`], i.e. no change. Only used when
+    /// [`StandardMaterial::subsurface_radius`] is above `0.0`.
+    pub subsurface_color: Color,
+
     /// The UV channel to use for the [`StandardMaterial::normal_map_texture`].
     ///
     /// Defaults to [`UvChannel::Uv0`].
@@ -595,6 +615,19 @@ pub struct StandardMaterial {
     /// [z-fighting]: https://en.wikipedia.org/wiki/Z-fighting
     pub depth_bias: f32,
 
+    /// Replaces the blending of the [`AlphaMode`] with the main pass color target, see
+    /// [`Material::blend_state`].
+    ///
+    /// With blend factors that use the second blend source, like [`BlendFactor::OneMinusSrc1`],
+    /// the shader outputs the shaded color as the first source and its alpha in all the channels
+    /// of the second one. An [`ExtendedMaterial`](crate::ExtendedMaterial) can write another
+    /// second source, such as a coverage per color channel for subpixel text rendering.
+    ///
+    /// Defaults to `None`.
+    // TODO: include this in reflection somehow (maybe via remote types like serde https://serde.rs/remote-derive.html)
+    #[reflect(ignore)]
+    pub blend_state: Option<BlendState>,
+
     /// The depth map used for [parallax mapping].
     ///
     /// It is a greyscale image where white represents bottom and black the top.
@@ -794,6 +827,8 @@ impl Default for StandardMaterial {
             ior: 1.5,
             attenuation_color: Color::WHITE,
             attenuation_distance: f32::INFINITY,
+            subsurface_radius: 0.0,
+            subsurface_color: Color::WHITE,
             occlusion_channel: UvChannel::Uv0,
             occlusion_texture: None,
             normal_map_channel: UvChannel::Uv0,
@@ -823,6 +858,7 @@ impl Default for StandardMaterial {
             fog_enabled: true,
             alpha_mode: AlphaMode::Opaque,
             depth_bias: 0.0,
+            blend_state: None,
             depth_map: None,
             parallax_depth_scale: 0.1,
             max_parallax_layer_count: 16.0,
@@ -882,6 +918,7 @@ bitflags::bitflags! {
         const CLEARCOAT_ROUGHNESS_TEXTURE = 1 << 15;
         const CLEARCOAT_NORMAL_TEXTURE   = 1 << 16;
         const ANISOTROPY_TEXTURE         = 1 << 17;
+        const SUBSURFACE_SCATTERING      = 1 << 18;
         const ALPHA_MODE_RESERVED_BITS   = Self::ALPHA_MODE_MASK_BITS << Self::ALPHA_MODE_SHIFT_BITS; // ← Bitmask reserving bits for the `AlphaMode`
         const ALPHA_MODE_OPAQUE          = 0 << Self::ALPHA_MODE_SHIFT_BITS;                          // ← Values are just sequential values bitshifted into
         const ALPHA_MODE_MASK            = 1 << Self::ALPHA_MODE_SHIFT_BITS;                          //   the bitmask, and can range from 0 to 7.
@@ -911,6 +948,8 @@ pub struct StandardMaterialUniform {
     pub emissive: Vec4,
     /// Color white light takes after travelling through the attenuation distance underneath the material surface
     pub attenuation_color: Vec4,
+    /// Color diffuse light takes as it scatters underneath the material surface
+    pub subsurface_color: Vec4,
     /// The transform applied to the UVs corresponding to `ATTRIBUTE_UV_0` on the mesh before sampling. Default is identity.
     pub uv_transform: Mat3,
     /// Linear perceptual roughness, clamped to [0.089, 1.0] in the shader
@@ -931,6 +970,8 @@ pub struct StandardMaterialUniform {
     pub ior: f32,
     /// How far light travels through the volume underneath the material surface before being absorbed
     pub attenuation_distance: f32,
+    /// How far light scatters underneath the material surface before leaving it
+    pub subsurface_radius: f32,
     pub clearcoat: f32,
     pub clearcoat_perceptual_roughness: f32,
     pub anisotropy_strength: f32,
@@ -1056,6 +1097,13 @@ impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
             flags |= StandardMaterialFlags::ATTENUATION_ENABLED;
         }
 
+        // The subsurface scattering pass only sees opaque surfaces.
+        if self.subsurface_radius > 0.0
+            && matches!(self.alpha_mode, AlphaMode::Opaque | AlphaMode::Mask(_))
+        {
+            flags |= StandardMaterialFlags::SUBSURFACE_SCATTERING;
+        }
+
         let mut emissive = self.emissive.to_vec4();
         emissive[3] = self.emissive_exposure_weight;
 
@@ -1083,6 +1131,10 @@ impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
             attenuation_color: LinearRgba::from(self.attenuation_color)
                 .to_f32_array()
                 .into(),
+            subsurface_color: LinearRgba::from(self.subsurface_color)
+                .to_f32_array()
+                .into(),
+            subsurface_radius: self.subsurface_radius,
             flags: flags.bits(),
             alpha_cutoff,
             parallax_depth_scale: self.parallax_depth_scale,
@@ -1267,6 +1319,11 @@ impl Material for StandardMaterial {
         self.depth_bias
     }
 
+    #[inline]
+    fn blend_state(&self) -> Option<BlendState> {
+        self.blend_state
+    }
+
     #[inline]
     fn reads_view_transmission_texture(&self) -> bool {
         self.specular_transmission > 0.0
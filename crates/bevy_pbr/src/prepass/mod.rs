@@ -40,6 +40,9 @@ use std::{hash::Hash, marker::PhantomData};
 
 pub const PREPASS_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(921124473254008983);
 
+pub const PREPASS_VERTEX_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(11963945581301685316);
+
 pub const PREPASS_BINDINGS_SHADER_HANDLE: Handle<Shader> =
     Handle::weak_from_u128(5533152893177403494);
 
@@ -70,6 +73,13 @@ where
             Shader::from_wgsl
         );
 
+        load_internal_asset!(
+            app,
+            PREPASS_VERTEX_SHADER_HANDLE,
+            "prepass_vertex.wgsl",
+            Shader::from_wgsl
+        );
+
         load_internal_asset!(
             app,
             PREPASS_BINDINGS_SHADER_HANDLE,
@@ -818,6 +828,13 @@ pub fn queue_prepass_material_meshes<M: Material>(
                 mesh_key |= MeshPipelineKey::LIGHTMAPPED;
             }
 
+            if mesh_instance
+                .flags
+                .contains(RenderMeshInstanceFlags::DUAL_QUATERNION_SKINNING)
+            {
+                mesh_key |= MeshPipelineKey::DUAL_QUATERNION_SKINNING;
+            }
+
             // If the previous frame has skins or morph targets, note that.
             if motion_vector_prepass.is_some() {
                 if mesh_instance
@@ -840,6 +857,8 @@ pub fn queue_prepass_material_meshes<M: Material>(
                 MaterialPipelineKey {
                     mesh_key,
                     bind_group_data: material.key.clone(),
+                    stencil: None,
+                    blend_state: None,
                 },
                 &mesh.layout,
             );
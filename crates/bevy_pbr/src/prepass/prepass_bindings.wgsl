@@ -1,10 +1,14 @@
 #define_import_path bevy_pbr::prepass_bindings
 
+#import bevy_render::globals::Globals
+
 struct PreviousViewUniforms {
     view_from_world: mat4x4<f32>,
     clip_from_world: mat4x4<f32>,
 }
 
+@group(0) @binding(1) var<uniform> globals: Globals;
+
 #ifdef MOTION_VECTOR_PREPASS
 @group(0) @binding(2) var<uniform> previous_view_uniforms: PreviousViewUniforms;
 #endif // MOTION_VECTOR_PREPASS
@@ -13,8 +13,8 @@
 // NOTE: Keep in sync with bevy_pbr/src/light.rs
 fn view_z_to_z_slice(view_z: f32, is_orthographic: bool) -> u32 {
     var z_slice: u32 = 0u;
-    if is_orthographic {
-        // NOTE: view_z is correct in the orthographic case
+    if is_orthographic || bindings::lights.linear_cluster_z_slicing != 0u {
+        // NOTE: view_z is correct in the linear case
         z_slice = u32(floor((view_z - bindings::lights.cluster_factors.z) * bindings::lights.cluster_factors.w));
     } else {
         // NOTE: had to use -view_z to make it positive else log(negative) is nan
@@ -84,11 +84,11 @@ fn cluster_debug_visualization(
     if (z_slice & 1u) == 1u {
         z_slice = z_slice + bindings::lights.cluster_dimensions.z / 2u;
     }
-    let slice_color = hsv_to_rgb(
+    let slice_color = hsv_to_rgb(vec3(
         f32(z_slice) / f32(bindings::lights.cluster_dimensions.z + 1u) * PI_2,
         1.0,
         0.5
-    );
+    ));
     output_color = vec4<f32>(
         (1.0 - cluster_overlay_alpha) * output_color.rgb + cluster_overlay_alpha * slice_color,
         output_color.a
@@ -97,25 +97,25 @@ fn cluster_debug_visualization(
 #ifdef CLUSTERED_FORWARD_DEBUG_CLUSTER_COMPLEXITY
     // NOTE: This debug mode visualises the number of clusterable objects within
     // the cluster that contains the fragment. It shows a sort of cluster
-    // complexity measure.
-    let cluster_overlay_alpha = 0.1;
+    // complexity measure, as a heatmap going from blue for no objects through
+    // green and yellow to red for `max_complexity_per_cluster` or more.
+    let cluster_overlay_alpha = 0.5;
     let max_complexity_per_cluster = 64.0;
-    output_color.r = (1.0 - cluster_overlay_alpha) * output_color.r + cluster_overlay_alpha *
-        smoothStep(
-            0.0,
-            max_complexity_per_cluster,
-            f32(offset_and_counts[1] + offset_and_counts[2]));
-    output_color.g = (1.0 - cluster_overlay_alpha) * output_color.g + cluster_overlay_alpha *
-        (1.0 - smoothStep(
-            0.0,
-            max_complexity_per_cluster,
-            f32(offset_and_counts[1] + offset_and_counts[2])));
+    let complexity = saturate(
+        f32(offset_and_counts[1] + offset_and_counts[2]) / max_complexity_per_cluster
+    );
+    // Hues from 240° for blue down to 0° for red
+    let heat_color = hsv_to_rgb(vec3((1.0 - complexity) * PI_2 * (2.0 / 3.0), 1.0, 1.0));
+    output_color = vec4<f32>(
+        (1.0 - cluster_overlay_alpha) * output_color.rgb + cluster_overlay_alpha * heat_color,
+        output_color.a
+    );
 #endif // CLUSTERED_FORWARD_DEBUG_CLUSTER_COMPLEXITY
 #ifdef CLUSTERED_FORWARD_DEBUG_CLUSTER_COHERENCY
     // NOTE: Visualizes the cluster to which the fragment belongs
     let cluster_overlay_alpha = 0.1;
     var rng = cluster_index;
-    let cluster_color = hsv_to_rgb(rand_f(&rng) * PI_2, 1.0, 0.5);
+    let cluster_color = hsv_to_rgb(vec3(rand_f(&rng) * PI_2, 1.0, 0.5));
     output_color = vec4<f32>(
         (1.0 - cluster_overlay_alpha) * output_color.rgb + cluster_overlay_alpha * cluster_color,
         output_color.a
@@ -0,0 +1,31 @@
+#define_import_path bevy_pbr::debug_view
+
+#import bevy_pbr::{
+    forward_io::VertexOutput,
+    mesh_view_bindings::view,
+    pbr_types::PbrInput,
+}
+
+// Replaces the shaded `color` with the `DebugViewMode` of the view.
+fn debug_view_color(in: VertexOutput, pbr_input: PbrInput, color: vec4<f32>) -> vec4<f32> {
+#ifdef DEBUG_VIEW_DEPTH
+    let distance = -(view.view_from_world * in.world_position).z;
+    return vec4(vec3(1.0 / (1.0 + 0.1 * distance)), 1.0);
+#else ifdef DEBUG_VIEW_WORLD_NORMALS
+    return vec4(pbr_input.N * 0.5 + 0.5, 1.0);
+#else ifdef DEBUG_VIEW_VIEW_NORMALS
+    let normal = normalize((view.view_from_world * vec4(pbr_input.N, 0.0)).xyz);
+    return vec4(normal * 0.5 + 0.5, 1.0);
+#else ifdef DEBUG_VIEW_OVERDRAW
+    // The fragments are added up by the blend state
+    return vec4(0.1, 0.04, 0.01, 1.0);
+#else ifdef DEBUG_VIEW_LIGHTMAP_UVS
+#ifdef VERTEX_UVS_B
+    return vec4(fract(in.uv_b), 0.0, 1.0);
+#else
+    return vec4(1.0, 0.0, 1.0, 1.0);
+#endif
+#else
+    return color;
+#endif
+}
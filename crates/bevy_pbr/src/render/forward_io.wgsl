@@ -57,4 +57,7 @@ struct VertexOutput {
 
 struct FragmentOutput {
     @location(0) color: vec4<f32>,
+#ifdef DUAL_SOURCE_BLENDING
+    @location(0) @second_blend_source blend: vec4<f32>,
+#endif
 }
@@ -4,8 +4,9 @@ use bevy_core_pipeline::core_3d::CORE_3D_DEPTH_FORMAT;
 use bevy_ecs::entity::EntityHashSet;
 use bevy_ecs::prelude::*;
 use bevy_ecs::{entity::EntityHashMap, system::lifetimeless::Read};
-use bevy_math::{Mat4, UVec4, Vec2, Vec3, Vec3Swizzles, Vec4, Vec4Swizzles};
+use bevy_math::{Mat4, UVec2, UVec4, Vec2, Vec3, Vec3Swizzles, Vec4, Vec4Swizzles};
 use bevy_render::{
+    camera::Viewport,
     diagnostic::RecordDiagnostics,
     mesh::GpuMesh,
     primitives::{CascadesFrusta, CubemapFrusta, Frustum, HalfSpace},
@@ -24,6 +25,8 @@ use bevy_utils::tracing::info_span;
 use bevy_utils::tracing::{error, warn};
 use std::{hash::Hash, ops::Range};
 
+use super::shadow_atlas::allocate_shadow_atlas;
+use crate::light_texture::LIGHT_TEXTURES_ARE_USABLE;
 use crate::*;
 
 #[derive(Component)]
@@ -36,8 +39,12 @@ pub struct ExtractedPointLight {
     pub transform: GlobalTransform,
     pub shadows_enabled: bool,
     pub shadow_depth_bias: f32,
+    /// For spot lights, this is already scaled by the texel size of the shadow map. The texel size
+    /// of point light shadow maps is only known once they are allocated in the shadow atlas.
     pub shadow_normal_bias: f32,
+    pub shadow_atlas_priority: f32,
     pub spot_light_angles: Option<(f32, f32)>,
+    pub light_texture: Option<ExtractedLightTexture>,
 }
 
 #[derive(Component, Debug)]
@@ -61,16 +68,26 @@ bitflags::bitflags! {
     struct PointLightFlags: u32 {
         const SHADOWS_ENABLED            = 1 << 0;
         const SPOT_LIGHT_Y_NEGATIVE      = 1 << 1;
+        const SPOT_LIGHT                 = 1 << 2;
+        const LIGHT_TEXTURE              = 1 << 3;
+        const LIGHT_TEXTURE_PHOTOMETRIC  = 1 << 4;
         const NONE                       = 0;
         const UNINITIALIZED              = 0xFFFF;
     }
 }
 
+/// The flags of a [`GpuClusterableObject`] store the layer of its light texture from this bit
+/// upwards, see [`PointLightFlags::LIGHT_TEXTURE`].
+const POINT_LIGHT_FLAGS_LIGHT_TEXTURE_LAYER_SHIFT: u32 = 24;
+
 #[derive(Copy, Clone, ShaderType, Default, Debug)]
 pub struct GpuDirectionalCascade {
     clip_from_world: Mat4,
     texel_size: f32,
     far_bound: f32,
+    depth_bias: f32,
+    normal_bias: f32,
+    overlap_proportion: f32,
 }
 
 #[derive(Copy, Clone, ShaderType, Default, Debug)]
@@ -93,6 +110,7 @@ bitflags::bitflags! {
     struct DirectionalLightFlags: u32 {
         const SHADOWS_ENABLED            = 1 << 0;
         const VOLUMETRIC                 = 1 << 1;
+        const VISUALIZE_CASCADES         = 1 << 2;
         const NONE                       = 0;
         const UNINITIALIZED              = 0xFFFF;
     }
@@ -108,6 +126,8 @@ pub struct GpuLights {
     // z is cluster_dimensions.z / log(far / near)
     // w is cluster_dimensions.z * log(near) / log(far / near)
     cluster_factors: Vec4,
+    // 1 if the depth slices of the clusters are linear, see `ClusterZSlicing`
+    linear_cluster_z_slicing: u32,
     n_directional_lights: u32,
     // offset from spot light's light index to spot light's shadow map index
     spot_light_shadowmap_offset: i32,
@@ -171,7 +191,7 @@ impl FromWorld for ShadowSamplers {
 #[allow(clippy::too_many_arguments)]
 pub fn extract_lights(
     mut commands: Commands,
-    point_light_shadow_map: Extract<Res<PointLightShadowMap>>,
+    shadow_atlas_settings: Extract<Res<ShadowAtlasSettings>>,
     directional_light_shadow_map: Extract<Res<DirectionalLightShadowMap>>,
     global_point_lights: Extract<Res<GlobalVisibleClusterableObjects>>,
     point_lights: Extract<
@@ -181,6 +201,8 @@ pub fn extract_lights(
             &GlobalTransform,
             &ViewVisibility,
             &CubemapFrusta,
+            Option<&LightTexture>,
+            Option<&ShadowAtlasPriority>,
         )>,
     >,
     spot_lights: Extract<
@@ -190,6 +212,7 @@ pub fn extract_lights(
             &GlobalTransform,
             &ViewVisibility,
             &Frustum,
+            Option<&LightTexture>,
         )>,
     >,
     directional_lights: Extract<
@@ -214,25 +237,23 @@ pub fn extract_lights(
 ) {
     // NOTE: These shadow map resources are extracted here as they are used here too so this avoids
     // races between scheduling of ExtractResourceSystems and this system.
-    if point_light_shadow_map.is_changed() {
-        commands.insert_resource(point_light_shadow_map.clone());
+    if shadow_atlas_settings.is_changed() {
+        commands.insert_resource(shadow_atlas_settings.clone());
     }
     if directional_light_shadow_map.is_changed() {
         commands.insert_resource(directional_light_shadow_map.clone());
     }
-    // This is the point light shadow map texel size for one face of the cube as a distance of 1.0
-    // world unit from the light.
-    // point_light_texel_size = 2.0 * 1.0 * tan(PI / 4.0) / cube face width in texels
-    // PI / 4.0 is half the cube face fov, tan(PI / 4.0) = 1.0, so this simplifies to:
-    // point_light_texel_size = 2.0 / cube face width in texels
-    // NOTE: When using various PCF kernel sizes, this will need to be adjusted, according to:
-    // https://catlikecoding.com/unity/tutorials/custom-srp/point-and-spot-shadows/
-    let point_light_texel_size = 2.0 / point_light_shadow_map.size as f32;
-
     let mut point_lights_values = Vec::with_capacity(*previous_point_lights_len);
     for entity in global_point_lights.iter().copied() {
-        let Ok((point_light, cubemap_visible_entities, transform, view_visibility, frusta)) =
-            point_lights.get(entity)
+        let Ok((
+            point_light,
+            cubemap_visible_entities,
+            transform,
+            view_visibility,
+            frusta,
+            light_texture,
+            shadow_atlas_priority,
+        )) = point_lights.get(entity)
         else {
             continue;
         };
@@ -253,11 +274,11 @@ pub fn extract_lights(
             transform: *transform,
             shadows_enabled: point_light.shadows_enabled,
             shadow_depth_bias: point_light.shadow_depth_bias,
-            // The factor of SQRT_2 is for the worst-case diagonal offset
-            shadow_normal_bias: point_light.shadow_normal_bias
-                * point_light_texel_size
-                * std::f32::consts::SQRT_2,
+            // Scaled by the texel size in `prepare_lights`, once the shadow map is allocated.
+            shadow_normal_bias: point_light.shadow_normal_bias,
+            shadow_atlas_priority: shadow_atlas_priority.copied().unwrap_or_default().0,
             spot_light_angles: None,
+            light_texture: light_texture.map(ExtractedLightTexture::from),
         };
         point_lights_values.push((
             entity,
@@ -273,8 +294,14 @@ pub fn extract_lights(
 
     let mut spot_lights_values = Vec::with_capacity(*previous_spot_lights_len);
     for entity in global_point_lights.iter().copied() {
-        if let Ok((spot_light, visible_entities, transform, view_visibility, frustum)) =
-            spot_lights.get(entity)
+        if let Ok((
+            spot_light,
+            visible_entities,
+            transform,
+            view_visibility,
+            frustum,
+            light_texture,
+        )) = spot_lights.get(entity)
         {
             if !view_visibility.get() {
                 continue;
@@ -306,7 +333,9 @@ pub fn extract_lights(
                         shadow_normal_bias: spot_light.shadow_normal_bias
                             * texel_size
                             * std::f32::consts::SQRT_2,
+                        shadow_atlas_priority: 1.0,
                         spot_light_angles: Some((spot_light.inner_angle, spot_light.outer_angle)),
+                        light_texture: light_texture.map(ExtractedLightTexture::from),
                     },
                     render_visible_entities,
                     *frustum,
@@ -358,6 +387,13 @@ pub fn extract_lights(
 
 pub(crate) const POINT_LIGHT_NEAR_Z: f32 = 0.1f32;
 
+/// The border, in texels, around each face of a point light shadow map in the shadow atlas.
+///
+/// The faces are rendered with a field of view slightly wider than a cube face, so that filtering
+/// at the edges of a face reads this border rather than the neighboring faces of the atlas.
+// This must match POINT_SHADOW_FACE_PADDING in shadow_sampling.wgsl
+pub(crate) const SHADOW_ATLAS_FACE_PADDING: u32 = 1;
+
 pub(crate) struct CubeMapFace {
     pub(crate) target: Vec3,
     pub(crate) up: Vec3,
@@ -419,6 +455,8 @@ fn face_index_to_name(face_index: usize) -> &'static str {
 pub struct ShadowView {
     pub depth_attachment: DepthAttachment,
     pub pass_name: String,
+    /// The region of the depth attachment to render to, the whole attachment if `None`.
+    pub viewport: Option<Viewport>,
 }
 
 #[derive(Component)]
@@ -462,9 +500,9 @@ pub fn calculate_cluster_factors(
     near: f32,
     far: f32,
     z_slices: f32,
-    is_orthographic: bool,
+    linear_z_slicing: bool,
 ) -> Vec2 {
-    if is_orthographic {
+    if linear_z_slicing {
         Vec2::new(-near, z_slices / (-far - -near))
     } else {
         let z_slices_of_ln_zfar_over_znear = (z_slices - 1.0) / (far / near).ln();
@@ -502,6 +540,16 @@ pub(crate) fn spot_light_world_from_view(transform: &GlobalTransform) -> Mat4 {
     )
 }
 
+/// The projection of a face of a point light shadow map of `face_resolution` texels, whose inner
+/// texels cover the cube face and whose [`SHADOW_ATLAS_FACE_PADDING`] covers a bit more.
+pub(crate) fn point_light_face_clip_from_view(face_resolution: u32) -> Mat4 {
+    let inner_resolution = face_resolution
+        .saturating_sub(2 * SHADOW_ATLAS_FACE_PADDING)
+        .max(1);
+    let half_fov = (face_resolution as f32 / inner_resolution as f32).atan();
+    Mat4::perspective_infinite_reverse_rh(half_fov * 2.0, 1.0, POINT_LIGHT_NEAR_Z)
+}
+
 pub(crate) fn spot_light_clip_from_view(angle: f32) -> Mat4 {
     // spot light projection FOV is 2x the angle from spot light center to outer edge
     Mat4::perspective_infinite_reverse_rh(angle * 2.0, 1.0, POINT_LIGHT_NEAR_Z)
@@ -522,11 +570,15 @@ pub fn prepare_lights(
         Option<&RenderLayers>,
     )>,
     ambient_light: Res<AmbientLight>,
-    point_light_shadow_map: Res<PointLightShadowMap>,
-    directional_light_shadow_map: Res<DirectionalLightShadowMap>,
+    (shadow_atlas_settings, directional_light_shadow_map, light_textures): (
+        Res<ShadowAtlasSettings>,
+        Res<DirectionalLightShadowMap>,
+        Res<RenderLightTextures>,
+    ),
     mut shadow_render_phases: ResMut<ViewBinnedRenderPhases<Shadow>>,
     mut max_directional_lights_warning_emitted: Local<bool>,
     mut max_cascades_per_light_warning_emitted: Local<bool>,
+    mut shadow_atlas_full_warning_emitted: Local<bool>,
     point_lights: Query<(
         Entity,
         &ExtractedPointLight,
@@ -546,8 +598,9 @@ pub fn prepare_lights(
     };
 
     // Pre-calculate for PointLights
-    let cube_face_projection =
-        Mat4::perspective_infinite_reverse_rh(std::f32::consts::FRAC_PI_2, 1.0, POINT_LIGHT_NEAR_Z);
+    let shadow_atlas_size = shadow_atlas_settings
+        .size
+        .min(render_device.limits().max_texture_dimension_2d);
     let cube_face_rotations = CUBE_MAP_FACES
         .iter()
         .map(|CubeMapFace { target, up }| Transform::IDENTITY.looking_at(*target, *up))
@@ -564,16 +617,8 @@ pub fn prepare_lights(
         feature = "webgpu"
     ))]
     let max_texture_array_layers = render_device.limits().max_texture_array_layers as usize;
-    #[cfg(any(
-        not(feature = "webgl"),
-        not(target_arch = "wasm32"),
-        feature = "webgpu"
-    ))]
-    let max_texture_cubes = max_texture_array_layers / 6;
     #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
     let max_texture_array_layers = 1;
-    #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
-    let max_texture_cubes = 1;
 
     if !*max_directional_lights_warning_emitted && directional_lights.len() > MAX_DIRECTIONAL_LIGHTS
     {
@@ -605,8 +650,7 @@ pub fn prepare_lights(
     let point_light_shadow_maps_count = point_lights
         .iter()
         .filter(|light| light.1.shadows_enabled && light.1.spot_light_angles.is_none())
-        .count()
-        .min(max_texture_cubes);
+        .count();
 
     let directional_volumetric_enabled_count = directional_lights
         .iter()
@@ -630,8 +674,8 @@ pub fn prepare_lights(
 
     // Sort lights by
     // - point-light vs spot-light, so that we can iterate point lights and spot lights in contiguous blocks in the fragment shader,
-    // - then those with shadows enabled first, so that the index can be used to render at most `point_light_shadow_maps_count`
-    //   point light shadows and `spot_light_shadow_maps_count` spot light shadow maps,
+    // - then those with shadows enabled first, so that the index can be used to find the point light shadows allocated in the
+    //   shadow atlas and to render at most `spot_light_shadow_maps_count` spot light shadow maps,
     // - then by entity as a stable key to ensure that a consistent set of lights are chosen if the light count limit is exceeded.
     point_lights.sort_by(|(entity_1, light_1, _), (entity_2, light_2, _)| {
         crate::cluster::clusterable_object_order(
@@ -663,6 +707,54 @@ pub fn prepare_lights(
         )
     });
 
+    // Budget the resolution of point light shadows from the size of their range on screen, in the
+    // view where they are largest, as the light buffer and so the atlas layout is shared by all views.
+    let desired_shadow_resolutions = point_lights
+        .iter()
+        // Lights are sorted, shadow enabled point lights are first
+        .take(point_light_shadow_maps_count)
+        .map(|(_, light, _)| {
+            let screen_height = views
+                .iter()
+                .map(|(_, view, _, _)| {
+                    let distance = light
+                        .transform
+                        .translation()
+                        .distance(view.world_from_view.translation());
+                    let is_orthographic = view.clip_from_view.w_axis.w == 1.0;
+                    let screen_fraction = if is_orthographic {
+                        light.range * view.clip_from_view.y_axis.y
+                    } else if distance > light.range {
+                        light.range * view.clip_from_view.y_axis.y
+                            / (distance * distance - light.range * light.range).sqrt()
+                    } else {
+                        1.0
+                    };
+                    screen_fraction.min(1.0) * view.viewport.w as f32
+                })
+                .fold(0.0, f32::max);
+            screen_height * light.shadow_atlas_priority
+        })
+        .collect::<Vec<_>>();
+    let point_light_shadow_tiles = allocate_shadow_atlas(
+        shadow_atlas_size,
+        shadow_atlas_settings.min_resolution,
+        shadow_atlas_settings.max_resolution,
+        &desired_shadow_resolutions,
+    );
+    let dropped_shadows_count = point_light_shadow_tiles
+        .iter()
+        .filter(|tile| tile.is_none())
+        .count();
+    if !*shadow_atlas_full_warning_emitted && dropped_shadows_count > 0 {
+        warn!(
+            "The shadow atlas is too small for the shadows of all point lights, {} of them won't cast shadows. \
+            Consider increasing `ShadowAtlasSettings::size` or lowering `ShadowAtlasSettings::min_resolution`.",
+            dropped_shadows_count
+        );
+        *shadow_atlas_full_warning_emitted = true;
+    }
+
     if global_light_meta.entity_to_index.capacity() < point_lights.len() {
         global_light_meta
             .entity_to_index
@@ -672,18 +764,36 @@ pub fn prepare_lights(
     let mut gpu_point_lights = Vec::new();
     for (index, &(entity, light, _)) in point_lights.iter().enumerate() {
         let mut flags = PointLightFlags::NONE;
+        let shadow_tile = point_light_shadow_tiles.get(index).copied().flatten();
 
         // Lights are sorted, shadow enabled lights are first
         if light.shadows_enabled
-            && (index < point_light_shadow_maps_count
+            && (shadow_tile.is_some()
                 || (light.spot_light_angles.is_some()
                     && index - point_light_count < spot_light_shadow_maps_count))
         {
             flags |= PointLightFlags::SHADOWS_ENABLED;
         }
 
+        // Lights whose light texture isn't ready yet are drawn without it.
+        let light_texture_layer = light
+            .light_texture
+            .filter(|_| LIGHT_TEXTURES_ARE_USABLE)
+            .and_then(|light_texture| {
+                let layer = light_textures.layer(light_texture.image)?;
+                flags |= PointLightFlags::LIGHT_TEXTURE;
+                if light_texture.mapping == LightTextureMapping::Photometric
+                    || light.spot_light_angles.is_none()
+                {
+                    flags |= PointLightFlags::LIGHT_TEXTURE_PHOTOMETRIC;
+                }
+                Some(layer)
+            })
+            .unwrap_or(0);
+
         let (light_custom_data, spot_light_tan_angle) = match light.spot_light_angles {
             Some((inner, outer)) => {
+                flags |= PointLightFlags::SPOT_LIGHT;
                 let light_direction = light.transform.forward();
                 if light_direction.y.is_sign_negative() {
                     flags |= PointLightFlags::SPOT_LIGHT_Y_NEGATIVE;
@@ -700,20 +810,39 @@ pub fn prepare_lights(
                 )
             }
             None => {
+                let (tile_origin, face_size) = shadow_tile.map_or((Vec2::ZERO, 0.0), |tile| {
+                    (
+                        tile.origin.as_vec2() / shadow_atlas_size as f32,
+                        tile.face_resolution as f32 / shadow_atlas_size as f32,
+                    )
+                });
                 (
-                    // For point lights: the lower-right 2x2 values of the projection matrix [2][2] [2][3] [3][2] [3][3]
-                    Vec4::new(
-                        cube_face_projection.z_axis.z,
-                        cube_face_projection.z_axis.w,
-                        cube_face_projection.w_axis.z,
-                        cube_face_projection.w_axis.w,
-                    ),
+                    // For point lights: the position of the shadow map in the atlas and the size of
+                    // its faces, in UVs, and the near plane of the projection of the faces
+                    tile_origin.extend(face_size).extend(POINT_LIGHT_NEAR_Z),
                     // unused
                     0.0,
                 )
             }
         };
 
+        // This is the point light shadow map texel size for one face of the cube as a distance of 1.0
+        // world unit from the light.
+        // point_light_texel_size = 2.0 * 1.0 * tan(PI / 4.0) / cube face width in texels
+        // PI / 4.0 is half the cube face fov, tan(PI / 4.0) = 1.0, so this simplifies to:
+        // point_light_texel_size = 2.0 / cube face width in texels
+        // NOTE: When using various PCF kernel sizes, this will need to be adjusted, according to:
+        // https://catlikecoding.com/unity/tutorials/custom-srp/point-and-spot-shadows/
+        // The factor of SQRT_2 is for the worst-case diagonal offset
+        // The cube face is covered by the texels inside of the padding of the face.
+        let shadow_normal_bias = shadow_tile.map_or(light.shadow_normal_bias, |tile| {
+            let inner_resolution = tile
+                .face_resolution
+                .saturating_sub(2 * SHADOW_ATLAS_FACE_PADDING)
+                .max(1);
+            light.shadow_normal_bias * 2.0 / inner_resolution as f32 * std::f32::consts::SQRT_2
+        });
+
         gpu_point_lights.push(GpuClusterableObject {
             light_custom_data,
             // premultiply color by intensity
@@ -723,9 +852,10 @@ pub fn prepare_lights(
                 .xyz()
                 .extend(1.0 / (light.range * light.range)),
             position_radius: light.transform.translation().extend(light.radius),
-            flags: flags.bits(),
+            flags: flags.bits()
+                | (light_texture_layer << POINT_LIGHT_FLAGS_LIGHT_TEXTURE_LAYER_SHIFT),
             shadow_depth_bias: light.shadow_depth_bias,
-            shadow_normal_bias: light.shadow_normal_bias,
+            shadow_normal_bias,
             spot_light_tan_angle,
         });
         global_light_meta.entity_to_index.insert(entity, index);
@@ -751,6 +881,9 @@ pub fn prepare_lights(
         if light.shadows_enabled && (index < directional_shadow_enabled_count) {
             flags |= DirectionalLightFlags::SHADOWS_ENABLED;
         }
+        if light.cascade_shadow_config.visualize_cascades {
+            flags |= DirectionalLightFlags::VISUALIZE_CASCADES;
+        }
 
         let num_cascades = light
             .cascade_shadow_config
@@ -794,15 +927,15 @@ pub fn prepare_lights(
             &render_device,
             TextureDescriptor {
                 size: Extent3d {
-                    width: point_light_shadow_map.size as u32,
-                    height: point_light_shadow_map.size as u32,
-                    depth_or_array_layers: point_light_shadow_maps_count.max(1) as u32 * 6,
+                    width: shadow_atlas_size,
+                    height: shadow_atlas_size,
+                    depth_or_array_layers: 1,
                 },
                 mip_level_count: 1,
                 sample_count: 1,
                 dimension: TextureDimension::D2,
                 format: CORE_3D_DEPTH_FORMAT,
-                label: Some("point_light_shadow_map_texture"),
+                label: Some("point_light_shadow_atlas_texture"),
                 usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
                 view_formats: &[],
             },
@@ -830,12 +963,11 @@ pub fn prepare_lights(
         );
         let mut view_lights = Vec::new();
 
-        let is_orthographic = extracted_view.clip_from_view.w_axis.w == 1.0;
         let cluster_factors_zw = calculate_cluster_factors(
             clusters.near,
             clusters.far,
             clusters.dimensions.z as f32,
-            is_orthographic,
+            clusters.linear_z_slicing,
         );
 
         let n_clusters = clusters.dimensions.x * clusters.dimensions.y * clusters.dimensions.z;
@@ -850,6 +982,7 @@ pub fn prepare_lights(
                 cluster_factors_zw.y,
             ),
             cluster_dimensions: clusters.dimensions.extend(n_clusters),
+            linear_cluster_z_slicing: clusters.linear_z_slicing as u32,
             n_directional_lights: directional_lights.iter().len().min(MAX_DIRECTIONAL_LIGHTS)
                 as u32,
             // spotlight shadow maps are stored in the directional light array, starting at num_directional_cascades_enabled.
@@ -859,13 +992,17 @@ pub fn prepare_lights(
                 - point_light_count as i32,
         };
 
-        // TODO: this should select lights based on relevance to the view instead of the first ones that show up in a query
-        for &(light_entity, light, (point_light_frusta, _)) in point_lights
-            .iter()
-            // Lights are sorted, shadow enabled lights are first
-            .take(point_light_shadow_maps_count)
-            .filter(|(_, light, _)| light.shadows_enabled)
+        // All the faces render into the same atlas, it is only cleared by the first of them.
+        let point_light_depth_attachment =
+            DepthAttachment::new(point_light_depth_texture.default_view.clone(), Some(0.0));
+
+        // Lights are sorted, shadow enabled point lights are first and line up with their tiles
+        for (&(light_entity, light, (point_light_frusta, _)), tile) in
+            point_lights.iter().zip(&point_light_shadow_tiles)
         {
+            let Some(tile) = tile else {
+                continue;
+            };
             let light_index = *global_light_meta
                 .entity_to_index
                 .get(&light_entity)
@@ -880,40 +1017,33 @@ pub fn prepare_lights(
                 .zip(&point_light_frusta.unwrap().frusta)
                 .enumerate()
             {
-                let depth_texture_view =
-                    point_light_depth_texture
-                        .texture
-                        .create_view(&TextureViewDescriptor {
-                            label: Some("point_light_shadow_map_texture_view"),
-                            format: None,
-                            dimension: Some(TextureViewDimension::D2),
-                            aspect: TextureAspect::All,
-                            base_mip_level: 0,
-                            mip_level_count: None,
-                            base_array_layer: (light_index * 6 + face_index) as u32,
-                            array_layer_count: Some(1u32),
-                        });
+                let face_origin = tile.face_origin(face_index);
 
                 let view_light_entity = commands
                     .spawn((
                         ShadowView {
-                            depth_attachment: DepthAttachment::new(depth_texture_view, Some(0.0)),
+                            depth_attachment: point_light_depth_attachment.clone(),
                             pass_name: format!(
                                 "shadow pass point light {} {}",
                                 light_index,
                                 face_index_to_name(face_index)
                             ),
+                            viewport: Some(Viewport {
+                                physical_position: face_origin,
+                                physical_size: UVec2::splat(tile.face_resolution),
+                                ..Default::default()
+                            }),
                         },
                         ExtractedView {
                             viewport: UVec4::new(
-                                0,
-                                0,
-                                point_light_shadow_map.size as u32,
-                                point_light_shadow_map.size as u32,
+                                face_origin.x,
+                                face_origin.y,
+                                tile.face_resolution,
+                                tile.face_resolution,
                             ),
                             world_from_view: view_translation * *view_rotation,
                             clip_from_world: None,
-                            clip_from_view: cube_face_projection,
+                            clip_from_view: point_light_face_clip_from_view(tile.face_resolution),
                             hdr: false,
                             color_grading: Default::default(),
                         },
@@ -964,6 +1094,7 @@ pub fn prepare_lights(
                     ShadowView {
                         depth_attachment: DepthAttachment::new(depth_texture_view, Some(0.0)),
                         pass_name: format!("shadow pass spot light {light_index}"),
+                        viewport: None,
                     },
                     ExtractedView {
                         viewport: UVec4::new(
@@ -1027,11 +1158,27 @@ pub fn prepare_lights(
                 .zip(&light.cascade_shadow_config.bounds)
                 .enumerate()
             {
+                let overrides = light
+                    .cascade_shadow_config
+                    .cascade_overrides
+                    .get(cascade_index)
+                    .copied()
+                    .unwrap_or_default();
                 gpu_lights.directional_lights[light_index].cascades[cascade_index] =
                     GpuDirectionalCascade {
                         clip_from_world: cascade.clip_from_world,
                         texel_size: cascade.texel_size,
                         far_bound: *bound,
+                        depth_bias: overrides.depth_bias.unwrap_or(light.shadow_depth_bias),
+                        // The factor of SQRT_2 is for the worst-case diagonal offset
+                        normal_bias: overrides
+                            .normal_bias
+                            .map_or(light.shadow_normal_bias, |normal_bias| {
+                                normal_bias * std::f32::consts::SQRT_2
+                            }),
+                        overlap_proportion: light
+                            .cascade_shadow_config
+                            .cascade_overlap_proportion(cascade_index),
                     };
 
                 let depth_texture_view =
@@ -1060,6 +1207,7 @@ pub fn prepare_lights(
                             depth_attachment: DepthAttachment::new(depth_texture_view, Some(0.0)),
                             pass_name: format!(
                                 "shadow pass directional light {light_index} cascade {cascade_index}"),
+                            viewport: None,
                         },
                         ExtractedView {
                             viewport: UVec4::new(
@@ -1092,24 +1240,9 @@ pub fn prepare_lights(
             point_light_depth_texture
                 .texture
                 .create_view(&TextureViewDescriptor {
-                    label: Some("point_light_shadow_map_array_texture_view"),
+                    label: Some("point_light_shadow_atlas_texture_view"),
                     format: None,
-                    // NOTE: iOS Simulator is missing CubeArray support so we use Cube instead.
-                    // See https://github.com/bevyengine/bevy/pull/12052 - remove if support is added.
-                    #[cfg(all(
-                        not(feature = "ios_simulator"),
-                        any(
-                            not(feature = "webgl"),
-                            not(target_arch = "wasm32"),
-                            feature = "webgpu"
-                        )
-                    ))]
-                    dimension: Some(TextureViewDimension::CubeArray),
-                    #[cfg(any(
-                        feature = "ios_simulator",
-                        all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu"))
-                    ))]
-                    dimension: Some(TextureViewDimension::Cube),
+                    dimension: Some(TextureViewDimension::D2),
                     aspect: TextureAspect::DepthOnly,
                     base_mip_level: 0,
                     mip_level_count: None,
@@ -1251,6 +1384,13 @@ pub fn queue_shadows<M: Material>(
                     mesh_key |= MeshPipelineKey::LIGHTMAPPED;
                 }
 
+                if mesh_instance
+                    .flags
+                    .contains(RenderMeshInstanceFlags::DUAL_QUATERNION_SKINNING)
+                {
+                    mesh_key |= MeshPipelineKey::DUAL_QUATERNION_SKINNING;
+                }
+
                 mesh_key |= match material.properties.alpha_mode {
                     AlphaMode::Mask(_)
                     | AlphaMode::Blend
@@ -1265,6 +1405,8 @@ pub fn queue_shadows<M: Material>(
                     MaterialPipelineKey {
                         mesh_key,
                         bind_group_data: material.key.clone(),
+                        stencil: None,
+                        blend_state: None,
                     },
                     &mesh.layout,
                 );
@@ -1442,6 +1584,9 @@ impl Node for ShadowPassNode {
                     });
 
                     let mut render_pass = TrackedRenderPass::new(&render_device, render_pass);
+                    if let Some(viewport) = &view_light.viewport {
+                        render_pass.set_camera_viewport(viewport);
+                    }
                     let pass_span =
                         diagnostics.pass_span(&mut render_pass, view_light.pass_name.clone());
 
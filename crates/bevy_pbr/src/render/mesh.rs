@@ -2,7 +2,10 @@ use std::mem;
 
 use bevy_asset::{load_internal_asset, AssetId};
 use bevy_core_pipeline::{
-    core_3d::{AlphaMask3d, Opaque3d, Transmissive3d, Transparent3d, CORE_3D_DEPTH_FORMAT},
+    core_3d::{
+        AlphaMask3d, Opaque3d, Transmissive3d, Transparent3d, CORE_3D_DEPTH_FORMAT,
+        CORE_3D_DEPTH_STENCIL_FORMAT,
+    },
     deferred::{AlphaMask3dDeferred, Opaque3dDeferred},
     prepass::MotionVectorPrepass,
 };
@@ -14,6 +17,7 @@ use bevy_ecs::{
     system::{lifetimeless::*, SystemParamItem, SystemState},
 };
 use bevy_math::{Affine3, Rect, UVec2, Vec3, Vec4};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_render::{
     batching::{
         gpu_preprocessing::{
@@ -22,7 +26,7 @@ use bevy_render::{
         no_gpu_preprocessing, GetBatchData, GetFullBatchData, NoAutomaticBatching,
     },
     camera::Camera,
-    mesh::*,
+    mesh::{skinning::SkinningMethod, *},
     primitives::Aabb,
     render_asset::RenderAssets,
     render_phase::{
@@ -54,6 +58,7 @@ use crate::render::{
 use crate::*;
 
 use self::irradiance_volume::IRRADIANCE_VOLUMES_ARE_USABLE;
+use crate::light_texture::LIGHT_TEXTURES_ARE_USABLE;
 
 /// Provides support for rendering 3D meshes.
 #[derive(Default)]
@@ -72,6 +77,7 @@ pub const MESH_TYPES_HANDLE: Handle<Shader> = Handle::weak_from_u128(25060241019
 pub const MESH_BINDINGS_HANDLE: Handle<Shader> = Handle::weak_from_u128(16831548636314682308);
 pub const MESH_FUNCTIONS_HANDLE: Handle<Shader> = Handle::weak_from_u128(6300874327833745635);
 pub const MESH_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(3252377289100772450);
+pub const MESH_VERTEX_HANDLE: Handle<Shader> = Handle::weak_from_u128(7310268368959553553);
 pub const SKINNING_HANDLE: Handle<Shader> = Handle::weak_from_u128(13215291596265391738);
 pub const MORPH_HANDLE: Handle<Shader> = Handle::weak_from_u128(970982813587607345);
 
@@ -119,6 +125,12 @@ impl Plugin for MeshRenderPlugin {
             Shader::from_wgsl
         );
         load_internal_asset!(app, MESH_SHADER_HANDLE, "mesh.wgsl", Shader::from_wgsl);
+        load_internal_asset!(
+            app,
+            MESH_VERTEX_HANDLE,
+            "mesh_vertex.wgsl",
+            Shader::from_wgsl
+        );
         load_internal_asset!(app, SKINNING_HANDLE, "skinning.wgsl", Shader::from_wgsl);
         load_internal_asset!(app, MORPH_HANDLE, "morph.wgsl", Shader::from_wgsl);
 
@@ -245,6 +257,19 @@ impl Plugin for MeshRenderPlugin {
     }
 }
 
+/// Arbitrary data attached to a mesh instance, which material shaders can read
+/// with `bevy_pbr::mesh_functions::get_instance_custom_data`.
+///
+/// This is uploaded along with the transform of the mesh, so it's a cheap way
+/// to vary a material per entity, for example for tinting, dissolve amounts or
+/// selection highlights, without creating a material asset for each entity.
+/// Entities with different custom data can still be batched together.
+///
+/// Meshes without this component get zeros.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Deref, DerefMut, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq)]
+pub struct InstanceCustomData(pub Vec4);
+
 #[derive(Component)]
 pub struct MeshTransforms {
     pub world_from_local: Affine3,
@@ -274,6 +299,8 @@ pub struct MeshUniform {
     //
     // (MSB: most significant bit; LSB: least significant bit.)
     pub lightmap_uv_rect: UVec2,
+    /// The [`InstanceCustomData`] of the mesh.
+    pub custom_data: Vec4,
 }
 
 /// Information that has to be transferred from CPU to GPU in order to produce
@@ -304,6 +331,8 @@ pub struct MeshInputUniform {
     ///
     /// This is used for TAA. If not present, this will be `u32::MAX`.
     pub previous_input_index: u32,
+    /// The [`InstanceCustomData`] of the mesh.
+    pub custom_data: Vec4,
 }
 
 /// Information about each mesh instance needed to cull it on GPU.
@@ -330,7 +359,11 @@ pub struct MeshCullingData {
 pub struct MeshCullingDataBuffer(RawBufferVec<MeshCullingData>);
 
 impl MeshUniform {
-    pub fn new(mesh_transforms: &MeshTransforms, maybe_lightmap_uv_rect: Option<Rect>) -> Self {
+    pub fn new(
+        mesh_transforms: &MeshTransforms,
+        maybe_lightmap_uv_rect: Option<Rect>,
+        custom_data: Vec4,
+    ) -> Self {
         let (local_from_world_transpose_a, local_from_world_transpose_b) =
             mesh_transforms.world_from_local.inverse_transpose_3x3();
         Self {
@@ -340,6 +373,7 @@ impl MeshUniform {
             local_from_world_transpose_a,
             local_from_world_transpose_b,
             flags: mesh_transforms.flags,
+            custom_data,
         }
     }
 }
@@ -416,6 +450,8 @@ bitflags::bitflags! {
         /// The mesh had morph targets last frame and so they should be taken
         /// into account for motion vector computation.
         const HAS_PREVIOUS_MORPH      = 1 << 4;
+        /// The mesh is skinned with [`SkinningMethod::DualQuaternion`].
+        const DUAL_QUATERNION_SKINNING = 1 << 5;
     }
 }
 
@@ -431,6 +467,8 @@ pub struct RenderMeshInstanceCpu {
     ///
     /// This will be written into the [`MeshUniform`] at the appropriate time.
     pub transforms: MeshTransforms,
+    /// The [`InstanceCustomData`] of the mesh.
+    pub custom_data: Vec4,
 }
 
 /// CPU data that the render world needs to keep for each entity that contains a
@@ -489,6 +527,8 @@ pub struct RenderMeshInstanceGpuBuilder {
     pub previous_input_index: Option<NonMaxU32>,
     /// Various flags.
     pub mesh_flags: MeshFlags,
+    /// The [`InstanceCustomData`] of the mesh.
+    pub custom_data: Vec4,
 }
 
 /// The per-thread queues used during [`extract_meshes_for_gpu_building`].
@@ -724,6 +764,7 @@ impl RenderMeshInstanceGpuBuilder {
             world_from_local: self.world_from_local.to_transpose(),
             lightmap_uv_rect: self.lightmap_uv_rect,
             flags: self.mesh_flags.bits(),
+            custom_data: self.custom_data,
             previous_input_index: match self.previous_input_index {
                 Some(previous_input_index) => previous_input_index.into(),
                 None => u32::MAX,
@@ -815,6 +856,7 @@ pub fn extract_meshes_for_cpu_building(
             Has<NotShadowCaster>,
             Has<NoAutomaticBatching>,
             Has<VisibilityRange>,
+            Option<&InstanceCustomData>,
         )>,
     >,
 ) {
@@ -832,6 +874,7 @@ pub fn extract_meshes_for_cpu_building(
             not_shadow_caster,
             no_automatic_batching,
             visibility_range,
+            custom_data,
         )| {
             if !view_visibility.get() {
                 return;
@@ -869,6 +912,7 @@ pub fn extract_meshes_for_cpu_building(
                         flags: mesh_flags.bits(),
                     },
                     shared,
+                    custom_data: custom_data.map_or(Vec4::ZERO, |custom_data| custom_data.0),
                 },
             ));
         },
@@ -918,6 +962,7 @@ pub fn extract_meshes_for_gpu_building(
             Has<NotShadowCaster>,
             Has<NoAutomaticBatching>,
             Has<VisibilityRange>,
+            Option<&InstanceCustomData>,
         )>,
     >,
     cameras_query: Extract<Query<(), (With<Camera>, With<GpuCulling>)>>,
@@ -952,6 +997,7 @@ pub fn extract_meshes_for_gpu_building(
             not_shadow_caster,
             no_automatic_batching,
             visibility_range,
+            custom_data,
         )| {
             if !view_visibility.get() {
                 return;
@@ -998,6 +1044,7 @@ pub fn extract_meshes_for_gpu_building(
                 lightmap_uv_rect,
                 mesh_flags,
                 previous_input_index,
+                custom_data: custom_data.map_or(Vec4::ZERO, |custom_data| custom_data.0),
             };
 
             queue.push(entity, gpu_mesh_instance_builder, gpu_mesh_culling_data);
@@ -1026,6 +1073,9 @@ pub fn extract_meshes_for_gpu_building(
 /// [`crate::material::queue_material_meshes`] check the skin and morph target
 /// tables for each mesh, but that would be too slow in the hot mesh queuing
 /// loop.
+///
+/// For the same reason, this system also sets
+/// [`RenderMeshInstanceFlags::DUAL_QUATERNION_SKINNING`].
 fn set_mesh_motion_vector_flags(
     mut render_mesh_instances: ResMut<RenderMeshInstances>,
     skin_indices: Res<SkinIndices>,
@@ -1035,6 +1085,14 @@ fn set_mesh_motion_vector_flags(
         render_mesh_instances
             .insert_mesh_instance_flags(entity, RenderMeshInstanceFlags::HAS_PREVIOUS_SKIN);
     }
+    for (&entity, skin_index) in &skin_indices.current {
+        if skin_index.method == SkinningMethod::DualQuaternion {
+            render_mesh_instances.insert_mesh_instance_flags(
+                entity,
+                RenderMeshInstanceFlags::DUAL_QUATERNION_SKINNING,
+            );
+        }
+    }
     for &entity in morph_indices.prev.keys() {
         render_mesh_instances
             .insert_mesh_instance_flags(entity, RenderMeshInstanceFlags::HAS_PREVIOUS_MORPH);
@@ -1235,6 +1293,7 @@ impl GetBatchData for MeshPipeline {
             MeshUniform::new(
                 &mesh_instance.transforms,
                 maybe_lightmap.map(|lightmap| lightmap.uv_rect),
+                mesh_instance.custom_data,
             ),
             mesh_instance.should_batch().then_some((
                 mesh_instance.material_bind_group_id.get(),
@@ -1290,6 +1349,7 @@ impl GetFullBatchData for MeshPipeline {
         Some(MeshUniform::new(
             &mesh_instance.transforms,
             maybe_lightmap.map(|lightmap| lightmap.uv_rect),
+            mesh_instance.custom_data,
         ))
     }
 
@@ -1409,7 +1469,10 @@ bitflags::bitflags! {
         const SCREEN_SPACE_REFLECTIONS          = 1 << 16;
         const HAS_PREVIOUS_SKIN                 = 1 << 17;
         const HAS_PREVIOUS_MORPH                = 1 << 18;
-        const LAST_FLAG                         = Self::HAS_PREVIOUS_MORPH.bits();
+        const DUAL_QUATERNION_SKINNING          = 1 << 19;
+        const SCREEN_SPACE_SUBSURFACE_SCATTERING = 1 << 20;
+        const DEPTH_STENCIL                     = 1 << 21; // The view depth texture has a stencil aspect, see `Camera3d::stencil`
+        const LAST_FLAG                         = Self::DEPTH_STENCIL.bits();
 
         // Bitfields
         const MSAA_RESERVED_BITS                = Self::MSAA_MASK_BITS << Self::MSAA_SHIFT_BITS;
@@ -1442,13 +1505,28 @@ bitflags::bitflags! {
         const SCREEN_SPACE_SPECULAR_TRANSMISSION_MEDIUM = 1 << Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS;
         const SCREEN_SPACE_SPECULAR_TRANSMISSION_HIGH = 2 << Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS;
         const SCREEN_SPACE_SPECULAR_TRANSMISSION_ULTRA = 3 << Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS;
+        const CLUSTER_DEBUG_RESERVED_BITS       = Self::CLUSTER_DEBUG_MASK_BITS << Self::CLUSTER_DEBUG_SHIFT_BITS;
+        const CLUSTER_DEBUG_NONE                = 0 << Self::CLUSTER_DEBUG_SHIFT_BITS;
+        const CLUSTER_DEBUG_OBJECT_COUNT        = 1 << Self::CLUSTER_DEBUG_SHIFT_BITS;
+        const CLUSTER_DEBUG_Z_SLICES            = 2 << Self::CLUSTER_DEBUG_SHIFT_BITS;
+        const CLUSTER_DEBUG_CLUSTERS            = 3 << Self::CLUSTER_DEBUG_SHIFT_BITS;
+        const DEBUG_VIEW_RESERVED_BITS          = Self::DEBUG_VIEW_MASK_BITS << Self::DEBUG_VIEW_SHIFT_BITS;
+        const DEBUG_VIEW_NONE                   = 0 << Self::DEBUG_VIEW_SHIFT_BITS;
+        const DEBUG_VIEW_DEPTH                  = 1 << Self::DEBUG_VIEW_SHIFT_BITS;
+        const DEBUG_VIEW_WORLD_NORMALS          = 2 << Self::DEBUG_VIEW_SHIFT_BITS;
+        const DEBUG_VIEW_VIEW_NORMALS           = 3 << Self::DEBUG_VIEW_SHIFT_BITS;
+        const DEBUG_VIEW_OVERDRAW               = 4 << Self::DEBUG_VIEW_SHIFT_BITS;
+        const DEBUG_VIEW_CASCADES               = 5 << Self::DEBUG_VIEW_SHIFT_BITS;
+        const DEBUG_VIEW_LIGHTMAP_UVS           = 6 << Self::DEBUG_VIEW_SHIFT_BITS;
         const ALL_RESERVED_BITS =
             Self::BLEND_RESERVED_BITS.bits() |
             Self::MSAA_RESERVED_BITS.bits() |
             Self::TONEMAP_METHOD_RESERVED_BITS.bits() |
             Self::SHADOW_FILTER_METHOD_RESERVED_BITS.bits() |
             Self::VIEW_PROJECTION_RESERVED_BITS.bits() |
-            Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_RESERVED_BITS.bits();
+            Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_RESERVED_BITS.bits() |
+            Self::CLUSTER_DEBUG_RESERVED_BITS.bits() |
+            Self::DEBUG_VIEW_RESERVED_BITS.bits();
     }
 }
 
@@ -1476,6 +1554,15 @@ impl MeshPipelineKey {
     const SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS: u64 =
         Self::VIEW_PROJECTION_MASK_BITS.count_ones() as u64 + Self::VIEW_PROJECTION_SHIFT_BITS;
 
+    const CLUSTER_DEBUG_MASK_BITS: u64 = 0b11;
+    const CLUSTER_DEBUG_SHIFT_BITS: u64 = Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_MASK_BITS
+        .count_ones() as u64
+        + Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS;
+
+    const DEBUG_VIEW_MASK_BITS: u64 = 0b111;
+    const DEBUG_VIEW_SHIFT_BITS: u64 =
+        Self::CLUSTER_DEBUG_MASK_BITS.count_ones() as u64 + Self::CLUSTER_DEBUG_SHIFT_BITS;
+
     pub fn from_msaa_samples(msaa_samples: u32) -> Self {
         let msaa_bits =
             (msaa_samples.trailing_zeros() as u64 & Self::MSAA_MASK_BITS) << Self::MSAA_SHIFT_BITS;
@@ -1535,6 +1622,35 @@ fn is_skinned(layout: &MeshVertexBufferLayoutRef) -> bool {
     layout.0.contains(Mesh::ATTRIBUTE_JOINT_INDEX)
         && layout.0.contains(Mesh::ATTRIBUTE_JOINT_WEIGHT)
 }
+
+/// Returns the shader def of the [`ClusterDebugVisualization`](crate::ClusterDebugVisualization)
+/// in `key`, if there is one.
+pub(crate) fn cluster_debug_shader_def(key: MeshPipelineKey) -> Option<&'static str> {
+    match key.intersection(MeshPipelineKey::CLUSTER_DEBUG_RESERVED_BITS) {
+        MeshPipelineKey::CLUSTER_DEBUG_OBJECT_COUNT => {
+            Some("CLUSTERED_FORWARD_DEBUG_CLUSTER_COMPLEXITY")
+        }
+        MeshPipelineKey::CLUSTER_DEBUG_Z_SLICES => Some("CLUSTERED_FORWARD_DEBUG_Z_SLICES"),
+        MeshPipelineKey::CLUSTER_DEBUG_CLUSTERS => {
+            Some("CLUSTERED_FORWARD_DEBUG_CLUSTER_COHERENCY")
+        }
+        _ => None,
+    }
+}
+/// Returns the shader def of the [`DebugViewMode`](crate::DebugViewMode) in `key`, if there is
+/// one.
+pub(crate) fn debug_view_shader_def(key: MeshPipelineKey) -> Option<&'static str> {
+    match key.intersection(MeshPipelineKey::DEBUG_VIEW_RESERVED_BITS) {
+        MeshPipelineKey::DEBUG_VIEW_DEPTH => Some("DEBUG_VIEW_DEPTH"),
+        MeshPipelineKey::DEBUG_VIEW_WORLD_NORMALS => Some("DEBUG_VIEW_WORLD_NORMALS"),
+        MeshPipelineKey::DEBUG_VIEW_VIEW_NORMALS => Some("DEBUG_VIEW_VIEW_NORMALS"),
+        MeshPipelineKey::DEBUG_VIEW_OVERDRAW => Some("DEBUG_VIEW_OVERDRAW"),
+        MeshPipelineKey::DEBUG_VIEW_CASCADES => Some("DIRECTIONAL_LIGHT_SHADOW_MAP_DEBUG_CASCADES"),
+        MeshPipelineKey::DEBUG_VIEW_LIGHTMAP_UVS => Some("DEBUG_VIEW_LIGHTMAP_UVS"),
+        _ => None,
+    }
+}
+
 pub fn setup_morph_and_skinning_defs(
     mesh_layouts: &MeshLayouts,
     layout: &MeshVertexBufferLayoutRef,
@@ -1545,6 +1661,9 @@ pub fn setup_morph_and_skinning_defs(
 ) -> BindGroupLayout {
     let mut add_skin_data = || {
         shader_defs.push("SKINNED".into());
+        if key.contains(MeshPipelineKey::DUAL_QUATERNION_SKINNING) {
+            shader_defs.push("DUAL_QUATERNION_SKINNING".into());
+        }
         vertex_attributes.push(Mesh::ATTRIBUTE_JOINT_INDEX.at_shader_location(offset));
         vertex_attributes.push(Mesh::ATTRIBUTE_JOINT_WEIGHT.at_shader_location(offset + 1));
     };
@@ -1664,7 +1783,7 @@ impl SpecializedMeshPipeline for MeshPipeline {
 
         let vertex_buffer_layout = layout.0.get_layout(&vertex_attributes)?;
 
-        let (label, blend, depth_write_enabled);
+        let (label, mut blend, mut depth_write_enabled);
         let pass = key.intersection(MeshPipelineKey::BLEND_RESERVED_BITS);
         let (mut is_opaque, mut alpha_to_coverage_enabled) = (false, false);
         if pass == MeshPipelineKey::BLEND_ALPHA {
@@ -1718,6 +1837,28 @@ impl SpecializedMeshPipeline for MeshPipeline {
             is_opaque = !key.contains(MeshPipelineKey::READS_VIEW_TRANSMISSION_TEXTURE);
         }
 
+        let mut depth_compare = CompareFunction::GreaterEqual;
+        let debug_view = key.intersection(MeshPipelineKey::DEBUG_VIEW_RESERVED_BITS);
+        if let Some(debug_view_def) = debug_view_shader_def(key) {
+            if debug_view != MeshPipelineKey::DEBUG_VIEW_CASCADES {
+                shader_defs.push("DEBUG_VIEW".into());
+            }
+            shader_defs.push(debug_view_def.into());
+        }
+        if debug_view == MeshPipelineKey::DEBUG_VIEW_OVERDRAW {
+            // Add up all the fragments, including the hidden ones
+            blend = Some(BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::One,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+                alpha: BlendComponent::OVER,
+            });
+            depth_write_enabled = false;
+            depth_compare = CompareFunction::Always;
+        }
+
         if key.contains(MeshPipelineKey::NORMAL_PREPASS) {
             shader_defs.push("NORMAL_PREPASS".into());
         }
@@ -1843,6 +1984,14 @@ impl SpecializedMeshPipeline for MeshPipeline {
             shader_defs.push("VISIBILITY_RANGE_DITHER".into());
         }
 
+        if key.contains(MeshPipelineKey::SCREEN_SPACE_SUBSURFACE_SCATTERING) {
+            shader_defs.push("SCREEN_SPACE_SUBSURFACE_SCATTERING".into());
+        }
+
+        if let Some(cluster_debug_def) = cluster_debug_shader_def(key) {
+            shader_defs.push(cluster_debug_def.into());
+        }
+
         if self.binding_arrays_are_usable {
             shader_defs.push("MULTIPLE_LIGHT_PROBES_IN_ARRAY".into());
         }
@@ -1851,6 +2000,10 @@ impl SpecializedMeshPipeline for MeshPipeline {
             shader_defs.push("IRRADIANCE_VOLUMES_ARE_USABLE".into());
         }
 
+        if LIGHT_TEXTURES_ARE_USABLE {
+            shader_defs.push("LIGHT_TEXTURES_ARE_USABLE".into());
+        }
+
         let format = if key.contains(MeshPipelineKey::HDR) {
             ViewTarget::TEXTURE_FORMAT_HDR
         } else {
@@ -1896,9 +2049,13 @@ impl SpecializedMeshPipeline for MeshPipeline {
                 strip_index_format: None,
             },
             depth_stencil: Some(DepthStencilState {
-                format: CORE_3D_DEPTH_FORMAT,
+                format: if key.contains(MeshPipelineKey::DEPTH_STENCIL) {
+                    CORE_3D_DEPTH_STENCIL_FORMAT
+                } else {
+                    CORE_3D_DEPTH_FORMAT
+                },
                 depth_write_enabled,
-                depth_compare: CompareFunction::GreaterEqual,
+                depth_compare,
                 stencil: StencilState {
                     front: StencilFaceState::IGNORE,
                     back: StencilFaceState::IGNORE,
@@ -2326,11 +2483,80 @@ impl<P: PhaseItem> RenderCommand<P> for DrawMesh {
 
 #[cfg(test)]
 mod tests {
-    use super::MeshPipelineKey;
+    use std::mem::{offset_of, size_of};
+
+    use bevy_asset::Handle;
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+    use bevy_math::Vec4;
+    use bevy_render::{
+        mesh::Mesh,
+        render_resource::ShaderType,
+        view::{RenderVisibilityRanges, ViewVisibility},
+        MainWorld,
+    };
+    use bevy_transform::components::GlobalTransform;
+
+    use super::{
+        extract_meshes_for_cpu_building, InstanceCustomData, MeshInputUniform, MeshPipelineKey,
+        MeshUniform, RenderMeshInstances,
+    };
+
     #[test]
     fn mesh_key_msaa_samples() {
         for i in [1, 2, 4, 8, 16, 32, 64, 128] {
             assert_eq!(MeshPipelineKey::from_msaa_samples(i).msaa_samples(), i);
         }
     }
+
+    #[test]
+    fn instance_custom_data_layout() {
+        // The input uniforms are copied to the GPU as is, so their layout must match the one of
+        // `MeshInput` in mesh_preprocess.wgsl, with the custom data aligned to 16 bytes.
+        assert_eq!(
+            MeshInputUniform::min_size().get() as usize,
+            size_of::<MeshInputUniform>()
+        );
+        assert_eq!(offset_of!(MeshInputUniform, custom_data), 64);
+        assert_eq!(MeshUniform::min_size().get(), 160);
+    }
+
+    #[test]
+    fn extract_instance_custom_data() {
+        let mut main_world = MainWorld::default();
+        let mut visible = ViewVisibility::HIDDEN;
+        visible.set();
+        let custom_data = Vec4::new(1.0, 2.0, 3.0, 4.0);
+        let with_custom_data = main_world
+            .spawn((
+                visible,
+                GlobalTransform::default(),
+                Handle::<Mesh>::default(),
+                InstanceCustomData(custom_data),
+            ))
+            .id();
+        let without_custom_data = main_world
+            .spawn((
+                visible,
+                GlobalTransform::default(),
+                Handle::<Mesh>::default(),
+            ))
+            .id();
+
+        let mut render_world = World::new();
+        render_world.insert_resource(main_world);
+        render_world.insert_resource(RenderMeshInstances::new(false));
+        render_world.init_resource::<RenderVisibilityRanges>();
+        render_world.run_system_once(extract_meshes_for_cpu_building);
+
+        let RenderMeshInstances::CpuBuilding(instances) =
+            render_world.resource::<RenderMeshInstances>()
+        else {
+            panic!("meshes should be extracted for CPU building");
+        };
+        let instance = &instances[&with_custom_data];
+        assert_eq!(instance.custom_data, custom_data);
+        assert_eq!(instances[&without_custom_data].custom_data, Vec4::ZERO);
+        let uniform = MeshUniform::new(&instance.transforms, None, instance.custom_data);
+        assert_eq!(uniform.custom_data, custom_data);
+    }
 }
@@ -21,6 +21,11 @@ fn get_previous_world_from_local(instance_index: u32) -> mat4x4<f32> {
     return affine3_to_square(mesh[instance_index].previous_world_from_local);
 }
 
+// Returns the `InstanceCustomData` of the mesh instance, or zero if it has none.
+fn get_instance_custom_data(instance_index: u32) -> vec4<f32> {
+    return mesh[instance_index].custom_data;
+}
+
 fn mesh_position_local_to_world(world_from_local: mat4x4<f32>, vertex_position: vec4<f32>) -> vec4<f32> {
     return world_from_local * vertex_position;
 }
@@ -121,3 +126,16 @@ fn get_visibility_range_dither_level(instance_index: u32, world_position: vec4<f
     return offset + clamp(level, 0, 16);
 }
 #endif
+
+// An offset applied to a vertex by the vertex shader of a material, see
+// `bevy_pbr::mesh_vertex::process_vertex` and `bevy_pbr::prepass_vertex::process_vertex`.
+struct VertexDisplacement {
+    // The offset added to the world position of the vertex.
+    world_offset: vec3<f32>,
+    // The offset of the vertex in the previous frame, used to compute motion vectors.
+    previous_world_offset: vec3<f32>,
+}
+
+fn no_vertex_displacement() -> VertexDisplacement {
+    return VertexDisplacement(vec3(0.0), vec3(0.0));
+}
@@ -22,6 +22,8 @@ struct MeshInput {
     // The index of this mesh's `MeshInput` in the `previous_input` array, if
     // applicable. If not present, this is `u32::MAX`.
     previous_input_index: u32,
+    // The `InstanceCustomData` of the mesh.
+    custom_data: vec4<f32>,
 }
 
 // Information about each mesh instance needed to cull it on GPU.
@@ -186,4 +188,5 @@ fn main(@builtin(global_invocation_id) global_invocation_id: vec3<u32>) {
     output[mesh_output_index].local_from_world_transpose_b = local_from_world_transpose_b;
     output[mesh_output_index].flags = current_input[input_index].flags;
     output[mesh_output_index].lightmap_uv_rect = current_input[input_index].lightmap_uv_rect;
+    output[mesh_output_index].custom_data = current_input[input_index].custom_data;
 }
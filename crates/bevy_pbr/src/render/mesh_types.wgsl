@@ -15,6 +15,8 @@ struct Mesh {
     // 'flags' is a bit field indicating various options. u32 is 32 bits so we have up to 32 options.
     flags: u32,
     lightmap_uv_rect: vec2<u32>,
+    // The `InstanceCustomData` of the mesh, see `mesh_functions::get_instance_custom_data`.
+    custom_data: vec4<f32>,
 };
 
 #ifdef SKINNED
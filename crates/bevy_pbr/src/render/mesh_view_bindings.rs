@@ -27,8 +27,6 @@ use bevy_render::{
     },
 };
 
-#[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
-use bevy_render::render_resource::binding_types::texture_cube;
 #[cfg(debug_assertions)]
 use bevy_utils::warn_once;
 use environment_map::EnvironmentMapLight;
@@ -41,11 +39,12 @@ use crate::{
         self, IrradianceVolume, RenderViewIrradianceVolumeBindGroupEntries,
         IRRADIANCE_VOLUMES_ARE_USABLE,
     },
+    light_texture::LIGHT_TEXTURES_ARE_USABLE,
     prepass, FogMeta, GlobalClusterableObjectMeta, GpuClusterableObjects, GpuFog, GpuLights,
     LightMeta, LightProbesBuffer, LightProbesUniform, MeshPipeline, MeshPipelineKey,
-    RenderViewLightProbes, ScreenSpaceAmbientOcclusionTextures, ScreenSpaceReflectionsBuffer,
-    ScreenSpaceReflectionsUniform, ShadowSamplers, ViewClusterBindings, ViewShadowBindings,
-    CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT,
+    RenderLightTextures, RenderViewLightProbes, ScreenSpaceAmbientOcclusionTextures,
+    ScreenSpaceReflectionsBuffer, ScreenSpaceReflectionsUniform, ShadowSamplers,
+    ViewClusterBindings, ViewShadowBindings, CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT,
 };
 
 #[derive(Clone)]
@@ -195,25 +194,9 @@ fn layout_entries(
             ),
             // Lights
             (1, uniform_buffer::<GpuLights>(true)),
-            // Point Shadow Texture Cube Array
-            (
-                2,
-                #[cfg(all(
-                    not(feature = "ios_simulator"),
-                    any(
-                        not(feature = "webgl"),
-                        not(target_arch = "wasm32"),
-                        feature = "webgpu"
-                    )
-                ))]
-                texture_cube_array(TextureSampleType::Depth),
-                #[cfg(any(
-                    feature = "ios_simulator",
-                    all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu"))
-                ))]
-                texture_cube(TextureSampleType::Depth),
-            ),
-            // Point Shadow Texture Array Sampler
+            // Point Shadow Atlas
+            (2, texture_depth_2d()),
+            // Point Shadow Atlas Sampler
             (3, sampler(SamplerBindingType::Comparison)),
             // Directional Shadow Texture Array
             (
@@ -342,6 +325,17 @@ fn layout_entries(
         (27, sampler(SamplerBindingType::Filtering)),
     ));
 
+    // Light textures
+    if LIGHT_TEXTURES_ARE_USABLE {
+        entries = entries.extend_with_indices((
+            (
+                28,
+                texture_2d_array(TextureSampleType::Float { filterable: true }),
+            ),
+            (29, sampler(SamplerBindingType::Filtering)),
+        ));
+    }
+
     entries.to_vec()
 }
 
@@ -462,11 +456,12 @@ pub fn prepare_mesh_view_bind_groups(
         Option<&RenderViewLightProbes<EnvironmentMapLight>>,
         Option<&RenderViewLightProbes<IrradianceVolume>>,
     )>,
-    (images, mut fallback_images, fallback_image, fallback_image_zero): (
+    (images, mut fallback_images, fallback_image, fallback_image_zero, light_textures): (
         Res<RenderAssets<GpuImage>>,
         FallbackImageMsaa,
         Res<FallbackImage>,
         Res<FallbackImageZero>,
+        Res<RenderLightTextures>,
     ),
     msaa: Res<Msaa>,
     globals_buffer: Res<GlobalsBuffer>,
@@ -632,6 +627,13 @@ pub fn prepare_mesh_view_bind_groups(
             entries =
                 entries.extend_with_indices(((26, transmission_view), (27, transmission_sampler)));
 
+            if LIGHT_TEXTURES_ARE_USABLE {
+                entries = entries.extend_with_indices((
+                    (28, &light_textures.texture_view),
+                    (29, &light_textures.sampler),
+                ));
+            }
+
             commands.entity(entity).insert(MeshViewBindGroup {
                 value: render_device.create_bind_group("mesh_view_bind_group", layout, &entries),
             });
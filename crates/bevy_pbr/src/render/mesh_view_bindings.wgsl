@@ -8,11 +8,7 @@
 
 @group(0) @binding(0) var<uniform> view: View;
 @group(0) @binding(1) var<uniform> lights: types::Lights;
-#ifdef NO_CUBE_ARRAY_TEXTURES_SUPPORT
-@group(0) @binding(2) var point_shadow_textures: texture_depth_cube;
-#else
-@group(0) @binding(2) var point_shadow_textures: texture_depth_cube_array;
-#endif
+@group(0) @binding(2) var point_shadow_textures: texture_depth_2d;
 @group(0) @binding(3) var point_shadow_textures_sampler: sampler_comparison;
 #ifdef NO_ARRAY_TEXTURES_SUPPORT
 @group(0) @binding(4) var directional_shadow_textures: texture_depth_2d;
@@ -97,3 +93,8 @@ const VISIBILITY_RANGE_UNIFORM_BUFFER_SIZE: u32 = 64u;
 
 @group(0) @binding(26) var view_transmission_texture: texture_2d<f32>;
 @group(0) @binding(27) var view_transmission_sampler: sampler;
+
+#ifdef LIGHT_TEXTURES_ARE_USABLE
+@group(0) @binding(28) var light_textures: texture_2d_array<f32>;
+@group(0) @binding(29) var light_textures_sampler: sampler;
+#endif
@@ -1,7 +1,7 @@
 #define_import_path bevy_pbr::mesh_view_types
 
 struct ClusterableObject {
-    // For point lights: the lower-right 2x2 values of the projection matrix [2][2] [2][3] [3][2] [3][3]
+    // For point lights: the UV of the shadow atlas tile (x,y), the UV size of a face and the near plane
     // For spot lights: the direction (x,z), spot_scale and spot_offset
     light_custom_data: vec4<f32>,
     color_inverse_square_range: vec4<f32>,
@@ -13,13 +13,22 @@ struct ClusterableObject {
     spot_light_tan_angle: f32,
 };
 
-const POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT: u32   = 1u;
-const POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE: u32 = 2u;
+const POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT: u32       = 1u;
+const POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE: u32     = 2u;
+const POINT_LIGHT_FLAGS_SPOT_LIGHT: u32                = 4u;
+const POINT_LIGHT_FLAGS_LIGHT_TEXTURE: u32             = 8u;
+const POINT_LIGHT_FLAGS_LIGHT_TEXTURE_PHOTOMETRIC: u32 = 16u;
+// The layer of the light texture is stored in the flags from this bit upwards.
+const POINT_LIGHT_FLAGS_LIGHT_TEXTURE_LAYER_SHIFT: u32 = 24u;
 
 struct DirectionalCascade {
     clip_from_world: mat4x4<f32>,
     texel_size: f32,
     far_bound: f32,
+    depth_bias: f32,
+    normal_bias: f32,
+    // The proportion of overlap with the previous cascade.
+    overlap_proportion: f32,
 }
 
 struct DirectionalLight {
@@ -36,8 +45,9 @@ struct DirectionalLight {
     skip: u32,
 };
 
-const DIRECTIONAL_LIGHT_FLAGS_SHADOWS_ENABLED_BIT: u32 = 1u;
-const DIRECTIONAL_LIGHT_FLAGS_VOLUMETRIC_BIT: u32      = 2u;
+const DIRECTIONAL_LIGHT_FLAGS_SHADOWS_ENABLED_BIT: u32    = 1u;
+const DIRECTIONAL_LIGHT_FLAGS_VOLUMETRIC_BIT: u32         = 2u;
+const DIRECTIONAL_LIGHT_FLAGS_VISUALIZE_CASCADES_BIT: u32 = 4u;
 
 struct Lights {
     // NOTE: this array size must be kept in sync with the constants defined in bevy_pbr/src/render/light.rs
@@ -55,7 +65,12 @@ struct Lights {
     // NOTE: near and far are +ve but -z is infront of the camera
     // z is -near
     // w is cluster_dimensions.z / (-far - -near)
+    //
+    // Linear depth slices for perspective projections use the orthographic
+    // factors, with a near of 0.
     cluster_factors: vec4<f32>,
+    // 1 if the depth slices are linear, see `ClusterZSlicing`
+    linear_cluster_z_slicing: u32,
     n_directional_lights: u32,
     spot_light_shadowmap_offset: i32,
     environment_map_smallest_specular_mip_level: u32,
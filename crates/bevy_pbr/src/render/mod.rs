@@ -5,6 +5,7 @@ pub(crate) mod mesh;
 mod mesh_bindings;
 mod mesh_view_bindings;
 mod morph;
+mod shadow_atlas;
 mod skin;
 
 pub use fog::*;
@@ -13,8 +13,11 @@
     forward_io::{VertexOutput, FragmentOutput},
     pbr_functions,
     pbr_functions::{apply_pbr_lighting, main_pass_post_lighting_processing},
-    pbr_types::STANDARD_MATERIAL_FLAGS_UNLIT_BIT,
+    pbr_types::{STANDARD_MATERIAL_FLAGS_UNLIT_BIT, STANDARD_MATERIAL_FLAGS_SUBSURFACE_SCATTERING_BIT},
 }
+#ifdef DEBUG_VIEW
+#import bevy_pbr::debug_view::debug_view_color
+#endif
 #endif
 
 #ifdef MESHLET_MESH_MATERIAL_PASS
@@ -63,6 +66,24 @@ fn fragment(
     // apply in-shader post processing (fog, alpha-premultiply, and also tonemapping, debanding if the camera is non-hdr)
     // note this does not include fullscreen postprocessing effects like bloom.
     out.color = main_pass_post_lighting_processing(pbr_input, out.color);
+
+#ifdef DEBUG_VIEW
+    // replace the shading with the `DebugViewMode` of the camera
+    out.color = debug_view_color(in, pbr_input, out.color);
+#endif
+
+#ifdef DUAL_SOURCE_BLENDING
+    // the second source of the custom `StandardMaterial::blend_state`
+    out.blend = vec4(out.color.a);
+#endif
+
+#ifdef SCREEN_SPACE_SUBSURFACE_SCATTERING
+    // mark the pixel for the subsurface scattering pass, which reads the scatter radius from the
+    // alpha channel of the (HDR) main pass texture and restores the alpha afterwards
+    if (pbr_input.material.flags & STANDARD_MATERIAL_FLAGS_SUBSURFACE_SCATTERING_BIT) != 0u {
+        out.color.a = -pbr_input.material.subsurface_radius;
+    }
+#endif
 #endif
 
     return out;
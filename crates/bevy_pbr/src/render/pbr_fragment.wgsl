@@ -175,6 +175,8 @@ fn pbr_input_from_standard_material(
         pbr_input.material.ior = pbr_bindings::material.ior;
         pbr_input.material.attenuation_color = pbr_bindings::material.attenuation_color;
         pbr_input.material.attenuation_distance = pbr_bindings::material.attenuation_distance;
+        pbr_input.material.subsurface_color = pbr_bindings::material.subsurface_color;
+        pbr_input.material.subsurface_radius = pbr_bindings::material.subsurface_radius;
         pbr_input.material.alpha_cutoff = pbr_bindings::material.alpha_cutoff;
 
         // emissive
@@ -323,13 +323,18 @@ fn apply_pbr_lighting(
     let clearcoat_R = reflect(-in.V, clearcoat_N);
 #endif  // STANDARD_MATERIAL_CLEARCOAT
 
-    let diffuse_color = calculate_diffuse_color(
+    var diffuse_color = calculate_diffuse_color(
         output_color.rgb,
         metallic,
         specular_transmission,
         diffuse_transmission
     );
 
+    // Light scattering beneath the surface picks up the subsurface color
+    if (in.material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_SUBSURFACE_SCATTERING_BIT) != 0u {
+        diffuse_color *= in.material.subsurface_color.rgb;
+    }
+
     // Diffuse transmissive strength is inversely related to metallicity and specular transmission, but directly related to diffuse transmission
     let diffuse_transmissive_color = output_color.rgb * (1.0 - metallic) * (1.0 - specular_transmission) * diffuse_transmission;
 
@@ -496,6 +501,10 @@ fn apply_pbr_lighting(
 
 #ifdef DIRECTIONAL_LIGHT_SHADOW_MAP_DEBUG_CASCADES
         light_contrib = shadows::cascade_debug_visualization(light_contrib, i, view_z);
+#else
+        if ((*light).flags & mesh_view_types::DIRECTIONAL_LIGHT_FLAGS_VISUALIZE_CASCADES_BIT) != 0u {
+            light_contrib = shadows::cascade_debug_visualization(light_contrib, i, view_z);
+        }
 #endif
         direct_light += light_contrib * shadow;
 
@@ -1,10 +1,14 @@
 #define_import_path bevy_pbr::lighting
 
 #import bevy_pbr::{
-    mesh_view_types::POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE,
+    mesh_view_types::{
+        POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE, POINT_LIGHT_FLAGS_SPOT_LIGHT,
+        POINT_LIGHT_FLAGS_LIGHT_TEXTURE, POINT_LIGHT_FLAGS_LIGHT_TEXTURE_PHOTOMETRIC,
+        POINT_LIGHT_FLAGS_LIGHT_TEXTURE_LAYER_SHIFT,
+    },
     mesh_view_bindings as view_bindings,
 }
-#import bevy_render::maths::PI
+#import bevy_render::maths::{PI, PI_2}
 
 const LAYER_BASE: u32 = 0;
 const LAYER_CLEARCOAT: u32 = 1;
@@ -532,10 +536,86 @@ fn point_light(light_id: u32, input: ptr<function, LightingInput>) -> vec3<f32>
     color = diffuse + specular_light;
 #endif  // STANDARD_MATERIAL_CLEARCOAT
 
-    return color * (*light).color_inverse_square_range.rgb *
+    return color * (*light).color_inverse_square_range.rgb * light_texture(light_id, -L) *
         (rangeAttenuation * derived_input.NdotL);
 }
 
+// Returns the `uv` of the direction `dir` in a photometric light texture: `u` is the horizontal
+// angle around the `nadir` from `x_axis` towards `y_axis`, and `v` is the vertical angle from the
+// `nadir`.
+fn photometric_light_texture_uv(
+    dir: vec3<f32>,
+    nadir: vec3<f32>,
+    x_axis: vec3<f32>,
+    y_axis: vec3<f32>,
+) -> vec2<f32> {
+    let vertical = acos(clamp(dot(dir, nadir), -1.0, 1.0));
+    let horizontal = atan2(dot(dir, y_axis), dot(dir, x_axis));
+    return vec2(fract(horizontal / PI_2 + 1.0), vertical / PI);
+}
+
+// Returns the color that the light texture of the light `light_id` modulates the light emitted
+// in the direction `light_to_frag_dir` with, or white if the light has no light texture.
+fn light_texture(light_id: u32, light_to_frag_dir: vec3<f32>) -> vec3<f32> {
+#ifdef LIGHT_TEXTURES_ARE_USABLE
+    let light = &view_bindings::clusterable_objects.data[light_id];
+    let flags = (*light).flags;
+    if (flags & POINT_LIGHT_FLAGS_LIGHT_TEXTURE) == 0u {
+        return vec3(1.0);
+    }
+
+    var uv: vec2<f32>;
+    if (flags & POINT_LIGHT_FLAGS_SPOT_LIGHT) != 0u {
+        // reconstruct spot dir from x/z and y-direction flag
+        var spot_dir = vec3<f32>((*light).light_custom_data.x, 0.0, (*light).light_custom_data.y);
+        spot_dir.y = sqrt(max(0.0, 1.0 - spot_dir.x * spot_dir.x - spot_dir.z * spot_dir.z));
+        if (flags & POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE) != 0u {
+            spot_dir.y = -spot_dir.y;
+        }
+
+        // orient the light texture so that its top points upwards, or towards -Z for lights
+        // pointing straight up or down
+        var reference_up = vec3<f32>(0.0, 1.0, 0.0);
+        if (abs(spot_dir.y) > 0.999) {
+            reference_up = vec3<f32>(0.0, 0.0, -1.0);
+        }
+        let right_dir = normalize(cross(spot_dir, reference_up));
+        let up_dir = cross(right_dir, spot_dir);
+
+        if (flags & POINT_LIGHT_FLAGS_LIGHT_TEXTURE_PHOTOMETRIC) != 0u {
+            uv = photometric_light_texture_uv(light_to_frag_dir, spot_dir, right_dir, up_dir);
+        } else {
+            let distance_along_spot_dir = dot(light_to_frag_dir, spot_dir);
+            if (distance_along_spot_dir <= 0.0) {
+                // behind the light
+                return vec3(0.0);
+            }
+            let projected = vec2<f32>(dot(light_to_frag_dir, right_dir), dot(light_to_frag_dir, up_dir));
+            let ndc = projected / ((*light).spot_light_tan_angle * distance_along_spot_dir);
+            uv = ndc * vec2<f32>(0.5, -0.5) + vec2<f32>(0.5, 0.5);
+        }
+    } else {
+        uv = photometric_light_texture_uv(
+            light_to_frag_dir,
+            vec3(0.0, -1.0, 0.0),
+            vec3(1.0, 0.0, 0.0),
+            vec3(0.0, 0.0, 1.0),
+        );
+    }
+
+    let layer = flags >> POINT_LIGHT_FLAGS_LIGHT_TEXTURE_LAYER_SHIFT;
+    return textureSampleLevel(
+        view_bindings::light_textures,
+        view_bindings::light_textures_sampler,
+        uv,
+        layer,
+        0.0
+    ).rgb;
+#else   // LIGHT_TEXTURES_ARE_USABLE
+    return vec3(1.0);
+#endif  // LIGHT_TEXTURES_ARE_USABLE
+}
+
 fn spot_light(light_id: u32, input: ptr<function, LightingInput>) -> vec3<f32> {
     // reuse the point light calculations
     let point_light = point_light(light_id, input);
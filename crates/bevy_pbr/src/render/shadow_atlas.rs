@@ -0,0 +1,142 @@
+//! Allocation of the point light shadow maps in the shadow atlas, see [`ShadowAtlasSettings`](crate::ShadowAtlasSettings).
+
+use bevy_math::UVec2;
+
+/// The region of the shadow atlas holding the six faces of the shadow map of a point light.
+///
+/// The faces are laid out in a grid of three columns and two rows, in the order of
+/// [`CUBE_MAP_FACES`](super::CUBE_MAP_FACES).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct ShadowAtlasTile {
+    /// The top left corner of the tile, in texels.
+    pub origin: UVec2,
+    /// The width and height of each face, in texels.
+    pub face_resolution: u32,
+}
+
+impl ShadowAtlasTile {
+    /// The top left corner of the face with the given index, in texels.
+    pub fn face_origin(&self, face_index: usize) -> UVec2 {
+        self.origin
+            + UVec2::new(face_index as u32 % 3, face_index as u32 / 3) * self.face_resolution
+    }
+}
+
+/// Allocates a tile of an atlas of `atlas_size` texels for each point light, given the face
+/// resolution each of them would ideally get.
+///
+/// Resolutions are rounded up to a power of two between `min_resolution` and `max_resolution`.
+/// When the tiles don't fit, the largest ones are halved until they do, and once every tile is at
+/// the minimum resolution the lights with the smallest desired resolutions get no tile.
+pub(crate) fn allocate_shadow_atlas(
+    atlas_size: u32,
+    min_resolution: u32,
+    max_resolution: u32,
+    desired_resolutions: &[f32],
+) -> Vec<Option<ShadowAtlasTile>> {
+    let min_resolution = min_resolution.max(1).next_power_of_two();
+    let max_resolution = max_resolution.max(min_resolution).next_power_of_two();
+    let mut resolutions: Vec<u32> = desired_resolutions
+        .iter()
+        .map(|&desired| {
+            (desired.max(1.0).ceil() as u32)
+                .next_power_of_two()
+                .clamp(min_resolution, max_resolution)
+        })
+        .collect();
+
+    // Packing the largest tiles first keeps the shelves tightly filled.
+    let mut order: Vec<usize> = (0..desired_resolutions.len()).collect();
+    order.sort_by(|&a, &b| desired_resolutions[b].total_cmp(&desired_resolutions[a]));
+
+    loop {
+        if let Some(origins) = pack_tiles(atlas_size, &order, &resolutions) {
+            let mut tiles = vec![None; desired_resolutions.len()];
+            for (&index, origin) in order.iter().zip(origins) {
+                tiles[index] = Some(ShadowAtlasTile {
+                    origin,
+                    face_resolution: resolutions[index],
+                });
+            }
+            return tiles;
+        }
+
+        let largest = order
+            .iter()
+            .map(|&index| resolutions[index])
+            .max()
+            .unwrap_or(min_resolution);
+        if largest > min_resolution {
+            for &index in &order {
+                if resolutions[index] == largest {
+                    resolutions[index] /= 2;
+                }
+            }
+        } else {
+            order.pop();
+        }
+    }
+}
+
+/// Packs the tiles of the lights in `order`, whose resolutions must be decreasing, into rows
+/// ("shelves") of the atlas. Returns `None` if they don't fit.
+fn pack_tiles(atlas_size: u32, order: &[usize], resolutions: &[u32]) -> Option<Vec<UVec2>> {
+    let mut origins = Vec::with_capacity(order.len());
+    let mut cursor = UVec2::ZERO;
+    let mut shelf_height = 0;
+    for &index in order {
+        let size = UVec2::new(3, 2) * resolutions[index];
+        if cursor.x + size.x > atlas_size {
+            cursor = UVec2::new(0, cursor.y + shelf_height);
+            shelf_height = 0;
+        }
+        if cursor.x + size.x > atlas_size || cursor.y + size.y > atlas_size {
+            return None;
+        }
+        origins.push(cursor);
+        cursor.x += size.x;
+        shelf_height = shelf_height.max(size.y);
+    }
+    Some(origins)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{allocate_shadow_atlas, ShadowAtlasTile};
+    use crate::render::light::{point_light_face_clip_from_view, SHADOW_ATLAS_FACE_PADDING};
+    use bevy_math::{UVec2, Vec3};
+
+    #[test]
+    fn shrink_then_drop_tiles_that_do_not_fit() {
+        let tile = |x, y, face_resolution| {
+            Some(ShadowAtlasTile {
+                origin: UVec2::new(x, y),
+                face_resolution,
+            })
+        };
+
+        // The largest tile is too wide for the atlas at 1024 and is halved.
+        let tiles = allocate_shadow_atlas(2048, 64, 1024, &[100.0, 2000.0, 300.0]);
+        assert_eq!(
+            tiles,
+            vec![tile(1536, 1024, 128), tile(0, 0, 512), tile(0, 1024, 512)]
+        );
+        assert_eq!(tiles[0].unwrap().face_origin(4), UVec2::new(1664, 1152));
+
+        // Only a single tile fits at the minimum resolution.
+        let tiles = allocate_shadow_atlas(200, 64, 1024, &[100.0, 2000.0]);
+        assert_eq!(tiles, vec![None, tile(0, 0, 64)]);
+    }
+
+    #[test]
+    fn face_padding_is_outside_of_the_cube_face() {
+        let face_resolution = 64;
+        let clip_from_view = point_light_face_clip_from_view(face_resolution);
+        // The corner of the cube face projects to the corner of the texels inside of the padding.
+        let corner = clip_from_view.project_point3(Vec3::new(1.0, 1.0, -1.0));
+        let inner_resolution = face_resolution - 2 * SHADOW_ATLAS_FACE_PADDING;
+        let expected = inner_resolution as f32 / face_resolution as f32;
+        assert!((corner.x - expected).abs() < 1e-5);
+        assert!((corner.y - expected).abs() < 1e-5);
+    }
+}
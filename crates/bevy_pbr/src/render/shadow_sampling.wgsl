@@ -169,17 +169,69 @@ fn sample_shadow_map(light_local: vec2<f32>, depth: f32, array_index: i32, texel
 #endif
 }
 
+// The border, in texels, around each face of a point light shadow map in the shadow atlas.
+// This must match SHADOW_ATLAS_FACE_PADDING in light.rs
+const POINT_SHADOW_FACE_PADDING: f32 = 1.0;
+
+// A face of the shadow map of a point light and the UV within it.
+struct CubeFaceUv {
+    face: u32,
+    uv: vec2<f32>,
+}
+
+// Returns the face that `light_local` points to, in the order of the faces of the shadow map
+// (+X, -X, +Y, -Y, -Z, +Z, see `CUBE_MAP_FACES`), and the UV within that face.
+fn point_shadow_face_uv(light_local: vec3<f32>) -> CubeFaceUv {
+    let abs_light_local = abs(light_local);
+    var face_uv: CubeFaceUv;
+    if (abs_light_local.x >= abs_light_local.y && abs_light_local.x >= abs_light_local.z) {
+        if (light_local.x > 0.0) {
+            face_uv.face = 0u;
+            face_uv.uv = vec2(light_local.z, -light_local.y) / abs_light_local.x;
+        } else {
+            face_uv.face = 1u;
+            face_uv.uv = vec2(-light_local.z, -light_local.y) / abs_light_local.x;
+        }
+    } else if (abs_light_local.y >= abs_light_local.z) {
+        if (light_local.y > 0.0) {
+            face_uv.face = 2u;
+            face_uv.uv = vec2(light_local.x, -light_local.z) / abs_light_local.y;
+        } else {
+            face_uv.face = 3u;
+            face_uv.uv = vec2(light_local.x, light_local.z) / abs_light_local.y;
+        }
+    } else {
+        if (light_local.z < 0.0) {
+            face_uv.face = 4u;
+            face_uv.uv = vec2(light_local.x, -light_local.y) / abs_light_local.z;
+        } else {
+            face_uv.face = 5u;
+            face_uv.uv = vec2(-light_local.x, -light_local.y) / abs_light_local.z;
+        }
+    }
+    face_uv.uv = face_uv.uv * 0.5 + 0.5;
+    return face_uv;
+}
+
 // NOTE: Due to the non-uniform control flow in `shadows::fetch_point_shadow`,
 // we must use the Level variant of textureSampleCompare to avoid undefined
 // behavior due to some of the fragments in a quad (2x2 fragments) being
 // processed not being sampled, and this messing with mip-mapping functionality.
 // The shadow maps have no mipmaps so Level just samples from LOD 0.
 fn sample_shadow_cubemap_hardware(light_local: vec3<f32>, depth: f32, light_id: u32) -> f32 {
-#ifdef NO_CUBE_ARRAY_TEXTURES_SUPPORT
-    return textureSampleCompare(view_bindings::point_shadow_textures, view_bindings::point_shadow_textures_sampler, light_local, depth);
-#else
-    return textureSampleCompareLevel(view_bindings::point_shadow_textures, view_bindings::point_shadow_textures_sampler, light_local, i32(light_id), depth);
-#endif
+    // The faces of the shadow map are laid out in three columns and two rows of the tile of the
+    // light in the shadow atlas. xy is the top left corner of the tile and z the size of a face.
+    let tile = view_bindings::clusterable_objects.data[light_id].light_custom_data.xyz;
+    let face_uv = point_shadow_face_uv(light_local);
+    let face_origin = tile.xy + vec2(f32(face_uv.face % 3u), f32(face_uv.face / 3u)) * tile.z;
+
+    // The cube face only covers the texels inside of the padding of the face, which holds the
+    // depths just past its edges. Filtering at the edges reads the padding rather than the
+    // neighboring faces of the atlas, so it stays seamless across the edges of the faces.
+    let padding = POINT_SHADOW_FACE_PADDING / f32(textureDimensions(view_bindings::point_shadow_textures).x);
+    let uv = face_origin + padding + face_uv.uv * (tile.z - 2.0 * padding);
+
+    return textureSampleCompareLevel(view_bindings::point_shadow_textures, view_bindings::point_shadow_textures_sampler, uv, depth);
 }
 
 fn sample_shadow_cubemap_at_offset(
@@ -11,8 +11,6 @@
     maths::PI_2
 }
 
-const flip_z: vec3<f32> = vec3<f32>(1.0, 1.0, -1.0);
-
 fn fetch_point_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: vec3<f32>) -> f32 {
     let light = &view_bindings::clusterable_objects.data[light_id];
 
@@ -34,16 +32,13 @@ fn fetch_point_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: v
     let abs_position_ls = abs(frag_ls);
     let major_axis_magnitude = max(abs_position_ls.x, max(abs_position_ls.y, abs_position_ls.z));
 
-    // NOTE: These simplifications come from multiplying:
-    // projection * vec4(0, 0, -major_axis_magnitude, 1.0)
-    // and keeping only the terms that have any impact on the depth.
-    // Projection-agnostic approach:
-    let zw = -major_axis_magnitude * (*light).light_custom_data.xy + (*light).light_custom_data.zw;
-    let depth = zw.x / zw.y;
+    // NOTE: This simplification comes from multiplying the infinite reverse-z projection of the
+    // cube faces by vec4(0, 0, -major_axis_magnitude, 1.0), w of light_custom_data being the near
+    // plane of the projection.
+    let depth = (*light).light_custom_data.w / major_axis_magnitude;
 
-    // Do the lookup, using HW PCF and comparison. Cubemaps assume a left-handed coordinate space,
-    // so we have to flip the z-axis when sampling.
-    return sample_shadow_cubemap(frag_ls * flip_z, distance_to_light, depth, light_id);
+    // Do the lookup, using HW PCF and comparison.
+    return sample_shadow_cubemap(frag_ls, distance_to_light, depth, light_id);
 }
 
 fn fetch_spot_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: vec3<f32>) -> f32 {
@@ -151,8 +146,8 @@ fn sample_directional_cascade(light_id: u32, cascade_index: u32, frag_position:
     let cascade = &(*light).cascades[cascade_index];
 
     // The normal bias is scaled to the texel size.
-    let normal_offset = (*light).shadow_normal_bias * (*cascade).texel_size * surface_normal.xyz;
-    let depth_offset = (*light).shadow_depth_bias * (*light).direction_to_light.xyz;
+    let normal_offset = (*cascade).normal_bias * (*cascade).texel_size * surface_normal.xyz;
+    let depth_offset = (*cascade).depth_bias * (*light).direction_to_light.xyz;
     let offset_position = vec4<f32>(frag_position.xyz + normal_offset + depth_offset, frag_position.w);
 
     let light_local = world_to_directional_light_local(light_id, cascade_index, offset_position);
@@ -178,7 +173,7 @@ fn fetch_directional_shadow(light_id: u32, frag_position: vec4<f32>, surface_nor
     let next_cascade_index = cascade_index + 1u;
     if (next_cascade_index < (*light).num_cascades) {
         let this_far_bound = (*light).cascades[cascade_index].far_bound;
-        let next_near_bound = (1.0 - (*light).cascades_overlap_proportion) * this_far_bound;
+        let next_near_bound = (1.0 - (*light).cascades[next_cascade_index].overlap_proportion) * this_far_bound;
         if (-view_z >= next_near_bound) {
             let next_shadow = sample_directional_cascade(light_id, next_cascade_index, frag_position, surface_normal);
             shadow = mix(shadow, next_shadow, (-view_z - next_near_bound) / (this_far_bound - next_near_bound));
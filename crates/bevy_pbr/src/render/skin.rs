@@ -3,10 +3,10 @@ use std::mem;
 use bevy_asset::Assets;
 use bevy_ecs::entity::EntityHashMap;
 use bevy_ecs::prelude::*;
-use bevy_math::Mat4;
+use bevy_math::{Mat4, Quat, Vec4};
 use bevy_render::{
     batching::NoAutomaticBatching,
-    mesh::skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
+    mesh::skinning::{SkinnedMesh, SkinnedMeshInverseBindposes, SkinningMethod},
     render_resource::{BufferUsages, RawBufferVec},
     renderer::{RenderDevice, RenderQueue},
     view::ViewVisibility,
@@ -20,13 +20,17 @@ pub const MAX_JOINTS: usize = 256;
 #[derive(Component)]
 pub struct SkinIndex {
     pub index: u32,
+    /// How the joints of the skin are packed in the buffer, see
+    /// [`SkinUniforms`].
+    pub method: SkinningMethod,
 }
 
 impl SkinIndex {
     /// Index to be in address space based on [`SkinUniform`] size.
-    const fn new(start: usize) -> Self {
+    const fn new(start: usize, method: SkinningMethod) -> Self {
         SkinIndex {
             index: (start * std::mem::size_of::<Mat4>()) as u32,
+            method,
         }
     }
 }
@@ -55,6 +59,10 @@ pub struct SkinIndices {
 /// the joint matrix buffer from two frames ago with the data for the current
 /// frame.
 ///
+/// With [`SkinningMethod::DualQuaternion`], each joint is packed in a matrix
+/// slot as the real and dual parts of its dual quaternion, followed by its
+/// scale, see [`pack_dual_quaternion_joint`].
+///
 /// Notes on implementation: see comment on top of the `extract_skins` system.
 #[derive(Resource)]
 pub struct SkinUniforms {
@@ -149,13 +157,17 @@ pub fn extract_skins(
         let start = buffer.len();
 
         let target = start + skin.joints.len().min(MAX_JOINTS);
-        buffer.extend(
-            joints
-                .iter_many(&skin.joints)
-                .zip(inverse_bindposes.iter())
-                .take(MAX_JOINTS)
-                .map(|(joint, bindpose)| joint.affine() * *bindpose),
-        );
+        let joint_matrices = joints
+            .iter_many(&skin.joints)
+            .zip(inverse_bindposes.iter())
+            .take(MAX_JOINTS)
+            .map(|(joint, bindpose)| joint.affine() * *bindpose);
+        match skin.skinning_method {
+            SkinningMethod::Linear => buffer.extend(joint_matrices),
+            SkinningMethod::DualQuaternion => {
+                buffer.extend(joint_matrices.map(pack_dual_quaternion_joint));
+            }
+        }
         // iter_many will skip any failed fetches. This will cause it to assign the wrong bones,
         // so just bail by truncating to the start.
         if buffer.len() != target {
@@ -169,7 +181,9 @@ pub fn extract_skins(
             buffer.push(Mat4::ZERO);
         }
 
-        skin_indices.current.insert(entity, SkinIndex::new(start));
+        skin_indices
+            .current
+            .insert(entity, SkinIndex::new(start, skin.skinning_method));
     }
 
     // Pad out the buffer to ensure that there's enough space for bindings
@@ -178,6 +192,21 @@ pub fn extract_skins(
     }
 }
 
+/// Packs a joint matrix for [`SkinningMethod::DualQuaternion`] in the columns of
+/// a matrix: the real part of the dual quaternion, its dual part, and the scale.
+///
+/// This must match `dual_quaternion_skin_model` in `skinning.wgsl`.
+fn pack_dual_quaternion_joint(joint_matrix: Mat4) -> Mat4 {
+    let (scale, rotation, translation) = joint_matrix.to_scale_rotation_translation();
+    let dual = Quat::from_xyzw(translation.x, translation.y, translation.z, 0.0) * rotation * 0.5;
+    Mat4::from_cols(
+        Vec4::from(rotation),
+        Vec4::from(dual),
+        scale.extend(0.0),
+        Vec4::ZERO,
+    )
+}
+
 // NOTE: The skinned joints uniform buffer has to be bound at a dynamic offset per
 // entity and so cannot currently be batched.
 pub fn no_automatic_skin_batching(
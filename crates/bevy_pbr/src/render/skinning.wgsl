@@ -18,10 +18,20 @@ fn skin_model(
     indexes: vec4<u32>,
     weights: vec4<f32>,
 ) -> mat4x4<f32> {
+#ifdef DUAL_QUATERNION_SKINNING
+    return dual_quaternion_skin_model(
+        joint_matrices.data[indexes.x],
+        joint_matrices.data[indexes.y],
+        joint_matrices.data[indexes.z],
+        joint_matrices.data[indexes.w],
+        weights,
+    );
+#else
     return weights.x * joint_matrices.data[indexes.x]
         + weights.y * joint_matrices.data[indexes.y]
         + weights.z * joint_matrices.data[indexes.z]
         + weights.w * joint_matrices.data[indexes.w];
+#endif
 }
 
 // Returns the skinned position of a vertex with the given weights from the
@@ -32,11 +42,70 @@ fn skin_prev_model(
     indexes: vec4<u32>,
     weights: vec4<f32>,
 ) -> mat4x4<f32> {
+#ifdef DUAL_QUATERNION_SKINNING
+    return dual_quaternion_skin_model(
+        prev_joint_matrices.data[indexes.x],
+        prev_joint_matrices.data[indexes.y],
+        prev_joint_matrices.data[indexes.z],
+        prev_joint_matrices.data[indexes.w],
+        weights,
+    );
+#else
     return weights.x * prev_joint_matrices.data[indexes.x]
         + weights.y * prev_joint_matrices.data[indexes.y]
         + weights.z * prev_joint_matrices.data[indexes.z]
         + weights.w * prev_joint_matrices.data[indexes.w];
+#endif
+}
+
+#ifdef DUAL_QUATERNION_SKINNING
+// Blends four joints packed as dual quaternions by `pack_dual_quaternion_joint`
+// in `skin.rs`, and returns the blended transform as a matrix.
+//
+// Each joint is packed as the real part of its dual quaternion in column 0, the
+// dual part in column 1, and the scale in column 2.
+fn dual_quaternion_skin_model(
+    joint_0: mat4x4<f32>,
+    joint_1: mat4x4<f32>,
+    joint_2: mat4x4<f32>,
+    joint_3: mat4x4<f32>,
+    weights: vec4<f32>,
+) -> mat4x4<f32> {
+    // Flip the quaternions into the hemisphere of the first one, so that the
+    // blend takes the shortest path.
+    let signed_weights = weights * vec4(
+        1.0,
+        select(-1.0, 1.0, dot(joint_0[0], joint_1[0]) >= 0.0),
+        select(-1.0, 1.0, dot(joint_0[0], joint_2[0]) >= 0.0),
+        select(-1.0, 1.0, dot(joint_0[0], joint_3[0]) >= 0.0),
+    );
+
+    var real = signed_weights.x * joint_0[0] + signed_weights.y * joint_1[0] +
+        signed_weights.z * joint_2[0] + signed_weights.w * joint_3[0];
+    var dual = signed_weights.x * joint_0[1] + signed_weights.y * joint_1[1] +
+        signed_weights.z * joint_2[1] + signed_weights.w * joint_3[1];
+    let scale = weights.x * joint_0[2].xyz + weights.y * joint_1[2].xyz +
+        weights.z * joint_2[2].xyz + weights.w * joint_3[2].xyz;
+
+    let inverse_length = 1.0 / length(real);
+    real *= inverse_length;
+    dual *= inverse_length;
+
+    // The translation is `2 * dual * conjugate(real)`.
+    let translation = 2.0 * (real.w * dual.xyz - dual.w * real.xyz + cross(real.xyz, dual.xyz));
+
+    let x = real.x;
+    let y = real.y;
+    let z = real.z;
+    let w = real.w;
+    return mat4x4<f32>(
+        vec4(1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y + w * z), 2.0 * (x * z - w * y), 0.0) * scale.x,
+        vec4(2.0 * (x * y - w * z), 1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z + w * x), 0.0) * scale.y,
+        vec4(2.0 * (x * z + w * y), 2.0 * (y * z - w * x), 1.0 - 2.0 * (x * x + y * y), 0.0) * scale.z,
+        vec4(translation, 1.0),
+    );
 }
+#endif
 
 fn inverse_transpose_3x3m(in: mat3x3<f32>) -> mat3x3<f32> {
     let x = cross(in[1], in[2]);
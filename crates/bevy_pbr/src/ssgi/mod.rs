@@ -0,0 +1,554 @@
+//! Screen space global illumination implemented via raymarching.
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Handle};
+use bevy_core_pipeline::{
+    core_3d::{
+        graph::{Core3d, Node3d},
+        DEPTH_TEXTURE_SAMPLING_SUPPORTED,
+    },
+    fullscreen_vertex_shader,
+    prepass::{DeferredPrepass, DepthPrepass, MotionVectorPrepass, NormalPrepass},
+};
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+    bundle::Bundle,
+    component::Component,
+    entity::Entity,
+    query::{Has, QueryItem, With},
+    reflect::ReflectComponent,
+    schedule::IntoSystemConfigs as _,
+    system::{lifetimeless::Read, Commands, Query, Res, ResMut, Resource},
+    world::{FromWorld, World},
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    render_graph::{NodeRunError, RenderGraphApp, RenderGraphContext, ViewNode, ViewNodeRunner},
+    render_resource::{
+        binding_types, AddressMode, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries,
+        CachedRenderPipelineId, ColorTargetState, ColorWrites, DynamicUniformBuffer, FilterMode,
+        FragmentState, Operations, PipelineCache, RenderPassColorAttachment, RenderPassDescriptor,
+        RenderPipelineDescriptor, Sampler, SamplerBindingType, SamplerDescriptor, Shader,
+        ShaderStages, ShaderType, SpecializedRenderPipeline, SpecializedRenderPipelines,
+        TextureFormat, TextureSampleType,
+    },
+    renderer::{RenderContext, RenderDevice, RenderQueue},
+    texture::BevyDefault as _,
+    view::{ExtractedView, Msaa, ViewTarget, ViewUniformOffset},
+    Render, RenderApp, RenderSet,
+};
+use bevy_utils::{info_once, prelude::default};
+
+use crate::{
+    binding_arrays_are_usable, graph::NodePbr, prelude::EnvironmentMapLight,
+    MeshPipelineViewLayoutKey, MeshPipelineViewLayouts, MeshViewBindGroup, RenderViewLightProbes,
+    ViewFogUniformOffset, ViewLightProbesUniformOffset, ViewLightsUniformOffset,
+    ViewScreenSpaceReflectionsUniformOffset,
+};
+
+const SSGI_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(3817240551982034167);
+
+/// Enables screen-space global illumination for cameras with a [`GlobalIllumination`] component.
+///
+/// Screen-space global illumination is currently only supported with deferred rendering.
+pub struct GlobalIlluminationPlugin;
+
+/// A convenient bundle to add screen space global illumination to a camera,
+/// along with the depth and deferred prepasses required to enable it.
+#[derive(Bundle, Default)]
+pub struct GlobalIlluminationBundle {
+    /// The component that enables global illumination.
+    pub settings: GlobalIllumination,
+    /// The depth prepass, needed for global illumination.
+    pub depth_prepass: DepthPrepass,
+    /// The deferred prepass, needed for global illumination.
+    pub deferred_prepass: DeferredPrepass,
+}
+
+/// Add this component to a camera to enable *screen-space global illumination*
+/// (SSGI), one bounce of diffuse light between the objects on screen.
+///
+/// For each pixel, rays are traced in random directions over the hemisphere
+/// around the surface normal by marching the depth buffer, and the lit color
+/// of whatever they hit is reflected onto the surface. Rays that don't hit
+/// anything fall back to the prefiltered diffuse light of the
+/// [`EnvironmentMapLight`], like surfaces without global illumination.
+///
+/// Like screen-space reflections, this currently requires deferred rendering,
+/// so you'll generally need to add a [`DepthPrepass`] and a [`DeferredPrepass`]
+/// to the camera as well, see [`GlobalIlluminationBundle`].
+///
+/// The samples are rotated every frame, so the result is noisy unless temporal
+/// antialiasing is enabled to accumulate them. As with all screen-space
+/// techniques, only objects on screen contribute light: the bounce light
+/// disappears when the objects casting it leave the camera.
+///
+/// Global illumination is presently unsupported on WebGL 2 for the same reason
+/// as [`ScreenSpaceReflectionsSettings`](crate::ScreenSpaceReflectionsSettings).
+#[derive(Clone, Copy, Component, Reflect)]
+#[reflect(Component, Default)]
+pub struct GlobalIllumination {
+    /// How many rays are traced for each pixel, and how precisely.
+    pub quality: GlobalIlluminationQuality,
+
+    /// A multiplier applied to the bounce light.
+    pub intensity: f32,
+
+    /// The length of the rays, in world units.
+    ///
+    /// Only the objects within this distance of a surface bounce light onto it.
+    pub max_distance: f32,
+
+    /// The assumed thickness of the objects in the depth buffer, see
+    /// [`ScreenSpaceReflectionsSettings::thickness`](crate::ScreenSpaceReflectionsSettings::thickness).
+    pub thickness: f32,
+}
+
+/// The quality of [`GlobalIllumination`].
+#[derive(Reflect, PartialEq, Eq, Hash, Clone, Copy, Default, Debug)]
+pub enum GlobalIlluminationQuality {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Ultra,
+    Custom {
+        /// Higher ray counts mean less noise, but worse performance.
+        ray_count: u32,
+        /// The number of steps taken along each ray to find an intersection. Must not be zero.
+        linear_steps: u32,
+    },
+}
+
+impl GlobalIlluminationQuality {
+    fn sample_counts(&self) -> (u32, u32) {
+        match self {
+            Self::Low => (2, 8),
+            Self::Medium => (4, 12),
+            Self::High => (8, 16),
+            Self::Ultra => (16, 24),
+            Self::Custom {
+                ray_count,
+                linear_steps,
+            } => (*ray_count, *linear_steps),
+        }
+    }
+}
+
+/// A version of [`GlobalIllumination`] for upload to the GPU.
+#[derive(Clone, Copy, Component, ShaderType)]
+pub struct GlobalIlluminationUniform {
+    intensity: f32,
+    max_distance: f32,
+    thickness: f32,
+    ray_count: u32,
+    linear_steps: u32,
+    bisection_steps: u32,
+}
+
+/// The node in the render graph that traces screen space global illumination.
+#[derive(Default)]
+pub struct GlobalIlluminationNode;
+
+/// Identifies which global illumination render pipeline a view needs.
+#[derive(Component, Deref, DerefMut)]
+pub struct GlobalIlluminationPipelineId(pub CachedRenderPipelineId);
+
+/// Information relating to the render pipeline for the global illumination shader.
+#[derive(Resource)]
+pub struct GlobalIlluminationPipeline {
+    mesh_view_layouts: MeshPipelineViewLayouts,
+    color_sampler: Sampler,
+    depth_linear_sampler: Sampler,
+    depth_nearest_sampler: Sampler,
+    bind_group_layout: BindGroupLayout,
+    binding_arrays_are_usable: bool,
+}
+
+/// A GPU buffer that stores the global illumination settings for each view.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct GlobalIlluminationBuffer(pub DynamicUniformBuffer<GlobalIlluminationUniform>);
+
+/// A component that stores the offset within the [`GlobalIlluminationBuffer`]
+/// for each view.
+#[derive(Component, Default, Deref, DerefMut)]
+pub struct ViewGlobalIlluminationUniformOffset(u32);
+
+/// Identifies a specific configuration of the global illumination pipeline shader.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GlobalIlluminationPipelineKey {
+    mesh_pipeline_view_key: MeshPipelineViewLayoutKey,
+    is_hdr: bool,
+    has_environment_maps: bool,
+}
+
+impl Plugin for GlobalIlluminationPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, SSGI_SHADER_HANDLE, "ssgi.wgsl", Shader::from_wgsl);
+
+        app.register_type::<GlobalIllumination>()
+            .add_plugins(ExtractComponentPlugin::<GlobalIllumination>::default());
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<GlobalIlluminationBuffer>()
+            .add_systems(Render, prepare_ssgi_pipelines.in_set(RenderSet::Prepare))
+            .add_systems(
+                Render,
+                prepare_ssgi_settings.in_set(RenderSet::PrepareResources),
+            )
+            .add_render_graph_node::<ViewNodeRunner<GlobalIlluminationNode>>(
+                Core3d,
+                NodePbr::GlobalIllumination,
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        // Bounce light is applied before reflections so that it shows up in them.
+        render_app
+            .init_resource::<GlobalIlluminationPipeline>()
+            .init_resource::<SpecializedRenderPipelines<GlobalIlluminationPipeline>>()
+            .add_render_graph_edges(
+                Core3d,
+                (
+                    NodePbr::DeferredLightingPass,
+                    NodePbr::GlobalIllumination,
+                    NodePbr::ScreenSpaceReflections,
+                    Node3d::MainOpaquePass,
+                ),
+            );
+    }
+}
+
+impl Default for GlobalIllumination {
+    fn default() -> Self {
+        Self {
+            quality: GlobalIlluminationQuality::default(),
+            intensity: 1.0,
+            max_distance: 2.0,
+            thickness: 0.25,
+        }
+    }
+}
+
+impl ViewNode for GlobalIlluminationNode {
+    type ViewQuery = (
+        Read<ViewTarget>,
+        Read<ViewUniformOffset>,
+        Read<ViewLightsUniformOffset>,
+        Read<ViewFogUniformOffset>,
+        Read<ViewLightProbesUniformOffset>,
+        Read<ViewScreenSpaceReflectionsUniformOffset>,
+        Read<ViewGlobalIlluminationUniformOffset>,
+        Read<MeshViewBindGroup>,
+        Read<GlobalIlluminationPipelineId>,
+    );
+
+    fn run<'w>(
+        &self,
+        _: &mut RenderGraphContext,
+        render_context: &mut RenderContext<'w>,
+        (
+            view_target,
+            view_uniform_offset,
+            view_lights_offset,
+            view_fog_offset,
+            view_light_probes_offset,
+            view_ssr_offset,
+            view_ssgi_offset,
+            view_bind_group,
+            ssgi_pipeline_id,
+        ): QueryItem<'w, Self::ViewQuery>,
+        world: &'w World,
+    ) -> Result<(), NodeRunError> {
+        // Grab the render pipeline.
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let Some(render_pipeline) = pipeline_cache.get_render_pipeline(**ssgi_pipeline_id) else {
+            return Ok(());
+        };
+        let Some(ssgi_settings_binding) = world.resource::<GlobalIlluminationBuffer>().binding()
+        else {
+            return Ok(());
+        };
+
+        // Set up a standard pair of postprocessing textures.
+        let postprocess = view_target.post_process_write();
+
+        // Create the bind group for this view.
+        let ssgi_pipeline = world.resource::<GlobalIlluminationPipeline>();
+        let ssgi_bind_group = render_context.render_device().create_bind_group(
+            "SSGI bind group",
+            &ssgi_pipeline.bind_group_layout,
+            &BindGroupEntries::sequential((
+                postprocess.source,
+                &ssgi_pipeline.color_sampler,
+                &ssgi_pipeline.depth_linear_sampler,
+                &ssgi_pipeline.depth_nearest_sampler,
+                ssgi_settings_binding,
+            )),
+        );
+
+        // Build the SSGI render pass.
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("SSGI pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: postprocess.destination,
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        // Set bind groups.
+        render_pass.set_render_pipeline(render_pipeline);
+        render_pass.set_bind_group(
+            0,
+            &view_bind_group.value,
+            &[
+                view_uniform_offset.offset,
+                view_lights_offset.offset,
+                view_fog_offset.offset,
+                **view_light_probes_offset,
+                **view_ssr_offset,
+            ],
+        );
+        render_pass.set_bind_group(1, &ssgi_bind_group, &[**view_ssgi_offset]);
+
+        // Perform the SSGI render pass.
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
+
+impl FromWorld for GlobalIlluminationPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let mesh_view_layouts = world.resource::<MeshPipelineViewLayouts>().clone();
+        let render_device = world.resource::<RenderDevice>();
+
+        // Create the bind group layout. The samplers are laid out as
+        // `raymarch.wgsl` expects them.
+        let bind_group_layout = render_device.create_bind_group_layout(
+            "SSGI bind group layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    binding_types::texture_2d(TextureSampleType::Float { filterable: true }),
+                    binding_types::sampler(SamplerBindingType::Filtering),
+                    binding_types::sampler(SamplerBindingType::Filtering),
+                    binding_types::sampler(SamplerBindingType::NonFiltering),
+                    binding_types::uniform_buffer::<GlobalIlluminationUniform>(true),
+                ),
+            ),
+        );
+
+        // Create the samplers we need.
+
+        let color_sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: "SSGI color sampler".into(),
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..default()
+        });
+
+        let depth_linear_sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: "SSGI depth linear sampler".into(),
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..default()
+        });
+
+        let depth_nearest_sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: "SSGI depth nearest sampler".into(),
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            mag_filter: FilterMode::Nearest,
+            min_filter: FilterMode::Nearest,
+            ..default()
+        });
+
+        Self {
+            mesh_view_layouts,
+            color_sampler,
+            depth_linear_sampler,
+            depth_nearest_sampler,
+            bind_group_layout,
+            binding_arrays_are_usable: binding_arrays_are_usable(render_device),
+        }
+    }
+}
+
+/// Sets up global illumination pipelines for each applicable view.
+pub fn prepare_ssgi_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<GlobalIlluminationPipeline>>,
+    ssgi_pipeline: Res<GlobalIlluminationPipeline>,
+    views: Query<
+        (
+            Entity,
+            &ExtractedView,
+            Has<RenderViewLightProbes<EnvironmentMapLight>>,
+            Has<NormalPrepass>,
+            Has<MotionVectorPrepass>,
+        ),
+        (
+            With<GlobalIlluminationUniform>,
+            With<DepthPrepass>,
+            With<DeferredPrepass>,
+        ),
+    >,
+) {
+    for (
+        entity,
+        extracted_view,
+        has_environment_maps,
+        has_normal_prepass,
+        has_motion_vector_prepass,
+    ) in &views
+    {
+        // Global illumination is only supported in the deferred pipeline,
+        // which has no MSAA support. Thus we can assume MSAA is off.
+        let mut mesh_pipeline_view_key = MeshPipelineViewLayoutKey::from(Msaa::Off)
+            | MeshPipelineViewLayoutKey::DEPTH_PREPASS
+            | MeshPipelineViewLayoutKey::DEFERRED_PREPASS;
+        mesh_pipeline_view_key.set(
+            MeshPipelineViewLayoutKey::NORMAL_PREPASS,
+            has_normal_prepass,
+        );
+        mesh_pipeline_view_key.set(
+            MeshPipelineViewLayoutKey::MOTION_VECTOR_PREPASS,
+            has_motion_vector_prepass,
+        );
+
+        let pipeline_id = pipelines.specialize(
+            &pipeline_cache,
+            &ssgi_pipeline,
+            GlobalIlluminationPipelineKey {
+                mesh_pipeline_view_key,
+                is_hdr: extracted_view.hdr,
+                has_environment_maps,
+            },
+        );
+
+        commands
+            .entity(entity)
+            .insert(GlobalIlluminationPipelineId(pipeline_id));
+    }
+}
+
+/// Gathers up global illumination settings for each applicable view and writes
+/// them into a GPU buffer.
+pub fn prepare_ssgi_settings(
+    mut commands: Commands,
+    views: Query<(Entity, &GlobalIlluminationUniform), With<ExtractedView>>,
+    mut ssgi_settings_buffer: ResMut<GlobalIlluminationBuffer>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    let Some(mut writer) =
+        ssgi_settings_buffer.get_writer(views.iter().len(), &render_device, &render_queue)
+    else {
+        return;
+    };
+
+    for (view, ssgi_uniform) in views.iter() {
+        commands
+            .entity(view)
+            .insert(ViewGlobalIlluminationUniformOffset(
+                writer.write(ssgi_uniform),
+            ));
+    }
+}
+
+impl ExtractComponent for GlobalIllumination {
+    type QueryData = Read<GlobalIllumination>;
+
+    type QueryFilter = ();
+
+    type Out = GlobalIlluminationUniform;
+
+    fn extract_component(settings: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        if !DEPTH_TEXTURE_SAMPLING_SUPPORTED {
+            info_once!(
+                "Disabling screen-space global illumination on this platform because depth \
+                textures aren't supported correctly"
+            );
+            return None;
+        }
+
+        Some((*settings).into())
+    }
+}
+
+impl SpecializedRenderPipeline for GlobalIlluminationPipeline {
+    type Key = GlobalIlluminationPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mesh_view_layout = self
+            .mesh_view_layouts
+            .get_view_layout(key.mesh_pipeline_view_key);
+
+        let mut shader_defs = vec!["DEPTH_PREPASS".into(), "DEFERRED_PREPASS".into()];
+
+        if key.has_environment_maps {
+            shader_defs.push("ENVIRONMENT_MAP".into());
+        }
+
+        if self.binding_arrays_are_usable {
+            shader_defs.push("MULTIPLE_LIGHT_PROBES_IN_ARRAY".into());
+        }
+
+        RenderPipelineDescriptor {
+            label: Some("SSGI pipeline".into()),
+            layout: vec![mesh_view_layout.clone(), self.bind_group_layout.clone()],
+            vertex: fullscreen_vertex_shader::fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: SSGI_SHADER_HANDLE,
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: if key.is_hdr {
+                        ViewTarget::TEXTURE_FORMAT_HDR
+                    } else {
+                        TextureFormat::bevy_default()
+                    },
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            push_constant_ranges: vec![],
+            primitive: default(),
+            depth_stencil: None,
+            multisample: default(),
+        }
+    }
+}
+
+impl From<GlobalIllumination> for GlobalIlluminationUniform {
+    fn from(settings: GlobalIllumination) -> Self {
+        let (ray_count, linear_steps) = settings.quality.sample_counts();
+        Self {
+            intensity: settings.intensity,
+            max_distance: settings.max_distance,
+            thickness: settings.thickness,
+            ray_count,
+            linear_steps,
+            bisection_steps: 2,
+        }
+    }
+}
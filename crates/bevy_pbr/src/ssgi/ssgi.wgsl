@@ -0,0 +1,169 @@
+// A postprocessing pass that performs screen-space global illumination.
+
+#define_import_path bevy_pbr::ssgi
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+#import bevy_pbr::{
+    lighting,
+    lighting::LAYER_BASE,
+    mesh_view_bindings::{view, globals, depth_prepass_texture, deferred_prepass_texture},
+    pbr_deferred_functions::pbr_input_from_deferred_gbuffer,
+    pbr_functions,
+    pbr_types::STANDARD_MATERIAL_FLAGS_UNLIT_BIT,
+    prepass_utils,
+    raymarch::{
+        depth_ray_march_from_cs,
+        depth_ray_march_march,
+        depth_ray_march_new_from_depth,
+        depth_ray_march_to_ws,
+    },
+    utils::interleaved_gradient_noise,
+    view_transformations::position_world_to_ndc,
+}
+#import bevy_render::maths::{orthonormalize, PI_2}
+
+#ifdef ENVIRONMENT_MAP
+#import bevy_pbr::environment_map
+#endif
+
+// The settings of the global illumination pass.
+//
+// For more information on these settings, see the documentation for
+// `bevy_pbr::ssgi::GlobalIllumination`.
+struct GlobalIlluminationSettings {
+    intensity: f32,
+    max_distance: f32,
+    thickness: f32,
+    ray_count: u32,
+    linear_steps: u32,
+    bisection_steps: u32,
+}
+
+// The texture representing the color framebuffer.
+@group(1) @binding(0) var color_texture: texture_2d<f32>;
+
+// The sampler that lets us sample from the color framebuffer.
+@group(1) @binding(1) var color_sampler: sampler;
+
+// Group 1, bindings 2 and 3 are in `raymarch.wgsl`.
+
+@group(1) @binding(4) var<uniform> ssgi_settings: GlobalIlluminationSettings;
+
+// Traces a single ray of length `max_distance` from `P_world` in the direction
+// `L_world` and returns the radiance reflected toward `P_world` in the RGB
+// channels, and whether the ray hit something in the alpha channel.
+fn trace_ssgi_ray(P_world: vec3<f32>, L_world: vec3<f32>, jitter: f32) -> vec4<f32> {
+    let depth_size = vec2<f32>(textureDimensions(depth_prepass_texture));
+
+    var raymarch = depth_ray_march_new_from_depth(depth_size);
+    depth_ray_march_from_cs(&raymarch, position_world_to_ndc(P_world));
+    depth_ray_march_to_ws(&raymarch, P_world + L_world * ssgi_settings.max_distance);
+    raymarch.linear_steps = ssgi_settings.linear_steps;
+    raymarch.bisection_steps = ssgi_settings.bisection_steps;
+    raymarch.use_secant = true;
+    raymarch.depth_thickness_linear_z = ssgi_settings.thickness;
+    raymarch.jitter = jitter;
+    raymarch.march_behind_surfaces = false;
+
+    let raymarch_result = depth_ray_march_march(&raymarch);
+    if (raymarch_result.hit) {
+        return vec4(
+            textureSampleLevel(color_texture, color_sampler, raymarch_result.hit_uv, 0.0).rgb,
+            1.0
+        );
+    }
+
+    return vec4(0.0);
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    // Sample the depth.
+    var frag_coord = in.position;
+    frag_coord.z = prepass_utils::prepass_depth(in.position, 0u);
+
+    let fragment = textureLoad(color_texture, vec2<i32>(frag_coord.xy), 0);
+
+    // Skip the background.
+    if (frag_coord.z == 0.0) {
+        return fragment;
+    }
+
+    // Load the G-buffer data.
+    let gbuffer = textureLoad(deferred_prepass_texture, vec2<i32>(frag_coord.xy), 0);
+    let pbr_input = pbr_input_from_deferred_gbuffer(frag_coord, gbuffer);
+    if ((pbr_input.material.flags & STANDARD_MATERIAL_FLAGS_UNLIT_BIT) != 0u) {
+        return fragment;
+    }
+
+    let world_position = pbr_input.world_position.xyz;
+    let N = pbr_input.N;
+    let diffuse_color = pbr_functions::calculate_diffuse_color(
+        pbr_input.material.base_color.rgb,
+        pbr_input.material.metallic,
+        pbr_input.material.specular_transmission,
+        pbr_input.material.diffuse_transmission
+    );
+
+    // Trace cosine-weighted rays over the hemisphere. The noise is rotated
+    // every frame so that temporal antialiasing can accumulate the samples.
+    let basis = orthonormalize(N, select(vec3(0.0, 1.0, 0.0), vec3(1.0, 0.0, 0.0), abs(N.y) > 0.99));
+    let noise = vec2(
+        interleaved_gradient_noise(frag_coord.xy, globals.frame_count),
+        interleaved_gradient_noise(frag_coord.xy + vec2(17.0, 59.0), globals.frame_count),
+    );
+    var radiance = vec3(0.0);
+    var hit_count = 0.0;
+    for (var ray_index = 0u; ray_index < ssgi_settings.ray_count; ray_index += 1u) {
+        let u = fract(noise + vec2(
+            (f32(ray_index) + 0.5) / f32(ssgi_settings.ray_count),
+            f32(ray_index) * 0.618034
+        ));
+        let r = sqrt(u.x);
+        let phi = u.y * PI_2;
+        let L = basis * vec3(r * cos(phi), r * sin(phi), sqrt(1.0 - u.x));
+
+        let ray = trace_ssgi_ray(world_position, L, fract(noise.x + f32(ray_index) * 0.618034));
+        radiance += ray.rgb;
+        hit_count += ray.a;
+    }
+    let ray_count = f32(max(ssgi_settings.ray_count, 1u));
+
+    // With cosine-weighted rays, the average radiance times the diffuse color
+    // is the outgoing diffuse light.
+    var indirect_light = diffuse_color * (radiance / ray_count) * ssgi_settings.intensity *
+        pbr_input.diffuse_occlusion;
+
+    // The rays that missed fall back to the prefiltered environment map, which
+    // the lighting pass has already applied. Remove it for the rays that hit,
+    // since they were occluded.
+#ifdef ENVIRONMENT_MAP
+    let perceptual_roughness = pbr_input.material.perceptual_roughness;
+    let V = pbr_input.V;
+    let NdotV = max(dot(N, V), 0.0001);
+
+    var lighting_input: lighting::LightingInput;
+    lighting_input.layers[LAYER_BASE].NdotV = NdotV;
+    lighting_input.layers[LAYER_BASE].N = N;
+    lighting_input.layers[LAYER_BASE].R = reflect(-V, N);
+    lighting_input.layers[LAYER_BASE].perceptual_roughness = perceptual_roughness;
+    lighting_input.layers[LAYER_BASE].roughness =
+        lighting::perceptualRoughnessToRoughness(perceptual_roughness);
+    lighting_input.P = world_position;
+    lighting_input.V = V;
+    lighting_input.diffuse_color = diffuse_color;
+    lighting_input.F0_ = pbr_functions::calculate_F0(
+        pbr_input.material.base_color.rgb,
+        pbr_input.material.metallic,
+        pbr_input.material.reflectance
+    );
+    lighting_input.F_ab = lighting::F_AB(perceptual_roughness, NdotV);
+
+    let environment_light = environment_map::environment_map_light(&lighting_input, false);
+    indirect_light -= view.exposure * environment_light.diffuse * pbr_input.diffuse_occlusion *
+        (hit_count / ray_count) * ssgi_settings.intensity;
+#endif  // ENVIRONMENT_MAP
+
+    // Write the results.
+    return vec4(max(fragment.rgb + indirect_light, vec3(0.0)), fragment.a);
+}
@@ -0,0 +1,353 @@
+//! Screen space subsurface scattering implemented via a separable blur.
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Handle};
+use bevy_core_pipeline::{
+    core_3d::{
+        graph::{Core3d, Node3d},
+        DEPTH_TEXTURE_SAMPLING_SUPPORTED,
+    },
+    fullscreen_vertex_shader,
+    prepass::{DepthPrepass, ViewPrepassTextures},
+};
+use bevy_ecs::{
+    bundle::Bundle,
+    component::Component,
+    query::{Has, QueryItem},
+    reflect::ReflectComponent,
+    system::{lifetimeless::Read, Resource},
+    world::{FromWorld, World},
+};
+use bevy_math::Vec3;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::Camera,
+    extract_component::{
+        ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+        UniformComponentPlugin,
+    },
+    render_graph::{NodeRunError, RenderGraphApp, RenderGraphContext, ViewNode, ViewNodeRunner},
+    render_resource::{
+        binding_types, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries,
+        CachedRenderPipelineId, ColorTargetState, ColorWrites, FragmentState, Operations,
+        PipelineCache, RenderPassColorAttachment, RenderPassDescriptor, RenderPipelineDescriptor,
+        Shader, ShaderStages, ShaderType, TextureSampleType,
+    },
+    renderer::{RenderContext, RenderDevice},
+    view::{Msaa, ViewTarget, ViewUniform, ViewUniformOffset, ViewUniforms},
+    RenderApp,
+};
+use bevy_utils::{info_once, prelude::default, warn_once};
+
+use crate::graph::NodePbr;
+
+const SSSS_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(9245064577038410391);
+
+/// Enables screen-space subsurface scattering for cameras with a
+/// [`ScreenSpaceSubsurfaceScattering`] component.
+///
+/// Screen-space subsurface scattering is currently only supported with forward
+/// rendering.
+pub struct ScreenSpaceSubsurfaceScatteringPlugin;
+
+/// A convenient bundle to add screen space subsurface scattering to a camera,
+/// along with the depth prepass required to enable it.
+#[derive(Bundle, Default)]
+pub struct ScreenSpaceSubsurfaceScatteringBundle {
+    /// The component that enables subsurface scattering.
+    pub settings: ScreenSpaceSubsurfaceScattering,
+    /// The depth prepass, needed for subsurface scattering.
+    pub depth_prepass: DepthPrepass,
+}
+
+/// Add this component to a camera to enable *screen-space subsurface
+/// scattering* (SSSS), the soft look of skin, wax or marble due to light
+/// entering the surface in one place and leaving it in another.
+///
+/// The lit color of the surfaces of a [`StandardMaterial`] with a
+/// [`subsurface_radius`] is blurred across the screen, by about that distance
+/// in world units, after the opaque pass. The blur stops at the edges of the
+/// surfaces with subsurface scattering, and where the depth changes by more than
+/// the radius, so light doesn't bleed onto the background.
+///
+/// The camera must render in HDR, since the radius is passed in the alpha
+/// channel of the main pass texture, and have a [`DepthPrepass`], see
+/// [`ScreenSpaceSubsurfaceScatteringBundle`]. MSAA must be off: views with
+/// MSAA skip the effect. Only forward rendered, opaque or alpha masked
+/// surfaces scatter light.
+///
+/// Subsurface scattering is presently unsupported on WebGL 2 for the same
+/// reason as [`ScreenSpaceReflectionsSettings`](crate::ScreenSpaceReflectionsSettings).
+///
+/// [`StandardMaterial`]: crate::StandardMaterial
+/// [`subsurface_radius`]: crate::StandardMaterial::subsurface_radius
+#[derive(Clone, Copy, Component, Reflect)]
+#[reflect(Component, Default)]
+pub struct ScreenSpaceSubsurfaceScattering {
+    /// How far each of the red, green and blue channels scatters, relative to
+    /// the [`subsurface_radius`](crate::StandardMaterial::subsurface_radius)
+    /// of the material.
+    ///
+    /// The default scatters red the furthest, as in skin.
+    pub falloff: Vec3,
+
+    /// The number of samples taken along each axis of the blur.
+    ///
+    /// More samples give a smoother result at a higher cost.
+    pub sample_count: u32,
+}
+
+/// A version of [`ScreenSpaceSubsurfaceScattering`] for upload to the GPU.
+#[derive(Clone, Copy, Component, ShaderType)]
+pub struct ScreenSpaceSubsurfaceScatteringUniform {
+    falloff: Vec3,
+    sample_count: u32,
+}
+
+/// The node in the render graph that blurs the subsurface scattering.
+#[derive(Default)]
+pub struct ScreenSpaceSubsurfaceScatteringNode;
+
+/// The render pipelines of the horizontal and vertical blur passes.
+#[derive(Resource)]
+pub struct ScreenSpaceSubsurfaceScatteringPipeline {
+    bind_group_layout: BindGroupLayout,
+    horizontal_pipeline_id: CachedRenderPipelineId,
+    vertical_pipeline_id: CachedRenderPipelineId,
+}
+
+impl Plugin for ScreenSpaceSubsurfaceScatteringPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, SSSS_SHADER_HANDLE, "ssss.wgsl", Shader::from_wgsl);
+
+        app.register_type::<ScreenSpaceSubsurfaceScattering>()
+            .add_plugins((
+                ExtractComponentPlugin::<ScreenSpaceSubsurfaceScattering>::default(),
+                UniformComponentPlugin::<ScreenSpaceSubsurfaceScatteringUniform>::default(),
+            ));
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app.add_render_graph_node::<ViewNodeRunner<ScreenSpaceSubsurfaceScatteringNode>>(
+            Core3d,
+            NodePbr::ScreenSpaceSubsurfaceScattering,
+        );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        // Scattering is applied before the transmissive pass so that it shows
+        // through transmissive surfaces.
+        render_app
+            .init_resource::<ScreenSpaceSubsurfaceScatteringPipeline>()
+            .add_render_graph_edges(
+                Core3d,
+                (
+                    Node3d::MainOpaquePass,
+                    NodePbr::ScreenSpaceSubsurfaceScattering,
+                    Node3d::MainTransmissivePass,
+                ),
+            );
+    }
+}
+
+impl Default for ScreenSpaceSubsurfaceScattering {
+    fn default() -> Self {
+        Self {
+            falloff: Vec3::new(1.0, 0.37, 0.3),
+            sample_count: 11,
+        }
+    }
+}
+
+impl ViewNode for ScreenSpaceSubsurfaceScatteringNode {
+    type ViewQuery = (
+        Read<ViewTarget>,
+        Read<ViewPrepassTextures>,
+        Read<ViewUniformOffset>,
+        Read<DynamicUniformIndex<ScreenSpaceSubsurfaceScatteringUniform>>,
+    );
+
+    fn run<'w>(
+        &self,
+        _: &mut RenderGraphContext,
+        render_context: &mut RenderContext<'w>,
+        (view_target, prepass_textures, view_uniform_offset, ssss_uniform_index): QueryItem<
+            'w,
+            Self::ViewQuery,
+        >,
+        world: &'w World,
+    ) -> Result<(), NodeRunError> {
+        // Materials only mark their pixels for the blur when MSAA is off.
+        if world.resource::<Msaa>().samples() > 1 {
+            warn_once!("Screen-space subsurface scattering doesn't support MSAA, skipping it");
+            return Ok(());
+        }
+
+        let ssss_pipeline = world.resource::<ScreenSpaceSubsurfaceScatteringPipeline>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let (Some(horizontal_pipeline), Some(vertical_pipeline)) = (
+            pipeline_cache.get_render_pipeline(ssss_pipeline.horizontal_pipeline_id),
+            pipeline_cache.get_render_pipeline(ssss_pipeline.vertical_pipeline_id),
+        ) else {
+            return Ok(());
+        };
+        let (Some(view_uniforms_binding), Some(ssss_settings_binding)) = (
+            world.resource::<ViewUniforms>().uniforms.binding(),
+            world
+                .resource::<ComponentUniforms<ScreenSpaceSubsurfaceScatteringUniform>>()
+                .uniforms()
+                .binding(),
+        ) else {
+            return Ok(());
+        };
+        let Some(depth_view) = prepass_textures.depth_view() else {
+            return Ok(());
+        };
+
+        for (label, render_pipeline) in [
+            ("SSSS horizontal pass", horizontal_pipeline),
+            ("SSSS vertical pass", vertical_pipeline),
+        ] {
+            // Each pass reads the output of the previous one.
+            let postprocess = view_target.post_process_write();
+
+            let ssss_bind_group = render_context.render_device().create_bind_group(
+                "SSSS bind group",
+                &ssss_pipeline.bind_group_layout,
+                &BindGroupEntries::sequential((
+                    postprocess.source,
+                    depth_view,
+                    view_uniforms_binding.clone(),
+                    ssss_settings_binding.clone(),
+                )),
+            );
+
+            let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+                label: Some(label),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: postprocess.destination,
+                    resolve_target: None,
+                    ops: Operations::default(),
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            render_pass.set_render_pipeline(render_pipeline);
+            render_pass.set_bind_group(
+                0,
+                &ssss_bind_group,
+                &[view_uniform_offset.offset, ssss_uniform_index.index()],
+            );
+            render_pass.draw(0..3, 0..1);
+        }
+
+        Ok(())
+    }
+}
+
+impl FromWorld for ScreenSpaceSubsurfaceScatteringPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        let bind_group_layout = render_device.create_bind_group_layout(
+            "SSSS bind group layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    binding_types::texture_2d(TextureSampleType::Float { filterable: false }),
+                    binding_types::texture_depth_2d(),
+                    binding_types::uniform_buffer::<ViewUniform>(true),
+                    binding_types::uniform_buffer::<ScreenSpaceSubsurfaceScatteringUniform>(true),
+                ),
+            ),
+        );
+
+        let descriptor = |label: &'static str, vertical: bool| RenderPipelineDescriptor {
+            label: Some(label.into()),
+            layout: vec![bind_group_layout.clone()],
+            vertex: fullscreen_vertex_shader::fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: SSSS_SHADER_HANDLE,
+                shader_defs: if vertical {
+                    vec!["VERTICAL".into()]
+                } else {
+                    vec![]
+                },
+                entry_point: "fragment".into(),
+                // Subsurface scattering requires HDR.
+                targets: vec![Some(ColorTargetState {
+                    format: ViewTarget::TEXTURE_FORMAT_HDR,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            push_constant_ranges: vec![],
+            primitive: default(),
+            depth_stencil: None,
+            multisample: default(),
+        };
+
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let horizontal_pipeline_id =
+            pipeline_cache.queue_render_pipeline(descriptor("SSSS horizontal pipeline", false));
+        let vertical_pipeline_id =
+            pipeline_cache.queue_render_pipeline(descriptor("SSSS vertical pipeline", true));
+
+        Self {
+            bind_group_layout,
+            horizontal_pipeline_id,
+            vertical_pipeline_id,
+        }
+    }
+}
+
+impl ExtractComponent for ScreenSpaceSubsurfaceScattering {
+    type QueryData = (
+        Read<ScreenSpaceSubsurfaceScattering>,
+        Read<Camera>,
+        Has<DepthPrepass>,
+    );
+
+    type QueryFilter = ();
+
+    type Out = ScreenSpaceSubsurfaceScatteringUniform;
+
+    fn extract_component(
+        (settings, camera, has_depth_prepass): QueryItem<'_, Self::QueryData>,
+    ) -> Option<Self::Out> {
+        if !DEPTH_TEXTURE_SAMPLING_SUPPORTED {
+            info_once!(
+                "Disabling screen-space subsurface scattering on this platform because depth \
+                textures aren't supported correctly"
+            );
+            return None;
+        }
+
+        if !camera.hdr || !has_depth_prepass {
+            warn_once!(
+                "Screen-space subsurface scattering needs an HDR camera with a depth prepass, \
+                disabling it"
+            );
+            return None;
+        }
+
+        Some((*settings).into())
+    }
+}
+
+impl From<ScreenSpaceSubsurfaceScattering> for ScreenSpaceSubsurfaceScatteringUniform {
+    fn from(settings: ScreenSpaceSubsurfaceScattering) -> Self {
+        Self {
+            falloff: settings.falloff,
+            sample_count: settings.sample_count,
+        }
+    }
+}
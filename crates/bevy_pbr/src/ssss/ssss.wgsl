@@ -0,0 +1,95 @@
+// A postprocessing pass that performs screen-space subsurface scattering.
+//
+// Materials with a subsurface radius mark their pixels by writing the negated radius to the alpha
+// channel of the HDR main pass texture. This pass blurs those pixels horizontally, then vertically
+// when `VERTICAL` is defined, and the vertical pass restores their alpha.
+
+#define_import_path bevy_pbr::ssss
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+#import bevy_render::view::View
+
+// The settings of the subsurface scattering pass.
+//
+// For more information on these settings, see the documentation for
+// `bevy_pbr::ssss::ScreenSpaceSubsurfaceScattering`.
+struct ScreenSpaceSubsurfaceScatteringSettings {
+    falloff: vec3<f32>,
+    sample_count: u32,
+}
+
+@group(0) @binding(0) var color_texture: texture_2d<f32>;
+@group(0) @binding(1) var depth_texture: texture_depth_2d;
+@group(0) @binding(2) var<uniform> view: View;
+@group(0) @binding(3) var<uniform> settings: ScreenSpaceSubsurfaceScatteringSettings;
+
+// Converts a depth buffer value to a view space Z coordinate.
+fn depth_to_view_z(depth: f32) -> f32 {
+    let view_position = view.view_from_clip * vec4(0.0, 0.0, depth, 1.0);
+    return view_position.z / view_position.w;
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let pixel = vec2<i32>(in.position.xy);
+    let center = textureLoad(color_texture, pixel, 0);
+
+    // Pixels without subsurface scattering are left as they are.
+    if (center.a >= 0.0) {
+        return center;
+    }
+
+    let radius = -center.a;
+    let center_z = depth_to_view_z(textureLoad(depth_texture, pixel, 0));
+
+    // The number of pixels covered by one world unit at this depth.
+    var pixels_per_unit = view.clip_from_view[1][1] * 0.5 * view.viewport.w;
+    if (view.clip_from_view[3][3] != 1.0) {
+        // Perspective projection.
+        pixels_per_unit /= max(-center_z, 0.0001);
+    }
+
+#ifdef VERTICAL
+    let direction = vec2(0.0, 1.0);
+#else
+    let direction = vec2(1.0, 0.0);
+#endif
+
+    // Each channel scatters as a Gaussian, and the samples cover three standard deviations of the
+    // widest one.
+    let sigma = radius * max(settings.falloff, vec3(0.0001));
+    let extent = 3.0 * max(sigma.r, max(sigma.g, sigma.b));
+    let size = vec2<i32>(textureDimensions(color_texture));
+    let sample_count = max(settings.sample_count, 2u);
+
+    var total = vec3(0.0);
+    var total_weight = vec3(0.0);
+    for (var i = 0u; i < sample_count; i += 1u) {
+        let offset = (f32(i) / f32(sample_count - 1u) * 2.0 - 1.0) * extent;
+        let sample_pixel = clamp(
+            pixel + vec2<i32>(round(direction * offset * pixels_per_unit)),
+            vec2(0),
+            size - 1
+        );
+
+        // Light doesn't scatter in from surfaces without subsurface scattering...
+        var sample_color = textureLoad(color_texture, sample_pixel, 0);
+        if (sample_color.a >= 0.0) {
+            sample_color = center;
+        }
+
+        // ...nor from surfaces far in front of or behind this one.
+        let sample_z = depth_to_view_z(textureLoad(depth_texture, sample_pixel, 0));
+        let sample_rgb = mix(sample_color.rgb, center.rgb, saturate(abs(sample_z - center_z) / radius));
+
+        let weight = exp(-(offset * offset) / (2.0 * sigma * sigma));
+        total += sample_rgb * weight;
+        total_weight += weight;
+    }
+
+#ifdef VERTICAL
+    return vec4(total / total_weight, 1.0);
+#else
+    return vec4(total / total_weight, center.a);
+#endif
+}
@@ -15,6 +15,13 @@
 //! directly with the number of directional lights in use, so apply
 //! [`VolumetricLight`] sparingly for the best results.
 //!
+//! On top of the fog that surrounds the camera, [`FogVolume`]s add localized
+//! regions of fog, such as ground fog or dusty rooms lit by light shafts. Their
+//! density can vary across the volume following a 3D texture, which can scroll
+//! through the volume to simulate wind. Each fog volume is raymarched in its own
+//! pass over the whole screen, blended on top of the result of the previous ones,
+//! so they should be used sparingly too.
+//!
 //! The overall algorithm, which is implemented as a postprocessing effect, is a
 //! combination of the techniques described in [Scratchapixel] and [this blog
 //! post]. It uses raymarching in screen space, transformed into shadow map
@@ -30,7 +37,7 @@
 //! [Henyey-Greenstein phase function]: https://www.pbr-book.org/4ed/Volume_Scattering/Phase_Functions#TheHenyeyndashGreensteinPhaseFunction
 
 use bevy_app::{App, Plugin};
-use bevy_asset::{load_internal_asset, Handle};
+use bevy_asset::{load_internal_asset, AssetId, Handle};
 use bevy_color::{Color, ColorToComponents};
 use bevy_core_pipeline::{
     core_3d::{
@@ -42,6 +49,7 @@ use bevy_core_pipeline::{
 };
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
+    bundle::Bundle,
     component::Component,
     entity::Entity,
     query::{Has, QueryItem, With},
@@ -50,26 +58,33 @@ use bevy_ecs::{
     system::{lifetimeless::Read, Commands, Query, Res, ResMut, Resource},
     world::{FromWorld, World},
 };
-use bevy_math::Vec3;
-use bevy_reflect::Reflect;
+use bevy_math::{Mat4, Vec3};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_render::{
+    render_asset::RenderAssets,
     render_graph::{NodeRunError, RenderGraphApp, RenderGraphContext, ViewNode, ViewNodeRunner},
     render_resource::{
         binding_types::{
-            sampler, texture_2d, texture_depth_2d, texture_depth_2d_multisampled, uniform_buffer,
+            sampler, texture_2d, texture_3d, texture_depth_2d, texture_depth_2d_multisampled,
+            uniform_buffer,
         },
-        BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, CachedRenderPipelineId,
-        ColorTargetState, ColorWrites, DynamicUniformBuffer, FilterMode, FragmentState,
-        MultisampleState, Operations, PipelineCache, PrimitiveState, RenderPassColorAttachment,
-        RenderPassDescriptor, RenderPipelineDescriptor, Sampler, SamplerBindingType,
-        SamplerDescriptor, Shader, ShaderStages, ShaderType, SpecializedRenderPipeline,
-        SpecializedRenderPipelines, TextureFormat, TextureSampleType, TextureUsages,
+        AddressMode, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, BlendComponent,
+        BlendFactor, BlendOperation, BlendState, CachedRenderPipelineId, ColorTargetState,
+        ColorWrites, DynamicUniformBuffer, FilterMode, FragmentState, MultisampleState, Operations,
+        PipelineCache, PrimitiveState, RenderPassColorAttachment, RenderPassDescriptor,
+        RenderPipelineDescriptor, Sampler, SamplerBindingType, SamplerDescriptor, Shader,
+        ShaderStages, ShaderType, SpecializedRenderPipeline, SpecializedRenderPipelines,
+        TextureFormat, TextureSampleType, TextureUsages,
     },
     renderer::{RenderContext, RenderDevice, RenderQueue},
-    texture::BevyDefault,
-    view::{ExtractedView, Msaa, ViewDepthTexture, ViewTarget, ViewUniformOffset},
+    texture::{BevyDefault, FallbackImage, GpuImage, Image},
+    view::{
+        ExtractedView, InheritedVisibility, Msaa, ViewDepthTexture, ViewTarget, ViewUniformOffset,
+        ViewVisibility, Visibility,
+    },
     Extract, ExtractSchedule, Render, RenderApp, RenderSet,
 };
+use bevy_transform::components::{GlobalTransform, Transform};
 use bevy_utils::prelude::default;
 
 use crate::{
@@ -187,6 +202,73 @@ pub struct VolumetricFogSettings {
     pub light_intensity: f32,
 }
 
+/// A localized region of fog, rendered on top of the fog of
+/// [`VolumetricFogSettings`] by the cameras that have it.
+///
+/// The volume is a cube with sides of length 1 or a sphere of diameter 1,
+/// centered on the entity and transformed by its [`GlobalTransform`]. The
+/// quality of the fog, its lighting and its absorption and scattering
+/// coefficients come from the [`VolumetricFogSettings`] of the camera.
+#[derive(Clone, Component, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct FogVolume {
+    /// The shape of the volume.
+    ///
+    /// Defaults to a box.
+    pub shape: FogVolumeShape,
+
+    /// The color of the fog.
+    ///
+    /// Defaults to white.
+    pub fog_color: Color,
+
+    /// The density of the fog.
+    ///
+    /// The default value is 1.0.
+    pub density: f32,
+
+    /// A 3D texture whose red channel scales the density of the fog across the
+    /// volume, mapped to the cube that contains the volume.
+    ///
+    /// The texture is repeated as it scrolls, so it should tile seamlessly.
+    /// The volume isn't rendered until the texture is loaded.
+    ///
+    /// Defaults to `None`, meaning that the density is uniform.
+    pub density_texture: Option<Handle<Image>>,
+
+    /// The speed at which the density texture scrolls through the volume, in
+    /// texture coordinates per second, which can be used to simulate wind.
+    ///
+    /// Defaults to zero.
+    pub density_texture_scroll: Vec3,
+}
+
+/// The shape of a [`FogVolume`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default)]
+pub enum FogVolumeShape {
+    /// A cube with sides of length 1.
+    #[default]
+    Box,
+    /// A sphere of diameter 1.
+    Sphere,
+}
+
+/// A component bundle for [`FogVolume`] entities.
+#[derive(Bundle, Clone, Debug, Default)]
+pub struct FogVolumeBundle {
+    pub fog_volume: FogVolume,
+    /// The position, rotation and size of the volume.
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+    /// Enables or disables the volume.
+    pub visibility: Visibility,
+    /// Inherited visibility of an entity.
+    pub inherited_visibility: InheritedVisibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub view_visibility: ViewVisibility,
+}
+
 /// The GPU pipeline for the volumetric fog postprocessing effect.
 #[derive(Resource)]
 pub struct VolumetricFogPipeline {
@@ -196,13 +278,24 @@ pub struct VolumetricFogPipeline {
     volumetric_view_bind_group_layout_no_msaa: BindGroupLayout,
     /// The view bind group when multisample antialiasing is in use.
     volumetric_view_bind_group_layout_msaa: BindGroupLayout,
+    /// The bind group of a fog volume when multisample antialiasing isn't in use.
+    fog_volume_bind_group_layout_no_msaa: BindGroupLayout,
+    /// The bind group of a fog volume when multisample antialiasing is in use.
+    fog_volume_bind_group_layout_msaa: BindGroupLayout,
     /// The sampler that we use to sample the postprocessing input.
     color_sampler: Sampler,
+    /// The sampler that we use to sample the density textures of fog volumes.
+    density_sampler: Sampler,
 }
 
 #[derive(Component, Deref, DerefMut)]
 pub struct ViewVolumetricFogPipeline(pub CachedRenderPipelineId);
 
+/// The pipeline that renders the [`FogVolume`]s for a view, only present when
+/// there are fog volumes.
+#[derive(Component, Deref, DerefMut)]
+pub struct ViewFogVolumePipeline(pub CachedRenderPipelineId);
+
 /// The node in the render graph, part of the postprocessing stack, that
 /// implements volumetric fog.
 #[derive(Default)]
@@ -215,6 +308,9 @@ pub struct VolumetricFogPipelineKey {
     mesh_pipeline_view_key: MeshPipelineViewLayoutKey,
     /// Whether the view has high dynamic range.
     hdr: bool,
+    /// Whether this pipeline renders a [`FogVolume`] instead of the fog of the
+    /// [`VolumetricFogSettings`].
+    fog_volume: bool,
 }
 
 /// The same as [`VolumetricFogSettings`], but formatted for the GPU.
@@ -242,6 +338,44 @@ pub struct ViewVolumetricFogUniformOffset(u32);
 #[derive(Resource, Default, Deref, DerefMut)]
 pub struct VolumetricFogUniformBuffer(pub DynamicUniformBuffer<VolumetricFogUniform>);
 
+/// The same as [`FogVolume`], but formatted for the GPU.
+#[derive(ShaderType)]
+pub struct FogVolumeUniform {
+    local_from_world: Mat4,
+    fog_color: Vec3,
+    density: f32,
+    density_texture_scroll: Vec3,
+    shape: u32,
+}
+
+impl FogVolumeUniform {
+    fn new(fog_volume: &FogVolume, transform: &GlobalTransform) -> Self {
+        Self {
+            local_from_world: transform.compute_matrix().inverse(),
+            fog_color: fog_volume.fog_color.to_linear().to_vec3(),
+            density: fog_volume.density,
+            density_texture_scroll: fog_volume.density_texture_scroll,
+            shape: fog_volume.shape as u32,
+        }
+    }
+}
+
+/// The GPU buffer that stores the [`FogVolumeUniform`] data.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct FogVolumeUniformBuffer(pub DynamicUniformBuffer<FogVolumeUniform>);
+
+/// A [`FogVolume`] ready to be rendered.
+pub struct PreparedFogVolume {
+    /// The offset of the [`FogVolumeUniform`] within the [`FogVolumeUniformBuffer`].
+    uniform_offset: u32,
+    /// The density texture of the fog volume, if any.
+    density_texture: Option<AssetId<Image>>,
+}
+
+/// The [`FogVolume`]s to render this frame.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct PreparedFogVolumes(pub Vec<PreparedFogVolume>);
+
 impl Plugin for VolumetricFogPlugin {
     fn build(&self, app: &mut App) {
         load_internal_asset!(
@@ -251,7 +385,9 @@ impl Plugin for VolumetricFogPlugin {
             Shader::from_wgsl
         );
         app.register_type::<VolumetricFogSettings>()
-            .register_type::<VolumetricLight>();
+            .register_type::<VolumetricLight>()
+            .register_type::<FogVolume>()
+            .register_type::<FogVolumeShape>();
 
         let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
             return;
@@ -260,12 +396,15 @@ impl Plugin for VolumetricFogPlugin {
         render_app
             .init_resource::<SpecializedRenderPipelines<VolumetricFogPipeline>>()
             .init_resource::<VolumetricFogUniformBuffer>()
+            .init_resource::<FogVolumeUniformBuffer>()
+            .init_resource::<PreparedFogVolumes>()
             .add_systems(ExtractSchedule, extract_volumetric_fog)
             .add_systems(
                 Render,
                 (
                     prepare_volumetric_fog_pipelines.in_set(RenderSet::Prepare),
                     prepare_volumetric_fog_uniforms.in_set(RenderSet::Prepare),
+                    prepare_fog_volumes.in_set(RenderSet::Prepare),
                     prepare_view_depth_textures_for_volumetric_fog
                         .in_set(RenderSet::Prepare)
                         .before(prepare_core_3d_depth_textures),
@@ -312,6 +451,18 @@ impl Default for VolumetricFogSettings {
     }
 }
 
+impl Default for FogVolume {
+    fn default() -> Self {
+        Self {
+            shape: FogVolumeShape::Box,
+            fog_color: Color::WHITE,
+            density: 1.0,
+            density_texture: None,
+            density_texture_scroll: Vec3::ZERO,
+        }
+    }
+}
+
 impl FromWorld for VolumetricFogPipeline {
     fn from_world(world: &mut World) -> Self {
         let render_device = world.resource::<RenderDevice>();
@@ -355,6 +506,48 @@ impl FromWorld for VolumetricFogPipeline {
             &bind_group_layout_entries_msaa,
         );
 
+        // Fog volumes are blended on top of the color target instead of
+        // sampling it, and need their own uniform and density texture.
+        let fog_volume_bind_group_layout_entries = BindGroupLayoutEntries::with_indices(
+            ShaderStages::FRAGMENT,
+            (
+                // `volumetric_fog`
+                (0, uniform_buffer::<VolumetricFogUniform>(true)),
+                // `fog_volume`
+                (4, uniform_buffer::<FogVolumeUniform>(true)),
+                // `density_texture`
+                (5, texture_3d(TextureSampleType::Float { filterable: true })),
+                // `density_sampler`
+                (6, sampler(SamplerBindingType::Filtering)),
+            ),
+        );
+
+        let mut fog_volume_bind_group_layout_entries_no_msaa =
+            fog_volume_bind_group_layout_entries.to_vec();
+        fog_volume_bind_group_layout_entries_no_msaa.extend_from_slice(
+            &BindGroupLayoutEntries::with_indices(
+                ShaderStages::FRAGMENT,
+                ((3, texture_depth_2d()),),
+            ),
+        );
+        let fog_volume_bind_group_layout_no_msaa = render_device.create_bind_group_layout(
+            "fog volume bind group layout",
+            &fog_volume_bind_group_layout_entries_no_msaa,
+        );
+
+        let mut fog_volume_bind_group_layout_entries_msaa =
+            fog_volume_bind_group_layout_entries.to_vec();
+        fog_volume_bind_group_layout_entries_msaa.extend_from_slice(
+            &BindGroupLayoutEntries::with_indices(
+                ShaderStages::FRAGMENT,
+                ((3, texture_depth_2d_multisampled()),),
+            ),
+        );
+        let fog_volume_bind_group_layout_msaa = render_device.create_bind_group_layout(
+            "fog volume bind group layout (multisampled)",
+            &fog_volume_bind_group_layout_entries_msaa,
+        );
+
         let color_sampler = render_device.create_sampler(&SamplerDescriptor {
             label: Some("volumetric lighting color sampler"),
             mag_filter: FilterMode::Linear,
@@ -363,21 +556,35 @@ impl FromWorld for VolumetricFogPipeline {
             ..default()
         });
 
+        let density_sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("fog volume density sampler"),
+            address_mode_u: AddressMode::Repeat,
+            address_mode_v: AddressMode::Repeat,
+            address_mode_w: AddressMode::Repeat,
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..default()
+        });
+
         VolumetricFogPipeline {
             mesh_view_layouts: mesh_view_layouts.clone(),
             volumetric_view_bind_group_layout_no_msaa,
             volumetric_view_bind_group_layout_msaa,
+            fog_volume_bind_group_layout_no_msaa,
+            fog_volume_bind_group_layout_msaa,
             color_sampler,
+            density_sampler,
         }
     }
 }
 
-/// Extracts [`VolumetricFogSettings`] and [`VolumetricLight`]s from the main
-/// world to the render world.
+/// Extracts [`VolumetricFogSettings`], [`VolumetricLight`]s and
+/// [`FogVolume`]s from the main world to the render world.
 pub fn extract_volumetric_fog(
     mut commands: Commands,
     view_targets: Extract<Query<(Entity, &VolumetricFogSettings)>>,
     volumetric_lights: Extract<Query<(Entity, &VolumetricLight)>>,
+    fog_volumes: Extract<Query<(Entity, &FogVolume, &GlobalTransform, &InheritedVisibility)>>,
 ) {
     if volumetric_lights.is_empty() {
         return;
@@ -392,6 +599,15 @@ pub fn extract_volumetric_fog(
     for (entity, volumetric_light) in volumetric_lights.iter() {
         commands.get_or_spawn(entity).insert(*volumetric_light);
     }
+
+    for (entity, fog_volume, transform, inherited_visibility) in fog_volumes.iter() {
+        if !inherited_visibility.get() {
+            continue;
+        }
+        commands
+            .get_or_spawn(entity)
+            .insert((fog_volume.clone(), *transform));
+    }
 }
 
 impl ViewNode for VolumetricFogNode {
@@ -406,6 +622,7 @@ impl ViewNode for VolumetricFogNode {
         Read<ViewVolumetricFogUniformOffset>,
         Read<MeshViewBindGroup>,
         Read<ViewScreenSpaceReflectionsUniformOffset>,
+        Option<Read<ViewFogVolumePipeline>>,
     );
 
     fn run<'w>(
@@ -423,6 +640,7 @@ impl ViewNode for VolumetricFogNode {
             view_volumetric_lighting_uniform_buffer_offset,
             view_bind_group,
             view_ssr_offset,
+            view_fog_volume_pipeline,
         ): QueryItem<'w, Self::ViewQuery>,
         world: &'w World,
     ) -> Result<(), NodeRunError> {
@@ -452,13 +670,51 @@ impl ViewNode for VolumetricFogNode {
             None,
             volumetric_view_bind_group_layout,
             &BindGroupEntries::sequential((
-                volumetric_lighting_uniform_buffer_binding,
+                volumetric_lighting_uniform_buffer_binding.clone(),
                 postprocess.source,
                 &volumetric_lighting_pipeline.color_sampler,
                 view_depth_texture.view(),
             )),
         );
 
+        // Create the bind groups of the fog volumes, skipping those whose
+        // density texture isn't loaded yet.
+        let fog_volume_pipeline = view_fog_volume_pipeline
+            .and_then(|pipeline| pipeline_cache.get_render_pipeline(**pipeline));
+        let mut fog_volume_bind_groups = Vec::new();
+        if let (Some(_), Some(fog_volume_uniform_buffer_binding)) = (
+            fog_volume_pipeline,
+            world.resource::<FogVolumeUniformBuffer>().binding(),
+        ) {
+            let fallback_image = world.resource::<FallbackImage>();
+            let gpu_images = world.resource::<RenderAssets<GpuImage>>();
+            let fog_volume_bind_group_layout = match *msaa {
+                Msaa::Off => &volumetric_lighting_pipeline.fog_volume_bind_group_layout_no_msaa,
+                _ => &volumetric_lighting_pipeline.fog_volume_bind_group_layout_msaa,
+            };
+            for fog_volume in world.resource::<PreparedFogVolumes>().iter() {
+                let density_texture_view = match fog_volume.density_texture {
+                    None => &fallback_image.d3.texture_view,
+                    Some(id) => match gpu_images.get(id) {
+                        Some(gpu_image) => &gpu_image.texture_view,
+                        None => continue,
+                    },
+                };
+                let bind_group = render_context.render_device().create_bind_group(
+                    None,
+                    fog_volume_bind_group_layout,
+                    &BindGroupEntries::with_indices((
+                        (0, volumetric_lighting_uniform_buffer_binding.clone()),
+                        (3, view_depth_texture.view()),
+                        (4, fog_volume_uniform_buffer_binding.clone()),
+                        (5, density_texture_view),
+                        (6, &volumetric_lighting_pipeline.density_sampler),
+                    )),
+                );
+                fog_volume_bind_groups.push((bind_group, fog_volume.uniform_offset));
+            }
+        }
+
         let render_pass_descriptor = RenderPassDescriptor {
             label: Some("volumetric lighting pass"),
             color_attachments: &[Some(RenderPassColorAttachment {
@@ -493,6 +749,55 @@ impl ViewNode for VolumetricFogNode {
             &[**view_volumetric_lighting_uniform_buffer_offset],
         );
         render_pass.draw(0..3, 0..1);
+        drop(render_pass);
+
+        let Some(fog_volume_pipeline) = fog_volume_pipeline else {
+            return Ok(());
+        };
+        if fog_volume_bind_groups.is_empty() {
+            return Ok(());
+        }
+
+        // Blend the fog volumes on top of the output of the pass above, which
+        // is now the main texture.
+        let mut render_pass =
+            render_context
+                .command_encoder()
+                .begin_render_pass(&RenderPassDescriptor {
+                    label: Some("fog volumes pass"),
+                    color_attachments: &[Some(RenderPassColorAttachment {
+                        view: view_target.main_texture_view(),
+                        resolve_target: None,
+                        ops: Operations::default(),
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+
+        render_pass.set_pipeline(fog_volume_pipeline);
+        render_pass.set_bind_group(
+            0,
+            &view_bind_group.value,
+            &[
+                view_uniform_offset.offset,
+                view_lights_offset.offset,
+                view_fog_offset.offset,
+                **view_light_probes_offset,
+                **view_ssr_offset,
+            ],
+        );
+        for (fog_volume_bind_group, fog_volume_uniform_offset) in &fog_volume_bind_groups {
+            render_pass.set_bind_group(
+                1,
+                fog_volume_bind_group,
+                &[
+                    **view_volumetric_lighting_uniform_buffer_offset,
+                    *fog_volume_uniform_offset,
+                ],
+            );
+            render_pass.draw(0..3, 0..1);
+        }
 
         Ok(())
     }
@@ -512,14 +817,37 @@ impl SpecializedRenderPipeline for VolumetricFogPipeline {
         let mut shader_defs = vec!["SHADOW_FILTER_METHOD_HARDWARE_2X2".into()];
 
         // We need a separate layout for MSAA and non-MSAA.
-        let volumetric_view_bind_group_layout = if key
+        let multisampled = key
             .mesh_pipeline_view_key
-            .contains(MeshPipelineViewLayoutKey::MULTISAMPLED)
-        {
+            .contains(MeshPipelineViewLayoutKey::MULTISAMPLED);
+        if multisampled {
             shader_defs.push("MULTISAMPLED".into());
-            self.volumetric_view_bind_group_layout_msaa.clone()
+        }
+        let volumetric_view_bind_group_layout = match (key.fog_volume, multisampled) {
+            (false, false) => self.volumetric_view_bind_group_layout_no_msaa.clone(),
+            (false, true) => self.volumetric_view_bind_group_layout_msaa.clone(),
+            (true, false) => self.fog_volume_bind_group_layout_no_msaa.clone(),
+            (true, true) => self.fog_volume_bind_group_layout_msaa.clone(),
+        };
+
+        // Fog volumes output the light they scatter toward the camera and their
+        // transmittance, which attenuates what's behind them.
+        let blend = if key.fog_volume {
+            shader_defs.push("FOG_VOLUME".into());
+            Some(BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::One,
+                    dst_factor: BlendFactor::SrcAlpha,
+                    operation: BlendOperation::Add,
+                },
+                alpha: BlendComponent {
+                    src_factor: BlendFactor::Zero,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+            })
         } else {
-            self.volumetric_view_bind_group_layout_no_msaa.clone()
+            None
         };
 
         RenderPipelineDescriptor {
@@ -540,7 +868,7 @@ impl SpecializedRenderPipeline for VolumetricFogPipeline {
                     } else {
                         TextureFormat::bevy_default()
                     },
-                    blend: None,
+                    blend,
                     write_mask: ColorWrites::ALL,
                 })],
             }),
@@ -565,6 +893,7 @@ pub fn prepare_volumetric_fog_pipelines(
         ),
         With<VolumetricFogSettings>,
     >,
+    fog_volumes: Query<(), With<FogVolume>>,
     msaa: Res<Msaa>,
 ) {
     for (entity, view, normal_prepass, depth_prepass, motion_vector_prepass, deferred_prepass) in
@@ -590,12 +919,29 @@ pub fn prepare_volumetric_fog_pipelines(
             VolumetricFogPipelineKey {
                 mesh_pipeline_view_key,
                 hdr: view.hdr,
+                fog_volume: false,
             },
         );
 
         commands
             .entity(entity)
             .insert(ViewVolumetricFogPipeline(pipeline_id));
+
+        if fog_volumes.is_empty() {
+            continue;
+        }
+        let fog_volume_pipeline_id = pipelines.specialize(
+            &pipeline_cache,
+            &volumetric_lighting_pipeline,
+            VolumetricFogPipelineKey {
+                mesh_pipeline_view_key,
+                hdr: view.hdr,
+                fog_volume: true,
+            },
+        );
+        commands
+            .entity(entity)
+            .insert(ViewFogVolumePipeline(fog_volume_pipeline_id));
     }
 }
 
@@ -636,6 +982,33 @@ pub fn prepare_volumetric_fog_uniforms(
     }
 }
 
+/// A system that writes the [`FogVolumeUniform`]s of all fog volumes.
+pub fn prepare_fog_volumes(
+    mut fog_volume_uniform_buffer: ResMut<FogVolumeUniformBuffer>,
+    mut prepared_fog_volumes: ResMut<PreparedFogVolumes>,
+    fog_volumes: Query<(&FogVolume, &GlobalTransform)>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    prepared_fog_volumes.clear();
+
+    let Some(mut writer) = fog_volume_uniform_buffer.get_writer(
+        fog_volumes.iter().len(),
+        &render_device,
+        &render_queue,
+    ) else {
+        return;
+    };
+
+    for (fog_volume, transform) in fog_volumes.iter() {
+        let uniform_offset = writer.write(&FogVolumeUniform::new(fog_volume, transform));
+        prepared_fog_volumes.push(PreparedFogVolume {
+            uniform_offset,
+            density_texture: fog_volume.density_texture.as_ref().map(Handle::id),
+        });
+    }
+}
+
 /// A system that marks all view depth textures as readable in shaders.
 ///
 /// The volumetric lighting pass needs to do this, and it doesn't happen by
@@ -647,3 +1020,75 @@ pub fn prepare_view_depth_textures_for_volumetric_fog(
         camera.depth_texture_usages.0 |= TextureUsages::TEXTURE_BINDING.bits();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_color::{Color, ColorToComponents, LinearRgba};
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+    use bevy_math::{Quat, Vec3};
+    use bevy_render::{view::InheritedVisibility, MainWorld};
+    use bevy_transform::components::{GlobalTransform, Transform};
+
+    use super::{
+        extract_volumetric_fog, FogVolume, FogVolumeShape, FogVolumeUniform, VolumetricLight,
+    };
+
+    #[test]
+    fn fog_volume_uniform() {
+        let fog_volume = FogVolume {
+            shape: FogVolumeShape::Sphere,
+            fog_color: Color::srgb(1.0, 0.5, 0.0),
+            density: 0.25,
+            density_texture_scroll: Vec3::X,
+            ..Default::default()
+        };
+        let transform = GlobalTransform::from(Transform {
+            translation: Vec3::new(1.0, 2.0, 3.0),
+            rotation: Quat::from_rotation_y(1.0),
+            scale: Vec3::new(2.0, 4.0, 8.0),
+        });
+        let uniform = FogVolumeUniform::new(&fog_volume, &transform);
+
+        // The corners of the volume are mapped to the corners of the unit cube.
+        let corner = transform.transform_point(Vec3::splat(0.5));
+        assert!(uniform
+            .local_from_world
+            .transform_point3(corner)
+            .abs_diff_eq(Vec3::splat(0.5), 1e-5));
+        assert_eq!(
+            uniform.fog_color,
+            LinearRgba::from(fog_volume.fog_color).to_vec3()
+        );
+        assert_eq!(uniform.density, 0.25);
+        assert_eq!(uniform.density_texture_scroll, Vec3::X);
+        // This must match FOG_VOLUME_SHAPE_SPHERE in volumetric_fog.wgsl
+        assert_eq!(uniform.shape, 1);
+    }
+
+    #[test]
+    fn extract_visible_fog_volumes() {
+        let mut main_world = MainWorld::default();
+        main_world.spawn(VolumetricLight);
+        let visible = main_world
+            .spawn((
+                FogVolume::default(),
+                GlobalTransform::default(),
+                InheritedVisibility::VISIBLE,
+            ))
+            .id();
+        let hidden = main_world
+            .spawn((
+                FogVolume::default(),
+                GlobalTransform::default(),
+                InheritedVisibility::HIDDEN,
+            ))
+            .id();
+
+        let mut render_world = World::new();
+        render_world.insert_resource(main_world);
+        render_world.run_system_once(extract_volumetric_fog);
+
+        assert!(render_world.get::<FogVolume>(visible).is_some());
+        assert!(render_world.get::<FogVolume>(hidden).is_none());
+    }
+}
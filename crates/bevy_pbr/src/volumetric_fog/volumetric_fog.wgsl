@@ -11,9 +11,14 @@
 // [1]: https://www.scratchapixel.com/lessons/3d-basic-rendering/volume-rendering-for-developers/intro-volume-rendering.html
 //
 // [2]: http://www.alexandre-pestana.com/volumetric-lights/
+//
+// When `FOG_VOLUME` is defined, this instead raymarches the part of the ray
+// that's within a single fog volume, and outputs the light that the volume
+// scatters toward the camera along with the fraction of the background that
+// shows through, to be blended on top of the output of the main pass.
 
 #import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
-#import bevy_pbr::mesh_view_bindings::{lights, view}
+#import bevy_pbr::mesh_view_bindings::{globals, lights, view}
 #import bevy_pbr::mesh_view_types::DIRECTIONAL_LIGHT_FLAGS_VOLUMETRIC_BIT
 #import bevy_pbr::shadow_sampling::sample_shadow_map_hardware
 #import bevy_pbr::shadows::{get_cascade_index, world_to_directional_light_local}
@@ -39,9 +44,28 @@ struct VolumetricFog {
     light_intensity: f32,
 }
 
+// The GPU version of [`FogVolume`].
+struct FogVolume {
+    local_from_world: mat4x4<f32>,
+    fog_color: vec3<f32>,
+    density: f32,
+    density_texture_scroll: vec3<f32>,
+    shape: u32,
+}
+
+// NOTE: These must match the variants of `FogVolumeShape`.
+const FOG_VOLUME_SHAPE_BOX: u32 = 0u;
+const FOG_VOLUME_SHAPE_SPHERE: u32 = 1u;
+
 @group(1) @binding(0) var<uniform> volumetric_fog: VolumetricFog;
+#ifdef FOG_VOLUME
+@group(1) @binding(4) var<uniform> fog_volume: FogVolume;
+@group(1) @binding(5) var density_texture: texture_3d<f32>;
+@group(1) @binding(6) var density_sampler: sampler;
+#else
 @group(1) @binding(1) var color_texture: texture_2d<f32>;
 @group(1) @binding(2) var color_sampler: sampler;
+#endif
 
 #ifdef MULTISAMPLED
 @group(1) @binding(3) var depth_texture: texture_depth_multisampled_2d;
@@ -67,6 +91,39 @@ fn henyey_greenstein(neg_LdotV: f32) -> f32 {
     return FRAC_4_PI * (1.0 - g * g) / (denom * sqrt(denom));
 }
 
+// Returns the fraction of the directional light with the given index that
+// reaches `P_world`, whose depth in view space is `P_view_z`, according to its
+// shadow map.
+fn directional_light_visibility(light_index: u32, P_world: vec3<f32>, P_view_z: f32) -> f32 {
+    let light = &lights.directional_lights[light_index];
+
+    // Prepare to sample the shadow map.
+    let cascade_index = get_cascade_index(light_index, P_view_z);
+    // Offset the depth value by the bias of the cascade.
+    let depth_offset =
+        (*light).cascades[cascade_index].depth_bias * (*light).direction_to_light.xyz;
+    let light_local = world_to_directional_light_local(
+        light_index,
+        cascade_index,
+        vec4(P_world + depth_offset, 1.0)
+    );
+
+    // If we're outside the shadow map entirely, local light attenuation
+    // is zero.
+    var local_light_attenuation = f32(light_local.w != 0.0);
+
+    // Otherwise, sample the shadow map to determine whether, and by how
+    // much, this sample is in the light.
+    if (local_light_attenuation != 0.0) {
+        let array_index = i32((*light).depth_texture_base_index + cascade_index);
+        local_light_attenuation =
+            sample_shadow_map_hardware(light_local.xy, light_local.z, array_index);
+    }
+
+    return local_light_attenuation;
+}
+
+#ifndef FOG_VOLUME
 @fragment
 fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
     // Unpack the `volumetric_fog` settings.
@@ -150,9 +207,6 @@ fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
             break;
         }
 
-        // Offset the depth value by the bias.
-        let depth_offset = (*light).shadow_depth_bias * (*light).direction_to_light.xyz;
-
         // Compute phase, which determines the fraction of light that's
         // scattered toward the camera instead of away from it.
         let neg_LdotV = dot(normalize((*light).direction_to_light.xyz), Rd_world);
@@ -182,27 +236,8 @@ fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
             // Compute in-scattering (amount of light other fog particles
             // scattered into this ray). This is where any directional light is
             // scattered in.
-
-            // Prepare to sample the shadow map.
-            let cascade_index = get_cascade_index(light_index, P_view.z);
-            let light_local = world_to_directional_light_local(
-                light_index,
-                cascade_index,
-                vec4(P_world + depth_offset, 1.0)
-            );
-
-            // If we're outside the shadow map entirely, local light attenuation
-            // is zero.
-            var local_light_attenuation = f32(light_local.w != 0.0);
-
-            // Otherwise, sample the shadow map to determine whether, and by how
-            // much, this sample is in the light.
-            if (local_light_attenuation != 0.0) {
-                let cascade = &(*light).cascades[cascade_index];
-                let array_index = i32((*light).depth_texture_base_index + cascade_index);
-                local_light_attenuation =
-                    sample_shadow_map_hardware(light_local.xy, light_local.z, array_index);
-            }
+            let local_light_attenuation =
+                directional_light_visibility(light_index, P_world, P_view.z);
 
             if (local_light_attenuation != 0.0) {
                 // Accumulate the light.
@@ -216,3 +251,132 @@ fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
     let source = textureSample(color_texture, color_sampler, in.uv);
     return vec4(source.rgb * background_alpha + accumulated_color, source.a);
 }
+#else
+
+// Returns the distances along the ray at which it enters and exits the fog
+// volume, the ray being given in the local space of the volume. The exit
+// distance is smaller than the entry distance if the ray misses the volume.
+fn intersect_fog_volume(Ro_local: vec3<f32>, Rd_local: vec3<f32>) -> vec2<f32> {
+    if (fog_volume.shape == FOG_VOLUME_SHAPE_SPHERE) {
+        // Solve |Ro + t * Rd|² = 0.5² for t.
+        let a = dot(Rd_local, Rd_local);
+        let b = dot(Ro_local, Rd_local);
+        let c = dot(Ro_local, Ro_local) - 0.25;
+        let discriminant = b * b - a * c;
+        if (discriminant < 0.0) {
+            return vec2(1.0, 0.0);
+        }
+        return (vec2(-b) + vec2(-1.0, 1.0) * sqrt(discriminant)) / a;
+    }
+
+    // Intersect the slabs of the box, avoiding divisions by zero for rays that
+    // are parallel to them.
+    let Rd_safe = select(Rd_local, vec3(1.0e-6), abs(Rd_local) < vec3(1.0e-6));
+    let t0 = (vec3(-0.5) - Ro_local) / Rd_safe;
+    let t1 = (vec3(0.5) - Ro_local) / Rd_safe;
+    let t_min = min(t0, t1);
+    let t_max = max(t0, t1);
+    return vec2(
+        max(max(t_min.x, t_min.y), t_min.z),
+        min(min(t_max.x, t_max.y), t_max.z)
+    );
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    // Unpack the `volumetric_fog` settings.
+    let ambient_color = volumetric_fog.ambient_color;
+    let ambient_intensity = volumetric_fog.ambient_intensity;
+    let step_count = volumetric_fog.step_count;
+    let max_depth = volumetric_fog.max_depth;
+    let absorption = volumetric_fog.absorption;
+    let scattering = volumetric_fog.scattering;
+    let light_tint = volumetric_fog.light_tint;
+    let light_intensity = volumetric_fog.light_intensity;
+
+    let exposure = view.exposure;
+
+    // Sample the depth. If this is multisample, just use sample 0; this is
+    // approximate but good enough.
+    let frag_coord = in.position;
+    let depth = textureLoad(depth_texture, vec2<i32>(frag_coord.xy), 0);
+
+    // Calculate the ray origin (`Ro`) and the ray direction (`Rd`) in view,
+    // world, and local coordinates. The local direction isn't normalized, so
+    // that distances along the ray are the same in all spaces.
+    let Rd_ndc = vec3(frag_coord_to_ndc(in.position).xy, 1.0);
+    let Rd_view = normalize(position_ndc_to_view(Rd_ndc));
+    let Ro_world = view.world_position;
+    let Rd_world = normalize(position_ndc_to_world(Rd_ndc) - Ro_world);
+    let Ro_local = (fog_volume.local_from_world * vec4(Ro_world, 1.0)).xyz;
+    let Rd_local = (fog_volume.local_from_world * vec4(Rd_world, 0.0)).xyz;
+
+    // Only march the part of the ray that's within the volume and in front of
+    // the first opaque surface.
+    let surface_distance = min(
+        max_depth,
+        position_ndc_to_view(frag_coord_to_ndc(vec4(in.position.xy, depth, 1.0))).z / Rd_view.z
+    );
+    let volume_distances = intersect_fog_volume(Ro_local, Rd_local);
+    let start_distance = max(volume_distances.x, 0.0);
+    let end_distance = min(volume_distances.y, surface_distance);
+    if (start_distance >= end_distance) {
+        return vec4(0.0, 0.0, 0.0, 1.0);
+    }
+    let step_size = (end_distance - start_distance) / f32(step_count);
+
+    let density_texture_offset = fract(fog_volume.density_texture_scroll * globals.time);
+    let ambient_light = ambient_color * ambient_intensity;
+
+    var accumulated_color = vec3(0.0);
+    var background_alpha = 1.0;
+
+    for (var step = 0u; step < step_count; step += 1u) {
+        // As an optimization, break if we've gotten too dark.
+        if (background_alpha < 0.001) {
+            break;
+        }
+
+        // Calculate where we are in the ray, sampling the middle of each step.
+        let distance = start_distance + (f32(step) + 0.5) * step_size;
+        let P_world = Ro_world + Rd_world * distance;
+        let P_local = Ro_local + Rd_local * distance;
+
+        // The density texture covers the cube that contains the volume.
+        let density = fog_volume.density * textureSampleLevel(
+            density_texture,
+            density_sampler,
+            P_local + 0.5 + density_texture_offset,
+            0.0
+        ).r;
+        if (density <= 0.0) {
+            continue;
+        }
+
+        // Process absorption and out-scattering.
+        background_alpha *= exp(-step_size * density * (absorption + scattering));
+
+        // Compute in-scattering from the ambient light and from the volumetric
+        // directional lights, which are all sorted first.
+        var in_scattered_light = ambient_light;
+        for (var light_index = 0u; light_index < lights.n_directional_lights; light_index += 1u) {
+            let light = &lights.directional_lights[light_index];
+            if (((*light).flags & DIRECTIONAL_LIGHT_FLAGS_VOLUMETRIC_BIT) == 0) {
+                break;
+            }
+
+            let neg_LdotV = dot(normalize((*light).direction_to_light.xyz), Rd_world);
+            let local_light_attenuation =
+                directional_light_visibility(light_index, P_world, Rd_view.z * distance);
+            in_scattered_light += (*light).color.rgb * light_tint * light_intensity * exposure *
+                henyey_greenstein(neg_LdotV) * local_light_attenuation;
+        }
+
+        accumulated_color += in_scattered_light * fog_volume.fog_color * scattering * density *
+            step_size * background_alpha;
+    }
+
+    return vec4(accumulated_color, background_alpha);
+}
+
+#endif  // FOG_VOLUME
@@ -0,0 +1,111 @@
+//! A material extension that sways meshes in the wind, for foliage.
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Asset, Handle};
+use bevy_math::Vec3;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    render_asset::RenderAssets,
+    render_resource::{AsBindGroup, AsBindGroupShaderType, Shader, ShaderRef, ShaderType},
+    texture::GpuImage,
+};
+
+use crate::{ExtendedMaterial, MaterialExtension, MaterialPlugin, StandardMaterial};
+
+const WIND_SWAY_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(5162497347756021582);
+
+/// Adds the [`WindSwayMaterial`] to the app, and loads the shader needed to use
+/// [`WindSway`] with other base materials.
+pub struct WindSwayPlugin;
+
+/// A [`StandardMaterial`] that sways in the wind.
+pub type WindSwayMaterial = ExtendedMaterial<StandardMaterial, WindSway>;
+
+/// A [`MaterialExtension`] that sways meshes in the wind, such as grass, leaves
+/// or bushes, by displacing their vertices.
+///
+/// The displacement grows from zero at the base of the mesh, its local origin,
+/// to [`strength`](Self::strength) at [`height`](Self::height) above it, so
+/// models should be authored with the base at `y = 0`. Each instance sways with
+/// a phase based on its position, so that neighboring plants don't move in unison.
+///
+/// Shadows, the prepasses and motion vectors see the same displacement, so
+/// this works with temporal antialiasing and motion blur.
+///
+/// This is also an example of a vertex stage extension: its shader computes the
+/// displacement and passes it to `bevy_pbr::mesh_vertex::process_vertex`, see
+/// [`MaterialExtension::vertex_shader`]. Its uniform is at binding 90 of the
+/// material bind group.
+#[derive(Asset, AsBindGroup, Reflect, Debug, Clone)]
+#[uniform(90, WindSwayUniform)]
+#[reflect(Default, Debug)]
+pub struct WindSway {
+    /// The direction the wind blows toward, in world space.
+    pub direction: Vec3,
+    /// How far the top of the mesh moves, in world units.
+    pub strength: f32,
+    /// How many times the mesh sways back and forth per second.
+    pub frequency: f32,
+    /// The height above the local origin of the mesh at which the displacement
+    /// reaches [`strength`](Self::strength), in local units.
+    pub height: f32,
+}
+
+impl Default for WindSway {
+    fn default() -> Self {
+        Self {
+            direction: Vec3::X,
+            strength: 0.1,
+            frequency: 0.5,
+            height: 1.0,
+        }
+    }
+}
+
+/// The GPU representation of the uniform data of a [`WindSway`].
+#[derive(Clone, Default, ShaderType)]
+pub struct WindSwayUniform {
+    direction: Vec3,
+    strength: f32,
+    frequency: f32,
+    height: f32,
+}
+
+impl AsBindGroupShaderType<WindSwayUniform> for WindSway {
+    fn as_bind_group_shader_type(&self, _images: &RenderAssets<GpuImage>) -> WindSwayUniform {
+        WindSwayUniform {
+            direction: self.direction.normalize_or_zero(),
+            strength: self.strength,
+            frequency: self.frequency,
+            height: self.height,
+        }
+    }
+}
+
+impl MaterialExtension for WindSway {
+    fn vertex_shader() -> ShaderRef {
+        WIND_SWAY_SHADER_HANDLE.into()
+    }
+
+    fn prepass_vertex_shader() -> ShaderRef {
+        WIND_SWAY_SHADER_HANDLE.into()
+    }
+
+    fn deferred_vertex_shader() -> ShaderRef {
+        WIND_SWAY_SHADER_HANDLE.into()
+    }
+}
+
+impl Plugin for WindSwayPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            WIND_SWAY_SHADER_HANDLE,
+            "wind_sway.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<WindSway>()
+            .add_plugins(MaterialPlugin::<WindSwayMaterial>::default());
+    }
+}
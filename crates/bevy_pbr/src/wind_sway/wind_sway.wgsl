@@ -0,0 +1,57 @@
+// A vertex shader that sways meshes in the wind, see `bevy_pbr::wind_sway::WindSway`.
+
+#import bevy_pbr::mesh_functions
+#import bevy_render::maths::PI_2
+
+#ifdef PREPASS_PIPELINE
+#import bevy_pbr::{
+    prepass_bindings::globals,
+    prepass_io::{Vertex, VertexOutput},
+    prepass_vertex::process_vertex,
+}
+#else
+#import bevy_pbr::{
+    forward_io::{Vertex, VertexOutput},
+    mesh_view_bindings::globals,
+    mesh_vertex::process_vertex,
+}
+#endif
+
+struct WindSway {
+    direction: vec3<f32>,
+    strength: f32,
+    frequency: f32,
+    height: f32,
+}
+
+@group(2) @binding(90) var<uniform> wind_sway: WindSway;
+
+// Returns the world space offset of a vertex at the local `position` of a mesh
+// with the given transform, at the given time.
+fn sway_offset(position: vec3<f32>, world_from_local: mat4x4<f32>, time: f32) -> vec3<f32> {
+    // Each instance gets a phase from its position, so that neighboring plants
+    // don't sway in unison.
+    let origin = world_from_local[3].xyz;
+    let t = time * wind_sway.frequency * PI_2 + dot(origin.xz, vec2(0.37, 0.61));
+    // A second, faster wave breaks up the regularity of the motion like gusts do.
+    let sway = sin(t) * 0.75 + sin(t * 2.7 + 1.3) * 0.25;
+    // The base of the mesh stays in place and the top sways the most.
+    let weight = saturate(position.y / max(wind_sway.height, 1e-4));
+    return wind_sway.direction * (wind_sway.strength * sway * weight * weight);
+}
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var displacement = mesh_functions::no_vertex_displacement();
+    displacement.world_offset = sway_offset(
+        vertex.position,
+        mesh_functions::get_world_from_local(vertex.instance_index),
+        globals.time
+    );
+    displacement.previous_world_offset = sway_offset(
+        vertex.position,
+        mesh_functions::get_previous_world_from_local(vertex.instance_index),
+        globals.time - globals.delta_time
+    );
+    return process_vertex(vertex, displacement);
+}
@@ -1,7 +1,8 @@
+use crate as bevy_reflect;
 use crate::Reflect;
 use bevy_utils::TypeIdMap;
 use core::fmt::{Debug, Formatter};
-use std::any::TypeId;
+use std::{any::TypeId, sync::Arc};
 
 /// A collection of custom attributes for a type, field, or variant.
 ///
@@ -173,6 +174,107 @@ macro_rules! impl_custom_attribute_methods {
 
 pub(crate) use impl_custom_attribute_methods;
 
+/// A name to show for a type or field in editors and inspectors, instead of its identifier.
+///
+/// This is one of the custom attributes understood by Bevy's tooling, along with [`Tooltip`],
+/// [`NumericRange`] and [`DefaultValue`]:
+///
+/// ```
+/// # use bevy_reflect::{Reflect, Typed, TypeInfo};
+/// use bevy_reflect::attributes::{DefaultValue, DisplayName, NumericRange, Tooltip};
+///
+/// #[derive(Reflect)]
+/// struct Light {
+///     #[reflect(@DisplayName("Intensity (lumens)"))]
+///     #[reflect(@Tooltip("How bright the light is"))]
+///     #[reflect(@NumericRange::new(0.0, 10_000.0).with_step(10.0))]
+///     #[reflect(@DefaultValue::new(800.0_f32))]
+///     intensity: f32,
+/// }
+///
+/// let TypeInfo::Struct(info) = <Light as Typed>::type_info() else {
+///     panic!("expected struct info");
+/// };
+///
+/// let intensity = info.field("intensity").unwrap();
+/// assert_eq!(intensity.get_attribute::<DisplayName>().unwrap().0, "Intensity (lumens)");
+/// assert!(!intensity.get_attribute::<NumericRange>().unwrap().contains(-1.0));
+/// let default = intensity.get_attribute::<DefaultValue>().unwrap();
+/// assert_eq!(default.value().downcast_ref::<f32>(), Some(&800.0));
+/// ```
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Debug, PartialEq)]
+pub struct DisplayName(pub &'static str);
+
+/// A short description of a type or field, shown by editors and inspectors on hover.
+///
+/// See [`DisplayName`] for an example.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Debug, PartialEq)]
+pub struct Tooltip(pub &'static str);
+
+/// The values a numeric field accepts, and the increment editors use to change it.
+///
+/// Tooling like the dev console rejects values outside of the range, see [`DisplayName`] for an
+/// example.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Debug, PartialEq)]
+pub struct NumericRange {
+    /// The smallest accepted value.
+    pub min: f64,
+    /// The largest accepted value.
+    pub max: f64,
+    /// The increment used by sliders and drag values, if the field has a preferred one.
+    pub step: Option<f64>,
+}
+
+impl NumericRange {
+    /// Creates a range accepting the values from `min` to `max`, both included.
+    pub const fn new(min: f64, max: f64) -> Self {
+        Self {
+            min,
+            max,
+            step: None,
+        }
+    }
+
+    /// Sets the [`step`](Self::step) of the range.
+    pub const fn with_step(mut self, step: f64) -> Self {
+        self.step = Some(step);
+        self
+    }
+
+    /// Returns `true` if `value` is within the range.
+    pub fn contains(&self, value: f64) -> bool {
+        (self.min..=self.max).contains(&value)
+    }
+}
+
+/// The value editors reset a field to, which can differ from the [`Default`] of its type.
+///
+/// See [`DisplayName`] for an example.
+#[derive(Reflect, Clone)]
+#[reflect_value(Debug)]
+pub struct DefaultValue(Arc<dyn Reflect>);
+
+impl DefaultValue {
+    /// Creates the attribute from the default value of the field.
+    pub fn new<T: Reflect>(value: T) -> Self {
+        Self(Arc::new(value))
+    }
+
+    /// The default value of the field.
+    pub fn value(&self) -> &dyn Reflect {
+        &*self.0
+    }
+}
+
+impl Debug for DefaultValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("DefaultValue").field(&self.0).finish()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4,11 +4,14 @@ use crate::{
     prelude::Image,
     primitives::Frustum,
     render_asset::RenderAssets,
-    render_graph::{InternedRenderSubGraph, RenderSubGraph},
+    render_graph::{InternedRenderSubGraph, RenderGraph, RenderSubGraph},
     render_resource::TextureView,
+    renderer::RenderAdapter,
+    texture::BevyDefault,
     texture::GpuImage,
     view::{
-        ColorGrading, ExtractedView, ExtractedWindows, GpuCulling, RenderLayers, VisibleEntities,
+        ColorGrading, ExtractedView, ExtractedWindows, GpuCulling, Msaa, RenderLayers, ViewTarget,
+        VisibleEntities,
     },
     Extract,
 };
@@ -17,27 +20,32 @@ use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
     change_detection::DetectChanges,
     component::Component,
-    entity::Entity,
+    entity::{Entity, EntityHashMap},
     event::EventReader,
     prelude::With,
     query::Has,
     reflect::ReflectComponent,
-    system::{Commands, Query, Res, ResMut, Resource},
+    removal_detection::RemovedComponents,
+    system::{Commands, Local, Query, Res, ResMut, Resource},
+    world::Ref,
 };
 use bevy_math::{vec2, Dir3, Mat4, Ray3d, Rect, URect, UVec2, UVec4, Vec2, Vec3};
 use bevy_reflect::prelude::*;
 use bevy_render_macros::ExtractComponent;
 use bevy_transform::components::GlobalTransform;
-use bevy_utils::{tracing::warn, warn_once};
+use bevy_utils::{
+    tracing::{error, warn},
+    warn_once,
+};
 use bevy_utils::{HashMap, HashSet};
 use bevy_window::{
-    NormalizedWindowRef, PrimaryWindow, Window, WindowCreated, WindowRef, WindowResized,
-    WindowScaleFactorChanged,
+    HdrRequirement, NormalizedWindowRef, PrimaryWindow, Window, WindowCreated, WindowRef,
+    WindowResized, WindowScaleFactorChanged,
 };
 use std::ops::Range;
 use wgpu::{BlendState, TextureFormat, TextureUsages};
 
-use super::{ClearColorConfig, Projection};
+use super::{ClearColorConfig, ExtractedPixelPerfect, PixelPerfect, Projection};
 
 /// Render viewport configuration for the [`Camera`] component.
 ///
@@ -510,7 +518,39 @@ impl Default for CameraOutputMode {
 }
 
 /// Configures the [`RenderGraph`](crate::render_graph::RenderGraph) name assigned to be run for a given [`Camera`] entity.
-#[derive(Component, Deref, DerefMut, Reflect, Clone)]
+///
+/// The graph can be changed at runtime, for example to render cutscenes with a cheaper graph than
+/// the gameplay, the render world resources of the camera are created for the new graph from the
+/// next frame on:
+///
+/// ```ignore
+/// # TODO: Remove when #10645 is fixed
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_render::{camera::CameraRenderGraph, render_graph::RenderSubGraph};
+/// #[derive(RenderSubGraph, Debug, Clone, PartialEq, Eq, Hash)]
+/// struct GameplayGraph;
+///
+/// #[derive(RenderSubGraph, Debug, Clone, PartialEq, Eq, Hash)]
+/// struct CutsceneGraph;
+///
+/// #[derive(Resource)]
+/// struct InCutscene(bool);
+///
+/// fn switch_graph(in_cutscene: Res<InCutscene>, mut graphs: Query<&mut CameraRenderGraph>) {
+///     for mut graph in &mut graphs {
+///         if in_cutscene.0 {
+///             graph.set(CutsceneGraph);
+///         } else {
+///             graph.set(GameplayGraph);
+///         }
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(switch_graph);
+/// ```
+///
+/// Cameras whose graph isn't a sub graph of the render graph aren't extracted to the render world,
+/// so they don't render anything until the graph is added.
+#[derive(Component, Deref, DerefMut, Reflect, Clone, Debug, PartialEq, Eq)]
 #[reflect_value(Component)]
 pub struct CameraRenderGraph(InternedRenderSubGraph);
 
@@ -526,6 +566,12 @@ impl CameraRenderGraph {
     pub fn set<T: RenderSubGraph>(&mut self, name: T) {
         self.0 = name.intern();
     }
+
+    /// Returns the graph name.
+    #[inline]
+    pub fn get(&self) -> InternedRenderSubGraph {
+        self.0
+    }
 }
 
 /// The "target" that a [`Camera`] will render to. For example, this could be a [`Window`]
@@ -706,9 +752,11 @@ pub fn camera_system<T: CameraProjection + Component>(
     windows: Query<(Entity, &Window)>,
     images: Res<Assets<Image>>,
     manual_texture_views: Res<ManualTextureViews>,
-    mut cameras: Query<(&mut Camera, &mut T)>,
+    mut removed_pixel_perfect: RemovedComponents<PixelPerfect>,
+    mut cameras: Query<(Entity, &mut Camera, &mut T, Option<Ref<PixelPerfect>>)>,
 ) {
     let primary_window = primary_window.iter().next();
+    let removed_pixel_perfect: HashSet<Entity> = removed_pixel_perfect.read().collect();
 
     let mut changed_window_ids = HashSet::new();
     changed_window_ids.extend(window_created_events.read().map(|event| event.window));
@@ -727,7 +775,7 @@ pub fn camera_system<T: CameraProjection + Component>(
         })
         .collect();
 
-    for (mut camera, mut camera_projection) in &mut cameras {
+    for (entity, mut camera, mut camera_projection, pixel_perfect) in &mut cameras {
         let mut viewport_size = camera
             .viewport
             .as_ref()
@@ -738,6 +786,10 @@ pub fn camera_system<T: CameraProjection + Component>(
                 || camera.is_added()
                 || camera_projection.is_changed()
                 || camera.computed.old_viewport_size != viewport_size
+                || pixel_perfect
+                    .as_ref()
+                    .is_some_and(DetectChanges::is_changed)
+                || removed_pixel_perfect.contains(&entity)
             {
                 let new_computed_target_info = normalized_target.get_render_target_info(
                     &windows,
@@ -783,7 +835,12 @@ pub fn camera_system<T: CameraProjection + Component>(
                     }
                 }
                 camera.computed.target_info = new_computed_target_info;
-                if let Some(size) = camera.logical_viewport_size() {
+                // A pixel perfect camera renders at its own resolution, whatever its viewport.
+                let size = match &pixel_perfect {
+                    Some(pixel_perfect) => Some(pixel_perfect.resolution.as_vec2()),
+                    None => camera.logical_viewport_size(),
+                };
+                if let Some(size) = size {
                     camera_projection.update(size.x, size.y);
                     camera.computed.clip_from_view = camera_projection.get_clip_from_view();
                 }
@@ -842,10 +899,12 @@ pub fn extract_cameras(
             Option<&RenderLayers>,
             Option<&Projection>,
             Has<GpuCulling>,
+            Option<&PixelPerfect>,
         )>,
     >,
     primary_window: Extract<Query<Entity, With<PrimaryWindow>>>,
     gpu_preprocessing_support: Res<GpuPreprocessingSupport>,
+    render_graph: Res<RenderGraph>,
 ) {
     let primary_window = primary_window.iter().next();
     for (
@@ -861,6 +920,7 @@ pub fn extract_cameras(
         render_layers,
         projection,
         gpu_culling,
+        pixel_perfect,
     ) in query.iter()
     {
         let color_grading = color_grading.unwrap_or(&ColorGrading::default()).clone();
@@ -869,14 +929,15 @@ pub fn extract_cameras(
             continue;
         }
 
-        if let (
-            Some(URect {
-                min: viewport_origin,
-                ..
-            }),
-            Some(viewport_size),
-            Some(target_size),
-        ) = (
+        if render_graph.get_sub_graph(**camera_render_graph).is_none() {
+            warn_once!(
+                "Camera {entity:?} uses the render graph {:?}, which isn't a sub graph of the render graph. The camera isn't rendered.",
+                **camera_render_graph
+            );
+            continue;
+        }
+
+        if let (Some(viewport_rect), Some(viewport_size), Some(target_size)) = (
             camera.physical_viewport_rect(),
             camera.physical_viewport_size(),
             camera.physical_target_size(),
@@ -887,10 +948,35 @@ pub fn extract_cameras(
 
             let mut commands = commands.get_or_spawn(entity);
 
+            let mut viewport_origin = viewport_rect.min;
+            let mut viewport_size = viewport_size;
+            let mut target_size = target_size;
+            let mut viewport = camera.viewport.clone();
+            let mut world_from_view = *transform;
+            if let Some(pixel_perfect) = pixel_perfect {
+                // The camera renders to a texture of its resolution, which is scaled up to its
+                // viewport by the upscaling pass.
+                viewport_origin = UVec2::ZERO;
+                viewport_size = pixel_perfect.resolution.max(UVec2::ONE);
+                target_size = viewport_size;
+                viewport = None;
+                if pixel_perfect.snap {
+                    let mut affine = transform.affine();
+                    affine.translation = pixel_perfect
+                        .snap_camera_translation(camera, transform.translation())
+                        .into();
+                    world_from_view = affine.into();
+                }
+                commands.insert(ExtractedPixelPerfect {
+                    output_rect: pixel_perfect.output_rect(viewport_rect),
+                    letterbox: pixel_perfect.letterbox,
+                });
+            }
+
             commands.insert((
                 ExtractedCamera {
                     target: camera.target.normalize(primary_window),
-                    viewport: camera.viewport.clone(),
+                    viewport,
                     physical_viewport_size: Some(viewport_size),
                     physical_target_size: Some(target_size),
                     render_graph: camera_render_graph.0,
@@ -907,7 +993,7 @@ pub fn extract_cameras(
                 },
                 ExtractedView {
                     clip_from_view: camera.clip_from_view(),
-                    world_from_view: *transform,
+                    world_from_view,
                     clip_from_world: None,
                     hdr: camera.hdr,
                     viewport: UVec4::new(
@@ -1011,6 +1097,102 @@ pub fn sort_cameras(
     }
 }
 
+/// Checks that the active cameras are compatible with their render targets,
+/// and logs an error when one isn't, once until its problems change.
+///
+/// This covers the [`WindowRenderRequirements`](bevy_window::WindowRenderRequirements)
+/// of windows, whether the device supports the [`Msaa`] sample count for the
+/// main texture of the camera, and non-HDR cameras rendering to floating point
+/// images, which lose the precision of the image.
+pub fn validate_camera_targets(
+    cameras: Query<(Entity, &ExtractedCamera)>,
+    windows: Res<ExtractedWindows>,
+    images: Res<RenderAssets<GpuImage>>,
+    msaa: Res<Msaa>,
+    render_adapter: Res<RenderAdapter>,
+    mut reported_problems: Local<EntityHashMap<Vec<String>>>,
+) {
+    reported_problems.retain(|entity, _| cameras.contains(*entity));
+
+    for (entity, camera) in &cameras {
+        let mut problems = Vec::new();
+
+        let main_texture_format = if camera.hdr {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+        if !render_adapter
+            .get_texture_format_features(main_texture_format)
+            .flags
+            .sample_count_supported(msaa.samples())
+        {
+            problems.push(format!(
+                "the device doesn't support {} MSAA samples for the {} main texture \
+                ({main_texture_format:?})",
+                msaa.samples(),
+                if camera.hdr { "HDR" } else { "non-HDR" },
+            ));
+        }
+
+        match &camera.target {
+            Some(NormalizedRenderTarget::Window(window_ref)) => {
+                if let Some(window) = windows.get(&window_ref.entity()) {
+                    let requirements = window.render_requirements;
+                    if let Some(sample_count) = requirements.sample_count {
+                        if sample_count != msaa.samples() {
+                            problems.push(format!(
+                                "its window requires {sample_count} MSAA samples, but `Msaa` is \
+                                set to {} samples",
+                                msaa.samples()
+                            ));
+                        }
+                    }
+                    match (requirements.hdr, camera.hdr) {
+                        (HdrRequirement::Required, false) => problems
+                            .push("its window requires HDR, but `Camera::hdr` is false".into()),
+                        (HdrRequirement::Forbidden, true) => problems
+                            .push("its window forbids HDR, but `Camera::hdr` is true".into()),
+                        _ => {}
+                    }
+                }
+            }
+            Some(NormalizedRenderTarget::Image(image)) => {
+                if let Some(image) = images.get(image) {
+                    if !camera.hdr && is_float_format(image.texture_format) {
+                        problems.push(format!(
+                            "it renders to a {:?} image without `Camera::hdr`, so the \
+                            image only gets 8 bits of precision per channel",
+                            image.texture_format
+                        ));
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        if reported_problems.get(&entity) != Some(&problems) {
+            for problem in &problems {
+                error!("Camera {entity:?} is incompatible with its render target: {problem}");
+            }
+            reported_problems.insert(entity, problems);
+        }
+    }
+}
+
+fn is_float_format(format: TextureFormat) -> bool {
+    matches!(
+        format,
+        TextureFormat::R16Float
+            | TextureFormat::Rg16Float
+            | TextureFormat::Rgba16Float
+            | TextureFormat::R32Float
+            | TextureFormat::Rg32Float
+            | TextureFormat::Rgba32Float
+            | TextureFormat::Rg11b10Float
+    )
+}
+
 /// A subpixel offset to jitter a perspective camera's frustum by.
 ///
 /// Useful for temporal rendering techniques.
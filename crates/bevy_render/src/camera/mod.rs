@@ -3,17 +3,20 @@ mod camera;
 mod camera_driver_node;
 mod clear_color;
 mod manual_texture_view;
+mod pixel_perfect;
 mod projection;
 
 pub use camera::*;
 pub use camera_driver_node::*;
 pub use clear_color::*;
 pub use manual_texture_view::*;
+pub use pixel_perfect::*;
 pub use projection::*;
 
 use crate::{
     extract_component::ExtractComponentPlugin, extract_resource::ExtractResourcePlugin,
-    render_graph::RenderGraph, ExtractSchedule, Render, RenderApp, RenderSet,
+    render_graph::RenderGraph, view::prepare_windows, ExtractSchedule, Render, RenderApp,
+    RenderSet,
 };
 use bevy_app::{App, Plugin};
 use bevy_ecs::schedule::IntoSystemConfigs;
@@ -30,6 +33,7 @@ impl Plugin for CameraPlugin {
             .register_type::<Exposure>()
             .register_type::<TemporalJitter>()
             .register_type::<MipBias>()
+            .register_type::<PixelPerfect>()
             .init_resource::<ManualTextureViews>()
             .init_resource::<ClearColor>()
             .add_plugins((
@@ -45,7 +49,15 @@ impl Plugin for CameraPlugin {
             render_app
                 .init_resource::<SortedCameras>()
                 .add_systems(ExtractSchedule, extract_cameras)
-                .add_systems(Render, sort_cameras.in_set(RenderSet::ManageViews));
+                .add_systems(
+                    Render,
+                    (
+                        sort_cameras.in_set(RenderSet::ManageViews),
+                        validate_camera_targets
+                            .in_set(RenderSet::ManageViews)
+                            .after(prepare_windows),
+                    ),
+                );
             let camera_driver_node = CameraDriverNode::new(render_app.world_mut());
             let mut render_graph = render_app.world_mut().resource_mut::<RenderGraph>();
             render_graph.add_node(crate::graph::CameraDriverLabel, camera_driver_node);
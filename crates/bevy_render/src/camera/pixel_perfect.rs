@@ -0,0 +1,183 @@
+use bevy_ecs::{component::Component, reflect::ReflectComponent};
+use bevy_math::{URect, UVec2, Vec2, Vec3};
+use bevy_reflect::prelude::*;
+use bevy_transform::components::GlobalTransform;
+
+use super::{Camera, ClearColorConfig};
+
+/// Renders a camera at a low, fixed resolution and scales its image up to its viewport, for crisp
+/// pixel art.
+///
+/// The camera renders to an intermediate texture of [`resolution`](Self::resolution) virtual
+/// pixels, with its projection sized to that resolution, so that with the default
+/// [`ScalingMode::WindowSize`](super::ScalingMode::WindowSize) of an orthographic projection one
+/// world unit is one virtual pixel. The image is then scaled up to the viewport of the camera with
+/// nearest filtering and centered in it, and the rest of the viewport is filled with the
+/// [`letterbox`](Self::letterbox) color.
+///
+/// This is meant for 2d cameras with an orthographic projection. Note that:
+/// - UI rendered by this camera is laid out for the window, so UI should be rendered by another
+///   camera drawn after this one. As the cameras don't share their main texture, that camera
+///   should clear to a transparent color rather than use [`ClearColorConfig::None`].
+/// - The conversions of [`Camera`] between the viewport and the world don't account for the
+///   letterboxing, use [`PixelPerfect::viewport_to_world_2d`] instead.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+#[reflect(Component, Default)]
+pub struct PixelPerfect {
+    /// The size of the image rendered by the camera, in virtual pixels.
+    pub resolution: UVec2,
+    /// How the image is scaled up to the viewport of the camera.
+    pub scaling: PixelScaling,
+    /// Whether the camera and sprites are snapped to the grid of virtual pixels, so that they
+    /// move by whole virtual pixels rather than shimmer when moving by fractions of a pixel.
+    ///
+    /// Snapping ignores the rotation of the camera. When several cameras snap, sprites are
+    /// snapped to the grid of the first one.
+    pub snap: bool,
+    /// The color the viewport is cleared with around the scaled up image.
+    pub letterbox: ClearColorConfig,
+}
+
+impl Default for PixelPerfect {
+    fn default() -> Self {
+        Self {
+            resolution: UVec2::new(320, 180),
+            scaling: PixelScaling::default(),
+            snap: true,
+            letterbox: ClearColorConfig::Custom(bevy_color::Color::BLACK),
+        }
+    }
+}
+
+/// How a [`PixelPerfect`] camera scales its image up to its viewport.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[reflect(Default)]
+pub enum PixelScaling {
+    /// Scales the image by the largest whole factor fitting the viewport, so that every virtual
+    /// pixel has the same size on screen.
+    ///
+    /// If the viewport is smaller than the image, falls back to [`PixelScaling::Fit`].
+    #[default]
+    Integer,
+    /// Scales the image by the largest factor fitting the viewport, keeping its aspect ratio.
+    /// Virtual pixels may then differ in size by one physical pixel.
+    Fit,
+}
+
+impl PixelPerfect {
+    /// Creates a [`PixelPerfect`] camera rendering at the given resolution, in virtual pixels.
+    pub fn new(resolution: UVec2) -> Self {
+        Self {
+            resolution,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the rect of the given physical viewport the scaled up image covers.
+    pub fn output_rect(&self, viewport: URect) -> URect {
+        let area = viewport.size();
+        let resolution = self.resolution.max(UVec2::ONE);
+        let scale = (area / resolution).min_element();
+        let size = if self.scaling == PixelScaling::Integer && scale > 0 {
+            resolution * scale
+        } else {
+            let scale = (area.as_vec2() / resolution.as_vec2()).min_element();
+            (resolution.as_vec2() * scale).round().as_uvec2().min(area)
+        };
+        let min = viewport.min + (area - size) / 2;
+        URect::from_corners(min, min + size)
+    }
+
+    /// Returns the size of a virtual pixel in world units, for a camera with an orthographic
+    /// projection.
+    pub fn world_pixel_size(&self, camera: &Camera) -> Vec2 {
+        let clip_from_view = camera.clip_from_view();
+        let view_size = 2.0 / Vec2::new(clip_from_view.x_axis.x, clip_from_view.y_axis.y).abs();
+        view_size / self.resolution.max(UVec2::ONE).as_vec2()
+    }
+
+    /// Snaps the translation of a camera so that the edges of its view lie on the grid of virtual
+    /// pixels.
+    pub fn snap_camera_translation(&self, camera: &Camera, translation: Vec3) -> Vec3 {
+        let clip_from_view = camera.clip_from_view();
+        let scale = Vec2::new(clip_from_view.x_axis.x, clip_from_view.y_axis.y);
+        if scale.x == 0.0 || scale.y == 0.0 {
+            return translation;
+        }
+        // The view space position of the left and bottom edges of the view.
+        let edge = (-Vec2::ONE - clip_from_view.w_axis.truncate().truncate()) / scale;
+        let pixel_size = self.world_pixel_size(camera);
+        let snapped = ((translation.truncate() + edge) / pixel_size).round() * pixel_size - edge;
+        snapped.extend(translation.z)
+    }
+
+    /// Converts a position in the logical viewport of the camera, such as the cursor position,
+    /// to world coordinates, accounting for the scaling and letterboxing of the image.
+    ///
+    /// Returns `None` if the position is outside of the scaled up image, or if the viewport size
+    /// can't be computed.
+    pub fn viewport_to_world_2d(
+        &self,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+        viewport_position: Vec2,
+    ) -> Option<Vec2> {
+        let viewport_size = camera.physical_viewport_size()?;
+        let scale_factor = camera.target_scaling_factor()?;
+        let output = self
+            .output_rect(URect::from_corners(UVec2::ZERO, viewport_size))
+            .as_rect();
+        let physical_position = viewport_position * scale_factor;
+        if !output.contains(physical_position) {
+            return None;
+        }
+        let uv = (physical_position - output.min) / output.size();
+        let ndc = Vec2::new(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0);
+        let world_near_plane = camera.ndc_to_world(camera_transform, ndc.extend(1.))?;
+        Some(world_near_plane.truncate())
+    }
+}
+
+/// The rect of the render target the image of a [`PixelPerfect`] camera is scaled up to, and
+/// the color the rest of its viewport is cleared with, extracted to the render world.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ExtractedPixelPerfect {
+    /// The physical rect of the render target covered by the scaled up image.
+    pub output_rect: URect,
+    /// The color the viewport is cleared with around the image.
+    pub letterbox: ClearColorConfig,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_scaling_is_centered() {
+        let pixel_perfect = PixelPerfect {
+            resolution: UVec2::new(320, 180),
+            ..Default::default()
+        };
+        let rect = pixel_perfect.output_rect(URect::new(0, 0, 1280, 800));
+        assert_eq!(rect, URect::new(0, 40, 1280, 760));
+
+        let rect = pixel_perfect.output_rect(URect::new(100, 0, 1100, 600));
+        assert_eq!(rect, URect::new(120, 30, 1080, 570));
+    }
+
+    #[test]
+    fn small_viewports_fit_the_image() {
+        let pixel_perfect = PixelPerfect::new(UVec2::new(320, 180));
+        let rect = pixel_perfect.output_rect(URect::new(0, 0, 160, 160));
+        assert_eq!(rect, URect::new(0, 35, 160, 125));
+
+        let pixel_perfect = PixelPerfect {
+            scaling: PixelScaling::Fit,
+            ..pixel_perfect
+        };
+        let rect = pixel_perfect.output_rect(URect::new(0, 0, 1280, 800));
+        assert_eq!(rect, URect::new(0, 40, 1280, 760));
+        let rect = pixel_perfect.output_rect(URect::new(0, 0, 1000, 800));
+        assert_eq!(rect, URect::new(0, 118, 1000, 681));
+    }
+}
@@ -32,6 +32,7 @@ pub mod render_phase;
 pub mod render_resource;
 pub mod renderer;
 pub mod settings;
+pub mod shader_debug;
 mod spatial_bundle;
 pub mod texture;
 pub mod view;
@@ -62,7 +63,9 @@ use bevy_window::{PrimaryWindow, RawHandleWrapperHolder};
 use extract_resource::ExtractResourcePlugin;
 use globals::GlobalsPlugin;
 use render_asset::RenderAssetBytesPerFrame;
-use renderer::{RenderAdapter, RenderAdapterInfo, RenderDevice, RenderQueue};
+use renderer::{
+    FrameEventsPlugin, GpuInfoPlugin, RenderAdapter, RenderAdapterInfo, RenderDevice, RenderQueue,
+};
 
 use crate::mesh::GpuMesh;
 use crate::renderer::WgpuWrapper;
@@ -70,7 +73,7 @@ use crate::{
     camera::CameraPlugin,
     mesh::{morph::MorphPlugin, MeshPlugin},
     render_asset::prepare_assets,
-    render_resource::{PipelineCache, Shader, ShaderLoader},
+    render_resource::{PipelineCache, PipelineCachePersistence, Shader, ShaderLoader},
     renderer::{render_system, RenderInstance},
     settings::RenderCreation,
     view::{ViewPlugin, WindowRenderPlugin},
@@ -98,6 +101,9 @@ pub struct RenderPlugin {
     /// If `true`, disables asynchronous pipeline compilation.
     /// This has no effect on macOS, Wasm, iOS, or without the `multi_threaded` feature.
     pub synchronous_pipeline_compilation: bool,
+    /// If set, the shader permutations used by the [`PipelineCache`] are saved to disk, and
+    /// processed ahead of time on the next runs.
+    pub pipeline_cache_persistence: Option<PipelineCachePersistence>,
 }
 
 /// The systems sets of the default [`App`] rendering schedule.
@@ -346,6 +352,8 @@ impl Plugin for RenderPlugin {
             GlobalsPlugin,
             MorphPlugin,
             BatchingPlugin,
+            FrameEventsPlugin,
+            GpuInfoPlugin,
         ));
 
         app.init_resource::<RenderAssetBytesPerFrame>()
@@ -390,11 +398,17 @@ impl Plugin for RenderPlugin {
 
             render_app
                 .insert_resource(instance)
-                .insert_resource(PipelineCache::new(
-                    device.clone(),
-                    render_adapter.clone(),
-                    self.synchronous_pipeline_compilation,
-                ))
+                .insert_resource({
+                    let pipeline_cache = PipelineCache::new(
+                        device.clone(),
+                        render_adapter.clone(),
+                        self.synchronous_pipeline_compilation,
+                    );
+                    match &self.pipeline_cache_persistence {
+                        Some(persistence) => pipeline_cache.with_persistence(persistence.clone()),
+                        None => pipeline_cache,
+                    }
+                })
                 .insert_resource(device)
                 .insert_resource(queue)
                 .insert_resource(render_adapter)
@@ -14,6 +14,29 @@ use std::ops::Deref;
 pub struct SkinnedMesh {
     pub inverse_bindposes: Handle<SkinnedMeshInverseBindposes>,
     pub joints: Vec<Entity>,
+    /// How the transforms of the joints are blended for each vertex.
+    pub skinning_method: SkinningMethod,
+}
+
+/// How the transforms of the joints of a [`SkinnedMesh`] are blended for each
+/// vertex.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Default, Debug, PartialEq, Hash)]
+pub enum SkinningMethod {
+    /// Blends the joint matrices linearly.
+    ///
+    /// This is the fastest method and what most assets are authored for, but
+    /// volume collapses around joints that twist or bend a lot, the so called
+    /// "candy wrapper" artifacts.
+    #[default]
+    Linear,
+    /// Blends the rotations and translations of the joints as dual quaternions,
+    /// which preserves volume around twisting joints such as wrists and
+    /// shoulders.
+    ///
+    /// The scale of the joints is blended linearly, and shear is not
+    /// supported.
+    DualQuaternion,
 }
 
 impl MapEntities for SkinnedMesh {
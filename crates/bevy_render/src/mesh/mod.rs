@@ -25,6 +25,7 @@ impl Plugin for MeshPlugin {
             .init_asset::<skinning::SkinnedMeshInverseBindposes>()
             .register_asset_reflect::<Mesh>()
             .register_type::<skinning::SkinnedMesh>()
+            .register_type::<skinning::SkinningMethod>()
             .register_type::<Vec<Entity>>()
             // 'Mesh' must be prepared after 'Image' as meshes rely on the morph target image being ready
             .add_plugins(RenderAssetPlugin::<GpuMesh, GpuImage>::default());
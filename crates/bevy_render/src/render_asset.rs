@@ -1,6 +1,6 @@
 use crate::{ExtractSchedule, MainWorld, Render, RenderApp, RenderSet};
 use bevy_app::{App, Plugin, SubApp};
-use bevy_asset::{Asset, AssetEvent, AssetId, Assets};
+use bevy_asset::{Asset, AssetEvent, AssetId, AssetUsages, Assets};
 use bevy_ecs::{
     prelude::{Commands, EventReader, IntoSystemConfigs, ResMut, Resource},
     schedule::SystemConfigs,
@@ -146,6 +146,26 @@ impl<A: RenderAsset, AFTER: RenderAssetDependency + 'static> Plugin
             );
         }
     }
+
+    fn finish(&self, app: &mut App) {
+        // Share the usages of the source assets with the render world, so that the assets it uses
+        // by id are kept by their `RetainPolicy`
+        let Some(usages) = app
+            .world()
+            .get_resource::<AssetUsages<A::SourceAsset>>()
+            .cloned()
+        else {
+            return;
+        };
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            if !render_app
+                .world()
+                .contains_resource::<AssetUsages<A::SourceAsset>>()
+            {
+                render_app.insert_resource(usages);
+            }
+        }
+    }
 }
 
 // helper to allow specifying dependencies between render assets
@@ -10,16 +10,20 @@ use bevy_tasks::Task;
 use bevy_utils::hashbrown::hash_map::EntryRef;
 use bevy_utils::{
     default,
-    tracing::{debug, error},
+    tracing::{debug, error, warn},
     HashMap, HashSet,
 };
 use naga::valid::Capabilities;
+use serde::{Deserialize, Serialize};
 use std::{
     borrow::Cow,
+    collections::BTreeMap,
+    fs,
     future::Future,
     hash::Hash,
-    mem,
+    io, mem,
     ops::Deref,
+    path::PathBuf,
     sync::{Arc, Mutex, PoisonError},
 };
 use thiserror::Error;
@@ -139,10 +143,17 @@ struct ShaderCache {
     shaders: HashMap<AssetId<Shader>, Shader>,
     import_path_shaders: HashMap<ShaderImport, AssetId<Shader>>,
     waiting_on_import: HashMap<ShaderImport, Vec<AssetId<Shader>>>,
+    /// Shader permutations to process ahead of time, by [`Shader::path`].
+    precompile_queue: HashMap<String, Vec<Box<[ShaderDefVal]>>>,
     composer: naga_oil::compose::Composer,
 }
 
-#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+/// The shader defs each shader was processed with, by [`Shader::path`], as persisted by
+/// [`PipelineCachePersistence`].
+#[derive(Default, Serialize, Deserialize)]
+struct ShaderPermutations(BTreeMap<String, Vec<Vec<ShaderDefVal>>>);
+
+#[derive(Clone, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
 pub enum ShaderDefVal {
     Bool(String, bool),
     Int(String, i32),
@@ -191,6 +202,7 @@ impl ShaderCache {
             shaders: Default::default(),
             import_path_shaders: Default::default(),
             waiting_on_import: Default::default(),
+            precompile_queue: Default::default(),
         }
     }
 
@@ -221,11 +233,14 @@ impl ShaderCache {
         Ok(())
     }
 
+    /// Returns the shader module of a permutation of a shader, processing it if needed.
+    ///
+    /// `pipeline` is recreated when the shader changes. It's `None` when precompiling.
     #[allow(clippy::result_large_err)]
     fn get(
         &mut self,
         render_device: &RenderDevice,
-        pipeline: CachedPipelineId,
+        pipeline: Option<CachedPipelineId>,
         id: AssetId<Shader>,
         shader_defs: &[ShaderDefVal],
     ) -> Result<ErasedShaderModule, PipelineCacheError> {
@@ -247,7 +262,9 @@ impl ShaderCache {
             return Err(PipelineCacheError::ShaderImportNotYetAvailable);
         }
 
-        data.pipelines.insert(pipeline);
+        if let Some(pipeline) = pipeline {
+            data.pipelines.insert(pipeline);
+        }
 
         // PERF: this shader_defs clone isn't great. use raw_entry_mut when it stabilizes
         let module = match data.processed_shaders.entry_ref(shader_defs) {
@@ -408,6 +425,83 @@ impl ShaderCache {
 
         pipelines_to_queue
     }
+
+    fn queue_precompile(&mut self, permutations: ShaderPermutations) {
+        for (path, permutations) in permutations.0 {
+            self.precompile_queue
+                .entry(path)
+                .or_default()
+                .extend(permutations.into_iter().map(Vec::into_boxed_slice));
+        }
+    }
+
+    /// Processes the queued permutations of the shaders that are loaded, keeping the ones whose
+    /// imports aren't available yet.
+    fn precompile(&mut self, render_device: &RenderDevice) {
+        if self.precompile_queue.is_empty() {
+            return;
+        }
+
+        let loaded = self
+            .shaders
+            .iter()
+            .filter(|(_, shader)| self.precompile_queue.contains_key(&shader.path))
+            .map(|(id, shader)| (*id, shader.path.clone()))
+            .collect::<Vec<_>>();
+        for (id, path) in loaded {
+            let Some(permutations) = self.precompile_queue.remove(&path) else {
+                continue;
+            };
+            let mut waiting = Vec::new();
+            for shader_defs in permutations {
+                match self.get(render_device, None, id, &shader_defs) {
+                    Ok(_) => {}
+                    Err(PipelineCacheError::ShaderImportNotYetAvailable) => {
+                        waiting.push(shader_defs);
+                    }
+                    Err(err) => debug!(
+                        "failed to precompile shader {}, with shader defs {:?}: {}",
+                        path, shader_defs, err
+                    ),
+                }
+            }
+            if !waiting.is_empty() {
+                self.precompile_queue.insert(path, waiting);
+            }
+        }
+    }
+
+    fn permutation_count(&self) -> usize {
+        self.data
+            .values()
+            .map(|data| data.processed_shaders.len())
+            .sum()
+    }
+
+    /// Returns the processed permutations, along with the ones still queued for precompilation.
+    fn permutations(&self) -> ShaderPermutations {
+        let mut permutations = ShaderPermutations::default();
+        for (id, data) in &self.data {
+            let Some(shader) = self.shaders.get(id) else {
+                continue;
+            };
+            if !data.processed_shaders.is_empty() {
+                permutations
+                    .0
+                    .entry(shader.path.clone())
+                    .or_default()
+                    .extend(data.processed_shaders.keys().map(|defs| defs.to_vec()));
+            }
+        }
+        for (path, queued) in &self.precompile_queue {
+            permutations
+                .0
+                .entry(path.clone())
+                .or_default()
+                .extend(queued.iter().map(|defs| defs.to_vec()));
+        }
+        permutations
+    }
 }
 
 type LayoutCacheKey = (Vec<BindGroupLayoutId>, Vec<PushConstantRange>);
@@ -466,6 +560,56 @@ pub struct PipelineCache {
     /// If `true`, disables asynchronous pipeline compilation.
     /// This has no effect on MacOS, wasm, or without the `multi_threaded` feature.
     synchronous_pipeline_compilation: bool,
+    persistence: Option<PipelineCachePersistence>,
+    /// The number of shader permutations when they were last persisted.
+    persisted_permutation_count: usize,
+}
+
+/// Where and how a [`PipelineCache`] persists the shader permutations it processes, so that later
+/// runs can process them ahead of time.
+///
+/// A shader permutation is a shader along with the shader defs a pipeline uses it with, like the
+/// PBR shader with the defs of a material and mesh layout. Once all queued pipelines are created,
+/// the permutations processed so far are written to [`path`](Self::path) as RON. On startup, the
+/// permutations read from there are processed into shader modules as soon as their shaders are
+/// loaded, before any pipeline asks for them. This moves most of the shader processing out of the
+/// first frames that draw with a new material or mesh, which would otherwise hitch.
+///
+/// Only the permutations that were actually used are recorded, so a file generated by playing
+/// through an application precompiles no more than what it needs.
+///
+/// The file doesn't contain the driver's compiled pipelines, as `wgpu` doesn't expose a pipeline
+/// cache yet: pipelines are still created when they are first queued.
+#[derive(Clone, Debug)]
+pub struct PipelineCachePersistence {
+    /// The file the shader permutations are read from and written to.
+    pub path: PathBuf,
+    /// Whether the file is read, written, or both.
+    pub strategy: PipelineCacheStrategy,
+}
+
+/// How a [`PipelineCachePersistence`] uses its file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PipelineCacheStrategy {
+    /// Only record the shader permutations used by this run, replacing the file.
+    ///
+    /// This is useful to generate the file shipped with an application.
+    Record,
+    /// Only precompile the shader permutations of the file, leaving it untouched.
+    Precompile,
+    /// Precompile the shader permutations of the file, and add the ones used by this run.
+    #[default]
+    PrecompileAndRecord,
+}
+
+impl PipelineCacheStrategy {
+    fn precompiles(self) -> bool {
+        matches!(self, Self::Precompile | Self::PrecompileAndRecord)
+    }
+
+    fn records(self) -> bool {
+        matches!(self, Self::Record | Self::PrecompileAndRecord)
+    }
 }
 
 impl PipelineCache {
@@ -493,9 +637,40 @@ impl PipelineCache {
             new_pipelines: default(),
             pipelines: default(),
             synchronous_pipeline_compilation,
+            persistence: None,
+            persisted_permutation_count: 0,
         }
     }
 
+    /// Persists the shader permutations processed by this cache, and precompiles the ones
+    /// persisted by earlier runs, as configured by `persistence`.
+    pub fn with_persistence(mut self, persistence: PipelineCachePersistence) -> Self {
+        if persistence.strategy.precompiles() {
+            match fs::read_to_string(&persistence.path) {
+                Ok(text) => match ron::from_str(&text) {
+                    Ok(permutations) => self
+                        .shader_cache
+                        .lock()
+                        .unwrap()
+                        .queue_precompile(permutations),
+                    Err(err) => warn!(
+                        "Ignoring the invalid pipeline cache file {:?}: {}",
+                        persistence.path, err
+                    ),
+                },
+                // This is the first run
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => warn!(
+                    "Failed to read the pipeline cache file {:?}: {}",
+                    persistence.path, err
+                ),
+            }
+        }
+
+        self.persistence = Some(persistence);
+        self
+    }
+
     /// Get the state of a cached render pipeline.
     ///
     /// See [`PipelineCache::queue_render_pipeline()`].
@@ -683,7 +858,7 @@ impl PipelineCache {
 
                 let vertex_module = match shader_cache.get(
                     &device,
-                    id,
+                    Some(id),
                     descriptor.vertex.shader.id(),
                     &descriptor.vertex.shader_defs,
                 ) {
@@ -695,7 +870,7 @@ impl PipelineCache {
                     Some(fragment) => {
                         match shader_cache.get(
                             &device,
-                            id,
+                            Some(id),
                             fragment.shader.id(),
                             &fragment.shader_defs,
                         ) {
@@ -792,7 +967,7 @@ impl PipelineCache {
 
                 let compute_module = match shader_cache.get(
                     &device,
-                    id,
+                    Some(id),
                     descriptor.shader.id(),
                     &descriptor.shader_defs,
                 ) {
@@ -840,6 +1015,11 @@ impl PipelineCache {
     ///
     /// [`RenderSet::Render`]: crate::RenderSet::Render
     pub fn process_queue(&mut self) {
+        // Pipeline creation tasks may hold the shader cache, precompiling can wait for next frame.
+        if let Ok(mut shader_cache) = self.shader_cache.try_lock() {
+            shader_cache.precompile(&self.device);
+        }
+
         let mut waiting_pipelines = mem::take(&mut self.waiting_pipelines);
         let mut pipelines = mem::take(&mut self.pipelines);
 
@@ -860,6 +1040,43 @@ impl PipelineCache {
         }
 
         self.pipelines = pipelines;
+
+        if self.waiting_pipelines.is_empty() {
+            self.persist_shader_permutations();
+        }
+    }
+
+    /// Writes the shader permutations to the [`PipelineCachePersistence`] file, if there are new
+    /// ones since the last time.
+    fn persist_shader_permutations(&mut self) {
+        let Some(persistence) = self
+            .persistence
+            .as_ref()
+            .filter(|persistence| persistence.strategy.records())
+        else {
+            return;
+        };
+        let shader_cache = self.shader_cache.lock().unwrap();
+        let permutation_count = shader_cache.permutation_count();
+        if permutation_count == self.persisted_permutation_count {
+            return;
+        }
+        self.persisted_permutation_count = permutation_count;
+
+        let result = ron::ser::to_string_pretty(&shader_cache.permutations(), default())
+            .map_err(|err| err.to_string())
+            .and_then(|text| {
+                if let Some(parent) = persistence.path.parent() {
+                    fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+                }
+                fs::write(&persistence.path, text).map_err(|err| err.to_string())
+            });
+        if let Err(err) = result {
+            warn!(
+                "Failed to write the pipeline cache file {:?}: {}",
+                persistence.path, err
+            );
+        }
     }
 
     fn process_pipeline(&mut self, cached_pipeline: &mut CachedPipeline, id: usize) {
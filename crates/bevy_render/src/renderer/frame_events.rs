@@ -0,0 +1,162 @@
+use async_channel::{Receiver, Sender};
+use bevy_app::{App, Plugin, PreUpdate, Update};
+use bevy_core::FrameCount;
+use bevy_diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+use bevy_ecs::prelude::*;
+use bevy_time::{Real, Time};
+use bevy_utils::{Duration, Instant};
+
+use crate::{Extract, ExtractSchedule, RenderApp};
+
+/// Sent when the render world submitted the command buffers of a frame to the GPU.
+///
+/// Like [`FramePresented`], this is sent in the main world, in [`PreUpdate`] of the next update
+/// after the frame was rendered.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct FrameSubmitted {
+    /// The [`FrameCount`] extracted with the frame.
+    pub frame: u32,
+    /// When the main app started updating the frame, right after receiving its input.
+    pub started: Instant,
+    /// When the command buffers were submitted.
+    pub time: Instant,
+}
+
+/// Sent when a frame was presented to a window.
+///
+/// `present` only schedules the frame to be shown by the compositor, so the time is a lower bound
+/// of when the frame reaches the display.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct FramePresented {
+    /// The [`FrameCount`] extracted with the frame.
+    pub frame: u32,
+    /// The window entity the frame was presented to.
+    pub window: Entity,
+    /// When the main app started updating the frame, right after receiving its input.
+    pub started: Instant,
+    /// When the frame was presented.
+    pub time: Instant,
+}
+
+impl FramePresented {
+    /// The time between receiving the input of the frame and presenting it.
+    pub fn latency(&self) -> Duration {
+        self.time.saturating_duration_since(self.started)
+    }
+}
+
+enum FrameEvent {
+    Submitted(FrameSubmitted),
+    Presented(FramePresented),
+}
+
+/// Sends [`FrameSubmitted`] and [`FramePresented`] from the render world.
+#[derive(Resource, Clone)]
+pub(crate) struct FrameEventSender {
+    sender: Sender<FrameEvent>,
+    frame: u32,
+    started: Instant,
+}
+
+impl FrameEventSender {
+    pub(crate) fn submitted(&self) {
+        // The receiver is only dropped with the main world
+        let _ = self.sender.try_send(FrameEvent::Submitted(FrameSubmitted {
+            frame: self.frame,
+            started: self.started,
+            time: Instant::now(),
+        }));
+    }
+
+    pub(crate) fn presented(&self, window: Entity) {
+        let _ = self.sender.try_send(FrameEvent::Presented(FramePresented {
+            frame: self.frame,
+            window,
+            started: self.started,
+            time: Instant::now(),
+        }));
+    }
+}
+
+#[derive(Resource)]
+struct FrameEventReceiver(Receiver<FrameEvent>);
+
+/// Adds the [`FrameSubmitted`] and [`FramePresented`] events.
+pub struct FrameEventsPlugin;
+
+impl Plugin for FrameEventsPlugin {
+    fn build(&self, app: &mut App) {
+        let (sender, receiver) = async_channel::unbounded();
+        app.add_event::<FrameSubmitted>()
+            .add_event::<FramePresented>()
+            .insert_resource(FrameEventReceiver(receiver))
+            .add_systems(PreUpdate, receive_frame_events);
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .insert_resource(FrameEventSender {
+                    sender,
+                    frame: 0,
+                    started: Instant::now(),
+                })
+                .add_systems(ExtractSchedule, extract_frame_start);
+        }
+    }
+}
+
+fn extract_frame_start(
+    mut frame_events: ResMut<FrameEventSender>,
+    frame_count: Extract<Res<FrameCount>>,
+    time: Extract<Res<Time<Real>>>,
+) {
+    frame_events.frame = frame_count.0;
+    frame_events.started = time.last_update().unwrap_or_else(Instant::now);
+}
+
+fn receive_frame_events(
+    receiver: Res<FrameEventReceiver>,
+    mut submitted: EventWriter<FrameSubmitted>,
+    mut presented: EventWriter<FramePresented>,
+) {
+    while let Ok(event) = receiver.0.try_recv() {
+        match event {
+            FrameEvent::Submitted(event) => {
+                submitted.send(event);
+            }
+            FrameEvent::Presented(event) => {
+                presented.send(event);
+            }
+        }
+    }
+}
+
+/// Adds the "frame latency" diagnostic to an App, the time in milliseconds between receiving the
+/// input of a frame and presenting it, see [`FramePresented::latency`].
+///
+/// The frames are measured by the [`RenderPlugin`](crate::RenderPlugin), which sends
+/// [`FramePresented`].
+#[derive(Default)]
+pub struct FrameLatencyDiagnosticsPlugin;
+
+impl Plugin for FrameLatencyDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<FramePresented>()
+            .register_diagnostic(Diagnostic::new(Self::FRAME_LATENCY).with_suffix("ms"))
+            .add_systems(Update, Self::diagnostic_system);
+    }
+}
+
+impl FrameLatencyDiagnosticsPlugin {
+    pub const FRAME_LATENCY: DiagnosticPath = DiagnosticPath::const_new("frame_latency");
+
+    pub fn diagnostic_system(
+        mut diagnostics: Diagnostics,
+        mut presented: EventReader<FramePresented>,
+    ) {
+        // Only one measurement is kept per update, so keep the slowest window
+        let Some(latency) = presented.read().map(FramePresented::latency).max() else {
+            return;
+        };
+        diagnostics.add_measurement(&Self::FRAME_LATENCY, || latency.as_secs_f64() * 1000.0);
+    }
+}
@@ -0,0 +1,122 @@
+use async_channel::{Receiver, Sender};
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::{entity::EntityHashMap, prelude::*};
+use bevy_window::WindowClosed;
+use wgpu::{Backend, DeviceType, TextureFormat};
+
+use crate::{
+    render_resource::{WgpuFeatures, WgpuLimits},
+    renderer::{RenderAdapterInfo, RenderDevice},
+    RenderApp,
+};
+
+/// A description of the GPU used by the renderer and of what it supports, available in the main
+/// world once the renderer is initialized.
+///
+/// It's meant for choosing quality settings and for bug reports. [`GpuInfoAvailable`] is sent when
+/// it's inserted.
+#[derive(Resource, Debug, Clone)]
+pub struct GpuInfo {
+    /// The name of the adapter.
+    pub name: String,
+    /// The PCI id of the vendor of the adapter, or `0` if it's unknown.
+    pub vendor: u32,
+    /// The PCI id of the adapter, or `0` if it's unknown.
+    pub device: u32,
+    /// The type of the adapter, like an integrated or a discrete GPU.
+    pub device_type: DeviceType,
+    /// The graphics API used by the renderer.
+    pub backend: Backend,
+    /// The name of the driver, empty if it's unknown.
+    pub driver: String,
+    /// The version of the driver, empty if it's unknown.
+    pub driver_info: String,
+    /// The limits of the [`RenderDevice`].
+    pub limits: WgpuLimits,
+    /// The features enabled on the [`RenderDevice`].
+    pub features: WgpuFeatures,
+    /// The texture formats supported by the surface of each window, filled in when the surfaces
+    /// are created, in the order preferred by the adapter.
+    pub surface_formats: EntityHashMap<Vec<TextureFormat>>,
+}
+
+impl GpuInfo {
+    fn new(adapter_info: &RenderAdapterInfo, device: &RenderDevice) -> Self {
+        Self {
+            name: adapter_info.name.clone(),
+            vendor: adapter_info.vendor,
+            device: adapter_info.device,
+            device_type: adapter_info.device_type,
+            backend: adapter_info.backend,
+            driver: adapter_info.driver.clone(),
+            driver_info: adapter_info.driver_info.clone(),
+            limits: device.limits(),
+            features: device.features(),
+            surface_formats: EntityHashMap::default(),
+        }
+    }
+}
+
+/// Sent when [`GpuInfo`] was inserted in the main world, once the renderer is initialized.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct GpuInfoAvailable;
+
+/// Sends the formats of the surfaces created in the render world to [`GpuInfo`].
+#[derive(Resource, Clone)]
+pub struct SurfaceFormatsSender(Sender<(Entity, Vec<TextureFormat>)>);
+
+impl SurfaceFormatsSender {
+    pub(crate) fn send(&self, window: Entity, formats: Vec<TextureFormat>) {
+        // The receiver is only dropped with the main world
+        let _ = self.0.try_send((window, formats));
+    }
+}
+
+#[derive(Resource)]
+struct SurfaceFormatsReceiver(Receiver<(Entity, Vec<TextureFormat>)>);
+
+/// Adds the [`GpuInfo`] resource and the [`GpuInfoAvailable`] event.
+pub struct GpuInfoPlugin;
+
+impl Plugin for GpuInfoPlugin {
+    fn build(&self, app: &mut App) {
+        let (sender, receiver) = async_channel::unbounded();
+        app.add_event::<GpuInfoAvailable>()
+            .insert_resource(SurfaceFormatsReceiver(receiver))
+            .add_systems(PreUpdate, update_surface_formats);
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.insert_resource(SurfaceFormatsSender(sender));
+        }
+    }
+
+    fn finish(&self, app: &mut App) {
+        // The device is inserted in the main world by `RenderPlugin::finish`, which runs first
+        let world = app.world_mut();
+        let (Some(adapter_info), Some(device)) = (
+            world.get_resource::<RenderAdapterInfo>(),
+            world.get_resource::<RenderDevice>(),
+        ) else {
+            return;
+        };
+        let gpu_info = GpuInfo::new(adapter_info, device);
+        world.insert_resource(gpu_info);
+        world.send_event(GpuInfoAvailable);
+    }
+}
+
+fn update_surface_formats(
+    receiver: Res<SurfaceFormatsReceiver>,
+    gpu_info: Option<ResMut<GpuInfo>>,
+    mut closed: EventReader<WindowClosed>,
+) {
+    let Some(mut gpu_info) = gpu_info else {
+        return;
+    };
+    while let Ok((window, formats)) = receiver.0.try_recv() {
+        gpu_info.surface_formats.insert(window, formats);
+    }
+    for closed in closed.read() {
+        gpu_info.surface_formats.remove(&closed.window);
+    }
+}
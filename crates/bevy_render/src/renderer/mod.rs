@@ -1,9 +1,13 @@
+mod frame_events;
+mod gpu_info;
 mod graph_runner;
 mod render_device;
 
 use bevy_derive::{Deref, DerefMut};
 use bevy_tasks::ComputeTaskPool;
 use bevy_utils::tracing::{error, info, info_span, warn};
+pub use frame_events::*;
+pub use gpu_info::*;
 pub use graph_runner::*;
 pub use render_device::*;
 
@@ -31,6 +35,7 @@ pub fn render_system(world: &mut World, state: &mut SystemState<Query<Entity, Wi
     });
 
     let diagnostics_recorder = world.remove_resource::<DiagnosticsRecorder>();
+    let frame_events = world.get_resource::<FrameEventSender>().cloned();
 
     let graph = world.resource::<RenderGraph>();
     let render_device = world.resource::<RenderDevice>();
@@ -46,9 +51,16 @@ pub fn render_system(world: &mut World, state: &mut SystemState<Query<Entity, Wi
         world,
         |encoder| {
             crate::view::screenshot::submit_screenshot_commands(world, encoder);
+            crate::shader_debug::submit_debug_print_commands(world, encoder);
         },
     );
 
+    if res.is_ok() {
+        if let Some(frame_events) = &frame_events {
+            frame_events.submitted();
+        }
+    }
+
     match res {
         Ok(Some(diagnostics_recorder)) => {
             world.insert_resource(diagnostics_recorder);
@@ -90,6 +102,9 @@ pub fn render_system(world: &mut World, state: &mut SystemState<Query<Entity, Wi
                     // by wgpu.
                     // https://docs.rs/winit/0.29.9/wasm32-unknown-unknown/winit/window/struct.Window.html#method.pre_present_notify
                     surface_texture.present();
+                    if let Some(frame_events) = &frame_events {
+                        frame_events.presented(window.entity);
+                    }
                 }
             }
         }
@@ -103,6 +118,7 @@ pub fn render_system(world: &mut World, state: &mut SystemState<Query<Entity, Wi
     }
 
     crate::view::screenshot::collect_screenshots(world);
+    crate::shader_debug::collect_debug_prints(world);
 
     // update the time and send it to the app world
     let time_sender = world.resource::<TimeSender>();
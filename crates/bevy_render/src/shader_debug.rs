@@ -0,0 +1,222 @@
+//! A `printf`-style facility to debug shaders.
+//!
+//! For more info, see [`ShaderDebugPlugin`].
+
+use std::{borrow::Cow, sync::Mutex};
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Handle};
+use bevy_ecs::prelude::*;
+use bevy_math::Vec4;
+use bevy_tasks::AsyncComputeTaskPool;
+use bevy_utils::{
+    tracing::{info, warn},
+    HashMap,
+};
+use wgpu::{BufferDescriptor, BufferUsages, CommandEncoder, MapMode, ShaderStages};
+
+use crate::{
+    render_resource::{
+        binding_types::storage_buffer_sized, BindGroupLayoutEntryBuilder, Buffer, Shader,
+        ShaderDefVal,
+    },
+    renderer::RenderDevice,
+    RenderApp,
+};
+
+pub const SHADER_DEBUG_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(2347873877445454854);
+
+/// The size of the header of the buffers, containing the number of written values.
+const HEADER_SIZE: u64 = 16;
+const VALUE_SIZE: u64 = 16;
+
+/// Lets shaders log values in the console with `debug_print(vec4<f32>)`, to debug them without
+/// guessing from colors. It's meant for development builds.
+///
+/// The values are written to a bounded storage buffer, which is read back after the frame is
+/// rendered and logged with the label of the draw or dispatch:
+///  1. Import the function in the shader, and only call it for a few invocations, like the
+///     invocations of a given pixel or thread.
+///     ```wgsl
+///     #import bevy_render::debug_print::debug_print
+///
+///     if all(vec2<u32>(position.xy) == vec2(100u, 100u)) {
+///         debug_print(color);
+///     }
+///     ```
+///  2. Add the shader defs returned by [`ShaderDebugPrints::shader_defs`] to the pipeline, and the
+///     layout entry returned by [`ShaderDebugPrints::layout_entry`] to the bind group layout, at the
+///     same group and binding. Without the shader defs, `debug_print` doesn't do anything.
+///  3. Each frame, bind the buffer returned by [`ShaderDebugPrints::buffer`], with the label of the
+///     draw or dispatch.
+///
+/// The buffers are only read back for the labels used in the frame.
+pub struct ShaderDebugPlugin {
+    /// The maximum number of values logged per label and per frame. The values written over this
+    /// capacity are dropped.
+    pub capacity: u32,
+}
+
+impl Default for ShaderDebugPlugin {
+    fn default() -> Self {
+        Self { capacity: 256 }
+    }
+}
+
+impl Plugin for ShaderDebugPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            SHADER_DEBUG_SHADER_HANDLE,
+            "shader_debug.wgsl",
+            Shader::from_wgsl
+        );
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.insert_resource(ShaderDebugPrints {
+                capacity: self.capacity,
+                buffers: HashMap::new(),
+            });
+        }
+    }
+}
+
+struct DebugPrintBuffer {
+    buffer: Buffer,
+    // this is in a mutex to enable recording the copy with only an immutable reference
+    readback: Mutex<Option<Buffer>>,
+}
+
+/// The buffers the shaders write their `debug_print` values to, in the render world.
+///
+/// See [`ShaderDebugPlugin`].
+#[derive(Resource)]
+pub struct ShaderDebugPrints {
+    capacity: u32,
+    buffers: HashMap<Cow<'static, str>, DebugPrintBuffer>,
+}
+
+impl ShaderDebugPrints {
+    /// Returns the shader defs enabling `debug_print` in a shader, whose buffer is bound at the
+    /// given `group` and `binding`.
+    pub fn shader_defs(group: u32, binding: u32) -> Vec<ShaderDefVal> {
+        vec![
+            "DEBUG_PRINT".into(),
+            ShaderDefVal::UInt("DEBUG_PRINT_BIND_GROUP".into(), group),
+            ShaderDefVal::UInt("DEBUG_PRINT_BINDING".into(), binding),
+        ]
+    }
+
+    /// Returns the bind group layout entry of the buffer, visible to the given `stages`.
+    pub fn layout_entry(stages: ShaderStages) -> BindGroupLayoutEntryBuilder {
+        storage_buffer_sized(false, None).visibility(stages)
+    }
+
+    /// Returns the buffer the draws or dispatches with the given `label` write their values to,
+    /// and reads it back at the end of the frame.
+    pub fn buffer(
+        &mut self,
+        render_device: &RenderDevice,
+        label: impl Into<Cow<'static, str>>,
+    ) -> &Buffer {
+        let size = HEADER_SIZE + self.capacity as u64 * VALUE_SIZE;
+        let label = label.into();
+        let debug_print_buffer =
+            self.buffers
+                .entry(label.clone())
+                .or_insert_with(|| DebugPrintBuffer {
+                    buffer: render_device.create_buffer(&BufferDescriptor {
+                        label: Some(&format!("debug_print_buffer_{label}")),
+                        size,
+                        usage: BufferUsages::STORAGE
+                            | BufferUsages::COPY_SRC
+                            | BufferUsages::COPY_DST,
+                        mapped_at_creation: false,
+                    }),
+                    readback: Mutex::new(None),
+                });
+        debug_print_buffer
+            .readback
+            .get_mut()
+            .unwrap()
+            .get_or_insert_with(|| {
+                render_device.create_buffer(&BufferDescriptor {
+                    label: Some("debug_print_readback_buffer"),
+                    size,
+                    usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                })
+            });
+        &debug_print_buffer.buffer
+    }
+}
+
+pub(crate) fn submit_debug_print_commands(world: &World, encoder: &mut CommandEncoder) {
+    let Some(debug_prints) = world.get_resource::<ShaderDebugPrints>() else {
+        return;
+    };
+    for debug_print_buffer in debug_prints.buffers.values() {
+        if let Some(readback) = &*debug_print_buffer.readback.lock().unwrap() {
+            encoder.copy_buffer_to_buffer(
+                &debug_print_buffer.buffer,
+                0,
+                readback,
+                0,
+                readback.size(),
+            );
+            // reset the count for the next frame
+            encoder.clear_buffer(&debug_print_buffer.buffer, 0, Some(HEADER_SIZE));
+        }
+    }
+}
+
+pub(crate) fn collect_debug_prints(world: &mut World) {
+    let Some(mut debug_prints) = world.get_resource_mut::<ShaderDebugPrints>() else {
+        return;
+    };
+    let capacity = debug_prints.capacity;
+    for (label, debug_print_buffer) in &mut debug_prints.buffers {
+        let Some(readback) = debug_print_buffer.readback.get_mut().unwrap().take() else {
+            continue;
+        };
+        let label = label.clone();
+        let finish = async move {
+            let map_label = label.clone();
+            let (tx, rx) = async_channel::bounded(1);
+            let buffer_slice = readback.slice(..);
+            // The polling for this map call is done every frame when the command queue is submitted.
+            buffer_slice.map_async(MapMode::Read, move |result| {
+                if let Err(err) = result {
+                    warn!("Failed to read back the debug prints of {map_label}: {err}");
+                    return;
+                }
+                tx.try_send(()).unwrap();
+            });
+            if rx.recv().await.is_err() {
+                return;
+            }
+            let data = buffer_slice.get_mapped_range();
+            let count = u32::from_ne_bytes(data[..4].try_into().unwrap());
+            let values = data[HEADER_SIZE as usize..]
+                .chunks(VALUE_SIZE as usize)
+                .take(count as usize)
+                .map(|value| {
+                    let [x, y, z, w] = [0, 4, 8, 12]
+                        .map(|i| f32::from_ne_bytes(value[i..i + 4].try_into().unwrap()));
+                    Vec4::new(x, y, z, w)
+                })
+                .collect::<Vec<_>>();
+            drop(data);
+            for value in values {
+                info!("debug_print ({label}): {value}");
+            }
+            if count > capacity {
+                warn!(
+                    "{} debug prints of {label} were dropped, over the capacity of {capacity}",
+                    count - capacity
+                );
+            }
+        };
+        AsyncComputeTaskPool::get().spawn(finish).detach();
+    }
+}
@@ -0,0 +1,29 @@
+#define_import_path bevy_render::debug_print
+
+// The values written by `debug_print`, read back and logged by the `ShaderDebugPlugin`.
+//
+// The buffer is only bound when the pipeline defines `DEBUG_PRINT`, with the shader defs returned by
+// `ShaderDebugPrints::shader_defs`. Otherwise `debug_print` doesn't do anything.
+struct DebugPrintBuffer {
+    // The number of values written, which can be more than the capacity of the buffer
+    count: atomic<u32>,
+    values: array<vec4<f32>>,
+}
+
+#ifdef DEBUG_PRINT
+@group(#{DEBUG_PRINT_BIND_GROUP}) @binding(#{DEBUG_PRINT_BINDING})
+var<storage, read_write> debug_print_buffer: DebugPrintBuffer;
+#endif
+
+// Logs the value in the console, with the label of the draw or dispatch.
+//
+// Beware that every invocation of the shader writes a value, so this should be called from a
+// condition selecting a few invocations, like a given pixel or thread.
+fn debug_print(value: vec4<f32>) {
+#ifdef DEBUG_PRINT
+    let index = atomicAdd(&debug_print_buffer.count, 1u);
+    if index < arrayLength(&debug_print_buffer.values) {
+        debug_print_buffer.values[index] = value;
+    }
+#endif
+}
@@ -14,6 +14,8 @@ mod image;
 mod image_loader;
 #[cfg(feature = "ktx2")]
 mod ktx2;
+#[cfg(feature = "png")]
+mod png_image_saver;
 mod texture_attachment;
 mod texture_cache;
 
@@ -33,6 +35,8 @@ pub use hdr_texture_loader::*;
 pub use compressed_image_saver::*;
 pub use fallback_image::*;
 pub use image_loader::*;
+#[cfg(feature = "png")]
+pub use png_image_saver::*;
 pub use texture_attachment::*;
 pub use texture_cache::*;
 
@@ -102,6 +106,9 @@ impl Plugin for ImagePlugin {
         image_assets.insert(&Handle::default(), Image::default());
         image_assets.insert(&TRANSPARENT_IMAGE_HANDLE, Image::transparent());
 
+        #[cfg(feature = "png")]
+        app.register_asset_saver(PngImageSaver);
+
         #[cfg(feature = "basis-universal")]
         if let Some(processor) = app
             .world()
@@ -0,0 +1,51 @@
+use crate::texture::{
+    image_texture_conversion::IntoDynamicImageError, Image, ImageFormat, ImageFormatSetting,
+    ImageLoader, ImageLoaderSettings,
+};
+use bevy_asset::saver::{AssetSaver, SavedAsset};
+use futures_lite::AsyncWriteExt;
+use thiserror::Error;
+
+/// Saves [`Image`]s as PNG files, used to save the images created at runtime with
+/// [`AssetServer::save`](bevy_asset::AssetServer::save).
+pub struct PngImageSaver;
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum PngImageSaverError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    IntoDynamicImage(#[from] IntoDynamicImageError),
+    #[error(transparent)]
+    Encode(#[from] image::ImageError),
+}
+
+impl AssetSaver for PngImageSaver {
+    type Asset = Image;
+
+    type Settings = ();
+    type OutputLoader = ImageLoader;
+    type Error = PngImageSaverError;
+
+    async fn save<'a>(
+        &'a self,
+        writer: &'a mut bevy_asset::io::Writer,
+        image: SavedAsset<'a, Self::Asset>,
+        _settings: &'a Self::Settings,
+    ) -> Result<ImageLoaderSettings, Self::Error> {
+        let is_srgb = image.texture_descriptor.format.is_srgb();
+        let dynamic_image = image.get().clone().try_into_dynamic()?;
+
+        let mut png_data = std::io::Cursor::new(Vec::new());
+        dynamic_image.write_to(&mut png_data, image::ImageFormat::Png)?;
+
+        writer.write_all(png_data.get_ref()).await?;
+        Ok(ImageLoaderSettings {
+            format: ImageFormatSetting::Format(ImageFormat::Png),
+            is_srgb,
+            sampler: image.sampler.clone(),
+            asset_usage: image.asset_usage,
+        })
+    }
+}
@@ -8,14 +8,17 @@ pub use window::*;
 use crate::{
     camera::{
         CameraMainTextureUsages, ClearColor, ClearColorConfig, Exposure, ExtractedCamera,
-        ManualTextureViews, MipBias, TemporalJitter,
+        ManualTextureViews, MipBias, NormalizedRenderTarget, TemporalJitter,
     },
     extract_resource::{ExtractResource, ExtractResourcePlugin},
     prelude::Shader,
     primitives::Frustum,
     render_asset::RenderAssets,
     render_phase::ViewRangefinder3d,
-    render_resource::{DynamicUniformBuffer, ShaderType, Texture, TextureView},
+    render_resource::{
+        DynamicUniformBuffer, ShaderType, Texture, TextureAspect, TextureView,
+        TextureViewDescriptor,
+    },
     renderer::{RenderDevice, RenderQueue},
     texture::{
         BevyDefault, CachedTexture, ColorAttachment, DepthAttachment, GpuImage,
@@ -30,6 +33,7 @@ use bevy_math::{mat3, vec2, vec3, Mat3, Mat4, UVec4, Vec2, Vec3, Vec4, Vec4Swizz
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_transform::components::GlobalTransform;
 use bevy_utils::HashMap;
+use bevy_window::OutputColorSpace;
 use std::{
     ops::Range,
     sync::{
@@ -691,6 +695,9 @@ impl ViewTarget {
 pub struct ViewDepthTexture {
     pub texture: Texture,
     attachment: DepthAttachment,
+    /// A view of the depth aspect only, for textures with a stencil aspect, as those can't be
+    /// sampled as a whole.
+    depth_only_view: Option<TextureView>,
 }
 
 impl ViewDepthTexture {
@@ -698,15 +705,32 @@ impl ViewDepthTexture {
         Self {
             texture: texture.texture,
             attachment: DepthAttachment::new(texture.default_view, clear_value),
+            depth_only_view: None,
         }
     }
 
+    /// Also attaches the stencil aspect of the texture, see [`DepthAttachment::with_stencil`].
+    ///
+    /// [`view`](Self::view) then only covers the depth aspect.
+    pub fn with_stencil(mut self) -> Self {
+        self.attachment = self.attachment.with_stencil();
+        self.depth_only_view = Some(self.texture.create_view(&TextureViewDescriptor {
+            label: Some("view_depth_texture_depth_only"),
+            aspect: TextureAspect::DepthOnly,
+            ..Default::default()
+        }));
+        self
+    }
+
     pub fn get_attachment(&self, store: StoreOp) -> RenderPassDepthStencilAttachment {
         self.attachment.get_attachment(store)
     }
 
+    /// The view to sample the depth from.
     pub fn view(&self) -> &TextureView {
-        &self.attachment.view
+        self.depth_only_view
+            .as_ref()
+            .unwrap_or(&self.attachment.view)
     }
 }
 
@@ -822,7 +846,16 @@ pub fn prepare_view_targets(
                 .get_texture_view(&windows, &images, &manual_texture_views)
                 .zip(target.get_texture_format(&windows, &images, &manual_texture_views))
                 .map(|(view, format)| {
-                    OutputColorAttachment::new(view.clone(), format.add_srgb_suffix())
+                    let output_color_space = match target {
+                        NormalizedRenderTarget::Window(window_ref) => windows
+                            .get(&window_ref.entity())
+                            .map_or(OutputColorSpace::Srgb, |window| window.output_color_space),
+                        _ => OutputColorSpace::Srgb,
+                    };
+                    OutputColorAttachment::new(
+                        view.clone(),
+                        surface_view_format(format, output_color_space),
+                    )
                 })
         }) else {
             continue;
@@ -847,7 +880,7 @@ pub fn prepare_view_targets(
         };
 
         let (a, b, sampled, main_texture) = textures
-            .entry((camera.target.clone(), view.hdr))
+            .entry((camera.target.clone(), view.hdr, target_size))
             .or_insert_with(|| {
                 let descriptor = TextureDescriptor {
                     label: None,
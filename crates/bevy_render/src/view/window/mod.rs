@@ -2,7 +2,7 @@ use crate::{
     render_resource::{
         BindGroupEntries, PipelineCache, SpecializedRenderPipelines, SurfaceTexture, TextureView,
     },
-    renderer::{RenderAdapter, RenderDevice, RenderInstance},
+    renderer::{RenderAdapter, RenderDevice, RenderInstance, SurfaceFormatsSender},
     texture::TextureFormatPixelInfo,
     Extract, ExtractSchedule, Render, RenderApp, RenderSet, WgpuWrapper,
 };
@@ -12,7 +12,8 @@ use bevy_ecs::{entity::EntityHashMap, prelude::*};
 use bevy_utils::warn_once;
 use bevy_utils::{default, tracing::debug, HashSet};
 use bevy_window::{
-    CompositeAlphaMode, PresentMode, PrimaryWindow, RawHandleWrapper, Window, WindowClosing,
+    CompositeAlphaMode, OutputColorSpace, PresentMode, PrimaryWindow, RawHandleWrapper, Window,
+    WindowClosing, WindowRenderRequirements,
 };
 use std::{
     num::NonZeroU32,
@@ -78,13 +79,19 @@ pub struct ExtractedWindow {
     pub size_changed: bool,
     pub present_mode_changed: bool,
     pub alpha_mode: CompositeAlphaMode,
+    /// How colors are encoded on the surface, which is only read when the surface is created.
+    pub output_color_space: OutputColorSpace,
+    pub render_requirements: WindowRenderRequirements,
     pub screenshot_func: Option<screenshot::ScreenshotFn>,
 }
 
 impl ExtractedWindow {
     fn set_swapchain_texture(&mut self, frame: wgpu::SurfaceTexture) {
         let texture_view_descriptor = TextureViewDescriptor {
-            format: Some(frame.texture.format().add_srgb_suffix()),
+            format: Some(surface_view_format(
+                frame.texture.format(),
+                self.output_color_space,
+            )),
             ..default()
         };
         self.swap_chain_texture_view = Some(TextureView::from(
@@ -94,6 +101,21 @@ impl ExtractedWindow {
     }
 }
 
+/// Returns the format of the views of a surface texture of the given format,
+/// which is what the colors rendered to a window are written to.
+///
+/// This is the sRGB version of the format, unless colors are passed through as
+/// they are with [`OutputColorSpace::LinearPassthrough`].
+pub fn surface_view_format(
+    format: TextureFormat,
+    output_color_space: OutputColorSpace,
+) -> TextureFormat {
+    match output_color_space {
+        OutputColorSpace::Srgb => format.add_srgb_suffix(),
+        OutputColorSpace::LinearPassthrough => format.remove_srgb_suffix(),
+    }
+}
+
 #[derive(Default, Resource)]
 pub struct ExtractedWindows {
     pub primary: Option<Entity>,
@@ -145,6 +167,8 @@ fn extract_windows(
             swap_chain_texture_format: None,
             present_mode_changed: false,
             alpha_mode: window.composite_alpha_mode,
+            output_color_space: window.output_color_space,
+            render_requirements: window.render_requirements,
             screenshot_func: None,
             screenshot_memory: None,
         });
@@ -155,6 +179,7 @@ fn extract_windows(
             || new_height != extracted_window.physical_height;
         extracted_window.present_mode_changed =
             window.present_mode != extracted_window.present_mode;
+        extracted_window.render_requirements = window.render_requirements;
 
         if extracted_window.size_changed {
             debug!(
@@ -379,7 +404,10 @@ pub fn prepare_windows(
                 mip_level_count: 1,
                 sample_count: 1,
                 dimension: wgpu::TextureDimension::D2,
-                format: surface_data.configuration.format.add_srgb_suffix(),
+                format: surface_view_format(
+                    surface_data.configuration.format,
+                    window.output_color_space,
+                ),
                 usage: TextureUsages::RENDER_ATTACHMENT
                     | TextureUsages::COPY_SRC
                     | TextureUsages::TEXTURE_BINDING,
@@ -450,6 +478,7 @@ pub fn create_surfaces(
     render_instance: Res<RenderInstance>,
     render_adapter: Res<RenderAdapter>,
     render_device: Res<RenderDevice>,
+    surface_formats: Option<Res<SurfaceFormatsSender>>,
 ) {
     for window in windows.windows.values() {
         let data = window_surfaces
@@ -470,19 +499,28 @@ pub fn create_surfaces(
                 };
                 let caps = surface.get_capabilities(&render_adapter);
                 let formats = caps.formats;
+                if let Some(surface_formats) = &surface_formats {
+                    surface_formats.send(window.entity, formats.clone());
+                }
                 // For future HDR output support, we'll need to request a format that supports HDR,
                 // but as of wgpu 0.15 that is not yet supported.
                 // Prefer sRGB formats for surfaces, but fall back to first available format if no sRGB formats are available.
-                let mut format = *formats.first().expect("No supported formats for surface");
-                for available_format in formats {
+                // Linear formats are preferred instead when colors are passed through without conversion.
+                let preferred_formats = match window.output_color_space {
                     // Rgba8UnormSrgb and Bgra8UnormSrgb and the only sRGB formats wgpu exposes that we can use for surfaces.
-                    if available_format == TextureFormat::Rgba8UnormSrgb
-                        || available_format == TextureFormat::Bgra8UnormSrgb
-                    {
-                        format = available_format;
-                        break;
+                    OutputColorSpace::Srgb => {
+                        [TextureFormat::Rgba8UnormSrgb, TextureFormat::Bgra8UnormSrgb]
                     }
-                }
+                    OutputColorSpace::LinearPassthrough => {
+                        [TextureFormat::Rgba8Unorm, TextureFormat::Bgra8Unorm]
+                    }
+                };
+                let format = formats
+                    .iter()
+                    .copied()
+                    .find(|format| preferred_formats.contains(format))
+                    .unwrap_or_else(|| *formats.first().expect("No supported formats for surface"));
+                let view_format = surface_view_format(format, window.output_color_space);
 
                 let configuration = wgpu::SurfaceConfiguration {
                     format,
@@ -512,8 +550,8 @@ pub fn create_surfaces(
                         }
                         CompositeAlphaMode::Inherit => wgpu::CompositeAlphaMode::Inherit,
                     },
-                    view_formats: if !format.is_srgb() {
-                        vec![format.add_srgb_suffix()]
+                    view_formats: if view_format != format {
+                        vec![view_format]
                     } else {
                         vec![]
                     },
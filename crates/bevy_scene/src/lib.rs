@@ -17,6 +17,7 @@ mod dynamic_scene_builder;
 mod scene;
 mod scene_filter;
 mod scene_loader;
+mod scene_saver;
 mod scene_spawner;
 
 #[cfg(feature = "serialize")]
@@ -32,6 +33,7 @@ pub use dynamic_scene_builder::*;
 pub use scene::*;
 pub use scene_filter::*;
 pub use scene_loader::*;
+pub use scene_saver::*;
 pub use scene_spawner::*;
 
 #[allow(missing_docs)]
@@ -45,6 +47,8 @@ pub mod prelude {
 
 use bevy_app::prelude::*;
 use bevy_asset::{AssetApp, Handle};
+#[cfg(feature = "serialize")]
+use bevy_ecs::world::FromWorld;
 
 /// Plugin that provides scene functionality to an [`App`].
 #[derive(Default)]
@@ -60,6 +64,9 @@ impl Plugin for ScenePlugin {
             .init_resource::<SceneSpawner>()
             .add_systems(SpawnScene, (scene_spawner, scene_spawner_system).chain());
 
+        let saver = SceneSaver::from_world(app.world_mut());
+        app.register_asset_saver(saver);
+
         // Register component hooks for DynamicScene
         app.world_mut()
             .register_component_hooks::<Handle<DynamicScene>>()
@@ -0,0 +1,61 @@
+#[cfg(feature = "serialize")]
+use crate::{DynamicScene, SceneLoader};
+#[cfg(feature = "serialize")]
+use bevy_asset::{
+    io::Writer,
+    saver::{AssetSaver, SavedAsset},
+    AsyncWriteExt,
+};
+use bevy_ecs::reflect::AppTypeRegistry;
+use bevy_ecs::world::{FromWorld, World};
+use bevy_reflect::TypeRegistryArc;
+use thiserror::Error;
+
+/// Asset saver for a Bevy dynamic scene (`.scn` / `.scn.ron`), used to save the scenes created at
+/// runtime with [`AssetServer::save`](bevy_asset::AssetServer::save).
+///
+/// The scenes are serialized with [`DynamicScene::serialize`], to be loaded back by [`SceneLoader`].
+#[derive(Debug)]
+pub struct SceneSaver {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for SceneSaver {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        SceneSaver {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+/// Possible errors that can be produced by [`SceneSaver`]
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum SceneSaverError {
+    /// An [IO Error](std::io::Error)
+    #[error("Error while trying to write the scene file: {0}")]
+    Io(#[from] std::io::Error),
+    /// A [RON Error](ron::Error)
+    #[error("Could not serialize the scene to RON: {0}")]
+    RonError(#[from] crate::ron::Error),
+}
+
+#[cfg(feature = "serialize")]
+impl AssetSaver for SceneSaver {
+    type Asset = DynamicScene;
+    type Settings = ();
+    type OutputLoader = SceneLoader;
+    type Error = SceneSaverError;
+
+    async fn save<'a>(
+        &'a self,
+        writer: &'a mut Writer,
+        asset: SavedAsset<'a, Self::Asset>,
+        _settings: &'a (),
+    ) -> Result<(), Self::Error> {
+        let serialized = asset.serialize(&self.type_registry.read())?;
+        writer.write_all(serialized.as_bytes()).await?;
+        Ok(())
+    }
+}
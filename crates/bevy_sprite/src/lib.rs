@@ -10,12 +10,16 @@
 //! Provides 2D sprite rendering functionality.
 mod bundle;
 mod dynamic_texture_atlas_builder;
+mod light2d;
 mod mesh2d;
 mod render;
+mod sorting;
 mod sprite;
+mod sprite_animation;
 mod texture_atlas;
 mod texture_atlas_builder;
 mod texture_slice;
+mod tilemap;
 
 pub mod prelude {
     #[allow(deprecated)]
@@ -25,9 +29,20 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
         bundle::SpriteBundle,
+        light2d::{
+            AmbientLight2d, LightOccluder2d, Lighting2d, PointLight2d, PointLight2dBundle,
+            Shadows2d, SpotLight2d, SpotLight2dBundle, SpriteNormalMap,
+        },
+        sorting::{LayerSortMode, OrderInLayer, SortingLayer, SortingLayers},
         sprite::{ImageScaleMode, Sprite},
+        sprite_animation::{
+            SpriteAnimation, SpriteAnimationClip, SpriteAnimationEvent, SpriteAnimationMode,
+            SpriteAnimationSet,
+        },
         texture_atlas::{TextureAtlas, TextureAtlasLayout},
-        texture_slice::{BorderRect, SliceScaleMode, TextureSlice, TextureSlicer},
+        texture_slice::{
+            BorderRect, ScaledImageMesh2d, SliceScaleMode, TextureSlice, TextureSlicer,
+        },
         ColorMaterial, ColorMesh2dBundle, TextureAtlasBuilder,
     };
 }
@@ -35,12 +50,16 @@ pub mod prelude {
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 pub use bundle::*;
 pub use dynamic_texture_atlas_builder::*;
+pub use light2d::*;
 pub use mesh2d::*;
 pub use render::*;
+pub use sorting::*;
 pub use sprite::*;
+pub use sprite_animation::*;
 pub use texture_atlas::*;
 pub use texture_atlas_builder::*;
 pub use texture_slice::*;
+pub use tilemap::*;
 
 use bevy_app::prelude::*;
 use bevy_asset::{load_internal_asset, AssetApp, Assets, Handle};
@@ -64,12 +83,17 @@ pub struct SpritePlugin;
 pub const SPRITE_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(2763343953151597127);
 pub const SPRITE_VIEW_BINDINGS_SHADER_HANDLE: Handle<Shader> =
     Handle::weak_from_u128(8846920112458963210);
+pub const SPRITE_IO_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(4316174447479355493);
+pub const SPRITE_BINDINGS_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(10396076716196066504);
 
 /// System set for sprite rendering.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
 pub enum SpriteSystem {
     ExtractSprites,
     ComputeSlices,
+    /// Advances the [`SpriteAnimation`]s, see [`animate_sprites`].
+    Animate,
 }
 
 /// A component that marks entities that aren't themselves sprites but become
@@ -102,18 +126,37 @@ impl Plugin for SpritePlugin {
             "render/sprite_view_bindings.wgsl",
             Shader::from_wgsl
         );
+        load_internal_asset!(
+            app,
+            SPRITE_IO_SHADER_HANDLE,
+            "render/sprite_io.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            SPRITE_BINDINGS_SHADER_HANDLE,
+            "render/sprite_bindings.wgsl",
+            Shader::from_wgsl
+        );
         app.init_asset::<TextureAtlasLayout>()
             .register_asset_reflect::<TextureAtlasLayout>()
             .register_type::<Sprite>()
             .register_type::<ImageScaleMode>()
             .register_type::<TextureSlicer>()
+            .register_type::<ScaledImageMesh2d>()
             .register_type::<Anchor>()
             .register_type::<TextureAtlas>()
             .register_type::<Mesh2dHandle>()
             .register_type::<SpriteSource>()
+            .register_type::<SortingLayer>()
+            .register_type::<OrderInLayer>()
+            .init_resource::<SortingLayers>()
             .add_plugins((
                 Mesh2dRenderPlugin,
                 ColorMaterialPlugin,
+                TilemapChunkPlugin,
+                Light2dPlugin,
+                SpriteAnimationPlugin,
                 ExtractComponentPlugin::<SpriteSource>::default(),
             ))
             .add_systems(
@@ -123,6 +166,7 @@ impl Plugin for SpritePlugin {
                     (
                         compute_slices_on_asset_event,
                         compute_slices_on_sprite_change,
+                        update_scaled_image_meshes_2d,
                     )
                         .in_set(SpriteSystem::ComputeSlices),
                     (
@@ -139,6 +183,7 @@ impl Plugin for SpritePlugin {
                 .init_resource::<SpecializedRenderPipelines<SpritePipeline>>()
                 .init_resource::<SpriteMeta>()
                 .init_resource::<ExtractedSprites>()
+                .init_resource::<RenderSpriteMaterialInstances>()
                 .init_resource::<SpriteAssetEvents>()
                 .add_render_command::<Transparent2d, DrawSprite>()
                 .add_systems(
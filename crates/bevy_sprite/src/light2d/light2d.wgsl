@@ -0,0 +1,62 @@
+#define_import_path bevy_sprite::light2d
+
+#import bevy_render::maths::PI_2
+#import bevy_sprite::{
+    light2d_types::Light2d,
+    sprite_view_bindings::{lights, shadow_map},
+}
+
+// How far fragments can be past the shadow map distance before being shadowed, in world units.
+const SHADOW_BIAS: f32 = 1.0;
+// The number of shadow map samples averaged on each side of a fragment for soft shadows.
+const SOFT_SHADOW_SAMPLES: i32 = 3;
+
+// The fraction of the light that reaches a fragment `distance` away from the light, in direction
+// `direction`.
+fn shadow(light_index: u32, light: Light2d, direction: vec2<f32>, distance: f32) -> f32 {
+    let width = i32(textureDimensions(shadow_map).x);
+    let angle = atan2(direction.y, direction.x);
+    let x = i32(fract(angle / PI_2) * f32(width));
+
+    var samples = 0;
+    var spread = 0;
+    if light.shadow_softness > 0.0 {
+        samples = SOFT_SHADOW_SAMPLES;
+        spread = max(i32(light.shadow_softness / PI_2 * f32(width)) / SOFT_SHADOW_SAMPLES, 1);
+    }
+
+    var lit = 0.0;
+    for (var i = -samples; i <= samples; i += 1) {
+        let sample_x = (x + i * spread + width) % width;
+        let reach = textureLoad(shadow_map, vec2(sample_x, i32(light_index)), 0).r * light.radius;
+        lit += select(0.0, 1.0, distance <= reach + SHADOW_BIAS);
+    }
+    return lit / f32(2 * samples + 1);
+}
+
+// The light reaching a fragment at `world_position` with the given world space `normal`, which
+// only changes the lighting of sprites with normal maps.
+fn lighting(world_position: vec2<f32>, normal: vec3<f32>) -> vec3<f32> {
+    var light_sum = lights.ambient.rgb;
+    for (var i = 0u; i < lights.light_count; i += 1u) {
+        let light = lights.lights[i];
+        let to_light = light.position - world_position;
+        let distance = length(to_light);
+        if distance >= light.radius {
+            continue;
+        }
+
+        let direction = -to_light / max(distance, 1e-4);
+        var attenuation = pow(1.0 - distance / light.radius, light.falloff);
+        attenuation *= smoothstep(light.cos_outer_angle, light.cos_inner_angle, dot(direction, light.direction));
+        if light.shadow_softness >= 0.0 && attenuation > 0.0 {
+            attenuation *= shadow(i, light, direction, distance);
+        }
+#ifdef NORMAL_MAP
+        attenuation *= max(dot(normal, normalize(vec3(to_light, light.height))), 0.0);
+#endif
+
+        light_sum += light.color.rgb * attenuation;
+    }
+    return light_sum;
+}
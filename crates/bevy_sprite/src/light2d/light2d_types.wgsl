@@ -0,0 +1,38 @@
+#define_import_path bevy_sprite::light2d_types
+
+// Must match `MAX_LIGHTS_2D` and `MAX_OCCLUDERS_2D`.
+const MAX_LIGHTS_2D: u32 = 32u;
+const MAX_OCCLUDERS_2D: u32 = 128u;
+
+struct Light2d {
+    // The linear color of the light, multiplied by its intensity.
+    color: vec4<f32>,
+    position: vec2<f32>,
+    // The direction of spot lights.
+    direction: vec2<f32>,
+    radius: f32,
+    falloff: f32,
+    height: f32,
+    // Both -2.0 for point lights, so the cone covers every direction.
+    cos_inner_angle: f32,
+    cos_outer_angle: f32,
+    // Negative for lights without shadows.
+    shadow_softness: f32,
+};
+
+// A rounded rectangle, which also describes circles and capsules.
+struct Occluder2d {
+    center: vec2<f32>,
+    half_size: vec2<f32>,
+    // The cosine and sine of the rotation of the occluder.
+    rotation: vec2<f32>,
+    corner_radius: f32,
+};
+
+struct Lights2d {
+    ambient: vec4<f32>,
+    lights: array<Light2d, MAX_LIGHTS_2D>,
+    occluders: array<Occluder2d, MAX_OCCLUDERS_2D>,
+    light_count: u32,
+    occluder_count: u32,
+};
@@ -0,0 +1,314 @@
+mod render;
+
+pub use render::*;
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Handle};
+use bevy_color::Color;
+use bevy_core_pipeline::core_2d::graph::{Core2d, Node2d};
+use bevy_ecs::prelude::*;
+use bevy_math::{
+    primitives::{Capsule2d, Circle, Rectangle},
+    Vec2,
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::Camera,
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    render_graph::{RenderGraphApp, RenderLabel, ViewNodeRunner},
+    render_resource::Shader,
+    texture::Image,
+    view::{InheritedVisibility, Visibility},
+    ExtractSchedule, Render, RenderApp, RenderSet,
+};
+use bevy_transform::components::{GlobalTransform, Transform};
+
+pub const LIGHT2D_TYPES_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(13406270349867345813);
+pub const LIGHT2D_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(4935104578839651306);
+pub const SHADOW_MAP_2D_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(11739802619240418719);
+
+/// The maximum number of 2D lights drawn at once. Further lights are ignored.
+pub const MAX_LIGHTS_2D: usize = 32;
+/// The maximum number of [`LightOccluder2d`]s casting shadows at once. Only the occluders within
+/// the radius of a light with shadows count, further ones are ignored.
+pub const MAX_OCCLUDERS_2D: usize = 128;
+
+/// Render graph nodes of 2D lighting.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+pub enum NodeLight2d {
+    /// Renders the shadow map of the lights, before the main pass of cameras with [`Lighting2d`].
+    ShadowMaps,
+}
+
+/// Adds 2D lights and shadows.
+///
+/// Sprites drawn by cameras with [`Lighting2d`] are lit by the [`AmbientLight2d`], the
+/// [`PointLight2d`]s and the [`SpotLight2d`]s, and shadowed by the [`LightOccluder2d`]s. Sprites
+/// with a [`SpriteNormalMap`] are shaded by it.
+#[derive(Default)]
+pub struct Light2dPlugin;
+
+impl Plugin for Light2dPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            LIGHT2D_TYPES_SHADER_HANDLE,
+            "light2d_types.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            LIGHT2D_SHADER_HANDLE,
+            "light2d.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            SHADOW_MAP_2D_SHADER_HANDLE,
+            "shadow_map_2d.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Lighting2d>()
+            .register_type::<AmbientLight2d>()
+            .register_type::<PointLight2d>()
+            .register_type::<SpotLight2d>()
+            .register_type::<LightOccluder2d>()
+            .register_type::<SpriteNormalMap>()
+            .init_resource::<AmbientLight2d>()
+            .add_plugins(ExtractComponentPlugin::<Lighting2d>::default());
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app
+            .init_resource::<ExtractedLights2d>()
+            .add_systems(ExtractSchedule, extract_lights_2d)
+            .add_systems(
+                Render,
+                prepare_lights_2d.in_set(RenderSet::PrepareResources),
+            )
+            .add_render_graph_node::<ViewNodeRunner<ShadowMap2dNode>>(
+                Core2d,
+                NodeLight2d::ShadowMaps,
+            )
+            .add_render_graph_edges(
+                Core2d,
+                (
+                    Node2d::StartMainPass,
+                    NodeLight2d::ShadowMaps,
+                    Node2d::MainTransparentPass,
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<Lights2dMeta>()
+                .init_resource::<ShadowMap2dPipeline>();
+        }
+    }
+}
+
+/// Lights the sprites drawn by a 2D camera.
+///
+/// Without it, sprites are drawn with their own colors, whatever the lights around them.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, ExtractComponent)]
+#[reflect(Component, Default)]
+#[extract_component_filter(With<Camera>)]
+pub struct Lighting2d;
+
+/// The light reaching every lit sprite, on top of the light of the 2D lights.
+#[derive(Resource, Reflect, Clone, Debug)]
+#[reflect(Resource, Default)]
+pub struct AmbientLight2d {
+    pub color: Color,
+    /// A multiplier of the color. Sprites are drawn with their own colors when the ambient
+    /// light is white with a brightness of `1.0`.
+    pub brightness: f32,
+}
+
+impl Default for AmbientLight2d {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            brightness: 0.25,
+        }
+    }
+}
+
+/// The shadows cast by a 2D light on the other side of [`LightOccluder2d`]s.
+///
+/// Occluders are lit themselves, their shadow starts where the light would leave them.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[reflect(Default)]
+pub enum Shadows2d {
+    /// The light goes through occluders.
+    #[default]
+    None,
+    /// The light casts shadows with sharp edges.
+    Hard,
+    /// The light casts shadows with edges blurred over `softness` radians around the light, so
+    /// the edges get softer away from the occluders.
+    Soft { softness: f32 },
+}
+
+/// A light shining in every direction in 2D, lighting the sprites within its
+/// [`radius`](Self::radius).
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+#[reflect(Component, Default)]
+pub struct PointLight2d {
+    pub color: Color,
+    /// A multiplier of the color. Sprites are drawn with their own colors at the center of a
+    /// white light with an intensity of `1.0`.
+    pub intensity: f32,
+    /// How far the light reaches, in world units.
+    pub radius: f32,
+    /// How quickly the light fades towards its radius, `1.0` fading linearly and higher values
+    /// keeping the light near the center.
+    pub falloff: f32,
+    /// How far above the sprites the light is, in world units. Only changes the lighting of
+    /// sprites with a [`SpriteNormalMap`], which are lit more from the side by lower lights.
+    pub height: f32,
+    pub shadows: Shadows2d,
+}
+
+impl Default for PointLight2d {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            intensity: 1.0,
+            radius: 300.0,
+            falloff: 2.0,
+            height: 100.0,
+            shadows: Shadows2d::None,
+        }
+    }
+}
+
+/// A light shining in a cone in 2D, towards the X axis of its [`Transform`].
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+#[reflect(Component, Default)]
+pub struct SpotLight2d {
+    pub color: Color,
+    /// A multiplier of the color, see [`PointLight2d::intensity`].
+    pub intensity: f32,
+    /// How far the light reaches, in world units.
+    pub radius: f32,
+    /// How quickly the light fades towards its radius, see [`PointLight2d::falloff`].
+    pub falloff: f32,
+    /// How far above the sprites the light is, see [`PointLight2d::height`].
+    pub height: f32,
+    /// The angle, in radians, from the direction of the light to where it starts fading.
+    pub inner_angle: f32,
+    /// The angle, in radians, from the direction of the light to where it has faded out.
+    pub outer_angle: f32,
+    pub shadows: Shadows2d,
+}
+
+impl Default for SpotLight2d {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            intensity: 1.0,
+            radius: 300.0,
+            falloff: 2.0,
+            height: 100.0,
+            inner_angle: 0.0,
+            outer_angle: std::f32::consts::FRAC_PI_4,
+            shadows: Shadows2d::None,
+        }
+    }
+}
+
+/// A shape blocking the light of 2D lights with [`Shadows2d`].
+///
+/// The shape is a rectangle with rounded corners, which also describes circles and capsules,
+/// centered on the [`Transform`] of the entity. It is rotated and scaled with the entity.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[reflect(Component, Default)]
+pub struct LightOccluder2d {
+    /// Half the size of the rectangle, without its rounded corners.
+    pub half_size: Vec2,
+    /// The radius of the corners, added around the rectangle.
+    pub corner_radius: f32,
+}
+
+impl LightOccluder2d {
+    /// Creates an occluder in the shape of a rectangle of the given `size`.
+    pub fn rectangle(size: Vec2) -> Self {
+        Self {
+            half_size: size / 2.0,
+            corner_radius: 0.0,
+        }
+    }
+
+    /// Creates an occluder in the shape of a circle.
+    pub fn circle(radius: f32) -> Self {
+        Self {
+            half_size: Vec2::ZERO,
+            corner_radius: radius,
+        }
+    }
+}
+
+impl From<Rectangle> for LightOccluder2d {
+    fn from(rectangle: Rectangle) -> Self {
+        Self {
+            half_size: rectangle.half_size,
+            corner_radius: 0.0,
+        }
+    }
+}
+
+impl From<Circle> for LightOccluder2d {
+    fn from(circle: Circle) -> Self {
+        Self::circle(circle.radius)
+    }
+}
+
+impl From<Capsule2d> for LightOccluder2d {
+    fn from(capsule: Capsule2d) -> Self {
+        Self {
+            half_size: Vec2::new(0.0, capsule.half_length),
+            corner_radius: capsule.radius,
+        }
+    }
+}
+
+/// The normal map of a sprite, shading it according to the direction of the 2D lights.
+///
+/// The normal map is sampled like the image of the sprite, including its texture atlas and
+/// flipping, so it must have the same layout. Its red, green and blue channels are the X, Y and
+/// Z components of the normals, with Y pointing up the image. It should be loaded as linear
+/// rather than sRGB, with [`ImageLoaderSettings::is_srgb`](bevy_render::texture::ImageLoaderSettings::is_srgb).
+#[derive(Component, Reflect, Clone, Debug, Default)]
+#[reflect(Component, Default)]
+pub struct SpriteNormalMap(pub Handle<Image>);
+
+/// A bundle of the components of a [`PointLight2d`].
+#[derive(Bundle, Clone, Debug, Default)]
+pub struct PointLight2dBundle {
+    pub point_light: PointLight2d,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+    /// User indication of whether an entity is visible
+    pub visibility: Visibility,
+    /// Inherited visibility of an entity.
+    pub inherited_visibility: InheritedVisibility,
+}
+
+/// A bundle of the components of a [`SpotLight2d`].
+#[derive(Bundle, Clone, Debug, Default)]
+pub struct SpotLight2dBundle {
+    pub spot_light: SpotLight2d,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+    /// User indication of whether an entity is visible
+    pub visibility: Visibility,
+    /// Inherited visibility of an entity.
+    pub inherited_visibility: InheritedVisibility,
+}
@@ -0,0 +1,396 @@
+use bevy_color::{ColorToComponents, LinearRgba};
+use bevy_core_pipeline::fullscreen_vertex_shader::fullscreen_shader_vertex_state;
+use bevy_ecs::{prelude::*, query::QueryItem};
+use bevy_math::{Vec2, Vec4};
+use bevy_render::{
+    render_graph::{NodeRunError, RenderGraphContext, ViewNode},
+    render_resource::{
+        binding_types::uniform_buffer, BindGroup, BindGroupEntries, BindGroupLayout,
+        BindGroupLayoutEntries, CachedRenderPipelineId, ColorTargetState, ColorWrites, Extent3d,
+        FragmentState, MultisampleState, Operations, PipelineCache, PrimitiveState,
+        RenderPassColorAttachment, RenderPassDescriptor, RenderPipelineDescriptor, ShaderStages,
+        ShaderType, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages, TextureView,
+        TextureViewDescriptor, UniformBuffer,
+    },
+    renderer::{RenderContext, RenderDevice, RenderQueue},
+    view::InheritedVisibility,
+    Extract,
+};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::warn_once;
+
+use super::{
+    AmbientLight2d, LightOccluder2d, Lighting2d, PointLight2d, Shadows2d, SpotLight2d,
+    MAX_LIGHTS_2D, MAX_OCCLUDERS_2D, SHADOW_MAP_2D_SHADER_HANDLE,
+};
+
+/// The number of directions around each light stored in the shadow map.
+pub const SHADOW_MAP_2D_RESOLUTION: u32 = 1024;
+/// The format of the shadow map, storing how far each light reaches in each direction as a
+/// fraction of its radius.
+pub const SHADOW_MAP_2D_FORMAT: TextureFormat = TextureFormat::R16Float;
+
+/// A 2D light as laid out in the `Light2d` struct of `light2d_types.wgsl`.
+#[derive(ShaderType, Clone, Copy, Debug, Default)]
+pub struct GpuLight2d {
+    pub color: Vec4,
+    pub position: Vec2,
+    pub direction: Vec2,
+    pub radius: f32,
+    pub falloff: f32,
+    pub height: f32,
+    pub cos_inner_angle: f32,
+    pub cos_outer_angle: f32,
+    /// Negative for lights without shadows.
+    pub shadow_softness: f32,
+}
+
+impl GpuLight2d {
+    fn point(light: &PointLight2d, transform: &GlobalTransform) -> Self {
+        Self {
+            color: (LinearRgba::from(light.color) * light.intensity).to_vec4(),
+            position: transform.translation().truncate(),
+            direction: Vec2::X,
+            radius: light.radius,
+            falloff: light.falloff,
+            height: light.height,
+            // Every direction is inside the cone.
+            cos_inner_angle: -2.0,
+            cos_outer_angle: -2.0,
+            shadow_softness: shadow_softness(light.shadows),
+        }
+    }
+
+    fn spot(light: &SpotLight2d, transform: &GlobalTransform) -> Self {
+        Self {
+            color: (LinearRgba::from(light.color) * light.intensity).to_vec4(),
+            position: transform.translation().truncate(),
+            direction: transform.right().truncate().normalize_or_zero(),
+            radius: light.radius,
+            falloff: light.falloff,
+            height: light.height,
+            cos_inner_angle: light.inner_angle.cos(),
+            cos_outer_angle: light.outer_angle.cos(),
+            shadow_softness: shadow_softness(light.shadows),
+        }
+    }
+
+    fn casts_shadows(&self) -> bool {
+        self.shadow_softness >= 0.0
+    }
+}
+
+fn shadow_softness(shadows: Shadows2d) -> f32 {
+    match shadows {
+        Shadows2d::None => -1.0,
+        Shadows2d::Hard => 0.0,
+        Shadows2d::Soft { softness } => softness.max(0.0),
+    }
+}
+
+/// A [`LightOccluder2d`] as laid out in the `Occluder2d` struct of `light2d_types.wgsl`.
+#[derive(ShaderType, Clone, Copy, Debug, Default)]
+pub struct GpuOccluder2d {
+    pub center: Vec2,
+    pub half_size: Vec2,
+    /// The cosine and sine of the rotation of the occluder.
+    pub rotation: Vec2,
+    pub corner_radius: f32,
+}
+
+impl GpuOccluder2d {
+    fn new(occluder: &LightOccluder2d, transform: &GlobalTransform) -> Self {
+        let matrix = transform.affine().matrix3;
+        let x_axis = matrix.x_axis.truncate();
+        let scale = Vec2::new(x_axis.length(), matrix.y_axis.truncate().length());
+        Self {
+            center: transform.translation().truncate(),
+            half_size: occluder.half_size * scale,
+            rotation: x_axis.normalize_or(Vec2::X),
+            corner_radius: occluder.corner_radius * scale.min_element(),
+        }
+    }
+
+    /// Whether the occluder is within the radius of the `light`.
+    fn is_near(&self, light: &GpuLight2d) -> bool {
+        let bounding_radius = self.half_size.length() + self.corner_radius;
+        self.center.distance(light.position) < light.radius + bounding_radius
+    }
+}
+
+/// The lights and occluders of all the 2D cameras, as laid out in the `Lights2d` struct of
+/// `light2d_types.wgsl`.
+#[derive(ShaderType, Clone, Debug)]
+pub struct GpuLights2d {
+    pub ambient: Vec4,
+    pub lights: [GpuLight2d; MAX_LIGHTS_2D],
+    pub occluders: [GpuOccluder2d; MAX_OCCLUDERS_2D],
+    pub light_count: u32,
+    pub occluder_count: u32,
+}
+
+impl Default for GpuLights2d {
+    fn default() -> Self {
+        Self {
+            ambient: Vec4::ZERO,
+            lights: [GpuLight2d::default(); MAX_LIGHTS_2D],
+            occluders: [GpuOccluder2d::default(); MAX_OCCLUDERS_2D],
+            light_count: 0,
+            occluder_count: 0,
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct ExtractedLights2d {
+    pub ambient: LinearRgba,
+    pub lights: Vec<GpuLight2d>,
+    pub occluders: Vec<GpuOccluder2d>,
+}
+
+pub fn extract_lights_2d(
+    mut extracted_lights: ResMut<ExtractedLights2d>,
+    ambient_light: Extract<Res<AmbientLight2d>>,
+    point_lights: Extract<
+        Query<(
+            &PointLight2d,
+            &GlobalTransform,
+            Option<&InheritedVisibility>,
+        )>,
+    >,
+    spot_lights: Extract<Query<(&SpotLight2d, &GlobalTransform, Option<&InheritedVisibility>)>>,
+    occluders: Extract<
+        Query<(
+            &LightOccluder2d,
+            &GlobalTransform,
+            Option<&InheritedVisibility>,
+        )>,
+    >,
+) {
+    let is_visible = |visibility: Option<&InheritedVisibility>| {
+        visibility
+            .map(|visibility| visibility.get())
+            .unwrap_or(true)
+    };
+
+    extracted_lights.ambient = LinearRgba::from(ambient_light.color) * ambient_light.brightness;
+
+    extracted_lights.lights.clear();
+    extracted_lights.lights.extend(
+        point_lights
+            .iter()
+            .filter(|(.., visibility)| is_visible(*visibility))
+            .map(|(light, transform, _)| GpuLight2d::point(light, transform)),
+    );
+    extracted_lights.lights.extend(
+        spot_lights
+            .iter()
+            .filter(|(.., visibility)| is_visible(*visibility))
+            .map(|(light, transform, _)| GpuLight2d::spot(light, transform)),
+    );
+    if extracted_lights.lights.len() > MAX_LIGHTS_2D {
+        warn_once!(
+            "There are more than {MAX_LIGHTS_2D} 2D lights, only the first {MAX_LIGHTS_2D} are drawn"
+        );
+        extracted_lights.lights.truncate(MAX_LIGHTS_2D);
+    }
+
+    let ExtractedLights2d {
+        lights,
+        occluders: extracted_occluders,
+        ..
+    } = &mut *extracted_lights;
+    extracted_occluders.clear();
+    extracted_occluders.extend(
+        occluders
+            .iter()
+            .filter(|(.., visibility)| is_visible(*visibility))
+            .map(|(occluder, transform, _)| GpuOccluder2d::new(occluder, transform))
+            .filter(|occluder| {
+                lights
+                    .iter()
+                    .any(|light| light.casts_shadows() && occluder.is_near(light))
+            }),
+    );
+    if extracted_occluders.len() > MAX_OCCLUDERS_2D {
+        warn_once!(
+            "There are more than {MAX_OCCLUDERS_2D} 2D light occluders near lights with shadows, \
+            only the first {MAX_OCCLUDERS_2D} cast shadows"
+        );
+        extracted_occluders.truncate(MAX_OCCLUDERS_2D);
+    }
+}
+
+/// The GPU resources of 2D lighting, shared by all the cameras.
+#[derive(Resource)]
+pub struct Lights2dMeta {
+    pub uniform: UniformBuffer<GpuLights2d>,
+    /// Stores how far each light reaches in each direction around it, in a row per light.
+    pub shadow_map: TextureView,
+    pub shadow_map_bind_group: Option<BindGroup>,
+    /// Whether any light casts shadows this frame.
+    pub shadows: bool,
+}
+
+impl FromWorld for Lights2dMeta {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let render_queue = world.resource::<RenderQueue>();
+
+        let mut uniform = UniformBuffer::from(GpuLights2d::default());
+        uniform.set_label(Some("lights_2d_uniform"));
+        uniform.write_buffer(render_device, render_queue);
+
+        let shadow_map = render_device
+            .create_texture(&TextureDescriptor {
+                label: Some("shadow_map_2d"),
+                size: Extent3d {
+                    width: SHADOW_MAP_2D_RESOLUTION,
+                    height: MAX_LIGHTS_2D as u32,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: SHADOW_MAP_2D_FORMAT,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            })
+            .create_view(&TextureViewDescriptor::default());
+
+        Self {
+            uniform,
+            shadow_map,
+            shadow_map_bind_group: None,
+            shadows: false,
+        }
+    }
+}
+
+pub fn prepare_lights_2d(
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    extracted_lights: Res<ExtractedLights2d>,
+    mut lights_meta: ResMut<Lights2dMeta>,
+    pipeline: Res<ShadowMap2dPipeline>,
+) {
+    let lights = lights_meta.uniform.get_mut();
+    lights.ambient = extracted_lights.ambient.to_vec4();
+    lights.light_count = extracted_lights.lights.len() as u32;
+    lights.lights[..extracted_lights.lights.len()].copy_from_slice(&extracted_lights.lights);
+    lights.occluder_count = extracted_lights.occluders.len() as u32;
+    lights.occluders[..extracted_lights.occluders.len()]
+        .copy_from_slice(&extracted_lights.occluders);
+
+    lights_meta.shadows = extracted_lights
+        .lights
+        .iter()
+        .any(GpuLight2d::casts_shadows);
+    lights_meta
+        .uniform
+        .write_buffer(&render_device, &render_queue);
+
+    // The uniform has a fixed size, so its buffer is never reallocated.
+    if lights_meta.shadow_map_bind_group.is_none() {
+        lights_meta.shadow_map_bind_group = Some(render_device.create_bind_group(
+            "shadow_map_2d_bind_group",
+            &pipeline.layout,
+            &BindGroupEntries::single(lights_meta.uniform.binding().unwrap()),
+        ));
+    }
+}
+
+#[derive(Resource)]
+pub struct ShadowMap2dPipeline {
+    pub layout: BindGroupLayout,
+    pub pipeline_id: CachedRenderPipelineId,
+}
+
+impl FromWorld for ShadowMap2dPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let layout = render_device.create_bind_group_layout(
+            "shadow_map_2d_layout",
+            &BindGroupLayoutEntries::single(
+                ShaderStages::FRAGMENT,
+                uniform_buffer::<GpuLights2d>(false),
+            ),
+        );
+        let pipeline_id =
+            world
+                .resource_mut::<PipelineCache>()
+                .queue_render_pipeline(RenderPipelineDescriptor {
+                    label: Some("shadow_map_2d_pipeline".into()),
+                    layout: vec![layout.clone()],
+                    vertex: fullscreen_shader_vertex_state(),
+                    fragment: Some(FragmentState {
+                        shader: SHADOW_MAP_2D_SHADER_HANDLE,
+                        shader_defs: Vec::new(),
+                        entry_point: "fragment".into(),
+                        targets: vec![Some(ColorTargetState {
+                            format: SHADOW_MAP_2D_FORMAT,
+                            blend: None,
+                            write_mask: ColorWrites::ALL,
+                        })],
+                    }),
+                    primitive: PrimitiveState::default(),
+                    depth_stencil: None,
+                    multisample: MultisampleState::default(),
+                    push_constant_ranges: Vec::new(),
+                });
+
+        Self {
+            layout,
+            pipeline_id,
+        }
+    }
+}
+
+/// Renders the shadow map of the 2D lights with shadows, see [`Lights2dMeta::shadow_map`].
+///
+/// The shadow map is shared by all the cameras, so it is rendered again for each camera with
+/// [`Lighting2d`].
+#[derive(Default)]
+pub struct ShadowMap2dNode;
+
+impl ViewNode for ShadowMap2dNode {
+    type ViewQuery = &'static Lighting2d;
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        _lighting: QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let lights_meta = world.resource::<Lights2dMeta>();
+        if !lights_meta.shadows {
+            return Ok(());
+        }
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let pipeline_id = world.resource::<ShadowMap2dPipeline>().pipeline_id;
+        let (Some(pipeline), Some(bind_group)) = (
+            pipeline_cache.get_render_pipeline(pipeline_id),
+            &lights_meta.shadow_map_bind_group,
+        ) else {
+            return Ok(());
+        };
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("shadow_map_2d_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: &lights_meta.shadow_map,
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
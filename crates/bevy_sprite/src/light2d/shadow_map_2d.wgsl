@@ -0,0 +1,79 @@
+// Renders the shadow map of 2D lights. Each row is a light and each column a direction around it,
+// storing how far the light reaches in that direction as a fraction of its radius.
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+#import bevy_render::maths::PI_2
+#import bevy_sprite::light2d_types::{Lights2d, Occluder2d}
+
+@group(0) @binding(0) var<uniform> lights: Lights2d;
+
+const MAX_STEPS: u32 = 64u;
+const MIN_STEP: f32 = 0.5;
+
+fn occluder_distance(position: vec2<f32>, occluder: Occluder2d) -> f32 {
+    let offset = position - occluder.center;
+    let local = vec2(
+        dot(offset, occluder.rotation),
+        dot(offset, vec2(-occluder.rotation.y, occluder.rotation.x)),
+    );
+    let q = abs(local) - occluder.half_size;
+    return length(max(q, vec2(0.0))) + min(max(q.x, q.y), 0.0) - occluder.corner_radius;
+}
+
+fn scene_distance(position: vec2<f32>) -> f32 {
+    var distance = 1e30;
+    for (var i = 0u; i < lights.occluder_count; i += 1u) {
+        distance = min(distance, occluder_distance(position, lights.occluders[i]));
+    }
+    return distance;
+}
+
+// Marches from `start` along `direction` until leaving the occluders, stepping by the distance to
+// their edge. Inside convex shapes, that never steps past the exit.
+fn march_out(origin: vec2<f32>, direction: vec2<f32>, start: f32, end: f32) -> f32 {
+    var t = start;
+    for (var i = 0u; i < MAX_STEPS && t < end; i += 1u) {
+        let distance = scene_distance(origin + direction * t);
+        if distance >= 0.0 {
+            break;
+        }
+        t += max(-distance, MIN_STEP);
+    }
+    return t;
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let light_index = u32(in.position.y);
+    if light_index >= lights.light_count {
+        return vec4(1.0);
+    }
+    let light = lights.lights[light_index];
+    if light.shadow_softness < 0.0 {
+        return vec4(1.0);
+    }
+
+    let angle = in.uv.x * PI_2;
+    let direction = vec2(cos(angle), sin(angle));
+
+    // The occluders the light is in don't cast shadows.
+    var t = march_out(light.position, direction, 0.0, light.radius);
+
+    // Sphere trace to the next occluder.
+    var hit = false;
+    for (var i = 0u; i < MAX_STEPS && t < light.radius; i += 1u) {
+        let distance = scene_distance(light.position + direction * t);
+        if distance < MIN_STEP {
+            hit = true;
+            break;
+        }
+        t += distance;
+    }
+    if !hit {
+        return vec4(1.0);
+    }
+
+    // Occluders are lit themselves, their shadow starts where the light would leave them.
+    t = march_out(light.position, direction, t, light.radius);
+    return vec4(min(t / light.radius, 1.0), 0.0, 0.0, 1.0);
+}
@@ -2,12 +2,12 @@ use crate::{Material2d, Material2dPlugin, MaterialMesh2dBundle};
 use bevy_app::{App, Plugin};
 use bevy_asset::{load_internal_asset, Asset, AssetApp, Assets, Handle};
 use bevy_color::{Color, ColorToComponents, LinearRgba};
-use bevy_math::Vec4;
+use bevy_math::{Rect, UVec2, Vec4};
 use bevy_reflect::prelude::*;
 use bevy_render::{
-    render_asset::RenderAssets,
+    render_asset::{RenderAssetUsages, RenderAssets},
     render_resource::*,
-    texture::{GpuImage, Image},
+    texture::{GpuImage, Image, ImageSampler},
 };
 
 pub const COLOR_MATERIAL_SHADER_HANDLE: Handle<Shader> =
@@ -49,6 +49,30 @@ pub struct ColorMaterial {
     #[texture(1)]
     #[sampler(2)]
     pub texture: Option<Handle<Image>>,
+    /// A texture covering [`Self::visibility_mask_rect`], to hide the parts of the mesh where its
+    /// red channel is 0, like a fog of war.
+    ///
+    /// Gameplay systems update the data of the image, see [`ColorMaterial::new_visibility_mask`].
+    #[texture(3)]
+    #[sampler(4)]
+    pub visibility_mask: Option<Handle<Image>>,
+    /// The area covered by the [`Self::visibility_mask`], in world space.
+    ///
+    /// The mesh is hidden outside of it.
+    pub visibility_mask_rect: Rect,
+    /// How the [`Self::visibility_mask`] is applied.
+    pub visibility_mask_mode: VisibilityMaskMode,
+}
+
+/// How the [`ColorMaterial::visibility_mask`] is applied to the color.
+#[derive(Reflect, Debug, Default, Clone, Copy, PartialEq)]
+#[reflect(Default, Debug, PartialEq)]
+pub enum VisibilityMaskMode {
+    /// The color is multiplied by the mask, darkening the hidden parts.
+    #[default]
+    Multiply,
+    /// The fragments where the mask is below the threshold are discarded.
+    Discard(f32),
 }
 
 impl ColorMaterial {
@@ -56,6 +80,27 @@ impl ColorMaterial {
     pub fn from_color(color: impl Into<Color>) -> Self {
         Self::from(color.into())
     }
+
+    /// Creates an image to use as a [`ColorMaterial::visibility_mask`], with one byte per texel
+    /// that's initially 0 (hidden).
+    ///
+    /// Setting a byte of [`Image::data`] to 255 reveals the texel, rows start at the top of the
+    /// [`ColorMaterial::visibility_mask_rect`].
+    pub fn new_visibility_mask(size: UVec2) -> Image {
+        let mut image = Image::new_fill(
+            Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0],
+            TextureFormat::R8Unorm,
+            RenderAssetUsages::default(),
+        );
+        image.sampler = ImageSampler::linear();
+        image
+    }
 }
 
 impl Default for ColorMaterial {
@@ -63,6 +108,9 @@ impl Default for ColorMaterial {
         ColorMaterial {
             color: Color::WHITE,
             texture: None,
+            visibility_mask: None,
+            visibility_mask_rect: Rect::default(),
+            visibility_mask_mode: VisibilityMaskMode::default(),
         }
     }
 }
@@ -90,6 +138,8 @@ bitflags::bitflags! {
     #[repr(transparent)]
     pub struct ColorMaterialFlags: u32 {
         const TEXTURE           = 1 << 0;
+        const VISIBILITY_MASK   = 1 << 1;
+        const VISIBILITY_MASK_DISCARD = 1 << 2;
         const NONE              = 0;
         const UNINITIALIZED     = 0xFFFF;
     }
@@ -99,6 +149,9 @@ bitflags::bitflags! {
 #[derive(Clone, Default, ShaderType)]
 pub struct ColorMaterialUniform {
     pub color: Vec4,
+    /// The min and max corners of the visibility mask rect.
+    pub visibility_mask_rect: Vec4,
+    pub visibility_mask_threshold: f32,
     pub flags: u32,
 }
 
@@ -108,9 +161,23 @@ impl AsBindGroupShaderType<ColorMaterialUniform> for ColorMaterial {
         if self.texture.is_some() {
             flags |= ColorMaterialFlags::TEXTURE;
         }
+        let mut visibility_mask_threshold = 0.0;
+        if self.visibility_mask.is_some() {
+            flags |= ColorMaterialFlags::VISIBILITY_MASK;
+            if let VisibilityMaskMode::Discard(threshold) = self.visibility_mask_mode {
+                flags |= ColorMaterialFlags::VISIBILITY_MASK_DISCARD;
+                visibility_mask_threshold = threshold;
+            }
+        }
 
         ColorMaterialUniform {
             color: LinearRgba::from(self.color).to_f32_array().into(),
+            visibility_mask_rect: self
+                .visibility_mask_rect
+                .min
+                .extend(self.visibility_mask_rect.max.x)
+                .extend(self.visibility_mask_rect.max.y),
+            visibility_mask_threshold,
             flags: flags.bits(),
         }
     }
@@ -9,14 +9,21 @@
 
 struct ColorMaterial {
     color: vec4<f32>,
+    // The min (xy) and max (zw) corners of the visibility mask, in world space.
+    visibility_mask_rect: vec4<f32>,
+    visibility_mask_threshold: f32,
     // 'flags' is a bit field indicating various options. u32 is 32 bits so we have up to 32 options.
     flags: u32,
 };
 const COLOR_MATERIAL_FLAGS_TEXTURE_BIT: u32 = 1u;
+const COLOR_MATERIAL_FLAGS_VISIBILITY_MASK_BIT: u32 = 2u;
+const COLOR_MATERIAL_FLAGS_VISIBILITY_MASK_DISCARD_BIT: u32 = 4u;
 
 @group(2) @binding(0) var<uniform> material: ColorMaterial;
 @group(2) @binding(1) var texture: texture_2d<f32>;
 @group(2) @binding(2) var texture_sampler: sampler;
+@group(2) @binding(3) var visibility_mask: texture_2d<f32>;
+@group(2) @binding(4) var visibility_mask_sampler: sampler;
 
 @fragment
 fn fragment(
@@ -29,6 +36,23 @@ fn fragment(
     if ((material.flags & COLOR_MATERIAL_FLAGS_TEXTURE_BIT) != 0u) {
         output_color = output_color * textureSample(texture, texture_sampler, mesh.uv);
     }
+    if ((material.flags & COLOR_MATERIAL_FLAGS_VISIBILITY_MASK_BIT) != 0u) {
+        let rect = material.visibility_mask_rect;
+        var mask_uv = (mesh.world_position.xy - rect.xy) / (rect.zw - rect.xy);
+        // The rows of the image start at the top
+        mask_uv.y = 1.0 - mask_uv.y;
+        var visibility = textureSample(visibility_mask, visibility_mask_sampler, mask_uv).r;
+        if any(mask_uv < vec2(0.0)) || any(mask_uv > vec2(1.0)) {
+            visibility = 0.0;
+        }
+        if ((material.flags & COLOR_MATERIAL_FLAGS_VISIBILITY_MASK_DISCARD_BIT) != 0u) {
+            if visibility < material.visibility_mask_threshold {
+                discard;
+            }
+        } else {
+            output_color = vec4(output_color.rgb * visibility, output_color.a);
+        }
+    }
 #ifdef TONEMAP_IN_SHADER
     output_color = tonemapping::tone_mapping(output_color, view.color_grading);
 #endif
@@ -1,7 +1,7 @@
 use bevy_app::{App, Plugin};
 use bevy_asset::{Asset, AssetApp, AssetId, AssetServer, Handle};
 use bevy_core_pipeline::{
-    core_2d::Transparent2d,
+    core_2d::{Camera2d, Sort2dKey, Transparent2d},
     tonemapping::{DebandDither, Tonemapping},
 };
 use bevy_derive::{Deref, DerefMut};
@@ -24,6 +24,7 @@ use bevy_render::{
         AsBindGroup, AsBindGroupError, BindGroup, BindGroupId, BindGroupLayout,
         OwnedBindingResource, PipelineCache, RenderPipelineDescriptor, Shader, ShaderRef,
         SpecializedMeshPipeline, SpecializedMeshPipelineError, SpecializedMeshPipelines,
+        StencilState,
     },
     renderer::RenderDevice,
     texture::{FallbackImage, GpuImage},
@@ -121,6 +122,21 @@ pub trait Material2d: AsBindGroup + Asset + Clone + Sized {
         0.0
     }
 
+    /// Returns the stencil test and operations of this material, or `None` to ignore the stencil buffer.
+    ///
+    /// This only applies to views with a stencil texture, see [`Camera2d::stencil`]. The reference
+    /// value the stencil is compared to and written with is [`Material2d::stencil_reference`].
+    #[inline]
+    fn stencil(&self) -> Option<StencilState> {
+        None
+    }
+
+    /// Returns the reference value used by the [`Material2d::stencil`] test and operations.
+    #[inline]
+    fn stencil_reference(&self) -> u32 {
+        0
+    }
+
     /// Customizes the default [`RenderPipelineDescriptor`].
     #[allow(unused_variables)]
     #[inline]
@@ -207,6 +223,8 @@ pub struct Material2dPipeline<M: Material2d> {
 pub struct Material2dKey<M: Material2d> {
     pub mesh_key: Mesh2dPipelineKey,
     pub bind_group_data: M::Data,
+    /// The [`Material2d::stencil`] state, used if the [`Mesh2dPipelineKey::STENCIL`] flag is set.
+    pub stencil: Option<StencilState>,
 }
 
 impl<M: Material2d> Eq for Material2dKey<M> where M::Data: PartialEq {}
@@ -216,7 +234,9 @@ where
     M::Data: PartialEq,
 {
     fn eq(&self, other: &Self) -> bool {
-        self.mesh_key == other.mesh_key && self.bind_group_data == other.bind_group_data
+        self.mesh_key == other.mesh_key
+            && self.bind_group_data == other.bind_group_data
+            && self.stencil == other.stencil
     }
 }
 
@@ -228,6 +248,7 @@ where
         Self {
             mesh_key: self.mesh_key,
             bind_group_data: self.bind_group_data.clone(),
+            stencil: self.stencil.clone(),
         }
     }
 }
@@ -239,6 +260,7 @@ where
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.mesh_key.hash(state);
         self.bind_group_data.hash(state);
+        self.stencil.hash(state);
     }
 }
 
@@ -279,6 +301,14 @@ where
             self.material2d_layout.clone(),
         ];
 
+        if key.mesh_key.contains(Mesh2dPipelineKey::STENCIL) {
+            if let (Some(depth_stencil), Some(stencil)) =
+                (descriptor.depth_stencil.as_mut(), &key.stencil)
+            {
+                depth_stencil.stencil = stencil.clone();
+            }
+        }
+
         M::specialize(&mut descriptor, layout, key)?;
         Ok(descriptor)
     }
@@ -313,6 +343,7 @@ type DrawMaterial2d<M> = (
     SetMesh2dViewBindGroup<0>,
     SetMesh2dBindGroup<1>,
     SetMaterial2dBindGroup<M, 2>,
+    SetMaterial2dStencilReference<M>,
     DrawMesh2d,
 );
 
@@ -348,6 +379,39 @@ impl<P: PhaseItem, M: Material2d, const I: usize> RenderCommand<P>
     }
 }
 
+/// Sets the [`Material2d::stencil_reference`] of a [`Material2d`] that uses the stencil buffer.
+pub struct SetMaterial2dStencilReference<M: Material2d>(PhantomData<M>);
+impl<P: PhaseItem, M: Material2d> RenderCommand<P> for SetMaterial2dStencilReference<M> {
+    type Param = (
+        SRes<RenderAssets<PreparedMaterial2d<M>>>,
+        SRes<RenderMaterial2dInstances<M>>,
+    );
+    type ViewQuery = ();
+    type ItemQuery = ();
+
+    #[inline]
+    fn render<'w>(
+        item: &P,
+        _view: (),
+        _item_query: Option<()>,
+        (materials, material_instances): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let materials = materials.into_inner();
+        let material_instances = material_instances.into_inner();
+        let Some(material_instance) = material_instances.get(&item.entity()) else {
+            return RenderCommandResult::Failure;
+        };
+        let Some(material2d) = materials.get(*material_instance) else {
+            return RenderCommandResult::Failure;
+        };
+        if material2d.stencil.is_some() {
+            pass.set_stencil_reference(material2d.stencil_reference);
+        }
+        RenderCommandResult::Success
+    }
+}
+
 pub const fn tonemapping_pipeline_key(tonemapping: Tonemapping) -> Mesh2dPipelineKey {
     match tonemapping {
         Tonemapping::None => Mesh2dPipelineKey::TONEMAP_METHOD_NONE,
@@ -381,6 +445,7 @@ pub fn queue_material2d_meshes<M: Material2d>(
         &VisibleEntities,
         Option<&Tonemapping>,
         Option<&DebandDither>,
+        Option<&Camera2d>,
     )>,
 ) where
     M::Data: PartialEq + Eq + Hash + Clone,
@@ -389,7 +454,7 @@ pub fn queue_material2d_meshes<M: Material2d>(
         return;
     }
 
-    for (view_entity, view, visible_entities, tonemapping, dither) in &mut views {
+    for (view_entity, view, visible_entities, tonemapping, dither, camera_2d) in &mut views {
         let Some(transparent_phase) = transparent_render_phases.get_mut(&view_entity) else {
             continue;
         };
@@ -408,6 +473,9 @@ pub fn queue_material2d_meshes<M: Material2d>(
                 view_key |= Mesh2dPipelineKey::DEBAND_DITHER;
             }
         }
+        if camera_2d.is_some_and(|camera_2d| camera_2d.stencil) {
+            view_key |= Mesh2dPipelineKey::STENCIL;
+        }
         for visible_entity in visible_entities.iter::<WithMesh2d>() {
             let Some(material_asset_id) = render_material_instances.get(visible_entity) else {
                 continue;
@@ -430,6 +498,7 @@ pub fn queue_material2d_meshes<M: Material2d>(
                 Material2dKey {
                     mesh_key,
                     bind_group_data: material_2d.key.clone(),
+                    stencil: material_2d.stencil.clone(),
                 },
                 &mesh.layout,
             );
@@ -444,16 +513,19 @@ pub fn queue_material2d_meshes<M: Material2d>(
 
             mesh_instance.material_bind_group_id = material_2d.get_bind_group_id();
 
-            let mesh_z = mesh_instance.transforms.world_from_local.translation.z;
+            let sort_key = mesh_instance.sort_key;
             transparent_phase.add(Transparent2d {
                 entity: *visible_entity,
                 draw_function: draw_transparent_2d,
                 pipeline: pipeline_id,
                 // NOTE: Back-to-front ordering for transparent with ascending sort means far should have the
-                // lowest sort key and getting closer should increase. As we have
-                // -z in front of the camera, the largest distance is -far with values increasing toward the
-                // camera. As such we can just use mesh_z as the distance
-                sort_key: FloatOrd(mesh_z + material_2d.depth_bias),
+                // lowest sort key and getting closer should increase. With the default sort mode,
+                // the depth is the mesh's z, as we have -z in front of the camera, the largest
+                // distance being -far with values increasing toward the camera.
+                sort_key: Sort2dKey {
+                    depth: FloatOrd(sort_key.depth.0 + material_2d.depth_bias),
+                    ..sort_key
+                },
                 // Batching is done in batch_and_prepare_render_phase
                 batch_range: 0..1,
                 extra_index: PhaseItemExtraIndex::NONE,
@@ -471,6 +543,10 @@ pub struct PreparedMaterial2d<T: Material2d> {
     pub bind_group: BindGroup,
     pub key: T::Data,
     pub depth_bias: f32,
+    /// The [`Material2d::stencil`] state of the material.
+    pub stencil: Option<StencilState>,
+    /// The [`Material2d::stencil_reference`] value of the material.
+    pub stencil_reference: u32,
 }
 
 impl<T: Material2d> PreparedMaterial2d<T> {
@@ -504,6 +580,8 @@ impl<M: Material2d> RenderAsset for PreparedMaterial2d<M> {
                 bind_group: prepared.bind_group,
                 key: prepared.data,
                 depth_bias: material.depth_bias(),
+                stencil: material.stencil(),
+                stencil_reference: material.stencil_reference(),
             }),
             Err(AsBindGroupError::RetryNextUpdate) => {
                 Err(PrepareAssetError::RetryNextUpdate(material))
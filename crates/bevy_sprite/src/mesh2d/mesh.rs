@@ -1,7 +1,7 @@
 use bevy_app::Plugin;
 use bevy_asset::{load_internal_asset, AssetId, Handle};
 
-use bevy_core_pipeline::core_2d::Transparent2d;
+use bevy_core_pipeline::core_2d::{Sort2dKey, Transparent2d, CORE_2D_STENCIL_FORMAT};
 use bevy_core_pipeline::tonemapping::{
     get_lut_bind_group_layout_entries, get_lut_bindings, Tonemapping, TonemappingLuts,
 };
@@ -38,7 +38,7 @@ use bevy_render::{
 };
 use bevy_transform::components::GlobalTransform;
 
-use crate::Material2dBindGroupId;
+use crate::{Material2dBindGroupId, OrderInLayer, SortingLayer, SortingLayers};
 
 /// Component for rendering with meshes in the 2d pipeline, usually with a [2d material](crate::Material2d) such as [`ColorMaterial`](crate::ColorMaterial).
 ///
@@ -197,6 +197,8 @@ bitflags::bitflags! {
 
 pub struct RenderMesh2dInstance {
     pub transforms: Mesh2dTransforms,
+    /// The key the mesh is sorted by in the [`Transparent2d`] phase, see [`SortingLayers`].
+    pub sort_key: Sort2dKey,
     pub mesh_asset_id: AssetId<Mesh>,
     pub material_bind_group_id: Material2dBindGroupId,
     pub automatic_batching: bool,
@@ -212,6 +214,7 @@ pub fn extract_mesh2d(
     mut commands: Commands,
     mut previous_len: Local<usize>,
     mut render_mesh_instances: ResMut<RenderMesh2dInstances>,
+    sorting_layers: Extract<Res<SortingLayers>>,
     query: Extract<
         Query<(
             Entity,
@@ -219,13 +222,16 @@ pub fn extract_mesh2d(
             &GlobalTransform,
             &Mesh2dHandle,
             Has<NoAutomaticBatching>,
+            Option<&SortingLayer>,
+            Option<&OrderInLayer>,
         )>,
     >,
 ) {
     render_mesh_instances.clear();
     let mut entities = Vec::with_capacity(*previous_len);
 
-    for (entity, view_visibility, transform, handle, no_automatic_batching) in &query {
+    for (entity, view_visibility, transform, handle, no_automatic_batching, layer, order) in &query
+    {
         if !view_visibility.get() {
             continue;
         }
@@ -239,6 +245,7 @@ pub fn extract_mesh2d(
                     world_from_local: (&transform.affine()).into(),
                     flags: MeshFlags::empty().bits(),
                 },
+                sort_key: sorting_layers.sort_key(layer, order, transform),
                 mesh_asset_id: handle.0.id(),
                 material_bind_group_id: Material2dBindGroupId::default(),
                 automatic_batching: !no_automatic_batching,
@@ -387,6 +394,7 @@ bitflags::bitflags! {
         const HDR                               = 1 << 0;
         const TONEMAP_IN_SHADER                 = 1 << 1;
         const DEBAND_DITHER                     = 1 << 2;
+        const STENCIL                           = 1 << 3; // The view has a stencil texture, see `Camera2d::stencil`
         const MSAA_RESERVED_BITS                = Self::MSAA_MASK_BITS << Self::MSAA_SHIFT_BITS;
         const PRIMITIVE_TOPOLOGY_RESERVED_BITS  = Self::PRIMITIVE_TOPOLOGY_MASK_BITS << Self::PRIMITIVE_TOPOLOGY_SHIFT_BITS;
         const TONEMAP_METHOD_RESERVED_BITS      = Self::TONEMAP_METHOD_MASK_BITS << Self::TONEMAP_METHOD_SHIFT_BITS;
@@ -566,7 +574,15 @@ impl SpecializedMeshPipeline for Mesh2dPipeline {
                 topology: key.primitive_topology(),
                 strip_index_format: None,
             },
-            depth_stencil: None,
+            depth_stencil: key
+                .contains(Mesh2dPipelineKey::STENCIL)
+                .then_some(DepthStencilState {
+                    format: CORE_2D_STENCIL_FORMAT,
+                    depth_write_enabled: false,
+                    depth_compare: CompareFunction::Always,
+                    stencil: StencilState::default(),
+                    bias: DepthBiasState::default(),
+                }),
             multisample: MultisampleState {
                 count: key.msaa_samples(),
                 mask: !0,
@@ -1,13 +1,18 @@
 use std::ops::Range;
 
+mod sprite_material;
+
+pub use sprite_material::*;
+
 use crate::{
     texture_atlas::{TextureAtlas, TextureAtlasLayout},
-    ComputedTextureSlices, Sprite, WithSprite, SPRITE_SHADER_HANDLE,
+    ComputedTextureSlices, GpuLights2d, Lighting2d, Lights2dMeta, OrderInLayer, SortingLayer,
+    SortingLayers, Sprite, SpriteNormalMap, WithSprite, SPRITE_SHADER_HANDLE,
 };
-use bevy_asset::{AssetEvent, AssetId, Assets, Handle};
+use bevy_asset::{AssetEvent, AssetId, Assets, Handle, UntypedAssetId};
 use bevy_color::{ColorToComponents, LinearRgba};
 use bevy_core_pipeline::{
-    core_2d::Transparent2d,
+    core_2d::{Camera2d, Sort2dKey, Transparent2d, CORE_2D_STENCIL_FORMAT},
     tonemapping::{
         get_lut_bind_group_layout_entries, get_lut_bindings, DebandDither, Tonemapping,
         TonemappingLuts,
@@ -18,9 +23,10 @@ use bevy_ecs::{
     prelude::*,
     system::{lifetimeless::*, SystemParamItem, SystemState},
 };
-use bevy_math::{Affine3A, FloatOrd, Quat, Rect, Vec2, Vec4};
+use bevy_math::{Affine3A, Quat, Rect, Vec2, Vec4};
 use bevy_render::{
-    render_asset::RenderAssets,
+    camera::{Camera, PixelPerfect},
+    render_asset::{RenderAssetUsages, RenderAssets},
     render_phase::{
         DrawFunctions, PhaseItem, PhaseItemExtraIndex, RenderCommand, RenderCommandResult,
         SetItemPipeline, TrackedRenderPass, ViewSortedRenderPhases,
@@ -45,11 +51,13 @@ use bevy_utils::HashMap;
 use bytemuck::{Pod, Zeroable};
 use fixedbitset::FixedBitSet;
 
-#[derive(Resource)]
+#[derive(Resource, Clone)]
 pub struct SpritePipeline {
     view_layout: BindGroupLayout,
     material_layout: BindGroupLayout,
     pub dummy_white_gpu_image: GpuImage,
+    /// A normal map of normals facing the camera, bound for sprites without a [`SpriteNormalMap`].
+    pub dummy_normal_map_gpu_image: GpuImage,
 }
 
 impl FromWorld for SpritePipeline {
@@ -76,6 +84,11 @@ impl FromWorld for SpritePipeline {
                         2,
                         tonemapping_lut_entries[1].visibility(ShaderStages::FRAGMENT),
                     ),
+                    (3, uniform_buffer::<GpuLights2d>(false)),
+                    (
+                        4,
+                        texture_2d(TextureSampleType::Float { filterable: false }),
+                    ),
                 ),
             ),
         );
@@ -87,11 +100,11 @@ impl FromWorld for SpritePipeline {
                 (
                     texture_2d(TextureSampleType::Float { filterable: true }),
                     sampler(SamplerBindingType::Filtering),
+                    texture_2d(TextureSampleType::Float { filterable: true }),
                 ),
             ),
         );
-        let dummy_white_gpu_image = {
-            let image = Image::default();
+        let create_dummy_gpu_image = |image: Image| {
             let texture = render_device.create_texture(&image.texture_descriptor);
             let sampler = match image.sampler {
                 ImageSampler::Default => (**default_sampler).clone(),
@@ -121,11 +134,20 @@ impl FromWorld for SpritePipeline {
                 mip_level_count: image.texture_descriptor.mip_level_count,
             }
         };
+        let dummy_white_gpu_image = create_dummy_gpu_image(Image::default());
+        let dummy_normal_map_gpu_image = create_dummy_gpu_image(Image::new_fill(
+            Extent3d::default(),
+            TextureDimension::D2,
+            &[128, 128, 255, 255],
+            TextureFormat::Rgba8Unorm,
+            RenderAssetUsages::RENDER_WORLD,
+        ));
 
         SpritePipeline {
             view_layout,
             material_layout,
             dummy_white_gpu_image,
+            dummy_normal_map_gpu_image,
         }
     }
 }
@@ -140,6 +162,10 @@ bitflags::bitflags! {
         const HDR                               = 1 << 0;
         const TONEMAP_IN_SHADER                 = 1 << 1;
         const DEBAND_DITHER                     = 1 << 2;
+        const STENCIL                           = 1 << 3; // The view has a stencil texture, see `Camera2d::stencil`
+        const DISTANCE_FIELD                    = 1 << 4; // The sprites are distance fields, see `ExtractedSprite::distance_field`
+        const LIGHTING                          = 1 << 5; // The view has `Lighting2d`
+        const NORMAL_MAP                        = 1 << 6; // The sprites are lit with a `SpriteNormalMap`
         const MSAA_RESERVED_BITS                = Self::MSAA_MASK_BITS << Self::MSAA_SHIFT_BITS;
         const TONEMAP_METHOD_RESERVED_BITS      = Self::TONEMAP_METHOD_MASK_BITS << Self::TONEMAP_METHOD_SHIFT_BITS;
         const TONEMAP_METHOD_NONE               = 0 << Self::TONEMAP_METHOD_SHIFT_BITS;
@@ -180,6 +206,64 @@ impl SpritePipelineKey {
             SpritePipelineKey::NONE
         }
     }
+
+    /// Returns the key for the sprites of a view with the given settings.
+    pub fn from_view(
+        view: &ExtractedView,
+        tonemapping: Option<&Tonemapping>,
+        dither: Option<&DebandDither>,
+        camera_2d: Option<&Camera2d>,
+        lighting_2d: Option<&Lighting2d>,
+        msaa: &Msaa,
+    ) -> Self {
+        let mut view_key =
+            SpritePipelineKey::from_hdr(view.hdr) | Self::from_msaa_samples(msaa.samples());
+
+        if !view.hdr {
+            if let Some(tonemapping) = tonemapping {
+                view_key |= SpritePipelineKey::TONEMAP_IN_SHADER;
+                view_key |= match tonemapping {
+                    Tonemapping::None => SpritePipelineKey::TONEMAP_METHOD_NONE,
+                    Tonemapping::Reinhard => SpritePipelineKey::TONEMAP_METHOD_REINHARD,
+                    Tonemapping::ReinhardLuminance => {
+                        SpritePipelineKey::TONEMAP_METHOD_REINHARD_LUMINANCE
+                    }
+                    Tonemapping::AcesFitted => SpritePipelineKey::TONEMAP_METHOD_ACES_FITTED,
+                    Tonemapping::AgX => SpritePipelineKey::TONEMAP_METHOD_AGX,
+                    Tonemapping::SomewhatBoringDisplayTransform => {
+                        SpritePipelineKey::TONEMAP_METHOD_SOMEWHAT_BORING_DISPLAY_TRANSFORM
+                    }
+                    Tonemapping::TonyMcMapface => SpritePipelineKey::TONEMAP_METHOD_TONY_MC_MAPFACE,
+                    Tonemapping::BlenderFilmic => SpritePipelineKey::TONEMAP_METHOD_BLENDER_FILMIC,
+                };
+            }
+            if let Some(DebandDither::Enabled) = dither {
+                view_key |= SpritePipelineKey::DEBAND_DITHER;
+            }
+        }
+
+        if camera_2d.is_some_and(|camera_2d| camera_2d.stencil) {
+            view_key |= SpritePipelineKey::STENCIL;
+        }
+
+        if lighting_2d.is_some() {
+            view_key |= SpritePipelineKey::LIGHTING;
+        }
+
+        view_key
+    }
+
+    /// Returns the key for the `sprite` in a view with this key.
+    pub fn for_sprite(self, sprite: &ExtractedSprite) -> Self {
+        let mut key = self;
+        if sprite.distance_field {
+            key |= SpritePipelineKey::DISTANCE_FIELD;
+        }
+        if sprite.normal_map_id.is_some() && key.contains(SpritePipelineKey::LIGHTING) {
+            key |= SpritePipelineKey::NORMAL_MAP;
+        }
+        key
+    }
 }
 
 impl SpecializedRenderPipeline for SpritePipeline {
@@ -225,6 +309,18 @@ impl SpecializedRenderPipeline for SpritePipeline {
             }
         }
 
+        if key.contains(SpritePipelineKey::DISTANCE_FIELD) {
+            shader_defs.push("DISTANCE_FIELD".into());
+        }
+
+        if key.contains(SpritePipelineKey::LIGHTING) {
+            shader_defs.push("LIGHTING".into());
+        }
+
+        if key.contains(SpritePipelineKey::NORMAL_MAP) {
+            shader_defs.push("NORMAL_MAP".into());
+        }
+
         let format = match key.contains(SpritePipelineKey::HDR) {
             true => ViewTarget::TEXTURE_FORMAT_HDR,
             false => TextureFormat::bevy_default(),
@@ -234,19 +330,19 @@ impl SpecializedRenderPipeline for SpritePipeline {
             array_stride: 80,
             step_mode: VertexStepMode::Instance,
             attributes: vec![
-                // @location(0) i_model_transpose_col0: vec4<f32>,
+                // @location(0) i_model_transpose_col_x: vec4<f32>,
                 VertexAttribute {
                     format: VertexFormat::Float32x4,
                     offset: 0,
                     shader_location: 0,
                 },
-                // @location(1) i_model_transpose_col1: vec4<f32>,
+                // @location(1) i_model_transpose_col_y: vec4<f32>,
                 VertexAttribute {
                     format: VertexFormat::Float32x4,
                     offset: 16,
                     shader_location: 1,
                 },
-                // @location(2) i_model_transpose_col2: vec4<f32>,
+                // @location(2) i_model_transpose_col_z: vec4<f32>,
                 VertexAttribute {
                     format: VertexFormat::Float32x4,
                     offset: 32,
@@ -294,7 +390,15 @@ impl SpecializedRenderPipeline for SpritePipeline {
                 topology: PrimitiveTopology::TriangleList,
                 strip_index_format: None,
             },
-            depth_stencil: None,
+            depth_stencil: key
+                .contains(SpritePipelineKey::STENCIL)
+                .then_some(DepthStencilState {
+                    format: CORE_2D_STENCIL_FORMAT,
+                    depth_write_enabled: false,
+                    depth_compare: CompareFunction::Always,
+                    stencil: StencilState::default(),
+                    bias: DepthBiasState::default(),
+                }),
             multisample: MultisampleState {
                 count: key.msaa_samples(),
                 mask: !0,
@@ -308,6 +412,8 @@ impl SpecializedRenderPipeline for SpritePipeline {
 
 pub struct ExtractedSprite {
     pub transform: GlobalTransform,
+    /// The key the sprite is sorted by in the [`Transparent2d`] phase, see [`SortingLayers`].
+    pub sort_key: Sort2dKey,
     pub color: LinearRgba,
     /// Select an area of the texture
     pub rect: Option<Rect>,
@@ -316,12 +422,18 @@ pub struct ExtractedSprite {
     /// Asset ID of the [`Image`] of this sprite
     /// PERF: storing an `AssetId` instead of `Handle<Image>` enables some optimizations (`ExtractedSprite` becomes `Copy` and doesn't need to be dropped)
     pub image_handle_id: AssetId<Image>,
+    /// Asset ID of the [`SpriteNormalMap`] of this sprite, if any.
+    pub normal_map_id: Option<AssetId<Image>>,
     pub flip_x: bool,
     pub flip_y: bool,
     pub anchor: Vec2,
     /// For cases where additional [`ExtractedSprites`] are created during extraction, this stores the
     /// entity that caused that creation for use in determining visibility.
     pub original_entity: Option<Entity>,
+    /// Whether the image is a multi-channel signed distance field, such as the glyphs of text
+    /// rendered with distance fields. The sprite is then filled with its color where the median
+    /// of the red, green and blue channels is over one half.
+    pub distance_field: bool,
 }
 
 #[derive(Resource, Default)]
@@ -349,7 +461,9 @@ pub fn extract_sprite_events(
 pub fn extract_sprites(
     mut commands: Commands,
     mut extracted_sprites: ResMut<ExtractedSprites>,
+    mut sprite_material_instances: ResMut<RenderSpriteMaterialInstances>,
     texture_atlases: Extract<Res<Assets<TextureAtlasLayout>>>,
+    sorting_layers: Extract<Res<SortingLayers>>,
     sprite_query: Extract<
         Query<(
             Entity,
@@ -359,19 +473,48 @@ pub fn extract_sprites(
             &Handle<Image>,
             Option<&TextureAtlas>,
             Option<&ComputedTextureSlices>,
+            Option<&SortingLayer>,
+            Option<&OrderInLayer>,
+            Option<&SpriteNormalMap>,
         )>,
     >,
+    pixel_perfect_cameras: Extract<Query<(&Camera, &PixelPerfect)>>,
 ) {
     extracted_sprites.sprites.clear();
-    for (entity, view_visibility, sprite, transform, handle, sheet, slices) in sprite_query.iter() {
+    sprite_material_instances.clear();
+    // Sprites are snapped to the grid of virtual pixels of the first snapping pixel perfect camera.
+    let pixel_size = pixel_perfect_cameras
+        .iter()
+        .find(|(camera, pixel_perfect)| camera.is_active && pixel_perfect.snap)
+        .map(|(camera, pixel_perfect)| pixel_perfect.world_pixel_size(camera))
+        .filter(|pixel_size| pixel_size.is_finite());
+    for (
+        entity,
+        view_visibility,
+        sprite,
+        transform,
+        handle,
+        sheet,
+        slices,
+        layer,
+        order,
+        normal_map,
+    ) in sprite_query.iter()
+    {
         if !view_visibility.get() {
             continue;
         }
 
+        let sort_key = sorting_layers.sort_key(layer, order, transform);
+        let transform = &match pixel_size {
+            Some(pixel_size) => snap_to_pixel_grid(transform, pixel_size),
+            None => *transform,
+        };
+        let normal_map_id = normal_map.map(|normal_map| normal_map.0.id());
         if let Some(slices) = slices {
             extracted_sprites.sprites.extend(
                 slices
-                    .extract_sprites(transform, entity, sprite, handle)
+                    .extract_sprites(transform, sort_key, entity, sprite, handle, normal_map_id)
                     .map(|e| (commands.spawn_empty().id(), e)),
             );
         } else {
@@ -394,20 +537,34 @@ pub fn extract_sprites(
                 ExtractedSprite {
                     color: sprite.color.into(),
                     transform: *transform,
+                    sort_key,
                     rect,
                     // Pass the custom size
                     custom_size: sprite.custom_size,
                     flip_x: sprite.flip_x,
                     flip_y: sprite.flip_y,
                     image_handle_id: handle.id(),
+                    normal_map_id,
                     anchor: sprite.anchor.as_vec(),
                     original_entity: None,
+                    distance_field: false,
                 },
             );
         }
     }
 }
 
+/// Rounds the translation of a transform to a multiple of the given pixel size on the x and y
+/// axes.
+fn snap_to_pixel_grid(transform: &GlobalTransform, pixel_size: Vec2) -> GlobalTransform {
+    let mut affine = transform.affine();
+    let translation = affine.translation.truncate();
+    let snapped = (translation / pixel_size).round() * pixel_size;
+    affine.translation.x = snapped.x;
+    affine.translation.y = snapped.y;
+    affine.into()
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, Pod, Zeroable)]
 struct SpriteInstance {
@@ -456,12 +613,17 @@ pub struct SpriteViewBindGroup {
 #[derive(Component, PartialEq, Eq, Clone)]
 pub struct SpriteBatch {
     image_handle_id: AssetId<Image>,
+    /// The [`SpriteNormalMap`] of the sprites of the batch, if any is loaded.
+    normal_map_id: Option<AssetId<Image>>,
+    /// The [`SpriteMaterial`] of the sprites of the batch, if any.
+    material_id: Option<UntypedAssetId>,
     range: Range<u32>,
 }
 
 #[derive(Resource, Default)]
 pub struct ImageBindGroups {
-    values: HashMap<AssetId<Image>, BindGroup>,
+    /// The bind groups of each image and normal map.
+    values: HashMap<(AssetId<Image>, Option<AssetId<Image>>), BindGroup>,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -473,6 +635,7 @@ pub fn queue_sprites(
     pipeline_cache: Res<PipelineCache>,
     msaa: Res<Msaa>,
     extracted_sprites: Res<ExtractedSprites>,
+    sprite_material_instances: Res<RenderSpriteMaterialInstances>,
     mut transparent_render_phases: ResMut<ViewSortedRenderPhases<Transparent2d>>,
     mut views: Query<(
         Entity,
@@ -480,43 +643,24 @@ pub fn queue_sprites(
         &ExtractedView,
         Option<&Tonemapping>,
         Option<&DebandDither>,
+        Option<&Camera2d>,
+        Option<&Lighting2d>,
     )>,
 ) {
-    let msaa_key = SpritePipelineKey::from_msaa_samples(msaa.samples());
-
     let draw_sprite_function = draw_functions.read().id::<DrawSprite>();
 
-    for (view_entity, visible_entities, view, tonemapping, dither) in &mut views {
+    for (view_entity, visible_entities, view, tonemapping, dither, camera_2d, lighting_2d) in
+        &mut views
+    {
         let Some(transparent_phase) = transparent_render_phases.get_mut(&view_entity) else {
             continue;
         };
 
-        let mut view_key = SpritePipelineKey::from_hdr(view.hdr) | msaa_key;
+        let view_key =
+            SpritePipelineKey::from_view(view, tonemapping, dither, camera_2d, lighting_2d, &msaa);
 
-        if !view.hdr {
-            if let Some(tonemapping) = tonemapping {
-                view_key |= SpritePipelineKey::TONEMAP_IN_SHADER;
-                view_key |= match tonemapping {
-                    Tonemapping::None => SpritePipelineKey::TONEMAP_METHOD_NONE,
-                    Tonemapping::Reinhard => SpritePipelineKey::TONEMAP_METHOD_REINHARD,
-                    Tonemapping::ReinhardLuminance => {
-                        SpritePipelineKey::TONEMAP_METHOD_REINHARD_LUMINANCE
-                    }
-                    Tonemapping::AcesFitted => SpritePipelineKey::TONEMAP_METHOD_ACES_FITTED,
-                    Tonemapping::AgX => SpritePipelineKey::TONEMAP_METHOD_AGX,
-                    Tonemapping::SomewhatBoringDisplayTransform => {
-                        SpritePipelineKey::TONEMAP_METHOD_SOMEWHAT_BORING_DISPLAY_TRANSFORM
-                    }
-                    Tonemapping::TonyMcMapface => SpritePipelineKey::TONEMAP_METHOD_TONY_MC_MAPFACE,
-                    Tonemapping::BlenderFilmic => SpritePipelineKey::TONEMAP_METHOD_BLENDER_FILMIC,
-                };
-            }
-            if let Some(DebandDither::Enabled) = dither {
-                view_key |= SpritePipelineKey::DEBAND_DITHER;
-            }
-        }
-
-        let pipeline = pipelines.specialize(&pipeline_cache, &sprite_pipeline, view_key);
+        // The pipelines of the view, specialized on demand for distance fields and normal maps.
+        let mut view_pipelines = [None; 4];
 
         view_entities.clear();
         view_entities.extend(
@@ -536,15 +680,25 @@ pub fn queue_sprites(
                 continue;
             }
 
-            // These items will be sorted by depth with other phase items
-            let sort_key = FloatOrd(extracted_sprite.transform.translation().z);
+            // Sprites with a material are queued by `queue_material_sprites`
+            if sprite_material_instances.contains_key(entity) {
+                continue;
+            }
+
+            let sprite_key = view_key.for_sprite(extracted_sprite);
+            let pipeline_index = sprite_key.contains(SpritePipelineKey::DISTANCE_FIELD) as usize
+                | (sprite_key.contains(SpritePipelineKey::NORMAL_MAP) as usize) << 1;
+            let pipeline = *view_pipelines[pipeline_index].get_or_insert_with(|| {
+                pipelines.specialize(&pipeline_cache, &sprite_pipeline, sprite_key)
+            });
 
             // Add the item to the render phase
             transparent_phase.add(Transparent2d {
                 draw_function: draw_sprite_function,
                 pipeline,
                 entity: *entity,
-                sort_key,
+                // These items will be sorted by layer, order and depth with other phase items
+                sort_key: extracted_sprite.sort_key,
                 // batch_range and dynamic_offset will be calculated in prepare_sprites
                 batch_range: 0..0,
                 extra_index: PhaseItemExtraIndex::NONE,
@@ -563,8 +717,12 @@ pub fn prepare_sprite_view_bind_groups(
     tonemapping_luts: Res<TonemappingLuts>,
     images: Res<RenderAssets<GpuImage>>,
     fallback_image: Res<FallbackImage>,
+    lights_meta: Res<Lights2dMeta>,
 ) {
-    let Some(view_binding) = view_uniforms.uniforms.binding() else {
+    let (Some(view_binding), Some(lights_binding)) = (
+        view_uniforms.uniforms.binding(),
+        lights_meta.uniform.binding(),
+    ) else {
         return;
     };
 
@@ -578,6 +736,8 @@ pub fn prepare_sprite_view_bind_groups(
                 (0, view_binding.clone()),
                 (1, lut_bindings.0),
                 (2, lut_bindings.1),
+                (3, lights_binding.clone()),
+                (4, &lights_meta.shadow_map),
             )),
         );
 
@@ -598,6 +758,7 @@ pub fn prepare_sprite_image_bind_groups(
     mut image_bind_groups: ResMut<ImageBindGroups>,
     gpu_images: Res<RenderAssets<GpuImage>>,
     extracted_sprites: Res<ExtractedSprites>,
+    sprite_material_instances: Res<RenderSpriteMaterialInstances>,
     mut phases: ResMut<ViewSortedRenderPhases<Transparent2d>>,
     events: Res<SpriteAssetEvents>,
 ) {
@@ -608,7 +769,9 @@ pub fn prepare_sprite_image_bind_groups(
             // Images don't have dependencies
             AssetEvent::LoadedWithDependencies { .. } => {}
             AssetEvent::Unused { id } | AssetEvent::Modified { id } | AssetEvent::Removed { id } => {
-                image_bind_groups.values.remove(id);
+                image_bind_groups
+                    .values
+                    .retain(|(image, normal_map), _| image != id && *normal_map != Some(*id));
             }
         };
     }
@@ -627,6 +790,9 @@ pub fn prepare_sprite_image_bind_groups(
         let mut batch_item_index = 0;
         let mut batch_image_size = Vec2::ZERO;
         let mut batch_image_handle = AssetId::invalid();
+        let mut batch_normal_map_id = None;
+        let mut batch_material_id = None;
+        let mut batch_distance_field = false;
 
         // Iterate through the phase items and detect when successive sprites that can be batched.
         // Spawn an entity with a `SpriteBatch` component for each possible batch.
@@ -640,18 +806,31 @@ pub fn prepare_sprite_image_bind_groups(
                 batch_image_handle = AssetId::invalid();
                 continue;
             };
-
-            let batch_image_changed = batch_image_handle != extracted_sprite.image_handle_id;
+            let material_id = sprite_material_instances.get(&item.entity).copied();
+            // Normal maps that aren't loaded yet are replaced by the dummy normal map
+            let normal_map = extracted_sprite
+                .normal_map_id
+                .and_then(|id| Some((id, gpu_images.get(id)?)));
+            let normal_map_id = normal_map.map(|(id, _)| id);
+
+            // The image and normal map are bound together, a change of either starts a new batch
+            let batch_image_changed = batch_image_handle != extracted_sprite.image_handle_id
+                || batch_normal_map_id != normal_map_id;
             if batch_image_changed {
                 let Some(gpu_image) = gpu_images.get(extracted_sprite.image_handle_id) else {
                     continue;
                 };
+                let gpu_normal_map = normal_map.map_or(
+                    &sprite_pipeline.dummy_normal_map_gpu_image,
+                    |(_, gpu_normal_map)| gpu_normal_map,
+                );
 
                 batch_image_size = gpu_image.size.as_vec2();
                 batch_image_handle = extracted_sprite.image_handle_id;
+                batch_normal_map_id = normal_map_id;
                 image_bind_groups
                     .values
-                    .entry(batch_image_handle)
+                    .entry((batch_image_handle, batch_normal_map_id))
                     .or_insert_with(|| {
                         render_device.create_bind_group(
                             "sprite_material_bind_group",
@@ -659,11 +838,19 @@ pub fn prepare_sprite_image_bind_groups(
                             &BindGroupEntries::sequential((
                                 &gpu_image.texture_view,
                                 &gpu_image.sampler,
+                                &gpu_normal_map.texture_view,
                             )),
                         )
                     });
             }
 
+            // Sprites with different materials or pipelines can't share a batch either
+            let batch_changed = batch_image_changed
+                || batch_material_id != material_id
+                || batch_distance_field != extracted_sprite.distance_field;
+            batch_material_id = material_id;
+            batch_distance_field = extracted_sprite.distance_field;
+
             // By default, the size of the quad is the size of the texture
             let mut quad_size = batch_image_size;
 
@@ -713,13 +900,15 @@ pub fn prepare_sprite_image_bind_groups(
                     &uv_offset_scale,
                 ));
 
-            if batch_image_changed {
+            if batch_changed {
                 batch_item_index = item_index;
 
                 batches.push((
                     item.entity,
                     SpriteBatch {
                         image_handle_id: batch_image_handle,
+                        normal_map_id: batch_normal_map_id,
+                        material_id,
                         range: index..index,
                     },
                 ));
@@ -811,7 +1000,7 @@ impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetSpriteTextureBindGrou
             I,
             image_bind_groups
                 .values
-                .get(&batch.image_handle_id)
+                .get(&(batch.image_handle_id, batch.normal_map_id))
                 .unwrap(),
             &[],
         );
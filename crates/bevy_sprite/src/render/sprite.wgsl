@@ -7,25 +7,15 @@
     view::View,
 }
 
-#import bevy_sprite::sprite_view_bindings::view
-
-struct VertexInput {
-    @builtin(vertex_index) index: u32,
-    // NOTE: Instance-rate vertex buffer members prefixed with i_
-    // NOTE: i_model_transpose_colN are the 3 columns of a 3x4 matrix that is the transpose of the
-    // affine 4x3 model matrix.
-    @location(0) i_model_transpose_col0: vec4<f32>,
-    @location(1) i_model_transpose_col1: vec4<f32>,
-    @location(2) i_model_transpose_col2: vec4<f32>,
-    @location(3) i_color: vec4<f32>,
-    @location(4) i_uv_offset_scale: vec4<f32>,
+#import bevy_sprite::{
+    sprite_bindings::{sprite_texture, sprite_sampler, sprite_normal_map},
+    sprite_io::{VertexInput, VertexOutput},
+    sprite_view_bindings::view,
 }
 
-struct VertexOutput {
-    @builtin(position) clip_position: vec4<f32>,
-    @location(0) uv: vec2<f32>,
-    @location(1) @interpolate(flat) color: vec4<f32>,
-};
+#ifdef LIGHTING
+#import bevy_sprite::light2d
+#endif
 
 @vertex
 fn vertex(in: VertexInput) -> VertexOutput {
@@ -37,23 +27,62 @@ fn vertex(in: VertexInput) -> VertexOutput {
         0.0
     );
 
-    out.clip_position = view.clip_from_world * affine3_to_square(mat3x4<f32>(
-        in.i_model_transpose_col0,
-        in.i_model_transpose_col1,
-        in.i_model_transpose_col2,
-    )) * vec4<f32>(vertex_position, 1.0);
+    let world_from_local = affine3_to_square(mat3x4<f32>(
+        in.i_model_transpose_col_x,
+        in.i_model_transpose_col_y,
+        in.i_model_transpose_col_z,
+    ));
+    let world_position = world_from_local * vec4<f32>(vertex_position, 1.0);
+    out.clip_position = view.clip_from_world * world_position;
     out.uv = vec2<f32>(vertex_position.xy) * in.i_uv_offset_scale.zw + in.i_uv_offset_scale.xy;
     out.color = in.i_color;
+    out.world_position = world_position.xy;
+    // The image is upside down in UV space unless it is flipped vertically.
+    let uv_sign = sign(in.i_uv_offset_scale.zw);
+    out.world_tangents = vec4<f32>(
+        normalize(world_from_local[0].xy) * uv_sign.x,
+        normalize(world_from_local[1].xy) * -uv_sign.y,
+    );
 
     return out;
 }
 
-@group(1) @binding(0) var sprite_texture: texture_2d<f32>;
-@group(1) @binding(1) var sprite_sampler: sampler;
+#ifdef DISTANCE_FIELD
+// The range of the distance fields in texels, see `bevy_text::DISTANCE_FIELD_RANGE`.
+const DISTANCE_FIELD_RANGE: f32 = 4.0;
+
+// The coverage of a multi-channel signed distance field at `uv`, antialiased over a pixel on
+// screen whatever the scale it's drawn at.
+fn distance_field_coverage(distances: vec4<f32>, uv: vec2<f32>) -> f32 {
+    let texture_size = vec2<f32>(textureDimensions(sprite_texture));
+    let texels_per_pixel = fwidth(uv) * texture_size;
+    let screen_range = max(0.5 * dot(vec2(DISTANCE_FIELD_RANGE) / texels_per_pixel, vec2(1.0)), 1.0);
+    let median = max(min(distances.r, distances.g), min(max(distances.r, distances.g), distances.b));
+    return saturate(screen_range * (median - 0.5) + 0.5);
+}
+#endif
 
 @fragment
 fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+#ifdef DISTANCE_FIELD
+    let distances = textureSample(sprite_texture, sprite_sampler, in.uv);
+    var color = vec4(in.color.rgb, in.color.a * distance_field_coverage(distances, in.uv));
+#else
     var color = in.color * textureSample(sprite_texture, sprite_sampler, in.uv);
+#endif
+
+#ifdef LIGHTING
+#ifdef NORMAL_MAP
+    let tangent_normal = textureSample(sprite_normal_map, sprite_sampler, in.uv).xyz * 2.0 - 1.0;
+    let normal = normalize(vec3<f32>(
+        in.world_tangents.xy * tangent_normal.x + in.world_tangents.zw * tangent_normal.y,
+        tangent_normal.z,
+    ));
+#else
+    let normal = vec3<f32>(0.0, 0.0, 1.0);
+#endif
+    color = vec4<f32>(color.rgb * light2d::lighting(in.world_position, normal), color.a);
+#endif
 
 #ifdef TONEMAP_IN_SHADER
     color = tonemapping::tone_mapping(color, view.color_grading);
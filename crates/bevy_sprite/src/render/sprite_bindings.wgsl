@@ -0,0 +1,6 @@
+#define_import_path bevy_sprite::sprite_bindings
+
+@group(1) @binding(0) var sprite_texture: texture_2d<f32>;
+@group(1) @binding(1) var sprite_sampler: sampler;
+// The normal map of the sprite, see `SpriteNormalMap`. Only sampled with `NORMAL_MAP`.
+@group(1) @binding(2) var sprite_normal_map: texture_2d<f32>;
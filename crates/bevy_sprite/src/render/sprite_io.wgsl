@@ -0,0 +1,22 @@
+#define_import_path bevy_sprite::sprite_io
+
+struct VertexInput {
+    @builtin(vertex_index) index: u32,
+    // NOTE: Instance-rate vertex buffer members prefixed with i_
+    // NOTE: i_model_transpose_col_* are the 3 columns of a 3x4 matrix that is the transpose of the
+    // affine 4x3 model matrix.
+    @location(0) i_model_transpose_col_x: vec4<f32>,
+    @location(1) i_model_transpose_col_y: vec4<f32>,
+    @location(2) i_model_transpose_col_z: vec4<f32>,
+    @location(3) i_color: vec4<f32>,
+    @location(4) i_uv_offset_scale: vec4<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) @interpolate(flat) color: vec4<f32>,
+    @location(2) world_position: vec2<f32>,
+    // The world space directions of the x and y axes of the sprite's image, in `xy` and `zw`.
+    @location(3) @interpolate(flat) world_tangents: vec4<f32>,
+};
@@ -0,0 +1,472 @@
+use std::{hash::Hash, marker::PhantomData};
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{Asset, AssetApp, AssetServer, Handle, UntypedAssetId};
+use bevy_core_pipeline::{
+    core_2d::{Camera2d, Transparent2d},
+    tonemapping::{DebandDither, Tonemapping},
+};
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+    entity::EntityHashMap,
+    prelude::*,
+    system::{
+        lifetimeless::{Read, SRes},
+        SystemParamItem,
+    },
+};
+use bevy_render::{
+    render_asset::{
+        prepare_assets, PrepareAssetError, RenderAsset, RenderAssetPlugin, RenderAssets,
+    },
+    render_phase::{
+        AddRenderCommand, DrawFunctions, PhaseItem, PhaseItemExtraIndex, RenderCommand,
+        RenderCommandResult, SetItemPipeline, TrackedRenderPass, ViewSortedRenderPhases,
+    },
+    render_resource::{
+        AsBindGroup, AsBindGroupError, BindGroup, BindGroupLayout, OwnedBindingResource,
+        PipelineCache, RenderPipelineDescriptor, Shader, ShaderRef, SpecializedRenderPipeline,
+        SpecializedRenderPipelines,
+    },
+    renderer::RenderDevice,
+    texture::{FallbackImage, GpuImage},
+    view::{ExtractedView, Msaa, VisibleEntities},
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+};
+use fixedbitset::FixedBitSet;
+
+use crate::{
+    DrawSpriteBatch, ExtractedSprites, Lighting2d, SetSpriteTextureBindGroup,
+    SetSpriteViewBindGroup, SpriteBatch, SpritePipeline, SpritePipelineKey, SpriteSystem,
+    WithSprite,
+};
+
+/// Sprite materials are used alongside [`SpriteMaterialPlugin`] to render [`Sprite`](crate::Sprite)
+/// entities with custom shader logic, by adding a [`Handle`] to the material next to the sprite.
+///
+/// Unlike [`Material2d`](crate::Material2d), sprite materials keep the sprite pipeline: the
+/// sprite's image, color, rect, flipping, atlas and slicing all still apply, and sprites sharing
+/// both an image and a material are still drawn in a single batch.
+///
+/// Sprite materials must implement [`AsBindGroup`] to define how data will be transferred to the GPU and bound in shaders.
+/// [`AsBindGroup`] can be derived, which makes generating bindings straightforward. See the [`AsBindGroup`] docs for details.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_sprite::{SpriteBundle, SpriteMaterial};
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_reflect::TypePath;
+/// # use bevy_render::render_resource::{AsBindGroup, ShaderRef};
+/// # use bevy_color::LinearRgba;
+/// # use bevy_asset::{Assets, Asset, AssetServer};
+///
+/// #[derive(AsBindGroup, Debug, Clone, Asset, TypePath)]
+/// pub struct DissolveMaterial {
+///     #[uniform(0)]
+///     edge_color: LinearRgba,
+/// }
+///
+/// // All functions on `SpriteMaterial` have default impls. You only need to implement the
+/// // functions that are relevant for your material.
+/// impl SpriteMaterial for DissolveMaterial {
+///     fn fragment_shader() -> ShaderRef {
+///         "shaders/dissolve_sprite.wgsl".into()
+///     }
+/// }
+///
+/// // Spawn a sprite using `DissolveMaterial`.
+/// fn setup(
+///     mut commands: Commands,
+///     mut materials: ResMut<Assets<DissolveMaterial>>,
+///     asset_server: Res<AssetServer>,
+/// ) {
+///     commands.spawn((
+///         SpriteBundle {
+///             texture: asset_server.load("some_image.png"),
+///             ..Default::default()
+///         },
+///         materials.add(DissolveMaterial {
+///             edge_color: LinearRgba::RED,
+///         }),
+///     ));
+/// }
+/// ```
+///
+/// The shaders can reuse the sprite's vertex inputs and outputs, as well as its texture, which
+/// stays bound at `@group(1)`. The material's bindings are at `@group(2)`:
+///
+/// ```wgsl
+/// #import bevy_sprite::{
+///     sprite_bindings::{sprite_texture, sprite_sampler},
+///     sprite_io::VertexOutput,
+/// }
+///
+/// struct DissolveMaterial {
+///     edge_color: vec4<f32>,
+/// }
+///
+/// @group(2) @binding(0) var<uniform> material: DissolveMaterial;
+///
+/// @fragment
+/// fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+///     return in.color * textureSample(sprite_texture, sprite_sampler, in.uv) * material.edge_color;
+/// }
+/// ```
+///
+/// Custom fragment shaders are responsible for tonemapping when the `TONEMAP_IN_SHADER` shader
+/// def is set, as the default sprite shader does.
+pub trait SpriteMaterial: AsBindGroup + Asset + Clone + Sized {
+    /// Returns this material's vertex shader. If [`ShaderRef::Default`] is returned, the default sprite vertex shader
+    /// will be used.
+    fn vertex_shader() -> ShaderRef {
+        ShaderRef::Default
+    }
+
+    /// Returns this material's fragment shader. If [`ShaderRef::Default`] is returned, the default sprite fragment shader
+    /// will be used.
+    fn fragment_shader() -> ShaderRef {
+        ShaderRef::Default
+    }
+
+    /// Customizes the default [`RenderPipelineDescriptor`].
+    #[allow(unused_variables)]
+    #[inline]
+    fn specialize(descriptor: &mut RenderPipelineDescriptor, key: SpriteMaterialKey<Self>) {}
+}
+
+/// Adds the necessary ECS resources and render logic to enable rendering [`Sprite`](crate::Sprite)
+/// entities with the given [`SpriteMaterial`] asset type.
+pub struct SpriteMaterialPlugin<M: SpriteMaterial>(PhantomData<M>);
+
+impl<M: SpriteMaterial> Default for SpriteMaterialPlugin<M> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<M: SpriteMaterial> Plugin for SpriteMaterialPlugin<M>
+where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    fn build(&self, app: &mut App) {
+        app.init_asset::<M>()
+            .add_plugins(RenderAssetPlugin::<PreparedSpriteMaterial<M>>::default());
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .add_render_command::<Transparent2d, DrawSpriteMaterial<M>>()
+                .init_resource::<SpecializedRenderPipelines<SpriteMaterialPipeline<M>>>()
+                .add_systems(
+                    ExtractSchedule,
+                    extract_sprite_materials::<M>.after(SpriteSystem::ExtractSprites),
+                )
+                .add_systems(
+                    Render,
+                    queue_material_sprites::<M>
+                        .in_set(RenderSet::Queue)
+                        .after(prepare_assets::<PreparedSpriteMaterial<M>>),
+                );
+        }
+    }
+
+    fn finish(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.init_resource::<SpriteMaterialPipeline<M>>();
+        }
+    }
+}
+
+/// The [`SpriteMaterial`] of each extracted sprite that has one.
+///
+/// This isn't generic over the material type, so that the default sprite systems can skip these
+/// sprites and break batches between sprites with different materials.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct RenderSpriteMaterialInstances(EntityHashMap<UntypedAssetId>);
+
+fn extract_sprite_materials<M: SpriteMaterial>(
+    mut material_instances: ResMut<RenderSpriteMaterialInstances>,
+    extracted_sprites: Res<ExtractedSprites>,
+    query: Extract<Query<&Handle<M>>>,
+) {
+    for (entity, extracted_sprite) in extracted_sprites.sprites.iter() {
+        // Sliced sprites are extracted to new entities, look up their original entity.
+        let original_entity = extracted_sprite.original_entity.unwrap_or(*entity);
+        if let Ok(handle) = query.get(original_entity) {
+            material_instances.insert(*entity, handle.id().untyped());
+        }
+    }
+}
+
+/// Render pipeline data for a given [`SpriteMaterial`]
+#[derive(Resource)]
+pub struct SpriteMaterialPipeline<M: SpriteMaterial> {
+    pub sprite_pipeline: SpritePipeline,
+    pub sprite_material_layout: BindGroupLayout,
+    pub vertex_shader: Option<Handle<Shader>>,
+    pub fragment_shader: Option<Handle<Shader>>,
+    marker: PhantomData<M>,
+}
+
+pub struct SpriteMaterialKey<M: SpriteMaterial> {
+    pub sprite_key: SpritePipelineKey,
+    pub bind_group_data: M::Data,
+}
+
+impl<M: SpriteMaterial> Eq for SpriteMaterialKey<M> where M::Data: PartialEq {}
+
+impl<M: SpriteMaterial> PartialEq for SpriteMaterialKey<M>
+where
+    M::Data: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.sprite_key == other.sprite_key && self.bind_group_data == other.bind_group_data
+    }
+}
+
+impl<M: SpriteMaterial> Clone for SpriteMaterialKey<M>
+where
+    M::Data: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            sprite_key: self.sprite_key,
+            bind_group_data: self.bind_group_data.clone(),
+        }
+    }
+}
+
+impl<M: SpriteMaterial> Hash for SpriteMaterialKey<M>
+where
+    M::Data: Hash,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.sprite_key.hash(state);
+        self.bind_group_data.hash(state);
+    }
+}
+
+impl<M: SpriteMaterial> Clone for SpriteMaterialPipeline<M> {
+    fn clone(&self) -> Self {
+        Self {
+            sprite_pipeline: self.sprite_pipeline.clone(),
+            sprite_material_layout: self.sprite_material_layout.clone(),
+            vertex_shader: self.vertex_shader.clone(),
+            fragment_shader: self.fragment_shader.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<M: SpriteMaterial> SpecializedRenderPipeline for SpriteMaterialPipeline<M>
+where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    type Key = SpriteMaterialKey<M>;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut descriptor = self.sprite_pipeline.specialize(key.sprite_key);
+        if let Some(vertex_shader) = &self.vertex_shader {
+            descriptor.vertex.shader = vertex_shader.clone();
+        }
+
+        if let Some(fragment_shader) = &self.fragment_shader {
+            descriptor.fragment.as_mut().unwrap().shader = fragment_shader.clone();
+        }
+        descriptor.layout.push(self.sprite_material_layout.clone());
+        descriptor.label = Some("sprite_material_pipeline".into());
+
+        M::specialize(&mut descriptor, key);
+        descriptor
+    }
+}
+
+impl<M: SpriteMaterial> FromWorld for SpriteMaterialPipeline<M> {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        let render_device = world.resource::<RenderDevice>();
+        let sprite_material_layout = M::bind_group_layout(render_device);
+
+        SpriteMaterialPipeline {
+            sprite_pipeline: world.resource::<SpritePipeline>().clone(),
+            sprite_material_layout,
+            vertex_shader: match M::vertex_shader() {
+                ShaderRef::Default => None,
+                ShaderRef::Handle(handle) => Some(handle),
+                ShaderRef::Path(path) => Some(asset_server.load(path)),
+            },
+            fragment_shader: match M::fragment_shader() {
+                ShaderRef::Default => None,
+                ShaderRef::Handle(handle) => Some(handle),
+                ShaderRef::Path(path) => Some(asset_server.load(path)),
+            },
+            marker: PhantomData,
+        }
+    }
+}
+
+/// [`RenderCommand`] for rendering sprites with a [`SpriteMaterial`].
+pub type DrawSpriteMaterial<M> = (
+    SetItemPipeline,
+    SetSpriteViewBindGroup<0>,
+    SetSpriteTextureBindGroup<1>,
+    SetSpriteMaterialBindGroup<M, 2>,
+    DrawSpriteBatch,
+);
+
+pub struct SetSpriteMaterialBindGroup<M: SpriteMaterial, const I: usize>(PhantomData<M>);
+impl<P: PhaseItem, M: SpriteMaterial, const I: usize> RenderCommand<P>
+    for SetSpriteMaterialBindGroup<M, I>
+{
+    type Param = SRes<RenderAssets<PreparedSpriteMaterial<M>>>;
+    type ViewQuery = ();
+    type ItemQuery = Read<SpriteBatch>;
+
+    #[inline]
+    fn render<'w>(
+        _item: &P,
+        _view: (),
+        batch: Option<&'_ SpriteBatch>,
+        materials: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let materials = materials.into_inner();
+        let Some(material_id) = batch.and_then(|batch| batch.material_id) else {
+            return RenderCommandResult::Failure;
+        };
+        let Some(material) = material_id
+            .try_typed::<M>()
+            .ok()
+            .and_then(|material_id| materials.get(material_id))
+        else {
+            return RenderCommandResult::Failure;
+        };
+        pass.set_bind_group(I, &material.bind_group, &[]);
+        RenderCommandResult::Success
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn queue_material_sprites<M: SpriteMaterial>(
+    mut view_entities: Local<FixedBitSet>,
+    draw_functions: Res<DrawFunctions<Transparent2d>>,
+    sprite_material_pipeline: Res<SpriteMaterialPipeline<M>>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<SpriteMaterialPipeline<M>>>,
+    pipeline_cache: Res<PipelineCache>,
+    msaa: Res<Msaa>,
+    extracted_sprites: Res<ExtractedSprites>,
+    render_materials: Res<RenderAssets<PreparedSpriteMaterial<M>>>,
+    sprite_material_instances: Res<RenderSpriteMaterialInstances>,
+    mut transparent_render_phases: ResMut<ViewSortedRenderPhases<Transparent2d>>,
+    mut views: Query<(
+        Entity,
+        &VisibleEntities,
+        &ExtractedView,
+        Option<&Tonemapping>,
+        Option<&DebandDither>,
+        Option<&Camera2d>,
+        Option<&Lighting2d>,
+    )>,
+) where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    if sprite_material_instances.is_empty() {
+        return;
+    }
+
+    let draw_sprite_material_function = draw_functions.read().id::<DrawSpriteMaterial<M>>();
+
+    for (view_entity, visible_entities, view, tonemapping, dither, camera_2d, lighting_2d) in
+        &mut views
+    {
+        let Some(transparent_phase) = transparent_render_phases.get_mut(&view_entity) else {
+            continue;
+        };
+
+        let view_key =
+            SpritePipelineKey::from_view(view, tonemapping, dither, camera_2d, lighting_2d, &msaa);
+
+        view_entities.clear();
+        view_entities.extend(
+            visible_entities
+                .iter::<WithSprite>()
+                .map(|e| e.index() as usize),
+        );
+
+        for (entity, material_id) in sprite_material_instances.iter() {
+            let Ok(material_id) = material_id.try_typed::<M>() else {
+                continue;
+            };
+            let Some(material) = render_materials.get(material_id) else {
+                continue;
+            };
+            let Some(extracted_sprite) = extracted_sprites.sprites.get(entity) else {
+                continue;
+            };
+            let index = extracted_sprite.original_entity.unwrap_or(*entity).index();
+            if !view_entities.contains(index as usize) {
+                continue;
+            }
+
+            let sprite_key = view_key.for_sprite(extracted_sprite);
+            let pipeline = pipelines.specialize(
+                &pipeline_cache,
+                &sprite_material_pipeline,
+                SpriteMaterialKey {
+                    sprite_key,
+                    bind_group_data: material.key.clone(),
+                },
+            );
+
+            transparent_phase.add(Transparent2d {
+                draw_function: draw_sprite_material_function,
+                pipeline,
+                entity: *entity,
+                // These items will be sorted by layer, order and depth with other phase items
+                sort_key: extracted_sprite.sort_key,
+                // batch_range and dynamic_offset will be calculated in prepare_sprites
+                batch_range: 0..0,
+                extra_index: PhaseItemExtraIndex::NONE,
+            });
+        }
+    }
+}
+
+/// Data prepared for a [`SpriteMaterial`] instance.
+pub struct PreparedSpriteMaterial<T: SpriteMaterial> {
+    pub bindings: Vec<(u32, OwnedBindingResource)>,
+    pub bind_group: BindGroup,
+    pub key: T::Data,
+}
+
+impl<M: SpriteMaterial> RenderAsset for PreparedSpriteMaterial<M> {
+    type SourceAsset = M;
+
+    type Param = (
+        SRes<RenderDevice>,
+        SRes<RenderAssets<GpuImage>>,
+        SRes<FallbackImage>,
+        SRes<SpriteMaterialPipeline<M>>,
+    );
+
+    fn prepare_asset(
+        material: Self::SourceAsset,
+        (render_device, images, fallback_image, pipeline): &mut SystemParamItem<Self::Param>,
+    ) -> Result<Self, PrepareAssetError<Self::SourceAsset>> {
+        match material.as_bind_group(
+            &pipeline.sprite_material_layout,
+            render_device,
+            images,
+            fallback_image,
+        ) {
+            Ok(prepared) => Ok(PreparedSpriteMaterial {
+                bindings: prepared.bindings,
+                bind_group: prepared.bind_group,
+                key: prepared.data,
+            }),
+            Err(AsBindGroupError::RetryNextUpdate) => {
+                Err(PrepareAssetError::RetryNextUpdate(material))
+            }
+        }
+    }
+}
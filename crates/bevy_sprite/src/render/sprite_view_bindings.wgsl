@@ -1,9 +1,12 @@
 #define_import_path bevy_sprite::sprite_view_bindings
 
 #import bevy_render::view::View
+#import bevy_sprite::light2d_types::Lights2d
 
 @group(0) @binding(0) var<uniform> view: View;
 
 @group(0) @binding(1) var dt_lut_texture: texture_3d<f32>;
 @group(0) @binding(2) var dt_lut_sampler: sampler;
 
+@group(0) @binding(3) var<uniform> lights: Lights2d;
+@group(0) @binding(4) var shadow_map: texture_2d<f32>;
@@ -0,0 +1,131 @@
+pub use bevy_core_pipeline::core_2d::Sort2dKey;
+
+use bevy_ecs::prelude::*;
+use bevy_math::FloatOrd;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::HashMap;
+
+/// The layer a 2D entity is drawn in.
+///
+/// Layers are drawn from the lowest to the highest, so the entities of a layer are drawn on top
+/// of the entities of all the lower layers, whatever their transforms. Within a layer, entities
+/// are drawn by their [`OrderInLayer`], then by the [`LayerSortMode`] of the layer.
+///
+/// Entities without this component are in layer `0`.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[reflect(Component, Default)]
+pub struct SortingLayer(pub i32);
+
+/// The order a 2D entity is drawn in within its [`SortingLayer`].
+///
+/// Entities with a higher order are drawn on top of the ones with a lower order in the same
+/// layer. Entities with the same order are sorted by the [`LayerSortMode`] of their layer.
+///
+/// Entities without this component have an order of `0`.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[reflect(Component, Default)]
+pub struct OrderInLayer(pub i32);
+
+/// How the 2D entities of a [`SortingLayer`] with the same [`OrderInLayer`] are sorted.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum LayerSortMode {
+    /// Entities with a higher Z translation are drawn on top. This is how entities are sorted
+    /// without layers.
+    #[default]
+    Z,
+    /// Entities lower on the screen are drawn on top, as in top-down games. The Z translation of
+    /// the entities is ignored.
+    Y,
+    /// Entities are drawn from the lowest to the highest key returned by the function, such as
+    /// the depth of a tile in an isometric game.
+    Custom(fn(&GlobalTransform) -> f32),
+}
+
+impl LayerSortMode {
+    /// Returns the depth of an entity with the given `transform`, entities with a higher depth
+    /// being drawn on top.
+    pub fn depth(&self, transform: &GlobalTransform) -> f32 {
+        match self {
+            LayerSortMode::Z => transform.translation().z,
+            LayerSortMode::Y => -transform.translation().y,
+            LayerSortMode::Custom(key) => key(transform),
+        }
+    }
+}
+
+/// The [`LayerSortMode`] of each [`SortingLayer`].
+///
+/// The layers that aren't configured are sorted by [`LayerSortMode::Z`].
+///
+/// ```
+/// # use bevy_sprite::{LayerSortMode, SortingLayer, SortingLayers};
+/// const CHARACTERS: SortingLayer = SortingLayer(1);
+///
+/// let mut layers = SortingLayers::default();
+/// layers.set_sort_mode(CHARACTERS, LayerSortMode::Y);
+/// ```
+#[derive(Resource, Clone, Debug, Default)]
+pub struct SortingLayers {
+    sort_modes: HashMap<SortingLayer, LayerSortMode>,
+}
+
+impl SortingLayers {
+    /// Returns the [`LayerSortMode`] of the `layer`.
+    pub fn sort_mode(&self, layer: SortingLayer) -> LayerSortMode {
+        self.sort_modes.get(&layer).copied().unwrap_or_default()
+    }
+
+    /// Sets the [`LayerSortMode`] of the `layer`.
+    pub fn set_sort_mode(&mut self, layer: SortingLayer, sort_mode: LayerSortMode) -> &mut Self {
+        self.sort_modes.insert(layer, sort_mode);
+        self
+    }
+
+    /// Returns the key the render phase sorts an entity with the given layer, order and
+    /// transform by. A missing layer or order is `0`.
+    pub fn sort_key(
+        &self,
+        layer: Option<&SortingLayer>,
+        order: Option<&OrderInLayer>,
+        transform: &GlobalTransform,
+    ) -> Sort2dKey {
+        let layer = layer.copied().unwrap_or_default();
+        Sort2dKey {
+            layer: layer.0,
+            order: order.copied().unwrap_or_default().0,
+            depth: FloatOrd(self.sort_mode(layer).depth(transform)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_transform::components::Transform;
+
+    use super::*;
+
+    #[test]
+    fn sort_keys() {
+        let mut layers = SortingLayers::default();
+        layers
+            .set_sort_mode(SortingLayer(1), LayerSortMode::Y)
+            .set_sort_mode(
+                SortingLayer(2),
+                LayerSortMode::Custom(|transform| transform.translation().x),
+            );
+        let transform = GlobalTransform::from(Transform::from_xyz(1., 2., 3.));
+
+        assert_eq!(
+            layers.sort_key(None, None, &transform),
+            Sort2dKey::from_depth(3.)
+        );
+        let key = |layer| layers.sort_key(Some(&SortingLayer(layer)), None, &transform);
+        assert_eq!(key(1).depth, FloatOrd(-2.));
+        assert_eq!(key(2).depth, FloatOrd(1.));
+
+        let below = layers.sort_key(Some(&SortingLayer(1)), Some(&OrderInLayer(5)), &transform);
+        let above = layers.sort_key(Some(&SortingLayer(2)), None, &transform);
+        assert!(below < above);
+    }
+}
@@ -0,0 +1,125 @@
+use std::io;
+
+use bevy_asset::{io::Reader, Asset, AssetLoader, AsyncReadExt as _, Handle, LoadContext};
+use bevy_reflect::Reflect;
+use bevy_utils::HashMap;
+use ron::de::SpannedError;
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::SpriteAnimationClip;
+
+/// The named [`SpriteAnimationClip`]s of a sprite sheet, loaded from a [RON] file.
+///
+/// Canonically, such files have a `.spriteanim.ron` extension. Each clip is also a labeled
+/// asset, so a single clip can be loaded with a path like `"player.spriteanim.ron#run"`:
+///
+/// ```ron
+/// (
+///     clips: {
+///         "idle": (first: 0, last: 0, fps: 1.0),
+///         "run": (
+///             first: 1,
+///             last: 6,
+///             fps: 10.0,
+///             mode: Loop,
+///             marks: [(frame: 2, name: "step"), (frame: 5, name: "step")],
+///         ),
+///     },
+/// )
+/// ```
+///
+/// [RON]: https://github.com/ron-rs/ron
+#[derive(Asset, Reflect, Clone, Debug, Default)]
+pub struct SpriteAnimationSet {
+    pub clips: HashMap<String, Handle<SpriteAnimationClip>>,
+}
+
+impl SpriteAnimationSet {
+    /// Returns the clip named `name`.
+    pub fn get(&self, name: &str) -> Option<&Handle<SpriteAnimationClip>> {
+        self.clips.get(name)
+    }
+}
+
+#[derive(Deserialize)]
+struct SerializedSpriteAnimationSet {
+    clips: HashMap<String, SpriteAnimationClip>,
+}
+
+/// Loads a [`SpriteAnimationSet`] and its [`SpriteAnimationClip`]s.
+#[derive(Default)]
+pub struct SpriteAnimationLoader;
+
+/// Possible errors that can be produced by [`SpriteAnimationLoader`].
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum SpriteAnimationLoadError {
+    /// An I/O error occurred.
+    #[error("I/O")]
+    Io(#[from] io::Error),
+    /// An error occurred in RON deserialization, and the location of the error is supplied.
+    #[error("RON deserialization")]
+    SpannedRon(#[from] SpannedError),
+}
+
+impl AssetLoader for SpriteAnimationLoader {
+    type Asset = SpriteAnimationSet;
+
+    type Settings = ();
+
+    type Error = SpriteAnimationLoadError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _: &'a Self::Settings,
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let serialized = ron::de::from_bytes::<SerializedSpriteAnimationSet>(&bytes)?;
+
+        Ok(SpriteAnimationSet {
+            clips: serialized
+                .clips
+                .into_iter()
+                .map(|(name, clip)| {
+                    let handle = load_context.add_labeled_asset(name.clone(), clip);
+                    (name, handle)
+                })
+                .collect(),
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["spriteanim", "spriteanim.ron"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SpriteAnimationMode;
+
+    #[test]
+    fn deserialize_clips() {
+        let set: SerializedSpriteAnimationSet = ron::de::from_str(
+            r#"(
+                clips: {
+                    "idle": (first: 0, last: 0, fps: 1.0),
+                    "jump": (first: 7, last: 9, fps: 8.0, mode: Once, marks: [(frame: 9, name: "land")]),
+                },
+            )"#,
+        )
+        .unwrap();
+
+        assert_eq!(set.clips["idle"], SpriteAnimationClip::new(0, 0, 1.0));
+        assert_eq!(
+            set.clips["jump"],
+            SpriteAnimationClip::new(7, 9, 8.0)
+                .with_mode(SpriteAnimationMode::Once)
+                .with_mark(9, "land")
+        );
+    }
+}
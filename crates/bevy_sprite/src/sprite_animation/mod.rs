@@ -0,0 +1,320 @@
+mod loader;
+
+pub use loader::{SpriteAnimationLoadError, SpriteAnimationLoader, SpriteAnimationSet};
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{Asset, AssetApp, Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::view::VisibilitySystems;
+use bevy_time::Time;
+use serde::{Deserialize, Serialize};
+
+use crate::{SpriteSystem, TextureAtlas};
+
+/// Adds flipbook animation of [`TextureAtlas`] sprites with [`SpriteAnimation`].
+#[derive(Default)]
+pub struct SpriteAnimationPlugin;
+
+impl Plugin for SpriteAnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<SpriteAnimationClip>()
+            .register_asset_reflect::<SpriteAnimationClip>()
+            .init_asset::<SpriteAnimationSet>()
+            .register_asset_reflect::<SpriteAnimationSet>()
+            .init_asset_loader::<SpriteAnimationLoader>()
+            .register_type::<SpriteAnimation>()
+            .add_event::<SpriteAnimationEvent>()
+            .add_systems(
+                PostUpdate,
+                animate_sprites
+                    .in_set(SpriteSystem::Animate)
+                    .before(SpriteSystem::ComputeSlices)
+                    .before(VisibilitySystems::CalculateBounds),
+            );
+    }
+}
+
+/// How a [`SpriteAnimationClip`] goes on once it reaches its last frame.
+#[derive(Reflect, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[reflect(Default)]
+pub enum SpriteAnimationMode {
+    /// The animation stops on its last frame.
+    Once,
+    /// The animation starts over from its first frame.
+    #[default]
+    Loop,
+    /// The animation plays backwards to its first frame, then forwards again, and so on.
+    PingPong,
+}
+
+/// A named mark on a frame of a [`SpriteAnimationClip`], sending a [`SpriteAnimationEvent`]
+/// every time the frame is shown, such as a footstep.
+#[derive(Reflect, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SpriteAnimationMark {
+    /// The frame of the mark, as an index into the [`TextureAtlasLayout`](crate::TextureAtlasLayout)
+    /// between [`SpriteAnimationClip::first`] and [`SpriteAnimationClip::last`].
+    pub frame: usize,
+    pub name: String,
+}
+
+/// A flipbook animation, showing a range of the sections of a
+/// [`TextureAtlasLayout`](crate::TextureAtlasLayout) one after the other.
+///
+/// Clips are usually loaded by name from a [`SpriteAnimationSet`].
+#[derive(Asset, Reflect, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[reflect(Default)]
+pub struct SpriteAnimationClip {
+    /// The index of the first frame in the texture atlas layout.
+    pub first: usize,
+    /// The index of the last frame in the texture atlas layout, included in the animation.
+    pub last: usize,
+    /// How many frames are shown per second.
+    pub fps: f32,
+    #[serde(default)]
+    pub mode: SpriteAnimationMode,
+    #[serde(default)]
+    pub marks: Vec<SpriteAnimationMark>,
+}
+
+impl Default for SpriteAnimationClip {
+    fn default() -> Self {
+        Self::new(0, 0, 10.0)
+    }
+}
+
+impl SpriteAnimationClip {
+    /// Creates a looping clip from the `first` to the `last` frame, both included.
+    pub fn new(first: usize, last: usize, fps: f32) -> Self {
+        Self {
+            first,
+            last,
+            fps,
+            mode: SpriteAnimationMode::Loop,
+            marks: Vec::new(),
+        }
+    }
+
+    /// Returns the clip with the given [`SpriteAnimationMode`].
+    #[must_use]
+    pub fn with_mode(mut self, mode: SpriteAnimationMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Returns the clip with a [`SpriteAnimationMark`] named `name` on the `frame`.
+    #[must_use]
+    pub fn with_mark(mut self, frame: usize, name: impl Into<String>) -> Self {
+        self.marks.push(SpriteAnimationMark {
+            frame,
+            name: name.into(),
+        });
+        self
+    }
+
+    /// Returns the number of frames of the clip.
+    pub fn frame_count(&self) -> usize {
+        self.last.saturating_sub(self.first) + 1
+    }
+}
+
+/// Plays a [`SpriteAnimationClip`] on the [`TextureAtlas`] of a sprite, setting its index to the
+/// frame of the animation.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component, Default)]
+pub struct SpriteAnimation {
+    pub clip: Handle<SpriteAnimationClip>,
+    /// A multiplier of the frames per second of the clip.
+    pub speed: f32,
+    pub paused: bool,
+    /// The current frame, from the first frame of the clip, or `None` before the clip starts.
+    frame: Option<usize>,
+    /// The time since the current frame was shown, in seconds.
+    elapsed: f32,
+    /// Whether the frames are played backwards by a [`SpriteAnimationMode::PingPong`] clip.
+    backwards: bool,
+    finished: bool,
+}
+
+impl Default for SpriteAnimation {
+    fn default() -> Self {
+        Self::new(Handle::default())
+    }
+}
+
+impl SpriteAnimation {
+    /// Creates an animation playing the `clip` from its first frame.
+    pub fn new(clip: Handle<SpriteAnimationClip>) -> Self {
+        Self {
+            clip,
+            speed: 1.0,
+            paused: false,
+            frame: None,
+            elapsed: 0.0,
+            backwards: false,
+            finished: false,
+        }
+    }
+
+    /// Returns the animation with the given [`speed`](Self::speed).
+    #[must_use]
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Plays the `clip` from its first frame, unless it is already playing.
+    pub fn play(&mut self, clip: Handle<SpriteAnimationClip>) -> &mut Self {
+        if self.clip != clip {
+            self.clip = clip;
+            self.restart();
+        }
+        self
+    }
+
+    /// Plays the clip again from its first frame.
+    pub fn restart(&mut self) -> &mut Self {
+        self.frame = None;
+        self.elapsed = 0.0;
+        self.backwards = false;
+        self.finished = false;
+        self
+    }
+
+    pub fn pause(&mut self) -> &mut Self {
+        self.paused = true;
+        self
+    }
+
+    pub fn resume(&mut self) -> &mut Self {
+        self.paused = false;
+        self
+    }
+
+    /// Returns the current frame, from the first frame of the clip, or `None` if the clip hasn't
+    /// started yet, for example because it isn't loaded.
+    pub fn frame(&self) -> Option<usize> {
+        self.frame
+    }
+
+    /// Returns `true` if a [`SpriteAnimationMode::Once`] clip has shown its last frame for a full
+    /// frame, staying on it.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Moves to the next frame of a clip of `frame_count` frames.
+    fn advance(&mut self, frame_count: usize, mode: SpriteAnimationMode) {
+        let frame = self.frame.unwrap_or(0);
+        let last = frame_count.saturating_sub(1);
+        self.frame = Some(match mode {
+            SpriteAnimationMode::Once if frame >= last => {
+                self.finished = true;
+                last
+            }
+            SpriteAnimationMode::Loop if frame >= last => 0,
+            SpriteAnimationMode::Once | SpriteAnimationMode::Loop => frame + 1,
+            SpriteAnimationMode::PingPong if last == 0 => 0,
+            SpriteAnimationMode::PingPong => {
+                if frame >= last {
+                    self.backwards = true;
+                } else if frame == 0 {
+                    self.backwards = false;
+                }
+                if self.backwards {
+                    frame - 1
+                } else {
+                    frame + 1
+                }
+            }
+        });
+    }
+}
+
+/// Sent when a [`SpriteAnimation`] shows a frame with a [`SpriteAnimationMark`].
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct SpriteAnimationEvent {
+    /// The entity of the [`SpriteAnimation`].
+    pub entity: Entity,
+    /// The name of the mark.
+    pub name: String,
+    /// The frame of the mark, as an index into the texture atlas layout.
+    pub frame: usize,
+}
+
+/// System advancing the [`SpriteAnimation`]s and setting the index of their [`TextureAtlas`].
+pub fn animate_sprites(
+    time: Res<Time>,
+    clips: Res<Assets<SpriteAnimationClip>>,
+    mut animations: Query<(Entity, &mut SpriteAnimation, &mut TextureAtlas)>,
+    mut events: EventWriter<SpriteAnimationEvent>,
+) {
+    let delta = time.delta_seconds();
+    for (entity, mut animation, mut atlas) in &mut animations {
+        let Some(clip) = clips.get(&animation.clip) else {
+            continue;
+        };
+        let mut send_marks =
+            |frame: usize| {
+                let frame = clip.first + frame;
+                events.send_batch(clip.marks.iter().filter(|mark| mark.frame == frame).map(
+                    |mark| SpriteAnimationEvent {
+                        entity,
+                        name: mark.name.clone(),
+                        frame,
+                    },
+                ));
+            };
+
+        let frame = match animation.frame {
+            Some(frame) => frame,
+            None => {
+                animation.frame = Some(0);
+                send_marks(0);
+                0
+            }
+        };
+        if !animation.paused && !animation.finished && clip.fps > 0.0 {
+            let frame_duration = 1.0 / clip.fps;
+            animation.elapsed += delta * animation.speed;
+            while animation.elapsed >= frame_duration && !animation.finished {
+                animation.elapsed -= frame_duration;
+                animation.advance(clip.frame_count(), clip.mode);
+                if !animation.finished {
+                    send_marks(animation.frame.unwrap_or(frame));
+                }
+            }
+        }
+
+        let index = clip.first + animation.frame.unwrap_or(frame);
+        if atlas.index != index {
+            atlas.index = index;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames(mode: SpriteAnimationMode, frame_count: usize, steps: usize) -> Vec<usize> {
+        let mut animation = SpriteAnimation::default();
+        (0..steps)
+            .map(|_| {
+                animation.advance(frame_count, mode);
+                animation.frame().unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn advance_frames() {
+        assert_eq!(frames(SpriteAnimationMode::Loop, 3, 5), [1, 2, 0, 1, 2]);
+        assert_eq!(frames(SpriteAnimationMode::Once, 3, 4), [1, 2, 2, 2]);
+        assert_eq!(
+            frames(SpriteAnimationMode::PingPong, 3, 6),
+            [1, 2, 1, 0, 1, 2]
+        );
+        assert_eq!(frames(SpriteAnimationMode::PingPong, 1, 2), [0, 0]);
+    }
+}
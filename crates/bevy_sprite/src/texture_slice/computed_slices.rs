@@ -1,7 +1,9 @@
 use crate::{ExtractedSprite, ImageScaleMode, Sprite, TextureAtlas, TextureAtlasLayout};
 
+use bevy_core_pipeline::core_2d::Sort2dKey;
+
 use super::TextureSlice;
-use bevy_asset::{AssetEvent, Assets, Handle};
+use bevy_asset::{AssetEvent, AssetId, Assets, Handle};
 use bevy_ecs::prelude::*;
 use bevy_math::{Rect, Vec2};
 use bevy_render::texture::Image;
@@ -20,6 +22,7 @@ impl ComputedTextureSlices {
     /// # Arguments
     ///
     /// * `transform` - the sprite entity global transform
+    /// * `sort_key` - the sort key of the sprite entity, shared by all its slices
     /// * `original_entity` - the sprite entity
     /// * `sprite` - The sprite component
     /// * `handle` - The sprite texture handle
@@ -27,9 +30,11 @@ impl ComputedTextureSlices {
     pub(crate) fn extract_sprites<'a>(
         &'a self,
         transform: &'a GlobalTransform,
+        sort_key: Sort2dKey,
         original_entity: Entity,
         sprite: &'a Sprite,
         handle: &'a Handle<Image>,
+        normal_map_id: Option<AssetId<Image>>,
     ) -> impl ExactSizeIterator<Item = ExtractedSprite> + 'a {
         let mut flip = Vec2::ONE;
         let [mut flip_x, mut flip_y] = [false; 2];
@@ -48,12 +53,15 @@ impl ComputedTextureSlices {
                 original_entity: Some(original_entity),
                 color: sprite.color.into(),
                 transform,
+                sort_key,
                 rect: Some(slice.texture_rect),
                 custom_size: Some(slice.draw_size),
                 flip_x,
                 flip_y,
                 image_handle_id: handle.id(),
+                normal_map_id,
                 anchor: Self::redepend_anchor_from_sprite_to_slice(sprite, slice),
+                distance_field: false,
             }
         })
     }
@@ -114,22 +122,11 @@ fn compute_sprite_slices(
             (size, rect)
         }
     };
-    let slices = match scale_mode {
-        ImageScaleMode::Sliced(slicer) => slicer.compute_slices(texture_rect, sprite.custom_size),
-        ImageScaleMode::Tiled {
-            tile_x,
-            tile_y,
-            stretch_value,
-        } => {
-            let slice = TextureSlice {
-                texture_rect,
-                draw_size: sprite.custom_size.unwrap_or(image_size),
-                offset: Vec2::ZERO,
-            };
-            slice.tiled(*stretch_value, (*tile_x, *tile_y))
-        }
-    };
-    Some(ComputedTextureSlices(slices))
+    Some(ComputedTextureSlices(scale_mode.compute_slices(
+        texture_rect,
+        image_size,
+        sprite.custom_size,
+    )))
 }
 
 /// System reacting to added or modified [`Image`] handles, and recompute sprite slices
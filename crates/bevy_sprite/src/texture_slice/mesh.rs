@@ -0,0 +1,194 @@
+use bevy_asset::{AssetEvent, Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_math::{Rect, Vec2, Vec3A};
+use bevy_reflect::Reflect;
+use bevy_render::{
+    mesh::{Indices, Mesh, MeshBuilder, PrimitiveTopology},
+    primitives::Aabb,
+    render_asset::RenderAssetUsages,
+    texture::Image,
+};
+use bevy_utils::HashSet;
+
+use super::TextureSlice;
+use crate::{ImageScaleMode, Mesh2dHandle};
+
+/// A builder of a [`Mesh`] drawing [`TextureSlice`]s, with a quad per slice.
+///
+/// The UVs of each quad sample the texture rect of its slice in an image of
+/// [`image_size`](Self::image_size), so any material sampling its texture with the UVs of the
+/// mesh draws the image sliced or tiled like a sprite.
+#[derive(Clone, Debug, Default)]
+pub struct TextureSliceMeshBuilder {
+    pub slices: Vec<TextureSlice>,
+    /// The size of the image the texture rects of the slices are in.
+    pub image_size: Vec2,
+}
+
+impl TextureSliceMeshBuilder {
+    /// Creates a builder of a mesh drawing the `slices` of an image of `image_size`.
+    pub fn new(slices: Vec<TextureSlice>, image_size: Vec2) -> Self {
+        Self { slices, image_size }
+    }
+
+    /// Creates a builder of a rectangle of `size`, centered on the origin, drawing the
+    /// `texture_rect` section of an image of `image_size` scaled by the `scale_mode`.
+    pub fn from_scale_mode(
+        scale_mode: &ImageScaleMode,
+        size: Vec2,
+        texture_rect: Rect,
+        image_size: Vec2,
+    ) -> Self {
+        Self::new(
+            scale_mode.compute_slices(texture_rect, image_size, Some(size)),
+            image_size,
+        )
+    }
+}
+
+impl MeshBuilder for TextureSliceMeshBuilder {
+    fn build(&self) -> Mesh {
+        let vertex_count = self.slices.len() * 4;
+        let mut positions = Vec::with_capacity(vertex_count);
+        let mut uvs = Vec::with_capacity(vertex_count);
+        let mut indices = Vec::with_capacity(self.slices.len() * 6);
+        for slice in &self.slices {
+            let min = slice.offset - slice.draw_size / 2.0;
+            let max = slice.offset + slice.draw_size / 2.0;
+            // The top of the texture rect is drawn at the top of the quad.
+            let uv_min = slice.texture_rect.min / self.image_size;
+            let uv_max = slice.texture_rect.max / self.image_size;
+
+            let first_index = positions.len() as u32;
+            positions.extend([
+                [max.x, max.y, 0.0],
+                [min.x, max.y, 0.0],
+                [min.x, min.y, 0.0],
+                [max.x, min.y, 0.0],
+            ]);
+            uvs.extend([
+                [uv_max.x, uv_min.y],
+                [uv_min.x, uv_min.y],
+                [uv_min.x, uv_max.y],
+                [uv_max.x, uv_max.y],
+            ]);
+            indices.extend([0, 1, 2, 0, 2, 3].map(|index| first_index + index));
+        }
+
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_indices(Indices::U32(indices))
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; vertex_count])
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+    }
+}
+
+/// Generates the [`Mesh2dHandle`] of the entity: a rectangle of [`size`](Self::size) drawing its
+/// [`image`](Self::image) sliced or tiled by an [`ImageScaleMode`], like a sprite.
+///
+/// This draws scalable panels and repeating backgrounds with any [`Material2d`](crate::Material2d),
+/// with the same [`ImageScaleMode`] as sprites and UI images. The image is only used for its size:
+/// the material must sample it with the UVs of the mesh, like the texture of a
+/// [`ColorMaterial`](crate::ColorMaterial).
+///
+/// The mesh is generated again whenever this component or the image changes.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+pub struct ScaledImageMesh2d {
+    pub image: Handle<Image>,
+    /// The size of the rectangle, in world units.
+    pub size: Vec2,
+    pub scale_mode: ImageScaleMode,
+    /// An optional section of the image to draw, instead of the whole image.
+    pub rect: Option<Rect>,
+}
+
+/// System generating the [`Mesh2dHandle`] of the [`ScaledImageMesh2d`]s that changed, or whose
+/// image was added or modified.
+pub fn update_scaled_image_meshes_2d(
+    mut commands: Commands,
+    mut events: EventReader<AssetEvent<Image>>,
+    images: Res<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    scaled_meshes: Query<(Entity, Ref<ScaledImageMesh2d>)>,
+) {
+    let changed_images: HashSet<_> = events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+
+    for (entity, scaled_mesh) in &scaled_meshes {
+        if !scaled_mesh.is_changed() && !changed_images.contains(&scaled_mesh.image.id()) {
+            continue;
+        }
+        let Some(image) = images.get(&scaled_mesh.image) else {
+            continue;
+        };
+        let image_size = image.size_f32();
+        let texture_rect = scaled_mesh.rect.unwrap_or(Rect {
+            min: Vec2::ZERO,
+            max: image_size,
+        });
+        let mesh = TextureSliceMeshBuilder::from_scale_mode(
+            &scaled_mesh.scale_mode,
+            scaled_mesh.size,
+            texture_rect,
+            image_size,
+        )
+        .build();
+        commands.entity(entity).try_insert((
+            Mesh2dHandle(meshes.add(mesh)),
+            Aabb {
+                center: Vec3A::ZERO,
+                half_extents: (scaled_mesh.size / 2.0).extend(0.0).into(),
+            },
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_render::mesh::VertexAttributeValues;
+
+    use super::*;
+
+    #[test]
+    fn tiled_mesh_uvs() {
+        let scale_mode = ImageScaleMode::Tiled {
+            tile_x: true,
+            tile_y: false,
+            stretch_value: 1.0,
+        };
+        let image_size = Vec2::new(10.0, 20.0);
+        let mesh = TextureSliceMeshBuilder::from_scale_mode(
+            &scale_mode,
+            Vec2::new(25.0, 20.0),
+            Rect::from_corners(Vec2::ZERO, image_size),
+            image_size,
+        )
+        .build();
+
+        // Two whole tiles and half a tile.
+        assert_eq!(mesh.count_vertices(), 12);
+        let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+        else {
+            panic!("missing UVs");
+        };
+        let max_u = uvs.iter().map(|uv| uv[0]).fold(0.0, f32::max);
+        assert_eq!(max_u, 1.0);
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("missing positions");
+        };
+        let min_x = positions.iter().map(|p| p[0]).fold(0.0, f32::min);
+        let max_x = positions.iter().map(|p| p[0]).fold(0.0, f32::max);
+        assert_eq!((min_x, max_x), (-12.5, 12.5));
+    }
+}
@@ -1,15 +1,54 @@
 mod border_rect;
 mod computed_slices;
+mod mesh;
 mod slicer;
 
 use bevy_math::{Rect, Vec2};
 pub use border_rect::BorderRect;
+pub use mesh::{update_scaled_image_meshes_2d, ScaledImageMesh2d, TextureSliceMeshBuilder};
 pub use slicer::{SliceScaleMode, TextureSlicer};
 
+use crate::ImageScaleMode;
+
 pub(crate) use computed_slices::{
     compute_slices_on_asset_event, compute_slices_on_sprite_change, ComputedTextureSlices,
 };
 
+impl ImageScaleMode {
+    /// Computes the slices drawing the `texture_rect` section of an image of `image_size` scaled
+    /// by this mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `texture_rect` - The section of the texture to slice or tile
+    /// * `image_size` - The size of the whole texture
+    /// * `render_size` - The optional draw size of the texture. If not set the `texture_rect`
+    ///     size is used for slicing, and the `image_size` for tiling.
+    #[must_use]
+    pub fn compute_slices(
+        &self,
+        texture_rect: Rect,
+        image_size: Vec2,
+        render_size: Option<Vec2>,
+    ) -> Vec<TextureSlice> {
+        match self {
+            ImageScaleMode::Sliced(slicer) => slicer.compute_slices(texture_rect, render_size),
+            ImageScaleMode::Tiled {
+                tile_x,
+                tile_y,
+                stretch_value,
+            } => {
+                let slice = TextureSlice {
+                    texture_rect,
+                    draw_size: render_size.unwrap_or(image_size),
+                    offset: Vec2::ZERO,
+                };
+                slice.tiled(*stretch_value, (*tile_x, *tile_y))
+            }
+        }
+    }
+}
+
 /// Single texture slice, representing a texture rect to draw in a given area
 #[derive(Debug, Clone, PartialEq)]
 pub struct TextureSlice {
@@ -0,0 +1,360 @@
+use crate::{Material2d, Material2dPlugin, Mesh2dHandle};
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{load_internal_asset, Asset, Assets, Handle};
+use bevy_color::{Color, ColorToComponents};
+use bevy_ecs::prelude::*;
+use bevy_math::{primitives::Rectangle, UVec2, Vec2};
+use bevy_reflect::prelude::*;
+use bevy_render::{
+    mesh::Mesh,
+    render_asset::RenderAssetUsages,
+    render_resource::{AsBindGroup, Extent3d, Shader, ShaderRef, TextureDimension, TextureFormat},
+    texture::Image,
+    view::{InheritedVisibility, ViewVisibility, Visibility, VisibilitySystems},
+};
+use bevy_transform::components::{GlobalTransform, Transform};
+
+pub const TILEMAP_CHUNK_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(8104238419568302133);
+
+/// Adds support for rendering [`TilemapChunk`]s.
+#[derive(Default)]
+pub struct TilemapChunkPlugin;
+
+impl Plugin for TilemapChunkPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            TILEMAP_CHUNK_SHADER_HANDLE,
+            "tilemap_chunk.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.add_plugins(Material2dPlugin::<TilemapChunkMaterial>::default())
+            .register_type::<TilemapChunk>()
+            .register_type::<TilemapChunkTiles>()
+            .add_systems(
+                PostUpdate,
+                update_tilemap_chunks.before(VisibilitySystems::CalculateBounds),
+            );
+    }
+}
+
+/// A rectangular chunk of a tilemap, drawn in a single draw call.
+///
+/// The tiles of the chunk are set in its [`TilemapChunkTiles`], and drawn from the layers of the
+/// [`tileset`](Self::tileset) array texture. Larger maps are made of many chunks, each on its
+/// own entity, so that only the chunks in view are drawn and only the chunks whose tiles change
+/// are sent to the GPU again.
+///
+/// The chunk is centered on its [`Transform`].
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct TilemapChunk {
+    /// The size of each tile, in world units.
+    pub tile_size: Vec2,
+    /// An array texture with the image of each tile in its own layer.
+    ///
+    /// A tileset with the tiles stacked vertically in a single image can be turned into an array
+    /// texture with [`Image::reinterpret_stacked_2d_as_array`]. It must have at least two layers.
+    pub tileset: Handle<Image>,
+}
+
+impl Default for TilemapChunk {
+    fn default() -> Self {
+        Self {
+            tile_size: Vec2::splat(16.),
+            tileset: Handle::default(),
+        }
+    }
+}
+
+/// The tiles of a [`TilemapChunk`].
+///
+/// Tiles are stored row by row, starting from the bottom left corner of the chunk. Changing any
+/// tile sends the tiles of the whole chunk to the GPU again, which is a few kilobytes for
+/// typical chunk sizes.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct TilemapChunkTiles {
+    size: UVec2,
+    tiles: Vec<Option<TileData>>,
+}
+
+impl TilemapChunkTiles {
+    /// Creates empty tiles for a chunk of `size` columns and rows.
+    pub fn new(size: UVec2) -> Self {
+        Self {
+            size,
+            tiles: vec![None; size.element_product() as usize],
+        }
+    }
+
+    /// Returns the number of columns and rows of tiles.
+    pub fn size(&self) -> UVec2 {
+        self.size
+    }
+
+    /// Returns the tile at `position`, in columns and rows from the bottom left corner of the
+    /// chunk, or `None` if the tile is empty or out of the chunk.
+    pub fn get(&self, position: UVec2) -> Option<&TileData> {
+        self.index(position)
+            .and_then(|index| self.tiles[index].as_ref())
+    }
+
+    /// Sets the tile at `position`, in columns and rows from the bottom left corner of the
+    /// chunk, or empties it with `None`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `position` is out of the chunk.
+    pub fn set(&mut self, position: UVec2, tile: Option<TileData>) {
+        let index = self.index(position).unwrap_or_else(|| {
+            panic!(
+                "tile position {position} is out of a chunk of size {}",
+                self.size
+            )
+        });
+        self.tiles[index] = tile;
+    }
+
+    /// Returns the tiles, row by row from the bottom left corner of the chunk.
+    pub fn tiles(&self) -> &[Option<TileData>] {
+        &self.tiles
+    }
+
+    /// Returns the tiles mutably, row by row from the bottom left corner of the chunk.
+    pub fn tiles_mut(&mut self) -> &mut [Option<TileData>] {
+        &mut self.tiles
+    }
+
+    fn index(&self, position: UVec2) -> Option<usize> {
+        (position.x < self.size.x && position.y < self.size.y)
+            .then(|| (position.y * self.size.x + position.x) as usize)
+    }
+
+    /// Creates the image the tiles are read from by the shader, with one texel per tile.
+    fn to_image(&self) -> Image {
+        let data = self
+            .tiles
+            .iter()
+            .flat_map(|tile| tile.as_ref().map_or([0; 4], TileData::pack))
+            .flat_map(u32::to_le_bytes)
+            .collect();
+        Image::new(
+            Extent3d {
+                width: self.size.x,
+                height: self.size.y,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba32Uint,
+            RenderAssetUsages::default(),
+        )
+    }
+}
+
+/// A single tile of a [`TilemapChunkTiles`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Default, Debug, PartialEq)]
+pub struct TileData {
+    /// The layer of the [`TilemapChunk::tileset`] drawn for this tile.
+    pub tileset_index: u16,
+    /// Multiplied with the color of the tile.
+    pub color: Color,
+    /// Cycles the tile through the following layers of the tileset.
+    pub animation: TileAnimation,
+}
+
+impl TileData {
+    /// Creates a tile drawn with the layer `tileset_index` of the tileset.
+    pub fn from_tileset_index(tileset_index: u16) -> Self {
+        Self {
+            tileset_index,
+            ..Default::default()
+        }
+    }
+
+    /// Returns this tile tinted with `color`.
+    pub fn with_color(mut self, color: impl Into<Color>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Returns this tile with an [`TileAnimation`].
+    pub fn with_animation(mut self, animation: TileAnimation) -> Self {
+        self.animation = animation;
+        self
+    }
+
+    /// Packs the tile into a texel of the tile data image, see `tilemap_chunk.wgsl`.
+    fn pack(&self) -> [u32; 4] {
+        let animation = self.animation;
+        let [r, g, b, a] = self
+            .color
+            .to_linear()
+            .to_f32_array()
+            .map(|channel| (channel.clamp(0., 1.) * u16::MAX as f32).round() as u32);
+        [
+            self.tileset_index as u32
+                | (animation.frame_count.max(1) as u32) << 16
+                | (animation.frames_per_second as u32) << 24,
+            r | g << 16,
+            b | a << 16,
+            0,
+        ]
+    }
+}
+
+impl Default for TileData {
+    fn default() -> Self {
+        Self {
+            tileset_index: 0,
+            color: Color::WHITE,
+            animation: TileAnimation::default(),
+        }
+    }
+}
+
+/// Animates a [`TileData`] on the GPU, by drawing the layers of the tileset that follow the
+/// tile's [`TileData::tileset_index`] one after the other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Default, Debug, PartialEq, Hash)]
+pub struct TileAnimation {
+    /// The number of layers of the tileset the tile cycles through, including its own.
+    pub frame_count: u8,
+    /// How many frames are shown per second.
+    pub frames_per_second: u8,
+}
+
+impl Default for TileAnimation {
+    fn default() -> Self {
+        Self {
+            frame_count: 1,
+            frames_per_second: 0,
+        }
+    }
+}
+
+/// A bundle of the components needed to draw a [`TilemapChunk`].
+#[derive(Bundle, Clone, Debug, Default)]
+pub struct TilemapChunkBundle {
+    pub chunk: TilemapChunk,
+    pub tiles: TilemapChunkTiles,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+    /// User indication of whether an entity is visible
+    pub visibility: Visibility,
+    /// Inherited visibility of an entity.
+    pub inherited_visibility: InheritedVisibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub view_visibility: ViewVisibility,
+}
+
+/// The [`Material2d`] a [`TilemapChunk`] is drawn with, added by the [`TilemapChunkPlugin`].
+#[derive(Asset, AsBindGroup, Reflect, Debug, Clone)]
+#[reflect(Debug)]
+pub struct TilemapChunkMaterial {
+    #[texture(0, dimension = "2d_array")]
+    #[sampler(1)]
+    pub tileset: Handle<Image>,
+    /// The tiles of the chunk, see [`TilemapChunkTiles`].
+    #[texture(2, sample_type = "u_int")]
+    pub tile_data: Handle<Image>,
+}
+
+impl Material2d for TilemapChunkMaterial {
+    fn fragment_shader() -> ShaderRef {
+        TILEMAP_CHUNK_SHADER_HANDLE.into()
+    }
+}
+
+/// Creates and updates the meshes and materials of changed [`TilemapChunk`]s.
+#[allow(clippy::type_complexity)]
+pub fn update_tilemap_chunks(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<TilemapChunkMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    chunks: Query<
+        (
+            Entity,
+            Ref<TilemapChunk>,
+            Ref<TilemapChunkTiles>,
+            Option<&Handle<TilemapChunkMaterial>>,
+        ),
+        Or<(Changed<TilemapChunk>, Changed<TilemapChunkTiles>)>,
+    >,
+) {
+    for (entity, chunk, tiles, material) in &chunks {
+        let tile_data = material
+            .and_then(|material| materials.get(material))
+            .map(|material| material.tile_data.clone());
+        // The image is replaced rather than updated when the number of tiles changes.
+        let tile_data_image = tile_data
+            .and_then(|tile_data| images.get_mut(&tile_data))
+            .filter(|image| image.size() == tiles.size());
+
+        if let (Some(material), Some(image)) = (material, tile_data_image) {
+            if tiles.is_changed() {
+                image.data = tiles.to_image().data;
+            }
+            if chunk.is_changed() {
+                let material = materials.get_mut(material).unwrap();
+                if material.tileset != chunk.tileset {
+                    material.tileset = chunk.tileset.clone();
+                }
+            } else {
+                continue;
+            }
+        } else {
+            let material = materials.add(TilemapChunkMaterial {
+                tileset: chunk.tileset.clone(),
+                tile_data: images.add(tiles.to_image()),
+            });
+            commands.entity(entity).insert(material);
+        }
+
+        let size = tiles.size().as_vec2() * chunk.tile_size;
+        let mesh = meshes.add(Rectangle::from_size(size));
+        commands.entity(entity).insert(Mesh2dHandle(mesh));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tiles_are_stored_from_the_bottom_left() {
+        let mut tiles = TilemapChunkTiles::new(UVec2::new(3, 2));
+        let tile = TileData::from_tileset_index(7);
+        tiles.set(UVec2::new(2, 1), Some(tile));
+        assert_eq!(tiles.get(UVec2::new(2, 1)), Some(&tile));
+        assert_eq!(tiles.get(UVec2::new(3, 1)), None);
+        assert_eq!(tiles.tiles()[5], Some(tile));
+    }
+
+    #[test]
+    fn tile_data_image() {
+        let mut tiles = TilemapChunkTiles::new(UVec2::new(2, 1));
+        tiles.set(
+            UVec2::new(1, 0),
+            Some(
+                TileData::from_tileset_index(3).with_animation(TileAnimation {
+                    frame_count: 4,
+                    frames_per_second: 10,
+                }),
+            ),
+        );
+        let image = tiles.to_image();
+        assert_eq!(image.size(), UVec2::new(2, 1));
+        // Empty tiles have no frames.
+        assert_eq!(image.data[..16], [0; 16]);
+        assert_eq!(
+            image.data[16..20],
+            (3u32 | 4 << 16 | 10 << 24).to_le_bytes()
+        );
+        assert_eq!(image.data[20..28], [255; 8]);
+    }
+}
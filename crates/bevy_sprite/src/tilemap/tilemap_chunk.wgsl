@@ -0,0 +1,43 @@
+#import bevy_sprite::{
+    mesh2d_vertex_output::VertexOutput,
+    mesh2d_view_bindings::{globals, view},
+}
+
+#ifdef TONEMAP_IN_SHADER
+#import bevy_core_pipeline::tonemapping
+#endif
+
+@group(2) @binding(0) var tileset: texture_2d_array<f32>;
+@group(2) @binding(1) var tileset_sampler: sampler;
+// One texel per tile, see `TilemapChunkTiles`.
+@group(2) @binding(2) var tile_data: texture_2d<u32>;
+
+fn unpack_unorm16(packed: u32) -> vec2<f32> {
+    return vec2(f32(packed & 0xffffu), f32(packed >> 16u)) / 65535.0;
+}
+
+@fragment
+fn fragment(mesh: VertexOutput) -> @location(0) vec4<f32> {
+    let chunk_size = textureDimensions(tile_data);
+    let tile_uv = mesh.uv * vec2<f32>(chunk_size);
+    let tile = min(vec2<u32>(tile_uv), chunk_size - 1u);
+    // The rows of tiles start at the bottom, while the uvs start at the top.
+    let data = textureLoad(tile_data, vec2(tile.x, chunk_size.y - 1u - tile.y), 0);
+
+    let frame_count = (data.r >> 16u) & 0xffu;
+    if frame_count == 0u {
+        discard;
+    }
+    let frames_per_second = f32(data.r >> 24u);
+    let frame = u32(globals.time * frames_per_second) % frame_count;
+    let layer = (data.r & 0xffffu) + frame;
+
+    // Sample the top mip level, as the uvs jump at the edges of the tiles.
+    var color = textureSampleLevel(tileset, tileset_sampler, fract(tile_uv), layer, 0.0);
+    color *= vec4(unpack_unorm16(data.g), unpack_unorm16(data.b));
+
+#ifdef TONEMAP_IN_SHADER
+    color = tonemapping::tone_mapping(color, view.color_grading);
+#endif
+    return color;
+}
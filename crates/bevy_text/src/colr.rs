@@ -0,0 +1,624 @@
+//! Rasterization of the color glyphs of the `COLR` table, made of layers of glyph outlines filled
+//! with solid colors (version 0) or with gradients, clipped, transformed and composited
+//! (version 1).
+
+use ab_glyph::{Font as _, FontArc, Glyph, PxScaleFont, ScaleFont as _};
+use ab_glyph_rasterizer::{point, Point, Rasterizer};
+use bevy_math::{Affine2, Vec2};
+use bevy_render::{
+    render_asset::RenderAssetUsages,
+    render_resource::{Extent3d, TextureDimension, TextureFormat},
+    texture::Image,
+};
+use ttf_parser::{
+    colr::{ClipBox, CompositeMode, GradientExtend, Paint, Painter},
+    Face, GlyphId, OutlineBuilder, RgbaColor, Transform,
+};
+
+/// The largest width or height, in pixels, of the color glyphs that are rasterized.
+const MAX_COLOR_GLYPH_SIZE: f32 = 1024.;
+
+/// The color the layers using the foreground color of the text are painted with. It's white,
+/// as color glyphs are tinted with the alpha of the text color only.
+const FOREGROUND_COLOR: RgbaColor = RgbaColor {
+    red: 255,
+    green: 255,
+    blue: 255,
+    alpha: 255,
+};
+
+/// Returns the pixel bounds of `glyph` if it's a color glyph of the `COLR` table of its font, in
+/// the same space as [`ab_glyph::OutlinedGlyph::px_bounds`].
+pub(crate) fn colr_glyph_bounds(
+    scaled_font: &PxScaleFont<&FontArc>,
+    glyph: &Glyph,
+) -> Option<ab_glyph::Rect> {
+    let face = Face::parse(scaled_font.font().font_data(), 0).ok()?;
+    let glyph_id = GlyphId(glyph.id.0);
+    if !face.is_color_glyph(glyph_id) {
+        return None;
+    }
+
+    let mut painter = BoundsPainter {
+        face: &face,
+        transforms: vec![Affine2::IDENTITY],
+        bounds: None,
+    };
+    face.paint_color_glyph(glyph_id, 0, FOREGROUND_COLOR, &mut painter)?;
+    let (min, max) = painter.bounds?;
+
+    let scale = scaled_font.scale_factor();
+    let bounds = ab_glyph::Rect {
+        min: point(
+            (glyph.position.x + min.x * scale.horizontal).floor(),
+            (glyph.position.y - max.y * scale.vertical).floor(),
+        ),
+        max: point(
+            (glyph.position.x + max.x * scale.horizontal).ceil(),
+            (glyph.position.y - min.y * scale.vertical).ceil(),
+        ),
+    };
+    if bounds.width() < 1.
+        || bounds.height() < 1.
+        || bounds.width() > MAX_COLOR_GLYPH_SIZE
+        || bounds.height() > MAX_COLOR_GLYPH_SIZE
+    {
+        return None;
+    }
+    Some(bounds)
+}
+
+/// Paints the `COLR` color glyph `glyph` within its pixel `bounds`, returned by
+/// [`colr_glyph_bounds`], into a glyph texture with a pixel wide transparent border.
+pub(crate) fn get_colr_glyph_texture(
+    scaled_font: &PxScaleFont<&FontArc>,
+    glyph: &Glyph,
+    bounds: ab_glyph::Rect,
+) -> Option<Image> {
+    let face = Face::parse(scaled_font.font().font_data(), 0).ok()?;
+    let width = bounds.width() as usize;
+    let height = bounds.height() as usize;
+    let scale = scaled_font.scale_factor();
+    // Font units, with y up, to the pixels of the glyph, with y down.
+    let to_pixels = Affine2::from_cols(
+        Vec2::new(scale.horizontal, 0.),
+        Vec2::new(0., -scale.vertical),
+        Vec2::new(
+            glyph.position.x - bounds.min.x,
+            glyph.position.y - bounds.min.y,
+        ),
+    );
+
+    let mut painter = RasterPainter {
+        face: &face,
+        width,
+        height,
+        transforms: vec![to_pixels],
+        outline: None,
+        clips: Vec::new(),
+        layers: vec![(vec![[0.; 4]; width * height], CompositeMode::SourceOver)],
+    };
+    face.paint_color_glyph(GlyphId(glyph.id.0), 0, FOREGROUND_COLOR, &mut painter)?;
+    let (pixels, _) = painter.layers.pop()?;
+
+    // Add a pixel wide transparent border, matching outlined glyph textures.
+    let padded_width = width + 2;
+    let mut data = vec![0; padded_width * (height + 2) * 4];
+    for (index, [r, g, b, a]) in pixels.into_iter().enumerate() {
+        let (x, y) = (index % width, index / width);
+        let offset = ((y + 1) * padded_width + x + 1) * 4;
+        let unpremultiply = |c: f32| if a > 0. { c / a } else { 0. };
+        data[offset..offset + 4].copy_from_slice(
+            &[unpremultiply(r), unpremultiply(g), unpremultiply(b), a]
+                .map(|c| (c.clamp(0., 1.) * 255.).round() as u8),
+        );
+    }
+
+    Some(Image::new(
+        Extent3d {
+            width: padded_width as u32,
+            height: height as u32 + 2,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        // Like the other glyph textures, this one is placed into a font texture atlas.
+        RenderAssetUsages::MAIN_WORLD,
+    ))
+}
+
+fn to_affine(transform: Transform) -> Affine2 {
+    let Transform { a, b, c, d, e, f } = transform;
+    Affine2::from_cols_array(&[a, b, c, d, e, f])
+}
+
+/// Finds the bounds of the outlines of a color glyph, in font units.
+struct BoundsPainter<'a> {
+    face: &'a Face<'a>,
+    transforms: Vec<Affine2>,
+    bounds: Option<(Vec2, Vec2)>,
+}
+
+impl<'a> Painter<'a> for BoundsPainter<'a> {
+    fn outline_glyph(&mut self, glyph_id: GlyphId) {
+        let mut builder = OutlineBounds {
+            transform: *self.transforms.last().unwrap(),
+            bounds: self.bounds,
+        };
+        self.face.outline_glyph(glyph_id, &mut builder);
+        self.bounds = builder.bounds;
+    }
+
+    fn paint(&mut self, _: Paint<'a>) {}
+
+    fn push_clip(&mut self) {}
+
+    fn push_clip_box(&mut self, _: ClipBox) {}
+
+    fn pop_clip(&mut self) {}
+
+    fn push_layer(&mut self, _: CompositeMode) {}
+
+    fn pop_layer(&mut self) {}
+
+    fn push_transform(&mut self, transform: Transform) {
+        let current = *self.transforms.last().unwrap();
+        self.transforms.push(current * to_affine(transform));
+    }
+
+    fn pop_transform(&mut self) {
+        self.transforms.pop();
+    }
+}
+
+/// Grows `bounds` to contain the points of an outline. The control points of the curves are
+/// included, which may make the bounds slightly larger than the outline.
+struct OutlineBounds {
+    transform: Affine2,
+    bounds: Option<(Vec2, Vec2)>,
+}
+
+impl OutlineBounds {
+    fn add(&mut self, x: f32, y: f32) {
+        let point = self.transform.transform_point2(Vec2::new(x, y));
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (min.min(point), max.max(point)),
+            None => (point, point),
+        });
+    }
+}
+
+impl OutlineBuilder for OutlineBounds {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.add(x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.add(x, y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.add(x1, y1);
+        self.add(x, y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.add(x1, y1);
+        self.add(x2, y2);
+        self.add(x, y);
+    }
+
+    fn close(&mut self) {}
+}
+
+/// Rasterizes an outline into a coverage mask.
+struct OutlineRasterizer {
+    transform: Affine2,
+    rasterizer: Rasterizer,
+    start: Point,
+    last: Point,
+}
+
+impl OutlineRasterizer {
+    fn point(&self, x: f32, y: f32) -> Point {
+        let p = self.transform.transform_point2(Vec2::new(x, y));
+        point(p.x, p.y)
+    }
+}
+
+impl OutlineBuilder for OutlineRasterizer {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.start = self.point(x, y);
+        self.last = self.start;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let p = self.point(x, y);
+        self.rasterizer.draw_line(self.last, p);
+        self.last = p;
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (p1, p) = (self.point(x1, y1), self.point(x, y));
+        self.rasterizer.draw_quad(self.last, p1, p);
+        self.last = p;
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (p1, p2, p) = (self.point(x1, y1), self.point(x2, y2), self.point(x, y));
+        self.rasterizer.draw_cubic(self.last, p1, p2, p);
+        self.last = p;
+    }
+
+    fn close(&mut self) {
+        if self.last != self.start {
+            self.rasterizer.draw_line(self.last, self.start);
+            self.last = self.start;
+        }
+    }
+}
+
+/// Paints a color glyph into premultiplied RGBA pixels.
+///
+/// Separable and non-separable blend modes of composite paints are drawn like
+/// [`CompositeMode::SourceOver`].
+struct RasterPainter<'a> {
+    face: &'a Face<'a>,
+    width: usize,
+    height: usize,
+    /// The transforms from the current paint space to pixels.
+    transforms: Vec<Affine2>,
+    /// The coverage of the last outline, painted by [`Painter::paint`].
+    outline: Option<Vec<f32>>,
+    /// The coverage of the clip paths.
+    clips: Vec<Vec<f32>>,
+    /// The layers being painted, with the mode they are composited with.
+    layers: Vec<(Vec<[f32; 4]>, CompositeMode)>,
+}
+
+impl RasterPainter<'_> {
+    fn coverage(&self, rasterize: impl FnOnce(&mut OutlineRasterizer)) -> Vec<f32> {
+        let mut builder = OutlineRasterizer {
+            transform: *self.transforms.last().unwrap(),
+            rasterizer: Rasterizer::new(self.width, self.height),
+            start: point(0., 0.),
+            last: point(0., 0.),
+        };
+        rasterize(&mut builder);
+        let mut coverage = vec![0.; self.width * self.height];
+        builder
+            .rasterizer
+            .for_each_pixel(|index, alpha| coverage[index] = alpha.clamp(0., 1.));
+        if let Some(clip) = self.clips.last() {
+            coverage
+                .iter_mut()
+                .zip(clip)
+                .for_each(|(c, clip)| *c *= clip);
+        }
+        coverage
+    }
+
+    fn push_coverage(&mut self, coverage: Vec<f32>) {
+        self.clips.push(coverage);
+    }
+}
+
+impl<'a> Painter<'a> for RasterPainter<'a> {
+    fn outline_glyph(&mut self, glyph_id: GlyphId) {
+        let face = self.face;
+        self.outline = Some(self.coverage(|builder| {
+            face.outline_glyph(glyph_id, builder);
+        }));
+    }
+
+    fn paint(&mut self, paint: Paint<'a>) {
+        let Some(color_at) = ColorSource::new(&paint, self.face) else {
+            return;
+        };
+        let to_paint = self.transforms.last().unwrap().inverse();
+        let coverage = self.outline.as_ref().or(self.clips.last());
+        let (pixels, _) = self.layers.last_mut().unwrap();
+        for (index, pixel) in pixels.iter_mut().enumerate() {
+            let coverage = coverage.map_or(1., |coverage| coverage[index]);
+            if coverage <= 0. {
+                continue;
+            }
+            let center = Vec2::new(
+                (index % self.width) as f32 + 0.5,
+                (index / self.width) as f32 + 0.5,
+            );
+            let Some([r, g, b, a]) = color_at.color(to_paint.transform_point2(center)) else {
+                continue;
+            };
+            let alpha = a * coverage;
+            let source = [r * alpha, g * alpha, b * alpha, alpha];
+            *pixel = composite(CompositeMode::SourceOver, source, *pixel);
+        }
+    }
+
+    fn push_clip(&mut self) {
+        let coverage = match (self.outline.take(), self.clips.last()) {
+            (Some(outline), _) => outline,
+            (None, Some(clip)) => clip.clone(),
+            (None, None) => vec![1.; self.width * self.height],
+        };
+        self.push_coverage(coverage);
+    }
+
+    fn push_clip_box(&mut self, clip_box: ClipBox) {
+        let coverage = self.coverage(|builder| {
+            builder.move_to(clip_box.x_min, clip_box.y_min);
+            builder.line_to(clip_box.x_max, clip_box.y_min);
+            builder.line_to(clip_box.x_max, clip_box.y_max);
+            builder.line_to(clip_box.x_min, clip_box.y_max);
+            builder.close();
+        });
+        self.push_coverage(coverage);
+    }
+
+    fn pop_clip(&mut self) {
+        self.clips.pop();
+        self.outline = None;
+    }
+
+    fn push_layer(&mut self, mode: CompositeMode) {
+        self.layers
+            .push((vec![[0.; 4]; self.width * self.height], mode));
+    }
+
+    fn pop_layer(&mut self) {
+        if self.layers.len() < 2 {
+            return;
+        }
+        let (layer, mode) = self.layers.pop().unwrap();
+        let (pixels, _) = self.layers.last_mut().unwrap();
+        for (pixel, source) in pixels.iter_mut().zip(layer) {
+            *pixel = composite(mode, source, *pixel);
+        }
+    }
+
+    fn push_transform(&mut self, transform: Transform) {
+        let current = *self.transforms.last().unwrap();
+        self.transforms.push(current * to_affine(transform));
+    }
+
+    fn pop_transform(&mut self) {
+        self.transforms.pop();
+    }
+}
+
+/// Composites the premultiplied `source` color over `destination` with a Porter-Duff `mode`.
+fn composite(mode: CompositeMode, source: [f32; 4], destination: [f32; 4]) -> [f32; 4] {
+    let (source_alpha, destination_alpha) = (source[3], destination[3]);
+    let (source_factor, destination_factor) = match mode {
+        CompositeMode::Clear => (0., 0.),
+        CompositeMode::Source => (1., 0.),
+        CompositeMode::Destination => (0., 1.),
+        CompositeMode::DestinationOver => (1. - destination_alpha, 1.),
+        CompositeMode::SourceIn => (destination_alpha, 0.),
+        CompositeMode::DestinationIn => (0., source_alpha),
+        CompositeMode::SourceOut => (1. - destination_alpha, 0.),
+        CompositeMode::DestinationOut => (0., 1. - source_alpha),
+        CompositeMode::SourceAtop => (destination_alpha, 1. - source_alpha),
+        CompositeMode::DestinationAtop => (1. - destination_alpha, source_alpha),
+        CompositeMode::Xor => (1. - destination_alpha, 1. - source_alpha),
+        CompositeMode::Plus => (1., 1.),
+        _ => (1., 1. - source_alpha),
+    };
+    std::array::from_fn(|i| {
+        (source[i] * source_factor + destination[i] * destination_factor).min(1.)
+    })
+}
+
+/// The colors of a [`Paint`], in its own space.
+enum ColorSource {
+    Solid([f32; 4]),
+    Linear {
+        start: Vec2,
+        direction: Vec2,
+        stops: ColorStops,
+    },
+    Radial {
+        center: Vec2,
+        radius: f32,
+        center_delta: Vec2,
+        radius_delta: f32,
+        stops: ColorStops,
+    },
+    Sweep {
+        center: Vec2,
+        start_angle: f32,
+        end_angle: f32,
+        stops: ColorStops,
+    },
+}
+
+impl ColorSource {
+    fn new(paint: &Paint, face: &Face) -> Option<Self> {
+        let coords = face.variation_coordinates();
+        Some(match paint {
+            Paint::Solid(color) => Self::Solid(to_rgba(*color)),
+            Paint::LinearGradient(gradient) => {
+                let start = Vec2::new(gradient.x0, gradient.y0);
+                let end = Vec2::new(gradient.x1, gradient.y1);
+                // The gradient line is rotated to be perpendicular to the line from the start
+                // to the rotation point.
+                let normal = Vec2::new(gradient.x2, gradient.y2) - start;
+                let normal = Vec2::new(normal.y, -normal.x);
+                let end = if normal.length_squared() > 0. {
+                    start + normal * (end - start).dot(normal) / normal.length_squared()
+                } else {
+                    end
+                };
+                let direction = end - start;
+                Self::Linear {
+                    start,
+                    direction: direction / direction.length_squared().max(f32::EPSILON),
+                    stops: ColorStops::new(gradient.stops(0, coords), gradient.extend)?,
+                }
+            }
+            Paint::RadialGradient(gradient) => Self::Radial {
+                center: Vec2::new(gradient.x0, gradient.y0),
+                radius: gradient.r0,
+                center_delta: Vec2::new(gradient.x1 - gradient.x0, gradient.y1 - gradient.y0),
+                radius_delta: gradient.r1 - gradient.r0,
+                stops: ColorStops::new(gradient.stops(0, coords), gradient.extend)?,
+            },
+            Paint::SweepGradient(gradient) => Self::Sweep {
+                center: Vec2::new(gradient.center_x, gradient.center_y),
+                start_angle: gradient.start_angle,
+                end_angle: gradient.end_angle,
+                stops: ColorStops::new(gradient.stops(0, coords), gradient.extend)?,
+            },
+        })
+    }
+
+    /// The color at `point` in paint space, if any.
+    fn color(&self, point: Vec2) -> Option<[f32; 4]> {
+        match self {
+            Self::Solid(color) => Some(*color),
+            Self::Linear {
+                start,
+                direction,
+                stops,
+            } => Some(stops.color((point - *start).dot(*direction))),
+            Self::Radial {
+                center,
+                radius,
+                center_delta,
+                radius_delta,
+                stops,
+            } => {
+                // Finds the largest `t` for which `point` is on the circle interpolated between
+                // the start and end circles, with a positive radius.
+                let offset = point - *center;
+                let a = center_delta.length_squared() - radius_delta * radius_delta;
+                let b = offset.dot(*center_delta) + radius * radius_delta;
+                let c = offset.length_squared() - radius * radius;
+                let positive_radius = |t: f32| radius + t * radius_delta >= 0.;
+                let t = if a.abs() < f32::EPSILON {
+                    Some(c / (2. * b)).filter(|t| b != 0. && positive_radius(*t))
+                } else {
+                    let discriminant = b * b - a * c;
+                    (discriminant >= 0.)
+                        .then(|| {
+                            let root = discriminant.sqrt();
+                            let (t0, t1) = ((b + root) / a, (b - root) / a);
+                            [t0.max(t1), t0.min(t1)]
+                                .into_iter()
+                                .find(|t| positive_radius(*t))
+                        })
+                        .flatten()
+                };
+                t.map(|t| stops.color(t))
+            }
+            Self::Sweep {
+                center,
+                start_angle,
+                end_angle,
+                stops,
+            } => {
+                // Angles are in half turns, counterclockwise from the x axis.
+                let offset = point - *center;
+                let angle = offset.y.atan2(offset.x) / std::f32::consts::PI;
+                let angle = if angle < 0. { angle + 2. } else { angle };
+                let range = end_angle - start_angle;
+                (range != 0.).then(|| stops.color((angle - start_angle) / range))
+            }
+        }
+    }
+}
+
+/// The sorted color stops of a gradient.
+struct ColorStops {
+    stops: Vec<(f32, [f32; 4])>,
+    extend: GradientExtend,
+}
+
+impl ColorStops {
+    fn new(
+        stops: impl Iterator<Item = ttf_parser::colr::ColorStop>,
+        extend: GradientExtend,
+    ) -> Option<Self> {
+        let mut stops = stops
+            .map(|stop| (stop.stop_offset, to_rgba(stop.color)))
+            .collect::<Vec<_>>();
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        (!stops.is_empty()).then_some(Self { stops, extend })
+    }
+
+    fn color(&self, t: f32) -> [f32; 4] {
+        let (first, last) = (self.stops[0], self.stops[self.stops.len() - 1]);
+        let range = last.0 - first.0;
+        if range <= 0. {
+            return if t < first.0 { first.1 } else { last.1 };
+        }
+        // The extend mode applies outside of the offsets of the stops.
+        let u = (t - first.0) / range;
+        let u = match self.extend {
+            GradientExtend::Pad => u.clamp(0., 1.),
+            GradientExtend::Repeat => u.rem_euclid(1.),
+            GradientExtend::Reflect => 1. - (u.rem_euclid(2.) - 1.).abs(),
+        };
+        let t = first.0 + u * range;
+        let next = self
+            .stops
+            .iter()
+            .position(|(offset, _)| *offset >= t)
+            .unwrap_or(self.stops.len() - 1);
+        if next == 0 {
+            return first.1;
+        }
+        let (from, to) = (self.stops[next - 1], self.stops[next]);
+        let s = if to.0 > from.0 {
+            (t - from.0) / (to.0 - from.0)
+        } else {
+            1.
+        };
+        std::array::from_fn(|i| from.1[i] + (to.1[i] - from.1[i]) * s)
+    }
+}
+
+fn to_rgba(color: RgbaColor) -> [f32; 4] {
+    [color.red, color.green, color.blue, color.alpha].map(|c| c as f32 / 255.)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{composite, ColorStops};
+    use ttf_parser::colr::{ColorStop, CompositeMode, GradientExtend};
+    use ttf_parser::RgbaColor;
+
+    #[test]
+    fn gradient_stops_are_interpolated_and_extended() {
+        let stops = [(0.25, 0), (0.75, 255)].map(|(stop_offset, value)| ColorStop {
+            stop_offset,
+            color: RgbaColor::new(value, value, value, 255),
+        });
+        let pad = ColorStops::new(stops.into_iter(), GradientExtend::Pad).unwrap();
+        assert_eq!(pad.color(0.), [0., 0., 0., 1.]);
+        assert_eq!(pad.color(0.5), [0.5, 0.5, 0.5, 1.]);
+        assert_eq!(pad.color(1.), [1., 1., 1., 1.]);
+
+        let reflect = ColorStops::new(stops.into_iter(), GradientExtend::Reflect).unwrap();
+        assert_eq!(reflect.color(1.), [0.5, 0.5, 0.5, 1.]);
+        let repeat = ColorStops::new(stops.into_iter(), GradientExtend::Repeat).unwrap();
+        assert_eq!(repeat.color(1.), [0.5, 0.5, 0.5, 1.]);
+    }
+
+    #[test]
+    fn layers_are_composited() {
+        let red = [1., 0., 0., 1.];
+        let half_blue = [0., 0., 0.5, 0.5];
+        assert_eq!(
+            composite(CompositeMode::SourceOver, half_blue, red),
+            [0.5, 0., 0.5, 1.]
+        );
+        assert_eq!(
+            composite(CompositeMode::DestinationIn, half_blue, red),
+            [0.5, 0., 0., 0.5]
+        );
+        assert_eq!(composite(CompositeMode::Clear, half_blue, red), [0.; 4]);
+    }
+}
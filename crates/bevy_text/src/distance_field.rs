@@ -0,0 +1,479 @@
+//! Generation of the multi-channel signed distance fields of glyphs, which are drawn sharp at any
+//! scale and rotation, see [`TextRendering::DistanceField`](crate::TextRendering::DistanceField).
+//!
+//! Each of the red, green and blue channels holds the signed distance to a subset of the edges of
+//! the outline, so that the median of the three channels keeps the corners sharp. The alpha
+//! channel holds the true signed distance to the outline.
+
+use ab_glyph::{Font as _, FontArc, Glyph, GlyphId, ScaleFont as _};
+use bevy_math::{IVec2, UVec2, Vec2};
+use bevy_render::{
+    render_asset::RenderAssetUsages,
+    render_resource::{Extent3d, TextureDimension, TextureFormat},
+    texture::Image,
+};
+use ttf_parser::{Face, OutlineBuilder};
+
+/// The font size the distance fields of glyphs are generated at, whatever the size they are
+/// drawn at.
+pub const DISTANCE_FIELD_FONT_SIZE: f32 = 48.;
+
+/// The distance from the outline of a glyph, in pixels of its distance field, at which the
+/// distance field saturates. Each distance field extends this far around the outline.
+///
+/// The text shaders of `bevy_sprite` and `bevy_ui` use the same range.
+pub const DISTANCE_FIELD_RANGE: f32 = 4.;
+
+const RED: u8 = 1;
+const GREEN: u8 = 2;
+const BLUE: u8 = 4;
+const MAGENTA: u8 = RED | BLUE;
+const CYAN: u8 = GREEN | BLUE;
+const WHITE: u8 = RED | GREEN | BLUE;
+
+/// The sine of the smallest angle between two edges for their junction to be a corner.
+const CORNER_THRESHOLD: f32 = 0.141;
+
+/// Returns the pixel bounds of the distance field of `glyph` when drawn at its scale and position,
+/// in the same space as [`ab_glyph::OutlinedGlyph::px_bounds`].
+///
+/// Returns `None` if the glyph has no outline.
+pub(crate) fn distance_field_glyph_bounds(font: &FontArc, glyph: &Glyph) -> Option<ab_glyph::Rect> {
+    let face = Face::parse(font.font_data(), 0).ok()?;
+    let (min, size) = texture_bounds(font, &face, glyph.id)?;
+    // The distance field is generated at `DISTANCE_FIELD_FONT_SIZE`, with y up.
+    let scale = Vec2::new(glyph.scale.x, glyph.scale.y) / DISTANCE_FIELD_FONT_SIZE;
+    let min = min.as_vec2() * scale;
+    let max = min + size.as_vec2() * scale;
+    Some(ab_glyph::Rect {
+        min: ab_glyph::point(glyph.position.x + min.x, glyph.position.y - max.y),
+        max: ab_glyph::point(glyph.position.x + max.x, glyph.position.y - min.y),
+    })
+}
+
+/// Generates the distance field texture of the glyph `glyph_id`, to be drawn within
+/// [`distance_field_glyph_bounds`].
+pub(crate) fn get_distance_field_glyph_texture(font: &FontArc, glyph_id: GlyphId) -> Option<Image> {
+    let face = Face::parse(font.font_data(), 0).ok()?;
+    let (min, size) = texture_bounds(font, &face, glyph_id)?;
+    let scale = font.as_scaled(DISTANCE_FIELD_FONT_SIZE).scale_factor();
+
+    let mut builder = ContourBuilder {
+        scale: Vec2::new(scale.horizontal, scale.vertical),
+        contours: Vec::new(),
+        start: Vec2::ZERO,
+        last: Vec2::ZERO,
+    };
+    face.outline_glyph(ttf_parser::GlyphId(glyph_id.0), &mut builder)?;
+    let mut contours = builder.contours;
+    contours.retain(|contour| !contour.is_empty());
+    contours.iter_mut().for_each(color_edges);
+    let orientation = orientation(&contours);
+
+    let mut data = Vec::with_capacity(size.x as usize * size.y as usize * 4);
+    for y in (0..size.y as i32).rev() {
+        for x in 0..size.x as i32 {
+            let point = (min + IVec2::new(x, y)).as_vec2() + 0.5;
+            data.extend(
+                pixel_distances(&contours, orientation, point).map(|distance| {
+                    ((0.5 + distance / DISTANCE_FIELD_RANGE).clamp(0., 1.) * 255.).round() as u8
+                }),
+            );
+        }
+    }
+
+    Some(Image::new(
+        Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8Unorm,
+        // This glyph image is placed into a font texture atlas.
+        RenderAssetUsages::MAIN_WORLD,
+    ))
+}
+
+/// The bottom left corner and the size of the distance field of a glyph, in pixels with y up
+/// from the glyph origin at `DISTANCE_FIELD_FONT_SIZE`.
+fn texture_bounds(font: &FontArc, face: &Face, glyph_id: GlyphId) -> Option<(IVec2, UVec2)> {
+    let bounding_box = face.glyph_bounding_box(ttf_parser::GlyphId(glyph_id.0))?;
+    let scale = font.as_scaled(DISTANCE_FIELD_FONT_SIZE).scale_factor();
+    let padding = DISTANCE_FIELD_RANGE as i32;
+    let min = IVec2::new(
+        (bounding_box.x_min as f32 * scale.horizontal).floor() as i32 - padding,
+        (bounding_box.y_min as f32 * scale.vertical).floor() as i32 - padding,
+    );
+    let max = IVec2::new(
+        (bounding_box.x_max as f32 * scale.horizontal).ceil() as i32 + padding,
+        (bounding_box.y_max as f32 * scale.vertical).ceil() as i32 + padding,
+    );
+    Some((min, (max - min).as_uvec2()))
+}
+
+/// A part of a contour between two corners, or a single curve, flattened into line segments.
+struct Edge {
+    points: Vec<Vec2>,
+    /// The channels holding the distance to this edge.
+    color: u8,
+}
+
+impl Edge {
+    fn start_direction(&self) -> Vec2 {
+        (self.points[1] - self.points[0]).normalize_or_zero()
+    }
+
+    fn end_direction(&self) -> Vec2 {
+        let n = self.points.len();
+        (self.points[n - 1] - self.points[n - 2]).normalize_or_zero()
+    }
+
+    fn segments(&self) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+        self.points.windows(2).map(|points| (points[0], points[1]))
+    }
+
+    /// Returns the distance from `point` to this edge, signed positively on the left of the edge.
+    fn distance(&self, point: Vec2) -> EdgeDistance {
+        let last_segment = self.points.len() - 2;
+        let mut nearest = EdgeDistance {
+            distance: f32::INFINITY,
+            orthogonality: 0.,
+            pseudo_distance: 0.,
+        };
+        for (index, (a, b)) in self.segments().enumerate() {
+            let direction = b - a;
+            let length_squared = direction.length_squared();
+            let t = (point - a).dot(direction) / length_squared;
+            let nearest_point = a + direction * t.clamp(0., 1.);
+            let offset = point - nearest_point;
+            let distance = offset.length();
+            let direction = direction / length_squared.sqrt();
+            let sign = if direction.perp_dot(offset) >= 0. {
+                1.
+            } else {
+                -1.
+            };
+            let orthogonality = direction.perp_dot(offset.normalize_or_zero()).abs();
+            // Beyond the ends of the edge, the distance is the distance to its extension, so that
+            // the channels of the edges meeting at a corner cross on the bisector.
+            let extension = direction.perp_dot(point - a);
+            let pseudo_distance = if ((index == 0 && t < 0.) || (index == last_segment && t > 1.))
+                && extension.abs() <= distance
+            {
+                extension
+            } else {
+                sign * distance
+            };
+            let candidate = EdgeDistance {
+                distance,
+                orthogonality,
+                pseudo_distance,
+            };
+            if candidate.is_closer_than(&nearest) {
+                nearest = candidate;
+            }
+        }
+        nearest
+    }
+}
+
+#[derive(Clone, Copy)]
+struct EdgeDistance {
+    distance: f32,
+    /// How perpendicular the edge is to the direction of the point, to pick the right edge
+    /// among edges at the same distance, at corners.
+    orthogonality: f32,
+    pseudo_distance: f32,
+}
+
+impl EdgeDistance {
+    fn is_closer_than(&self, other: &EdgeDistance) -> bool {
+        self.distance < other.distance - 1e-5
+            || (self.distance < other.distance + 1e-5 && self.orthogonality > other.orthogonality)
+    }
+}
+
+/// Returns `1.` if the outer contours are counterclockwise, with the inside on the left of their
+/// edges, and `-1.` otherwise. Fonts may use either orientation.
+fn orientation(contours: &[Vec<Edge>]) -> f32 {
+    let area = contours
+        .iter()
+        .flatten()
+        .flat_map(Edge::segments)
+        .map(|(a, b)| a.perp_dot(b))
+        .sum::<f32>();
+    if area >= 0. {
+        1.
+    } else {
+        -1.
+    }
+}
+
+/// Returns the signed distances from `point` held by the red, green, blue and alpha channels, in
+/// pixels, positive inside the outline.
+fn pixel_distances(contours: &[Vec<Edge>], orientation: f32, point: Vec2) -> [f32; 4] {
+    let far = EdgeDistance {
+        distance: f32::INFINITY,
+        orthogonality: 0.,
+        pseudo_distance: -f32::INFINITY,
+    };
+    let mut channels = [far; 3];
+    let mut true_distance = f32::INFINITY;
+    let mut winding = 0;
+    for edge in contours.iter().flatten() {
+        let distance = edge.distance(point);
+        true_distance = true_distance.min(distance.distance);
+        for (channel, mask) in channels.iter_mut().zip([RED, GREEN, BLUE]) {
+            if edge.color & mask != 0 && distance.is_closer_than(channel) {
+                *channel = distance;
+            }
+        }
+        for (a, b) in edge.segments() {
+            let side = (b - a).perp_dot(point - a);
+            if a.y <= point.y && b.y > point.y && side > 0. {
+                winding += 1;
+            } else if b.y <= point.y && a.y > point.y && side < 0. {
+                winding -= 1;
+            }
+        }
+    }
+
+    let mut distances = channels.map(|channel| channel.pseudo_distance * orientation);
+    let true_distance = if winding != 0 {
+        true_distance
+    } else {
+        -true_distance
+    };
+
+    // Where the median is on the wrong side of the outline, such as between edges of the same
+    // color, fall back to the true distance.
+    let [r, g, b] = distances;
+    let median = r.max(g).min(r.min(g).max(b));
+    if (median > 0.) != (true_distance > 0.) {
+        distances = [true_distance; 3];
+    }
+    [distances[0], distances[1], distances[2], true_distance]
+}
+
+/// Assigns the channels of the edges of a contour, so that the two edges meeting at each corner
+/// share a single channel.
+fn color_edges(edges: &mut Vec<Edge>) {
+    let corners = (0..edges.len())
+        .filter(|&index| {
+            let previous = &edges[(index + edges.len() - 1) % edges.len()];
+            let (a, b) = (previous.end_direction(), edges[index].start_direction());
+            a.dot(b) <= 0. || a.perp_dot(b).abs() > CORNER_THRESHOLD
+        })
+        .collect::<Vec<_>>();
+
+    match corners.len() {
+        // A smooth contour.
+        0 => edges.iter_mut().for_each(|edge| edge.color = WHITE),
+        // A teardrop, split into three edges around its corner.
+        1 => {
+            if edges.len() < 3 {
+                split_into_thirds(edges, corners[0]);
+            }
+            let colors = [CYAN, WHITE, MAGENTA];
+            let count = edges.len();
+            for i in 0..count {
+                let third =
+                    (3. + 2.875 * i as f32 / (count - 1) as f32 - 1.4375 + 0.5) as usize - 2;
+                edges[(corners[0] + i) % count].color = colors[third];
+            }
+        }
+        _ => {
+            let initial_color = CYAN;
+            let mut color = initial_color;
+            let mut spline = 0;
+            for i in 0..edges.len() {
+                let index = (corners[0] + i) % edges.len();
+                if spline + 1 < corners.len() && corners[spline + 1] == index {
+                    spline += 1;
+                    let banned = if spline == corners.len() - 1 {
+                        initial_color
+                    } else {
+                        0
+                    };
+                    color = switch_color(color, banned);
+                }
+                edges[index].color = color;
+            }
+        }
+    }
+}
+
+/// Returns the next of the cyan, magenta and yellow colors, other than `banned`.
+fn switch_color(color: u8, banned: u8) -> u8 {
+    let combined = color & banned;
+    if matches!(combined, RED | GREEN | BLUE) {
+        return combined ^ WHITE;
+    }
+    let shifted = color << 1;
+    (shifted | shifted >> 3) & WHITE
+}
+
+/// Joins the edges of a contour starting at `start` and splits them into three edges, subdividing
+/// their segments if needed.
+fn split_into_thirds(edges: &mut Vec<Edge>, start: usize) {
+    let mut points = vec![edges[start].points[0]];
+    for i in 0..edges.len() {
+        points.extend_from_slice(&edges[(start + i) % edges.len()].points[1..]);
+    }
+    while points.len() < 4 {
+        points = points
+            .windows(2)
+            .flat_map(|points| [points[0], points[0].lerp(points[1], 0.5)])
+            .chain(points.last().copied())
+            .collect();
+    }
+    let segments = points.len() - 1;
+    let ends = [0, segments / 3, 2 * segments / 3, segments];
+    *edges = ends
+        .windows(2)
+        .map(|range| Edge {
+            points: points[range[0]..=range[1]].to_vec(),
+            color: WHITE,
+        })
+        .collect();
+}
+
+/// Collects the contours of a glyph as edges, in pixels with y up at `DISTANCE_FIELD_FONT_SIZE`.
+struct ContourBuilder {
+    scale: Vec2,
+    contours: Vec<Vec<Edge>>,
+    start: Vec2,
+    last: Vec2,
+}
+
+impl ContourBuilder {
+    fn push_curve(&mut self, control_points: &[Vec2]) {
+        let points = if control_points.len() == 1 {
+            vec![self.last, control_points[0]]
+        } else {
+            // Flatten the curve into segments of about two pixels.
+            let length = std::iter::once(&self.last)
+                .chain(control_points)
+                .collect::<Vec<_>>()
+                .windows(2)
+                .map(|points| points[0].distance(*points[1]))
+                .sum::<f32>();
+            let count = (length / 2.).ceil().clamp(2., 16.) as usize;
+            (0..=count)
+                .map(|i| bezier(self.last, control_points, i as f32 / count as f32))
+                .collect()
+        };
+        let mut points = points;
+        points.dedup_by(|a, b| a.distance_squared(*b) < 1e-8);
+        self.last = *control_points.last().unwrap();
+        if points.len() >= 2 {
+            if let Some(contour) = self.contours.last_mut() {
+                contour.push(Edge {
+                    points,
+                    color: WHITE,
+                });
+            }
+        }
+    }
+}
+
+/// Evaluates the Bézier curve from `start` through `control_points` at `t`.
+fn bezier(start: Vec2, control_points: &[Vec2], t: f32) -> Vec2 {
+    let mut points = std::iter::once(start)
+        .chain(control_points.iter().copied())
+        .collect::<Vec<_>>();
+    while points.len() > 1 {
+        points = points
+            .windows(2)
+            .map(|points| points[0].lerp(points[1], t))
+            .collect();
+    }
+    points[0]
+}
+
+impl OutlineBuilder for ContourBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.start = Vec2::new(x, y) * self.scale;
+        self.last = self.start;
+        self.contours.push(Vec::new());
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.push_curve(&[Vec2::new(x, y) * self.scale]);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.push_curve(&[Vec2::new(x1, y1) * self.scale, Vec2::new(x, y) * self.scale]);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.push_curve(&[
+            Vec2::new(x1, y1) * self.scale,
+            Vec2::new(x2, y2) * self.scale,
+            Vec2::new(x, y) * self.scale,
+        ]);
+    }
+
+    fn close(&mut self) {
+        if self.last.distance_squared(self.start) > 1e-8 {
+            self.push_curve(&[self.start]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        color_edges, orientation, pixel_distances, Edge, CYAN, GREEN, MAGENTA, RED, WHITE,
+    };
+
+    const YELLOW: u8 = RED | GREEN;
+    use bevy_math::Vec2;
+
+    fn square() -> Vec<Edge> {
+        let corners = [(0., 0.), (10., 0.), (10., 10.), (0., 10.), (0., 0.)].map(Vec2::from);
+        corners
+            .windows(2)
+            .map(|points| Edge {
+                points: points.to_vec(),
+                color: WHITE,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn corners_share_a_single_channel() {
+        let mut edges = square();
+        color_edges(&mut edges);
+        let colors = edges.iter().map(|edge| edge.color).collect::<Vec<_>>();
+        assert_eq!(colors, [CYAN, MAGENTA, YELLOW, MAGENTA]);
+        for i in 0..4 {
+            let shared = colors[i] & colors[(i + 1) % 4];
+            assert_eq!(shared.count_ones(), 1);
+        }
+    }
+
+    #[test]
+    fn distances_are_positive_inside() {
+        let mut edges = square();
+        color_edges(&mut edges);
+        let contours = [edges];
+        assert_eq!(orientation(&contours), 1.);
+        let median = |[r, g, b, _]: [f32; 4]| r.max(g).min(r.min(g).max(b));
+
+        let inside = pixel_distances(&contours, 1., Vec2::new(3., 5.));
+        assert!((median(inside) - 3.).abs() < 1e-4);
+        assert!((inside[3] - 3.).abs() < 1e-4);
+        let outside = pixel_distances(&contours, 1., Vec2::new(-2., 5.));
+        assert!((median(outside) + 2.).abs() < 1e-4);
+        assert!((outside[3] + 2.).abs() < 1e-4);
+
+        // Beyond a corner, the median of the channels keeps the corner sharp while the true
+        // distance is rounded.
+        let corner = pixel_distances(&contours, 1., Vec2::new(11., 11.));
+        assert!((median(corner) + 1.).abs() < 1e-4);
+        assert!((corner[3] + 2f32.sqrt()).abs() < 1e-4);
+    }
+}
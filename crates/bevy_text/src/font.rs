@@ -1,4 +1,7 @@
-use ab_glyph::{FontArc, FontVec, InvalidFont, OutlinedGlyph};
+use ab_glyph::{
+    v2::GlyphImage, Font as _, FontArc, FontVec, GlyphImageFormat, InvalidFont, OutlinedGlyph,
+    ScaleFont as _,
+};
 use bevy_asset::Asset;
 use bevy_reflect::TypePath;
 use bevy_render::{
@@ -19,6 +22,35 @@ impl Font {
         Ok(Font { font })
     }
 
+    /// Returns the horizontal position of the caret before each character of a single line of
+    /// `text`, followed by its position after the last character, for a font size of `font_size`.
+    ///
+    /// The positions include kerning, and are in the same units as `font_size`.
+    pub fn caret_positions(&self, text: &str, font_size: f32) -> Vec<f32> {
+        let font = self.font.as_scaled(font_size);
+        let mut positions = Vec::with_capacity(text.chars().count() + 1);
+        let mut x = 0.;
+        let mut previous = None;
+        for character in text.chars() {
+            let glyph = font.glyph_id(character);
+            if let Some(previous) = previous {
+                x += font.kern(previous, glyph);
+            }
+            positions.push(x);
+            x += font.h_advance(glyph);
+            previous = Some(glyph);
+        }
+        positions.push(x);
+        positions
+    }
+
+    /// Returns the height of a line of text laid out with a font size of `font_size`, including
+    /// the gap between lines.
+    pub fn line_height(&self, font_size: f32) -> f32 {
+        let font = self.font.as_scaled(font_size);
+        font.ascent() - font.descent() + font.line_gap()
+    }
+
     pub fn get_outlined_glyph_texture(outlined_glyph: OutlinedGlyph) -> Image {
         let bounds = outlined_glyph.px_bounds();
         // Increase the length of the glyph texture by 2-pixels on each axis to make space
@@ -50,4 +82,138 @@ impl Font {
             RenderAssetUsages::MAIN_WORLD,
         )
     }
+
+    /// Converts the image data of a color glyph (such as an emoji stored in a `CBDT` or `sbix`
+    /// table) into a glyph texture of `size` pixels.
+    ///
+    /// Like [`Font::get_outlined_glyph_texture`], the texture has a pixel wide transparent border.
+    /// Returns `None` if the image format is not supported.
+    pub fn get_raster_glyph_texture(glyph_image: &GlyphImage, size: (u32, u32)) -> Option<Image> {
+        raster_glyph_texture(
+            &glyph_image.format,
+            glyph_image.data,
+            (glyph_image.width as u32, glyph_image.height as u32),
+            size,
+        )
+    }
+}
+
+/// Decodes the `data` of a color glyph image of `format`, whose dimensions are `image_size`
+/// for raw bitmaps, into a glyph texture of `size` pixels plus a transparent border.
+fn raster_glyph_texture(
+    format: &GlyphImageFormat,
+    data: &[u8],
+    image_size: (u32, u32),
+    size: (u32, u32),
+) -> Option<Image> {
+    let (width, height) = (size.0.max(1), size.1.max(1));
+    let rgba = match format {
+        GlyphImageFormat::Png => image::load_from_memory_with_format(data, image::ImageFormat::Png)
+            .ok()?
+            .into_rgba8(),
+        GlyphImageFormat::BitmapPremulBgra32 => {
+            let data = data
+                .chunks_exact(4)
+                .flat_map(|bgra| {
+                    let [b, g, r, a] = [bgra[0], bgra[1], bgra[2], bgra[3]];
+                    let unpremultiply = |c: u8| {
+                        if a == 0 {
+                            0
+                        } else {
+                            (c as u32 * 255 / a as u32).min(255) as u8
+                        }
+                    };
+                    [unpremultiply(r), unpremultiply(g), unpremultiply(b), a]
+                })
+                .collect();
+            image::RgbaImage::from_raw(image_size.0, image_size.1, data)?
+        }
+        _ => return None,
+    };
+    let rgba = if rgba.dimensions() == (width, height) {
+        rgba
+    } else {
+        image::imageops::resize(&rgba, width, height, image::imageops::FilterType::Triangle)
+    };
+
+    // Add a pixel wide transparent border, matching outlined glyph textures.
+    let padded_width = width as usize + 2;
+    let padded_height = height as usize + 2;
+    let mut data = vec![0; padded_width * padded_height * 4];
+    for (y, row) in rgba.rows().enumerate() {
+        for (x, pixel) in row.enumerate() {
+            let offset = ((y + 1) * padded_width + x + 1) * 4;
+            data[offset..offset + 4].copy_from_slice(&pixel.0);
+        }
+    }
+
+    Some(Image::new(
+        Extent3d {
+            width: padded_width as u32,
+            height: padded_height as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::MAIN_WORLD,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use ab_glyph::GlyphImageFormat;
+
+    use super::raster_glyph_texture;
+
+    /// Returns the RGBA pixel at `(x, y)` of a glyph texture, excluding its border.
+    fn pixel(texture: &bevy_render::texture::Image, x: usize, y: usize) -> [u8; 4] {
+        let width = texture.width() as usize;
+        let offset = ((y + 1) * width + x + 1) * 4;
+        texture.data[offset..offset + 4].try_into().unwrap()
+    }
+
+    #[test]
+    fn decode_png_glyph() {
+        // A 2x1 PNG, as stored in `CBDT` and `sbix` tables.
+        let source =
+            image::RgbaImage::from_raw(2, 1, vec![255, 0, 0, 255, 0, 0, 255, 128]).unwrap();
+        let mut png = std::io::Cursor::new(Vec::new());
+        source.write_to(&mut png, image::ImageFormat::Png).unwrap();
+
+        let texture =
+            raster_glyph_texture(&GlyphImageFormat::Png, png.get_ref(), (0, 0), (2, 1)).unwrap();
+        assert_eq!((texture.width(), texture.height()), (4, 3));
+        assert_eq!(pixel(&texture, 0, 0), [255, 0, 0, 255]);
+        assert_eq!(pixel(&texture, 1, 0), [0, 0, 255, 128]);
+        // The border is transparent.
+        assert_eq!(&texture.data[..16], &[0; 16]);
+
+        // The image is resized to the requested size.
+        let texture =
+            raster_glyph_texture(&GlyphImageFormat::Png, png.get_ref(), (0, 0), (4, 2)).unwrap();
+        assert_eq!((texture.width(), texture.height()), (6, 4));
+
+        assert!(raster_glyph_texture(&GlyphImageFormat::Png, &[1, 2, 3], (0, 0), (2, 1)).is_none());
+    }
+
+    #[test]
+    fn decode_premultiplied_bgra_glyph() {
+        let data = [0, 0, 128, 128, 255, 0, 0, 255, 0, 0, 0, 0];
+        let texture =
+            raster_glyph_texture(&GlyphImageFormat::BitmapPremulBgra32, &data, (3, 1), (3, 1))
+                .unwrap();
+        assert_eq!(pixel(&texture, 0, 0), [255, 0, 0, 128]);
+        assert_eq!(pixel(&texture, 1, 0), [0, 0, 255, 255]);
+        assert_eq!(pixel(&texture, 2, 0), [0, 0, 0, 0]);
+
+        // Too little data for the image dimensions.
+        assert!(
+            raster_glyph_texture(&GlyphImageFormat::BitmapPremulBgra32, &data, (2, 2), (2, 2))
+                .is_none()
+        );
+        assert!(
+            raster_glyph_texture(&GlyphImageFormat::BitmapMono, &[0], (1, 1), (1, 1)).is_none()
+        );
+    }
 }
@@ -4,7 +4,7 @@ use bevy_math::UVec2;
 use bevy_render::{
     render_asset::RenderAssetUsages,
     render_resource::{Extent3d, TextureDimension, TextureFormat},
-    texture::Image,
+    texture::{Image, ImageSampler},
 };
 use bevy_sprite::{DynamicTextureAtlasBuilder, TextureAtlasLayout};
 use bevy_utils::HashMap;
@@ -62,7 +62,41 @@ impl FontAtlas {
         texture_atlases: &mut Assets<TextureAtlasLayout>,
         size: UVec2,
     ) -> FontAtlas {
-        let texture = textures.add(Image::new_fill(
+        Self::with_format(
+            textures,
+            texture_atlases,
+            size,
+            TextureFormat::Rgba8UnormSrgb,
+            ImageSampler::Default,
+        )
+    }
+
+    /// Creates an atlas of glyph distance fields, see
+    /// [`TextRendering::DistanceField`](crate::TextRendering::DistanceField).
+    ///
+    /// The distances are stored linearly and always sampled with linear filtering.
+    pub fn new_distance_field(
+        textures: &mut Assets<Image>,
+        texture_atlases: &mut Assets<TextureAtlasLayout>,
+        size: UVec2,
+    ) -> FontAtlas {
+        Self::with_format(
+            textures,
+            texture_atlases,
+            size,
+            TextureFormat::Rgba8Unorm,
+            ImageSampler::linear(),
+        )
+    }
+
+    fn with_format(
+        textures: &mut Assets<Image>,
+        texture_atlases: &mut Assets<TextureAtlasLayout>,
+        size: UVec2,
+        format: TextureFormat,
+        sampler: ImageSampler,
+    ) -> FontAtlas {
+        let mut texture = Image::new_fill(
             Extent3d {
                 width: size.x,
                 height: size.y,
@@ -70,10 +104,12 @@ impl FontAtlas {
             },
             TextureDimension::D2,
             &[0, 0, 0, 0],
-            TextureFormat::Rgba8UnormSrgb,
+            format,
             // Need to keep this image CPU persistent in order to add additional glyphs later on
             RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
-        ));
+        );
+        texture.sampler = sampler;
+        let texture = textures.add(texture);
         let texture_atlas = TextureAtlasLayout::new_empty(size);
         Self {
             texture_atlas: texture_atlases.add(texture_atlas),
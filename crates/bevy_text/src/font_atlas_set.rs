@@ -3,7 +3,7 @@ use ab_glyph::{GlyphId, OutlinedGlyph, Point};
 use bevy_asset::{AssetEvent, AssetId};
 use bevy_asset::{Assets, Handle};
 use bevy_ecs::prelude::*;
-use bevy_math::{FloatOrd, UVec2};
+use bevy_math::{FloatOrd, Rect, UVec2};
 use bevy_reflect::Reflect;
 use bevy_render::texture::Image;
 use bevy_sprite::TextureAtlasLayout;
@@ -38,6 +38,7 @@ pub fn remove_dropped_font_atlas_sets(
 
 pub struct FontAtlasSet {
     font_atlases: HashMap<FontSizeKey, Vec<FontAtlas>>,
+    distance_field_atlases: Vec<FontAtlas>,
 }
 
 #[derive(Debug, Clone, Reflect)]
@@ -47,10 +48,24 @@ pub struct GlyphAtlasInfo {
     pub glyph_index: usize,
 }
 
+impl GlyphAtlasInfo {
+    /// Returns the area of the glyph in its atlas texture, in normalized texture coordinates,
+    /// for example to build a custom mesh from the glyphs of a [`TextLayoutInfo`](crate::TextLayoutInfo).
+    ///
+    /// Returns `None` if the atlas layout is not in `texture_atlases`.
+    pub fn uv_rect(&self, texture_atlases: &Assets<TextureAtlasLayout>) -> Option<Rect> {
+        let layout = texture_atlases.get(&self.texture_atlas)?;
+        let rect = layout.textures.get(self.glyph_index)?.as_rect();
+        let size = layout.size.as_vec2();
+        Some(Rect::from_corners(rect.min / size, rect.max / size))
+    }
+}
+
 impl Default for FontAtlasSet {
     fn default() -> Self {
         FontAtlasSet {
             font_atlases: HashMap::with_capacity_and_hasher(1, Default::default()),
+            distance_field_atlases: Vec::new(),
         }
     }
 }
@@ -86,40 +101,67 @@ impl FontAtlasSet {
             subpixel_offset: glyph.position.into(),
         };
         let font_size = glyph.scale.y;
+        let glyph_texture = Font::get_outlined_glyph_texture(outlined_glyph);
+        self.add_glyph_texture_to_atlas(
+            texture_atlases,
+            textures,
+            font_size,
+            placed_glyph,
+            &glyph_texture,
+        )
+    }
+
+    /// Adds an already rasterized glyph texture, such as a color emoji bitmap, to the atlases
+    /// of the given `font_size`.
+    pub fn add_glyph_texture_to_atlas(
+        &mut self,
+        texture_atlases: &mut Assets<TextureAtlasLayout>,
+        textures: &mut Assets<Image>,
+        font_size: f32,
+        placed_glyph: PlacedGlyph,
+        glyph_texture: &Image,
+    ) -> Result<GlyphAtlasInfo, TextError> {
         let font_atlases = self
             .font_atlases
             .entry(FloatOrd(font_size))
             .or_insert_with(|| vec![FontAtlas::new(textures, texture_atlases, UVec2::splat(512))]);
+        add_to_atlases(
+            font_atlases,
+            FontAtlas::new,
+            texture_atlases,
+            textures,
+            placed_glyph,
+            glyph_texture,
+        )?;
+        Ok(self.get_glyph_atlas_info(font_size, &placed_glyph).unwrap())
+    }
 
-        let glyph_texture = Font::get_outlined_glyph_texture(outlined_glyph);
-        let add_char_to_font_atlas = |atlas: &mut FontAtlas| -> bool {
-            atlas.add_glyph(textures, texture_atlases, &placed_glyph, &glyph_texture)
-        };
-        if !font_atlases.iter_mut().any(add_char_to_font_atlas) {
-            // Find the largest dimension of the glyph, either its width or its height
-            let glyph_max_size: u32 = glyph_texture
-                .texture_descriptor
-                .size
-                .height
-                .max(glyph_texture.width());
-            // Pick the higher of 512 or the smallest power of 2 greater than glyph_max_size
-            let containing = (1u32 << (32 - glyph_max_size.leading_zeros())).max(512);
-            font_atlases.push(FontAtlas::new(
-                textures,
-                texture_atlases,
-                UVec2::splat(containing),
-            ));
-            if !font_atlases.last_mut().unwrap().add_glyph(
-                textures,
-                texture_atlases,
-                &placed_glyph,
-                &glyph_texture,
-            ) {
-                return Err(TextError::FailedToAddGlyph(placed_glyph.glyph_id));
-            }
+    /// Adds the distance field of a glyph to the distance field atlases, which are shared by all
+    /// font sizes, see [`TextRendering::DistanceField`](crate::TextRendering::DistanceField).
+    pub fn add_distance_field_glyph_to_atlas(
+        &mut self,
+        texture_atlases: &mut Assets<TextureAtlasLayout>,
+        textures: &mut Assets<Image>,
+        glyph_id: GlyphId,
+        glyph_texture: &Image,
+    ) -> Result<GlyphAtlasInfo, TextError> {
+        if self.distance_field_atlases.is_empty() {
+            self.distance_field_atlases
+                .push(FontAtlas::new_distance_field(
+                    textures,
+                    texture_atlases,
+                    UVec2::splat(512),
+                ));
         }
-
-        Ok(self.get_glyph_atlas_info(font_size, &placed_glyph).unwrap())
+        add_to_atlases(
+            &mut self.distance_field_atlases,
+            FontAtlas::new_distance_field,
+            texture_atlases,
+            textures,
+            distance_field_glyph(glyph_id),
+            glyph_texture,
+        )?;
+        Ok(self.get_distance_field_glyph_atlas_info(glyph_id).unwrap())
     }
 
     pub fn get_glyph_atlas_info(
@@ -149,6 +191,20 @@ impl FontAtlasSet {
             })
     }
 
+    /// Returns the location of the distance field of a glyph in the distance field atlases.
+    pub fn get_distance_field_glyph_atlas_info(&self, glyph_id: GlyphId) -> Option<GlyphAtlasInfo> {
+        let placed_glyph = distance_field_glyph(glyph_id);
+        self.distance_field_atlases.iter().find_map(|atlas| {
+            atlas
+                .get_glyph_index(&placed_glyph)
+                .map(|glyph_index| GlyphAtlasInfo {
+                    texture_atlas: atlas.texture_atlas.clone_weak(),
+                    texture: atlas.texture.clone_weak(),
+                    glyph_index,
+                })
+        })
+    }
+
     /// Returns the number of font atlases in this set
     pub fn len(&self) -> usize {
         self.font_atlases.len()
@@ -159,3 +215,50 @@ impl FontAtlasSet {
         self.font_atlases.is_empty()
     }
 }
+
+/// Adds a glyph texture to the first of `font_atlases` with room for it, or to a new atlas large
+/// enough for it.
+fn add_to_atlases(
+    font_atlases: &mut Vec<FontAtlas>,
+    new_atlas: fn(&mut Assets<Image>, &mut Assets<TextureAtlasLayout>, UVec2) -> FontAtlas,
+    texture_atlases: &mut Assets<TextureAtlasLayout>,
+    textures: &mut Assets<Image>,
+    placed_glyph: PlacedGlyph,
+    glyph_texture: &Image,
+) -> Result<(), TextError> {
+    let add_char_to_font_atlas = |atlas: &mut FontAtlas| -> bool {
+        atlas.add_glyph(textures, texture_atlases, &placed_glyph, glyph_texture)
+    };
+    if !font_atlases.iter_mut().any(add_char_to_font_atlas) {
+        // Find the largest dimension of the glyph, either its width or its height
+        let glyph_max_size: u32 = glyph_texture
+            .texture_descriptor
+            .size
+            .height
+            .max(glyph_texture.width());
+        // Pick the higher of 512 or the smallest power of 2 greater than glyph_max_size
+        let containing = (1u32 << (32 - glyph_max_size.leading_zeros())).max(512);
+        font_atlases.push(new_atlas(
+            textures,
+            texture_atlases,
+            UVec2::splat(containing),
+        ));
+        if !font_atlases.last_mut().unwrap().add_glyph(
+            textures,
+            texture_atlases,
+            &placed_glyph,
+            glyph_texture,
+        ) {
+            return Err(TextError::FailedToAddGlyph(placed_glyph.glyph_id));
+        }
+    }
+    Ok(())
+}
+
+/// Distance fields don't depend on the position of glyphs.
+fn distance_field_glyph(glyph_id: GlyphId) -> PlacedGlyph {
+    PlacedGlyph {
+        glyph_id,
+        subpixel_offset: Point::default().into(),
+    }
+}
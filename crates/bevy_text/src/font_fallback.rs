@@ -0,0 +1,183 @@
+use ab_glyph::Font as _;
+use bevy_asset::{Assets, Handle};
+
+use crate::{Font, TextError, TextSection};
+
+/// A contiguous piece of a [`TextSection`] that is laid out with a single font.
+///
+/// Sections are split into runs so that characters missing from a section's font
+/// (for example emoji in a latin font) can be drawn with a fallback font instead.
+#[derive(Debug, Clone)]
+pub(crate) struct FontRun<'a> {
+    /// The index of the [`TextSection`] this run is part of.
+    pub section_index: usize,
+    /// The byte offset of this run in the section's text.
+    pub byte_offset: usize,
+    /// The text of this run.
+    pub text: &'a str,
+    /// The font used to lay out and draw this run.
+    pub font: &'a Handle<Font>,
+}
+
+/// Returns `true` for characters that never start a new run, because they modify or join
+/// the preceding character (zero width joiners and variation selectors in emoji sequences).
+fn continues_run(c: char) -> bool {
+    matches!(c, '\u{200C}' | '\u{200D}' | '\u{FE00}'..='\u{FE0F}' | '\u{E0020}'..='\u{E007F}')
+}
+
+/// Splits `sections` into [`FontRun`]s.
+///
+/// Every character uses the section's own font if that font has a glyph for it, otherwise the
+/// first font that does from the section's [`TextStyle::fallback_fonts`](crate::TextStyle::fallback_fonts),
+/// then from `fallback_fonts`. Characters that no font covers are kept in the
+/// section's font, so they render as the font's missing glyph.
+///
+/// Fallback fonts that have not finished loading are skipped.
+pub(crate) fn split_font_runs<'a>(
+    sections: &'a [TextSection],
+    fonts: &Assets<Font>,
+    fallback_fonts: &'a [Handle<Font>],
+) -> Result<Vec<FontRun<'a>>, TextError> {
+    let mut runs = Vec::with_capacity(sections.len());
+    for (section_index, section) in sections.iter().enumerate() {
+        let primary = fonts
+            .get(&section.style.font)
+            .ok_or(TextError::NoSuchFont)?;
+
+        let mut run_font = &section.style.font;
+        let mut run_start = 0;
+        for (byte_index, c) in section.value.char_indices() {
+            let font = if continues_run(c) {
+                run_font
+            } else if c.is_whitespace() || c.is_control() || primary.font.glyph_id(c).0 != 0 {
+                &section.style.font
+            } else {
+                section
+                    .style
+                    .fallback_fonts
+                    .iter()
+                    .chain(fallback_fonts)
+                    .find(|handle| {
+                        fonts
+                            .get(*handle)
+                            .is_some_and(|fallback| fallback.font.glyph_id(c).0 != 0)
+                    })
+                    .unwrap_or(&section.style.font)
+            };
+
+            if font != run_font {
+                if byte_index > run_start {
+                    runs.push(FontRun {
+                        section_index,
+                        byte_offset: run_start,
+                        text: &section.value[run_start..byte_index],
+                        font: run_font,
+                    });
+                }
+                run_font = font;
+                run_start = byte_index;
+            }
+        }
+
+        // Always emit the final run, even when empty, so every section is represented.
+        runs.push(FontRun {
+            section_index,
+            byte_offset: run_start,
+            text: &section.value[run_start..],
+            font: run_font,
+        });
+    }
+    Ok(runs)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::Assets;
+
+    use super::split_font_runs;
+    use crate::{Font, TextSection, TextStyle};
+
+    #[test]
+    fn split_runs_by_font_coverage() {
+        let mut fonts = Assets::<Font>::default();
+        // The subset only covers latin characters, Fira Sans also covers cyrillic and greek.
+        let latin = fonts
+            .add(Font::try_from_bytes(include_bytes!("FiraMono-subset.ttf").to_vec()).unwrap());
+        let fallback = fonts.add(
+            Font::try_from_bytes(
+                include_bytes!("../../../assets/fonts/FiraSans-Bold.ttf").to_vec(),
+            )
+            .unwrap(),
+        );
+        let sections = [
+            TextSection::new(
+                "Hello Привет αβ",
+                TextStyle {
+                    font: latin.clone(),
+                    ..Default::default()
+                },
+            ),
+            // No font covers these CJK characters, they stay in the section's font.
+            TextSection::new(
+                "日本 ok",
+                TextStyle {
+                    font: latin.clone(),
+                    ..Default::default()
+                },
+            ),
+            TextSection::new(
+                "",
+                TextStyle {
+                    font: latin.clone(),
+                    ..Default::default()
+                },
+            ),
+        ];
+
+        let fallback_fonts = [fallback.clone()];
+        let runs = split_font_runs(&sections, &fonts, &fallback_fonts).unwrap();
+        let runs: Vec<_> = runs
+            .iter()
+            .map(|run| {
+                (
+                    run.section_index,
+                    run.byte_offset,
+                    run.text,
+                    run.font == &fallback,
+                )
+            })
+            .collect();
+        assert_eq!(
+            runs,
+            [
+                (0, 0, "Hello ", false),
+                (0, 6, "Привет", true),
+                (0, 18, " ", false),
+                (0, 19, "αβ", true),
+                (1, 0, "日本 ok", false),
+                (2, 0, "", false),
+            ]
+        );
+
+        // Section fallbacks are tried before the global ones, unloaded fonts are skipped.
+        let unloaded = fonts.reserve_handle();
+        let sections = [TextSection::new(
+            "aПb",
+            TextStyle {
+                font: latin.clone(),
+                ..Default::default()
+            }
+            .with_fallback_font(unloaded)
+            .with_fallback_font(fallback.clone()),
+        )];
+        let runs = split_font_runs(&sections, &fonts, &[]).unwrap();
+        let texts: Vec<_> = runs
+            .iter()
+            .map(|run| (run.text, run.font == &fallback))
+            .collect();
+        assert_eq!(texts, [("a", false), ("П", true), ("b", false)]);
+
+        let sections = [TextSection::new("a", TextStyle::default())];
+        assert!(split_font_runs(&sections, &fonts, &[]).is_err());
+    }
+}
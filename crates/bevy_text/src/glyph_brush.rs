@@ -1,18 +1,17 @@
-use ab_glyph::{Font as _, FontArc, Glyph, PxScaleFont, ScaleFont as _};
+use ab_glyph::{v2::GlyphImage, Font as _, FontArc, Glyph, PxScaleFont, ScaleFont as _};
 use bevy_asset::{AssetId, Assets};
 use bevy_math::{Rect, Vec2};
 use bevy_reflect::Reflect;
 use bevy_render::texture::Image;
 use bevy_sprite::TextureAtlasLayout;
 use bevy_utils::warn_once;
-use glyph_brush_layout::{
-    BuiltInLineBreaker, FontId, GlyphPositioner, Layout, SectionGeometry, SectionGlyph,
-    SectionText, ToSectionText,
-};
+use glyph_brush_layout::{FontId, SectionGeometry, SectionGlyph, SectionText};
 
+use crate::typesetting::{layout_glyphs, TextSpacing};
 use crate::{
-    error::TextError, BreakLineOn, Font, FontAtlasSet, FontAtlasSets, GlyphAtlasInfo, JustifyText,
-    PlacedGlyph, TextSettings, YAxisOrientation,
+    colr, distance_field, error::TextError, BreakLineOn, Font, FontAtlasSet, FontAtlasSets,
+    GlyphAtlasInfo, JustifyText, PlacedGlyph, TabStops, TextRendering, TextSettings,
+    YAxisOrientation,
 };
 
 pub struct GlyphBrush {
@@ -32,9 +31,12 @@ impl Default for GlyphBrush {
 }
 
 impl GlyphBrush {
-    pub fn compute_glyphs<S: ToSectionText>(
+    /// Lays out `sections`, see [`layout_glyphs`].
+    pub(crate) fn compute_glyphs(
         &self,
-        sections: &[S],
+        sections: &[SectionText],
+        spacings: &[TextSpacing],
+        tab_stops: &TabStops,
         bounds: Vec2,
         text_alignment: JustifyText,
         linebreak_behavior: BreakLineOn,
@@ -44,12 +46,15 @@ impl GlyphBrush {
             ..Default::default()
         };
 
-        let lbb: BuiltInLineBreaker = linebreak_behavior.into();
-
-        let section_glyphs = Layout::default()
-            .h_align(text_alignment.into())
-            .line_breaker(lbb)
-            .calculate_glyphs(&self.fonts, &geom, sections);
+        let section_glyphs = layout_glyphs(
+            &self.fonts,
+            sections,
+            spacings,
+            tab_stops,
+            &geom,
+            text_alignment,
+            linebreak_behavior.into(),
+        );
         Ok(section_glyphs)
     }
 
@@ -63,6 +68,7 @@ impl GlyphBrush {
         texture_atlases: &mut Assets<TextureAtlasLayout>,
         textures: &mut Assets<Image>,
         text_settings: &TextSettings,
+        rendering: TextRendering,
         y_axis_orientation: YAxisOrientation,
         h_anchor: f32,
     ) -> Result<Vec<PositionedGlyph>, TextError> {
@@ -101,55 +107,138 @@ impl GlyphBrush {
             };
             let adjust = GlyphPlacementAdjuster::new(&mut glyph);
             let section_data = sections_data[sg.section_index];
-            if let Some(outlined_glyph) = section_data.1.font.outline_glyph(glyph) {
-                let bounds = outlined_glyph.px_bounds();
-                let font_atlas_set = font_atlas_sets
-                    .sets
-                    .entry(*section_data.0)
-                    .or_insert_with(FontAtlasSet::default);
+            let font_atlas_set = font_atlas_sets
+                .sets
+                .entry(*section_data.0)
+                .or_insert_with(FontAtlasSet::default);
+
+            let color_glyph = raster_glyph_image(&section_data.3, &glyph)
+                .and_then(|(bounds, glyph_image, size)| {
+                    if let Some(atlas_info) =
+                        font_atlas_set.get_glyph_atlas_info(section_data.2, &placed_glyph)
+                    {
+                        return Some(Ok((bounds, atlas_info)));
+                    }
+                    let glyph_texture = Font::get_raster_glyph_texture(&glyph_image, size)?;
+                    Some(
+                        font_atlas_set
+                            .add_glyph_texture_to_atlas(
+                                texture_atlases,
+                                textures,
+                                section_data.2,
+                                placed_glyph,
+                                &glyph_texture,
+                            )
+                            .map(|atlas_info| (bounds, atlas_info)),
+                    )
+                })
+                .or_else(|| {
+                    let bounds = colr::colr_glyph_bounds(&section_data.3, &glyph)?;
+                    if let Some(atlas_info) =
+                        font_atlas_set.get_glyph_atlas_info(section_data.2, &placed_glyph)
+                    {
+                        return Some(Ok((bounds, atlas_info)));
+                    }
+                    let glyph_texture =
+                        colr::get_colr_glyph_texture(&section_data.3, &glyph, bounds)?;
+                    Some(
+                        font_atlas_set
+                            .add_glyph_texture_to_atlas(
+                                texture_atlases,
+                                textures,
+                                section_data.2,
+                                placed_glyph,
+                                &glyph_texture,
+                            )
+                            .map(|atlas_info| (bounds, atlas_info)),
+                    )
+                })
+                .transpose()?;
+
+            let distance_field_glyph =
+                if color_glyph.is_none() && rendering == TextRendering::DistanceField {
+                    distance_field::distance_field_glyph_bounds(&section_data.1.font, &glyph)
+                        .and_then(|bounds| {
+                            if let Some(atlas_info) =
+                                font_atlas_set.get_distance_field_glyph_atlas_info(glyph.id)
+                            {
+                                return Some(Ok((bounds, atlas_info)));
+                            }
+                            let glyph_texture = distance_field::get_distance_field_glyph_texture(
+                                &section_data.1.font,
+                                glyph.id,
+                            )?;
+                            Some(
+                                font_atlas_set
+                                    .add_distance_field_glyph_to_atlas(
+                                        texture_atlases,
+                                        textures,
+                                        glyph.id,
+                                        &glyph_texture,
+                                    )
+                                    .map(|atlas_info| (bounds, atlas_info)),
+                            )
+                        })
+                        .transpose()?
+                } else {
+                    None
+                };
+            let distance_field = distance_field_glyph.is_some();
 
+            let (bounds, atlas_info, is_color) = if let Some((bounds, atlas_info)) = color_glyph {
+                (bounds, atlas_info, true)
+            } else if let Some((bounds, atlas_info)) = distance_field_glyph {
+                (bounds, atlas_info, false)
+            } else if let Some(outlined_glyph) = section_data.1.font.outline_glyph(glyph) {
+                let bounds = outlined_glyph.px_bounds();
                 let atlas_info = font_atlas_set
                     .get_glyph_atlas_info(section_data.2, &placed_glyph)
                     .map(Ok)
                     .unwrap_or_else(|| {
                         font_atlas_set.add_glyph_to_atlas(texture_atlases, textures, outlined_glyph)
                     })?;
+                (bounds, atlas_info, false)
+            } else {
+                continue;
+            };
+
+            if !text_settings.allow_dynamic_font_size
+                && font_atlas_set.len() > text_settings.soft_max_font_atlases.get()
+            {
+                warn_once!(
+                    "warning[B0005]: Number of font atlases has exceeded the maximum of {}. Performance and memory usage may suffer. See: https://bevyengine.org/learn/errors/#b0005",
+                    text_settings.soft_max_font_atlases.get());
+            }
 
-                if !text_settings.allow_dynamic_font_size
-                    && font_atlas_set.len() > text_settings.soft_max_font_atlases.get()
-                {
-                    warn_once!(
-                        "warning[B0005]: Number of font atlases has exceeded the maximum of {}. Performance and memory usage may suffer. See: https://bevyengine.org/learn/errors/#b0005",
-                        text_settings.soft_max_font_atlases.get());
-                }
+            let texture_atlas = texture_atlases.get(&atlas_info.texture_atlas).unwrap();
+            let glyph_rect = texture_atlas.textures[atlas_info.glyph_index];
+            // Distance fields are scaled to the font size, and cover their bounds exactly.
+            let (size, padding) = if distance_field {
+                (Vec2::new(bounds.width(), bounds.height()), 0.)
+            } else {
+                (glyph_rect.size().as_vec2(), 1.)
+            };
 
-                let texture_atlas = texture_atlases.get(&atlas_info.texture_atlas).unwrap();
-                let glyph_rect = texture_atlas.textures[atlas_info.glyph_index];
-                let size = glyph_rect.size().as_vec2();
+            let x = bounds.min.x + size.x / 2.0 + h_anchor;
 
-                let x = bounds.min.x + size.x / 2.0 + h_anchor;
+            let y = match y_axis_orientation {
+                YAxisOrientation::BottomToTop => text_bounds.max.y - bounds.max.y + size.y / 2.0,
+                YAxisOrientation::TopToBottom => bounds.min.y + size.y / 2.0 - text_bounds.min.y,
+            };
 
-                let y = match y_axis_orientation {
-                    YAxisOrientation::BottomToTop => {
-                        text_bounds.max.y - bounds.max.y + size.y / 2.0
-                    }
-                    YAxisOrientation::TopToBottom => {
-                        bounds.min.y + size.y / 2.0 - text_bounds.min.y
-                    }
-                };
+            // We must offset by 1 to account for glyph texture padding.
+            // See https://github.com/bevyengine/bevy/pull/11662
+            let position = adjust.position(Vec2::new(x, y) - padding);
 
-                // We must offset by 1 to account for glyph texture padding.
-                // See https://github.com/bevyengine/bevy/pull/11662
-                let position = adjust.position(Vec2::new(x, y) - 1.);
-
-                positioned_glyphs.push(PositionedGlyph {
-                    position,
-                    size,
-                    atlas_info,
-                    section_index: sg.section_index,
-                    byte_index,
-                });
-            }
+            positioned_glyphs.push(PositionedGlyph {
+                position,
+                size,
+                atlas_info,
+                section_index: sg.section_index,
+                byte_index,
+                is_color,
+                distance_field,
+            });
         }
         Ok(positioned_glyphs)
     }
@@ -166,10 +255,122 @@ impl GlyphBrush {
 #[derive(Debug, Clone, Reflect)]
 pub struct PositionedGlyph {
     pub position: Vec2,
+    /// The size the glyph is drawn at, in physical pixels. This is the size of its texture in
+    /// the atlas, except for distance field glyphs, which are scaled to the font size.
     pub size: Vec2,
     pub atlas_info: GlyphAtlasInfo,
     pub section_index: usize,
     pub byte_index: usize,
+    /// Whether this glyph was drawn from a color bitmap or `COLR` layers, such as an emoji.
+    ///
+    /// Color glyphs keep their own colors instead of being tinted by the [`TextStyle`](crate::TextStyle) color.
+    pub is_color: bool,
+    /// Whether this glyph is drawn from a distance field, see
+    /// [`TextRendering::DistanceField`](crate::TextRendering::DistanceField).
+    pub distance_field: bool,
+}
+
+/// A run of consecutive glyphs of a single section on a single line of text, used to draw the
+/// highlight and lines of the section and to find the section under a point.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct PositionedSpan {
+    pub section_index: usize,
+    /// The area covered by the glyphs, from the ascent to the descent of their font, in the same
+    /// space as [`PositionedGlyph::position`].
+    pub rect: Rect,
+    /// The line drawn under the glyphs when [`TextStyle::underline`](crate::TextStyle::underline) is set.
+    pub underline: Rect,
+    /// The line drawn through the glyphs when [`TextStyle::strikethrough`](crate::TextStyle::strikethrough) is set.
+    pub strikethrough: Rect,
+}
+
+/// Computes the [`PositionedSpan`]s of the laid out glyphs, in the same space as
+/// [`GlyphBrush::process_glyphs`]. `section_of` maps the section index of a glyph to the index of
+/// the section of the text it was laid out from.
+pub(crate) fn compute_text_spans<T>(
+    section_glyphs: &[SectionGlyph],
+    get_scaled_font: impl Fn(usize) -> PxScaleFont<T>,
+    section_of: impl Fn(usize) -> usize,
+    text_bounds: Rect,
+    y_axis_orientation: YAxisOrientation,
+    h_anchor: f32,
+) -> Vec<PositionedSpan>
+where
+    T: ab_glyph::Font,
+{
+    let to_rect = |min: Vec2, max: Vec2| {
+        let [min_y, max_y] = [min.y, max.y].map(|y| match y_axis_orientation {
+            YAxisOrientation::BottomToTop => text_bounds.max.y - y,
+            YAxisOrientation::TopToBottom => y - text_bounds.min.y,
+        });
+        Rect::new(min.x + h_anchor, min_y, max.x + h_anchor, max_y)
+    };
+
+    let mut spans: Vec<PositionedSpan> = Vec::new();
+    let mut line_baseline = f32::NAN;
+    for sg in section_glyphs {
+        let scaled_font = get_scaled_font(sg.section_index);
+        let section_index = section_of(sg.section_index);
+        let glyph = &sg.glyph;
+        // The glyphs of a line share its baseline.
+        let baseline = glyph.position.y;
+        let min_x = glyph.position.x;
+        let max_x = min_x + scaled_font.h_advance(glyph.id);
+        let thickness = (scaled_font.height() / 14.).round().max(1.);
+        let strikethrough_y = baseline - 0.3 * scaled_font.ascent();
+        let span = PositionedSpan {
+            section_index,
+            rect: to_rect(
+                Vec2::new(min_x, baseline - scaled_font.ascent()),
+                Vec2::new(max_x, baseline - scaled_font.descent()),
+            ),
+            underline: to_rect(
+                Vec2::new(min_x, baseline + thickness),
+                Vec2::new(max_x, baseline + 2. * thickness),
+            ),
+            strikethrough: to_rect(
+                Vec2::new(min_x, strikethrough_y - 0.5 * thickness),
+                Vec2::new(max_x, strikethrough_y + 0.5 * thickness),
+            ),
+        };
+        match spans.last_mut() {
+            Some(last) if last.section_index == section_index && line_baseline == baseline => {
+                last.rect = last.rect.union(span.rect);
+                last.underline = last.underline.union(span.underline);
+                last.strikethrough = last.strikethrough.union(span.strikethrough);
+            }
+            _ => spans.push(span),
+        }
+        line_baseline = baseline;
+    }
+    spans
+}
+
+/// Looks up the color bitmap image of `glyph` in its font, if there is any.
+///
+/// Returns the pixel bounds of the glyph, in the same space as [`ab_glyph::OutlinedGlyph::px_bounds`],
+/// the image and the size in pixels it should be drawn at.
+fn raster_glyph_image<'a>(
+    scaled_font: &PxScaleFont<&'a FontArc>,
+    glyph: &Glyph,
+) -> Option<(ab_glyph::Rect, GlyphImage<'a>, (u32, u32))> {
+    let font = *scaled_font.font();
+    // `PxScale` is the height of the font's line, bitmap strikes are sized in pixels per em.
+    let pixels_per_em = scaled_font.scale_factor().vertical * font.units_per_em()?;
+    let glyph_image = font.glyph_raster_image2(glyph.id, pixels_per_em.round() as u16)?;
+    let image_scale = pixels_per_em / glyph_image.pixels_per_em.max(1) as f32;
+
+    let width = (glyph_image.width as f32 * image_scale).round();
+    let height = (glyph_image.height as f32 * image_scale).round();
+
+    // The image origin is the offset of its bottom left corner from the glyph origin, with y up.
+    let min_x = glyph.position.x + glyph_image.origin.x * image_scale;
+    let max_y = glyph.position.y - glyph_image.origin.y * image_scale;
+    let bounds = ab_glyph::Rect {
+        min: ab_glyph::point(min_x, max_y - height),
+        max: ab_glyph::point(min_x + width, max_y),
+    };
+    Some((bounds, glyph_image, (width as u32, height as u32)))
 }
 
 #[cfg(feature = "subpixel_glyph_atlas")]
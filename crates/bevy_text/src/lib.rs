@@ -7,16 +7,23 @@
     html_favicon_url = "https://bevyengine.org/assets/icon.png"
 )]
 
+mod colr;
+mod distance_field;
 mod error;
 mod font;
 mod font_atlas;
 mod font_atlas_set;
+mod font_fallback;
 mod font_loader;
 mod glyph_brush;
 mod pipeline;
+mod system_fonts;
 mod text;
 mod text2d;
+mod text_effect;
+mod typesetting;
 
+pub use distance_field::{DISTANCE_FIELD_FONT_SIZE, DISTANCE_FIELD_RANGE};
 pub use error::*;
 pub use font::*;
 pub use font_atlas::*;
@@ -24,18 +31,22 @@ pub use font_atlas_set::*;
 pub use font_loader::*;
 pub use glyph_brush::*;
 pub use pipeline::*;
+pub use system_fonts::*;
 pub use text::*;
 pub use text2d::*;
+pub use text_effect::*;
 
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{Font, JustifyText, Text, Text2dBundle, TextError, TextSection, TextStyle};
+    pub use crate::{
+        Font, JustifyText, Text, Text2dBundle, TextError, TextRendering, TextSection, TextStyle,
+    };
 }
 
 use bevy_app::prelude::*;
-use bevy_asset::AssetApp;
 #[cfg(feature = "default_font")]
-use bevy_asset::{load_internal_binary_asset, Handle};
+use bevy_asset::load_internal_binary_asset;
+use bevy_asset::{AssetApp, Handle};
 use bevy_ecs::prelude::*;
 use bevy_render::{
     camera::CameraUpdateSystem, view::VisibilitySystems, ExtractSchedule, RenderApp,
@@ -59,6 +70,13 @@ pub struct TextSettings {
     /// Allows font size to be set dynamically exceeding the amount set in `soft_max_font_atlases`.
     /// Note each font size has to be generated which can have a strong performance impact.
     pub allow_dynamic_font_size: bool,
+    /// Fonts used, in order, for characters that are missing from a [`TextStyle`]'s font
+    /// and its own [`TextStyle::fallback_fonts`].
+    ///
+    /// This is typically used to add an emoji font, or fonts covering other scripts.
+    /// Color glyphs, stored as bitmaps (`CBDT` and `sbix` tables) or as layers of colored outlines
+    /// (`COLR` table, versions 0 and 1), are drawn with their own colors.
+    pub fallback_fonts: Vec<Handle<Font>>,
 }
 
 impl Default for TextSettings {
@@ -66,6 +84,7 @@ impl Default for TextSettings {
         Self {
             soft_max_font_atlases: NonZeroUsize::new(16).unwrap(),
             allow_dynamic_font_size: false,
+            fallback_fonts: Vec::new(),
         }
     }
 }
@@ -73,6 +92,7 @@ impl Default for TextSettings {
 /// Text is rendered for two different view projections, a [`Text2dBundle`] is rendered with a
 /// `BottomToTop` y axis, while UI is rendered with a `TopToBottom` y axis. This matters for text because
 /// the glyph positioning is different in either layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum YAxisOrientation {
     TopToBottom,
     BottomToTop,
@@ -87,9 +107,11 @@ impl Plugin for TextPlugin {
         app.init_asset::<Font>()
             .register_type::<Text>()
             .register_type::<Text2dBounds>()
+            .register_type::<TextGlyphEffects>()
             .init_asset_loader::<FontLoader>()
             .init_resource::<TextSettings>()
             .init_resource::<FontAtlasSets>()
+            .init_resource::<SystemFonts>()
             .insert_resource(TextPipeline::default())
             .add_systems(
                 PostUpdate,
@@ -105,8 +127,10 @@ impl Plugin for TextPlugin {
                         // will never modify a pre-existing `Image` asset.
                         .ambiguous_with(CameraUpdateSystem),
                     remove_dropped_font_atlas_sets,
+                    text_effect::reset_text_glyph_effects.before(TextEffectSystem),
                 ),
-            );
+            )
+            .configure_sets(PostUpdate, TextEffectSystem.after(update_text2d_layout));
 
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app.add_systems(
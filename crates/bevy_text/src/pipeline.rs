@@ -1,6 +1,12 @@
 use crate::{
-    compute_text_bounds, error::TextError, glyph_brush::GlyphBrush, scale_value, BreakLineOn, Font,
-    FontAtlasSets, JustifyText, PositionedGlyph, Text, TextSection, TextSettings, YAxisOrientation,
+    compute_text_bounds, compute_text_spans,
+    error::TextError,
+    font_fallback::split_font_runs,
+    glyph_brush::GlyphBrush,
+    scale_value,
+    typesetting::{layout_glyphs, TextSpacing},
+    BreakLineOn, Font, FontAtlasSets, JustifyText, PositionedGlyph, PositionedSpan, TabStops, Text,
+    TextRendering, TextSection, TextSettings, YAxisOrientation,
 };
 use ab_glyph::PxScale;
 use bevy_asset::{AssetId, Assets, Handle};
@@ -13,7 +19,7 @@ use bevy_reflect::Reflect;
 use bevy_render::texture::Image;
 use bevy_sprite::TextureAtlasLayout;
 use bevy_utils::HashMap;
-use glyph_brush_layout::{FontId, GlyphPositioner, SectionGeometry, SectionText, ToSectionText};
+use glyph_brush_layout::{FontId, SectionGeometry, SectionText, ToSectionText};
 
 #[derive(Default, Resource)]
 pub struct TextPipeline {
@@ -28,9 +34,34 @@ pub struct TextPipeline {
 #[reflect(Component, Default)]
 pub struct TextLayoutInfo {
     pub glyphs: Vec<PositionedGlyph>,
+    /// The runs of glyphs of each section on each line, in the order they were laid out.
+    pub spans: Vec<PositionedSpan>,
     pub logical_size: Vec2,
 }
 
+impl TextLayoutInfo {
+    /// Returns the index of the section with a glyph at `point`, in the same space as
+    /// [`PositionedGlyph::position`].
+    pub fn section_at(&self, point: Vec2) -> Option<usize> {
+        self.spans
+            .iter()
+            .find(|span| span.rect.contains(point))
+            .map(|span| span.section_index)
+    }
+
+    /// Returns the glyphs laid out from the section at `section_index`, along with their index in
+    /// [`glyphs`](Self::glyphs).
+    pub fn section_glyphs(
+        &self,
+        section_index: usize,
+    ) -> impl Iterator<Item = (usize, &PositionedGlyph)> {
+        self.glyphs
+            .iter()
+            .enumerate()
+            .filter(move |(_, glyph)| glyph.section_index == section_index)
+    }
+}
+
 impl TextPipeline {
     pub fn get_or_insert_font_id(&mut self, handle: &Handle<Font>, font: &Font) -> FontId {
         let brush = &mut self.brush;
@@ -48,6 +79,8 @@ impl TextPipeline {
         scale_factor: f32,
         text_alignment: JustifyText,
         linebreak_behavior: BreakLineOn,
+        rendering: TextRendering,
+        tab_stops: &TabStops,
         bounds: Vec2,
         font_atlas_sets: &mut FontAtlasSets,
         texture_atlases: &mut Assets<TextureAtlasLayout>,
@@ -55,37 +88,47 @@ impl TextPipeline {
         text_settings: &TextSettings,
         y_axis_orientation: YAxisOrientation,
     ) -> Result<TextLayoutInfo, TextError> {
-        let mut scaled_fonts = Vec::with_capacity(sections.len());
-        let sections = sections
+        let runs = split_font_runs(sections, fonts, &text_settings.fallback_fonts)?;
+        let mut scaled_fonts = Vec::with_capacity(runs.len());
+        let spacings: Vec<_> = runs
+            .iter()
+            .map(|run| TextSpacing::of(&sections[run.section_index].style, scale_factor))
+            .collect();
+        let run_sections = runs
             .iter()
-            .map(|section| {
-                let font = fonts
-                    .get(&section.style.font)
-                    .ok_or(TextError::NoSuchFont)?;
-                let font_id = self.get_or_insert_font_id(&section.style.font, font);
-                let font_size = scale_value(section.style.font_size, scale_factor);
+            .map(|run| {
+                let font = fonts.get(run.font).ok_or(TextError::NoSuchFont)?;
+                let font_id = self.get_or_insert_font_id(run.font, font);
+                let font_size =
+                    scale_value(sections[run.section_index].style.font_size, scale_factor);
 
                 scaled_fonts.push(ab_glyph::Font::as_scaled(&font.font, font_size));
 
                 let section = SectionText {
                     font_id,
                     scale: PxScale::from(font_size),
-                    text: &section.value,
+                    text: run.text,
                 };
 
                 Ok(section)
             })
             .collect::<Result<Vec<_>, _>>()?;
 
-        let section_glyphs =
-            self.brush
-                .compute_glyphs(&sections, bounds, text_alignment, linebreak_behavior)?;
+        let section_glyphs = self.brush.compute_glyphs(
+            &run_sections,
+            &spacings,
+            &tab_stops.scaled(scale_factor),
+            bounds,
+            text_alignment,
+            linebreak_behavior,
+        )?;
 
         if section_glyphs.is_empty() {
             return Ok(TextLayoutInfo::default());
         }
 
-        let size = compute_text_bounds(&section_glyphs, |index| scaled_fonts[index]).size();
+        let text_bounds = compute_text_bounds(&section_glyphs, |index| scaled_fonts[index]);
+        let size = text_bounds.size();
 
         let h_limit = if bounds.x.is_finite() {
             bounds.x
@@ -100,20 +143,38 @@ impl TextPipeline {
         }
         .floor();
 
-        let glyphs = self.brush.process_glyphs(
+        let spans = compute_text_spans(
+            &section_glyphs,
+            |index| scaled_fonts[index],
+            |index| runs[index].section_index,
+            text_bounds,
+            y_axis_orientation,
+            h_anchor,
+        );
+
+        let mut glyphs = self.brush.process_glyphs(
             section_glyphs,
-            &sections,
+            &run_sections,
             font_atlas_sets,
             fonts,
             texture_atlases,
             textures,
             text_settings,
+            rendering,
             y_axis_orientation,
             h_anchor,
         )?;
 
+        // Glyphs are laid out per font run, map them back to the sections they came from.
+        for glyph in &mut glyphs {
+            let run = &runs[glyph.section_index];
+            glyph.section_index = run.section_index;
+            glyph.byte_index += run.byte_offset;
+        }
+
         Ok(TextLayoutInfo {
             glyphs,
+            spans,
             logical_size: size,
         })
     }
@@ -124,6 +185,10 @@ pub struct TextMeasureSection {
     pub text: Box<str>,
     pub scale: f32,
     pub font_id: FontId,
+    /// The [`TextStyle::letter_spacing`](crate::TextStyle::letter_spacing) of the section, in physical pixels.
+    pub letter_spacing: f32,
+    /// The [`TextStyle::word_spacing`](crate::TextStyle::word_spacing) of the section, in physical pixels.
+    pub word_spacing: f32,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -132,6 +197,8 @@ pub struct TextMeasureInfo {
     pub sections: Box<[TextMeasureSection]>,
     pub justification: JustifyText,
     pub linebreak_behavior: glyph_brush_layout::BuiltInLineBreaker,
+    /// The [`Text::tab_stops`], in physical pixels.
+    pub tab_stops: TabStops,
     pub min: Vec2,
     pub max: Vec2,
 }
@@ -140,19 +207,23 @@ impl TextMeasureInfo {
     pub fn from_text(
         text: &Text,
         fonts: &Assets<Font>,
+        text_settings: &TextSettings,
         scale_factor: f32,
     ) -> Result<TextMeasureInfo, TextError> {
-        let sections = &text.sections;
-        let mut auto_fonts = Vec::with_capacity(sections.len());
-        let mut out_sections = Vec::with_capacity(sections.len());
-        for (i, section) in sections.iter().enumerate() {
-            match fonts.get(&section.style.font) {
+        let runs = split_font_runs(&text.sections, fonts, &text_settings.fallback_fonts)?;
+        let mut auto_fonts = Vec::with_capacity(runs.len());
+        let mut out_sections = Vec::with_capacity(runs.len());
+        for (i, run) in runs.iter().enumerate() {
+            match fonts.get(run.font) {
                 Some(font) => {
                     auto_fonts.push(font.font.clone());
+                    let style = &text.sections[run.section_index].style;
                     out_sections.push(TextMeasureSection {
                         font_id: FontId(i),
-                        scale: scale_value(section.style.font_size, scale_factor),
-                        text: section.value.clone().into_boxed_str(),
+                        scale: scale_value(style.font_size, scale_factor),
+                        text: run.text.into(),
+                        letter_spacing: scale_value(style.letter_spacing, scale_factor),
+                        word_spacing: scale_value(style.word_spacing, scale_factor),
                     });
                 }
                 None => return Err(TextError::NoSuchFont),
@@ -164,6 +235,7 @@ impl TextMeasureInfo {
             out_sections,
             text.justify,
             text.linebreak_behavior.into(),
+            text.tab_stops.scaled(scale_factor),
         ))
     }
     fn new(
@@ -171,12 +243,14 @@ impl TextMeasureInfo {
         sections: Vec<TextMeasureSection>,
         justification: JustifyText,
         linebreak_behavior: glyph_brush_layout::BuiltInLineBreaker,
+        tab_stops: TabStops,
     ) -> Self {
         let mut info = Self {
             fonts: fonts.into_boxed_slice(),
             sections: sections.into_boxed_slice(),
             justification,
             linebreak_behavior,
+            tab_stops,
             min: Vec2::ZERO,
             max: Vec2::ZERO,
         };
@@ -189,15 +263,28 @@ impl TextMeasureInfo {
     }
 
     pub fn compute_size(&self, bounds: Vec2) -> Vec2 {
-        let sections = &self.sections;
         let geom = SectionGeometry {
             bounds: (bounds.x, bounds.y),
             ..Default::default()
         };
-        let section_glyphs = glyph_brush_layout::Layout::default()
-            .h_align(self.justification.into())
-            .line_breaker(self.linebreak_behavior)
-            .calculate_glyphs(&self.fonts, &geom, sections);
+        let sections: Vec<_> = self.sections.iter().map(|s| s.to_section_text()).collect();
+        let spacings: Vec<_> = self
+            .sections
+            .iter()
+            .map(|section| TextSpacing {
+                letter: section.letter_spacing,
+                word: section.word_spacing,
+            })
+            .collect();
+        let section_glyphs = layout_glyphs(
+            &self.fonts,
+            &sections,
+            &spacings,
+            &self.tab_stops,
+            &geom,
+            self.justification,
+            self.linebreak_behavior,
+        );
 
         compute_text_bounds(&section_glyphs, |index| {
             let font = &self.fonts[index];
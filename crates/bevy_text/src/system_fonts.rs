@@ -0,0 +1,292 @@
+use std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::system::Resource;
+use bevy_utils::{tracing::warn, HashMap};
+
+use crate::Font;
+
+/// How deep font directories are searched for font files.
+const MAX_DIRECTORY_DEPTH: usize = 8;
+
+/// A font file installed on the system, found by [`SystemFonts::discover`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemFont {
+    /// The path of the font file.
+    pub path: PathBuf,
+    /// The family name of the font, for example `"Noto Sans CJK JP"`.
+    pub family: String,
+    /// The style of the font within its family, for example `"Regular"` or `"Bold Italic"`.
+    pub style: String,
+}
+
+/// The fonts installed on the system, which can be loaded at runtime and used as
+/// [`TextStyle::fallback_fonts`](crate::TextStyle::fallback_fonts).
+///
+/// This avoids shipping large fonts, for example for CJK scripts, that are usually already present on desktop platforms.
+/// Discovery is only done when requested, as scanning the font directories can take a while.
+///
+/// ```no_run
+/// # use bevy_asset::Assets;
+/// # use bevy_ecs::system::ResMut;
+/// # use bevy_text::{Font, SystemFonts, TextSettings};
+/// fn use_system_cjk_font(
+///     mut system_fonts: ResMut<SystemFonts>,
+///     mut fonts: ResMut<Assets<Font>>,
+///     mut text_settings: ResMut<TextSettings>,
+/// ) {
+///     if let Some(font) = system_fonts.load_family("Noto Sans CJK JP", &mut fonts) {
+///         text_settings.fallback_fonts.push(font);
+///     }
+/// }
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct SystemFonts {
+    fonts: Option<Vec<SystemFont>>,
+    loaded: HashMap<PathBuf, Handle<Font>>,
+}
+
+impl SystemFonts {
+    /// The directories searched for fonts on the current platform.
+    pub fn font_directories() -> Vec<PathBuf> {
+        let mut directories = Vec::new();
+        let home = std::env::var_os("HOME").map(PathBuf::from);
+        if cfg!(target_os = "windows") {
+            if let Some(windows) = std::env::var_os("WINDIR") {
+                directories.push(Path::new(&windows).join("Fonts"));
+            }
+            if let Some(local) = std::env::var_os("LOCALAPPDATA") {
+                directories.push(Path::new(&local).join("Microsoft/Windows/Fonts"));
+            }
+        } else if cfg!(target_os = "macos") {
+            directories.push("/System/Library/Fonts".into());
+            directories.push("/Library/Fonts".into());
+            if let Some(home) = &home {
+                directories.push(home.join("Library/Fonts"));
+            }
+        } else {
+            directories.push("/usr/share/fonts".into());
+            directories.push("/usr/local/share/fonts".into());
+            if let Some(data_home) = std::env::var_os("XDG_DATA_HOME") {
+                directories.push(Path::new(&data_home).join("fonts"));
+            } else if let Some(home) = &home {
+                directories.push(home.join(".local/share/fonts"));
+            }
+            if let Some(home) = &home {
+                directories.push(home.join(".fonts"));
+            }
+        }
+        directories
+    }
+
+    /// Scans the [font directories](Self::font_directories) for font files.
+    ///
+    /// This is done automatically the first time fonts are looked up, call it again to pick up
+    /// newly installed fonts.
+    pub fn discover(&mut self) -> &[SystemFont] {
+        let mut fonts = Vec::new();
+        for directory in Self::font_directories() {
+            scan_directory(&directory, 0, &mut fonts);
+        }
+        fonts.sort_by(|a, b| (&a.family, &a.style).cmp(&(&b.family, &b.style)));
+        self.fonts.insert(fonts)
+    }
+
+    /// Returns all the fonts installed on the system.
+    pub fn fonts(&mut self) -> &[SystemFont] {
+        if self.fonts.is_none() {
+            self.discover();
+        }
+        self.fonts.as_deref().unwrap_or_default()
+    }
+
+    /// Finds a font of the given family, ignoring case.
+    ///
+    /// The `"Regular"` style is preferred when the family has several styles.
+    pub fn find_family(&mut self, family: &str) -> Option<&SystemFont> {
+        let mut matching = self
+            .fonts()
+            .iter()
+            .filter(|font| font.family.eq_ignore_ascii_case(family));
+        let first = matching.next()?;
+        Some(
+            std::iter::once(first)
+                .chain(matching)
+                .find(|font| font.style.eq_ignore_ascii_case("regular"))
+                .unwrap_or(first),
+        )
+    }
+
+    /// Loads the font of the given family into `fonts`, see [`SystemFonts::find_family`].
+    ///
+    /// The font file is read synchronously. Loading the same font again returns the same handle.
+    pub fn load_family(&mut self, family: &str, fonts: &mut Assets<Font>) -> Option<Handle<Font>> {
+        let path = self.find_family(family)?.path.clone();
+        self.load(&path, fonts)
+    }
+
+    /// Loads the font at `path`, which is usually one of the [discovered](Self::fonts) fonts, into `fonts`.
+    ///
+    /// The font file is read synchronously. Loading the same font again returns the same handle.
+    pub fn load(&mut self, path: &Path, fonts: &mut Assets<Font>) -> Option<Handle<Font>> {
+        if let Some(handle) = self.loaded.get(path) {
+            return Some(handle.clone());
+        }
+        let font = std::fs::read(path)
+            .map_err(|err| err.to_string())
+            .and_then(|bytes| Font::try_from_bytes(bytes).map_err(|err| err.to_string()));
+        match font {
+            Ok(font) => {
+                let handle = fonts.add(font);
+                self.loaded.insert(path.to_path_buf(), handle.clone());
+                Some(handle)
+            }
+            Err(err) => {
+                warn!("Failed to load system font {}: {err}", path.display());
+                None
+            }
+        }
+    }
+}
+
+fn scan_directory(directory: &Path, depth: usize, fonts: &mut Vec<SystemFont>) {
+    if depth > MAX_DIRECTORY_DEPTH {
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(directory) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            scan_directory(&path, depth + 1, fonts);
+            continue;
+        }
+        let is_font = path.extension().is_some_and(|extension| {
+            ["ttf", "otf", "ttc", "otc"]
+                .iter()
+                .any(|font_extension| extension.eq_ignore_ascii_case(font_extension))
+        });
+        if !is_font {
+            continue;
+        }
+        if let Some((family, style)) = File::open(&path)
+            .ok()
+            .and_then(|mut file| read_font_names(&mut file))
+        {
+            fonts.push(SystemFont {
+                path,
+                family,
+                style,
+            });
+        }
+    }
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(
+        bytes.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(
+        bytes.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+fn read_at(file: &mut (impl Read + Seek), offset: u64, length: usize) -> Option<Vec<u8>> {
+    let mut bytes = vec![0; length];
+    file.seek(SeekFrom::Start(offset)).ok()?;
+    file.read_exact(&mut bytes).ok()?;
+    Some(bytes)
+}
+
+/// Reads the family and style names from the `name` table of a font file, without reading the
+/// whole file. Only the first font of a collection is considered, matching [`Font::try_from_bytes`].
+fn read_font_names(file: &mut (impl Read + Seek)) -> Option<(String, String)> {
+    let header = read_at(file, 0, 12)?;
+    let font_offset = if &header[0..4] == b"ttcf" {
+        read_u32(&header, 8)? as u64
+    } else {
+        0
+    };
+
+    let offset_table = read_at(file, font_offset, 12)?;
+    let table_count = read_u16(&offset_table, 4)? as usize;
+    let table_records = read_at(file, font_offset + 12, table_count * 16)?;
+    let (name_offset, name_length) = table_records.chunks_exact(16).find_map(|record| {
+        (&record[0..4] == b"name").then(|| (read_u32(record, 8), read_u32(record, 12)))
+    })?;
+    let name_table = read_at(file, name_offset? as u64, name_length? as usize)?;
+
+    let record_count = read_u16(&name_table, 2)? as usize;
+    let storage_offset = read_u16(&name_table, 4)? as usize;
+    let mut family = None;
+    let mut typographic_family = None;
+    let mut style = None;
+    for index in 0..record_count {
+        let record = 6 + index * 12;
+        let platform = read_u16(&name_table, record)?;
+        let language = read_u16(&name_table, record + 4)?;
+        let name_id = read_u16(&name_table, record + 6)?;
+        let length = read_u16(&name_table, record + 8)? as usize;
+        let offset = storage_offset + read_u16(&name_table, record + 10)? as usize;
+        let target = match name_id {
+            1 => &mut family,
+            2 => &mut style,
+            16 => &mut typographic_family,
+            _ => continue,
+        };
+        let Some(bytes) = name_table.get(offset..offset + length) else {
+            continue;
+        };
+        // Prefer english names from the Windows and Unicode platforms, which are UTF-16 encoded.
+        let (priority, name) = match platform {
+            0 | 3 => {
+                let units = bytes
+                    .chunks_exact(2)
+                    .map(|unit| u16::from_be_bytes([unit[0], unit[1]]));
+                let priority = if platform == 3 && language == 0x409 {
+                    2
+                } else {
+                    1
+                };
+                (
+                    priority,
+                    String::from_utf16_lossy(&units.collect::<Vec<_>>()),
+                )
+            }
+            1 => (0, bytes.iter().map(|&byte| byte as char).collect()),
+            _ => continue,
+        };
+        if target
+            .as_ref()
+            .map_or(true, |(current, _)| priority > *current)
+        {
+            *target = Some((priority, name));
+        }
+    }
+
+    let family = typographic_family.or(family)?.1;
+    let style = style.map(|(_, style)| style).unwrap_or_default();
+    Some((family, style))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::read_font_names;
+
+    #[test]
+    fn read_names_from_font_file() {
+        let mut font = std::io::Cursor::new(include_bytes!("FiraMono-subset.ttf"));
+        let (family, style) = read_font_names(&mut font).expect("Font should have a name table");
+        // The typographic family name is preferred over the legacy family name "Fira Mono Medium".
+        assert_eq!(family, "Fira Mono");
+        assert_eq!(style, "Regular");
+    }
+}
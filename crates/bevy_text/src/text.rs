@@ -1,6 +1,7 @@
 use bevy_asset::Handle;
-use bevy_color::Color;
+use bevy_color::{Color, ColorToComponents, LinearRgba};
 use bevy_ecs::{prelude::Component, reflect::ReflectComponent};
+use bevy_math::Vec2;
 use bevy_reflect::prelude::*;
 use bevy_utils::default;
 use serde::{Deserialize, Serialize};
@@ -16,6 +17,10 @@ pub struct Text {
     pub justify: JustifyText,
     /// How the text should linebreak when running out of the bounds determined by `max_size`
     pub linebreak_behavior: BreakLineOn,
+    /// How the glyphs of the text are rendered.
+    pub rendering: TextRendering,
+    /// Where tab characters (`\t`) move the glyphs that follow them.
+    pub tab_stops: TabStops,
 }
 
 impl Text {
@@ -25,6 +30,7 @@ impl Text {
     /// # use bevy_asset::Handle;
     /// # use bevy_color::Color;
     /// # use bevy_text::{Font, Text, TextStyle, JustifyText};
+    /// # use bevy_utils::default;
     /// #
     /// # let font_handle: Handle<Font> = Default::default();
     /// #
@@ -36,6 +42,7 @@ impl Text {
     ///         font: font_handle.clone(),
     ///         font_size: 60.0,
     ///         color: Color::WHITE,
+    ///         ..default()
     ///     },
     /// );
     ///
@@ -45,6 +52,7 @@ impl Text {
     ///         font: font_handle,
     ///         font_size: 60.0,
     ///         color: Color::WHITE,
+    ///         ..default()
     ///     },
     /// ) // You can still add text justifaction.
     /// .with_justify(JustifyText::Center);
@@ -63,6 +71,7 @@ impl Text {
     /// # use bevy_color::Color;
     /// # use bevy_color::palettes::basic::{RED, BLUE};
     /// # use bevy_text::{Font, Text, TextStyle, TextSection};
+    /// # use bevy_utils::default;
     /// #
     /// # let font_handle: Handle<Font> = Default::default();
     /// #
@@ -73,6 +82,7 @@ impl Text {
     ///             font: font_handle.clone(),
     ///             font_size: 60.0,
     ///             color: BLUE.into(),
+    ///             ..default()
     ///         },
     ///     ),
     ///     TextSection::new(
@@ -81,6 +91,7 @@ impl Text {
     ///             font: font_handle,
     ///             font_size: 60.0,
     ///             color: RED.into(),
+    ///             ..default()
     ///         },
     ///     ),
     /// ]);
@@ -104,6 +115,98 @@ impl Text {
         self.linebreak_behavior = BreakLineOn::NoWrap;
         self
     }
+
+    /// Returns this [`Text`] with a new [`TextRendering`].
+    pub const fn with_rendering(mut self, rendering: TextRendering) -> Self {
+        self.rendering = rendering;
+        self
+    }
+
+    /// Returns this [`Text`] with new [`TabStops`].
+    pub fn with_tab_stops(mut self, tab_stops: TabStops) -> Self {
+        self.tab_stops = tab_stops;
+        self
+    }
+}
+
+/// Per-glyph offsets and tints applied to a [`Text`] when it is drawn, for effects such as wavy,
+/// shaking or typewriter text.
+///
+/// The effect at index `i` applies to the glyph at index `i` of the entity's
+/// [`TextLayoutInfo`](crate::TextLayoutInfo), which lists the glyphs along with their positions
+/// and the section and byte index they were laid out from. Glyphs past the end of
+/// [`glyphs`](Self::glyphs) are drawn unchanged.
+///
+/// The effects are applied when the glyphs are extracted for rendering, so they can be
+/// changed every frame without laying the text out again, and the glyphs are still batched
+/// together. This works for both `Text2dBundle` and UI text entities.
+///
+/// To compute the effects from a component every frame, implement
+/// [`TextEffect`](crate::TextEffect) for it instead.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::Vec2;
+/// # use bevy_text::{TextGlyphEffects, TextLayoutInfo};
+/// #
+/// // Make the glyphs of every text with effects bob up and down.
+/// fn wavy_text(mut texts: Query<(&TextLayoutInfo, &mut TextGlyphEffects)>) {
+///     for (layout, mut effects) in &mut texts {
+///         for index in 0..layout.glyphs.len() {
+///             effects.glyph_mut(index).offset = Vec2::new(0.0, (index as f32 * 0.5).sin() * 4.0);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct TextGlyphEffects {
+    pub glyphs: Vec<GlyphEffect>,
+}
+
+impl TextGlyphEffects {
+    /// Returns the effect of the glyph at `index`.
+    pub fn get(&self, index: usize) -> GlyphEffect {
+        self.glyphs.get(index).copied().unwrap_or_default()
+    }
+
+    /// Returns a mutable reference to the effect of the glyph at `index`, adding default effects
+    /// as needed.
+    pub fn glyph_mut(&mut self, index: usize) -> &mut GlyphEffect {
+        if index >= self.glyphs.len() {
+            self.glyphs.resize(index + 1, GlyphEffect::default());
+        }
+        &mut self.glyphs[index]
+    }
+}
+
+/// How a single glyph of a [`TextGlyphEffects`] is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Default)]
+pub struct GlyphEffect {
+    /// Moves the glyph from its laid out position, in the same space as
+    /// [`PositionedGlyph::position`](crate::PositionedGlyph::position): physical pixels, with `y`
+    /// pointing up for `Text2dBundle` entities and down for UI text.
+    pub offset: Vec2,
+    /// Multiplied with the color of the glyph. Set the alpha to zero to hide the glyph, for
+    /// example to reveal the text one glyph at a time.
+    pub tint: LinearRgba,
+}
+
+impl GlyphEffect {
+    /// Returns `color` tinted by this effect.
+    pub fn tint_color(&self, color: LinearRgba) -> LinearRgba {
+        LinearRgba::from_vec4(color.to_vec4() * self.tint.to_vec4())
+    }
+}
+
+impl Default for GlyphEffect {
+    fn default() -> Self {
+        Self {
+            offset: Vec2::ZERO,
+            tint: LinearRgba::WHITE,
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone, Reflect)]
@@ -180,6 +283,82 @@ impl From<JustifyText> for glyph_brush_layout::HorizontalAlign {
     }
 }
 
+/// How the glyphs of a [`Text`] are rendered.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+#[reflect(Serialize, Deserialize)]
+pub enum TextRendering {
+    /// Glyphs are rasterized at the size they are drawn at, into font atlases for each font size.
+    /// They are the crispest at that size, but blur when the text is scaled, for example by its
+    /// transform or the camera projection.
+    #[default]
+    Raster,
+    /// Glyphs are drawn from multi-channel signed distance fields, generated once per glyph at
+    /// [`DISTANCE_FIELD_FONT_SIZE`](crate::DISTANCE_FIELD_FONT_SIZE) and shared by all font
+    /// sizes. They stay sharp at any scale and rotation, which suits labels in world space and
+    /// zoomable maps, but small text is slightly softer than rasterized text.
+    ///
+    /// Color glyphs, such as emoji, are still rasterized.
+    DistanceField,
+}
+
+/// The positions that tab characters (`\t`) in a [`Text`] move the following glyphs to, in
+/// logical pixels from the start of the line.
+///
+/// Tabs are laid out as spaces, so lines can be wrapped after them, and are expanded to the next
+/// tab stop once the text is wrapped, so lines with tabs can end up wider than the bounds of
+/// the text.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+#[reflect(Default, PartialEq)]
+pub struct TabStops {
+    /// The positions of the first tab stops, in increasing order.
+    pub positions: Vec<f32>,
+    /// The distance between the tab stops after the last of [`positions`](Self::positions), or
+    /// from the start of the line if there are none.
+    pub interval: f32,
+}
+
+impl TabStops {
+    /// Tab stops every `interval` logical pixels.
+    pub const fn every(interval: f32) -> Self {
+        Self {
+            positions: Vec::new(),
+            interval,
+        }
+    }
+
+    /// Returns these tab stops with more stops at `positions`, before the regular ones.
+    pub fn with_positions(mut self, positions: impl IntoIterator<Item = f32>) -> Self {
+        self.positions.extend(positions);
+        self
+    }
+
+    /// Returns the position of the first tab stop after `x`.
+    pub fn next(&self, x: f32) -> f32 {
+        if let Some(&position) = self.positions.iter().find(|&&position| position > x) {
+            return position;
+        }
+        if self.interval <= 0. {
+            return x;
+        }
+        let start = self.positions.last().copied().unwrap_or(0.);
+        start + ((x - start) / self.interval).floor() * self.interval + self.interval
+    }
+
+    /// Returns these tab stops with their positions and interval multiplied by `factor`.
+    pub(crate) fn scaled(&self, factor: f32) -> Self {
+        Self {
+            positions: self.positions.iter().map(|x| x * factor).collect(),
+            interval: self.interval * factor,
+        }
+    }
+}
+
+impl Default for TabStops {
+    fn default() -> Self {
+        Self::every(48.)
+    }
+}
+
 #[derive(Clone, Debug, Reflect)]
 pub struct TextStyle {
     /// If this is not specified, then
@@ -196,6 +375,26 @@ pub struct TextStyle {
     /// which can have a strong performance impact.
     pub font_size: f32,
     pub color: Color,
+    /// Fonts used, in order, for characters that are missing from `font`.
+    ///
+    /// These are tried before the global [`TextSettings::fallback_fonts`](crate::TextSettings::fallback_fonts).
+    /// Fonts installed on the system can be used here, see [`SystemFonts`](crate::SystemFonts).
+    pub fallback_fonts: Vec<Handle<Font>>,
+    /// The color of the highlight drawn behind the glyphs of the section, on each line they span.
+    pub background_color: Color,
+    /// Draws a line under the glyphs of the section, in the section's `color`.
+    pub underline: bool,
+    /// Draws a line through the glyphs of the section, in the section's `color`.
+    pub strikethrough: bool,
+    /// Makes the section a link. UI text sends events when the glyphs of its links are hovered
+    /// and clicked.
+    pub link: Option<TextLink>,
+    /// Extra space added after every glyph of the section, in logical pixels, also known as
+    /// tracking. Negative values bring the glyphs closer together.
+    pub letter_spacing: f32,
+    /// Extra space added after every space of the section, in logical pixels, on top of the
+    /// `letter_spacing`.
+    pub word_spacing: f32,
 }
 
 impl Default for TextStyle {
@@ -204,10 +403,38 @@ impl Default for TextStyle {
             font: Default::default(),
             font_size: 24.0,
             color: Color::WHITE,
+            fallback_fonts: Vec::new(),
+            background_color: Color::NONE,
+            underline: false,
+            strikethrough: false,
+            link: None,
+            letter_spacing: 0.,
+            word_spacing: 0.,
         }
     }
 }
 
+impl TextStyle {
+    /// Returns this [`TextStyle`] with an additional fallback font, see [`TextStyle::fallback_fonts`].
+    pub fn with_fallback_font(mut self, font: Handle<Font>) -> Self {
+        self.fallback_fonts.push(font);
+        self
+    }
+
+    /// Returns this [`TextStyle`] with a [`TextLink`] to `target`, see [`TextStyle::link`].
+    pub fn with_link(mut self, target: impl Into<String>) -> Self {
+        self.link = Some(TextLink(target.into()));
+        self
+    }
+}
+
+/// The target of a link in a [`Text`], set on the [`TextStyle`] of its sections.
+///
+/// The target is only used to tell the links apart, it can be a URL or any identifier.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Default, PartialEq, Hash)]
+pub struct TextLink(pub String);
+
 /// Determines how lines will be broken when preventing text from running out of bounds.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Reflect, Serialize, Deserialize)]
 #[reflect(Serialize, Deserialize)]
@@ -215,6 +442,10 @@ pub enum BreakLineOn {
     /// Uses the [Unicode Line Breaking Algorithm](https://www.unicode.org/reports/tr14/).
     /// Lines will be broken up at the nearest suitable word boundary, usually a space.
     /// This behavior suits most cases, as it keeps words intact across linebreaks.
+    ///
+    /// Words are also broken after soft hyphens (`\u{AD}`), which are drawn as a hyphen at the
+    /// end of a line and hidden elsewhere. Text can be hyphenated by inserting soft hyphens, for
+    /// example with a hyphenation dictionary.
     #[default]
     WordBoundary,
     /// Lines will be broken without discrimination on any character that would leave bounds.
@@ -1,9 +1,9 @@
 use crate::{
-    BreakLineOn, Font, FontAtlasSets, PositionedGlyph, Text, TextError, TextLayoutInfo,
-    TextPipeline, TextSettings, YAxisOrientation,
+    BreakLineOn, Font, FontAtlasSets, PositionedGlyph, Text, TextError, TextGlyphEffects,
+    TextLayoutInfo, TextPipeline, TextSettings, YAxisOrientation,
 };
-use bevy_asset::Assets;
-use bevy_color::LinearRgba;
+use bevy_asset::{AssetId, Assets};
+use bevy_color::{Alpha, Color, LinearRgba};
 use bevy_ecs::{
     bundle::Bundle,
     change_detection::{DetectChanges, Ref},
@@ -15,7 +15,7 @@ use bevy_ecs::{
     reflect::ReflectComponent,
     system::{Commands, Local, Query, Res, ResMut},
 };
-use bevy_math::Vec2;
+use bevy_math::{Rect, Vec2};
 use bevy_reflect::Reflect;
 use bevy_render::{
     primitives::Aabb,
@@ -23,7 +23,10 @@ use bevy_render::{
     view::{InheritedVisibility, NoFrustumCulling, ViewVisibility, Visibility},
     Extract,
 };
-use bevy_sprite::{Anchor, ExtractedSprite, ExtractedSprites, SpriteSource, TextureAtlasLayout};
+use bevy_sprite::{
+    Anchor, ExtractedSprite, ExtractedSprites, OrderInLayer, Sort2dKey, SortingLayer,
+    SortingLayers, SpriteSource, TextureAtlasLayout,
+};
 use bevy_transform::prelude::{GlobalTransform, Transform};
 use bevy_utils::HashSet;
 use bevy_window::{PrimaryWindow, Window, WindowScaleFactorChanged};
@@ -106,8 +109,12 @@ pub fn extract_text2d_sprite(
             &TextLayoutInfo,
             &Anchor,
             &GlobalTransform,
+            Option<&TextGlyphEffects>,
+            Option<&SortingLayer>,
+            Option<&OrderInLayer>,
         )>,
     >,
+    sorting_layers: Extract<Res<SortingLayers>>,
 ) {
     // TODO: Support window-independent scaling: https://github.com/bevyengine/bevy/issues/5621
     let scale_factor = windows
@@ -116,52 +123,148 @@ pub fn extract_text2d_sprite(
         .unwrap_or(1.0);
     let scaling = GlobalTransform::from_scale(Vec2::splat(scale_factor.recip()).extend(1.));
 
-    for (original_entity, view_visibility, text, text_layout_info, anchor, global_transform) in
-        text2d_query.iter()
+    for (
+        original_entity,
+        view_visibility,
+        text,
+        text_layout_info,
+        anchor,
+        global_transform,
+        glyph_effects,
+        layer,
+        order,
+    ) in text2d_query.iter()
     {
         if !view_visibility.get() {
             continue;
         }
+        let sort_key = sorting_layers.sort_key(layer, order, global_transform);
 
         let text_anchor = -(anchor.as_vec() + 0.5);
         let alignment_translation = text_layout_info.logical_size * text_anchor;
         let transform = *global_transform
             * GlobalTransform::from_translation(alignment_translation.extend(0.))
             * scaling;
+        // Highlights are drawn behind the glyphs, and lines over them.
+        for span in &text_layout_info.spans {
+            let style = &text.sections[span.section_index].style;
+            if !style.background_color.is_fully_transparent() {
+                extract_text_rect(
+                    &mut commands,
+                    &mut extracted_sprites,
+                    original_entity,
+                    transform,
+                    sort_key,
+                    span.rect,
+                    style.background_color,
+                );
+            }
+        }
+
         let mut color = LinearRgba::WHITE;
         let mut current_section = usize::MAX;
-        for PositionedGlyph {
-            position,
-            atlas_info,
-            section_index,
-            ..
-        } in &text_layout_info.glyphs
+        for (
+            glyph_index,
+            PositionedGlyph {
+                position,
+                atlas_info,
+                size,
+                section_index,
+                is_color,
+                distance_field,
+                ..
+            },
+        ) in text_layout_info.glyphs.iter().enumerate()
         {
             if *section_index != current_section {
                 color = LinearRgba::from(text.sections[*section_index].style.color);
                 current_section = *section_index;
             }
+            // Color glyphs such as emoji only take the alpha of the text color.
+            let glyph_color = if *is_color {
+                LinearRgba::WHITE.with_alpha(color.alpha)
+            } else {
+                color
+            };
+            let glyph_effect = glyph_effects
+                .map(|glyph_effects| glyph_effects.get(glyph_index))
+                .unwrap_or_default();
             let atlas = texture_atlases.get(&atlas_info.texture_atlas).unwrap();
 
             let entity = commands.spawn_empty().id();
             extracted_sprites.sprites.insert(
                 entity,
                 ExtractedSprite {
-                    transform: transform * GlobalTransform::from_translation(position.extend(0.)),
-                    color,
+                    transform: transform
+                        * GlobalTransform::from_translation(
+                            (*position + glyph_effect.offset).extend(0.),
+                        ),
+                    sort_key,
+                    color: glyph_effect.tint_color(glyph_color),
                     rect: Some(atlas.textures[atlas_info.glyph_index].as_rect()),
-                    custom_size: None,
+                    custom_size: Some(*size),
                     image_handle_id: atlas_info.texture.id(),
+                    normal_map_id: None,
                     flip_x: false,
                     flip_y: false,
                     anchor: Anchor::Center.as_vec(),
                     original_entity: Some(original_entity),
+                    distance_field: *distance_field,
                 },
             );
         }
+
+        for span in &text_layout_info.spans {
+            let style = &text.sections[span.section_index].style;
+            for (draw, rect) in [
+                (style.underline, span.underline),
+                (style.strikethrough, span.strikethrough),
+            ] {
+                if draw {
+                    extract_text_rect(
+                        &mut commands,
+                        &mut extracted_sprites,
+                        original_entity,
+                        transform,
+                        sort_key,
+                        rect,
+                        style.color,
+                    );
+                }
+            }
+        }
     }
 }
 
+/// Extracts a rectangle of a single `color` drawn with a text, such as a highlight or underline.
+fn extract_text_rect(
+    commands: &mut Commands,
+    extracted_sprites: &mut ExtractedSprites,
+    original_entity: Entity,
+    transform: GlobalTransform,
+    sort_key: Sort2dKey,
+    rect: Rect,
+    color: Color,
+) {
+    extracted_sprites.sprites.insert(
+        commands.spawn_empty().id(),
+        ExtractedSprite {
+            transform: transform * GlobalTransform::from_translation(rect.center().extend(0.)),
+            sort_key,
+            color: color.into(),
+            rect: None,
+            custom_size: Some(rect.size()),
+            image_handle_id: AssetId::default(),
+            normal_map_id: None,
+            flip_x: false,
+            flip_y: false,
+            anchor: Anchor::Center.as_vec(),
+            original_entity: Some(original_entity),
+            distance_field: false,
+        },
+    );
+}
+
 /// Updates the layout and size information whenever the text or style is changed.
 /// This information is computed by the [`TextPipeline`] on insertion, then stored.
 ///
@@ -185,6 +288,8 @@ pub fn update_text2d_layout(
 ) {
     // We need to consume the entire iterator, hence `last`
     let factor_changed = scale_factor_changed.read().last().is_some();
+    // Fallback fonts may change which glyphs are used
+    let settings_changed = text_settings.is_changed();
 
     // TODO: Support window-independent scaling: https://github.com/bevyengine/bevy/issues/5621
     let scale_factor = windows
@@ -195,7 +300,12 @@ pub fn update_text2d_layout(
     let inverse_scale_factor = scale_factor.recip();
 
     for (entity, text, bounds, mut text_layout_info) in &mut text_query {
-        if factor_changed || text.is_changed() || bounds.is_changed() || queue.remove(&entity) {
+        if factor_changed
+            || settings_changed
+            || text.is_changed()
+            || bounds.is_changed()
+            || queue.remove(&entity)
+        {
             let text_bounds = Vec2::new(
                 if text.linebreak_behavior == BreakLineOn::NoWrap {
                     f32::INFINITY
@@ -210,6 +320,8 @@ pub fn update_text2d_layout(
                 scale_factor,
                 text.justify,
                 text.linebreak_behavior,
+                text.rendering,
+                &text.tab_stops,
                 text_bounds,
                 &mut font_atlas_sets,
                 &mut texture_atlases,
@@ -0,0 +1,222 @@
+use std::marker::PhantomData;
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::{With, Without},
+    schedule::{IntoSystemConfigs, SystemSet},
+    system::{Commands, Query, Res},
+};
+use bevy_time::Time;
+
+use crate::{GlyphEffect, PositionedGlyph, Text, TextGlyphEffects, TextLayoutInfo, TextSection};
+
+/// A component that animates the glyphs of the [`Text`] on its entity, such as a wave, a
+/// typewriter reveal or a shake.
+///
+/// Every frame, after the text is laid out, [`apply`](Self::apply) is called for each glyph of
+/// the entity's [`TextLayoutInfo`] to update the glyph's [`GlyphEffect`] in the entity's
+/// [`TextGlyphEffects`], which starts the frame at its default. An entity can have several
+/// effects, which are applied one after the other.
+///
+/// Add a [`TextEffectPlugin`] for each type of effect to run it.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::Vec2;
+/// # use bevy_text::{GlyphEffect, TextEffect, TextEffectGlyph};
+/// #
+/// /// Makes the glyphs bob up and down.
+/// #[derive(Component)]
+/// struct Wave {
+///     height: f32,
+/// }
+///
+/// impl TextEffect for Wave {
+///     fn apply(&self, glyph: &TextEffectGlyph, effect: &mut GlyphEffect) {
+///         let phase = glyph.index as f32 * 0.5 + glyph.elapsed_seconds * 4.0;
+///         effect.offset.y += phase.sin() * self.height;
+///     }
+/// }
+/// ```
+pub trait TextEffect: Component {
+    /// Updates the `effect` of a single glyph.
+    fn apply(&self, glyph: &TextEffectGlyph, effect: &mut GlyphEffect);
+}
+
+/// A glyph passed to [`TextEffect::apply`].
+pub struct TextEffectGlyph<'a> {
+    /// The index of the glyph in [`TextLayoutInfo::glyphs`].
+    pub index: usize,
+    /// The number of glyphs in the text.
+    pub count: usize,
+    /// The laid out glyph.
+    pub glyph: &'a PositionedGlyph,
+    /// The section the glyph was laid out from.
+    pub section: &'a TextSection,
+    /// The time elapsed since the app started, in seconds, from [`Time`].
+    pub elapsed_seconds: f32,
+}
+
+/// Adds the systems that apply the text effect `E` to the glyphs of entities with an `E` component.
+pub struct TextEffectPlugin<E: TextEffect>(PhantomData<E>);
+
+impl<E: TextEffect> Default for TextEffectPlugin<E> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<E: TextEffect> Plugin for TextEffectPlugin<E> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            (
+                insert_text_glyph_effects::<E>.before(TextEffectSystem),
+                apply_text_effect::<E>.in_set(TextEffectSystem),
+            ),
+        );
+    }
+}
+
+/// The [`SystemSet`] of the systems added by [`TextEffectPlugin`]s, which run in [`PostUpdate`]
+/// after the text is laid out.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
+pub struct TextEffectSystem;
+
+/// Marks the entities whose [`TextGlyphEffects`] are computed by [`TextEffect`]s.
+#[derive(Component)]
+pub(crate) struct TextEffectTarget;
+
+fn insert_text_glyph_effects<E: TextEffect>(
+    mut commands: Commands,
+    query: Query<(Entity, Option<&TextGlyphEffects>), (With<E>, Without<TextEffectTarget>)>,
+) {
+    for (entity, glyph_effects) in &query {
+        let mut entity = commands.entity(entity);
+        entity.insert(TextEffectTarget);
+        if glyph_effects.is_none() {
+            entity.insert(TextGlyphEffects::default());
+        }
+    }
+}
+
+/// Resets the [`TextGlyphEffects`] of the entities with [`TextEffect`]s before the effects are
+/// applied again.
+pub(crate) fn reset_text_glyph_effects(
+    mut query: Query<&mut TextGlyphEffects, With<TextEffectTarget>>,
+) {
+    for mut glyph_effects in &mut query {
+        glyph_effects.glyphs.clear();
+    }
+}
+
+fn apply_text_effect<E: TextEffect>(
+    time: Res<Time>,
+    mut query: Query<(&E, &Text, &TextLayoutInfo, &mut TextGlyphEffects), With<TextEffectTarget>>,
+) {
+    let elapsed_seconds = time.elapsed_seconds();
+    for (text_effect, text, text_layout_info, mut glyph_effects) in &mut query {
+        let count = text_layout_info.glyphs.len();
+        for (index, glyph) in text_layout_info.glyphs.iter().enumerate() {
+            let Some(section) = text.sections.get(glyph.section_index) else {
+                continue;
+            };
+            let glyph = TextEffectGlyph {
+                index,
+                count,
+                glyph,
+                section,
+                elapsed_seconds,
+            };
+            text_effect.apply(&glyph, glyph_effects.glyph_mut(index));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::{App, PostUpdate};
+    use bevy_color::LinearRgba;
+    use bevy_ecs::prelude::*;
+    use bevy_math::Vec2;
+    use bevy_time::Time;
+
+    use super::*;
+    use crate::{GlyphAtlasInfo, PositionedGlyph};
+
+    #[derive(Component)]
+    struct Raise(f32);
+
+    impl TextEffect for Raise {
+        fn apply(&self, glyph: &TextEffectGlyph, effect: &mut GlyphEffect) {
+            effect.offset.y += self.0 * glyph.index as f32;
+        }
+    }
+
+    #[derive(Component)]
+    struct HideLast;
+
+    impl TextEffect for HideLast {
+        fn apply(&self, glyph: &TextEffectGlyph, effect: &mut GlyphEffect) {
+            if glyph.index + 1 == glyph.count {
+                effect.tint.alpha = 0.0;
+            }
+        }
+    }
+
+    fn glyph(byte_index: usize) -> PositionedGlyph {
+        PositionedGlyph {
+            position: Vec2::new(byte_index as f32 * 10.0, 0.0),
+            size: Vec2::splat(10.0),
+            atlas_info: GlyphAtlasInfo {
+                texture_atlas: Default::default(),
+                texture: Default::default(),
+                glyph_index: 0,
+            },
+            section_index: 0,
+            byte_index,
+            is_color: false,
+            distance_field: false,
+        }
+    }
+
+    #[test]
+    fn effects_are_combined_and_reset_every_frame() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .add_plugins((
+                TextEffectPlugin::<Raise>::default(),
+                TextEffectPlugin::<HideLast>::default(),
+            ))
+            .add_systems(
+                PostUpdate,
+                reset_text_glyph_effects.before(TextEffectSystem),
+            );
+
+        let entity = app
+            .world_mut()
+            .spawn((
+                Text::from_section("abc", Default::default()),
+                TextLayoutInfo {
+                    glyphs: (0..3).map(glyph).collect(),
+                    ..Default::default()
+                },
+                Raise(2.0),
+                HideLast,
+            ))
+            .id();
+
+        // The effects of the previous frame are cleared rather than accumulated.
+        app.update();
+        app.update();
+
+        let glyph_effects = app.world().get::<TextGlyphEffects>(entity).unwrap();
+        assert_eq!(glyph_effects.glyphs.len(), 3);
+        assert_eq!(glyph_effects.get(1).offset, Vec2::new(0.0, 2.0));
+        assert_eq!(glyph_effects.get(2).offset, Vec2::new(0.0, 4.0));
+        assert_eq!(glyph_effects.get(0).tint, LinearRgba::WHITE);
+        assert_eq!(glyph_effects.get(2).tint.alpha, 0.0);
+    }
+}
@@ -0,0 +1,333 @@
+use std::borrow::Cow;
+
+use ab_glyph::{
+    v2::GlyphImage, CodepointIdIter, Font, FontArc, GlyphId, GlyphSvg, Outline, ScaleFont as _,
+};
+use glyph_brush_layout::{
+    BuiltInLineBreaker, FontId, GlyphPositioner, Layout, SectionGeometry, SectionGlyph, SectionText,
+};
+
+use crate::{scale_value, JustifyText, TabStops, TextStyle};
+
+/// The soft hyphen, which marks where a word can be hyphenated if it doesn't fit on a line.
+const SOFT_HYPHEN: char = '\u{AD}';
+
+/// The glyph laid out for soft hyphens. It isn't drawn, it is replaced by the font's hyphen at
+/// the end of a line and removed elsewhere.
+const SOFT_HYPHEN_ID: GlyphId = GlyphId(u16::MAX);
+
+/// The extra space added between the glyphs of a section, in physical pixels.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct TextSpacing {
+    /// Added after every glyph.
+    pub letter: f32,
+    /// Added after every space, on top of `letter`.
+    pub word: f32,
+}
+
+impl TextSpacing {
+    /// Returns the spacing of a section with the given `style`, scaled by `scale_factor`.
+    pub fn of(style: &TextStyle, scale_factor: f32) -> Self {
+        Self {
+            letter: scale_value(style.letter_spacing, scale_factor),
+            word: scale_value(style.word_spacing, scale_factor),
+        }
+    }
+}
+
+/// A font with the [`TextSpacing`] of a section added to the advance of its glyphs, and zero
+/// width soft hyphens.
+///
+/// The spacing has to be applied while the text is laid out rather than afterwards, so it is
+/// accounted for when lines are wrapped.
+struct SpacedFont<'a> {
+    font: &'a FontArc,
+    /// The [`TextSpacing::letter`] of the section, in font units.
+    letter_spacing: f32,
+    /// The [`TextSpacing::word`] of the section, in font units.
+    word_spacing: f32,
+    space_id: GlyphId,
+}
+
+impl<'a> SpacedFont<'a> {
+    fn new(font: &'a FontArc, scale: f32, spacing: TextSpacing) -> Self {
+        // Scaled fonts multiply unscaled advances by `scale / height_unscaled`.
+        let to_unscaled = if scale > 0. {
+            font.height_unscaled() / scale
+        } else {
+            0.
+        };
+        Self {
+            font,
+            letter_spacing: spacing.letter * to_unscaled,
+            word_spacing: spacing.word * to_unscaled,
+            space_id: font.glyph_id(' '),
+        }
+    }
+}
+
+impl Font for SpacedFont<'_> {
+    fn units_per_em(&self) -> Option<f32> {
+        self.font.units_per_em()
+    }
+
+    fn ascent_unscaled(&self) -> f32 {
+        self.font.ascent_unscaled()
+    }
+
+    fn descent_unscaled(&self) -> f32 {
+        self.font.descent_unscaled()
+    }
+
+    fn line_gap_unscaled(&self) -> f32 {
+        self.font.line_gap_unscaled()
+    }
+
+    fn italic_angle(&self) -> f32 {
+        self.font.italic_angle()
+    }
+
+    fn glyph_id(&self, c: char) -> GlyphId {
+        if c == SOFT_HYPHEN {
+            SOFT_HYPHEN_ID
+        } else {
+            self.font.glyph_id(c)
+        }
+    }
+
+    fn h_advance_unscaled(&self, id: GlyphId) -> f32 {
+        if id == SOFT_HYPHEN_ID {
+            return 0.;
+        }
+        let mut advance = self.font.h_advance_unscaled(id) + self.letter_spacing;
+        if id == self.space_id {
+            advance += self.word_spacing;
+        }
+        advance
+    }
+
+    fn h_side_bearing_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.h_side_bearing_unscaled(id)
+    }
+
+    fn v_advance_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.v_advance_unscaled(id)
+    }
+
+    fn v_side_bearing_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.v_side_bearing_unscaled(id)
+    }
+
+    fn kern_unscaled(&self, first: GlyphId, second: GlyphId) -> f32 {
+        self.font.kern_unscaled(first, second)
+    }
+
+    fn outline(&self, id: GlyphId) -> Option<Outline> {
+        self.font.outline(id)
+    }
+
+    fn glyph_count(&self) -> usize {
+        self.font.glyph_count()
+    }
+
+    fn codepoint_ids(&self) -> CodepointIdIter<'_> {
+        self.font.codepoint_ids()
+    }
+
+    fn glyph_raster_image2(&self, id: GlyphId, pixel_size: u16) -> Option<GlyphImage<'_>> {
+        self.font.glyph_raster_image2(id, pixel_size)
+    }
+
+    fn glyph_svg_image(&self, id: GlyphId) -> Option<GlyphSvg<'_>> {
+        self.font.glyph_svg_image(id)
+    }
+
+    fn font_data(&self) -> &[u8] {
+        self.font.font_data()
+    }
+}
+
+/// Lays out `sections`, where the font of each section is `fonts[section.font_id]` and its
+/// spacing is `spacings[section_index]`.
+///
+/// Tabs are laid out as spaces, so lines can be wrapped after them, then the glyphs after them
+/// are moved to the next of the `tab_stops`. Soft hyphens are drawn as hyphens at the end of a
+/// line and hidden elsewhere.
+///
+/// The font ids of the returned glyphs are the indices of their sections.
+pub(crate) fn layout_glyphs(
+    fonts: &[FontArc],
+    sections: &[SectionText],
+    spacings: &[TextSpacing],
+    tab_stops: &TabStops,
+    geometry: &SectionGeometry,
+    text_alignment: JustifyText,
+    line_breaker: BuiltInLineBreaker,
+) -> Vec<SectionGlyph> {
+    let spaced_fonts: Vec<_> = sections
+        .iter()
+        .zip(spacings)
+        .map(|(section, spacing)| {
+            SpacedFont::new(&fonts[section.font_id.0], section.scale.x, *spacing)
+        })
+        .collect();
+    let texts: Vec<_> = sections
+        .iter()
+        .map(|section| match section.text.contains('\t') {
+            // Both are a single byte, so the byte indices of the glyphs are kept.
+            true => Cow::Owned(section.text.replace('\t', " ")),
+            false => Cow::Borrowed(section.text),
+        })
+        .collect();
+    let spaced_sections: Vec<_> = sections
+        .iter()
+        .zip(&texts)
+        .enumerate()
+        .map(|(index, (section, text))| SectionText {
+            text,
+            scale: section.scale,
+            font_id: FontId(index),
+        })
+        .collect();
+
+    let mut glyphs = Layout::default()
+        .h_align(text_alignment.into())
+        .line_breaker(line_breaker)
+        .calculate_glyphs(&spaced_fonts, geometry, &spaced_sections);
+
+    expand_tabs(
+        &mut glyphs,
+        |sg| sections[sg.section_index].text.as_bytes()[sg.byte_index] == b'\t',
+        |sg| {
+            let scaled_font = spaced_fonts[sg.section_index].as_scaled(sg.glyph.scale);
+            scaled_font.h_advance(sg.glyph.id)
+        },
+        tab_stops,
+        text_alignment,
+    );
+    finish_soft_hyphens(&mut glyphs, |sg| {
+        fonts[sections[sg.section_index].font_id.0].glyph_id('-')
+    });
+    glyphs
+}
+
+/// Moves the glyphs following each tab to the next tab stop, and realigns the lines that got
+/// longer.
+fn expand_tabs(
+    glyphs: &mut [SectionGlyph],
+    is_tab: impl Fn(&SectionGlyph) -> bool,
+    advance: impl Fn(&SectionGlyph) -> f32,
+    tab_stops: &TabStops,
+    text_alignment: JustifyText,
+) {
+    for line in glyphs.chunk_by_mut(|a, b| a.glyph.position.y == b.glyph.position.y) {
+        let line_start = line[0].glyph.position.x;
+        let mut shift = 0.;
+        for sg in line.iter_mut() {
+            sg.glyph.position.x += shift;
+            if is_tab(sg) {
+                let x = sg.glyph.position.x - line_start;
+                shift += tab_stops.next(x) - (x + advance(sg));
+            }
+        }
+        let realign = match text_alignment {
+            JustifyText::Left => 0.,
+            JustifyText::Center => shift / 2.,
+            JustifyText::Right => shift,
+        };
+        for sg in line.iter_mut() {
+            sg.glyph.position.x -= realign;
+        }
+    }
+}
+
+/// Replaces the soft hyphens that end a line with the glyph returned by `hyphen`, and removes
+/// the others.
+fn finish_soft_hyphens(glyphs: &mut Vec<SectionGlyph>, hyphen: impl Fn(&SectionGlyph) -> GlyphId) {
+    if !glyphs.iter().any(|sg| sg.glyph.id == SOFT_HYPHEN_ID) {
+        return;
+    }
+    let line_ends: Vec<bool> = glyphs
+        .iter()
+        .zip(glyphs.iter().skip(1).map(Some).chain([None]))
+        .map(|(sg, next)| next.map(|next| next.glyph.position.y) != Some(sg.glyph.position.y))
+        .collect();
+    let mut line_ends = line_ends.into_iter();
+    glyphs.retain_mut(|sg| {
+        let line_end = line_ends.next().unwrap_or(true);
+        if sg.glyph.id != SOFT_HYPHEN_ID {
+            return true;
+        }
+        sg.glyph.id = hyphen(sg);
+        line_end
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use ab_glyph::{point, Glyph, PxScale};
+
+    use super::*;
+
+    fn glyph(x: f32, y: f32, byte_index: usize) -> SectionGlyph {
+        SectionGlyph {
+            section_index: 0,
+            byte_index,
+            glyph: Glyph {
+                id: GlyphId(1),
+                scale: PxScale::from(10.),
+                position: point(x, y),
+            },
+            font_id: FontId(0),
+        }
+    }
+
+    #[test]
+    fn tab_stops() {
+        let tab_stops = TabStops {
+            positions: vec![30., 70.],
+            interval: 50.,
+        };
+        assert_eq!(tab_stops.next(0.), 30.);
+        assert_eq!(tab_stops.next(30.), 70.);
+        assert_eq!(tab_stops.next(70.), 120.);
+        assert_eq!(tab_stops.next(125.), 170.);
+        assert_eq!(TabStops::every(40.).next(10.), 40.);
+    }
+
+    #[test]
+    fn tabs_move_the_rest_of_their_line() {
+        // "a\tb" on the first line, "c" on the second, with glyphs 10 wide.
+        let mut glyphs = vec![
+            glyph(0., 10., 0),
+            glyph(10., 10., 1),
+            glyph(20., 10., 2),
+            glyph(0., 20., 3),
+        ];
+        expand_tabs(
+            &mut glyphs,
+            |sg| sg.byte_index == 1,
+            |_| 10.,
+            &TabStops::every(50.),
+            JustifyText::Left,
+        );
+        let x: Vec<_> = glyphs.iter().map(|sg| sg.glyph.position.x).collect();
+        assert_eq!(x, [0., 10., 50., 0.]);
+    }
+
+    #[test]
+    fn soft_hyphens_are_only_kept_at_line_ends() {
+        let mut glyphs = vec![
+            glyph(0., 10., 0),
+            glyph(10., 10., 1),
+            glyph(10., 10., 3),
+            glyph(20., 10., 4),
+            glyph(0., 20., 6),
+        ];
+        glyphs[1].glyph.id = SOFT_HYPHEN_ID;
+        glyphs[3].glyph.id = SOFT_HYPHEN_ID;
+        finish_soft_hyphens(&mut glyphs, |_| GlyphId(2));
+        let ids: Vec<_> = glyphs.iter().map(|sg| sg.glyph.id.0).collect();
+        assert_eq!(ids, [1, 1, 2, 1]);
+    }
+}
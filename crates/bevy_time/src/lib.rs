@@ -10,6 +10,8 @@
 pub mod common_conditions;
 mod fixed;
 mod real;
+#[cfg(feature = "bevy_reflect")]
+mod recorder;
 mod stopwatch;
 #[allow(clippy::module_inception)]
 mod time;
@@ -18,6 +20,8 @@ mod virt;
 
 pub use fixed::*;
 pub use real::*;
+#[cfg(feature = "bevy_reflect")]
+pub use recorder::*;
 pub use stopwatch::*;
 pub use time::*;
 pub use timer::*;
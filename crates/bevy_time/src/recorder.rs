@@ -0,0 +1,672 @@
+use bevy_app::{prelude::*, FixedFirst};
+use bevy_ecs::{
+    event::{EventUpdates, ManualEventReader},
+    prelude::*,
+};
+use bevy_reflect::{FromReflect, GetTypeRegistration, Reflect, TypePath};
+use bevy_utils::{tracing::warn, Duration};
+
+use crate::{Real, Time, TimeSystem, TimeUpdateStrategy};
+
+/// Adds the [`EventRecorder`], to record the events of the types registered with
+/// [`EventRecorderAppExt::record_event`] and replay them in a later run.
+pub struct EventRecorderPlugin;
+
+impl Plugin for EventRecorderPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EventRecorder>()
+            .configure_sets(First, EventRecorderSystems::Replay.after(EventUpdates))
+            .configure_sets(Last, EventRecorderSystems::Record)
+            .add_systems(
+                First,
+                begin_frame
+                    .before(TimeSystem)
+                    .before(EventRecorderSystems::Replay),
+            )
+            .add_systems(FixedFirst, count_fixed_step)
+            .add_systems(Last, end_frame.after(EventRecorderSystems::Record));
+    }
+}
+
+/// The systems recording and replaying the events.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventRecorderSystems {
+    /// Sends the replayed events, in [`First`].
+    Replay,
+    /// Records the events sent during the frame, in [`Last`].
+    Record,
+}
+
+/// Registers the events recorded by the [`EventRecorder`].
+pub trait EventRecorderAppExt {
+    /// Records the events of type `E`, and replays them.
+    ///
+    /// While replaying, the events of type `E` sent by the app itself, like the input events
+    /// received from the windowing backend, are cleared to only keep the replayed ones.
+    fn record_event<E: Event + FromReflect + TypePath + GetTypeRegistration>(
+        &mut self,
+    ) -> &mut Self;
+}
+
+impl EventRecorderAppExt for App {
+    fn record_event<E: Event + FromReflect + TypePath + GetTypeRegistration>(
+        &mut self,
+    ) -> &mut Self {
+        self.register_type::<E>()
+            .add_systems(
+                First,
+                replay_events::<E>.in_set(EventRecorderSystems::Replay),
+            )
+            .add_systems(
+                Last,
+                record_events::<E>.in_set(EventRecorderSystems::Record),
+            )
+    }
+}
+
+/// The events, durations and fixed timesteps of the frames of a run, recorded by the
+/// [`EventRecorder`].
+///
+/// The log is serialized with [`EventLog::serializer`] and deserialized with an
+/// [`EventLogDeserializer`], with the `serialize` feature, using the types registered in the
+/// `AppTypeRegistry`.
+#[derive(Debug, Default)]
+pub struct EventLog {
+    /// The seed of the random number generators of the run.
+    pub seed: u64,
+    /// The recorded frames.
+    pub frames: Vec<RecordedFrame>,
+}
+
+/// A frame of an [`EventLog`].
+#[derive(Debug, Default)]
+pub struct RecordedFrame {
+    /// The real time elapsed since the previous frame.
+    pub delta: Duration,
+    /// The number of times the `FixedMain` schedule ran in the frame.
+    pub fixed_steps: u32,
+    /// The events sent during the frame.
+    pub events: Vec<Box<dyn Reflect>>,
+}
+
+#[derive(Debug, Default)]
+enum RecorderState {
+    #[default]
+    Idle,
+    Recording,
+    Replaying {
+        frame: usize,
+    },
+}
+
+/// Records the events of the registered types in an [`EventLog`], to replay them in a later run
+/// and reproduce it deterministically.
+///
+/// The events are registered with [`EventRecorderAppExt::record_event`]. Replaying a log feeds back
+/// the events through [`Events`], frame by frame, and advances the time by the recorded durations
+/// so the fixed timesteps happen in the same frames. The app must seed its random number
+/// generators with [`EventRecorder::seed`] for the replay to be deterministic.
+///
+/// ```
+/// # use bevy_app::prelude::*;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_reflect::Reflect;
+/// # use bevy_time::{EventRecorder, EventRecorderAppExt, EventRecorderPlugin, TimePlugin};
+/// #[derive(Event, Reflect)]
+/// struct Jump;
+///
+/// let mut app = App::new();
+/// app.add_plugins((TimePlugin, EventRecorderPlugin))
+///     .add_event::<Jump>()
+///     .record_event::<Jump>();
+///
+/// app.world_mut().resource_mut::<EventRecorder>().start_recording(42);
+/// app.update();
+/// app.world_mut().send_event(Jump);
+/// app.update();
+/// let log = app.world_mut().resource_mut::<EventRecorder>().stop_recording();
+/// assert_eq!(log.frames.len(), 2);
+/// assert_eq!(log.frames[1].events.len(), 1);
+/// ```
+#[derive(Resource, Default)]
+pub struct EventRecorder {
+    state: RecorderState,
+    log: EventLog,
+    in_frame: bool,
+    fixed_steps: u32,
+    previous_strategy: Option<TimeUpdateStrategy>,
+}
+
+impl EventRecorder {
+    /// Starts recording a new log from the next frame, for a run whose random number generators
+    /// use the `seed`.
+    pub fn start_recording(&mut self, seed: u64) {
+        self.stop();
+        self.state = RecorderState::Recording;
+        self.log = EventLog {
+            seed,
+            frames: Vec::new(),
+        };
+    }
+
+    /// Stops recording, and returns the recorded log.
+    pub fn stop_recording(&mut self) -> EventLog {
+        self.stop();
+        std::mem::take(&mut self.log)
+    }
+
+    /// Replays the `log` from the next frame.
+    pub fn start_replay(&mut self, log: EventLog) {
+        self.stop();
+        self.state = RecorderState::Replaying { frame: 0 };
+        self.log = log;
+    }
+
+    /// Stops recording or replaying.
+    ///
+    /// The [`TimeUpdateStrategy`] overridden by a replay is restored in the next frame.
+    pub fn stop(&mut self) {
+        self.state = RecorderState::Idle;
+        self.in_frame = false;
+    }
+
+    /// Returns `true` if the events are being recorded.
+    pub fn is_recording(&self) -> bool {
+        matches!(self.state, RecorderState::Recording)
+    }
+
+    /// Returns `true` if a log is being replayed.
+    pub fn is_replaying(&self) -> bool {
+        matches!(self.state, RecorderState::Replaying { frame } if frame < self.log.frames.len())
+    }
+
+    /// The seed the random number generators should use, while recording or replaying.
+    pub fn seed(&self) -> Option<u64> {
+        (self.is_recording() || self.is_replaying()).then_some(self.log.seed)
+    }
+
+    /// The log being recorded or replayed.
+    pub fn log(&self) -> &EventLog {
+        &self.log
+    }
+
+    fn replayed_frame(&self) -> Option<&RecordedFrame> {
+        match self.state {
+            RecorderState::Replaying { frame } if self.in_frame => self.log.frames.get(frame),
+            _ => None,
+        }
+    }
+}
+
+fn begin_frame(mut recorder: ResMut<EventRecorder>, mut strategy: ResMut<TimeUpdateStrategy>) {
+    let recorder = &mut *recorder;
+    match recorder.state {
+        RecorderState::Recording => {
+            recorder.log.frames.push(RecordedFrame::default());
+            recorder.in_frame = true;
+        }
+        RecorderState::Replaying { frame } if frame < recorder.log.frames.len() => {
+            let replayed = TimeUpdateStrategy::ManualDuration(recorder.log.frames[frame].delta);
+            let previous = std::mem::replace(&mut *strategy, replayed);
+            recorder.previous_strategy.get_or_insert(previous);
+            recorder.fixed_steps = 0;
+            recorder.in_frame = true;
+            return;
+        }
+        RecorderState::Replaying { .. } => recorder.state = RecorderState::Idle,
+        RecorderState::Idle => {}
+    }
+    // Restores the strategy overridden by the replay once it's finished or stopped
+    if let Some(previous) = recorder.previous_strategy.take() {
+        *strategy = previous;
+    }
+}
+
+fn count_fixed_step(mut recorder: ResMut<EventRecorder>) {
+    let recorder = &mut *recorder;
+    if !recorder.in_frame {
+        return;
+    }
+    match &mut recorder.state {
+        RecorderState::Recording => {
+            if let Some(frame) = recorder.log.frames.last_mut() {
+                frame.fixed_steps += 1;
+            }
+        }
+        RecorderState::Replaying { .. } => recorder.fixed_steps += 1,
+        RecorderState::Idle => {}
+    }
+}
+
+fn end_frame(mut recorder: ResMut<EventRecorder>, real_time: Res<Time<Real>>) {
+    let recorder = &mut *recorder;
+    if !std::mem::take(&mut recorder.in_frame) {
+        return;
+    }
+    match &mut recorder.state {
+        RecorderState::Recording => {
+            if let Some(frame) = recorder.log.frames.last_mut() {
+                frame.delta = real_time.delta();
+            }
+        }
+        RecorderState::Replaying { frame } => {
+            if let Some(recorded) = recorder.log.frames.get(*frame) {
+                if recorded.fixed_steps != recorder.fixed_steps {
+                    warn!(
+                        "The replay diverged from the recording in frame {frame}: {} fixed timesteps ran instead of {}.",
+                        recorder.fixed_steps, recorded.fixed_steps
+                    );
+                }
+            }
+            *frame += 1;
+        }
+        RecorderState::Idle => {}
+    }
+}
+
+fn record_events<E: Event + FromReflect + TypePath>(
+    mut recorder: ResMut<EventRecorder>,
+    events: Res<Events<E>>,
+    mut reader: Local<ManualEventReader<E>>,
+) {
+    // The events are read even when not recording, to not record the events sent before it starts
+    let events = reader.read(&events);
+    if !recorder.is_recording() || !recorder.in_frame {
+        return;
+    }
+    if let Some(frame) = recorder.log.frames.last_mut() {
+        frame.events.extend(events.map(|event| {
+            Box::new(E::from_reflect(event).expect("FromReflect should accept its own type"))
+                as Box<dyn Reflect>
+        }));
+    }
+}
+
+fn replay_events<E: Event + FromReflect + TypePath>(
+    recorder: Res<EventRecorder>,
+    mut events: ResMut<Events<E>>,
+) {
+    let Some(frame) = recorder.replayed_frame() else {
+        return;
+    };
+    events.clear();
+    for event in &frame.events {
+        let is_event = event
+            .get_represented_type_info()
+            .is_some_and(|info| info.type_path() == E::type_path());
+        if !is_event {
+            continue;
+        }
+        match E::from_reflect(event.as_ref()) {
+            Some(event) => {
+                events.send(event);
+            }
+            None => warn!(
+                "Failed to replay an event of type `{}`, its value was {event:?}.",
+                E::type_path()
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "serialize")]
+mod serde {
+    use std::fmt;
+
+    use bevy_reflect::{
+        serde::{ReflectDeserializer, ReflectSerializer},
+        Reflect, TypeRegistry,
+    };
+    use bevy_utils::Duration;
+    use serde::{
+        de::{DeserializeSeed, Error, MapAccess, SeqAccess, Visitor},
+        ser::{SerializeSeq, SerializeStruct},
+        Deserializer, Serialize, Serializer,
+    };
+
+    use super::{EventLog, RecordedFrame};
+
+    impl EventLog {
+        /// Returns a [`Serialize`] implementation serializing the log, whose events are
+        /// registered in the `registry`.
+        pub fn serializer<'a>(&'a self, registry: &'a TypeRegistry) -> EventLogSerializer<'a> {
+            EventLogSerializer {
+                log: self,
+                registry,
+            }
+        }
+    }
+
+    /// Serializes an [`EventLog`], created with [`EventLog::serializer`].
+    pub struct EventLogSerializer<'a> {
+        log: &'a EventLog,
+        registry: &'a TypeRegistry,
+    }
+
+    impl Serialize for EventLogSerializer<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut state = serializer.serialize_struct("EventLog", 2)?;
+            state.serialize_field("seed", &self.log.seed)?;
+            state.serialize_field(
+                "frames",
+                &FramesSerializer {
+                    frames: &self.log.frames,
+                    registry: self.registry,
+                },
+            )?;
+            state.end()
+        }
+    }
+
+    struct FramesSerializer<'a> {
+        frames: &'a [RecordedFrame],
+        registry: &'a TypeRegistry,
+    }
+
+    impl Serialize for FramesSerializer<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut seq = serializer.serialize_seq(Some(self.frames.len()))?;
+            for frame in self.frames {
+                seq.serialize_element(&FrameSerializer {
+                    frame,
+                    registry: self.registry,
+                })?;
+            }
+            seq.end()
+        }
+    }
+
+    struct FrameSerializer<'a> {
+        frame: &'a RecordedFrame,
+        registry: &'a TypeRegistry,
+    }
+
+    impl Serialize for FrameSerializer<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut state = serializer.serialize_struct("RecordedFrame", 3)?;
+            state.serialize_field("delta", &self.frame.delta)?;
+            state.serialize_field("fixed_steps", &self.frame.fixed_steps)?;
+            state.serialize_field(
+                "events",
+                &EventsSerializer {
+                    events: &self.frame.events,
+                    registry: self.registry,
+                },
+            )?;
+            state.end()
+        }
+    }
+
+    struct EventsSerializer<'a> {
+        events: &'a [Box<dyn Reflect>],
+        registry: &'a TypeRegistry,
+    }
+
+    impl Serialize for EventsSerializer<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut seq = serializer.serialize_seq(Some(self.events.len()))?;
+            for event in self.events {
+                seq.serialize_element(&ReflectSerializer::new(event.as_ref(), self.registry))?;
+            }
+            seq.end()
+        }
+    }
+
+    /// Deserializes an [`EventLog`], whose events are registered in the `registry`.
+    pub struct EventLogDeserializer<'a> {
+        /// The registry of the types of the events.
+        pub registry: &'a TypeRegistry,
+    }
+
+    impl<'a, 'de> DeserializeSeed<'de> for EventLogDeserializer<'a> {
+        type Value = EventLog;
+
+        fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<EventLog, D::Error> {
+            deserializer.deserialize_struct("EventLog", &["seed", "frames"], self)
+        }
+    }
+
+    impl<'a, 'de> Visitor<'de> for EventLogDeserializer<'a> {
+        type Value = EventLog;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an event log")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<EventLog, A::Error> {
+            let seed = seq
+                .next_element()?
+                .ok_or_else(|| Error::invalid_length(0, &self))?;
+            let frames = seq
+                .next_element_seed(FramesDeserializer {
+                    registry: self.registry,
+                })?
+                .ok_or_else(|| Error::invalid_length(1, &self))?;
+            Ok(EventLog { seed, frames })
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<EventLog, A::Error> {
+            let (mut seed, mut frames) = (None, None);
+            while let Some(key) = map.next_key::<&str>()? {
+                match key {
+                    "seed" => seed = Some(map.next_value()?),
+                    "frames" => {
+                        frames = Some(map.next_value_seed(FramesDeserializer {
+                            registry: self.registry,
+                        })?);
+                    }
+                    _ => return Err(Error::unknown_field(key, &["seed", "frames"])),
+                }
+            }
+            Ok(EventLog {
+                seed: seed.ok_or_else(|| Error::missing_field("seed"))?,
+                frames: frames.ok_or_else(|| Error::missing_field("frames"))?,
+            })
+        }
+    }
+
+    struct FramesDeserializer<'a> {
+        registry: &'a TypeRegistry,
+    }
+
+    impl<'a, 'de> DeserializeSeed<'de> for FramesDeserializer<'a> {
+        type Value = Vec<RecordedFrame>;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            deserializer.deserialize_seq(self)
+        }
+    }
+
+    impl<'a, 'de> Visitor<'de> for FramesDeserializer<'a> {
+        type Value = Vec<RecordedFrame>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a list of recorded frames")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut frames = Vec::new();
+            while let Some(frame) = seq.next_element_seed(FrameDeserializer {
+                registry: self.registry,
+            })? {
+                frames.push(frame);
+            }
+            Ok(frames)
+        }
+    }
+
+    struct FrameDeserializer<'a> {
+        registry: &'a TypeRegistry,
+    }
+
+    const FRAME_FIELDS: &[&str] = &["delta", "fixed_steps", "events"];
+
+    impl<'a, 'de> DeserializeSeed<'de> for FrameDeserializer<'a> {
+        type Value = RecordedFrame;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            deserializer.deserialize_struct("RecordedFrame", FRAME_FIELDS, self)
+        }
+    }
+
+    impl<'a, 'de> Visitor<'de> for FrameDeserializer<'a> {
+        type Value = RecordedFrame;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a recorded frame")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let delta = seq
+                .next_element()?
+                .ok_or_else(|| Error::invalid_length(0, &self))?;
+            let fixed_steps = seq
+                .next_element()?
+                .ok_or_else(|| Error::invalid_length(1, &self))?;
+            let events = seq
+                .next_element_seed(EventsDeserializer {
+                    registry: self.registry,
+                })?
+                .ok_or_else(|| Error::invalid_length(2, &self))?;
+            Ok(RecordedFrame {
+                delta,
+                fixed_steps,
+                events,
+            })
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let (mut delta, mut fixed_steps, mut events) = (None::<Duration>, None, None);
+            while let Some(key) = map.next_key::<&str>()? {
+                match key {
+                    "delta" => delta = Some(map.next_value()?),
+                    "fixed_steps" => fixed_steps = Some(map.next_value()?),
+                    "events" => {
+                        events = Some(map.next_value_seed(EventsDeserializer {
+                            registry: self.registry,
+                        })?);
+                    }
+                    _ => return Err(Error::unknown_field(key, FRAME_FIELDS)),
+                }
+            }
+            Ok(RecordedFrame {
+                delta: delta.ok_or_else(|| Error::missing_field("delta"))?,
+                fixed_steps: fixed_steps.ok_or_else(|| Error::missing_field("fixed_steps"))?,
+                events: events.ok_or_else(|| Error::missing_field("events"))?,
+            })
+        }
+    }
+
+    struct EventsDeserializer<'a> {
+        registry: &'a TypeRegistry,
+    }
+
+    impl<'a, 'de> DeserializeSeed<'de> for EventsDeserializer<'a> {
+        type Value = Vec<Box<dyn Reflect>>;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            deserializer.deserialize_seq(self)
+        }
+    }
+
+    impl<'a, 'de> Visitor<'de> for EventsDeserializer<'a> {
+        type Value = Vec<Box<dyn Reflect>>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a list of events")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut events = Vec::new();
+            while let Some(event) =
+                seq.next_element_seed(ReflectDeserializer::new(self.registry))?
+            {
+                events.push(event);
+            }
+            Ok(events)
+        }
+    }
+}
+
+#[cfg(feature = "serialize")]
+pub use self::serde::{EventLogDeserializer, EventLogSerializer};
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::prelude::*;
+    use bevy_ecs::prelude::*;
+    use bevy_reflect::Reflect;
+
+    use super::{EventRecorder, EventRecorderAppExt, EventRecorderPlugin};
+    use crate::TimePlugin;
+
+    #[derive(Event, Reflect, Debug, PartialEq)]
+    struct Input(u32);
+
+    #[derive(Resource, Default)]
+    struct Received(Vec<(u32, u32)>);
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((TimePlugin, EventRecorderPlugin))
+            .add_event::<Input>()
+            .record_event::<Input>()
+            .init_resource::<Received>()
+            .add_systems(
+                Update,
+                |mut frame: Local<u32>,
+                 mut events: EventReader<Input>,
+                 mut received: ResMut<Received>| {
+                    *frame += 1;
+                    received
+                        .0
+                        .extend(events.read().map(|event| (*frame, event.0)));
+                },
+            );
+        app
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let mut app = app();
+        app.world_mut()
+            .resource_mut::<EventRecorder>()
+            .start_recording(7);
+        for i in 0..4 {
+            if i % 2 == 1 {
+                app.world_mut().send_event(Input(i));
+            }
+            app.update();
+        }
+        let log = app
+            .world_mut()
+            .resource_mut::<EventRecorder>()
+            .stop_recording();
+        assert_eq!(log.seed, 7);
+        assert_eq!(log.frames.len(), 4);
+        let recorded = std::mem::take(&mut app.world_mut().resource_mut::<Received>().0);
+        assert_eq!(recorded, [(2, 1), (4, 3)]);
+
+        let mut app = self::app();
+        app.world_mut()
+            .resource_mut::<EventRecorder>()
+            .start_replay(log);
+        assert_eq!(app.world().resource::<EventRecorder>().seed(), Some(7));
+        // The events sent by the app itself are ignored while replaying
+        app.world_mut().send_event(Input(100));
+        for _ in 0..5 {
+            app.update();
+        }
+        assert!(!app.world().resource::<EventRecorder>().is_replaying());
+        assert_eq!(app.world().resource::<Received>().0, recorded);
+    }
+}
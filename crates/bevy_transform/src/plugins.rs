@@ -4,7 +4,10 @@ use bevy_hierarchy::ValidParentCheckPlugin;
 
 use crate::{
     prelude::{GlobalTransform, Transform},
-    systems::{propagate_transforms, sync_simple_transforms},
+    systems::{
+        propagate_transforms, send_global_transform_changed, sync_simple_transforms,
+        GlobalTransformChanged,
+    },
 };
 
 /// Set enum for the systems relating to transform propagation
@@ -60,3 +63,20 @@ impl Plugin for TransformPlugin {
             );
     }
 }
+
+/// Sends a [`GlobalTransformChanged`] event after the propagation for each entity whose
+/// [`GlobalTransform`] changed, to be added with the [`TransformPlugin`].
+///
+/// Systems that only care about the entities that moved in the world, like a spatial index, can
+/// read these events instead of querying all the entities with a changed [`GlobalTransform`].
+#[derive(Default)]
+pub struct GlobalTransformChangedPlugin;
+
+impl Plugin for GlobalTransformChangedPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<GlobalTransformChanged>().add_systems(
+            PostUpdate,
+            send_global_transform_changed.after(TransformSystem::TransformPropagate),
+        );
+    }
+}
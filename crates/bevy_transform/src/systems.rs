@@ -1,6 +1,7 @@
 use crate::components::{GlobalTransform, Transform};
 use bevy_ecs::{
-    change_detection::Ref,
+    change_detection::{DetectChangesMut, Ref},
+    event::{Event, EventWriter},
     prelude::{Changed, DetectChanges, Entity, Query, With, Without},
     query::{Added, Or},
     removal_detection::RemovedComponents,
@@ -8,6 +9,21 @@ use bevy_ecs::{
 };
 use bevy_hierarchy::{Children, Parent};
 
+/// Sent by [`send_global_transform_changed`] for each entity whose [`GlobalTransform`] changed
+/// during the propagation, added by the
+/// [`GlobalTransformChangedPlugin`](crate::plugins::GlobalTransformChangedPlugin).
+///
+/// The propagation only changes the [`GlobalTransform`]s whose value is different, so the
+/// descendants of an entity whose [`Transform`] was mutably accessed without changing its value
+/// don't get an event.
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct GlobalTransformChanged {
+    /// The entity whose [`GlobalTransform`] changed.
+    pub entity: Entity,
+    /// The new value of its [`GlobalTransform`].
+    pub global_transform: GlobalTransform,
+}
+
 /// Update [`GlobalTransform`] component of entities that aren't in the hierarchy
 ///
 /// Third party plugins should ensure that this is used in concert with [`propagate_transforms`].
@@ -30,14 +46,14 @@ pub fn sync_simple_transforms(
         .p0()
         .par_iter_mut()
         .for_each(|(transform, mut global_transform)| {
-            *global_transform = GlobalTransform::from(*transform);
+            global_transform.set_if_neq(GlobalTransform::from(*transform));
         });
     // Update orphaned entities.
     let mut query = query.p1();
     let mut iter = query.iter_many_mut(orphaned.read());
     while let Some((transform, mut global_transform)) = iter.fetch_next() {
         if !transform.is_changed() && !global_transform.is_added() {
-            *global_transform = GlobalTransform::from(*transform);
+            global_transform.set_if_neq(GlobalTransform::from(*transform));
         }
     }
 }
@@ -63,7 +79,7 @@ pub fn propagate_transforms(
         |(entity, children, transform, mut global_transform)| {
             let changed = transform.is_changed() || global_transform.is_added() || orphaned_entities.binary_search(&entity).is_ok();
             if changed {
-                *global_transform = GlobalTransform::from(*transform);
+                global_transform.set_if_neq(GlobalTransform::from(*transform));
             }
 
             for (child, actual_parent) in parent_query.iter_many(children) {
@@ -152,7 +168,7 @@ unsafe fn propagate_recursive(
 
         changed |= transform.is_changed() || global_transform.is_added();
         if changed {
-            *global_transform = parent.mul_transform(*transform);
+            global_transform.set_if_neq(parent.mul_transform(*transform));
         }
         (*global_transform, children)
     };
@@ -180,6 +196,24 @@ unsafe fn propagate_recursive(
     }
 }
 
+/// Sends a [`GlobalTransformChanged`] event for each entity whose [`GlobalTransform`] changed
+/// since the last run, to run after [`TransformSystem::TransformPropagate`].
+///
+/// [`TransformSystem::TransformPropagate`]: crate::TransformSystem::TransformPropagate
+pub fn send_global_transform_changed(
+    query: Query<(Entity, &GlobalTransform), Changed<GlobalTransform>>,
+    mut events: EventWriter<GlobalTransformChanged>,
+) {
+    events.send_batch(
+        query
+            .iter()
+            .map(|(entity, global_transform)| GlobalTransformChanged {
+                entity,
+                global_transform: *global_transform,
+            }),
+    );
+}
+
 #[cfg(test)]
 mod test {
     use bevy_app::prelude::*;
@@ -189,6 +223,7 @@ mod test {
     use bevy_tasks::{ComputeTaskPool, TaskPool};
 
     use crate::bundles::TransformBundle;
+    use crate::plugins::{GlobalTransformChangedPlugin, TransformPlugin};
     use crate::systems::*;
     use bevy_hierarchy::{BuildChildren, ChildBuild};
 
@@ -486,6 +521,55 @@ mod test {
         app.update();
     }
 
+    #[test]
+    fn global_transform_changed_events() {
+        ComputeTaskPool::get_or_init(TaskPool::default);
+        let mut app = App::new();
+        app.add_plugins((TransformPlugin, GlobalTransformChangedPlugin));
+
+        let child = app.world_mut().spawn(TransformBundle::IDENTITY).id();
+        let parent = app
+            .world_mut()
+            .spawn(TransformBundle::from_transform(Transform::from_xyz(
+                1.0, 0.0, 0.0,
+            )))
+            .add_child(child)
+            .id();
+        let still = app.world_mut().spawn(TransformBundle::IDENTITY).id();
+
+        let changed_entities = |app: &mut App| {
+            app.update();
+            let mut entities = app
+                .world_mut()
+                .resource_mut::<Events<GlobalTransformChanged>>()
+                .drain()
+                .map(|event| event.entity)
+                .collect::<Vec<_>>();
+            entities.sort();
+            entities
+        };
+        assert_eq!(changed_entities(&mut app), [child, parent, still]);
+        assert_eq!(changed_entities(&mut app), []);
+
+        // Touching the transform without changing it doesn't change the global transforms
+        app.world_mut()
+            .get_mut::<Transform>(parent)
+            .unwrap()
+            .set_changed();
+        assert_eq!(changed_entities(&mut app), []);
+
+        app.world_mut()
+            .get_mut::<Transform>(parent)
+            .unwrap()
+            .translation
+            .y = 2.0;
+        assert_eq!(changed_entities(&mut app), [child, parent]);
+        assert_eq!(
+            *app.world().get::<GlobalTransform>(child).unwrap(),
+            GlobalTransform::from_xyz(1.0, 2.0, 0.0)
+        );
+    }
+
     #[test]
     fn global_transform_should_not_be_overwritten_after_reparenting() {
         let translation = Vec3::ONE;
@@ -97,6 +97,16 @@ impl RelativeCursorPosition {
     }
 }
 
+/// The position of the cursor in the viewport of a camera that doesn't render to a window, in
+/// logical pixels, `None` if the cursor isn't over its render target.
+///
+/// [`ui_focus_system`] only knows the cursor position of the cameras rendering to a window. This
+/// component gives it to the cameras rendering UI to a texture displayed elsewhere, like the
+/// cameras of a [`WorldSpaceUi`](crate::WorldSpaceUi), so that their UI nodes are interactive.
+#[derive(Component, Copy, Clone, Default, PartialEq, Debug, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct CameraCursorPosition(pub Option<Vec2>);
+
 /// Describes whether the node should block interactions with lower nodes
 #[derive(Component, Copy, Clone, Eq, PartialEq, Debug, Reflect)]
 #[reflect(Component, Default, PartialEq)]
@@ -149,7 +159,7 @@ pub struct NodeQuery {
 #[allow(clippy::too_many_arguments)]
 pub fn ui_focus_system(
     mut state: Local<State>,
-    camera_query: Query<(Entity, &Camera)>,
+    camera_query: Query<(Entity, &Camera, Option<&CameraCursorPosition>)>,
     default_ui_camera: DefaultUiCamera,
     primary_window: Query<Entity, With<PrimaryWindow>>,
     windows: Query<&Window>,
@@ -189,8 +199,12 @@ pub fn ui_focus_system(
 
     let camera_cursor_positions: HashMap<Entity, Vec2> = camera_query
         .iter()
-        .filter_map(|(entity, camera)| {
-            // Interactions are only supported for cameras rendering to a window.
+        .filter_map(|(entity, camera, cursor_position)| {
+            if let Some(cursor_position) = cursor_position {
+                return cursor_position.0.map(|position| (entity, position));
+            }
+
+            // Otherwise, interactions are only supported for cameras rendering to a window.
             let Some(NormalizedRenderTarget::Window(window_ref)) =
                 camera.target.normalize(primary_window)
             else {
@@ -2,9 +2,9 @@ use taffy::style_helpers;
 
 use crate::{
     AlignContent, AlignItems, AlignSelf, Display, FlexDirection, FlexWrap, GridAutoFlow,
-    GridPlacement, GridTrack, GridTrackRepetition, JustifyContent, JustifyItems, JustifySelf,
-    MaxTrackSizingFunction, MinTrackSizingFunction, OverflowAxis, PositionType, RepeatedGridTrack,
-    Style, UiRect, Val,
+    GridPlacement, GridTemplateArea, GridTrack, GridTrackRepetition, JustifyContent, JustifyItems,
+    JustifySelf, MaxTrackSizingFunction, MinTrackSizingFunction, OverflowAxis, PositionType,
+    RepeatedGridTrack, Style, UiRect, Val,
 };
 
 use super::LayoutContext;
@@ -63,11 +63,23 @@ impl UiRect {
     }
 }
 
+/// Converts a [`Style`] to a taffy style.
+///
+/// The `grid_area` of the style is looked up in `parent_grid_areas`, the `grid_template_areas` of the
+/// parent node.
 pub fn from_style(
     context: &LayoutContext,
     style: &Style,
+    parent_grid_areas: &[GridTemplateArea],
     ignore_padding_and_border: bool,
 ) -> taffy::style::Style {
+    let (grid_row, grid_column) = style
+        .grid_area
+        .as_ref()
+        .and_then(|name| parent_grid_areas.iter().find(|area| area.name == *name))
+        .map_or((style.grid_row, style.grid_column), |area| {
+            (area.row, area.column)
+        });
     taffy::style::Style {
         display: style.display.into(),
         overflow: taffy::Point {
@@ -152,8 +164,8 @@ pub fn from_style(
             .iter()
             .map(|track| track.into_taffy_track(context))
             .collect::<Vec<_>>(),
-        grid_row: style.grid_row.into(),
-        grid_column: style.grid_column.into(),
+        grid_row: grid_row.into(),
+        grid_column: grid_column.into(),
     }
 }
 
@@ -264,6 +276,7 @@ impl From<OverflowAxis> for taffy::style::Overflow {
             OverflowAxis::Visible => taffy::style::Overflow::Visible,
             OverflowAxis::Clip => taffy::style::Overflow::Clip,
             OverflowAxis::Hidden => taffy::style::Overflow::Hidden,
+            OverflowAxis::Scroll => taffy::style::Overflow::Scroll,
         }
     }
 }
@@ -519,9 +532,11 @@ mod tests {
             ],
             grid_column: GridPlacement::start(4),
             grid_row: GridPlacement::span(3),
+            grid_template_areas: Vec::new(),
+            grid_area: None,
         };
         let viewport_values = LayoutContext::new(1.0, bevy_math::Vec2::new(800., 600.));
-        let taffy_style = from_style(&viewport_values, &bevy_style, false);
+        let taffy_style = from_style(&viewport_values, &bevy_style, &[], false);
         assert_eq!(taffy_style.display, taffy::style::Display::Flex);
         assert_eq!(taffy_style.position, taffy::style::Position::Absolute);
         assert_eq!(
@@ -1,6 +1,9 @@
 use thiserror::Error;
 
-use crate::{ContentSize, DefaultUiCamera, Node, Outline, Style, TargetCamera, UiScale};
+use crate::{
+    ContentSize, DefaultUiCamera, Node, Outline, ScrollPosition, ScrollbarTrack, Style,
+    TargetCamera, UiScale,
+};
 use bevy_ecs::{
     change_detection::{DetectChanges, DetectChangesMut},
     entity::Entity,
@@ -87,13 +90,21 @@ pub fn ui_layout_system(
             Ref<Style>,
             Option<&mut ContentSize>,
             Option<&TargetCamera>,
+            Option<Ref<Parent>>,
         ),
         With<Node>,
     >,
+    parent_style_query: Query<Ref<Style>, With<Node>>,
     children_query: Query<(Entity, Ref<Children>), With<Node>>,
     just_children_query: Query<&Children>,
     mut removed_components: UiLayoutSystemRemovedComponentParam,
-    mut node_transform_query: Query<(&mut Node, &mut Transform)>,
+    mut node_transform_query: Query<(
+        &mut Node,
+        &mut Transform,
+        &Style,
+        Option<&mut ScrollPosition>,
+    )>,
+    scrollbar_track_query: Query<(), With<ScrollbarTrack>>,
 ) {
     struct CameraLayoutInfo {
         size: UVec2,
@@ -164,14 +175,22 @@ pub fn ui_layout_system(
     }
 
     // Sync Style and ContentSize to Taffy for all nodes
-    for (entity, style, content_size, target_camera) in style_query.iter_mut() {
+    for (entity, style, content_size, target_camera, parent) in style_query.iter_mut() {
         if let Some(camera) =
             camera_with_default(target_camera).and_then(|c| camera_layout_info.get(&c))
         {
+            let parent_style = parent
+                .as_ref()
+                .and_then(|parent| parent_style_query.get(parent.get()).ok());
+            // The placement of the items in named grid areas depends on the areas of their parent.
+            let grid_area_changed = style.grid_area.is_some()
+                && (parent.as_ref().is_some_and(DetectChanges::is_changed)
+                    || parent_style.as_ref().is_some_and(DetectChanges::is_changed));
             if camera.resized
                 || !scale_factor_events.is_empty()
                 || ui_scale.is_changed()
                 || style.is_changed()
+                || grid_area_changed
                 || content_size
                     .as_ref()
                     .map(|c| c.measure.is_some())
@@ -182,10 +201,19 @@ pub fn ui_layout_system(
                     [camera.size.x as f32, camera.size.y as f32].into(),
                 );
                 let measure = content_size.and_then(|mut c| c.measure.take());
-                ui_surface.upsert_node(&layout_context, entity, &style, measure);
+                let parent_grid_areas = parent_style
+                    .as_deref()
+                    .map_or(&[][..], |style| &style.grid_template_areas);
+                ui_surface.upsert_node(&layout_context, entity, &style, parent_grid_areas, measure);
             }
         } else {
-            ui_surface.upsert_node(&LayoutContext::DEFAULT, entity, &Style::default(), None);
+            ui_surface.upsert_node(
+                &LayoutContext::DEFAULT,
+                entity,
+                &Style::default(),
+                &[],
+                None,
+            );
         }
     }
     scale_factor_events.clear();
@@ -227,9 +255,11 @@ pub fn ui_layout_system(
                 &ui_surface,
                 &mut node_transform_query,
                 &just_children_query,
+                &scrollbar_track_query,
                 inverse_target_scale_factor,
                 Vec2::ZERO,
                 Vec2::ZERO,
+                Vec2::ZERO,
             );
         }
     }
@@ -237,20 +267,38 @@ pub fn ui_layout_system(
     fn update_uinode_geometry_recursive(
         entity: Entity,
         ui_surface: &UiSurface,
-        node_transform_query: &mut Query<(&mut Node, &mut Transform)>,
+        node_transform_query: &mut Query<(
+            &mut Node,
+            &mut Transform,
+            &Style,
+            Option<&mut ScrollPosition>,
+        )>,
         children_query: &Query<&Children>,
+        scrollbar_track_query: &Query<(), With<ScrollbarTrack>>,
         inverse_target_scale_factor: f32,
         parent_size: Vec2,
+        parent_scroll_offset: Vec2,
         mut absolute_location: Vec2,
     ) {
-        if let Ok((mut node, mut transform)) = node_transform_query.get_mut(entity) {
+        if let Ok((mut node, mut transform, style, scroll_position)) =
+            node_transform_query.get_mut(entity)
+        {
             let Ok(layout) = ui_surface.get_layout(entity) else {
                 return;
             };
             let layout_size =
                 inverse_target_scale_factor * Vec2::new(layout.size.width, layout.size.height);
-            let layout_location =
-                inverse_target_scale_factor * Vec2::new(layout.location.x, layout.location.y);
+            let content_size = inverse_target_scale_factor
+                * Vec2::new(layout.content_size.width, layout.content_size.height);
+            // scrollbars stay in place while the content of their node scrolls
+            let parent_scroll_offset = if scrollbar_track_query.contains(entity) {
+                Vec2::ZERO
+            } else {
+                parent_scroll_offset
+            };
+            let layout_location = inverse_target_scale_factor
+                * Vec2::new(layout.location.x, layout.location.y)
+                - parent_scroll_offset;
 
             absolute_location += layout_location;
 
@@ -261,13 +309,43 @@ pub fn ui_layout_system(
                 round_layout_coords(layout_location) + 0.5 * (rounded_size - parent_size);
 
             // only trigger change detection when the new values are different
-            if node.calculated_size != rounded_size || node.unrounded_size != layout_size {
+            if node.calculated_size != rounded_size
+                || node.unrounded_size != layout_size
+                || node.content_size != content_size
+            {
                 node.calculated_size = rounded_size;
                 node.unrounded_size = layout_size;
+                node.content_size = content_size;
             }
             if transform.translation.truncate() != rounded_location {
                 transform.translation = rounded_location.extend(0.);
             }
+
+            let mut scroll_offset = Vec2::ZERO;
+            if let Some(mut scroll_position) = scroll_position {
+                let clamped_offset = scroll_position
+                    .offset()
+                    .clamp(Vec2::ZERO, node.max_scroll_offset());
+                if clamped_offset != scroll_position.offset() {
+                    *scroll_position = clamped_offset.into();
+                }
+                // round in physical pixels so that the scrolled content stays pixel aligned
+                let physical_offset = (clamped_offset / inverse_target_scale_factor).round()
+                    * inverse_target_scale_factor;
+                scroll_offset = Vec2::new(
+                    if style.overflow.x.is_scroll() {
+                        physical_offset.x
+                    } else {
+                        0.
+                    },
+                    if style.overflow.y.is_scroll() {
+                        physical_offset.y
+                    } else {
+                        0.
+                    },
+                );
+            }
+
             if let Ok(children) = children_query.get(entity) {
                 for &child_uinode in children {
                     update_uinode_geometry_recursive(
@@ -275,8 +353,10 @@ pub fn ui_layout_system(
                         ui_surface,
                         node_transform_query,
                         children_query,
+                        scrollbar_track_query,
                         inverse_target_scale_factor,
                         rounded_size,
+                        scroll_offset,
                         absolute_location,
                     );
                 }
@@ -1023,6 +1103,146 @@ mod tests {
         }
     }
 
+    #[test]
+    fn scroll_position_offsets_children_except_scrollbars() {
+        let (mut world, mut ui_schedule) = setup_ui_test_world();
+
+        let child_style = |height: f32| Style {
+            width: Val::Percent(100.),
+            height: Val::Px(height),
+            flex_shrink: 0.,
+            ..default()
+        };
+        let ui_root = world
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(100.),
+                        height: Val::Px(50.),
+                        flex_direction: FlexDirection::Column,
+                        overflow: Overflow::scroll_y(),
+                        ..default()
+                    },
+                    ..default()
+                },
+                ScrollPosition::new(0., 30.),
+            ))
+            .with_children(|parent| {
+                parent.spawn(NodeBundle {
+                    style: child_style(200.),
+                    ..default()
+                });
+                parent.spawn((
+                    NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            ..child_style(10.)
+                        },
+                        ..default()
+                    },
+                    crate::ScrollbarTrack {
+                        axis: crate::ScrollbarAxis::Y,
+                    },
+                ));
+            })
+            .id();
+        let children = world.get::<Children>(ui_root).unwrap().to_vec();
+        let translation_y = |world: &World, entity: Entity| {
+            world
+                .get::<GlobalTransform>(entity)
+                .unwrap()
+                .translation()
+                .y
+        };
+
+        ui_schedule.run(&mut world);
+
+        let root_node = world.get::<Node>(ui_root).unwrap();
+        assert_eq!(root_node.content_size().y, 200.);
+        assert_eq!(root_node.max_scroll_offset().y, 150.);
+        // the top of the content is 30 pixels above the node
+        assert_eq!(translation_y(&world, children[0]), 70.);
+        assert_eq!(translation_y(&world, children[1]), 5.);
+
+        // the offset is clamped to the size of the content
+        world.get_mut::<ScrollPosition>(ui_root).unwrap().offset_y = 1000.;
+        ui_schedule.run(&mut world);
+
+        assert_eq!(
+            *world.get::<ScrollPosition>(ui_root).unwrap(),
+            ScrollPosition::new(0., 150.)
+        );
+        assert_eq!(translation_y(&world, children[0]), -50.);
+        assert_eq!(translation_y(&world, children[1]), 5.);
+    }
+
+    #[test]
+    fn grid_items_are_placed_in_named_areas() {
+        let (mut world, mut ui_schedule) = setup_ui_test_world();
+
+        let ui_root = world
+            .spawn(NodeBundle {
+                style: Style {
+                    display: Display::Grid,
+                    width: Val::Px(200.),
+                    height: Val::Px(100.),
+                    grid_template_columns: RepeatedGridTrack::flex(2, 1.),
+                    grid_template_rows: RepeatedGridTrack::flex(2, 1.),
+                    grid_template_areas: GridTemplateArea::from_rows(["a a", "b c"]).unwrap(),
+                    ..default()
+                },
+                ..default()
+            })
+            .id();
+        let area_child = |world: &mut World, area: &str| {
+            let child = world
+                .spawn(NodeBundle {
+                    style: Style {
+                        grid_area: Some(area.to_string()),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .id();
+            world.entity_mut(ui_root).add_child(child);
+            child
+        };
+        let c = area_child(&mut world, "c");
+        let a = area_child(&mut world, "a");
+        // unknown areas fall back to automatic placement, in the first free cell
+        let unknown = area_child(&mut world, "unknown");
+
+        ui_schedule.run(&mut world);
+
+        let layout = |world: &World, entity: Entity| {
+            let layout = world.resource::<UiSurface>().get_layout(entity).unwrap();
+            (
+                Vec2::new(layout.location.x, layout.location.y),
+                Vec2::new(layout.size.width, layout.size.height),
+            )
+        };
+        assert_eq!(layout(&world, a), (Vec2::ZERO, Vec2::new(200., 50.)));
+        assert_eq!(
+            layout(&world, c),
+            (Vec2::new(100., 50.), Vec2::new(100., 50.))
+        );
+        assert_eq!(
+            layout(&world, unknown),
+            (Vec2::new(0., 50.), Vec2::new(100., 50.))
+        );
+
+        // changing the areas of the parent moves the items
+        world.get_mut::<Style>(ui_root).unwrap().grid_template_areas =
+            GridTemplateArea::from_rows(["a c", "a b"]).unwrap();
+        ui_schedule.run(&mut world);
+
+        assert_eq!(layout(&world, a), (Vec2::ZERO, Vec2::new(100., 100.)));
+        assert_eq!(
+            layout(&world, c),
+            (Vec2::new(100., 0.), Vec2::new(100., 50.))
+        );
+    }
+
     #[test]
     fn no_camera_ui() {
         let mut world = World::new();
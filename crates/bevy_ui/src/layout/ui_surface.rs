@@ -10,7 +10,7 @@ use bevy_utils::default;
 use bevy_utils::tracing::warn;
 
 use crate::layout::convert;
-use crate::{LayoutContext, LayoutError, Measure, NodeMeasure, Style};
+use crate::{GridTemplateArea, LayoutContext, LayoutError, Measure, NodeMeasure, Style};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RootNodePair {
@@ -65,6 +65,7 @@ impl UiSurface {
         layout_context: &LayoutContext,
         entity: Entity,
         style: &Style,
+        parent_grid_areas: &[GridTemplateArea],
         mut new_node_context: Option<NodeMeasure>,
     ) {
         let taffy = &mut self.taffy;
@@ -75,13 +76,18 @@ impl UiSurface {
             if let Some(measure) = new_node_context.take() {
                 taffy
                     .new_leaf_with_context(
-                        convert::from_style(layout_context, style, true),
+                        convert::from_style(layout_context, style, parent_grid_areas, true),
                         measure,
                     )
                     .unwrap()
             } else {
                 taffy
-                    .new_leaf(convert::from_style(layout_context, style, false))
+                    .new_leaf(convert::from_style(
+                        layout_context,
+                        style,
+                        parent_grid_areas,
+                        false,
+                    ))
                     .unwrap()
             }
         });
@@ -99,7 +105,7 @@ impl UiSurface {
             taffy
                 .set_style(
                     taffy_node_id,
-                    convert::from_style(layout_context, style, has_measure),
+                    convert::from_style(layout_context, style, parent_grid_areas, has_measure),
                 )
                 .unwrap();
         }
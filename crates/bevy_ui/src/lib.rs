@@ -24,26 +24,45 @@ mod accessibility;
 mod focus;
 mod geometry;
 mod layout;
+mod navigation;
 mod render;
+mod scroll;
 mod stack;
 mod texture_slice;
+mod transition;
 mod ui_node;
+#[cfg(feature = "bevy_pbr")]
+mod world_space;
 
 pub use focus::*;
 pub use geometry::*;
 pub use layout::*;
 pub use measurement::*;
+pub use navigation::*;
 pub use render::*;
+pub use scroll::*;
+pub use transition::*;
 pub use ui_material::*;
 pub use ui_node::*;
 use widget::UiImageSize;
+#[cfg(feature = "bevy_pbr")]
+pub use world_space::*;
 
 #[doc(hidden)]
 pub mod prelude {
+    #[cfg(feature = "bevy_text")]
+    #[doc(hidden)]
+    pub use crate::widget::{
+        TextInput, TextInputChanged, TextInputStyle, TextInputSubmitted, TextLinkEvent,
+        TextLinkEventKind,
+    };
     #[doc(hidden)]
     pub use crate::{
-        geometry::*, node_bundles::*, ui_material::*, ui_node::*, widget::Button, widget::Label,
-        Interaction, UiMaterialPlugin, UiScale,
+        geometry::*, navigation::FocusChanged, navigation::Focusable, navigation::UiFocus,
+        node_bundles::*, scroll::ScrollAnimation, scroll::Scrollbar, transition::Easing,
+        transition::TransitionProperty, transition::UiTransition, ui_material::*, ui_node::*,
+        widget::Button, widget::Label, widget::VirtualList, widget::VirtualListRow, Interaction,
+        UiMaterialPlugin, UiScale,
     };
     // `bevy_sprite` re-exports for texture slicing
     #[doc(hidden)]
@@ -74,6 +93,12 @@ pub enum UiSystem {
     Layout,
     /// After this label, input interactions with UI entities have been updated for this frame
     Focus,
+    /// After this label, the [`UiFocus`] has been moved by input, and the focused node pressed by
+    /// the keyboard and gamepads
+    Navigation,
+    /// After this label, the [`ScrollPosition`]s have been updated by input, kinetic scrolling and
+    /// [`ScrollAnimation`]s for this frame
+    Scroll,
     /// After this label, the [`UiStack`] resource has been updated
     Stack,
     /// After this label, node outline widths have been updated
@@ -110,28 +135,69 @@ impl Plugin for UiPlugin {
         app.init_resource::<UiSurface>()
             .init_resource::<UiScale>()
             .init_resource::<UiStack>()
+            .init_resource::<ScrollSettings>()
+            .init_resource::<UiFocus>()
+            .add_event::<FocusChanged>()
             .register_type::<BackgroundColor>()
             .register_type::<CalculatedClip>()
             .register_type::<ContentSize>()
+            .register_type::<FocusChanged>()
             .register_type::<FocusPolicy>()
+            .register_type::<Focusable>()
             .register_type::<Interaction>()
+            .register_type::<Easing>()
             .register_type::<Node>()
+            .register_type::<Opacity>()
+            .register_type::<PropertyTransition>()
             .register_type::<RelativeCursorPosition>()
+            .register_type::<ScrollAnimation>()
+            .register_type::<ScrollPosition>()
+            .register_type::<ScrollSettings>()
+            .register_type::<ScrollVelocity>()
+            .register_type::<Scrollbar>()
+            .register_type::<ScrollbarThumb>()
+            .register_type::<ScrollbarTrack>()
             .register_type::<Style>()
             .register_type::<TargetCamera>()
+            .register_type::<TransitionProperty>()
             .register_type::<UiImage>()
+            .register_type::<UiFocus>()
             .register_type::<UiImageSize>()
             .register_type::<UiRect>()
             .register_type::<UiScale>()
+            .register_type::<UiTransition>()
+            .register_type::<CameraCursorPosition>()
             .register_type::<BorderColor>()
             .register_type::<BorderRadius>()
+            .register_type::<BorderStyle>()
+            .register_type::<BoxShadow>()
             .register_type::<widget::Button>()
             .register_type::<widget::Label>()
+            .register_type::<widget::VirtualListRow>()
             .register_type::<ZIndex>()
             .register_type::<Outline>()
             .add_systems(
                 PreUpdate,
-                ui_focus_system.in_set(UiSystem::Focus).after(InputSystem),
+                (
+                    insert_scroll_components_system.before(UiSystem::Focus),
+                    ui_focus_system.in_set(UiSystem::Focus).after(InputSystem),
+                    (
+                        ui_navigation_system,
+                        ui_focus_activation_system,
+                        focus_changed_system,
+                    )
+                        .chain()
+                        .in_set(UiSystem::Navigation)
+                        .after(UiSystem::Focus),
+                    (
+                        scroll_input_system,
+                        kinetic_scroll_system,
+                        scroll_animation_system,
+                    )
+                        .chain()
+                        .in_set(UiSystem::Scroll)
+                        .after(UiSystem::Focus),
+                ),
             );
 
         app.add_systems(
@@ -139,6 +205,11 @@ impl Plugin for UiPlugin {
             (
                 check_visibility::<WithNode>.in_set(VisibilitySystems::CheckVisibility),
                 update_target_camera_system.before(UiSystem::Layout),
+                ui_transition_system.before(UiSystem::Layout),
+                widget::update_virtual_list_system.before(update_target_camera_system),
+                (spawn_scrollbars_system, update_scrollbars_system)
+                    .chain()
+                    .before(UiSystem::Layout),
                 apply_deferred
                     .after(update_target_camera_system)
                     .before(UiSystem::Layout),
@@ -170,6 +241,7 @@ impl Plugin for UiPlugin {
                 (
                     texture_slice::compute_slices_on_asset_event,
                     texture_slice::compute_slices_on_image_change,
+                    texture_slice::remove_slices_on_scale_mode_removal,
                 )
                     .after(UiSystem::Layout),
             ),
@@ -178,6 +250,9 @@ impl Plugin for UiPlugin {
         #[cfg(feature = "bevy_text")]
         build_text_interop(app);
 
+        #[cfg(feature = "bevy_pbr")]
+        build_world_space_ui(app);
+
         build_ui_render(app);
     }
 
@@ -190,14 +265,60 @@ impl Plugin for UiPlugin {
     }
 }
 
+/// A function that should be called from [`UiPlugin::build`] when [`bevy_pbr`] is enabled.
+#[cfg(feature = "bevy_pbr")]
+fn build_world_space_ui(app: &mut App) {
+    app.register_type::<WorldSpaceUi>()
+        .register_type::<WorldSpaceUiQuad>()
+        .register_type::<WorldSpaceUiRoot>()
+        .add_systems(
+            PreUpdate,
+            world_space_ui_picking_system
+                .before(UiSystem::Focus)
+                .after(InputSystem),
+        )
+        .add_systems(
+            PostUpdate,
+            (
+                (
+                    spawn_world_space_ui_system,
+                    apply_deferred,
+                    update_world_space_ui_roots_system,
+                    apply_deferred,
+                )
+                    .chain()
+                    .before(update_target_camera_system),
+                billboard_world_space_ui_system.before(TransformSystem::TransformPropagate),
+            ),
+        );
+}
+
 /// A function that should be called from [`UiPlugin::build`] when [`bevy_text`] is enabled.
 #[cfg(feature = "bevy_text")]
 fn build_text_interop(app: &mut App) {
-    use crate::widget::TextFlags;
+    use crate::widget::{
+        TextFlags, TextInput, TextInputChanged, TextInputStyle, TextInputSubmitted, TextLinkEvent,
+    };
     use bevy_text::TextLayoutInfo;
 
     app.register_type::<TextLayoutInfo>()
-        .register_type::<TextFlags>();
+        .register_type::<TextFlags>()
+        .register_type::<TextInput>()
+        .register_type::<TextInputStyle>()
+        .add_event::<TextInputChanged>()
+        .add_event::<TextInputSubmitted>()
+        .add_event::<TextLinkEvent>();
+
+    app.add_systems(
+        PreUpdate,
+        (
+            widget::text_input_mouse_system,
+            widget::text_input_keyboard_system,
+        )
+            .chain()
+            .after(UiSystem::Navigation),
+    );
+    app.add_systems(PreUpdate, widget::text_link_system.after(UiSystem::Focus));
 
     app.add_systems(
         PostUpdate,
@@ -216,6 +337,14 @@ fn build_text_interop(app: &mut App) {
                 // We assume Text is on disjoint UI entities to UiImage and UiTextureAtlasImage
                 // FIXME: Add an archetype invariant for this https://github.com/bevyengine/bevy/issues/1481.
                 .ambiguous_with(widget::update_image_content_size_system),
+            (
+                widget::spawn_text_input_system,
+                apply_deferred,
+                widget::update_text_input_system,
+            )
+                .chain()
+                .before(widget::measure_text_system)
+                .in_set(AmbiguousWithTextSystem),
             widget::text_system
                 .after(UiSystem::Layout)
                 .after(bevy_text::remove_dropped_font_atlas_sets)
@@ -235,4 +364,9 @@ fn build_text_interop(app: &mut App) {
         PostUpdate,
         AmbiguousWithUpdateText2DLayout.ambiguous_with(bevy_text::update_text2d_layout),
     );
+
+    app.configure_sets(
+        PostUpdate,
+        bevy_text::TextEffectSystem.after(widget::text_system),
+    );
 }
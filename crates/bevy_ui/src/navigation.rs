@@ -0,0 +1,433 @@
+//! Keyboard and gamepad focus of the UI nodes: the [`UiFocus`] moves between the [`Focusable`]
+//! nodes with tab, the arrow keys, the d-pad and the left stick, and the focused node is pressed
+//! with enter, space or the south gamepad button.
+
+use crate::{Interaction, Node};
+use bevy_ecs::prelude::*;
+use bevy_input::{
+    gamepad::{GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, Gamepads},
+    keyboard::{KeyCode, KeyboardInput},
+    mouse::MouseButton,
+    touch::Touches,
+    Axis, ButtonInput,
+};
+use bevy_math::{Rect, Vec2};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::view::ViewVisibility;
+use bevy_transform::components::GlobalTransform;
+
+/// Marks a UI node that can get the [`UiFocus`].
+///
+/// The node is focused when it's clicked, with tab and shift-tab in the order of
+/// [`Focusable::tab_index`], and with the arrow keys, the d-pad and the left stick of gamepads,
+/// which move the focus to the closest node in their direction.
+#[derive(Component, Debug, Copy, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct Focusable {
+    /// The position of the node in the tab order. Nodes with the same index are ordered by their
+    /// stack index, and nodes with a negative index are skipped by tab.
+    pub tab_index: i32,
+    /// Whether the arrow keys, the d-pad and the left stick move the focus away from this node.
+    ///
+    /// This is `false` for the nodes using them, like text inputs.
+    pub directional_navigation: bool,
+}
+
+impl Focusable {
+    pub const DEFAULT: Self = Self {
+        tab_index: 0,
+        directional_navigation: true,
+    };
+}
+
+impl Default for Focusable {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// The [`Focusable`] node that receives the keyboard and gamepad input, if any.
+#[derive(Resource, Debug, Copy, Clone, Default, PartialEq, Eq, Reflect)]
+#[reflect(Resource, Default, PartialEq)]
+pub struct UiFocus {
+    /// The focused node.
+    pub entity: Option<Entity>,
+    /// Whether the focus was moved with the keyboard or a gamepad, rather than by clicking.
+    ///
+    /// A focus indicator is usually only drawn in that case.
+    pub visible: bool,
+}
+
+impl UiFocus {
+    /// Returns `true` if `entity` has the focus.
+    pub fn is_focused(&self, entity: Entity) -> bool {
+        self.entity == Some(entity)
+    }
+}
+
+/// Sent when the [`UiFocus`] moves to another node, or is removed.
+#[derive(Event, Debug, Copy, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+pub struct FocusChanged {
+    /// The node that had the focus.
+    pub previous: Option<Entity>,
+    /// The node that has the focus.
+    pub current: Option<Entity>,
+}
+
+/// A direction the [`UiFocus`] can be moved in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Debug, PartialEq)]
+pub enum NavigationDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl NavigationDirection {
+    /// The direction in UI coordinates, where the y axis points down.
+    pub fn as_vec2(self) -> Vec2 {
+        match self {
+            Self::Up => Vec2::NEG_Y,
+            Self::Down => Vec2::Y,
+            Self::Left => Vec2::NEG_X,
+            Self::Right => Vec2::X,
+        }
+    }
+
+    fn from_stick(stick: Vec2) -> Option<Self> {
+        // the gamepad y axis points up
+        if stick.x.abs() > stick.y.abs() {
+            Some(if stick.x > 0. {
+                Self::Right
+            } else {
+                Self::Left
+            })
+        } else if stick.y != 0. {
+            Some(if stick.y > 0. { Self::Up } else { Self::Down })
+        } else {
+            None
+        }
+    }
+}
+
+/// How far the left stick of a gamepad has to be pushed to move the [`UiFocus`].
+const STICK_THRESHOLD: f32 = 0.5;
+
+/// Returns the entity after (or before, if `backward` is `true`) `current` in the tab order,
+/// wrapping around. `nodes` are the tab index, stack index and entity of the nodes in the tab
+/// order.
+fn next_in_tab_order(
+    mut nodes: Vec<(i32, u32, Entity)>,
+    current: Option<Entity>,
+    backward: bool,
+) -> Option<Entity> {
+    nodes.sort_unstable();
+    let position =
+        current.and_then(|current| nodes.iter().position(|&(_, _, entity)| entity == current));
+    let next = match (position, backward) {
+        (None, false) => 0,
+        (None, true) => nodes.len().checked_sub(1)?,
+        (Some(position), false) => (position + 1) % nodes.len(),
+        (Some(position), true) => (position + nodes.len() - 1) % nodes.len(),
+    };
+    nodes.get(next).map(|&(_, _, entity)| entity)
+}
+
+/// Returns the node of `candidates` closest to `origin` in `direction`.
+///
+/// The candidates must be past the edge of `origin` facing `direction`. The distance
+/// perpendicular to `direction` counts more than the distance along it, so that nodes in the same
+/// row or column are preferred.
+fn find_in_direction(
+    origin: Rect,
+    candidates: impl IntoIterator<Item = (Entity, Rect)>,
+    direction: NavigationDirection,
+) -> Option<Entity> {
+    let direction = direction.as_vec2();
+    let perpendicular = direction.perp();
+    let origin_edge = origin.center().dot(direction) + origin.half_size().dot(direction.abs());
+    candidates
+        .into_iter()
+        .filter_map(|(entity, rect)| {
+            let center = rect.center();
+            let near_edge = center.dot(direction) - rect.half_size().dot(direction.abs());
+            let distance = near_edge - origin_edge;
+            // allow a little overlap with the origin, as with nodes in a wrapping flex row
+            if center.dot(direction) <= origin.center().dot(direction)
+                || distance < -0.5 * rect.size().dot(direction.abs())
+            {
+                return None;
+            }
+            let offset = (center - origin.center()).dot(perpendicular).abs();
+            Some((entity, distance.max(0.) + 2. * offset))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(entity, _)| entity)
+}
+
+/// The [`Focusable`] nodes that can get the [`UiFocus`].
+type FocusableQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Focusable,
+        &'static Node,
+        &'static GlobalTransform,
+        &'static ViewVisibility,
+    ),
+>;
+
+/// Moves the [`UiFocus`] with the mouse, the keyboard and the gamepads.
+///
+/// A [`Focusable`] node gets the focus when it's pressed, and the focus is removed when anything
+/// else is clicked. Tab and shift-tab follow the tab order, and the arrow keys, the d-pad and the
+/// left stick move the focus in their direction. A node is focused by these even when nothing
+/// had the focus.
+#[allow(clippy::too_many_arguments)]
+pub fn ui_navigation_system(
+    mut stick_directions: Local<Vec<Option<NavigationDirection>>>,
+    mut focus: ResMut<UiFocus>,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    focusables: FocusableQuery,
+    interactions: Query<(Entity, Ref<Interaction>), With<Focusable>>,
+) {
+    let is_focusable = |entity| {
+        focusables
+            .get(entity)
+            .is_ok_and(|(.., visibility)| visibility.get())
+    };
+    if focus.entity.is_some_and(|entity| !is_focusable(entity)) {
+        *focus = UiFocus::default();
+    }
+
+    if mouse.just_pressed(MouseButton::Left) || touches.any_just_pressed() {
+        let pressed = interactions
+            .iter()
+            .find(|(_, interaction)| {
+                interaction.is_changed() && **interaction == Interaction::Pressed
+            })
+            .map(|(entity, _)| entity);
+        focus.set_if_neq(UiFocus {
+            entity: pressed,
+            visible: false,
+        });
+    }
+
+    let mut tab = None;
+    let mut directions = Vec::new();
+    for event in keyboard_events.read() {
+        if !event.state.is_pressed() {
+            continue;
+        }
+        match event.key_code {
+            KeyCode::Tab => {
+                tab = Some(keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]));
+            }
+            KeyCode::ArrowUp => directions.push(NavigationDirection::Up),
+            KeyCode::ArrowDown => directions.push(NavigationDirection::Down),
+            KeyCode::ArrowLeft => directions.push(NavigationDirection::Left),
+            KeyCode::ArrowRight => directions.push(NavigationDirection::Right),
+            _ => {}
+        }
+    }
+    for gamepad in gamepads.iter() {
+        for (button, direction) in [
+            (GamepadButtonType::DPadUp, NavigationDirection::Up),
+            (GamepadButtonType::DPadDown, NavigationDirection::Down),
+            (GamepadButtonType::DPadLeft, NavigationDirection::Left),
+            (GamepadButtonType::DPadRight, NavigationDirection::Right),
+        ] {
+            if gamepad_buttons.just_pressed(GamepadButton::new(gamepad, button)) {
+                directions.push(direction);
+            }
+        }
+
+        // the stick moves the focus once each time it's pushed past the threshold
+        let axis = |axis_type| {
+            gamepad_axes
+                .get(GamepadAxis::new(gamepad, axis_type))
+                .unwrap_or(0.)
+        };
+        let stick = Vec2::new(
+            axis(GamepadAxisType::LeftStickX),
+            axis(GamepadAxisType::LeftStickY),
+        );
+        let direction = if stick.length() > STICK_THRESHOLD {
+            NavigationDirection::from_stick(stick)
+        } else {
+            None
+        };
+        if gamepad.id >= stick_directions.len() {
+            stick_directions.resize(gamepad.id + 1, None);
+        }
+        let previous = std::mem::replace(&mut stick_directions[gamepad.id], direction);
+        if let Some(direction) = direction.filter(|&direction| Some(direction) != previous) {
+            directions.push(direction);
+        }
+    }
+
+    let visible_focusables = || {
+        focusables
+            .iter()
+            .filter(|(.., visibility)| visibility.get())
+    };
+    if let Some(backward) = tab {
+        let nodes = visible_focusables()
+            .filter(|(_, focusable, ..)| focusable.tab_index >= 0)
+            .map(|(entity, focusable, node, ..)| (focusable.tab_index, node.stack_index(), entity))
+            .collect();
+        if let Some(entity) = next_in_tab_order(nodes, focus.entity, backward) {
+            focus.set_if_neq(UiFocus {
+                entity: Some(entity),
+                visible: true,
+            });
+        }
+    }
+
+    for direction in directions {
+        let next = match focus.entity.and_then(|entity| focusables.get(entity).ok()) {
+            Some((_, focusable, ..)) if !focusable.directional_navigation => continue,
+            Some((entity, _, node, transform, _)) => find_in_direction(
+                node.logical_rect(transform),
+                visible_focusables()
+                    .filter(|(candidate, ..)| *candidate != entity)
+                    .map(|(candidate, _, node, transform, _)| {
+                        (candidate, node.logical_rect(transform))
+                    }),
+                direction,
+            ),
+            None => next_in_tab_order(
+                visible_focusables()
+                    .map(|(entity, focusable, node, ..)| {
+                        (focusable.tab_index, node.stack_index(), entity)
+                    })
+                    .collect(),
+                None,
+                false,
+            ),
+        };
+        if let Some(entity) = next {
+            focus.set_if_neq(UiFocus {
+                entity: Some(entity),
+                visible: true,
+            });
+        }
+    }
+}
+
+/// Presses the [`Interaction`] of the focused node while enter, space or the south button of a
+/// gamepad is held, as if it was clicked.
+pub fn ui_focus_activation_system(
+    mut pressed: Local<Option<Entity>>,
+    focus: Res<UiFocus>,
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    mut interactions: Query<&mut Interaction, With<Focusable>>,
+) {
+    let activation_keys = [KeyCode::Enter, KeyCode::NumpadEnter, KeyCode::Space];
+    let south_buttons = || {
+        gamepads
+            .iter()
+            .map(|gamepad| GamepadButton::new(gamepad, GamepadButtonType::South))
+    };
+
+    if let Some(entity) = *pressed {
+        let held =
+            keys.any_pressed(activation_keys) || gamepad_buttons.any_pressed(south_buttons());
+        if !held || !focus.is_focused(entity) {
+            if let Ok(mut interaction) = interactions.get_mut(entity) {
+                interaction.set_if_neq(Interaction::None);
+            }
+            *pressed = None;
+        }
+    }
+
+    let just_pressed =
+        keys.any_just_pressed(activation_keys) || gamepad_buttons.any_just_pressed(south_buttons());
+    if !just_pressed {
+        return;
+    }
+    if let Some(entity) = focus.entity {
+        if let Ok(mut interaction) = interactions.get_mut(entity) {
+            *interaction = Interaction::Pressed;
+            *pressed = Some(entity);
+        }
+    }
+}
+
+/// Sends the [`FocusChanged`] events, and mirrors the [`UiFocus`] in the accessibility
+/// [`Focus`](bevy_a11y::Focus).
+pub fn focus_changed_system(
+    mut previous: Local<Option<Entity>>,
+    focus: Res<UiFocus>,
+    accessibility_focus: Option<ResMut<bevy_a11y::Focus>>,
+    mut events: EventWriter<FocusChanged>,
+) {
+    if focus.entity == *previous {
+        return;
+    }
+    events.send(FocusChanged {
+        previous: *previous,
+        current: focus.entity,
+    });
+    *previous = focus.entity;
+    if let Some(mut accessibility_focus) = accessibility_focus {
+        accessibility_focus.0 = focus.entity;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_in_direction, next_in_tab_order, NavigationDirection};
+    use bevy_ecs::entity::Entity;
+    use bevy_math::{Rect, Vec2};
+
+    #[test]
+    fn tab_order() {
+        let [a, b, c, d] = [0, 1, 2, 3].map(Entity::from_raw);
+        let nodes = vec![(1, 0, a), (0, 5, b), (0, 2, c), (1, 1, d)];
+
+        assert_eq!(next_in_tab_order(nodes.clone(), None, false), Some(c));
+        assert_eq!(next_in_tab_order(nodes.clone(), None, true), Some(d));
+        assert_eq!(next_in_tab_order(nodes.clone(), Some(c), false), Some(b));
+        assert_eq!(next_in_tab_order(nodes.clone(), Some(b), false), Some(a));
+        assert_eq!(next_in_tab_order(nodes.clone(), Some(d), false), Some(c));
+        assert_eq!(next_in_tab_order(nodes.clone(), Some(c), true), Some(d));
+        assert_eq!(next_in_tab_order(Vec::new(), Some(c), true), None);
+    }
+
+    #[test]
+    fn directional_navigation() {
+        // a 3x3 grid of 10x10 nodes, 20 pixels apart
+        let grid: Vec<(Entity, Rect)> = (0..9)
+            .map(|index| {
+                let center = Vec2::new((index % 3) as f32, (index / 3) as f32) * 20.;
+                (
+                    Entity::from_raw(index),
+                    Rect::from_center_size(center, Vec2::splat(10.)),
+                )
+            })
+            .collect();
+        let navigate = |from: u32, direction| {
+            find_in_direction(grid[from as usize].1, grid.clone(), direction).map(Entity::index)
+        };
+
+        assert_eq!(navigate(4, NavigationDirection::Up), Some(1));
+        assert_eq!(navigate(4, NavigationDirection::Down), Some(7));
+        assert_eq!(navigate(4, NavigationDirection::Left), Some(3));
+        assert_eq!(navigate(4, NavigationDirection::Right), Some(5));
+        assert_eq!(navigate(0, NavigationDirection::Up), None);
+        assert_eq!(navigate(0, NavigationDirection::Left), None);
+        assert_eq!(navigate(2, NavigationDirection::Down), Some(5));
+    }
+}
@@ -3,11 +3,11 @@
 //! This module contains basic node bundles used to build UIs
 
 #[cfg(feature = "bevy_text")]
-use crate::widget::TextFlags;
+use crate::widget::{TextFlags, TextInput, TextInputStyle};
 use crate::{
     widget::{Button, UiImageSize},
-    BackgroundColor, BorderColor, BorderRadius, ContentSize, FocusPolicy, Interaction, Node, Style,
-    UiImage, UiMaterial, ZIndex,
+    BackgroundColor, BorderColor, BorderRadius, ContentSize, FocusPolicy, Focusable, Interaction,
+    Node, Style, UiImage, UiMaterial, ZIndex,
 };
 use bevy_asset::Handle;
 use bevy_color::Color;
@@ -269,6 +269,8 @@ pub struct ButtonBundle {
     pub node: Node,
     /// Marker component that signals this node is a button
     pub button: Button,
+    /// Lets the button get the [`UiFocus`](crate::UiFocus), to be pressed with the keyboard and gamepads
+    pub focusable: Focusable,
     /// Styles which control the layout (size and position) of the node and its children
     /// In some cases these styles also affect how the node drawn/painted.
     pub style: Style,
@@ -308,6 +310,7 @@ impl Default for ButtonBundle {
         Self {
             node: Default::default(),
             button: Default::default(),
+            focusable: Default::default(),
             style: Default::default(),
             interaction: Default::default(),
             focus_policy: FocusPolicy::Block,
@@ -325,6 +328,80 @@ impl Default for ButtonBundle {
     }
 }
 
+#[cfg(feature = "bevy_text")]
+/// A UI node that is a single line text field
+///
+/// The text, caret, selection and IME composition are child nodes spawned when the
+/// [`TextInput`] is added.
+#[derive(Bundle, Clone, Debug)]
+pub struct TextInputBundle {
+    /// Describes the logical size of the node
+    pub node: Node,
+    /// The value, caret and selection of the text field
+    pub text_input: TextInput,
+    /// Lets the text field get the [`UiFocus`](crate::UiFocus) to be edited
+    pub focusable: Focusable,
+    /// How the text, caret, selection and IME composition are drawn
+    pub text_input_style: TextInputStyle,
+    /// Styles which control the layout (size and position) of the node and its children
+    /// In some cases these styles also affect how the node drawn/painted.
+    pub style: Style,
+    /// Describes whether and how the text field has been interacted with by the input
+    pub interaction: Interaction,
+    /// Whether this node should block interaction with lower nodes
+    pub focus_policy: FocusPolicy,
+    /// The color of the Node's border
+    pub border_color: BorderColor,
+    /// The border radius of the node
+    pub border_radius: BorderRadius,
+    /// The background color that will fill the containing node
+    pub background_color: BackgroundColor,
+    /// The transform of the node
+    ///
+    /// This component is automatically managed by the UI layout system.
+    /// To alter the position of the `TextInputBundle`, use the properties of the [`Style`] component.
+    pub transform: Transform,
+    /// The global transform of the node
+    ///
+    /// This component is automatically updated by the [`TransformPropagate`](`bevy_transform::TransformSystem::TransformPropagate`) systems.
+    pub global_transform: GlobalTransform,
+    /// Describes the visibility properties of the node
+    pub visibility: Visibility,
+    /// Inherited visibility of an entity.
+    pub inherited_visibility: InheritedVisibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub view_visibility: ViewVisibility,
+    /// Indicates the depth at which the node should appear in the UI
+    pub z_index: ZIndex,
+}
+
+#[cfg(feature = "bevy_text")]
+impl Default for TextInputBundle {
+    fn default() -> Self {
+        Self {
+            node: Default::default(),
+            text_input: Default::default(),
+            focusable: Focusable {
+                directional_navigation: false,
+                ..Focusable::DEFAULT
+            },
+            text_input_style: Default::default(),
+            style: Default::default(),
+            interaction: Default::default(),
+            focus_policy: FocusPolicy::Block,
+            border_color: Default::default(),
+            border_radius: Default::default(),
+            background_color: Default::default(),
+            transform: Default::default(),
+            global_transform: Default::default(),
+            visibility: Default::default(),
+            inherited_visibility: Default::default(),
+            view_visibility: Default::default(),
+            z_index: Default::default(),
+        }
+    }
+}
+
 /// A UI node that is rendered using a [`UiMaterial`]
 ///
 /// Adding a `BackgroundColor` component to an entity with this bundle will ignore the custom
@@ -338,6 +415,8 @@ pub struct MaterialNodeBundle<M: UiMaterial> {
     pub style: Style,
     /// The [`UiMaterial`] used to render the node.
     pub material: Handle<M>,
+    /// The border radius of the node, passed to the shader of the material
+    pub border_radius: BorderRadius,
     /// Whether this node should block interaction with lower nodes
     pub focus_policy: FocusPolicy,
     /// The transform of the node
@@ -366,6 +445,7 @@ impl<M: UiMaterial> Default for MaterialNodeBundle<M> {
             node: Default::default(),
             style: Default::default(),
             material: Default::default(),
+            border_radius: Default::default(),
             focus_policy: Default::default(),
             transform: Default::default(),
             global_transform: Default::default(),
@@ -1,5 +1,6 @@
 mod pipeline;
 mod render_pass;
+mod ui_group;
 mod ui_material_pipeline;
 
 use bevy_color::{Alpha, ColorToComponents, LinearRgba};
@@ -17,13 +18,14 @@ use bevy_render::{
 use bevy_sprite::{SpriteAssetEvents, TextureAtlas};
 pub use pipeline::*;
 pub use render_pass::*;
+pub use ui_group::*;
 pub use ui_material_pipeline::*;
 
 use crate::graph::{NodeUi, SubGraphUi};
 use crate::{
-    texture_slice::ComputedTextureSlices, BackgroundColor, BorderColor, BorderRadius,
-    CalculatedClip, ContentSize, DefaultUiCamera, Node, Outline, Style, TargetCamera, UiImage,
-    UiScale, Val,
+    texture_slice::ComputedTextureSlices, BackgroundColor, BorderColor, BorderRadius, BorderStyle,
+    BoxShadow, CalculatedClip, ContentSize, DefaultUiCamera, Node, Outline, Style, TargetCamera,
+    UiImage, UiScale, Val,
 };
 
 use bevy_app::prelude::*;
@@ -44,7 +46,7 @@ use bevy_render::{
 };
 use bevy_sprite::TextureAtlasLayout;
 #[cfg(feature = "bevy_text")]
-use bevy_text::{PositionedGlyph, Text, TextLayoutInfo};
+use bevy_text::{PositionedGlyph, Text, TextGlyphEffects, TextLayoutInfo};
 use bevy_transform::components::GlobalTransform;
 use bevy_utils::HashMap;
 use bytemuck::{Pod, Zeroable};
@@ -66,6 +68,8 @@ pub const UI_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(130128470471
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
 pub enum RenderUiSystem {
+    ExtractGroups,
+    ExtractBoxShadows,
     ExtractBackgrounds,
     ExtractImages,
     ExtractBorders,
@@ -84,6 +88,7 @@ pub fn build_ui_render(app: &mut App) {
         .init_resource::<UiImageBindGroups>()
         .init_resource::<UiMeta>()
         .init_resource::<ExtractedUiNodes>()
+        .init_resource::<ExtractedUiGroups>()
         .allow_ambiguous_resource::<ExtractedUiNodes>()
         .init_resource::<DrawFunctions<TransparentUi>>()
         .init_resource::<ViewSortedRenderPhases<TransparentUi>>()
@@ -91,6 +96,8 @@ pub fn build_ui_render(app: &mut App) {
         .configure_sets(
             ExtractSchedule,
             (
+                RenderUiSystem::ExtractGroups,
+                RenderUiSystem::ExtractBoxShadows,
                 RenderUiSystem::ExtractBackgrounds,
                 RenderUiSystem::ExtractImages,
                 RenderUiSystem::ExtractBorders,
@@ -102,6 +109,10 @@ pub fn build_ui_render(app: &mut App) {
             ExtractSchedule,
             (
                 extract_default_ui_camera_view,
+                extract_ui_groups
+                    .in_set(RenderUiSystem::ExtractGroups)
+                    .after(extract_default_ui_camera_view),
+                extract_uinode_box_shadows.in_set(RenderUiSystem::ExtractBoxShadows),
                 extract_uinode_background_colors.in_set(RenderUiSystem::ExtractBackgrounds),
                 extract_uinode_images.in_set(RenderUiSystem::ExtractImages),
                 extract_uinode_borders.in_set(RenderUiSystem::ExtractBorders),
@@ -115,6 +126,7 @@ pub fn build_ui_render(app: &mut App) {
             (
                 queue_uinodes.in_set(RenderSet::Queue),
                 sort_phase_system::<TransparentUi>.in_set(RenderSet::PhaseSort),
+                prepare_ui_groups.in_set(RenderSet::PrepareResources),
                 prepare_uinodes.in_set(RenderSet::PrepareBindGroups),
             ),
         );
@@ -154,6 +166,22 @@ fn get_ui_graph(render_app: &mut SubApp) -> RenderGraph {
 pub enum NodeType {
     Rect,
     Border,
+    /// A border drawn with dashes, of the given lengths in logical pixels.
+    DashedBorder {
+        dash_length: f32,
+        gap_length: f32,
+    },
+    /// A shadow whose edges fade out over the blur radius, in logical pixels.
+    /// The rect of the node includes the blur radius on each side.
+    BoxShadow {
+        blur_radius: f32,
+    },
+    /// The texture of a group of nodes, see [`ExtractedUiGroup`]. Its colors are premultiplied by
+    /// their alpha.
+    Composite,
+    /// A glyph of text drawn from a multi-channel signed distance field, see
+    /// [`TextRendering::DistanceField`](bevy_text::TextRendering::DistanceField).
+    DistanceField,
 }
 
 pub struct ExtractedUiNode {
@@ -186,6 +214,7 @@ pub struct ExtractedUiNodes {
 
 pub fn extract_uinode_background_colors(
     mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    extracted_groups: Res<ExtractedUiGroups>,
     camera_query: Extract<Query<(Entity, &Camera)>>,
     default_ui_camera: Extract<DefaultUiCamera>,
     ui_scale: Extract<Res<UiScale>>,
@@ -280,7 +309,7 @@ pub fn extract_uinode_background_colors(
                 atlas_size: None,
                 flip_x: false,
                 flip_y: false,
-                camera_entity,
+                camera_entity: extracted_groups.view_entity(entity, camera_entity),
                 border,
                 border_radius,
                 node_type: NodeType::Rect,
@@ -293,12 +322,14 @@ pub fn extract_uinode_background_colors(
 pub fn extract_uinode_images(
     mut commands: Commands,
     mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    extracted_groups: Res<ExtractedUiGroups>,
     camera_query: Extract<Query<(Entity, &Camera)>>,
     texture_atlases: Extract<Res<Assets<TextureAtlasLayout>>>,
     ui_scale: Extract<Res<UiScale>>,
     default_ui_camera: Extract<DefaultUiCamera>,
     uinode_query: Extract<
         Query<(
+            Entity,
             &Node,
             &GlobalTransform,
             &ViewVisibility,
@@ -315,6 +346,7 @@ pub fn extract_uinode_images(
     node_query: Extract<Query<&Node>>,
 ) {
     for (
+        entity,
         uinode,
         transform,
         view_visibility,
@@ -341,7 +373,13 @@ pub fn extract_uinode_images(
         if let Some(slices) = slices {
             extracted_uinodes.uinodes.extend(
                 slices
-                    .extract_ui_nodes(transform, uinode, image, clip, camera_entity)
+                    .extract_ui_nodes(
+                        transform,
+                        uinode,
+                        image,
+                        clip,
+                        extracted_groups.view_entity(entity, camera_entity),
+                    )
                     .map(|e| (commands.spawn_empty().id(), e)),
             );
             continue;
@@ -419,7 +457,7 @@ pub fn extract_uinode_images(
                 atlas_size,
                 flip_x: image.flip_x,
                 flip_y: image.flip_y,
-                camera_entity,
+                camera_entity: extracted_groups.view_entity(entity, camera_entity),
                 border,
                 border_radius,
                 node_type: NodeType::Rect,
@@ -489,15 +527,18 @@ fn clamp_radius(
     ]
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn extract_uinode_borders(
     mut commands: Commands,
     mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    extracted_groups: Res<ExtractedUiGroups>,
     camera_query: Extract<Query<(Entity, &Camera)>>,
     default_ui_camera: Extract<DefaultUiCamera>,
     ui_scale: Extract<Res<UiScale>>,
     uinode_query: Extract<
         Query<
             (
+                Entity,
                 &Node,
                 &GlobalTransform,
                 &ViewVisibility,
@@ -507,6 +548,7 @@ pub fn extract_uinode_borders(
                 &Style,
                 &BorderColor,
                 &BorderRadius,
+                Option<&BorderStyle>,
             ),
             Without<ContentSize>,
         >,
@@ -516,6 +558,7 @@ pub fn extract_uinode_borders(
     let image = AssetId::<Image>::default();
 
     for (
+        entity,
         node,
         global_transform,
         view_visibility,
@@ -525,6 +568,7 @@ pub fn extract_uinode_borders(
         style,
         border_color,
         border_radius,
+        border_style,
     ) in &uinode_query
     {
         let Some(camera_entity) = camera.map(TargetCamera::entity).or(default_ui_camera.get())
@@ -581,6 +625,7 @@ pub fn extract_uinode_borders(
 
         let border_radius = clamp_radius(border_radius, node.size(), border.into());
         let transform = global_transform.compute_matrix();
+        let node_type = border_node_type(border_style.copied().unwrap_or_default(), border);
 
         extracted_uinodes.uinodes.insert(
             commands.spawn_empty().id(),
@@ -598,32 +643,69 @@ pub fn extract_uinode_borders(
                 clip: clip.map(|clip| clip.clip),
                 flip_x: false,
                 flip_y: false,
-                camera_entity,
+                camera_entity: extracted_groups.view_entity(entity, camera_entity),
                 border_radius,
                 border,
-                node_type: NodeType::Border,
+                node_type,
             },
         );
     }
 }
 
+/// The [`NodeType`] drawing a border of the given style.
+fn border_node_type(border_style: BorderStyle, border: [f32; 4]) -> NodeType {
+    match border_style {
+        BorderStyle::Dashed {
+            dash_length,
+            gap_length,
+        } if dash_length > 0. => NodeType::DashedBorder {
+            dash_length,
+            gap_length: gap_length.max(0.),
+        },
+        // dashes without length are drawn as a solid border
+        BorderStyle::Solid | BorderStyle::Dashed { .. } => NodeType::Border,
+        BorderStyle::Dotted => {
+            let thickness = border.into_iter().fold(0., f32::max);
+            NodeType::DashedBorder {
+                dash_length: thickness,
+                gap_length: thickness,
+            }
+        }
+    }
+}
+
 pub fn extract_uinode_outlines(
     mut commands: Commands,
     mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    extracted_groups: Res<ExtractedUiGroups>,
+    camera_query: Extract<Query<(Entity, &Camera)>>,
     default_ui_camera: Extract<DefaultUiCamera>,
+    ui_scale: Extract<Res<UiScale>>,
     uinode_query: Extract<
         Query<(
+            Entity,
             &Node,
             &GlobalTransform,
             &ViewVisibility,
             Option<&CalculatedClip>,
             Option<&TargetCamera>,
             &Outline,
+            Option<&BorderRadius>,
         )>,
     >,
 ) {
     let image = AssetId::<Image>::default();
-    for (node, global_transform, view_visibility, maybe_clip, camera, outline) in &uinode_query {
+    for (
+        entity,
+        node,
+        global_transform,
+        view_visibility,
+        maybe_clip,
+        camera,
+        outline,
+        border_radius,
+    ) in &uinode_query
+    {
         let Some(camera_entity) = camera.map(TargetCamera::entity).or(default_ui_camera.get())
         else {
             continue;
@@ -637,68 +719,175 @@ pub fn extract_uinode_outlines(
             continue;
         }
 
-        // Calculate the outline rects.
-        let inner_rect = Rect::from_center_size(Vec2::ZERO, node.size() + 2. * node.outline_offset);
-        let outer_rect = inner_rect.inflate(node.outline_width());
-        let outline_edges = [
-            // Left edge
-            Rect::new(
-                outer_rect.min.x,
-                outer_rect.min.y,
-                inner_rect.min.x,
-                outer_rect.max.y,
-            ),
-            // Right edge
-            Rect::new(
-                inner_rect.max.x,
-                outer_rect.min.y,
-                outer_rect.max.x,
-                outer_rect.max.y,
-            ),
-            // Top edge
-            Rect::new(
-                inner_rect.min.x,
-                outer_rect.min.y,
-                inner_rect.max.x,
-                inner_rect.min.y,
-            ),
-            // Bottom edge
-            Rect::new(
-                inner_rect.min.x,
-                inner_rect.max.y,
-                inner_rect.max.x,
-                outer_rect.max.y,
-            ),
-        ];
+        // The outline is drawn as a border around the node and the outline offset, following the
+        // rounded corners of the node.
+        let outline_extent = node.outline_offset + node.outline_width();
+        let outline_size = node.size() + 2. * outline_extent;
+        if outline_size.x <= 0. || outline_size.y <= 0. {
+            continue;
+        }
 
-        let world_from_local = global_transform.compute_matrix();
-        for edge in outline_edges {
-            if edge.min.x < edge.max.x && edge.min.y < edge.max.y {
-                extracted_uinodes.uinodes.insert(
-                    commands.spawn_empty().id(),
-                    ExtractedUiNode {
-                        stack_index: node.stack_index,
-                        // This translates the uinode's transform to the center of the current border rectangle
-                        transform: world_from_local
-                            * Mat4::from_translation(edge.center().extend(0.)),
-                        color: outline.color.into(),
-                        rect: Rect {
-                            max: edge.size(),
-                            ..Default::default()
-                        },
-                        image,
-                        atlas_size: None,
-                        clip: maybe_clip.map(|clip| clip.clip),
-                        flip_x: false,
-                        flip_y: false,
-                        camera_entity,
-                        border: [0.; 4],
-                        border_radius: [0.; 4],
-                        node_type: NodeType::Rect,
-                    },
-                );
-            }
+        let border_radius = border_radius
+            .map(|border_radius| {
+                let ui_logical_viewport_size = camera_query
+                    .get(camera_entity)
+                    .ok()
+                    .and_then(|(_, c)| c.logical_viewport_size())
+                    .unwrap_or(Vec2::ZERO)
+                    / ui_scale.0;
+                resolve_border_radius(
+                    border_radius,
+                    node.size(),
+                    ui_logical_viewport_size,
+                    ui_scale.0,
+                )
+                // square corners stay square
+                .map(|radius| {
+                    if radius > 0. {
+                        radius + outline_extent
+                    } else {
+                        0.
+                    }
+                })
+            })
+            .unwrap_or([0.; 4]);
+        let border = [node.outline_width(); 4];
+        let border_radius = clamp_radius(border_radius, outline_size, border.into());
+
+        extracted_uinodes.uinodes.insert(
+            commands.spawn_empty().id(),
+            ExtractedUiNode {
+                stack_index: node.stack_index,
+                transform: global_transform.compute_matrix(),
+                color: outline.color.into(),
+                rect: Rect {
+                    max: outline_size,
+                    ..Default::default()
+                },
+                image,
+                atlas_size: None,
+                clip: maybe_clip.map(|clip| clip.clip),
+                flip_x: false,
+                flip_y: false,
+                camera_entity: extracted_groups.view_entity(entity, camera_entity),
+                border,
+                border_radius,
+                node_type: NodeType::Border,
+            },
+        );
+    }
+}
+
+pub fn extract_uinode_box_shadows(
+    mut commands: Commands,
+    mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    extracted_groups: Res<ExtractedUiGroups>,
+    camera_query: Extract<Query<(Entity, &Camera)>>,
+    default_ui_camera: Extract<DefaultUiCamera>,
+    ui_scale: Extract<Res<UiScale>>,
+    uinode_query: Extract<
+        Query<(
+            Entity,
+            &Node,
+            &GlobalTransform,
+            &ViewVisibility,
+            Option<&CalculatedClip>,
+            Option<&TargetCamera>,
+            &BoxShadow,
+            Option<&BorderRadius>,
+        )>,
+    >,
+) {
+    let image = AssetId::<Image>::default();
+    for (
+        entity,
+        node,
+        global_transform,
+        view_visibility,
+        clip,
+        camera,
+        box_shadow,
+        border_radius,
+    ) in &uinode_query
+    {
+        let Some(camera_entity) = camera.map(TargetCamera::entity).or(default_ui_camera.get())
+        else {
+            continue;
+        };
+
+        // Skip invisible shadows
+        if !view_visibility.get()
+            || box_shadow.color.is_fully_transparent()
+            || node.size().x <= 0.
+            || node.size().y <= 0.
+        {
+            continue;
         }
+
+        let ui_logical_viewport_size = camera_query
+            .get(camera_entity)
+            .ok()
+            .and_then(|(_, c)| c.logical_viewport_size())
+            .unwrap_or(Vec2::ZERO)
+            // The logical window resolution returned by `Window` only takes into account the window scale factor and not `UiScale`,
+            // so we have to divide by `UiScale` to get the size of the UI viewport.
+            / ui_scale.0;
+        let resolve =
+            |value: Val, base: f32| value.resolve(base, ui_logical_viewport_size).unwrap_or(0.);
+        let offset = Vec2::new(
+            resolve(box_shadow.x_offset, node.size().x),
+            resolve(box_shadow.y_offset, node.size().y),
+        );
+        let spread = resolve(box_shadow.spread_radius, node.size().min_element());
+        let blur_radius = resolve(box_shadow.blur_radius, node.size().min_element()).max(0.);
+
+        let shadow_size = node.size() + 2. * spread;
+        if shadow_size.x <= 0. || shadow_size.y <= 0. {
+            continue;
+        }
+
+        // The corners grow and shrink with the spread, like in CSS.
+        let max_radius = 0.5 * shadow_size.min_element();
+        let border_radius = border_radius
+            .map(|border_radius| {
+                resolve_border_radius(
+                    border_radius,
+                    node.size(),
+                    ui_logical_viewport_size,
+                    ui_scale.0,
+                )
+                .map(|radius| {
+                    if radius > 0. {
+                        (radius + spread).clamp(0., max_radius)
+                    } else {
+                        0.
+                    }
+                })
+            })
+            .unwrap_or([0.; 4]);
+
+        extracted_uinodes.uinodes.insert(
+            commands.spawn_empty().id(),
+            ExtractedUiNode {
+                stack_index: node.stack_index,
+                transform: global_transform.compute_matrix()
+                    * Mat4::from_translation(offset.extend(0.)),
+                color: box_shadow.color.into(),
+                rect: Rect {
+                    max: shadow_size + 2. * blur_radius,
+                    ..Default::default()
+                },
+                image,
+                atlas_size: None,
+                clip: clip.map(|clip| clip.clip),
+                flip_x: false,
+                flip_y: false,
+                camera_entity: extracted_groups.view_entity(entity, camera_entity),
+                border: [0.; 4],
+                border_radius,
+                node_type: NodeType::BoxShadow { blur_radius },
+            },
+        );
     }
 }
 
@@ -716,6 +905,44 @@ const UI_CAMERA_TRANSFORM_OFFSET: f32 = -0.1;
 #[derive(Component)]
 pub struct DefaultCameraView(pub Entity);
 
+/// The view of the UI of `camera`, in logical pixels scaled by `ui_scale` with the origin in the
+/// top left of its viewport.
+pub(crate) fn ui_camera_view(camera: &Camera, ui_scale: f32) -> Option<ExtractedView> {
+    let logical_size = camera.logical_viewport_size()?;
+    let URect {
+        min: physical_origin,
+        ..
+    } = camera.physical_viewport_rect()?;
+    let physical_size = camera.physical_viewport_size()?;
+    let scale = ui_scale.recip();
+    // use a projection matrix with the origin in the top left instead of the bottom left that comes with OrthographicProjection
+    let projection_matrix = Mat4::orthographic_rh(
+        0.0,
+        logical_size.x * scale,
+        logical_size.y * scale,
+        0.0,
+        0.0,
+        UI_CAMERA_FAR,
+    );
+    Some(ExtractedView {
+        clip_from_view: projection_matrix,
+        world_from_view: GlobalTransform::from_xyz(
+            0.0,
+            0.0,
+            UI_CAMERA_FAR + UI_CAMERA_TRANSFORM_OFFSET,
+        ),
+        clip_from_world: None,
+        hdr: camera.hdr,
+        viewport: UVec4::new(
+            physical_origin.x,
+            physical_origin.y,
+            physical_size.x,
+            physical_size.y,
+        ),
+        color_grading: Default::default(),
+    })
+}
+
 /// Extracts all UI elements associated with a camera into the render world.
 pub fn extract_default_ui_camera_view(
     mut commands: Commands,
@@ -726,53 +953,14 @@ pub fn extract_default_ui_camera_view(
 ) {
     live_entities.clear();
 
-    let scale = ui_scale.0.recip();
     for (entity, camera) in &query {
         // ignore inactive cameras
         if !camera.is_active {
             continue;
         }
 
-        if let (
-            Some(logical_size),
-            Some(URect {
-                min: physical_origin,
-                ..
-            }),
-            Some(physical_size),
-        ) = (
-            camera.logical_viewport_size(),
-            camera.physical_viewport_rect(),
-            camera.physical_viewport_size(),
-        ) {
-            // use a projection matrix with the origin in the top left instead of the bottom left that comes with OrthographicProjection
-            let projection_matrix = Mat4::orthographic_rh(
-                0.0,
-                logical_size.x * scale,
-                logical_size.y * scale,
-                0.0,
-                0.0,
-                UI_CAMERA_FAR,
-            );
-            let default_camera_view = commands
-                .spawn(ExtractedView {
-                    clip_from_view: projection_matrix,
-                    world_from_view: GlobalTransform::from_xyz(
-                        0.0,
-                        0.0,
-                        UI_CAMERA_FAR + UI_CAMERA_TRANSFORM_OFFSET,
-                    ),
-                    clip_from_world: None,
-                    hdr: camera.hdr,
-                    viewport: UVec4::new(
-                        physical_origin.x,
-                        physical_origin.y,
-                        physical_size.x,
-                        physical_size.y,
-                    ),
-                    color_grading: Default::default(),
-                })
-                .id();
+        if let Some(view) = ui_camera_view(camera, ui_scale.0) {
+            let default_camera_view = commands.spawn(view).id();
             commands
                 .get_or_spawn(entity)
                 .insert(DefaultCameraView(default_camera_view));
@@ -786,15 +974,18 @@ pub fn extract_default_ui_camera_view(
 }
 
 #[cfg(feature = "bevy_text")]
+#[allow(clippy::too_many_arguments)]
 pub fn extract_uinode_text(
     mut commands: Commands,
     mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    extracted_groups: Res<ExtractedUiGroups>,
     camera_query: Extract<Query<(Entity, &Camera)>>,
     default_ui_camera: Extract<DefaultUiCamera>,
     texture_atlases: Extract<Res<Assets<TextureAtlasLayout>>>,
     ui_scale: Extract<Res<UiScale>>,
     uinode_query: Extract<
         Query<(
+            Entity,
             &Node,
             &GlobalTransform,
             &ViewVisibility,
@@ -802,11 +993,21 @@ pub fn extract_uinode_text(
             Option<&TargetCamera>,
             &Text,
             &TextLayoutInfo,
+            Option<&TextGlyphEffects>,
         )>,
     >,
 ) {
-    for (uinode, global_transform, view_visibility, clip, camera, text, text_layout_info) in
-        &uinode_query
+    for (
+        entity,
+        uinode,
+        global_transform,
+        view_visibility,
+        clip,
+        camera,
+        text,
+        text_layout_info,
+        glyph_effects,
+    ) in &uinode_query
     {
         let Some(camera_entity) = camera.map(TargetCamera::entity).or(default_ui_camera.get())
         else {
@@ -842,44 +1043,115 @@ pub fn extract_uinode_text(
         transform.translation = transform.translation.round();
         transform.translation *= inverse_scale_factor;
 
+        // A rectangle of a single color drawn with the text, such as a highlight or underline.
+        let text_rect = |rect: Rect, color: LinearRgba| ExtractedUiNode {
+            stack_index: uinode.stack_index,
+            transform: transform
+                * Mat4::from_translation(rect.center().extend(0.) * inverse_scale_factor),
+            color,
+            rect: Rect {
+                min: Vec2::ZERO,
+                max: rect.size() * inverse_scale_factor,
+            },
+            image: AssetId::default(),
+            atlas_size: None,
+            clip: clip.map(|clip| clip.clip),
+            flip_x: false,
+            flip_y: false,
+            camera_entity: extracted_groups.view_entity(entity, camera_entity),
+            border: [0.; 4],
+            border_radius: [0.; 4],
+            node_type: NodeType::Rect,
+        };
+
+        // Highlights are drawn behind the glyphs, and lines over them.
+        for span in &text_layout_info.spans {
+            let style = &text.sections[span.section_index].style;
+            if !style.background_color.is_fully_transparent() {
+                extracted_uinodes.uinodes.insert(
+                    commands.spawn_empty().id(),
+                    text_rect(span.rect, style.background_color.into()),
+                );
+            }
+        }
+
         let mut color = LinearRgba::WHITE;
         let mut current_section = usize::MAX;
-        for PositionedGlyph {
-            position,
-            atlas_info,
-            section_index,
-            ..
-        } in &text_layout_info.glyphs
+        for (
+            glyph_index,
+            PositionedGlyph {
+                position,
+                size,
+                atlas_info,
+                section_index,
+                is_color,
+                distance_field,
+                ..
+            },
+        ) in text_layout_info.glyphs.iter().enumerate()
         {
             if *section_index != current_section {
                 color = LinearRgba::from(text.sections[*section_index].style.color);
                 current_section = *section_index;
             }
+            // Color glyphs such as emoji only take the alpha of the text color.
+            let glyph_color = if *is_color {
+                LinearRgba::WHITE.with_alpha(color.alpha)
+            } else {
+                color
+            };
+            let glyph_effect = glyph_effects
+                .map(|glyph_effects| glyph_effects.get(glyph_index))
+                .unwrap_or_default();
             let atlas = texture_atlases.get(&atlas_info.texture_atlas).unwrap();
 
             let mut rect = atlas.textures[atlas_info.glyph_index].as_rect();
-            rect.min *= inverse_scale_factor;
-            rect.max *= inverse_scale_factor;
+            // Scales the glyph from its size in the atlas to its drawn size, which differ for
+            // distance field glyphs, and to logical pixels.
+            let scale = *size / rect.size() * inverse_scale_factor;
+            rect.min *= scale;
+            rect.max *= scale;
             extracted_uinodes.uinodes.insert(
                 commands.spawn_empty().id(),
                 ExtractedUiNode {
                     stack_index: uinode.stack_index,
                     transform: transform
-                        * Mat4::from_translation(position.extend(0.) * inverse_scale_factor),
-                    color,
+                        * Mat4::from_translation(
+                            (*position + glyph_effect.offset).extend(0.) * inverse_scale_factor,
+                        ),
+                    color: glyph_effect.tint_color(glyph_color),
                     rect,
                     image: atlas_info.texture.id(),
-                    atlas_size: Some(atlas.size.as_vec2() * inverse_scale_factor),
+                    atlas_size: Some(atlas.size.as_vec2() * scale),
                     clip: clip.map(|clip| clip.clip),
                     flip_x: false,
                     flip_y: false,
-                    camera_entity,
+                    camera_entity: extracted_groups.view_entity(entity, camera_entity),
                     border: [0.; 4],
                     border_radius: [0.; 4],
-                    node_type: NodeType::Rect,
+                    node_type: if *distance_field {
+                        NodeType::DistanceField
+                    } else {
+                        NodeType::Rect
+                    },
                 },
             );
         }
+
+        for span in &text_layout_info.spans {
+            let style = &text.sections[span.section_index].style;
+            for (draw, rect) in [
+                (style.underline, span.underline),
+                (style.strikethrough, span.strikethrough),
+            ] {
+                if draw {
+                    extracted_uinodes.uinodes.insert(
+                        commands.spawn_empty().id(),
+                        text_rect(rect, style.color.into()),
+                    );
+                }
+            }
+        }
     }
 }
 
@@ -900,6 +1172,9 @@ struct UiVertex {
     pub border: [f32; 4],
     /// Size of the UI node.
     pub size: [f32; 2],
+    /// Parameters of the node type: the dash and gap lengths of dashed borders, or the blur radius
+    /// of box shadows.
+    pub effect: [f32; 2],
 }
 
 #[derive(Resource)]
@@ -942,6 +1217,10 @@ pub mod shader_flags {
     /// Ordering: top left, top right, bottom right, bottom left.
     pub const CORNERS: [u32; 4] = [0, 2, 2 | 4, 4];
     pub const BORDER: u32 = 8;
+    pub const DASHED: u32 = 16;
+    pub const BOX_SHADOW: u32 = 32;
+    pub const COMPOSITE: u32 = 64;
+    pub const DISTANCE_FIELD: u32 = 128;
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -1208,9 +1487,32 @@ pub fn prepare_uinodes(
                     };
 
                     let color = extracted_uinode.color.to_f32_array();
-                    if extracted_uinode.node_type == NodeType::Border {
-                        flags |= shader_flags::BORDER;
-                    }
+                    let effect = match extracted_uinode.node_type {
+                        NodeType::Rect => [0.; 2],
+                        NodeType::Border => {
+                            flags |= shader_flags::BORDER;
+                            [0.; 2]
+                        }
+                        NodeType::DashedBorder {
+                            dash_length,
+                            gap_length,
+                        } => {
+                            flags |= shader_flags::BORDER | shader_flags::DASHED;
+                            [dash_length, gap_length]
+                        }
+                        NodeType::BoxShadow { blur_radius } => {
+                            flags |= shader_flags::BOX_SHADOW;
+                            [blur_radius, 0.]
+                        }
+                        NodeType::Composite => {
+                            flags |= shader_flags::COMPOSITE;
+                            [0.; 2]
+                        }
+                        NodeType::DistanceField => {
+                            flags |= shader_flags::DISTANCE_FIELD;
+                            [0.; 2]
+                        }
+                    };
 
                     for i in 0..4 {
                         ui_meta.vertices.push(UiVertex {
@@ -1221,6 +1523,7 @@ pub fn prepare_uinodes(
                             radius: extracted_uinode.border_radius,
                             border: extracted_uinode.border,
                             size: rect_size.xy().into(),
+                            effect,
                         });
                     }
 
@@ -71,6 +71,8 @@ impl SpecializedRenderPipeline for UiPipeline {
                 VertexFormat::Float32x4,
                 // border size
                 VertexFormat::Float32x2,
+                // effect
+                VertexFormat::Float32x2,
             ],
         );
         let shader_defs = Vec::new();
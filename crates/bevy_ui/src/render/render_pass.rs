@@ -1,6 +1,6 @@
 use std::ops::Range;
 
-use super::{UiBatch, UiImageBindGroups, UiMeta};
+use super::{ExtractedUiGroups, UiBatch, UiImageBindGroups, UiMeta};
 use crate::DefaultCameraView;
 use bevy_ecs::{
     prelude::*,
@@ -11,7 +11,10 @@ use bevy_render::{
     camera::ExtractedCamera,
     render_graph::*,
     render_phase::*,
-    render_resource::{CachedRenderPipelineId, RenderPassDescriptor},
+    render_resource::{
+        CachedRenderPipelineId, LoadOp, Operations, RenderPassColorAttachment,
+        RenderPassDescriptor, StoreOp,
+    },
     renderer::*,
     view::*,
 };
@@ -61,6 +64,40 @@ impl Node for UiPassNode {
             return Ok(());
         }
 
+        // render the groups of nodes to their textures, before the nodes drawing these textures
+        if let Some(extracted_groups) = world.get_resource::<ExtractedUiGroups>() {
+            let mut groups = extracted_groups
+                .groups
+                .iter()
+                .filter(|group| group.camera_entity == input_view_entity)
+                .collect::<Vec<_>>();
+            groups.sort_by_key(|group| std::cmp::Reverse(group.depth));
+            for group in groups {
+                let (Some(phase), Some(texture)) = (
+                    transparent_render_phases.get(&group.view),
+                    group.texture.as_ref(),
+                ) else {
+                    continue;
+                };
+                let mut render_pass =
+                    render_context.begin_tracked_render_pass(RenderPassDescriptor {
+                        label: Some("ui_group_pass"),
+                        color_attachments: &[Some(RenderPassColorAttachment {
+                            view: &texture.default_view,
+                            resolve_target: None,
+                            ops: Operations {
+                                load: LoadOp::Clear(Default::default()),
+                                store: StoreOp::Store,
+                            },
+                        })],
+                        depth_stencil_attachment: None,
+                        timestamp_writes: None,
+                        occlusion_query_set: None,
+                    });
+                phase.render(&mut render_pass, world, group.view);
+            }
+        }
+
         // use the "default" view entity if it is defined
         let view_entity = if let Ok(default_view) = self
             .default_camera_view_query
@@ -4,6 +4,13 @@ const TEXTURED = 1u;
 const RIGHT_VERTEX = 2u;
 const BOTTOM_VERTEX = 4u;
 const BORDER: u32 = 8u;
+const DASHED: u32 = 16u;
+const BOX_SHADOW: u32 = 32u;
+const COMPOSITE: u32 = 64u;
+const DISTANCE_FIELD: u32 = 128u;
+
+// The range of the distance fields of text glyphs in texels, see `bevy_text::DISTANCE_FIELD_RANGE`.
+const DISTANCE_FIELD_RANGE: f32 = 4.0;
 
 fn enabled(flags: u32, mask: u32) -> bool {
     return (flags & mask) != 0u;
@@ -19,9 +26,10 @@ struct VertexOutput {
     @location(3) @interpolate(flat) flags: u32,
     @location(4) @interpolate(flat) radius: vec4<f32>,    
     @location(5) @interpolate(flat) border: vec4<f32>,    
+    @location(6) @interpolate(flat) effect: vec2<f32>,
 
     // Position relative to the center of the rectangle.
-    @location(6) point: vec2<f32>,
+    @location(7) point: vec2<f32>,
     @builtin(position) position: vec4<f32>,
 };
 
@@ -38,6 +46,10 @@ fn vertex(
     // x: left, y: top, z: right, w: bottom.
     @location(5) border: vec4<f32>,
     @location(6) size: vec2<f32>,
+
+    // Dashed borders: x: dash length, y: gap length.
+    // Box shadows: x: blur radius.
+    @location(7) effect: vec2<f32>,
 ) -> VertexOutput {
     var out: VertexOutput;
     out.uv = vertex_uv;
@@ -47,6 +59,7 @@ fn vertex(
     out.radius = radius;
     out.size = size;
     out.border = border;
+    out.effect = effect;
     var point = 0.49999 * size;
     if (flags & RIGHT_VERTEX) == 0u {
         point.x *= -1.;
@@ -154,12 +167,42 @@ fn draw(in: VertexOutput, texture_color: vec4<f32>) -> vec4<f32> {
     // This select statement ensures we only perform anti-aliasing where a non-zero width border 
     // is present, otherwise an outline about the external boundary would be drawn even without 
     // a border.
-    let t = select(1.0 - step(0.0, border_distance), antialias(border_distance), external_distance < internal_distance);
+    var t = select(1.0 - step(0.0, border_distance), antialias(border_distance), external_distance < internal_distance);
+
+    if enabled(in.flags, DASHED) {
+        t *= dash_coverage(in);
+    }
 
     // Blend mode ALPHA_BLENDING is used for UI elements, so we don't premultiply alpha here.
     return vec4(color.rgb, saturate(color.a * t));
 }
 
+// The coverage of the dashes of a dashed border at the point.
+//
+// The dashes are laid out along the closest edge of the rectangle, starting from the top left 
+// corner.
+fn dash_coverage(in: VertexOutput) -> f32 {
+    let dash_length = in.effect.x;
+    let period = in.effect.x + in.effect.y;
+    let half_size = 0.5 * in.size;
+    let edge_distance = half_size - abs(in.point);
+    // Distance along the closest of the vertical or horizontal edges.
+    let along = select(in.point.x + half_size.x, in.point.y + half_size.y, edge_distance.x < edge_distance.y);
+    let phase = along % period;
+    // Signed distance from the closest end of the dash, negative inside the dash.
+    return antialias(max(phase - dash_length, -phase));
+}
+
+fn draw_box_shadow(in: VertexOutput) -> vec4<f32> {
+    let blur_radius = in.effect.x;
+    // The quad is larger than the shadow by the blur radius on each side.
+    let shadow_size = in.size - 2.0 * blur_radius;
+    let distance = sd_rounded_box(in.point, shadow_size, in.radius);
+    // Approximate a gaussian blur by fading out over the blur radius on both sides of the edge.
+    let t = select(antialias(distance), 1.0 - smoothstep(-blur_radius, blur_radius, distance), 0.0 < blur_radius);
+    return vec4(in.color.rgb, saturate(in.color.a * t));
+}
+
 fn draw_background(in: VertexOutput, texture_color: vec4<f32>) -> vec4<f32> {
     let color = select(in.color, in.color * texture_color, enabled(in.flags, TEXTURED));
 
@@ -169,11 +212,34 @@ fn draw_background(in: VertexOutput, texture_color: vec4<f32>) -> vec4<f32> {
     return vec4(color.rgb, saturate(color.a * t));
 }
 
+// The coverage of a multi-channel signed distance field, antialiased over a pixel on screen
+// whatever the scale it's drawn at. `uv_width` is the change of the UVs over a pixel.
+fn distance_field_coverage(distances: vec4<f32>, uv_width: vec2<f32>) -> f32 {
+    let texels_per_pixel = uv_width * vec2<f32>(textureDimensions(sprite_texture));
+    let screen_range = max(0.5 * dot(vec2(DISTANCE_FIELD_RANGE) / texels_per_pixel, vec2(1.0)), 1.0);
+    let median = max(min(distances.r, distances.g), min(max(distances.r, distances.g), distances.b));
+    return saturate(screen_range * (median - 0.5) + 0.5);
+}
+
 @fragment
 fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
-    let texture_color = textureSample(sprite_texture, sprite_sampler, in.uv);
+    var texture_color = textureSample(sprite_texture, sprite_sampler, in.uv);
+    // Derivatives must be computed in uniform control flow.
+    let uv_width = fwidth(in.uv);
+
+    if enabled(in.flags, DISTANCE_FIELD) {
+        texture_color = vec4(1.0, 1.0, 1.0, distance_field_coverage(texture_color, uv_width));
+    }
+
+    if enabled(in.flags, COMPOSITE) {
+        // The texture of a group of nodes was blended over transparency, which multiplied its
+        // colors by their alpha.
+        texture_color = vec4(texture_color.rgb / max(texture_color.a, 1e-5), texture_color.a);
+    }
 
-    if enabled(in.flags, BORDER) {
+    if enabled(in.flags, BOX_SHADOW) {
+        return draw_box_shadow(in);
+    } else if enabled(in.flags, BORDER) {
         return draw(in, texture_color);    
     } else {
         return draw_background(in, texture_color);
@@ -0,0 +1,314 @@
+use super::{
+    resolve_border_radius, ui_camera_view, ExtractedUiNode, ExtractedUiNodes, NodeType,
+    TransparentUi, UiImageBindGroups,
+};
+use crate::{
+    BorderRadius, CalculatedClip, DefaultUiCamera, Display, Node, Opacity, Style, TargetCamera,
+    UiScale,
+};
+use bevy_asset::{AssetId, Handle};
+use bevy_color::{Alpha, LinearRgba};
+use bevy_ecs::{entity::EntityHashMap, prelude::*};
+use bevy_hierarchy::{Children, Parent};
+use bevy_math::{Mat4, Rect, UVec2, Vec2, Vec4Swizzles};
+use bevy_render::{
+    camera::Camera,
+    render_asset::RenderAssets,
+    render_phase::ViewSortedRenderPhases,
+    render_resource::{
+        Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+    },
+    renderer::RenderDevice,
+    texture::{BevyDefault, CachedTexture, DefaultImageSampler, GpuImage, Image, TextureCache},
+    view::ViewTarget,
+    Extract,
+};
+use bevy_transform::components::GlobalTransform;
+
+/// The ids of the textures of the groups start from this value.
+const UI_GROUP_IMAGE_ID: u128 = 183406982354104728130546937628406123872;
+
+fn group_image(index: usize) -> AssetId<Image> {
+    Handle::<Image>::weak_from_u128(UI_GROUP_IMAGE_ID + index as u128).id()
+}
+
+/// Nodes rendered to an intermediate texture, which is then drawn like an image by the group
+/// containing it, or by the camera.
+///
+/// A group is made of a node with an [`Opacity`] less than `1.` and its descendants, or of the
+/// descendants of a node clipping them with rounded corners.
+pub struct ExtractedUiGroup {
+    /// The view the nodes of the group are queued to.
+    pub view: Entity,
+    /// The camera rendering the group.
+    pub camera_entity: Entity,
+    /// The number of groups containing this group. Nested groups are rendered first.
+    pub depth: u32,
+    /// The id the texture of the group is bound with.
+    pub image: AssetId<Image>,
+    /// The size of the texture, which is the physical size of the viewport of the camera.
+    pub size: UVec2,
+    pub hdr: bool,
+    /// The texture of the group, available once prepared.
+    pub texture: Option<CachedTexture>,
+}
+
+#[derive(Resource, Default)]
+pub struct ExtractedUiGroups {
+    pub groups: Vec<ExtractedUiGroup>,
+    /// The views of the groups the nodes are rendered to, for the nodes in groups.
+    pub node_views: EntityHashMap<Entity>,
+}
+
+impl ExtractedUiGroups {
+    /// The view `node` is rendered to, either the view of its group or `camera_entity`.
+    pub fn view_entity(&self, node: Entity, camera_entity: Entity) -> Entity {
+        self.node_views.get(&node).copied().unwrap_or(camera_entity)
+    }
+}
+
+/// Where the nodes being extracted are rendered to.
+#[derive(Clone, Copy)]
+enum GroupContext {
+    /// Directly to the camera.
+    Camera,
+    /// To the group with this view and depth.
+    Group(Entity, u32),
+    /// Nowhere, the nodes are in a group that is fully transparent.
+    Hidden(Entity),
+}
+
+impl GroupContext {
+    fn view(self) -> Option<Entity> {
+        match self {
+            Self::Camera => None,
+            Self::Group(view, _) | Self::Hidden(view) => Some(view),
+        }
+    }
+
+    fn depth(self) -> u32 {
+        match self {
+            Self::Group(_, depth) => depth + 1,
+            _ => 0,
+        }
+    }
+}
+
+/// Finds the groups of the UI nodes and extracts their views, and the nodes drawing their
+/// textures.
+#[allow(clippy::too_many_arguments)]
+pub fn extract_ui_groups(
+    mut commands: Commands,
+    mut extracted_groups: ResMut<ExtractedUiGroups>,
+    mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    mut transparent_render_phases: ResMut<ViewSortedRenderPhases<TransparentUi>>,
+    camera_query: Extract<Query<&Camera>>,
+    default_ui_camera: Extract<DefaultUiCamera>,
+    ui_scale: Extract<Res<UiScale>>,
+    root_node_query: Extract<Query<(Entity, Option<&TargetCamera>), (With<Node>, Without<Parent>)>>,
+    node_query: Extract<
+        Query<(
+            &Node,
+            &GlobalTransform,
+            &Style,
+            Option<&Opacity>,
+            Option<&BorderRadius>,
+            Option<&CalculatedClip>,
+            Option<&Children>,
+        )>,
+    >,
+) {
+    let ExtractedUiGroups { groups, node_views } = &mut *extracted_groups;
+    groups.clear();
+    node_views.clear();
+
+    let mut stack = Vec::new();
+    for (root, target_camera) in &root_node_query {
+        let Some(camera_entity) = target_camera
+            .map(TargetCamera::entity)
+            .or(default_ui_camera.get())
+        else {
+            continue;
+        };
+        let Ok(camera) = camera_query.get(camera_entity) else {
+            continue;
+        };
+        if !camera.is_active || ui_camera_view(camera, ui_scale.0).is_none() {
+            continue;
+        }
+        let viewport_size = camera.logical_viewport_size().unwrap_or(Vec2::ZERO) / ui_scale.0;
+
+        // Spawns the view of a new group rendered to `context`, and returns its context.
+        let mut add_group =
+            |context: GroupContext, extracted_uinode: ExtractedUiNode, commands: &mut Commands| {
+                let view = ui_camera_view(camera, ui_scale.0).unwrap();
+                let size = view.viewport.zw();
+                let hdr = view.hdr;
+                let view = commands.spawn(view).id();
+                transparent_render_phases.insert_or_clear(view);
+                let image = group_image(groups.len());
+                let depth = context.depth();
+                groups.push(ExtractedUiGroup {
+                    view,
+                    camera_entity,
+                    depth,
+                    image,
+                    size,
+                    hdr,
+                    texture: None,
+                });
+                extracted_uinodes.uinodes.insert(
+                    commands.spawn_empty().id(),
+                    ExtractedUiNode {
+                        image,
+                        camera_entity: context.view().unwrap_or(camera_entity),
+                        ..extracted_uinode
+                    },
+                );
+                GroupContext::Group(view, depth)
+            };
+
+        stack.push((root, GroupContext::Camera));
+        while let Some((entity, context)) = stack.pop() {
+            let Ok((node, transform, style, opacity, border_radius, clip, children)) =
+                node_query.get(entity)
+            else {
+                continue;
+            };
+            if style.display == Display::None {
+                continue;
+            }
+
+            let mut own_context = context;
+            if let (GroupContext::Camera | GroupContext::Group(..), Some(&Opacity(opacity))) =
+                (context, opacity)
+            {
+                if opacity <= 0. {
+                    own_context = GroupContext::Hidden(commands.spawn_empty().id());
+                } else if opacity < 1. {
+                    // The texture covers the whole viewport, as the descendants may overflow the
+                    // node.
+                    own_context = add_group(
+                        context,
+                        ExtractedUiNode {
+                            stack_index: node.stack_index,
+                            transform: Mat4::from_translation((0.5 * viewport_size).extend(0.)),
+                            color: LinearRgba::WHITE.with_alpha(opacity),
+                            rect: Rect {
+                                min: Vec2::ZERO,
+                                max: viewport_size,
+                            },
+                            image: AssetId::default(),
+                            atlas_size: Some(viewport_size),
+                            clip: None,
+                            flip_x: false,
+                            flip_y: false,
+                            camera_entity,
+                            border: [0.; 4],
+                            border_radius: [0.; 4],
+                            node_type: NodeType::Composite,
+                        },
+                        &mut commands,
+                    );
+                }
+            }
+            if let Some(view) = own_context.view() {
+                node_views.insert(entity, view);
+            }
+
+            let mut children_context = own_context;
+            let radius = border_radius
+                .map(|border_radius| {
+                    resolve_border_radius(border_radius, node.size(), viewport_size, ui_scale.0)
+                })
+                .unwrap_or_default();
+            if !matches!(own_context, GroupContext::Hidden(_))
+                && !style.overflow.x.is_visible()
+                && !style.overflow.y.is_visible()
+                && radius.iter().any(|radius| 0. < *radius)
+            {
+                // The texture is drawn with the rounded corners of the node, after the node itself
+                // and before its siblings, whose stack indices are greater than its descendants'.
+                children_context = add_group(
+                    own_context,
+                    ExtractedUiNode {
+                        stack_index: node.stack_index + 1,
+                        transform: transform.compute_matrix(),
+                        color: LinearRgba::WHITE,
+                        rect: node.logical_rect(transform),
+                        image: AssetId::default(),
+                        atlas_size: Some(viewport_size),
+                        clip: clip.map(|clip| clip.clip),
+                        flip_x: false,
+                        flip_y: false,
+                        camera_entity,
+                        border: [0.; 4],
+                        border_radius: radius,
+                        node_type: NodeType::Composite,
+                    },
+                    &mut commands,
+                );
+            }
+
+            if let Some(children) = children {
+                stack.extend(children.iter().map(|&child| (child, children_context)));
+            }
+        }
+    }
+}
+
+/// Prepares the textures of the groups and binds them to the image ids of their groups.
+pub fn prepare_ui_groups(
+    render_device: Res<RenderDevice>,
+    default_sampler: Res<DefaultImageSampler>,
+    mut texture_cache: ResMut<TextureCache>,
+    mut extracted_groups: ResMut<ExtractedUiGroups>,
+    mut gpu_images: ResMut<RenderAssets<GpuImage>>,
+    mut image_bind_groups: ResMut<UiImageBindGroups>,
+    mut previous_len: Local<usize>,
+) {
+    for group in &mut extracted_groups.groups {
+        let format = if group.hdr {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+        let texture = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("ui_group_texture"),
+                size: Extent3d {
+                    width: group.size.x.max(1),
+                    height: group.size.y.max(1),
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        );
+        gpu_images.insert(
+            group.image,
+            GpuImage {
+                texture: texture.texture.clone(),
+                texture_view: texture.default_view.clone(),
+                texture_format: format,
+                sampler: (**default_sampler).clone(),
+                size: group.size,
+                mip_level_count: 1,
+            },
+        );
+        // The cached texture may be a different one than in the previous frame
+        image_bind_groups.values.remove(&group.image);
+        group.texture = Some(texture);
+    }
+
+    for index in extracted_groups.groups.len()..*previous_len {
+        gpu_images.remove(group_image(index));
+        image_bind_groups.values.remove(&group_image(index));
+    }
+    *previous_len = extracted_groups.groups.len();
+}
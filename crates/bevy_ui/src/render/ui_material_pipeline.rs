@@ -75,7 +75,11 @@ where
                 .add_systems(
                     Render,
                     (
-                        queue_ui_material_nodes::<M>.in_set(RenderSet::Queue),
+                        // Queued after the other nodes, so that the material of a node is drawn
+                        // over its background color
+                        queue_ui_material_nodes::<M>
+                            .in_set(RenderSet::Queue)
+                            .after(queue_uinodes),
                         prepare_uimaterial_nodes::<M>.in_set(RenderSet::PrepareBindGroups),
                     ),
                 );
@@ -113,6 +117,7 @@ pub struct UiMaterialVertex {
     pub uv: [f32; 2],
     pub size: [f32; 2],
     pub border_widths: [f32; 4],
+    pub border_radius: [f32; 4],
 }
 
 // in this [`UiMaterialPipeline`] there is (currently) no batching going on.
@@ -152,6 +157,8 @@ where
                 VertexFormat::Float32x2,
                 // border_widths
                 VertexFormat::Float32x4,
+                // border_radius
+                VertexFormat::Float32x4,
             ],
         );
         let shader_defs = Vec::new();
@@ -331,6 +338,9 @@ pub struct ExtractedUiMaterialNode<M: UiMaterial> {
     pub transform: Mat4,
     pub rect: Rect,
     pub border: [f32; 4],
+    /// Border radius of the UI node.
+    /// Ordering: top left, top right, bottom right, bottom left.
+    pub border_radius: [f32; 4],
     pub material: AssetId<M>,
     pub clip: Option<Rect>,
     // Camera to render this UI node to. By the time it is extracted,
@@ -352,25 +362,25 @@ impl<M: UiMaterial> Default for ExtractedUiMaterialNodes<M> {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn extract_ui_material_nodes<M: UiMaterial>(
     mut extracted_uinodes: ResMut<ExtractedUiMaterialNodes<M>>,
+    extracted_groups: Res<ExtractedUiGroups>,
     materials: Extract<Res<Assets<M>>>,
     ui_stack: Extract<Res<UiStack>>,
     default_ui_camera: Extract<DefaultUiCamera>,
     uinode_query: Extract<
-        Query<
-            (
-                Entity,
-                &Node,
-                &Style,
-                &GlobalTransform,
-                &Handle<M>,
-                &ViewVisibility,
-                Option<&CalculatedClip>,
-                Option<&TargetCamera>,
-            ),
-            Without<BackgroundColor>,
-        >,
+        Query<(
+            Entity,
+            &Node,
+            &Style,
+            &GlobalTransform,
+            &Handle<M>,
+            &ViewVisibility,
+            Option<&BorderRadius>,
+            Option<&CalculatedClip>,
+            Option<&TargetCamera>,
+        )>,
     >,
     windows: Extract<Query<&Window, With<PrimaryWindow>>>,
     ui_scale: Extract<Res<UiScale>>,
@@ -387,8 +397,17 @@ pub fn extract_ui_material_nodes<M: UiMaterial>(
     let default_single_camera = default_ui_camera.get();
 
     for (stack_index, entity) in ui_stack.uinodes.iter().enumerate() {
-        if let Ok((entity, uinode, style, transform, handle, view_visibility, clip, camera)) =
-            uinode_query.get(*entity)
+        if let Ok((
+            entity,
+            uinode,
+            style,
+            transform,
+            handle,
+            view_visibility,
+            border_radius,
+            clip,
+            camera,
+        )) = uinode_query.get(*entity)
         {
             let Some(camera_entity) = camera.map(TargetCamera::entity).or(default_single_camera)
             else {
@@ -425,6 +444,17 @@ pub fn extract_ui_material_nodes<M: UiMaterial>(
                 ui_logical_viewport_size,
             ) / uinode.size().y;
 
+            let border_radius = border_radius
+                .map(|border_radius| {
+                    resolve_border_radius(
+                        border_radius,
+                        uinode.size(),
+                        ui_logical_viewport_size,
+                        ui_scale.0,
+                    )
+                })
+                .unwrap_or_default();
+
             extracted_uinodes.uinodes.insert(
                 entity,
                 ExtractedUiMaterialNode {
@@ -436,8 +466,9 @@ pub fn extract_ui_material_nodes<M: UiMaterial>(
                         max: uinode.calculated_size,
                     },
                     border: [left, right, top, bottom],
+                    border_radius,
                     clip: clip.map(|clip| clip.clip),
-                    camera_entity,
+                    camera_entity: extracted_groups.view_entity(entity, camera_entity),
                 },
             );
         };
@@ -577,6 +608,7 @@ pub fn prepare_uimaterial_nodes<M: UiMaterial>(
                             uv: uvs[i].into(),
                             size: extracted_uinode.rect.size().into(),
                             border_widths: extracted_uinode.border,
+                            border_radius: extracted_uinode.border_radius,
                         });
                     }
 
@@ -7,5 +7,30 @@ struct UiVertexOutput {
     @location(1) border_widths: vec4<f32>,
     // The size of the node in pixels. Order is width, height.
     @location(2) @interpolate(flat) size: vec2<f32>,
+    // The radius of the rounded corners of the node in pixels.
+    // Order is Top Left, Top Right, Bottom Right, Bottom Left.
+    @location(3) @interpolate(flat) border_radius: vec4<f32>,
     @builtin(position) position: vec4<f32>,
 };
+
+// Signed distance from `point` to the edge of a box of `size` centered on the origin, with its
+// corners rounded by `corner_radii`, ordered top left, top right, bottom right, bottom left.
+// The distance is negative inside the box.
+fn sd_rounded_box(point: vec2<f32>, size: vec2<f32>, corner_radii: vec4<f32>) -> f32 {
+    let rs = select(corner_radii.xy, corner_radii.wz, 0.0 < point.y);
+    let radius = select(rs.x, rs.y, 0.0 < point.x);
+    let corner_to_point = abs(point) - 0.5 * size;
+    let q = corner_to_point + radius;
+    let l = length(max(q, vec2(0.0)));
+    let m = min(max(q.x, q.y), 0.0);
+    return l + m - radius;
+}
+
+// How much of the fragment is inside the rounded corners of the node, from 0 outside to 1 inside.
+// Multiply the alpha of the color returned by a material by it to clip the material to the
+// `BorderRadius` of its node.
+fn border_radius_coverage(in: UiVertexOutput) -> f32 {
+    let point = (in.uv - 0.5) * in.size;
+    let distance = sd_rounded_box(point, in.size, in.border_radius);
+    return clamp(0.5 - 2.0 * distance, 0.0, 1.0);
+}
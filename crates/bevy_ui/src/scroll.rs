@@ -0,0 +1,654 @@
+//! Mouse wheel and touch scrolling of the nodes with [`OverflowAxis::Scroll`], with kinetic
+//! scrolling, [`ScrollAnimation`]s and [`Scrollbar`]s.
+
+use crate::{
+    node_bundles::NodeBundle, BackgroundColor, Display, FocusPolicy, Interaction, Node,
+    OverflowAxis, PositionType, RelativeCursorPosition, ScrollPosition, Style, UiScale, UiStack,
+    Val,
+};
+use bevy_color::Color;
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{prelude::*, query::QueryData};
+use bevy_hierarchy::{BuildChildren, ChildBuild, Children, DespawnRecursiveExt, Parent};
+use bevy_input::{
+    mouse::{MouseScrollUnit, MouseWheel},
+    touch::Touches,
+};
+use bevy_math::Vec2;
+use bevy_reflect::prelude::*;
+use bevy_time::Time;
+use bevy_utils::default;
+use bevy_window::RequestRedraw;
+use std::time::Duration;
+
+/// How the nodes with [`OverflowAxis::Scroll`] are scrolled by user input.
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource, Default)]
+pub struct ScrollSettings {
+    /// The distance scrolled by a line of mouse wheel, in logical pixels.
+    pub line_height: f32,
+    /// How fast kinetic scrolling slows down. Each second, the [`ScrollVelocity`] is divided by
+    /// `e` to the power of `deceleration`.
+    pub deceleration: f32,
+    /// The speed under which kinetic scrolling stops, in logical pixels per second.
+    pub min_velocity: f32,
+}
+
+impl Default for ScrollSettings {
+    fn default() -> Self {
+        Self {
+            line_height: 20.,
+            deceleration: 8.,
+            min_velocity: 5.,
+        }
+    }
+}
+
+/// The speed of the kinetic scrolling of a node, in logical pixels per second.
+///
+/// It is set when a touch drag ends and by the mouse wheel, then slowed down according to
+/// [`ScrollSettings::deceleration`]. This is inserted with the [`ScrollPosition`] of the nodes
+/// with [`OverflowAxis::Scroll`].
+#[derive(Component, Debug, Copy, Clone, Default, PartialEq, Deref, DerefMut, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct ScrollVelocity(pub Vec2);
+
+/// Animates the [`ScrollPosition`] of a node, like scrolling back to the top of a list.
+///
+/// The component is removed once the animation is finished, or when the node is scrolled by
+/// user input.
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component, PartialEq)]
+pub struct ScrollAnimation {
+    target: Vec2,
+    relative: bool,
+    duration: Duration,
+    elapsed: Duration,
+    start: Option<Vec2>,
+}
+
+impl ScrollAnimation {
+    /// Scrolls to the given offset over `duration`. The offset is clamped like the
+    /// [`ScrollPosition`].
+    pub fn to(offset: Vec2, duration: Duration) -> Self {
+        Self {
+            target: offset,
+            relative: false,
+            duration,
+            elapsed: Duration::ZERO,
+            start: None,
+        }
+    }
+
+    /// Scrolls by `delta` from the current offset over `duration`.
+    pub fn by(delta: Vec2, duration: Duration) -> Self {
+        Self {
+            relative: true,
+            ..Self::to(delta, duration)
+        }
+    }
+
+    /// Scrolls to the top left of the content over `duration`.
+    pub fn to_start(duration: Duration) -> Self {
+        Self::to(Vec2::ZERO, duration)
+    }
+
+    /// Scrolls to the bottom right of the content over `duration`.
+    pub fn to_end(duration: Duration) -> Self {
+        Self::to(Vec2::splat(f32::INFINITY), duration)
+    }
+
+    /// The duration of the animation.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// How long the animation has been running for.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns `true` once the animation reached its target.
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
+
+/// Adds scrollbars to a node with [`OverflowAxis::Scroll`].
+///
+/// A track entity is spawned as a child of the node for each axis that scrolls, with a thumb
+/// entity showing the visible part of the content that can be dragged to scroll. The track is
+/// hidden while all the content fits in the node.
+#[derive(Component, Debug, Copy, Clone, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct Scrollbar {
+    /// The thickness of the scrollbars, in logical pixels.
+    pub width: f32,
+    /// The smallest length of the thumbs, in logical pixels.
+    pub min_thumb_length: f32,
+    /// The color of the tracks.
+    pub track_color: Color,
+    /// The color of the thumbs.
+    pub thumb_color: Color,
+}
+
+impl Default for Scrollbar {
+    fn default() -> Self {
+        Self {
+            width: 8.,
+            min_thumb_length: 16.,
+            track_color: Color::srgba(0., 0., 0., 0.2),
+            thumb_color: Color::srgba(1., 1., 1., 0.5),
+        }
+    }
+}
+
+/// The axis of a [`ScrollbarTrack`] or [`ScrollbarThumb`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Reflect)]
+#[reflect(PartialEq)]
+pub enum ScrollbarAxis {
+    /// The horizontal scrollbar, at the bottom of the node.
+    X,
+    /// The vertical scrollbar, at the right of the node.
+    Y,
+}
+
+impl ScrollbarAxis {
+    fn get(self, value: Vec2) -> f32 {
+        match self {
+            Self::X => value.x,
+            Self::Y => value.y,
+        }
+    }
+}
+
+/// Marker for the track of a [`Scrollbar`], a child of the scrolled node.
+///
+/// The tracks are not moved by the [`ScrollPosition`] of their parent.
+#[derive(Component, Debug, Copy, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component, PartialEq)]
+pub struct ScrollbarTrack {
+    pub axis: ScrollbarAxis,
+}
+
+/// Marker for the thumb of a [`Scrollbar`], a child of its [`ScrollbarTrack`].
+#[derive(Component, Debug, Copy, Clone, PartialEq, Reflect)]
+#[reflect(Component, PartialEq)]
+pub struct ScrollbarThumb {
+    pub axis: ScrollbarAxis,
+    /// Where the thumb was grabbed, along its axis, when it's dragged.
+    #[reflect(ignore)]
+    grab: Option<f32>,
+}
+
+/// Inserts the [`ScrollPosition`], [`ScrollVelocity`] and [`RelativeCursorPosition`] used to
+/// scroll the nodes with [`OverflowAxis::Scroll`].
+pub fn insert_scroll_components_system(
+    mut commands: Commands,
+    query: Query<
+        (
+            Entity,
+            &Style,
+            Has<ScrollPosition>,
+            Has<ScrollVelocity>,
+            Has<RelativeCursorPosition>,
+        ),
+        Changed<Style>,
+    >,
+) {
+    for (entity, style, has_position, has_velocity, has_cursor_position) in &query {
+        if !style.overflow.is_scroll() {
+            continue;
+        }
+        let mut entity = commands.entity(entity);
+        if !has_position {
+            entity.insert(ScrollPosition::default());
+        }
+        if !has_velocity {
+            entity.insert(ScrollVelocity::default());
+        }
+        if !has_cursor_position {
+            entity.insert(RelativeCursorPosition::default());
+        }
+    }
+}
+
+/// The touch currently scrolling a node.
+#[derive(Default)]
+pub struct TouchScroll {
+    touch: Option<(u64, Entity)>,
+    velocity: Vec2,
+}
+
+/// Returns the offset change on each axis of scrolling `delta` on a node, `None` if the node
+/// can't scroll in that direction.
+///
+/// Vertical scrolling scrolls the nodes that only scroll horizontally.
+fn scroll_delta(style: &Style, node: &Node, offset: Vec2, delta: Vec2) -> Option<Vec2> {
+    let delta = if !style.overflow.y.is_scroll() && delta.x == 0. {
+        Vec2::new(delta.y, 0.)
+    } else {
+        delta
+    };
+    let max = node.max_scroll_offset();
+    let can_scroll = |axis: OverflowAxis, delta: f32, offset: f32, max: f32| {
+        axis.is_scroll() && ((delta > 0. && offset < max) || (delta < 0. && offset > 0.))
+    };
+    let delta = Vec2::new(
+        if can_scroll(style.overflow.x, delta.x, offset.x, max.x) {
+            delta.x
+        } else {
+            0.
+        },
+        if can_scroll(style.overflow.y, delta.y, offset.y, max.y) {
+            delta.y
+        } else {
+            0.
+        },
+    );
+    (delta != Vec2::ZERO).then_some(delta)
+}
+
+/// Main query for [`scroll_input_system`]
+#[derive(QueryData)]
+#[query_data(mutable)]
+pub struct ScrollNodeQuery {
+    node: &'static Node,
+    style: &'static Style,
+    relative_cursor_position: &'static RelativeCursorPosition,
+    scroll_position: &'static mut ScrollPosition,
+    velocity: &'static mut ScrollVelocity,
+    animated: Has<ScrollAnimation>,
+}
+
+/// Returns the top node under the cursor that can scroll by `delta`, with the offset change, or
+/// the top node under the cursor that overflows without `delta`.
+fn find_scrolled_node(
+    ui_stack: &UiStack,
+    nodes: &Query<ScrollNodeQuery>,
+    delta: Option<Vec2>,
+) -> Option<(Entity, Vec2)> {
+    ui_stack.uinodes.iter().rev().find_map(|&entity| {
+        let node = nodes.get(entity).ok()?;
+        if !node.relative_cursor_position.mouse_over() {
+            return None;
+        }
+        match delta {
+            Some(delta) => {
+                scroll_delta(node.style, node.node, node.scroll_position.offset(), delta)
+                    .map(|delta| (entity, delta))
+            }
+            None => (node.node.max_scroll_offset() != Vec2::ZERO).then_some((entity, Vec2::ZERO)),
+        }
+    })
+}
+
+/// Scrolls the nodes with [`OverflowAxis::Scroll`] under the cursor with the mouse wheel, and the
+/// nodes dragged by a touch or with their [`ScrollbarThumb`].
+///
+/// The mouse wheel scrolls the top node that can scroll further in its direction, so that nested
+/// nodes pass the scrolling to their parent once they've reached their end. Mouse wheels scrolling
+/// by lines scroll smoothly with [`ScrollVelocity`], while the pixel precise scrolling of touchpads
+/// is applied directly.
+#[allow(clippy::too_many_arguments)]
+pub fn scroll_input_system(
+    mut commands: Commands,
+    mut touch_scroll: Local<TouchScroll>,
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    touches: Res<Touches>,
+    time: Res<Time>,
+    settings: Res<ScrollSettings>,
+    ui_scale: Res<UiScale>,
+    ui_stack: Res<UiStack>,
+    mut nodes: Query<ScrollNodeQuery>,
+    mut thumbs: Query<(&mut ScrollbarThumb, &Interaction, &Style, &Parent)>,
+    tracks: Query<(&Node, &RelativeCursorPosition, &Parent), With<ScrollbarTrack>>,
+) {
+    let mut dragging_thumb = false;
+    for (mut thumb, interaction, thumb_style, track) in &mut thumbs {
+        if *interaction != Interaction::Pressed {
+            thumb.grab = None;
+            continue;
+        }
+        dragging_thumb = true;
+        let Ok((track_node, track_cursor, container)) = tracks.get(track.get()) else {
+            continue;
+        };
+        let Some(cursor) = track_cursor.normalized else {
+            continue;
+        };
+        let Ok(mut node) = nodes.get_mut(container.get()) else {
+            continue;
+        };
+        let axis = thumb.axis;
+        let track_length = axis.get(track_node.size());
+        let cursor = axis.get(cursor) * track_length;
+        let (thumb_position, thumb_length) = match axis {
+            ScrollbarAxis::X => (thumb_style.left, thumb_style.width),
+            ScrollbarAxis::Y => (thumb_style.top, thumb_style.height),
+        };
+        let (Val::Px(thumb_position), Val::Px(thumb_length)) = (thumb_position, thumb_length)
+        else {
+            continue;
+        };
+        let grab = *thumb.grab.get_or_insert(cursor - thumb_position);
+        let free_length = track_length - thumb_length;
+        if free_length <= 0. {
+            continue;
+        }
+        let offset =
+            ((cursor - grab) / free_length).clamp(0., 1.) * axis.get(node.node.max_scroll_offset());
+        match axis {
+            ScrollbarAxis::X => node.scroll_position.offset_x = offset,
+            ScrollbarAxis::Y => node.scroll_position.offset_y = offset,
+        }
+        node.velocity.0 = Vec2::ZERO;
+        if node.animated {
+            commands.entity(container.get()).remove::<ScrollAnimation>();
+        }
+    }
+
+    for event in mouse_wheel_events.read() {
+        // a positive wheel movement scrolls toward the start of the content
+        let (delta, smooth) = match event.unit {
+            MouseScrollUnit::Line => (-Vec2::new(event.x, event.y) * settings.line_height, true),
+            MouseScrollUnit::Pixel => (-Vec2::new(event.x, event.y) / ui_scale.0, false),
+        };
+        let Some((entity, delta)) = find_scrolled_node(&ui_stack, &nodes, Some(delta)) else {
+            continue;
+        };
+        let Ok(mut node) = nodes.get_mut(entity) else {
+            continue;
+        };
+        if smooth {
+            // the kinetic scrolling covers `velocity / deceleration`
+            node.velocity.0 += delta * settings.deceleration;
+        } else {
+            *node.scroll_position = (node.scroll_position.offset() + delta).into();
+            node.velocity.0 = Vec2::ZERO;
+        }
+        if node.animated {
+            commands.entity(entity).remove::<ScrollAnimation>();
+        }
+    }
+
+    if dragging_thumb {
+        touch_scroll.touch = None;
+        return;
+    }
+
+    if touch_scroll.touch.is_none() {
+        if let Some(touch) = touches.iter_just_pressed().next() {
+            touch_scroll.touch =
+                find_scrolled_node(&ui_stack, &nodes, None).map(|(entity, _)| (touch.id(), entity));
+            touch_scroll.velocity = Vec2::ZERO;
+        }
+    }
+    let Some((id, entity)) = touch_scroll.touch else {
+        return;
+    };
+    let Ok(mut node) = nodes.get_mut(entity) else {
+        touch_scroll.touch = None;
+        return;
+    };
+    if node.animated {
+        commands.entity(entity).remove::<ScrollAnimation>();
+    }
+    let Some(touch) = touches.get_pressed(id) else {
+        // the content keeps moving once released
+        node.velocity.0 = touch_scroll.velocity;
+        touch_scroll.touch = None;
+        return;
+    };
+    let mut delta = -touch.delta() / ui_scale.0;
+    if !node.style.overflow.x.is_scroll() {
+        delta.x = 0.;
+    }
+    if !node.style.overflow.y.is_scroll() {
+        delta.y = 0.;
+    }
+    *node.scroll_position = (node.scroll_position.offset() + delta).into();
+    node.velocity.0 = Vec2::ZERO;
+    let delta_seconds = time.delta_seconds();
+    if delta_seconds > 0. {
+        touch_scroll.velocity = (touch_scroll.velocity + delta / delta_seconds) / 2.;
+    }
+}
+
+/// Moves the nodes with a [`ScrollVelocity`], slowing them down according to the
+/// [`ScrollSettings`].
+///
+/// A redraw is requested while the nodes move, so that they don't stop in reactive update modes.
+pub fn kinetic_scroll_system(
+    time: Res<Time>,
+    settings: Res<ScrollSettings>,
+    mut redraw_events: EventWriter<RequestRedraw>,
+    mut query: Query<(&Node, &mut ScrollPosition, &mut ScrollVelocity)>,
+) {
+    let delta_seconds = time.delta_seconds();
+    let mut moving = false;
+    for (node, mut scroll_position, mut velocity) in &mut query {
+        if velocity.0 == Vec2::ZERO {
+            continue;
+        }
+        moving = true;
+        let max = node.max_scroll_offset();
+        let offset = scroll_position.offset() + velocity.0 * delta_seconds;
+        let clamped_offset = offset.clamp(Vec2::ZERO, max);
+        // stop at the ends of the content
+        if clamped_offset.x != offset.x {
+            velocity.x = 0.;
+        }
+        if clamped_offset.y != offset.y {
+            velocity.y = 0.;
+        }
+        *scroll_position = clamped_offset.into();
+
+        velocity.0 *= (-settings.deceleration * delta_seconds).exp();
+        if velocity.length() < settings.min_velocity {
+            velocity.0 = Vec2::ZERO;
+        }
+    }
+    if moving {
+        redraw_events.send(RequestRedraw);
+    }
+}
+
+/// Advances the [`ScrollAnimation`]s, easing out toward their target.
+pub fn scroll_animation_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut redraw_events: EventWriter<RequestRedraw>,
+    mut query: Query<(
+        Entity,
+        &Node,
+        &mut ScrollPosition,
+        &mut ScrollAnimation,
+        Option<&mut ScrollVelocity>,
+    )>,
+) {
+    for (entity, node, mut scroll_position, mut animation, velocity) in &mut query {
+        let start = *animation.start.get_or_insert(scroll_position.offset());
+        if animation.relative {
+            animation.target += start;
+            animation.relative = false;
+        }
+        animation.elapsed = (animation.elapsed + time.delta()).min(animation.duration);
+
+        let progress = if animation.duration.is_zero() {
+            1.
+        } else {
+            animation.elapsed.as_secs_f32() / animation.duration.as_secs_f32()
+        };
+        let eased = 1. - (1. - progress).powi(3);
+        let target = animation.target.clamp(Vec2::ZERO, node.max_scroll_offset());
+        *scroll_position = start.lerp(target, eased).into();
+
+        if let Some(mut velocity) = velocity {
+            velocity.0 = Vec2::ZERO;
+        }
+        if animation.is_finished() {
+            commands.entity(entity).remove::<ScrollAnimation>();
+        } else {
+            redraw_events.send(RequestRedraw);
+        }
+    }
+}
+
+/// Spawns the tracks and thumbs of the added [`Scrollbar`]s, and despawns those of the removed
+/// ones.
+pub fn spawn_scrollbars_system(
+    mut commands: Commands,
+    added: Query<(Entity, &Scrollbar), Added<Scrollbar>>,
+    mut removed: RemovedComponents<Scrollbar>,
+    children_query: Query<&Children>,
+    tracks: Query<(), With<ScrollbarTrack>>,
+) {
+    for entity in removed.read() {
+        let Ok(children) = children_query.get(entity) else {
+            continue;
+        };
+        for &child in children {
+            if tracks.contains(child) {
+                commands.entity(child).despawn_recursive();
+            }
+        }
+    }
+
+    for (entity, scrollbar) in &added {
+        commands.entity(entity).with_children(|parent| {
+            for axis in [ScrollbarAxis::X, ScrollbarAxis::Y] {
+                parent
+                    .spawn((
+                        NodeBundle {
+                            style: Style {
+                                position_type: PositionType::Absolute,
+                                display: Display::None,
+                                ..default()
+                            },
+                            background_color: scrollbar.track_color.into(),
+                            ..default()
+                        },
+                        ScrollbarTrack { axis },
+                        RelativeCursorPosition::default(),
+                    ))
+                    .with_children(|track| {
+                        track.spawn((
+                            NodeBundle {
+                                style: Style {
+                                    position_type: PositionType::Absolute,
+                                    ..default()
+                                },
+                                background_color: scrollbar.thumb_color.into(),
+                                focus_policy: FocusPolicy::Block,
+                                ..default()
+                            },
+                            ScrollbarThumb { axis, grab: None },
+                            Interaction::default(),
+                        ));
+                    });
+            }
+        });
+    }
+}
+
+/// Shows the tracks of the [`Scrollbar`]s of the nodes that overflow, and sizes and moves their
+/// thumbs according to the [`ScrollPosition`] of the node.
+#[allow(clippy::type_complexity)]
+pub fn update_scrollbars_system(
+    containers: Query<(&Node, &Style, &ScrollPosition, &Scrollbar, &Children)>,
+    mut tracks: Query<
+        (&ScrollbarTrack, &mut Style, &mut BackgroundColor, &Children),
+        (Without<Scrollbar>, Without<ScrollbarThumb>),
+    >,
+    mut thumbs: Query<
+        (&mut Style, &mut BackgroundColor),
+        (
+            With<ScrollbarThumb>,
+            Without<Scrollbar>,
+            Without<ScrollbarTrack>,
+        ),
+    >,
+) {
+    for (node, style, scroll_position, scrollbar, children) in &containers {
+        let max = node.max_scroll_offset();
+        let shown = |axis: ScrollbarAxis| match axis {
+            ScrollbarAxis::X => style.overflow.x.is_scroll() && max.x > 0.,
+            ScrollbarAxis::Y => style.overflow.y.is_scroll() && max.y > 0.,
+        };
+        // the tracks don't overlap in the corner when both are shown
+        let corner = if shown(ScrollbarAxis::X) && shown(ScrollbarAxis::Y) {
+            scrollbar.width
+        } else {
+            0.
+        };
+
+        let mut track_iter = tracks.iter_many_mut(children);
+        while let Some((track, mut track_style, mut track_color, track_children)) =
+            track_iter.fetch_next()
+        {
+            let axis = track.axis;
+            let mut new_track_style = track_style.clone();
+            new_track_style.display = if shown(axis) {
+                Display::Flex
+            } else {
+                Display::None
+            };
+            match axis {
+                ScrollbarAxis::X => {
+                    new_track_style.left = Val::Px(0.);
+                    new_track_style.right = Val::Px(corner);
+                    new_track_style.bottom = Val::Px(0.);
+                    new_track_style.height = Val::Px(scrollbar.width);
+                }
+                ScrollbarAxis::Y => {
+                    new_track_style.top = Val::Px(0.);
+                    new_track_style.bottom = Val::Px(corner);
+                    new_track_style.right = Val::Px(0.);
+                    new_track_style.width = Val::Px(scrollbar.width);
+                }
+            }
+            track_style.set_if_neq(new_track_style);
+            track_color.set_if_neq(scrollbar.track_color.into());
+
+            let track_length = (axis.get(node.size()) - corner).max(0.);
+            let content_length = axis.get(node.content_size());
+            let thumb_length = if content_length > 0. {
+                (track_length * axis.get(node.size()) / content_length)
+                    .max(scrollbar.min_thumb_length)
+                    .min(track_length)
+            } else {
+                track_length
+            };
+            let progress = if axis.get(max) > 0. {
+                (axis.get(scroll_position.offset()) / axis.get(max)).clamp(0., 1.)
+            } else {
+                0.
+            };
+            let thumb_position = (track_length - thumb_length) * progress;
+
+            for &thumb in track_children {
+                let Ok((mut thumb_style, mut thumb_color)) = thumbs.get_mut(thumb) else {
+                    continue;
+                };
+                let mut new_thumb_style = thumb_style.clone();
+                match axis {
+                    ScrollbarAxis::X => {
+                        new_thumb_style.top = Val::Px(0.);
+                        new_thumb_style.bottom = Val::Px(0.);
+                        new_thumb_style.left = Val::Px(thumb_position);
+                        new_thumb_style.width = Val::Px(thumb_length);
+                    }
+                    ScrollbarAxis::Y => {
+                        new_thumb_style.left = Val::Px(0.);
+                        new_thumb_style.right = Val::Px(0.);
+                        new_thumb_style.top = Val::Px(thumb_position);
+                        new_thumb_style.height = Val::Px(thumb_length);
+                    }
+                }
+                thumb_style.set_if_neq(new_thumb_style);
+                thumb_color.set_if_neq(scrollbar.thumb_color.into());
+            }
+        }
+    }
+}
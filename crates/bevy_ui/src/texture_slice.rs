@@ -217,3 +217,21 @@ pub(crate) fn compute_slices_on_image_change(
         }
     }
 }
+
+/// System removing the computed slices of the ui nodes whose [`ImageScaleMode`] was removed, so that
+/// their image is drawn whole again
+pub(crate) fn remove_slices_on_scale_mode_removal(
+    mut commands: Commands,
+    mut removed: RemovedComponents<ImageScaleMode>,
+    scale_modes: Query<(), With<ImageScaleMode>>,
+) {
+    for entity in removed.read() {
+        // the scale mode may have been replaced in the same frame
+        if scale_modes.contains(entity) {
+            continue;
+        }
+        if let Some(mut entity) = commands.get_entity(entity) {
+            entity.remove::<ComputedTextureSlices>();
+        }
+    }
+}
@@ -0,0 +1,550 @@
+//! Transitions of the style properties of the UI nodes, animating them toward their new value when
+//! it changes.
+
+use crate::{BackgroundColor, BorderColor, BorderRadius, Opacity, Style, UiImage, UiRect, Val};
+use bevy_color::{Alpha, Color, Mix};
+use bevy_ecs::prelude::*;
+use bevy_math::{cubic_splines::CubicSegment, Vec2};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_time::Time;
+use bevy_utils::default;
+use std::time::Duration;
+
+/// A style property of a UI node that can be animated by a [`UiTransition`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Reflect)]
+#[reflect(PartialEq)]
+pub enum TransitionProperty {
+    /// [`Style::width`]
+    Width,
+    /// [`Style::height`]
+    Height,
+    /// [`Style::margin`]
+    Margin,
+    /// The [`BackgroundColor`].
+    BackgroundColor,
+    /// The [`Opacity`] of the node, or the alpha of the [`BackgroundColor`], [`BorderColor`] and
+    /// [`UiImage::color`] when it doesn't have one.
+    ///
+    /// Animating the [`Opacity`] fades the node and its descendants as a group. Otherwise, the alpha of the [`BackgroundColor`] is animated by [`TransitionProperty::BackgroundColor`]
+    /// instead when the node has both transitions.
+    Opacity,
+    /// The [`BorderRadius`].
+    BorderRadius,
+}
+
+/// How the progress of a transition is distributed over its duration.
+///
+/// The named curves are the cubic Bezier curves of the CSS easing functions.
+#[derive(Debug, Copy, Clone, PartialEq, Default, Reflect)]
+#[reflect(Default, PartialEq)]
+pub enum Easing {
+    /// Constant speed.
+    Linear,
+    /// Starts quickly and slows down at the end.
+    #[default]
+    Ease,
+    /// Starts slowly and speeds up.
+    EaseIn,
+    /// Starts quickly and slows down.
+    EaseOut,
+    /// Starts and ends slowly.
+    EaseInOut,
+    /// A cubic Bezier curve from `(0, 0)` to `(1, 1)` with the two given control points, see
+    /// [`CubicSegment::new_bezier`].
+    CubicBezier(Vec2, Vec2),
+}
+
+impl Easing {
+    /// Returns how far along a transition is when `time` of its duration has elapsed, both
+    /// usually in `0..=1`.
+    pub fn ease(self, time: f32) -> f32 {
+        let (p1, p2) = match self {
+            Self::Linear => return time,
+            Self::Ease => (Vec2::new(0.25, 0.1), Vec2::new(0.25, 1.)),
+            Self::EaseIn => (Vec2::new(0.42, 0.), Vec2::ONE),
+            Self::EaseOut => (Vec2::ZERO, Vec2::new(0.58, 1.)),
+            Self::EaseInOut => (Vec2::new(0.42, 0.), Vec2::new(0.58, 1.)),
+            Self::CubicBezier(p1, p2) => (p1, p2),
+        };
+        CubicSegment::new_bezier(p1, p2).ease(time)
+    }
+}
+
+/// The duration and [`Easing`] of the transition of a [`TransitionProperty`].
+#[derive(Debug, Copy, Clone, PartialEq, Reflect)]
+#[reflect(PartialEq)]
+pub struct PropertyTransition {
+    pub property: TransitionProperty,
+    pub duration: Duration,
+    pub easing: Easing,
+}
+
+impl PropertyTransition {
+    pub const fn new(property: TransitionProperty, duration: Duration, easing: Easing) -> Self {
+        Self {
+            property,
+            duration,
+            easing,
+        }
+    }
+}
+
+/// Animates style properties of a UI node when their value changes, like the `transition` of CSS.
+///
+/// When a transitioned property is set, the node keeps its current value and moves toward the new
+/// one over the duration of the [`PropertyTransition`], so that hover and press effects are
+/// animated by changing the style in the usual way. The components hold the animated value until
+/// the transition ends, and the first value of a property is never animated.
+///
+/// [`Val`]s are only interpolated between values of the same unit, other changes are immediate.
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct UiTransition {
+    /// The transitioned properties. The first transition of a property is used if it's listed
+    /// more than once.
+    pub transitions: Vec<PropertyTransition>,
+    #[reflect(ignore)]
+    state: TransitionState,
+}
+
+impl UiTransition {
+    /// Creates a [`UiTransition`] of the given properties.
+    pub fn new(transitions: impl IntoIterator<Item = PropertyTransition>) -> Self {
+        Self {
+            transitions: transitions.into_iter().collect(),
+            state: default(),
+        }
+    }
+
+    /// Creates a [`UiTransition`] of all the [`TransitionProperty`]s, with the same duration and
+    /// easing.
+    pub fn all(duration: Duration, easing: Easing) -> Self {
+        Self::new(
+            [
+                TransitionProperty::Width,
+                TransitionProperty::Height,
+                TransitionProperty::Margin,
+                TransitionProperty::BackgroundColor,
+                TransitionProperty::Opacity,
+                TransitionProperty::BorderRadius,
+            ]
+            .map(|property| PropertyTransition::new(property, duration, easing)),
+        )
+    }
+
+    /// Returns this [`UiTransition`] with an additional transition of `property`.
+    pub fn with(
+        mut self,
+        property: TransitionProperty,
+        duration: Duration,
+        easing: Easing,
+    ) -> Self {
+        self.transitions
+            .push(PropertyTransition::new(property, duration, easing));
+        self
+    }
+
+    /// The transition of `property`, if it's animated.
+    pub fn get(&self, property: TransitionProperty) -> Option<&PropertyTransition> {
+        self.transitions
+            .iter()
+            .find(|transition| transition.property == property)
+    }
+
+    /// Returns `true` while a property is being animated.
+    pub fn is_running(&self) -> bool {
+        self.state.is_running()
+    }
+}
+
+/// Values that can be animated by a [`UiTransition`].
+trait Interpolate: Clone + PartialEq {
+    /// Returns the value at `factor` between `self` and `target`, or `None` if they can't be
+    /// interpolated.
+    fn interpolate(&self, target: &Self, factor: f32) -> Option<Self>;
+}
+
+impl Interpolate for f32 {
+    fn interpolate(&self, target: &Self, factor: f32) -> Option<Self> {
+        Some(self + (target - self) * factor)
+    }
+}
+
+impl Interpolate for Color {
+    fn interpolate(&self, target: &Self, factor: f32) -> Option<Self> {
+        Some(self.mix(target, factor))
+    }
+}
+
+impl Interpolate for Val {
+    fn interpolate(&self, target: &Self, factor: f32) -> Option<Self> {
+        let lerp = |a: f32, b: f32| a.interpolate(&b, factor);
+        Some(match (*self, *target) {
+            (Val::Px(a), Val::Px(b)) => Val::Px(lerp(a, b)?),
+            (Val::Percent(a), Val::Percent(b)) => Val::Percent(lerp(a, b)?),
+            (Val::Vw(a), Val::Vw(b)) => Val::Vw(lerp(a, b)?),
+            (Val::Vh(a), Val::Vh(b)) => Val::Vh(lerp(a, b)?),
+            (Val::VMin(a), Val::VMin(b)) => Val::VMin(lerp(a, b)?),
+            (Val::VMax(a), Val::VMax(b)) => Val::VMax(lerp(a, b)?),
+            _ => return None,
+        })
+    }
+}
+
+impl Interpolate for UiRect {
+    fn interpolate(&self, target: &Self, factor: f32) -> Option<Self> {
+        Some(UiRect {
+            left: self.left.interpolate(&target.left, factor)?,
+            right: self.right.interpolate(&target.right, factor)?,
+            top: self.top.interpolate(&target.top, factor)?,
+            bottom: self.bottom.interpolate(&target.bottom, factor)?,
+        })
+    }
+}
+
+impl Interpolate for BorderRadius {
+    fn interpolate(&self, target: &Self, factor: f32) -> Option<Self> {
+        Some(BorderRadius {
+            top_left: self.top_left.interpolate(&target.top_left, factor)?,
+            top_right: self.top_right.interpolate(&target.top_right, factor)?,
+            bottom_left: self.bottom_left.interpolate(&target.bottom_left, factor)?,
+            bottom_right: self
+                .bottom_right
+                .interpolate(&target.bottom_right, factor)?,
+        })
+    }
+}
+
+/// A running transition of a property.
+#[derive(Debug, Clone)]
+struct Tween<T> {
+    start: T,
+    target: T,
+    elapsed: Duration,
+}
+
+/// The transition state of a property.
+#[derive(Debug, Clone)]
+struct Track<T> {
+    /// The value written by the last update, to detect when the target changes.
+    written: T,
+    tween: Option<Tween<T>>,
+}
+
+impl<T: Interpolate> Track<T> {
+    /// Advances the transition of a property with the given `value` by `delta`, returning the new
+    /// value of the property if it changed.
+    fn update(
+        track: &mut Option<Self>,
+        value: &T,
+        transition: Option<&PropertyTransition>,
+        delta: Duration,
+    ) -> Option<T> {
+        let Some(transition) = transition else {
+            *track = None;
+            return None;
+        };
+        let Some(track) = track else {
+            *track = Some(Self {
+                written: value.clone(),
+                tween: None,
+            });
+            return None;
+        };
+
+        if *value != track.written && !matches!(&track.tween, Some(tween) if tween.target == *value)
+        {
+            // the property was set, animate from the displayed value
+            track.tween = Some(Tween {
+                start: track.written.clone(),
+                target: value.clone(),
+                elapsed: Duration::ZERO,
+            });
+        }
+        let tween = track.tween.as_mut()?;
+        tween.elapsed += delta;
+        let time = if transition.duration.is_zero() {
+            1.
+        } else {
+            tween.elapsed.as_secs_f32() / transition.duration.as_secs_f32()
+        };
+        let current = if time >= 1. {
+            None
+        } else {
+            tween
+                .start
+                .interpolate(&tween.target, transition.easing.ease(time))
+        };
+        let current = match current {
+            Some(current) => current,
+            None => track.tween.take()?.target,
+        };
+        track.written = current.clone();
+        (current != *value).then_some(current)
+    }
+}
+
+/// The state of the transitions of a [`UiTransition`].
+#[derive(Debug, Clone, Default)]
+struct TransitionState {
+    width: Option<Track<Val>>,
+    height: Option<Track<Val>>,
+    margin: Option<Track<UiRect>>,
+    background_color: Option<Track<Color>>,
+    opacity: Option<Track<f32>>,
+    background_alpha: Option<Track<f32>>,
+    border_alpha: Option<Track<f32>>,
+    image_alpha: Option<Track<f32>>,
+    border_radius: Option<Track<BorderRadius>>,
+}
+
+impl TransitionState {
+    fn is_running(&self) -> bool {
+        fn running<T>(track: &Option<Track<T>>) -> bool {
+            track.as_ref().is_some_and(|track| track.tween.is_some())
+        }
+        running(&self.width)
+            || running(&self.height)
+            || running(&self.margin)
+            || running(&self.background_color)
+            || running(&self.opacity)
+            || running(&self.background_alpha)
+            || running(&self.border_alpha)
+            || running(&self.image_alpha)
+            || running(&self.border_radius)
+    }
+}
+
+/// Animates the properties of the nodes with a [`UiTransition`] toward their new values.
+pub fn ui_transition_system(
+    time: Res<Time>,
+    mut query: Query<(
+        &mut UiTransition,
+        &mut Style,
+        Option<&mut BackgroundColor>,
+        Option<&mut BorderColor>,
+        Option<&mut BorderRadius>,
+        Option<&mut UiImage>,
+        Option<&mut Opacity>,
+    )>,
+) {
+    let delta = time.delta();
+    for (
+        mut transition,
+        mut style,
+        background_color,
+        border_color,
+        border_radius,
+        image,
+        node_opacity,
+    ) in &mut query
+    {
+        // only mark the transition as changed when the properties are updated
+        let transition = transition.bypass_change_detection();
+        let UiTransition { transitions, state } = transition;
+        let get = |property| {
+            transitions
+                .iter()
+                .find(|transition| transition.property == property)
+        };
+        let mut opacity = get(TransitionProperty::Opacity);
+        if let Some(mut node_opacity) = node_opacity {
+            if let Some(value) = Track::update(&mut state.opacity, &node_opacity.0, opacity, delta)
+            {
+                node_opacity.0 = value;
+            }
+            // the colors keep their alpha
+            opacity = None;
+        }
+
+        if let Some(width) = Track::update(
+            &mut state.width,
+            &style.width,
+            get(TransitionProperty::Width),
+            delta,
+        ) {
+            style.width = width;
+        }
+        if let Some(height) = Track::update(
+            &mut state.height,
+            &style.height,
+            get(TransitionProperty::Height),
+            delta,
+        ) {
+            style.height = height;
+        }
+        if let Some(margin) = Track::update(
+            &mut state.margin,
+            &style.margin,
+            get(TransitionProperty::Margin),
+            delta,
+        ) {
+            style.margin = margin;
+        }
+
+        if let Some(mut background_color) = background_color {
+            let color_transition = get(TransitionProperty::BackgroundColor);
+            if let Some(color) = Track::update(
+                &mut state.background_color,
+                &background_color.0,
+                color_transition,
+                delta,
+            ) {
+                background_color.0 = color;
+            }
+            if let Some(alpha) = Track::update(
+                &mut state.background_alpha,
+                &background_color.0.alpha(),
+                opacity.filter(|_| color_transition.is_none()),
+                delta,
+            ) {
+                background_color.0.set_alpha(alpha);
+            }
+        }
+        if let Some(mut border_color) = border_color {
+            if let Some(alpha) = Track::update(
+                &mut state.border_alpha,
+                &border_color.0.alpha(),
+                opacity,
+                delta,
+            ) {
+                border_color.0.set_alpha(alpha);
+            }
+        }
+        if let Some(mut image) = image {
+            if let Some(alpha) =
+                Track::update(&mut state.image_alpha, &image.color.alpha(), opacity, delta)
+            {
+                image.color.set_alpha(alpha);
+            }
+        }
+        if let Some(mut border_radius) = border_radius {
+            if let Some(radius) = Track::update(
+                &mut state.border_radius,
+                &*border_radius,
+                get(TransitionProperty::BorderRadius),
+                delta,
+            ) {
+                *border_radius = radius;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Easing, TransitionProperty, UiTransition};
+    use crate::{BackgroundColor, Opacity, Style, Val};
+    use bevy_color::Color;
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+    use bevy_time::Time;
+    use bevy_utils::default;
+    use std::time::Duration;
+
+    #[test]
+    fn easing_curves_start_and_end() {
+        for easing in [
+            Easing::Linear,
+            Easing::Ease,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+        ] {
+            assert!(easing.ease(0.).abs() < 1e-4);
+            assert!((easing.ease(1.) - 1.).abs() < 1e-4);
+        }
+        assert_eq!(Easing::Linear.ease(0.25), 0.25);
+        assert!(Easing::EaseIn.ease(0.5) < 0.5);
+        assert!(Easing::EaseOut.ease(0.5) > 0.5);
+    }
+
+    #[test]
+    fn properties_are_animated_toward_their_new_value() {
+        let mut world = World::new();
+        world.insert_resource(Time::<()>::default());
+        let entity = world
+            .spawn((
+                Style {
+                    width: Val::Px(0.),
+                    height: Val::Px(10.),
+                    ..default()
+                },
+                BackgroundColor(Color::BLACK),
+                UiTransition::default()
+                    .with(
+                        TransitionProperty::Width,
+                        Duration::from_secs(1),
+                        Easing::Linear,
+                    )
+                    .with(
+                        TransitionProperty::Opacity,
+                        Duration::from_secs(2),
+                        Easing::Linear,
+                    ),
+            ))
+            .id();
+        let advance = |world: &mut World, seconds: f32| {
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_secs_f32(seconds));
+            world.run_system_once(super::ui_transition_system);
+        };
+
+        // the first values are not animated
+        advance(&mut world, 0.);
+        let mut node = world.entity_mut(entity);
+        let mut style = node.get_mut::<Style>().unwrap();
+        style.width = Val::Px(100.);
+        style.height = Val::Px(20.);
+        node.get_mut::<BackgroundColor>().unwrap().0 = Color::NONE;
+
+        advance(&mut world, 0.5);
+        let node = world.entity(entity);
+        assert_eq!(node.get::<Style>().unwrap().width, Val::Px(50.));
+        assert_eq!(node.get::<Style>().unwrap().height, Val::Px(20.));
+        let alpha = bevy_color::Alpha::alpha(&node.get::<BackgroundColor>().unwrap().0);
+        assert!((alpha - 0.75).abs() < 1e-4);
+        assert!(node.get::<UiTransition>().unwrap().is_running());
+
+        // setting the target again doesn't restart the transition
+        world.get_mut::<Style>(entity).unwrap().width = Val::Px(100.);
+        advance(&mut world, 0.25);
+        assert_eq!(world.get::<Style>(entity).unwrap().width, Val::Px(75.));
+
+        advance(&mut world, 2.);
+        let node = world.entity(entity);
+        assert_eq!(node.get::<Style>().unwrap().width, Val::Px(100.));
+        assert_eq!(node.get::<BackgroundColor>().unwrap().0, Color::NONE);
+        assert!(!node.get::<UiTransition>().unwrap().is_running());
+    }
+
+    #[test]
+    fn opacity_component_is_animated_instead_of_alphas() {
+        let mut world = World::new();
+        world.insert_resource(Time::<()>::default());
+        let entity = world
+            .spawn((
+                Style::default(),
+                BackgroundColor(Color::BLACK),
+                Opacity(1.),
+                UiTransition::default().with(
+                    TransitionProperty::Opacity,
+                    Duration::from_secs(1),
+                    Easing::Linear,
+                ),
+            ))
+            .id();
+        world.run_system_once(super::ui_transition_system);
+        world.get_mut::<Opacity>(entity).unwrap().0 = 0.;
+
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(0.25));
+        world.run_system_once(super::ui_transition_system);
+        assert_eq!(world.get::<Opacity>(entity).unwrap().0, 0.75);
+        assert_eq!(
+            world.get::<BackgroundColor>(entity).unwrap().0,
+            Color::BLACK
+        );
+    }
+}
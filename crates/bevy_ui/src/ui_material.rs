@@ -16,6 +16,13 @@ use bevy_render::render_resource::{AsBindGroup, RenderPipelineDescriptor, Shader
 /// from `bevy_ui::ui_vertex_output` and uses it as the input of your fragment shader like the
 /// example below does.
 ///
+/// A material can be added to any UI node by inserting its [`Handle`](bevy_asset::Handle), and is
+/// drawn over the [`BackgroundColor`](crate::BackgroundColor) of the node. The node's
+/// [`BorderRadius`](crate::BorderRadius) is passed to the shader in `UiVertexOutput::border_radius`:
+/// multiply the alpha of the output by `border_radius_coverage` from `bevy_ui::ui_vertex_output`
+/// to clip the material to the rounded corners. Materials are also clipped by the rounded
+/// corners of the ancestors of the node, like any other node.
+///
 /// # Example
 ///
 /// Here is a simple [`UiMaterial`] implementation. The [`AsBindGroup`] derive has many features. To see what else is available,
@@ -44,6 +44,11 @@ pub struct Node {
     ///
     /// Automatically calculated by [`super::layout::ui_layout_system`].
     pub(crate) unrounded_size: Vec2,
+    /// The size of the content of the node as width and height in logical pixels, including the
+    /// parts overflowing the node.
+    ///
+    /// Automatically calculated by [`super::layout::ui_layout_system`].
+    pub(crate) content_size: Vec2,
 }
 
 impl Node {
@@ -67,6 +72,19 @@ impl Node {
         self.unrounded_size
     }
 
+    /// The size of the content of the node as width and height in logical pixels, including the
+    /// parts overflowing the node. This is how far the node can be scrolled with a [`ScrollPosition`].
+    ///
+    /// Automatically calculated by [`super::layout::ui_layout_system`].
+    pub const fn content_size(&self) -> Vec2 {
+        self.content_size
+    }
+
+    /// The largest [`ScrollPosition`] offset of the node on each axis, in logical pixels.
+    pub fn max_scroll_offset(&self) -> Vec2 {
+        (self.content_size - self.unrounded_size).max(Vec2::ZERO)
+    }
+
     /// Returns the size of the node in physical pixels based on the given scale factor and `UiScale`.
     #[inline]
     pub fn physical_size(&self, scale_factor: f32, ui_scale: f32) -> Vec2 {
@@ -118,6 +136,7 @@ impl Node {
         outline_width: 0.,
         outline_offset: 0.,
         unrounded_size: Vec2::ZERO,
+        content_size: Vec2::ZERO,
     };
 }
 
@@ -127,6 +146,50 @@ impl Default for Node {
     }
 }
 
+/// The offset of the content of a node whose [`Style::overflow`] is [`OverflowAxis::Scroll`],
+/// in logical pixels.
+///
+/// The offset is clamped by [`super::layout::ui_layout_system`] between zero and
+/// [`Node::max_scroll_offset`], and only applies to the axes that scroll.
+/// It is updated by mouse wheel and touch input, and can be animated with a
+/// [`ScrollAnimation`](crate::ScrollAnimation).
+#[derive(Component, Debug, Copy, Clone, Default, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct ScrollPosition {
+    /// How far the content is scrolled to the left, in logical pixels.
+    pub offset_x: f32,
+    /// How far the content is scrolled up, in logical pixels.
+    pub offset_y: f32,
+}
+
+impl ScrollPosition {
+    pub const DEFAULT: Self = Self {
+        offset_x: 0.,
+        offset_y: 0.,
+    };
+
+    /// Creates a [`ScrollPosition`] with the given offsets.
+    pub const fn new(offset_x: f32, offset_y: f32) -> Self {
+        Self { offset_x, offset_y }
+    }
+
+    /// The offsets on both axes.
+    pub const fn offset(&self) -> Vec2 {
+        Vec2::new(self.offset_x, self.offset_y)
+    }
+}
+
+impl From<Vec2> for ScrollPosition {
+    fn from(offset: Vec2) -> Self {
+        Self::new(offset.x, offset.y)
+    }
+}
+
 /// Describes the style of a UI container node
 ///
 /// Nodes can be laid out using either Flexbox or CSS Grid Layout.
@@ -425,6 +488,20 @@ pub struct Style {
     ///
     /// <https://developer.mozilla.org/en-US/docs/Web/CSS/grid-column>
     pub grid_column: GridPlacement,
+
+    /// Names areas of the grid, which the grid items can be placed in with `grid_area`.
+    /// Only affects Grid layouts.
+    ///
+    /// <https://developer.mozilla.org/en-US/docs/Web/CSS/grid-template-areas>
+    pub grid_template_areas: Vec<GridTemplateArea>,
+
+    /// The named area of the parent grid the grid item is placed in.
+    ///
+    /// Takes precedence over `grid_row` and `grid_column`, which are used instead if the parent doesn't define
+    /// an area with this name in its `grid_template_areas`.
+    ///
+    /// <https://developer.mozilla.org/en-US/docs/Web/CSS/grid-area>
+    pub grid_area: Option<String>,
 }
 
 impl Style {
@@ -467,6 +544,8 @@ impl Style {
         grid_auto_columns: Vec::new(),
         grid_column: GridPlacement::DEFAULT,
         grid_row: GridPlacement::DEFAULT,
+        grid_template_areas: Vec::new(),
+        grid_area: None,
     };
 }
 
@@ -875,10 +954,39 @@ impl Overflow {
         }
     }
 
+    /// Scroll overflowing items on both axes
+    pub const fn scroll() -> Self {
+        Self {
+            x: OverflowAxis::Scroll,
+            y: OverflowAxis::Scroll,
+        }
+    }
+
+    /// Scroll overflowing items on the x axis
+    pub const fn scroll_x() -> Self {
+        Self {
+            x: OverflowAxis::Scroll,
+            y: OverflowAxis::Visible,
+        }
+    }
+
+    /// Scroll overflowing items on the y axis
+    pub const fn scroll_y() -> Self {
+        Self {
+            x: OverflowAxis::Visible,
+            y: OverflowAxis::Scroll,
+        }
+    }
+
     /// Overflow is visible on both axes
     pub const fn is_visible(&self) -> bool {
         self.x.is_visible() && self.y.is_visible()
     }
+
+    /// Overflowing items are scrolled on at least one axis
+    pub const fn is_scroll(&self) -> bool {
+        self.x.is_scroll() || self.y.is_scroll()
+    }
 }
 
 impl Default for Overflow {
@@ -902,6 +1010,9 @@ pub enum OverflowAxis {
     Clip,
     /// Hide overflowing items by influencing layout and then clipping.
     Hidden,
+    /// Scroll overflowing items with the node's [`ScrollPosition`], clipping them like
+    /// [`OverflowAxis::Hidden`].
+    Scroll,
 }
 
 impl OverflowAxis {
@@ -911,6 +1022,11 @@ impl OverflowAxis {
     pub const fn is_visible(&self) -> bool {
         matches!(self, Self::Visible)
     }
+
+    /// Overflow is scrolled on this axis
+    pub const fn is_scroll(&self) -> bool {
+        matches!(self, Self::Scroll)
+    }
 }
 
 impl Default for OverflowAxis {
@@ -1680,6 +1796,129 @@ pub enum GridPlacementError {
     InvalidZeroSpan,
 }
 
+/// A named area of a grid container, which its grid items can be placed in with [`Style::grid_area`].
+///
+/// The areas are usually defined all at once with [`GridTemplateArea::from_rows`]. Areas placed
+/// beyond the tracks of `grid_template_rows` or `grid_template_columns` create implicit tracks,
+/// sized by `grid_auto_rows` and `grid_auto_columns`.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/grid-template-areas>
+#[derive(Clone, PartialEq, Eq, Debug, Reflect)]
+#[reflect(PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct GridTemplateArea {
+    /// The name of the area, referenced by the grid items.
+    pub name: String,
+    /// The rows spanned by the area.
+    pub row: GridPlacement,
+    /// The columns spanned by the area.
+    pub column: GridPlacement,
+}
+
+impl GridTemplateArea {
+    /// Creates a named area spanning the given rows and columns.
+    pub fn new(name: impl Into<String>, row: GridPlacement, column: GridPlacement) -> Self {
+        Self {
+            name: name.into(),
+            row,
+            column,
+        }
+    }
+
+    /// Parses the areas of a grid from the rows of a CSS `grid-template-areas` declaration.
+    ///
+    /// Each row lists the names of the areas its cells belong to, separated by whitespace.
+    /// Cells that don't belong to any area are written with one or more `.`.
+    ///
+    /// ```
+    /// # use bevy_ui::GridTemplateArea;
+    /// let areas = GridTemplateArea::from_rows([
+    ///     "header header",
+    ///     "sidebar main",
+    ///     ".       footer",
+    /// ])
+    /// .unwrap();
+    /// assert_eq!(areas.len(), 4);
+    /// ```
+    pub fn from_rows<'a>(
+        rows: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Vec<Self>, GridTemplateAreasError> {
+        // The names of the areas, with the first and last rows and columns of their cells.
+        let mut bounds: Vec<(&str, [usize; 4], usize)> = Vec::new();
+        let mut column_count = None;
+        for (row, cells) in rows.into_iter().enumerate() {
+            let mut columns = 0;
+            for (column, cell) in cells.split_whitespace().enumerate() {
+                columns += 1;
+                if cell.chars().all(|c| c == '.') {
+                    continue;
+                }
+                match bounds.iter_mut().find(|(name, ..)| *name == cell) {
+                    Some((_, [_, last_row, first_column, last_column], cell_count)) => {
+                        *last_row = row;
+                        *first_column = (*first_column).min(column);
+                        *last_column = (*last_column).max(column);
+                        *cell_count += 1;
+                    }
+                    None => bounds.push((cell, [row, row, column, column], 1)),
+                }
+            }
+            match column_count {
+                None => column_count = Some(columns),
+                Some(expected) if expected != columns => {
+                    return Err(GridTemplateAreasError::MismatchedColumnCount {
+                        row,
+                        expected,
+                        found: columns,
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+
+        bounds
+            .into_iter()
+            .map(
+                |(name, [first_row, last_row, first_column, last_column], cell_count)| {
+                    // The area is a rectangle if it fills its bounding box.
+                    let rows = last_row - first_row + 1;
+                    let columns = last_column - first_column + 1;
+                    if rows * columns != cell_count {
+                        return Err(GridTemplateAreasError::NonRectangularArea(name.to_string()));
+                    }
+                    let line = |index: usize| {
+                        i16::try_from(index + 1).map_err(|_| GridTemplateAreasError::TooManyTracks)
+                    };
+                    Ok(Self::new(
+                        name,
+                        GridPlacement::start_end(line(first_row)?, line(last_row + 1)?),
+                        GridPlacement::start_end(line(first_column)?, line(last_column + 1)?),
+                    ))
+                },
+            )
+            .collect()
+    }
+}
+
+/// Errors that occur when parsing the areas of a grid with [`GridTemplateArea::from_rows`].
+#[derive(Debug, Eq, PartialEq, Clone, Error)]
+pub enum GridTemplateAreasError {
+    #[error("Row {row} has {found} columns, but the previous rows have {expected} columns")]
+    MismatchedColumnCount {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    #[error("The cells of the grid area `{0}` don't form a rectangle")]
+    NonRectangularArea(String),
+    #[error("Grid areas can span at most `i16::MAX - 1` tracks")]
+    TooManyTracks,
+}
+
 /// The background color of the node
 ///
 /// This serves as the "fill" color.
@@ -1820,6 +2059,122 @@ impl Outline {
     }
 }
 
+/// The style of the border of a UI node, drawn with its [`BorderColor`].
+///
+/// Dashes are laid out along each edge of the node, starting from its top left corner.
+#[derive(Component, Copy, Clone, Default, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum BorderStyle {
+    /// A continuous border.
+    #[default]
+    Solid,
+    /// A border made of dashes.
+    Dashed {
+        /// The length of the dashes, in logical pixels.
+        dash_length: f32,
+        /// The length of the gaps between the dashes, in logical pixels.
+        gap_length: f32,
+    },
+    /// A border made of square dots as long as the border is thick.
+    Dotted,
+}
+
+/// Adds a shadow behind a UI node, following its [`BorderRadius`].
+///
+/// Box shadows do not take up space in the layout, and are drawn under the node's background.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ui::prelude::*;
+/// # use bevy_color::Color;
+/// fn setup_ui(mut commands: Commands) {
+///     commands.spawn((
+///         NodeBundle {
+///             style: Style {
+///                 width: Val::Px(100.),
+///                 height: Val::Px(100.),
+///                 ..Default::default()
+///             },
+///             background_color: Color::WHITE.into(),
+///             border_radius: BorderRadius::all(Val::Px(10.)),
+///             ..Default::default()
+///         },
+///         BoxShadow::new(
+///             Color::srgba(0., 0., 0., 0.5),
+///             Val::Px(4.),
+///             Val::Px(4.),
+///             Val::ZERO,
+///             Val::Px(8.),
+///         ),
+///     ));
+/// }
+/// ```
+#[derive(Component, Copy, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct BoxShadow {
+    /// The color of the shadow.
+    pub color: Color,
+    /// The horizontal offset of the shadow, positive values move it to the right.
+    ///
+    /// Percentage `Val` values are resolved based on the width of the [`Node`].
+    pub x_offset: Val,
+    /// The vertical offset of the shadow, positive values move it down.
+    ///
+    /// Percentage `Val` values are resolved based on the height of the [`Node`].
+    pub y_offset: Val,
+    /// How much the shadow is larger than the node on each side, negative values make it smaller.
+    ///
+    /// Percentage `Val` values are resolved based on the smallest side of the [`Node`].
+    pub spread_radius: Val,
+    /// How far the edges of the shadow fade out.
+    ///
+    /// Percentage `Val` values are resolved based on the smallest side of the [`Node`].
+    pub blur_radius: Val,
+}
+
+impl BoxShadow {
+    pub const DEFAULT: Self = Self {
+        color: Color::BLACK,
+        x_offset: Val::ZERO,
+        y_offset: Val::ZERO,
+        spread_radius: Val::ZERO,
+        blur_radius: Val::Px(4.),
+    };
+
+    /// Create a new box shadow
+    pub const fn new(
+        color: Color,
+        x_offset: Val,
+        y_offset: Val,
+        spread_radius: Val,
+        blur_radius: Val,
+    ) -> Self {
+        Self {
+            color,
+            x_offset,
+            y_offset,
+            spread_radius,
+            blur_radius,
+        }
+    }
+}
+
+impl Default for BoxShadow {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
 /// The 2D texture displayed for this UI node
 #[derive(Component, Clone, Debug, Reflect)]
 #[reflect(Component, Default)]
@@ -1918,6 +2273,35 @@ pub struct CalculatedClip {
     pub clip: Rect,
 }
 
+/// The opacity of the node and all its descendants, from `0.` (transparent) to `1.` (opaque).
+///
+/// Unlike the alpha of the node colors, the opacity applies to the node and its descendants as a
+/// group: when it's less than `1.`, they are rendered to an intermediate texture, which is then
+/// blended with the opacity. This way, the overlapping parts of the children of a panel that is
+/// faded out aren't blended with each other.
+///
+/// Nodes that clip their overflow with a [`BorderRadius`] are rendered the same way, so that their
+/// descendants are clipped to the rounded corners.
+#[derive(Component, Copy, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct Opacity(pub f32);
+
+impl Opacity {
+    /// Fully opaque, the node is rendered directly.
+    pub const DEFAULT: Self = Self(1.);
+}
+
+impl Default for Opacity {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
 /// Indicates that this [`Node`] entity's front-to-back ordering is not controlled solely
 /// by its location in the UI hierarchy. A node with a higher z-index will appear on top
 /// of other nodes with a lower z-index.
@@ -2211,7 +2595,7 @@ impl BorderRadius {
 
 #[cfg(test)]
 mod tests {
-    use crate::GridPlacement;
+    use crate::{GridPlacement, GridTemplateArea, GridTemplateAreasError};
 
     #[test]
     fn invalid_grid_placement_values() {
@@ -2238,6 +2622,49 @@ mod tests {
         assert_eq!(GridPlacement::start_span(3, 5).get_end(), None);
         assert_eq!(GridPlacement::end_span(-4, 12).get_start(), None);
     }
+
+    #[test]
+    fn grid_template_areas_from_rows() {
+        let areas =
+            GridTemplateArea::from_rows(["header header", "sidebar main", ".. main"]).unwrap();
+        assert_eq!(
+            areas,
+            vec![
+                GridTemplateArea::new(
+                    "header",
+                    GridPlacement::start_end(1, 2),
+                    GridPlacement::start_end(1, 3)
+                ),
+                GridTemplateArea::new(
+                    "sidebar",
+                    GridPlacement::start_end(2, 3),
+                    GridPlacement::start_end(1, 2)
+                ),
+                GridTemplateArea::new(
+                    "main",
+                    GridPlacement::start_end(2, 4),
+                    GridPlacement::start_end(2, 3)
+                ),
+            ]
+        );
+
+        assert_eq!(
+            GridTemplateArea::from_rows(["a a", "b"]),
+            Err(GridTemplateAreasError::MismatchedColumnCount {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            GridTemplateArea::from_rows(["a a", "a b"]),
+            Err(GridTemplateAreasError::NonRectangularArea("a".to_string()))
+        );
+        assert_eq!(
+            GridTemplateArea::from_rows(["a b a"]),
+            Err(GridTemplateAreasError::NonRectangularArea("a".to_string()))
+        );
+    }
 }
 
 /// Indicates that this root [`Node`] entity should be rendered to a specific camera.
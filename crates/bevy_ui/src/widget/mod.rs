@@ -5,9 +5,19 @@ mod image;
 mod label;
 #[cfg(feature = "bevy_text")]
 mod text;
+#[cfg(feature = "bevy_text")]
+mod text_input;
+#[cfg(feature = "bevy_text")]
+mod text_link;
+mod virtual_list;
 
 pub use button::*;
 pub use image::*;
 pub use label::*;
 #[cfg(feature = "bevy_text")]
 pub use text::*;
+#[cfg(feature = "bevy_text")]
+pub use text_input::*;
+#[cfg(feature = "bevy_text")]
+pub use text_link::*;
+pub use virtual_list::*;
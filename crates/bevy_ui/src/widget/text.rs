@@ -84,12 +84,13 @@ impl Measure for TextMeasure {
 #[inline]
 fn create_text_measure(
     fonts: &Assets<Font>,
+    text_settings: &TextSettings,
     scale_factor: f32,
     text: Ref<Text>,
     mut content_size: Mut<ContentSize>,
     mut text_flags: Mut<TextFlags>,
 ) {
-    match TextMeasureInfo::from_text(&text, fonts, scale_factor) {
+    match TextMeasureInfo::from_text(&text, fonts, text_settings, scale_factor) {
         Ok(measure) => {
             if text.linebreak_behavior == BreakLineOn::NoWrap {
                 content_size.set(NodeMeasure::Fixed(FixedMeasure { size: measure.max }));
@@ -123,6 +124,7 @@ fn create_text_measure(
 pub fn measure_text_system(
     mut last_scale_factors: Local<EntityHashMap<f32>>,
     fonts: Res<Assets<Font>>,
+    text_settings: Res<TextSettings>,
     camera_query: Query<(Entity, &Camera)>,
     default_ui_camera: DefaultUiCamera,
     ui_scale: Res<UiScale>,
@@ -158,8 +160,16 @@ pub fn measure_text_system(
             || text.is_changed()
             || text_flags.needs_new_measure_func
             || content_size.is_added()
+            || text_settings.is_changed()
         {
-            create_text_measure(&fonts, scale_factor, text, content_size, text_flags);
+            create_text_measure(
+                &fonts,
+                &text_settings,
+                scale_factor,
+                text,
+                content_size,
+                text_flags,
+            );
         }
     }
     *last_scale_factors = scale_factors;
@@ -200,6 +210,8 @@ fn queue_text(
             scale_factor,
             text.justify,
             text.linebreak_behavior,
+            text.rendering,
+            &text.tab_stops,
             physical_node_size,
             font_atlas_sets,
             texture_atlases,
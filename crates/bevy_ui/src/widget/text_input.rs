@@ -0,0 +1,833 @@
+//! A single line text field, with a blinking caret, mouse and keyboard selection, clipboard
+//! shortcuts and IME composition.
+
+use crate::{
+    node_bundles::{NodeBundle, TextBundle},
+    BackgroundColor, Interaction, Node, Overflow, PositionType, RelativeCursorPosition, Style,
+    UiFocus, Val,
+};
+use bevy_asset::Assets;
+use bevy_color::Color;
+use bevy_ecs::{prelude::*, query::QueryData};
+use bevy_hierarchy::{BuildChildren, ChildBuild, DespawnRecursiveExt};
+use bevy_input::{
+    keyboard::{Key, KeyCode, KeyboardInput},
+    mouse::MouseButton,
+    ButtonInput,
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::view::Visibility;
+use bevy_text::{Font, Text, TextSection, TextStyle};
+use bevy_time::Time;
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::default;
+use bevy_window::{Clipboard, Ime, PrimaryWindow, Window};
+use std::{ops::Range, time::Duration};
+
+/// A single line text field.
+///
+/// The value is edited with the keyboard and the IME of the platform while the field has the
+/// [`UiFocus`], which it gets when it's clicked or with tab. Mouse drags and the arrow keys with shift
+/// select text, that can be copied, cut and pasted with the [`Clipboard`] by pressing ctrl (or
+/// cmd) with `C`, `X` and `V`. The [`Clipboard`] is only shared within the app, not with the
+/// operating system.
+///
+/// A [`TextInputChanged`] event is sent when the value is edited, and a [`TextInputSubmitted`]
+/// event when enter is pressed. The text, caret, selection and IME composition are drawn by child
+/// nodes spawned when the component is added, styled with the [`TextInputStyle`] of the field.
+#[derive(Component, Debug, Clone, Default, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct TextInput {
+    value: String,
+    /// The byte index of the caret in the value.
+    cursor: usize,
+    /// The byte index of the other end of the selection, equal to the cursor without selection.
+    anchor: usize,
+    /// The text being composed with the IME, not yet part of the value.
+    preedit: String,
+    /// The byte index of the caret in the preedit text, `None` if the IME hides it.
+    preedit_cursor: Option<usize>,
+    /// The largest number of characters of the value, `None` for no limit.
+    pub max_length: Option<usize>,
+}
+
+impl TextInput {
+    /// Creates a text input with the given value and the caret at its end.
+    pub fn new(value: impl Into<String>) -> Self {
+        let mut input = Self::default();
+        input.set_value(value);
+        input
+    }
+
+    /// Returns this [`TextInput`] with the given [`TextInput::max_length`], truncating the value.
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        let value = std::mem::take(&mut self.value);
+        self.set_value(value);
+        self
+    }
+
+    /// The text in the field.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Replaces the text in the field, moving the caret at its end.
+    ///
+    /// The value is truncated to the [`TextInput::max_length`].
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        let mut value = value.into();
+        if let Some((index, _)) = self
+            .max_length
+            .and_then(|max_length| value.char_indices().nth(max_length))
+        {
+            value.truncate(index);
+        }
+        self.value = value;
+        self.cursor = self.value.len();
+        self.anchor = self.cursor;
+    }
+
+    /// The byte index of the caret in the value.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Moves the caret to the byte index `cursor`, clamped to the value and moved back to the
+    /// start of its character. The selection is extended if `extend` is `true`, and cleared
+    /// otherwise.
+    pub fn set_cursor(&mut self, cursor: usize, extend: bool) {
+        let mut cursor = cursor.min(self.value.len());
+        while !self.value.is_char_boundary(cursor) {
+            cursor -= 1;
+        }
+        self.cursor = cursor;
+        if !extend {
+            self.anchor = cursor;
+        }
+    }
+
+    /// The byte range of the selected text in the value, empty without selection.
+    pub fn selection(&self) -> Range<usize> {
+        self.cursor.min(self.anchor)..self.cursor.max(self.anchor)
+    }
+
+    /// The selected text.
+    pub fn selected_text(&self) -> &str {
+        &self.value[self.selection()]
+    }
+
+    /// Selects the whole value.
+    pub fn select_all(&mut self) {
+        self.anchor = 0;
+        self.cursor = self.value.len();
+    }
+
+    /// The text being composed with the IME, shown at the caret until it's committed.
+    pub fn preedit(&self) -> &str {
+        &self.preedit
+    }
+
+    /// Returns `true` while text is being composed with the IME.
+    pub fn is_composing(&self) -> bool {
+        !self.preedit.is_empty()
+    }
+
+    /// Inserts `text` at the caret, replacing the selection.
+    ///
+    /// Control characters like line breaks are skipped, and the characters that don't fit in the
+    /// [`TextInput::max_length`] are dropped. Returns `true` if the value changed.
+    pub fn insert(&mut self, text: &str) -> bool {
+        let deleted = self.delete_selection();
+        let mut available = self.max_length.map_or(usize::MAX, |max_length| {
+            max_length.saturating_sub(self.value.chars().count())
+        });
+        let mut inserted = String::new();
+        for character in text.chars().filter(|character| !character.is_control()) {
+            if available == 0 {
+                break;
+            }
+            inserted.push(character);
+            available -= 1;
+        }
+        self.value.insert_str(self.cursor, &inserted);
+        self.set_cursor(self.cursor + inserted.len(), false);
+        deleted || !inserted.is_empty()
+    }
+
+    /// Deletes the selected text. Returns `true` if there was a selection.
+    pub fn delete_selection(&mut self) -> bool {
+        let selection = self.selection();
+        if selection.is_empty() {
+            return false;
+        }
+        self.value.replace_range(selection.clone(), "");
+        self.set_cursor(selection.start, false);
+        true
+    }
+
+    /// Deletes the selection, or the character before the caret. Returns `true` if the value
+    /// changed.
+    pub fn delete_backward(&mut self) -> bool {
+        if self.delete_selection() {
+            return true;
+        }
+        let start = self.previous_boundary(self.cursor);
+        self.value.replace_range(start..self.cursor, "");
+        let changed = start != self.cursor;
+        self.set_cursor(start, false);
+        changed
+    }
+
+    /// Deletes the selection, or the character after the caret. Returns `true` if the value
+    /// changed.
+    pub fn delete_forward(&mut self) -> bool {
+        if self.delete_selection() {
+            return true;
+        }
+        let end = self.next_boundary(self.cursor);
+        self.value.replace_range(self.cursor..end, "");
+        end != self.cursor
+    }
+
+    /// Moves the caret one character to the left.
+    ///
+    /// Without `extend`, a selection is cleared and the caret moved to its start instead.
+    pub fn move_left(&mut self, extend: bool) {
+        let selection = self.selection();
+        if !extend && !selection.is_empty() {
+            self.set_cursor(selection.start, false);
+        } else {
+            self.set_cursor(self.previous_boundary(self.cursor), extend);
+        }
+    }
+
+    /// Moves the caret one character to the right.
+    ///
+    /// Without `extend`, a selection is cleared and the caret moved to its end instead.
+    pub fn move_right(&mut self, extend: bool) {
+        let selection = self.selection();
+        if !extend && !selection.is_empty() {
+            self.set_cursor(selection.end, false);
+        } else {
+            self.set_cursor(self.next_boundary(self.cursor), extend);
+        }
+    }
+
+    /// Moves the caret to the start of the current or previous word.
+    pub fn move_word_left(&mut self, extend: bool) {
+        let before = &self.value[..self.cursor];
+        let word_end = before.trim_end().len();
+        let word_start = before[..word_end]
+            .rfind(char::is_whitespace)
+            .map_or(0, |index| self.next_boundary(index));
+        self.set_cursor(word_start, extend);
+    }
+
+    /// Moves the caret to the end of the current or next word.
+    pub fn move_word_right(&mut self, extend: bool) {
+        let after = &self.value[self.cursor..];
+        let word_start = after.len() - after.trim_start().len();
+        let word_end = after[word_start..]
+            .find(char::is_whitespace)
+            .map_or(after.len(), |index| word_start + index);
+        self.set_cursor(self.cursor + word_end, extend);
+    }
+
+    /// Moves the caret to the start of the value.
+    pub fn move_to_start(&mut self, extend: bool) {
+        self.set_cursor(0, extend);
+    }
+
+    /// Moves the caret to the end of the value.
+    pub fn move_to_end(&mut self, extend: bool) {
+        self.set_cursor(self.value.len(), extend);
+    }
+
+    /// The text displayed in the field: the value with the IME composition at the caret.
+    pub fn displayed_text(&self) -> String {
+        let mut text = self.value.clone();
+        text.insert_str(self.cursor, &self.preedit);
+        text
+    }
+
+    fn previous_boundary(&self, index: usize) -> usize {
+        self.value[..index]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(index, _)| index)
+    }
+
+    fn next_boundary(&self, index: usize) -> usize {
+        self.value[index..]
+            .chars()
+            .next()
+            .map_or(index, |character| index + character.len_utf8())
+    }
+
+    fn set_preedit(&mut self, preedit: String, cursor: Option<usize>) {
+        self.preedit = preedit;
+        self.preedit_cursor = cursor;
+    }
+}
+
+/// How a [`TextInput`] is drawn.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct TextInputStyle {
+    /// The font, size and color of the text.
+    pub text_style: TextStyle,
+    /// The color of the caret.
+    pub caret_color: Color,
+    /// The width of the caret, in logical pixels.
+    pub caret_width: f32,
+    /// How long the caret stays visible, then hidden, while it blinks. The caret doesn't blink
+    /// if this is zero.
+    pub caret_blink_period: Duration,
+    /// The color drawn behind the selected text.
+    pub selection_color: Color,
+    /// The color of the text being composed with the IME, and of the line under it.
+    pub composition_color: Color,
+}
+
+impl Default for TextInputStyle {
+    fn default() -> Self {
+        Self {
+            text_style: default(),
+            caret_color: Color::WHITE,
+            caret_width: 2.,
+            caret_blink_period: Duration::from_millis(530),
+            selection_color: Color::srgba(0.3, 0.5, 0.9, 0.6),
+            composition_color: Color::srgb(0.9, 0.9, 0.6),
+        }
+    }
+}
+
+/// Sent when the value of a [`TextInput`] is edited by the user.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct TextInputChanged {
+    /// The [`TextInput`] entity.
+    pub entity: Entity,
+    /// The new value.
+    pub value: String,
+}
+
+/// Sent when enter is pressed in a [`TextInput`].
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct TextInputSubmitted {
+    /// The [`TextInput`] entity.
+    pub entity: Entity,
+    /// The submitted value.
+    pub value: String,
+}
+
+/// The child nodes drawing a [`TextInput`], spawned by [`spawn_text_input_system`].
+///
+/// The viewport clips the content, which is moved to the left to keep the caret visible.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct TextInputNodes {
+    pub viewport: Entity,
+    pub content: Entity,
+    pub selection: Entity,
+    pub text: Entity,
+    pub composition: Entity,
+    pub caret: Entity,
+    /// How far the content is scrolled to the left, in logical pixels.
+    pub scroll: f32,
+    /// When the caret started blinking, reset when the input is edited.
+    pub blink_start: Duration,
+}
+
+/// Spawns the [`TextInputNodes`] of the added [`TextInput`]s, and despawns them when the
+/// component is removed.
+pub fn spawn_text_input_system(
+    mut commands: Commands,
+    inputs: Query<(Entity, &TextInputStyle), Added<TextInput>>,
+    nodes: Query<&TextInputNodes>,
+    mut removed: RemovedComponents<TextInput>,
+    time: Res<Time>,
+) {
+    for entity in removed.read() {
+        let Ok(nodes) = nodes.get(entity) else {
+            continue;
+        };
+        commands.entity(nodes.viewport).despawn_recursive();
+        if let Some(mut entity) = commands.get_entity(entity) {
+            entity.remove::<TextInputNodes>();
+        }
+    }
+
+    let overlay = |color: Color| NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(0.),
+            height: Val::Percent(100.),
+            ..default()
+        },
+        background_color: color.into(),
+        visibility: Visibility::Hidden,
+        ..default()
+    };
+    for (entity, input_style) in &inputs {
+        let mut nodes = None;
+        commands.entity(entity).with_children(|parent| {
+            let mut viewport = parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        flex_grow: 1.,
+                        min_width: Val::Px(0.),
+                        overflow: Overflow::clip(),
+                        ..default()
+                    },
+                    ..default()
+                },
+                RelativeCursorPosition::default(),
+            ));
+            let viewport_entity = viewport.id();
+            viewport.with_children(|parent| {
+                let mut content = parent.spawn(NodeBundle {
+                    style: Style {
+                        flex_shrink: 0.,
+                        ..default()
+                    },
+                    ..default()
+                });
+                let content_entity = content.id();
+                content.with_children(|parent| {
+                    let selection = parent.spawn(overlay(input_style.selection_color)).id();
+                    let text = parent.spawn(TextBundle::default().with_no_wrap()).id();
+                    let composition = parent.spawn(overlay(input_style.composition_color)).id();
+                    let caret = parent.spawn(overlay(input_style.caret_color)).id();
+                    nodes = Some(TextInputNodes {
+                        viewport: viewport_entity,
+                        content: content_entity,
+                        selection,
+                        text,
+                        composition,
+                        caret,
+                        scroll: 0.,
+                        blink_start: time.elapsed(),
+                    });
+                });
+            });
+        });
+        if let Some(nodes) = nodes {
+            commands.entity(entity).insert(nodes);
+        }
+    }
+}
+
+/// Returns the horizontal position of the caret before each character of `text` in a
+/// [`TextInput`], followed by its position after the last character.
+fn caret_positions(fonts: &Assets<Font>, style: &TextStyle, text: &str) -> Option<Vec<f32>> {
+    fonts
+        .get(&style.font)
+        .map(|font| font.caret_positions(text, style.font_size))
+}
+
+/// Returns the byte index in `text` of the caret position closest to `x`.
+fn cursor_at(text: &str, positions: &[f32], x: f32) -> usize {
+    let closest = positions
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| (*a - x).abs().total_cmp(&(*b - x).abs()))
+        .map_or(0, |(index, _)| index);
+    text.char_indices()
+        .nth(closest)
+        .map_or(text.len(), |(index, _)| index)
+}
+
+/// Moves the caret of the [`TextInput`]s when they are clicked, selecting text while the mouse
+/// is dragged.
+pub fn text_input_mouse_system(
+    mut dragged: Local<Option<Entity>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    fonts: Res<Assets<Font>>,
+    mut inputs: Query<(
+        Entity,
+        &mut TextInput,
+        &TextInputStyle,
+        &Interaction,
+        &TextInputNodes,
+    )>,
+    viewports: Query<(&Node, &RelativeCursorPosition)>,
+) {
+    if mouse.just_pressed(MouseButton::Left) {
+        *dragged = inputs
+            .iter()
+            .find(|(.., interaction, _)| **interaction == Interaction::Pressed)
+            .map(|(entity, ..)| entity);
+    } else if !mouse.pressed(MouseButton::Left) {
+        *dragged = None;
+    }
+
+    let Some(entity) = *dragged else {
+        return;
+    };
+    let Ok((_, mut input, input_style, _, nodes)) = inputs.get_mut(entity) else {
+        *dragged = None;
+        return;
+    };
+    if input.is_composing() {
+        return;
+    }
+    let Ok((viewport, cursor)) = viewports.get(nodes.viewport) else {
+        return;
+    };
+    let (Some(cursor), Some(positions)) = (
+        cursor.normalized,
+        caret_positions(&fonts, &input_style.text_style, input.value()),
+    ) else {
+        return;
+    };
+    let x = cursor.x * viewport.size().x + nodes.scroll;
+    let index = cursor_at(input.value(), &positions, x);
+    let extend = !mouse.just_pressed(MouseButton::Left)
+        || keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    if index != input.cursor() || (!extend && !input.selection().is_empty()) {
+        input.set_cursor(index, extend);
+    }
+}
+
+/// Edits the focused [`TextInput`] with the keyboard, the IME and the [`Clipboard`], and sends
+/// the [`TextInputChanged`] and [`TextInputSubmitted`] events.
+#[allow(clippy::too_many_arguments)]
+pub fn text_input_keyboard_system(
+    mut focus: ResMut<UiFocus>,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut ime_events: EventReader<Ime>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut clipboard: ResMut<Clipboard>,
+    mut inputs: Query<&mut TextInput>,
+    mut changed_events: EventWriter<TextInputChanged>,
+    mut submitted_events: EventWriter<TextInputSubmitted>,
+) {
+    let Some((entity, mut input)) = focus
+        .entity
+        .and_then(|entity| Some((entity, inputs.get_mut(entity).ok()?)))
+    else {
+        keyboard_events.clear();
+        ime_events.clear();
+        return;
+    };
+
+    let mut changed = false;
+    for event in ime_events.read() {
+        match event {
+            Ime::Preedit { value, cursor, .. } => {
+                if value.is_empty() && !input.is_composing() {
+                    continue;
+                }
+                input.set_preedit(value.clone(), cursor.map(|(_, end)| end));
+            }
+            Ime::Commit { value, .. } => {
+                input.set_preedit(String::new(), None);
+                changed |= input.insert(value);
+            }
+            Ime::Disabled { .. } => input.set_preedit(String::new(), None),
+            Ime::Enabled { .. } => {}
+        }
+    }
+
+    let command = keys.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]);
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    for event in keyboard_events.read() {
+        // the keys are handled by the IME while composing
+        if !event.state.is_pressed() || input.is_composing() {
+            continue;
+        }
+        match &event.logical_key {
+            Key::ArrowLeft if command => input.move_word_left(shift),
+            Key::ArrowLeft => input.move_left(shift),
+            Key::ArrowRight if command => input.move_word_right(shift),
+            Key::ArrowRight => input.move_right(shift),
+            Key::Home => input.move_to_start(shift),
+            Key::End => input.move_to_end(shift),
+            Key::Backspace => changed |= input.delete_backward(),
+            Key::Delete => changed |= input.delete_forward(),
+            Key::Enter => {
+                submitted_events.send(TextInputSubmitted {
+                    entity,
+                    value: input.value().to_string(),
+                });
+            }
+            Key::Escape => *focus = UiFocus::default(),
+            Key::Space if !command => changed |= input.insert(" "),
+            Key::Character(character) if command => match character.to_lowercase().as_str() {
+                "a" => input.select_all(),
+                "c" if !input.selection().is_empty() => {
+                    clipboard.set_text(input.selected_text());
+                }
+                "x" if !input.selection().is_empty() => {
+                    clipboard.set_text(input.selected_text());
+                    changed |= input.delete_selection();
+                }
+                "v" => changed |= input.insert(clipboard.text()),
+                _ => {}
+            },
+            Key::Character(character) => changed |= input.insert(character),
+            _ => {}
+        }
+    }
+
+    if changed {
+        changed_events.send(TextInputChanged {
+            entity,
+            value: input.value().to_string(),
+        });
+    }
+}
+
+/// Main query for [`update_text_input_system`]
+#[derive(QueryData)]
+#[query_data(mutable)]
+pub struct TextInputQuery {
+    entity: Entity,
+    input: Ref<'static, TextInput>,
+    input_style: Ref<'static, TextInputStyle>,
+    nodes: &'static mut TextInputNodes,
+}
+
+/// Updates the [`TextInputNodes`] of the [`TextInput`]s: the displayed text, the selection, the
+/// IME composition and the blinking caret, scrolling the content to keep the caret visible.
+///
+/// The IME of the primary window is enabled while a [`TextInput`] is focused, with its candidate
+/// box under the caret.
+#[allow(clippy::too_many_arguments)]
+pub fn update_text_input_system(
+    mut ime_enabled: Local<bool>,
+    focus: Res<UiFocus>,
+    time: Res<Time>,
+    fonts: Res<Assets<Font>>,
+    mut inputs: Query<TextInputQuery>,
+    mut texts: Query<&mut Text>,
+    mut parts: Query<(&mut Style, &mut Visibility, &mut BackgroundColor)>,
+    nodes: Query<(&Node, &GlobalTransform)>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let mut ime_position = None;
+    for mut item in &mut inputs {
+        let input = &*item.input;
+        let input_style = &*item.input_style;
+        let nodes_changed = item.nodes.is_added();
+        let focused = focus.is_focused(item.entity);
+        if item.input.is_changed() || focus.is_changed() {
+            item.nodes.blink_start = time.elapsed();
+        }
+
+        let displayed = input.displayed_text();
+        let text_changed = item.input.is_changed() || item.input_style.is_changed();
+        if let Ok(mut text) = texts.get_mut(item.nodes.text) {
+            if text_changed || nodes_changed {
+                let cursor = input.cursor();
+                let preedit_end = cursor + input.preedit().len();
+                let section = |value: &str, color: Color| TextSection {
+                    value: value.to_string(),
+                    style: TextStyle {
+                        color,
+                        ..input_style.text_style.clone()
+                    },
+                };
+                let text_color = input_style.text_style.color;
+                text.sections = vec![
+                    section(&displayed[..cursor], text_color),
+                    section(
+                        &displayed[cursor..preedit_end],
+                        input_style.composition_color,
+                    ),
+                    section(&displayed[preedit_end..], text_color),
+                ];
+            }
+        }
+
+        let Some(font) = fonts.get(&input_style.text_style.font) else {
+            continue;
+        };
+        let font_size = input_style.text_style.font_size;
+        let positions = font.caret_positions(&displayed, font_size);
+        let line_height = font.line_height(font_size);
+        let char_index = |byte_index: usize| displayed[..byte_index].chars().count();
+        let x_at = |byte_index: usize| positions[char_index(byte_index)];
+
+        // the caret is in the composition while composing
+        let caret = input.cursor() + input.preedit_cursor.unwrap_or(input.preedit().len());
+        let caret_x = x_at(caret);
+
+        let viewport_width = nodes
+            .get(item.nodes.viewport)
+            .map_or(0., |(node, _)| node.size().x);
+        let content_width = positions.last().copied().unwrap_or(0.) + input_style.caret_width;
+        let mut scroll = item.nodes.scroll;
+        if caret_x < scroll {
+            scroll = caret_x;
+        } else if caret_x + input_style.caret_width > scroll + viewport_width {
+            scroll = caret_x + input_style.caret_width - viewport_width;
+        }
+        scroll = scroll.clamp(0., (content_width - viewport_width).max(0.));
+        if item.nodes.scroll != scroll {
+            item.nodes.scroll = scroll;
+        }
+
+        let mut update_part = |entity: Entity, left: f32, width: f32, visible: bool, color| {
+            let Ok((mut style, mut visibility, mut background_color)) = parts.get_mut(entity)
+            else {
+                return;
+            };
+            let new_style = Style {
+                left: Val::Px(left),
+                width: Val::Px(width),
+                ..style.clone()
+            };
+            style.set_if_neq(new_style);
+            visibility.set_if_neq(if visible {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            });
+            background_color.set_if_neq(BackgroundColor(color));
+        };
+
+        let selection = input.selection();
+        // the selection is hidden while composing
+        let (selection_start, selection_end) = if input.is_composing() {
+            (0., 0.)
+        } else {
+            (x_at(selection.start), x_at(selection.end))
+        };
+        update_part(
+            item.nodes.selection,
+            selection_start,
+            selection_end - selection_start,
+            focused && !selection.is_empty() && !input.is_composing(),
+            input_style.selection_color,
+        );
+
+        let composition_start = x_at(input.cursor());
+        let composition_end = x_at(input.cursor() + input.preedit().len());
+        update_part(
+            item.nodes.composition,
+            composition_start,
+            composition_end - composition_start,
+            focused && input.is_composing(),
+            input_style.composition_color,
+        );
+
+        let period = input_style.caret_blink_period;
+        let blink_on = period.is_zero()
+            || ((time.elapsed() - item.nodes.blink_start).as_secs_f32() / period.as_secs_f32())
+                % 2.
+                < 1.;
+        update_part(
+            item.nodes.caret,
+            caret_x,
+            input_style.caret_width,
+            focused && blink_on,
+            input_style.caret_color,
+        );
+
+        if let Ok((mut style, ..)) = parts.get_mut(item.nodes.content) {
+            let new_style = Style {
+                left: Val::Px(-scroll),
+                min_width: Val::Px(content_width),
+                min_height: Val::Px(line_height),
+                ..style.clone()
+            };
+            style.set_if_neq(new_style);
+        }
+
+        if focused {
+            ime_position = nodes
+                .get(item.nodes.viewport)
+                .ok()
+                .map(|(node, transform)| {
+                    let rect = node.logical_rect(transform);
+                    bevy_math::Vec2::new(rect.min.x + caret_x - scroll, rect.max.y)
+                });
+        }
+    }
+
+    let Ok(mut window) = windows.get_single_mut() else {
+        return;
+    };
+    match ime_position {
+        Some(position) => {
+            if !window.ime_enabled || window.ime_position != position {
+                window.ime_enabled = true;
+                window.ime_position = position;
+            }
+            *ime_enabled = true;
+        }
+        None if *ime_enabled => {
+            window.ime_enabled = false;
+            *ime_enabled = false;
+        }
+        None => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TextInput;
+
+    #[test]
+    fn insert_and_delete() {
+        let mut input = TextInput::new("héllo");
+        assert_eq!(input.cursor(), "héllo".len());
+
+        input.move_left(false);
+        input.move_left(false);
+        assert!(input.insert("\n!"));
+        assert_eq!(input.value(), "hél!lo");
+
+        assert!(input.delete_backward());
+        assert!(input.delete_backward());
+        assert_eq!(input.value(), "hélo");
+        input.move_to_start(false);
+        assert!(!input.delete_backward());
+        assert!(input.delete_forward());
+        assert_eq!(input.value(), "élo");
+        assert!(input.delete_forward());
+        assert_eq!(input.value(), "lo");
+    }
+
+    #[test]
+    fn selection() {
+        let mut input = TextInput::new("hello wide world");
+        input.move_word_left(true);
+        assert_eq!(input.selected_text(), "world");
+        input.move_word_left(true);
+        assert_eq!(input.selected_text(), "wide world");
+
+        assert!(input.insert("small"));
+        assert_eq!(input.value(), "hello small");
+        assert!(input.selection().is_empty());
+
+        input.move_to_start(false);
+        input.move_word_right(true);
+        assert_eq!(input.selected_text(), "hello");
+        input.move_right(false);
+        assert_eq!(input.cursor(), "hello".len());
+        assert!(input.selection().is_empty());
+
+        input.select_all();
+        assert!(input.delete_backward());
+        assert_eq!(input.value(), "");
+    }
+
+    #[test]
+    fn max_length() {
+        let mut input = TextInput::new("abcdef").with_max_length(4);
+        assert_eq!(input.value(), "abcd");
+        assert!(!input.insert("e"));
+
+        input.move_left(true);
+        assert!(input.insert("xyz"));
+        assert_eq!(input.value(), "abcx");
+    }
+}
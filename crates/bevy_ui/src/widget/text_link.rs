@@ -0,0 +1,192 @@
+//! Events for the links in the sections of UI text.
+
+use crate::{DefaultUiCamera, Interaction, Node, RelativeCursorPosition, TargetCamera, UiScale};
+use bevy_ecs::{entity::EntityHashMap, prelude::*};
+use bevy_reflect::Reflect;
+use bevy_render::camera::Camera;
+use bevy_text::{Text, TextLayoutInfo, TextLink};
+
+/// What happened to the link of a [`TextLinkEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum TextLinkEventKind {
+    /// The cursor moved over the glyphs of the link.
+    Over,
+    /// The cursor moved out of the glyphs of the link.
+    Out,
+    /// The link was pressed.
+    Click,
+}
+
+/// Sent when the glyphs of a section of UI text with a [`TextLink`] are hovered or pressed, see
+/// [`TextStyle::link`](bevy_text::TextStyle::link).
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct TextLinkEvent {
+    /// The text entity.
+    pub entity: Entity,
+    /// The index of the section of the link.
+    pub section: usize,
+    pub link: TextLink,
+    pub kind: TextLinkEventKind,
+}
+
+/// Sends the [`TextLinkEvent`]s of the links under the cursor.
+///
+/// The text entities with links are given an [`Interaction`] and a [`RelativeCursorPosition`]
+/// if they don't have them, so that their links are only hovered when the text is. A link is
+/// clicked when the text is pressed with the cursor over the glyphs of the link.
+#[allow(clippy::too_many_arguments)]
+pub fn text_link_system(
+    mut commands: Commands,
+    mut hovered_links: Local<EntityHashMap<usize>>,
+    camera_query: Query<&Camera>,
+    default_ui_camera: DefaultUiCamera,
+    ui_scale: Res<UiScale>,
+    texts: Query<(
+        Entity,
+        &Text,
+        &TextLayoutInfo,
+        &Node,
+        Option<Ref<Interaction>>,
+        Option<&RelativeCursorPosition>,
+        Option<&TargetCamera>,
+    )>,
+    mut events: EventWriter<TextLinkEvent>,
+) {
+    let mut previous_links = std::mem::take(&mut *hovered_links);
+    for (entity, text, layout_info, node, interaction, cursor_position, target_camera) in &texts {
+        let link = |section: usize| {
+            text.sections
+                .get(section)
+                .and_then(|section| section.style.link.clone())
+        };
+        let (Some(interaction), Some(cursor_position)) = (interaction, cursor_position) else {
+            if text
+                .sections
+                .iter()
+                .any(|section| section.style.link.is_some())
+            {
+                commands
+                    .entity(entity)
+                    .insert((Interaction::default(), RelativeCursorPosition::default()));
+            }
+            continue;
+        };
+
+        let scale_factor = target_camera
+            .map(TargetCamera::entity)
+            .or(default_ui_camera.get())
+            .and_then(|camera_entity| camera_query.get(camera_entity).ok())
+            .and_then(Camera::target_scaling_factor)
+            .unwrap_or(1.0)
+            * ui_scale.0;
+        // The glyphs are laid out in physical pixels from the top left corner of the node.
+        let hovered = cursor_position
+            .normalized
+            .filter(|_| *interaction != Interaction::None)
+            .and_then(|position| layout_info.section_at(position * node.size() * scale_factor))
+            .filter(|&section| link(section).is_some());
+
+        let previous = previous_links.remove(&entity);
+        if previous != hovered {
+            if let Some((section, link)) =
+                previous.and_then(|section| Some((section, link(section)?)))
+            {
+                events.send(TextLinkEvent {
+                    entity,
+                    section,
+                    link,
+                    kind: TextLinkEventKind::Out,
+                });
+            }
+            if let Some((section, link)) =
+                hovered.and_then(|section| Some((section, link(section)?)))
+            {
+                events.send(TextLinkEvent {
+                    entity,
+                    section,
+                    link,
+                    kind: TextLinkEventKind::Over,
+                });
+            }
+        }
+        if let Some(section) = hovered {
+            if interaction.is_changed() && *interaction == Interaction::Pressed {
+                events.send(TextLinkEvent {
+                    entity,
+                    section,
+                    link: link(section).unwrap(),
+                    kind: TextLinkEventKind::Click,
+                });
+            }
+            hovered_links.insert(entity, section);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{text_link_system, TextLinkEvent, TextLinkEventKind};
+    use crate::{Interaction, Node, RelativeCursorPosition, UiScale};
+    use bevy_ecs::{event::Events, world::World};
+    use bevy_math::{Rect, Vec2};
+    use bevy_text::{PositionedSpan, Text, TextLayoutInfo, TextSection, TextStyle};
+
+    #[test]
+    fn links_are_hovered_and_clicked() {
+        let mut world = World::new();
+        world.init_resource::<UiScale>();
+        world.init_resource::<Events<TextLinkEvent>>();
+        let span = |section_index, min_x, max_x| PositionedSpan {
+            section_index,
+            rect: Rect::new(min_x, 0., max_x, 20.),
+            underline: Rect::default(),
+            strikethrough: Rect::default(),
+        };
+        let text = world
+            .spawn((
+                Node {
+                    calculated_size: Vec2::new(100., 20.),
+                    ..Default::default()
+                },
+                Text::from_sections([
+                    TextSection::new("see ", TextStyle::default()),
+                    TextSection::new("here", TextStyle::default().with_link("help")),
+                ]),
+                TextLayoutInfo {
+                    spans: vec![span(0, 0., 40.), span(1, 40., 80.)],
+                    ..Default::default()
+                },
+            ))
+            .id();
+        let system = world.register_system(text_link_system);
+        world.run_system(system).unwrap();
+        assert!(world.get::<Interaction>(text).is_some());
+
+        let set_cursor = |world: &mut World, x: f32, interaction: Interaction| {
+            *world.get_mut::<Interaction>(text).unwrap() = interaction;
+            world
+                .get_mut::<RelativeCursorPosition>(text)
+                .unwrap()
+                .normalized = Some(Vec2::new(x, 0.5));
+            world.run_system(system).unwrap();
+            world
+                .resource_mut::<Events<TextLinkEvent>>()
+                .drain()
+                .map(|event| (event.section, event.kind))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(set_cursor(&mut world, 0.2, Interaction::Hovered), vec![]);
+        assert_eq!(
+            set_cursor(&mut world, 0.5, Interaction::Hovered),
+            vec![(1, TextLinkEventKind::Over)]
+        );
+        assert_eq!(
+            set_cursor(&mut world, 0.6, Interaction::Pressed),
+            vec![(1, TextLinkEventKind::Click)]
+        );
+        assert_eq!(
+            set_cursor(&mut world, 0.9, Interaction::Hovered),
+            vec![(1, TextLinkEventKind::Out)]
+        );
+    }
+}
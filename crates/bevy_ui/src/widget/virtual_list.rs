@@ -0,0 +1,285 @@
+//! A vertical list of items of the same height, that only spawns the rows of the visible items.
+
+use crate::{node_bundles::NodeBundle, Display, Node, PositionType, ScrollPosition, Style, Val};
+use bevy_ecs::{prelude::*, world::EntityWorldMut};
+use bevy_hierarchy::{BuildChildren, DespawnRecursiveExt};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_utils::{default, HashMap};
+use std::{fmt, ops::Range, sync::Arc};
+
+/// The callback filling the row of an item of a [`VirtualList`], see [`VirtualList::new`].
+pub type VirtualListProvider = Arc<dyn Fn(usize, &mut EntityWorldMut) + Send + Sync>;
+
+/// A vertical list of items of the same height, that only spawns and lays out the rows of the
+/// items in view, so that it can show a very large number of items.
+///
+/// Add it to a node scrolling vertically with [`Overflow::scroll_y`](crate::Overflow::scroll_y):
+/// the list spawns a child node as high as all the items, and the rows of the items visible
+/// through the [`ScrollPosition`] as its children, positioned absolutely. Each row has a
+/// [`VirtualListRow`] with the index of its item and is filled by the provider of the list.
+///
+/// Rows are recycled: when an item scrolls out of view, its row is reused for an item scrolling
+/// into view, and the provider is called again with that row, which keeps its components and
+/// children.
+#[derive(Component, Clone)]
+pub struct VirtualList {
+    /// The number of items in the list.
+    pub item_count: usize,
+    /// The height of each row, in logical pixels.
+    pub item_height: f32,
+    /// The number of rows spawned above and below the visible ones, so that they're ready before
+    /// they scroll into view.
+    pub overscan: usize,
+    provider: VirtualListProvider,
+    state: VirtualListState,
+}
+
+#[derive(Clone, Default)]
+struct VirtualListState {
+    content: Option<Entity>,
+    rows: HashMap<usize, Entity>,
+    recycled: Vec<Entity>,
+}
+
+impl VirtualList {
+    /// Creates a list of `item_count` items of height `item_height`.
+    ///
+    /// The rows of the items are filled by `provider`, which is called with the index of the item
+    /// and the row, a [`NodeBundle`] the first time. The row can be filled with any components,
+    /// and the [`Style`] fields positioning it are set after the provider is called.
+    pub fn new(
+        item_count: usize,
+        item_height: f32,
+        provider: impl Fn(usize, &mut EntityWorldMut) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            item_count,
+            item_height,
+            overscan: 2,
+            provider: Arc::new(provider),
+            state: default(),
+        }
+    }
+
+    /// Returns this [`VirtualList`] with `overscan` rows spawned above and below the visible ones.
+    pub fn with_overscan(mut self, overscan: usize) -> Self {
+        self.overscan = overscan;
+        self
+    }
+
+    /// Replaces the provider filling the rows. The visible rows are filled again.
+    pub fn set_provider(
+        &mut self,
+        provider: impl Fn(usize, &mut EntityWorldMut) + Send + Sync + 'static,
+    ) {
+        self.provider = Arc::new(provider);
+    }
+
+    /// The range of the items with a row, ordered by index.
+    pub fn spawned_items(&self) -> Range<usize> {
+        let start = self.state.rows.keys().min().copied().unwrap_or(0);
+        let end = self.state.rows.keys().max().map_or(0, |index| index + 1);
+        start..end
+    }
+
+    /// The row of the item at `index`, if it's spawned.
+    pub fn row(&self, index: usize) -> Option<Entity> {
+        self.state.rows.get(&index).copied()
+    }
+
+    /// The items with a row when the list of height `height` is scrolled by `offset`.
+    fn visible_items(&self, offset: f32, height: f32) -> Range<usize> {
+        if self.item_height <= 0. || height <= 0. {
+            return 0..0;
+        }
+        let start = (offset / self.item_height).floor().max(0.) as usize;
+        let end = ((offset + height) / self.item_height).ceil().max(0.) as usize;
+        start.saturating_sub(self.overscan).min(self.item_count)
+            ..end.saturating_add(self.overscan).min(self.item_count)
+    }
+}
+
+impl Default for VirtualList {
+    fn default() -> Self {
+        Self::new(0, 0., |_, _| {})
+    }
+}
+
+impl fmt::Debug for VirtualList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VirtualList")
+            .field("item_count", &self.item_count)
+            .field("item_height", &self.item_height)
+            .field("overscan", &self.overscan)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The row of an item of a [`VirtualList`].
+#[derive(Component, Debug, Copy, Clone, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct VirtualListRow {
+    /// The [`VirtualList`] entity.
+    pub list: Option<Entity>,
+    /// The index of the item shown by the row.
+    pub index: usize,
+}
+
+/// Spawns the rows of the items in view of the [`VirtualList`]s, recycling the rows of the items
+/// scrolled out of view.
+///
+/// The visible items are found from the size of the list computed by the last layout.
+pub fn update_virtual_list_system(
+    mut commands: Commands,
+    mut lists: Query<(Entity, &mut VirtualList, &Node, Option<&ScrollPosition>)>,
+) {
+    for (entity, mut list, node, scroll_position) in &mut lists {
+        let refresh = list.is_changed();
+        // only mark the list as changed when it's modified by the user
+        let list = list.bypass_change_detection();
+        let offset = scroll_position.map_or(0., |position| position.offset_y);
+        let visible = list.visible_items(offset, node.size().y);
+        let item_height = list.item_height;
+        let provider = &list.provider;
+        let VirtualListState {
+            content,
+            rows,
+            recycled,
+        } = &mut list.state;
+
+        let content = *content.get_or_insert_with(|| {
+            commands
+                .spawn(NodeBundle::default())
+                .set_parent(entity)
+                .id()
+        });
+        if refresh {
+            let height = Val::Px(list.item_count as f32 * item_height);
+            commands.add(move |world: &mut World| {
+                if let Some(mut style) = world.get_mut::<Style>(content) {
+                    style.width = Val::Percent(100.);
+                    style.height = height;
+                    style.min_height = height;
+                    style.flex_shrink = 0.;
+                }
+            });
+        }
+
+        // Rows of items out of view are recycled
+        rows.retain(|index, row| {
+            let keep = visible.contains(index);
+            if !keep {
+                recycled.push(*row);
+            }
+            keep
+        });
+
+        for index in visible.clone() {
+            let (row, new) = match rows.get(&index) {
+                Some(&row) => (row, refresh),
+                None => {
+                    let row = recycled.pop().unwrap_or_else(|| {
+                        commands
+                            .spawn(NodeBundle::default())
+                            .set_parent(content)
+                            .id()
+                    });
+                    rows.insert(index, row);
+                    (row, true)
+                }
+            };
+            if !new {
+                continue;
+            }
+            let provider = provider.clone();
+            commands.add(move |world: &mut World| {
+                let Some(mut row) = world.get_entity_mut(row) else {
+                    return;
+                };
+                row.insert(VirtualListRow {
+                    list: Some(entity),
+                    index,
+                });
+                provider(index, &mut row);
+                if let Some(mut style) = row.get_mut::<Style>() {
+                    if style.display == Display::None {
+                        style.display = Display::Flex;
+                    }
+                    style.position_type = PositionType::Absolute;
+                    style.left = Val::Px(0.);
+                    style.right = Val::Px(0.);
+                    style.top = Val::Px(index as f32 * item_height);
+                    style.height = Val::Px(item_height);
+                }
+            });
+        }
+
+        // Hide the recycled rows that aren't reused, and despawn those that won't be needed
+        let needed = visible.len();
+        while recycled.len() > needed {
+            commands.entity(recycled.pop().unwrap()).despawn_recursive();
+        }
+        for &row in recycled.iter() {
+            commands.add(move |world: &mut World| {
+                if let Some(mut style) = world.get_mut::<Style>(row) {
+                    style.display = Display::None;
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{update_virtual_list_system, VirtualList, VirtualListRow};
+    use crate::{Node, ScrollPosition, Style, Val};
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+    use bevy_math::Vec2;
+
+    #[test]
+    fn only_visible_rows_are_spawned_and_recycled() {
+        let mut world = World::new();
+        let list = world
+            .spawn((
+                Node {
+                    calculated_size: Vec2::new(100., 100.),
+                    ..Default::default()
+                },
+                ScrollPosition::default(),
+                VirtualList::new(10_000, 20., |_, _| {}).with_overscan(1),
+            ))
+            .id();
+        world.run_system_once(update_virtual_list_system);
+        let rows = |world: &mut World| {
+            let mut rows = world
+                .query::<(&VirtualListRow, &Style)>()
+                .iter(world)
+                .filter(|(_, style)| style.display != crate::Display::None)
+                .map(|(row, style)| (row.index, style.top))
+                .collect::<Vec<_>>();
+            rows.sort_by_key(|(index, _)| *index);
+            rows
+        };
+        assert_eq!(
+            world.get::<VirtualList>(list).unwrap().spawned_items(),
+            0..6
+        );
+        assert_eq!(rows(&mut world)[5], (5, Val::Px(100.)));
+
+        let row_count = world.query::<&VirtualListRow>().iter(&world).count();
+        world.get_mut::<ScrollPosition>(list).unwrap().offset_y = 50_000.;
+        world.run_system_once(update_virtual_list_system);
+        assert_eq!(
+            world.get::<VirtualList>(list).unwrap().spawned_items(),
+            2499..2506
+        );
+        let rows = rows(&mut world);
+        assert_eq!(rows.len(), 7);
+        assert_eq!(rows[0], (2499, Val::Px(49_980.)));
+        // the rows are reused, one is spawned for the item partially in view at the bottom
+        assert_eq!(
+            world.query::<&VirtualListRow>().iter(&world).count(),
+            row_count + 1
+        );
+    }
+}
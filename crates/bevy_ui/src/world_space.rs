@@ -0,0 +1,310 @@
+//! UI rendered in world space, on a quad attached to a 3D entity.
+
+use crate::{CameraCursorPosition, TargetCamera};
+use bevy_asset::{Assets, Handle};
+use bevy_color::Color;
+use bevy_core_pipeline::{core_2d::Camera2dBundle, core_3d::Camera3d};
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::{BuildChildren, DespawnRecursiveExt};
+use bevy_input::touch::Touches;
+use bevy_math::{primitives::Rectangle, UVec2, Vec2, Vec3};
+use bevy_pbr::{PbrBundle, StandardMaterial};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    alpha::AlphaMode,
+    camera::{Camera, ClearColorConfig, NormalizedRenderTarget, RenderTarget},
+    mesh::Mesh,
+    render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+    texture::Image,
+};
+use bevy_transform::components::{GlobalTransform, Transform};
+use bevy_utils::default;
+use bevy_window::{PrimaryWindow, Window};
+
+/// Renders UI on a quad attached to this 3D entity, like health bars and interaction prompts.
+///
+/// The UI is rendered to a texture by a camera spawned with the quad, listed in the
+/// [`WorldSpaceUiTarget`] inserted on this entity. UI root nodes are displayed on the quad by
+/// adding a [`WorldSpaceUiRoot`] pointing to this entity. The quad is hidden by the geometry in
+/// front of it, and its UI nodes get [`Interaction`](crate::Interaction)s from the cursor of the
+/// 3D camera rendering to the primary window.
+#[derive(Component, Debug, Copy, Clone, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct WorldSpaceUi {
+    /// The size of the UI, in logical pixels, which is the resolution of its texture.
+    pub resolution: UVec2,
+    /// The size of the quad, in world units.
+    pub size: Vec2,
+    /// The position of the center of the quad relative to this entity.
+    pub offset: Vec3,
+    /// Whether the quad is turned to face the camera, instead of following the rotation of this
+    /// entity.
+    pub billboard: bool,
+}
+
+impl Default for WorldSpaceUi {
+    fn default() -> Self {
+        Self {
+            resolution: UVec2::new(256, 128),
+            size: Vec2::new(1., 0.5),
+            offset: Vec3::ZERO,
+            billboard: false,
+        }
+    }
+}
+
+/// The entities spawned to render a [`WorldSpaceUi`], inserted on its entity.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct WorldSpaceUiTarget {
+    /// The camera rendering the UI to the texture.
+    pub camera: Entity,
+    /// The quad displaying the texture, a child of the [`WorldSpaceUi`] entity.
+    pub quad: Entity,
+    /// The texture the UI is rendered to.
+    pub image: Handle<Image>,
+}
+
+/// Displays this UI root node on the [`WorldSpaceUi`] of the given entity.
+///
+/// The [`TargetCamera`] of the node is set to the camera of the [`WorldSpaceUi`].
+#[derive(Component, Debug, Copy, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component, PartialEq)]
+pub struct WorldSpaceUiRoot(pub Entity);
+
+/// Marker for the quad of a [`WorldSpaceUi`].
+#[derive(Component, Debug, Copy, Clone, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct WorldSpaceUiQuad;
+
+fn target_image(resolution: UVec2) -> Image {
+    let size = Extent3d {
+        width: resolution.x.max(1),
+        height: resolution.y.max(1),
+        ..default()
+    };
+    let mut image = Image::new_fill(
+        size,
+        TextureDimension::D2,
+        &[0; 4],
+        TextureFormat::Bgra8UnormSrgb,
+        default(),
+    );
+    image.texture_descriptor.usage =
+        TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST | TextureUsages::RENDER_ATTACHMENT;
+    image
+}
+
+/// Spawns the camera and the quad of the added [`WorldSpaceUi`]s, and despawns them when the
+/// component is removed. The texture and the quad are resized when the [`WorldSpaceUi`] changes.
+pub fn spawn_world_space_ui_system(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    added: Query<(Entity, &WorldSpaceUi), Without<WorldSpaceUiTarget>>,
+    changed: Query<(&WorldSpaceUi, &WorldSpaceUiTarget), Changed<WorldSpaceUi>>,
+    mut quads: Query<(&mut Transform, &Handle<Mesh>), With<WorldSpaceUiQuad>>,
+    targets: Query<&WorldSpaceUiTarget>,
+    mut removed: RemovedComponents<WorldSpaceUi>,
+) {
+    for entity in removed.read() {
+        let Ok(target) = targets.get(entity) else {
+            continue;
+        };
+        commands.entity(target.camera).despawn_recursive();
+        commands.entity(target.quad).despawn_recursive();
+        if let Some(mut entity) = commands.get_entity(entity) {
+            entity.remove::<WorldSpaceUiTarget>();
+        }
+    }
+
+    for (world_space_ui, target) in &changed {
+        if let Some(image) = images.get_mut(&target.image) {
+            let size = target_image(world_space_ui.resolution)
+                .texture_descriptor
+                .size;
+            if image.texture_descriptor.size != size {
+                image.resize(size);
+            }
+        }
+        if let Ok((mut transform, mesh)) = quads.get_mut(target.quad) {
+            meshes.insert(mesh, Rectangle::from_size(world_space_ui.size).into());
+            transform.translation = world_space_ui.offset;
+        }
+    }
+
+    for (entity, world_space_ui) in &added {
+        let image = images.add(target_image(world_space_ui.resolution));
+        let camera = commands
+            .spawn((
+                Camera2dBundle {
+                    camera: Camera {
+                        // render before the cameras displaying the quad
+                        order: -1,
+                        target: RenderTarget::Image(image.clone()),
+                        clear_color: ClearColorConfig::Custom(Color::NONE),
+                        ..default()
+                    },
+                    ..default()
+                },
+                CameraCursorPosition::default(),
+            ))
+            .id();
+        let quad = commands
+            .spawn((
+                PbrBundle {
+                    mesh: meshes.add(Rectangle::from_size(world_space_ui.size)),
+                    material: materials.add(StandardMaterial {
+                        base_color_texture: Some(image.clone()),
+                        unlit: true,
+                        // the UI is blended over the transparent texture
+                        alpha_mode: AlphaMode::Premultiplied,
+                        double_sided: true,
+                        cull_mode: None,
+                        ..default()
+                    }),
+                    transform: Transform::from_translation(world_space_ui.offset),
+                    ..default()
+                },
+                WorldSpaceUiQuad,
+            ))
+            .set_parent(entity)
+            .id();
+        commands.entity(entity).insert(WorldSpaceUiTarget {
+            camera,
+            quad,
+            image,
+        });
+    }
+}
+
+/// Sets the [`TargetCamera`] of the nodes with a [`WorldSpaceUiRoot`] to the camera of their
+/// [`WorldSpaceUi`].
+pub fn update_world_space_ui_roots_system(
+    mut commands: Commands,
+    roots: Query<(Entity, &WorldSpaceUiRoot, Option<&TargetCamera>)>,
+    targets: Query<&WorldSpaceUiTarget>,
+) {
+    for (entity, root, target_camera) in &roots {
+        let Ok(target) = targets.get(root.0) else {
+            continue;
+        };
+        if target_camera.map(TargetCamera::entity) != Some(target.camera) {
+            commands.entity(entity).insert(TargetCamera(target.camera));
+        }
+    }
+}
+
+/// Returns the 3D camera rendering to the primary window with the highest order, used to
+/// billboard and pick the [`WorldSpaceUi`]s.
+fn window_camera<'a>(
+    cameras: &'a Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    primary_window: Option<Entity>,
+) -> Option<(&'a Camera, &'a GlobalTransform)> {
+    cameras
+        .iter()
+        .filter(|(camera, _)| {
+            camera.is_active
+                && matches!(
+                    camera.target.normalize(primary_window),
+                    Some(NormalizedRenderTarget::Window(window)) if Some(window.entity()) == primary_window
+                )
+        })
+        .max_by_key(|(camera, _)| camera.order)
+}
+
+/// Turns the quads of the [`WorldSpaceUi`]s with [`WorldSpaceUi::billboard`] to face the camera.
+pub fn billboard_world_space_ui_system(
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    world_space_uis: Query<(&WorldSpaceUi, &WorldSpaceUiTarget, &GlobalTransform)>,
+    mut quads: Query<&mut Transform, With<WorldSpaceUiQuad>>,
+) {
+    let Some((_, camera_transform)) = window_camera(&cameras, primary_window.iter().next()) else {
+        return;
+    };
+    let camera_rotation = camera_transform.compute_transform().rotation;
+    for (world_space_ui, target, transform) in &world_space_uis {
+        if !world_space_ui.billboard {
+            continue;
+        }
+        let Ok(mut quad_transform) = quads.get_mut(target.quad) else {
+            continue;
+        };
+        // the quad faces the same way as the camera, in the space of its parent
+        let rotation = transform.compute_transform().rotation.inverse() * camera_rotation;
+        if quad_transform.rotation != rotation {
+            quad_transform.rotation = rotation;
+        }
+    }
+}
+
+/// Sets the [`CameraCursorPosition`] of the cameras of the [`WorldSpaceUi`]s to the point of their
+/// texture under the cursor of the primary window.
+///
+/// Only the closest quad under the cursor gets a position, so that the UI nodes of the quads
+/// behind it aren't interacted with.
+pub fn world_space_ui_picking_system(
+    primary_window: Query<(Entity, &Window), With<PrimaryWindow>>,
+    touches: Res<Touches>,
+    window_cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    world_space_uis: Query<(&WorldSpaceUi, &WorldSpaceUiTarget)>,
+    quads: Query<&GlobalTransform, With<WorldSpaceUiQuad>>,
+    mut cursor_positions: Query<&mut CameraCursorPosition>,
+) {
+    let ray = primary_window
+        .get_single()
+        .ok()
+        .and_then(|(entity, window)| {
+            let cursor = window
+                .cursor_position()
+                .or_else(|| touches.first_pressed_position())?;
+            let (camera, camera_transform) = window_camera(&window_cameras, Some(entity))?;
+            let viewport_position = camera
+                .logical_viewport_rect()
+                .map(|rect| rect.min)
+                .unwrap_or_default();
+            camera.viewport_to_world(camera_transform, cursor - viewport_position)
+        });
+
+    let mut closest: Option<(f32, Entity, Vec2)> = None;
+    for (world_space_ui, target) in &world_space_uis {
+        let (Some(ray), Ok(quad_transform)) = (ray, quads.get(target.quad)) else {
+            continue;
+        };
+        // intersect the ray with the plane of the quad, in the space of the quad
+        let inverse = quad_transform.affine().inverse();
+        let origin = inverse.transform_point3(ray.origin);
+        let direction = inverse.transform_vector3(*ray.direction);
+        if direction.z.abs() <= f32::EPSILON {
+            continue;
+        }
+        let distance = -origin.z / direction.z;
+        let hit = origin + direction * distance;
+        let uv = Vec2::new(
+            hit.x / world_space_ui.size.x + 0.5,
+            0.5 - hit.y / world_space_ui.size.y,
+        );
+        if distance < 0. || uv.cmplt(Vec2::ZERO).any() || uv.cmpgt(Vec2::ONE).any() {
+            continue;
+        }
+        let world_distance = quad_transform.transform_point(hit).distance(ray.origin);
+        if closest.map_or(true, |(closest, ..)| world_distance < closest) {
+            closest = Some((
+                world_distance,
+                target.camera,
+                uv * world_space_ui.resolution.as_vec2(),
+            ));
+        }
+    }
+
+    for (_, target) in &world_space_uis {
+        let Ok(mut cursor_position) = cursor_positions.get_mut(target.camera) else {
+            continue;
+        };
+        let position = closest
+            .filter(|(_, camera, _)| *camera == target.camera)
+            .map(|(.., position)| position);
+        cursor_position.set_if_neq(CameraCursorPosition(position));
+    }
+}
@@ -0,0 +1,53 @@
+use bevy_ecs::{reflect::ReflectResource, system::Resource};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+
+#[cfg(feature = "serialize")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// The text copied and pasted by the widgets of the app, like the `TextInput` of `bevy_ui`.
+///
+/// This is an in-app clipboard only: it isn't backed by the clipboard of the operating system,
+/// which `winit` doesn't give access to, and no Bevy backend syncs it. Text copied in the app
+/// can't be pasted in other applications, and text copied in other applications can't be pasted
+/// in the app.
+///
+/// A third party plugin can keep it in sync with the system clipboard, by sending the text to it
+/// when [`Clipboard::is_changed`], then calling [`Clipboard::clear_changed`], and by calling
+/// [`Clipboard::set_text`] followed by [`Clipboard::clear_changed`] when the system clipboard
+/// changes.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq, Reflect)]
+#[reflect(Resource, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct Clipboard {
+    text: String,
+    changed: bool,
+}
+
+impl Clipboard {
+    /// The text in the clipboard, empty if nothing was copied.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Replaces the text in the clipboard, as done when copying or cutting text.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        self.changed = true;
+    }
+
+    /// Returns `true` if the text was set since the last [`Clipboard::clear_changed`].
+    ///
+    /// Used by backends to know when to send the text to the system clipboard.
+    pub fn is_changed(&self) -> bool {
+        self.changed
+    }
+
+    /// Resets the [`Clipboard::is_changed`] flag, once the text has been synchronized.
+    pub fn clear_changed(&mut self) {
+        self.changed = false;
+    }
+}
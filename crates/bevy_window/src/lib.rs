@@ -15,6 +15,7 @@ use std::sync::{Arc, Mutex};
 
 use bevy_a11y::Focus;
 
+mod clipboard;
 mod cursor;
 mod event;
 mod raw_handle;
@@ -23,6 +24,7 @@ mod window;
 
 pub use crate::raw_handle::*;
 
+pub use clipboard::*;
 pub use cursor::*;
 pub use event::*;
 pub use system::*;
@@ -110,6 +112,8 @@ impl Plugin for WindowPlugin {
             .add_event::<WindowThemeChanged>()
             .add_event::<AppLifecycle>();
 
+        app.init_resource::<Clipboard>();
+
         if let Some(primary_window) = &self.primary_window {
             let initial_focus = app
                 .world_mut()
@@ -162,7 +166,8 @@ impl Plugin for WindowPlugin {
 
         // Register window descriptor and related types
         app.register_type::<Window>()
-            .register_type::<PrimaryWindow>();
+            .register_type::<PrimaryWindow>()
+            .register_type::<Clipboard>();
     }
 }
 
@@ -157,6 +157,12 @@ pub struct Window {
     pub name: Option<String>,
     /// How the alpha channel of textures should be handled while compositing.
     pub composite_alpha_mode: CompositeAlphaMode,
+    /// How the renderer encodes colors written to the window, see [`OutputColorSpace`].
+    ///
+    /// Notes: Changing this field during runtime will have no effect for now.
+    pub output_color_space: OutputColorSpace,
+    /// Requirements on the cameras rendering to the window, see [`WindowRenderRequirements`].
+    pub render_requirements: WindowRenderRequirements,
     /// The limits of the window's logical size
     /// (found in its [`resolution`](WindowResolution)) when resizing.
     pub resize_constraints: WindowResizeConstraints,
@@ -194,6 +200,11 @@ pub struct Window {
     pub transparent: bool,
     /// Get/set whether the window is focused.
     pub focused: bool,
+    /// The refresh rate of the monitor the window is on, in millihertz, if it's known.
+    ///
+    /// This is set by the windowing backend when the window is created or moves to another
+    /// monitor, changing it has no effect. It's used by `UpdateMode::MonitorRefreshRate`.
+    pub monitor_refresh_rate_millihertz: Option<u32>,
     /// Where should the window appear relative to other overlapping window.
     ///
     /// ## Platform-specific
@@ -322,6 +333,8 @@ impl Default for Window {
             resolution: Default::default(),
             internal: Default::default(),
             composite_alpha_mode: Default::default(),
+            output_color_space: Default::default(),
+            render_requirements: Default::default(),
             resize_constraints: Default::default(),
             ime_enabled: Default::default(),
             ime_position: Default::default(),
@@ -330,6 +343,7 @@ impl Default for Window {
             decorations: true,
             transparent: false,
             focused: true,
+            monitor_refresh_rate_millihertz: None,
             window_level: Default::default(),
             fit_canvas_to_parent: false,
             prevent_default_event_handling: true,
@@ -1078,6 +1092,68 @@ pub enum CompositeAlphaMode {
     Inherit = 4,
 }
 
+/// Requirements on the cameras that render to a [`Window`], for example because
+/// the platform or the rest of the app depends on a given configuration.
+///
+/// The renderer checks the cameras rendering to the window against these every
+/// frame and logs an error for each camera that doesn't meet them, rather than
+/// silently rendering with a different configuration.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+#[reflect(Debug, PartialEq, Hash, Default)]
+pub struct WindowRenderRequirements {
+    /// The MSAA sample count that cameras rendering to the window must use, or
+    /// `None` to allow any sample count supported by the device.
+    pub sample_count: Option<u32>,
+    /// Whether cameras rendering to the window must, or must not, render in HDR.
+    pub hdr: HdrRequirement,
+}
+
+/// Whether the cameras rendering to a [`Window`] must render in HDR, see
+/// [`WindowRenderRequirements`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+#[reflect(Debug, PartialEq, Hash, Default)]
+pub enum HdrRequirement {
+    /// Cameras may render both in HDR and not.
+    #[default]
+    Any,
+    /// Cameras must render in HDR.
+    Required,
+    /// Cameras must not render in HDR, for example to save memory bandwidth.
+    Forbidden,
+}
+
+/// Specifies how the colors rendered to a [`Window`] are encoded on its surface.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+#[reflect(Debug, PartialEq, Hash)]
+pub enum OutputColorSpace {
+    /// Colors are tonemapped and converted from linear to sRGB, which is what
+    /// displays expect.
+    #[default]
+    Srgb,
+    /// Colors are written to the surface as they are, without tonemapping,
+    /// debanding or sRGB conversion.
+    ///
+    /// This lets data visualization and image processing apps display exact
+    /// pixel values. Use an HDR camera to avoid the precision loss of the 8-bit
+    /// sRGB main texture of other cameras.
+    LinearPassthrough,
+}
+
 /// Defines the way a [`Window`] is displayed.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
 #[cfg_attr(
@@ -15,7 +15,7 @@ use bevy_log::{error, trace, warn};
 use bevy_math::{ivec2, DVec2, Vec2};
 #[cfg(not(target_arch = "wasm32"))]
 use bevy_tasks::tick_global_task_pools_on_main_thread;
-use bevy_utils::Instant;
+use bevy_utils::{Duration, Instant};
 use std::marker::PhantomData;
 use winit::application::ApplicationHandler;
 use winit::dpi::PhysicalSize;
@@ -24,6 +24,9 @@ use winit::event::{DeviceEvent, DeviceId, StartCause, WindowEvent};
 use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop};
 use winit::window::WindowId;
 
+use bevy_window::PrimaryWindow;
+#[cfg(target_os = "android")]
+use bevy_window::RawHandleWrapper;
 #[allow(deprecated)]
 use bevy_window::{
     AppLifecycle, CursorEntered, CursorLeft, CursorMoved, FileDragAndDrop, Ime, ReceivedCharacter,
@@ -31,11 +34,10 @@ use bevy_window::{
     WindowFocused, WindowMoved, WindowOccluded, WindowResized, WindowScaleFactorChanged,
     WindowThemeChanged,
 };
-#[cfg(target_os = "android")]
-use bevy_window::{PrimaryWindow, RawHandleWrapper};
 
 use crate::accessibility::AccessKitAdapters;
 use crate::system::CachedWindow;
+use crate::winit_windows::monitor_refresh_rate;
 use crate::{
     converters, create_windows, AppSendEvent, CreateWindowParams, UpdateMode, WinitEvent,
     WinitSettings, WinitWindows,
@@ -217,6 +219,9 @@ impl<T: Event> ApplicationHandler<T> for WinitAppRunnerState<T> {
                 react_to_resize(window, &mut win, size, &mut window_resized);
             }
             WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                win.monitor_refresh_rate_millihertz = winit_windows
+                    .get_window(window)
+                    .and_then(|winit_window| monitor_refresh_rate(winit_window));
                 react_to_scale_factor_change(
                     window,
                     &mut win,
@@ -343,6 +348,9 @@ impl<T: Event> ApplicationHandler<T> for WinitAppRunnerState<T> {
             WindowEvent::Moved(position) => {
                 let position = ivec2(position.x, position.y);
                 win.position.set(position);
+                win.monitor_refresh_rate_millihertz = winit_windows
+                    .get_window(window)
+                    .and_then(|winit_window| monitor_refresh_rate(winit_window));
                 self.winit_events.send(WindowMoved { window, position });
             }
             WindowEvent::Ime(event) => match event {
@@ -410,8 +418,10 @@ impl<T: Event> ApplicationHandler<T> for WinitAppRunnerState<T> {
 
         let mut redraw_event_reader = ManualEventReader::<RequestRedraw>::default();
 
-        let mut focused_windows_state: SystemState<(Res<WinitSettings>, Query<(Entity, &Window)>)> =
-            SystemState::new(self.world_mut());
+        let mut focused_windows_state: SystemState<(
+            Res<WinitSettings>,
+            Query<(Entity, &Window, Has<PrimaryWindow>)>,
+        )> = SystemState::new(self.world_mut());
 
         if let Some(app_redraw_events) = self.world().get_resource::<Events<RequestRedraw>>() {
             if redraw_event_reader.read(app_redraw_events).last().is_some() {
@@ -420,9 +430,9 @@ impl<T: Event> ApplicationHandler<T> for WinitAppRunnerState<T> {
         }
 
         let (config, windows) = focused_windows_state.get(self.world());
-        let focused = windows.iter().any(|(_, window)| window.focused);
+        let focused = windows.iter().any(|(_, window, _)| window.focused);
 
-        let mut update_mode = config.update_mode(focused);
+        let mut update_mode = paced_update_mode(config.update_mode(focused), &windows);
         let mut should_update = self.should_update(update_mode);
 
         if self.startup_forced_updates > 0 {
@@ -514,9 +524,9 @@ impl<T: Event> ApplicationHandler<T> for WinitAppRunnerState<T> {
 
             // Running the app may have changed the WinitSettings resource, so we have to re-extract it.
             let (config, windows) = focused_windows_state.get(self.world());
-            let focused = windows.iter().any(|(_, window)| window.focused);
+            let focused = windows.iter().any(|(_, window, _)| window.focused);
 
-            update_mode = config.update_mode(focused);
+            update_mode = paced_update_mode(config.update_mode(focused), &windows);
         }
 
         // The update mode could have been changed, so we need to redraw and force an update
@@ -530,7 +540,8 @@ impl<T: Event> ApplicationHandler<T> for WinitAppRunnerState<T> {
         }
 
         match update_mode {
-            UpdateMode::Continuous => {
+            // Resolved by `paced_update_mode`
+            UpdateMode::Continuous | UpdateMode::MonitorRefreshRate => {
                 // per winit's docs on [Window::is_visible](https://docs.rs/winit/latest/winit/window/struct.Window.html#method.is_visible),
                 // we cannot use the visibility to drive rendering on these platforms
                 // so we cannot discern whether to beneficially use `Poll` or not?
@@ -603,7 +614,8 @@ impl<T: Event> ApplicationHandler<T> for WinitAppRunnerState<T> {
 impl<T: Event> WinitAppRunnerState<T> {
     fn should_update(&self, update_mode: UpdateMode) -> bool {
         let handle_event = match update_mode {
-            UpdateMode::Continuous => {
+            // Resolved by `paced_update_mode`
+            UpdateMode::Continuous | UpdateMode::MonitorRefreshRate => {
                 self.wait_elapsed
                     || self.user_event_received
                     || self.window_event_received
@@ -772,6 +784,35 @@ pub fn winit_runner<T: Event>(mut app: App) -> AppExit {
     })
 }
 
+/// Resolves [`UpdateMode::MonitorRefreshRate`] to waiting for the refresh interval of the monitor
+/// of the focused window, or of the primary window, between the updates.
+///
+/// This is a timer, so it only approximates the refresh rate, it isn't synchronized with it.
+fn paced_update_mode(
+    update_mode: UpdateMode,
+    windows: &Query<(Entity, &Window, Has<PrimaryWindow>)>,
+) -> UpdateMode {
+    if update_mode != UpdateMode::MonitorRefreshRate {
+        return update_mode;
+    }
+    let window = windows
+        .iter()
+        .find(|(_, window, _)| window.focused)
+        .or_else(|| windows.iter().find(|&(.., primary)| primary));
+    match window
+        .and_then(|(_, window, _)| window.monitor_refresh_rate_millihertz)
+        .filter(|&refresh_rate| refresh_rate > 0)
+    {
+        Some(refresh_rate) => UpdateMode::Reactive {
+            wait: Duration::from_secs_f64(1000.0 / refresh_rate as f64),
+            react_to_device_events: false,
+            react_to_user_events: false,
+            react_to_window_events: false,
+        },
+        None => UpdateMode::Continuous,
+    }
+}
+
 pub(crate) fn react_to_resize(
     window_entity: Entity,
     window: &mut Mut<'_, Window>,
@@ -77,6 +77,8 @@ pub fn create_windows<F: QueryFilter + 'static>(
         window
             .resolution
             .set_scale_factor_and_apply_to_physical_size(winit_window.scale_factor() as f32);
+        window.monitor_refresh_rate_millihertz =
+            crate::winit_windows::monitor_refresh_rate(winit_window);
 
         commands.entity(entity).insert(CachedWindow {
             window: window.clone(),
@@ -82,6 +82,23 @@ pub enum UpdateMode {
         /// Reacts to window events, that will wake up the loop if it's in a wait state
         react_to_window_events: bool,
     },
+    /// The [`App`](bevy_app::App) will update at about the refresh rate of the monitor of the
+    /// focused window, or of the primary window if no window is focused, until an
+    /// [`AppExit`](bevy_app::AppExit) event appears.
+    ///
+    /// This paces the updates on the monitor the user is looking at, which reduces judder when
+    /// monitors with different refresh rates are in use. The events received between two updates
+    /// are handled by the next one.
+    ///
+    /// **Note:** The pacing is approximate. The updates are started by a timer of the refresh
+    /// interval, like in [`Reactive`](UpdateMode::Reactive), not by the vertical blanks of the
+    /// monitor, so they drift relative to them and the rate can be slightly lower than the
+    /// refresh rate. Use a vsync [`PresentMode`](bevy_window::PresentMode) to synchronize the
+    /// frames with the monitor.
+    ///
+    /// The app updates [`Continuous`](UpdateMode::Continuous)ly while the refresh rate of the
+    /// monitor is unknown, see [`Window::monitor_refresh_rate_millihertz`](bevy_window::Window::monitor_refresh_rate_millihertz).
+    MonitorRefreshRate,
 }
 
 impl UpdateMode {
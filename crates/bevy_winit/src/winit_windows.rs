@@ -327,6 +327,13 @@ pub fn get_best_videomode(monitor: &MonitorHandle) -> VideoModeHandle {
     modes.first().unwrap().clone()
 }
 
+/// Gets the refresh rate of the monitor the window is on, in millihertz.
+pub(crate) fn monitor_refresh_rate(winit_window: &WinitWindow) -> Option<u32> {
+    winit_window
+        .current_monitor()
+        .and_then(|monitor| monitor.refresh_rate_millihertz())
+}
+
 pub(crate) fn attempt_grab(winit_window: &WinitWindow, grab_mode: CursorGrabMode) {
     let grab_result = match grab_mode {
         CursorGrabMode::None => winit_window.set_cursor_grab(WinitCursorGrabMode::None),
@@ -0,0 +1,113 @@
+//! Hides a 2D scene behind a fog of war, that's revealed around the player.
+//!
+//! The meshes use a [`ColorMaterial`] with a visibility mask, an image that's updated each frame.
+
+use bevy::{color::palettes::css::*, prelude::*};
+
+const MAP_SIZE: f32 = 800.0;
+const MASK_SIZE: u32 = 64;
+const VIEW_RADIUS: f32 = 100.0;
+const PLAYER_SPEED: f32 = 300.0;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .add_systems(Update, (move_player, reveal_fog).chain())
+        .run();
+}
+
+#[derive(Component)]
+struct Player;
+
+#[derive(Resource)]
+struct FogOfWar(Handle<Image>);
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    commands.spawn(Camera2dBundle::default());
+
+    let fog = images.add(ColorMaterial::new_visibility_mask(UVec2::splat(MASK_SIZE)));
+    let colors = [CRIMSON, GOLD, LIME, DEEP_SKY_BLUE, ORCHID];
+    let tile = meshes.add(Rectangle::from_length(MAP_SIZE / 10.0 - 8.0));
+    for x in 0..10 {
+        for y in 0..10 {
+            let material = materials.add(ColorMaterial {
+                color: colors[(x * 3 + y) % colors.len()].into(),
+                visibility_mask: Some(fog.clone()),
+                visibility_mask_rect: Rect::from_center_size(Vec2::ZERO, Vec2::splat(MAP_SIZE)),
+                ..default()
+            });
+            let position = (Vec2::new(x as f32, y as f32) + 0.5) * MAP_SIZE / 10.0 - MAP_SIZE / 2.0;
+            commands.spawn(ColorMesh2dBundle {
+                mesh: tile.clone().into(),
+                material,
+                transform: Transform::from_translation(position.extend(0.0)),
+                ..default()
+            });
+        }
+    }
+    commands.insert_resource(FogOfWar(fog));
+
+    commands.spawn((
+        ColorMesh2dBundle {
+            mesh: meshes.add(Circle::new(16.0)).into(),
+            material: materials.add(Color::WHITE),
+            transform: Transform::from_xyz(0.0, 0.0, 1.0),
+            ..default()
+        },
+        Player,
+    ));
+}
+
+fn move_player(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    mut player: Query<&mut Transform, With<Player>>,
+) {
+    let mut direction = Vec2::ZERO;
+    for (key, key_direction) in [
+        (KeyCode::ArrowLeft, Vec2::NEG_X),
+        (KeyCode::ArrowRight, Vec2::X),
+        (KeyCode::ArrowDown, Vec2::NEG_Y),
+        (KeyCode::ArrowUp, Vec2::Y),
+    ] {
+        if keyboard_input.pressed(key) {
+            direction += key_direction;
+        }
+    }
+    let mut transform = player.single_mut();
+    let translation = transform.translation.truncate()
+        + direction.normalize_or_zero() * PLAYER_SPEED * time.delta_seconds();
+    transform.translation = translation
+        .clamp(Vec2::splat(-MAP_SIZE / 2.0), Vec2::splat(MAP_SIZE / 2.0))
+        .extend(transform.translation.z);
+}
+
+fn reveal_fog(
+    fog: Res<FogOfWar>,
+    player: Query<&Transform, (With<Player>, Changed<Transform>)>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let Ok(transform) = player.get_single() else {
+        return;
+    };
+    let Some(image) = images.get_mut(&fog.0) else {
+        return;
+    };
+    let texel_size = MAP_SIZE / MASK_SIZE as f32;
+    for y in 0..MASK_SIZE {
+        for x in 0..MASK_SIZE {
+            // The first row of the mask is at the top of the map
+            let texel = Vec2::new(x as f32 + 0.5, (MASK_SIZE - y) as f32 - 0.5) * texel_size
+                - MAP_SIZE / 2.0;
+            if texel.distance(transform.translation.truncate()) < VIEW_RADIUS {
+                image.data[(y * MASK_SIZE + x) as usize] = 255;
+            }
+        }
+    }
+}
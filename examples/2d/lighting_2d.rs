@@ -0,0 +1,146 @@
+//! Lights sprites with 2D point and spot lights, shading a normal mapped sprite and casting
+//! shadows behind occluders.
+//!
+//! The point light follows the cursor. Press space to switch its shadows between hard and soft.
+
+use bevy::{color::palettes::css::*, prelude::*, render::texture::ImageLoaderSettings};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .insert_resource(AmbientLight2d {
+            color: Color::WHITE,
+            brightness: 0.1,
+        })
+        .add_systems(Startup, setup)
+        .add_systems(
+            Update,
+            (follow_cursor, toggle_soft_shadows, rotate_spot_light),
+        )
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    // Sprites are only lit by cameras with `Lighting2d`.
+    commands.spawn((Camera2dBundle::default(), Lighting2d));
+
+    // The ground.
+    commands.spawn(SpriteBundle {
+        sprite: Sprite {
+            color: Color::srgb(0.6, 0.6, 0.5),
+            custom_size: Some(Vec2::new(1200.0, 800.0)),
+            ..default()
+        },
+        transform: Transform::from_xyz(0.0, 0.0, -1.0),
+        ..default()
+    });
+
+    // A normal mapped sprite, shaded according to where the lights are.
+    commands.spawn((
+        SpriteBundle {
+            texture: asset_server.load("textures/parallax_example/cube_color.png"),
+            sprite: Sprite {
+                custom_size: Some(Vec2::splat(200.0)),
+                ..default()
+            },
+            transform: Transform::from_xyz(-300.0, 150.0, 0.0),
+            ..default()
+        },
+        SpriteNormalMap(asset_server.load_with_settings(
+            "textures/parallax_example/cube_normal.png",
+            // Normal maps aren't colors, they must not be converted from sRGB.
+            |settings: &mut ImageLoaderSettings| settings.is_srgb = false,
+        )),
+    ));
+
+    // Occluders, with sprites of the same shape so they can be seen.
+    let occluders = [
+        (
+            Vec2::new(-100.0, -150.0),
+            LightOccluder2d::rectangle(Vec2::new(120.0, 40.0)),
+        ),
+        (Vec2::new(150.0, 100.0), LightOccluder2d::circle(40.0)),
+        (
+            Vec2::new(250.0, -150.0),
+            LightOccluder2d::rectangle(Vec2::splat(80.0)),
+        ),
+    ];
+    for (position, occluder) in occluders {
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: DIM_GRAY.into(),
+                    custom_size: Some((occluder.half_size + occluder.corner_radius) * 2.0),
+                    ..default()
+                },
+                transform: Transform::from_translation(position.extend(0.0))
+                    .with_rotation(Quat::from_rotation_z(0.3)),
+                ..default()
+            },
+            occluder,
+        ));
+    }
+
+    commands.spawn(PointLight2dBundle {
+        point_light: PointLight2d {
+            color: LIGHT_YELLOW.into(),
+            intensity: 2.0,
+            radius: 500.0,
+            shadows: Shadows2d::Hard,
+            ..default()
+        },
+        ..default()
+    });
+
+    commands.spawn(SpotLight2dBundle {
+        spot_light: SpotLight2d {
+            color: DEEP_SKY_BLUE.into(),
+            intensity: 3.0,
+            radius: 700.0,
+            inner_angle: 0.2,
+            outer_angle: 0.5,
+            shadows: Shadows2d::Soft { softness: 0.05 },
+            ..default()
+        },
+        transform: Transform::from_xyz(500.0, 300.0, 0.0),
+        ..default()
+    });
+}
+
+fn follow_cursor(
+    windows: Query<&Window>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut lights: Query<&mut Transform, With<PointLight2d>>,
+) {
+    let (camera, camera_transform) = cameras.single();
+    let Some(position) = windows
+        .single()
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor))
+    else {
+        return;
+    };
+    for mut transform in &mut lights {
+        transform.translation = position.extend(0.0);
+    }
+}
+
+fn toggle_soft_shadows(keyboard: Res<ButtonInput<KeyCode>>, mut lights: Query<&mut PointLight2d>) {
+    if !keyboard.just_pressed(KeyCode::Space) {
+        return;
+    }
+    for mut light in &mut lights {
+        light.shadows = match light.shadows {
+            Shadows2d::Hard => Shadows2d::Soft { softness: 0.1 },
+            _ => Shadows2d::Hard,
+        };
+    }
+}
+
+/// Sweeps the spot light back and forth across the scene.
+fn rotate_spot_light(time: Res<Time>, mut lights: Query<&mut Transform, With<SpotLight2d>>) {
+    let angle = std::f32::consts::PI * 1.2 + 0.5 * time.elapsed_seconds().sin();
+    for mut transform in &mut lights {
+        transform.rotation = Quat::from_rotation_z(angle);
+    }
+}
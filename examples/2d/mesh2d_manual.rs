@@ -7,8 +7,7 @@
 
 use bevy::{
     color::palettes::basic::YELLOW,
-    core_pipeline::core_2d::Transparent2d,
-    math::FloatOrd,
+    core_pipeline::core_2d::{Sort2dKey, Transparent2d},
     prelude::*,
     render::{
         mesh::{GpuMesh, Indices, MeshVertexAttribute},
@@ -335,6 +334,7 @@ pub fn extract_colored_mesh2d(
             RenderMesh2dInstance {
                 mesh_asset_id: handle.0.id(),
                 transforms,
+                sort_key: Sort2dKey::from_depth(transform.translation().z),
                 material_bind_group_id: Material2dBindGroupId::default(),
                 automatic_batching: false,
             },
@@ -375,7 +375,6 @@ pub fn queue_colored_mesh2d(
         for visible_entity in visible_entities.iter::<WithMesh2d>() {
             if let Some(mesh_instance) = render_mesh_instances.get(visible_entity) {
                 let mesh2d_handle = mesh_instance.mesh_asset_id;
-                let mesh2d_transforms = &mesh_instance.transforms;
                 // Get our specialized pipeline
                 let mut mesh2d_key = mesh_key;
                 if let Some(mesh) = render_meshes.get(mesh2d_handle) {
@@ -386,14 +385,13 @@ pub fn queue_colored_mesh2d(
                 let pipeline_id =
                     pipelines.specialize(&pipeline_cache, &colored_mesh2d_pipeline, mesh2d_key);
 
-                let mesh_z = mesh2d_transforms.world_from_local.translation.z;
                 transparent_phase.add(Transparent2d {
                     entity: *visible_entity,
                     draw_function: draw_colored_mesh2d,
                     pipeline: pipeline_id,
                     // The 2d render items are sorted according to their z value before rendering,
                     // in order to get correct transparency
-                    sort_key: FloatOrd(mesh_z),
+                    sort_key: mesh_instance.sort_key,
                     // This material is not batched
                     batch_range: 0..1,
                     extra_index: PhaseItemExtraIndex::NONE,
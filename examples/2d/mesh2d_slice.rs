@@ -0,0 +1,99 @@
+//! Draws 2D meshes with their image sliced or tiled by an `ImageScaleMode`, like sprites: a
+//! 9-sliced panel with a custom material and a tiled background with a `ColorMaterial`.
+//!
+//! The panel is resized over time, its corners keep their size while its sides and center stretch.
+
+use bevy::{
+    prelude::*,
+    render::render_resource::{AsBindGroup, ShaderRef},
+    sprite::{Material2d, Material2dPlugin, MaterialMesh2dBundle, ScaledImageMesh2d},
+};
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, Material2dPlugin::<PanelMaterial>::default()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, resize_panel)
+        .run();
+}
+
+/// A material sampling its texture with the UVs of the mesh, tinted by a color.
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+struct PanelMaterial {
+    #[uniform(0)]
+    color: LinearRgba,
+    #[texture(1)]
+    #[sampler(2)]
+    texture: Handle<Image>,
+}
+
+impl Material2d for PanelMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/custom_material_2d.wgsl".into()
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut color_materials: ResMut<Assets<ColorMaterial>>,
+    mut panel_materials: ResMut<Assets<PanelMaterial>>,
+) {
+    commands.spawn(Camera2dBundle::default());
+
+    // A background repeating its image in both directions.
+    let background = asset_server.load("textures/slice_square.png");
+    commands.spawn((
+        ScaledImageMesh2d {
+            image: background.clone(),
+            size: Vec2::new(1000.0, 600.0),
+            scale_mode: ImageScaleMode::Tiled {
+                tile_x: true,
+                tile_y: true,
+                stretch_value: 0.5,
+            },
+            rect: None,
+        },
+        ColorMesh2dBundle {
+            material: color_materials.add(ColorMaterial {
+                color: Color::srgb(0.3, 0.3, 0.3),
+                texture: Some(background),
+                ..default()
+            }),
+            transform: Transform::from_xyz(0.0, 0.0, -1.0),
+            ..default()
+        },
+    ));
+
+    // A panel keeping the size of its borders whatever its size.
+    let panel = asset_server.load("textures/slice_square_2.png");
+    commands.spawn((
+        ScaledImageMesh2d {
+            image: panel.clone(),
+            size: Vec2::new(300.0, 200.0),
+            scale_mode: ImageScaleMode::Sliced(TextureSlicer {
+                border: BorderRect::square(80.0),
+                center_scale_mode: SliceScaleMode::Stretch,
+                ..default()
+            }),
+            rect: None,
+        },
+        MaterialMesh2dBundle {
+            material: panel_materials.add(PanelMaterial {
+                color: LinearRgba::rgb(0.8, 0.9, 1.0),
+                texture: panel,
+            }),
+            ..default()
+        },
+    ));
+}
+
+fn resize_panel(
+    time: Res<Time>,
+    mut panels: Query<&mut ScaledImageMesh2d, With<Handle<PanelMaterial>>>,
+) {
+    let t = time.elapsed_seconds();
+    for mut panel in &mut panels {
+        panel.size = Vec2::new(500.0 + 200.0 * t.sin(), 300.0 + 100.0 * (t * 0.7).cos());
+    }
+}
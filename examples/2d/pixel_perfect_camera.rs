@@ -0,0 +1,169 @@
+//! Renders a low resolution scene with a pixel perfect camera, which scales it up to the window by
+//! whole factors and snaps the sprites to its grid of pixels.
+//!
+//! ## Controls
+//!
+//! | Key Binding | Action                                   |
+//! |:------------|:-----------------------------------------|
+//! | `Space`     | Toggle the snapping to the pixel grid    |
+//! | `S`         | Toggle between integer and fit scaling   |
+
+use bevy::{
+    prelude::*,
+    render::{
+        camera::{PixelPerfect, PixelScaling},
+        view::RenderLayers,
+    },
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
+        .insert_resource(Msaa::Off)
+        .add_systems(Startup, setup)
+        .add_systems(
+            Update,
+            (orbit, follow_cursor, toggle_settings, update_help_text),
+        )
+        .run();
+}
+
+#[derive(Component)]
+struct Orbit;
+
+#[derive(Component)]
+struct Cursor;
+
+#[derive(Component)]
+struct HelpText;
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    // The camera renders the world at 160x90 pixels, one pixel per world unit.
+    commands.spawn((
+        Camera2dBundle::default(),
+        PixelPerfect {
+            resolution: UVec2::new(160, 90),
+            letterbox: ClearColorConfig::Custom(Color::srgb(0.05, 0.05, 0.08)),
+            ..default()
+        },
+    ));
+
+    commands.spawn(SpriteBundle {
+        sprite: Sprite {
+            color: Color::srgb(0.2, 0.25, 0.35),
+            custom_size: Some(Vec2::new(160.0, 30.0)),
+            ..default()
+        },
+        transform: Transform::from_xyz(0.0, -30.0, 0.0),
+        ..default()
+    });
+
+    // Moves slowly, by fractions of a pixel every frame.
+    commands.spawn((
+        SpriteBundle {
+            texture: asset_server.load("pixel/bevy_pixel_dark.png"),
+            transform: Transform::from_xyz(0.0, 10.0, 1.0),
+            ..default()
+        },
+        Orbit,
+    ));
+
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::srgb(1.0, 0.8, 0.2),
+                custom_size: Some(Vec2::splat(3.0)),
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, 0.0, 2.0),
+            ..default()
+        },
+        Cursor,
+    ));
+
+    // The UI is rendered at the resolution of the window, by a camera drawn over the pixel
+    // perfect one and seeing none of the sprites.
+    commands.spawn((
+        Camera2dBundle {
+            camera: Camera {
+                order: 1,
+                clear_color: ClearColorConfig::Custom(Color::NONE),
+                ..default()
+            },
+            ..default()
+        },
+        RenderLayers::layer(1),
+    ));
+    commands.spawn((
+        TextBundle::from_section("", TextStyle::default()).with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        }),
+        HelpText,
+    ));
+}
+
+fn orbit(time: Res<Time>, mut query: Query<&mut Transform, With<Orbit>>) {
+    let angle = time.elapsed_seconds() * 0.5;
+    for mut transform in &mut query {
+        transform.translation.x = angle.cos() * 40.0;
+        transform.translation.y = 10.0 + angle.sin() * 15.0;
+    }
+}
+
+fn follow_cursor(
+    windows: Query<&Window>,
+    cameras: Query<(&Camera, &GlobalTransform, &PixelPerfect)>,
+    mut cursor: Query<(&mut Transform, &mut Visibility), With<Cursor>>,
+) {
+    let (Ok(window), Ok((camera, camera_transform, pixel_perfect))) =
+        (windows.get_single(), cameras.get_single())
+    else {
+        return;
+    };
+    let Ok((mut transform, mut visibility)) = cursor.get_single_mut() else {
+        return;
+    };
+
+    let position = window.cursor_position().and_then(|cursor_position| {
+        pixel_perfect.viewport_to_world_2d(camera, camera_transform, cursor_position)
+    });
+    match position {
+        Some(position) => {
+            transform.translation = position.extend(transform.translation.z);
+            *visibility = Visibility::Inherited;
+        }
+        None => *visibility = Visibility::Hidden,
+    }
+}
+
+fn toggle_settings(keyboard: Res<ButtonInput<KeyCode>>, mut query: Query<&mut PixelPerfect>) {
+    for mut pixel_perfect in &mut query {
+        if keyboard.just_pressed(KeyCode::Space) {
+            pixel_perfect.snap = !pixel_perfect.snap;
+        }
+        if keyboard.just_pressed(KeyCode::KeyS) {
+            pixel_perfect.scaling = match pixel_perfect.scaling {
+                PixelScaling::Integer => PixelScaling::Fit,
+                PixelScaling::Fit => PixelScaling::Integer,
+            };
+        }
+    }
+}
+
+fn update_help_text(
+    pixel_perfect: Query<&PixelPerfect, Changed<PixelPerfect>>,
+    mut text: Query<&mut Text, With<HelpText>>,
+) {
+    let (Ok(pixel_perfect), Ok(mut text)) = (pixel_perfect.get_single(), text.get_single_mut())
+    else {
+        return;
+    };
+    text.sections[0].value = format!(
+        "Space: snapping to the pixel grid ({})\nS: scaling ({:?})",
+        if pixel_perfect.snap { "on" } else { "off" },
+        pixel_perfect.scaling,
+    );
+}
@@ -0,0 +1,81 @@
+//! Plays flipbook animations of a sprite sheet with `SpriteAnimation`, loading the animations
+//! from a `.spriteanim.ron` file.
+//!
+//! Hold the left or right arrow key to run, and watch the log for the footsteps marked in the
+//! run animation.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest())) // prevents blurry sprites
+        .add_systems(Startup, setup)
+        .add_systems(Update, (run, log_footsteps))
+        .run();
+}
+
+#[derive(Resource)]
+struct Animations(Handle<SpriteAnimationSet>);
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    commands.spawn(Camera2dBundle::default());
+
+    let layout = TextureAtlasLayout::from_grid(UVec2::splat(24), 7, 1, None, None);
+    commands.spawn((
+        SpriteBundle {
+            transform: Transform::from_scale(Vec3::splat(6.0)),
+            texture: asset_server.load("textures/rpg/chars/gabe/gabe-idle-run.png"),
+            ..default()
+        },
+        TextureAtlas {
+            layout: texture_atlas_layouts.add(layout),
+            index: 0,
+        },
+        // A single clip can be loaded with its label.
+        SpriteAnimation::new(
+            asset_server.load("textures/rpg/chars/gabe/gabe-idle-run.spriteanim.ron#idle"),
+        ),
+    ));
+
+    // All the clips of the file, to switch between them by name.
+    commands.insert_resource(Animations(
+        asset_server.load("textures/rpg/chars/gabe/gabe-idle-run.spriteanim.ron"),
+    ));
+}
+
+fn run(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    animations: Res<Animations>,
+    animation_sets: Res<Assets<SpriteAnimationSet>>,
+    mut sprites: Query<(&mut SpriteAnimation, &mut Sprite)>,
+) {
+    let Some(animations) = animation_sets.get(&animations.0) else {
+        return;
+    };
+    let direction = match (
+        keyboard.pressed(KeyCode::ArrowLeft),
+        keyboard.pressed(KeyCode::ArrowRight),
+    ) {
+        (true, false) => Some(true),
+        (false, true) => Some(false),
+        _ => None,
+    };
+    let clip = if direction.is_some() { "run" } else { "idle" };
+    for (mut animation, mut sprite) in &mut sprites {
+        // `play` doesn't restart the clip that is already playing.
+        animation.play(animations.get(clip).unwrap().clone());
+        if let Some(flip_x) = direction {
+            sprite.flip_x = flip_x;
+        }
+    }
+}
+
+fn log_footsteps(mut events: EventReader<SpriteAnimationEvent>) {
+    for event in events.read() {
+        info!("{} on frame {}", event.name, event.frame);
+    }
+}
@@ -0,0 +1,106 @@
+//! Orders sprites with sorting layers instead of their Z translation, as in a top-down game.
+//!
+//! All the sprites have a Z translation of zero. The ground is drawn first, with the shadows on
+//! top of it, then the trees and the player are sorted by their Y translation so the player walks
+//! both in front of and behind the trees.
+
+use bevy::{color::palettes::css::*, ecs::system::EntityCommands, prelude::*, sprite::Anchor};
+
+const GROUND: SortingLayer = SortingLayer(0);
+const CHARACTERS: SortingLayer = SortingLayer(1);
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .add_systems(Update, move_player)
+        .run();
+}
+
+#[derive(Component)]
+struct Player;
+
+fn setup(mut commands: Commands, mut sorting_layers: ResMut<SortingLayers>) {
+    commands.spawn(Camera2dBundle::default());
+
+    sorting_layers.set_sort_mode(CHARACTERS, LayerSortMode::Y);
+
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: DARK_OLIVEGREEN.into(),
+                custom_size: Some(Vec2::new(700.0, 500.0)),
+                ..default()
+            },
+            ..default()
+        },
+        GROUND,
+    ));
+
+    for x in [-200.0, 0.0, 200.0] {
+        for y in [-100.0, 100.0] {
+            spawn_character(
+                &mut commands,
+                Vec2::new(x, y),
+                Vec2::new(60.0, 140.0),
+                FOREST_GREEN.into(),
+            );
+        }
+    }
+
+    spawn_character(
+        &mut commands,
+        Vec2::ZERO,
+        Vec2::new(40.0, 60.0),
+        GOLD.into(),
+    )
+    .insert(Player);
+}
+
+/// Spawns a sprite standing on its bottom edge in the characters layer, with a shadow on the
+/// ground.
+fn spawn_character<'a>(
+    commands: &'a mut Commands,
+    position: Vec2,
+    size: Vec2,
+    color: Color,
+) -> EntityCommands<'a> {
+    let mut character = commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color,
+                custom_size: Some(size),
+                // Anchoring the sprite on its bottom edge sorts it by where it stands.
+                anchor: Anchor::BottomCenter,
+                ..default()
+            },
+            transform: Transform::from_translation(position.extend(0.0)),
+            ..default()
+        },
+        CHARACTERS,
+    ));
+    character.with_children(|parent| {
+        parent.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::BLACK.with_alpha(0.4),
+                    custom_size: Some(Vec2::new(size.x * 1.4, 16.0)),
+                    ..default()
+                },
+                ..default()
+            },
+            // Shadows are drawn on the ground, after it and before all the characters.
+            GROUND,
+            OrderInLayer(1),
+        ));
+    });
+    character
+}
+
+fn move_player(time: Res<Time>, mut player: Query<&mut Transform, With<Player>>) {
+    let t = time.elapsed_seconds();
+    for mut transform in &mut player {
+        transform.translation.x = 260.0 * (t * 0.5).sin();
+        transform.translation.y = 130.0 * (t * 0.8).sin();
+    }
+}
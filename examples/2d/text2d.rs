@@ -96,6 +96,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                     )],
                     justify: JustifyText::Left,
                     linebreak_behavior: BreakLineOn::WordBoundary,
+                    ..default()
                 },
                 text_2d_bounds: Text2dBounds {
                     // Wrap text in the rectangle
@@ -128,6 +129,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                     )],
                     justify: JustifyText::Left,
                     linebreak_behavior: BreakLineOn::AnyCharacter,
+                    ..default()
                 },
                 text_2d_bounds: Text2dBounds {
                     // Wrap text in the rectangle
@@ -187,7 +189,8 @@ fn animate_scale(
     mut query: Query<&mut Transform, (With<Text>, With<AnimateScale>)>,
 ) {
     // Consider changing font-size instead of scaling the transform. Scaling a Text2D will scale the
-    // rendered quad, resulting in a pixellated look.
+    // rendered quad, resulting in a pixellated look, unless it's rendered with distance fields as
+    // in the `text2d_distance_field` example.
     for mut transform in &mut query {
         let scale = (time.elapsed_seconds().sin() + 1.1) * 2.0;
         transform.scale.x = scale;
@@ -0,0 +1,41 @@
+//! Compares rasterized text with text rendered from distance fields while zooming and rotating.
+//!
+//! Rasterized glyphs are rendered at the font size and blur when scaled, while distance field
+//! glyphs stay sharp at any scale and rotation. This suits labels in world space and zoomable
+//! maps.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .add_systems(Update, zoom_camera)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 20.0,
+        ..default()
+    };
+    for (rendering, label, y) in [
+        (TextRendering::Raster, "Raster", 25.0),
+        (TextRendering::DistanceField, "Distance field", -25.0),
+    ] {
+        commands.spawn(Text2dBundle {
+            text: Text::from_section(label, text_style.clone()).with_rendering(rendering),
+            transform: Transform::from_xyz(0.0, y, 0.0).with_rotation(Quat::from_rotation_z(0.1)),
+            ..default()
+        });
+    }
+}
+
+fn zoom_camera(time: Res<Time>, mut cameras: Query<&mut OrthographicProjection>) {
+    for mut projection in &mut cameras {
+        projection.scale = 0.55 + 0.45 * time.elapsed_seconds().sin();
+    }
+}
@@ -0,0 +1,112 @@
+//! Draws a tilemap made of chunks, each drawn in a single draw call, with animated tiles and
+//! tiles that change over time.
+
+use bevy::{
+    asset::LoadState,
+    prelude::*,
+    sprite::{TileAnimation, TileData, TilemapChunk, TilemapChunkBundle, TilemapChunkTiles},
+};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+const CHUNK_SIZE: UVec2 = UVec2::new(16, 16);
+const TILE_SIZE: Vec2 = Vec2::splat(20.0);
+const TILESET_LAYERS: u32 = 4;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .insert_resource(SeededRng(ChaCha8Rng::seed_from_u64(42)))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (create_tileset, change_tiles))
+        .run();
+}
+
+#[derive(Resource, Deref, DerefMut)]
+struct SeededRng(ChaCha8Rng);
+
+#[derive(Resource)]
+struct Tileset {
+    handle: Handle<Image>,
+    is_loaded: bool,
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+
+    // The tiles are stacked vertically in the image, it is turned into an array texture with a
+    // layer per tile once it is loaded.
+    commands.insert_resource(Tileset {
+        handle: asset_server.load("textures/array_texture.png"),
+        is_loaded: false,
+    });
+}
+
+fn random_tile(rng: &mut SeededRng) -> Option<TileData> {
+    match rng.gen_range(0..10) {
+        // Leave some tiles empty.
+        0 => None,
+        // Animate some tiles through all the layers of the tileset.
+        1 => Some(
+            TileData::from_tileset_index(0).with_animation(TileAnimation {
+                frame_count: TILESET_LAYERS as u8,
+                frames_per_second: 4,
+            }),
+        ),
+        _ => Some(
+            TileData::from_tileset_index(rng.gen_range(0..TILESET_LAYERS) as u16)
+                .with_color(Color::hsl(rng.gen_range(0.0..360.0), 0.3, 0.9)),
+        ),
+    }
+}
+
+fn create_tileset(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut tileset: ResMut<Tileset>,
+    mut images: ResMut<Assets<Image>>,
+    mut rng: ResMut<SeededRng>,
+) {
+    if tileset.is_loaded || asset_server.load_state(tileset.handle.id()) != LoadState::Loaded {
+        return;
+    }
+    tileset.is_loaded = true;
+    let image = images.get_mut(&tileset.handle).unwrap();
+    image.reinterpret_stacked_2d_as_array(TILESET_LAYERS);
+
+    // A 2x2 grid of chunks.
+    let chunk_world_size = CHUNK_SIZE.as_vec2() * TILE_SIZE;
+    for chunk_x in 0..2 {
+        for chunk_y in 0..2 {
+            let mut tiles = TilemapChunkTiles::new(CHUNK_SIZE);
+            for tile in tiles.tiles_mut() {
+                *tile = random_tile(&mut rng);
+            }
+            let center = (Vec2::new(chunk_x as f32, chunk_y as f32) - 0.5) * chunk_world_size;
+            commands.spawn(TilemapChunkBundle {
+                chunk: TilemapChunk {
+                    tile_size: TILE_SIZE,
+                    tileset: tileset.handle.clone(),
+                },
+                tiles,
+                transform: Transform::from_translation(center.extend(0.0)),
+                ..default()
+            });
+        }
+    }
+}
+
+/// Changes a few random tiles every frame. Only the chunks with changed tiles are sent to the GPU
+/// again.
+fn change_tiles(mut chunks: Query<&mut TilemapChunkTiles>, mut rng: ResMut<SeededRng>) {
+    for mut tiles in &mut chunks {
+        if rng.gen_bool(0.2) {
+            let position = UVec2::new(
+                rng.gen_range(0..CHUNK_SIZE.x),
+                rng.gen_range(0..CHUNK_SIZE.y),
+            );
+            let tile = random_tile(&mut rng);
+            tiles.set(position, tile);
+        }
+    }
+}
@@ -346,6 +346,7 @@ fn add_text<'a>(
             font: font.clone(),
             font_size: 18.0,
             color,
+            ..default()
         },
     ))
 }
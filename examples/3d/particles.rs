@@ -0,0 +1,199 @@
+//! Emits particles simulated on the CPU and on the GPU: a fountain bouncing off the scene, soft
+//! smoke fading against the ground, and additive sparks.
+//!
+//! ## Controls
+//!
+//! | Key Binding | Action                            |
+//! |:------------|:----------------------------------|
+//! | `Space`     | Restart the particle systems      |
+//! | `E`         | Toggle the emission of particles  |
+
+use std::f32::consts::PI;
+
+use bevy::{
+    core_pipeline::{bloom::BloomSettings, prepass::DepthPrepass},
+    prelude::*,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .add_systems(Update, (rotate_sparks, control_particle_systems))
+        .run();
+}
+
+#[derive(Component)]
+struct Sparks;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut effects: ResMut<Assets<ParticleEffect>>,
+) {
+    // The depth prepass lets the smoke fade out against the ground, and the fountain bounce off
+    // the scene.
+    commands.spawn((
+        Camera3dBundle {
+            camera: Camera {
+                hdr: true,
+                ..default()
+            },
+            transform: Transform::from_xyz(-6.0, 5.0, 9.0)
+                .looking_at(Vec3::new(0.0, 1.5, 0.0), Vec3::Y),
+            ..default()
+        },
+        BloomSettings::NATURAL,
+        DepthPrepass,
+        ParticleCollisionCamera,
+    ));
+
+    commands.spawn(DirectionalLightBundle {
+        directional_light: DirectionalLight {
+            illuminance: 3000.0,
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_rotation(Quat::from_euler(EulerRot::ZYX, 0.0, 1.0, -PI / 4.0)),
+        ..default()
+    });
+
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Plane3d::default().mesh().size(20.0, 20.0)),
+        material: materials.add(Color::srgb(0.3, 0.5, 0.3)),
+        ..default()
+    });
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Cuboid::new(1.5, 1.0, 1.5)),
+        material: materials.add(Color::srgb(0.6, 0.6, 0.7)),
+        transform: Transform::from_xyz(-2.0, 0.5, 0.0).with_rotation(Quat::from_rotation_y(0.4)),
+        ..default()
+    });
+
+    // A fountain of many particles simulated on the GPU, bouncing off the box and the ground.
+    let fountain = effects.add(ParticleEffect {
+        capacity: 50_000,
+        simulation: ParticleSimulation::Gpu,
+        emitter: ParticleEmitter {
+            rate: 10_000.0,
+            shape: EmitterShape::Cone {
+                radius: 0.1,
+                angle: 0.35,
+            },
+            ..default()
+        },
+        lifetime: 3.0..5.0,
+        speed: 6.0..7.0,
+        acceleration: Vec3::new(0.0, -9.8, 0.0),
+        color: ParticleCurve::new([
+            (0.0, LinearRgba::rgb(0.6, 0.8, 2.0)),
+            (0.8, LinearRgba::new(0.2, 0.4, 1.0, 0.8)),
+            (1.0, LinearRgba::new(0.2, 0.4, 1.0, 0.0)),
+        ]),
+        size: ParticleCurve::constant(0.04),
+        collision: Some(DepthCollision {
+            restitution: 0.4,
+            friction: 0.1,
+            ..default()
+        }),
+        ..default()
+    });
+    commands.spawn(ParticleSystemBundle {
+        particle_system: ParticleSystem::new(fountain),
+        transform: Transform::from_xyz(-1.4, 0.0, 1.6).with_rotation(Quat::from_rotation_x(-0.3)),
+        ..default()
+    });
+
+    // Smoke simulated on the CPU, fading out softly where it meets the ground.
+    let smoke = effects.add(ParticleEffect {
+        capacity: 500,
+        emitter: ParticleEmitter {
+            rate: 40.0,
+            shape: EmitterShape::Sphere { radius: 0.3 },
+            ..default()
+        },
+        lifetime: 4.0..6.0,
+        speed: 0.1..0.3,
+        acceleration: Vec3::new(0.3, 0.4, 0.0),
+        drag: 0.3,
+        color: ParticleCurve::new([
+            (0.0, LinearRgba::new(0.3, 0.3, 0.3, 0.0)),
+            (0.1, LinearRgba::new(0.3, 0.3, 0.3, 0.5)),
+            (1.0, LinearRgba::new(0.6, 0.6, 0.6, 0.0)),
+        ]),
+        size: ParticleCurve::new([(0.0, 0.6), (1.0, 2.5)]),
+        soft_distance: 0.8,
+        ..default()
+    });
+    commands.spawn(ParticleSystemBundle {
+        particle_system: ParticleSystem::new(smoke),
+        transform: Transform::from_xyz(2.5, 0.1, -1.0),
+        ..default()
+    });
+
+    // Sparks emitted by a spinning emitter, glowing with bloom.
+    let sparks = effects.add(ParticleEffect {
+        capacity: 2000,
+        emitter: ParticleEmitter {
+            rate: 300.0,
+            burst: 200,
+            shape: EmitterShape::Arc {
+                radius: 0.2,
+                angle: 0.3,
+            },
+        },
+        lifetime: 0.5..1.0,
+        speed: 3.0..5.0,
+        acceleration: Vec3::new(0.0, -6.0, 0.0),
+        drag: 1.0,
+        color: ParticleCurve::new([
+            (0.0, LinearRgba::rgb(8.0, 4.0, 1.0)),
+            (1.0, LinearRgba::new(2.0, 0.3, 0.0, 0.0)),
+        ]),
+        size: ParticleCurve::new([(0.0, 0.06), (1.0, 0.02)]),
+        blend_mode: ParticleBlendMode::Additive,
+        ..default()
+    });
+    commands.spawn((
+        ParticleSystemBundle {
+            particle_system: ParticleSystem::new(sparks),
+            transform: Transform::from_xyz(1.5, 2.5, 2.0),
+            ..default()
+        },
+        Sparks,
+    ));
+
+    commands.spawn(
+        TextBundle::from_section(
+            "Space: restart the particle systems\nE: toggle the emission",
+            TextStyle::default(),
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        }),
+    );
+}
+
+fn rotate_sparks(time: Res<Time>, mut sparks: Query<&mut Transform, With<Sparks>>) {
+    for mut transform in &mut sparks {
+        transform.rotate_y(time.delta_seconds() * 2.0);
+    }
+}
+
+fn control_particle_systems(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut particle_systems: Query<&mut ParticleSystem>,
+) {
+    for mut particle_system in &mut particle_systems {
+        if keyboard.just_pressed(KeyCode::Space) {
+            particle_system.restart();
+        }
+        if keyboard.just_pressed(KeyCode::KeyE) {
+            particle_system.emitting = !particle_system.emitting;
+        }
+    }
+}
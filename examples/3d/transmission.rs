@@ -26,7 +26,7 @@ use bevy::{
         bloom::BloomSettings, core_3d::ScreenSpaceTransmissionQuality, prepass::DepthPrepass,
         tonemapping::Tonemapping,
     },
-    pbr::{NotShadowCaster, PointLightShadowMap, TransmittedShadowReceiver},
+    pbr::{NotShadowCaster, ShadowAtlasSettings, TransmittedShadowReceiver},
     prelude::*,
     render::{
         camera::{Exposure, TemporalJitter},
@@ -45,7 +45,11 @@ fn main() {
 
     app.add_plugins(DefaultPlugins)
         .insert_resource(ClearColor(Color::BLACK))
-        .insert_resource(PointLightShadowMap { size: 2048 })
+        .insert_resource(ShadowAtlasSettings {
+            size: 8192,
+            max_resolution: 2048,
+            ..default()
+        })
         .insert_resource(AmbientLight {
             brightness: 0.0,
             ..default()
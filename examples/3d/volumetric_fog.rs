@@ -3,7 +3,7 @@
 use bevy::{
     core_pipeline::{bloom::BloomSettings, tonemapping::Tonemapping, Skybox},
     math::vec3,
-    pbr::{VolumetricFogSettings, VolumetricLight},
+    pbr::{FogVolume, FogVolumeBundle, VolumetricFogSettings, VolumetricLight},
     prelude::*,
 };
 
@@ -59,6 +59,17 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
             ..default()
         });
 
+    // Add a layer of denser fog near the ground, on top of the fog surrounding
+    // the camera.
+    commands.spawn(FogVolumeBundle {
+        fog_volume: FogVolume {
+            density: 1.5,
+            ..default()
+        },
+        transform: Transform::from_xyz(-1.5, 0.25, 3.0).with_scale(vec3(4.0, 0.5, 4.0)),
+        ..default()
+    });
+
     // Add the help text.
     commands.spawn(
         TextBundle {
@@ -0,0 +1,146 @@
+//! Places events on the timeline of an animation clip, and reacts to them with
+//! an observer when the animation crosses them.
+
+use bevy::{
+    animation::{AnimationTarget, AnimationTargetId},
+    prelude::*,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .insert_resource(AmbientLight {
+            color: Color::WHITE,
+            brightness: 150.0,
+        })
+        .add_systems(Startup, setup)
+        .add_systems(Update, fade_flash)
+        .run();
+}
+
+/// How bright the ball flashes, fading back to 0.0 after each bounce.
+#[derive(Component, Default)]
+struct Flash(f32);
+
+#[derive(Component)]
+struct EventText;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut animations: ResMut<Assets<AnimationClip>>,
+    mut graphs: ResMut<Assets<AnimationGraph>>,
+) {
+    // Camera
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(0.0, 2.0, 6.0).looking_at(Vec3::new(0.0, 1.0, 0.0), Vec3::Y),
+        ..default()
+    });
+
+    // Light
+    commands.spawn(PointLightBundle {
+        point_light: PointLight {
+            intensity: 500_000.0,
+            ..default()
+        },
+        transform: Transform::from_xyz(2.0, 4.0, 2.0),
+        ..default()
+    });
+
+    // Ground
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Plane3d::default().mesh().size(10.0, 10.0)),
+        material: materials.add(Color::srgb(0.3, 0.5, 0.3)),
+        ..default()
+    });
+
+    // A ball bouncing on the ground, with an event each time it hits it.
+    let ball = Name::new("ball");
+    let ball_animation_target_id = AnimationTargetId::from_name(&ball);
+    let mut animation = AnimationClip::default();
+    animation.add_curve_to_target(
+        ball_animation_target_id,
+        VariableCurve {
+            keyframe_timestamps: vec![0.0, 0.5, 1.0, 1.25, 1.5],
+            keyframes: Keyframes::Translation(vec![
+                Vec3::new(-1.0, 2.5, 0.0),
+                Vec3::new(0.0, 0.5, 0.0),
+                Vec3::new(0.5, 1.5, 0.0),
+                Vec3::new(1.0, 0.5, 0.0),
+                Vec3::new(-1.0, 2.5, 0.0),
+            ]),
+            interpolation: Interpolation::Linear,
+        },
+    );
+    animation
+        .add_event(0.5, "bounce", "high")
+        .add_event(1.25, "bounce", "low");
+
+    let (graph, animation_index) = AnimationGraph::from_clip(animations.add(animation));
+    let mut player = AnimationPlayer::default();
+    player.play(animation_index).repeat();
+
+    let ball_entity = commands
+        .spawn((
+            PbrBundle {
+                mesh: meshes.add(Sphere::new(0.5)),
+                material: materials.add(Color::srgb(0.8, 0.7, 0.6)),
+                ..default()
+            },
+            ball,
+            graphs.add(graph),
+            player,
+            Flash::default(),
+        ))
+        .id();
+    commands
+        .entity(ball_entity)
+        .insert(AnimationTarget {
+            id: ball_animation_target_id,
+            player: ball_entity,
+        })
+        // React to the events of the animation of the ball.
+        .observe(
+            |trigger: Trigger<AnimationEvent>,
+             mut flashes: Query<&mut Flash>,
+             mut text: Query<&mut Text, With<EventText>>| {
+                let event = trigger.event();
+                if event.name != "bounce" {
+                    return;
+                }
+                if let Ok(mut flash) = flashes.get_mut(trigger.entity()) {
+                    flash.0 = 1.0;
+                }
+                if let Ok(mut text) = text.get_single_mut() {
+                    text.sections[0].value = format!(
+                        "Last event: {} ({}) at {:.2}s",
+                        event.name, event.payload, event.time
+                    );
+                }
+            },
+        );
+
+    commands.spawn((
+        TextBundle::from_section("", TextStyle::default()).with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        }),
+        EventText,
+    ));
+}
+
+fn fade_flash(
+    time: Res<Time>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut flashes: Query<(&mut Flash, &Handle<StandardMaterial>)>,
+) {
+    for (mut flash, material) in &mut flashes {
+        flash.0 = (flash.0 - time.delta_seconds() * 3.0).max(0.0);
+        if let Some(material) = materials.get_mut(material) {
+            material.emissive = LinearRgba::rgb(4.0, 1.0, 0.2) * flash.0;
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! Switches between the animations of a skinned glTF with a state machine,
+//! driven by the speed of the character and a trigger.
+
+use std::{f32::consts::PI, time::Duration};
+
+use bevy::{animation::animate_targets, pbr::CascadeShadowConfigBuilder, prelude::*};
+
+/// The parameter of the speed of the fox.
+const SPEED: &str = "speed";
+
+/// The parameter triggering the survey animation.
+const SURVEY: &str = "survey";
+
+/// The maximum speed of the fox.
+const MAX_SPEED: f32 = 4.0;
+
+fn main() {
+    App::new()
+        .insert_resource(AmbientLight {
+            color: Color::WHITE,
+            brightness: 2000.,
+        })
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .add_systems(Update, setup_scene_once_loaded.before(animate_targets))
+        .add_systems(Update, (control_fox, update_text).chain())
+        .run();
+}
+
+#[derive(Resource)]
+struct Animations {
+    graph: Handle<AnimationGraph>,
+    state_machine: Handle<AnimationStateMachine>,
+}
+
+#[derive(Component)]
+struct StateText;
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut graphs: ResMut<Assets<AnimationGraph>>,
+    mut state_machines: ResMut<Assets<AnimationStateMachine>>,
+) {
+    // Build the animation graph, with the survey, walk and run animations.
+    let mut graph = AnimationGraph::new();
+    let [survey, walk, run] = [0, 1, 2].map(|animation| {
+        let clip = asset_server
+            .load(GltfAssetLabel::Animation(animation).from_asset("models/animated/Fox.glb"));
+        graph.add_clip(clip, 1.0, graph.root)
+    });
+
+    // Build the state machine: the fox walks slowly, runs quickly, and
+    // surveys its surroundings once when triggered, before walking again.
+    let mut state_machine = AnimationStateMachine::new();
+    let walk = state_machine.add_state(AnimationState::new("Walk", walk));
+    let run = state_machine.add_state(AnimationState::new("Run", run));
+    let survey = state_machine.add_state(AnimationState::new("Survey", survey).once());
+    let fade = Duration::from_millis(300);
+    state_machine
+        .add_transition(walk, run, fade)
+        .when(TransitionCondition::Greater(SPEED.into(), 2.0));
+    state_machine
+        .add_transition(run, walk, fade)
+        .when(TransitionCondition::Less(SPEED.into(), 2.0));
+    state_machine
+        .add_transition_from_any(survey, fade)
+        .when(TransitionCondition::Trigger(SURVEY.into()));
+    state_machine
+        .add_transition(survey, walk, fade)
+        .with_exit_time(1.0);
+
+    commands.insert_resource(Animations {
+        graph: graphs.add(graph),
+        state_machine: state_machines.add(state_machine),
+    });
+
+    // Camera
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(100.0, 100.0, 150.0)
+            .looking_at(Vec3::new(0.0, 20.0, 0.0), Vec3::Y),
+        ..default()
+    });
+
+    // Plane
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Plane3d::default().mesh().size(500000.0, 500000.0)),
+        material: materials.add(Color::srgb(0.3, 0.5, 0.3)),
+        ..default()
+    });
+
+    // Light
+    commands.spawn(DirectionalLightBundle {
+        transform: Transform::from_rotation(Quat::from_euler(EulerRot::ZYX, 0.0, 1.0, -PI / 4.)),
+        directional_light: DirectionalLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        cascade_shadow_config: CascadeShadowConfigBuilder {
+            first_cascade_far_bound: 200.0,
+            maximum_distance: 400.0,
+            ..default()
+        }
+        .into(),
+        ..default()
+    });
+
+    // Fox
+    commands.spawn(SceneBundle {
+        scene: asset_server.load(GltfAssetLabel::Scene(0).from_asset("models/animated/Fox.glb")),
+        ..default()
+    });
+
+    commands.spawn((
+        TextBundle::from_section("", TextStyle::default()).with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        }),
+        StateText,
+    ));
+}
+
+// Once the scene is loaded, let the state machine play the animations.
+fn setup_scene_once_loaded(
+    mut commands: Commands,
+    animations: Res<Animations>,
+    players: Query<Entity, Added<AnimationPlayer>>,
+) {
+    for entity in &players {
+        commands.entity(entity).insert((
+            animations.graph.clone(),
+            AnimationStateMachinePlayer::new(animations.state_machine.clone()),
+        ));
+    }
+}
+
+fn control_fox(
+    time: Res<Time>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut players: Query<&mut AnimationPlayer>,
+) {
+    let mut change = 0.0;
+    if keyboard_input.pressed(KeyCode::ArrowUp) {
+        change += 1.0;
+    }
+    if keyboard_input.pressed(KeyCode::ArrowDown) {
+        change -= 1.0;
+    }
+    for mut player in &mut players {
+        let speed = player.parameter(SPEED).unwrap_or_default();
+        let speed = (speed + change * 2.0 * time.delta_seconds()).clamp(0.0, MAX_SPEED);
+        player.set_parameter(SPEED, speed);
+        if keyboard_input.just_pressed(KeyCode::Space) {
+            player.set_parameter(SURVEY, 1.0);
+        }
+    }
+}
+
+fn update_text(
+    state_machines: Res<Assets<AnimationStateMachine>>,
+    players: Query<(&AnimationPlayer, &AnimationStateMachinePlayer)>,
+    mut text: Query<&mut Text, With<StateText>>,
+) {
+    let (Ok((player, machine_player)), Ok(mut text)) =
+        (players.get_single(), text.get_single_mut())
+    else {
+        return;
+    };
+    let state = machine_player
+        .current_state()
+        .and_then(|state| {
+            state_machines
+                .get(&machine_player.state_machine)?
+                .state(state)
+        })
+        .map_or("", |state| &state.name);
+    text.sections[0].value = format!(
+        "State: {state}\nSpeed: {:.2} (arrow up / down to change)\nSpace: survey",
+        player.parameter(SPEED).unwrap_or_default(),
+    );
+}
@@ -0,0 +1,168 @@
+//! Blends the locomotion animations of a skinned glTF with a blend space,
+//! driven by the speed of the character.
+
+use std::f32::consts::PI;
+
+use bevy::{
+    animation::{animate_targets, ActiveAnimation},
+    pbr::CascadeShadowConfigBuilder,
+    prelude::*,
+};
+
+/// The name of the parameter of the blend space.
+const SPEED: &str = "speed";
+
+/// The maximum speed of the fox.
+const MAX_SPEED: f32 = 4.0;
+
+fn main() {
+    App::new()
+        .insert_resource(AmbientLight {
+            color: Color::WHITE,
+            brightness: 2000.,
+        })
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .add_systems(Update, setup_scene_once_loaded.before(animate_targets))
+        .add_systems(Update, (change_speed, update_text).chain())
+        .run();
+}
+
+#[derive(Resource)]
+struct Animations {
+    animations: Vec<AnimationNodeIndex>,
+    graph: Handle<AnimationGraph>,
+}
+
+#[derive(Component)]
+struct SpeedText;
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut graphs: ResMut<Assets<AnimationGraph>>,
+) {
+    // Build the animation graph, placing the animations along the speed of the
+    // fox: it stands still at 0, walks at 1 and runs at 4.
+    let mut graph = AnimationGraph::new();
+    let blend_space = graph.add_blend_space_1d(SPEED, 1.0, graph.root);
+    let animations = [(0, 0.0), (1, 1.0), (2, MAX_SPEED)]
+        .into_iter()
+        .map(|(animation, speed)| {
+            let clip = asset_server
+                .load(GltfAssetLabel::Animation(animation).from_asset("models/animated/Fox.glb"));
+            graph.add_blend_space_clip_1d(clip, speed, blend_space)
+        })
+        .collect();
+
+    commands.insert_resource(Animations {
+        animations,
+        graph: graphs.add(graph),
+    });
+
+    // Camera
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(100.0, 100.0, 150.0)
+            .looking_at(Vec3::new(0.0, 20.0, 0.0), Vec3::Y),
+        ..default()
+    });
+
+    // Plane
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Plane3d::default().mesh().size(500000.0, 500000.0)),
+        material: materials.add(Color::srgb(0.3, 0.5, 0.3)),
+        ..default()
+    });
+
+    // Light
+    commands.spawn(DirectionalLightBundle {
+        transform: Transform::from_rotation(Quat::from_euler(EulerRot::ZYX, 0.0, 1.0, -PI / 4.)),
+        directional_light: DirectionalLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        cascade_shadow_config: CascadeShadowConfigBuilder {
+            first_cascade_far_bound: 200.0,
+            maximum_distance: 400.0,
+            ..default()
+        }
+        .into(),
+        ..default()
+    });
+
+    // Fox
+    commands.spawn(SceneBundle {
+        scene: asset_server.load(GltfAssetLabel::Scene(0).from_asset("models/animated/Fox.glb")),
+        ..default()
+    });
+
+    commands.spawn((
+        TextBundle::from_section("", TextStyle::default()).with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        }),
+        SpeedText,
+    ));
+}
+
+// Once the scene is loaded, play all the animations of the blend space, which
+// sets their weights.
+fn setup_scene_once_loaded(
+    mut commands: Commands,
+    animations: Res<Animations>,
+    mut players: Query<(Entity, &mut AnimationPlayer), Added<AnimationPlayer>>,
+) {
+    for (entity, mut player) in &mut players {
+        for &animation in &animations.animations {
+            player.play(animation).repeat();
+        }
+        player.set_parameter(SPEED, 0.0);
+
+        commands.entity(entity).insert(animations.graph.clone());
+    }
+}
+
+fn change_speed(
+    time: Res<Time>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut players: Query<&mut AnimationPlayer>,
+) {
+    let mut change = 0.0;
+    if keyboard_input.pressed(KeyCode::ArrowUp) {
+        change += 1.0;
+    }
+    if keyboard_input.pressed(KeyCode::ArrowDown) {
+        change -= 1.0;
+    }
+    for mut player in &mut players {
+        let speed = player.parameter(SPEED).unwrap_or_default();
+        let speed = (speed + change * 2.0 * time.delta_seconds()).clamp(0.0, MAX_SPEED);
+        player.set_parameter(SPEED, speed);
+    }
+}
+
+fn update_text(
+    animations: Res<Animations>,
+    players: Query<&AnimationPlayer>,
+    mut text: Query<&mut Text, With<SpeedText>>,
+) {
+    let (Ok(player), Ok(mut text)) = (players.get_single(), text.get_single_mut()) else {
+        return;
+    };
+    let weight = |index: usize| {
+        player
+            .animation(animations.animations[index])
+            .map_or(0.0, ActiveAnimation::computed_weight)
+    };
+    text.sections[0].value = format!(
+        "Speed: {:.2} (arrow up / down to change)\nSurvey: {:.2}\nWalk: {:.2}\nRun: {:.2}",
+        player.parameter(SPEED).unwrap_or_default(),
+        weight(0),
+        weight(1),
+        weight(2),
+    );
+}
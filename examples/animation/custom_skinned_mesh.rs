@@ -159,6 +159,7 @@ fn setup(
             SkinnedMesh {
                 inverse_bindposes: inverse_bindposes.clone(),
                 joints: joint_entities,
+                ..default()
             },
         ));
     }
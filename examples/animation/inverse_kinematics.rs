@@ -0,0 +1,136 @@
+//! Bends chains of bones towards moving targets with inverse kinematics: an arm
+//! with two bones and a tail with many.
+
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .add_systems(Update, move_targets)
+        .run();
+}
+
+#[derive(Component)]
+struct Target {
+    center: Vec3,
+    radius: f32,
+    speed: f32,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let bone_mesh = meshes.add(Cuboid::new(0.15, 1.0, 0.15));
+    let bone_material = materials.add(Color::srgb(0.8, 0.7, 0.6));
+    let target_mesh = meshes.add(Sphere::new(0.15));
+    let target_material = materials.add(Color::srgb(0.9, 0.2, 0.2));
+    let pole_material = materials.add(Color::srgb(0.2, 0.2, 0.9));
+
+    // Spawns a chain of bones, each one unit long and pointing up from its
+    // parent, and returns them from the root to the end.
+    let spawn_chain = |commands: &mut Commands, root: Vec3, count: usize| {
+        let mut bones: Vec<Entity> = Vec::new();
+        for index in 0..count {
+            let translation = if index == 0 { root } else { Vec3::Y };
+            let bone = commands
+                .spawn(SpatialBundle::from_transform(Transform::from_translation(
+                    translation,
+                )))
+                .with_children(|parent| {
+                    // The end only marks the tip of the chain.
+                    if index + 1 < count {
+                        parent.spawn(PbrBundle {
+                            mesh: bone_mesh.clone(),
+                            material: bone_material.clone(),
+                            transform: Transform::from_xyz(0.0, 0.5, 0.0),
+                            ..default()
+                        });
+                    }
+                })
+                .id();
+            if let Some(&parent) = bones.last() {
+                commands.entity(parent).add_child(bone);
+            }
+            bones.push(bone);
+        }
+        bones
+    };
+
+    let spawn_target = |commands: &mut Commands, target: Target, material| {
+        commands
+            .spawn((
+                PbrBundle {
+                    mesh: target_mesh.clone(),
+                    material,
+                    transform: Transform::from_translation(target.center),
+                    ..default()
+                },
+                target,
+            ))
+            .id()
+    };
+
+    // The arm, which bends its elbow towards the blue pole.
+    let arm = spawn_chain(&mut commands, Vec3::new(-2.0, 0.0, 0.0), 3);
+    let arm_target = spawn_target(
+        &mut commands,
+        Target {
+            center: Vec3::new(-2.0, 1.0, 0.0),
+            radius: 0.8,
+            speed: 1.0,
+        },
+        target_material.clone(),
+    );
+    let arm_pole = spawn_target(
+        &mut commands,
+        Target {
+            center: Vec3::new(-2.0, 1.0, 2.0),
+            radius: 1.0,
+            speed: -0.3,
+        },
+        pole_material,
+    );
+    commands
+        .entity(arm[2])
+        .insert(TwoBoneIk::new(arm[0], arm[1], arm[2], arm_target).with_pole(arm_pole));
+
+    // The tail, which follows the red target.
+    let tail = spawn_chain(&mut commands, Vec3::new(2.0, 0.0, 0.0), 6);
+    let tail_target = spawn_target(
+        &mut commands,
+        Target {
+            center: Vec3::new(2.0, 3.0, 0.0),
+            radius: 1.5,
+            speed: 0.7,
+        },
+        target_material,
+    );
+    commands
+        .entity(tail[0])
+        .insert(FabrikChain::new(tail, tail_target));
+
+    // Camera
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(0.0, 3.0, 9.0).looking_at(Vec3::new(0.0, 1.5, 0.0), Vec3::Y),
+        ..default()
+    });
+
+    // Light
+    commands.spawn(DirectionalLightBundle {
+        transform: Transform::from_rotation(Quat::from_euler(EulerRot::ZYX, 0.0, 1.0, -PI / 4.)),
+        ..default()
+    });
+}
+
+fn move_targets(time: Res<Time>, mut targets: Query<(&mut Transform, &Target)>) {
+    for (mut transform, target) in &mut targets {
+        let angle = time.elapsed_seconds() * target.speed;
+        transform.translation =
+            target.center + Vec3::new(angle.cos(), angle.sin(), angle.sin() * 0.5) * target.radius;
+    }
+}
@@ -0,0 +1,111 @@
+//! This example illustrates how to apply effects to the sound of an audio entity, and animate
+//! them while it plays.
+//!
+//! ## Controls
+//!
+//! | Key Binding | Action                                   |
+//! |:------------|:-----------------------------------------|
+//! | `Space`     | Dive underwater, muffling the music      |
+//! | `R`         | Toggle the reverberation of a large hall |
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .add_systems(Update, (toggle_effects, animate_effects).chain())
+        .run();
+}
+
+/// The values the effects of the music move towards.
+#[derive(Component)]
+struct Targets {
+    cutoff: f32,
+    reverb_send: f32,
+}
+
+const OPEN_CUTOFF: f32 = 20_000.0;
+const UNDERWATER_CUTOFF: f32 = 400.0;
+const HALL_REVERB_SEND: f32 = 0.6;
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        AudioBundle {
+            source: asset_server.load("sounds/Windless Slopes.ogg"),
+            settings: PlaybackSettings::LOOP,
+        },
+        // The effects are applied in order: the reverberation of the hall is muffled too.
+        AudioEffects(vec![
+            AudioEffect::Reverb {
+                send: 0.0,
+                room_size: 0.85,
+                damping: 0.5,
+            },
+            AudioEffect::LowPass {
+                cutoff: OPEN_CUTOFF,
+            },
+        ]),
+        Targets {
+            cutoff: OPEN_CUTOFF,
+            reverb_send: 0.0,
+        },
+    ));
+
+    commands.spawn(Camera2dBundle::default());
+    commands.spawn(
+        TextBundle::from_section(
+            "Space: dive underwater\nR: toggle the reverberation of a large hall",
+            TextStyle::default(),
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        }),
+    );
+}
+
+fn toggle_effects(keyboard_input: Res<ButtonInput<KeyCode>>, mut targets: Query<&mut Targets>) {
+    for mut targets in &mut targets {
+        if keyboard_input.just_pressed(KeyCode::Space) {
+            targets.cutoff = if targets.cutoff == OPEN_CUTOFF {
+                UNDERWATER_CUTOFF
+            } else {
+                OPEN_CUTOFF
+            };
+        }
+        if keyboard_input.just_pressed(KeyCode::KeyR) {
+            targets.reverb_send = HALL_REVERB_SEND - targets.reverb_send;
+        }
+    }
+}
+
+/// Moves the parameters of the effects towards their targets, which are applied to the music
+/// while it plays.
+fn animate_effects(time: Res<Time>, mut query: Query<(&mut AudioEffects, &Targets)>) {
+    let blend = 1.0 - (-4.0 * time.delta_seconds()).exp();
+    for (mut effects, targets) in &mut query {
+        // Only mark the effects as changed while they move, so they're not sent to the audio
+        // thread every frame.
+        let mut changed = false;
+        for effect in effects.bypass_change_detection().iter_mut() {
+            match effect {
+                AudioEffect::LowPass { cutoff } if (*cutoff - targets.cutoff).abs() > 1.0 => {
+                    // Frequencies are perceived logarithmically.
+                    *cutoff = cutoff.ln().lerp(targets.cutoff.ln(), blend).exp();
+                    changed = true;
+                }
+                AudioEffect::Reverb { send, .. } if (*send - targets.reverb_send).abs() > 1e-3 => {
+                    *send = send.lerp(targets.reverb_send, blend);
+                    changed = true;
+                }
+                _ => {}
+            }
+        }
+        if changed {
+            effects.set_changed();
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! This example illustrates how to mix sounds into the buses of the [`AudioMixer`], and lower the
+//! volume of the music while the voices play.
+//!
+//! ## Controls
+//!
+//! | Key Binding | Action                                 |
+//! |:------------|:---------------------------------------|
+//! | `Space`     | Play a sound on the voice bus          |
+//! | `Enter`     | Play a sound on the sound effects bus  |
+//! | `Up`        | Raise the volume of the music bus      |
+//! | `Down`      | Lower the volume of the music bus      |
+
+use bevy::{
+    audio::{AudioPlugin, Ducking, Volume},
+    prelude::*,
+};
+
+fn main() {
+    let mut mixer = AudioMixer::default();
+    // Lower the music while the voices play, so they can be heard.
+    mixer.bus_mut(&AudioBus::MUSIC).ducking = Some(Ducking::new(AudioBus::VOICE, Volume::new(0.2)));
+
+    App::new()
+        .add_plugins(DefaultPlugins.set(AudioPlugin { mixer, ..default() }))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (play_sounds, change_music_volume))
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        AudioBundle {
+            source: asset_server.load("sounds/Windless Slopes.ogg"),
+            settings: PlaybackSettings::LOOP,
+        },
+        AudioBus::MUSIC,
+    ));
+
+    commands.spawn(Camera2dBundle::default());
+    commands.spawn(
+        TextBundle::from_section(
+            "Space: play a voice, ducking the music\n\
+            Enter: play a sound effect\n\
+            Up/Down: change the volume of the music",
+            TextStyle::default(),
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        }),
+    );
+}
+
+fn play_sounds(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    asset_server: Res<AssetServer>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        // A guitar stands in for a line of dialogue.
+        commands.spawn((
+            AudioBundle {
+                source: asset_server.load("sounds/Mysterious acoustic guitar.ogg"),
+                settings: PlaybackSettings::DESPAWN,
+            },
+            AudioBus::VOICE,
+        ));
+    }
+    if keyboard_input.just_pressed(KeyCode::Enter) {
+        commands.spawn((
+            AudioBundle {
+                source: asset_server.load("sounds/breakout_collision.ogg"),
+                settings: PlaybackSettings::DESPAWN,
+            },
+            AudioBus::SFX,
+        ));
+    }
+}
+
+fn change_music_volume(keyboard_input: Res<ButtonInput<KeyCode>>, mut mixer: ResMut<AudioMixer>) {
+    let change = if keyboard_input.just_pressed(KeyCode::ArrowUp) {
+        0.1
+    } else if keyboard_input.just_pressed(KeyCode::ArrowDown) {
+        -0.1
+    } else {
+        return;
+    };
+    let music = mixer.bus_mut(&AudioBus::MUSIC);
+    music.volume = Volume::new((*music.volume + change).clamp(0.0, 1.0));
+}
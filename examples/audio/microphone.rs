@@ -0,0 +1,128 @@
+//! This example illustrates how to capture the sound of a microphone, and react to its loudness.
+//!
+//! ## Controls
+//!
+//! | Key Binding      | Action                               |
+//! |:-----------------|:-------------------------------------|
+//! | `Space`          | Start or stop capturing              |
+//! | `0`              | Capture the default input device     |
+//! | `1` - `9`        | Capture one of the listed devices    |
+
+use bevy::{
+    audio::{AudioCapture, AudioInputDevice, CapturedAudio},
+    prelude::*,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .add_systems(Update, (select_device, update_level, update_text))
+        .run();
+}
+
+#[derive(Resource)]
+struct InputDevices(Vec<AudioInputDevice>);
+
+#[derive(Component)]
+struct LevelBar;
+
+#[derive(Component)]
+struct DevicesText;
+
+fn setup(mut commands: Commands, mut capture: ResMut<AudioCapture>) {
+    capture.enabled = true;
+    commands.insert_resource(InputDevices(AudioInputDevice::available()));
+
+    commands.spawn(Camera2dBundle::default());
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::srgb(0.3, 0.8, 0.4),
+                custom_size: Some(Vec2::new(40.0, 1.0)),
+                anchor: bevy::sprite::Anchor::BottomCenter,
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, -200.0, 0.0),
+            ..default()
+        },
+        LevelBar,
+    ));
+    commands.spawn((
+        TextBundle::from_section("", TextStyle::default()).with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        }),
+        DevicesText,
+    ));
+}
+
+fn select_device(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    devices: Res<InputDevices>,
+    mut capture: ResMut<AudioCapture>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        capture.enabled = !capture.enabled;
+    }
+    if keyboard_input.just_pressed(KeyCode::Digit0) {
+        capture.device = None;
+    }
+    let digits = [
+        KeyCode::Digit1,
+        KeyCode::Digit2,
+        KeyCode::Digit3,
+        KeyCode::Digit4,
+        KeyCode::Digit5,
+        KeyCode::Digit6,
+        KeyCode::Digit7,
+        KeyCode::Digit8,
+        KeyCode::Digit9,
+    ];
+    for (key, device) in digits.into_iter().zip(&devices.0) {
+        if keyboard_input.just_pressed(key) {
+            capture.device = Some(device.name.clone());
+        }
+    }
+}
+
+fn update_level(
+    time: Res<Time>,
+    mut captured_audio: EventReader<CapturedAudio>,
+    mut level_bar: Query<&mut Transform, With<LevelBar>>,
+    mut level: Local<f32>,
+) {
+    // Rise with the loudest sound since the last frame, and fall back smoothly.
+    let loudness = captured_audio
+        .read()
+        .map(CapturedAudio::rms)
+        .fold(0.0, f32::max);
+    *level = loudness.max(*level - time.delta_seconds() * 0.5);
+    if let Ok(mut transform) = level_bar.get_single_mut() {
+        transform.scale.y = (*level * 2000.0).min(400.0);
+    }
+}
+
+fn update_text(
+    capture: Res<AudioCapture>,
+    devices: Res<InputDevices>,
+    mut text: Query<&mut Text, With<DevicesText>>,
+) {
+    let Ok(mut text) = text.get_single_mut() else {
+        return;
+    };
+    let mut value = match capture.active_device() {
+        Some(device) => format!(
+            "Capturing {} ({} channels, {} Hz)\n",
+            device.name, device.channels, device.sample_rate
+        ),
+        None => "Not capturing\n".to_string(),
+    };
+    value.push_str("\nSpace: start or stop capturing\n0: default input device\n");
+    for (index, device) in devices.0.iter().take(9).enumerate() {
+        value.push_str(&format!("{}: {}\n", index + 1, device.name));
+    }
+    text.sections[0].value = value;
+}
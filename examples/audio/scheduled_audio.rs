@@ -0,0 +1,105 @@
+//! This example illustrates how to schedule sounds on the audio clock, to start them in sync with
+//! the beats of the music, and how to loop a region of the music after an intro.
+//!
+//! ## Controls
+//!
+//! | Key Binding | Action                              |
+//! |:------------|:------------------------------------|
+//! | `Space`     | Play a sound on the next beat       |
+//! | `Enter`     | Play a sound on the next bar        |
+
+use bevy::{
+    audio::{LoopPoints, Tempo},
+    prelude::*,
+    utils::Duration,
+};
+
+/// How far ahead of the audio clock sounds are scheduled, so they're never late.
+const SCHEDULE_MARGIN: Duration = Duration::from_millis(50);
+
+/// The number of beats in a bar of the music.
+const BEATS_PER_BAR: u32 = 4;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .add_systems(Update, (play_on_beat, update_beat_text))
+        .run();
+}
+
+#[derive(Component)]
+struct BeatText;
+
+#[derive(Resource)]
+struct HitSound(Handle<AudioSource>);
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>, clock: Res<AudioClock>) {
+    // Start the music a bit ahead, so its first beat is exactly on the tempo.
+    let start = clock.now() + SCHEDULE_MARGIN;
+    commands.spawn((
+        AudioBundle {
+            source: asset_server.load("sounds/Windless Slopes.ogg"),
+            // Play the first four seconds once, and then repeat the rest of the music.
+            settings: PlaybackSettings::LOOP
+                .start_at(start)
+                .with_loop_points(LoopPoints {
+                    start: Duration::from_secs(4),
+                    end: None,
+                }),
+        },
+        Tempo::new(120.0, start),
+    ));
+    commands.insert_resource(HitSound(asset_server.load("sounds/breakout_collision.ogg")));
+
+    commands.spawn(Camera2dBundle::default());
+    commands.spawn((
+        TextBundle::from_section("", TextStyle::default()).with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        }),
+        BeatText,
+    ));
+}
+
+fn play_on_beat(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    clock: Res<AudioClock>,
+    hit_sound: Res<HitSound>,
+    tempo: Query<&Tempo>,
+) {
+    let Ok(tempo) = tempo.get_single() else {
+        return;
+    };
+    let earliest = clock.now() + SCHEDULE_MARGIN;
+    let start = if keyboard_input.just_pressed(KeyCode::Space) {
+        tempo.next_beat(earliest)
+    } else if keyboard_input.just_pressed(KeyCode::Enter) {
+        tempo.next_bar(earliest, BEATS_PER_BAR)
+    } else {
+        return;
+    };
+    commands.spawn(AudioBundle {
+        source: hit_sound.0.clone(),
+        settings: PlaybackSettings::DESPAWN.start_at(start),
+    });
+}
+
+fn update_beat_text(
+    clock: Res<AudioClock>,
+    tempo: Query<&Tempo>,
+    mut text: Query<&mut Text, With<BeatText>>,
+) {
+    let (Ok(tempo), Ok(mut text)) = (tempo.get_single(), text.get_single_mut()) else {
+        return;
+    };
+    let beats = tempo.beats_at(clock.now()).max(0.0) as u32;
+    text.sections[0].value = format!(
+        "Bar {}, beat {}\nSpace: play a sound on the next beat\nEnter: play a sound on the next bar",
+        beats / BEATS_PER_BAR + 1,
+        beats % BEATS_PER_BAR + 1,
+    );
+}
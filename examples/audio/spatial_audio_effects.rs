@@ -0,0 +1,150 @@
+//! This example illustrates how to change how spatial sounds are heard: how they fade with the
+//! distance, which direction they're emitted towards, the doppler effect of moving emitters, and
+//! their occlusion by walls.
+//!
+//! A car playing music drives back and forth behind a wall, its sound louder in front of it.
+
+use bevy::{
+    audio::{
+        AttenuationModel, DopplerSettings, SpatialAttenuation, SpatialCone, SpatialOcclusion,
+        SpatialVelocity,
+    },
+    math::bounding::{Aabb2d, IntersectsVolume, RayCast2d},
+    prelude::*,
+    sprite::MaterialMesh2dBundle,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        // The world is measured in pixels, so the speed of sound is too.
+        .insert_resource(DopplerSettings {
+            speed_of_sound: 1500.0,
+            factor: 1.0,
+        })
+        .add_systems(Startup, setup)
+        .add_systems(Update, (drive, update_occlusion))
+        .run();
+}
+
+const CAR_SPEED: f32 = 400.0;
+const ROAD_LENGTH: f32 = 1000.0;
+const WALL_SIZE: Vec2 = Vec2::new(300.0, 20.0);
+const WALL_POSITION: Vec2 = Vec2::new(0.0, 80.0);
+
+#[derive(Component)]
+struct Car;
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.spawn(Camera2dBundle::default());
+
+    commands.spawn((
+        SpatialBundle::default(),
+        SpatialListener::new(40.0),
+        SpatialVelocity::default(),
+    ));
+    commands.spawn(MaterialMesh2dBundle {
+        mesh: meshes.add(Circle::new(15.0)).into(),
+        material: materials.add(Color::srgb(0.3, 0.6, 1.0)),
+        ..default()
+    });
+
+    commands.spawn(MaterialMesh2dBundle {
+        mesh: meshes.add(Rectangle::from_size(WALL_SIZE)).into(),
+        material: materials.add(Color::srgb(0.5, 0.5, 0.5)),
+        transform: Transform::from_translation(WALL_POSITION.extend(0.0)),
+        ..default()
+    });
+
+    commands.spawn((
+        MaterialMesh2dBundle {
+            mesh: meshes.add(Rectangle::new(60.0, 30.0)).into(),
+            material: materials.add(Color::srgb(1.0, 0.3, 0.2)),
+            transform: Transform::from_xyz(0.0, 200.0, 0.0),
+            ..default()
+        },
+        Car,
+        AudioBundle {
+            source: asset_server.load("sounds/Windless Slopes.ogg"),
+            settings: PlaybackSettings::LOOP.with_spatial(true),
+        },
+        // Full volume within 100 pixels, fading out to silence at 800 pixels.
+        SpatialAttenuation {
+            model: AttenuationModel::Inverse { rolloff: 1.0 },
+            min_distance: 100.0,
+            max_distance: 800.0,
+        },
+        // Louder in front of the car.
+        SpatialCone {
+            direction: Dir3::X,
+            inner_angle: 90f32.to_radians(),
+            outer_angle: 270f32.to_radians(),
+            outer_volume: 0.3,
+        },
+        SpatialOcclusion::default(),
+        SpatialVelocity::default(),
+    ));
+
+    commands.spawn(
+        TextBundle::from_section(
+            "The sound of the car is louder in front of it, muffled behind the wall,\n\
+            and its pitch changes as it passes by",
+            TextStyle::default(),
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        }),
+    );
+}
+
+fn drive(time: Res<Time>, mut cars: Query<(&mut Transform, &mut SpatialVelocity), With<Car>>) {
+    for (mut transform, mut velocity) in &mut cars {
+        let mut direction = transform.rotation * Vec3::X;
+        if transform.translation.x.abs() > ROAD_LENGTH / 2.0
+            && direction.x * transform.translation.x > 0.0
+        {
+            // Turn around at the end of the road.
+            transform.rotate_z(std::f32::consts::PI);
+            direction = -direction;
+        }
+        velocity.0 = direction * CAR_SPEED;
+        transform.translation += velocity.0 * time.delta_seconds();
+    }
+}
+
+/// Computes how much the wall blocks the sound of the car, with a ray from the listener to the
+/// car.
+fn update_occlusion(
+    time: Res<Time>,
+    listeners: Query<&GlobalTransform, With<SpatialListener>>,
+    mut emitters: Query<(&GlobalTransform, &mut SpatialOcclusion)>,
+) {
+    let Ok(listener) = listeners.get_single() else {
+        return;
+    };
+    let wall = Aabb2d::new(WALL_POSITION, WALL_SIZE / 2.0);
+    for (emitter, mut occlusion) in &mut emitters {
+        let to_emitter = (emitter.translation() - listener.translation()).truncate();
+        let blocked = Dir2::new(to_emitter).is_ok_and(|direction| {
+            RayCast2d::new(
+                listener.translation().truncate(),
+                direction,
+                to_emitter.length(),
+            )
+            .intersects(&wall)
+        });
+        // Move the occlusion smoothly, as the sound bends around the edges of the wall.
+        let target = if blocked { 1.0 } else { 0.0 };
+        occlusion.factor = occlusion
+            .factor
+            .lerp(target, 1.0 - (-8.0 * time.delta_seconds()).exp());
+    }
+}
@@ -0,0 +1,94 @@
+//! This example illustrates how to stream a long sound like music while it plays, instead of
+//! keeping it decoded in memory, and seek in it.
+//!
+//! ## Controls
+//!
+//! | Key Binding | Action                      |
+//! |:------------|:----------------------------|
+//! | `Left`      | Seek 10 seconds backward    |
+//! | `Right`     | Seek 10 seconds forward     |
+//! | `Home`      | Seek back to the start      |
+
+use bevy::{
+    audio::{AudioLoaderSettings, StreamingSettings},
+    prelude::*,
+    utils::Duration,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .add_systems(Update, (seek, update_position_text))
+        .run();
+}
+
+#[derive(Component)]
+struct MyMusic;
+
+#[derive(Component)]
+struct PositionText;
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        AudioBundle {
+            source: asset_server.load_with_settings(
+                "sounds/Epic orchestra music.ogg",
+                |settings: &mut AudioLoaderSettings| {
+                    // Decode a second or so ahead of the playback.
+                    settings.streaming = Some(StreamingSettings {
+                        chunk_frames: 4096,
+                        buffered_chunks: 12,
+                    });
+                },
+            ),
+            settings: PlaybackSettings::LOOP,
+        },
+        MyMusic,
+    ));
+
+    commands.spawn(Camera2dBundle::default());
+    commands.spawn((
+        TextBundle::from_section("", TextStyle::default()).with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        }),
+        PositionText,
+    ));
+}
+
+fn seek(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    music_controller: Query<&AudioSink, With<MyMusic>>,
+) {
+    let Ok(sink) = music_controller.get_single() else {
+        return;
+    };
+    let position = if keyboard_input.just_pressed(KeyCode::ArrowLeft) {
+        sink.position().saturating_sub(Duration::from_secs(10))
+    } else if keyboard_input.just_pressed(KeyCode::ArrowRight) {
+        sink.position() + Duration::from_secs(10)
+    } else if keyboard_input.just_pressed(KeyCode::Home) {
+        Duration::ZERO
+    } else {
+        return;
+    };
+    if let Err(error) = sink.try_seek(position) {
+        warn!("Failed to seek in the music: {error}");
+    }
+}
+
+fn update_position_text(
+    music_controller: Query<&AudioSink, With<MyMusic>>,
+    mut text: Query<&mut Text, With<PositionText>>,
+) {
+    let (Ok(sink), Ok(mut text)) = (music_controller.get_single(), text.get_single_mut()) else {
+        return;
+    };
+    text.sections[0].value = format!(
+        "Position: {:.1}s\nLeft/Right: seek 10 seconds\nHome: seek to the start",
+        sink.position().as_secs_f32()
+    );
+}
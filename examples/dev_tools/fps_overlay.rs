@@ -18,7 +18,10 @@ fn main() {
                         color: Color::srgb(0.0, 1.0, 0.0),
                         // If we want, we can use a custom font
                         font: default(),
+                        ..default()
                     },
+                    // We can also show the input-to-present latency of the frames
+                    show_latency: false,
                 },
             },
         ))
@@ -47,7 +50,8 @@ fn setup(mut commands: Commands) {
             c.spawn(TextBundle::from_section(
                 concat!(
                     "Press 1 to change color of the overlay.\n",
-                    "Press 2 to change size of the overlay."
+                    "Press 2 to change size of the overlay.\n",
+                    "Press 3 to toggle the frame latency."
                 ),
                 TextStyle {
                     font_size: 25.0,
@@ -65,4 +69,7 @@ fn customize_config(input: Res<ButtonInput<KeyCode>>, mut overlay: ResMut<FpsOve
     if input.just_pressed(KeyCode::Digit2) {
         overlay.text_config.font_size -= 2.0;
     }
+    if input.just_pressed(KeyCode::Digit3) {
+        overlay.show_latency = !overlay.show_latency;
+    }
 }
@@ -0,0 +1,49 @@
+//! Shows an infinite reference grid under a moving camera.
+
+use bevy::{
+    dev_tools::infinite_grid::{InfiniteGrid, InfiniteGridPlugin},
+    prelude::*,
+};
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, InfiniteGridPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, move_camera)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(8.0, 4.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        },
+        InfiniteGrid::default(),
+    ));
+
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Cuboid::default()),
+        material: materials.add(Color::srgb(0.8, 0.7, 0.6)),
+        transform: Transform::from_xyz(0.0, 0.5, 0.0),
+        ..default()
+    });
+
+    commands.spawn(DirectionalLightBundle {
+        transform: Transform::from_xyz(4.0, 8.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+}
+
+fn move_camera(time: Res<Time>, mut cameras: Query<&mut Transform, With<InfiniteGrid>>) {
+    let angle = time.elapsed_seconds() * 0.2;
+    let height = 4.0 + 3.0 * (time.elapsed_seconds() * 0.5).sin();
+    for mut transform in &mut cameras {
+        *transform = Transform::from_xyz(10.0 * angle.cos(), height, 10.0 * angle.sin())
+            .looking_at(Vec3::ZERO, Vec3::Y);
+    }
+}
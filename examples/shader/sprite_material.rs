@@ -0,0 +1,118 @@
+//! Renders sprites with a custom shader, using a sprite material to make them flash white when
+//! they are hit and draw an outline around them.
+//!
+//! Unlike a [`Material2d`](bevy::sprite::Material2d), which needs a mesh, sprite materials are
+//! added to regular sprites and keep the sprite's image, color, flipping and batching.
+
+use bevy::{
+    prelude::*,
+    reflect::TypePath,
+    render::render_resource::{AsBindGroup, ShaderRef},
+    sprite::{SpriteMaterial, SpriteMaterialPlugin},
+};
+
+/// This example uses a shader source file from the assets subdirectory
+const SHADER_ASSET_PATH: &str = "shaders/sprite_material.wgsl";
+
+fn main() {
+    App::new()
+        .add_plugins((
+            DefaultPlugins,
+            SpriteMaterialPlugin::<HitMaterial>::default(),
+        ))
+        .insert_resource(HitTimer(Timer::from_seconds(0.7, TimerMode::Repeating)))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (hit_birds, fade_flashes).chain())
+        .run();
+}
+
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+struct HitMaterial {
+    /// The color the sprite flashes to, blended by its alpha.
+    #[uniform(0)]
+    flash: LinearRgba,
+    /// The color of the outline, which isn't drawn if its alpha is zero.
+    #[uniform(0)]
+    outline: LinearRgba,
+    /// The thickness of the outline, in texels.
+    #[uniform(0)]
+    outline_thickness: f32,
+}
+
+impl SpriteMaterial for HitMaterial {
+    fn fragment_shader() -> ShaderRef {
+        SHADER_ASSET_PATH.into()
+    }
+}
+
+#[derive(Component)]
+struct Bird;
+
+fn setup(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<HitMaterial>>,
+    asset_server: Res<AssetServer>,
+) {
+    commands.spawn(Camera2dBundle::default());
+
+    let texture = asset_server.load("branding/bevy_bird_dark.png");
+    for (i, outline) in [Color::NONE, Color::srgb(1.0, 0.8, 0.0), Color::NONE]
+        .into_iter()
+        .enumerate()
+    {
+        commands.spawn((
+            SpriteBundle {
+                texture: texture.clone(),
+                transform: Transform::from_xyz(i as f32 * 300.0 - 300.0, 0.0, 0.0)
+                    .with_scale(Vec3::splat(0.4)),
+                ..default()
+            },
+            // Each bird has its own material so it can flash on its own. Sprites sharing a
+            // material and an image are drawn in a single batch.
+            materials.add(HitMaterial {
+                flash: LinearRgba::NONE,
+                outline: outline.into(),
+                outline_thickness: 12.0,
+            }),
+            Bird,
+        ));
+    }
+}
+
+#[derive(Resource)]
+struct HitTimer(Timer);
+
+/// Hits one bird after the other, making it flash white.
+fn hit_birds(
+    time: Res<Time>,
+    mut timer: ResMut<HitTimer>,
+    mut next_bird: Local<usize>,
+    birds: Query<&Handle<HitMaterial>, With<Bird>>,
+    mut materials: ResMut<Assets<HitMaterial>>,
+) {
+    if !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+    let birds: Vec<_> = birds.iter().collect();
+    if birds.is_empty() {
+        return;
+    }
+    let handle = birds[*next_bird % birds.len()];
+    *next_bird += 1;
+    if let Some(material) = materials.get_mut(handle) {
+        material.flash = LinearRgba::WHITE;
+    }
+}
+
+fn fade_flashes(time: Res<Time>, mut materials: ResMut<Assets<HitMaterial>>) {
+    // Only the materials that are changed are prepared for the GPU again.
+    let flashing: Vec<_> = materials
+        .iter()
+        .filter(|(_, material)| material.flash.alpha > 0.0)
+        .map(|(id, _)| id)
+        .collect();
+    for id in flashing {
+        let material = materials.get_mut(id).unwrap();
+        material.flash.alpha = (material.flash.alpha - time.delta_seconds() * 4.0).max(0.0);
+    }
+}
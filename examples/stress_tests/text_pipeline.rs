@@ -48,6 +48,7 @@ fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
                         font: asset_server.load("fonts/FiraMono-Medium.ttf"),
                         font_size: (4 + i % 10) as f32,
                         color: BLUE.into(),
+                        ..default()
                     },
                 },
                 TextSection {
@@ -56,6 +57,7 @@ fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
                         font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                         font_size: (4 + i % 11) as f32,
                         color: YELLOW.into(),
+                        ..default()
                     },
                 },
             ]
@@ -66,6 +68,7 @@ fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
             sections,
             justify: JustifyText::Center,
             linebreak_behavior: BreakLineOn::AnyCharacter,
+            ..default()
         },
         ..Default::default()
     });
@@ -0,0 +1,93 @@
+//! Demonstrates the [`Opacity`] of UI nodes: the left panel fades out as a group, while the right
+//! panel fades the alpha of each of its children, so that they show through each other.
+//! The panels clip their children with rounded corners.
+
+use bevy::{color::palettes::css::*, prelude::*};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .add_systems(Update, fade)
+        .run();
+}
+
+#[derive(Component)]
+struct GroupFade;
+
+#[derive(Component)]
+struct AlphaFade;
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+
+    let panel = || NodeBundle {
+        style: Style {
+            width: Val::Px(300.),
+            height: Val::Px(300.),
+            margin: UiRect::all(Val::Px(30.)),
+            overflow: Overflow::clip(),
+            ..default()
+        },
+        background_color: Color::srgb(0.15, 0.15, 0.2).into(),
+        border_radius: BorderRadius::all(Val::Px(60.)),
+        ..default()
+    };
+    let square = |left: f32, top: f32, color: Srgba| NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            left: Val::Px(left),
+            top: Val::Px(top),
+            width: Val::Px(180.),
+            height: Val::Px(180.),
+            ..default()
+        },
+        background_color: color.into(),
+        ..default()
+    };
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            background_color: Color::srgb(0.6, 0.6, 0.6).into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn((panel(), Opacity(1.), GroupFade))
+                .with_children(|parent| {
+                    parent.spawn(square(-20., -20., CRIMSON));
+                    parent.spawn(square(140., 140., ROYAL_BLUE));
+                    parent.spawn(square(60., 60., GOLD));
+                });
+            parent.spawn(panel()).with_children(|parent| {
+                for (left, top, color) in [
+                    (-20., -20., CRIMSON),
+                    (140., 140., ROYAL_BLUE),
+                    (60., 60., GOLD),
+                ] {
+                    parent.spawn((square(left, top, color), AlphaFade));
+                }
+            });
+        });
+}
+
+fn fade(
+    time: Res<Time>,
+    mut groups: Query<&mut Opacity, With<GroupFade>>,
+    mut squares: Query<&mut BackgroundColor, With<AlphaFade>>,
+) {
+    let alpha = 0.5 + 0.5 * time.elapsed_seconds().cos();
+    for mut opacity in &mut groups {
+        opacity.0 = alpha;
+    }
+    for mut background_color in &mut squares {
+        background_color.0.set_alpha(alpha);
+    }
+}
@@ -61,6 +61,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                         font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                         font_size: 40.0,
                         color: Color::srgb(0.9, 0.9, 0.9),
+                        ..default()
                     },
                 ),
                 ..default()
@@ -0,0 +1,139 @@
+//! Demonstrates text made of sections with their own font size, color, highlight, underline and
+//! strikethrough, and links that can be hovered and clicked.
+
+use bevy::{color::palettes::css::*, prelude::*};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .add_systems(Update, follow_links)
+        .run();
+}
+
+#[derive(Component)]
+struct Status;
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let style = TextStyle {
+        font: font.clone(),
+        font_size: 30.,
+        ..default()
+    };
+    let link_style = |target: &str| {
+        TextStyle {
+            color: DEEP_SKY_BLUE.into(),
+            underline: true,
+            ..style.clone()
+        }
+        .with_link(target)
+    };
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(30.),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn(
+                TextBundle::from_sections([
+                    TextSection::new("Sections can be ", style.clone()),
+                    TextSection::new(
+                        "larger",
+                        TextStyle {
+                            font_size: 45.,
+                            ..style.clone()
+                        },
+                    ),
+                    TextSection::new(", ", style.clone()),
+                    TextSection::new(
+                        "colored",
+                        TextStyle {
+                            color: GOLD.into(),
+                            ..style.clone()
+                        },
+                    ),
+                    TextSection::new(", ", style.clone()),
+                    TextSection::new(
+                        "highlighted",
+                        TextStyle {
+                            color: BLACK.into(),
+                            background_color: YELLOW.into(),
+                            ..style.clone()
+                        },
+                    ),
+                    TextSection::new(", ", style.clone()),
+                    TextSection::new(
+                        "underlined",
+                        TextStyle {
+                            underline: true,
+                            ..style.clone()
+                        },
+                    ),
+                    TextSection::new(" or ", style.clone()),
+                    TextSection::new(
+                        "struck through",
+                        TextStyle {
+                            strikethrough: true,
+                            ..style.clone()
+                        },
+                    ),
+                    TextSection::new(".", style.clone()),
+                ])
+                .with_style(Style {
+                    max_width: Val::Px(600.),
+                    ..default()
+                }),
+            );
+            parent.spawn(TextBundle::from_sections([
+                TextSection::new("Read the ", style.clone()),
+                TextSection::new("credits", link_style("credits")),
+                TextSection::new(" or the ", style.clone()),
+                TextSection::new("license", link_style("license")),
+                TextSection::new(".", style.clone()),
+            ]));
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font,
+                        font_size: 24.,
+                        color: GRAY.into(),
+                        ..default()
+                    },
+                ),
+                Status,
+            ));
+        });
+}
+
+fn follow_links(
+    mut events: EventReader<TextLinkEvent>,
+    mut texts: Query<&mut Text, Without<Status>>,
+    mut status: Query<&mut Text, With<Status>>,
+) {
+    for event in events.read() {
+        let Ok(mut text) = texts.get_mut(event.entity) else {
+            continue;
+        };
+        let style = &mut text.sections[event.section].style;
+        match event.kind {
+            TextLinkEventKind::Over => style.background_color = MIDNIGHT_BLUE.into(),
+            TextLinkEventKind::Out => style.background_color = Color::NONE,
+            TextLinkEventKind::Click => {
+                status.single_mut().sections[0].value = format!("Followed \"{}\"", event.link.0);
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-//! Example demonstrating rounded bordered UI nodes
+//! Example demonstrating rounded bordered UI nodes, border styles and box shadows
 
 use bevy::{color::palettes::css::*, prelude::*};
 
@@ -173,4 +173,83 @@ fn setup(mut commands: Commands) {
             .id();
         commands.entity(root).add_child(container);
     }
+
+    // panels styled with border styles and box shadows, without any image assets
+    let panels = [
+        (
+            "Dashed",
+            BorderStyle::Dashed {
+                dash_length: 8.,
+                gap_length: 4.,
+            },
+            None,
+        ),
+        ("Dotted", BorderStyle::Dotted, None),
+        (
+            "Shadow",
+            BorderStyle::Solid,
+            Some(BoxShadow::new(
+                Color::BLACK.with_alpha(0.8),
+                Val::Px(4.),
+                Val::Px(4.),
+                Val::ZERO,
+                Val::Px(8.),
+            )),
+        ),
+        (
+            "Glow",
+            BorderStyle::Solid,
+            Some(BoxShadow::new(
+                YELLOW.into(),
+                Val::ZERO,
+                Val::ZERO,
+                Val::Px(4.),
+                Val::Px(12.),
+            )),
+        ),
+    ];
+
+    for (label, border_style, box_shadow) in panels {
+        let mut panel = commands.spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Px(80.),
+                    height: Val::Px(50.),
+                    border: UiRect::all(Val::Px(4.)),
+                    margin: UiRect::all(Val::Px(20.)),
+                    ..Default::default()
+                },
+                background_color: MAROON.into(),
+                border_color: RED.into(),
+                border_radius: BorderRadius::px(16., 4., 16., 4.),
+                ..Default::default()
+            },
+            border_style,
+        ));
+        if let Some(box_shadow) = box_shadow {
+            panel.insert(box_shadow);
+        }
+        let panel = panel.id();
+        let label_node = commands
+            .spawn(TextBundle::from_section(
+                label,
+                TextStyle {
+                    font_size: 9.0,
+                    ..Default::default()
+                },
+            ))
+            .id();
+        let container = commands
+            .spawn(NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .push_children(&[panel, label_node])
+            .id();
+        commands.entity(root).add_child(container);
+    }
 }
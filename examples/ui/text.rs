@@ -78,6 +78,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font: asset_server.load("fonts/FiraMono-Medium.ttf"),
                     font_size: 60.0,
                     color: GOLD.into(),
+                    ..default()
                 }
             }),
         ]),
@@ -0,0 +1,123 @@
+//! Demonstrates animating the glyphs of UI text with text effects: a wave, a typewriter reveal
+//! and a shake.
+
+use bevy::{
+    prelude::*,
+    text::{GlyphEffect, TextEffect, TextEffectGlyph, TextEffectPlugin},
+};
+
+fn main() {
+    App::new()
+        .add_plugins((
+            DefaultPlugins,
+            TextEffectPlugin::<Wave>::default(),
+            TextEffectPlugin::<Typewriter>::default(),
+            TextEffectPlugin::<Shake>::default(),
+        ))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+/// Moves the glyphs up and down in a wave.
+#[derive(Component)]
+struct Wave {
+    height: f32,
+}
+
+impl TextEffect for Wave {
+    fn apply(&self, glyph: &TextEffectGlyph, effect: &mut GlyphEffect) {
+        let phase = glyph.index as f32 * 0.6 - glyph.elapsed_seconds * 5.0;
+        // UI text has `y` pointing down.
+        effect.offset.y -= phase.sin() * self.height;
+    }
+}
+
+/// Reveals the glyphs one at a time, fading each one in, then starts again.
+#[derive(Component)]
+struct Typewriter {
+    glyphs_per_second: f32,
+}
+
+impl TextEffect for Typewriter {
+    fn apply(&self, glyph: &TextEffectGlyph, effect: &mut GlyphEffect) {
+        let revealed =
+            (glyph.elapsed_seconds * self.glyphs_per_second) % (glyph.count as f32 + 10.);
+        effect.tint.alpha *= (revealed - glyph.index as f32).clamp(0., 1.);
+    }
+}
+
+/// Jitters the glyphs of the sections with the given color.
+#[derive(Component)]
+struct Shake {
+    strength: f32,
+    color: Color,
+}
+
+impl TextEffect for Shake {
+    fn apply(&self, glyph: &TextEffectGlyph, effect: &mut GlyphEffect) {
+        if glyph.section.style.color != self.color {
+            return;
+        }
+        // Change direction 20 times per second, differently for each glyph.
+        let step = (glyph.elapsed_seconds * 20.).floor();
+        let seed = glyph.index as f32 * 12.9898 + step * 78.233;
+        let random = |offset: f32| ((seed + offset).sin() * 43758.547).fract();
+        effect.offset += Vec2::new(random(0.), random(1.)) * self.strength;
+    }
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 50.0,
+        ..default()
+    };
+    let angry = Color::srgb(0.9, 0.2, 0.2);
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(40.),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section("Riding the wave", text_style.clone()),
+                Wave { height: 8. },
+            ));
+            parent.spawn((
+                TextBundle::from_section("One letter at a time...", text_style.clone()),
+                Typewriter {
+                    glyphs_per_second: 12.,
+                },
+            ));
+            // Effects can be combined, and can look at the section of each glyph.
+            parent.spawn((
+                TextBundle::from_sections([
+                    TextSection::new("I am ", text_style.clone()),
+                    TextSection::new(
+                        "very angry",
+                        TextStyle {
+                            color: angry,
+                            ..text_style.clone()
+                        },
+                    ),
+                    TextSection::new(" about this", text_style),
+                ]),
+                Wave { height: 3. },
+                Shake {
+                    strength: 3.,
+                    color: angry,
+                },
+            ));
+        });
+}
@@ -0,0 +1,106 @@
+//! This example illustrates the [`TextInput`] widget: click a field or press tab to focus it,
+//! select text with the mouse or shift and the arrow keys, and use ctrl (or cmd) with `C`, `X` and
+//! `V` to copy, cut and paste. Text can also be composed with the IME of the platform.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .add_systems(Update, (log_changes, show_submitted, highlight_focused))
+        .run();
+}
+
+#[derive(Component)]
+struct SubmittedText;
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+
+    let field = |value: &str, max_length: Option<usize>| {
+        let mut text_input = TextInput::new(value);
+        text_input.max_length = max_length;
+        TextInputBundle {
+            text_input,
+            text_input_style: TextInputStyle {
+                text_style: TextStyle {
+                    font_size: 30.,
+                    ..default()
+                },
+                ..default()
+            },
+            style: Style {
+                width: Val::Px(400.),
+                padding: UiRect::axes(Val::Px(10.), Val::Px(5.)),
+                border: UiRect::all(Val::Px(2.)),
+                ..default()
+            },
+            border_color: Color::srgb(0.4, 0.4, 0.4).into(),
+            border_radius: BorderRadius::all(Val::Px(5.)),
+            background_color: Color::srgb(0.1, 0.1, 0.1).into(),
+            ..default()
+        }
+    };
+    let label = |value: &str| {
+        TextBundle::from_section(
+            value,
+            TextStyle {
+                font_size: 20.,
+                ..default()
+            },
+        )
+    };
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(10.),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn(label("Name (up to 16 characters)"));
+            parent.spawn(field("", Some(16)));
+            parent.spawn(label("Message"));
+            parent.spawn(field("Press enter to submit", None));
+            parent.spawn((label(""), SubmittedText));
+        });
+}
+
+fn log_changes(mut events: EventReader<TextInputChanged>) {
+    for event in events.read() {
+        info!("{:?} changed to {:?}", event.entity, event.value);
+    }
+}
+
+fn show_submitted(
+    mut events: EventReader<TextInputSubmitted>,
+    mut text: Query<&mut Text, With<SubmittedText>>,
+) {
+    for event in events.read() {
+        text.single_mut().sections[0].value = format!("Submitted: {}", event.value);
+    }
+}
+
+fn highlight_focused(
+    focus: Res<UiFocus>,
+    mut inputs: Query<(Entity, &mut BorderColor), With<TextInput>>,
+) {
+    if !focus.is_changed() {
+        return;
+    }
+    for (entity, mut border_color) in &mut inputs {
+        border_color.0 = if focus.is_focused(entity) {
+            Color::srgb(0.3, 0.5, 0.9)
+        } else {
+            Color::srgb(0.4, 0.4, 0.4)
+        };
+    }
+}
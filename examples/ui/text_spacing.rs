@@ -0,0 +1,76 @@
+//! Demonstrates letter spacing, word spacing, tab stops and soft hyphens in UI text.
+
+use bevy::{prelude::*, text::TabStops};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 30.0,
+        ..default()
+    };
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(30.),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            for letter_spacing in [-2., 0., 6.] {
+                parent.spawn(TextBundle::from_section(
+                    format!("Letter spacing {letter_spacing}"),
+                    TextStyle {
+                        letter_spacing,
+                        ..text_style.clone()
+                    },
+                ));
+            }
+
+            parent.spawn(TextBundle::from_section(
+                "Word spacing makes room between words",
+                TextStyle {
+                    word_spacing: 20.,
+                    ..text_style.clone()
+                },
+            ));
+
+            // Tabs line up the columns of a table.
+            parent.spawn(TextBundle {
+                text: Text::from_section(
+                    "Item\tPrice\tStock\nApple\t1.20\t45\nWatermelon\t3.50\t7",
+                    text_style.clone(),
+                )
+                .with_tab_stops(TabStops::every(100.).with_positions([180.])),
+                ..default()
+            });
+
+            // Soft hyphens (`\u{AD}`) mark where words can be hyphenated. Only the ones at the
+            // end of a line are drawn.
+            parent.spawn(
+                TextBundle::from_section(
+                    "Ty\u{AD}pog\u{AD}ra\u{AD}phy is the art of ar\u{AD}rang\u{AD}ing type to make writ\u{AD}ten lan\u{AD}guage leg\u{AD}i\u{AD}ble and ap\u{AD}peal\u{AD}ing.",
+                    text_style,
+                )
+                .with_style(Style {
+                    max_width: Val::Px(300.),
+                    ..default()
+                }),
+            );
+        });
+}
@@ -1,4 +1,5 @@
-//! Demonstrates the use of [`UiMaterials`](UiMaterial) and how to change material values
+//! Demonstrates the use of [`UiMaterials`](UiMaterial) and how to change material values.
+//! The material is clipped to the rounded corners of its node, and drawn over a background color.
 
 use bevy::prelude::*;
 use bevy::reflect::TypePath;
@@ -49,8 +50,29 @@ fn setup(
                     slider: 0.5,
                     color_texture: asset_server.load("branding/banner.png"),
                 }),
+                border_radius: BorderRadius::all(Val::Px(40.0)),
                 ..default()
             });
+            // Materials can be added to any node, here a button with a background color
+            parent.spawn((
+                ButtonBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        bottom: Val::Px(40.0),
+                        width: Val::Px(300.0),
+                        height: Val::Px(60.0),
+                        ..default()
+                    },
+                    background_color: Color::srgb(0.15, 0.15, 0.15).into(),
+                    border_radius: BorderRadius::MAX,
+                    ..default()
+                },
+                ui_materials.add(CustomUiMaterial {
+                    color: LinearRgba::WHITE.to_f32_array().into(),
+                    slider: 0.5,
+                    color_texture: asset_server.load("branding/banner.png"),
+                }),
+            ));
         });
 }
 
@@ -107,6 +107,7 @@ fn setup(
                                 font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                                 font_size: 40.0,
                                 color: Color::srgb(0.9, 0.9, 0.9),
+                                ..default()
                             },
                         ));
                     });
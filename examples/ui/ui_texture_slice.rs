@@ -1,5 +1,8 @@
 //! This example illustrates how to create buttons with their textures sliced
-//! and kept in proportion instead of being stretched by the button dimensions
+//! and kept in proportion instead of being stretched by the button dimensions.
+//!
+//! The buttons of the first row stretch the sides and center of their texture,
+//! the buttons of the second row repeat them.
 
 use bevy::{
     color::palettes::css::{GOLD, ORANGE},
@@ -46,12 +49,17 @@ fn button_system(
 fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     let image = asset_server.load("textures/fantasy_ui_borders/panel-border-010.png");
 
-    let slicer = TextureSlicer {
+    let stretched_slicer = TextureSlicer {
         border: BorderRect::square(22.0),
         center_scale_mode: SliceScaleMode::Stretch,
         sides_scale_mode: SliceScaleMode::Stretch,
         max_corner_scale: 1.0,
     };
+    let tiled_slicer = TextureSlicer {
+        center_scale_mode: SliceScaleMode::Tile { stretch_value: 1.0 },
+        sides_scale_mode: SliceScaleMode::Tile { stretch_value: 1.0 },
+        ..stretched_slicer.clone()
+    };
     // ui camera
     commands.spawn(Camera2dBundle::default());
     commands
@@ -59,6 +67,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
             style: Style {
                 width: Val::Percent(100.0),
                 height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
                 align_items: AlignItems::Center,
                 justify_content: JustifyContent::Center,
                 ..default()
@@ -66,35 +75,57 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
             ..default()
         })
         .with_children(|parent| {
-            for [w, h] in [[150.0, 150.0], [300.0, 150.0], [150.0, 300.0]] {
+            for slicer in [stretched_slicer, tiled_slicer] {
                 parent
-                    .spawn((
-                        ButtonBundle {
-                            style: Style {
-                                width: Val::Px(w),
-                                height: Val::Px(h),
-                                // horizontally center child text
-                                justify_content: JustifyContent::Center,
-                                // vertically center child text
-                                align_items: AlignItems::Center,
-                                margin: UiRect::all(Val::Px(20.0)),
-                                ..default()
-                            },
-                            image: image.clone().into(),
+                    .spawn(NodeBundle {
+                        style: Style {
+                            align_items: AlignItems::Center,
                             ..default()
                         },
-                        ImageScaleMode::Sliced(slicer.clone()),
-                    ))
+                        ..default()
+                    })
                     .with_children(|parent| {
-                        parent.spawn(TextBundle::from_section(
-                            "Button",
-                            TextStyle {
-                                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                                font_size: 40.0,
-                                color: Color::srgb(0.9, 0.9, 0.9),
-                            },
-                        ));
+                        spawn_buttons(parent, &image, &slicer, &asset_server);
                     });
             }
         });
 }
+
+fn spawn_buttons(
+    parent: &mut ChildBuilder,
+    image: &Handle<Image>,
+    slicer: &TextureSlicer,
+    asset_server: &AssetServer,
+) {
+    for [w, h] in [[150.0, 150.0], [300.0, 150.0], [150.0, 300.0]] {
+        parent
+            .spawn((
+                ButtonBundle {
+                    style: Style {
+                        width: Val::Px(w),
+                        height: Val::Px(h),
+                        // horizontally center child text
+                        justify_content: JustifyContent::Center,
+                        // vertically center child text
+                        align_items: AlignItems::Center,
+                        margin: UiRect::all(Val::Px(20.0)),
+                        ..default()
+                    },
+                    image: image.clone().into(),
+                    ..default()
+                },
+                ImageScaleMode::Sliced(slicer.clone()),
+            ))
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    "Button",
+                    TextStyle {
+                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                        font_size: 40.0,
+                        color: Color::srgb(0.9, 0.9, 0.9),
+                        ..default()
+                    },
+                ));
+            });
+    }
+}
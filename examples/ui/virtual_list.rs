@@ -0,0 +1,85 @@
+//! Shows a [`VirtualList`] of a hundred thousand items: only the rows of the items in view are
+//! spawned, and they are recycled as the list is scrolled.
+
+use bevy::prelude::*;
+
+const ITEM_COUNT: usize = 100_000;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .add_systems(Update, update_header)
+        .run();
+}
+
+#[derive(Component)]
+struct Header;
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(10.),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((TextBundle::from_section("", TextStyle::default()), Header));
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        width: Val::Px(400.),
+                        height: Val::Percent(70.),
+                        overflow: Overflow::scroll_y(),
+                        ..default()
+                    },
+                    background_color: Color::srgb(0.1, 0.1, 0.1).into(),
+                    ..default()
+                },
+                Scrollbar::default(),
+                VirtualList::new(ITEM_COUNT, 30., |index, row| {
+                    let color = if index % 2 == 0 {
+                        Color::srgb(0.15, 0.15, 0.15)
+                    } else {
+                        Color::srgb(0.2, 0.2, 0.2)
+                    };
+                    row.insert((
+                        TextBundle {
+                            style: Style {
+                                padding: UiRect::horizontal(Val::Px(10.)),
+                                align_content: AlignContent::Center,
+                                ..default()
+                            },
+                            background_color: color.into(),
+                            ..TextBundle::from_section(
+                                format!("Item {index}"),
+                                TextStyle {
+                                    font_size: 24.,
+                                    ..default()
+                                },
+                            )
+                        },
+                        Label,
+                    ));
+                }),
+            ));
+        });
+}
+
+fn update_header(lists: Query<&VirtualList>, mut header: Query<&mut Text, With<Header>>) {
+    let list = lists.single();
+    let items = list.spawned_items();
+    header.single_mut().sections[0].value = format!(
+        "Rows of items {} to {} out of {} are spawned",
+        items.start, items.end, list.item_count
+    );
+}
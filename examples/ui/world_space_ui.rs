@@ -0,0 +1,147 @@
+//! Shows how to attach UI to 3D entities with [`WorldSpaceUi`]: each cube carries a panel that is
+//! hidden behind the geometry in front of it, and whose button can be clicked through the 3D camera.
+
+use std::f32::consts::PI;
+
+use bevy::{
+    color::palettes::css::GOLD,
+    prelude::*,
+    ui::{WorldSpaceUi, WorldSpaceUiRoot},
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .add_systems(Update, (rotate_cubes, button_system))
+        .run();
+}
+
+#[derive(Component)]
+struct Rotating;
+
+#[derive(Component)]
+struct ClickCount(u32);
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(0., 3., 7.).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+    commands.spawn(DirectionalLightBundle {
+        transform: Transform::from_rotation(Quat::from_euler(EulerRot::ZYX, 0., 1., -PI / 4.)),
+        ..default()
+    });
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Plane3d::default().mesh().size(20., 20.)),
+        material: materials.add(Color::srgb(0.3, 0.5, 0.3)),
+        ..default()
+    });
+
+    let cube = meshes.add(Cuboid::default());
+    for (i, billboard) in [false, true].into_iter().enumerate() {
+        let entity = commands
+            .spawn((
+                PbrBundle {
+                    mesh: cube.clone(),
+                    material: materials.add(Color::srgb(0.8, 0.7, 0.6)),
+                    transform: Transform::from_xyz(i as f32 * 3. - 1.5, 0.5, 0.),
+                    ..default()
+                },
+                WorldSpaceUi {
+                    resolution: UVec2::new(300, 150),
+                    size: Vec2::new(2., 1.),
+                    offset: Vec3::new(0., 1.3, 0.),
+                    billboard,
+                },
+                Rotating,
+            ))
+            .id();
+
+        commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(100.),
+                        height: Val::Percent(100.),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::SpaceEvenly,
+                        ..default()
+                    },
+                    background_color: Color::srgba(0.1, 0.1, 0.1, 0.8).into(),
+                    border_radius: BorderRadius::all(Val::Px(15.)),
+                    ..default()
+                },
+                WorldSpaceUiRoot(entity),
+            ))
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    if billboard { "Billboard" } else { "Attached" },
+                    TextStyle {
+                        font_size: 30.,
+                        ..default()
+                    },
+                ));
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                padding: UiRect::axes(Val::Px(15.), Val::Px(5.)),
+                                ..default()
+                            },
+                            background_color: Color::srgb(0.15, 0.15, 0.15).into(),
+                            border_radius: BorderRadius::all(Val::Px(5.)),
+                            ..default()
+                        },
+                        ClickCount(0),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            "Clicked 0 times",
+                            TextStyle {
+                                font_size: 25.,
+                                ..default()
+                            },
+                        ));
+                    });
+            });
+    }
+}
+
+fn rotate_cubes(time: Res<Time>, mut query: Query<&mut Transform, With<Rotating>>) {
+    for mut transform in &mut query {
+        transform.rotate_y(time.delta_seconds() * 0.5);
+    }
+}
+
+fn button_system(
+    mut buttons: Query<
+        (
+            &Interaction,
+            &mut BackgroundColor,
+            &mut ClickCount,
+            &Children,
+        ),
+        Changed<Interaction>,
+    >,
+    mut texts: Query<&mut Text>,
+) {
+    for (interaction, mut background_color, mut click_count, children) in &mut buttons {
+        background_color.0 = match interaction {
+            Interaction::Pressed => {
+                click_count.0 += 1;
+                if let Ok(mut text) = texts.get_mut(children[0]) {
+                    text.sections[0].value = format!("Clicked {} times", click_count.0);
+                }
+                GOLD.into()
+            }
+            Interaction::Hovered => Color::srgb(0.25, 0.25, 0.25),
+            Interaction::None => Color::srgb(0.15, 0.15, 0.15),
+        };
+    }
+}
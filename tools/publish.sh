@@ -39,6 +39,7 @@ crates=(
     bevy_sprite
     bevy_gizmos/macros
     bevy_gizmos
+    bevy_particles
     bevy_text
     bevy_a11y
     bevy_ui